@@ -14,11 +14,13 @@ use deepseek_app_server::{
     AppServerOptions, run as run_app_server, run_stdio as run_app_server_stdio,
 };
 use deepseek_config::{
-    CliRuntimeOverrides, ConfigStore, ProviderKind, ResolvedRuntimeOptions, RuntimeApiKeySource,
+    CliRuntimeOverrides, ConfigDocument, ConfigStore, ConfigToml, ProviderKind,
+    ResolvedRuntimeOptions, RuntimeApiKeySource, migrate, org, parse_key_path, resolve_config_path,
+    schema,
 };
 use deepseek_execpolicy::{AskForApproval, ExecPolicyContext, ExecPolicyEngine};
 use deepseek_mcp::{McpServerDefinition, run_stdio_server};
-use deepseek_secrets::Secrets;
+use deepseek_secrets::{Secrets, credential_slot};
 use deepseek_state::{StateStore, ThreadListFilters};
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -66,6 +68,9 @@ struct Cli {
     config: Option<PathBuf>,
     #[arg(long)]
     profile: Option<String>,
+    /// Named credential to use (see `deepseek login --name`).
+    #[arg(long)]
+    credential: Option<String>,
     #[arg(
         long,
         value_enum,
@@ -117,6 +122,8 @@ enum Commands {
     Run(RunArgs),
     /// Run DeepSeek TUI diagnostics.
     Doctor(TuiPassthroughArgs),
+    /// Write a sanitized bug-report bundle.
+    Report(TuiPassthroughArgs),
     /// List live DeepSeek API models via the TUI binary.
     Models(TuiPassthroughArgs),
     /// List saved TUI sessions.
@@ -142,12 +149,18 @@ Common forwarded flags:
     Exec(TuiPassthroughArgs),
     /// Run a DeepSeek-powered code review over a git diff.
     Review(TuiPassthroughArgs),
+    /// Run an agent turn whenever files matching a glob change.
+    Watch(TuiPassthroughArgs),
     /// Apply a patch file or stdin to the working tree.
     Apply(TuiPassthroughArgs),
     /// Run the offline TUI evaluation harness.
     Eval(TuiPassthroughArgs),
+    /// Compare models on a YAML task suite with real model calls.
+    Bench(TuiPassthroughArgs),
     /// Manage TUI MCP servers.
     Mcp(TuiPassthroughArgs),
+    /// Run the agent's tools directly (`tool list`, `tool run <NAME>`).
+    Tool(TuiPassthroughArgs),
     /// Inspect TUI feature flags.
     Features(TuiPassthroughArgs),
     /// Run a local TUI server.
@@ -235,6 +248,13 @@ struct LoginArgs {
     provider: ProviderArg,
     #[arg(long)]
     api_key: Option<String>,
+    /// Store the key as a named credential (e.g. `work`) so profiles can
+    /// select it with `credential = "<NAME>"`.
+    #[arg(long)]
+    name: Option<String>,
+    /// Skip the OS keyring and keep the key in the config file.
+    #[arg(long, default_value_t = false)]
+    no_keyring: bool,
     #[arg(long, default_value_t = false, hide = true)]
     chatgpt: bool,
     #[arg(long, default_value_t = false, hide = true)]
@@ -280,8 +300,7 @@ enum AuthCommand {
     /// List all known providers with their auth state, without
     /// revealing keys.
     List,
-    /// Advanced: migrate config-file keys into a platform credential store.
-    #[command(hide = true)]
+    /// Move plaintext config-file keys into the OS keyring.
     Migrate {
         /// Don't actually write anything; print what would change.
         #[arg(long, default_value_t = false)]
@@ -291,17 +310,55 @@ enum AuthCommand {
 
 #[derive(Debug, Args)]
 struct ConfigArgs {
+    /// Read or write `[profiles.<NAME>]` instead of the top level.
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
     #[command(subcommand)]
     command: ConfigCommand,
 }
 
 #[derive(Debug, Subcommand)]
 enum ConfigCommand {
-    Get { key: String },
-    Set { key: String, value: String },
-    Unset { key: String },
+    Get {
+        key: String,
+    },
+    Set {
+        key: String,
+        value: String,
+    },
+    Unset {
+        key: String,
+    },
+    /// Open the config in $VISUAL / $EDITOR; it is saved only if it validates.
+    Edit,
     List,
     Path,
+    /// Check the config against the schema and report every problem.
+    Validate,
+    /// Upgrade the config to the current `config_version`.
+    Migrate {
+        /// Print what would change without writing the file.
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+    /// Pull the shared team config (org layer) from a git repository.
+    ///
+    /// Without `--from`, re-syncs from the repository recorded by the last
+    /// sync.
+    Sync {
+        /// Git URL or path of the team config repository.
+        #[arg(long, value_name = "REPO")]
+        from: Option<String>,
+        /// Branch or tag to track instead of the default branch.
+        #[arg(long)]
+        branch: Option<String>,
+        /// Background refresh interval in hours (0 disables).
+        #[arg(long, value_name = "HOURS")]
+        refresh_hours: Option<u64>,
+        /// Drop the org layer instead of syncing it.
+        #[arg(long, default_value_t = false, conflicts_with_all = ["from", "branch", "refresh_hours"])]
+        remove: bool,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -429,6 +486,14 @@ pub fn run_cli() -> std::process::ExitCode {
 fn run() -> Result<()> {
     let mut cli = Cli::parse();
 
+    // `deepseek config` loads the file itself so `edit` and `validate` still
+    // work on a config that no longer parses.
+    if let Some(Commands::Config(args)) = cli.command.take_if(|c| matches!(c, Commands::Config(_)))
+    {
+        let profile = args.profile.or_else(|| cli.profile.clone());
+        return run_config_command(cli.config.clone(), profile.as_deref(), args.command);
+    }
+
     let mut store = ConfigStore::load(cli.config.clone())?;
     let runtime_overrides = CliRuntimeOverrides {
         provider: cli.provider.map(Into::into),
@@ -436,6 +501,10 @@ fn run() -> Result<()> {
         api_key: cli.api_key.clone(),
         base_url: cli.base_url.clone(),
         auth_mode: None,
+        credential: cli
+            .credential
+            .clone()
+            .or_else(|| profile_credential(&store, cli.profile.as_deref())),
         output_mode: cli.output_mode.clone(),
         log_level: cli.log_level.clone(),
         telemetry: cli.telemetry,
@@ -454,6 +523,10 @@ fn run() -> Result<()> {
             let resolved_runtime = resolve_runtime_for_dispatch(&mut store, &runtime_overrides);
             delegate_to_tui(&cli, &resolved_runtime, tui_args("doctor", args))
         }
+        Some(Commands::Report(args)) => {
+            let resolved_runtime = resolve_runtime_for_dispatch(&mut store, &runtime_overrides);
+            delegate_to_tui(&cli, &resolved_runtime, tui_args("report", args))
+        }
         Some(Commands::Models(args)) => {
             let resolved_runtime = resolve_runtime_for_dispatch(&mut store, &runtime_overrides);
             delegate_to_tui(&cli, &resolved_runtime, tui_args("models", args))
@@ -486,6 +559,10 @@ fn run() -> Result<()> {
             let resolved_runtime = resolve_runtime_for_dispatch(&mut store, &runtime_overrides);
             delegate_to_tui(&cli, &resolved_runtime, tui_args("review", args))
         }
+        Some(Commands::Watch(args)) => {
+            let resolved_runtime = resolve_runtime_for_dispatch(&mut store, &runtime_overrides);
+            delegate_to_tui(&cli, &resolved_runtime, tui_args("watch", args))
+        }
         Some(Commands::Apply(args)) => {
             let resolved_runtime = resolve_runtime_for_dispatch(&mut store, &runtime_overrides);
             delegate_to_tui(&cli, &resolved_runtime, tui_args("apply", args))
@@ -494,10 +571,18 @@ fn run() -> Result<()> {
             let resolved_runtime = resolve_runtime_for_dispatch(&mut store, &runtime_overrides);
             delegate_to_tui(&cli, &resolved_runtime, tui_args("eval", args))
         }
+        Some(Commands::Bench(args)) => {
+            let resolved_runtime = resolve_runtime_for_dispatch(&mut store, &runtime_overrides);
+            delegate_to_tui(&cli, &resolved_runtime, tui_args("bench", args))
+        }
         Some(Commands::Mcp(args)) => {
             let resolved_runtime = resolve_runtime_for_dispatch(&mut store, &runtime_overrides);
             delegate_to_tui(&cli, &resolved_runtime, tui_args("mcp", args))
         }
+        Some(Commands::Tool(args)) => {
+            let resolved_runtime = resolve_runtime_for_dispatch(&mut store, &runtime_overrides);
+            delegate_to_tui(&cli, &resolved_runtime, tui_args("tool", args))
+        }
         Some(Commands::Features(args)) => {
             let resolved_runtime = resolve_runtime_for_dispatch(&mut store, &runtime_overrides);
             delegate_to_tui(&cli, &resolved_runtime, tui_args("features", args))
@@ -514,7 +599,7 @@ fn run() -> Result<()> {
         Some(Commands::Logout) => run_logout_command(&mut store),
         Some(Commands::Auth(args)) => run_auth_command(&mut store, args.command),
         Some(Commands::McpServer) => run_mcp_server_command(&mut store),
-        Some(Commands::Config(args)) => run_config_command(&mut store, args.command),
+        Some(Commands::Config(_)) => unreachable!("handled before the config store loads"),
         Some(Commands::Model(args)) => run_model_command(args.command),
         Some(Commands::Thread(args)) => run_thread_command(args.command),
         Some(Commands::Sandbox(args)) => run_sandbox_command(args.command),
@@ -548,11 +633,33 @@ fn run() -> Result<()> {
     }
 }
 
+/// `credential = "<NAME>"` from `[profiles.<profile>]`, if any.
+fn profile_credential(store: &ConfigStore, profile: Option<&str>) -> Option<String> {
+    store
+        .config
+        .extras
+        .get("profiles")?
+        .get(profile?)?
+        .get("credential")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Secret store selected by the `credential_store` config key.
+fn configured_secrets(store: &ConfigStore) -> Secrets {
+    Secrets::with_preference(store.config.credential_store.as_deref())
+}
+
+/// Whether `deepseek login` moved this user's keys into the OS keyring.
+fn keys_live_in_keyring(store: &ConfigStore) -> bool {
+    store.config.credential_store.as_deref() == Some("keyring")
+}
+
 fn resolve_runtime_for_dispatch(
     store: &mut ConfigStore,
     runtime_overrides: &CliRuntimeOverrides,
 ) -> ResolvedRuntimeOptions {
-    let runtime_secrets = Secrets::auto_detect();
+    let runtime_secrets = configured_secrets(store);
     resolve_runtime_for_dispatch_with_secrets(store, runtime_overrides, &runtime_secrets)
 }
 
@@ -565,7 +672,13 @@ fn resolve_runtime_for_dispatch_with_secrets(
         .config
         .resolve_runtime_options_with_secrets(runtime_overrides, secrets);
 
+    // Self-heal file-store keys into config.toml, but never copy a key the
+    // user deliberately keeps out of plaintext (keyring mode or a named
+    // credential).
     if resolved.api_key_source == Some(RuntimeApiKeySource::Keyring)
+        && !keys_live_in_keyring(store)
+        && runtime_overrides.credential.is_none()
+        && store.config.credential.is_none()
         && !provider_config_set(store, resolved.provider)
         && let Some(api_key) = resolved.api_key.clone()
     {
@@ -597,14 +710,39 @@ fn tui_args(command: &str, args: TuiPassthroughArgs) -> Vec<String> {
     forwarded
 }
 
+/// Where `deepseek login` should keep an API key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LoginStorage {
+    /// OS credential store only; plaintext config slots are migrated out.
+    Keyring,
+    /// Config file plus the file-backed secret store.
+    ConfigFile,
+}
+
 fn run_login_command(store: &mut ConfigStore, args: LoginArgs) -> Result<()> {
-    run_login_command_with_secrets(store, args, &Secrets::auto_detect())
+    if args.no_keyring || args.chatgpt || args.device_code {
+        let secrets = configured_secrets(store);
+        return run_login_command_with_secrets(store, args, &secrets, LoginStorage::ConfigFile);
+    }
+    let (secrets, unavailable) = Secrets::system_keyring_or_file();
+    let storage = match unavailable {
+        None => LoginStorage::Keyring,
+        Some(err) => {
+            eprintln!(
+                "warning: OS keyring unavailable ({err}); the API key will be stored in plaintext in {}",
+                store.path().display()
+            );
+            LoginStorage::ConfigFile
+        }
+    };
+    run_login_command_with_secrets(store, args, &secrets, storage)
 }
 
 fn run_login_command_with_secrets(
     store: &mut ConfigStore,
     args: LoginArgs,
     secrets: &Secrets,
+    storage: LoginStorage,
 ) -> Result<()> {
     let provider: ProviderKind = args.provider.into();
     store.config.provider = provider;
@@ -642,14 +780,75 @@ fn run_login_command_with_secrets(
         Some(v) => v,
         None => read_api_key_from_stdin()?,
     };
-    write_provider_api_key_to_config(store, provider, &api_key);
-    let keyring_saved = write_provider_api_key_to_keyring(secrets, provider, &api_key);
-    store.save()?;
-    let destination = if keyring_saved {
-        format!("{} and {}", store.path().display(), secrets.backend_name())
-    } else {
-        store.path().display().to_string()
+    let name = args
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty());
+    let slot = credential_slot(provider_slot(provider), name);
+    let mut storage = storage;
+    if storage == LoginStorage::Keyring
+        && let Err(err) = secrets.set(&slot, &api_key)
+    {
+        eprintln!(
+            "warning: failed to write to {} ({err}); the API key will be stored in plaintext in {}",
+            secrets.backend_name(),
+            store.path().display()
+        );
+        storage = LoginStorage::ConfigFile;
+    }
+
+    let destination = match storage {
+        LoginStorage::Keyring => {
+            select_provider_api_key_mode(store, provider);
+            if name.is_none() {
+                // The fresh key already replaced the default slot; drop the
+                // stale plaintext copy so migration can't write it back.
+                clear_provider_api_key_from_config(store, provider);
+            }
+            store.config.credential_store = Some("keyring".to_string());
+            let (migrated, warnings) = migrate_plaintext_keys(store, secrets, false);
+            store.save()?;
+            for slot in &migrated {
+                println!(
+                    "moved plaintext {slot} key from {} into {}",
+                    store.path().display(),
+                    secrets.backend_name()
+                );
+            }
+            for warning in warnings {
+                eprintln!("warning: {warning}");
+            }
+            secrets.backend_name().to_string()
+        }
+        LoginStorage::ConfigFile if name.is_some() => {
+            // Named credentials never go into config.toml; the file-backed
+            // secret store keeps them apart from the default slot.
+            select_provider_api_key_mode(store, provider);
+            secrets
+                .set(&slot, &api_key)
+                .with_context(|| format!("failed to save credential {slot}"))?;
+            store.config.credential_store = Some("file".to_string());
+            store.save()?;
+            secrets.backend_name().to_string()
+        }
+        LoginStorage::ConfigFile => {
+            write_provider_api_key_to_config(store, provider, &api_key);
+            let keyring_saved = write_provider_api_key_to_keyring(secrets, provider, &api_key);
+            store.save()?;
+            if keyring_saved {
+                format!("{} and {}", store.path().display(), secrets.backend_name())
+            } else {
+                store.path().display().to_string()
+            }
+        }
     };
+    if let Some(name) = name {
+        println!(
+            "saved credential {slot}; select it with `--credential {name}` or `credential = \"{name}\"` in config or a profile"
+        );
+        return Ok(());
+    }
     if provider == ProviderKind::Deepseek {
         println!("logged in using API key mode (deepseek); saved key to {destination}");
     } else {
@@ -662,7 +861,8 @@ fn run_login_command_with_secrets(
 }
 
 fn run_logout_command(store: &mut ConfigStore) -> Result<()> {
-    run_logout_command_with_secrets(store, &Secrets::auto_detect())
+    let secrets = configured_secrets(store);
+    run_logout_command_with_secrets(store, &secrets)
 }
 
 fn run_logout_command_with_secrets(store: &mut ConfigStore, secrets: &Secrets) -> Result<()> {
@@ -672,6 +872,9 @@ fn run_logout_command_with_secrets(store: &mut ConfigStore, secrets: &Secrets) -
         clear_provider_api_key_from_config(store, provider);
     }
     clear_provider_api_key_from_keyring(secrets, active_provider);
+    if let Some(name) = store.config.credential.as_deref() {
+        let _ = secrets.delete(&credential_slot(provider_slot(active_provider), Some(name)));
+    }
     store.config.auth_mode = None;
     store.config.chatgpt_access_token = None;
     store.config.device_code_session = None;
@@ -724,22 +927,27 @@ fn write_provider_api_key_to_config(
     provider: ProviderKind,
     api_key: &str,
 ) {
-    store.config.provider = provider;
-    store.config.auth_mode = Some("api_key".to_string());
+    select_provider_api_key_mode(store, provider);
     store.config.providers.for_provider_mut(provider).api_key = Some(api_key.to_string());
     if provider == ProviderKind::Deepseek {
         store.config.api_key = Some(api_key.to_string());
-        if store.config.default_text_model.is_none() {
-            store.config.default_text_model = Some(
-                store
-                    .config
-                    .providers
-                    .deepseek
-                    .model
-                    .clone()
-                    .unwrap_or_else(|| "deepseek-v4-pro".to_string()),
-            );
-        }
+    }
+}
+
+/// Make `provider` active in API-key mode without touching its key slot.
+fn select_provider_api_key_mode(store: &mut ConfigStore, provider: ProviderKind) {
+    store.config.provider = provider;
+    store.config.auth_mode = Some("api_key".to_string());
+    if provider == ProviderKind::Deepseek && store.config.default_text_model.is_none() {
+        store.config.default_text_model = Some(
+            store
+                .config
+                .providers
+                .deepseek
+                .model
+                .clone()
+                .unwrap_or_else(|| "deepseek-v4-pro".to_string()),
+        );
     }
 }
 
@@ -891,7 +1099,18 @@ fn last4_label(value: &str) -> String {
 }
 
 fn run_auth_command(store: &mut ConfigStore, command: AuthCommand) -> Result<()> {
-    run_auth_command_with_secrets(store, command, &Secrets::auto_detect())
+    if let AuthCommand::Migrate { dry_run } = command {
+        let (secrets, unavailable) = Secrets::system_keyring_or_file();
+        if let Some(err) = &unavailable {
+            eprintln!(
+                "warning: OS keyring unavailable ({err}); migrating into {} instead",
+                secrets.backend_name()
+            );
+        }
+        return run_auth_migrate(store, &secrets, dry_run, unavailable.is_none());
+    }
+    let secrets = configured_secrets(store);
+    run_auth_command_with_secrets(store, command, &secrets)
 }
 
 fn run_auth_command_with_secrets(
@@ -931,6 +1150,15 @@ fn run_auth_command_with_secrets(
                 (None, true) => read_api_key_from_stdin()?,
                 (None, false) => prompt_api_key(slot)?,
             };
+            if keys_live_in_keyring(store)
+                && write_provider_api_key_to_keyring(secrets, provider, &api_key)
+            {
+                select_provider_api_key_mode(store, provider);
+                clear_provider_api_key_from_config(store, provider);
+                store.save()?;
+                println!("saved API key for {slot} to {}", secrets.backend_name());
+                return Ok(());
+            }
             write_provider_api_key_to_config(store, provider, &api_key);
             let keyring_saved = write_provider_api_key_to_keyring(secrets, provider, &api_key);
             store.save()?;
@@ -1004,7 +1232,7 @@ fn run_auth_command_with_secrets(
             }
             Ok(())
         }
-        AuthCommand::Migrate { dry_run } => run_auth_migrate(store, secrets, dry_run),
+        AuthCommand::Migrate { dry_run } => run_auth_migrate(store, secrets, dry_run, false),
     }
 }
 
@@ -1039,51 +1267,21 @@ fn prompt_api_key(slot: &str) -> Result<String> {
     Ok(key)
 }
 
-/// Move plaintext keys from config.toml into the configured secret store.
-/// Hidden in v0.8.8 because the normal setup path is config/env only.
-fn run_auth_migrate(store: &mut ConfigStore, secrets: &Secrets, dry_run: bool) -> Result<()> {
-    let mut migrated: Vec<(ProviderKind, &'static str)> = Vec::new();
-    let mut warnings: Vec<String> = Vec::new();
-
-    for provider in PROVIDER_LIST {
-        let slot = provider_slot(provider);
-        let from_provider_block = store
-            .config
-            .providers
-            .for_provider(provider)
-            .api_key
-            .clone()
-            .filter(|v| !v.trim().is_empty());
-        let from_root = (provider == ProviderKind::Deepseek)
-            .then(|| store.config.api_key.clone())
-            .flatten()
-            .filter(|v| !v.trim().is_empty());
-        let value = from_provider_block.or(from_root);
-        let Some(value) = value else { continue };
-
-        if let Ok(Some(existing)) = secrets.get(slot)
-            && existing == value
-        {
-            // Already migrated; safe to strip the file slot.
-        } else if dry_run {
-            migrated.push((provider, slot));
-            continue;
-        } else if let Err(err) = secrets.set(slot, &value) {
-            warnings.push(format!(
-                "skipped {slot}: failed to write to secret store: {err}"
-            ));
-            continue;
-        }
-        if !dry_run {
-            store.config.providers.for_provider_mut(provider).api_key = None;
-            if provider == ProviderKind::Deepseek {
-                store.config.api_key = None;
-            }
-        }
-        migrated.push((provider, slot));
+/// Move plaintext keys from config.toml into the given secret store.
+/// `keyring` marks the config as keyring-backed once the move succeeds, so
+/// dispatch stops self-healing keys back into the file.
+fn run_auth_migrate(
+    store: &mut ConfigStore,
+    secrets: &Secrets,
+    dry_run: bool,
+    keyring: bool,
+) -> Result<()> {
+    let (migrated, warnings) = migrate_plaintext_keys(store, secrets, dry_run);
+    if !dry_run && keyring && warnings.is_empty() {
+        store.config.credential_store = Some("keyring".to_string());
     }
 
-    if !dry_run && !migrated.is_empty() {
+    if !dry_run && (!migrated.is_empty() || keyring) {
         store
             .save()
             .context("failed to write updated config.toml")?;
@@ -1098,7 +1296,7 @@ fn run_auth_migrate(store: &mut ConfigStore, secrets: &Secrets, dry_run: bool) -
             if dry_run { "would migrate" } else { "migrated" },
             migrated.len()
         );
-        for (_, slot) in &migrated {
+        for slot in &migrated {
             println!("  - {slot}");
         }
         if !dry_run {
@@ -1114,40 +1312,303 @@ fn run_auth_migrate(store: &mut ConfigStore, secrets: &Secrets, dry_run: bool) -
     Ok(())
 }
 
-fn run_config_command(store: &mut ConfigStore, command: ConfigCommand) -> Result<()> {
+/// Copy every plaintext provider key into `secrets` and strip it from the
+/// config (unless `dry_run`). Returns the migrated slots and any per-slot
+/// write failures; the caller decides whether to save.
+fn migrate_plaintext_keys(
+    store: &mut ConfigStore,
+    secrets: &Secrets,
+    dry_run: bool,
+) -> (Vec<&'static str>, Vec<String>) {
+    let mut migrated: Vec<&'static str> = Vec::new();
+    let mut warnings: Vec<String> = Vec::new();
+
+    for provider in PROVIDER_LIST {
+        let slot = provider_slot(provider);
+        let Some(value) = provider_config_api_key(store, provider).map(str::to_string) else {
+            continue;
+        };
+
+        if let Ok(Some(existing)) = secrets.get(slot)
+            && existing == value
+        {
+            // Already migrated; safe to strip the file slot.
+        } else if dry_run {
+            migrated.push(slot);
+            continue;
+        } else if let Err(err) = secrets.set(slot, &value) {
+            warnings.push(format!(
+                "skipped {slot}: failed to write to secret store: {err}"
+            ));
+            continue;
+        }
+        if !dry_run {
+            clear_provider_api_key_from_config(store, provider);
+        }
+        migrated.push(slot);
+    }
+    (migrated, warnings)
+}
+
+fn run_config_command(
+    config_path: Option<PathBuf>,
+    profile: Option<&str>,
+    command: ConfigCommand,
+) -> Result<()> {
+    // Top-level keys the typed config knows go through `ConfigStore`, which
+    // normalizes them (provider aliases, header lists); everything else is
+    // checked against the schema and edited in place.
+    let typed = |key: &str| profile.is_none() && ConfigToml::is_builtin_key(key);
     match command {
-        ConfigCommand::Get { key } => {
+        ConfigCommand::Get { key } if typed(&key) => {
+            let store = ConfigStore::load(config_path)?;
             if let Some(value) = store.config.get_display_value(&key) {
                 println!("{value}");
                 return Ok(());
             }
             bail!("key not found: {key}");
         }
-        ConfigCommand::Set { key, value } => {
+        ConfigCommand::Get { key } => {
+            let path = config_key_path(profile, &key)?;
+            let doc = ConfigDocument::load(&resolve_config_path(config_path)?)?;
+            match doc.get_display(&path) {
+                Some(value) => {
+                    println!("{value}");
+                    Ok(())
+                }
+                None => bail!("key not found: {}", path.join(".")),
+            }
+        }
+        ConfigCommand::Set { key, value } if typed(&key) => {
+            let mut store = ConfigStore::load(config_path)?;
             store.config.set_value(&key, &value)?;
             store.save()?;
             println!("set {key}");
             Ok(())
         }
-        ConfigCommand::Unset { key } => {
+        ConfigCommand::Set { key, value } => {
+            let path = config_key_path(profile, &key)?;
+            let dotted = path.join(".");
+            let kind = schema::lookup(&path)?;
+            let value = kind.parse_value(&dotted, &value)?;
+            let mut doc = ConfigDocument::load(&resolve_config_path(config_path)?)?;
+            doc.set(&path, value)?;
+            doc.save()?;
+            println!("set {dotted}");
+            Ok(())
+        }
+        ConfigCommand::Unset { key } if typed(&key) => {
+            let mut store = ConfigStore::load(config_path)?;
             store.config.unset_value(&key)?;
             store.save()?;
             println!("unset {key}");
             Ok(())
         }
+        ConfigCommand::Unset { key } => {
+            let path = config_key_path(profile, &key)?;
+            let mut doc = ConfigDocument::load(&resolve_config_path(config_path)?)?;
+            if doc.unset(&path) {
+                doc.save()?;
+            }
+            println!("unset {}", path.join("."));
+            Ok(())
+        }
+        ConfigCommand::Edit => edit_config_file(&resolve_config_path(config_path)?, profile),
         ConfigCommand::List => {
+            if let Some(profile) = profile {
+                let doc = ConfigDocument::load(&resolve_config_path(config_path)?)?;
+                let prefix = ["profiles".to_string(), profile.to_string()];
+                for (key, value) in doc.flatten(&prefix) {
+                    println!("{key} = {value}");
+                }
+                return Ok(());
+            }
+            let store = ConfigStore::load(config_path)?;
             for (key, value) in store.config.list_values() {
                 println!("{key} = {value}");
             }
             Ok(())
         }
         ConfigCommand::Path => {
-            println!("{}", store.path().display());
+            println!("{}", resolve_config_path(config_path)?.display());
             Ok(())
         }
+        ConfigCommand::Validate => {
+            let path = resolve_config_path(config_path)?;
+            let issues = ConfigDocument::load(&path)?.validate()?;
+            if issues.is_empty() {
+                println!("{}: ok", path.display());
+                return Ok(());
+            }
+            for issue in &issues {
+                eprintln!("  {issue}");
+            }
+            bail!("{} problem(s) in {}", issues.len(), path.display());
+        }
+        ConfigCommand::Migrate { dry_run } => {
+            let mut doc = ConfigDocument::load(&resolve_config_path(config_path)?)?;
+            let notes = doc.migrate()?;
+            if notes.is_empty() {
+                println!(
+                    "{} is up to date (config_version {})",
+                    doc.path().display(),
+                    migrate::CURRENT_CONFIG_VERSION
+                );
+                return Ok(());
+            }
+            for note in &notes {
+                println!("  {note}");
+            }
+            if dry_run {
+                println!("dry run: {} not modified", doc.path().display());
+            } else {
+                doc.save()?;
+                println!("migrated {}", doc.path().display());
+            }
+            Ok(())
+        }
+        ConfigCommand::Sync {
+            from,
+            branch,
+            refresh_hours,
+            remove,
+        } => run_config_sync(from, branch, refresh_hours, remove),
     }
 }
 
+fn run_config_sync(
+    from: Option<String>,
+    branch: Option<String>,
+    refresh_hours: Option<u64>,
+    remove: bool,
+) -> Result<()> {
+    let layer = org::OrgLayer::default_location()
+        .context("failed to resolve home directory for the org config layer")?;
+    if remove {
+        if layer.remove()? {
+            println!("removed org config layer");
+        } else {
+            println!("no org config layer to remove");
+        }
+        return Ok(());
+    }
+    let previous = layer.state()?;
+    let Some(url) = from.or_else(|| previous.as_ref().map(|state| state.url.clone())) else {
+        bail!("no org config repository recorded; pass --from <REPO>");
+    };
+    // A re-sync keeps the recorded branch and interval unless overridden.
+    let same_source = previous.as_ref().filter(|state| state.url == url);
+    let branch = branch.or_else(|| same_source.and_then(|state| state.branch.clone()));
+    let refresh_hours = refresh_hours
+        .or_else(|| same_source.map(|state| state.refresh_hours))
+        .unwrap_or(org::DEFAULT_REFRESH_HOURS);
+    let state = layer.sync(&url, branch.as_deref(), refresh_hours)?;
+    println!(
+        "synced org config layer from {} at {} into {}",
+        state.url,
+        state.short_commit(),
+        layer.checkout_dir().display()
+    );
+    for (label, path) in [
+        ("config.toml", layer.config_path()),
+        ("requirements.toml", layer.requirements_path()),
+        ("execpolicy.toml", layer.execpolicy_path()),
+        ("skills/", layer.skills_dir()),
+    ] {
+        if path.exists() {
+            println!("  {label}");
+        }
+    }
+    Ok(())
+}
+
+/// Dotted `key` as path segments, under `[profiles.<profile>]` when given.
+fn config_key_path(profile: Option<&str>, key: &str) -> Result<Vec<String>> {
+    let mut path = Vec::new();
+    if let Some(profile) = profile {
+        path.push("profiles".to_string());
+        path.push(profile.to_string());
+    }
+    path.extend(parse_key_path(key)?);
+    Ok(path)
+}
+
+/// Edit a copy of the config and only replace the real file when the result
+/// parses and validates. Rejected edits are kept so nothing typed is lost.
+fn edit_config_file(path: &Path, profile: Option<&str>) -> Result<()> {
+    let mut doc = ConfigDocument::load(path)?;
+    if let Some(profile) = profile {
+        doc.ensure_table(&["profiles".to_string(), profile.to_string()])?;
+    }
+    let seed = doc.to_string();
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("failed to create config directory {}", dir.display()))?;
+    let mut staged = tempfile::Builder::new()
+        .prefix("config.")
+        .suffix(".toml")
+        .tempfile_in(dir)
+        .context("failed to create a scratch copy of the config")?;
+    staged.write_all(seed.as_bytes())?;
+    staged.flush()?;
+
+    run_editor(staged.path())?;
+
+    let edited = std::fs::read_to_string(staged.path())
+        .with_context(|| format!("failed to read {}", staged.path().display()))?;
+    if edited == seed {
+        println!("no changes");
+        return Ok(());
+    }
+    let problems: Vec<String> = match ConfigDocument::parse(path.to_path_buf(), &edited) {
+        Ok(updated) => {
+            let mut problems: Vec<String> = updated
+                .validate()?
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            if problems.is_empty() {
+                updated.save()?;
+                println!("saved {}", path.display());
+                return Ok(());
+            }
+            problems.dedup();
+            problems
+        }
+        Err(err) => err.chain().map(ToString::to_string).collect(),
+    };
+    for problem in &problems {
+        eprintln!("  {problem}");
+    }
+    let (_, kept) = staged
+        .keep()
+        .context("failed to keep the edited copy of the config")?;
+    bail!(
+        "config not saved; your edits are in {} (fix them and copy the file over, or run `deepseek config edit` again)",
+        kept.display()
+    );
+}
+
+/// Run `$VISUAL` / `$EDITOR` (falling back to `vi`) on `file` and wait.
+fn run_editor(file: &Path) -> Result<()> {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().context("editor command is empty")?;
+    let status = Command::new(program)
+        .args(parts)
+        .arg(file)
+        .status()
+        .with_context(|| format!("failed to launch editor `{editor}`"))?;
+    if !status.success() {
+        bail!("editor `{editor}` exited with {status}; config not saved");
+    }
+    Ok(())
+}
+
 fn run_model_command(command: ModelCommand) -> Result<()> {
     let registry = ModelRegistry::default();
     match command {
@@ -1726,7 +2187,8 @@ mod tests {
         assert!(matches!(
             cli.command,
             Some(Commands::Config(ConfigArgs {
-                command: ConfigCommand::Get { ref key }
+                command: ConfigCommand::Get { ref key },
+                ..
             })) if key == "provider"
         ));
 
@@ -1734,7 +2196,8 @@ mod tests {
         assert!(matches!(
             cli.command,
             Some(Commands::Config(ConfigArgs {
-                command: ConfigCommand::Set { ref key, ref value }
+                command: ConfigCommand::Set { ref key, ref value },
+                ..
             })) if key == "model" && value == "deepseek-v4-flash"
         ));
 
@@ -1742,24 +2205,116 @@ mod tests {
         assert!(matches!(
             cli.command,
             Some(Commands::Config(ConfigArgs {
-                command: ConfigCommand::Unset { ref key }
+                command: ConfigCommand::Unset { ref key },
+                ..
             })) if key == "model"
         ));
 
         assert!(matches!(
             parse_ok(&["deepseek", "config", "list"]).command,
             Some(Commands::Config(ConfigArgs {
-                command: ConfigCommand::List
+                command: ConfigCommand::List,
+                ..
             }))
         ));
         assert!(matches!(
             parse_ok(&["deepseek", "config", "path"]).command,
             Some(Commands::Config(ConfigArgs {
-                command: ConfigCommand::Path
+                command: ConfigCommand::Path,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn parses_config_edit_validate_migrate_and_profile() {
+        assert!(matches!(
+            parse_ok(&["deepseek", "config", "edit", "--profile", "work"]).command,
+            Some(Commands::Config(ConfigArgs {
+                command: ConfigCommand::Edit,
+                profile: Some(ref profile),
+            })) if profile == "work"
+        ));
+        assert!(matches!(
+            parse_ok(&["deepseek", "config", "validate"]).command,
+            Some(Commands::Config(ConfigArgs {
+                command: ConfigCommand::Validate,
+                profile: None,
+            }))
+        ));
+        assert!(matches!(
+            parse_ok(&["deepseek", "config", "migrate", "--dry-run"]).command,
+            Some(Commands::Config(ConfigArgs {
+                command: ConfigCommand::Migrate { dry_run: true },
+                ..
             }))
         ));
     }
 
+    #[test]
+    fn config_command_edits_profiles_and_schema_keys_in_place() {
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "# my config\nprovider = \"deepseek\" # default\n\n[tui]\nmouse_capture = true # wheel\n",
+        )
+        .unwrap();
+        let run = |profile: Option<&str>, command| {
+            run_config_command(Some(path.clone()), profile, command)
+        };
+
+        run(
+            None,
+            ConfigCommand::Set {
+                key: "tui.mouse_capture".to_string(),
+                value: "false".to_string(),
+            },
+        )
+        .expect("set schema key");
+        run(
+            Some("work"),
+            ConfigCommand::Set {
+                key: "model".to_string(),
+                value: "deepseek-v4-flash".to_string(),
+            },
+        )
+        .expect("set profile key");
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(
+            raw.starts_with("# my config\nprovider = \"deepseek\" # default\n"),
+            "{raw}"
+        );
+        assert!(raw.contains("mouse_capture = false # wheel"), "{raw}");
+        assert!(
+            raw.contains("[profiles.work]\nmodel = \"deepseek-v4-flash\""),
+            "{raw}"
+        );
+
+        let err = run(
+            None,
+            ConfigCommand::Set {
+                key: "tui.mouse_captur".to_string(),
+                value: "false".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("mouse_capture"), "{err}");
+        let err = run(
+            None,
+            ConfigCommand::Set {
+                key: "tui.mouse_capture".to_string(),
+                value: "sometimes".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("tui.mouse_capture"), "{err}");
+
+        run(None, ConfigCommand::Validate).expect("valid config");
+        std::fs::write(&path, "[tui]\nmouse_capture = \"yes\"\n").unwrap();
+        assert!(run(None, ConfigCommand::Validate).is_err());
+    }
+
     #[test]
     fn parses_model_command_matrix() {
         let cli = parse_ok(&["deepseek", "model", "list"]);
@@ -1952,6 +2507,21 @@ mod tests {
             Some(Commands::Setup(TuiPassthroughArgs { ref args }))
                 if args == &["--skills", "--local"]
         ));
+
+        let cli = parse_ok(&[
+            "deepseek",
+            "tool",
+            "run",
+            "grep_files",
+            "--pattern",
+            "TODO",
+            "--json",
+        ]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Tool(TuiPassthroughArgs { ref args }))
+                if args == &["run", "grep_files", "--pattern", "TODO", "--json"]
+        ));
     }
 
     #[test]
@@ -1989,11 +2559,14 @@ mod tests {
             LoginArgs {
                 provider: ProviderArg::Deepseek,
                 api_key: Some("sk-test".to_string()),
+                name: None,
+                no_keyring: true,
                 chatgpt: false,
                 device_code: false,
                 token: None,
             },
             &secrets,
+            LoginStorage::ConfigFile,
         )
         .expect("login should write config");
 
@@ -2013,6 +2586,125 @@ mod tests {
         let _ = std::fs::remove_file(path);
     }
 
+    #[test]
+    fn keyring_login_keeps_key_out_of_config_and_migrates_plaintext_keys() {
+        use deepseek_secrets::{InMemoryKeyringStore, KeyringStore};
+        use std::sync::Arc;
+
+        let nanos = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+        let path = std::env::temp_dir().join(format!(
+            "deepseek-cli-keyring-login-test-{}-{nanos}.toml",
+            std::process::id()
+        ));
+        let mut store = ConfigStore::load(Some(path.clone())).expect("store should load");
+        store.config.api_key = Some("sk-stale".to_string());
+        store.config.providers.deepseek.api_key = Some("sk-stale".to_string());
+        store.config.providers.openrouter.api_key = Some("or-plain".to_string());
+        let inner = Arc::new(InMemoryKeyringStore::new());
+        let secrets = Secrets::new(inner.clone());
+
+        run_login_command_with_secrets(
+            &mut store,
+            LoginArgs {
+                provider: ProviderArg::Deepseek,
+                api_key: Some("sk-fresh".to_string()),
+                name: None,
+                no_keyring: false,
+                chatgpt: false,
+                device_code: false,
+                token: None,
+            },
+            &secrets,
+            LoginStorage::Keyring,
+        )
+        .expect("login should succeed");
+
+        assert_eq!(inner.get("deepseek").unwrap().as_deref(), Some("sk-fresh"));
+        assert_eq!(
+            inner.get("openrouter").unwrap().as_deref(),
+            Some("or-plain")
+        );
+        assert_eq!(store.config.credential_store.as_deref(), Some("keyring"));
+        let saved = std::fs::read_to_string(&path).expect("config should be written");
+        assert!(!saved.contains("sk-fresh"));
+        assert!(!saved.contains("sk-stale"));
+        assert!(!saved.contains("or-plain"));
+
+        // Dispatch resolves from the keyring without writing it back.
+        let resolved = resolve_runtime_for_dispatch_with_secrets(
+            &mut store,
+            &CliRuntimeOverrides::default(),
+            &secrets,
+        );
+        assert_eq!(resolved.api_key.as_deref(), Some("sk-fresh"));
+        assert_eq!(resolved.api_key_source, Some(RuntimeApiKeySource::Keyring));
+        assert!(store.config.api_key.is_none());
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn named_login_stores_separate_credential_selected_by_profile() {
+        use deepseek_secrets::{InMemoryKeyringStore, KeyringStore};
+        use std::sync::Arc;
+
+        let nanos = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+        let path = std::env::temp_dir().join(format!(
+            "deepseek-cli-named-login-test-{}-{nanos}.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, "[profiles.work]\ncredential = \"work\"\n").unwrap();
+        let mut store = ConfigStore::load(Some(path.clone())).expect("store should load");
+        let inner = Arc::new(InMemoryKeyringStore::new());
+        inner.set("deepseek", "sk-default").unwrap();
+        let secrets = Secrets::new(inner.clone());
+
+        run_login_command_with_secrets(
+            &mut store,
+            LoginArgs {
+                provider: ProviderArg::Deepseek,
+                api_key: Some("sk-work".to_string()),
+                name: Some("work".to_string()),
+                no_keyring: false,
+                chatgpt: false,
+                device_code: false,
+                token: None,
+            },
+            &secrets,
+            LoginStorage::Keyring,
+        )
+        .expect("named login should succeed");
+
+        assert_eq!(
+            inner.get("deepseek:work").unwrap().as_deref(),
+            Some("sk-work")
+        );
+        assert_eq!(
+            inner.get("deepseek").unwrap().as_deref(),
+            Some("sk-default")
+        );
+        assert_eq!(
+            profile_credential(&store, Some("work")).as_deref(),
+            Some("work")
+        );
+        assert_eq!(profile_credential(&store, Some("home")), None);
+
+        let overrides = CliRuntimeOverrides {
+            credential: profile_credential(&store, Some("work")),
+            ..CliRuntimeOverrides::default()
+        };
+        let resolved = resolve_runtime_for_dispatch_with_secrets(&mut store, &overrides, &secrets);
+        assert_eq!(resolved.api_key.as_deref(), Some("sk-work"));
+        let resolved = resolve_runtime_for_dispatch_with_secrets(
+            &mut store,
+            &CliRuntimeOverrides::default(),
+            &secrets,
+        );
+        assert_eq!(resolved.api_key.as_deref(), Some("sk-default"));
+
+        let _ = std::fs::remove_file(path);
+    }
+
     #[test]
     fn parses_auth_subcommand_matrix() {
         let cli = parse_ok(&["deepseek", "auth", "set", "--provider", "deepseek"]);
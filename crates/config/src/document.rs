@@ -0,0 +1,307 @@
+//! Comment-preserving edits to `config.toml`.
+//!
+//! [`ConfigDocument`] edits the file as a TOML document rather than a
+//! deserialized struct, so `deepseek config set` / `unset` only touch the
+//! key they name: comments, ordering, and formatting elsewhere survive.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use toml_edit::{DocumentMut, Item, Key, Table, TableLike, Value};
+
+use crate::migrate;
+use crate::schema::{self, ConfigIssue};
+
+/// A config file held as an editable TOML document.
+#[derive(Debug, Clone)]
+pub struct ConfigDocument {
+    path: PathBuf,
+    doc: DocumentMut,
+}
+
+impl ConfigDocument {
+    /// Load `path`, or start an empty document when it does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = if path.exists() {
+            fs::read_to_string(path)
+                .with_context(|| format!("failed to read config at {}", path.display()))?
+        } else {
+            String::new()
+        };
+        Self::parse(path.to_path_buf(), &raw)
+    }
+
+    pub fn parse(path: PathBuf, raw: &str) -> Result<Self> {
+        let doc = raw
+            .parse::<DocumentMut>()
+            .with_context(|| format!("failed to parse config at {}", path.display()))?;
+        Ok(Self { path, doc })
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[must_use]
+    pub fn get(&self, path: &[String]) -> Option<&Item> {
+        let mut item = self.doc.as_item();
+        for segment in path {
+            item = item.as_table_like()?.get(segment)?;
+        }
+        (!item.is_none()).then_some(item)
+    }
+
+    /// [`display_item`] for `path`, with secrets redacted.
+    #[must_use]
+    pub fn get_display(&self, path: &[String]) -> Option<String> {
+        let value = display_item(self.get(path)?);
+        Some(match path.last() {
+            Some(key) if crate::is_sensitive_config_key(key) => crate::redact_secret(&value),
+            _ => value,
+        })
+    }
+
+    /// Set `path` to `value`, creating intermediate tables. An existing
+    /// value keeps its trailing comment.
+    pub fn set(&mut self, path: &[String], mut value: Value) -> Result<()> {
+        let (last, parents) = path.split_last().context("config key cannot be empty")?;
+        let mut table: &mut dyn TableLike = self.doc.as_table_mut();
+        for (depth, segment) in parents.iter().enumerate() {
+            if table.get(segment).is_none() {
+                let mut child = Table::new();
+                child.set_implicit(true);
+                table.insert(segment, Item::Table(child));
+            }
+            table = table
+                .get_mut(segment)
+                .and_then(Item::as_table_like_mut)
+                .with_context(|| format!("`{}` is not a table", path[..=depth].join(".")))?;
+        }
+        match table.get_mut(last) {
+            Some(Item::Value(existing)) => {
+                *value.decor_mut() = existing.decor().clone();
+                *existing = value;
+            }
+            Some(item) if !item.is_none() => {
+                bail!("`{}` is a table; set its keys individually", path.join("."));
+            }
+            _ => {
+                table.insert(last, Item::Value(value));
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove `path`. Returns whether it was present.
+    pub fn unset(&mut self, path: &[String]) -> bool {
+        let Some((last, parents)) = path.split_last() else {
+            return false;
+        };
+        let mut table: &mut dyn TableLike = self.doc.as_table_mut();
+        for segment in parents {
+            match table.get_mut(segment).and_then(Item::as_table_like_mut) {
+                Some(child) => table = child,
+                None => return false,
+            }
+        }
+        table.remove(last).is_some()
+    }
+
+    /// Make sure the table at `path` exists, e.g. `[profiles.work]` before
+    /// opening an editor on it.
+    pub fn ensure_table(&mut self, path: &[String]) -> Result<()> {
+        let mut table: &mut dyn TableLike = self.doc.as_table_mut();
+        for (depth, segment) in path.iter().enumerate() {
+            if table.get(segment).is_none() {
+                let mut child = Table::new();
+                // Only the innermost table needs a visible header.
+                child.set_implicit(depth + 1 < path.len());
+                table.insert(segment, Item::Table(child));
+            }
+            table = table
+                .get_mut(segment)
+                .and_then(Item::as_table_like_mut)
+                .with_context(|| format!("`{}` is not a table", path[..=depth].join(".")))?;
+        }
+        Ok(())
+    }
+
+    /// Every leaf value under `prefix` as `dotted.key -> value`, with
+    /// secrets redacted.
+    #[must_use]
+    pub fn flatten(&self, prefix: &[String]) -> BTreeMap<String, String> {
+        let mut out = BTreeMap::new();
+        if let Some(table) = self.get(prefix).and_then(Item::as_table_like) {
+            flatten_into(table, "", &mut out);
+        }
+        out
+    }
+
+    /// Check the document against the config schema.
+    pub fn validate(&self) -> Result<Vec<ConfigIssue>> {
+        let table: toml::Table = toml::from_str(&self.doc.to_string())
+            .with_context(|| format!("failed to parse config at {}", self.path.display()))?;
+        Ok(schema::validate(&table))
+    }
+
+    /// Run pending migrations in place; see [`migrate::migrate`].
+    pub fn migrate(&mut self) -> Result<Vec<String>> {
+        migrate::migrate(&mut self.doc)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        crate::write_config_file(&self.path, &self.doc.to_string())
+    }
+}
+
+impl fmt::Display for ConfigDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.doc.fmt(f)
+    }
+}
+
+/// Split a dotted config key into segments. Quoted segments may contain
+/// dots: `models."deepseek-v4.1".temperature`.
+pub fn parse_key_path(key: &str) -> Result<Vec<String>> {
+    let keys = Key::parse(key).with_context(|| format!("invalid config key '{key}'"))?;
+    Ok(keys.iter().map(|k| k.get().to_string()).collect())
+}
+
+/// Display form of a stored value: strings unquoted, everything else as
+/// TOML.
+#[must_use]
+pub fn display_item(item: &Item) -> String {
+    match item {
+        Item::Value(Value::String(s)) => s.value().clone(),
+        Item::Value(value) => value.clone().decorated("", "").to_string(),
+        other => other.to_string().trim().to_string(),
+    }
+}
+
+fn flatten_into(table: &dyn TableLike, prefix: &str, out: &mut BTreeMap<String, String>) {
+    for (key, item) in table.iter() {
+        let path = if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        if let Some(child) = item.as_table_like() {
+            flatten_into(child, &path, out);
+            continue;
+        }
+        let value = display_item(item);
+        let value = if crate::is_sensitive_config_key(key) {
+            crate::redact_secret(&value)
+        } else {
+            value
+        };
+        out.insert(path, value);
+    }
+}
+
+/// Re-render `updated` (a freshly serialized config) on top of `existing`,
+/// keeping the comments and layout of every entry whose value did not
+/// change. Falls back to `updated` verbatim when `existing` does not parse.
+pub(crate) fn preserve_formatting(existing: &str, updated: &str) -> String {
+    let (Ok(mut old), Ok(new)) = (
+        existing.parse::<DocumentMut>(),
+        updated.parse::<DocumentMut>(),
+    ) else {
+        return updated.to_string();
+    };
+    sync_table(old.as_table_mut(), new.as_table());
+    old.to_string()
+}
+
+fn sync_table(old: &mut dyn TableLike, new: &dyn TableLike) {
+    let stale: Vec<String> = old
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| !new.contains_key(key))
+        .collect();
+    for key in stale {
+        old.remove(&key);
+    }
+    for (key, new_item) in new.iter() {
+        match old.get_mut(key) {
+            Some(old_item) => sync_item(old_item, new_item),
+            None => {
+                old.insert(key, new_item.clone());
+            }
+        }
+    }
+}
+
+fn sync_item(old: &mut Item, new: &Item) {
+    if let (Some(old_table), Some(new_table)) = (old.as_table_like_mut(), new.as_table_like()) {
+        sync_table(old_table, new_table);
+        return;
+    }
+    match (old, new) {
+        (Item::Value(old_value), Item::Value(new_value)) => {
+            if plain_value(old_value) != plain_value(new_value) {
+                let decor = old_value.decor().clone();
+                *old_value = new_value.clone();
+                *old_value.decor_mut() = decor;
+            }
+        }
+        (old, new) => *old = new.clone(),
+    }
+}
+
+/// Formatting-independent form of a value, so `'x'` and `"x"` compare
+/// equal.
+fn plain_value(value: &Value) -> Option<toml::Value> {
+    toml::from_str::<toml::Table>(&format!("v = {}", value.clone().decorated("", "")))
+        .ok()?
+        .remove("v")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(raw: &str) -> Vec<String> {
+        parse_key_path(raw).unwrap()
+    }
+
+    #[test]
+    fn set_and_unset_keep_comments_and_layout() {
+        let raw = "# top comment\nprovider = \"deepseek\" # default\n\n[tui]\n# scroll with the wheel\nmouse_capture = true # inline\n";
+        let mut doc = ConfigDocument::parse(PathBuf::from("config.toml"), raw).unwrap();
+
+        doc.set(&key("tui.mouse_capture"), false.into()).unwrap();
+        doc.set(&key("profiles.work.model"), "deepseek-v4-flash".into())
+            .unwrap();
+        doc.set(&key("models.\"deepseek-v4.1\".temperature"), 0.2.into())
+            .unwrap();
+        assert_eq!(
+            doc.to_string(),
+            "# top comment\nprovider = \"deepseek\" # default\n\n[tui]\n# scroll with the wheel\nmouse_capture = false # inline\n\n[profiles.work]\nmodel = \"deepseek-v4-flash\"\n\n[models.\"deepseek-v4.1\"]\ntemperature = 0.2\n"
+        );
+        assert_eq!(
+            doc.get(&key("profiles.work.model")).map(display_item),
+            Some("deepseek-v4-flash".to_string())
+        );
+        assert!(doc.set(&key("tui"), 1.into()).is_err());
+
+        assert!(doc.unset(&key("profiles.work.model")));
+        assert!(!doc.unset(&key("profiles.home.model")));
+        assert!(doc.get(&key("profiles.work.model")).is_none());
+    }
+
+    #[test]
+    fn preserve_formatting_only_rewrites_changed_entries() {
+        let existing = "# keep\nprovider = 'deepseek' # default provider\nmodel = \"old\" # pinned\nlog_level = \"info\"\n\n[tui]\nmouse_capture = false # wheel\n";
+        let updated = "provider = \"deepseek\"\nmodel = \"new\"\n\n[tui]\nmouse_capture = false\n";
+        assert_eq!(
+            preserve_formatting(existing, updated),
+            "# keep\nprovider = 'deepseek' # default provider\nmodel = \"new\" # pinned\n\n[tui]\nmouse_capture = false # wheel\n"
+        );
+        assert_eq!(preserve_formatting("not = [toml", updated), updated);
+    }
+}
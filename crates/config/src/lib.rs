@@ -13,6 +13,14 @@ use serde::{Deserialize, Serialize};
 #[cfg(unix)]
 use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
 
+mod document;
+pub mod migrate;
+pub mod org;
+pub mod schema;
+
+pub use document::{ConfigDocument, display_item, parse_key_path};
+pub use schema::ConfigIssue;
+
 pub const CONFIG_FILE_NAME: &str = "config.toml";
 const DEFAULT_DEEPSEEK_MODEL: &str = "deepseek-v4-pro";
 const DEFAULT_NVIDIA_NIM_MODEL: &str = "deepseek-ai/deepseek-v4-pro";
@@ -200,6 +208,13 @@ pub struct ConfigToml {
     pub auth_mode: Option<String>,
     pub chatgpt_access_token: Option<String>,
     pub device_code_session: Option<String>,
+    /// Where `deepseek login` keeps API keys: `keyring` (OS credential
+    /// store, nothing in this file) or `file` (this file plus the
+    /// file-backed secret store). Unset behaves like `file`.
+    pub credential_store: Option<String>,
+    /// Named credential to use for the active provider (`deepseek login
+    /// --name <NAME>`). Profiles can override it to switch keys.
+    pub credential: Option<String>,
     pub output_mode: Option<String>,
     pub log_level: Option<String>,
     pub telemetry: Option<bool>,
@@ -418,6 +433,53 @@ impl ConfigToml {
         }
     }
 
+    /// Whether `key` is one of the typed keys handled by [`Self::get_value`],
+    /// [`Self::set_value`], and [`Self::unset_value`]. Other keys are edited
+    /// through [`ConfigDocument`] and checked against [`schema`].
+    #[must_use]
+    pub fn is_builtin_key(key: &str) -> bool {
+        const ROOT_KEYS: &[&str] = &[
+            "provider",
+            "api_key",
+            "base_url",
+            "http_headers",
+            "default_text_model",
+            "model",
+            "auth.mode",
+            "auth.chatgpt_access_token",
+            "auth.device_code_session",
+            "credential_store",
+            "credential",
+            "output_mode",
+            "log_level",
+            "telemetry",
+            "approval_policy",
+            "sandbox_mode",
+        ];
+        const PROVIDER_TABLES: &[&str] = &[
+            "deepseek",
+            "nvidia_nim",
+            "openai",
+            "atlascloud",
+            "wanjie_ark",
+            "openrouter",
+            "novita",
+            "fireworks",
+            "sglang",
+            "vllm",
+            "ollama",
+        ];
+        if ROOT_KEYS.contains(&key) {
+            return true;
+        }
+        let mut parts = key.splitn(3, '.');
+        matches!(
+            (parts.next(), parts.next(), parts.next()),
+            (Some("providers"), Some(table), Some("api_key" | "base_url" | "model" | "http_headers"))
+                if PROVIDER_TABLES.contains(&table)
+        )
+    }
+
     #[must_use]
     pub fn get_value(&self, key: &str) -> Option<String> {
         match key {
@@ -430,6 +492,8 @@ impl ConfigToml {
             "auth.mode" => self.auth_mode.clone(),
             "auth.chatgpt_access_token" => self.chatgpt_access_token.clone(),
             "auth.device_code_session" => self.device_code_session.clone(),
+            "credential_store" => self.credential_store.clone(),
+            "credential" => self.credential.clone(),
             "output_mode" => self.output_mode.clone(),
             "log_level" => self.log_level.clone(),
             "telemetry" => self.telemetry.map(|v| v.to_string()),
@@ -530,6 +594,14 @@ impl ConfigToml {
             "auth.mode" => self.auth_mode = Some(value.to_string()),
             "auth.chatgpt_access_token" => self.chatgpt_access_token = Some(value.to_string()),
             "auth.device_code_session" => self.device_code_session = Some(value.to_string()),
+            "credential_store" => {
+                let store = value.trim().to_ascii_lowercase();
+                if !matches!(store.as_str(), "keyring" | "file") {
+                    bail!("invalid credential_store '{value}', expected keyring or file");
+                }
+                self.credential_store = Some(store);
+            }
+            "credential" => self.credential = Some(value.trim().to_string()),
             "output_mode" => self.output_mode = Some(value.to_string()),
             "log_level" => self.log_level = Some(value.to_string()),
             "telemetry" => {
@@ -690,6 +762,8 @@ impl ConfigToml {
             "auth.mode" => self.auth_mode = None,
             "auth.chatgpt_access_token" => self.chatgpt_access_token = None,
             "auth.device_code_session" => self.device_code_session = None,
+            "credential_store" => self.credential_store = None,
+            "credential" => self.credential = None,
             "output_mode" => self.output_mode = None,
             "log_level" => self.log_level = None,
             "telemetry" => self.telemetry = None,
@@ -959,7 +1033,10 @@ impl ConfigToml {
 
     /// Resolve runtime options using an explicit secrets façade.
     ///
-    /// API-key precedence is **CLI flag → config-file → secret store → environment**.
+    /// API-key precedence is **CLI flag → named credential → config-file →
+    /// secret store → environment**. A named credential lives only in the
+    /// secret store, so selecting one deliberately shadows the plaintext
+    /// config slot.
     #[must_use]
     pub fn resolve_runtime_options_with_secrets(
         &self,
@@ -1009,8 +1086,22 @@ impl ConfigToml {
         // secrets façade recovers configured secret-store credentials before
         // falling back to ambient env.
         let from_file = provider_cfg.api_key.clone().or(root_deepseek_api_key);
+        let credential = cli
+            .credential
+            .as_deref()
+            .or(self.credential.as_deref())
+            .map(str::trim)
+            .filter(|name| !name.is_empty());
+        let named_slot =
+            credential.map(|name| deepseek_secrets::credential_slot(provider.as_str(), Some(name)));
         let (api_key, api_key_source) = if let Some(value) = cli.api_key.clone() {
             (Some(value), Some(RuntimeApiKeySource::Cli))
+        } else if let Some(value) = named_slot
+            .as_deref()
+            .and_then(|slot| secrets.get(slot).ok().flatten())
+            .filter(|v| !v.trim().is_empty())
+        {
+            (Some(value), Some(RuntimeApiKeySource::Keyring))
         } else if let Some(value) = from_file.clone().filter(|v| !v.trim().is_empty()) {
             (Some(value), Some(RuntimeApiKeySource::ConfigFile))
         } else if should_skip_secret_store_for_provider(provider, &base_url, auth_mode.as_deref()) {
@@ -1306,6 +1397,9 @@ pub struct CliRuntimeOverrides {
     pub api_key: Option<String>,
     pub base_url: Option<String>,
     pub auth_mode: Option<String>,
+    /// Named credential (`--credential` / profile `credential`), overriding
+    /// the config file's top-level `credential`.
+    pub credential: Option<String>,
     pub output_mode: Option<String>,
     pub log_level: Option<String>,
     pub telemetry: Option<bool>,
@@ -1371,6 +1465,14 @@ impl ConfigStore {
             .with_context(|| format!("failed to read config at {}", path.display()))?;
         let parsed: ConfigToml = toml::from_str(&raw)
             .with_context(|| format!("failed to parse config at {}", path.display()))?;
+        if let Some(version) = parsed
+            .extras
+            .get(migrate::CONFIG_VERSION_KEY)
+            .and_then(toml::Value::as_integer)
+        {
+            migrate::ensure_supported(version)
+                .with_context(|| format!("unsupported config at {}", path.display()))?;
+        }
 
         Ok(Self {
             path,
@@ -1378,38 +1480,15 @@ impl ConfigStore {
         })
     }
 
+    /// Write the config back. Entries whose value did not change keep their
+    /// comments and formatting.
     pub fn save(&self) -> Result<()> {
-        if let Some(parent) = self.path.parent() {
-            fs::create_dir_all(parent).with_context(|| {
-                format!("failed to create config directory {}", parent.display())
-            })?;
-        }
         let body = toml::to_string_pretty(&self.config).context("failed to serialize config")?;
-        #[cfg(unix)]
-        {
-            let mut file = fs::OpenOptions::new()
-                .write(true)
-                .create(true)
-                .truncate(true)
-                .mode(0o600)
-                .open(&self.path)
-                .with_context(|| format!("failed to write config at {}", self.path.display()))?;
-            file.write_all(body.as_bytes())
-                .with_context(|| format!("failed to write config at {}", self.path.display()))?;
-            file.set_permissions(fs::Permissions::from_mode(0o600))
-                .with_context(|| {
-                    format!(
-                        "failed to set config permissions at {}",
-                        self.path.display()
-                    )
-                })?;
-        }
-        #[cfg(not(unix))]
-        {
-            fs::write(&self.path, body)
-                .with_context(|| format!("failed to write config at {}", self.path.display()))?;
-        }
-        Ok(())
+        let body = match fs::read_to_string(&self.path) {
+            Ok(existing) => document::preserve_formatting(&existing, &body),
+            Err(_) => body,
+        };
+        write_config_file(&self.path, &body)
     }
 
     #[must_use]
@@ -1418,6 +1497,35 @@ impl ConfigStore {
     }
 }
 
+/// Write `body` to `path` with owner-only permissions on Unix, creating the
+/// parent directory.
+fn write_config_file(path: &Path, body: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create config directory {}", parent.display()))?;
+    }
+    #[cfg(unix)]
+    {
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)
+            .with_context(|| format!("failed to write config at {}", path.display()))?;
+        file.write_all(body.as_bytes())
+            .with_context(|| format!("failed to write config at {}", path.display()))?;
+        file.set_permissions(fs::Permissions::from_mode(0o600))
+            .with_context(|| format!("failed to set config permissions at {}", path.display()))?;
+    }
+    #[cfg(not(unix))]
+    {
+        fs::write(path, body)
+            .with_context(|| format!("failed to write config at {}", path.display()))?;
+    }
+    Ok(())
+}
+
 /// Process-wide default [`Secrets`] façade. The first caller wins; the
 /// lock is exposed so test or CLI code can install an explicit
 /// backend (e.g. an [`deepseek_secrets::InMemoryKeyringStore`]) before
@@ -2693,6 +2801,52 @@ mod tests {
         assert_eq!(resolved.model, "DeepSeek-V4-Pro");
     }
 
+    #[test]
+    fn named_credential_shadows_config_file_and_cli_name_wins() {
+        use deepseek_secrets::KeyringStore;
+        let _lock = env_lock();
+        let _env = EnvGuard::without_deepseek_runtime_overrides();
+
+        let store = std::sync::Arc::new(deepseek_secrets::InMemoryKeyringStore::new());
+        store.set("deepseek:work", "work-key").unwrap();
+        store.set("deepseek:personal", "personal-key").unwrap();
+        let secrets = Secrets::new(store);
+
+        let mut config = ConfigToml {
+            credential: Some("work".to_string()),
+            ..ConfigToml::default()
+        };
+        config.providers.deepseek.api_key = Some("file-key".to_string());
+
+        let resolved =
+            config.resolve_runtime_options_with_secrets(&CliRuntimeOverrides::default(), &secrets);
+        assert_eq!(resolved.api_key.as_deref(), Some("work-key"));
+        assert_eq!(resolved.api_key_source, Some(RuntimeApiKeySource::Keyring));
+
+        let cli = CliRuntimeOverrides {
+            credential: Some("personal".to_string()),
+            ..CliRuntimeOverrides::default()
+        };
+        let resolved = config.resolve_runtime_options_with_secrets(&cli, &secrets);
+        assert_eq!(resolved.api_key.as_deref(), Some("personal-key"));
+
+        // Unknown names fall through to the ordinary chain.
+        let cli = CliRuntimeOverrides {
+            credential: Some("missing".to_string()),
+            ..CliRuntimeOverrides::default()
+        };
+        let resolved = config.resolve_runtime_options_with_secrets(&cli, &secrets);
+        assert_eq!(resolved.api_key.as_deref(), Some("file-key"));
+    }
+
+    #[test]
+    fn credential_store_rejects_unknown_backends() {
+        let mut config = ConfigToml::default();
+        config.set_value("credential_store", "Keyring").unwrap();
+        assert_eq!(config.credential_store.as_deref(), Some("keyring"));
+        assert!(config.set_value("credential_store", "vault").is_err());
+    }
+
     #[test]
     fn config_file_resolves_above_env_and_keyring() {
         use deepseek_secrets::KeyringStore;
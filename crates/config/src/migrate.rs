@@ -0,0 +1,139 @@
+//! Versioned migrations for `config.toml`.
+//!
+//! The file records the schema it was written for in a top-level
+//! `config_version` key (absent means `0`). `deepseek config migrate` runs
+//! every migration newer than that version, in order, on the parsed
+//! document so comments and formatting of untouched entries survive, then
+//! stamps [`CURRENT_CONFIG_VERSION`]. A file stamped with a newer version
+//! than this build understands is refused rather than half-read.
+
+use anyhow::{Result, bail};
+use toml_edit::{DocumentMut, Item, TableLike};
+
+pub const CONFIG_VERSION_KEY: &str = "config_version";
+
+/// Schema version written by this build.
+pub const CURRENT_CONFIG_VERSION: i64 = 1;
+
+struct Migration {
+    /// Version the file is at after this migration runs.
+    version: i64,
+    apply: fn(&mut DocumentMut, &mut Vec<String>),
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    apply: rename_model_thinking,
+}];
+
+/// The `config_version` recorded in `doc`, or `0` when absent.
+#[must_use]
+pub fn config_version(doc: &DocumentMut) -> i64 {
+    doc.get(CONFIG_VERSION_KEY)
+        .and_then(Item::as_integer)
+        .unwrap_or(0)
+}
+
+/// Refuse files written for a newer schema than this build understands.
+pub fn ensure_supported(version: i64) -> Result<()> {
+    if version > CURRENT_CONFIG_VERSION {
+        bail!(
+            "config_version {version} was written by a newer deepseek \
+             (this build understands up to {CURRENT_CONFIG_VERSION}); upgrade deepseek"
+        );
+    }
+    Ok(())
+}
+
+/// Apply pending migrations to `doc` in place. Returns one line per change;
+/// empty when the file is already current.
+pub fn migrate(doc: &mut DocumentMut) -> Result<Vec<String>> {
+    let version = config_version(doc);
+    ensure_supported(version)?;
+    let mut notes = Vec::new();
+    if version == CURRENT_CONFIG_VERSION {
+        return Ok(notes);
+    }
+    for migration in MIGRATIONS.iter().filter(|m| m.version > version) {
+        (migration.apply)(doc, &mut notes);
+    }
+    doc[CONFIG_VERSION_KEY] = toml_edit::value(CURRENT_CONFIG_VERSION);
+    notes.push(format!(
+        "{CONFIG_VERSION_KEY} {version} -> {CURRENT_CONFIG_VERSION}"
+    ));
+    Ok(notes)
+}
+
+/// v1: `[models."<id>"] thinking` became `thinking_budget`.
+fn rename_model_thinking(doc: &mut DocumentMut, notes: &mut Vec<String>) {
+    rename_in_models(doc.as_table_mut(), "", notes);
+    if let Some(profiles) = doc.get_mut("profiles").and_then(Item::as_table_like_mut) {
+        for (name, profile) in profiles.iter_mut() {
+            if let Some(profile) = profile.as_table_like_mut() {
+                rename_in_models(profile, &format!("profiles.{}.", name.get()), notes);
+            }
+        }
+    }
+}
+
+fn rename_in_models(table: &mut dyn TableLike, prefix: &str, notes: &mut Vec<String>) {
+    let Some(models) = table.get_mut("models").and_then(Item::as_table_like_mut) else {
+        return;
+    };
+    for (id, preset) in models.iter_mut() {
+        let Some(preset) = preset.as_table_like_mut() else {
+            continue;
+        };
+        let Some(old) = preset.remove("thinking") else {
+            continue;
+        };
+        let path = format!("{prefix}models.{}", id.get());
+        if preset.contains_key("thinking_budget") {
+            notes.push(format!(
+                "{path}.thinking removed (thinking_budget is already set)"
+            ));
+        } else {
+            preset.insert("thinking_budget", old);
+            notes.push(format!("{path}.thinking -> thinking_budget"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrate_renames_legacy_keys_and_stamps_the_version() {
+        let mut doc: DocumentMut = r#"# my settings
+provider = "deepseek" # keep me
+
+[models."deepseek-v4-pro"]
+thinking = "high"
+
+[profiles.work.models.fast]
+thinking = "off"
+"#
+        .parse()
+        .unwrap();
+        let notes = migrate(&mut doc).unwrap();
+        assert_eq!(
+            notes,
+            vec![
+                "models.deepseek-v4-pro.thinking -> thinking_budget",
+                "profiles.work.models.fast.thinking -> thinking_budget",
+                "config_version 0 -> 1",
+            ]
+        );
+        let out = doc.to_string();
+        assert!(out.starts_with("# my settings\nprovider = \"deepseek\" # keep me\n"));
+        assert!(out.contains("thinking_budget = \"high\""));
+        assert_eq!(config_version(&doc), CURRENT_CONFIG_VERSION);
+
+        // Already current: nothing to do.
+        assert!(migrate(&mut doc).unwrap().is_empty());
+
+        let mut newer: DocumentMut = "config_version = 99\n".parse().unwrap();
+        assert!(migrate(&mut newer).is_err());
+    }
+}
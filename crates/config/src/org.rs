@@ -0,0 +1,365 @@
+//! Shared team configuration ("org layer") synced from a git repository.
+//!
+//! `deepseek config sync --from <repo>` clones the repo into
+//! `~/.deepseek/org` and records where it came from in
+//! `~/.deepseek/org-sync.toml`. The checkout is read-only from the user's
+//! point of view: every sync hard-resets it to the remote, so local edits are
+//! discarded. The TUI layers it *beneath* the user config:
+//!
+//! - `config.toml` — merged under `~/.deepseek/config.toml` (user keys win).
+//! - `requirements.toml` — enforced like the user requirements file.
+//! - `execpolicy.toml` — rule groups added alongside the user's, prefixed
+//!   `org/`, so org deny rules always apply.
+//! - `skills/` — searched after every user and workspace skills directory.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+/// Default refresh interval when `--refresh-hours` is not given.
+pub const DEFAULT_REFRESH_HOURS: u64 = 24;
+
+const CHECKOUT_DIR: &str = "org";
+const STATE_FILE: &str = "org-sync.toml";
+
+/// Provenance of the last successful sync.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrgSyncState {
+    /// Git URL or path the layer was cloned from.
+    pub url: String,
+    /// Branch or tag; `None` tracks the remote's default branch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Commit the checkout is at.
+    pub commit: String,
+    /// Unix seconds of the last successful sync.
+    pub synced_at: u64,
+    /// How often the TUI refreshes the layer in the background; `0` disables.
+    #[serde(default = "default_refresh_hours")]
+    pub refresh_hours: u64,
+}
+
+fn default_refresh_hours() -> u64 {
+    DEFAULT_REFRESH_HOURS
+}
+
+impl OrgSyncState {
+    /// Whether the layer is due for a background refresh at `now`.
+    #[must_use]
+    pub fn is_stale(&self, now: SystemTime) -> bool {
+        if self.refresh_hours == 0 {
+            return false;
+        }
+        let age = unix_secs(now).saturating_sub(self.synced_at);
+        age >= self.refresh_hours.saturating_mul(3600)
+    }
+
+    /// First 12 characters of the commit, for display.
+    #[must_use]
+    pub fn short_commit(&self) -> &str {
+        self.commit.get(..12).unwrap_or(&self.commit)
+    }
+}
+
+/// Location of the org layer checkout and its sync state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrgLayer {
+    root: PathBuf,
+}
+
+impl OrgLayer {
+    /// Org layer under `deepseek_dir` (normally `~/.deepseek`).
+    #[must_use]
+    pub fn at(deepseek_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            root: deepseek_dir.into(),
+        }
+    }
+
+    /// Org layer under `~/.deepseek`.
+    #[must_use]
+    pub fn default_location() -> Option<Self> {
+        dirs::home_dir().map(|home| Self::at(home.join(".deepseek")))
+    }
+
+    /// The git checkout.
+    #[must_use]
+    pub fn checkout_dir(&self) -> PathBuf {
+        self.root.join(CHECKOUT_DIR)
+    }
+
+    #[must_use]
+    pub fn state_path(&self) -> PathBuf {
+        self.root.join(STATE_FILE)
+    }
+
+    #[must_use]
+    pub fn config_path(&self) -> PathBuf {
+        self.checkout_dir().join("config.toml")
+    }
+
+    #[must_use]
+    pub fn requirements_path(&self) -> PathBuf {
+        self.checkout_dir().join("requirements.toml")
+    }
+
+    #[must_use]
+    pub fn execpolicy_path(&self) -> PathBuf {
+        self.checkout_dir().join("execpolicy.toml")
+    }
+
+    /// Shared execpolicy packs.
+    #[must_use]
+    pub fn execpolicy_packs_dir(&self) -> PathBuf {
+        self.checkout_dir().join("execpolicy").join("packs")
+    }
+
+    #[must_use]
+    pub fn skills_dir(&self) -> PathBuf {
+        self.checkout_dir().join("skills")
+    }
+
+    /// Sync state, or `None` when the layer was never synced.
+    pub fn state(&self) -> Result<Option<OrgSyncState>> {
+        let path = self.state_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let raw = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&raw)
+            .map(Some)
+            .with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    /// Whether a synced checkout is present.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.checkout_dir().join(".git").is_dir() && self.state_path().is_file()
+    }
+
+    /// Clone `url` (or fast-forward an existing checkout of it) and record
+    /// the new provenance.
+    pub fn sync(
+        &self,
+        url: &str,
+        branch: Option<&str>,
+        refresh_hours: u64,
+    ) -> Result<OrgSyncState> {
+        let url = url.trim();
+        if url.is_empty() {
+            bail!("org config repository URL is empty");
+        }
+        // Both reach git as positional arguments; a leading `-` would be
+        // parsed as an option such as `--upload-pack=<command>`.
+        if url.starts_with('-') {
+            bail!("org config repository URL must not start with '-': {url}");
+        }
+        if let Some(branch) = branch.filter(|branch| branch.starts_with('-')) {
+            bail!("org config branch must not start with '-': {branch}");
+        }
+        let checkout = self.checkout_dir();
+        let same_remote = checkout.join(".git").is_dir()
+            && git_output(&checkout, &["remote", "get-url", "origin"])
+                .ok()
+                .as_deref()
+                == Some(url);
+        if same_remote {
+            let target = branch.unwrap_or("HEAD");
+            git(&checkout, &["fetch", "--depth", "1", "origin", target])?;
+            git(&checkout, &["reset", "--hard", "FETCH_HEAD"])?;
+            git(&checkout, &["clean", "-fdx"])?;
+        } else {
+            if checkout.exists() {
+                fs::remove_dir_all(&checkout)
+                    .with_context(|| format!("failed to remove {}", checkout.display()))?;
+            }
+            fs::create_dir_all(&self.root)
+                .with_context(|| format!("failed to create {}", self.root.display()))?;
+            let checkout_arg = checkout.to_string_lossy();
+            let mut args = vec!["clone", "--quiet", "--depth", "1"];
+            if let Some(branch) = branch {
+                args.extend(["--branch", branch]);
+            }
+            args.extend(["--", url, checkout_arg.as_ref()]);
+            git(&self.root, &args)?;
+        }
+        let state = OrgSyncState {
+            url: url.to_string(),
+            branch: branch.map(str::to_string),
+            commit: git_output(&checkout, &["rev-parse", "HEAD"])?,
+            synced_at: unix_secs(SystemTime::now()),
+            refresh_hours,
+        };
+        let raw = toml::to_string(&state).context("failed to serialize org sync state")?;
+        fs::write(self.state_path(), raw)
+            .with_context(|| format!("failed to write {}", self.state_path().display()))?;
+        Ok(state)
+    }
+
+    /// Re-sync from the recorded source when the refresh interval has passed.
+    /// Returns the new state when a refresh ran.
+    pub fn refresh_if_stale(&self) -> Result<Option<OrgSyncState>> {
+        match self.state()? {
+            Some(state) if state.is_stale(SystemTime::now()) => self
+                .sync(&state.url, state.branch.as_deref(), state.refresh_hours)
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Delete the checkout and its sync state.
+    pub fn remove(&self) -> Result<bool> {
+        let mut removed = false;
+        let checkout = self.checkout_dir();
+        if checkout.exists() {
+            fs::remove_dir_all(&checkout)
+                .with_context(|| format!("failed to remove {}", checkout.display()))?;
+            removed = true;
+        }
+        let state = self.state_path();
+        if state.exists() {
+            fs::remove_file(&state)
+                .with_context(|| format!("failed to remove {}", state.display()))?;
+            removed = true;
+        }
+        Ok(removed)
+    }
+}
+
+/// Human-readable age such as `3h ago`.
+#[must_use]
+pub fn format_age(synced_at: u64, now: SystemTime) -> String {
+    let secs = unix_secs(now).saturating_sub(synced_at);
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86_400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs()
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    git_output(dir, args).map(|_| ())
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .with_context(|| format!("failed to run git {}", args.join(" ")))?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn init_repo(dir: &Path, config: &str) {
+        fs::create_dir_all(dir).unwrap();
+        for args in [
+            &["init", "--quiet", "-b", "main"][..],
+            &["config", "user.email", "org@example.com"],
+            &["config", "user.name", "org"],
+        ] {
+            git(dir, args).unwrap();
+        }
+        commit_config(dir, config);
+    }
+
+    fn commit_config(dir: &Path, config: &str) {
+        fs::write(dir.join("config.toml"), config).unwrap();
+        git(dir, &["add", "-A"]).unwrap();
+        git(dir, &["commit", "--quiet", "-m", "update"]).unwrap();
+    }
+
+    #[test]
+    fn sync_clones_then_fast_forwards_and_discards_local_edits() {
+        let tmp = std::env::temp_dir().join(format!(
+            "deepseek-org-sync-{}-{}",
+            std::process::id(),
+            unix_secs(SystemTime::now())
+        ));
+        let remote = tmp.join("team-config");
+        init_repo(&remote, "approval_policy = \"on-request\"\n");
+        let url = remote.to_string_lossy().to_string();
+        let layer = OrgLayer::at(tmp.join("home"));
+
+        let first = layer.sync(&url, None, 6).unwrap();
+        assert!(layer.is_active());
+        assert_eq!(layer.state().unwrap().as_ref(), Some(&first));
+        assert!(!first.is_stale(SystemTime::now()));
+
+        fs::write(layer.config_path(), "tampered = true\n").unwrap();
+        commit_config(&remote, "approval_policy = \"untrusted\"\n");
+        let second = layer.sync(&url, None, 6).unwrap();
+        assert_ne!(first.commit, second.commit);
+        assert_eq!(
+            fs::read_to_string(layer.config_path()).unwrap(),
+            "approval_policy = \"untrusted\"\n"
+        );
+
+        assert!(layer.remove().unwrap());
+        assert!(!layer.is_active());
+        assert_eq!(layer.state().unwrap(), None);
+        let _ = fs::remove_dir_all(&tmp);
+    }
+
+    #[test]
+    fn sync_rejects_urls_and_branches_that_look_like_options() {
+        let tmp = std::env::temp_dir().join(format!(
+            "deepseek-org-sync-opts-{}-{}",
+            std::process::id(),
+            unix_secs(SystemTime::now())
+        ));
+        let layer = OrgLayer::at(tmp.join("home"));
+        let err = layer
+            .sync("--upload-pack=touch pwned", None, 6)
+            .unwrap_err();
+        assert!(err.to_string().contains("must not start with '-'"), "{err}");
+        let err = layer
+            .sync("https://example.com/team.git", Some("--upload-pack=x"), 6)
+            .unwrap_err();
+        assert!(err.to_string().contains("must not start with '-'"), "{err}");
+        assert!(!layer.checkout_dir().exists());
+        let _ = fs::remove_dir_all(&tmp);
+    }
+
+    #[test]
+    fn staleness_follows_refresh_interval() {
+        let now = SystemTime::now();
+        let mut state = OrgSyncState {
+            url: "git@example.com:team-config.git".to_string(),
+            branch: None,
+            commit: "0123456789abcdef".to_string(),
+            synced_at: unix_secs(now) - 2 * 3600,
+            refresh_hours: 1,
+        };
+        assert!(state.is_stale(now));
+        state.refresh_hours = 3;
+        assert!(!state.is_stale(now));
+        state.refresh_hours = 0;
+        assert!(!state.is_stale(now));
+        assert_eq!(state.short_commit(), "0123456789ab");
+        assert_eq!(format_age(state.synced_at, now), "2h ago");
+    }
+}
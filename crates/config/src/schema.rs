@@ -0,0 +1,697 @@
+//! Key schema for `~/.deepseek/config.toml`.
+//!
+//! Both binaries read the same file but deserialize it leniently: serde
+//! drops keys it does not know, so a typo such as `[tui] mouse_captur =
+//! false` is silently ignored. This module describes every key the CLI and
+//! TUI understand so `deepseek config validate`, `set`, and `edit` can
+//! reject unknown keys and mistyped values with an actionable message.
+//!
+//! Tables whose shape is owned elsewhere (hooks, feature flags, key
+//! bindings, tool filters, …) are described as [`Kind::Any`] and are not
+//! checked below their root.
+
+use std::fmt;
+
+use anyhow::{Context, Result, bail};
+
+/// Expected shape of a config value.
+#[derive(Debug)]
+pub enum Kind {
+    String,
+    Bool,
+    Integer,
+    /// Floats also accept integer literals (`0.5`, `1`).
+    Float,
+    /// One of a fixed set of strings.
+    Enum(&'static [&'static str]),
+    StringArray,
+    /// Array of anything (e.g. arrays of tables).
+    Array,
+    /// Table of string values with arbitrary keys (`http_headers`).
+    StringMap,
+    /// Table with a fixed set of keys.
+    Table(&'static [Field]),
+    /// Table with arbitrary keys whose values all share one shape
+    /// (`[profiles.<name>]`, `[models."<id>"]`).
+    Map(&'static Kind),
+    /// Not validated below this point.
+    Any,
+    /// Deprecated key kept readable for compatibility. `deepseek config
+    /// migrate` rewrites it to the named replacement.
+    Renamed(&'static str),
+}
+
+/// One named key inside a [`Kind::Table`].
+#[derive(Debug)]
+pub struct Field {
+    pub name: &'static str,
+    pub kind: Kind,
+}
+
+const fn field(name: &'static str, kind: Kind) -> Field {
+    Field { name, kind }
+}
+
+/// A problem found while validating a config file or a `set` request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// Dotted path of the offending key, e.g. `tui.mouse_capture`.
+    pub key: String,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.key, self.message)
+    }
+}
+
+impl std::error::Error for ConfigIssue {}
+
+const PROVIDER_FIELDS: &[Field] = &[
+    field("api_key", Kind::String),
+    field("base_url", Kind::String),
+    field("model", Kind::String),
+    field("http_headers", Kind::StringMap),
+];
+
+const PROVIDER: Kind = Kind::Table(PROVIDER_FIELDS);
+
+const PROVIDERS_FIELDS: &[Field] = &[
+    field("deepseek", PROVIDER),
+    field("deepseek_cn", PROVIDER),
+    field("nvidia_nim", PROVIDER),
+    field("openai", PROVIDER),
+    field("atlascloud", PROVIDER),
+    field("wanjie_ark", PROVIDER),
+    field("openrouter", PROVIDER),
+    field("novita", PROVIDER),
+    field("fireworks", PROVIDER),
+    field("sglang", PROVIDER),
+    field("vllm", PROVIDER),
+    field("ollama", PROVIDER),
+];
+
+const RETRY_FIELDS: &[Field] = &[
+    field("enabled", Kind::Bool),
+    field("max_retries", Kind::Integer),
+    field("initial_delay", Kind::Float),
+    field("max_delay", Kind::Float),
+    field("exponential_base", Kind::Float),
+];
+
+const CAPACITY_FIELDS: &[Field] = &[
+    field("enabled", Kind::Bool),
+    field("low_risk_max", Kind::Float),
+    field("medium_risk_max", Kind::Float),
+    field("severe_min_slack", Kind::Float),
+    field("severe_violation_ratio", Kind::Float),
+    field("refresh_cooldown_turns", Kind::Integer),
+    field("replan_cooldown_turns", Kind::Integer),
+    field("max_replay_per_turn", Kind::Integer),
+    field("min_turns_before_guardrail", Kind::Integer),
+    field("profile_window", Kind::Integer),
+    field("deepseek_v3_2_chat_prior", Kind::Float),
+    field("deepseek_v3_2_reasoner_prior", Kind::Float),
+    field("deepseek_v4_pro_prior", Kind::Float),
+    field("deepseek_v4_flash_prior", Kind::Float),
+    field("fallback_default_prior", Kind::Float),
+];
+
+const TUI_FIELDS: &[Field] = &[
+    field("alternate_screen", Kind::String),
+    field("mouse_capture", Kind::Bool),
+    field("terminal_probe_timeout_ms", Kind::Integer),
+    field("status_items", Kind::StringArray),
+    field("osc8_links", Kind::Bool),
+    field("notification_condition", Kind::Enum(&["always", "never"])),
+    field("composer_arrows_scroll", Kind::Bool),
+    // Fallbacks for `~/.deepseek/tui.toml` preferences.
+    field("theme", Kind::String),
+    field("font_size", Kind::Integer),
+    field("keybinds", Kind::Any),
+];
+
+const NOTIFICATION_METHODS: &[&str] = &["auto", "osc9", "bel", "kitty", "ghostty", "off"];
+
+const NOTIFICATION_EVENT_FIELDS: &[Field] = &[
+    field("enabled", Kind::Bool),
+    field("threshold_secs", Kind::Integer),
+    field("when_unfocused", Kind::Bool),
+    field("desktop", Kind::Bool),
+    field("bell", Kind::Bool),
+];
+
+const NOTIFICATIONS_FIELDS: &[Field] = &[
+    field("method", Kind::Enum(NOTIFICATION_METHODS)),
+    field("threshold_secs", Kind::Integer),
+    field("include_summary", Kind::Bool),
+    field("when_unfocused", Kind::Bool),
+    field("desktop", Kind::Bool),
+    field("bell", Kind::Bool),
+    field("turn", Kind::Table(NOTIFICATION_EVENT_FIELDS)),
+    field("subagent", Kind::Table(NOTIFICATION_EVENT_FIELDS)),
+];
+
+const NETWORK_FIELDS: &[Field] = &[
+    field("default", Kind::Enum(&["allow", "deny", "prompt"])),
+    field("allow", Kind::StringArray),
+    field("deny", Kind::StringArray),
+    field("proxy", Kind::StringArray),
+    field("audit", Kind::Bool),
+];
+
+const SKILLS_FIELDS: &[Field] = &[
+    field("registry_url", Kind::String),
+    field("max_install_size_bytes", Kind::Integer),
+];
+
+const SNAPSHOTS_FIELDS: &[Field] = &[
+    field("enabled", Kind::Bool),
+    field("max_age_days", Kind::Integer),
+    field("max_workspace_gb", Kind::Integer),
+    field("auto_before_write", Kind::Bool),
+];
+
+const SESSIONS_FIELDS: &[Field] = &[
+    field("max_sessions", Kind::Integer),
+    field("max_age_days", Kind::Integer),
+    field("max_total_mb", Kind::Integer),
+    field("auto_gc", Kind::Bool),
+];
+
+const TOOL_CACHE_FIELDS: &[Field] = &[field("scope", Kind::Enum(&["off", "turn", "session"]))];
+
+const PROMPT_LAYERS_FIELDS: &[Field] = &[
+    field("disabled", Kind::StringArray),
+    field("order", Kind::StringArray),
+    field("replace", Kind::StringMap),
+    field("custom", Kind::Array),
+];
+
+const SEARCH_FIELDS: &[Field] = &[
+    field(
+        "provider",
+        Kind::Enum(&["bing", "duck_duck_go", "duckduckgo", "tavily", "bocha"]),
+    ),
+    field("api_key", Kind::String),
+];
+
+const MEMORY_FIELDS: &[Field] = &[
+    field("enabled", Kind::Bool),
+    field("command_lessons", Kind::Bool),
+];
+
+const AUTO_FIELDS: &[Field] = &[field("cost_saving", Kind::Bool)];
+
+const MODEL_PRESET_FIELDS: &[Field] = &[
+    field("temperature", Kind::Float),
+    field("top_p", Kind::Float),
+    field("max_tokens", Kind::Integer),
+    field("thinking_budget", Kind::String),
+    field("thinking", Kind::Renamed("thinking_budget")),
+];
+
+const MODEL_PRESET: Kind = Kind::Table(MODEL_PRESET_FIELDS);
+
+const EXEC_PROFILE_FIELDS: &[Field] = &[
+    field("command_prefix", Kind::StringArray),
+    field("join_command", Kind::Bool),
+];
+
+const EXEC_PROFILE: Kind = Kind::Table(EXEC_PROFILE_FIELDS);
+
+const SHELL_LIMITS_FIELDS: &[Field] = &[
+    field("cpu_seconds", Kind::Integer),
+    field("memory_mb", Kind::Integer),
+    field("write_mb", Kind::Integer),
+];
+
+const SHELL_LIMITS: Kind = Kind::Table(SHELL_LIMITS_FIELDS);
+
+const GUARDRAILS_FIELDS: &[Field] = &[field("enabled", Kind::Bool), field("rules", Kind::Array)];
+
+const LSP_FIELDS: &[Field] = &[
+    field("enabled", Kind::Bool),
+    field("poll_after_edit_ms", Kind::Integer),
+    field("max_diagnostics_per_file", Kind::Integer),
+    field("include_warnings", Kind::Bool),
+    field("servers", Kind::Any),
+];
+
+const THINKING_FIELDS: &[Field] = &[
+    field("budget_tokens", Kind::Integer),
+    field("abort_repetition", Kind::Bool),
+    field("auto_off", Kind::Bool),
+    field("chat_model", Kind::String),
+];
+
+const TRAIL_FIELDS: &[Field] = &[field("enabled", Kind::Bool), field("branch", Kind::String)];
+
+const CONTEXT_FIELDS: &[Field] = &[
+    field("enabled", Kind::Bool),
+    field("project_pack", Kind::Bool),
+    field("repo_map", Kind::Bool),
+    field("repo_map_tokens", Kind::Integer),
+    field("verbatim_window_turns", Kind::Integer),
+    field("l1_threshold", Kind::Integer),
+    field("l2_threshold", Kind::Integer),
+    field("l3_threshold", Kind::Integer),
+    field("cycle_threshold", Kind::Integer),
+    field("seam_model", Kind::String),
+];
+
+const SUBAGENTS_FIELDS: &[Field] = &[
+    field("default_model", Kind::String),
+    field("worker_model", Kind::String),
+    field("explorer_model", Kind::String),
+    field("awaiter_model", Kind::String),
+    field("review_model", Kind::String),
+    field("custom_model", Kind::String),
+    field("models", Kind::StringMap),
+    field("max_concurrent", Kind::Integer),
+    field("api_timeout_secs", Kind::Integer),
+    field("max_steps", Kind::Integer),
+    field("allowed_models", Kind::StringArray),
+];
+
+const RUNTIME_API_TLS_FIELDS: &[Field] = &[
+    field("cert", Kind::String),
+    field("key", Kind::String),
+    field("client_ca", Kind::String),
+];
+
+const RUNTIME_API_FIELDS: &[Field] = &[
+    field("cors_origins", Kind::StringArray),
+    field("max_upload_bytes", Kind::Integer),
+    field("openai_tools", Kind::Bool),
+    field("token_file", Kind::String),
+    field("read_only_token", Kind::String),
+    field("turns_per_minute", Kind::Integer),
+    field("tls", Kind::Table(RUNTIME_API_TLS_FIELDS)),
+];
+
+const VISION_MODEL_FIELDS: &[Field] = &[
+    field("model", Kind::String),
+    field("api_key", Kind::String),
+    field("base_url", Kind::String),
+];
+
+const SMART_CONTEXT_FIELDS: &[Field] = &[
+    field("enabled", Kind::Bool),
+    field("max_files", Kind::Integer),
+];
+
+static ROOT_FIELDS: &[Field] = &[
+    field(crate::migrate::CONFIG_VERSION_KEY, Kind::Integer),
+    field("provider", Kind::String),
+    field("api_key", Kind::String),
+    field("base_url", Kind::String),
+    field("http_headers", Kind::StringMap),
+    field("default_text_model", Kind::String),
+    field("model", Kind::String),
+    field("reasoning_effort", Kind::String),
+    field("auth_mode", Kind::String),
+    field("chatgpt_access_token", Kind::String),
+    field("device_code_session", Kind::String),
+    field("credential_store", Kind::String),
+    field("credential", Kind::String),
+    field("output_mode", Kind::String),
+    field("log_level", Kind::String),
+    field("telemetry", Kind::Bool),
+    field("tools_file", Kind::String),
+    field("skills_dir", Kind::String),
+    field("mcp_config_path", Kind::String),
+    field("notes_path", Kind::String),
+    field("memory_path", Kind::String),
+    field("strict_tool_mode", Kind::Bool),
+    field("self_report", Kind::Bool),
+    field("instructions", Kind::StringArray),
+    field("allow_shell", Kind::Bool),
+    field("approval_policy", Kind::String),
+    field("sandbox_mode", Kind::String),
+    field("yolo", Kind::Bool),
+    field("sandbox_backend", Kind::String),
+    field("sandbox_url", Kind::String),
+    field("sandbox_api_key", Kind::String),
+    field("managed_config_path", Kind::String),
+    field("requirements_path", Kind::String),
+    field("max_subagents", Kind::Integer),
+    field("model_fallbacks", Kind::StringArray),
+    field("mcp.server_definitions", Kind::String),
+    field("providers", Kind::Table(PROVIDERS_FIELDS)),
+    field("retry", Kind::Table(RETRY_FIELDS)),
+    field("capacity", Kind::Table(CAPACITY_FIELDS)),
+    field("tui", Kind::Table(TUI_FIELDS)),
+    field("notifications", Kind::Table(NOTIFICATIONS_FIELDS)),
+    field("network", Kind::Table(NETWORK_FIELDS)),
+    field("skills", Kind::Table(SKILLS_FIELDS)),
+    field("snapshots", Kind::Table(SNAPSHOTS_FIELDS)),
+    field("sessions", Kind::Table(SESSIONS_FIELDS)),
+    field("tool_cache", Kind::Table(TOOL_CACHE_FIELDS)),
+    field("prompt_layers", Kind::Table(PROMPT_LAYERS_FIELDS)),
+    field("search", Kind::Table(SEARCH_FIELDS)),
+    field("memory", Kind::Table(MEMORY_FIELDS)),
+    field("auto", Kind::Table(AUTO_FIELDS)),
+    field("models", Kind::Map(&MODEL_PRESET)),
+    field("exec_profiles", Kind::Map(&EXEC_PROFILE)),
+    field("env", Kind::StringMap),
+    field("shell_limits", Kind::Map(&SHELL_LIMITS)),
+    field("guardrails", Kind::Table(GUARDRAILS_FIELDS)),
+    field("lsp", Kind::Table(LSP_FIELDS)),
+    field("thinking", Kind::Table(THINKING_FIELDS)),
+    field("trail", Kind::Table(TRAIL_FIELDS)),
+    field("context", Kind::Table(CONTEXT_FIELDS)),
+    field("subagents", Kind::Table(SUBAGENTS_FIELDS)),
+    field("runtime_api", Kind::Table(RUNTIME_API_FIELDS)),
+    field("vision_model", Kind::Table(VISION_MODEL_FIELDS)),
+    field("smart_context", Kind::Table(SMART_CONTEXT_FIELDS)),
+    field("features", Kind::Any),
+    field("hooks", Kind::Any),
+    field("keys", Kind::Any),
+    field("edit_validation", Kind::Any),
+    field("tool_filters", Kind::Any),
+    field("workshop", Kind::Any),
+    field("projects", Kind::Any),
+    // `[profiles.<name>]` accepts the same keys as the top level.
+    field("profiles", Kind::Map(&ROOT)),
+];
+
+/// Schema of the whole file.
+pub static ROOT: Kind = Kind::Table(ROOT_FIELDS);
+
+impl Kind {
+    fn describe(&self) -> &'static str {
+        match self {
+            Kind::String | Kind::Enum(_) => "a string",
+            Kind::Bool => "a boolean",
+            Kind::Integer => "an integer",
+            Kind::Float => "a number",
+            Kind::StringArray => "an array of strings",
+            Kind::Array => "an array",
+            Kind::StringMap | Kind::Table(_) | Kind::Map(_) => "a table",
+            Kind::Any | Kind::Renamed(_) => "a value",
+        }
+    }
+
+    fn child(&self, key: &str) -> Option<&Kind> {
+        match self {
+            Kind::Table(fields) => fields.iter().find(|f| f.name == key).map(|f| &f.kind),
+            Kind::Map(kind) => Some(kind),
+            Kind::StringMap => Some(&Kind::String),
+            Kind::Any => Some(&Kind::Any),
+            _ => None,
+        }
+    }
+
+    /// Parse a command-line value for `deepseek config set`. Arrays and
+    /// tables accept TOML literals (`["a", "b"]`, `{ X-Team = "core" }`);
+    /// string arrays also accept a comma-separated list.
+    pub fn parse_value(&self, key: &str, raw: &str) -> Result<toml_edit::Value> {
+        let value = match self {
+            Kind::String => raw.into(),
+            Kind::Enum(allowed) => {
+                if !allowed.contains(&raw) {
+                    bail!(ConfigIssue {
+                        key: key.to_string(),
+                        message: format!("expected one of {}", allowed.join(", ")),
+                    });
+                }
+                raw.into()
+            }
+            Kind::Bool => crate::parse_bool(raw)
+                .with_context(|| format!("`{key}` expects true or false"))?
+                .into(),
+            Kind::Integer => raw
+                .trim()
+                .parse::<i64>()
+                .with_context(|| format!("`{key}` expects an integer, got '{raw}'"))?
+                .into(),
+            Kind::Float => raw
+                .trim()
+                .parse::<f64>()
+                .with_context(|| format!("`{key}` expects a number, got '{raw}'"))?
+                .into(),
+            Kind::StringArray => {
+                if raw.trim_start().starts_with('[') {
+                    self.parse_literal(key, raw)?
+                } else {
+                    raw.split(',')
+                        .map(str::trim)
+                        .filter(|item| !item.is_empty())
+                        .collect::<toml_edit::Array>()
+                        .into()
+                }
+            }
+            Kind::Array | Kind::StringMap | Kind::Any => self.parse_literal(key, raw)?,
+            Kind::Table(_) | Kind::Map(_) => bail!(ConfigIssue {
+                key: key.to_string(),
+                message: "is a table; set its keys individually (e.g. `<table>.<key>`)".to_string(),
+            }),
+            Kind::Renamed(to) => bail!(renamed_issue(key, to)),
+        };
+        Ok(value)
+    }
+
+    fn parse_literal(&self, key: &str, raw: &str) -> Result<toml_edit::Value> {
+        let value = match raw.parse::<toml_edit::Value>() {
+            Ok(value) => value,
+            // Bare words are strings for `Any` values.
+            Err(_) if matches!(self, Kind::Any) => raw.into(),
+            Err(err) => {
+                return Err(anyhow::Error::new(err))
+                    .with_context(|| format!("`{key}` expects a TOML {}", self.describe()));
+            }
+        };
+        let toml_value: toml::Value = toml::from_str::<toml::Table>(&format!("v = {value}"))
+            .ok()
+            .and_then(|mut table| table.remove("v"))
+            .context("failed to re-read parsed value")?;
+        let mut issues = Vec::new();
+        self.check(key, &toml_value, &mut issues);
+        if let Some(issue) = issues.into_iter().next() {
+            bail!(issue);
+        }
+        Ok(value.decorated(" ", ""))
+    }
+
+    fn check(&self, path: &str, value: &toml::Value, issues: &mut Vec<ConfigIssue>) {
+        use toml::Value;
+        let ok = match (self, value) {
+            (Kind::Any, _) => true,
+            (Kind::Renamed(to), _) => {
+                issues.push(renamed_issue(path, to));
+                return;
+            }
+            (Kind::String, Value::String(_)) | (Kind::Bool, Value::Boolean(_)) => true,
+            (Kind::Integer, Value::Integer(_)) => true,
+            (Kind::Float, Value::Float(_) | Value::Integer(_)) => true,
+            (Kind::Enum(allowed), Value::String(s)) => {
+                if !allowed.contains(&s.as_str()) {
+                    issues.push(ConfigIssue {
+                        key: path.to_string(),
+                        message: format!(
+                            "'{s}' is not valid; expected one of {}",
+                            allowed.join(", ")
+                        ),
+                    });
+                }
+                true
+            }
+            (Kind::StringArray, Value::Array(items)) => {
+                items.iter().all(|item| matches!(item, Value::String(_)))
+            }
+            (Kind::Array, Value::Array(_)) => true,
+            (Kind::StringMap | Kind::Table(_) | Kind::Map(_), Value::Table(table)) => {
+                self.check_table(path, table, issues);
+                true
+            }
+            _ => false,
+        };
+        if !ok {
+            issues.push(ConfigIssue {
+                key: path.to_string(),
+                message: format!("expected {}, found {}", self.describe(), value.type_str()),
+            });
+        }
+    }
+
+    fn check_table(&self, path: &str, table: &toml::Table, issues: &mut Vec<ConfigIssue>) {
+        for (key, value) in table {
+            let child_path = join(path, key);
+            match self.child(key) {
+                Some(kind) => kind.check(&child_path, value, issues),
+                None => issues.push(unknown_key(self, path, key)),
+            }
+        }
+    }
+}
+
+/// Check a parsed config file against the schema.
+#[must_use]
+pub fn validate(table: &toml::Table) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    ROOT.check_table("", table, &mut issues);
+    issues.sort_by(|a, b| a.key.cmp(&b.key));
+    issues
+}
+
+/// Resolve the schema entry for a dotted key path, or explain why the key
+/// is not recognized.
+pub fn lookup(path: &[String]) -> std::result::Result<&'static Kind, ConfigIssue> {
+    let mut kind: &'static Kind = &ROOT;
+    for (depth, segment) in path.iter().enumerate() {
+        let parent = path[..depth].join(".");
+        kind = kind
+            .child(segment)
+            .ok_or_else(|| unknown_key(kind, &parent, segment))?;
+        if let Kind::Renamed(to) = kind {
+            return Err(renamed_issue(&join(&parent, segment), to));
+        }
+    }
+    Ok(kind)
+}
+
+fn join(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
+
+fn renamed_issue(key: &str, to: &str) -> ConfigIssue {
+    ConfigIssue {
+        key: key.to_string(),
+        message: format!("renamed to `{to}`; run `deepseek config migrate`"),
+    }
+}
+
+fn unknown_key(parent: &Kind, parent_path: &str, key: &str) -> ConfigIssue {
+    let message = match parent {
+        Kind::Table(fields) => {
+            let suggestion = fields
+                .iter()
+                .filter(|f| !matches!(f.kind, Kind::Renamed(_)))
+                .map(|f| (edit_distance(key, f.name), f.name))
+                .filter(|(distance, name)| *distance <= (name.len() / 3).max(2))
+                .min_by_key(|(distance, _)| *distance);
+            match suggestion {
+                Some((_, name)) => {
+                    format!("unknown key; did you mean `{}`?", join(parent_path, name))
+                }
+                None if parent_path.is_empty() => "unknown key".to_string(),
+                None => format!("unknown key in `[{parent_path}]`"),
+            }
+        }
+        _ => format!("`{parent_path}` is {}, not a table", parent.describe()),
+    };
+    ConfigIssue {
+        key: join(parent_path, key),
+        message,
+    }
+}
+
+/// Levenshtein distance, used for "did you mean" suggestions.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issues(src: &str) -> Vec<String> {
+        let table: toml::Table = toml::from_str(src).expect("toml");
+        validate(&table).iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn validate_reports_unknown_keys_with_suggestions_and_type_errors() {
+        let found = issues(
+            r#"
+provider = "deepseek"
+modle = "deepseek-v4-pro"
+
+[tui]
+mouse_captur = false
+osc8_links = "yes"
+
+[notifications]
+method = "toast"
+
+[providers.openai]
+base_url = "https://example.test/v1"
+
+[profiles.work.tui]
+mouse_capture = 1
+
+[models."deepseek-v4-pro"]
+thinking = "high"
+
+[features]
+anything_goes = { enabled = true }
+"#,
+        );
+        assert_eq!(
+            found,
+            vec![
+                "`models.deepseek-v4-pro.thinking`: renamed to `thinking_budget`; run `deepseek config migrate`",
+                "`modle`: unknown key; did you mean `model`?",
+                "`notifications.method`: 'toast' is not valid; expected one of auto, osc9, bel, kitty, ghostty, off",
+                "`profiles.work.tui.mouse_capture`: expected a boolean, found integer",
+                "`tui.mouse_captur`: unknown key; did you mean `tui.mouse_capture`?",
+                "`tui.osc8_links`: expected a boolean, found string",
+            ]
+        );
+    }
+
+    #[test]
+    fn shipped_example_config_is_valid() {
+        assert_eq!(
+            issues(include_str!("../../../config.example.toml")),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn lookup_and_parse_value_follow_the_schema() {
+        let path = |key: &str| key.split('.').map(str::to_string).collect::<Vec<_>>();
+        let kind = lookup(&path("tui.mouse_capture")).expect("known");
+        assert_eq!(
+            kind.parse_value("tui.mouse_capture", "off")
+                .unwrap()
+                .as_bool(),
+            Some(false)
+        );
+        let kind = lookup(&path("profiles.work.network.allow")).expect("known");
+        let value = kind.parse_value("allow", "a.com, b.com").unwrap();
+        assert_eq!(value.as_array().map(toml_edit::Array::len), Some(2));
+
+        let err = lookup(&path("tui.mouse_captur")).unwrap_err();
+        assert!(err.message.contains("did you mean `tui.mouse_capture`"));
+        assert!(lookup(&path("tui.mouse_capture.extra")).is_err());
+        assert!(lookup(&path("hooks.anything.below")).is_ok());
+
+        let kind = lookup(&path("notifications.method")).unwrap();
+        assert!(kind.parse_value("notifications.method", "toast").is_err());
+        let kind = lookup(&path("retry.max_retries")).unwrap();
+        assert!(kind.parse_value("retry.max_retries", "three").is_err());
+        assert!(
+            lookup(&path("tui"))
+                .unwrap()
+                .parse_value("tui", "x")
+                .is_err()
+        );
+    }
+}
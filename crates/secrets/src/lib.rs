@@ -6,9 +6,10 @@
 //! (`InMemoryKeyringStore`).
 //!
 //! Higher-level lookup through [`Secrets::resolve`] checks the secret store first
-//! and falls back to environment variables. Config-file precedence lives in the
-//! config crate so user-facing commands can keep `config -> secret store -> env`
-//! explicit at the call site.
+//! and falls back to environment variables. Credentials can be named per
+//! provider (see [`credential_slot`]) so profiles can pick different keys.
+//! Config-file precedence lives in the config crate so user-facing commands
+//! can keep `config -> secret store -> env` explicit at the call site.
 #![deny(missing_docs)]
 
 use std::collections::HashMap;
@@ -425,7 +426,19 @@ impl Secrets {
     /// [`SECRET_BACKEND_ENV`] to `system` or `keyring` to opt into the OS
     /// credential store.
     pub fn auto_detect() -> Self {
-        match secret_backend_selection(std::env::var(SECRET_BACKEND_ENV).ok().as_deref()) {
+        Self::with_preference(None)
+    }
+
+    /// Construct the backend named by a persisted preference (the
+    /// `credential_store` config key). [`SECRET_BACKEND_ENV`] still wins
+    /// when set, so a shell can force the file store for one invocation.
+    pub fn with_preference(preference: Option<&str>) -> Self {
+        let env = std::env::var(SECRET_BACKEND_ENV).ok();
+        let selected = env
+            .as_deref()
+            .filter(|value| !value.trim().is_empty())
+            .or(preference);
+        match secret_backend_selection(selected) {
             SecretBackendSelection::File => Self::file_backed_default(),
             SecretBackendSelection::Unknown => {
                 tracing::warn!(
@@ -476,6 +489,19 @@ impl Secrets {
         }
     }
 
+    /// Open the OS credential store for writing, reporting why it was not
+    /// used when the file-backed store had to stand in. Interactive callers
+    /// (`deepseek login`) surface the error as a warning so users know
+    /// their key landed in a plaintext-adjacent file instead.
+    #[must_use]
+    pub fn system_keyring_or_file() -> (Self, Option<SecretsError>) {
+        let default_store = DefaultKeyringStore::default();
+        match default_store.probe() {
+            Ok(()) => (Self::new(Arc::new(default_store)), None),
+            Err(err) => (Self::file_backed_default(), Some(err)),
+        }
+    }
+
     /// Backend label, suitable for `doctor` output.
     #[must_use]
     pub fn backend_name(&self) -> &'static str {
@@ -504,6 +530,25 @@ impl Secrets {
         env_for(name).map(|value| (value, SecretSource::Env))
     }
 
+    /// Resolve a named credential for `provider`, falling back to the
+    /// unnamed slot and then the provider's environment variables. With
+    /// `name = None` this is [`Self::resolve_with_source`].
+    #[must_use]
+    pub fn resolve_named_with_source(
+        &self,
+        provider: &str,
+        name: Option<&str>,
+    ) -> Option<(String, SecretSource)> {
+        let slot = credential_slot(provider, name);
+        if slot != provider
+            && let Ok(Some(v)) = self.store.get(&slot)
+            && !v.trim().is_empty()
+        {
+            return Some((v, SecretSource::Keyring));
+        }
+        self.resolve_with_source(provider)
+    }
+
     /// Convenience: write a secret through the underlying store.
     pub fn set(&self, name: &str, value: &str) -> Result<(), SecretsError> {
         self.store.set(name, value)
@@ -520,6 +565,18 @@ impl Secrets {
     }
 }
 
+/// Store slot for a provider credential. Unnamed credentials use the bare
+/// provider name (`deepseek`) so existing entries keep resolving; named ones
+/// append the name (`deepseek:work`) so several keys per provider can live
+/// side by side for different profiles.
+#[must_use]
+pub fn credential_slot(provider: &str, name: Option<&str>) -> String {
+    match name.map(str::trim).filter(|name| !name.is_empty()) {
+        Some(name) => format!("{provider}:{name}"),
+        None => provider.to_string(),
+    }
+}
+
 /// Map a canonical provider name to its environment variable, returning
 /// the value if non-empty.
 #[must_use]
@@ -649,6 +706,50 @@ mod tests {
         unsafe { std::env::remove_var(SECRET_BACKEND_ENV) };
     }
 
+    #[test]
+    fn preference_selects_file_backend_and_env_overrides_it() {
+        let _lock = env_lock();
+        clear_known_envs();
+
+        let secrets = Secrets::with_preference(Some("file"));
+        assert_eq!(secrets.backend_name(), "file-based (~/.deepseek/secrets/)");
+
+        // Safety: env mutation guarded by env_lock().
+        unsafe { std::env::set_var(SECRET_BACKEND_ENV, "file") };
+        let secrets = Secrets::with_preference(Some("keyring"));
+        assert_eq!(secrets.backend_name(), "file-based (~/.deepseek/secrets/)");
+        // Safety: env mutation guarded by env_lock().
+        unsafe { std::env::remove_var(SECRET_BACKEND_ENV) };
+    }
+
+    #[test]
+    fn named_credentials_use_separate_slots_and_fall_back_to_default() {
+        let _lock = env_lock();
+        clear_known_envs();
+
+        assert_eq!(credential_slot("deepseek", None), "deepseek");
+        assert_eq!(credential_slot("deepseek", Some("  ")), "deepseek");
+        assert_eq!(credential_slot("openai", Some("work")), "openai:work");
+
+        let store = Arc::new(InMemoryKeyringStore::new());
+        store.set("deepseek", "default-key").unwrap();
+        store.set("deepseek:work", "work-key").unwrap();
+        let secrets = Secrets::new(store);
+
+        assert_eq!(
+            secrets.resolve_named_with_source("deepseek", Some("work")),
+            Some(("work-key".to_string(), SecretSource::Keyring))
+        );
+        assert_eq!(
+            secrets.resolve_named_with_source("deepseek", Some("personal")),
+            Some(("default-key".to_string(), SecretSource::Keyring))
+        );
+        assert_eq!(
+            secrets.resolve_named_with_source("deepseek", None),
+            Some(("default-key".to_string(), SecretSource::Keyring))
+        );
+    }
+
     #[test]
     fn in_memory_store_round_trips() {
         let store = InMemoryKeyringStore::new();
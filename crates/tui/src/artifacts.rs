@@ -215,7 +215,8 @@ pub fn render_transcript_artifact_ref(reference: &TranscriptArtifactRef) -> Stri
          size:         {size}\n\
          path:         {path}\n\
          preview:      {preview}\n\
-         retrieve:     retrieve_tool_result ref={id}",
+         retrieve:     retrieve_tool_result ref={id}\n\
+         page:         read_result_page result_id={id}",
         tool = reference.tool_name,
         id = reference.artifact_id,
         tool_call_id = reference.tool_call_id,
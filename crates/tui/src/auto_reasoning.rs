@@ -36,6 +36,40 @@ pub fn select(is_subagent: bool, last_msg: &str) -> ReasoningEffort {
     ReasoningEffort::High
 }
 
+/// Follow-up requests longer than this are never treated as simple.
+const SIMPLE_FOLLOWUP_MAX_CHARS: usize = 200;
+
+/// Whether a follow-up request is mechanical enough (formatting, renames) to
+/// answer without thinking. Used by `[thinking] auto_off`; anything that also
+/// looks like debugging stays on the reasoning path.
+#[must_use]
+pub fn is_simple_followup(msg: &str) -> bool {
+    let trimmed = msg.trim();
+    if trimmed.is_empty() || trimmed.chars().count() > SIMPLE_FOLLOWUP_MAX_CHARS {
+        return false;
+    }
+    let lower = trimmed.to_ascii_lowercase();
+    SIMPLE_FOLLOWUP_KEYWORDS.iter().any(|kw| lower.contains(kw))
+        && !HIGH_EFFORT_KEYWORDS.iter().any(|kw| lower.contains(kw))
+}
+
+/// Keywords for mechanical follow-ups: formatting, renames, typo fixes.
+const SIMPLE_FOLLOWUP_KEYWORDS: &[&str] = &[
+    "rename",
+    "format",
+    "indent",
+    "whitespace",
+    "typo",
+    "spelling",
+    "capitalize",
+    "sort imports",
+    "\u{91cd}\u{547d}\u{540d}",         // 重命名
+    "\u{683c}\u{5f0f}\u{5316}",         // 格式化
+    "\u{7f29}\u{8fdb}",                 // 缩进
+    "\u{540d}\u{524d}\u{5909}\u{66f4}", // 名前変更
+    "\u{6574}\u{5f62}",                 // 整形
+];
+
 /// Keywords that bump `reasoning_effort` to `Max`. Latin terms are
 /// lowercase because the caller lowercases the message; CJK has no
 /// case so the literal form matches as-is. Covers the Chinese and
@@ -77,6 +111,19 @@ const LOW_EFFORT_KEYWORDS: &[&str] = &[
 mod tests {
     use super::*;
 
+    #[test]
+    fn simple_followups_are_short_mechanical_requests() {
+        assert!(is_simple_followup("rename `foo` to `bar`"));
+        assert!(is_simple_followup("Reformat that table"));
+        assert!(is_simple_followup(
+            "\u{683c}\u{5f0f}\u{5316}\u{4e00}\u{4e0b}"
+        )); // 格式化一下
+        assert!(!is_simple_followup("now add retries to the client"));
+        assert!(!is_simple_followup("rename it, but first debug the crash"));
+        assert!(!is_simple_followup(&format!("rename {}", "x".repeat(300))));
+        assert!(!is_simple_followup("   "));
+    }
+
     #[test]
     fn subagent_returns_low() {
         assert_eq!(select(true, "anything"), ReasoningEffort::Low);
@@ -0,0 +1,588 @@
+//! Model benchmark: run a suite of agentic tasks against several models.
+//!
+//! `deepseek bench --suite tasks.yaml --models deepseek-v4-pro,deepseek-v4-flash`
+//! runs every task once per model, each in a fresh temporary workspace, with
+//! real model calls. Tasks are scored with the same assertion shapes as eval
+//! scenarios (see [`crate::eval`]) plus `command` checks such as running the
+//! tests. A suite file looks like:
+//!
+//! ```yaml
+//! name: smoke
+//! tasks:
+//!   - name: fix-greeting
+//!     prompt: Fix the typo in src/lib.rs.
+//!     files:
+//!       src/lib.rs: "pub fn greet() -> &'static str { \"helo\" }\n"
+//!     assertions:
+//!       - file: src/lib.rs
+//!         contains: [hello]
+//!       - command: grep -q hello src/lib.rs
+//!       - contains: [typo]          # no `file`: checks the final response
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+use crate::eval::{ScenarioAssertion, check_assertion, workspace_path};
+use crate::self_report::{Confidence, SelfReport, SelfReportTally};
+
+/// Default per-task time limit.
+const DEFAULT_TASK_TIMEOUT_SECS: u64 = 600;
+
+/// A benchmark suite loaded from YAML.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BenchSuite {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub tasks: Vec<BenchTask>,
+}
+
+/// One task: seed files, a prompt, and the checks that score the result.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BenchTask {
+    pub name: String,
+    pub prompt: String,
+    /// Workspace-relative files written before the run.
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+    /// Shell command run in the workspace after seeding, before the model.
+    #[serde(default)]
+    pub setup: Option<String>,
+    #[serde(default)]
+    pub assertions: Vec<BenchAssertion>,
+    /// Time limit for the model run, in seconds.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+/// A check on the finished workspace. `command` passes when it exits 0;
+/// otherwise the fields mean what they mean in an eval scenario assertion.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BenchAssertion {
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub file: Option<String>,
+    #[serde(default)]
+    pub exists: Option<bool>,
+    #[serde(default)]
+    pub contains: Vec<String>,
+    #[serde(default)]
+    pub not_contains: Vec<String>,
+}
+
+impl BenchSuite {
+    /// Parse and validate a suite from YAML source.
+    pub fn from_yaml(source: &str) -> Result<Self> {
+        let suite: Self = serde_yaml::from_str(source).context("invalid bench suite YAML")?;
+        if suite.tasks.is_empty() {
+            return Err(anyhow!("bench suite has no tasks"));
+        }
+        let mut names = std::collections::BTreeSet::new();
+        for task in &suite.tasks {
+            if task.name.trim().is_empty() {
+                return Err(anyhow!("task name must not be empty"));
+            }
+            if !names.insert(task.name.as_str()) {
+                return Err(anyhow!("duplicate task name '{}'", task.name));
+            }
+            if task.prompt.trim().is_empty() {
+                return Err(anyhow!("task '{}' has an empty prompt", task.name));
+            }
+            if task.assertions.is_empty() {
+                return Err(anyhow!("task '{}' has no assertions", task.name));
+            }
+            for path in task.files.keys() {
+                workspace_path(Path::new("."), path)?;
+            }
+            for assertion in &task.assertions {
+                if assertion.command.is_some()
+                    && (assertion.file.is_some()
+                        || assertion.exists.is_some()
+                        || !assertion.contains.is_empty()
+                        || !assertion.not_contains.is_empty())
+                {
+                    return Err(anyhow!(
+                        "task '{}': a `command` assertion takes no other fields",
+                        task.name
+                    ));
+                }
+            }
+        }
+        Ok(suite)
+    }
+
+    /// Load a suite file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::from_yaml(&source).with_context(|| format!("in {}", path.display()))
+    }
+}
+
+impl BenchTask {
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs.unwrap_or(DEFAULT_TASK_TIMEOUT_SECS))
+    }
+}
+
+/// What one model run produced.
+#[derive(Debug, Clone, Default)]
+pub struct AgentOutcome {
+    pub final_response: String,
+    pub tool_calls: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Turn-level error, if the run did not complete normally.
+    pub error: Option<String>,
+}
+
+/// Result of one task for one model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskResult {
+    pub task: String,
+    pub model: String,
+    pub passed: bool,
+    /// Assertions that held.
+    pub score: usize,
+    pub total: usize,
+    pub duration_ms: u64,
+    pub tool_calls: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub failures: Vec<String>,
+    /// `<self_report>` footer of the final response (`self_report = true`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_report: Option<SelfReport>,
+}
+
+/// Totals for one model across the suite.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelSummary {
+    pub model: String,
+    pub passed: usize,
+    pub tasks: usize,
+    pub score: usize,
+    pub total: usize,
+    pub duration_ms: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub self_reports: SelfReportTally,
+    /// Failed tasks the model had rated `confidence: high`.
+    #[serde(default)]
+    pub confident_failures: usize,
+}
+
+/// Full benchmark report, printed with `--json` or written with `--report`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchReport {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suite: Option<String>,
+    pub models: Vec<ModelSummary>,
+    pub results: Vec<TaskResult>,
+}
+
+/// Run every task against every model. `run_agent` drives one model on one
+/// prompt in the given workspace; it is a parameter so the scoring can be
+/// exercised without network access.
+pub async fn run_suite<F, Fut>(
+    suite: &BenchSuite,
+    models: &[String],
+    mut run_agent: F,
+    mut on_result: impl FnMut(&TaskResult),
+) -> BenchReport
+where
+    F: FnMut(String, String, PathBuf) -> Fut,
+    Fut: Future<Output = Result<AgentOutcome>>,
+{
+    let mut results = Vec::new();
+    for model in models {
+        for task in &suite.tasks {
+            let started_at = Instant::now();
+            let result = match prepare_workspace(task) {
+                Ok(workspace) => {
+                    let run = tokio::time::timeout(
+                        task.timeout(),
+                        run_agent(
+                            model.clone(),
+                            task.prompt.clone(),
+                            workspace.path().to_path_buf(),
+                        ),
+                    )
+                    .await;
+                    let outcome = match run {
+                        Ok(Ok(outcome)) => outcome,
+                        Ok(Err(err)) => AgentOutcome {
+                            error: Some(format!("{err:#}")),
+                            ..AgentOutcome::default()
+                        },
+                        Err(_) => AgentOutcome {
+                            error: Some(format!("timed out after {}s", task.timeout().as_secs())),
+                            ..AgentOutcome::default()
+                        },
+                    };
+                    score_task(task, model, workspace.path(), outcome, started_at)
+                }
+                Err(err) => TaskResult {
+                    task: task.name.clone(),
+                    model: model.clone(),
+                    passed: false,
+                    score: 0,
+                    total: task.assertions.len(),
+                    duration_ms: 0,
+                    tool_calls: 0,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    error: Some(format!("{err:#}")),
+                    failures: Vec::new(),
+                    self_report: None,
+                },
+            };
+            on_result(&result);
+            results.push(result);
+        }
+    }
+    BenchReport {
+        suite: suite.name.clone(),
+        models: summarize(models, &results),
+        results,
+    }
+}
+
+fn prepare_workspace(task: &BenchTask) -> Result<tempfile::TempDir> {
+    let workspace = tempfile::Builder::new()
+        .prefix("deepseek-bench-")
+        .tempdir()
+        .context("failed to create bench workspace")?;
+    let root = workspace.path();
+    for (path, content) in &task.files {
+        let target = workspace_path(root, path)?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        fs::write(&target, content)
+            .with_context(|| format!("failed to write {}", target.display()))?;
+    }
+    if let Some(setup) = task.setup.as_deref() {
+        run_check_command(root, setup).map_err(|err| anyhow!("setup failed: {err}"))?;
+    }
+    Ok(workspace)
+}
+
+fn score_task(
+    task: &BenchTask,
+    model: &str,
+    root: &Path,
+    outcome: AgentOutcome,
+    started_at: Instant,
+) -> TaskResult {
+    // Assertions see the answer without its footer.
+    let (response, self_report) = crate::self_report::split(&outcome.final_response);
+    let mut failures = Vec::new();
+    let mut score = 0;
+    for assertion in &task.assertions {
+        let failed = match assertion.command.as_deref() {
+            Some(command) => run_check_command(root, command)
+                .err()
+                .map(|err| vec![format!("`{command}`: {err}")])
+                .unwrap_or_default(),
+            None => check_assertion(
+                root,
+                &ScenarioAssertion {
+                    file: assertion.file.clone(),
+                    exists: assertion.exists,
+                    contains: assertion.contains.clone(),
+                    not_contains: assertion.not_contains.clone(),
+                },
+                response,
+            ),
+        };
+        if failed.is_empty() {
+            score += 1;
+        }
+        failures.extend(failed);
+    }
+    TaskResult {
+        task: task.name.clone(),
+        model: model.to_string(),
+        passed: outcome.error.is_none() && score == task.assertions.len(),
+        score,
+        total: task.assertions.len(),
+        duration_ms: u64::try_from(started_at.elapsed().as_millis()).unwrap_or(u64::MAX),
+        tool_calls: outcome.tool_calls,
+        input_tokens: outcome.input_tokens,
+        output_tokens: outcome.output_tokens,
+        error: outcome.error,
+        failures,
+        self_report,
+    }
+}
+
+/// Run a check command in the workspace; `Err` carries the tail of its
+/// output when it exits non-zero.
+fn run_check_command(root: &Path, command: &str) -> Result<()> {
+    let (program, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let output = Command::new(program)
+        .args([flag, command])
+        .current_dir(root)
+        .output()
+        .with_context(|| format!("failed to run {command}"))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let mut text = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if text.is_empty() {
+        text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    }
+    let mut tail: Vec<&str> = text.lines().rev().take(3).collect();
+    tail.reverse();
+    if tail.is_empty() {
+        Err(anyhow!("exited with {}", output.status))
+    } else {
+        Err(anyhow!(
+            "exited with {}: {}",
+            output.status,
+            tail.join(" | ")
+        ))
+    }
+}
+
+fn summarize(models: &[String], results: &[TaskResult]) -> Vec<ModelSummary> {
+    models
+        .iter()
+        .map(|model| {
+            let rows = results.iter().filter(|result| &result.model == model);
+            let mut summary = ModelSummary {
+                model: model.clone(),
+                passed: 0,
+                tasks: 0,
+                score: 0,
+                total: 0,
+                duration_ms: 0,
+                input_tokens: 0,
+                output_tokens: 0,
+                self_reports: SelfReportTally::default(),
+                confident_failures: 0,
+            };
+            for row in rows {
+                summary.tasks += 1;
+                summary.passed += usize::from(row.passed);
+                summary.score += row.score;
+                summary.total += row.total;
+                summary.duration_ms += row.duration_ms;
+                summary.input_tokens += row.input_tokens;
+                summary.output_tokens += row.output_tokens;
+                if let Some(report) = &row.self_report {
+                    summary.self_reports.add(report);
+                    if !row.passed && report.confidence == Some(Confidence::High) {
+                        summary.confident_failures += 1;
+                    }
+                }
+            }
+            summary
+        })
+        .collect()
+}
+
+impl BenchReport {
+    /// A task-by-model table with a totals row.
+    #[must_use]
+    pub fn table(&self) -> String {
+        let cell = |result: &TaskResult| {
+            let status = if result.passed { "PASS" } else { "FAIL" };
+            format!("{status} {}/{}", result.score, result.total)
+        };
+        let mut tasks: Vec<&str> = Vec::new();
+        for result in &self.results {
+            if !tasks.contains(&result.task.as_str()) {
+                tasks.push(&result.task);
+            }
+        }
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut header = vec!["task".to_string()];
+        header.extend(self.models.iter().map(|summary| summary.model.clone()));
+        rows.push(header);
+        for task in &tasks {
+            let mut row = vec![(*task).to_string()];
+            for summary in &self.models {
+                row.push(
+                    self.results
+                        .iter()
+                        .find(|r| r.task == *task && r.model == summary.model)
+                        .map_or_else(|| "-".to_string(), cell),
+                );
+            }
+            rows.push(row);
+        }
+        let mut totals = vec!["passed".to_string()];
+        totals.extend(
+            self.models
+                .iter()
+                .map(|summary| format!("{}/{}", summary.passed, summary.tasks)),
+        );
+        rows.push(totals);
+        let mut tokens = vec!["tokens in/out".to_string()];
+        tokens.extend(
+            self.models
+                .iter()
+                .map(|summary| format!("{}/{}", summary.input_tokens, summary.output_tokens)),
+        );
+        rows.push(tokens);
+        let mut time = vec!["time".to_string()];
+        time.extend(
+            self.models
+                .iter()
+                .map(|summary| format!("{:.1}s", summary.duration_ms as f64 / 1000.0)),
+        );
+        rows.push(time);
+        if self
+            .models
+            .iter()
+            .any(|summary| summary.self_reports.reports > 0)
+        {
+            let mut confidence = vec!["high-conf fails".to_string()];
+            confidence.extend(self.models.iter().map(|summary| {
+                format!(
+                    "{}/{}",
+                    summary.confident_failures, summary.self_reports.high
+                )
+            }));
+            rows.push(confidence);
+        }
+
+        let columns = rows[0].len();
+        let widths: Vec<usize> = (0..columns)
+            .map(|col| {
+                rows.iter()
+                    .map(|row| row[col].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let mut out = String::new();
+        for (index, row) in rows.iter().enumerate() {
+            let line: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(text, width)| format!("{text:<width$}"))
+                .collect();
+            out.push_str(line.join("  ").trim_end());
+            out.push('\n');
+            if index == 0 || index == tasks.len() {
+                let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+                out.push_str(&rule.join("  "));
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUITE: &str = r#"
+name: smoke
+tasks:
+  - name: fix-greeting
+    prompt: Fix the typo.
+    files:
+      src/lib.rs: "helo\n"
+    assertions:
+      - file: src/lib.rs
+        contains: [hello]
+      - command: grep -q hello src/lib.rs
+      - contains: [Fixed]
+  - name: add-readme
+    prompt: Add a README.
+    assertions:
+      - file: README.md
+"#;
+
+    #[tokio::test]
+    async fn suite_scores_each_model_in_its_own_workspace() {
+        let suite = BenchSuite::from_yaml(SUITE).unwrap();
+        let models = vec!["good".to_string(), "lazy".to_string()];
+        let report = run_suite(
+            &suite,
+            &models,
+            |model, prompt, workspace| async move {
+                if model == "good" {
+                    if prompt.contains("typo") {
+                        fs::write(workspace.join("src/lib.rs"), "hello\n")?;
+                    } else {
+                        fs::write(workspace.join("README.md"), "# Demo\n")?;
+                    }
+                }
+                Ok(AgentOutcome {
+                    final_response: "Fixed it.\n<self_report>\nconfidence: high\n</self_report>"
+                        .to_string(),
+                    tool_calls: 1,
+                    input_tokens: 100,
+                    output_tokens: 10,
+                    error: None,
+                })
+            },
+            |_| {},
+        )
+        .await;
+
+        assert_eq!(report.results.len(), 4);
+        assert_eq!(report.models[0].passed, 2);
+        assert_eq!(report.models[1].passed, 0);
+        let lazy_fix = &report.results[2];
+        assert_eq!((lazy_fix.score, lazy_fix.total), (1, 3));
+        assert!(
+            lazy_fix
+                .failures
+                .iter()
+                .any(|f| f.contains("grep -q hello"))
+        );
+
+        let table = report.table();
+        let row = |name: &str| {
+            table
+                .lines()
+                .find(|line| line.starts_with(name))
+                .unwrap()
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        assert_eq!(row("fix-greeting"), "fix-greeting PASS 3/3 FAIL 1/3");
+        assert_eq!(row("passed"), "passed 2/2 0/2");
+        assert_eq!(report.models[1].self_reports.high, 2);
+        assert_eq!(report.models[1].confident_failures, 2);
+        assert_eq!(row("high-conf"), "high-conf fails 0/2 2/2");
+    }
+
+    #[test]
+    fn suite_validation_rejects_bad_tasks() {
+        let err = BenchSuite::from_yaml("tasks:\n  - name: a\n    prompt: x\n").unwrap_err();
+        assert!(format!("{err:#}").contains("no assertions"));
+        let err = BenchSuite::from_yaml(
+            "tasks:\n  - name: a\n    prompt: x\n    assertions:\n      - command: \"true\"\n        file: x\n",
+        )
+        .unwrap_err();
+        assert!(format!("{err:#}").contains("takes no other fields"));
+    }
+}
@@ -0,0 +1,540 @@
+//! `deepseek exec --gate`: run one agentic prompt as a CI gate.
+//!
+//! The prompt gets an instruction to end the reply with a findings report in
+//! the `review` tool's JSON schema. After the turn, the post-conditions in
+//! the `[gate]` table of config.toml decide the exit code:
+//!
+//! ```toml
+//! [gate]
+//! max_severity = "warning"   # highest finding that still passes
+//! required_checks = ["cargo test --locked", "cargo clippy -- -D warnings"]
+//! check_timeout_secs = 600
+//! ```
+//!
+//! `max_severity` is `none`, `info`, `warning` (the default), or `error`.
+//! Each required check is a shell command run in the workspace once the
+//! agent is done; it passes when it exits 0. The gate also fails when the
+//! turn does not complete or the reply has no findings report.
+//!
+//! Findings and failed checks print as annotations: GitHub Actions workflow
+//! commands (`::error file=src/lib.rs,line=12::...`) when `GITHUB_ACTIONS`
+//! is set, otherwise `path:line: severity: message` lines a problem matcher
+//! can pick up. A one-line verdict follows.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use serde::Deserialize;
+
+use crate::config::Config;
+use crate::core::engine::EngineHandle;
+use crate::exec_script::{SessionSnapshot, drive_turn};
+use crate::tools::review::{ReviewIssue, ReviewOutput};
+
+const DEFAULT_CHECK_TIMEOUT_SECS: u64 = 600;
+/// Lines of a failed check's output echoed under its annotation.
+const CHECK_OUTPUT_TAIL_LINES: usize = 20;
+
+/// Appended to the prompt so the reply ends in a report the gate can read.
+const REPORT_INSTRUCTIONS: &str = "\n\nWhen you are done, end your reply with a \
+fenced ```json block holding your findings in this schema:\n\
+{\"summary\": \"short overview\", \"issues\": [{\"severity\": \"error|warning|info\", \
+\"title\": \"issue title\", \"description\": \"details and impact\", \
+\"path\": \"relative/file/path or null\", \"line\": 123}]}\n\
+Use an empty issues list when nothing needs attention.";
+
+/// Severity levels, ordered from least to most serious.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GateSeverity {
+    /// Only meaningful as `max_severity`: any finding fails the gate.
+    None,
+    Info,
+    Warning,
+    Error,
+}
+
+impl GateSeverity {
+    fn of(issue: &ReviewIssue) -> Self {
+        match issue.severity.as_str() {
+            "error" => Self::Error,
+            "warning" => Self::Warning,
+            _ => Self::Info,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// `[gate]` table — post-conditions for `deepseek exec --gate`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GateConfig {
+    /// Highest finding severity that still passes. Defaults to `warning`.
+    #[serde(default)]
+    pub max_severity: Option<GateSeverity>,
+    /// Shell commands that must exit 0 in the workspace after the turn.
+    #[serde(default)]
+    pub required_checks: Vec<String>,
+    /// Seconds each required check may run. Defaults to 600.
+    #[serde(default)]
+    pub check_timeout_secs: Option<u64>,
+}
+
+impl GateConfig {
+    #[must_use]
+    pub fn max_severity(&self) -> GateSeverity {
+        self.max_severity.unwrap_or(GateSeverity::Warning)
+    }
+
+    fn check_timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.check_timeout_secs
+                .unwrap_or(DEFAULT_CHECK_TIMEOUT_SECS)
+                .max(1),
+        )
+    }
+}
+
+/// Outcome of one required check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    pub command: String,
+    /// Why the check failed; `None` when it passed.
+    pub failure: Option<String>,
+    /// Tail of the check's combined output, kept for failures.
+    pub output: String,
+}
+
+/// Everything the gate decided, ready to print.
+#[derive(Debug, Clone)]
+pub struct GateReport {
+    pub max_severity: GateSeverity,
+    pub summary: String,
+    pub findings: Vec<ReviewIssue>,
+    pub checks: Vec<CheckResult>,
+    /// Reasons the gate failed outright (turn failed, no report).
+    pub errors: Vec<String>,
+}
+
+impl GateReport {
+    /// Findings above the allowed severity.
+    pub fn blocking(&self) -> impl Iterator<Item = &ReviewIssue> {
+        self.findings
+            .iter()
+            .filter(|issue| GateSeverity::of(issue) > self.max_severity)
+    }
+
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.errors.is_empty()
+            && self.blocking().next().is_none()
+            && self.checks.iter().all(|check| check.failure.is_none())
+    }
+
+    /// Annotation lines plus the verdict, in `style`.
+    #[must_use]
+    pub fn render(&self, style: AnnotationStyle) -> Vec<String> {
+        let mut lines = Vec::new();
+        for error in &self.errors {
+            lines.push(style.annotation("error", None, None, "Gate", error));
+        }
+        for issue in &self.findings {
+            let severity = GateSeverity::of(issue);
+            // Findings the gate tolerates are still reported, one level down.
+            let level = if severity > self.max_severity {
+                "error"
+            } else if severity == GateSeverity::Error {
+                "warning"
+            } else {
+                "notice"
+            };
+            let message = if issue.description.is_empty() {
+                issue.title.clone()
+            } else {
+                format!("{}: {}", issue.title, issue.description)
+            };
+            lines.push(style.annotation(
+                level,
+                issue.path.as_deref(),
+                issue.line,
+                &issue.title,
+                &message,
+            ));
+        }
+        for check in &self.checks {
+            if let Some(failure) = &check.failure {
+                let mut message = format!("`{}` {failure}", check.command);
+                if !check.output.is_empty() {
+                    message.push('\n');
+                    message.push_str(&check.output);
+                }
+                lines.push(style.annotation(
+                    "error",
+                    None,
+                    None,
+                    "Required check failed",
+                    &message,
+                ));
+            }
+        }
+
+        let blocking = self.blocking().count();
+        let failed_checks = self
+            .checks
+            .iter()
+            .filter(|check| check.failure.is_some())
+            .count();
+        lines.push(format!(
+            "gate {}: {} finding(s), {blocking} above {}; {} of {} required check(s) passed",
+            if self.passed() { "passed" } else { "failed" },
+            self.findings.len(),
+            self.max_severity.label(),
+            self.checks.len() - failed_checks,
+            self.checks.len(),
+        ));
+        if !self.summary.is_empty() {
+            lines.push(self.summary.clone());
+        }
+        lines
+    }
+}
+
+/// How annotations are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationStyle {
+    /// GitHub Actions workflow commands.
+    GithubActions,
+    /// `path:line: level: message`, for problem matchers and humans.
+    Plain,
+}
+
+impl AnnotationStyle {
+    #[must_use]
+    pub fn detect() -> Self {
+        if std::env::var_os("GITHUB_ACTIONS").is_some_and(|value| value == "true") {
+            Self::GithubActions
+        } else {
+            Self::Plain
+        }
+    }
+
+    fn annotation(
+        self,
+        level: &str,
+        path: Option<&str>,
+        line: Option<u32>,
+        title: &str,
+        message: &str,
+    ) -> String {
+        match self {
+            Self::GithubActions => {
+                let mut properties = Vec::new();
+                if let Some(path) = path {
+                    properties.push(format!("file={}", escape_property(path)));
+                    if let Some(line) = line {
+                        properties.push(format!("line={line}"));
+                    }
+                }
+                if !title.is_empty() {
+                    properties.push(format!("title={}", escape_property(title)));
+                }
+                let properties = if properties.is_empty() {
+                    String::new()
+                } else {
+                    format!(" {}", properties.join(","))
+                };
+                format!("::{level}{properties}::{}", escape_data(message))
+            }
+            Self::Plain => {
+                let location = match (path, line) {
+                    (Some(path), Some(line)) => format!("{path}:{line}: "),
+                    (Some(path), None) => format!("{path}: "),
+                    _ => String::new(),
+                };
+                let message = message.replace('\n', "\n    ");
+                format!("{location}{level}: {message}")
+            }
+        }
+    }
+}
+
+/// Workflow command message escaping.
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Workflow command property escaping, which also covers the separators.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// The findings report at the end of `reply`: the last fenced `json` block,
+/// else any review JSON in the reply.
+#[must_use]
+pub fn parse_report(reply: &str) -> Option<ReviewOutput> {
+    let fenced = reply.rfind("```json").and_then(|start| {
+        let body = &reply[start + "```json".len()..];
+        body.find("```").map(|end| &body[..end])
+    });
+    fenced
+        .and_then(ReviewOutput::parse)
+        .or_else(|| ReviewOutput::parse(reply))
+}
+
+/// Judge a finished turn. `status` is the turn status and `reply` the
+/// assistant's text; checks are added by the caller.
+#[must_use]
+pub fn evaluate(config: &GateConfig, status: &str, error: Option<&str>, reply: &str) -> GateReport {
+    let mut report = GateReport {
+        max_severity: config.max_severity(),
+        summary: String::new(),
+        findings: Vec::new(),
+        checks: Vec::new(),
+        errors: Vec::new(),
+    };
+    if status != "completed" {
+        let detail = error.map(|error| format!(": {error}")).unwrap_or_default();
+        report.errors.push(format!("agent turn {status}{detail}"));
+    }
+    match parse_report(reply) {
+        Some(output) => {
+            report.summary = output.summary;
+            report.findings = output.issues;
+        }
+        None if status == "completed" => report
+            .errors
+            .push("agent reply has no findings report".to_string()),
+        None => {}
+    }
+    report
+}
+
+fn shell_command(command: &str) -> tokio::process::Command {
+    #[cfg(windows)]
+    {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    }
+    #[cfg(not(windows))]
+    {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+}
+
+/// The last [`CHECK_OUTPUT_TAIL_LINES`] non-blank lines of `output`.
+fn output_tail(output: &str) -> String {
+    let lines: Vec<&str> = output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    lines[lines.len().saturating_sub(CHECK_OUTPUT_TAIL_LINES)..].join("\n")
+}
+
+/// Run one required check in `workspace`.
+async fn run_check(command: &str, workspace: &Path, timeout: Duration) -> CheckResult {
+    let mut cmd = shell_command(command);
+    cmd.current_dir(workspace)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let (failure, output) = match tokio::time::timeout(timeout, cmd.output()).await {
+        Err(_) => (
+            Some(format!("timed out after {}s", timeout.as_secs())),
+            String::new(),
+        ),
+        Ok(Err(err)) => (Some(format!("could not start: {err}")), String::new()),
+        Ok(Ok(output)) if output.status.success() => (None, String::new()),
+        Ok(Ok(output)) => {
+            let failure = match output.status.code() {
+                Some(code) => format!("exited with {code}"),
+                None => "was terminated by a signal".to_string(),
+            };
+            let combined = format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+            (Some(failure), output_tail(&combined))
+        }
+    };
+    CheckResult {
+        command: command.to_string(),
+        failure,
+        output,
+    }
+}
+
+/// Options for [`run`].
+pub struct GateOptions {
+    pub prompt: String,
+    pub model: String,
+    pub workspace: PathBuf,
+    pub max_subagents: usize,
+    pub auto_approve: bool,
+}
+
+/// Run the prompt, evaluate the `[gate]` post-conditions, and fail when
+/// they are not met.
+pub async fn run(config: &Config, options: GateOptions) -> Result<()> {
+    let GateOptions {
+        prompt,
+        model,
+        workspace,
+        max_subagents,
+        auto_approve,
+    } = options;
+    let gate = config.gate.clone().unwrap_or_default();
+
+    let route = crate::resolve_cli_auto_route(config, &model, &prompt).await;
+    let engine: EngineHandle = crate::core::engine::spawn_engine(
+        crate::exec_engine_config(
+            config,
+            &route.model,
+            &workspace,
+            max_subagents,
+            auto_approve,
+            auto_approve,
+        ),
+        config,
+    );
+    let prompt = format!("{prompt}{REPORT_INSTRUCTIONS}");
+    engine
+        .send(crate::exec_send_message(
+            config,
+            &prompt,
+            route,
+            auto_approve,
+            auto_approve,
+        ))
+        .await?;
+    let mut session = SessionSnapshot::default();
+    let outcome = drive_turn(&engine, auto_approve, &mut session).await?;
+    let _ = engine.send(crate::core::ops::Op::Shutdown).await;
+    let total_tokens = u64::from(outcome.input_tokens) + u64::from(outcome.output_tokens);
+    if let Some(id) = session.persist(total_tokens) {
+        eprintln!("session: {id}");
+    }
+
+    let mut report = evaluate(
+        &gate,
+        &outcome.status,
+        outcome.error.as_deref(),
+        &outcome.output,
+    );
+    for command in &gate.required_checks {
+        report
+            .checks
+            .push(run_check(command, &workspace, gate.check_timeout()).await);
+    }
+
+    for line in report.render(AnnotationStyle::detect()) {
+        println!("{line}");
+    }
+    if !report.passed() {
+        bail!("gate failed");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPLY: &str = "I looked at `src/auth.rs` and `{ token }` handling.\n\n```json\n{\"summary\": \"One injection risk\", \"issues\": [\n  {\"severity\": \"high\", \"title\": \"SQL injection\", \"description\": \"user input, unescaped\", \"path\": \"src/db.rs\", \"line\": 42},\n  {\"severity\": \"info\", \"title\": \"Naming\", \"description\": \"\", \"path\": null, \"line\": null}\n]}\n```\n";
+
+    #[test]
+    fn report_comes_from_the_last_json_fence_and_severity_sets_the_verdict() {
+        let report = evaluate(&GateConfig::default(), "completed", None, REPLY);
+        assert!(report.errors.is_empty());
+        assert_eq!(report.summary, "One injection risk");
+        assert_eq!(report.findings.len(), 2);
+        assert_eq!(report.blocking().count(), 1);
+        assert!(!report.passed());
+
+        let lenient = GateConfig {
+            max_severity: Some(GateSeverity::Error),
+            ..GateConfig::default()
+        };
+        assert!(evaluate(&lenient, "completed", None, REPLY).passed());
+        let strict = GateConfig {
+            max_severity: Some(GateSeverity::None),
+            ..GateConfig::default()
+        };
+        assert_eq!(
+            evaluate(&strict, "completed", None, REPLY)
+                .blocking()
+                .count(),
+            2
+        );
+
+        let missing = evaluate(&lenient, "completed", None, "Looks fine to me.");
+        assert_eq!(missing.errors, ["agent reply has no findings report"]);
+        let failed = evaluate(&lenient, "failed", Some("rate limited"), "");
+        assert_eq!(failed.errors, ["agent turn failed: rate limited"]);
+        assert!(toml::from_str::<GateConfig>("max_severity = \"critical\"").is_err());
+    }
+
+    #[test]
+    fn annotations_render_as_workflow_commands_or_plain_lines() {
+        let mut report = evaluate(&GateConfig::default(), "completed", None, REPLY);
+        report.checks.push(CheckResult {
+            command: "cargo test".to_string(),
+            failure: Some("exited with 101".to_string()),
+            output: "test a ... FAILED".to_string(),
+        });
+
+        let github = report.render(AnnotationStyle::GithubActions);
+        assert_eq!(
+            github[0],
+            "::error file=src/db.rs,line=42,title=SQL injection::SQL injection: user input, unescaped"
+        );
+        assert_eq!(github[1], "::notice title=Naming::Naming");
+        assert_eq!(
+            github[2],
+            "::error title=Required check failed::`cargo test` exited with 101%0Atest a ... FAILED"
+        );
+        assert_eq!(
+            github[3],
+            "gate failed: 2 finding(s), 1 above warning; 0 of 1 required check(s) passed"
+        );
+
+        let plain = report.render(AnnotationStyle::Plain);
+        assert_eq!(
+            plain[0],
+            "src/db.rs:42: error: SQL injection: user input, unescaped"
+        );
+        assert_eq!(
+            plain[2],
+            "error: `cargo test` exited with 101\n    test a ... FAILED"
+        );
+        assert_eq!(escape_property("a:b,c%"), "a%3Ab%2Cc%25");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn required_checks_pass_on_exit_zero_and_keep_the_output_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        let timeout = Duration::from_secs(10);
+        let ok = run_check("true", dir.path(), timeout).await;
+        assert_eq!(ok.failure, None);
+        let failed = run_check("echo one; echo two >&2; exit 3", dir.path(), timeout).await;
+        assert_eq!(failed.failure.as_deref(), Some("exited with 3"));
+        assert_eq!(failed.output, "one\ntwo");
+    }
+}
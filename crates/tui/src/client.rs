@@ -628,6 +628,19 @@ impl DeepSeekClient {
         Ok(translated)
     }
 
+    /// Forward an OpenAI-style `chat/completions` body to the provider as-is.
+    ///
+    /// Used by the runtime API's plain-proxy mode; the caller relays the
+    /// response (JSON or SSE) without interpreting it.
+    pub async fn forward_chat_completion(
+        &self,
+        body: &serde_json::Value,
+    ) -> Result<reqwest::Response> {
+        let url = api_url(&self.base_url, "chat/completions");
+        self.send_with_retry(|| self.http_client.post(&url).json(body))
+            .await
+    }
+
     /// List available models from the provider.
     pub async fn list_models(&self) -> Result<Vec<AvailableModel>> {
         let url = api_url(&self.base_url, "models");
@@ -1048,6 +1061,15 @@ pub(super) fn parse_usage(usage: Option<&Value>) -> Usage {
 }
 
 impl DeepSeekClient {
+    /// Whether the provider serves the `/beta/completions` FIM endpoint.
+    #[must_use]
+    pub fn supports_fim(&self) -> bool {
+        matches!(
+            self.api_provider,
+            ApiProvider::Deepseek | ApiProvider::DeepseekCN
+        )
+    }
+
     /// Call the DeepSeek `/beta/completions` FIM endpoint.
     pub async fn fim_completion(
         &self,
@@ -1094,11 +1116,16 @@ pub(crate) fn build_cache_warmup_request(request: &MessageRequest) -> MessageReq
     chat::build_cache_warmup_request(request)
 }
 
+/// Whether `model` runs in thinking mode and returns reasoning content.
+pub(crate) fn model_supports_thinking(model: &str) -> bool {
+    chat::requires_reasoning_content(model)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::client::chat::{
-        build_chat_messages, build_chat_messages_for_request,
+        apply_cache_breakpoints, build_chat_messages, build_chat_messages_for_request,
         build_chat_messages_for_request_and_provider, count_reasoning_replay_chars,
         parse_chat_message, parse_sse_chunk, sanitize_thinking_mode_messages, tool_to_chat,
         tool_to_chat_for_base_url,
@@ -1860,6 +1887,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cache_breakpoint_marks_stable_system_prefix_for_routers_only() {
+        let system = "Base rules\n\n## Environment\nlinux\n\n## Current Session Goal\nship it";
+        let body = json!({
+            "messages": [
+                { "role": "system", "content": system },
+                { "role": "user", "content": "hi" },
+            ]
+        });
+
+        let mut deepseek = body.clone();
+        apply_cache_breakpoints(&mut deepseek, ApiProvider::Deepseek);
+        assert_eq!(deepseek, body);
+
+        let mut routed = body.clone();
+        apply_cache_breakpoints(&mut routed, ApiProvider::Openrouter);
+        let parts = routed
+            .pointer("/messages/0/content")
+            .and_then(Value::as_array)
+            .expect("system content split into parts");
+        assert_eq!(parts.len(), 2);
+        assert_eq!(
+            parts[0]["text"].as_str(),
+            Some("Base rules\n\n## Environment\nlinux\n\n")
+        );
+        assert_eq!(
+            parts[0]["cache_control"]["type"].as_str(),
+            Some("ephemeral")
+        );
+        assert_eq!(
+            parts[1]["text"].as_str(),
+            Some("## Current Session Goal\nship it")
+        );
+        assert!(parts[1].get("cache_control").is_none());
+        assert_eq!(routed["messages"][1], body["messages"][1]);
+    }
+
     #[test]
     fn reasoning_effort_uses_deepseek_top_level_thinking_parameter() {
         let mut body = json!({});
@@ -107,6 +107,7 @@ impl DeepSeekClient {
             request.reasoning_effort.as_deref(),
             self.api_provider,
         );
+        apply_cache_breakpoints(&mut body, self.api_provider);
 
         let url = api_url(&self.base_url, "chat/completions");
         let open_timeout = stream_open_timeout();
@@ -181,6 +182,7 @@ impl DeepSeekClient {
             request.reasoning_effort.as_deref(),
             self.api_provider,
         );
+        apply_cache_breakpoints(&mut body, self.api_provider);
 
         // Bulletproof final sanitizer: walk the wire payload and force
         // `reasoning_content` onto any assistant message that has tool_calls
@@ -700,7 +702,8 @@ fn turn_meta_inspection_for_message(message: &Value) -> Option<TurnMetaInspectio
     })
 }
 
-fn split_system_layers(content: &str) -> Vec<(String, PromptLayerStability, &str)> {
+/// Byte offsets where each known system-prompt layer starts, in order.
+fn system_layer_starts(content: &str) -> Vec<(usize, &'static str)> {
     let markers = [
         ("Project context", "<project_instructions"),
         ("Project context pack", "## Project Context Pack"),
@@ -714,12 +717,16 @@ fn split_system_layers(content: &str) -> Vec<(String, PromptLayerStability, &str
         ("Previous session relay", "## Previous Session Relay"),
     ];
 
-    let mut starts: Vec<(usize, &str)> = markers
+    let mut starts: Vec<(usize, &'static str)> = markers
         .iter()
         .filter_map(|(name, marker)| content.find(marker).map(|idx| (idx, *name)))
         .collect();
     starts.sort_by_key(|(idx, _)| *idx);
+    starts
+}
 
+fn split_system_layers(content: &str) -> Vec<(String, PromptLayerStability, &str)> {
+    let starts = system_layer_starts(content);
     let mut layers = Vec::new();
     let first_marker = starts.first().map_or(content.len(), |(idx, _)| *idx);
     if first_marker > 0 {
@@ -765,6 +772,57 @@ fn is_static_base_layer(name: &str) -> bool {
     )
 }
 
+/// End of the leading run of static layers (global prefix, environment,
+/// AGENTS.md project context, skills, ...). Everything before this offset
+/// is identical across turns and safe to mark as a cache breakpoint.
+fn stable_system_prefix_end(content: &str) -> usize {
+    system_layer_starts(content)
+        .into_iter()
+        .find(|(_, name)| !is_static_base_layer(name))
+        .map_or(content.len(), |(idx, _)| idx)
+}
+
+/// Whether the provider honors explicit `cache_control` breakpoints on
+/// chat messages. DeepSeek's own endpoints cache matching prefixes
+/// automatically and expect plain-string system content, so breakpoints
+/// are only sent to routers that forward them to caching backends.
+fn provider_honors_cache_breakpoints(provider: ApiProvider) -> bool {
+    matches!(provider, ApiProvider::Openrouter)
+}
+
+/// Mark the stable system-prompt prefix as a cache breakpoint. Tool schemas
+/// precede the system prompt in the cached prefix, so they are covered by
+/// the same breakpoint.
+pub(super) fn apply_cache_breakpoints(body: &mut Value, provider: ApiProvider) {
+    if !provider_honors_cache_breakpoints(provider) {
+        return;
+    }
+    let Some(system) = body
+        .get_mut("messages")
+        .and_then(Value::as_array_mut)
+        .and_then(|messages| messages.first_mut())
+        .filter(|message| message.get("role").and_then(Value::as_str) == Some("system"))
+    else {
+        return;
+    };
+    let Some(content) = system.get("content").and_then(Value::as_str) else {
+        return;
+    };
+    let (stable, rest) = content.split_at(stable_system_prefix_end(content));
+    if stable.trim().is_empty() {
+        return;
+    }
+    let mut parts = vec![json!({
+        "type": "text",
+        "text": stable,
+        "cache_control": { "type": "ephemeral" },
+    })];
+    if !rest.trim().is_empty() {
+        parts.push(json!({ "type": "text", "text": rest }));
+    }
+    system["content"] = Value::Array(parts);
+}
+
 fn stable_system_prompt(system: Option<&SystemPrompt>) -> Option<SystemPrompt> {
     let instructions = system_to_instructions(system.cloned())?;
     let stable = split_system_layers(&instructions)
@@ -1622,7 +1680,7 @@ fn log_thinking_mode_violations(body: &Value) {
     }
 }
 
-fn requires_reasoning_content(model: &str) -> bool {
+pub(super) fn requires_reasoning_content(model: &str) -> bool {
     let lower = model.to_lowercase();
     // V4-family direct model IDs.
     lower.contains("deepseek-v4")
@@ -0,0 +1,331 @@
+//! Per-project command aliases such as `/build`, `/test`, and `/deploy`.
+//!
+//! Aliases come from `<workspace>/.deepseek/config.toml`:
+//!
+//! ```toml
+//! [aliases]
+//! build = "cargo build --workspace"
+//! test = { run = "cargo test --workspace", description = "Run the test suite" }
+//! notes = { prompt = "Draft release notes for the changes since $1." }
+//! ```
+//!
+//! or from an `## Aliases` section in `<workspace>/AGENTS.md`:
+//!
+//! ```markdown
+//! ## Aliases
+//! - `/build`: `cargo build --workspace`
+//! - `/triage`: Read the newest CI failure log and summarize the cause.
+//! ```
+//!
+//! A plain string, a `run` key, or a backticked AGENTS.md body is a shell
+//! command; anything else is a prompt template with the same `$1` /
+//! `$ARGUMENTS` placeholders as user commands. When both files name the
+//! same alias, the config file wins. The TUI registers aliases as slash
+//! commands (never shadowing a built-in), and the engine offers them to the
+//! model as tools of the same name.
+
+use std::path::Path;
+
+/// What an alias does when invoked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AliasAction {
+    /// Run a shell command in the workspace.
+    Shell(String),
+    /// Send a prompt template as a user message.
+    Prompt(String),
+}
+
+/// One named alias.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandAlias {
+    pub name: String,
+    pub action: AliasAction,
+    pub description: Option<String>,
+}
+
+impl CommandAlias {
+    /// One-line description for the slash menu, help, and tool catalog.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        if let Some(description) = &self.description {
+            return description.clone();
+        }
+        match &self.action {
+            AliasAction::Shell(command) => format!("Runs `{command}`"),
+            AliasAction::Prompt(prompt) => {
+                let first = prompt.lines().next().unwrap_or_default().trim();
+                format!(
+                    "Prompt: {}",
+                    crate::utils::truncate_with_ellipsis(first, 60, "…")
+                )
+            }
+        }
+    }
+
+    /// The shell command or prompt with `args` substituted. Shell commands
+    /// without placeholders get the arguments appended. Arguments to a shell
+    /// command are quoted word by word, so they can never add a separator,
+    /// redirection, or substitution to it.
+    #[must_use]
+    pub fn expand(&self, args: &str) -> String {
+        let args = args.trim();
+        match &self.action {
+            AliasAction::Shell(command) => {
+                let words = quoted_words(args);
+                if has_placeholders(command) {
+                    substitute(command, &words.join(" "), &words)
+                } else if words.is_empty() {
+                    command.clone()
+                } else {
+                    format!("{command} {}", words.join(" "))
+                }
+            }
+            AliasAction::Prompt(template) => apply_template(template, args),
+        }
+    }
+}
+
+/// Substitute `$1`, `$2`, … and `$ARGUMENTS` placeholders.
+#[must_use]
+pub fn apply_template(template: &str, args: &str) -> String {
+    let positional: Vec<&str> = args.split_whitespace().collect();
+    substitute(template, args, &positional)
+}
+
+fn substitute(template: &str, all: &str, positional: &[impl AsRef<str>]) -> String {
+    let mut result = template.replace("$ARGUMENTS", all);
+    for (i, arg) in positional.iter().enumerate() {
+        result = result.replace(&format!("${}", i + 1), arg.as_ref());
+    }
+    result
+}
+
+/// Split `args` shell-style (falling back to whitespace when the quoting is
+/// unbalanced) and quote each word for the shell.
+fn quoted_words(args: &str) -> Vec<String> {
+    let words =
+        shlex::split(args).unwrap_or_else(|| args.split_whitespace().map(str::to_string).collect());
+    words
+        .into_iter()
+        .filter_map(|word| {
+            shlex::try_quote(&word.replace('\0', ""))
+                .ok()
+                .map(std::borrow::Cow::into_owned)
+        })
+        .collect()
+}
+
+fn has_placeholders(template: &str) -> bool {
+    template.contains("$ARGUMENTS") || (1..=9).any(|i| template.contains(&format!("${i}")))
+}
+
+/// Load the workspace's aliases, sorted by name. Unreadable or malformed
+/// sources are skipped.
+#[must_use]
+pub fn load(workspace: &Path) -> Vec<CommandAlias> {
+    let mut aliases = std::fs::read_to_string(workspace.join(".deepseek").join("config.toml"))
+        .map(|raw| from_config(&raw))
+        .unwrap_or_default();
+    if let Ok(doc) = std::fs::read_to_string(workspace.join("AGENTS.md")) {
+        for alias in from_agents_md(&doc) {
+            if !aliases.iter().any(|existing| existing.name == alias.name) {
+                aliases.push(alias);
+            }
+        }
+    }
+    aliases.sort_by(|a, b| a.name.cmp(&b.name));
+    aliases
+}
+
+/// Look up one alias by name.
+#[must_use]
+pub fn find(workspace: &Path, name: &str) -> Option<CommandAlias> {
+    load(workspace).into_iter().find(|alias| alias.name == name)
+}
+
+fn from_config(raw: &str) -> Vec<CommandAlias> {
+    let Ok(doc) = toml::from_str::<toml::Value>(raw) else {
+        return Vec::new();
+    };
+    let Some(table) = doc.get("aliases").and_then(toml::Value::as_table) else {
+        return Vec::new();
+    };
+    table
+        .iter()
+        .filter_map(|(name, value)| {
+            let name = normalize_name(name)?;
+            let (action, description) = match value {
+                toml::Value::String(command) => (AliasAction::Shell(command.clone()), None),
+                toml::Value::Table(entry) => {
+                    let text = |key: &str| entry.get(key).and_then(toml::Value::as_str);
+                    let action = match (text("run"), text("prompt")) {
+                        (Some(command), None) => AliasAction::Shell(command.to_string()),
+                        (None, Some(prompt)) => AliasAction::Prompt(prompt.to_string()),
+                        _ => return None,
+                    };
+                    (action, text("description").map(str::to_string))
+                }
+                _ => return None,
+            };
+            Some(CommandAlias {
+                name,
+                action,
+                description,
+            })
+        })
+        .collect()
+}
+
+fn from_agents_md(doc: &str) -> Vec<CommandAlias> {
+    let mut aliases = Vec::new();
+    let mut in_section = false;
+    for line in doc.lines() {
+        let trimmed = line.trim();
+        if let Some(heading) = trimmed.strip_prefix('#') {
+            in_section = heading
+                .trim_start_matches('#')
+                .trim()
+                .eq_ignore_ascii_case("aliases");
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        else {
+            continue;
+        };
+        let Some((name, body)) = item.split_once(':') else {
+            continue;
+        };
+        let Some(name) = name
+            .trim()
+            .trim_matches('`')
+            .strip_prefix('/')
+            .and_then(normalize_name)
+        else {
+            continue;
+        };
+        let body = body.trim();
+        let action = match body
+            .strip_prefix('`')
+            .and_then(|rest| rest.strip_suffix('`'))
+        {
+            Some(command) if !command.is_empty() && !command.contains('`') => {
+                AliasAction::Shell(command.to_string())
+            }
+            _ if body.is_empty() => continue,
+            _ => AliasAction::Prompt(body.to_string()),
+        };
+        aliases.push(CommandAlias {
+            name,
+            action,
+            description: None,
+        });
+    }
+    aliases
+}
+
+/// Alias names are lowercase ASCII letters, digits, `-`, and `_`, so they
+/// are valid as both slash commands and tool names.
+fn normalize_name(raw: &str) -> Option<String> {
+    let name = raw.trim().to_ascii_lowercase();
+    let valid = !name.is_empty()
+        && name.len() <= 48
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn config_aliases_win_over_agents_md_and_both_forms_parse() {
+        let tmp = TempDir::new().unwrap();
+        let ws = tmp.path();
+        std::fs::create_dir_all(ws.join(".deepseek")).unwrap();
+        std::fs::write(
+            ws.join(".deepseek/config.toml"),
+            r#"
+model = "deepseek-v4-pro"
+
+[aliases]
+build = "cargo build --workspace"
+test = { run = "cargo test --workspace", description = "Run the test suite" }
+notes = { prompt = "Draft release notes since $1." }
+"Bad Name" = "ignored"
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            ws.join("AGENTS.md"),
+            "# Project\n\n## Aliases\n- `/build`: `make`\n- /triage: Summarize the newest CI failure.\n\n## Style\n- /nope: not an alias\n",
+        )
+        .unwrap();
+
+        let aliases = load(ws);
+        let names: Vec<&str> = aliases.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["build", "notes", "test", "triage"]);
+        assert_eq!(
+            aliases[0].action,
+            AliasAction::Shell("cargo build --workspace".to_string())
+        );
+        assert_eq!(aliases[2].summary(), "Run the test suite");
+        assert_eq!(
+            aliases[3].action,
+            AliasAction::Prompt("Summarize the newest CI failure.".to_string())
+        );
+    }
+
+    #[test]
+    fn expand_appends_or_substitutes_arguments() {
+        let shell = CommandAlias {
+            name: "test".to_string(),
+            action: AliasAction::Shell("cargo test".to_string()),
+            description: None,
+        };
+        assert_eq!(shell.expand(""), "cargo test");
+        assert_eq!(shell.expand(" -p core "), "cargo test -p core");
+
+        let prompt = CommandAlias {
+            name: "notes".to_string(),
+            action: AliasAction::Prompt("Release notes since $1 ($ARGUMENTS)".to_string()),
+            description: None,
+        };
+        assert_eq!(
+            prompt.expand("v1.2 draft"),
+            "Release notes since v1.2 (v1.2 draft)"
+        );
+    }
+
+    #[test]
+    fn shell_arguments_are_quoted_so_they_cannot_chain_commands() {
+        let shell = CommandAlias {
+            name: "test".to_string(),
+            action: AliasAction::Shell("cargo test".to_string()),
+            description: None,
+        };
+        assert_eq!(shell.expand("; rm -rf x"), "cargo test ';' rm -rf x");
+        assert_eq!(
+            shell.expand("$(touch pwned) `id` | tee out"),
+            "cargo test '$(touch' 'pwned)' '`id`' '|' tee out"
+        );
+        assert_eq!(shell.expand("\"two words\""), "cargo test 'two words'");
+
+        let template = CommandAlias {
+            name: "deploy".to_string(),
+            action: AliasAction::Shell("./deploy.sh --env $1 -- $ARGUMENTS".to_string()),
+            description: None,
+        };
+        assert_eq!(
+            template.expand("prod && curl evil"),
+            "./deploy.sh --env prod -- prod '&&' curl evil"
+        );
+    }
+}
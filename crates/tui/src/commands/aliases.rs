@@ -0,0 +1,112 @@
+//! Project command aliases as slash commands. See [`crate::command_aliases`]
+//! for where they are declared.
+
+use std::path::Path;
+
+use crate::command_aliases::{self, AliasAction};
+use crate::tui::app::{App, AppAction, ShellJobAction};
+
+use super::{CommandResult, get_command_info};
+
+/// Run the alias named `command`, if the workspace declares one. Shell
+/// aliases start a background job; prompt aliases send their expanded
+/// template as a message.
+pub fn try_dispatch_alias(app: &mut App, command: &str, args: &str) -> Option<CommandResult> {
+    let alias = command_aliases::find(&app.workspace, command)?;
+    let expanded = alias.expand(args);
+    Some(match alias.action {
+        AliasAction::Shell(_) if app.trust_level.restricts_tools() => {
+            CommandResult::error(format!(
+                "/{} runs a shell command, which the `{}` trust level does not allow. Use /trust to change it.",
+                alias.name,
+                app.trust_level.as_str()
+            ))
+        }
+        AliasAction::Shell(_) => CommandResult::action(AppAction::ShellJob(ShellJobAction::Run {
+            label: format!("/{}", alias.name),
+            command: expanded,
+        })),
+        AliasAction::Prompt(_) => CommandResult::action(AppAction::SendMessage(expanded)),
+    })
+}
+
+/// Alias names starting with `prefix`, formatted as `/name`. Names taken by
+/// built-in commands are left out because the built-in wins.
+pub fn alias_names_matching(prefix: &str, workspace: &Path) -> Vec<String> {
+    command_aliases::load(workspace)
+        .into_iter()
+        .filter(|alias| alias.name.starts_with(prefix) && get_command_info(&alias.name).is_none())
+        .map(|alias| format!("/{}", alias.name))
+        .collect()
+}
+
+/// Slash-menu description for an alias.
+pub fn alias_summary(name: &str, workspace: &Path) -> Option<String> {
+    command_aliases::find(workspace, name)
+        .map(|alias| format!("Project alias: {}", alias.summary()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::tui::app::TuiOptions;
+    use tempfile::TempDir;
+
+    fn create_test_app(tmpdir: &TempDir) -> App {
+        let options = TuiOptions {
+            model: "deepseek-v4-pro".to_string(),
+            workspace: tmpdir.path().to_path_buf(),
+            config_path: None,
+            config_profile: None,
+            allow_shell: false,
+            use_alt_screen: true,
+            use_mouse_capture: false,
+            use_bracketed_paste: true,
+            max_subagents: 1,
+            skills_dir: tmpdir.path().join("skills"),
+            memory_path: tmpdir.path().join("memory.md"),
+            notes_path: tmpdir.path().join("notes.txt"),
+            mcp_config_path: tmpdir.path().join("mcp.json"),
+            use_memory: false,
+            start_in_agent_mode: true,
+            skip_onboarding: true,
+            yolo: false,
+            resume_session_id: None,
+            initial_input: None,
+        };
+        App::new(options, &Config::default())
+    }
+
+    #[test]
+    fn aliases_dispatch_to_shell_jobs_and_messages_without_shadowing_builtins() {
+        let tmpdir = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmpdir.path().join(".deepseek")).unwrap();
+        std::fs::write(
+            tmpdir.path().join(".deepseek/config.toml"),
+            "[aliases]\nbuild = \"cargo build\"\nhelp = \"echo shadowed\"\nnotes = { prompt = \"Notes since $1\" }\n",
+        )
+        .unwrap();
+        let mut app = create_test_app(&tmpdir);
+
+        let result = try_dispatch_alias(&mut app, "build", "--release").unwrap();
+        match result.action {
+            Some(AppAction::ShellJob(ShellJobAction::Run { label, command })) => {
+                assert_eq!(label, "/build");
+                assert_eq!(command, "cargo build --release");
+            }
+            other => panic!("expected a shell job, got {other:?}"),
+        }
+
+        let result = try_dispatch_alias(&mut app, "notes", "v1.0").unwrap();
+        assert!(
+            matches!(result.action, Some(AppAction::SendMessage(ref text)) if text == "Notes since v1.0")
+        );
+
+        assert!(try_dispatch_alias(&mut app, "missing", "").is_none());
+        assert_eq!(
+            alias_names_matching("", tmpdir.path()),
+            ["/build", "/notes"]
+        );
+    }
+}
@@ -0,0 +1,24 @@
+//! Bookmark commands: open the jump list or clear it
+
+use crate::tui::app::{App, AppAction};
+
+use super::CommandResult;
+
+pub fn bookmarks(app: &mut App, args: Option<&str>) -> CommandResult {
+    let arg = args.unwrap_or("").trim();
+    if arg.is_empty() || arg.eq_ignore_ascii_case("open") || arg.eq_ignore_ascii_case("list") {
+        if app.bookmarks.is_empty() {
+            return CommandResult::message(
+                "No bookmarks yet. Select a message in the transcript and press `m` to add one.",
+            );
+        }
+        return CommandResult::action(AppAction::OpenBookmarks);
+    }
+    if arg.eq_ignore_ascii_case("clear") {
+        let count = app.bookmarks.len();
+        app.bookmarks.clear();
+        app.needs_redraw = true;
+        return CommandResult::message(format!("Cleared {count} bookmark(s)."));
+    }
+    CommandResult::error("Usage: /bookmarks [clear]")
+}
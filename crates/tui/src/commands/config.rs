@@ -1,7 +1,7 @@
 //! Config commands: config, settings, mode switches, trust, logout
 
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use super::CommandResult;
 use crate::client::DeepSeekClient;
@@ -16,6 +16,7 @@ use crate::tui::app::{
 };
 use crate::tui::approval::ApprovalMode;
 use anyhow::Result;
+use deepseek_config::org::format_age;
 
 /// Open the interactive config editor.
 ///
@@ -45,6 +46,7 @@ pub fn show_config(_app: &mut App, arg: Option<&str>) -> CommandResult {
 /// - `/config` (no args) — opens the schemaui-driven TUI editor.
 /// - `/config tui` / `/config web` / `/config native` — open a specific
 ///   editor mode (web requires the `web` build feature).
+/// - `/config org` — shows where the shared team config layer came from.
 /// - `/config <key>` — shows the current value of a setting.
 /// - `/config <key> <value>` — sets a runtime value (session only, add --save to persist).
 pub fn config_command(app: &mut App, arg: Option<&str>) -> CommandResult {
@@ -62,6 +64,9 @@ pub fn config_command(app: &mut App, arg: Option<&str>) -> CommandResult {
         ) {
             return show_config(app, Some(token));
         }
+        if token.eq_ignore_ascii_case("org") {
+            return show_org_layer(app);
+        }
         // `/config <key>` — show current value
         show_single_setting(app, token)
     } else {
@@ -81,6 +86,71 @@ pub fn config_command(app: &mut App, arg: Option<&str>) -> CommandResult {
 }
 
 /// Show the current value of a single setting.
+/// `/config org` — provenance of the shared team config layer.
+fn show_org_layer(app: &App) -> CommandResult {
+    let Some(layer) = crate::config::org_layer() else {
+        return CommandResult::error("Could not resolve the home directory.");
+    };
+    let state = match layer.state() {
+        Ok(Some(state)) if layer.is_active() => state,
+        Ok(_) => {
+            return CommandResult::message(
+                "No org config layer. Pull one with `deepseek config sync --from <repo>`.",
+            );
+        }
+        Err(err) => return CommandResult::error(format!("Org config layer: {err:#}")),
+    };
+    let now = SystemTime::now();
+    let mut out = format!(
+        "Org config layer (read-only, beneath your config)\n  source:  {}{}\n  commit:  {}\n  synced:  {}",
+        state.url,
+        state
+            .branch
+            .as_deref()
+            .map(|branch| format!(" @ {branch}"))
+            .unwrap_or_default(),
+        state.short_commit(),
+        format_age(state.synced_at, now),
+    );
+    match state.refresh_hours {
+        0 => out.push_str(" (auto-refresh off)"),
+        hours if state.is_stale(now) => {
+            out.push_str(&format!(" (refresh due, every {hours}h)"));
+        }
+        hours => out.push_str(&format!(" (refreshes every {hours}h)")),
+    }
+    match crate::config::org_layer_keys(&layer, app.config_path.clone()) {
+        Ok(keys) => {
+            if !keys.inherited.is_empty() {
+                out.push_str(&format!(
+                    "\n  from org:        {}",
+                    keys.inherited.join(", ")
+                ));
+            }
+            if !keys.overridden.is_empty() {
+                out.push_str(&format!(
+                    "\n  overridden by you: {}",
+                    keys.overridden.join(", ")
+                ));
+            }
+        }
+        Err(err) => out.push_str(&format!("\n  config.toml: {err:#}")),
+    }
+    for (label, path) in [
+        ("requirements.toml (enforced)", layer.requirements_path()),
+        (
+            "execpolicy.toml (org/ rule groups)",
+            layer.execpolicy_path(),
+        ),
+        ("skills/ (lowest precedence)", layer.skills_dir()),
+    ] {
+        if path.exists() {
+            out.push_str(&format!("\n  + {label}"));
+        }
+    }
+    CommandResult::message(out)
+}
+
 fn show_single_setting(app: &App, key: &str) -> CommandResult {
     let key = key.to_lowercase();
     fn locale_display(l: crate::localization::Locale) -> &'static str {
@@ -133,6 +203,9 @@ fn show_single_setting(app: &App, key: &str) -> CommandResult {
         "auto_compact" | "compact" => {
             Some(if app.auto_compact { "true" } else { "false" }.to_string())
         }
+        "idle_maintenance_secs" | "idle_maintenance" => {
+            Some(app.idle_maintenance.idle_secs().to_string())
+        }
         "calm_mode" | "calm" => Some(if app.calm_mode { "true" } else { "false" }.to_string()),
         "low_motion" | "motion" => Some(if app.low_motion { "true" } else { "false" }.to_string()),
         "fancy_animations" | "fancy" | "animations" => Some(
@@ -172,6 +245,7 @@ fn show_single_setting(app: &App, key: &str) -> CommandResult {
         ),
         "mode" | "default_mode" => Some(app.mode.as_setting().to_string()),
         "max_history" | "history" => Some(app.max_input_history.to_string()),
+        "paste_prompt_lines" | "paste_prompt" => Some(app.paste_prompt_lines.to_string()),
         "sidebar_width" | "sidebar" => Some(app.sidebar_width_percent.to_string()),
         "sidebar_focus" | "focus" => Some(app.sidebar_focus.as_setting().to_string()),
         "context_panel" | "context" | "session_panel" => {
@@ -181,6 +255,7 @@ fn show_single_setting(app: &App, key: &str) -> CommandResult {
         "composer_border" | "border" => {
             Some(if app.composer_border { "true" } else { "false" }.to_string())
         }
+        "prompt_lint" | "lint" => Some(if app.prompt_lint { "true" } else { "false" }.to_string()),
         "composer_vim_mode" | "vim_mode" | "vim" => Some(
             if app.composer.vim_enabled {
                 "vim"
@@ -217,6 +292,17 @@ fn show_single_setting(app: &App, key: &str) -> CommandResult {
         "prefer_external_pdftotext" | "external_pdftotext" | "pdftotext" => Settings::load()
             .ok()
             .map(|settings| settings.prefer_external_pdftotext.to_string()),
+        _ if crate::settings::parse_mode_prompt_key(&key).is_some() => {
+            crate::settings::parse_mode_prompt_key(&key).map(|(mode, is_prefix)| {
+                let prompt = app.mode_prompts.get(mode);
+                let text = if is_prefix {
+                    prompt.and_then(|prompt| prompt.prefix.as_deref())
+                } else {
+                    prompt.and_then(|prompt| prompt.suffix.as_deref())
+                };
+                text.unwrap_or("(none)").to_string()
+            })
+        }
         _ => {
             let known = Settings::available_settings()
                 .iter()
@@ -249,6 +335,11 @@ pub fn status_line(_app: &mut App) -> CommandResult {
     CommandResult::action(AppAction::OpenStatusPicker)
 }
 
+/// List keybindings, `[keys]` remaps, and conflicts.
+pub fn keys(app: &mut App) -> CommandResult {
+    CommandResult::message(app.keymap.describe())
+}
+
 /// Toggle whether the live transcript renders full thinking detail.
 pub fn verbose(app: &mut App, arg: Option<&str>) -> CommandResult {
     let next = match arg.map(str::trim).filter(|s| !s.is_empty()) {
@@ -457,6 +548,10 @@ pub fn set_config_value(app: &mut App, key: &str, value: &str, persist: bool) ->
             app.auto_compact = settings.auto_compact;
             action = Some(AppAction::UpdateCompaction(app.compaction_config()));
         }
+        "idle_maintenance_secs" | "idle_maintenance" => {
+            app.idle_maintenance
+                .set_idle_secs(settings.idle_maintenance_secs);
+        }
         "calm_mode" | "calm" => {
             app.calm_mode = settings.calm_mode;
             app.mark_history_updated();
@@ -517,6 +612,13 @@ pub fn set_config_value(app: &mut App, key: &str, value: &str, persist: bool) ->
             app.composer_border = settings.composer_border;
             app.needs_redraw = true;
         }
+        "prompt_lint" | "lint" => {
+            app.prompt_lint = settings.prompt_lint;
+            if !app.prompt_lint {
+                app.prompt_hint = None;
+            }
+            app.needs_redraw = true;
+        }
         "composer_vim_mode" | "vim_mode" | "vim" => {
             app.composer.vim_enabled = settings.composer_vim_mode == "vim";
             app.composer.vim_mode = if app.composer.vim_enabled {
@@ -545,6 +647,9 @@ pub fn set_config_value(app: &mut App, key: &str, value: &str, persist: bool) ->
         "max_history" | "history" => {
             app.max_input_history = settings.max_input_history;
         }
+        "paste_prompt_lines" | "paste_prompt" => {
+            app.paste_prompt_lines = settings.paste_prompt_lines;
+        }
         "default_model" => {
             if let Some(ref model) = settings.default_model {
                 app.set_model_selection(model.clone());
@@ -582,6 +687,12 @@ pub fn set_config_value(app: &mut App, key: &str, value: &str, persist: bool) ->
             app.context_panel = settings.context_panel;
             app.needs_redraw = true;
         }
+        // Apply the same edit to the live presets so earlier session-only
+        // edits to other parts survive.
+        _ if crate::settings::parse_mode_prompt_key(&key).is_some() => {
+            let _ = crate::settings::set_mode_prompt(&mut app.mode_prompts, &key, value);
+            app.needs_redraw = true;
+        }
         _ => {}
     }
 
@@ -703,6 +814,9 @@ pub fn theme(app: &mut App, arg: Option<&str>) -> CommandResult {
 ///
 /// Subcommands:
 /// - `/trust`            – show current state and trusted external paths
+/// - `/trust level [<level>]` – show or set the workspace trust level
+///   (`untrusted`, `read-only`, `standard`, `full`), saved to
+///   `.deepseek/trust.json`
 /// - `/trust on`         – legacy: trust the entire workspace (turn off all path checks)
 /// - `/trust off`        – disable workspace-level trust mode
 /// - `/trust add <path>` – add a directory to the allowlist (#29)
@@ -728,10 +842,11 @@ pub fn trust(app: &mut App, arg: Option<&str>) -> CommandResult {
             app.trust_mode = false;
             CommandResult::message("Workspace trust mode disabled.")
         }
+        "level" => trust_level(app, rest),
         "add" => trust_add(&workspace, rest),
         "remove" | "rm" | "del" | "delete" => trust_remove(&workspace, rest),
         other => CommandResult::error(format!(
-            "Unknown /trust action `{other}`. Use `/trust`, `/trust on|off`, `/trust add <path>`, or `/trust remove <path>`."
+            "Unknown /trust action `{other}`. Use `/trust`, `/trust level <level>`, `/trust on|off`, `/trust add <path>`, or `/trust remove <path>`."
         )),
     }
 }
@@ -739,6 +854,11 @@ pub fn trust(app: &mut App, arg: Option<&str>) -> CommandResult {
 fn trust_status(workspace: &Path, app: &App, force_paths: bool) -> CommandResult {
     let trust = crate::workspace_trust::WorkspaceTrust::load_for(workspace);
     let mut lines = Vec::new();
+    lines.push(format!(
+        "Workspace trust level: {} ({})",
+        app.trust_level,
+        app.trust_level.summary()
+    ));
     lines.push(format!(
         "Workspace trust mode: {}",
         if app.trust_mode {
@@ -765,6 +885,32 @@ fn trust_status(workspace: &Path, app: &App, force_paths: bool) -> CommandResult
     CommandResult::message(lines.join("\n"))
 }
 
+fn trust_level(app: &mut App, raw: &str) -> CommandResult {
+    use crate::trust_level::TrustLevel;
+
+    if raw.is_empty() {
+        let mut lines = vec![format!("Workspace trust level: {}", app.trust_level)];
+        for level in TrustLevel::ALL {
+            lines.push(format!("  {:<10} {}", level.as_str(), level.summary()));
+        }
+        lines.push("Change it with `/trust level <level>`.".to_string());
+        return CommandResult::message(lines.join("\n"));
+    }
+    let Some(level) = TrustLevel::parse(raw) else {
+        return CommandResult::error(format!(
+            "Unknown trust level `{raw}`. Use untrusted, read-only, standard, or full."
+        ));
+    };
+    if let Err(err) = crate::trust_level::save(&app.workspace, level) {
+        return CommandResult::error(format!("Failed to save trust level: {err}"));
+    }
+    app.set_trust_level(level);
+    CommandResult::with_message_and_action(
+        format!("Workspace trust level: {level} ({}).", level.summary()),
+        AppAction::SetTrustLevel(level),
+    )
+}
+
 fn trust_add(workspace: &Path, raw: &str) -> CommandResult {
     if raw.is_empty() {
         return CommandResult::error(
@@ -1869,6 +2015,37 @@ mod tests {
         assert!(msg.contains("Workspace trust mode"));
     }
 
+    #[test]
+    fn test_trust_level_saves_and_scopes_session() {
+        use crate::trust_level::TrustLevel;
+
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let _guard = EnvGuard::new(tmp.path());
+        let mut app = create_test_app();
+        app.workspace = tmp.path().to_path_buf();
+
+        let result = trust(&mut app, Some("level read-only"));
+        assert_eq!(
+            result.action,
+            Some(AppAction::SetTrustLevel(TrustLevel::ReadOnly))
+        );
+        assert_eq!(app.trust_level, TrustLevel::ReadOnly);
+        assert_eq!(app.approval_mode, ApprovalMode::Suggest);
+        assert_eq!(
+            crate::trust_level::load(tmp.path()),
+            Some(TrustLevel::ReadOnly)
+        );
+
+        trust(&mut app, Some("level full"));
+        assert!(app.trust_mode);
+        assert_eq!(app.approval_mode, ApprovalMode::Auto);
+
+        let listing = trust(&mut app, Some("level")).message.expect("listing");
+        assert!(listing.contains("Workspace trust level: full"), "{listing}");
+        assert!(listing.contains("untrusted"), "{listing}");
+        assert!(trust(&mut app, Some("level yolo")).is_error);
+    }
+
     #[test]
     fn test_trust_add_requires_path() {
         let mut app = create_test_app();
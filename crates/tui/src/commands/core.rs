@@ -5,6 +5,7 @@ use std::path::PathBuf;
 
 use crate::config::{COMMON_DEEPSEEK_MODELS, normalize_model_name_for_provider};
 use crate::localization::{MessageId, tr};
+use crate::tools::exec_profile::ExecProfile;
 use crate::tui::app::{App, AppAction, AppMode, ReasoningEffort};
 use crate::tui::views::{HelpView, ModalKind, SubAgentsView, subagent_view_agents};
 
@@ -32,6 +33,14 @@ pub fn help(app: &mut App, topic: Option<&str>) -> CommandResult {
             }
             return CommandResult::message(help);
         }
+        let name = topic.strip_prefix('/').unwrap_or(topic);
+        if let Some(alias) = crate::command_aliases::find(&app.workspace, name) {
+            return CommandResult::message(format!(
+                "{}\n\n  Project alias: {}",
+                alias.name,
+                alias.summary()
+            ));
+        }
         return CommandResult::error(
             tr(app.ui_locale, MessageId::HelpUnknownCommand).replace("{topic}", topic),
         );
@@ -39,7 +48,8 @@ pub fn help(app: &mut App, topic: Option<&str>) -> CommandResult {
 
     // Show help overlay
     if app.view_stack.top_kind() != Some(ModalKind::Help) {
-        app.view_stack.push(HelpView::new_for_locale(app.ui_locale));
+        app.view_stack
+            .push(HelpView::new_for_locale(app.ui_locale).with_project_aliases(&app.workspace));
     }
     CommandResult::ok()
 }
@@ -49,7 +59,9 @@ pub fn clear(app: &mut App) -> CommandResult {
     app.clear_history();
     app.mark_history_updated();
     app.api_messages.clear();
+    app.api_message_times.clear();
     app.system_prompt = None;
+    app.auto_skills.clear();
     app.viewport.transcript_selection.clear();
     app.queued_messages.clear();
     app.queued_draft = None;
@@ -62,6 +74,10 @@ pub fn clear(app: &mut App) -> CommandResult {
     app.session.subagent_cost_event_seqs.clear();
     app.session.displayed_cost_high_water = 0.0;
     app.session.displayed_cost_high_water_cny = 0.0;
+    app.session.cache_hit_tokens_total = 0;
+    app.session.cache_miss_tokens_total = 0;
+    app.session.cache_saved = 0.0;
+    app.session.cache_saved_cny = 0.0;
     let todos_cleared = app.clear_todos();
     app.tool_log.clear();
     app.tool_cells.clear();
@@ -102,8 +118,8 @@ pub fn exit() -> CommandResult {
 }
 
 /// Switch or view current model. With no argument, open the two-pane
-/// picker (Pro/Flash + thinking effort) per #39 — gives users a discoverable
-/// way to flip both knobs without memorising the docs.
+/// picker (searchable models + thinking effort) per #39 — gives users a
+/// discoverable way to flip both knobs without memorising the docs.
 pub fn model(app: &mut App, model_name: Option<&str>) -> CommandResult {
     if let Some(name) = model_name {
         if name.trim().eq_ignore_ascii_case("auto") {
@@ -136,9 +152,18 @@ pub fn model(app: &mut App, model_name: Option<&str>) -> CommandResult {
         };
         let old_model = app.model_display_label();
         let model_changed = app.auto_model || app.model != model_id;
+        let context_warning = if model_changed {
+            crate::tui::model_picker::context_downsize_warning(
+                &model_id,
+                crate::tui::model_picker::current_context_tokens(app),
+            )
+        } else {
+            None
+        };
         app.auto_model = false;
         app.model = model_id.clone();
         app.last_effective_model = None;
+        let preset = app.apply_model_preset();
         app.update_model_compaction_budget();
         if model_changed {
             app.clear_model_scoped_telemetry();
@@ -146,10 +171,17 @@ pub fn model(app: &mut App, model_name: Option<&str>) -> CommandResult {
             app.session.last_prompt_tokens = None;
             app.session.last_completion_tokens = None;
         }
+        let mut message = tr(app.ui_locale, MessageId::ModelChanged)
+            .replace("{old}", &old_model)
+            .replace("{new}", &model_id);
+        if let Some(preset) = preset {
+            message.push_str(&format!("\nPreset: {preset}"));
+        }
+        if let Some(warning) = context_warning {
+            message.push_str(&format!("\nWarning: {warning}"));
+        }
         CommandResult::with_message_and_action(
-            tr(app.ui_locale, MessageId::ModelChanged)
-                .replace("{old}", &old_model)
-                .replace("{new}", &model_id),
+            message,
             AppAction::UpdateCompaction(app.compaction_config()),
         )
     } else {
@@ -157,6 +189,86 @@ pub fn model(app: &mut App, model_name: Option<&str>) -> CommandResult {
     }
 }
 
+const THINKING_USAGE: &str =
+    "Usage: /thinking [off|low|medium|high|max|auto]\n       /thinking budget <tokens|off>";
+
+/// Show or set the thinking tier sent with each request. The choice lasts for
+/// the session; the model picker's set-as-default persists it.
+pub fn thinking(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let Some(raw) = arg.map(str::trim).filter(|raw| !raw.is_empty()) else {
+        return CommandResult::message(format!(
+            "Thinking: {} · budget: {}\n{THINKING_USAGE}",
+            app.reasoning_effort.as_setting(),
+            thinking_budget_label(app.thinking_budget)
+        ));
+    };
+    if let Some(rest) = raw
+        .strip_prefix("budget")
+        .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+    {
+        return thinking_budget(app, rest.trim());
+    }
+    let effort = match raw.to_ascii_lowercase().as_str() {
+        "off" | "low" | "medium" | "high" | "max" | "auto" => ReasoningEffort::from_setting(raw),
+        _ => {
+            return CommandResult::error(format!(
+                "Unknown thinking tier '{raw}'. {THINKING_USAGE}"
+            ));
+        }
+    };
+    let previous = app.reasoning_effort;
+    app.reasoning_effort = effort;
+    app.last_effective_reasoning_effort = None;
+    app.update_model_compaction_budget();
+    app.needs_redraw = true;
+    CommandResult::with_message_and_action(
+        format!(
+            "Thinking: {} → {}",
+            previous.as_setting(),
+            effort.as_setting()
+        ),
+        AppAction::UpdateCompaction(app.compaction_config()),
+    )
+}
+
+/// `/thinking budget <n|off>`: cap estimated thinking tokens per turn. Once a
+/// turn spends the budget the engine stops the reasoning stream and finishes
+/// the turn with thinking off.
+fn thinking_budget(app: &mut App, raw: &str) -> CommandResult {
+    if raw.is_empty() {
+        return CommandResult::message(format!(
+            "Thinking budget: {}",
+            thinking_budget_label(app.thinking_budget)
+        ));
+    }
+    let budget = if raw.eq_ignore_ascii_case("off") {
+        None
+    } else {
+        match raw.replace('_', "").parse::<u32>() {
+            Ok(0) => None,
+            Ok(tokens) => Some(tokens),
+            Err(_) => {
+                return CommandResult::error(format!(
+                    "Invalid thinking budget '{raw}'. {THINKING_USAGE}"
+                ));
+            }
+        }
+    };
+    let previous = std::mem::replace(&mut app.thinking_budget, budget);
+    CommandResult::with_message_and_action(
+        format!(
+            "Thinking budget: {} → {}",
+            thinking_budget_label(previous),
+            thinking_budget_label(budget)
+        ),
+        AppAction::SetThinkingBudget(budget),
+    )
+}
+
+fn thinking_budget_label(budget: Option<u32>) -> String {
+    budget.map_or_else(|| "off".to_string(), |tokens| format!("{tokens} tokens"))
+}
+
 /// Fetch and list available models from the configured API endpoint.
 pub fn models(_app: &mut App) -> CommandResult {
     CommandResult::action(AppAction::FetchModels)
@@ -172,16 +284,20 @@ pub fn subagents(app: &mut App) -> CommandResult {
     CommandResult::action(AppAction::ListSubAgents)
 }
 
-/// Switch to a configured profile.
-pub fn profile_switch(_app: &mut App, arg: Option<&str>) -> CommandResult {
+/// Switch to a configured profile, or pick an exec profile with
+/// `/profile use <name|off>`.
+pub fn profile_switch(app: &mut App, arg: Option<&str>) -> CommandResult {
     let profile_name = match arg {
         Some(name) if !name.trim().is_empty() => name.trim().to_string(),
         _ => {
             return CommandResult::error(
-                "Usage: /profile <name>\n\nSwitch to a named config profile. Profiles are defined in ~/.deepseek/config.toml under [profiles] sections.",
+                "Usage: /profile <name>\n       /profile use <exec-profile|off>\n\nSwitch to a named config profile ([profiles] in ~/.deepseek/config.toml), or wrap shell commands with an [exec_profiles] entry.",
             );
         }
     };
+    if profile_name == "use" || profile_name.starts_with("use ") {
+        return exec_profile_use(app, profile_name["use".len()..].trim());
+    }
     CommandResult::with_message_and_action(
         format!("Switching to profile '{profile_name}'..."),
         AppAction::SwitchProfile {
@@ -190,6 +306,66 @@ pub fn profile_switch(_app: &mut App, arg: Option<&str>) -> CommandResult {
     )
 }
 
+fn exec_profile_use(app: &mut App, name: &str) -> CommandResult {
+    let active = app.exec_profile.get();
+    if name.is_empty() {
+        let mut out = match active.as_ref() {
+            Some(profile) => format!(
+                "Exec profile: {} ({})",
+                profile.name,
+                profile.display_prefix()
+            ),
+            None => "Exec profile: off (shell commands run on the host)".to_string(),
+        };
+        if app.exec_profiles.is_empty() {
+            out.push_str("\n\nNo [exec_profiles] are configured in ~/.deepseek/config.toml.");
+        } else {
+            out.push_str("\n\nAvailable:");
+            for name in app.exec_profiles.keys() {
+                let _ = write!(out, "\n  {name}");
+            }
+        }
+        return CommandResult::message(out);
+    }
+    if name.eq_ignore_ascii_case("off") || name.eq_ignore_ascii_case("host") {
+        app.exec_profile.set(None);
+        app.status_message = Some("Exec profile: off".to_string());
+        return CommandResult::message(match active {
+            Some(profile) => format!(
+                "Exec profile '{}' turned off; shell commands run on the host.",
+                profile.name
+            ),
+            None => "Shell commands already run on the host.".to_string(),
+        });
+    }
+    let Some(config) = app.exec_profiles.get(name) else {
+        let known = if app.exec_profiles.is_empty() {
+            "none configured".to_string()
+        } else {
+            app.exec_profiles
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        return CommandResult::error(format!(
+            "Unknown exec profile '{name}' (available: {known})"
+        ));
+    };
+    let profile = match ExecProfile::from_config(name, config) {
+        Ok(profile) => profile,
+        Err(err) => return CommandResult::error(err),
+    };
+    let message = format!(
+        "Exec profile '{}' active: shell commands run via `{}`. File edits still apply to the host workspace.",
+        profile.name,
+        profile.display_prefix()
+    );
+    app.status_message = Some(format!("Exec profile: {}", profile.name));
+    app.exec_profile.set(Some(profile));
+    CommandResult::message(message)
+}
+
 pub fn workspace_switch(app: &mut App, arg: Option<&str>) -> CommandResult {
     let Some(raw_path) = arg.map(str::trim).filter(|path| !path.is_empty()) else {
         return CommandResult::message(format!("Current workspace: {}", app.workspace.display()));
@@ -374,6 +550,32 @@ pub fn translate(app: &mut App) -> CommandResult {
     }
 }
 
+const SMART_CONTEXT_USAGE: &str = "Usage: /smartcontext [on|off]";
+
+/// Show or toggle automatic per-prompt context selection. Each prompt sent
+/// while it is on lists the files it auto-included in the transcript.
+pub fn smart_context(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let state = |enabled: bool| if enabled { "on" } else { "off" };
+    let enabled = match arg.map(|raw| raw.trim().to_ascii_lowercase()).as_deref() {
+        None | Some("") => {
+            return CommandResult::message(format!(
+                "Smart context: {} (up to {} files per prompt)\n{SMART_CONTEXT_USAGE}",
+                state(app.smart_context_enabled),
+                app.smart_context_max_files
+            ));
+        }
+        Some("on") => true,
+        Some("off") => false,
+        Some(other) => {
+            return CommandResult::error(format!(
+                "Unknown option '{other}'. {SMART_CONTEXT_USAGE}"
+            ));
+        }
+    };
+    app.smart_context_enabled = enabled;
+    CommandResult::message(format!("Smart context: {}", state(enabled)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -536,6 +738,8 @@ mod tests {
         app.session.subagent_cost_event_seqs.insert(7);
         app.session.displayed_cost_high_water = 0.53;
         app.session.displayed_cost_high_water_cny = 3.85;
+        app.session.cache_hit_tokens_total = 700;
+        app.session.cache_saved = 0.02;
         app.session.last_prompt_cache_hit_tokens = Some(70);
         app.session.last_prompt_cache_miss_tokens = Some(30);
         app.session.last_reasoning_replay_tokens = Some(12);
@@ -564,6 +768,8 @@ mod tests {
         assert!(app.session.subagent_cost_event_seqs.is_empty());
         assert_eq!(app.session.displayed_cost_high_water, 0.0);
         assert_eq!(app.session.displayed_cost_high_water_cny, 0.0);
+        assert_eq!(app.session.cache_hit_tokens_total, 0);
+        assert_eq!(app.session.cache_saved, 0.0);
         assert_eq!(app.session.last_prompt_cache_hit_tokens, None);
         assert_eq!(app.session.last_prompt_cache_miss_tokens, None);
         assert_eq!(app.session.last_reasoning_replay_tokens, None);
@@ -735,6 +941,103 @@ mod tests {
         assert!(result.action.is_none());
     }
 
+    #[test]
+    fn model_switch_applies_preset_thinking_tier() {
+        let mut app = create_test_app();
+        app.auto_model = false;
+        app.reasoning_effort = ReasoningEffort::Max;
+        app.model_presets.insert(
+            "deepseek-v4-flash".to_string(),
+            crate::config::ModelPreset {
+                temperature: Some(0.6),
+                max_tokens: Some(8192),
+                thinking_budget: Some("off".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let result = model(&mut app, Some("deepseek-v4-flash"));
+        let message = result.message.expect("message");
+        assert!(
+            message.contains("Preset: temperature 0.6 · max_tokens 8192 · thinking off"),
+            "{message}"
+        );
+        assert_eq!(app.reasoning_effort, ReasoningEffort::Off);
+
+        model(&mut app, Some("deepseek-v4-pro"));
+        assert_eq!(app.reasoning_effort, ReasoningEffort::Off);
+    }
+
+    #[test]
+    fn smart_context_toggles_and_reports_state() {
+        let mut app = create_test_app();
+        app.smart_context_enabled = false;
+
+        let result = smart_context(&mut app, Some("on"));
+        assert!(!result.is_error);
+        assert!(app.smart_context_enabled);
+
+        let shown = smart_context(&mut app, None).message.expect("message");
+        assert!(
+            shown.starts_with("Smart context: on (up to 3 files"),
+            "{shown}"
+        );
+
+        assert!(smart_context(&mut app, Some("maybe")).is_error);
+        assert!(app.smart_context_enabled);
+        smart_context(&mut app, Some("OFF"));
+        assert!(!app.smart_context_enabled);
+    }
+
+    #[test]
+    fn thinking_sets_shows_and_rejects_tiers() {
+        let mut app = create_test_app();
+        app.reasoning_effort = ReasoningEffort::Max;
+
+        let result = thinking(&mut app, Some("low"));
+        assert!(!result.is_error);
+        assert_eq!(app.reasoning_effort, ReasoningEffort::Low);
+        assert!(matches!(
+            result.action,
+            Some(AppAction::UpdateCompaction(_))
+        ));
+
+        let shown = thinking(&mut app, None);
+        assert!(shown.message.unwrap().starts_with("Thinking: low"));
+
+        let rejected = thinking(&mut app, Some("extreme"));
+        assert!(rejected.is_error);
+        assert_eq!(app.reasoning_effort, ReasoningEffort::Low);
+    }
+
+    #[test]
+    fn thinking_budget_sets_clears_and_rejects_values() {
+        let mut app = create_test_app();
+
+        let result = thinking(&mut app, Some("budget 4_000"));
+        assert!(!result.is_error);
+        assert_eq!(app.thinking_budget, Some(4000));
+        assert_eq!(
+            result.action,
+            Some(AppAction::SetThinkingBudget(Some(4000)))
+        );
+        assert!(
+            thinking(&mut app, None)
+                .message
+                .unwrap()
+                .contains("budget: 4000 tokens")
+        );
+
+        let cleared = thinking(&mut app, Some("budget off"));
+        assert_eq!(app.thinking_budget, None);
+        assert_eq!(cleared.action, Some(AppAction::SetThinkingBudget(None)));
+
+        let rejected = thinking(&mut app, Some("budget lots"));
+        assert!(rejected.is_error);
+        assert!(rejected.action.is_none());
+        assert!(thinking(&mut app, Some("budgetary")).is_error);
+    }
+
     #[test]
     fn test_model_without_args_opens_picker() {
         let mut app = create_test_app();
@@ -9,8 +9,11 @@ use crate::client::{PromptInspection, inspect_prompt_for_request};
 use crate::compaction::estimate_input_tokens_conservative;
 use crate::localization::{Locale, MessageId, tr};
 use crate::models::{ContentBlock, MessageRequest, SystemPrompt, context_window_for_model};
+use crate::prompts::layers::{LayerOrigin, PromptLayerSummary};
 use crate::tui::app::{App, AppAction, TurnCacheRecord};
+use crate::tui::changes_view::ChangesView;
 use crate::tui::history::HistoryCell;
+use crate::tui::views::ModalKind;
 
 fn token_count(value: Option<u32>, locale: Locale) -> String {
     value.map_or_else(
@@ -87,8 +90,18 @@ pub fn tokens(app: &mut App) -> CommandResult {
 /// Show session cost breakdown
 pub fn cost(app: &mut App) -> CommandResult {
     let total = app.displayed_session_cost_for_currency(app.cost_currency);
-    let report = tr(app.ui_locale, MessageId::CmdCostReport)
-        .replace("{cost}", &app.format_cost_amount_precise(total));
+    let mut cost = app.format_cost_amount_precise(total);
+    if let Some(percent) = app.session_cache_hit_percent() {
+        let saved = app.cache_saved_for_currency(app.cost_currency);
+        cost.push('\n');
+        cost.push_str(
+            &tr(app.ui_locale, MessageId::CmdCostCacheSavings)
+                .replace("{saved}", &app.format_cost_amount_precise(saved))
+                .replace("{hit}", &app.session.cache_hit_tokens_total.to_string())
+                .replace("{percent}", &format!("{percent:.0}")),
+        );
+    }
+    let report = tr(app.ui_locale, MessageId::CmdCostReport).replace("{cost}", &cost);
     CommandResult::message(report)
 }
 
@@ -121,18 +134,63 @@ pub fn system_prompt(app: &mut App) -> CommandResult {
         prompt_text
     };
 
+    let layers = format_prompt_layers(&app.prompt_layers);
     CommandResult::message(format!(
-        "System Prompt ({} mode):\n─────────────────────────────\n{}",
+        "System Prompt ({} mode):\n─────────────────────────────\n{}{}",
         app.mode.label(),
+        layers,
         display
     ))
 }
 
+/// Render the per-layer breakdown shown above the `/system` preview. Empty
+/// until the engine has built its first prompt.
+fn format_prompt_layers(layers: &[PromptLayerSummary]) -> String {
+    if layers.is_empty() {
+        return String::new();
+    }
+    let name_width = layers
+        .iter()
+        .map(|layer| layer.name.len())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::from("Layers:\n");
+    let mut total = 0;
+    for (index, layer) in layers.iter().enumerate() {
+        let note = match (layer.enabled, layer.origin) {
+            (false, _) => "  (disabled)",
+            (true, LayerOrigin::Replaced) => "  (replaced)",
+            (true, LayerOrigin::Custom) => "  (custom)",
+            (true, LayerOrigin::Builtin) => "",
+        };
+        if layer.enabled {
+            total += layer.tokens;
+        }
+        out.push_str(&format!(
+            "  {:>2}. {:<name_width$}  ~{:>6} tokens{note}\n",
+            index + 1,
+            layer.name,
+            layer.tokens,
+        ));
+    }
+    out.push_str(&format!(
+        "  {:<width$}  ~{total:>6} tokens\n─────────────────────────────\n",
+        "total",
+        width = name_width + 4,
+    ));
+    out
+}
+
 /// Show context window usage
 pub fn context(_app: &mut App) -> CommandResult {
     CommandResult::action(AppAction::OpenContextInspector)
 }
 
+/// Write a sanitized bug-report bundle (`deepseek report` from the TUI).
+pub fn report() -> CommandResult {
+    CommandResult::action(AppAction::GenerateReport)
+}
+
 /// Show per-turn DeepSeek prefix-cache telemetry for the last N turns (#263).
 ///
 /// `arg` is parsed as a count override (default 10, capped at the ring size).
@@ -145,6 +203,9 @@ pub fn cache(app: &mut App, arg: Option<&str>) -> CommandResult {
     if matches!(arg, Some("warmup")) {
         return CommandResult::action(AppAction::CacheWarmup);
     }
+    if matches!(arg, Some("clear")) {
+        return CommandResult::action(AppAction::ClearToolResultCache);
+    }
 
     let want = arg.and_then(|s| s.parse::<usize>().ok()).unwrap_or(10);
     let cap = app.session.turn_cache_history.len();
@@ -503,6 +564,29 @@ mod tests {
         assert!(msg.contains("Approx total spent:"));
         assert!(msg.contains("approximate"));
         assert!(msg.contains("$0.1234"));
+        assert!(!msg.contains("Cache saved"));
+    }
+
+    #[test]
+    fn test_cost_reports_cache_savings_once_hits_are_reported() {
+        let mut app = create_test_app();
+        app.accrue_cache_usage(
+            &crate::models::Usage {
+                input_tokens: 1_000,
+                prompt_cache_hit_tokens: Some(750),
+                prompt_cache_miss_tokens: Some(250),
+                ..Default::default()
+            },
+            Some(crate::pricing::CostEstimate {
+                usd: 0.25,
+                cny: 1.8,
+            }),
+        );
+        let msg = cost(&mut app).message.expect("cost report");
+        assert!(
+            msg.contains("Cache saved: $0.2500 (750 prompt tokens, 75% served"),
+            "{msg}"
+        );
     }
 
     #[test]
@@ -549,6 +633,41 @@ mod tests {
         assert!(msg.contains("(no system prompt)"));
     }
 
+    #[test]
+    fn test_system_prompt_lists_layers_with_token_counts() {
+        let mut app = create_test_app();
+        app.system_prompt = Some(SystemPrompt::Text("prompt".to_string()));
+        app.prompt_layers = vec![
+            PromptLayerSummary {
+                name: "base".to_string(),
+                origin: LayerOrigin::Replaced,
+                enabled: true,
+                tokens: 1200,
+            },
+            PromptLayerSummary {
+                name: "skills".to_string(),
+                origin: LayerOrigin::Builtin,
+                enabled: false,
+                tokens: 300,
+            },
+            PromptLayerSummary {
+                name: "team".to_string(),
+                origin: LayerOrigin::Custom,
+                enabled: true,
+                tokens: 40,
+            },
+        ];
+        let msg = system_prompt(&mut app).message.unwrap();
+        assert!(msg.contains("Layers:"));
+        assert!(msg.contains("1. base"), "got: {msg}");
+        assert!(msg.contains("(replaced)"));
+        assert!(msg.contains("(disabled)"));
+        assert!(msg.contains("(custom)"));
+        // Disabled layers are not counted toward the total.
+        assert!(msg.contains("~  1240 tokens"), "got: {msg}");
+        assert!(msg.contains("prompt"));
+    }
+
     #[test]
     fn test_system_prompt_truncates_long_text() {
         let mut app = create_test_app();
@@ -925,6 +1044,24 @@ mod tests {
         assert!(matches!(result.action, Some(AppAction::SendMessage(_))));
     }
 
+    #[test]
+    fn test_retry_resumes_soft_cancelled_turn_without_undo() {
+        let mut app = create_test_app();
+        app.history.push(HistoryCell::User {
+            content: "Fix the tests".to_string(),
+        });
+        app.resumable_interrupted_turn = true;
+
+        let result = retry(&mut app);
+        assert_eq!(result.message.as_deref(), Some("Resuming interrupted turn"));
+        let Some(AppAction::SendMessage(prompt)) = result.action else {
+            panic!("expected SendMessage");
+        };
+        assert!(prompt.contains("Continue the task that was interrupted"));
+        assert_eq!(app.history.len(), 1, "history must not be rolled back");
+        assert!(!app.resumable_interrupted_turn);
+    }
+
     #[test]
     fn test_retry_no_previous_message() {
         let mut app = create_test_app();
@@ -1715,8 +1852,33 @@ pub fn diff(app: &mut App) -> CommandResult {
     }
 }
 
+/// Open the `/changes` view: files written by tools this session, diffed
+/// against their contents when the session first touched them.
+pub fn changes(app: &mut App) -> CommandResult {
+    let changes = app.session_changes.changes();
+    if app.view_stack.top_kind() != Some(ModalKind::SessionChanges) {
+        app.view_stack
+            .push(ChangesView::new(app.workspace.clone(), changes));
+    }
+    CommandResult::ok()
+}
+
 /// Retry last request - remove last exchange and re-send the user's message
+/// Sent by `/retry` after a soft-cancelled turn. The interrupted turn's tool
+/// results are already in the conversation, so the model picks up from there.
+const RESUME_INTERRUPTED_TURN_PROMPT: &str = "Continue the task that was interrupted. \
+Results of the tools that finished are already in the conversation; do not repeat \
+that work. Re-run any tool that was marked as not run if it is still needed.";
+
 pub fn retry(app: &mut App) -> CommandResult {
+    if app.resumable_interrupted_turn {
+        app.resumable_interrupted_turn = false;
+        return CommandResult::with_message_and_action(
+            "Resuming interrupted turn",
+            AppAction::SendMessage(RESUME_INTERRUPTED_TURN_PROMPT.to_string()),
+        );
+    }
+
     let last_user_input = app.history.iter().rev().find_map(|cell| match cell {
         HistoryCell::User { content } => Some(content.clone()),
         _ => None,
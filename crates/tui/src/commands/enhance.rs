@@ -0,0 +1,19 @@
+//! Enhance command: rewrite a draft prompt with a fast model call.
+
+use crate::tui::app::{App, AppAction};
+
+use super::CommandResult;
+
+/// Rewrite `draft`, or the draft the pre-send lint held back, and put the
+/// result in the composer for review.
+pub fn enhance(app: &mut App, draft: Option<&str>) -> CommandResult {
+    let draft = draft
+        .map(str::trim)
+        .filter(|draft| !draft.is_empty())
+        .map(str::to_string)
+        .or_else(|| app.prompt_hint.as_ref().map(|hint| hint.draft.clone()));
+    match draft {
+        Some(draft) => CommandResult::action(AppAction::EnhancePrompt(draft)),
+        None => CommandResult::error("Usage: /enhance <draft>"),
+    }
+}
@@ -0,0 +1,144 @@
+//! `/env` slash command — session environment variables for shell tools.
+//!
+//! Variables come from the config `[env]` table and from `/env set`. They are
+//! injected into every `exec_shell`, `shell_open`, and `run_tests` process for
+//! the rest of the session; secret values are masked in `/env` listings and in
+//! tool output.
+
+use super::CommandResult;
+use crate::tools::session_env::is_valid_key;
+use crate::tui::app::App;
+
+const USAGE: &str = "Usage: /env [list|set [--secret] KEY=VALUE|unset KEY|clear]";
+
+/// Entry point for `/env [list|set …|unset KEY|clear]`.
+pub fn env(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let arg = arg.map(str::trim).unwrap_or_default();
+    let (sub, rest) = arg
+        .split_once(char::is_whitespace)
+        .map_or((arg, ""), |(sub, rest)| (sub, rest.trim()));
+    match sub {
+        "" | "list" => list(app),
+        "set" => set(app, rest),
+        "unset" | "rm" if !rest.is_empty() => unset(app, rest),
+        "clear" => {
+            app.session_env.clear();
+            CommandResult::message("Cleared all session environment variables.")
+        }
+        _ => CommandResult::error(USAGE),
+    }
+}
+
+fn list(app: &App) -> CommandResult {
+    let vars = app.session_env.vars();
+    if vars.is_empty() {
+        return CommandResult::message(
+            "No session environment variables. Add one with /env set KEY=VALUE or an [env] table in config.toml.",
+        );
+    }
+    let mut out = String::from("Session environment (injected into shell tools):\n");
+    for (key, var) in &vars {
+        out.push_str(&format!("  {key}={}\n", var.display_value()));
+    }
+    CommandResult::message(out.trim_end().to_string())
+}
+
+fn set(app: &mut App, rest: &str) -> CommandResult {
+    let (secret, assignment) = match rest.strip_prefix("--secret") {
+        Some(assignment) => (true, assignment.trim_start()),
+        None => (false, rest),
+    };
+    let Some((key, value)) = assignment.split_once('=') else {
+        return CommandResult::error(USAGE);
+    };
+    let key = key.trim();
+    if !is_valid_key(key) {
+        return CommandResult::error(format!(
+            "Invalid variable name '{key}': use letters, digits, and '_', not starting with a digit."
+        ));
+    }
+    app.session_env.set(key, value, secret);
+    let masked = app.session_env.vars()[key].secret;
+    CommandResult::message(format!(
+        "Set {key} for shell tools this session{}.",
+        if masked {
+            " (value masked in output)"
+        } else {
+            ""
+        }
+    ))
+}
+
+fn unset(app: &mut App, key: &str) -> CommandResult {
+    if app.session_env.unset(key) {
+        CommandResult::message(format!("Unset {key}."))
+    } else {
+        CommandResult::error(format!("{key} is not set."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::tui::app::TuiOptions;
+    use tempfile::TempDir;
+
+    fn create_test_app(tmpdir: &TempDir) -> App {
+        let options = TuiOptions {
+            model: "deepseek-v4-pro".to_string(),
+            workspace: tmpdir.path().to_path_buf(),
+            config_path: None,
+            config_profile: None,
+            allow_shell: false,
+            use_alt_screen: true,
+            use_mouse_capture: false,
+            use_bracketed_paste: true,
+            max_subagents: 1,
+            skills_dir: tmpdir.path().join("skills"),
+            memory_path: tmpdir.path().join("memory.md"),
+            notes_path: tmpdir.path().join("notes.txt"),
+            mcp_config_path: tmpdir.path().join("mcp.json"),
+            use_memory: false,
+            start_in_agent_mode: true,
+            skip_onboarding: true,
+            yolo: false,
+            resume_session_id: None,
+            initial_input: None,
+        };
+        App::new(options, &Config::default())
+    }
+
+    #[test]
+    fn set_list_and_unset_round_trip_with_masking() {
+        let tmpdir = TempDir::new().unwrap();
+        let mut app = create_test_app(&tmpdir);
+
+        assert!(!env(&mut app, Some("set FEATURE_X=on")).is_error);
+        assert!(!env(&mut app, Some("set NPM_TOKEN=abc=123")).is_error);
+        assert!(!env(&mut app, Some("set --secret DB_URL=postgres://x")).is_error);
+
+        let pairs = app.session_env.env_pairs();
+        assert_eq!(pairs["NPM_TOKEN"], "abc=123");
+
+        let listed = env(&mut app, None).message.unwrap_or_default();
+        assert!(listed.contains("FEATURE_X=on"), "{listed}");
+        assert!(listed.contains("NPM_TOKEN=***"), "{listed}");
+        assert!(listed.contains("DB_URL=***"), "{listed}");
+        assert!(!listed.contains("abc=123"), "{listed}");
+
+        assert!(!env(&mut app, Some("unset FEATURE_X")).is_error);
+        assert!(env(&mut app, Some("unset FEATURE_X")).is_error);
+    }
+
+    #[test]
+    fn rejects_bad_names_and_missing_values() {
+        let tmpdir = TempDir::new().unwrap();
+        let mut app = create_test_app(&tmpdir);
+
+        assert!(env(&mut app, Some("set 1BAD=x")).is_error);
+        assert!(env(&mut app, Some("set NOVALUE")).is_error);
+        assert!(env(&mut app, Some("unset")).is_error);
+        assert!(app.session_env.vars().is_empty());
+    }
+}
@@ -0,0 +1,112 @@
+//! `/inbox` slash command — finished background tasks and sub-agents.
+//!
+//! See `crates/tui/src/inbox.rs` for what is collected and where it is
+//! stored; the modal itself lives in `tui/inbox_view.rs`.
+
+use crate::tui::app::App;
+use crate::tui::inbox_view::InboxView;
+use crate::tui::views::ModalKind;
+
+use super::CommandResult;
+
+/// Entry point for `/inbox [clear]`. Opening the inbox marks the entry under
+/// the cursor read; `clear` drops every read entry.
+pub fn inbox(app: &mut App, arg: Option<&str>) -> CommandResult {
+    match arg.map(str::trim).unwrap_or_default() {
+        "" | "open" => open(app),
+        "clear" => {
+            let removed = app.inbox.clear_read();
+            if let Err(err) = app.inbox.save(&app.workspace) {
+                return CommandResult::error(format!("Failed to save the inbox: {err}"));
+            }
+            CommandResult::message(format!(
+                "Removed {removed} read inbox entr{}; {} unread kept.",
+                if removed == 1 { "y" } else { "ies" },
+                app.inbox.unread_count()
+            ))
+        }
+        other => CommandResult::error(format!(
+            "unknown subcommand `{other}`. Try `/inbox` or `/inbox clear`."
+        )),
+    }
+}
+
+fn open(app: &mut App) -> CommandResult {
+    if app.view_stack.top_kind() == Some(ModalKind::Inbox) {
+        return CommandResult::ok();
+    }
+    app.view_stack
+        .push(InboxView::new(app.inbox.entries.clone()));
+    if let Some(first) = app.inbox.entries.first().map(|entry| entry.id.clone())
+        && app.inbox.mark_read(&first)
+        && let Err(err) = app.inbox.save(&app.workspace)
+    {
+        return CommandResult::error(format!("Failed to save the inbox: {err}"));
+    }
+    CommandResult::ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::inbox::{InboxEntry, InboxSource};
+    use crate::tui::app::TuiOptions;
+    use tempfile::TempDir;
+
+    fn create_test_app(tmpdir: &TempDir) -> App {
+        let options = TuiOptions {
+            model: "deepseek-v4-pro".to_string(),
+            workspace: tmpdir.path().to_path_buf(),
+            config_path: None,
+            config_profile: None,
+            allow_shell: false,
+            use_alt_screen: true,
+            use_mouse_capture: false,
+            use_bracketed_paste: true,
+            max_subagents: 1,
+            skills_dir: tmpdir.path().join("skills"),
+            memory_path: tmpdir.path().join("memory.md"),
+            notes_path: tmpdir.path().join("notes.txt"),
+            mcp_config_path: tmpdir.path().join("mcp.json"),
+            use_memory: false,
+            start_in_agent_mode: true,
+            skip_onboarding: true,
+            yolo: false,
+            resume_session_id: None,
+            initial_input: None,
+        };
+        App::new(options, &Config::default())
+    }
+
+    #[test]
+    fn opening_marks_the_newest_entry_read_and_clear_drops_read_entries() {
+        let tmpdir = TempDir::new().unwrap();
+        let mut app = create_test_app(&tmpdir);
+        for id in ["task_old", "task_new"] {
+            app.inbox.push(InboxEntry::new(
+                id,
+                InboxSource::Task,
+                "Run the audit",
+                "completed",
+                "done",
+            ));
+        }
+
+        inbox(&mut app, None);
+        assert_eq!(app.view_stack.top_kind(), Some(ModalKind::Inbox));
+        assert_eq!(app.inbox.unread_count(), 1);
+
+        let result = inbox(&mut app, Some("clear"));
+        assert!(
+            result
+                .message
+                .unwrap()
+                .starts_with("Removed 1 read inbox entry")
+        );
+        // The change is persisted for the next session.
+        let reloaded = crate::inbox::InboxStore::load(tmpdir.path());
+        assert_eq!(reloaded.entries.len(), 1);
+        assert_eq!(reloaded.entries[0].id, "task_old");
+    }
+}
@@ -1,6 +1,6 @@
-//! Shell job-center commands.
+//! Shell job-center and persistent shell session commands.
 
-use crate::tui::app::{App, AppAction, ShellJobAction};
+use crate::tui::app::{App, AppAction, ShellJobAction, ShellSessionAction};
 
 use super::CommandResult;
 
@@ -63,6 +63,47 @@ pub fn jobs(_app: &mut App, args: Option<&str>) -> CommandResult {
     }
 }
 
+const SHELLS_USAGE: &str =
+    "Usage: /shells [list|open <name> [command]|attach <name>|detach|show <name>|kill <name>]";
+
+pub fn shells(_app: &mut App, args: Option<&str>) -> CommandResult {
+    let raw = args.unwrap_or("").trim();
+    if raw.is_empty() || raw.eq_ignore_ascii_case("list") {
+        return CommandResult::action(AppAction::ShellSession(ShellSessionAction::List));
+    }
+
+    let mut parts = raw.splitn(3, char::is_whitespace);
+    let action = parts.next().unwrap_or("").to_ascii_lowercase();
+    let name = parts.next().map(str::trim).filter(|s| !s.is_empty());
+    let rest = parts.next().map(str::trim).filter(|s| !s.is_empty());
+
+    match (action.as_str(), name) {
+        ("open" | "new", Some(name)) => {
+            CommandResult::action(AppAction::ShellSession(ShellSessionAction::Open {
+                name: name.to_string(),
+                command: rest.map(str::to_string),
+            }))
+        }
+        ("attach", Some(name)) => {
+            CommandResult::action(AppAction::ShellSession(ShellSessionAction::Attach {
+                name: name.to_string(),
+            }))
+        }
+        ("detach", _) => CommandResult::action(AppAction::ShellSession(ShellSessionAction::Detach)),
+        ("show" | "inspect", Some(name)) => {
+            CommandResult::action(AppAction::ShellJob(ShellJobAction::Show {
+                id: name.to_string(),
+            }))
+        }
+        ("kill" | "stop" | "close", Some(name)) => {
+            CommandResult::action(AppAction::ShellJob(ShellJobAction::Cancel {
+                id: name.to_string(),
+            }))
+        }
+        _ => CommandResult::error(SHELLS_USAGE),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,4 +160,29 @@ mod tests {
             Some(AppAction::ShellJob(ShellJobAction::CancelAll))
         ));
     }
+
+    #[test]
+    fn parses_shell_session_actions() {
+        let mut app = app();
+        let open = shells(&mut app, Some("open dev npm run dev"));
+        assert!(matches!(
+            open.action,
+            Some(AppAction::ShellSession(ShellSessionAction::Open { name, command: Some(command) }))
+                if name == "dev" && command == "npm run dev"
+        ));
+
+        let attach = shells(&mut app, Some("attach dev"));
+        assert!(matches!(
+            attach.action,
+            Some(AppAction::ShellSession(ShellSessionAction::Attach { name })) if name == "dev"
+        ));
+
+        let kill = shells(&mut app, Some("kill dev"));
+        assert!(matches!(
+            kill.action,
+            Some(AppAction::ShellJob(ShellJobAction::Cancel { id })) if id == "dev"
+        ));
+
+        assert!(shells(&mut app, Some("attach")).action.is_none());
+    }
 }
@@ -31,8 +31,9 @@ pub fn mcp(_app: &mut App, args: Option<&str>) -> CommandResult {
         },
         "validate" => CommandResult::action(AppAction::Mcp(McpUiAction::Validate)),
         "reload" | "reconnect" => CommandResult::action(AppAction::Mcp(McpUiAction::Reload)),
+        "prompt" => parse_prompt(parts.collect()),
         _ => CommandResult::error(
-            "Usage: /mcp [init|add stdio <name> <command> [args...]|add http <name> <url>|enable <name>|disable <name>|remove <name>|validate|reload]",
+            "Usage: /mcp [init|add stdio <name> <command> [args...]|add http <name> <url>|enable <name>|disable <name>|remove <name>|validate|reload|prompt <server> <name> [key=value...]]",
         ),
     }
 }
@@ -66,6 +67,27 @@ fn parse_add(parts: Vec<&str>) -> CommandResult {
     }
 }
 
+fn parse_prompt(parts: Vec<&str>) -> CommandResult {
+    const USAGE: &str = "Usage: /mcp prompt <server> <name> [key=value...]";
+    if parts.len() < 2 {
+        return CommandResult::error(USAGE);
+    }
+    let mut arguments = Vec::new();
+    for part in &parts[2..] {
+        match part.split_once('=') {
+            Some((key, value)) if !key.is_empty() => {
+                arguments.push((key.to_string(), value.to_string()));
+            }
+            _ => return CommandResult::error(USAGE),
+        }
+    }
+    CommandResult::action(AppAction::Mcp(McpUiAction::Prompt {
+        server: parts[0].to_string(),
+        name: parts[1].to_string(),
+        arguments,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,4 +138,23 @@ mod tests {
             Some(AppAction::Mcp(McpUiAction::Validate))
         ));
     }
+
+    #[test]
+    fn parses_prompt_with_arguments() {
+        let mut app = app();
+        let result = mcp(&mut app, Some("prompt docs summarize topic=rust depth=2"));
+        assert!(matches!(
+            result.action,
+            Some(AppAction::Mcp(McpUiAction::Prompt { server, name, arguments }))
+                if server == "docs"
+                    && name == "summarize"
+                    && arguments == vec![
+                        ("topic".to_string(), "rust".to_string()),
+                        ("depth".to_string(), "2".to_string()),
+                    ]
+        ));
+
+        let bad = mcp(&mut app, Some("prompt docs summarize nokey"));
+        assert!(bad.action.is_none());
+    }
 }
@@ -3,33 +3,43 @@
 //! This module provides a modular command system inspired by Codex-rs.
 //! Commands are organized by category and dispatched through a central registry.
 
+mod aliases;
 mod anchor;
 mod attachment;
+mod bookmarks;
 mod change;
 mod config;
 mod core;
 mod cycle;
 mod debug;
+mod enhance;
+mod env;
 mod feedback;
 mod goal;
 mod hooks;
+mod inbox;
 mod init;
 mod jobs;
 mod mcp;
 mod memory;
 mod network;
 mod note;
+mod plan;
+mod preview;
 mod provider;
 mod queue;
 mod rename;
+mod repomap;
 mod restore;
 mod review;
 mod session;
 pub mod share;
 mod skills;
+mod snapshot;
 mod stash;
 mod status;
 mod task;
+mod trail;
 mod user_commands;
 
 use std::fmt::Write as _;
@@ -176,6 +186,12 @@ pub const COMMANDS: &[CommandInfo] = &[
         usage: "/models",
         description_id: MessageId::CmdModelsDescription,
     },
+    CommandInfo {
+        name: "thinking",
+        aliases: &["think"],
+        usage: "/thinking [off|low|medium|high|max|auto|budget <n|off>]",
+        description_id: MessageId::CmdThinkingDescription,
+    },
     CommandInfo {
         name: "provider",
         aliases: &[],
@@ -185,7 +201,7 @@ pub const COMMANDS: &[CommandInfo] = &[
     CommandInfo {
         name: "queue",
         aliases: &["queued"],
-        usage: "/queue [list|edit <n>|drop <n>|clear]",
+        usage: "/queue [open|list|edit <n>|drop <n>|clear]",
         description_id: MessageId::CmdQueueDescription,
     },
     CommandInfo {
@@ -218,6 +234,12 @@ pub const COMMANDS: &[CommandInfo] = &[
         usage: "/links",
         description_id: MessageId::CmdLinksDescription,
     },
+    CommandInfo {
+        name: "bookmarks",
+        aliases: &["bookmark", "marks"],
+        usage: "/bookmarks [clear]",
+        description_id: MessageId::CmdBookmarksDescription,
+    },
     CommandInfo {
         name: "feedback",
         aliases: &[],
@@ -236,6 +258,12 @@ pub const COMMANDS: &[CommandInfo] = &[
         usage: "/workspace [path]",
         description_id: MessageId::CmdWorkspaceDescription,
     },
+    CommandInfo {
+        name: "enhance",
+        aliases: &[],
+        usage: "/enhance [draft]",
+        description_id: MessageId::CmdEnhanceDescription,
+    },
     CommandInfo {
         name: "note",
         aliases: &[],
@@ -257,7 +285,7 @@ pub const COMMANDS: &[CommandInfo] = &[
     CommandInfo {
         name: "task",
         aliases: &["tasks"],
-        usage: "/task [add <prompt>|list|show <id>|cancel <id>]",
+        usage: "/task [add <prompt>|list|show <id>|cancel <id>] (bare /task opens the manager)",
         description_id: MessageId::CmdTaskDescription,
     },
     CommandInfo {
@@ -266,10 +294,16 @@ pub const COMMANDS: &[CommandInfo] = &[
         usage: "/jobs [list|show <id>|poll <id>|wait <id>|stdin <id> <input>|cancel <id>]",
         description_id: MessageId::CmdJobsDescription,
     },
+    CommandInfo {
+        name: "shells",
+        aliases: &["shell"],
+        usage: "/shells [list|open <name> [command]|attach <name>|detach|show <name>|kill <name>]",
+        description_id: MessageId::CmdShellsDescription,
+    },
     CommandInfo {
         name: "mcp",
         aliases: &[],
-        usage: "/mcp [init|add stdio <name> <command> [args...]|add http <name> <url>|enable <name>|disable <name>|remove <name>|validate|reload]",
+        usage: "/mcp [init|add stdio <name> <command> [args...]|add http <name> <url>|enable <name>|disable <name>|remove <name>|validate|reload|prompt <server> <name> [key=value...]]",
         description_id: MessageId::CmdMcpDescription,
     },
     CommandInfo {
@@ -300,7 +334,7 @@ pub const COMMANDS: &[CommandInfo] = &[
     CommandInfo {
         name: "sessions",
         aliases: &["resume"],
-        usage: "/sessions [show|prune <days>]",
+        usage: "/sessions [show|prune <days>|pin|unpin [id]|tag|untag <tag> [id]]",
         description_id: MessageId::CmdSessionsDescription,
     },
     CommandInfo {
@@ -309,6 +343,12 @@ pub const COMMANDS: &[CommandInfo] = &[
         usage: "/load [path]",
         description_id: MessageId::CmdLoadDescription,
     },
+    CommandInfo {
+        name: "import",
+        aliases: &[],
+        usage: "/import <path>",
+        description_id: MessageId::CmdImportDescription,
+    },
     CommandInfo {
         name: "compact",
         aliases: &["yasuo"],
@@ -348,7 +388,7 @@ pub const COMMANDS: &[CommandInfo] = &[
     CommandInfo {
         name: "export",
         aliases: &["daochu"],
-        usage: "/export [path]",
+        usage: "/export [path] [--format markdown|json] [--redact thinking,secrets,shell-output]",
         description_id: MessageId::CmdExportDescription,
     },
     // Config commands
@@ -364,6 +404,12 @@ pub const COMMANDS: &[CommandInfo] = &[
         usage: "/mode [agent|plan|yolo|1|2|3]",
         description_id: MessageId::CmdModeDescription,
     },
+    CommandInfo {
+        name: "plan",
+        aliases: &[],
+        usage: "/plan [export [path] [--format markdown|org] | export off]",
+        description_id: MessageId::CmdPlanDescription,
+    },
     CommandInfo {
         name: "theme",
         aliases: &[],
@@ -379,7 +425,7 @@ pub const COMMANDS: &[CommandInfo] = &[
     CommandInfo {
         name: "trust",
         aliases: &["xinren"],
-        usage: "/trust [on|off|add <path>|remove <path>|list]",
+        usage: "/trust [level <untrusted|read-only|standard|full>|on|off|add <path>|remove <path>|list]",
         description_id: MessageId::CmdTrustDescription,
     },
     CommandInfo {
@@ -401,6 +447,12 @@ pub const COMMANDS: &[CommandInfo] = &[
         usage: "/translate",
         description_id: MessageId::CmdTranslateDescription,
     },
+    CommandInfo {
+        name: "smartcontext",
+        aliases: &["smart-context"],
+        usage: "/smartcontext [on|off]",
+        description_id: MessageId::CmdSmartContextDescription,
+    },
     CommandInfo {
         name: "system",
         aliases: &["xitong"],
@@ -419,6 +471,18 @@ pub const COMMANDS: &[CommandInfo] = &[
         usage: "/diff",
         description_id: MessageId::CmdDiffDescription,
     },
+    CommandInfo {
+        name: "changes",
+        aliases: &[],
+        usage: "/changes",
+        description_id: MessageId::CmdChangesDescription,
+    },
+    CommandInfo {
+        name: "inbox",
+        aliases: &[],
+        usage: "/inbox [clear]",
+        description_id: MessageId::CmdInboxDescription,
+    },
     CommandInfo {
         name: "change",
         aliases: &[],
@@ -452,7 +516,7 @@ pub const COMMANDS: &[CommandInfo] = &[
     CommandInfo {
         name: "share",
         aliases: &[],
-        usage: "/share",
+        usage: "/share [start|stop|status]",
         description_id: MessageId::CmdShareDescription,
     },
     CommandInfo {
@@ -479,6 +543,12 @@ pub const COMMANDS: &[CommandInfo] = &[
         usage: "/statusline",
         description_id: MessageId::CmdStatuslineDescription,
     },
+    CommandInfo {
+        name: "keys",
+        aliases: &["keybindings"],
+        usage: "/keys",
+        description_id: MessageId::CmdKeysDescription,
+    },
     // Skills commands
     CommandInfo {
         name: "skills",
@@ -489,13 +559,13 @@ pub const COMMANDS: &[CommandInfo] = &[
     CommandInfo {
         name: "skill",
         aliases: &["jineng"],
-        usage: "/skill <name|install <spec>|update <name>|uninstall <name>|trust <name>>",
+        usage: "/skill <name|install <spec>|update <name>|uninstall <name>|trust <name>|off <name>|on <name>>",
         description_id: MessageId::CmdSkillDescription,
     },
     CommandInfo {
         name: "review",
         aliases: &["shencha"],
-        usage: "/review <target>",
+        usage: "/review <target> | show",
         description_id: MessageId::CmdReviewDescription,
     },
     CommandInfo {
@@ -504,6 +574,36 @@ pub const COMMANDS: &[CommandInfo] = &[
         usage: "/restore [N]",
         description_id: MessageId::CmdRestoreDescription,
     },
+    CommandInfo {
+        name: "snapshot",
+        aliases: &[],
+        usage: "/snapshot [list|save <name>|restore <name>|delete <name>]",
+        description_id: MessageId::CmdSnapshotDescription,
+    },
+    CommandInfo {
+        name: "trail",
+        aliases: &[],
+        usage: "/trail [on|off|clear]",
+        description_id: MessageId::CmdTrailDescription,
+    },
+    CommandInfo {
+        name: "preview",
+        aliases: &[],
+        usage: "/preview <task> | /preview [show|run]",
+        description_id: MessageId::CmdPreviewDescription,
+    },
+    CommandInfo {
+        name: "env",
+        aliases: &[],
+        usage: "/env [list|set [--secret] KEY=VALUE|unset KEY|clear]",
+        description_id: MessageId::CmdEnvDescription,
+    },
+    CommandInfo {
+        name: "repomap",
+        aliases: &[],
+        usage: "/repomap [show|refresh]",
+        description_id: MessageId::CmdRepomapDescription,
+    },
     // RLM command
     CommandInfo {
         name: "rlm",
@@ -522,16 +622,22 @@ pub const COMMANDS: &[CommandInfo] = &[
     CommandInfo {
         name: "profile",
         aliases: &["dangan"],
-        usage: "/profile <name>",
+        usage: "/profile <name> | /profile use <exec-profile|off>",
         description_id: MessageId::CmdHelpDescription, // reuse for now
     },
     // Cache telemetry (#263)
     CommandInfo {
         name: "cache",
         aliases: &[],
-        usage: "/cache [count|inspect|warmup]",
+        usage: "/cache [count|inspect|warmup|clear]",
         description_id: MessageId::CmdCacheDescription,
     },
+    CommandInfo {
+        name: "report",
+        aliases: &[],
+        usage: "/report",
+        description_id: MessageId::CmdReportDescription,
+    },
 ];
 
 /// Execute a slash command
@@ -545,6 +651,13 @@ pub fn execute(cmd: &str, app: &mut App) -> CommandResult {
     if let Some(result) = user_commands::try_dispatch_user_command(app, cmd.trim()) {
         return result;
     }
+    // Project aliases come from the repository, so they never shadow a
+    // built-in.
+    if get_command_info(command).is_none()
+        && let Some(result) = aliases::try_dispatch_alias(app, command, arg.unwrap_or(""))
+    {
+        return result;
+    }
 
     // Match command or alias
     match command {
@@ -555,6 +668,7 @@ pub fn execute(cmd: &str, app: &mut App) -> CommandResult {
         "exit" | "quit" | "q" | "tuichu" => core::exit(),
         "model" | "moxing" => core::model(app, arg),
         "models" | "moxingliebiao" => core::models(app),
+        "thinking" | "think" => core::thinking(app, arg),
         "provider" => provider::provider(app, arg),
         "queue" | "queued" => queue::queue(app, arg),
         "stash" | "park" => stash::stash(app, arg),
@@ -562,14 +676,17 @@ pub fn execute(cmd: &str, app: &mut App) -> CommandResult {
         "subagents" | "agents" | "zhinengti" => core::subagents(app),
         "agent" | "daili" => agent(app, arg),
         "links" | "dashboard" | "api" | "lianjie" => core::deepseek_links(app),
+        "bookmarks" | "bookmark" | "marks" => bookmarks::bookmarks(app, arg),
         "feedback" => feedback::feedback(app, arg),
         "home" | "stats" | "overview" | "zhuye" | "shouye" => core::home_dashboard(app),
         "workspace" | "cwd" => core::workspace_switch(app, arg),
         "note" => note::note(app, arg),
+        "enhance" => enhance::enhance(app, arg),
         "memory" => memory::memory(app, arg),
         "attach" | "image" | "media" | "fujian" => attachment::attach(app, arg),
         "task" | "tasks" => task::task(app, arg),
         "jobs" | "job" | "zuoye" => jobs::jobs(app, arg),
+        "shells" | "shell" => jobs::shells(app, arg),
         "mcp" => mcp::mcp(app, arg),
         "network" => network::network(app, arg),
 
@@ -580,6 +697,7 @@ pub fn execute(cmd: &str, app: &mut App) -> CommandResult {
         "sessions" | "resume" => session::sessions(app, arg),
         "relay" | "batonpass" | "接力" => relay(app, arg),
         "load" | "jiazai" => session::load(app, arg),
+        "import" => session::import(app, arg),
         "compact" | "yasuo" => session::compact(app),
         "cycles" | "zhouqi" => cycle::list_cycles(app),
         "cycle" => cycle::show_cycle(app, arg),
@@ -591,9 +709,11 @@ pub fn execute(cmd: &str, app: &mut App) -> CommandResult {
         "settings" => config::show_settings(app),
         "status" => status::status(app),
         "statusline" => config::status_line(app),
+        "keys" | "keybindings" => config::keys(app),
         "mode" => config::mode(app, arg),
         "jihua" => config::mode(app, Some("plan")),
         "zidong" => config::mode(app, Some("yolo")),
+        "plan" => plan::plan(app, arg),
         "theme" => config::theme(app, arg),
         "verbose" => config::verbose(app, arg),
         "trust" | "xinren" => config::trust(app, arg),
@@ -601,9 +721,11 @@ pub fn execute(cmd: &str, app: &mut App) -> CommandResult {
 
         // Debug commands
         "translate" | "translation" | "transale" => core::translate(app),
+        "smartcontext" | "smart-context" => core::smart_context(app, arg),
         "tokens" => debug::tokens(app),
         "cost" => debug::cost(app),
         "cache" => debug::cache(app, arg),
+        "report" => debug::report(),
 
         // ChangeLog command
         "change" => change::change(app, arg),
@@ -611,6 +733,8 @@ pub fn execute(cmd: &str, app: &mut App) -> CommandResult {
         "context" | "ctx" => debug::context(app),
         "edit" => debug::edit(app),
         "diff" => debug::diff(app),
+        "changes" => debug::changes(app),
+        "inbox" => inbox::inbox(app, arg),
         "undo" => {
             // Try surgical patch-undo first; fall back to conversation undo
             // if no snapshots are available or if the snapshot undo couldn't
@@ -639,6 +763,11 @@ pub fn execute(cmd: &str, app: &mut App) -> CommandResult {
         "skill" | "jineng" => skills::run_skill(app, arg),
         "review" | "shencha" => review::review(app, arg),
         "restore" => restore::restore(app, arg),
+        "snapshot" => snapshot::snapshot(app, arg),
+        "trail" => trail::trail(app, arg),
+        "preview" => preview::preview(app, arg),
+        "env" => env::env(app, arg),
+        "repomap" => repomap::repomap(app, arg),
 
         // Profile switch (#390)
         "profile" | "dangan" => core::profile_switch(app, arg),
@@ -706,6 +835,7 @@ pub fn auto_model_heuristic(input: &str, current_model: &str) -> String {
     config::auto_model_heuristic(input, current_model)
 }
 
+pub use aliases::alias_summary;
 pub use config::{
     AutoRouteRecommendation, AutoRouteSelection, normalize_auto_route_effort,
     parse_auto_route_recommendation, resolve_auto_route_with_flash,
@@ -960,8 +1090,11 @@ pub fn all_command_names_matching(
         .map(|cmd| format!("/{}", cmd.name))
         .collect();
 
-    // Add user-defined commands
+    // Add user-defined commands and project aliases
     result.extend(user_commands::user_commands_matching(&prefix, workspace));
+    if let Some(workspace) = workspace {
+        result.extend(aliases::alias_names_matching(&prefix, workspace));
+    }
 
     result.sort();
     result.dedup();
@@ -1161,6 +1294,8 @@ mod tests {
                     step: "keep checklist primary".to_string(),
                     status: StepStatus::InProgress,
                 }],
+                open_questions: None,
+                decisions: None,
             });
         }
 
@@ -1271,6 +1406,56 @@ mod tests {
         assert!(matches!(result.action, Some(AppAction::CacheWarmup)));
     }
 
+    #[test]
+    fn enhance_uses_the_held_draft_when_no_argument_is_given() {
+        let mut app = create_test_app();
+        let result = execute("/enhance", &mut app);
+        assert!(result.is_error);
+
+        app.prompt_hint = crate::tui::prompt_lint::lint("fix it");
+        let result = execute("/enhance", &mut app);
+        assert!(matches!(result.action, Some(AppAction::EnhancePrompt(ref d)) if d == "fix it"));
+
+        let result = execute("/enhance make it work", &mut app);
+        assert!(
+            matches!(result.action, Some(AppAction::EnhancePrompt(ref d)) if d == "make it work")
+        );
+    }
+
+    #[test]
+    fn cache_clear_dispatches_action() {
+        let mut app = create_test_app();
+        let result = execute("/cache clear", &mut app);
+        assert!(result.message.is_none());
+        assert!(matches!(
+            result.action,
+            Some(AppAction::ClearToolResultCache)
+        ));
+    }
+
+    #[test]
+    fn keys_lists_bindings_from_app_keymap() {
+        let mut app = create_test_app();
+        let mut keys = std::collections::BTreeMap::new();
+        keys.insert(
+            "open_palette".to_string(),
+            crate::config::KeyChordsToml::One("ctrl+j".to_string()),
+        );
+        app.keymap = crate::tui::keymap::Keymap::from_config(&keys);
+        let result = execute("/keys", &mut app);
+        let message = result.message.expect("keys message");
+        assert!(message.contains("Ctrl+J (config), Ctrl+K"), "{message}");
+        assert!(message.contains("No conflicts."), "{message}");
+    }
+
+    #[test]
+    fn report_dispatches_action() {
+        let mut app = create_test_app();
+        let result = execute("/report", &mut app);
+        assert!(result.message.is_none());
+        assert!(matches!(result.action, Some(AppAction::GenerateReport)));
+    }
+
     #[test]
     fn execute_config_opens_config_view_action() {
         let mut app = create_test_app();
@@ -1428,13 +1613,13 @@ mod tests {
         }
     }
 
-    /// `/restore` is covered by its own dedicated tests in
-    /// `commands/restore.rs` that serialize on the global env mutex via
-    /// `scoped_home` (snapshot repo init shells out to git, which races
-    /// against parallel-running tests). Skip it here so this smoke test
-    /// stays parallel-safe.
+    /// `/restore` and `/snapshot` are covered by their own dedicated tests
+    /// in `commands/restore.rs` and `commands/snapshot.rs` that serialize
+    /// on the global env mutex via `scoped_home` (snapshot repo init shells
+    /// out to git, which races against parallel-running tests). Skip them
+    /// here so this smoke test stays parallel-safe.
     fn skip_in_dispatch_smoke(name: &str) -> bool {
-        name == "restore"
+        matches!(name, "restore" | "snapshot")
     }
 
     /// Smoke test: every entry in `COMMANDS` must dispatch to a real handler.
@@ -1509,4 +1694,45 @@ mod tests {
         assert!(msg.contains("Unknown command: /zzzzzz"));
         assert!(msg.contains("Type /help for available commands."));
     }
+
+    #[test]
+    fn plan_export_writes_once_a_plan_exists_and_follows_updates() {
+        let workspace = tempdir().expect("tempdir");
+        let mut app = create_test_app();
+        app.workspace = workspace.path().to_path_buf();
+        let path = workspace.path().join("docs").join("plan.md");
+
+        let result = execute("/plan export docs/plan.md", &mut app);
+        assert!(!result.is_error, "{:?}", result.message);
+        assert!(
+            result
+                .message
+                .as_deref()
+                .unwrap_or("")
+                .contains("No plan yet")
+        );
+        assert!(!path.exists());
+
+        app.plan_state
+            .try_lock()
+            .expect("plan lock")
+            .update(UpdatePlanArgs {
+                explanation: Some("Export plans".to_string()),
+                plan: vec![PlanItemArg {
+                    step: "Render the document".to_string(),
+                    status: StepStatus::Completed,
+                }],
+                open_questions: Some(vec!["Org too?".to_string()]),
+                decisions: None,
+            });
+        assert_eq!(crate::tui::plan_export::sync(&mut app), None);
+        let doc = std::fs::read_to_string(&path).expect("plan written");
+        assert!(doc.contains("1. [x] Render the document"), "{doc}");
+        assert!(doc.contains("- Org too?"), "{doc}");
+
+        let result = execute("/plan export off", &mut app);
+        assert!(result.message.as_deref().unwrap_or("").contains("Stopped"));
+        assert!(app.plan_export.is_none());
+        assert!(execute("/plan nope", &mut app).is_error);
+    }
 }
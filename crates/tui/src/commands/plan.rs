@@ -0,0 +1,118 @@
+//! Plan commands: preview the current plan or export it as a live document
+
+use std::path::PathBuf;
+
+use crate::tui::app::App;
+use crate::tui::plan_export::{self, DEFAULT_FILE, PlanExport, PlanFormat};
+
+use super::CommandResult;
+
+const USAGE: &str = "Usage: /plan [export [path] [--format markdown|org] | export off]";
+
+pub fn plan(app: &mut App, args: Option<&str>) -> CommandResult {
+    let arg = args.unwrap_or("").trim();
+    let (sub, rest) = arg
+        .split_once(char::is_whitespace)
+        .map_or((arg, ""), |(sub, rest)| (sub, rest.trim()));
+    match sub.to_ascii_lowercase().as_str() {
+        "" | "show" => show(app),
+        "export" => export(app, rest),
+        _ => CommandResult::error(USAGE),
+    }
+}
+
+fn show(app: &App) -> CommandResult {
+    let Ok(plan) = app.plan_state.try_lock() else {
+        return CommandResult::error("Plan state is busy; try again in a moment.");
+    };
+    let snapshot = plan.snapshot();
+    if plan_export::snapshot_is_empty(&snapshot) {
+        return CommandResult::message(
+            "No plan yet. Switch to plan mode (/mode plan) and ask for one.",
+        );
+    }
+    let mut out = plan_export::render(&snapshot, PlanFormat::Markdown);
+    if let Some(export) = app.plan_export.as_ref() {
+        out.push_str(&format!("\nExporting to {}", export.path.display()));
+    }
+    CommandResult::message(out)
+}
+
+fn export(app: &mut App, rest: &str) -> CommandResult {
+    if rest.eq_ignore_ascii_case("off") || rest.eq_ignore_ascii_case("stop") {
+        return match app.plan_export.take() {
+            Some(export) => CommandResult::message(format!(
+                "Stopped updating {}. The file is left as it is.",
+                export.path.display()
+            )),
+            None => CommandResult::message("Plan export is not active."),
+        };
+    }
+
+    let (path, format) = match parse_export_args(rest) {
+        Ok(parsed) => parsed,
+        Err(err) => return CommandResult::error(format!("{err}. {USAGE}")),
+    };
+    let path = path.unwrap_or_else(|| PathBuf::from(DEFAULT_FILE));
+    let path = if path.is_absolute() {
+        path
+    } else {
+        app.workspace.join(path)
+    };
+    let format = format.unwrap_or_else(|| PlanFormat::from_path(&path));
+    let export = PlanExport { path, format };
+
+    let Ok(plan) = app.plan_state.try_lock() else {
+        return CommandResult::error("Plan state is busy; try again in a moment.");
+    };
+    let snapshot = plan.snapshot();
+    drop(plan);
+    let message = if plan_export::snapshot_is_empty(&snapshot) {
+        format!(
+            "No plan yet. {} will be written ({}) once the model records one.",
+            export.path.display(),
+            format.label()
+        )
+    } else {
+        if let Err(err) = export.write(&snapshot) {
+            return CommandResult::error(format!("Failed to export plan: {err}"));
+        }
+        format!(
+            "Exported plan to {} ({}). It updates whenever the plan changes; /plan export off stops.",
+            export.path.display(),
+            format.label()
+        )
+    };
+    app.plan_export = Some(export);
+    CommandResult::message(message)
+}
+
+/// Split `/plan export` arguments into a path and a `--format` flag. Like
+/// `/export`, whatever is not a flag is the path.
+fn parse_export_args(arg: &str) -> Result<(Option<PathBuf>, Option<PlanFormat>), String> {
+    let mut path_parts = Vec::new();
+    let mut format = None;
+    let mut tokens = arg.split_whitespace();
+    while let Some(token) = tokens.next() {
+        let (flag, inline) = match token.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+            _ => (token, None),
+        };
+        match flag {
+            "--format" => {
+                let value = inline
+                    .or_else(|| tokens.next())
+                    .ok_or_else(|| "--format needs a value".to_string())?;
+                format = Some(match value.to_ascii_lowercase().as_str() {
+                    "markdown" | "md" => PlanFormat::Markdown,
+                    "org" => PlanFormat::Org,
+                    other => return Err(format!("unknown format `{other}`")),
+                });
+            }
+            _ if token.starts_with("--") => return Err(format!("unknown flag `{token}`")),
+            _ => path_parts.push(token),
+        }
+    }
+    let path = (!path_parts.is_empty()).then(|| PathBuf::from(path_parts.join(" ")));
+    Ok((path, format))
+}
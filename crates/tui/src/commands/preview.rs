@@ -0,0 +1,123 @@
+//! `/preview` — show the tool plan Agent mode would run, without running it.
+
+use crate::tui::app::{App, AppAction, AppMode};
+use crate::tui::history::HistoryCell;
+use crate::tui::plan_preview::PREVIEW_INSTRUCTION;
+
+use super::CommandResult;
+
+const USAGE: &str = "/preview <task> | /preview [show|run]";
+
+pub fn preview(app: &mut App, args: Option<&str>) -> CommandResult {
+    let arg = args.unwrap_or("").trim();
+    match arg.to_ascii_lowercase().as_str() {
+        "" | "show" => {
+            if app.plan_preview.is_some() {
+                CommandResult::action(AppAction::OpenPlanPreview)
+            } else {
+                CommandResult::error(format!("No plan preview yet. Usage: {USAGE}"))
+            }
+        }
+        "run" | "execute" => {
+            if app.plan_preview.is_some() {
+                CommandResult::action(AppAction::ExecutePlanPreview)
+            } else {
+                CommandResult::error("No plan preview to run. Start one with /preview <task>.")
+            }
+        }
+        _ => start_preview(app, arg),
+    }
+}
+
+fn start_preview(app: &mut App, task: &str) -> CommandResult {
+    if app.is_loading {
+        return CommandResult::error(
+            "Wait for the current turn to finish before starting a preview.",
+        );
+    }
+    if app.mode != AppMode::Plan {
+        // Plan mode only exposes read-only tools, so the preview turn
+        // cannot change anything even if the model ignores the instruction.
+        app.set_mode(AppMode::Plan);
+        app.add_message(HistoryCell::System {
+            content: "Switched to Plan mode for the preview; nothing will be executed.".to_string(),
+        });
+    }
+    app.plan_preview = None;
+    app.plan_preview_request = Some(task.to_string());
+    app.active_skill = Some(PREVIEW_INSTRUCTION.to_string());
+    CommandResult::action(AppAction::SendMessage(task.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::tui::app::TuiOptions;
+    use crate::tui::plan_preview::PlanPreview;
+    use tempfile::TempDir;
+
+    fn create_test_app(tmpdir: &TempDir) -> App {
+        let options = TuiOptions {
+            model: "deepseek-v4-pro".to_string(),
+            workspace: tmpdir.path().to_path_buf(),
+            config_path: None,
+            config_profile: None,
+            allow_shell: false,
+            use_alt_screen: true,
+            use_mouse_capture: false,
+            use_bracketed_paste: true,
+            max_subagents: 1,
+            skills_dir: tmpdir.path().join("skills"),
+            memory_path: tmpdir.path().join("memory.md"),
+            notes_path: tmpdir.path().join("notes.txt"),
+            mcp_config_path: tmpdir.path().join("mcp.json"),
+            use_memory: false,
+            start_in_agent_mode: true,
+            skip_onboarding: true,
+            yolo: false,
+            resume_session_id: None,
+            initial_input: None,
+        };
+        App::new(options, &Config::default())
+    }
+
+    #[test]
+    fn preview_switches_to_plan_mode_and_sends_with_instruction() {
+        let tmpdir = TempDir::new().unwrap();
+        let mut app = create_test_app(&tmpdir);
+        app.mode = AppMode::Agent;
+
+        let result = preview(&mut app, Some("rename the parser"));
+
+        assert_eq!(app.mode, AppMode::Plan);
+        assert_eq!(
+            app.plan_preview_request.as_deref(),
+            Some("rename the parser")
+        );
+        assert_eq!(app.active_skill.as_deref(), Some(PREVIEW_INSTRUCTION));
+        assert!(matches!(
+            result.action,
+            Some(AppAction::SendMessage(ref text)) if text == "rename the parser"
+        ));
+    }
+
+    #[test]
+    fn run_and_show_need_an_existing_preview() {
+        let tmpdir = TempDir::new().unwrap();
+        let mut app = create_test_app(&tmpdir);
+
+        assert!(preview(&mut app, Some("run")).is_error);
+        assert!(preview(&mut app, None).is_error);
+
+        app.plan_preview = PlanPreview::parse("x", "1. [risk: low] read_file — look");
+        assert!(matches!(
+            preview(&mut app, Some("run")).action,
+            Some(AppAction::ExecutePlanPreview)
+        ));
+        assert!(matches!(
+            preview(&mut app, None).action,
+            Some(AppAction::OpenPlanPreview)
+        ));
+    }
+}
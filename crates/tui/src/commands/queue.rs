@@ -1,6 +1,6 @@
-//! Queue commands: queue list/edit/drop/clear
+//! Queue commands: queue open/list/edit/drop/clear
 
-use crate::tui::app::App;
+use crate::tui::app::{App, AppAction};
 
 use super::CommandResult;
 
@@ -8,7 +8,15 @@ const PREVIEW_LIMIT: usize = 120;
 
 pub fn queue(app: &mut App, args: Option<&str>) -> CommandResult {
     let arg = args.unwrap_or("").trim();
-    if arg.is_empty() || arg.eq_ignore_ascii_case("list") {
+    if arg.is_empty() || arg.eq_ignore_ascii_case("open") {
+        // The modal edits entries in place; with nothing queued, fall back
+        // to the listing so a stray `/queue` still says why nothing opened.
+        if app.queued_message_count() > 0 {
+            return CommandResult::action(AppAction::OpenQueueEditor);
+        }
+        return list_queue(app);
+    }
+    if arg.eq_ignore_ascii_case("list") {
         return list_queue(app);
     }
 
@@ -19,7 +27,7 @@ pub fn queue(app: &mut App, args: Option<&str>) -> CommandResult {
         "edit" => edit_queue(app, parts.next()),
         "drop" | "remove" | "rm" => drop_queue(app, parts.next()),
         "clear" => clear_queue(app),
-        _ => CommandResult::error("Usage: /queue [list|edit <n>|drop <n>|clear]"),
+        _ => CommandResult::error("Usage: /queue [open|list|edit <n>|drop <n>|clear]"),
     }
 }
 
@@ -48,7 +56,10 @@ fn list_queue(app: &mut App) -> CommandResult {
         ));
     }
 
-    lines.push("Tip: /queue edit <n> to edit, /queue drop <n> to remove".to_string());
+    lines.push(
+        "Tip: /queue opens the editor (edit, reorder, per-message mode); /queue drop <n> removes"
+            .to_string(),
+    );
 
     CommandResult::message(lines.join("\n"))
 }
@@ -186,6 +197,16 @@ mod tests {
         assert!(msg.contains("2. Second message"));
     }
 
+    #[test]
+    fn test_queue_without_args_opens_editor_when_messages_are_queued() {
+        let tmpdir = TempDir::new().unwrap();
+        let mut app = create_test_app_with_tmpdir(&tmpdir);
+        app.queued_messages
+            .push_back(QueuedMessage::new("First message".to_string(), None));
+        let result = queue(&mut app, None);
+        assert!(matches!(result.action, Some(AppAction::OpenQueueEditor)));
+    }
+
     #[test]
     fn test_queue_edit_missing_index() {
         let tmpdir = TempDir::new().unwrap();
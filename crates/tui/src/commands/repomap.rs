@@ -0,0 +1,106 @@
+//! `/repomap` slash command — inspect or rebuild the workspace repo map.
+//!
+//! The map is rebuilt automatically when git `HEAD` moves; `/repomap
+//! refresh` covers the other case, uncommitted edits (new files, renamed
+//! symbols) that the cached map does not know about yet.
+
+use super::CommandResult;
+use crate::repo_map::{self, DEFAULT_REPO_MAP_TOKENS, RepoMap};
+use crate::tui::app::App;
+
+const USAGE: &str = "Usage: /repomap [show|refresh]";
+
+/// Entry point for `/repomap [show|refresh]`.
+pub fn repomap(app: &mut App, arg: Option<&str>) -> CommandResult {
+    match arg.map(str::trim).unwrap_or_default() {
+        "" | "show" => show(app, false),
+        "refresh" | "rebuild" => show(app, true),
+        _ => CommandResult::error(USAGE),
+    }
+}
+
+fn show(app: &App, refresh: bool) -> CommandResult {
+    let map = repo_map::load_or_build(&app.workspace, refresh);
+    if map.total_files == 0 {
+        return CommandResult::message(
+            "Repo map: no recognised source files in this workspace, so no map is injected.",
+        );
+    }
+    let mut out = summary_line(&map, refresh);
+    out.push_str("\n\n");
+    out.push_str(&map.render(DEFAULT_REPO_MAP_TOKENS));
+    CommandResult::message(out)
+}
+
+fn summary_line(map: &RepoMap, refreshed: bool) -> String {
+    let head = map.head.as_deref().map_or_else(
+        || "no git HEAD".to_string(),
+        |head| format!("HEAD {}", head.chars().take(12).collect::<String>()),
+    );
+    format!(
+        "Repo map {} ({head}): {} files, {} LOC, {} symbols{}. \
+         The prompt copy is cut to [context] repo_map_tokens.",
+        if refreshed { "rebuilt" } else { "cached" },
+        map.total_files,
+        map.total_loc,
+        map.symbol_count(),
+        if map.truncated { ", walk capped" } else { "" },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::tui::app::TuiOptions;
+    use tempfile::TempDir;
+
+    fn create_test_app(workspace: &std::path::Path) -> App {
+        let options = TuiOptions {
+            model: "deepseek-v4-pro".to_string(),
+            workspace: workspace.to_path_buf(),
+            config_path: None,
+            config_profile: None,
+            allow_shell: false,
+            use_alt_screen: true,
+            use_mouse_capture: false,
+            use_bracketed_paste: true,
+            max_subagents: 1,
+            skills_dir: workspace.join("skills"),
+            memory_path: workspace.join("memory.md"),
+            notes_path: workspace.join("notes.txt"),
+            mcp_config_path: workspace.join("mcp.json"),
+            use_memory: false,
+            start_in_agent_mode: false,
+            skip_onboarding: true,
+            yolo: false,
+            resume_session_id: None,
+            initial_input: None,
+        };
+        App::new(options, &Config::default())
+    }
+
+    #[test]
+    fn refresh_picks_up_new_files() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("lib.rs"), "pub fn first() {}\n").unwrap();
+        let mut app = create_test_app(tmp.path());
+
+        let shown = repomap(&mut app, None).message.unwrap();
+        assert!(shown.contains("fn first"));
+
+        std::fs::write(tmp.path().join("extra.rs"), "pub struct Added;\n").unwrap();
+        let refreshed = repomap(&mut app, Some("refresh")).message.unwrap();
+        assert!(refreshed.starts_with("Repo map rebuilt"));
+        assert!(refreshed.contains("struct Added"));
+    }
+
+    #[test]
+    fn unknown_subcommand_shows_usage() {
+        let tmp = TempDir::new().unwrap();
+        let mut app = create_test_app(tmp.path());
+        let result = repomap(&mut app, Some("bogus"));
+        assert!(result.is_error);
+        assert!(result.message.unwrap().contains(USAGE));
+    }
+}
@@ -1,8 +1,16 @@
-//! Review command: activate review skill and send a target immediately.
+//! Review command: activate review skill and send a target immediately, or
+//! (`/review show`) lay the latest review's findings over the current diff.
+
+use std::path::Path;
+use std::process::Command;
 
 use crate::skills::{SkillRegistry, default_skills_dir};
 use crate::tui::app::{App, AppAction};
-use crate::tui::history::HistoryCell;
+use crate::tui::history::{HistoryCell, ToolCell};
+use crate::tui::review_diff_view::{
+    ReviewDiffView, ReviewFinding, findings_from_review, findings_from_text,
+};
+use crate::tui::views::ModalKind;
 
 use super::CommandResult;
 
@@ -17,7 +25,10 @@ fn warnings_suffix(registry: &SkillRegistry) -> String {
 pub fn review(app: &mut App, args: Option<&str>) -> CommandResult {
     let target = args.unwrap_or("").trim();
     if target.is_empty() {
-        return CommandResult::error("Usage: /review <target>");
+        return CommandResult::error("Usage: /review <target> | /review show");
+    }
+    if target == "show" {
+        return show(app);
     }
 
     let skills_dir = app.skills_dir.clone();
@@ -62,6 +73,58 @@ pub fn review(app: &mut App, args: Option<&str>) -> CommandResult {
     CommandResult::action(AppAction::SendMessage(target.to_string()))
 }
 
+/// Open the latest review's findings inline on the uncommitted diff.
+fn show(app: &mut App) -> CommandResult {
+    let Some(findings) = latest_findings(&app.history) else {
+        return CommandResult::error(
+            "No review findings with file locations yet. Run /review <target> first.",
+        );
+    };
+    let diff = working_tree_diff(&app.workspace);
+    if app.view_stack.top_kind() != Some(ModalKind::ReviewDiff) {
+        let view = ReviewDiffView::new(
+            "Review",
+            &diff,
+            findings,
+            &app.review_addressed,
+            &app.workspace,
+        );
+        app.view_stack.push(view);
+    }
+    CommandResult::ok()
+}
+
+/// Findings of the most recent review: a structured `review` tool result, or
+/// the last assistant reply that cites `path:line` locations.
+fn latest_findings(history: &[HistoryCell]) -> Option<Vec<ReviewFinding>> {
+    history.iter().rev().find_map(|cell| {
+        let findings = match cell {
+            HistoryCell::Tool(ToolCell::Review(review)) => {
+                findings_from_review(review.output.as_ref()?)
+            }
+            HistoryCell::Assistant { content, .. } => findings_from_text(content),
+            _ => return None,
+        };
+        (!findings.is_empty()).then_some(findings)
+    })
+}
+
+/// Staged and unstaged changes against `HEAD`; plain `git diff` when the
+/// repository has no commits yet. Empty outside a git repository.
+fn working_tree_diff(workspace: &Path) -> String {
+    let run = |args: &[&str]| {
+        Command::new("git")
+            .args(["diff", "--no-color", "--no-ext-diff"])
+            .args(args)
+            .current_dir(workspace)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    run(&["HEAD"]).or_else(|| run(&[])).unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,6 +176,22 @@ mod tests {
         assert!(result.message.unwrap().contains("Usage: /review"));
     }
 
+    #[test]
+    fn review_show_opens_findings_from_the_last_review() {
+        let tmpdir = TempDir::new().unwrap();
+        let mut app = create_test_app_with_tmpdir(&tmpdir);
+        let result = review(&mut app, Some("show"));
+        assert!(result.message.unwrap().contains("No review findings"));
+
+        app.history.push(HistoryCell::Assistant {
+            content: "- **Bug**: `src/lib.rs:12` unwraps a None".to_string(),
+            streaming: false,
+        });
+        let result = review(&mut app, Some("show"));
+        assert!(result.message.is_none());
+        assert_eq!(app.view_stack.top_kind(), Some(ModalKind::ReviewDiff));
+    }
+
     #[test]
     fn test_review_without_skill_installed() {
         let tmpdir = TempDir::new().unwrap();
@@ -1,13 +1,16 @@
 //! Session commands: save, load, compact, export
 
-use std::fmt::Write;
 use std::path::PathBuf;
 
 use crate::session_manager::{
     create_saved_session_with_id_and_mode, create_saved_session_with_mode,
 };
+use crate::transcript_export::{
+    EntryKind, ExportEntry, ExportFormat, Redaction, SHELL_OUTPUT_REMOVED, Transcript,
+    is_shell_tool,
+};
 use crate::tui::app::{App, AppAction};
-use crate::tui::history::{HistoryCell, history_cells_from_message};
+use crate::tui::history::{HistoryCell, ToolCell, history_cells_from_message};
 use crate::tui::session_picker::SessionPickerView;
 
 use super::CommandResult;
@@ -203,6 +206,74 @@ pub fn load(app: &mut App, path: Option<&str>) -> CommandResult {
     )
 }
 
+/// Import a markdown conversation or plain notes into the current session.
+/// `## User` / `## Assistant` sections are appended to the history; a final
+/// user section, or a file with no role markers at all, lands in the
+/// composer so it can be reviewed before sending.
+pub fn import(app: &mut App, path: Option<&str>) -> CommandResult {
+    let Some(path) = path.map(str::trim).filter(|p| !p.is_empty()) else {
+        return CommandResult::error("Usage: /import <path>");
+    };
+    let import_path = PathBuf::from(path);
+    let import_path = if import_path.is_absolute() {
+        import_path
+    } else {
+        app.workspace.join(import_path)
+    };
+    let text = match std::fs::read_to_string(&import_path) {
+        Ok(text) => text,
+        Err(e) => {
+            return CommandResult::error(format!("Failed to read {}: {e}", import_path.display()));
+        }
+    };
+
+    let (mut messages, skipped) = crate::session_import::parse_markdown(&text);
+    if messages.is_empty() {
+        return CommandResult::error(format!("{} has nothing to import", import_path.display()));
+    }
+    let draft = crate::session_import::take_trailing_user_text(&mut messages);
+    let imported = messages.len();
+
+    let mut summary = format!(
+        "Imported {imported} message(s) from {}",
+        import_path.display()
+    );
+    if let Some(draft) = draft {
+        app.input = draft;
+        app.cursor_position = app.input.len();
+        summary.push_str("; the last user turn is in the composer");
+    }
+    if !skipped.is_empty() {
+        summary.push_str(&format!(
+            "; skipped {} section(s) with no chat equivalent",
+            skipped.len()
+        ));
+    }
+    if imported == 0 {
+        return CommandResult::message(summary);
+    }
+
+    let cells: Vec<_> = messages
+        .iter()
+        .flat_map(history_cells_from_message)
+        .collect();
+    app.api_messages.extend(messages);
+    app.extend_history(cells);
+    app.mark_history_updated();
+    app.scroll_to_bottom();
+
+    CommandResult::with_message_and_action(
+        summary,
+        AppAction::SyncSession {
+            session_id: app.current_session_id.clone(),
+            messages: app.api_messages.clone(),
+            system_prompt: app.system_prompt.clone(),
+            model: app.model.clone(),
+            workspace: app.workspace.clone(),
+        },
+    )
+}
+
 /// Trigger context compaction
 pub fn compact(_app: &mut App) -> CommandResult {
     // Trigger immediate compaction via engine
@@ -212,65 +283,159 @@ pub fn compact(_app: &mut App) -> CommandResult {
     )
 }
 
-/// Export conversation to markdown
-pub fn export(app: &mut App, path: Option<&str>) -> CommandResult {
-    let export_path = path.map_or_else(
-        || {
-            let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-            PathBuf::from(format!("chat_export_{timestamp}.md"))
-        },
-        PathBuf::from,
-    );
-
-    let mut content = String::new();
-    content.push_str("# Chat Export\n\n");
-    let _ = write!(
-        content,
-        "**Model:** {}\n**Workspace:** {}\n**Date:** {}\n\n---\n\n",
-        app.model,
-        app.workspace.display(),
-        chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
-    );
+/// Export the conversation as Markdown or JSON, optionally redacted.
+pub fn export(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let (path, format, redaction) = match parse_export_args(arg.unwrap_or("")) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return CommandResult::error(format!(
+                "{err}. usage: /export [path] [--format markdown|json] [--redact thinking,secrets,shell-output]"
+            ));
+        }
+    };
+    let export_path = path.unwrap_or_else(|| {
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let extension = match format {
+            Some(ExportFormat::Json) => "json",
+            _ => "md",
+        };
+        PathBuf::from(format!("chat_export_{timestamp}.{extension}"))
+    });
+    let format = format.unwrap_or_else(|| ExportFormat::from_path(&export_path));
 
+    let mut transcript = Transcript::new(&app.model, &app.workspace, redaction);
     for cell in &app.history {
-        let (role, body) = match cell {
-            HistoryCell::User { content } => ("**You:**", content.clone()),
-            HistoryCell::Assistant { content, .. } => ("**Assistant:**", content.clone()),
-            HistoryCell::System { content } => ("*System:*", content.clone()),
-            HistoryCell::Error { message, severity } => match severity {
-                crate::error_taxonomy::ErrorSeverity::Warning => ("**Warning:**", message.clone()),
-                crate::error_taxonomy::ErrorSeverity::Info => ("*Info:*", message.clone()),
-                _ => ("**Error:**", message.clone()),
-            },
-            HistoryCell::Thinking { content, .. } => ("*Thinking:*", content.clone()),
-            HistoryCell::Tool(tool) => ("**Tool:**", render_tool_cell(tool, 80)),
-            HistoryCell::SubAgent(sub) => ("**Sub-agent:**", render_subagent_cell(sub, 80)),
+        let entry = match cell {
+            HistoryCell::User { content } => ExportEntry::text(EntryKind::User, content.clone()),
+            HistoryCell::Assistant { content, .. } => {
+                ExportEntry::text(EntryKind::Assistant, content.clone())
+            }
+            HistoryCell::System { content } => {
+                ExportEntry::text(EntryKind::System, content.clone())
+            }
+            HistoryCell::Error { message, severity } => {
+                let kind = match severity {
+                    crate::error_taxonomy::ErrorSeverity::Warning => EntryKind::Warning,
+                    crate::error_taxonomy::ErrorSeverity::Info => EntryKind::Info,
+                    _ => EntryKind::Error,
+                };
+                ExportEntry::text(kind, message.clone())
+            }
+            HistoryCell::Thinking { content, .. } => {
+                ExportEntry::text(EntryKind::Thinking, content.clone())
+            }
+            HistoryCell::Tool(tool) => {
+                let text = if redaction.shell_output {
+                    render_tool_cell(&without_shell_output(tool), 80)
+                } else {
+                    render_tool_cell(tool, 80)
+                };
+                ExportEntry::text(EntryKind::Tool, text)
+            }
+            HistoryCell::SubAgent(sub) => {
+                ExportEntry::text(EntryKind::SubAgent, render_subagent_cell(sub, 80))
+            }
             HistoryCell::ArchivedContext {
                 level,
                 range,
                 summary,
                 ..
-            } => (
-                "**Archived Context:**",
+            } => ExportEntry::text(
+                EntryKind::ArchivedContext,
                 format!("L{level} [{range}]: {summary}"),
             ),
         };
-
-        let _ = write!(content, "{}\n\n{}\n\n---\n\n", role, body.trim());
+        transcript.push(entry);
     }
 
-    match std::fs::write(&export_path, content) {
-        Ok(()) => CommandResult::message(format!("Exported to {}", export_path.display())),
+    match std::fs::write(&export_path, transcript.render(format)) {
+        Ok(()) => {
+            let redacted = redaction.labels();
+            if redacted.is_empty() {
+                CommandResult::message(format!("Exported to {}", export_path.display()))
+            } else {
+                CommandResult::message(format!(
+                    "Exported to {} (redacted: {})",
+                    export_path.display(),
+                    redacted.join(", ")
+                ))
+            }
+        }
         Err(e) => CommandResult::error(format!("Failed to export: {e}")),
     }
 }
 
+/// Split `/export` arguments into a path and the `--format` / `--redact`
+/// flags. Whatever is not a flag is the path, so paths with spaces still
+/// work unquoted.
+fn parse_export_args(
+    arg: &str,
+) -> Result<(Option<PathBuf>, Option<ExportFormat>, Redaction), String> {
+    let mut path_parts = Vec::new();
+    let mut format = None;
+    let mut redaction = Redaction::default();
+    let mut tokens = arg.split_whitespace();
+    while let Some(token) = tokens.next() {
+        let (flag, inline) = match token.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+            _ => (token, None),
+        };
+        match flag {
+            "--redact" | "--format" => {
+                let value = inline
+                    .or_else(|| tokens.next())
+                    .ok_or_else(|| format!("{flag} needs a value"))?;
+                if flag == "--redact" {
+                    let more = Redaction::parse(value)?;
+                    redaction.thinking |= more.thinking;
+                    redaction.secrets |= more.secrets;
+                    redaction.shell_output |= more.shell_output;
+                } else {
+                    format = Some(match value.to_ascii_lowercase().as_str() {
+                        "markdown" | "md" => ExportFormat::Markdown,
+                        "json" => ExportFormat::Json,
+                        other => return Err(format!("unknown format `{other}`")),
+                    });
+                }
+            }
+            "--json" => format = Some(ExportFormat::Json),
+            _ if token.starts_with("--") => return Err(format!("unknown flag `{token}`")),
+            _ => path_parts.push(token),
+        }
+    }
+    let path = (!path_parts.is_empty()).then(|| PathBuf::from(path_parts.join(" ")));
+    Ok((path, format, redaction))
+}
+
+/// A copy of a shell tool cell with its output dropped, for
+/// `--redact shell-output`. Other tools are returned unchanged.
+fn without_shell_output(tool: &ToolCell) -> ToolCell {
+    let mut tool = tool.clone();
+    match &mut tool {
+        ToolCell::Exec(exec) => {
+            exec.output = Some(SHELL_OUTPUT_REMOVED.to_string());
+            exec.output_summary = None;
+        }
+        ToolCell::Generic(generic) if is_shell_tool(&generic.name) => {
+            generic.output = Some(SHELL_OUTPUT_REMOVED.to_string());
+            generic.output_summary = None;
+            generic.spillover_path = None;
+        }
+        _ => {}
+    }
+    tool
+}
+
 /// Open the session picker UI, or run a sub-action like
-/// `prune <days>` for housekeeping (#406 phase-1.5).
+/// `prune <days>` for housekeeping (#406 phase-1.5) or `pin`/`tag` to
+/// protect a session from retention GC.
 pub fn sessions(app: &mut App, arg: Option<&str>) -> CommandResult {
     let trimmed = arg.unwrap_or("").trim();
     if trimmed.is_empty() {
-        app.view_stack.push(SessionPickerView::new(&app.workspace));
+        app.view_stack.push(SessionPickerView::from_index(
+            &app.workspace,
+            app.session_index.take(),
+        ));
         return CommandResult::ok();
     }
 
@@ -278,12 +443,20 @@ pub fn sessions(app: &mut App, arg: Option<&str>) -> CommandResult {
     let action = parts.next().unwrap_or("").to_ascii_lowercase();
     match action.as_str() {
         "prune" => prune(app, parts.next()),
+        "pin" | "unpin" => pin(app, action == "pin", parts.next()),
+        "tag" | "untag" => match parts.next() {
+            Some(tag) => tag_session(app, action == "tag", tag, parts.next()),
+            None => CommandResult::error(format!("usage: /sessions {action} <tag> [id]")),
+        },
         "show" | "list" | "picker" => {
-            app.view_stack.push(SessionPickerView::new(&app.workspace));
+            app.view_stack.push(SessionPickerView::from_index(
+                &app.workspace,
+                app.session_index.take(),
+            ));
             CommandResult::ok()
         }
         _ => CommandResult::error(format!(
-            "unknown subcommand `{action}`. usage: /sessions [show|prune <days>]"
+            "unknown subcommand `{action}`. usage: /sessions [show|prune <days>|pin|unpin [id]|tag|untag <tag> [id]]"
         )),
     }
 }
@@ -329,6 +502,72 @@ fn prune(_app: &mut App, days_arg: Option<&str>) -> CommandResult {
     }
 }
 
+/// Pin or unpin session `id` (default: the open session).
+fn pin(app: &mut App, pinned: bool, id: Option<&str>) -> CommandResult {
+    let verb = if pinned { "pinned" } else { "unpinned" };
+    edit_session_labels(app, id, |meta| meta.pinned = pinned).map_or_else(
+        CommandResult::error,
+        |meta| {
+            CommandResult::message(format!(
+                "{verb} session {}",
+                crate::session_manager::truncate_id(&meta.id)
+            ))
+        },
+    )
+}
+
+/// Add or remove `tag` on session `id` (default: the open session).
+fn tag_session(app: &mut App, add: bool, tag: &str, id: Option<&str>) -> CommandResult {
+    let tag = tag.trim_start_matches('#').to_string();
+    if tag.is_empty() {
+        return CommandResult::error("tag cannot be empty");
+    }
+    let result = edit_session_labels(app, id, |meta| {
+        meta.tags.retain(|existing| existing != &tag);
+        if add {
+            meta.tags.push(tag.clone());
+        }
+    });
+    match result {
+        Ok(meta) if meta.tags.is_empty() => CommandResult::message(format!(
+            "session {} has no tags",
+            crate::session_manager::truncate_id(&meta.id)
+        )),
+        Ok(meta) => CommandResult::message(format!(
+            "session {} tags: {}",
+            crate::session_manager::truncate_id(&meta.id),
+            meta.tags
+                .iter()
+                .map(|tag| format!("#{tag}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )),
+        Err(err) => CommandResult::error(err),
+    }
+}
+
+fn edit_session_labels(
+    app: &mut App,
+    id: Option<&str>,
+    edit: impl FnOnce(&mut crate::session_manager::SessionMetadata),
+) -> Result<crate::session_manager::SessionMetadata, String> {
+    let Some(id) = id.or(app.current_session_id.as_deref()) else {
+        return Err("this session has not been saved yet; pass a session id".to_string());
+    };
+    let manager = crate::session_manager::SessionManager::default_location()
+        .map_err(|err| format!("could not open sessions directory: {err}"))?;
+    let meta = manager
+        .update_metadata(id, edit)
+        .map_err(|err| format!("could not update session {id}: {err}"))?;
+    // Keep the picker index in step with the file.
+    if let Some(index) = app.session_index.as_mut()
+        && let Some(entry) = index.iter_mut().find(|entry| entry.id == meta.id)
+    {
+        entry.clone_from(&meta);
+    }
+    Ok(meta)
+}
+
 fn render_tool_cell(tool: &crate::tui::history::ToolCell, width: u16) -> String {
     tool.lines(width)
         .into_iter()
@@ -385,6 +624,37 @@ mod tests {
         App::new(options, &Config::default())
     }
 
+    #[test]
+    fn import_appends_markdown_turns_and_drafts_the_last_user_turn() {
+        let tmpdir = TempDir::new().unwrap();
+        let mut app = create_test_app_with_tmpdir(&tmpdir);
+        std::fs::write(
+            tmpdir.path().join("chat.md"),
+            "## User\n\nplan the migration\n\n## Assistant\n\nThree steps.\n\n## User\n\nstart with step one\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmpdir.path().join("notes.md"),
+            "# Design\n\nKeep it small.\n",
+        )
+        .unwrap();
+
+        let result = import(&mut app, Some("chat.md"));
+        assert!(matches!(result.action, Some(AppAction::SyncSession { .. })));
+        assert!(result.message.unwrap().contains("Imported 2 message(s)"));
+        let roles: Vec<&str> = app.api_messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["user", "assistant"]);
+        assert_eq!(app.input, "start with step one");
+
+        let result = import(&mut app, Some("notes.md"));
+        assert!(result.action.is_none());
+        assert_eq!(app.api_messages.len(), 2);
+        assert_eq!(app.input, "# Design\n\nKeep it small.");
+
+        assert!(import(&mut app, None).is_error);
+        assert!(import(&mut app, Some("missing.md")).is_error);
+    }
+
     #[test]
     fn test_save_creates_file_and_sets_session_id() {
         let tmpdir = TempDir::new().unwrap();
@@ -700,6 +970,56 @@ mod tests {
         assert!(content.contains("**Assistant:**"));
     }
 
+    #[test]
+    fn test_export_redacts_and_writes_json() {
+        use crate::tui::history::{ExecCell, ExecSource, ToolStatus};
+
+        let tmpdir = TempDir::new().unwrap();
+        let mut app = create_test_app_with_tmpdir(&tmpdir);
+        app.history.push(HistoryCell::User {
+            content: "use key sk-abcdefgh12345678".to_string(),
+        });
+        app.history.push(HistoryCell::Thinking {
+            content: "private reasoning".to_string(),
+            streaming: false,
+            duration_secs: None,
+        });
+        app.history.push(HistoryCell::Tool(ToolCell::Exec(ExecCell {
+            command: "cat .env".to_string(),
+            status: ToolStatus::Success,
+            output: Some("DB_PASSWORD=letmein".to_string()),
+            started_at: None,
+            duration_ms: None,
+            source: ExecSource::Assistant,
+            interaction: None,
+            session: None,
+            output_summary: None,
+        })));
+
+        let export_path = tmpdir.path().join("chat.json");
+        let arg = format!(
+            "{} --redact thinking,secrets --redact=shell-output",
+            export_path.display()
+        );
+        let result = export(&mut app, Some(&arg));
+        assert!(
+            result
+                .message
+                .unwrap()
+                .contains("redacted: thinking, secrets, shell-output")
+        );
+        let content = std::fs::read_to_string(&export_path).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(json["entries"].as_array().unwrap().len(), 2);
+        assert!(content.contains("sk-<redacted>"), "{content}");
+        assert!(content.contains("cat .env"), "{content}");
+        assert!(!content.contains("private reasoning"));
+        assert!(!content.contains("letmein"), "{content}");
+
+        let bad = export(&mut app, Some("--redact tools"));
+        assert!(bad.is_error);
+    }
+
     #[test]
     fn test_export_with_default_path() {
         let tmpdir = TempDir::new().unwrap();
@@ -768,6 +1088,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sessions_pin_and_tag_need_a_target() {
+        let tmpdir = TempDir::new().unwrap();
+        let mut app = create_test_app_with_tmpdir(&tmpdir);
+        app.current_session_id = None;
+        let result = sessions(&mut app, Some("pin"));
+        assert!(result.is_error);
+        assert!(
+            result
+                .message
+                .as_deref()
+                .unwrap_or("")
+                .contains("not been saved"),
+            "expected unsaved-session error: {:?}",
+            result.message
+        );
+        let result = sessions(&mut app, Some("tag"));
+        assert!(result.is_error);
+        assert!(result.message.as_deref().unwrap_or("").contains("usage"));
+    }
+
     #[test]
     fn test_sessions_unknown_subcommand_errors() {
         let tmpdir = TempDir::new().unwrap();
@@ -1,42 +1,157 @@
-//! /share command — export the current session as a shareable web URL.
+//! /share command — export the current session as a shareable web URL, or
+//! let teammates watch it live.
 //!
 //! Renders the current session transcript as a static HTML page, uploads it
-//! to a GitHub Gist via the `gh` CLI, and displays the resulting URL.
+//! to a GitHub Gist via the `gh` CLI, and displays the resulting URL. The
+//! live variant serves a read-only, redacted event stream of this session
+//! (see `runtime_api::share`).
 //!
 //! # Usage
 //!
 //! - `/share` — export the current session and print the Gist URL
+//! - `/share start [--host H] [--port P] [--reasoning] [--no-tool-output]` —
+//!   start live read-only sharing and print the observer URL
+//! - `/share stop` — stop live sharing
+//! - `/share status` — show the live share URL and observer count
 //! - `/share help` — show usage
 
 use std::io::Write;
 use std::path::Path;
 
 use super::CommandResult;
+use crate::runtime_api::share::{ShareHub, ShareRedaction, start_share_server};
 use crate::tui::app::{App, AppAction};
+use crate::tui::history::HistoryCell;
 
 /// Share the current session as a web URL.
 pub fn share(app: &mut App, arg: Option<&str>) -> CommandResult {
     let raw = arg.map(str::trim).unwrap_or("");
 
-    match raw {
+    let (sub, rest) = raw.split_once(char::is_whitespace).unwrap_or((raw, ""));
+    match sub {
         "" => do_share(app),
+        "start" => start_live(app, rest),
+        "stop" => stop_live(app),
+        "status" => live_status(app),
         "help" | "--help" | "-h" => CommandResult::message(
             "/share — Export the current session as a shareable web URL.\n\
              \n\
              Usage:\n\
              /share         Export and upload the current session\n\
+             /share start   Let teammates watch this session live (read-only)\n\
+             /share stop    Stop live sharing\n\
+             /share status  Show the live share URL and observers\n\
              \n\
              The session transcript is rendered as static HTML and uploaded\n\
              to a GitHub Gist using the `gh` CLI. The Gist URL is displayed\n\
-             so you can paste it into Slack, GitHub, Twitter, etc."
+             so you can paste it into Slack, GitHub, Twitter, etc.\n\
+             \n\
+             `/share start` serves a redacted event stream on 127.0.0.1 with a\n\
+             random port. Options: --host <addr> (e.g. 0.0.0.0 for your LAN),\n\
+             --port <n>, --reasoning (include thinking), --no-tool-output."
                 .to_string(),
         ),
         _ => CommandResult::error(format!(
-            "Unknown /share argument `{raw}`. Use `/share` with no arguments or `/share help`."
+            "Unknown /share argument `{raw}`. Use `/share`, `/share start`, `/share stop`, \
+             or `/share help`."
         )),
     }
 }
 
+/// Start serving this session to read-only observers.
+fn start_live(app: &mut App, args: &str) -> CommandResult {
+    if let Some(server) = app.share.as_ref() {
+        return CommandResult::message(format!(
+            "Already sharing: {}\nUse /share stop first to start a new share.",
+            server.viewer_url()
+        ));
+    }
+    let mut redaction = ShareRedaction::default();
+    let mut host = "127.0.0.1".to_string();
+    let mut port = 0u16;
+    let mut tokens = args.split_whitespace();
+    while let Some(token) = tokens.next() {
+        match token {
+            "--reasoning" => redaction.reasoning = true,
+            "--no-tool-output" => redaction.tool_output = false,
+            "--host" => match tokens.next() {
+                Some(value) => host = value.to_string(),
+                None => return CommandResult::error("--host needs an address"),
+            },
+            "--port" => match tokens.next().and_then(|value| value.parse().ok()) {
+                Some(value) => port = value,
+                None => return CommandResult::error("--port needs a port number"),
+            },
+            other => {
+                return CommandResult::error(format!(
+                    "Unknown /share start option `{other}`. See /share help."
+                ));
+            }
+        }
+    }
+
+    let hub = ShareHub::new(redaction);
+    for cell in &app.history {
+        match cell {
+            HistoryCell::User { content } => hub.publish_user_message(content),
+            HistoryCell::Assistant { content, .. } => hub.publish_assistant_message(content),
+            _ => {}
+        }
+    }
+    let server = match start_share_server(hub, &host, port) {
+        Ok(server) => server,
+        Err(err) => return CommandResult::error(format!("Failed to start sharing: {err:#}")),
+    };
+    let mut message = format!(
+        "Sharing this session read-only.\n\
+         Viewer: {}\n\
+         Events (SSE): {}\n\
+         \n\
+         Anyone with the link can watch until /share stop. {}",
+        server.viewer_url(),
+        server.events_url(),
+        redaction_note(redaction)
+    );
+    if host == "0.0.0.0" || host == "::" {
+        message.push_str("\nReplace the wildcard address with this machine's address.");
+    }
+    app.share = Some(server);
+    CommandResult::message(message)
+}
+
+fn stop_live(app: &mut App) -> CommandResult {
+    match app.share.take() {
+        Some(server) => {
+            server.stop();
+            CommandResult::message("Stopped sharing; observers were disconnected.")
+        }
+        None => CommandResult::message("Not sharing this session."),
+    }
+}
+
+fn live_status(app: &App) -> CommandResult {
+    match app.share.as_ref() {
+        Some(server) => CommandResult::message(format!(
+            "Sharing: {}\nObservers connected: {}\n{}",
+            server.viewer_url(),
+            server.hub().observer_count(),
+            redaction_note(server.hub().redaction())
+        )),
+        None => CommandResult::message("Not sharing this session. Start with /share start."),
+    }
+}
+
+fn redaction_note(redaction: ShareRedaction) -> &'static str {
+    match (redaction.reasoning, redaction.tool_output) {
+        (false, true) => {
+            "Secrets are masked and long tool output is truncated; thinking is not shared."
+        }
+        (false, false) => "Secrets are masked; thinking and tool output are not shared.",
+        (true, true) => "Secrets are masked and long tool output is truncated.",
+        (true, false) => "Secrets are masked; tool output is not shared.",
+    }
+}
+
 /// Export the session as HTML, upload to a Gist, and show the URL.
 fn do_share(app: &mut App) -> CommandResult {
     // Check if there's any session content to share
@@ -212,6 +327,57 @@ mod tests {
         assert_eq!(content, "<html></html>");
     }
 
+    #[tokio::test]
+    async fn live_share_starts_seeded_and_stops() {
+        let tmpdir = tempfile::TempDir::new().unwrap();
+        let mut app = crate::tui::app::App::new(
+            crate::tui::app::TuiOptions {
+                model: "deepseek-v4-pro".to_string(),
+                workspace: tmpdir.path().to_path_buf(),
+                config_path: None,
+                config_profile: None,
+                allow_shell: false,
+                use_alt_screen: true,
+                use_mouse_capture: false,
+                use_bracketed_paste: true,
+                max_subagents: 1,
+                skills_dir: tmpdir.path().join("skills"),
+                memory_path: tmpdir.path().join("memory.md"),
+                notes_path: tmpdir.path().join("notes.txt"),
+                mcp_config_path: tmpdir.path().join("mcp.json"),
+                use_memory: false,
+                start_in_agent_mode: false,
+                skip_onboarding: true,
+                yolo: false,
+                resume_session_id: None,
+                initial_input: None,
+            },
+            &crate::config::Config::default(),
+        );
+        app.history.push(HistoryCell::User {
+            content: "earlier prompt".to_string(),
+        });
+
+        assert!(share(&mut app, Some("start --bogus")).is_error);
+        let started = share(&mut app, Some("start --no-tool-output"));
+        assert!(started.message.unwrap().contains("/share/dss_"));
+        let hub = app.share.as_ref().unwrap().hub().clone();
+        assert!(!hub.redaction().tool_output);
+        let url = app.share.as_ref().unwrap().viewer_url();
+        let transcript_url = format!("{}/transcript", url.replace("/share/", "/v1/share/"));
+        let transcript: serde_json::Value = reqwest::get(transcript_url)
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(transcript[0]["payload"]["content"], "earlier prompt");
+
+        let stopped = share(&mut app, Some("stop"));
+        assert!(stopped.message.unwrap().contains("Stopped sharing"));
+        assert!(app.share.is_none());
+    }
+
     #[test]
     fn test_render_session_html_metadata() {
         let html = render_session_html("test data", "deepseek-v4-flash", "plan");
@@ -194,7 +194,7 @@ pub fn run_skill(app: &mut App, name: Option<&str>) -> CommandResult {
         Some(n) => n.trim(),
         None => {
             return CommandResult::error(
-                "Usage: /skill <name>\n\nSubcommands:\n  /skill install <github:owner/repo|https://…|<registry-name>>\n  /skill update <name>\n  /skill uninstall <name>\n  /skill trust <name>",
+                "Usage: /skill <name>\n\nSubcommands:\n  /skill install <github:owner/repo|https://…|<registry-name>>\n  /skill update <name>\n  /skill uninstall <name>\n  /skill trust <name>\n  /skill off <name>\n  /skill on <name>",
             );
         }
     };
@@ -209,12 +209,49 @@ pub fn run_skill(app: &mut App, name: Option<&str>) -> CommandResult {
         "update" => return update_skill(app, rest),
         "uninstall" => return uninstall_skill(app, rest),
         "trust" => return trust_skill(app, rest),
+        "off" => return skill_off(app, rest),
+        "on" => return skill_on(app, rest),
         _ => {}
     }
 
     activate_skill(app, raw)
 }
 
+/// `/skill off <name>`: stop auto-activating a skill for this session.
+fn skill_off(app: &mut App, name: &str) -> CommandResult {
+    if name.is_empty() {
+        return CommandResult::error("Usage: /skill off <name>");
+    }
+    if discover_visible_skills(app).get(name).is_none() {
+        return CommandResult::error(format!("Skill '{name}' not found."));
+    }
+    let was_active = app.auto_skills.iter().any(|active| active == name);
+    app.auto_skills.retain(|active| active != name);
+    app.skills_off.insert(name.to_string());
+    let note = if was_active {
+        " Instructions it already injected stay in the conversation until /clear or compaction."
+    } else {
+        ""
+    };
+    CommandResult::message(format!(
+        "Skill '{name}' will not auto-activate this session.{note} /skill on {name} undoes this."
+    ))
+}
+
+/// `/skill on <name>`: let an `/skill off` skill auto-activate again.
+fn skill_on(app: &mut App, name: &str) -> CommandResult {
+    if name.is_empty() {
+        return CommandResult::error("Usage: /skill on <name>");
+    }
+    if app.skills_off.remove(name) {
+        CommandResult::message(format!(
+            "Skill '{name}' can auto-activate again when its rules match."
+        ))
+    } else {
+        CommandResult::message(format!("Skill '{name}' was not turned off."))
+    }
+}
+
 fn activate_skill(app: &mut App, name: &str) -> CommandResult {
     // `/skill new` is a friendly alias for `/skill skill-creator`.
     let name = if name == "new" { "skill-creator" } else { name };
@@ -991,4 +1028,27 @@ mod tests {
         assert!(app.active_skill.is_some());
         assert!(!app.history.is_empty());
     }
+
+    #[test]
+    fn test_skill_off_and_on_toggle_auto_activation() {
+        let tmpdir = TempDir::new().unwrap();
+        let _home = IsolatedHome::new(&tmpdir);
+        create_skill_dir(
+            &tmpdir,
+            "rusty",
+            "---\nname: rusty\nglobs: *.rs\n---\nRust rules",
+        );
+        let mut app = create_test_app_with_tmpdir(&tmpdir);
+        app.auto_skills.push("rusty".to_string());
+
+        let result = run_skill(&mut app, Some("off rusty"));
+        assert!(result.message.unwrap().contains("will not auto-activate"));
+        assert!(app.auto_skills.is_empty());
+        assert!(app.skills_off.contains("rusty"));
+        assert!(run_skill(&mut app, Some("off ghost")).is_error);
+
+        let result = run_skill(&mut app, Some("on rusty"));
+        assert!(result.message.unwrap().contains("auto-activate again"));
+        assert!(app.skills_off.is_empty());
+    }
 }
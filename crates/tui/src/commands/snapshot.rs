@@ -0,0 +1,246 @@
+//! `/snapshot` slash command — named workspace snapshots.
+//!
+//! `/snapshot save <name>` records the workspace in the side snapshot repo
+//! (git-backed even when the workspace itself is not a git repo), and
+//! `/snapshot restore <name>` puts it back, so a risky YOLO experiment can
+//! be undone without branch juggling. Restoring first takes a
+//! `pre-restore:<name>` turn snapshot, so `/restore 1` undoes the restore.
+//! Like `/restore`, mutating the workspace requires trusted mode.
+
+use super::CommandResult;
+use crate::snapshot::{NamedSnapshot, SnapshotRepo, validate_snapshot_name};
+use crate::tui::app::App;
+
+const USAGE: &str = "Usage: /snapshot [list|save <name>|restore <name>|delete <name>]";
+
+/// Entry point for `/snapshot [list|save|restore|delete]`.
+pub fn snapshot(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let arg = arg.map(str::trim).unwrap_or_default();
+    let (action, name) = match arg.split_once(char::is_whitespace) {
+        Some((action, name)) => (action, name.trim()),
+        None => (arg, ""),
+    };
+
+    let workspace = app.workspace.clone();
+    let repo = match SnapshotRepo::open_or_init(&workspace) {
+        Ok(r) => r,
+        Err(e) => {
+            return CommandResult::error(format!(
+                "Snapshot repo unavailable for {}: {e}",
+                workspace.display(),
+            ));
+        }
+    };
+
+    match action {
+        "" | "list" => list(&repo),
+        "save" | "restore" | "delete" if name.is_empty() => {
+            CommandResult::error(format!("Usage: /snapshot {action} <name>"))
+        }
+        "save" => save(&repo, name),
+        "restore" => restore(app, &repo, name),
+        "delete" => delete(&repo, name),
+        _ => CommandResult::error(USAGE),
+    }
+}
+
+fn list(repo: &SnapshotRepo) -> CommandResult {
+    let named = match repo.list_named() {
+        Ok(named) => named,
+        Err(e) => return CommandResult::error(format!("Failed to list snapshots: {e}")),
+    };
+    if named.is_empty() {
+        return CommandResult::message(
+            "No named snapshots yet. Run /snapshot save <name> to create one.",
+        );
+    }
+    let mut out = String::from("Named snapshots (newest first; /snapshot restore <name>):\n");
+    for snapshot in &named {
+        out.push_str(&format!(
+            "  {:<24}  {}  {}\n",
+            snapshot.name,
+            short_sha(snapshot.id.as_str()),
+            saved_at(snapshot),
+        ));
+    }
+    CommandResult::message(out)
+}
+
+fn save(repo: &SnapshotRepo, name: &str) -> CommandResult {
+    if let Err(e) = validate_snapshot_name(name) {
+        return CommandResult::error(e);
+    }
+    let replaced = matches!(repo.named(name), Ok(Some(_)));
+    match repo.save_named(name) {
+        Ok(id) => CommandResult::message(format!(
+            "{} snapshot '{name}' ({}). Restore it with /snapshot restore {name}.",
+            if replaced { "Replaced" } else { "Saved" },
+            short_sha(id.as_str()),
+        )),
+        Err(e) => CommandResult::error(format!("Snapshot failed: {e}")),
+    }
+}
+
+fn restore(app: &App, repo: &SnapshotRepo, name: &str) -> CommandResult {
+    let target = match repo.named(name) {
+        Ok(Some(target)) => target,
+        Ok(None) => {
+            return CommandResult::error(format!(
+                "No snapshot named '{name}'. Run /snapshot list to see saved snapshots."
+            ));
+        }
+        Err(e) => return CommandResult::error(format!("Failed to read snapshots: {e}")),
+    };
+
+    if !(app.yolo || app.trust_mode) {
+        return CommandResult::message(format!(
+            "Refusing to restore snapshot '{name}' outside trusted mode.\n\
+             Run `/trust on` or `/mode yolo` first, then re-run `/snapshot restore {name}`.",
+        ));
+    }
+
+    // Record the current state first: it makes the restore itself undoable
+    // and gives `restore` an up-to-date file list to remove stray files from.
+    if let Err(e) = repo.snapshot(&format!("pre-restore:{name}")) {
+        return CommandResult::error(format!("Could not snapshot the current workspace: {e}"));
+    }
+    if let Err(e) = repo.restore(&target.id) {
+        return CommandResult::error(format!("Restore failed: {e}"));
+    }
+
+    CommandResult::message(format!(
+        "Restored snapshot '{name}' ({}). The previous state was saved as turn snapshot #1; \
+         /restore 1 undoes this. Conversation history is unchanged.",
+        short_sha(target.id.as_str()),
+    ))
+}
+
+fn delete(repo: &SnapshotRepo, name: &str) -> CommandResult {
+    match repo.delete_named(name) {
+        Ok(true) => CommandResult::message(format!("Deleted snapshot '{name}'.")),
+        Ok(false) => CommandResult::error(format!("No snapshot named '{name}'.")),
+        Err(e) => CommandResult::error(format!("Delete failed: {e}")),
+    }
+}
+
+fn saved_at(snapshot: &NamedSnapshot) -> String {
+    chrono::DateTime::from_timestamp(snapshot.timestamp, 0)
+        .map(|at| {
+            at.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(8)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::test_support::lock_test_env;
+    use crate::tui::app::TuiOptions;
+    use std::sync::MutexGuard;
+    use tempfile::TempDir;
+
+    fn make_app(tmp: &TempDir, yolo: bool) -> App {
+        let options = TuiOptions {
+            model: "deepseek-v4-pro".to_string(),
+            workspace: tmp.path().to_path_buf(),
+            config_path: None,
+            config_profile: None,
+            allow_shell: false,
+            use_alt_screen: true,
+            use_mouse_capture: false,
+            use_bracketed_paste: true,
+            max_subagents: 1,
+            skills_dir: tmp.path().join("skills"),
+            memory_path: tmp.path().join("memory.md"),
+            notes_path: tmp.path().join("notes.txt"),
+            mcp_config_path: tmp.path().join("mcp.json"),
+            use_memory: false,
+            start_in_agent_mode: false,
+            skip_onboarding: true,
+            yolo,
+            resume_session_id: None,
+            initial_input: None,
+        };
+        App::new(options, &Config::default())
+    }
+
+    /// Pins HOME to a tempdir for the duration of the test under the
+    /// crate-wide env mutex.
+    struct ScopedHome {
+        prev: Option<std::ffi::OsString>,
+        _home: TempDir,
+        _guard: MutexGuard<'static, ()>,
+    }
+    impl Drop for ScopedHome {
+        fn drop(&mut self) {
+            // SAFETY: process-wide lock still held.
+            unsafe {
+                match self.prev.take() {
+                    Some(v) => std::env::set_var("HOME", v),
+                    None => std::env::remove_var("HOME"),
+                }
+            }
+        }
+    }
+    fn scoped_home() -> ScopedHome {
+        let guard = lock_test_env();
+        let prev = std::env::var_os("HOME");
+        let home = TempDir::new().expect("home tempdir");
+        // SAFETY: serialised by the global env lock.
+        unsafe {
+            std::env::set_var("HOME", home.path());
+        }
+        ScopedHome {
+            prev,
+            _home: home,
+            _guard: guard,
+        }
+    }
+
+    #[test]
+    fn save_then_restore_reverts_workspace_and_is_undoable() {
+        let tmp = TempDir::new().unwrap();
+        let _home = scoped_home();
+        let mut app = make_app(&tmp, true);
+        let f = app.workspace.join("a.txt");
+        std::fs::write(&f, b"stable").unwrap();
+
+        let saved = snapshot(&mut app, Some("save stable"));
+        assert!(saved.message.unwrap().contains("Saved snapshot 'stable'"));
+
+        std::fs::write(&f, b"risky").unwrap();
+        std::fs::write(app.workspace.join("scratch.txt"), b"tmp").unwrap();
+        let restored = snapshot(&mut app, Some("restore stable"));
+        assert!(restored.message.unwrap().contains("Restored snapshot"));
+        assert_eq!(std::fs::read_to_string(&f).unwrap(), "stable");
+        assert!(!app.workspace.join("scratch.txt").exists());
+
+        let listing = snapshot(&mut app, None).message.unwrap();
+        assert!(listing.contains("stable"));
+        let repo = SnapshotRepo::open_or_init(&app.workspace).unwrap();
+        assert_eq!(repo.list(1).unwrap()[0].label, "pre-restore:stable");
+    }
+
+    #[test]
+    fn restore_outside_trust_mode_refuses() {
+        let tmp = TempDir::new().unwrap();
+        let _home = scoped_home();
+        let mut app = make_app(&tmp, false);
+        std::fs::write(app.workspace.join("a.txt"), b"v1").unwrap();
+        snapshot(&mut app, Some("save base"));
+
+        let msg = snapshot(&mut app, Some("restore base")).message.unwrap();
+        assert!(msg.contains("Refusing"));
+        let missing = snapshot(&mut app, Some("restore nope")).message.unwrap();
+        assert!(missing.contains("No snapshot named 'nope'"));
+        let bad = snapshot(&mut app, Some("save ../x")).message.unwrap();
+        assert!(bad.contains("invalid snapshot name"));
+    }
+}
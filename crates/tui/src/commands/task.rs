@@ -1,4 +1,4 @@
-//! Task commands: add/list/show/cancel
+//! Task commands: panel/add/list/show/cancel
 
 use crate::tui::app::{App, AppAction};
 
@@ -6,8 +6,8 @@ use super::CommandResult;
 
 pub fn task(_app: &mut App, args: Option<&str>) -> CommandResult {
     let raw = args.unwrap_or("").trim();
-    if raw.is_empty() || raw.eq_ignore_ascii_case("list") {
-        return CommandResult::action(AppAction::TaskList);
+    if raw.is_empty() {
+        return CommandResult::action(AppAction::TaskPanel);
     }
 
     let mut parts = raw.splitn(2, char::is_whitespace);
@@ -90,6 +90,19 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn bare_task_opens_panel_and_list_stays_textual() {
+        let mut app = app();
+        assert!(matches!(
+            task(&mut app, None).action,
+            Some(AppAction::TaskPanel)
+        ));
+        assert!(matches!(
+            task(&mut app, Some("list")).action,
+            Some(AppAction::TaskList)
+        ));
+    }
+
     #[test]
     fn validates_usage() {
         let mut app = app();
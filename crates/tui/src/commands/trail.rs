@@ -0,0 +1,179 @@
+//! `/trail` slash command — the per-turn git audit trail.
+//!
+//! With the trail on, every turn that ran tools and changed files becomes one
+//! commit on the trail branch (`deepseek/trail` unless `[trail] branch` says
+//! otherwise), stacked on the `HEAD` the trail started from. The user's
+//! branch, index, and working tree are never touched. `/trail clear` deletes
+//! the branch; the next recorded turn starts a fresh trail on top of `HEAD`.
+
+use super::CommandResult;
+use crate::git_trail;
+use crate::tui::app::{App, AppAction};
+
+const USAGE: &str = "Usage: /trail [on|off|clear]";
+const RECENT_COMMITS: usize = 10;
+
+/// Entry point for `/trail [on|off|clear]`.
+pub fn trail(app: &mut App, arg: Option<&str>) -> CommandResult {
+    match arg.map(str::trim).unwrap_or_default() {
+        "" | "status" | "log" => status(app),
+        "on" => toggle(app, true),
+        "off" => toggle(app, false),
+        "clear" => clear(app),
+        _ => CommandResult::error(USAGE),
+    }
+}
+
+fn status(app: &App) -> CommandResult {
+    let state = if app.trail_enabled { "on" } else { "off" };
+    let status = match git_trail::status(&app.workspace, &app.trail_branch, RECENT_COMMITS) {
+        Ok(status) => status,
+        Err(e) => {
+            return CommandResult::message(format!(
+                "Trail: {state} (branch {}). {e}.",
+                app.trail_branch
+            ));
+        }
+    };
+    if status.tip.is_none() {
+        return CommandResult::message(format!(
+            "Trail: {state} (branch {}). No trail commits yet.{}",
+            status.branch,
+            if app.trail_enabled {
+                ""
+            } else {
+                " Run /trail on to record one commit per file-changing turn."
+            }
+        ));
+    }
+    let mut out = format!(
+        "Trail: {state} (branch {}), {} commit{} ahead of HEAD:\n",
+        status.branch,
+        status.ahead_of_head,
+        if status.ahead_of_head == 1 { "" } else { "s" },
+    );
+    for line in &status.recent {
+        out.push_str(&format!("  {line}\n"));
+    }
+    out.push_str(&format!(
+        "Inspect with `git log -p HEAD..{0}`; take a turn with `git cherry-pick <sha>`; /trail clear deletes the branch.",
+        status.branch
+    ));
+    CommandResult::message(out)
+}
+
+fn toggle(app: &mut App, enabled: bool) -> CommandResult {
+    if enabled && let Err(e) = git_trail::validate_branch(&app.trail_branch) {
+        return CommandResult::error(format!("Cannot record a trail: {e}"));
+    }
+    app.trail_enabled = enabled;
+    let message = if enabled {
+        format!(
+            "Trail on: each turn that changes files is committed to {}. Your branch is not touched.",
+            app.trail_branch
+        )
+    } else {
+        "Trail off. Existing trail commits are kept; /trail clear deletes them.".to_string()
+    };
+    CommandResult::with_message_and_action(message, AppAction::SetTrail(enabled))
+}
+
+fn clear(app: &App) -> CommandResult {
+    match git_trail::clear(&app.workspace, &app.trail_branch) {
+        Ok(true) => CommandResult::message(format!(
+            "Deleted trail branch {}. The next recorded turn starts a new trail on top of HEAD.",
+            app.trail_branch
+        )),
+        Ok(false) => {
+            CommandResult::message(format!("No trail branch {} to delete.", app.trail_branch))
+        }
+        Err(e) => CommandResult::error(format!("Could not delete the trail branch: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::tui::app::TuiOptions;
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn make_app(workspace: &Path) -> App {
+        let options = TuiOptions {
+            model: "deepseek-v4-pro".to_string(),
+            workspace: workspace.to_path_buf(),
+            config_path: None,
+            config_profile: None,
+            allow_shell: false,
+            use_alt_screen: true,
+            use_mouse_capture: false,
+            use_bracketed_paste: true,
+            max_subagents: 1,
+            skills_dir: workspace.join("skills"),
+            memory_path: workspace.join("memory.md"),
+            notes_path: workspace.join("notes.txt"),
+            mcp_config_path: workspace.join("mcp.json"),
+            use_memory: false,
+            start_in_agent_mode: false,
+            skip_onboarding: true,
+            yolo: false,
+            resume_session_id: None,
+            initial_input: None,
+        };
+        App::new(options, &Config::default())
+    }
+
+    #[test]
+    fn trail_toggles_reports_and_clears() {
+        let tmp = TempDir::new().expect("tempdir");
+        let repo = tmp.path();
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .arg("-C")
+                .arg(repo)
+                .args(args)
+                .env("GIT_AUTHOR_NAME", "t")
+                .env("GIT_AUTHOR_EMAIL", "t@example.com")
+                .env("GIT_COMMITTER_NAME", "t")
+                .env("GIT_COMMITTER_EMAIL", "t@example.com")
+                .output()
+                .expect("git");
+            assert!(output.status.success(), "git {args:?}: {output:?}");
+        };
+        git(&["init", "-q", "-b", "main"]);
+        std::fs::write(repo.join("a.txt"), "a\n").unwrap();
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", "base"]);
+        let mut app = make_app(repo);
+        assert!(!app.trail_enabled);
+
+        let on = trail(&mut app, Some("on"));
+        assert!(app.trail_enabled);
+        assert_eq!(on.action, Some(AppAction::SetTrail(true)));
+        assert!(
+            trail(&mut app, None)
+                .message
+                .unwrap()
+                .contains("No trail commits yet")
+        );
+
+        std::fs::write(repo.join("a.txt"), "b\n").unwrap();
+        git_trail::record_turn(repo, &app.trail_branch, "turn 1: edit a").unwrap();
+        let shown = trail(&mut app, None).message.unwrap();
+        assert!(shown.contains("1 commit ahead of HEAD"), "{shown}");
+        assert!(shown.contains("turn 1: edit a"), "{shown}");
+
+        assert!(
+            trail(&mut app, Some("clear"))
+                .message
+                .unwrap()
+                .starts_with("Deleted trail branch deepseek/trail")
+        );
+        let off = trail(&mut app, Some("off"));
+        assert!(!app.trail_enabled);
+        assert_eq!(off.action, Some(AppAction::SetTrail(false)));
+        assert!(trail(&mut app, Some("prune")).is_error);
+    }
+}
@@ -17,6 +17,7 @@
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+use crate::command_aliases::apply_template;
 use crate::tui::app::{App, AppAction};
 
 use super::CommandResult;
@@ -101,16 +102,6 @@ pub fn load_user_commands(workspace: Option<&Path>) -> Vec<(String, String)> {
 /// The `input` should be the full command string including the `/`
 /// prefix (e.g. `/mycmd` or `/mycmd with args`). Only exact matches
 /// on the command name are considered (no partial/alias matching).
-/// Substitute $1, $2, $ARGUMENTS placeholders in a command template.
-fn apply_template(template: &str, args: &str) -> String {
-    let positional: Vec<&str> = args.split_whitespace().collect();
-    let mut result = template.replace("$ARGUMENTS", args);
-    for (i, arg) in positional.iter().enumerate() {
-        result = result.replace(&format!("${}", i + 1), arg);
-    }
-    result
-}
-
 pub fn try_dispatch_user_command(app: &mut App, input: &str) -> Option<CommandResult> {
     let parts: Vec<&str> = input.trim().splitn(2, ' ').collect();
     let command = parts[0].to_lowercase();
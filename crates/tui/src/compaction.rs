@@ -1,4 +1,12 @@
 //! Context compaction for long conversations.
+//!
+//! Summary compaction is tiered. Each pass replaces the oldest unpinned
+//! messages with a tier-1 digest and archives the originals verbatim (see
+//! [`crate::history_archive`]). Once more than [`DIGESTS_PER_TIER`] digests
+//! share a tier, the oldest of them are condensed into one digest a tier
+//! up, so the summary prompt stays bounded while the recent past keeps the
+//! most detail. Every digest names the archive segments it covers, and the
+//! model can fetch those verbatim with `recall_history`.
 
 use anyhow::Result;
 use regex::Regex;
@@ -103,6 +111,13 @@ const LARGE_CONTEXT_SUMMARY_MAX_TOKENS: u32 = 2_048;
 const LARGE_CONTEXT_WINDOW_TOKENS: u32 = 500_000;
 const CACHE_ALIGNED_SUMMARY_CONTEXT_BUDGET_PERCENT: usize = 85;
 
+/// Digests allowed per tier before the oldest are folded a tier up.
+pub const DIGESTS_PER_TIER: usize = 3;
+/// Highest digest tier. Folds at this tier stay at this tier.
+pub const MAX_DIGEST_TIER: u8 = 3;
+const DIGEST_FOLD_MAX_TOKENS: u32 = 2_048;
+const DIGEST_FOLD_FALLBACK_CHARS: usize = 6_000;
+
 #[derive(Debug, Clone, Copy)]
 struct SummaryInputLimits {
     text_snippet_chars: usize,
@@ -884,8 +899,8 @@ pub struct CompactionResult {
     pub messages: Vec<Message>,
     /// Summary system prompt
     pub summary_prompt: Option<SystemPrompt>,
-    /// Messages that were removed from the active window
-    #[allow(dead_code)]
+    /// Verbatim originals of the summarized messages (before tool-result
+    /// pruning), for the history archive.
     pub removed_messages: Vec<Message>,
     /// Number of retries used before success
     pub retries_used: u32,
@@ -1005,7 +1020,13 @@ pub async fn compact_messages_safe(
         )
         .await
         {
-            Ok((msgs, prompt, removed)) => {
+            Ok((msgs, prompt, summarized)) => {
+                // `compaction_input` may have pruned tool results; archive
+                // the untouched originals (pruning keeps indices stable).
+                let removed = summarized
+                    .iter()
+                    .map(|&idx| messages[idx].clone())
+                    .collect();
                 return Ok(CompactionResult {
                     messages: msgs,
                     summary_prompt: prompt,
@@ -1065,6 +1086,8 @@ fn anchor_summary_section(workspace: Option<&Path>) -> String {
     section
 }
 
+/// Summarize the unpinned portion of `messages`. Returns the kept messages,
+/// the tier-1 digest, and the indices of the summarized messages.
 pub async fn compact_messages(
     client: &DeepSeekClient,
     messages: &[Message],
@@ -1072,7 +1095,7 @@ pub async fn compact_messages(
     workspace: Option<&Path>,
     external_pins: Option<&[usize]>,
     external_working_set_paths: Option<&[String]>,
-) -> Result<(Vec<Message>, Option<SystemPrompt>, Vec<Message>)> {
+) -> Result<(Vec<Message>, Option<SystemPrompt>, Vec<usize>)> {
     if messages.is_empty() {
         return Ok((Vec::new(), None, Vec::new()));
     }
@@ -1103,10 +1126,15 @@ pub async fn compact_messages(
     let anchors_section = anchor_summary_section(workspace);
 
     // Build new message list with enhanced summary as system block
+    let label = DigestLabel {
+        tier: 1,
+        segments: None,
+    };
     let summary_block = SystemBlock {
         block_type: "text".to_string(),
         text: format!(
-            "{anchors_section}\
+            "{label}\n\
+             {anchors_section}\
              ## 📋 Conversation Summary (Auto-Generated)\n\n\
              {summary}\n\n\
              ---\n\n\
@@ -1116,7 +1144,7 @@ pub async fn compact_messages(
              ## 💡 What to Do Next\n\n\
              You have just resumed from a context compaction. The conversation above was summarized to save space. \
              Review the summary and workflow context, then continue helping the user with their task. \
-             If you need more details about the summarized portion, ask the user to clarify.\n\n\
+             {RECALL_HINT}\n\n\
              ---\n\n\
              Pinned messages follow:"
         ),
@@ -1138,7 +1166,7 @@ pub async fn compact_messages(
     Ok((
         pinned_messages,
         Some(SystemPrompt::Blocks(vec![summary_block])),
-        to_summarize,
+        plan.summarize_indices,
     ))
 }
 
@@ -1563,6 +1591,235 @@ pub fn merge_system_prompts(
     }
 }
 
+const RECALL_HINT: &str = "If a digest lacks a detail you need (exact code, error text, a decision's \
+     rationale), call `recall_history` with one of its archived segments to read the original \
+     messages verbatim.";
+
+/// Machine-readable first line of a digest block, e.g.
+/// `[digest tier=2 segments=1-4]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigestLabel {
+    pub tier: u8,
+    /// First and last archive segment summarized; `None` when the originals
+    /// could not be archived.
+    pub segments: Option<(u32, u32)>,
+}
+
+impl DigestLabel {
+    /// The label on the first line of `text`, if it is a digest.
+    #[must_use]
+    pub fn parse(text: &str) -> Option<Self> {
+        let inner = text
+            .lines()
+            .next()?
+            .strip_prefix("[digest ")?
+            .strip_suffix(']')?;
+        let mut tier = None;
+        let mut segments = None;
+        for part in inner.split_whitespace() {
+            match part.split_once('=')? {
+                ("tier", value) => tier = value.parse().ok(),
+                ("segments", value) => {
+                    let (first, last) = value.split_once('-').unwrap_or((value, value));
+                    segments = Some((first.parse().ok()?, last.parse().ok()?));
+                }
+                _ => {}
+            }
+        }
+        Some(Self {
+            tier: tier?,
+            segments,
+        })
+    }
+}
+
+impl std::fmt::Display for DigestLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[digest tier={}", self.tier)?;
+        match self.segments {
+            Some((first, last)) if first == last => write!(f, " segments={first}")?,
+            Some((first, last)) => write!(f, " segments={first}-{last}")?,
+            None => {}
+        }
+        f.write_str("]")
+    }
+}
+
+/// `text` without its digest label line.
+fn digest_body(text: &str) -> &str {
+    if DigestLabel::parse(text).is_some() {
+        text.split_once('\n').map_or("", |(_, rest)| rest)
+    } else {
+        text
+    }
+}
+
+/// Record the archive segment holding the originals of a fresh digest.
+pub fn set_digest_segment(prompt: &mut SystemPrompt, segment: u32) {
+    let SystemPrompt::Blocks(blocks) = prompt else {
+        return;
+    };
+    for block in blocks {
+        if let Some(label) = DigestLabel::parse(&block.text)
+            && label.segments.is_none()
+        {
+            let label = DigestLabel {
+                segments: Some((segment, segment)),
+                ..label
+            };
+            block.text = format!("{label}\n{}", digest_body(&block.text));
+        }
+    }
+}
+
+/// The digests to fold next — the oldest [`DIGESTS_PER_TIER`] of the
+/// lowest tier holding more than that many — and the tier they fold into.
+#[must_use]
+pub fn plan_digest_fold(blocks: &[SystemBlock]) -> Option<(Vec<usize>, u8)> {
+    (1..=MAX_DIGEST_TIER).find_map(|tier| {
+        let at_tier: Vec<usize> = blocks
+            .iter()
+            .enumerate()
+            .filter(|(_, block)| DigestLabel::parse(&block.text).is_some_and(|l| l.tier == tier))
+            .map(|(idx, _)| idx)
+            .collect();
+        (at_tier.len() > DIGESTS_PER_TIER).then(|| {
+            (
+                at_tier[..DIGESTS_PER_TIER].to_vec(),
+                (tier + 1).min(MAX_DIGEST_TIER),
+            )
+        })
+    })
+}
+
+/// Condense `digests` into one digest at `tier`. When the summary call
+/// fails the digests are truncated locally instead, so a fold never drops
+/// the segment references.
+pub async fn fold_digests(
+    client: &DeepSeekClient,
+    model: &str,
+    digests: &[SystemBlock],
+    tier: u8,
+) -> SystemBlock {
+    let bodies: Vec<&str> = digests.iter().map(|b| digest_body(&b.text)).collect();
+    let summary = match client
+        .create_message(build_digest_fold_request(model, &bodies))
+        .await
+    {
+        Ok(response) => {
+            crate::cost_status::report(&response.model, &response.usage);
+            response
+                .content
+                .iter()
+                .filter_map(|block| match block {
+                    ContentBlock::Text { text, .. } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        Err(err) => {
+            logging::warn(format!(
+                "Digest fold summary failed ({err}); truncating digests locally"
+            ));
+            String::new()
+        }
+    };
+    let summary = if summary.trim().is_empty() {
+        fallback_fold_text(&bodies)
+    } else {
+        summary
+    };
+    folded_digest(digests, tier, &summary)
+}
+
+fn folded_digest(digests: &[SystemBlock], tier: u8, summary: &str) -> SystemBlock {
+    let segments = digests
+        .iter()
+        .filter_map(|b| DigestLabel::parse(&b.text)?.segments)
+        .reduce(|(a_first, a_last), (b_first, b_last)| (a_first.min(b_first), a_last.max(b_last)));
+    let label = DigestLabel { tier, segments };
+    SystemBlock {
+        block_type: "text".to_string(),
+        text: format!(
+            "{label}\n\
+             ## 📋 Conversation Summary (Auto-Generated) — tier {tier}\n\n\
+             {summary}\n\n\
+             ---\n\n\
+             {RECALL_HINT}"
+        ),
+        cache_control: digests.first().and_then(|b| b.cache_control.clone()),
+    }
+}
+
+fn fallback_fold_text(bodies: &[&str]) -> String {
+    let per_digest = DIGEST_FOLD_FALLBACK_CHARS / bodies.len().max(1);
+    bodies
+        .iter()
+        .map(|body| {
+            let body = body.trim();
+            let cut = truncate_chars(body, per_digest);
+            if cut.len() < body.len() {
+                format!("{cut}\n[…]")
+            } else {
+                cut.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n---\n\n")
+}
+
+fn build_digest_fold_request(model: &str, bodies: &[&str]) -> MessageRequest {
+    let mut text = String::from(
+        "Merge these conversation digests (oldest first) into one shorter digest. \
+         Keep decisions, exact file paths, commands, errors, and open tasks; drop \
+         detail that later digests supersede. Keep it under 600 words.",
+    );
+    for (idx, body) in bodies.iter().enumerate() {
+        let _ = write!(text, "\n\n--- Digest {} ---\n\n{}", idx + 1, body.trim());
+    }
+    MessageRequest {
+        model: model.to_string(),
+        messages: vec![Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::Text {
+                text,
+                cache_control: None,
+            }],
+        }],
+        max_tokens: DIGEST_FOLD_MAX_TOKENS,
+        system: Some(SystemPrompt::Text(
+            "You are a helpful assistant that condenses conversation summaries.".to_string(),
+        )),
+        tools: None,
+        tool_choice: None,
+        metadata: None,
+        thinking: None,
+        reasoning_effort: None,
+        stream: Some(false),
+        temperature: Some(0.3),
+        top_p: None,
+    }
+}
+
+/// Replace the `folded` digests in `prompt` with `replacement`, at the
+/// position of the first of them.
+pub fn replace_digests(
+    prompt: &mut SystemPrompt,
+    folded: &[SystemBlock],
+    replacement: SystemBlock,
+) {
+    let SystemPrompt::Blocks(blocks) = prompt else {
+        return;
+    };
+    let is_folded = |block: &SystemBlock| folded.iter().any(|f| f.text == block.text);
+    let Some(at) = blocks.iter().position(is_folded) else {
+        return;
+    };
+    blocks.retain(|block| !is_folded(block));
+    blocks.insert(at, replacement);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2795,6 +3052,95 @@ mod tests {
         }
     }
 
+    fn digest(tier: u8, segment: u32) -> SystemBlock {
+        SystemBlock {
+            block_type: "text".to_string(),
+            text: format!(
+                "{}\nsummary of segment {segment}",
+                DigestLabel {
+                    tier,
+                    segments: Some((segment, segment)),
+                }
+            ),
+            cache_control: None,
+        }
+    }
+
+    #[test]
+    fn digest_labels_round_trip_and_take_their_segment() {
+        for label in [
+            DigestLabel {
+                tier: 1,
+                segments: None,
+            },
+            DigestLabel {
+                tier: 2,
+                segments: Some((3, 3)),
+            },
+            DigestLabel {
+                tier: 3,
+                segments: Some((1, 7)),
+            },
+        ] {
+            assert_eq!(DigestLabel::parse(&format!("{label}\nbody")), Some(label));
+        }
+        assert_eq!(DigestLabel::parse("## Conversation Summary"), None);
+
+        let mut prompt = SystemPrompt::Blocks(vec![SystemBlock {
+            block_type: "text".to_string(),
+            text: "[digest tier=1]\nthe summary".to_string(),
+            cache_control: None,
+        }]);
+        set_digest_segment(&mut prompt, 5);
+        let SystemPrompt::Blocks(blocks) = &prompt else {
+            panic!("Expected Blocks");
+        };
+        assert_eq!(blocks[0].text, "[digest tier=1 segments=5]\nthe summary");
+    }
+
+    #[test]
+    fn digests_fold_oldest_first_into_the_next_tier() {
+        let base = SystemBlock {
+            block_type: "text".to_string(),
+            text: "base prompt".to_string(),
+            cache_control: None,
+        };
+        let mut blocks = vec![base.clone()];
+        blocks.extend((1..=DIGESTS_PER_TIER as u32).map(|s| digest(1, s)));
+        assert_eq!(plan_digest_fold(&blocks), None);
+
+        blocks.push(digest(1, DIGESTS_PER_TIER as u32 + 1));
+        let (indices, tier) = plan_digest_fold(&blocks).expect("fold");
+        assert_eq!(indices, (1..=DIGESTS_PER_TIER).collect::<Vec<_>>());
+        assert_eq!(tier, 2);
+
+        let folded: Vec<SystemBlock> = indices.iter().map(|&i| blocks[i].clone()).collect();
+        let bodies: Vec<&str> = folded.iter().map(|b| digest_body(&b.text)).collect();
+        let merged = folded_digest(&folded, tier, &fallback_fold_text(&bodies));
+        assert!(
+            merged
+                .text
+                .starts_with(&format!("[digest tier=2 segments=1-{DIGESTS_PER_TIER}]\n"))
+        );
+        assert!(
+            merged
+                .text
+                .contains("Conversation Summary (Auto-Generated)")
+        );
+        assert!(merged.text.contains("summary of segment 1"));
+
+        let mut prompt = SystemPrompt::Blocks(blocks);
+        replace_digests(&mut prompt, &folded, merged.clone());
+        let SystemPrompt::Blocks(blocks) = prompt else {
+            panic!("Expected Blocks");
+        };
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0], base);
+        assert_eq!(blocks[1], merged);
+        assert_eq!(DigestLabel::parse(&blocks[2].text).map(|l| l.tier), Some(1));
+        assert_eq!(plan_digest_fold(&blocks), None);
+    }
+
     #[test]
     fn test_compaction_result_retries_used() {
         // This test verifies the CompactionResult structure
@@ -1,6 +1,6 @@
 //! Configuration loading and defaults for DeepSeek TUI.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write;
 use std::fs;
 #[cfg(unix)]
@@ -8,13 +8,16 @@ use std::io::Write as _;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
+use deepseek_config::org::OrgLayer;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 #[cfg(unix)]
 use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
 
 use crate::audit::log_sensitive_event;
-use crate::features::{Features, FeaturesToml, is_known_feature_key};
+use crate::features::{
+    Feature, FeatureEntry, Features, FeaturesToml, is_known_feature_key, validate_feature_entries,
+};
 use crate::hooks::HooksConfig;
 
 pub const DEFAULT_MAX_SUBAGENTS: usize = 10;
@@ -31,6 +34,10 @@ pub const MIN_SUBAGENT_API_TIMEOUT_SECS: u64 = 1;
 /// keeps a misconfigured per-step timeout from masking real model/network
 /// hangs forever.
 pub const MAX_SUBAGENT_API_TIMEOUT_SECS: u64 = 1800;
+/// Default sub-agent step budget when `[subagents] max_steps` is unset.
+pub const DEFAULT_SUBAGENT_MAX_STEPS: u32 = 100;
+/// Upper bound for `[subagents] max_steps`.
+pub const MAX_SUBAGENT_MAX_STEPS: u32 = 500;
 pub const DEFAULT_TEXT_MODEL: &str = "deepseek-v4-pro";
 pub const DEFAULT_DEEPSEEK_BASE_URL: &str = "https://api.deepseek.com/beta";
 pub const DEFAULT_NVIDIA_NIM_MODEL: &str = "deepseek-ai/deepseek-v4-pro";
@@ -535,6 +542,42 @@ pub struct NotificationsConfig {
     /// Default: `false`.
     #[serde(default)]
     pub include_summary: bool,
+    /// Also notify, regardless of `threshold_secs`, when the terminal has
+    /// reported losing focus. Terminals that never report focus changes are
+    /// treated as focused. Default: `true`.
+    #[serde(default)]
+    pub when_unfocused: Option<bool>,
+    /// Also raise an OS desktop notification (`notify-send` on Linux,
+    /// `osascript` on macOS). Default: `false`.
+    #[serde(default)]
+    pub desktop: bool,
+    /// Ring the terminal bell in addition to `method`. Default: `false`.
+    #[serde(default)]
+    pub bell: bool,
+    /// Overrides for turn-completion notifications (`[notifications.turn]`).
+    #[serde(default)]
+    pub turn: NotificationEventConfig,
+    /// Overrides for sub-agent completion notifications
+    /// (`[notifications.subagent]`).
+    #[serde(default)]
+    pub subagent: NotificationEventConfig,
+}
+
+/// Per-event `[notifications.<event>]` overrides. Unset fields fall back to
+/// the `[notifications]` values.
+#[derive(Debug, Clone, Deserialize, Default, PartialEq, Eq)]
+pub struct NotificationEventConfig {
+    /// `false` silences this event. Default: `true`.
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub threshold_secs: Option<u64>,
+    #[serde(default)]
+    pub when_unfocused: Option<bool>,
+    #[serde(default)]
+    pub desktop: Option<bool>,
+    #[serde(default)]
+    pub bell: Option<bool>,
 }
 
 fn default_snapshots_enabled() -> bool {
@@ -566,6 +609,10 @@ pub struct SnapshotsConfig {
     /// what would actually land in a snapshot commit.
     #[serde(default = "default_snapshot_max_workspace_gb")]
     pub max_workspace_gb: u64,
+    /// Save the named snapshot `auto` before the first file write of each
+    /// turn, so `/snapshot restore auto` undoes the latest turn's edits.
+    #[serde(default)]
+    pub auto_before_write: bool,
 }
 
 impl Default for SnapshotsConfig {
@@ -574,6 +621,143 @@ impl Default for SnapshotsConfig {
             enabled: default_snapshots_enabled(),
             max_age_days: default_snapshot_max_age_days(),
             max_workspace_gb: default_snapshot_max_workspace_gb(),
+            auto_before_write: false,
+        }
+    }
+}
+
+/// Saved-session retention (`[sessions]`). Sessions that are pinned or
+/// tagged are never collected; everything else is removed oldest-first once
+/// a limit is exceeded. `0` disables a limit.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SessionsConfig {
+    /// Keep at most this many saved sessions.
+    #[serde(default = "default_sessions_max_sessions")]
+    pub max_sessions: usize,
+    /// Remove sessions not updated in this many days.
+    #[serde(default)]
+    pub max_age_days: u64,
+    /// Cap on the disk used by saved sessions and their artifacts, in MB.
+    #[serde(default)]
+    pub max_total_mb: u64,
+    /// Apply the limits when the TUI starts.
+    #[serde(default = "default_sessions_auto_gc")]
+    pub auto_gc: bool,
+}
+
+fn default_sessions_max_sessions() -> usize {
+    crate::session_manager::DEFAULT_MAX_SESSIONS
+}
+
+fn default_sessions_auto_gc() -> bool {
+    true
+}
+
+impl Default for SessionsConfig {
+    fn default() -> Self {
+        Self {
+            max_sessions: default_sessions_max_sessions(),
+            max_age_days: 0,
+            max_total_mb: 0,
+            auto_gc: default_sessions_auto_gc(),
+        }
+    }
+}
+
+impl SessionsConfig {
+    #[must_use]
+    pub fn retention(&self) -> crate::session_manager::SessionRetention {
+        crate::session_manager::SessionRetention {
+            max_sessions: (self.max_sessions > 0).then_some(self.max_sessions),
+            max_age: (self.max_age_days > 0).then(|| {
+                std::time::Duration::from_secs(self.max_age_days.saturating_mul(24 * 60 * 60))
+            }),
+            max_total_bytes: (self.max_total_mb > 0)
+                .then(|| self.max_total_mb.saturating_mul(1024 * 1024)),
+        }
+    }
+}
+
+/// Read-only tool result cache configuration.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ToolCacheConfig {
+    /// `turn` (default) reuses identical read-only tool calls within one
+    /// turn, `session` keeps them across turns until the workspace changes,
+    /// and `off` disables the cache.
+    #[serde(default)]
+    pub scope: crate::tools::result_cache::ToolCacheScope,
+}
+
+/// `[prompt_layers]` — disable, replace, reorder, or add named system
+/// prompt layers. Layer names are listed in
+/// [`crate::prompts::layers::BUILTIN_LAYERS`]; `/system` shows the live
+/// stack.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PromptLayersConfig {
+    /// Layers to leave out of the system prompt.
+    #[serde(default)]
+    pub disabled: Vec<String>,
+    /// Layers to move to the front, in this order.
+    #[serde(default)]
+    pub order: Vec<String>,
+    /// Layer name → file whose contents replace that layer's text.
+    #[serde(default)]
+    pub replace: BTreeMap<String, String>,
+    /// Extra user-defined layers.
+    #[serde(default)]
+    pub custom: Vec<CustomPromptLayerToml>,
+}
+
+/// One `[[prompt_layers.custom]]` entry. Set either `text` or `file`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CustomPromptLayerToml {
+    pub name: String,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub file: Option<String>,
+}
+
+impl PromptLayersConfig {
+    /// Resolve paths (`~` expansion) into runtime overrides.
+    #[must_use]
+    pub fn into_overrides(self) -> crate::prompts::layers::PromptLayerOverrides {
+        crate::prompts::layers::PromptLayerOverrides {
+            disabled: self.disabled,
+            order: self.order,
+            replace: self
+                .replace
+                .into_iter()
+                .map(|(name, path)| (name, expand_path(&path)))
+                .collect(),
+            custom: self
+                .custom
+                .into_iter()
+                .map(|layer| crate::prompts::layers::CustomLayer {
+                    name: layer.name,
+                    text: layer.text,
+                    file: layer.file.as_deref().map(expand_path),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// One `[keys]` value: a single chord (`"ctrl+j"`) or a list of chords.
+/// Chord syntax and action names live in [`crate::tui::keymap`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum KeyChordsToml {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyChordsToml {
+    #[must_use]
+    pub fn chords(&self) -> Vec<String> {
+        match self {
+            Self::One(chord) => vec![chord.clone()],
+            Self::Many(chords) => chords.clone(),
         }
     }
 }
@@ -590,6 +774,11 @@ pub struct MemoryConfig {
     /// `# foo` typed in the composer to append to that file. Default `false`.
     #[serde(default)]
     pub enabled: Option<bool>,
+    /// When `true` (the default), failed `exec_shell` commands are recorded
+    /// in `.deepseek/lessons.json` and a digest of them is added to the
+    /// system prompt.
+    #[serde(default)]
+    pub command_lessons: Option<bool>,
 }
 
 impl SnapshotsConfig {
@@ -850,6 +1039,13 @@ pub struct ContextConfig {
     /// prefix. Default: true; set `[context] project_pack = false` to disable.
     #[serde(default)]
     pub project_pack: Option<bool>,
+    /// Include a token-budgeted repo map (directories, LOC, public symbols)
+    /// in the stable prompt prefix. Default: true.
+    #[serde(default)]
+    pub repo_map: Option<bool>,
+    /// Token budget for the repo map layer. Default: 1500.
+    #[serde(default)]
+    pub repo_map_tokens: Option<usize>,
     /// Verbatim window: last N turns never summarized. Default: 16.
     #[serde(default)]
     pub verbatim_window_turns: Option<usize>,
@@ -899,6 +1095,16 @@ pub struct SubagentsConfig {
     /// (1..=1800). Zero or unset uses the legacy 120s default (#1806, #1808).
     #[serde(default)]
     pub api_timeout_secs: Option<u64>,
+    /// Step budget for each sub-agent and the ceiling for per-spawn
+    /// `max_steps` overrides. Clamped to `1..=MAX_SUBAGENT_MAX_STEPS`;
+    /// unset keeps `DEFAULT_SUBAGENT_MAX_STEPS`.
+    #[serde(default)]
+    pub max_steps: Option<u32>,
+    /// Models the parent may request through the spawn tool's `model`
+    /// argument. Unset allows any DeepSeek model; role/type models set in
+    /// this table are the user's own choice and are not restricted.
+    #[serde(default)]
+    pub allowed_models: Option<Vec<String>>,
 }
 
 /// `[auto]` table — knobs for the `--model auto` / `/model auto` router.
@@ -913,6 +1119,138 @@ pub struct AutoConfig {
     pub cost_saving: Option<bool>,
 }
 
+/// `[exec_profiles.<name>]` table — a command prefix that `exec_shell`
+/// wraps around every command while the profile is active (`/profile use`).
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct ExecProfileConfig {
+    /// Argv placed before the wrapped `sh -c <command>`, e.g.
+    /// `["docker", "exec", "-w", "/work", "dev"]`.
+    #[serde(default)]
+    pub command_prefix: Vec<String>,
+    /// Pass `sh -c <command>` as one quoted argument instead of separate
+    /// arguments. Needed for wrappers that re-parse their arguments with a
+    /// remote shell; defaults to true when the prefix starts with `ssh`.
+    #[serde(default)]
+    pub join_command: Option<bool>,
+}
+
+/// `[guardrails]` table — how shell commands are risk-scanned before the
+/// approval prompt.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GuardrailsConfig {
+    /// Scan shell commands and show the risk level in the approval prompt.
+    /// Default `true`.
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// Org rules checked alongside the built-in dangerous-pattern list.
+    #[serde(default)]
+    pub rules: Vec<GuardrailRuleConfig>,
+}
+
+/// `[[guardrails.rules]]` entry.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct GuardrailRuleConfig {
+    /// Shown in the approval prompt as `org:<name>`.
+    pub name: String,
+    /// Execpolicy-style pattern matched against the whole command; `*`
+    /// matches anything (`"*terraform destroy*"`).
+    pub pattern: String,
+    /// `low`, `medium`, or `high` (default). High risk always asks for a
+    /// typed confirmation, even in YOLO mode.
+    #[serde(default)]
+    pub risk: Option<crate::command_guardrail::GuardrailRisk>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// `[trail]` table — per-turn audit commits on a dedicated branch.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TrailConfig {
+    /// Commit the workspace to the trail branch after every turn that ran
+    /// tools and changed files. Default `false`; `/trail on` enables it for
+    /// the session.
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// Branch that receives the commits. Default `deepseek/trail`.
+    #[serde(default)]
+    pub branch: Option<String>,
+}
+
+/// `[thinking]` table — per-turn limits on reasoning-model thinking.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ThinkingConfig {
+    /// Estimated thinking tokens allowed per turn before the engine stops the
+    /// reasoning stream and finishes the turn with thinking off. Unset or `0`
+    /// means no cap. `/thinking budget <n>` overrides it for the session.
+    #[serde(default)]
+    pub budget_tokens: Option<u32>,
+    /// Abort thinking that keeps repeating the same sentences. Default `true`.
+    #[serde(default)]
+    pub abort_repetition: Option<bool>,
+    /// Run follow-up turns that look simple (formatting, renames) without
+    /// thinking. Default `false`.
+    #[serde(default)]
+    pub auto_off: Option<bool>,
+    /// Model used for turns that `auto_off` classifies as simple. Unset keeps
+    /// the current model with thinking off.
+    #[serde(default)]
+    pub chat_model: Option<String>,
+}
+
+/// `[models."<model-id>"]` table — request parameters applied whenever that
+/// model is active. Unset fields keep the built-in behaviour.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct ModelPreset {
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+    /// Output-token cap; replaces the per-model default.
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+    /// Thinking tier (`off` | `low` | `medium` | `high` | `max` | `auto`)
+    /// selected when `/model` or the model picker switches to this model.
+    #[serde(default, alias = "thinking")]
+    pub thinking_budget: Option<String>,
+}
+
+impl ModelPreset {
+    /// One-line summary of the fields this preset sets, e.g.
+    /// `temperature 0.6 · max_tokens 8192`.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(temperature) = self.temperature {
+            parts.push(format!("temperature {temperature}"));
+        }
+        if let Some(top_p) = self.top_p {
+            parts.push(format!("top_p {top_p}"));
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            parts.push(format!("max_tokens {max_tokens}"));
+        }
+        if let Some(thinking) = self.thinking_budget.as_deref() {
+            parts.push(format!("thinking {}", thinking.trim()));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Look up the preset for `model`. Keys match case-insensitively, so
+/// `[models."DeepSeek-V4-Pro"]` applies to `deepseek-v4-pro`.
+#[must_use]
+pub fn model_preset<'a>(
+    presets: &'a BTreeMap<String, ModelPreset>,
+    model: &str,
+) -> Option<&'a ModelPreset> {
+    presets.get(model).or_else(|| {
+        presets
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(model))
+            .map(|(_, preset)| preset)
+    })
+}
+
 /// Resolved CLI configuration, including defaults and environment overrides.
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct Config {
@@ -934,6 +1272,10 @@ pub struct Config {
     /// schemas into DeepSeek beta strict mode. Schemas with root alternatives
     /// stay non-strict to avoid changing optional/one-of tool semantics.
     pub strict_tool_mode: Option<bool>,
+    /// Ask the model to end each final answer with a `<self_report>` footer
+    /// (confidence, assumptions, files not inspected). The TUI shows it as
+    /// an annotation under the reply.
+    pub self_report: Option<bool>,
     /// Additional system-prompt sources concatenated in declared order
     /// (#454). Paths are expanded via `expand_path` so `~` and env
     /// vars work. Project config overrides user config (replace, not
@@ -994,6 +1336,26 @@ pub struct Config {
     #[serde(default)]
     pub snapshots: Option<SnapshotsConfig>,
 
+    /// Saved-session retention. Defaults to keeping the 50 most recent
+    /// sessions, collected at TUI startup, when the table is absent.
+    #[serde(default)]
+    pub sessions: Option<SessionsConfig>,
+
+    /// Read-only tool result cache. Defaults to per-turn caching when the
+    /// table is absent.
+    #[serde(default)]
+    pub tool_cache: Option<ToolCacheConfig>,
+
+    /// System prompt layer overrides. When absent the built-in layer stack
+    /// is used unchanged.
+    #[serde(default)]
+    pub prompt_layers: Option<PromptLayersConfig>,
+
+    /// Keybinding overrides: action name → chord or list of chords. Actions
+    /// left out keep their default chords; `/keys` shows the result.
+    #[serde(default)]
+    pub keys: Option<BTreeMap<String, KeyChordsToml>>,
+
     /// Web search provider configuration. When absent, defaults to Bing.
     /// Set `provider` to `duckduckgo`, `tavily`, or `bocha` to use those
     /// services instead; Tavily and Bocha also require an `api_key`.
@@ -1011,11 +1373,60 @@ pub struct Config {
     #[serde(default)]
     pub auto: Option<AutoConfig>,
 
+    /// Per-model request presets keyed by model ID
+    /// (`[models."deepseek-v4-pro"] temperature = 0.6`).
+    #[serde(default)]
+    pub models: Option<BTreeMap<String, ModelPreset>>,
+
+    /// Models retried in order when a turn request fails with an overload,
+    /// 5xx, rate-limit, or context-length error
+    /// (`model_fallbacks = ["deepseek-v4-flash"]`).
+    #[serde(default)]
+    pub model_fallbacks: Option<Vec<String>>,
+
+    /// Named command wrappers for `exec_shell`, switched with
+    /// `/profile use <name>` (`[exec_profiles.docker]`).
+    #[serde(default)]
+    pub exec_profiles: Option<BTreeMap<String, ExecProfileConfig>>,
+
+    /// Environment variables injected into every shell-tool process for the
+    /// session (`[env] RUST_LOG = "debug"`); edit live with `/env set`.
+    #[serde(default)]
+    pub env: Option<BTreeMap<String, String>>,
+
+    /// CPU/memory/file-size caps for shell commands, keyed by sandbox policy
+    /// (`[shell_limits.default]`, `[shell_limits.read-only]`, ...).
+    #[serde(default)]
+    pub shell_limits: Option<BTreeMap<String, crate::sandbox::ResourceLimits>>,
+
+    /// Risk scanning of shell commands before approval, plus org-specific
+    /// rules (`[[guardrails.rules]]`).
+    #[serde(default)]
+    pub guardrails: Option<GuardrailsConfig>,
+
     /// Post-edit LSP diagnostics injection (#136). When absent, the engine
     /// applies the defaults documented in [`LspConfigToml`].
     #[serde(default)]
     pub lsp: Option<LspConfigToml>,
 
+    /// Syntax checks that `write_file`, `edit_file`, and `apply_patch` run
+    /// before writing. On by default for JSON, TOML, Rust, Python, and shell.
+    #[serde(default)]
+    pub edit_validation: Option<crate::tools::edit_validation::EditValidationConfig>,
+
+    /// Per-tool filters applied to results before they reach the model
+    /// (`[tool_filters.<tool>]`). The raw output stays retrievable.
+    #[serde(default)]
+    pub tool_filters: Option<crate::tools::output_filter::ToolFilters>,
+
+    /// Thinking-token budget and auto-off heuristics (`[thinking]`).
+    #[serde(default)]
+    pub thinking: Option<ThinkingConfig>,
+
+    /// Per-turn git audit trail (`[trail]`).
+    #[serde(default)]
+    pub trail: Option<TrailConfig>,
+
     /// Append-only layered context management with Flash seam manager (#159).
     #[serde(default)]
     pub context: ContextConfig,
@@ -1039,6 +1450,42 @@ pub struct Config {
     /// Vision model configuration for the `image_analyze` tool.
     #[serde(default)]
     pub vision_model: Option<VisionModelConfig>,
+
+    /// Per-prompt automatic file selection (`/smartcontext`). Off unless
+    /// the table sets `enabled = true`.
+    #[serde(default)]
+    pub smart_context: Option<SmartContextConfig>,
+
+    /// Post-conditions for `deepseek exec --gate` (`[gate]`).
+    #[serde(default)]
+    pub gate: Option<crate::ci_gate::GateConfig>,
+}
+
+/// `[smart_context]` table — automatic context file selection.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct SmartContextConfig {
+    /// Start sessions with smart context on. Toggle at runtime with
+    /// `/smartcontext on|off`.
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// Files auto-included per prompt (1-8). Defaults to 3.
+    #[serde(default)]
+    pub max_files: Option<usize>,
+}
+
+impl SmartContextConfig {
+    #[must_use]
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(false)
+    }
+
+    /// Resolve the per-prompt file cap, clamped to the supported range.
+    #[must_use]
+    pub fn max_files(&self) -> usize {
+        self.max_files
+            .unwrap_or(crate::tui::smart_context::DEFAULT_SMART_CONTEXT_FILES)
+            .clamp(1, crate::tui::smart_context::MAX_SMART_CONTEXT_FILES)
+    }
 }
 
 /// Vision model configuration for the `image_analyze` tool.
@@ -1067,9 +1514,72 @@ pub struct RuntimeApiConfig {
     /// `DEEPSEEK_CORS_ORIGINS` env var (comma-separated), this field. Whalescale#255 / #561.
     #[serde(default)]
     pub cors_origins: Option<Vec<String>>,
+    /// Largest file, in bytes, a client may upload through
+    /// `POST /v1/workspace/files`. Defaults to 256 MiB.
+    #[serde(default)]
+    pub max_upload_bytes: Option<u64>,
+    /// Run `POST /v1/chat/completions` requests as agent turns with tools.
+    /// `false` forwards them unchanged to the provider. Defaults to true.
+    #[serde(default)]
+    pub openai_tools: Option<bool>,
+    /// Where the server writes its control token (mode 0600) for local
+    /// clients such as the desktop shell. Defaults to
+    /// `~/.deepseek/runtime/token`; `--token-file` overrides it.
+    #[serde(default)]
+    pub token_file: Option<String>,
+    /// Optional second bearer token limited to `GET`/`HEAD` routes.
+    /// `--read-token` and `DEEPSEEK_RUNTIME_READ_TOKEN` take precedence.
+    #[serde(default)]
+    pub read_only_token: Option<String>,
+    /// Cap on turn-creating requests (`/v1/stream`, thread turns, tasks,
+    /// `/v1/chat/completions`, automation runs) per minute. `0` disables.
+    /// Defaults to 30.
+    #[serde(default)]
+    pub turns_per_minute: Option<u32>,
+    /// TLS termination; see [`RuntimeApiTlsConfig`].
+    #[serde(default)]
+    pub tls: Option<RuntimeApiTlsConfig>,
+}
+
+/// `[runtime_api.tls]` — serve the runtime API over HTTPS.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct RuntimeApiTlsConfig {
+    /// PEM certificate chain.
+    #[serde(default)]
+    pub cert: Option<String>,
+    /// PEM private key for `cert`.
+    #[serde(default)]
+    pub key: Option<String>,
+    /// PEM CA bundle; when set, clients must present a certificate it signed.
+    #[serde(default)]
+    pub client_ca: Option<String>,
+}
+
+/// Default cap for `POST /v1/workspace/files` uploads.
+pub const DEFAULT_RUNTIME_MAX_UPLOAD_BYTES: u64 = 256 * 1024 * 1024;
+
+impl RuntimeApiConfig {
+    /// Resolve the upload size cap with the bundled default.
+    #[must_use]
+    pub fn max_upload_bytes(&self) -> u64 {
+        self.max_upload_bytes
+            .filter(|bytes| *bytes > 0)
+            .unwrap_or(DEFAULT_RUNTIME_MAX_UPLOAD_BYTES)
+    }
+
+    /// Configured token file path, with `~` expanded.
+    #[must_use]
+    pub fn token_file(&self) -> Option<PathBuf> {
+        self.token_file
+            .as_deref()
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(expand_path)
+    }
 }
 
-/// `[skills]` table — knobs for the community-skill installer.
+/// `[skills]` table — knobs for the community-skill installer and skill
+/// auto-activation.
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct SkillsConfig {
     /// Curated registry index. `/skill install <name>` looks up the spec here.
@@ -1080,6 +1590,11 @@ pub struct SkillsConfig {
     /// this limit are rejected during validation. Defaults to 5 MiB.
     #[serde(default)]
     pub max_install_size_bytes: Option<u64>,
+    /// Token budget for skills auto-activated by their frontmatter rules in
+    /// one turn. `0` turns auto-activation off. Defaults to
+    /// [`crate::skills::activation::DEFAULT_AUTO_ACTIVATE_TOKENS`].
+    #[serde(default)]
+    pub auto_activate_tokens: Option<usize>,
 }
 
 impl SkillsConfig {
@@ -1295,6 +1810,7 @@ impl Config {
             Config::default()
         };
 
+        apply_org_layer(&mut config)?;
         apply_env_overrides(&mut config);
         apply_managed_overrides(&mut config)?;
         apply_requirements(&mut config)?;
@@ -1371,9 +1887,15 @@ impl Config {
             anyhow::bail!("api_key cannot be empty string");
         }
         if let Some(features) = &self.features {
-            for key in features.entries.keys() {
-                if !is_known_feature_key(key) {
-                    anyhow::bail!("Unknown feature flag: {key}");
+            validate_feature_entries(&features.entries).map_err(|err| anyhow::anyhow!(err))?;
+            let features = self.features();
+            for key in ["low_risk_max", "medium_risk_max"] {
+                if let Some(v) = features.float_value(Feature::CapacityController, key)
+                    && !(0.0..=1.0).contains(&v)
+                {
+                    anyhow::bail!(
+                        "Invalid features.capacity_controller.{key} '{v}': expected a value in [0.0, 1.0]."
+                    );
                 }
             }
         }
@@ -1398,6 +1920,15 @@ impl Config {
                 );
             }
         }
+        for (key, limits) in self.shell_limits.iter().flatten() {
+            if !crate::sandbox::limits::POLICY_KEYS.contains(&key.as_str()) {
+                anyhow::bail!(
+                    "Invalid shell_limits key '{key}': expected one of {}.",
+                    crate::sandbox::limits::POLICY_KEYS.join(", ")
+                );
+            }
+            limits.validate(key).map_err(anyhow::Error::msg)?;
+        }
         if let Some(mode) = self.sandbox_mode.as_deref() {
             let normalized = mode.trim().to_ascii_lowercase();
             if !matches!(
@@ -1727,6 +2258,12 @@ impl Config {
         }
     }
 
+    /// The `[shell_limits]` table, ready to resolve per sandbox policy.
+    #[must_use]
+    pub fn shell_limits(&self) -> crate::sandbox::limits::ShellLimits {
+        crate::sandbox::limits::ShellLimits::new(self.shell_limits.clone().unwrap_or_default())
+    }
+
     /// Resolve the skills directory path.
     #[must_use]
     pub fn skills_dir(&self) -> PathBuf {
@@ -1796,6 +2333,44 @@ impl Config {
             .unwrap_or(false)
     }
 
+    /// Whether failed shell commands are remembered per workspace. On by
+    /// default; `[memory] command_lessons = false` disables it.
+    #[must_use]
+    pub fn command_lessons_enabled(&self) -> bool {
+        self.memory
+            .as_ref()
+            .and_then(|m| m.command_lessons)
+            .unwrap_or(true)
+    }
+
+    /// Whether shell commands are guardrail-scanned before approval. On by
+    /// default; `[guardrails] enabled = false` disables it.
+    #[must_use]
+    pub fn guardrails_enabled(&self) -> bool {
+        self.guardrails
+            .as_ref()
+            .and_then(|g| g.enabled)
+            .unwrap_or(true)
+    }
+
+    /// Whether turns are committed to the trail branch. Off by default.
+    #[must_use]
+    pub fn trail_enabled(&self) -> bool {
+        self.trail.as_ref().and_then(|t| t.enabled).unwrap_or(false)
+    }
+
+    /// Branch that receives trail commits (`[trail] branch`).
+    #[must_use]
+    pub fn trail_branch(&self) -> String {
+        self.trail
+            .as_ref()
+            .and_then(|t| t.branch.as_deref())
+            .map(str::trim)
+            .filter(|branch| !branch.is_empty())
+            .unwrap_or(crate::git_trail::DEFAULT_TRAIL_BRANCH)
+            .to_string()
+    }
+
     /// Return the configured vision model config, inheriting api_key from main config.
     #[must_use]
     pub fn vision_model_config(&self) -> Option<VisionModelConfig> {
@@ -1811,6 +2386,32 @@ impl Config {
         self.context.project_pack.unwrap_or(true)
     }
 
+    /// Token budget for rule-matched skill auto-activation, or `None` when
+    /// `[skills] auto_activate_tokens = 0`.
+    #[must_use]
+    pub fn skills_auto_activate_tokens(&self) -> Option<usize> {
+        let tokens = self
+            .skills
+            .as_ref()
+            .and_then(|skills| skills.auto_activate_tokens)
+            .unwrap_or(crate::skills::activation::DEFAULT_AUTO_ACTIVATE_TOKENS);
+        (tokens > 0).then_some(tokens)
+    }
+
+    /// Token budget for the repo map prompt layer, or `None` when
+    /// `[context] repo_map = false` (or the budget is zero).
+    #[must_use]
+    pub fn repo_map_tokens(&self) -> Option<usize> {
+        if !self.context.repo_map.unwrap_or(true) {
+            return None;
+        }
+        let tokens = self
+            .context
+            .repo_map_tokens
+            .unwrap_or(crate::repo_map::DEFAULT_REPO_MAP_TOKENS);
+        (tokens > 0).then_some(tokens)
+    }
+
     /// Return whether shell execution is allowed. Defaults to `false`: shell
     /// access must be opted into explicitly (GHSA-72w5-pf8h-xfp4).
     #[must_use]
@@ -1856,6 +2457,30 @@ impl Config {
         raw.clamp(MIN_SUBAGENT_API_TIMEOUT_SECS, MAX_SUBAGENT_API_TIMEOUT_SECS)
     }
 
+    /// Resolved sub-agent step budget from `[subagents] max_steps`, clamped
+    /// to `1..=MAX_SUBAGENT_MAX_STEPS`.
+    #[must_use]
+    pub fn subagent_max_steps(&self) -> u32 {
+        self.subagents
+            .as_ref()
+            .and_then(|cfg| cfg.max_steps)
+            .unwrap_or(DEFAULT_SUBAGENT_MAX_STEPS)
+            .clamp(1, MAX_SUBAGENT_MAX_STEPS)
+    }
+
+    /// Normalized `[subagents] allowed_models`. Entries that are not valid
+    /// model ids are dropped; `None` means any model may be requested.
+    #[must_use]
+    pub fn subagent_allowed_models(&self) -> Option<Vec<String>> {
+        let models = self.subagents.as_ref()?.allowed_models.as_ref()?;
+        Some(
+            models
+                .iter()
+                .filter_map(|model| normalize_model_name(model.trim()))
+                .collect(),
+        )
+    }
+
     /// Raw sub-agent model override map. Values are validated at spawn time
     /// so an invalid role/type model fails before any partial agent spawn.
     #[must_use]
@@ -1916,10 +2541,52 @@ impl Config {
         self.snapshots.clone().unwrap_or_default()
     }
 
+    /// Resolve saved-session retention with defaults applied.
+    #[must_use]
+    pub fn sessions_config(&self) -> SessionsConfig {
+        self.sessions.clone().unwrap_or_default()
+    }
+
+    /// Resolve `[prompt_layers]` into runtime overrides.
+    #[must_use]
+    pub fn prompt_layer_overrides(&self) -> crate::prompts::layers::PromptLayerOverrides {
+        self.prompt_layers
+            .clone()
+            .map(PromptLayersConfig::into_overrides)
+            .unwrap_or_default()
+    }
+
+    /// Build the keymap from `[keys]`, falling back to default chords.
+    #[must_use]
+    pub fn keymap(&self) -> crate::tui::keymap::Keymap {
+        self.keys
+            .as_ref()
+            .map(crate::tui::keymap::Keymap::from_config)
+            .unwrap_or_default()
+    }
+
+    /// Resolve how long read-only tool results stay cached.
+    #[must_use]
+    pub fn tool_cache_scope(&self) -> crate::tools::result_cache::ToolCacheScope {
+        self.tool_cache
+            .as_ref()
+            .map(|cache| cache.scope)
+            .unwrap_or_default()
+    }
+
     /// Resolve enabled features from defaults and config entries.
     #[must_use]
     pub fn features(&self) -> Features {
         let mut features = Features::with_defaults();
+        // Legacy `[capacity] enabled` still selects the controller; an
+        // explicit `[features] capacity_controller` entry wins over it.
+        if let Some(enabled) = self.capacity.as_ref().and_then(|capacity| capacity.enabled) {
+            if enabled {
+                features.enable(Feature::CapacityController);
+            } else {
+                features.disable(Feature::CapacityController);
+            }
+        }
         if let Some(table) = &self.features {
             features.apply_map(&table.entries);
         }
@@ -1932,7 +2599,11 @@ impl Config {
             anyhow::bail!("Unknown feature flag: {key}");
         }
         let table = self.features.get_or_insert_with(FeaturesToml::default);
-        table.entries.insert(key.to_string(), enabled);
+        table
+            .entries
+            .entry(key.to_string())
+            .and_modify(|entry| entry.set_enabled(enabled))
+            .or_insert(FeatureEntry::Toggle(enabled));
         Ok(())
     }
 
@@ -2016,7 +2687,18 @@ pub(crate) fn is_workspace_trusted(workspace: &Path) -> bool {
     workspace_trust_level_from_doc(&doc, workspace).is_some_and(is_trusted_level)
 }
 
-pub(crate) fn save_workspace_trust(workspace: &Path) -> Result<PathBuf> {
+/// The trust level recorded for `workspace` in the global config, if any.
+/// Legacy `trusted` entries read as `standard`.
+#[must_use]
+pub(crate) fn workspace_trust_level(workspace: &Path) -> Option<crate::trust_level::TrustLevel> {
+    let raw = fs::read_to_string(default_config_path()?).ok()?;
+    let doc = toml::from_str::<toml::Value>(&raw).ok()?;
+    workspace_trust_level_from_doc(&doc, workspace).and_then(crate::trust_level::TrustLevel::parse)
+}
+
+/// Record `level` as `[projects."<workspace>"] trust_level` in the global
+/// config. Returns the config path.
+pub(crate) fn save_workspace_trust(workspace: &Path, level: &str) -> Result<PathBuf> {
     let config_path = default_config_path()
         .context("Failed to resolve config path: home directory not found.")?;
     ensure_parent_dir(&config_path)?;
@@ -2044,7 +2726,7 @@ pub(crate) fn save_workspace_trust(workspace: &Path) -> Result<PathBuf> {
         .context("Project entry must be a table.")?;
     project.insert(
         "trust_level".to_string(),
-        toml::Value::String("trusted".to_string()),
+        toml::Value::String(level.to_string()),
     );
 
     let serialized = toml::to_string_pretty(&doc).context("failed to serialize updated config")?;
@@ -2066,7 +2748,7 @@ fn workspace_trust_level_from_doc<'a>(doc: &'a toml::Value, workspace: &Path) ->
 }
 
 fn is_trusted_level(level: &str) -> bool {
-    level.trim().eq_ignore_ascii_case("trusted")
+    crate::trust_level::TrustLevel::parse(level).is_some_and(|level| !level.restricts_tools())
 }
 
 fn workspace_config_key(workspace: &Path) -> String {
@@ -2914,6 +3596,8 @@ fn merge_config(base: Config, override_cfg: Config) -> Config {
         notes_path: override_cfg.notes_path.or(base.notes_path),
         memory_path: override_cfg.memory_path.or(base.memory_path),
         vision_model: override_cfg.vision_model.or(base.vision_model),
+        smart_context: override_cfg.smart_context.or(base.smart_context),
+        gate: override_cfg.gate.or(base.gate),
         // #454: project's instructions array replaces user's array
         // wholesale. The typical "merge" pattern is for users who want
         // both — they list `~/global.md` inside the project array.
@@ -2940,16 +3624,35 @@ fn merge_config(base: Config, override_cfg: Config) -> Config {
         network: override_cfg.network.or(base.network),
         skills: override_cfg.skills.or(base.skills),
         snapshots: override_cfg.snapshots.or(base.snapshots),
+        sessions: override_cfg.sessions.or(base.sessions),
+        tool_cache: override_cfg.tool_cache.or(base.tool_cache),
+        prompt_layers: override_cfg.prompt_layers.or(base.prompt_layers),
+        keys: override_cfg.keys.or(base.keys),
         search: override_cfg.search.or(base.search),
         memory: override_cfg.memory.or(base.memory),
         auto: override_cfg.auto.or(base.auto),
+        models: override_cfg.models.or(base.models),
+        model_fallbacks: override_cfg.model_fallbacks.or(base.model_fallbacks),
+        exec_profiles: override_cfg.exec_profiles.or(base.exec_profiles),
+        env: override_cfg.env.or(base.env),
+        shell_limits: override_cfg.shell_limits.or(base.shell_limits),
+        guardrails: override_cfg.guardrails.or(base.guardrails),
         lsp: override_cfg.lsp.or(base.lsp),
+        edit_validation: override_cfg.edit_validation.or(base.edit_validation),
+        tool_filters: override_cfg.tool_filters.or(base.tool_filters),
+        thinking: override_cfg.thinking.or(base.thinking),
+        trail: override_cfg.trail.or(base.trail),
         context: ContextConfig {
             enabled: override_cfg.context.enabled.or(base.context.enabled),
             project_pack: override_cfg
                 .context
                 .project_pack
                 .or(base.context.project_pack),
+            repo_map: override_cfg.context.repo_map.or(base.context.repo_map),
+            repo_map_tokens: override_cfg
+                .context
+                .repo_map_tokens
+                .or(base.context.repo_map_tokens),
             verbatim_window_turns: override_cfg
                 .context
                 .verbatim_window_turns
@@ -2974,6 +3677,7 @@ fn merge_config(base: Config, override_cfg: Config) -> Config {
         },
         subagents: override_cfg.subagents.or(base.subagents),
         strict_tool_mode: override_cfg.strict_tool_mode.or(base.strict_tool_mode),
+        self_report: override_cfg.self_report.or(base.self_report),
         runtime_api: override_cfg.runtime_api.or(base.runtime_api),
         workshop: override_cfg.workshop.or(base.workshop),
     }
@@ -3038,19 +3742,100 @@ fn apply_managed_overrides(config: &mut Config) -> Result<()> {
     Ok(())
 }
 
+/// Directory holding the shared team config layer (`deepseek config sync`).
+#[must_use]
+pub fn org_layer() -> Option<OrgLayer> {
+    effective_home_dir().map(|home| OrgLayer::at(home.join(".deepseek")))
+}
+
+/// Top-level keys of the org layer's `config.toml`, split by whether the user
+/// config sets them too (user wins) or inherits them from the org.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OrgLayerKeys {
+    pub inherited: Vec<String>,
+    pub overridden: Vec<String>,
+}
+
+pub fn org_layer_keys(layer: &OrgLayer, config_path: Option<PathBuf>) -> Result<OrgLayerKeys> {
+    let read_table = |path: &Path| -> Result<toml::Table> {
+        if !path.exists() {
+            return Ok(toml::Table::new());
+        }
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        toml::from_str(&raw)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))
+    };
+    let org = read_table(&layer.config_path())?;
+    let user = match resolve_load_config_path(config_path) {
+        Some(path) => read_table(&path)?,
+        None => toml::Table::new(),
+    };
+    let mut keys = OrgLayerKeys::default();
+    for key in org.keys() {
+        if user.contains_key(key) {
+            keys.overridden.push(key.clone());
+        } else {
+            keys.inherited.push(key.clone());
+        }
+    }
+    Ok(keys)
+}
+
+/// Re-sync the org layer on a background thread when its refresh interval
+/// has passed. The new commit takes effect the next time config is loaded.
+pub fn spawn_org_layer_refresh() {
+    let Some(layer) = org_layer().filter(OrgLayer::is_active) else {
+        return;
+    };
+    let _ = std::thread::Builder::new()
+        .name("org-config-refresh".to_string())
+        .spawn(move || match layer.refresh_if_stale() {
+            Ok(Some(state)) => crate::logging::info(format!(
+                "Refreshed org config layer from {} at {}",
+                state.url,
+                state.short_commit()
+            )),
+            Ok(None) => {}
+            Err(err) => crate::logging::warn(format!("Org config layer refresh failed: {err:#}")),
+        });
+}
+
+/// Merge the org layer's `config.toml` beneath the user config, so every key
+/// the user sets wins and every key they leave unset falls back to the org.
+fn apply_org_layer(config: &mut Config) -> Result<()> {
+    let Some(layer) = org_layer().filter(OrgLayer::is_active) else {
+        return Ok(());
+    };
+    let path = layer.config_path();
+    if !path.exists() {
+        return Ok(());
+    }
+    let org = load_single_config_file(&path)
+        .context("Org config layer is invalid; fix the team repo or run `deepseek config sync`")?;
+    *config = merge_config(org, config.clone());
+    Ok(())
+}
+
 fn apply_requirements(config: &mut Config) -> Result<()> {
     let path = config
         .requirements_path
         .as_deref()
         .map(expand_path)
         .or_else(default_requirements_path);
-    let Some(path) = path else {
-        return Ok(());
-    };
-    if !path.exists() {
-        return Ok(());
+    let org_path = org_layer()
+        .filter(OrgLayer::is_active)
+        .map(|layer| layer.requirements_path());
+    for path in path.into_iter().chain(org_path) {
+        if path.exists() {
+            enforce_requirements_file(config, &path)?;
+        }
     }
-    let contents = fs::read_to_string(&path)
+    Ok(())
+}
+
+fn enforce_requirements_file(config: &Config, path: &Path) -> Result<()> {
+    let contents = fs::read_to_string(path)
         .with_context(|| format!("Failed to read requirements file: {}", path.display()))?;
     let requirements: RequirementsFile = toml::from_str(&contents)
         .with_context(|| format!("Failed to parse requirements file: {}", path.display()))?;
@@ -3097,7 +3882,11 @@ fn merge_features(
         (None, None) => None,
         (Some(mut base), Some(override_cfg)) => {
             for (key, value) in override_cfg.entries {
-                base.entries.insert(key, value);
+                let merged = match base.entries.remove(&key) {
+                    Some(existing) => existing.merge(value),
+                    None => value,
+                };
+                base.entries.insert(key, merged);
             }
             Some(base)
         }
@@ -3205,6 +3994,15 @@ pub enum SavedCredential {
     /// keyring backend is reachable, or under `cfg(test)` so unit
     /// tests don't pollute the host keyring.
     ConfigFile(PathBuf),
+    /// Stored in the secret store only, because `deepseek login` set
+    /// `credential_store = "keyring"`. Any plaintext copy was removed from
+    /// the config file so it can't shadow the stored key.
+    Keyring {
+        /// `Secrets::backend_name()` at write time.
+        backend: String,
+        /// Config file the stale plaintext copy was removed from.
+        path: PathBuf,
+    },
 }
 
 impl SavedCredential {
@@ -3217,6 +4015,7 @@ impl SavedCredential {
                 format!("OS keyring ({backend}) and {}", path.display())
             }
             Self::ConfigFile(path) => path.display().to_string(),
+            Self::Keyring { backend, .. } => format!("secret store ({backend})"),
         }
     }
 }
@@ -3240,15 +4039,48 @@ impl SavedCredential {
 /// the function reports a [`SavedCredential::ConfigFile`] outcome —
 /// callers should not treat that as a failure.
 ///
+/// Once `deepseek login` has moved keys into the keyring
+/// (`credential_store = "keyring"`), the key goes to the secret store
+/// only and any plaintext copy is dropped from the config file; the
+/// config file is the fallback when that write fails.
+///
 /// Skipped under `cfg(test)` so the suite never touches the host
 /// keyring. The `secrets` crate has its own test coverage for
 /// keyring set/get.
 pub fn save_api_key(api_key: &str) -> Result<SavedCredential> {
+    save_api_key_with(api_key, preferred_secrets().as_ref())
+}
+
+/// Secret store named by the `credential_store` preference. `None` under
+/// `cfg(test)` so unit tests can't pollute the host keyring (macOS
+/// Always-Allow prompts, cross-test contamination).
+fn preferred_secrets() -> Option<deepseek_secrets::Secrets> {
+    #[cfg(not(test))]
+    {
+        Some(deepseek_secrets::Secrets::with_preference(
+            configured_credential_store().as_deref(),
+        ))
+    }
+    #[cfg(test)]
+    {
+        None
+    }
+}
+
+fn save_api_key_with(
+    api_key: &str,
+    secrets: Option<&deepseek_secrets::Secrets>,
+) -> Result<SavedCredential> {
     let trimmed = api_key.trim();
     if trimmed.is_empty() {
         anyhow::bail!("Refusing to save an empty API key.");
     }
 
+    if let Some(backend) = save_api_key_to_keyring_store("deepseek", trimmed, secrets) {
+        let path = remove_root_api_key_from_config_file()?;
+        return Ok(SavedCredential::Keyring { backend, path });
+    }
+
     // Always write the inspectable copy first. The config file is the
     // durable record everyone — including macOS Keychain-prompted
     // first-run, headless CI, and IDE terminals — can rely on.
@@ -3257,12 +4089,8 @@ pub fn save_api_key(api_key: &str) -> Result<SavedCredential> {
     // Then mirror to the OS keyring when one is reachable. This
     // overwrites any stale entry from a prior install so
     // `Secrets::resolve` (keyring → env → config-file) no longer
-    // shadows the fresh key. Skipped under `cfg(test)` so unit tests
-    // can't pollute the host keyring (macOS Always-Allow prompts,
-    // cross-test contamination).
-    #[cfg(not(test))]
-    {
-        let secrets = deepseek_secrets::Secrets::auto_detect();
+    // shadows the fresh key.
+    if let Some(secrets) = secrets {
         match secrets.set("deepseek", trimmed) {
             Ok(()) => {
                 let backend = secrets.backend_name().to_string();
@@ -3286,6 +4114,78 @@ pub fn save_api_key(api_key: &str) -> Result<SavedCredential> {
     Ok(SavedCredential::ConfigFile(path))
 }
 
+/// The `credential_store` preference `deepseek login` records in the
+/// config file, if any.
+fn configured_credential_store() -> Option<String> {
+    let raw = fs::read_to_string(default_config_path()?).ok()?;
+    let doc = toml::from_str::<toml::Value>(&raw).ok()?;
+    doc.get("credential_store")?
+        .as_str()
+        .map(|value| value.trim().to_ascii_lowercase())
+}
+
+/// Write `api_key` to the secret store under `slot` when the user chose
+/// `credential_store = "keyring"`. Returns the backend name on success;
+/// `None` means the caller should fall back to the config file.
+fn save_api_key_to_keyring_store(
+    slot: &str,
+    api_key: &str,
+    secrets: Option<&deepseek_secrets::Secrets>,
+) -> Option<String> {
+    if configured_credential_store().as_deref() != Some("keyring") {
+        return None;
+    }
+    let secrets = secrets?;
+    match secrets.set(slot, api_key) {
+        Ok(()) => {
+            let backend = secrets.backend_name().to_string();
+            log_sensitive_event(
+                "credential.save",
+                json!({
+                    "backend": backend.clone(),
+                    "slot": slot,
+                    "dual_write": false,
+                }),
+            );
+            Some(backend)
+        }
+        Err(err) => {
+            tracing::warn!("Secret store write failed; saving the key to config.toml: {err}");
+            None
+        }
+    }
+}
+
+/// Drop the root `api_key = ...` line from `config.toml`, leaving
+/// provider tables untouched. Returns the config path.
+fn remove_root_api_key_from_config_file() -> Result<PathBuf> {
+    let config_path = default_config_path()
+        .context("Failed to resolve config path: home directory not found.")?;
+    let Ok(existing) = fs::read_to_string(&config_path) else {
+        return Ok(config_path);
+    };
+    let mut result = String::new();
+    let mut in_root = true;
+    for line in existing.lines() {
+        let trimmed = line.trim_start();
+        in_root &= !trimmed.starts_with('[');
+        if in_root
+            && trimmed
+                .strip_prefix("api_key")
+                .is_some_and(|rest| rest.trim_start().starts_with('='))
+        {
+            continue;
+        }
+        result.push_str(line);
+        result.push('\n');
+    }
+    if result != existing {
+        write_config_file_secure(&config_path, &result)
+            .with_context(|| format!("Failed to write config to {}", config_path.display()))?;
+    }
+    Ok(config_path)
+}
+
 /// Write the `api_key` slot directly to `config.toml`.
 fn save_api_key_to_config_file(api_key: &str) -> Result<PathBuf> {
     fn is_api_key_assignment(line: &str) -> bool {
@@ -3498,13 +4398,15 @@ pub fn has_api_key_for(config: &Config, provider: ApiProvider) -> bool {
 
 /// Save an API key to the appropriate place for the given provider.
 /// DeepSeek goes through [`save_api_key`]. Other providers write
-/// `[providers.<name>] api_key = "..."` to `~/.deepseek/config.toml`.
+/// `[providers.<name>] api_key = "..."` to `~/.deepseek/config.toml`, or
+/// the secret store when `credential_store = "keyring"`.
 /// Returns the config file path.
 pub fn save_api_key_for(provider: ApiProvider, api_key: &str) -> Result<PathBuf> {
     if matches!(provider, ApiProvider::Deepseek | ApiProvider::DeepseekCN) {
         return match save_api_key(api_key)? {
             SavedCredential::KeyringAndConfigFile { path, .. }
-            | SavedCredential::ConfigFile(path) => Ok(path),
+            | SavedCredential::ConfigFile(path)
+            | SavedCredential::Keyring { path, .. } => Ok(path),
         };
     }
 
@@ -3570,10 +4472,16 @@ pub fn save_api_key_for(provider: ApiProvider, api_key: &str) -> Result<PathBuf>
         .or_insert_with(|| toml::Value::Table(toml::value::Table::new()))
         .as_table_mut()
         .with_context(|| format!("`{table_name}` must be a table."))?;
-    entry.insert(
-        "api_key".to_string(),
-        toml::Value::String(api_key.to_string()),
-    );
+    // `deepseek login` names provider slots with hyphens (`nvidia-nim`).
+    let slot = key_inside.replace('_', "-");
+    if save_api_key_to_keyring_store(&slot, api_key, preferred_secrets().as_ref()).is_some() {
+        entry.remove("api_key");
+    } else {
+        entry.insert(
+            "api_key".to_string(),
+            toml::Value::String(api_key.to_string()),
+        );
+    }
 
     let serialized = toml::to_string_pretty(&doc).context("failed to serialize updated config")?;
     write_config_file_secure(&config_path, &serialized)
@@ -3704,6 +4612,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn model_presets_parse_and_match_case_insensitively() {
+        let config: Config = toml::from_str(
+            r#"
+            [models."DeepSeek-Reasoner"]
+            temperature = 0.6
+            max_tokens = 8192
+            thinking = "high"
+            "#,
+        )
+        .expect("models config");
+        let presets = config.models.expect("presets");
+
+        let preset = model_preset(&presets, "deepseek-reasoner").expect("preset");
+        assert_eq!(preset.temperature, Some(0.6));
+        assert_eq!(preset.max_tokens, Some(8192));
+        assert_eq!(preset.thinking_budget.as_deref(), Some("high"));
+        assert_eq!(
+            preset.summary(),
+            "temperature 0.6 · max_tokens 8192 · thinking high"
+        );
+        assert!(model_preset(&presets, "deepseek-v4-flash").is_none());
+    }
+
     struct EnvGuard {
         home: Option<OsString>,
         userprofile: Option<OsString>,
@@ -4011,6 +4943,32 @@ mod tests {
         assert_eq!(high.max_subagents(), MAX_SUBAGENTS);
     }
 
+    #[test]
+    fn subagent_spawn_limits_resolve_from_config() {
+        assert_eq!(
+            Config::default().subagent_max_steps(),
+            DEFAULT_SUBAGENT_MAX_STEPS
+        );
+        assert_eq!(Config::default().subagent_allowed_models(), None);
+
+        let config = Config {
+            subagents: Some(SubagentsConfig {
+                max_steps: Some(MAX_SUBAGENT_MAX_STEPS + 1),
+                allowed_models: Some(vec![
+                    " deepseek-v4-flash ".to_string(),
+                    "not a model".to_string(),
+                ]),
+                ..SubagentsConfig::default()
+            }),
+            ..Config::default()
+        };
+        assert_eq!(config.subagent_max_steps(), MAX_SUBAGENT_MAX_STEPS);
+        assert_eq!(
+            config.subagent_allowed_models(),
+            Some(vec!["deepseek-v4-flash".to_string()])
+        );
+    }
+
     #[test]
     fn subagent_api_timeout_defaults_and_clamps() {
         assert_eq!(
@@ -4091,6 +5049,54 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn save_api_key_honors_keyring_credential_store() -> Result<()> {
+        let _lock = lock_test_env();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let temp_root = env::temp_dir().join(format!(
+            "deepseek-tui-keyring-store-{}-{}",
+            std::process::id(),
+            nanos
+        ));
+        fs::create_dir_all(&temp_root)?;
+        let _guard = EnvGuard::new(&temp_root);
+        let config_path = temp_root.join(".deepseek").join("config.toml");
+        ensure_parent_dir(&config_path)?;
+        fs::write(
+            &config_path,
+            "credential_store = \"keyring\"\napi_key = \"stale-key\"\n\n[providers.openai]\napi_key = \"openai-key\"\n",
+        )?;
+        let store = std::sync::Arc::new(deepseek_secrets::InMemoryKeyringStore::new());
+        let secrets = deepseek_secrets::Secrets::new(store.clone());
+
+        let saved = save_api_key_with("fresh-key", Some(&secrets))?;
+        assert!(
+            matches!(&saved, SavedCredential::Keyring { path, .. } if *path == config_path),
+            "{saved:?}"
+        );
+        assert_eq!(
+            deepseek_secrets::KeyringStore::get(store.as_ref(), "deepseek")?,
+            Some("fresh-key".to_string())
+        );
+        let contents = fs::read_to_string(&config_path)?;
+        assert!(!contents.contains("stale-key"), "{contents}");
+        assert!(!contents.contains("fresh-key"), "{contents}");
+        assert!(contents.contains("openai-key"), "{contents}");
+
+        // Without the preference the key still lands in the config file.
+        fs::write(&config_path, "")?;
+        let saved = save_api_key_with("plain-key", Some(&secrets))?;
+        assert!(
+            matches!(saved, SavedCredential::KeyringAndConfigFile { .. }),
+            "{saved:?}"
+        );
+        assert!(fs::read_to_string(&config_path)?.contains("plain-key"));
+        Ok(())
+    }
+
     #[test]
     fn ensure_config_file_exists_creates_first_run_template() -> Result<()> {
         let _lock = lock_test_env();
@@ -4117,6 +5123,53 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn org_layer_sits_beneath_user_config_and_enforces_its_requirements() -> Result<()> {
+        let _lock = lock_test_env();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let temp_root = env::temp_dir().join(format!(
+            "deepseek-tui-org-layer-{}-{}",
+            std::process::id(),
+            nanos
+        ));
+        let _guard = EnvGuard::new(&temp_root);
+        let deepseek_dir = temp_root.join(".deepseek");
+        let layer = OrgLayer::at(&deepseek_dir);
+        fs::create_dir_all(layer.checkout_dir().join(".git"))?;
+        fs::write(
+            layer.state_path(),
+            "url = \"git@example.com:team-config.git\"\ncommit = \"abc\"\nsynced_at = 0\n",
+        )?;
+        fs::write(
+            layer.config_path(),
+            "approval_policy = \"on-request\"\nsandbox_mode = \"read-only\"\n",
+        )?;
+        fs::write(
+            layer.requirements_path(),
+            "allowed_approval_policies = [\"on-request\", \"untrusted\"]\n",
+        )?;
+        let user_config = deepseek_dir.join("config.toml");
+        fs::write(&user_config, "sandbox_mode = \"workspace-write\"\n")?;
+
+        let config = Config::load(None, None)?;
+        assert_eq!(config.approval_policy.as_deref(), Some("on-request"));
+        assert_eq!(config.sandbox_mode.as_deref(), Some("workspace-write"));
+        let keys = org_layer_keys(&layer, None)?;
+        assert_eq!(keys.inherited, vec!["approval_policy".to_string()]);
+        assert_eq!(keys.overridden, vec!["sandbox_mode".to_string()]);
+
+        fs::write(&user_config, "approval_policy = \"never\"\n")?;
+        let err = Config::load(None, None).expect_err("org requirements reject never");
+        assert!(
+            err.to_string().contains("not allowed by requirements"),
+            "{err}"
+        );
+        Ok(())
+    }
+
     #[test]
     fn workspace_trust_round_trips_through_global_config() -> Result<()> {
         let _lock = lock_test_env();
@@ -4135,7 +5188,7 @@ mod tests {
         fs::create_dir_all(&workspace)?;
 
         assert!(!is_workspace_trusted(&workspace));
-        let saved = save_workspace_trust(&workspace)?;
+        let saved = save_workspace_trust(&workspace, "trusted")?;
 
         assert_eq!(saved, temp_root.join(".deepseek").join("config.toml"));
         assert!(is_workspace_trusted(&workspace));
@@ -4183,6 +5236,10 @@ mod tests {
 
         assert!(is_workspace_trusted(&workspace));
         assert!(!crate::tui::onboarding::needs_trust(&workspace));
+        assert_eq!(
+            crate::trust_level::load(&workspace),
+            Some(crate::trust_level::TrustLevel::Standard)
+        );
         Ok(())
     }
 
@@ -4918,6 +5975,21 @@ api_key = "old-openrouter-key"
         assert!(!config.project_context_pack_enabled());
     }
 
+    #[test]
+    fn repo_map_defaults_on_with_budget_and_can_be_disabled() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.repo_map_tokens(),
+            Some(crate::repo_map::DEFAULT_REPO_MAP_TOKENS)
+        );
+
+        config.context.repo_map_tokens = Some(800);
+        assert_eq!(config.repo_map_tokens(), Some(800));
+
+        config.context.repo_map = Some(false);
+        assert_eq!(config.repo_map_tokens(), None);
+    }
+
     #[test]
     fn validate_accepts_future_deepseek_model_id() -> Result<()> {
         let config = Config {
@@ -51,10 +51,14 @@ pub struct RuntimeSection {
 #[serde(rename_all = "snake_case")]
 pub struct SettingsSection {
     pub auto_compact: bool,
+    #[schemars(range(min = 0))]
+    pub idle_maintenance_secs: u64,
     pub calm_mode: bool,
     pub low_motion: bool,
     pub fancy_animations: bool,
     pub paste_burst_detection: bool,
+    #[schemars(range(min = 0))]
+    pub paste_prompt_lines: usize,
     pub show_thinking: bool,
     pub show_tool_details: bool,
     pub locale: UiLocale,
@@ -67,6 +71,7 @@ pub struct SettingsSection {
     pub bracketed_paste: bool,
     pub composer_density: ComposerDensityValue,
     pub composer_border: bool,
+    pub prompt_lint: bool,
     pub composer_vim_mode: ComposerVimModeValue,
     pub transcript_spacing: TranscriptSpacingValue,
     pub status_indicator: StatusIndicatorValue,
@@ -232,6 +237,7 @@ pub enum SidebarFocusValue {
     Tasks,
     Agents,
     Context,
+    Files,
     Hidden,
 }
 
@@ -313,10 +319,12 @@ pub fn build_document(app: &App, config: &Config) -> Result<ConfigUiDocument> {
         },
         settings: SettingsSection {
             auto_compact: settings.auto_compact,
+            idle_maintenance_secs: settings.idle_maintenance_secs,
             calm_mode: settings.calm_mode,
             low_motion: settings.low_motion,
             fancy_animations: settings.fancy_animations,
             paste_burst_detection: settings.paste_burst_detection,
+            paste_prompt_lines: settings.paste_prompt_lines,
             show_thinking: settings.show_thinking,
             show_tool_details: settings.show_tool_details,
             locale: UiLocale::from_setting(&settings.locale)?,
@@ -325,6 +333,7 @@ pub fn build_document(app: &App, config: &Config) -> Result<ConfigUiDocument> {
             bracketed_paste: settings.bracketed_paste,
             composer_density: settings.composer_density.as_str().into(),
             composer_border: settings.composer_border,
+            prompt_lint: settings.prompt_lint,
             composer_vim_mode: settings.composer_vim_mode.as_str().into(),
             transcript_spacing: settings.transcript_spacing.as_str().into(),
             status_indicator: settings.status_indicator.as_str().into(),
@@ -471,6 +480,10 @@ pub fn apply_document(
         ("model", doc.runtime.model.as_str()),
         ("approval_mode", doc.runtime.approval_mode.as_setting()),
         ("auto_compact", bool_str(doc.settings.auto_compact)),
+        (
+            "idle_maintenance_secs",
+            &doc.settings.idle_maintenance_secs.to_string(),
+        ),
         ("calm_mode", bool_str(doc.settings.calm_mode)),
         ("low_motion", bool_str(doc.settings.low_motion)),
         ("fancy_animations", bool_str(doc.settings.fancy_animations)),
@@ -478,6 +491,10 @@ pub fn apply_document(
             "paste_burst_detection",
             bool_str(doc.settings.paste_burst_detection),
         ),
+        (
+            "paste_prompt_lines",
+            &doc.settings.paste_prompt_lines.to_string(),
+        ),
         ("show_thinking", bool_str(doc.settings.show_thinking)),
         (
             "show_tool_details",
@@ -498,6 +515,7 @@ pub fn apply_document(
             doc.settings.composer_density.as_setting(),
         ),
         ("composer_border", bool_str(doc.settings.composer_border)),
+        ("prompt_lint", bool_str(doc.settings.prompt_lint)),
         (
             "composer_vim_mode",
             doc.settings.composer_vim_mode.as_setting(),
@@ -837,6 +855,7 @@ impl SidebarFocusValue {
             Self::Tasks => "tasks",
             Self::Agents => "agents",
             Self::Context => "context",
+            Self::Files => "files",
             Self::Hidden => "hidden",
         }
     }
@@ -975,6 +994,7 @@ impl From<&str> for SidebarFocusValue {
             SidebarFocus::Tasks => Self::Tasks,
             SidebarFocus::Agents => Self::Agents,
             SidebarFocus::Context => Self::Context,
+            SidebarFocus::Files => Self::Files,
             SidebarFocus::Hidden => Self::Hidden,
         }
     }
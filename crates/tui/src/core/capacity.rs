@@ -2,6 +2,8 @@
 
 use std::collections::{HashMap, VecDeque};
 
+use crate::features::{Feature, Features};
+
 /// Controller settings.
 #[derive(Debug, Clone, PartialEq)]
 pub struct CapacityControllerConfig {
@@ -61,59 +63,87 @@ impl CapacityControllerConfig {
     #[must_use]
     pub fn from_app_config(config: &crate::config::Config) -> Self {
         let mut out = Self::default();
-        let Some(capacity) = config.capacity.as_ref() else {
-            return out;
-        };
-
-        if let Some(v) = capacity.enabled {
-            out.enabled = v;
+        let features = config.features();
+        out.enabled = features.enabled(Feature::CapacityController);
+        if let Some(capacity) = config.capacity.as_ref() {
+            out.apply_capacity_table(capacity);
         }
+        // `[features] capacity_controller = { ... }` values win over the
+        // legacy `[capacity]` table.
+        out.apply_feature_values(&features);
+        out
+    }
+
+    fn apply_capacity_table(&mut self, capacity: &crate::config::CapacityConfig) {
         if let Some(v) = capacity.low_risk_max {
-            out.low_risk_max = v;
+            self.low_risk_max = v;
         }
         if let Some(v) = capacity.medium_risk_max {
-            out.medium_risk_max = v;
+            self.medium_risk_max = v;
         }
         if let Some(v) = capacity.severe_min_slack {
-            out.severe_min_slack = v;
+            self.severe_min_slack = v;
         }
         if let Some(v) = capacity.severe_violation_ratio {
-            out.severe_violation_ratio = v;
+            self.severe_violation_ratio = v;
         }
         if let Some(v) = capacity.refresh_cooldown_turns {
-            out.refresh_cooldown_turns = v;
+            self.refresh_cooldown_turns = v;
         }
         if let Some(v) = capacity.replan_cooldown_turns {
-            out.replan_cooldown_turns = v;
+            self.replan_cooldown_turns = v;
         }
         if let Some(v) = capacity.max_replay_per_turn {
-            out.max_replay_per_turn = v;
+            self.max_replay_per_turn = v;
         }
         if let Some(v) = capacity.min_turns_before_guardrail {
-            out.min_turns_before_guardrail = v;
+            self.min_turns_before_guardrail = v;
         }
         if let Some(v) = capacity.profile_window {
-            out.profile_window = v.max(2);
+            self.profile_window = v.max(2);
         }
 
         if let Some(v) = capacity.deepseek_v3_2_chat_prior {
-            out.model_priors.insert("deepseek_v3_2_chat".to_string(), v);
+            self.model_priors
+                .insert("deepseek_v3_2_chat".to_string(), v);
         }
         if let Some(v) = capacity.deepseek_v3_2_reasoner_prior {
-            out.model_priors
+            self.model_priors
                 .insert("deepseek_v3_2_reasoner".to_string(), v);
         }
         if let Some(v) = capacity.deepseek_v4_pro_prior {
-            out.model_priors.insert("deepseek_v4_pro".to_string(), v);
+            self.model_priors.insert("deepseek_v4_pro".to_string(), v);
         }
         if let Some(v) = capacity.deepseek_v4_flash_prior {
-            out.model_priors.insert("deepseek_v4_flash".to_string(), v);
+            self.model_priors.insert("deepseek_v4_flash".to_string(), v);
         }
         if let Some(v) = capacity.fallback_default_prior {
-            out.fallback_default = v;
+            self.fallback_default = v;
         }
+    }
 
-        out
+    fn apply_feature_values(&mut self, features: &Features) {
+        let feature = Feature::CapacityController;
+        let turns = |key| {
+            features
+                .int_value(feature, key)
+                .and_then(|v| u64::try_from(v).ok())
+        };
+        if let Some(v) = features.float_value(feature, "low_risk_max") {
+            self.low_risk_max = v;
+        }
+        if let Some(v) = features.float_value(feature, "medium_risk_max") {
+            self.medium_risk_max = v;
+        }
+        if let Some(v) = turns("refresh_cooldown_turns") {
+            self.refresh_cooldown_turns = v;
+        }
+        if let Some(v) = turns("replan_cooldown_turns") {
+            self.replan_cooldown_turns = v;
+        }
+        if let Some(v) = turns("min_turns_before_guardrail") {
+            self.min_turns_before_guardrail = v;
+        }
     }
 }
 
@@ -11,6 +11,7 @@ use std::collections::HashMap;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex as StdMutex};
 use std::time::{Duration, Instant};
 
@@ -23,26 +24,32 @@ use tokio_util::sync::CancellationToken;
 
 use crate::client::DeepSeekClient;
 use crate::compaction::{
-    CompactionConfig, compact_messages_safe, merge_system_prompts, should_compact,
+    CompactionConfig, compact_messages_safe, fold_digests, merge_system_prompts, plan_digest_fold,
+    replace_digests, set_digest_segment, should_compact,
 };
 use crate::config::{ApiProvider, Config, DEFAULT_MAX_SUBAGENTS, DEFAULT_TEXT_MODEL};
 use crate::cycle_manager::{
     CycleBriefing, CycleConfig, StructuredState, archive_cycle, build_seed_messages,
     estimate_briefing_tokens, produce_briefing, should_advance_cycle,
 };
-use crate::error_taxonomy::{ErrorCategory, ErrorEnvelope, StreamError};
+use crate::error_taxonomy::{ErrorCategory, ErrorEnvelope, StreamError, ToolFailure};
 use crate::features::{Feature, Features};
+use crate::history_archive::HistoryArchive;
 use crate::llm_client::LlmClient;
 use crate::mcp::McpPool;
 #[cfg(test)]
 use crate::models::ToolCaller;
 use crate::models::{
     ContentBlock, ContentBlockStart, Delta, LEGACY_DEEPSEEK_CONTEXT_WINDOW_TOKENS, Message,
-    MessageRequest, StreamEvent, SystemPrompt, Tool, Usage,
+    MessageRequest, StreamEvent, SystemBlock, SystemPrompt, Tool, Usage,
 };
 use crate::prompts;
+use crate::sandbox::limits::ShellLimits;
 use crate::seam_manager::{SeamConfig, SeamManager};
+use crate::tools::exec_profile::{SharedExecProfile, new_shared_exec_profile};
 use crate::tools::plan::{SharedPlanState, new_shared_plan_state};
+use crate::tools::session_changes::{SharedSessionChanges, new_shared_session_changes};
+use crate::tools::session_env::{SharedSessionEnv, new_shared_session_env};
 use crate::tools::shell::{SharedShellManager, new_shared_shell_manager};
 use crate::tools::spec::RuntimeToolServices;
 use crate::tools::spec::{ApprovalRequirement, ToolError, ToolResult};
@@ -96,6 +103,8 @@ pub struct EngineConfig {
     /// Resolved via `expand_path` so `~` works.
     pub instructions: Vec<PathBuf>,
     pub project_context_pack_enabled: bool,
+    /// Token budget for the repo map prompt layer; `None` disables it.
+    pub repo_map_tokens: Option<usize>,
     /// When true, the model is instructed to respond in the current locale
     /// and a post-hoc translation layer replaces remaining English output.
     pub translation_enabled: bool,
@@ -122,6 +131,14 @@ pub struct EngineConfig {
     pub todos: SharedTodoList,
     /// Shared Plan state.
     pub plan_state: SharedPlanState,
+    /// Session-start baselines of files changed by tools (`/changes`).
+    pub session_changes: SharedSessionChanges,
+    /// Active `/profile use` wrapper for shell commands.
+    pub exec_profile: SharedExecProfile,
+    /// `[env]` / `/env set` variables for shell tools.
+    pub session_env: SharedSessionEnv,
+    /// `[shell_limits]` resource caps for shell tools, per sandbox policy.
+    pub shell_limits: ShellLimits,
     /// Maximum sub-agent recursion depth (default 3). See
     /// `SubAgentRuntime::max_spawn_depth`. Override via
     /// `[runtime] max_spawn_depth = N` in `~/.deepseek/config.toml`.
@@ -136,6 +153,20 @@ pub struct EngineConfig {
     /// first init. `0` disables the cap. Resolved from
     /// `[snapshots] max_workspace_gb` × 1 GB at engine construction.
     pub snapshots_max_workspace_bytes: u64,
+    /// Save the named snapshot `auto` before the first file write of each
+    /// turn (`[snapshots] auto_before_write`).
+    pub snapshots_auto_before_write: bool,
+    /// Commit the workspace to `trail_branch` after each turn that ran tools
+    /// (`[trail] enabled`, toggled with `/trail on|off`).
+    pub trail_enabled: bool,
+    /// Branch that receives trail commits.
+    pub trail_branch: String,
+    /// Lifetime of cached read-only tool results. Resolved from
+    /// `[tool_cache] scope`; defaults to per-turn.
+    pub tool_cache_scope: crate::tools::result_cache::ToolCacheScope,
+    /// `[prompt_layers]` overrides applied every time the system prompt is
+    /// rebuilt.
+    pub prompt_layers: crate::prompts::layers::PromptLayerOverrides,
     /// Post-edit LSP diagnostics injection (#136). When `None`, the engine
     /// constructs a disabled manager so the field is always present.
     pub lsp_config: Option<crate::lsp::LspConfig>,
@@ -150,6 +181,9 @@ pub struct EngineConfig {
     /// Path to the user memory file (#489). Always populated; only
     /// consulted when `memory_enabled` is `true`.
     pub memory_path: PathBuf,
+    /// Record failed `exec_shell` commands in `.deepseek/lessons.json` and
+    /// add a digest of them to the system prompt.
+    pub command_lessons_enabled: bool,
     pub vision_config: Option<crate::config::VisionModelConfig>,
     pub goal_objective: Option<String>,
     /// Resolved BCP-47 locale tag (e.g. `"en"`, `"zh-Hans"`, `"ja"`)
@@ -160,17 +194,47 @@ pub struct EngineConfig {
     /// When true, force `tool_choice: "required"` and opt compatible function
     /// schemas into DeepSeek beta strict mode.
     pub strict_tool_mode: bool,
+    /// Ask the model for a `<self_report>` footer on each final answer
+    /// (`self_report = true`).
+    pub self_report: bool,
     /// Workshop / large-tool-output routing (#548). `None` disables routing.
     pub workshop: Option<crate::tools::large_output_router::WorkshopConfig>,
     /// Which search backend `web_search` should use. Default: Bing.
     pub search_provider: crate::config::SearchProvider,
     /// API key for Tavily or Bocha. `None` for Bing or DuckDuckGo.
     pub search_api_key: Option<String>,
+    /// Pre-write syntax checks for the file-editing tools
+    /// (`[edit_validation]`).
+    pub edit_validation: crate::tools::edit_validation::EditValidationConfig,
+    /// Per-tool result filters (`[tool_filters]`).
+    pub tool_filters: crate::tools::output_filter::ToolFilters,
+    /// Workspace trust level (`.deepseek/trust.json`); scopes the tool
+    /// catalog and the shell sandbox.
+    pub trust_level: crate::trust_level::TrustLevel,
+    /// `[models."<id>"]` presets: temperature, top_p, and max_tokens applied
+    /// to every request for the matching model.
+    pub model_presets: std::collections::BTreeMap<String, crate::config::ModelPreset>,
+    /// Models tried in order when a turn request fails with an overload,
+    /// 5xx, rate-limit, or context-length error (`model_fallbacks`).
+    pub model_fallbacks: Vec<String>,
+    /// Build or refresh the `docs_search` index in the background when the
+    /// engine starts. Only the interactive TUI turns this on; elsewhere the
+    /// tool indexes on first use.
+    pub docs_index_on_start: bool,
+    /// `[thinking]` budget and auto-off settings. `/thinking budget <n>`
+    /// replaces `budget_tokens` through [`Op::SetThinkingBudget`].
+    pub thinking: crate::config::ThinkingConfig,
     /// Per-step DeepSeek API timeout for sub-agent `create_message` requests.
     /// Resolved from `[subagents] api_timeout_secs` (clamped to 1..=1800)
     /// once at engine construction, then threaded onto every
     /// `SubAgentRuntime` the engine builds (#1806, #1808).
     pub subagent_api_timeout: Duration,
+    /// Sub-agent step budget and ceiling for per-spawn `max_steps`, from
+    /// `[subagents] max_steps`.
+    pub subagent_max_steps: u32,
+    /// Models the spawn tool may request, from `[subagents] allowed_models`.
+    /// `None` allows any DeepSeek model.
+    pub subagent_allowed_models: Option<Vec<String>>,
 }
 
 impl Default for EngineConfig {
@@ -185,6 +249,7 @@ impl Default for EngineConfig {
             skills_dir: crate::skills::default_skills_dir(),
             instructions: Vec::new(),
             project_context_pack_enabled: true,
+            repo_map_tokens: None,
             translation_enabled: false,
             max_steps: 100,
             max_subagents: DEFAULT_MAX_SUBAGENTS,
@@ -194,30 +259,57 @@ impl Default for EngineConfig {
             capacity: CapacityControllerConfig::default(),
             todos: new_shared_todo_list(),
             plan_state: new_shared_plan_state(),
+            session_changes: new_shared_session_changes(),
+            exec_profile: new_shared_exec_profile(),
+            session_env: new_shared_session_env(None),
+            shell_limits: ShellLimits::default(),
             max_spawn_depth: crate::tools::subagent::DEFAULT_MAX_SPAWN_DEPTH,
             network_policy: None,
             snapshots_enabled: true,
             snapshots_max_workspace_bytes:
                 crate::snapshot::DEFAULT_MAX_WORKSPACE_BYTES_FOR_SNAPSHOT,
+            snapshots_auto_before_write: false,
+            trail_enabled: false,
+            trail_branch: crate::git_trail::DEFAULT_TRAIL_BRANCH.to_string(),
+            tool_cache_scope: crate::tools::result_cache::ToolCacheScope::default(),
+            prompt_layers: crate::prompts::layers::PromptLayerOverrides::default(),
             lsp_config: None,
             runtime_services: RuntimeToolServices::default(),
             subagent_model_overrides: HashMap::new(),
             memory_enabled: false,
             memory_path: PathBuf::from("./memory.md"),
+            command_lessons_enabled: false,
             vision_config: None,
             strict_tool_mode: false,
+            self_report: false,
             goal_objective: None,
             locale_tag: "en".to_string(),
             workshop: None,
             search_provider: crate::config::SearchProvider::default(),
             search_api_key: None,
+            edit_validation: crate::tools::edit_validation::EditValidationConfig::default(),
+            tool_filters: crate::tools::output_filter::ToolFilters::new(),
+            trust_level: crate::trust_level::TrustLevel::default(),
+            model_presets: std::collections::BTreeMap::new(),
+            model_fallbacks: Vec::new(),
+            docs_index_on_start: false,
+            thinking: crate::config::ThinkingConfig::default(),
             subagent_api_timeout: Duration::from_secs(
                 crate::config::DEFAULT_SUBAGENT_API_TIMEOUT_SECS,
             ),
+            subagent_max_steps: crate::config::DEFAULT_SUBAGENT_MAX_STEPS,
+            subagent_allowed_models: None,
         }
     }
 }
 
+/// What opens a turn: the user's text, or tool results that continue a turn
+/// paused on `request_user_input` in an earlier run of the app.
+enum TurnInput {
+    Text(String),
+    ToolResults(Vec<ContentBlock>),
+}
+
 /// Reason the active turn was cancelled. The token from `tokio_util`
 /// does not carry a cause, so the engine keeps a sibling latch for
 /// approval and user-input waits that need to explain cancellation.
@@ -252,6 +344,46 @@ impl CancelReason {
     }
 }
 
+/// Soft-cancel latch shared between the engine and its handle.
+///
+/// A soft cancel lets the tool that is already executing finish, records its
+/// result in the session, skips any tools that have not started yet, and
+/// ends the turn as interrupted before the next model request. It only
+/// applies while a tool batch is running; at any other point (streaming,
+/// waiting on the API) there is nothing to finish, so the handle escalates
+/// to a hard cancel instead.
+#[derive(Debug, Default)]
+pub struct SoftStop {
+    requested: AtomicBool,
+    tools_running: AtomicBool,
+}
+
+impl SoftStop {
+    /// Latch a soft stop. Returns `false` when no tool batch is running, in
+    /// which case the caller should hard-cancel.
+    pub fn request(&self) -> bool {
+        if !self.tools_running.load(Ordering::SeqCst) {
+            return false;
+        }
+        self.requested.store(true, Ordering::SeqCst);
+        true
+    }
+
+    #[must_use]
+    pub fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    fn set_tools_running(&self, running: bool) {
+        self.tools_running.store(running, Ordering::SeqCst);
+    }
+
+    fn reset(&self) {
+        self.requested.store(false, Ordering::SeqCst);
+        self.tools_running.store(false, Ordering::SeqCst);
+    }
+}
+
 /// Handle to communicate with the engine
 #[derive(Clone)]
 pub struct EngineHandle {
@@ -265,12 +397,17 @@ pub struct EngineHandle {
     /// approval / user-input handlers to enrich their error strings.
     /// Cleared by the engine when a fresh turn starts.
     cancel_reason: Arc<StdMutex<Option<CancelReason>>>,
+    /// Soft-cancel latch for the current turn; see [`SoftStop`].
+    soft_stop: Arc<SoftStop>,
     /// Send approval decisions to the engine
     tx_approval: mpsc::Sender<ApprovalDecision>,
     /// Send user input responses to the engine
     tx_user_input: mpsc::Sender<UserInputDecision>,
     /// Send steer input for an in-flight turn.
     tx_steer: mpsc::Sender<String>,
+    /// Shared sub-agent manager, so the UI can cancel one agent while a turn
+    /// is running (ops queue behind the turn).
+    subagent_manager: SharedSubAgentManager,
 }
 
 // `impl EngineHandle { ... }` moved to `engine/handle.rs` so the
@@ -290,6 +427,9 @@ pub struct Engine {
     mcp_pool: Option<Arc<AsyncMutex<McpPool>>>,
     rx_op: mpsc::Receiver<Op>,
     rx_approval: mpsc::Receiver<ApprovalDecision>,
+    /// Decisions that arrived for a call announced later in the current
+    /// step, held until the turn loop reaches that call.
+    early_approvals: HashMap<String, ApprovalDecision>,
     rx_user_input: mpsc::Receiver<UserInputDecision>,
     rx_steer: mpsc::Receiver<String>,
     tx_event: mpsc::Sender<Event>,
@@ -308,6 +448,8 @@ pub struct Engine {
     /// surfacing the "Request cancelled while awaiting …" error so the
     /// user-facing message names a cause.
     pub(super) cancel_reason: Arc<StdMutex<Option<CancelReason>>>,
+    /// Mirrored to `EngineHandle::soft_stop`; reset with the cancel token.
+    soft_stop: Arc<SoftStop>,
     tool_exec_lock: Arc<RwLock<()>>,
     capacity_controller: CapacityController,
     /// Append-only layered context manager (#159). Opt-in for v0.7.5 while
@@ -315,6 +457,8 @@ pub struct Engine {
     seam_manager: Option<SeamManager>,
     coherence_state: CoherenceState,
     turn_counter: u64,
+    /// Turn whose first file write already saved the `auto` named snapshot.
+    auto_snapshot_turn: u64,
     /// Post-edit LSP diagnostics injection (#136). Populated unconditionally
     /// — when LSP is disabled in config, this is an inert manager that
     /// always returns `None` from `diagnostics_for`.
@@ -331,6 +475,13 @@ pub struct Engine {
     /// Diagnostics collected during the current step's tool calls. Drained
     /// and forwarded as a synthetic user message before the next API call.
     pending_lsp_blocks: Vec<crate::lsp::DiagnosticBlock>,
+    /// Cached results of deterministic read-only tool calls. Reset per turn
+    /// unless `[tool_cache] scope = "session"`; cleared by `/cache clear`.
+    tool_result_cache: crate::tools::result_cache::ToolResultCache,
+    /// Layer breakdown last sent to the UI via `Event::PromptLayers`.
+    last_prompt_layers: Vec<prompts::layers::PromptLayerSummary>,
+    /// Files the model has read, watched for changes made outside the agent.
+    stale_reads: crate::stale_reads::StaleReads,
 }
 
 // === Internal tool helpers ===
@@ -354,6 +505,7 @@ impl Engine {
             Ok(mut slot) => *slot = None,
             Err(poisoned) => *poisoned.into_inner() = None,
         }
+        self.soft_stop.reset();
     }
 
     fn env_only_api_key_recovery_hint(api_config: &Config) -> Option<String> {
@@ -408,6 +560,7 @@ impl Engine {
         let cancel_token = CancellationToken::new();
         let shared_cancel_token = Arc::new(StdMutex::new(cancel_token.clone()));
         let cancel_reason: Arc<StdMutex<Option<CancelReason>>> = Arc::new(StdMutex::new(None));
+        let soft_stop = Arc::new(SoftStop::default());
         let tool_exec_lock = Arc::new(RwLock::new(()));
 
         // Create clients for both providers
@@ -430,6 +583,8 @@ impl Engine {
         // message at request time so file churn does not rewrite this prefix.
         let user_memory_block =
             crate::memory::compose_block(config.memory_enabled, &config.memory_path);
+        let command_lessons_block =
+            crate::lessons::compose_block(config.command_lessons_enabled, &config.workspace);
         let system_prompt =
             prompts::system_prompt_for_mode_with_context_skills_session_and_approval(
                 AppMode::Agent,
@@ -439,10 +594,14 @@ impl Engine {
                 Some(&config.instructions),
                 prompts::PromptSessionContext {
                     user_memory_block: user_memory_block.as_deref(),
+                    command_lessons_block: command_lessons_block.as_deref(),
                     goal_objective: config.goal_objective.as_deref(),
                     project_context_pack_enabled: config.project_context_pack_enabled,
+                    repo_map_tokens: config.repo_map_tokens,
                     locale_tag: &config.locale_tag,
                     translation_enabled: config.translation_enabled,
+                    self_report: config.self_report,
+                    layer_overrides: Some(&config.prompt_layers),
                 },
                 session.approval_mode,
             );
@@ -536,17 +695,24 @@ impl Engine {
             })
             .map(std::sync::Arc::from);
 
+        let tool_result_cache =
+            crate::tools::result_cache::ToolResultCache::new(config.tool_cache_scope);
+        let stale_tx = tx_event.clone();
+        let stale_reads = crate::stale_reads::StaleReads::new(Arc::new(move |paths| {
+            let _ = stale_tx.try_send(Event::ReadFilesChanged { paths });
+        }));
         let mut engine = Engine {
             config,
             deepseek_client,
             deepseek_client_error,
             api_key_env_only_recovery,
             session,
-            subagent_manager,
+            subagent_manager: Arc::clone(&subagent_manager),
             shell_manager,
             mcp_pool: None,
             rx_op,
             rx_approval,
+            early_approvals: HashMap::new(),
             rx_user_input,
             rx_steer,
             tx_event,
@@ -555,13 +721,18 @@ impl Engine {
             cancel_token: cancel_token.clone(),
             shared_cancel_token: shared_cancel_token.clone(),
             cancel_reason: cancel_reason.clone(),
+            soft_stop: soft_stop.clone(),
             tool_exec_lock,
             capacity_controller,
             seam_manager,
             coherence_state: CoherenceState::default(),
             turn_counter: 0,
+            auto_snapshot_turn: 0,
             lsp_manager,
             pending_lsp_blocks: Vec::new(),
+            tool_result_cache,
+            last_prompt_layers: Vec::new(),
+            stale_reads,
             workshop_vars,
             sandbox_backend,
         };
@@ -572,9 +743,11 @@ impl Engine {
             rx_event: Arc::new(RwLock::new(rx_event)),
             cancel_token: shared_cancel_token,
             cancel_reason,
+            soft_stop,
             tx_approval,
             tx_user_input,
             tx_steer,
+            subagent_manager,
         };
 
         (engine, handle)
@@ -583,6 +756,14 @@ impl Engine {
     /// Run the engine event loop
     #[allow(clippy::too_many_lines)]
     pub async fn run(mut self) {
+        if self.config.docs_index_on_start {
+            let workspace = self.session.workspace.clone();
+            crate::utils::spawn_blocking_supervised("docs-index", move || {
+                if let Err(err) = crate::tools::docs_search::refresh_index(&workspace) {
+                    tracing::debug!("docs index refresh failed: {err}");
+                }
+            });
+        }
         while let Some(op) = self.rx_op.recv().await {
             match op {
                 Op::SendMessage {
@@ -600,7 +781,7 @@ impl Engine {
                     translation_enabled,
                 } => {
                     self.handle_send_message(
-                        content,
+                        TurnInput::Text(content),
                         mode,
                         model,
                         goal_objective,
@@ -665,6 +846,8 @@ impl Engine {
                     )
                     .with_max_spawn_depth(self.config.max_spawn_depth)
                     .with_step_api_timeout(self.config.subagent_api_timeout)
+                    .with_max_steps(self.config.subagent_max_steps)
+                    .with_allowed_models(self.config.subagent_allowed_models.clone())
                     .background_runtime();
                     let route = resolve_subagent_assignment_route(
                         &runtime,
@@ -734,6 +917,15 @@ impl Engine {
                         )))
                         .await;
                 }
+                Op::SetThinkingBudget { budget } => {
+                    self.config.thinking.budget_tokens = budget;
+                }
+                Op::SetTrail { enabled } => {
+                    self.config.trail_enabled = enabled;
+                }
+                Op::SetTrustLevel { level } => {
+                    self.config.trust_level = level;
+                }
                 Op::SetCompaction { config } => {
                     let enabled = config.enabled;
                     self.config.compaction = config;
@@ -776,6 +968,7 @@ impl Engine {
                         None
                     };
                     self.session.rebuild_working_set();
+                    self.stale_reads.clear();
                     self.rehydrate_latest_canonical_state();
                     self.emit_session_updated().await;
                     let _ = self
@@ -784,7 +977,45 @@ impl Engine {
                         .await;
                 }
                 Op::CompactContext => {
-                    self.handle_manual_compaction().await;
+                    self.handle_manual_compaction(false).await;
+                }
+                Op::IdleCompactContext => {
+                    self.handle_manual_compaction(true).await;
+                }
+                Op::WarmMcp => {
+                    self.warm_mcp().await;
+                }
+                Op::FetchMcpPrompt {
+                    server,
+                    name,
+                    arguments,
+                } => {
+                    self.fetch_mcp_prompt(server, name, arguments).await;
+                }
+                Op::RerunToolCall {
+                    tool_name,
+                    input,
+                    mode,
+                } => {
+                    self.handle_tool_rerun(tool_name, input, mode).await;
+                }
+                Op::ResumePausedInput {
+                    tool_id,
+                    outcome,
+                    mode,
+                } => {
+                    self.handle_resume_paused_input(tool_id, outcome, mode)
+                        .await;
+                }
+                Op::ClearToolResultCache => {
+                    let removed = self.tool_result_cache.clear();
+                    let _ = self
+                        .tx_event
+                        .send(Event::status(format!(
+                            "Cleared {removed} cached tool result{}",
+                            if removed == 1 { "" } else { "s" }
+                        )))
+                        .await;
                 }
                 Op::EditLastTurn { new_message } => {
                     // #383: /edit — remove the last user+assistant exchange
@@ -806,7 +1037,7 @@ impl Engine {
                     // reusing the engine's stored mode/model config.
                     let mode = AppMode::Agent; // default fallback
                     self.handle_send_message(
-                        new_message,
+                        TurnInput::Text(new_message),
                         mode,
                         self.session.model.clone(),
                         self.config.goal_objective.clone(),
@@ -856,7 +1087,7 @@ impl Engine {
         self.emit_session_updated().await;
     }
 
-    fn turn_metadata_block(&self) -> ContentBlock {
+    fn turn_metadata_block(&self, stale_note: Option<&str>) -> ContentBlock {
         let today = chrono::Local::now().format("%Y-%m-%d").to_string();
         let working_set_summary = self
             .session
@@ -865,11 +1096,15 @@ impl Engine {
             .map(|s| s.trim().to_string())
             .filter(|s| !s.is_empty());
 
-        let summary = if let Some(working_set_summary) = working_set_summary {
+        let mut summary = if let Some(working_set_summary) = working_set_summary {
             format!("Current local date: {today}\n{working_set_summary}")
         } else {
             format!("Current local date: {today}")
         };
+        if let Some(note) = stale_note {
+            summary.push('\n');
+            summary.push_str(note);
+        }
 
         ContentBlock::Text {
             text: format!("<turn_meta>\n{summary}\n</turn_meta>"),
@@ -878,10 +1113,16 @@ impl Engine {
     }
 
     fn user_text_message_with_turn_metadata(&self, text: String) -> Message {
+        self.user_text_message_with_stale_note(text, None)
+    }
+
+    /// A user message whose `<turn_meta>` also carries `stale_note`, the
+    /// list of previously read files that changed on disk.
+    fn user_text_message_with_stale_note(&self, text: String, stale_note: Option<&str>) -> Message {
         Message {
             role: "user".to_string(),
             content: vec![
-                self.turn_metadata_block(),
+                self.turn_metadata_block(stale_note),
                 ContentBlock::Text {
                     text,
                     cache_control: None,
@@ -894,7 +1135,7 @@ impl Engine {
     #[allow(clippy::too_many_arguments)]
     async fn handle_send_message(
         &mut self,
-        content: String,
+        input: TurnInput,
         mode: AppMode,
         model: String,
         goal_objective: Option<String>,
@@ -969,13 +1210,44 @@ impl Engine {
             return;
         }
 
-        self.session
-            .working_set
-            .observe_user_message(&content, &self.session.workspace);
-        let force_update_plan_first = should_force_update_plan_first(mode, &content);
+        // Files read earlier that changed on disk since; reported in this
+        // turn's metadata. Changes during the turn are the agent's own.
+        let stale_paths = self.stale_reads.begin_turn();
+        if !stale_paths.is_empty() {
+            let _ = self
+                .tx_event
+                .send(Event::ReadFilesChanged {
+                    paths: stale_paths.clone(),
+                })
+                .await;
+        }
+
+        let (user_msg, force_update_plan_first, trail_prompt) = match input {
+            TurnInput::Text(content) => {
+                self.session
+                    .working_set
+                    .observe_user_message(&content, &self.session.workspace);
+                let force_update_plan_first = should_force_update_plan_first(mode, &content);
+                let trail_prompt = self.config.trail_enabled.then(|| content.clone());
+                let stale_note =
+                    crate::stale_reads::stale_note(&stale_paths, &self.session.workspace);
+                (
+                    self.user_text_message_with_stale_note(content, stale_note.as_deref()),
+                    force_update_plan_first,
+                    trail_prompt,
+                )
+            }
+            TurnInput::ToolResults(blocks) => (
+                Message {
+                    role: "user".to_string(),
+                    content: blocks,
+                },
+                false,
+                None,
+            ),
+        };
 
         // Add user message to session
-        let user_msg = self.user_text_message_with_turn_metadata(content);
         self.session.add_message(user_msg);
 
         self.session.model = model;
@@ -1079,6 +1351,8 @@ impl Engine {
                         )
                         .with_max_spawn_depth(self.config.max_spawn_depth)
                         .with_step_api_timeout(self.config.subagent_api_timeout)
+                        .with_max_steps(self.config.subagent_max_steps)
+                        .with_allowed_models(self.config.subagent_allowed_models.clone())
                         .with_parent_completion_tx(self.tx_subagent_completion.clone());
                         if let Some(context) = fork_context_for_runtime.clone() {
                             rt = rt.with_fork_context(context);
@@ -1140,6 +1414,20 @@ impl Engine {
         // Update session usage
         self.session.total_usage.add(&turn.usage);
 
+        // Trail commit for `/trail` (read before `turn.usage` moves below).
+        let trail_commit = trail_prompt
+            .filter(|_| self.config.trail_enabled && !turn.tool_calls.is_empty())
+            .map(|prompt| {
+                let tools: Vec<String> = turn
+                    .tool_calls
+                    .iter()
+                    .map(|call| call.name.clone())
+                    .collect();
+                crate::git_trail::turn_message(self.turn_counter, &prompt, &tools)
+            });
+
+        self.stale_reads.end_turn();
+
         // Emit turn complete event — after all post-turn bookkeeping so
         // the terminal is immediately responsive when the UI receives it.
         let _ = self
@@ -1163,9 +1451,32 @@ impl Engine {
                 post_turn_snapshot(&post_workspace, post_seq, post_cap);
             });
         }
+
+        // Turn-level audit trail: one commit per file-changing turn on the
+        // trail branch. Same fire-and-forget model as the snapshot above.
+        if let Some(message) = trail_commit {
+            let workspace = self.session.workspace.clone();
+            let branch = self.config.trail_branch.clone();
+            crate::utils::spawn_blocking_supervised("git-trail", move || {
+                match crate::git_trail::record_turn(&workspace, &branch, &message) {
+                    Ok(Some(commit)) => {
+                        tracing::debug!(target: "git_trail", "recorded {commit} on {branch}");
+                    }
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!(target: "git_trail", "trail commit failed: {e}"),
+                }
+            });
+        }
     }
 
-    async fn handle_manual_compaction(&mut self) {
+    /// Compact on request. An `idle` compaction is started by the UI while
+    /// the user is away: it reports as automatic, skips `TurnComplete`, and
+    /// gives up as soon as the cancel token fires so a new turn never waits
+    /// on it.
+    async fn handle_manual_compaction(&mut self, idle: bool) {
+        if idle {
+            self.reset_cancel_token();
+        }
         let id = format!("compact_{}", &uuid::Uuid::new_v4().to_string()[..8]);
         let zero_usage = Usage {
             input_tokens: 0,
@@ -1174,8 +1485,10 @@ impl Engine {
         };
         let Some(client) = self.deepseek_client.clone() else {
             let message = "Manual compaction unavailable: API client not configured".to_string();
-            self.emit_compaction_failed(id, false, message.clone())
-                .await;
+            self.emit_compaction_failed(id, idle, message.clone()).await;
+            if idle {
+                return;
+            }
             let _ = self
                 .tx_event
                 .send(Event::error(ErrorEnvelope::fatal_auth(message.clone())))
@@ -1191,8 +1504,12 @@ impl Engine {
             return;
         };
 
-        let start_message = "Manual context compaction started".to_string();
-        self.emit_compaction_started(id.clone(), false, start_message)
+        let start_message = if idle {
+            "Idle context compaction started".to_string()
+        } else {
+            "Manual context compaction started".to_string()
+        };
+        self.emit_compaction_started(id.clone(), idle, start_message)
             .await;
 
         let compaction_pins = self
@@ -1204,21 +1521,41 @@ impl Engine {
         let mut turn_status = TurnOutcomeStatus::Completed;
         let mut turn_error = None;
 
-        match compact_messages_safe(
+        let compaction = compact_messages_safe(
             &client,
             &self.session.messages,
             &self.config.compaction,
             Some(&self.session.workspace),
             Some(&compaction_pins),
             Some(&compaction_paths),
-        )
-        .await
-        {
+        );
+        let outcome = if idle {
+            let cancel = self.cancel_token.clone();
+            tokio::select! {
+                outcome = compaction => Some(outcome),
+                () = cancel.cancelled() => None,
+            }
+        } else {
+            Some(compaction.await)
+        };
+        let Some(outcome) = outcome else {
+            self.reset_cancel_token();
+            self.emit_compaction_failed(id, true, "Idle compaction cancelled".to_string())
+                .await;
+            return;
+        };
+
+        match outcome {
             Ok(result) => {
                 if !result.messages.is_empty() || self.session.messages.is_empty() {
                     let messages_after = result.messages.len();
                     self.session.messages = result.messages;
-                    self.merge_compaction_summary(result.summary_prompt);
+                    self.merge_compaction_digest(
+                        &client,
+                        result.summary_prompt,
+                        &result.removed_messages,
+                    )
+                    .await;
                     self.emit_session_updated().await;
                     let removed = messages_before.saturating_sub(messages_after);
                     let message = if result.retries_used > 0 {
@@ -1233,7 +1570,7 @@ impl Engine {
                     };
                     self.emit_compaction_completed(
                         id,
-                        false,
+                        idle,
                         message,
                         Some(messages_before),
                         Some(messages_after),
@@ -1241,22 +1578,23 @@ impl Engine {
                     .await;
                 } else {
                     let message = "Compaction skipped: produced empty result".to_string();
-                    self.emit_compaction_failed(id, false, message.clone())
-                        .await;
+                    self.emit_compaction_failed(id, idle, message.clone()).await;
                     turn_status = TurnOutcomeStatus::Failed;
                     turn_error = Some(message);
                 }
             }
             Err(err) => {
                 let message = format!("Manual context compaction failed: {err}");
-                self.emit_compaction_failed(id, false, message.clone())
-                    .await;
+                self.emit_compaction_failed(id, idle, message.clone()).await;
                 let _ = self.tx_event.send(Event::status(message.clone())).await;
                 turn_status = TurnOutcomeStatus::Failed;
                 turn_error = Some(message);
             }
         }
 
+        if idle {
+            return;
+        }
         let _ = self
             .tx_event
             .send(Event::TurnComplete {
@@ -1307,6 +1645,7 @@ impl Engine {
 
         let mut retries_used = 0u32;
         let mut summary_prompt = None;
+        let mut removed_messages = Vec::new();
         let mut compacted_messages = self.session.messages.clone();
 
         let mut forced_config = self.config.compaction.clone();
@@ -1334,6 +1673,7 @@ impl Engine {
                 retries_used = result.retries_used;
                 compacted_messages = result.messages;
                 summary_prompt = result.summary_prompt;
+                removed_messages = result.removed_messages;
             }
             Err(err) => {
                 let _ = self
@@ -1348,7 +1688,8 @@ impl Engine {
         if !compacted_messages.is_empty() || self.session.messages.is_empty() {
             self.session.messages = compacted_messages;
         }
-        self.merge_compaction_summary(summary_prompt);
+        self.merge_compaction_digest(client, summary_prompt, &removed_messages)
+            .await;
 
         let trimmed = self.trim_oldest_messages_to_budget(target_budget);
         self.emit_session_updated().await;
@@ -1416,6 +1757,10 @@ impl Engine {
         .with_features(self.config.features.clone())
         .with_shell_manager(self.shell_manager.clone())
         .with_runtime_services(self.config.runtime_services.clone())
+        .with_session_changes(self.config.session_changes.clone())
+        .with_exec_profile(self.config.exec_profile.clone())
+        .with_session_env(self.config.session_env.clone())
+        .with_shell_limits(self.config.shell_limits.clone())
         .with_cancel_token(self.cancel_token.clone())
         .with_trusted_external_paths(trusted_external_paths);
 
@@ -1452,8 +1797,10 @@ impl Engine {
         // Wire search provider config.
         ctx.search_provider = self.config.search_provider;
         ctx.search_api_key = self.config.search_api_key.clone();
+        ctx.edit_validation = self.config.edit_validation.clone();
 
-        let policy = sandbox_policy_for_mode(mode, &self.session.workspace);
+        let policy =
+            sandbox_policy_for_trust(mode, self.config.trust_level, &self.session.workspace);
         let mut ctx = ctx.with_elevated_sandbox_policy(policy);
         if matches!(mode, AppMode::Plan) {
             ctx = ctx.with_shell_network_denied_hint(
@@ -1477,6 +1824,85 @@ impl Engine {
         Ok(pool)
     }
 
+    /// Connect the MCP pool on a background task. A turn that needs the pool
+    /// meanwhile waits on its lock instead of connecting a second time.
+    async fn warm_mcp(&mut self) {
+        let pool = match self.ensure_mcp_pool().await {
+            Ok(pool) => pool,
+            Err(err) => {
+                let _ = self
+                    .tx_event
+                    .send(Event::McpWarmed {
+                        connected: 0,
+                        failures: vec![err.to_string()],
+                    })
+                    .await;
+                return;
+            }
+        };
+        let tx_event = self.tx_event.clone();
+        spawn_supervised("mcp-warm", std::panic::Location::caller(), async move {
+            let mut pool = pool.lock().await;
+            let failures = pool
+                .connect_all()
+                .await
+                .into_iter()
+                .map(|(server, err)| format!("{server}: {err:#}"))
+                .collect();
+            let connected = pool.connected_servers().len();
+            let _ = tx_event
+                .send(Event::McpWarmed {
+                    connected,
+                    failures,
+                })
+                .await;
+        });
+    }
+
+    /// Fetch a prompt on a background task, reusing the session's pool so
+    /// `/mcp prompt` does not spawn or reconnect its server every time.
+    async fn fetch_mcp_prompt(
+        &mut self,
+        server: String,
+        name: String,
+        arguments: Vec<(String, String)>,
+    ) {
+        let pool = match self.ensure_mcp_pool().await {
+            Ok(pool) => pool,
+            Err(err) => {
+                let _ = self
+                    .tx_event
+                    .send(Event::McpPromptFetched {
+                        server,
+                        name,
+                        result: Err(err.to_string()),
+                    })
+                    .await;
+                return;
+            }
+        };
+        let tx_event = self.tx_event.clone();
+        spawn_supervised("mcp-prompt", std::panic::Location::caller(), async move {
+            let arguments = arguments
+                .into_iter()
+                .map(|(key, value)| (key, serde_json::Value::String(value)))
+                .collect::<serde_json::Map<_, _>>();
+            let result = pool
+                .lock()
+                .await
+                .get_prompt(&server, &name, serde_json::Value::Object(arguments))
+                .await
+                .map_err(|err| format!("{err:#}"));
+            let _ = tx_event
+                .send(Event::McpPromptFetched {
+                    server,
+                    name,
+                    result,
+                })
+                .await;
+        });
+    }
+
     async fn mcp_tools(&mut self) -> Vec<Tool> {
         let pool = match self.ensure_mcp_pool().await {
             Ok(pool) => pool,
@@ -1806,21 +2232,30 @@ impl Engine {
     fn refresh_system_prompt(&mut self, mode: AppMode) {
         let user_memory_block =
             crate::memory::compose_block(self.config.memory_enabled, &self.config.memory_path);
-        let base = prompts::system_prompt_for_mode_with_context_skills_session_and_approval(
+        let command_lessons_block = crate::lessons::compose_block(
+            self.config.command_lessons_enabled,
+            &self.config.workspace,
+        );
+        let layers = prompts::system_prompt_layers(
             mode,
             &self.config.workspace,
-            None,
             Some(&self.config.skills_dir),
             Some(&self.config.instructions),
             prompts::PromptSessionContext {
                 user_memory_block: user_memory_block.as_deref(),
+                command_lessons_block: command_lessons_block.as_deref(),
                 goal_objective: self.config.goal_objective.as_deref(),
                 project_context_pack_enabled: self.config.project_context_pack_enabled,
+                repo_map_tokens: self.config.repo_map_tokens,
                 locale_tag: &self.config.locale_tag,
                 translation_enabled: self.config.translation_enabled,
+                self_report: self.config.self_report,
+                layer_overrides: Some(&self.config.prompt_layers),
             },
             self.session.approval_mode,
         );
+        self.publish_prompt_layers(&layers);
+        let base = SystemPrompt::Text(prompts::layers::render(&layers));
         let stable_prompt =
             merge_system_prompts(Some(&base), self.session.compaction_summary_prompt.clone());
         let stable_hash = system_prompt_hash(stable_prompt.as_ref());
@@ -1834,6 +2269,25 @@ impl Engine {
         }
     }
 
+    /// Tell the UI about the layer stack when it changes. Sent with
+    /// `try_send` because prompt refresh is synchronous; a dropped update is
+    /// retried on the next refresh.
+    fn publish_prompt_layers(&mut self, layers: &[prompts::layers::PromptLayer]) {
+        let summary = prompts::layers::summarize(layers);
+        if summary == self.last_prompt_layers {
+            return;
+        }
+        if self
+            .tx_event
+            .try_send(Event::PromptLayers {
+                layers: summary.clone(),
+            })
+            .is_ok()
+        {
+            self.last_prompt_layers = summary;
+        }
+    }
+
     fn merge_compaction_summary(&mut self, summary_prompt: Option<SystemPrompt>) {
         if summary_prompt.is_none() {
             return;
@@ -1846,6 +2300,47 @@ impl Engine {
         self.session.last_system_prompt_hash = Some(system_prompt_hash(merged.as_ref()));
         self.session.system_prompt = merged;
     }
+
+    /// Merge the digest from a summary compaction. The summarized originals
+    /// are archived first so `recall_history` can return them, then digests
+    /// beyond [`crate::compaction::DIGESTS_PER_TIER`] per tier are folded a
+    /// tier up.
+    async fn merge_compaction_digest(
+        &mut self,
+        client: &DeepSeekClient,
+        mut summary_prompt: Option<SystemPrompt>,
+        removed_messages: &[Message],
+    ) {
+        if let Some(prompt) = summary_prompt.as_mut()
+            && !removed_messages.is_empty()
+        {
+            match HistoryArchive::for_session(&self.session.id)
+                .and_then(|archive| archive.append(removed_messages))
+            {
+                Ok(segment) => set_digest_segment(prompt, segment),
+                Err(err) => crate::logging::warn(format!(
+                    "Failed to archive compacted messages; recall_history will not see them: {err}"
+                )),
+            }
+        }
+        self.merge_compaction_summary(summary_prompt);
+
+        while let Some(SystemPrompt::Blocks(blocks)) =
+            self.session.compaction_summary_prompt.as_ref()
+            && let Some((indices, tier)) = plan_digest_fold(blocks)
+        {
+            let folded: Vec<SystemBlock> = indices.iter().map(|&idx| blocks[idx].clone()).collect();
+            let merged = fold_digests(client, &self.config.compaction.model, &folded, tier).await;
+            if let Some(prompt) = self.session.compaction_summary_prompt.as_mut() {
+                replace_digests(prompt, &folded, merged.clone());
+            }
+            if let Some(prompt) = self.session.system_prompt.as_mut() {
+                replace_digests(prompt, &folded, merged);
+            }
+            self.session.last_system_prompt_hash =
+                Some(system_prompt_hash(self.session.system_prompt.as_ref()));
+        }
+    }
 }
 
 fn system_prompt_hash(prompt: Option<&SystemPrompt>) -> u64 {
@@ -1910,6 +2405,10 @@ pub(crate) enum MockApprovalEvent {
         id: String,
         policy: crate::sandbox::SandboxPolicy,
     },
+    RetryWithHigherLimits {
+        id: String,
+        kind: crate::sandbox::limits::LimitKind,
+    },
 }
 
 #[cfg(test)]
@@ -1921,6 +2420,9 @@ impl MockEngineHandle {
             ApprovalDecision::RetryWithPolicy { id, policy } => {
                 Some(MockApprovalEvent::RetryWithPolicy { id, policy })
             }
+            ApprovalDecision::RetryWithHigherLimits { id, kind } => {
+                Some(MockApprovalEvent::RetryWithHigherLimits { id, kind })
+            }
         }
     }
 }
@@ -1940,9 +2442,14 @@ pub(crate) fn mock_engine_handle() -> MockEngineHandle {
         rx_event: Arc::new(RwLock::new(rx_event)),
         cancel_token: shared_cancel_token,
         cancel_reason,
+        soft_stop: Arc::new(SoftStop::default()),
         tx_approval,
         tx_user_input,
         tx_steer,
+        subagent_manager: Arc::new(RwLock::new(crate::tools::subagent::SubAgentManager::new(
+            std::env::temp_dir(),
+            1,
+        ))),
     };
 
     MockEngineHandle {
@@ -1969,7 +2476,11 @@ use context::{
 mod dispatch;
 mod loop_guard;
 mod lsp_hooks;
+mod model_fallback;
+mod rerun;
+mod resume_input;
 mod streaming;
+mod thinking_guard;
 mod tool_catalog;
 mod tool_execution;
 mod tool_setup;
@@ -1980,28 +2491,31 @@ use self::approval::{ApprovalDecision, ApprovalResult, UserInputDecision};
 use self::dispatch::should_parallelize_tool_batch;
 use self::dispatch::{
     ParallelToolResult, ParallelToolResultEntry, ToolExecGuard, ToolExecOutcome,
-    ToolExecutionBatch, ToolExecutionPlan, caller_allowed_for_tool, caller_type_for_tool_use,
-    final_tool_input, format_tool_error, mcp_tool_approval_description, mcp_tool_is_parallel_safe,
-    mcp_tool_is_read_only, parse_parallel_tool_calls, parse_tool_input,
-    plan_tool_execution_batches, should_force_update_plan_first, should_stop_after_plan_tool,
+    ToolExecutionBatch, ToolExecutionPlan, apply_tool_delegate, caller_allowed_for_tool,
+    caller_type_for_tool_use, final_tool_input, format_tool_error, mark_skipped_by_soft_stop,
+    mcp_tool_approval_description, mcp_tool_is_parallel_safe, mcp_tool_is_read_only,
+    parse_parallel_tool_calls, parse_tool_input, plan_awaits_approval, plan_tool_execution_batches,
+    should_force_update_plan_first, should_stop_after_plan_tool,
 };
 use self::loop_guard::{AttemptDecision, LoopGuard, OutcomeDecision};
 #[cfg(test)]
 use self::lsp_hooks::{edited_paths_for_tool, parse_patch_paths};
+use self::model_fallback::{fallback_reason, next_fallback_model};
 #[cfg(test)]
 use self::streaming::TOOL_CALL_START_MARKERS;
 use self::streaming::{
-    ContentBlockKind, FAKE_WRAPPER_NOTICE, MAX_STREAM_ERRORS_BEFORE_FAIL,
+    ContentBlockKind, ContextMeter, FAKE_WRAPPER_NOTICE, MAX_STREAM_ERRORS_BEFORE_FAIL,
     MAX_TRANSPARENT_STREAM_RETRIES, STREAM_MAX_CONTENT_BYTES, STREAM_MAX_DURATION_SECS,
     ToolUseState, contains_fake_tool_wrapper, filter_tool_call_delta,
     should_transparently_retry_stream, stream_chunk_timeout_secs,
 };
+use self::thinking_guard::{ThinkingGuard, ThinkingVerdict};
 use self::tool_catalog::{
     CODE_EXECUTION_TOOL_NAME, JS_EXECUTION_TOOL_NAME, MULTI_TOOL_PARALLEL_NAME,
     REQUEST_USER_INPUT_NAME, active_tools_for_step, build_model_tool_catalog,
     ensure_advanced_tooling, execute_code_execution_tool, execute_tool_search,
     initial_active_tools, is_tool_search_tool, maybe_hydrate_requested_deferred_tool,
-    missing_tool_error_message,
+    missing_tool_error_message, summarize_tool_catalog,
 };
 #[cfg(test)]
 use self::tool_catalog::{
@@ -2009,7 +2523,7 @@ use self::tool_catalog::{
     preflight_requested_deferred_tool, should_default_defer_tool,
 };
 use self::tool_execution::emit_tool_audit;
-use self::tool_setup::sandbox_policy_for_mode;
+use self::tool_setup::sandbox_policy_for_trust;
 use crate::tools::js_execution::execute_js_execution_tool;
 
 #[cfg(test)]
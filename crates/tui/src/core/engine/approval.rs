@@ -9,7 +9,9 @@ use crate::core::events::Event;
 use crate::tools::spec::ToolError;
 use crate::tools::user_input::{UserInputRequest, UserInputResponse};
 
-use super::Engine;
+use serde_json::json;
+
+use super::{Engine, ToolExecutionPlan, emit_tool_audit};
 
 #[derive(Debug, Clone)]
 pub(super) enum ApprovalDecision {
@@ -24,6 +26,11 @@ pub(super) enum ApprovalDecision {
         id: String,
         policy: crate::sandbox::SandboxPolicy,
     },
+    /// Retry a tool with a raised `[shell_limits]` cap.
+    RetryWithHigherLimits {
+        id: String,
+        kind: crate::sandbox::limits::LimitKind,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +44,26 @@ pub(super) enum UserInputDecision {
     },
 }
 
+impl ApprovalDecision {
+    fn id(&self) -> &str {
+        match self {
+            Self::Approved { id }
+            | Self::Denied { id }
+            | Self::RetryWithPolicy { id, .. }
+            | Self::RetryWithHigherLimits { id, .. } => id,
+        }
+    }
+
+    fn into_result(self) -> ApprovalResult {
+        match self {
+            Self::Approved { .. } => ApprovalResult::Approved,
+            Self::Denied { .. } => ApprovalResult::Denied,
+            Self::RetryWithPolicy { policy, .. } => ApprovalResult::RetryWithPolicy(policy),
+            Self::RetryWithHigherLimits { kind, .. } => ApprovalResult::RetryWithHigherLimits(kind),
+        }
+    }
+}
+
 /// Result of awaiting tool approval from the user.
 #[derive(Debug)]
 pub(super) enum ApprovalResult {
@@ -46,6 +73,8 @@ pub(super) enum ApprovalResult {
     Denied,
     /// User requested retry with an elevated sandbox policy.
     RetryWithPolicy(crate::sandbox::SandboxPolicy),
+    /// User requested retry with a raised resource limit.
+    RetryWithHigherLimits(crate::sandbox::limits::LimitKind),
 }
 
 impl Engine {
@@ -64,10 +93,36 @@ impl Engine {
         }
     }
 
+    /// Ask the UI to approve `plan`.
+    pub(super) async fn announce_tool_approval(&self, plan: &ToolExecutionPlan) {
+        emit_tool_audit(json!({
+            "event": "tool.approval_required",
+            "tool_id": plan.id.clone(),
+            "tool_name": plan.name.clone(),
+        }));
+        let approval_key =
+            crate::tools::approval_cache::build_approval_key(&plan.name, &plan.input).0;
+        let approval_grouping_key =
+            crate::tools::approval_cache::build_approval_grouping_key(&plan.name, &plan.input).0;
+        let _ = self
+            .tx_event
+            .send(Event::ApprovalRequired {
+                id: plan.id.clone(),
+                tool_name: plan.name.clone(),
+                description: plan.approval_description.clone(),
+                approval_key,
+                approval_grouping_key,
+            })
+            .await;
+    }
+
     pub(super) async fn await_tool_approval(
         &mut self,
         tool_id: &str,
     ) -> Result<ApprovalResult, ToolError> {
+        if let Some(decision) = self.early_approvals.remove(tool_id) {
+            return Ok(decision.into_result());
+        }
         loop {
             tokio::select! {
                 _ = self.cancel_token.cancelled() => {
@@ -85,18 +140,14 @@ impl Engine {
                                 .to_string(),
                         ));
                     };
-                    match decision {
-                        ApprovalDecision::Approved { id } if id == tool_id => {
-                            return Ok(ApprovalResult::Approved);
-                        }
-                        ApprovalDecision::Denied { id } if id == tool_id => {
-                            return Ok(ApprovalResult::Denied);
-                        }
-                        ApprovalDecision::RetryWithPolicy { id, policy } if id == tool_id => {
-                            return Ok(ApprovalResult::RetryWithPolicy(policy));
-                        }
-                        _ => continue,
+                    if decision.id() == tool_id {
+                        return Ok(decision.into_result());
                     }
+                    // Every approval in a step is announced up front, so the
+                    // UI may answer a later call first. Keep that answer
+                    // until the loop reaches the call.
+                    self.early_approvals
+                        .insert(decision.id().to_string(), decision);
                 }
             }
         }
@@ -419,7 +419,12 @@ impl Engine {
                 Ok(result) => {
                     if !result.messages.is_empty() || self.session.messages.is_empty() {
                         self.session.messages = result.messages;
-                        self.merge_compaction_summary(result.summary_prompt);
+                        self.merge_compaction_digest(
+                            client,
+                            result.summary_prompt,
+                            &result.removed_messages,
+                        )
+                        .await;
                         refreshed = true;
                     }
                 }
@@ -4,7 +4,8 @@
 //! lives in `Engine::handle_deepseek_turn`; this module owns:
 //!
 //! * Streaming-buffer parsing into a finalized `serde_json::Value` tool input
-//!   (`final_tool_input`, `parse_tool_input`, fenced/JSON segment helpers).
+//!   (`final_tool_input`, `parse_tool_input`, fenced/JSON segment helpers),
+//!   and delegation of alias tools to the call they stand for.
 //! * The `multi_tool_use.parallel` payload parser.
 //! * Policy predicates the turn loop consults — when a batch can run in
 //!   parallel, when an `update_plan` step should stop the turn, when a Plan
@@ -22,6 +23,10 @@ use crate::tools::spec::{ToolError, ToolResult};
 use crate::tui::app::AppMode;
 
 use super::ToolUseState;
+use super::tool_catalog::{
+    CODE_EXECUTION_TOOL_NAME, JS_EXECUTION_TOOL_NAME, MULTI_TOOL_PARALLEL_NAME,
+    REQUEST_USER_INPUT_NAME, is_tool_search_tool,
+};
 
 // === Types ============================================================
 
@@ -56,6 +61,15 @@ pub(super) enum ToolExecutionBatch {
     Serial(Box<ToolExecutionPlan>),
 }
 
+impl ToolExecutionBatch {
+    pub(super) fn plans(&self) -> &[ToolExecutionPlan] {
+        match self {
+            Self::Parallel(plans) => plans,
+            Self::Serial(plan) => std::slice::from_ref(plan.as_ref()),
+        }
+    }
+}
+
 #[derive(Debug, serde::Serialize)]
 pub(super) struct ParallelToolResultEntry {
     pub(super) tool_name: String,
@@ -70,6 +84,17 @@ pub(super) struct ParallelToolResult {
     pub(super) results: Vec<ParallelToolResultEntry>,
 }
 
+/// Record `plan` as skipped because the user soft-cancelled the turn before
+/// it started. Plans that already resolved without executing (guard hits,
+/// policy blocks) keep their result.
+pub(super) fn mark_skipped_by_soft_stop(plan: &mut ToolExecutionPlan) {
+    if plan.guard_result.is_none() && plan.blocked_error.is_none() {
+        plan.blocked_error = Some(ToolError::execution_failed(
+            "Not run: the user interrupted the turn before this tool started.",
+        ));
+    }
+}
+
 // Hold the lock guard for the duration of a tool execution.
 // The inner guards are held for RAII purposes (dropped when the guard is dropped).
 pub(super) enum ToolExecGuard<'a> {
@@ -154,6 +179,24 @@ pub(super) fn final_tool_input(state: &ToolUseState) -> serde_json::Value {
     state.input.clone()
 }
 
+/// Swap a call for the built-in call its tool delegates to (a shell command
+/// alias becomes `exec_shell` with the expanded command), so the UI, the
+/// approval card, and the guardrails all see what will actually run.
+pub(super) fn apply_tool_delegate(
+    registry: Option<&crate::tools::ToolRegistry>,
+    state: &mut ToolUseState,
+) {
+    let Some(spec) = registry.and_then(|registry| registry.get(&state.name)) else {
+        return;
+    };
+    if let Some((name, input)) = spec.delegate_call(&final_tool_input(state)) {
+        crate::logging::info(format!("Tool '{}' delegates to '{name}'", state.name));
+        state.name = name;
+        state.input = input;
+        state.input_buffer.clear();
+    }
+}
+
 pub(super) fn parse_tool_input(buffer: &str) -> Option<serde_json::Value> {
     let trimmed = buffer.trim();
     if trimmed.is_empty() {
@@ -279,6 +322,24 @@ pub(super) fn tool_plan_is_parallel_safe(plan: &ToolExecutionPlan) -> bool {
     plan.read_only && plan.supports_parallel && !plan.approval_required && !plan.interactive
 }
 
+/// Whether the serial driver stops at the approval handshake for `plan`.
+/// Guard hits, policy blocks, and the tools the driver runs inline
+/// (parallel fan-out, code execution, tool search, user input) never
+/// reach it.
+pub(super) fn plan_awaits_approval(plan: &ToolExecutionPlan) -> bool {
+    plan.approval_required
+        && plan.guard_result.is_none()
+        && plan.blocked_error.is_none()
+        && !matches!(
+            plan.name.as_str(),
+            MULTI_TOOL_PARALLEL_NAME
+                | CODE_EXECUTION_TOOL_NAME
+                | JS_EXECUTION_TOOL_NAME
+                | REQUEST_USER_INPUT_NAME
+        )
+        && !is_tool_search_tool(&plan.name)
+}
+
 pub(super) fn plan_tool_execution_batches(
     plans: Vec<ToolExecutionPlan>,
 ) -> Vec<ToolExecutionBatch> {
@@ -3,7 +3,7 @@
 //! The struct itself lives next door in `engine.rs` because two
 //! construction sites (`Engine::new` and the test-only
 //! `mock_engine_handle`) need access to its private mpsc channels.
-//! The method surface — `send`, `cancel*`, `is_cancelled`,
+//! The method surface — `send`, `cancel*`, `soft_cancel`, `is_cancelled`,
 //! `approve_tool_call` / `deny_tool_call` / `retry_tool_with_policy`,
 //! `submit_user_input` / `cancel_user_input`, and `steer` — moves here
 //! so the agent loop's mailbox API is reviewable on its own.
@@ -12,6 +12,7 @@ use anyhow::Result;
 
 use super::approval::{ApprovalDecision, UserInputDecision};
 use super::{CancelReason, EngineHandle, Op, UserInputResponse};
+use crate::tools::subagent::SubAgentResult;
 
 impl EngineHandle {
     /// Send an operation to the engine
@@ -40,6 +41,25 @@ impl EngineHandle {
         }
     }
 
+    /// Cancel one sub-agent without touching the current turn or its
+    /// siblings. Goes straight to the shared manager so it takes effect even
+    /// while a turn is holding the op queue.
+    pub async fn cancel_subagent(&self, agent_id: &str) -> Result<SubAgentResult> {
+        self.subagent_manager.write().await.cancel(agent_id)
+    }
+
+    /// Soft-cancel the current turn: let the running tool finish and keep
+    /// its result, then stop before the next model request. Falls back to
+    /// a hard [`cancel`](Self::cancel) when no tool is running. Returns
+    /// `true` when the soft path was taken.
+    pub fn soft_cancel(&self) -> bool {
+        if self.soft_stop.request() {
+            return true;
+        }
+        self.cancel();
+        false
+    }
+
     /// Check if a request is currently cancelled
     #[must_use]
     #[allow(dead_code)]
@@ -81,6 +101,21 @@ impl EngineHandle {
         Ok(())
     }
 
+    /// Retry a tool call with a raised `[shell_limits]` cap for `kind`.
+    pub async fn retry_tool_with_higher_limits(
+        &self,
+        id: impl Into<String>,
+        kind: crate::sandbox::limits::LimitKind,
+    ) -> Result<()> {
+        self.tx_approval
+            .send(ApprovalDecision::RetryWithHigherLimits {
+                id: id.into(),
+                kind,
+            })
+            .await?;
+        Ok(())
+    }
+
     /// Submit a response for request_user_input.
     pub async fn submit_user_input(
         &self,
@@ -8,6 +8,9 @@ use std::hash::{Hash, Hasher};
 use serde_json::Value;
 
 const IDENTICAL_CALL_BLOCK_THRESHOLD: u32 = 3;
+/// Identical failing calls (same tool, same arguments) before the loop is
+/// reported, the model is nudged, and the exact repeat is blocked.
+const IDENTICAL_FAILURE_THRESHOLD: u32 = 2;
 const FAILURE_WARN_THRESHOLD: u32 = 3;
 const FAILURE_HALT_THRESHOLD: u32 = 8;
 
@@ -21,6 +24,12 @@ pub(super) enum AttemptDecision {
 pub(super) enum OutcomeDecision {
     Continue,
     Warn(String),
+    /// The same call failed [`IDENTICAL_FAILURE_THRESHOLD`] times in a row.
+    /// `nudge` goes to the model; the exact repeat is blocked from now on.
+    LoopDetected {
+        failures: u32,
+        nudge: String,
+    },
     Halt(String),
 }
 
@@ -28,11 +37,18 @@ pub(super) enum OutcomeDecision {
 pub(super) struct LoopGuard {
     call_counts: HashMap<(String, u64), u32>,
     failure_counts: HashMap<String, u32>,
+    identical_failures: HashMap<(String, u64), u32>,
 }
 
 impl LoopGuard {
     pub(super) fn record_attempt(&mut self, tool: &str, args: &Value) -> AttemptDecision {
         let key = (tool.to_string(), hash_args(args));
+        let failures = self.identical_failures.get(&key).copied().unwrap_or(0);
+        if failures >= IDENTICAL_FAILURE_THRESHOLD {
+            return AttemptDecision::Block(format!(
+                "Blocked: `{tool}` already failed {failures} times with these exact arguments this turn. Read the error, then change the arguments or take a different approach."
+            ));
+        }
         let count = self.call_counts.entry(key).or_insert(0);
         *count = count.saturating_add(1);
         if *count >= IDENTICAL_CALL_BLOCK_THRESHOLD {
@@ -43,19 +59,31 @@ impl LoopGuard {
         AttemptDecision::Proceed
     }
 
-    pub(super) fn record_outcome(&mut self, tool: &str, ok: bool) -> OutcomeDecision {
+    pub(super) fn record_outcome(&mut self, tool: &str, args: &Value, ok: bool) -> OutcomeDecision {
+        let key = (tool.to_string(), hash_args(args));
         let failures = self.failure_counts.entry(tool.to_string()).or_insert(0);
         if ok {
             *failures = 0;
+            self.identical_failures.remove(&key);
             return OutcomeDecision::Continue;
         }
 
         *failures = failures.saturating_add(1);
+        let identical = self.identical_failures.entry(key).or_insert(0);
+        *identical = identical.saturating_add(1);
         if *failures >= FAILURE_HALT_THRESHOLD {
             return OutcomeDecision::Halt(format!(
                 "Stop retrying `{tool}` - it has failed {failures} consecutive times. Choose a different approach."
             ));
         }
+        if *identical == IDENTICAL_FAILURE_THRESHOLD {
+            return OutcomeDecision::LoopDetected {
+                failures: *identical,
+                nudge: format!(
+                    "Loop detected: `{tool}` failed {identical} times in a row with identical arguments. Repeating that exact call is now blocked for the rest of this turn. Read the error output, then change the arguments, try a different approach, or tell the user what is blocking you."
+                ),
+            };
+        }
         if *failures == FAILURE_WARN_THRESHOLD {
             return OutcomeDecision::Warn(format!(
                 "Tool `{tool}` has failed {failures} consecutive times this turn."
@@ -154,28 +182,22 @@ mod tests {
     #[test]
     fn tool_failure_counter_warns_at_three_and_halts_at_eight() {
         let mut guard = LoopGuard::default();
+        let mut fail = |pattern: u32| {
+            guard.record_outcome("grep_files", &json!({ "pattern": pattern }), false)
+        };
 
-        assert_eq!(
-            guard.record_outcome("grep_files", false),
-            OutcomeDecision::Continue
-        );
-        assert_eq!(
-            guard.record_outcome("grep_files", false),
-            OutcomeDecision::Continue
-        );
+        assert_eq!(fail(1), OutcomeDecision::Continue);
+        assert_eq!(fail(2), OutcomeDecision::Continue);
         assert!(matches!(
-            guard.record_outcome("grep_files", false),
+            fail(3),
             OutcomeDecision::Warn(message) if message.contains("failed 3 consecutive times")
         ));
 
-        for _ in 4..8 {
-            assert_eq!(
-                guard.record_outcome("grep_files", false),
-                OutcomeDecision::Continue
-            );
+        for pattern in 4..8 {
+            assert_eq!(fail(pattern), OutcomeDecision::Continue);
         }
         assert!(matches!(
-            guard.record_outcome("grep_files", false),
+            fail(8),
             OutcomeDecision::Halt(message) if message.contains("failed 8 consecutive times")
         ));
     }
@@ -183,22 +205,48 @@ mod tests {
     #[test]
     fn successful_tool_call_resets_failure_counter() {
         let mut guard = LoopGuard::default();
+        let mut record = |pattern: &str, ok: bool| {
+            guard.record_outcome("grep_files", &json!({ "pattern": pattern }), ok)
+        };
+
+        assert_eq!(record("a", false), OutcomeDecision::Continue);
+        assert_eq!(record("b", false), OutcomeDecision::Continue);
+        assert_eq!(record("c", true), OutcomeDecision::Continue);
+        assert_eq!(record("d", false), OutcomeDecision::Continue);
+    }
+
+    #[test]
+    fn identical_failures_report_a_loop_and_block_the_exact_repeat() {
+        let mut guard = LoopGuard::default();
+        let args = json!({"command": "cargo test"});
 
         assert_eq!(
-            guard.record_outcome("grep_files", false),
-            OutcomeDecision::Continue
+            guard.record_attempt("exec_shell", &args),
+            AttemptDecision::Proceed
         );
         assert_eq!(
-            guard.record_outcome("grep_files", false),
+            guard.record_outcome("exec_shell", &args, false),
             OutcomeDecision::Continue
         );
         assert_eq!(
-            guard.record_outcome("grep_files", true),
-            OutcomeDecision::Continue
+            guard.record_attempt("exec_shell", &args),
+            AttemptDecision::Proceed
         );
+        let OutcomeDecision::LoopDetected { failures, nudge } =
+            guard.record_outcome("exec_shell", &args, false)
+        else {
+            panic!("second identical failure should report a loop");
+        };
+        assert_eq!(failures, 2);
+        assert!(nudge.contains("exec_shell"), "{nudge}");
+
+        let AttemptDecision::Block(message) = guard.record_attempt("exec_shell", &args) else {
+            panic!("exact repeat should be blocked");
+        };
+        assert!(message.contains("already failed 2 times"), "{message}");
         assert_eq!(
-            guard.record_outcome("grep_files", false),
-            OutcomeDecision::Continue
+            guard.record_attempt("exec_shell", &json!({"command": "cargo test -p core"})),
+            AttemptDecision::Proceed
         );
     }
 
@@ -0,0 +1,81 @@
+//! Pure-data helpers for `model_fallbacks` chains.
+//!
+//! Network-level retries (`send_with_retry`) re-send the same request to the
+//! same model. When those are exhausted, or the provider rejects the request
+//! outright for a reason another model may not share, the turn loop walks the
+//! configured fallback list and re-issues the step with the next model.
+
+use crate::error_taxonomy::{ErrorCategory, classify_error_message};
+
+/// Why a failed request should move on to the next fallback model, or
+/// `None` when a different model would fail the same way (auth, bad input,
+/// content policy, plain connectivity).
+pub(super) fn fallback_reason(message: &str) -> Option<&'static str> {
+    let lower = message.to_lowercase();
+    if lower.contains("overloaded") || lower.contains("server is busy") {
+        return Some("overloaded");
+    }
+    if lower.contains("server error (") || lower.contains("internal server error") {
+        return Some("server error");
+    }
+    match classify_error_message(message) {
+        ErrorCategory::InvalidInput => Some("context length"),
+        ErrorCategory::RateLimit => Some("rate limited"),
+        _ => None,
+    }
+}
+
+/// First configured fallback that has not been tried this turn. Matching is
+/// case-insensitive and blank entries are ignored.
+pub(super) fn next_fallback_model(fallbacks: &[String], tried: &[String]) -> Option<String> {
+    fallbacks
+        .iter()
+        .map(|model| model.trim())
+        .filter(|model| !model.is_empty())
+        .find(|model| !tried.iter().any(|used| used.eq_ignore_ascii_case(model)))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fallback_reason_covers_overload_server_and_context_errors() {
+        assert_eq!(
+            fallback_reason("Server error (503): upstream unavailable"),
+            Some("server error")
+        );
+        assert_eq!(
+            fallback_reason("LLM error: model is overloaded, try later"),
+            Some("overloaded")
+        );
+        assert_eq!(
+            fallback_reason("Context length exceeded: maximum context length is 128000"),
+            Some("context length")
+        );
+        assert_eq!(
+            fallback_reason("Rate limit exceeded: too many requests"),
+            Some("rate limited")
+        );
+        assert_eq!(fallback_reason("Authentication failed: bad api key"), None);
+        assert_eq!(fallback_reason("Network error: dns lookup failed"), None);
+    }
+
+    #[test]
+    fn next_fallback_model_skips_tried_and_blank_entries() {
+        let chain = vec![
+            " ".to_string(),
+            "DeepSeek-V4-Pro".to_string(),
+            "deepseek-chat".to_string(),
+        ];
+        let tried = vec!["deepseek-v4-pro".to_string()];
+        assert_eq!(
+            next_fallback_model(&chain, &tried).as_deref(),
+            Some("deepseek-chat")
+        );
+        let tried = vec!["deepseek-v4-pro".to_string(), "deepseek-chat".to_string()];
+        assert_eq!(next_fallback_model(&chain, &tried), None);
+        assert_eq!(next_fallback_model(&[], &tried), None);
+    }
+}
@@ -0,0 +1,168 @@
+//! Re-running a recorded tool call from the transcript (`Op::RerunToolCall`).
+//!
+//! A re-run is a tool-only turn: it reuses the normal turn lifecycle events so
+//! the UI shows a fresh tool cell and can cancel it, but it never calls the
+//! model. The user's menu selection stands in for the approval prompt, and the
+//! call is recorded in the session (a short user note, the tool call, and its
+//! result) so the next turn sees the new output.
+
+use super::*;
+
+impl Engine {
+    pub(super) async fn handle_tool_rerun(
+        &mut self,
+        tool_name: String,
+        input: serde_json::Value,
+        mode: AppMode,
+    ) {
+        self.reset_cancel_token();
+        let turn = TurnContext::new(self.config.max_steps);
+        self.turn_counter = self.turn_counter.saturating_add(1);
+        let _ = self
+            .tx_event
+            .send(Event::TurnStarted {
+                turn_id: turn.id.clone(),
+            })
+            .await;
+
+        let tool_context = self.build_tool_context(mode, self.session.auto_approve);
+        let registry = self
+            .build_turn_tool_registry_builder(
+                mode,
+                self.config.todos.clone(),
+                self.config.plan_state.clone(),
+            )
+            .build(tool_context);
+        let mcp_pool = if McpPool::is_mcp_tool(&tool_name) {
+            match self.ensure_mcp_pool().await {
+                Ok(pool) => Some(pool),
+                Err(err) => {
+                    let _ = self.tx_event.send(Event::status(err.to_string())).await;
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let tool_id = format!("rerun_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+        self.session.add_message(Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::Text {
+                text: format!("Re-run `{tool_name}` with the same arguments."),
+                cache_control: None,
+            }],
+        });
+        self.add_session_message(Message {
+            role: "assistant".to_string(),
+            content: vec![ContentBlock::ToolUse {
+                id: tool_id.clone(),
+                name: tool_name.clone(),
+                input: input.clone(),
+                caller: None,
+            }],
+        })
+        .await;
+        let _ = self
+            .tx_event
+            .send(Event::ToolCallStarted {
+                id: tool_id.clone(),
+                name: tool_name.clone(),
+                input: input.clone(),
+            })
+            .await;
+
+        let cancel_token = self.cancel_token.clone();
+        let registry_has_tool = registry.contains(&tool_name) || mcp_pool.is_some();
+        let execution = async {
+            if !registry_has_tool {
+                return Err(ToolError::not_available(format!(
+                    "tool '{tool_name}' is not available in {} mode",
+                    mode.label()
+                )));
+            }
+            Self::execute_tool_with_lock(
+                self.tool_exec_lock.clone(),
+                false,
+                false,
+                self.tx_event.clone(),
+                tool_name.clone(),
+                input.clone(),
+                Some(&registry),
+                mcp_pool,
+                None,
+            )
+            .await
+        };
+        let (mut result, status) = tokio::select! {
+            result = execution => (result, TurnOutcomeStatus::Completed),
+            () = cancel_token.cancelled() => (
+                Err(ToolError::execution_failed("Re-run cancelled")),
+                TurnOutcomeStatus::Interrupted,
+            ),
+        };
+        if let Ok(tool_result) = result.as_mut() {
+            crate::tools::output_filter::apply_tool_filter(
+                &self.config.tool_filters,
+                tool_result,
+                &tool_id,
+                &tool_name,
+            );
+            crate::tools::truncate::apply_spillover_with_artifact(
+                tool_result,
+                &tool_id,
+                &tool_name,
+                &self.session.id,
+            );
+        }
+        self.tool_result_cache.clear();
+
+        let _ = self
+            .tx_event
+            .send(Event::ToolCallComplete {
+                id: tool_id.clone(),
+                name: tool_name.clone(),
+                result: result.clone(),
+            })
+            .await;
+        let (content, is_error) =
+            match &result {
+                Ok(output) if output.success => (
+                    compact_tool_result_for_context(&self.session.model, &tool_name, output),
+                    None,
+                ),
+                Ok(output) => (
+                    ToolFailure::from_failed_result(output).annotate(
+                        &compact_tool_result_for_context(&self.session.model, &tool_name, output),
+                    ),
+                    None,
+                ),
+                Err(err) => (
+                    ToolFailure::from_tool_error(err)
+                        .annotate(&format!("Error: {}", format_tool_error(err, &tool_name))),
+                    Some(true),
+                ),
+            };
+        self.add_session_message(Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::ToolResult {
+                tool_use_id: tool_id,
+                content,
+                is_error,
+                content_blocks: None,
+            }],
+        })
+        .await;
+
+        // A failing tool is still a completed re-run; its error is already
+        // in the tool cell.
+        let _ = self
+            .tx_event
+            .send(Event::TurnComplete {
+                usage: turn.usage,
+                status,
+                error: None,
+            })
+            .await;
+    }
+}
@@ -0,0 +1,184 @@
+//! Continuing a turn that was paused on `request_user_input`
+//! (`Op::ResumePausedInput`).
+//!
+//! When the app quits while a clarification prompt is open, the session is
+//! saved with the assistant's tool calls still unanswered. Resuming it re-opens
+//! the prompt; the answer is written as that call's tool result and the turn
+//! continues as if it had never stopped. Sibling calls from the same assistant
+//! message never ran, so they get an error result telling the model to call
+//! them again if it still needs them.
+
+use super::*;
+
+const SIBLING_NOT_RUN: &str = "Error: not run — the turn paused for user input before this \
+     call executed. Call it again if it is still needed.";
+
+impl Engine {
+    pub(super) async fn handle_resume_paused_input(
+        &mut self,
+        tool_id: String,
+        outcome: Result<UserInputResponse, String>,
+        mode: AppMode,
+    ) {
+        let Some(blocks) = paused_input_results(&self.session.messages, &tool_id, outcome) else {
+            let _ = self
+                .tx_event
+                .send(Event::status(
+                    "The paused question no longer matches this session; send a new message instead."
+                        .to_string(),
+                ))
+                .await;
+            return;
+        };
+        self.handle_send_message(
+            TurnInput::ToolResults(blocks),
+            mode,
+            self.session.model.clone(),
+            self.config.goal_objective.clone(),
+            self.session.reasoning_effort.clone(),
+            self.session.reasoning_effort_auto,
+            self.session.auto_model,
+            self.session.allow_shell,
+            self.session.trust_mode,
+            self.session.auto_approve,
+            self.session.approval_mode,
+            self.config.translation_enabled,
+        )
+        .await;
+    }
+}
+
+/// Tool results answering every call of the latest assistant message: the
+/// user's answer for `tool_id`, a "not run" error for the rest. `None` when
+/// that message does not contain `tool_id` or already has results.
+fn paused_input_results(
+    messages: &[Message],
+    tool_id: &str,
+    outcome: Result<UserInputResponse, String>,
+) -> Option<Vec<ContentBlock>> {
+    let index = crate::session_manager::paused_call_index(messages, tool_id)?;
+    if index + 1 != messages.len() {
+        return None;
+    }
+    let (answer, answer_is_error) = match outcome {
+        Ok(response) => match ToolResult::json(&response) {
+            Ok(result) => (result.content, None),
+            Err(err) => (format!("Error: {err}"), Some(true)),
+        },
+        Err(reason) => (format!("Error: {reason}"), Some(true)),
+    };
+    let mut answer = Some((answer, answer_is_error));
+    let blocks = messages[index]
+        .content
+        .iter()
+        .filter_map(|block| match block {
+            ContentBlock::ToolUse { id, .. } => {
+                let (content, is_error) = if id == tool_id {
+                    answer.take()?
+                } else {
+                    (SIBLING_NOT_RUN.to_string(), Some(true))
+                };
+                Some(ContentBlock::ToolResult {
+                    tool_use_id: id.clone(),
+                    content,
+                    is_error,
+                    content_blocks: None,
+                })
+            }
+            _ => None,
+        })
+        .collect();
+    Some(blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::user_input::UserInputAnswer;
+
+    fn tool_use(id: &str, name: &str) -> ContentBlock {
+        ContentBlock::ToolUse {
+            id: id.to_string(),
+            name: name.to_string(),
+            input: json!({}),
+            caller: None,
+        }
+    }
+
+    fn paused_session() -> Vec<Message> {
+        vec![
+            Message {
+                role: "user".to_string(),
+                content: vec![ContentBlock::Text {
+                    text: "Set up the release".to_string(),
+                    cache_control: None,
+                }],
+            },
+            Message {
+                role: "assistant".to_string(),
+                content: vec![
+                    tool_use("call_read", "read_file"),
+                    tool_use("call_ask", "request_user_input"),
+                ],
+            },
+        ]
+    }
+
+    #[test]
+    fn answer_fills_the_paused_call_and_siblings_are_marked_not_run() {
+        let response = UserInputResponse {
+            answers: vec![UserInputAnswer {
+                id: "channel".to_string(),
+                label: "Stable".to_string(),
+                value: "stable".to_string(),
+            }],
+            values: serde_json::Map::new(),
+        };
+        let blocks = paused_input_results(&paused_session(), "call_ask", Ok(response))
+            .expect("paused call found");
+        assert_eq!(blocks.len(), 2);
+        let ContentBlock::ToolResult {
+            tool_use_id,
+            is_error,
+            ..
+        } = &blocks[0]
+        else {
+            panic!("expected tool result");
+        };
+        assert_eq!(tool_use_id, "call_read");
+        assert_eq!(*is_error, Some(true));
+        let ContentBlock::ToolResult {
+            tool_use_id,
+            content,
+            is_error,
+            ..
+        } = &blocks[1]
+        else {
+            panic!("expected tool result");
+        };
+        assert_eq!(tool_use_id, "call_ask");
+        assert!(content.contains("stable"), "{content}");
+        assert_eq!(*is_error, None);
+    }
+
+    #[test]
+    fn stale_or_unknown_calls_are_rejected() {
+        let mut messages = paused_session();
+        assert!(paused_input_results(&messages, "call_other", Err("x".into())).is_none());
+
+        let cancelled =
+            paused_input_results(&messages, "call_ask", Err("User input cancelled".into()))
+                .expect("paused call found");
+        assert!(matches!(
+            &cancelled[1],
+            ContentBlock::ToolResult { content, is_error: Some(true), .. }
+                if content == "Error: User input cancelled"
+        ));
+
+        messages.push(Message {
+            role: "user".to_string(),
+            content: cancelled,
+        });
+        assert!(paused_input_results(&messages, "call_ask", Err("x".into())).is_none());
+    }
+}
@@ -4,7 +4,8 @@
 //! content block kind tracking, streamed tool-use buffers, transparent retry
 //! policy, and scrubbers for text that looks like a forged tool-call wrapper.
 
-use crate::models::ToolCaller;
+use crate::core::events::Event;
+use crate::models::{ToolCaller, Usage};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(super) enum ContentBlockKind {
@@ -151,6 +152,80 @@ pub(crate) fn filter_tool_call_delta(delta: &str, in_tool_call: &mut bool) -> St
     output
 }
 
+/// Streamed output that must accumulate before the header gauge is
+/// refreshed again. Keeps the event rate far below the delta rate.
+const CONTEXT_METER_STEP_TOKENS: u32 = 256;
+
+/// Running size of one in-flight request: the prompt (estimated until the
+/// provider reports usage) plus output streamed so far. Yields
+/// `Event::ContextUsage` updates so the header gauge moves during a turn.
+#[derive(Debug, Clone)]
+pub(super) struct ContextMeter {
+    prompt_tokens: u32,
+    estimated: bool,
+    output_bytes: usize,
+    output_tokens: u32,
+    reported_output_tokens: u32,
+}
+
+impl ContextMeter {
+    pub(super) fn new(estimated_prompt_tokens: usize) -> Self {
+        Self {
+            prompt_tokens: u32::try_from(estimated_prompt_tokens).unwrap_or(u32::MAX),
+            estimated: true,
+            output_bytes: 0,
+            output_tokens: 0,
+            reported_output_tokens: 0,
+        }
+    }
+
+    pub(super) fn event(&self) -> Event {
+        Event::ContextUsage {
+            prompt_tokens: self.prompt_tokens,
+            output_tokens: self.output_tokens,
+            estimated: self.estimated,
+        }
+    }
+
+    /// Count streamed text, reasoning, or tool-argument bytes. Returns an
+    /// update once enough output accumulated since the last one.
+    pub(super) fn observe_output(&mut self, bytes: usize) -> Option<Event> {
+        self.output_bytes = self.output_bytes.saturating_add(bytes);
+        let estimate = u32::try_from(self.output_bytes.div_ceil(3)).unwrap_or(u32::MAX);
+        self.output_tokens = self.output_tokens.max(estimate);
+        self.take_update()
+    }
+
+    /// Adopt provider-reported usage; the first report replaces the prompt
+    /// estimate.
+    pub(super) fn observe_usage(&mut self, usage: &Usage) -> Option<Event> {
+        if usage.input_tokens == 0 {
+            return None;
+        }
+        let changed = self.estimated || self.prompt_tokens != usage.input_tokens;
+        self.prompt_tokens = usage.input_tokens;
+        self.estimated = false;
+        self.output_tokens = self.output_tokens.max(usage.output_tokens);
+        if changed {
+            self.reported_output_tokens = self.output_tokens;
+            return Some(self.event());
+        }
+        self.take_update()
+    }
+
+    fn take_update(&mut self) -> Option<Event> {
+        if self.output_tokens
+            < self
+                .reported_output_tokens
+                .saturating_add(CONTEXT_METER_STEP_TOKENS)
+        {
+            return None;
+        }
+        self.reported_output_tokens = self.output_tokens;
+        Some(self.event())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,4 +241,41 @@ mod tests {
         assert_eq!(stream_chunk_timeout_secs_from_env(Some("90")), 90);
         assert_eq!(stream_chunk_timeout_secs_from_env(Some("99999")), 3600);
     }
+
+    #[test]
+    fn context_meter_throttles_output_and_adopts_reported_prompt() {
+        let mut meter = ContextMeter::new(10_000);
+        assert!(matches!(
+            meter.event(),
+            Event::ContextUsage {
+                prompt_tokens: 10_000,
+                output_tokens: 0,
+                estimated: true
+            }
+        ));
+        assert!(meter.observe_output(300).is_none());
+        assert!(matches!(
+            meter.observe_output(600),
+            Some(Event::ContextUsage {
+                output_tokens: 300,
+                ..
+            })
+        ));
+
+        let usage = Usage {
+            input_tokens: 9_200,
+            output_tokens: 120,
+            ..Usage::default()
+        };
+        assert!(matches!(
+            meter.observe_usage(&usage),
+            Some(Event::ContextUsage {
+                prompt_tokens: 9_200,
+                output_tokens: 300,
+                estimated: false
+            })
+        ));
+        assert!(meter.observe_usage(&usage).is_none());
+        assert!(meter.observe_usage(&Usage::default()).is_none());
+    }
 }
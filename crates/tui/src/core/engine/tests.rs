@@ -179,6 +179,72 @@ fn engine_handle_cancel_tracks_latest_turn_token() {
     assert!(!stale_token.is_cancelled());
 }
 
+#[test]
+fn soft_cancel_applies_only_while_tools_run() {
+    let (engine, handle) = Engine::new(EngineConfig::default(), &Config::default());
+
+    // Nothing to finish: soft cancel escalates to a hard cancel.
+    assert!(!handle.soft_cancel());
+    assert!(handle.is_cancelled());
+    assert!(!engine.soft_stop.is_requested());
+
+    let (mut engine, handle) = Engine::new(EngineConfig::default(), &Config::default());
+    engine.soft_stop.set_tools_running(true);
+    assert!(handle.soft_cancel());
+    assert!(engine.soft_stop.is_requested());
+    assert!(!handle.is_cancelled());
+
+    engine.reset_cancel_token();
+    assert!(!engine.soft_stop.is_requested());
+}
+
+#[test]
+fn soft_stop_skips_unstarted_plans_but_keeps_resolved_ones() {
+    let mut pending = make_plan(true, true, false, false);
+    mark_skipped_by_soft_stop(&mut pending);
+    assert!(
+        pending
+            .blocked_error
+            .as_ref()
+            .is_some_and(|err| err.to_string().contains("Not run"))
+    );
+
+    let mut cached = make_plan(true, true, false, false);
+    cached.guard_result = Some(ToolResult::success("cached"));
+    mark_skipped_by_soft_stop(&mut cached);
+    assert!(cached.blocked_error.is_none());
+}
+
+#[test]
+fn only_plans_that_reach_the_approval_handshake_are_announced() {
+    assert!(plan_awaits_approval(&make_plan(false, false, true, false)));
+    assert!(!plan_awaits_approval(&make_plan(true, true, false, false)));
+
+    let mut guarded = make_plan(false, false, true, false);
+    guarded.guard_result = Some(ToolResult::error("Blocked: repeated call"));
+    assert!(!plan_awaits_approval(&guarded));
+
+    let mut inline = make_plan(false, false, true, false);
+    inline.name = CODE_EXECUTION_TOOL_NAME.to_string();
+    assert!(!plan_awaits_approval(&inline));
+}
+
+#[tokio::test]
+async fn approvals_answered_out_of_order_reach_their_calls() {
+    let (mut engine, handle) = Engine::new(EngineConfig::default(), &Config::default());
+    handle.approve_tool_call("tool-1").await.expect("send");
+    handle.deny_tool_call("tool-0").await.expect("send");
+
+    assert!(matches!(
+        engine.await_tool_approval("tool-0").await,
+        Ok(ApprovalResult::Denied)
+    ));
+    assert!(matches!(
+        engine.await_tool_approval("tool-1").await,
+        Ok(ApprovalResult::Approved)
+    ));
+}
+
 #[test]
 fn engine_initial_prompt_includes_configured_goal() {
     let config = EngineConfig {
@@ -384,6 +450,19 @@ fn tool_exec_outcome_tracks_duration() {
     assert!(outcome.started_at.elapsed().as_nanos() > 0);
 }
 
+#[test]
+fn mcp_subscribe_resource_goes_through_approval() {
+    assert!(mcp_tool_is_read_only("mcp_read_resource"));
+    assert!(mcp_tool_is_read_only("mcp_get_prompt"));
+    // Subscribing creates server-side state and a notification stream.
+    assert!(!mcp_tool_is_read_only("mcp_subscribe_resource"));
+    assert!(!mcp_tool_is_parallel_safe("mcp_subscribe_resource"));
+    assert_eq!(
+        mcp_tool_approval_description("mcp_subscribe_resource"),
+        "MCP tool 'mcp_subscribe_resource' may have side effects"
+    );
+}
+
 #[test]
 fn yolo_mode_keeps_tools_preloaded() {
     assert!(!should_default_defer_tool("exec_shell", AppMode::Yolo));
@@ -436,6 +515,24 @@ fn model_tool_catalog_applies_native_and_mcp_deferral() {
     assert_eq!(defer_loading("mcp_server_write"), Some(true));
 }
 
+#[test]
+fn tool_catalog_summary_counts_loaded_and_deferred_schemas() {
+    let catalog = build_model_tool_catalog(
+        vec![api_tool("read_file"), api_tool("project_map")],
+        vec![api_tool("mcp_server_write")],
+        AppMode::Plan,
+    );
+    let active = initial_active_tools(&catalog);
+    let summary = summarize_tool_catalog(&catalog, &active, AppMode::Plan, true);
+
+    assert_eq!(summary.mode, AppMode::Plan);
+    assert!(summary.read_only);
+    assert_eq!(summary.loaded, 1);
+    assert_eq!(summary.deferred, 2);
+    // "read_file" + "Test tool read_file" + {"type":"object"}
+    assert_eq!(summary.schema_tokens, (9 + 19 + 17_usize).div_ceil(4));
+}
+
 #[test]
 fn deferred_edit_file_first_use_hydrates_schema_without_execution() {
     let mut edit = api_tool("edit_file");
@@ -705,6 +802,7 @@ fn turn_tool_registry_builder_keeps_plan_mode_read_only_for_files() {
     assert!(!registry.contains("task_gate_run"));
     assert!(!registry.contains("rlm"));
     assert!(!registry.contains("fim_edit"));
+    assert!(!registry.contains("edit_with_fim"));
     assert!(registry.contains("update_plan"));
     assert!(registry.contains("task_list"));
     assert!(registry.contains("task_read"));
@@ -739,7 +837,7 @@ fn turn_tool_registry_builder_keeps_plan_mode_read_only_for_files() {
 }
 
 #[test]
-fn parent_turn_registry_includes_recall_archive_for_investigative_modes() {
+fn parent_turn_registry_includes_recall_tools_for_investigative_modes() {
     let (engine, _handle) = Engine::new(EngineConfig::default(), &Config::default());
 
     for mode in [AppMode::Plan, AppMode::Agent, AppMode::Yolo] {
@@ -755,6 +853,10 @@ fn parent_turn_registry_includes_recall_archive_for_investigative_modes() {
             registry.contains("recall_archive"),
             "parent {mode:?} registry should expose recall_archive"
         );
+        assert!(
+            registry.contains("recall_history"),
+            "parent {mode:?} registry should expose recall_history"
+        );
     }
 }
 
@@ -872,6 +974,48 @@ fn sandbox_policy_for_mode_returns_correct_policy_per_mode() {
     ));
 }
 
+#[test]
+fn trust_level_scopes_sandbox_and_tool_catalog() {
+    use super::tool_setup::sandbox_policy_for_trust;
+    use crate::sandbox::SandboxPolicy;
+    use crate::trust_level::TrustLevel;
+
+    let workspace = PathBuf::from("/tmp/example-workspace");
+    assert!(matches!(
+        sandbox_policy_for_trust(AppMode::Agent, TrustLevel::ReadOnly, &workspace),
+        SandboxPolicy::ReadOnly
+    ));
+    assert!(matches!(
+        sandbox_policy_for_trust(AppMode::Agent, TrustLevel::Standard, &workspace),
+        SandboxPolicy::WorkspaceWrite { .. }
+    ));
+    assert!(matches!(
+        sandbox_policy_for_trust(AppMode::Agent, TrustLevel::Full, &workspace),
+        SandboxPolicy::DangerFullAccess
+    ));
+    assert!(matches!(
+        sandbox_policy_for_trust(AppMode::Plan, TrustLevel::Full, &workspace),
+        SandboxPolicy::ReadOnly
+    ));
+
+    let config = EngineConfig {
+        allow_shell: true,
+        trust_level: TrustLevel::Untrusted,
+        ..EngineConfig::default()
+    };
+    let (engine, _handle) = Engine::new(config, &Config::default());
+    let registry = engine
+        .build_turn_tool_registry_builder(
+            AppMode::Agent,
+            engine.config.todos.clone(),
+            engine.config.plan_state.clone(),
+        )
+        .build(engine.build_tool_context(AppMode::Agent, false));
+    assert!(registry.contains("read_file"));
+    assert!(!registry.contains("write_file"));
+    assert!(!registry.contains("exec_shell"));
+}
+
 #[tokio::test]
 async fn session_update_preserves_reasoning_tool_only_turn() {
     let (mut engine, handle) = Engine::new(EngineConfig::default(), &Config::default());
@@ -1078,6 +1222,31 @@ fn working_set_reaches_model_as_turn_metadata() {
     assert!(text.contains("src/lib.rs"));
 }
 
+#[test]
+fn files_changed_since_read_reach_model_as_turn_metadata() {
+    let tmp = tempdir().expect("tempdir");
+    fs::create_dir_all(tmp.path().join("src")).expect("mkdir");
+    fs::write(tmp.path().join("src/lib.rs"), "pub fn sample() {}").expect("write");
+
+    let config = EngineConfig {
+        workspace: tmp.path().to_path_buf(),
+        ..Default::default()
+    };
+    let (mut engine, _handle) = Engine::new(config, &Config::default());
+    engine.stale_reads.record_read(tmp.path(), "src/lib.rs");
+    fs::write(tmp.path().join("src/lib.rs"), "pub fn renamed() {}").expect("write");
+
+    let stale = engine.stale_reads.begin_turn();
+    let note = crate::stale_reads::stale_note(&stale, tmp.path());
+    let user_msg =
+        engine.user_text_message_with_stale_note("continue".to_string(), note.as_deref());
+    let ContentBlock::Text { text, .. } = &user_msg.content[0] else {
+        panic!("expected text metadata block");
+    };
+    assert!(text.contains("1 previously read file changed on disk since you read it: src/lib.rs."));
+    assert!(text.ends_with("</turn_meta>"));
+}
+
 #[test]
 fn turn_metadata_includes_current_local_date_without_working_set() {
     let tmp = tempdir().expect("tempdir");
@@ -2037,6 +2206,40 @@ fn final_tool_input_repairs_unparseable_buffer() {
     assert_eq!(final_tool_input(&state), json!({}));
 }
 
+#[test]
+fn shell_alias_calls_are_announced_and_planned_as_exec_shell() {
+    use crate::command_aliases::{AliasAction, CommandAlias};
+
+    let tmp = tempdir().unwrap();
+    let registry = ToolRegistryBuilder::new()
+        .with_command_alias_tools(
+            vec![CommandAlias {
+                name: "test".to_string(),
+                action: AliasAction::Shell("cargo test".to_string()),
+                description: None,
+            }],
+            true,
+        )
+        .build(crate::tools::spec::ToolContext::new(
+            tmp.path().to_path_buf(),
+        ));
+
+    let mut state = tool_state(json!({}), r#"{"arguments": "; rm -rf x"}"#);
+    state.name = "test".into();
+    apply_tool_delegate(Some(&registry), &mut state);
+    assert_eq!(state.name, "exec_shell");
+    assert_eq!(
+        final_tool_input(&state),
+        json!({"command": "cargo test ';' rm -rf x"})
+    );
+
+    // Tools without a delegate are left alone.
+    let mut state = tool_state(json!({"command": "ls"}), "");
+    apply_tool_delegate(Some(&registry), &mut state);
+    assert_eq!(state.name, "exec_shell");
+    assert_eq!(state.input, json!({"command": "ls"}));
+}
+
 // === #103 transparent stream-retry policy =====================================
 
 #[test]
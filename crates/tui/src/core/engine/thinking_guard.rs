@@ -0,0 +1,176 @@
+//! Per-turn limits on streamed thinking: a token budget and a detector for
+//! degenerate reasoning that keeps repeating itself.
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Same heuristic as the large-output router: ~4 characters per token.
+const CHARS_PER_TOKEN: usize = 4;
+/// Sentences shorter than this ("Okay.", "Wait.") repeat legitimately.
+const MIN_SENTENCE_CHARS: usize = 24;
+/// How often one sentence may appear in a single thinking block before the
+/// block counts as degenerate.
+const REPEAT_ABORT_THRESHOLD: u32 = 6;
+/// Flush unterminated text as a sentence once it grows this long, so a loop
+/// without punctuation is still caught.
+const MAX_PENDING_CHARS: usize = 400;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum ThinkingVerdict {
+    Continue,
+    OverBudget { budget: u32 },
+    Repeating,
+}
+
+impl ThinkingVerdict {
+    pub(super) fn status_message(&self) -> Option<String> {
+        match self {
+            Self::Continue => None,
+            Self::OverBudget { budget } => Some(format!(
+                "Thinking budget of ~{budget} tokens used; finishing the turn with thinking off"
+            )),
+            Self::Repeating => Some(
+                "Thinking started repeating itself; finishing the turn with thinking off"
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct ThinkingGuard {
+    budget: Option<u32>,
+    abort_repetition: bool,
+    chars: usize,
+    pending: String,
+    sentence_counts: HashMap<u64, u32>,
+}
+
+impl ThinkingGuard {
+    pub(super) fn new(budget: Option<u32>, abort_repetition: bool) -> Self {
+        Self {
+            budget: budget.filter(|budget| *budget > 0),
+            abort_repetition,
+            chars: 0,
+            pending: String::new(),
+            sentence_counts: HashMap::new(),
+        }
+    }
+
+    /// Forget the sentences seen so far. The token count keeps running: the
+    /// budget covers the whole turn, repetition only a single thinking block.
+    pub(super) fn begin_step(&mut self) {
+        self.pending.clear();
+        self.sentence_counts.clear();
+    }
+
+    /// Estimated thinking tokens streamed this turn.
+    fn tokens(&self) -> usize {
+        self.chars.div_ceil(CHARS_PER_TOKEN)
+    }
+
+    pub(super) fn observe(&mut self, delta: &str) -> ThinkingVerdict {
+        self.chars = self.chars.saturating_add(delta.chars().count());
+        if let Some(budget) = self.budget
+            && self.tokens() > budget as usize
+        {
+            return ThinkingVerdict::OverBudget { budget };
+        }
+        if self.abort_repetition && self.record_sentences(delta) {
+            return ThinkingVerdict::Repeating;
+        }
+        ThinkingVerdict::Continue
+    }
+
+    /// Returns true once any sentence reaches the repeat threshold.
+    fn record_sentences(&mut self, delta: &str) -> bool {
+        let mut repeating = false;
+        for ch in delta.chars() {
+            self.pending.push(ch);
+            if is_sentence_end(ch) || self.pending.len() >= MAX_PENDING_CHARS {
+                let sentence = std::mem::take(&mut self.pending);
+                repeating |= self.record_sentence(&sentence);
+            }
+        }
+        repeating
+    }
+
+    fn record_sentence(&mut self, sentence: &str) -> bool {
+        let normalized = sentence
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        if normalized.chars().count() < MIN_SENTENCE_CHARS {
+            return false;
+        }
+        let mut hasher = DefaultHasher::new();
+        normalized.hash(&mut hasher);
+        let count = self.sentence_counts.entry(hasher.finish()).or_insert(0);
+        *count = count.saturating_add(1);
+        *count >= REPEAT_ABORT_THRESHOLD
+    }
+}
+
+fn is_sentence_end(ch: char) -> bool {
+    matches!(
+        ch,
+        '.' | '!' | '?' | '\n' | '\u{3002}' | '\u{ff01}' | '\u{ff1f}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_counts_across_steps_and_zero_means_unlimited() {
+        let mut guard = ThinkingGuard::new(Some(10), false);
+        assert_eq!(guard.observe(&"a".repeat(36)), ThinkingVerdict::Continue);
+        guard.begin_step();
+        assert_eq!(
+            guard.observe("more reasoning"),
+            ThinkingVerdict::OverBudget { budget: 10 }
+        );
+
+        let mut unlimited = ThinkingGuard::new(Some(0), false);
+        assert_eq!(
+            unlimited.observe(&"a".repeat(10_000)),
+            ThinkingVerdict::Continue
+        );
+    }
+
+    #[test]
+    fn repeated_sentences_abort_but_varied_reasoning_does_not() {
+        let mut guard = ThinkingGuard::new(None, true);
+        let mut verdict = ThinkingVerdict::Continue;
+        for _ in 0..REPEAT_ABORT_THRESHOLD {
+            // Split mid-sentence the way streamed deltas arrive.
+            guard.observe("Let me re-check the config ");
+            verdict = guard.observe("loader once more. ");
+        }
+        assert_eq!(verdict, ThinkingVerdict::Repeating);
+
+        let mut varied = ThinkingGuard::new(None, true);
+        for step in 0..20 {
+            assert_eq!(
+                varied.observe(&format!(
+                    "Step {step}: inspect the next call site carefully. "
+                )),
+                ThinkingVerdict::Continue
+            );
+        }
+        for _ in 0..20 {
+            assert_eq!(varied.observe("Okay. Wait. "), ThinkingVerdict::Continue);
+        }
+
+        let mut disabled = ThinkingGuard::new(None, false);
+        for _ in 0..20 {
+            assert_eq!(
+                disabled.observe("Let me re-check the config loader once more. "),
+                ThinkingVerdict::Continue
+            );
+        }
+    }
+}
@@ -11,6 +11,7 @@ use std::time::Duration;
 
 use serde_json::{Value, json};
 
+use crate::core::events::ToolCatalogSummary;
 use crate::models::Tool;
 use crate::tools::spec::{ToolError, ToolResult, required_str};
 use crate::tui::app::AppMode;
@@ -64,7 +65,9 @@ pub(super) fn should_default_defer_tool(name: &str, mode: AppMode) -> bool {
             | "rlm_configure"
             | "rlm_close"
             | "handle_read"
+            | "read_result_page"
             | "recall_archive"
+            | "recall_history"
             | "notify"
             | MULTI_TOOL_PARALLEL_NAME
             | "update_plan"
@@ -125,6 +128,31 @@ pub(super) fn build_model_tool_catalog(
     native_tools
 }
 
+/// Summarize the catalog a turn starts with: what is loaded up front, what
+/// waits behind tool search, and roughly what the loaded schemas cost.
+pub(super) fn summarize_tool_catalog(
+    catalog: &[Tool],
+    active: &HashSet<String>,
+    mode: AppMode,
+    read_only: bool,
+) -> ToolCatalogSummary {
+    let mut loaded = 0usize;
+    let mut schema_chars = 0usize;
+    for tool in catalog.iter().filter(|tool| active.contains(&tool.name)) {
+        loaded += 1;
+        schema_chars += tool.name.len()
+            + tool.description.chars().count()
+            + tool.input_schema.to_string().chars().count();
+    }
+    ToolCatalogSummary {
+        mode,
+        read_only,
+        loaded,
+        deferred: catalog.len() - loaded,
+        schema_tokens: schema_chars.div_ceil(4),
+    }
+}
+
 pub(super) fn ensure_advanced_tooling(catalog: &mut Vec<Tool>, mode: AppMode) {
     // code_execution depends on a locally-installed Python interpreter
     // (python3 / python / py -3). Before v0.8.31, the tool was always
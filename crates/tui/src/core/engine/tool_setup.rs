@@ -6,6 +6,7 @@ use std::path::Path;
 
 use super::*;
 use crate::sandbox::SandboxPolicy;
+use crate::trust_level::TrustLevel;
 
 /// Pick the sandbox policy that gates shell commands for a given UI mode.
 ///
@@ -32,14 +33,38 @@ pub(crate) fn sandbox_policy_for_mode(mode: AppMode, workspace: &Path) -> Sandbo
     }
 }
 
+/// Narrow or widen the mode's sandbox policy by the workspace trust level.
+///
+/// `untrusted` and `read-only` workspaces always get `ReadOnly`; `full`
+/// drops the sandbox everywhere except Plan mode; `standard` keeps the
+/// mode's policy.
+pub(crate) fn sandbox_policy_for_trust(
+    mode: AppMode,
+    trust: TrustLevel,
+    workspace: &Path,
+) -> SandboxPolicy {
+    match trust {
+        TrustLevel::Untrusted | TrustLevel::ReadOnly => SandboxPolicy::ReadOnly,
+        TrustLevel::Full if mode != AppMode::Plan => SandboxPolicy::DangerFullAccess,
+        TrustLevel::Standard | TrustLevel::Full => sandbox_policy_for_mode(mode, workspace),
+    }
+}
+
 impl Engine {
+    /// Plan mode and `untrusted` / `read-only` workspaces get the read-only
+    /// catalog; Agent and YOLO get the full one.
+    pub(super) fn tool_catalog_is_read_only(&self, mode: AppMode) -> bool {
+        mode == AppMode::Plan || self.config.trust_level.restricts_tools()
+    }
+
     pub(super) fn build_turn_tool_registry_builder(
         &self,
         mode: AppMode,
         todo_list: SharedTodoList,
         plan_state: SharedPlanState,
     ) -> ToolRegistryBuilder {
-        let mut builder = if mode == AppMode::Plan {
+        let read_only = self.tool_catalog_is_read_only(mode);
+        let mut builder = if read_only {
             ToolRegistryBuilder::new()
                 .with_read_only_file_tools()
                 .with_search_tools()
@@ -63,26 +88,27 @@ impl Engine {
             .with_review_tool(self.deepseek_client.clone(), self.session.model.clone())
             .with_user_input_tool()
             .with_parallel_tool()
-            .with_recall_archive_tool();
+            .with_recall_archive_tool()
+            .with_recall_history_tool();
 
-        if mode != AppMode::Plan {
+        if !read_only {
             builder = builder
                 .with_rlm_tool(self.deepseek_client.clone(), self.session.model.clone())
                 .with_fim_tool(self.deepseek_client.clone(), self.session.model.clone());
         }
 
-        if self.config.features.enabled(Feature::ApplyPatch) && mode != AppMode::Plan {
+        if self.config.features.enabled(Feature::ApplyPatch) && !read_only {
             builder = builder.with_patch_tools();
         }
         if self.config.features.enabled(Feature::WebSearch) {
             builder = builder.with_web_tools();
         }
-        // Plan mode is strictly read-only: do not expose shell execution at
-        // all, even if the session would otherwise allow it.
-        if mode != AppMode::Plan
+        // Read-only catalogs never expose shell execution, even if the
+        // session would otherwise allow it.
+        let shell_allowed = !read_only
             && self.config.features.enabled(Feature::ShellTool)
-            && self.session.allow_shell
-        {
+            && self.session.allow_shell;
+        if shell_allowed {
             builder = builder.with_shell_tools();
         }
 
@@ -100,12 +126,22 @@ impl Engine {
             builder = builder.with_vision_tools(vision_config.clone());
         }
 
+        // `capture_terminal` reads scrollback the user may not expect the
+        // model to see, so it stays out of the catalog unless opted in.
+        if self.config.features.enabled(Feature::TerminalCapture) {
+            builder = builder.with_terminal_capture_tool();
+        }
+
         // Register the `notify` tool unconditionally (#1322). It has no
         // side effects beyond a single terminal escape write and respects
         // the user's `[notifications].method` config (including `off`),
         // so there's no failure mode worth gating on.
         builder = builder.with_notify_tool();
 
-        builder
+        // Project aliases go last so they never shadow a built-in tool.
+        builder.with_command_alias_tools(
+            crate::command_aliases::load(&self.session.workspace),
+            shell_allowed,
+        )
     }
 }
@@ -12,6 +12,12 @@ fn loop_guard_block_tool_result(message: String) -> ToolResult {
 }
 
 impl Engine {
+    fn should_auto_disable_thinking(&self) -> bool {
+        self.config.thinking.auto_off.unwrap_or(false)
+            && self.session.reasoning_effort.as_deref() != Some("off")
+            && is_simple_followup_turn(&self.session.messages)
+    }
+
     pub(super) async fn handle_deepseek_turn(
         &mut self,
         turn: &mut TurnContext,
@@ -28,12 +34,57 @@ impl Engine {
         let mut consecutive_tool_error_steps = 0u32;
         let mut turn_error: Option<String> = None;
         let mut context_recovery_attempts = 0u8;
+        // Model used for this turn's requests. Starts as the session model
+        // and moves down `model_fallbacks` when a request fails; the session
+        // model itself is left alone so the next turn tries it again.
+        let mut request_model = self.session.model.clone();
+        // Thinking stays off for the rest of the turn once the budget or
+        // the repetition guard trips, or when `[thinking] auto_off` sees a
+        // simple follow-up.
+        let mut thinking_off = false;
+        if self.should_auto_disable_thinking() {
+            thinking_off = true;
+            if let Some(chat_model) = self
+                .config
+                .thinking
+                .chat_model
+                .as_deref()
+                .map(str::trim)
+                .filter(|model| !model.is_empty())
+            {
+                request_model = chat_model.to_string();
+            }
+            let _ = self
+                .tx_event
+                .send(Event::status(format!(
+                    "Simple follow-up: answering with {request_model}, thinking off"
+                )))
+                .await;
+        }
+        let mut thinking_guard = ThinkingGuard::new(
+            self.config.thinking.budget_tokens,
+            self.config.thinking.abort_repetition.unwrap_or(true),
+        );
+        let mut tried_models = vec![request_model.clone()];
         let mut tool_catalog = tools.unwrap_or_default();
         if !tool_catalog.is_empty() {
             ensure_advanced_tooling(&mut tool_catalog, mode);
         }
         let mut active_tool_names = initial_active_tools(&tool_catalog);
+        let _ = self
+            .tx_event
+            .send(Event::ToolCatalog {
+                summary: summarize_tool_catalog(
+                    &tool_catalog,
+                    &active_tool_names,
+                    mode,
+                    self.tool_catalog_is_read_only(mode),
+                ),
+            })
+            .await;
         let mut loop_guard = LoopGuard::default();
+        self.early_approvals.clear();
+        self.tool_result_cache.begin_turn();
 
         // Transparent stream-retry counter: when the chunked-transfer
         // connection dies mid-stream and we got nothing useful out of it
@@ -50,6 +101,9 @@ impl Engine {
                 let _ = self.tx_event.send(Event::status("Request cancelled")).await;
                 return (TurnOutcomeStatus::Interrupted, None);
             }
+            if self.soft_stop.is_requested() {
+                return self.finish_soft_stopped_turn().await;
+            }
 
             while let Ok(steer) = self.rx_steer.try_recv() {
                 let steer = steer.trim().to_string();
@@ -123,7 +177,12 @@ impl Engine {
                         if !result.messages.is_empty() || self.session.messages.is_empty() {
                             let auto_messages_after = result.messages.len();
                             self.session.messages = result.messages;
-                            self.merge_compaction_summary(result.summary_prompt);
+                            self.merge_compaction_digest(
+                                &client,
+                                result.summary_prompt,
+                                &result.removed_messages,
+                            )
+                            .await;
                             self.emit_session_updated().await;
                             let removed = auto_messages_before.saturating_sub(auto_messages_after);
                             let status = if result.retries_used > 0 {
@@ -173,8 +232,7 @@ impl Engine {
                 continue;
             }
 
-            if let Some(input_budget) =
-                context_input_budget(&self.session.model, TURN_MAX_OUTPUT_TOKENS)
+            if let Some(input_budget) = context_input_budget(&request_model, TURN_MAX_OUTPUT_TOKENS)
             {
                 let estimated_input = self.estimated_input_tokens();
                 if estimated_input > input_budget {
@@ -234,10 +292,14 @@ impl Engine {
             }
 
             // Resolve `auto` reasoning_effort to a concrete tier (#663).
-            let effective_reasoning_effort = resolve_auto_effort(
-                self.session.reasoning_effort.as_deref(),
-                &self.session.messages,
-            );
+            let effective_reasoning_effort = if thinking_off {
+                Some("off".to_string())
+            } else {
+                resolve_auto_effort(
+                    self.session.reasoning_effort.as_deref(),
+                    &self.session.messages,
+                )
+            };
 
             // Check prefix-cache stability before building the request.
             // This detects system-prompt or tool-set drift that would
@@ -282,10 +344,14 @@ impl Engine {
                 }
             }
 
+            let preset = crate::config::model_preset(&self.config.model_presets, &request_model);
             let request = MessageRequest {
-                model: self.session.model.clone(),
+                model: request_model.clone(),
                 messages: self.messages_with_turn_metadata(),
-                max_tokens: effective_max_output_tokens(&self.session.model),
+                max_tokens: preset
+                    .and_then(|preset| preset.max_tokens)
+                    .filter(|max_tokens| *max_tokens > 0)
+                    .unwrap_or_else(|| effective_max_output_tokens(&request_model)),
                 system: self.session.system_prompt.clone(),
                 tools: active_tools.clone(),
                 tool_choice: if active_tools.is_some() {
@@ -301,8 +367,8 @@ impl Engine {
                 thinking: None,
                 reasoning_effort: effective_reasoning_effort,
                 stream: Some(true),
-                temperature: None,
-                top_p: None,
+                temperature: preset.and_then(|preset| preset.temperature),
+                top_p: preset.and_then(|preset| preset.top_p),
             };
 
             // Stream the response. Keep the request around (cloned into the
@@ -337,6 +403,27 @@ impl Engine {
                         context_recovery_attempts = context_recovery_attempts.saturating_add(1);
                         continue;
                     }
+                    if let Some(reason) = fallback_reason(&message)
+                        && let Some(next) =
+                            next_fallback_model(&self.config.model_fallbacks, &tried_models)
+                    {
+                        let from = std::mem::replace(&mut request_model, next.clone());
+                        tried_models.push(next.clone());
+                        tracing::warn!(
+                            "Model {from} failed ({reason}); falling back to {next}: {message}"
+                        );
+                        let _ = self
+                            .tx_event
+                            .send(Event::ModelFallback {
+                                from,
+                                to: next,
+                                reason: reason.to_string(),
+                                usage: turn.usage.clone(),
+                            })
+                            .await;
+                        context_recovery_attempts = 0;
+                        continue;
+                    }
                     turn_error = Some(message.clone());
                     let _ = self
                         .tx_event
@@ -391,6 +478,10 @@ impl Engine {
             // budget restarts with the fresh stream.
             let mut stream_start = Instant::now();
             let mut stream_content_bytes: usize = 0;
+            let mut context_meter = ContextMeter::new(self.estimated_input_tokens());
+            let _ = self.tx_event.send(context_meter.event()).await;
+            thinking_guard.begin_step();
+            let mut thinking_abort: Option<(usize, ThinkingVerdict)> = None;
             let chunk_timeout_secs = stream_chunk_timeout_secs();
             let chunk_timeout = Duration::from_secs(chunk_timeout_secs);
             let max_duration = Duration::from_secs(STREAM_MAX_DURATION_SECS);
@@ -539,6 +630,9 @@ impl Engine {
                 match event {
                     StreamEvent::MessageStart { message } => {
                         usage = message.usage;
+                        if let Some(update) = context_meter.observe_usage(&usage) {
+                            let _ = self.tx_event.send(update).await;
+                        }
                     }
                     StreamEvent::ContentBlockStart {
                         index,
@@ -621,6 +715,9 @@ impl Engine {
                     StreamEvent::ContentBlockDelta { index, delta } => match delta {
                         Delta::TextDelta { text } => {
                             stream_content_bytes = stream_content_bytes.saturating_add(text.len());
+                            if let Some(update) = context_meter.observe_output(text.len()) {
+                                let _ = self.tx_event.send(update).await;
+                            }
                             current_text_raw.push_str(&text);
                             let filtered = filter_tool_call_delta(&text, &mut in_tool_call_block);
                             if !fake_wrapper_notice_emitted
@@ -645,7 +742,15 @@ impl Engine {
                         Delta::ThinkingDelta { thinking } => {
                             stream_content_bytes =
                                 stream_content_bytes.saturating_add(thinking.len());
+                            if let Some(update) = context_meter.observe_output(thinking.len()) {
+                                let _ = self.tx_event.send(update).await;
+                            }
                             current_thinking.push_str(&thinking);
+                            let verdict = if thinking_off {
+                                ThinkingVerdict::Continue
+                            } else {
+                                thinking_guard.observe(&thinking)
+                            };
                             if !thinking.is_empty() {
                                 let _ = self
                                     .tx_event
@@ -655,8 +760,15 @@ impl Engine {
                                     })
                                     .await;
                             }
+                            if verdict != ThinkingVerdict::Continue {
+                                thinking_abort = Some((index as usize, verdict));
+                                break;
+                            }
                         }
                         Delta::InputJsonDelta { partial_json } => {
+                            if let Some(update) = context_meter.observe_output(partial_json.len()) {
+                                let _ = self.tx_event.send(update).await;
+                            }
                             if let Some(&tool_idx) = current_tool_indices.get(&index)
                                 && let Some(tool_state) = tool_uses.get_mut(tool_idx)
                             {
@@ -733,6 +845,8 @@ impl Engine {
                                 ));
                             }
 
+                            apply_tool_delegate(tool_registry, tool_state);
+
                             // Now that the input is finalized, announce the
                             // tool call to the UI. Deferring to here is what
                             // keeps the cell from rendering `<command>` /
@@ -753,6 +867,9 @@ impl Engine {
                     } => {
                         if let Some(u) = delta_usage {
                             usage = u;
+                            if let Some(update) = context_meter.observe_usage(&usage) {
+                                let _ = self.tx_event.send(update).await;
+                            }
                         }
                     }
                     StreamEvent::MessageStop | StreamEvent::Ping => {}
@@ -772,6 +889,23 @@ impl Engine {
             // nothing actionable — if any tool call landed or text was
             // streamed, ship the partial state to the rest of the turn
             // pipeline so we don't double-bill the user by re-running it.
+            // The thinking guard stopped this stream. Drop the partial step
+            // and re-issue it with thinking off for the rest of the turn.
+            if let Some((index, verdict)) = thinking_abort.take() {
+                turn.add_usage(&usage);
+                let _ = self.tx_event.send(Event::ThinkingComplete { index }).await;
+                if let Some(message) = verdict.status_message() {
+                    crate::logging::warn(&message);
+                    let _ = self.tx_event.send(Event::status(message)).await;
+                }
+                for steer in pending_steers.drain(..) {
+                    self.add_session_message(self.user_text_message_with_turn_metadata(steer))
+                        .await;
+                }
+                thinking_off = true;
+                continue;
+            }
+
             let stream_died_with_nothing = stream_errors > 0
                 && tool_uses.is_empty()
                 && current_text_visible.trim().is_empty()
@@ -834,21 +968,23 @@ impl Engine {
                 let parsed = tool_parser::parse_tool_calls(&current_text_raw);
                 final_text = parsed.clean_text;
                 for call in parsed.tool_calls {
-                    let _ = self
-                        .tx_event
-                        .send(Event::ToolCallStarted {
-                            id: call.id.clone(),
-                            name: call.name.clone(),
-                            input: call.args.clone(),
-                        })
-                        .await;
-                    tool_uses.push(ToolUseState {
+                    let mut tool_state = ToolUseState {
                         id: call.id,
                         name: call.name,
                         input: call.args,
                         caller: None,
                         input_buffer: String::new(),
-                    });
+                    };
+                    apply_tool_delegate(tool_registry, &mut tool_state);
+                    let _ = self
+                        .tx_event
+                        .send(Event::ToolCallStarted {
+                            id: tool_state.id.clone(),
+                            name: tool_state.name.clone(),
+                            input: tool_state.input.clone(),
+                        })
+                        .await;
+                    tool_uses.push(tool_state);
                 }
             }
 
@@ -1150,6 +1286,11 @@ impl Engine {
             let mut deferred_tools_hydrated_this_batch: std::collections::HashSet<String> =
                 std::collections::HashSet::new();
             let mut plans: Vec<ToolExecutionPlan> = Vec::with_capacity(tool_uses.len());
+            // Cache keys for read-only misses (taken just before their batch
+            // runs, stored once they succeed) and the ids of tools that may
+            // change the workspace.
+            let mut pending_cache_keys = std::collections::HashMap::new();
+            let mut mutating_tool_ids = std::collections::HashSet::new();
             for (index, tool) in tool_uses.iter_mut().enumerate() {
                 let tool_id = tool.id.clone();
                 let mut tool_name = tool.name.clone();
@@ -1182,6 +1323,7 @@ impl Engine {
                             | "exec_shell_interact"
                             | "exec_wait"
                             | "exec_interact"
+                            | "shell_open"
                             | CODE_EXECUTION_TOOL_NAME
                             | JS_EXECUTION_TOOL_NAME
                     )
@@ -1299,6 +1441,10 @@ impl Engine {
                     guard_result = Some(loop_guard_block_tool_result(message));
                 }
 
+                if !read_only {
+                    mutating_tool_ids.insert(tool_id.clone());
+                }
+
                 plans.push(ToolExecutionPlan {
                     index,
                     id: tool_id,
@@ -1346,14 +1492,60 @@ impl Engine {
             let mut outcomes: Vec<Option<ToolExecOutcome>> = Vec::with_capacity(plan_count);
             outcomes.resize_with(plan_count, || None);
 
+            // Announce every approval this step will wait on before awaiting
+            // the first, so the UI can queue them together instead of
+            // discovering them one at a time.
+            let mut announced_approvals = std::collections::HashSet::new();
+            if !self.soft_stop.is_requested() {
+                for plan in batches.iter().flat_map(ToolExecutionBatch::plans) {
+                    if plan_awaits_approval(plan) {
+                        self.announce_tool_approval(plan).await;
+                        announced_approvals.insert(plan.id.clone());
+                    }
+                }
+            }
+
+            self.soft_stop.set_tools_running(true);
             for batch in batches {
-                let (parallel_allowed, plans) = match batch {
+                let (parallel_allowed, mut plans) = match batch {
                     ToolExecutionBatch::Parallel(plans) => (true, plans),
                     ToolExecutionBatch::Serial(plan) => (false, vec![*plan]),
                 };
+                // Soft cancel: batches that have not started yet are
+                // recorded as skipped so every tool call still gets a
+                // result in the transcript.
+                if self.soft_stop.is_requested() {
+                    for plan in &mut plans {
+                        mark_skipped_by_soft_stop(plan);
+                    }
+                }
+                // Key cached reads against the workspace as the earlier
+                // batches left it, not as it was when the step was planned.
+                for plan in &mut plans {
+                    if plan.read_only
+                        && plan.guard_result.is_none()
+                        && plan.blocked_error.is_none()
+                        && let Some(key) = self.tool_result_cache.key_for(
+                            &plan.name,
+                            &plan.input,
+                            &self.session.workspace,
+                        )
+                    {
+                        match self.tool_result_cache.lookup(&key) {
+                            Some(cached) => plan.guard_result = Some(cached),
+                            None => {
+                                pending_cache_keys.insert(plan.id.clone(), key);
+                            }
+                        }
+                    }
+                }
+                let batch_may_mutate = plans.iter().any(|plan| {
+                    !plan.read_only && plan.guard_result.is_none() && plan.blocked_error.is_none()
+                });
 
                 if parallel_allowed {
                     let mut tool_tasks = FuturesUnordered::new();
+                    let tool_filters = &self.config.tool_filters;
                     for plan in plans {
                         if let Some(result) = plan.guard_result.clone() {
                             let result = Ok(result);
@@ -1411,6 +1603,14 @@ impl Engine {
                             // of the sequential path below). Emit a
                             // `tool.spillover` audit event so operators can
                             // correlate large-output episodes with disk usage.
+                            if let Ok(tool_result) = result.as_mut() {
+                                crate::tools::output_filter::apply_tool_filter(
+                                    tool_filters,
+                                    tool_result,
+                                    &plan.id,
+                                    &plan.name,
+                                );
+                            }
                             if let Ok(tool_result) = result.as_mut()
                                 && let Some(path) =
                                     crate::tools::truncate::apply_spillover_with_artifact(
@@ -1649,32 +1849,9 @@ impl Engine {
                             Option<Result<ToolResult, ToolError>>,
                             Option<crate::tools::ToolContext>,
                         ) = if plan.approval_required {
-                            emit_tool_audit(json!({
-                                "event": "tool.approval_required",
-                                "tool_id": tool_id.clone(),
-                                "tool_name": tool_name.clone(),
-                            }));
-                            let approval_key = crate::tools::approval_cache::build_approval_key(
-                                &tool_name,
-                                &tool_input,
-                            )
-                            .0;
-                            let approval_grouping_key =
-                                crate::tools::approval_cache::build_approval_grouping_key(
-                                    &tool_name,
-                                    &tool_input,
-                                )
-                                .0;
-                            let _ = self
-                                .tx_event
-                                .send(Event::ApprovalRequired {
-                                    id: tool_id.clone(),
-                                    tool_name: tool_name.clone(),
-                                    description: plan.approval_description.clone(),
-                                    approval_key,
-                                    approval_grouping_key,
-                                })
-                                .await;
+                            if !announced_approvals.contains(&tool_id) {
+                                self.announce_tool_approval(&plan).await;
+                            }
 
                             match self.await_tool_approval(&tool_id).await {
                                 Ok(ApprovalResult::Approved) => {
@@ -1716,6 +1893,23 @@ impl Engine {
                                     });
                                     (None, elevated_context)
                                 }
+                                Ok(ApprovalResult::RetryWithHigherLimits(kind)) => {
+                                    emit_tool_audit(json!({
+                                        "event": "tool.approval_decision",
+                                        "tool_id": tool_id.clone(),
+                                        "tool_name": tool_name.clone(),
+                                        "decision": "retry_with_higher_limits",
+                                        "limit": kind.as_str(),
+                                        "caller": caller_type_for_tool_use(tool_caller.as_ref()),
+                                    }));
+                                    let raised_context = tool_registry.map(|r| {
+                                        let context = r.context().clone();
+                                        let limits =
+                                            context.shell_limits.clone().unwrap_or_default();
+                                        context.with_shell_limits(limits.raised(kind))
+                                    });
+                                    (None, raised_context)
+                                }
                                 Err(err) => (Some(Err(err)), None),
                             }
                         } else {
@@ -1734,7 +1928,15 @@ impl Engine {
                             let ws = self.session.workspace.clone();
                             let tid = tool_id.clone();
                             let cap = self.config.snapshots_max_workspace_bytes;
+                            // Optional named `auto` snapshot, once per turn,
+                            // for `/snapshot restore auto`.
+                            let auto_named = self.config.snapshots_auto_before_write
+                                && self.auto_snapshot_turn != self.turn_counter;
+                            self.auto_snapshot_turn = self.turn_counter;
                             let _ = tokio::task::spawn_blocking(move || {
+                                if auto_named {
+                                    crate::core::turn::auto_named_snapshot(&ws, cap);
+                                }
                                 crate::core::turn::pre_tool_snapshot(&ws, &tid, cap)
                             })
                             .await;
@@ -1765,6 +1967,16 @@ impl Engine {
                         // Emit a discrete `tool.spillover` audit event so
                         // operators can correlate large-output episodes with
                         // disk-usage growth in `~/.deepseek/tool_outputs/`.
+                        // `[tool_filters]` trims the result first; the raw
+                        // output lands in the same spillover directory.
+                        if let Ok(tool_result) = result.as_mut() {
+                            crate::tools::output_filter::apply_tool_filter(
+                                &self.config.tool_filters,
+                                tool_result,
+                                &tool_id,
+                                &tool_name,
+                            );
+                        }
                         if let Ok(tool_result) = result.as_mut()
                             && let Some(path) =
                                 crate::tools::truncate::apply_spillover_with_artifact(
@@ -1801,8 +2013,15 @@ impl Engine {
                         });
                     }
                 }
+                // Later batches must not be served reads from before this
+                // batch's writes.
+                if batch_may_mutate {
+                    self.tool_result_cache.clear();
+                }
             }
 
+            self.soft_stop.set_tools_running(false);
+
             let mut step_error_count = 0usize;
             // Categorized tool errors collected this step. Feeds the capacity
             // controller's error-escalation checkpoint so it can distinguish
@@ -1811,8 +2030,16 @@ impl Engine {
             let mut step_error_categories: Vec<ErrorCategory> = Vec::new();
             let mut stop_after_plan_tool = false;
             let mut loop_guard_halt: Option<String> = None;
+            let mut loop_nudges: Vec<(String, u32, String)> = Vec::new();
 
             for outcome in outcomes.into_iter().flatten() {
+                if mutating_tool_ids.contains(&outcome.id) {
+                    self.tool_result_cache.clear();
+                } else if let Some(key) = pending_cache_keys.remove(&outcome.id)
+                    && let Ok(output) = &outcome.result
+                {
+                    self.tool_result_cache.insert(key, output);
+                }
                 let duration = outcome.started_at.elapsed();
                 let tool_input = outcome.input.clone();
                 let tool_name_for_ws = outcome.name.clone();
@@ -1823,33 +2050,59 @@ impl Engine {
 
                 match outcome.result {
                     Ok(output) => {
-                        match loop_guard.record_outcome(&outcome.name, output.success) {
+                        match loop_guard.record_outcome(&outcome.name, &tool_input, output.success)
+                        {
                             OutcomeDecision::Continue => {}
                             OutcomeDecision::Warn(message) => {
                                 crate::logging::warn(message.clone());
                                 let _ = self.tx_event.send(Event::status(message)).await;
                             }
+                            OutcomeDecision::LoopDetected { failures, nudge } => {
+                                loop_nudges.push((outcome.name.clone(), failures, nudge));
+                            }
                             OutcomeDecision::Halt(message) => {
                                 loop_guard_halt.get_or_insert(message);
                             }
                         }
+                        let failure =
+                            (!output.success).then(|| ToolFailure::from_failed_result(&output));
                         emit_tool_audit(json!({
                             "event": "tool.result",
                             "tool_id": outcome.id.clone(),
                             "tool_name": outcome.name.clone(),
                             "success": output.success,
+                            "error_code": failure.as_ref().map(|failure| failure.code),
                         }));
-                        let output_for_context = compact_tool_result_for_context(
+                        let mut output_for_context = compact_tool_result_for_context(
                             &self.session.model,
                             &outcome.name,
                             &output,
                         );
+                        if let Some(failure) = &failure {
+                            output_for_context = failure.annotate(&output_for_context);
+                        }
                         let tool_was_executed = output
                             .metadata
                             .as_ref()
                             .and_then(|metadata| metadata.get("executed"))
                             .and_then(serde_json::Value::as_bool)
                             .unwrap_or(true);
+                        if outcome.name == "exec_shell"
+                            && tool_was_executed
+                            && self.config.command_lessons_enabled
+                        {
+                            crate::lessons::observe_shell_result(
+                                &self.session.workspace,
+                                &tool_input,
+                                &output,
+                            );
+                        }
+                        if outcome.name == "read_file"
+                            && output.success
+                            && let Some(path) = tool_input.get("path").and_then(|v| v.as_str())
+                        {
+                            self.stale_reads.record_read(&self.session.workspace, path);
+                        }
                         let output_content = output.content;
 
                         tool_call.set_result(output_content.clone(), duration);
@@ -1881,23 +2134,28 @@ impl Engine {
                         .await;
                     }
                     Err(e) => {
-                        match loop_guard.record_outcome(&outcome.name, false) {
+                        match loop_guard.record_outcome(&outcome.name, &tool_input, false) {
                             OutcomeDecision::Continue => {}
                             OutcomeDecision::Warn(message) => {
                                 crate::logging::warn(message.clone());
                                 let _ = self.tx_event.send(Event::status(message)).await;
                             }
+                            OutcomeDecision::LoopDetected { failures, nudge } => {
+                                loop_nudges.push((outcome.name.clone(), failures, nudge));
+                            }
                             OutcomeDecision::Halt(message) => {
                                 loop_guard_halt.get_or_insert(message);
                             }
                         }
                         let envelope: ErrorEnvelope = e.clone().into();
+                        let failure = ToolFailure::from_tool_error(&e);
                         emit_tool_audit(json!({
                             "event": "tool.result",
                             "tool_id": outcome.id.clone(),
                             "tool_name": outcome.name.clone(),
                             "success": false,
                             "error": e.to_string(),
+                            "error_code": failure.code,
                             "category": envelope.category.to_string(),
                             "severity": envelope.severity.to_string(),
                         }));
@@ -1915,7 +2173,7 @@ impl Engine {
                             role: "user".to_string(),
                             content: vec![ContentBlock::ToolResult {
                                 tool_use_id: outcome.id,
-                                content: format!("Error: {error}"),
+                                content: failure.annotate(&format!("Error: {error}")),
                                 is_error: Some(true),
                                 content_blocks: None,
                             }],
@@ -1928,6 +2186,22 @@ impl Engine {
                 stop_after_plan_tool |= should_stop_this_turn;
             }
 
+            // The nudge follows the tool results so the model reads it
+            // before choosing its next call.
+            for (tool, failures, nudge) in loop_nudges {
+                crate::logging::warn(nudge.clone());
+                let _ = self
+                    .tx_event
+                    .send(Event::LoopDetected { tool, failures })
+                    .await;
+                self.add_session_message(self.user_text_message_with_turn_metadata(nudge))
+                    .await;
+            }
+
+            if self.soft_stop.is_requested() {
+                return self.finish_soft_stopped_turn().await;
+            }
+
             if stop_after_plan_tool {
                 break;
             }
@@ -1996,6 +2270,18 @@ impl Engine {
         (TurnOutcomeStatus::Completed, None)
     }
 
+    /// End a soft-cancelled turn. Every executed tool's result is already in
+    /// the session, so `/retry` can resume from here.
+    async fn finish_soft_stopped_turn(&self) -> (TurnOutcomeStatus, Option<String>) {
+        let _ = self
+            .tx_event
+            .send(Event::status(
+                "Turn interrupted after the running tool finished; /retry resumes",
+            ))
+            .await;
+        (TurnOutcomeStatus::Interrupted, None)
+    }
+
     pub(super) fn messages_with_turn_metadata(&self) -> Vec<Message> {
         // `<turn_meta>` is stored on user-text messages when the message is
         // appended. Do not rewrite historical messages at request time: doing
@@ -2056,29 +2342,7 @@ fn should_emit_thinking_only_status(
 fn resolve_auto_effort(reasoning_effort: Option<&str>, messages: &[Message]) -> Option<String> {
     match reasoning_effort {
         Some("auto") => {
-            // Find the last user message in the conversation.
-            let last_msg = messages
-                .iter()
-                .rev()
-                .find(|m| m.role == "user")
-                .map(|m| {
-                    m.content
-                        .iter()
-                        .filter_map(|block| {
-                            if let ContentBlock::Text { text, .. } = block {
-                                if is_turn_metadata_text(text) {
-                                    None
-                                } else {
-                                    Some(text.as_str())
-                                }
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<&str>>()
-                        .join(" ")
-                })
-                .unwrap_or_default();
+            let last_msg = last_user_text(messages);
 
             // is_subagent is false here — handle_deepseek_turn runs in the
             // main engine (not a sub-agent's inner loop). Sub-agents have
@@ -2098,6 +2362,40 @@ fn resolve_auto_effort(reasoning_effort: Option<&str>, messages: &[Message]) ->
     }
 }
 
+/// Text of the last user message, without the `<turn_meta>` block.
+fn last_user_text(messages: &[Message]) -> String {
+    messages
+        .iter()
+        .rev()
+        .find(|m| m.role == "user")
+        .map(|m| {
+            m.content
+                .iter()
+                .filter_map(|block| {
+                    if let ContentBlock::Text { text, .. } = block {
+                        if is_turn_metadata_text(text) {
+                            None
+                        } else {
+                            Some(text.as_str())
+                        }
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<&str>>()
+                .join(" ")
+        })
+        .unwrap_or_default()
+}
+
+/// Whether `[thinking] auto_off` should run this turn without thinking: the
+/// conversation already has an assistant reply and the new request is a
+/// simple follow-up per [`crate::auto_reasoning::is_simple_followup`].
+fn is_simple_followup_turn(messages: &[Message]) -> bool {
+    messages.iter().any(|m| m.role == "assistant")
+        && crate::auto_reasoning::is_simple_followup(&last_user_text(messages))
+}
+
 fn is_turn_metadata_text(text: &str) -> bool {
     text.trim_start().starts_with("<turn_meta>")
 }
@@ -2294,4 +2592,34 @@ mod tests {
             "auto thinking should classify the user request, not stored metadata"
         );
     }
+    #[test]
+    fn auto_off_only_applies_to_simple_follow_ups() {
+        let text = |role: &str, text: &str| Message {
+            role: role.to_string(),
+            content: vec![ContentBlock::Text {
+                text: text.to_string(),
+                cache_control: None,
+            }],
+        };
+        let first = vec![text("user", "rename `foo` to `bar`")];
+        assert!(!is_simple_followup_turn(&first));
+
+        let mut follow_up = vec![
+            text("user", "add a config loader"),
+            text("assistant", "Done."),
+            text("user", "<turn_meta>\nRecent errors: src/a.rs\n</turn_meta>"),
+        ];
+        follow_up
+            .last_mut()
+            .unwrap()
+            .content
+            .push(ContentBlock::Text {
+                text: "now rename it to `load_config`".to_string(),
+                cache_control: None,
+            });
+        assert!(is_simple_followup_turn(&follow_up));
+
+        follow_up.push(text("user", "why does it panic on empty files?"));
+        assert!(!is_simple_followup_turn(&follow_up));
+    }
 }
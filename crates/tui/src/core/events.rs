@@ -13,6 +13,7 @@ use crate::models::{Message, SystemPrompt, Usage};
 use crate::tools::spec::{ToolError, ToolResult};
 use crate::tools::subagent::SubAgentResult;
 use crate::tools::user_input::UserInputRequest;
+use crate::tui::app::AppMode;
 
 /// Final status for a turn.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,6 +23,21 @@ pub enum TurnOutcomeStatus {
     Failed,
 }
 
+/// Tool surface offered to the model for one turn, as assembled for the
+/// active mode and workspace trust level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolCatalogSummary {
+    pub mode: AppMode,
+    /// Plan mode or a restricted trust level: only read-only tools.
+    pub read_only: bool,
+    /// Tools whose schemas are sent with the first request.
+    pub loaded: usize,
+    /// Tools held back until tool search activates them.
+    pub deferred: usize,
+    /// Rough token estimate of the loaded schemas (~4 chars/token).
+    pub schema_tokens: usize,
+}
+
 /// Events emitted by the engine to update the UI.
 #[derive(Debug, Clone)]
 pub enum Event {
@@ -94,6 +110,16 @@ pub enum Event {
         error: Option<String>,
     },
 
+    /// Live size of the request the model is answering: the prompt (an
+    /// estimate until the provider reports usage) plus output streamed so
+    /// far. Sent at request start and throttled during streaming so the
+    /// header context gauge moves mid-turn.
+    ContextUsage {
+        prompt_tokens: u32,
+        output_tokens: u32,
+        estimated: bool,
+    },
+
     /// Context compaction started.
     CompactionStarted {
         id: String,
@@ -132,6 +158,16 @@ pub enum Event {
         briefing: crate::cycle_manager::CycleBriefing,
     },
 
+    /// A turn request failed and the engine switched to the next model in
+    /// `model_fallbacks` for the rest of the turn. `usage` is the turn's
+    /// usage up to the switch, which was billed to `from`.
+    ModelFallback {
+        from: String,
+        to: String,
+        reason: String,
+        usage: Usage,
+    },
+
     /// Capacity decision telemetry.
     #[allow(dead_code)]
     CapacityDecision {
@@ -254,6 +290,37 @@ pub enum Event {
         workspace: PathBuf,
     },
 
+    /// The system prompt's layer stack changed (names, order, sizes).
+    /// Drives the `/system` breakdown.
+    PromptLayers {
+        layers: Vec<crate::prompts::layers::PromptLayerSummary>,
+    },
+
+    /// Size of the tool catalog assembled for the current turn. Drives the
+    /// toolset line in `/context`.
+    ToolCatalog { summary: ToolCatalogSummary },
+
+    /// Files the model read earlier changed on disk outside the agent
+    /// (canonical paths). Drives the staleness badge on their read cells.
+    ReadFilesChanged { paths: Vec<PathBuf> },
+
+    /// The same tool call failed repeatedly with identical arguments. The
+    /// engine has nudged the model and blocks the exact repeat.
+    LoopDetected { tool: String, failures: u32 },
+
+    /// Background MCP connection started by `Op::WarmMcp` finished.
+    McpWarmed {
+        connected: usize,
+        failures: Vec<String>,
+    },
+
+    /// Result of `Op::FetchMcpPrompt`; `Err` carries a display-ready message.
+    McpPromptFetched {
+        server: String,
+        name: String,
+        result: Result<Value, String>,
+    },
+
     /// Request user decision after sandbox denial
     #[allow(dead_code)]
     ElevationRequired {
@@ -263,6 +330,9 @@ pub enum Event {
         denial_reason: String,
         blocked_network: bool,
         blocked_write: bool,
+        /// `[shell_limits]` cap the command ran into, when that caused the
+        /// denial; the dialog then offers a re-run with higher limits.
+        limit_hit: Option<crate::sandbox::limits::LimitHit>,
     },
 
     // === Prefix-Cache Stability Events ===
@@ -301,4 +371,69 @@ impl Event {
             message: message.into(),
         }
     }
+
+    /// One-line summary kept in the `/report` event history. Streaming
+    /// deltas and events that carry conversation content return `None`.
+    pub fn report_line(&self) -> Option<String> {
+        let line = match self {
+            Event::TurnStarted { turn_id } => format!("turn_started {turn_id}"),
+            Event::TurnComplete {
+                usage,
+                status,
+                error,
+            } => {
+                let mut line = format!(
+                    "turn_complete {status:?} in={} out={}",
+                    usage.input_tokens, usage.output_tokens
+                );
+                if let Some(error) = error {
+                    line.push_str(&format!(" error={error}"));
+                }
+                line
+            }
+            Event::ToolCallStarted { id, name, .. } => format!("tool_started {name} {id}"),
+            Event::ToolCallComplete { id, name, result } => match result {
+                Ok(result) if result.success => format!("tool_complete {name} {id} ok"),
+                Ok(_) => format!("tool_complete {name} {id} failed"),
+                Err(err) => format!("tool_complete {name} {id} error={err}"),
+            },
+            Event::ApprovalRequired { id, tool_name, .. } => {
+                format!("approval_required {tool_name} {id}")
+            }
+            Event::ElevationRequired {
+                tool_id, tool_name, ..
+            } => format!("elevation_required {tool_name} {tool_id}"),
+            Event::CompactionStarted { auto, .. } => format!("compaction_started auto={auto}"),
+            Event::CompactionCompleted { auto, .. } => {
+                format!("compaction_completed auto={auto}")
+            }
+            Event::CompactionFailed { auto, message, .. } => {
+                format!("compaction_failed auto={auto} {message}")
+            }
+            Event::CycleAdvanced { from, to, .. } => format!("cycle_advanced {from}->{to}"),
+            Event::ModelFallback {
+                from, to, reason, ..
+            } => format!("model_fallback {from}->{to} ({reason})"),
+            Event::AgentSpawned { id, .. } => format!("agent_spawned {id}"),
+            Event::AgentComplete { id, .. } => format!("agent_complete {id}"),
+            Event::Error {
+                envelope,
+                recoverable,
+            } => format!(
+                "error {} {} recoverable={recoverable}: {}",
+                envelope.category, envelope.code, envelope.message
+            ),
+            Event::Status { message } => format!("status {message}"),
+            Event::PrefixCacheChange {
+                description,
+                changed: true,
+                ..
+            } => format!("prefix_cache_changed {description}"),
+            _ => return None,
+        };
+        Some(line.chars().take(REPORT_LINE_MAX_CHARS).collect())
+    }
 }
+
+/// Longest single line kept by [`Event::report_line`].
+const REPORT_LINE_MAX_CHARS: usize = 300;
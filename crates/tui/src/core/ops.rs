@@ -5,6 +5,7 @@
 
 use crate::compaction::CompactionConfig;
 use crate::models::{Message, SystemPrompt};
+use crate::tools::user_input::UserInputResponse;
 use crate::tui::app::AppMode;
 use crate::tui::approval::ApprovalMode;
 use std::path::PathBuf;
@@ -60,6 +61,18 @@ pub enum Op {
     #[allow(dead_code)]
     SetModel { model: String },
 
+    /// Cap estimated thinking tokens per turn (`/thinking budget`). `None`
+    /// removes the cap.
+    SetThinkingBudget { budget: Option<u32> },
+
+    /// Turn the per-turn git audit trail on or off (`/trail on|off`).
+    SetTrail { enabled: bool },
+
+    /// Change the workspace trust level (`/trust level`).
+    SetTrustLevel {
+        level: crate::trust_level::TrustLevel,
+    },
+
     /// Update auto-compaction settings
     SetCompaction { config: CompactionConfig },
 
@@ -76,6 +89,42 @@ pub enum Op {
     /// Run context compaction immediately.
     CompactContext,
 
+    /// Compact while the UI is idle. Abandoned, leaving the history as it
+    /// was, if the cancel token fires before the summary returns.
+    IdleCompactContext,
+
+    /// Connect every configured MCP server in the background right after
+    /// startup, so the first turn does not wait on them. Answered with
+    /// `Event::McpWarmed`.
+    WarmMcp,
+
+    /// Fetch an MCP prompt (`/mcp prompt`) through the session's server
+    /// pool. Answered with `Event::McpPromptFetched`.
+    FetchMcpPrompt {
+        server: String,
+        name: String,
+        arguments: Vec<(String, String)>,
+    },
+
+    /// Drop every cached read-only tool result (`/cache clear`).
+    ClearToolResultCache,
+
+    /// Run a recorded tool call again with the same input, without a model
+    /// round-trip (transcript tool-cell menu).
+    RerunToolCall {
+        tool_name: String,
+        input: serde_json::Value,
+        mode: AppMode,
+    },
+
+    /// Answer a `request_user_input` call restored from a saved session and
+    /// continue the paused turn. `Err` carries a cancellation reason.
+    ResumePausedInput {
+        tool_id: String,
+        outcome: Result<UserInputResponse, String>,
+        mode: AppMode,
+    },
+
     /// Edit the last user message: remove the last user+assistant exchange
     /// from the session, then re-send with the new content.
     #[allow(dead_code)]
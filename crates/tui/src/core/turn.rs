@@ -158,6 +158,28 @@ pub fn post_turn_snapshot(workspace: &Path, turn_seq: u64, cap_bytes: u64) -> Op
     snapshot_with_label(workspace, &format!("post-turn:{turn_seq}"), cap_bytes)
 }
 
+/// Name of the snapshot saved before the first file write of a turn when
+/// `[snapshots] auto_before_write` is on.
+pub const AUTO_NAMED_SNAPSHOT: &str = "auto";
+
+/// Save the named snapshot [`AUTO_NAMED_SNAPSHOT`], replacing the previous
+/// turn's. Same failure model as [`pre_turn_snapshot`].
+pub fn auto_named_snapshot(workspace: &Path, cap_bytes: u64) -> Option<String> {
+    match SnapshotRepo::open_or_init_with_cap(workspace, cap_bytes) {
+        Ok(repo) => match repo.save_named(AUTO_NAMED_SNAPSHOT) {
+            Ok(id) => Some(id.0),
+            Err(e) => {
+                tracing::warn!(target: "snapshot", "auto named snapshot failed: {e}");
+                None
+            }
+        },
+        Err(e) => {
+            tracing::warn!(target: "snapshot", "snapshot repo init failed: {e}");
+            None
+        }
+    }
+}
+
 fn snapshot_with_label(workspace: &Path, label: &str, cap_bytes: u64) -> Option<String> {
     match SnapshotRepo::open_or_init_with_cap(workspace, cap_bytes) {
         Ok(repo) => {
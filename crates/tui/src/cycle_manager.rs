@@ -1002,6 +1002,8 @@ mod tests {
                     step: "Update prompts".to_string(),
                     status: crate::tools::plan::StepStatus::Pending,
                 }],
+                open_questions: Vec::new(),
+                decisions: Vec::new(),
             }),
             subagent_snapshots: Vec::new(),
         };
@@ -1,8 +1,9 @@
 //! Shared error taxonomy across client, tools, runtime, and UI.
+use std::collections::BTreeMap;
 use std::fmt;
 
 use crate::llm_client::LlmError;
-use crate::tools::spec::ToolError;
+use crate::tools::spec::{ToolError, ToolResult};
 
 /// Broad category for typed error handling and policy decisions.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -411,6 +412,272 @@ impl From<ToolError> for ErrorEnvelope {
     }
 }
 
+/// Machine-readable class of a failed tool call, surfaced to the model so it
+/// can pick a recovery instead of re-reading free-form error text.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolErrorClass {
+    NotFound,
+    Permission,
+    Timeout,
+    Syntax,
+    SandboxDenied,
+    Network,
+    InvalidInput,
+    Unavailable,
+    Failed,
+}
+
+impl ToolErrorClass {
+    #[must_use]
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::Permission => "permission",
+            Self::Timeout => "timeout",
+            Self::Syntax => "syntax",
+            Self::SandboxDenied => "sandbox_denied",
+            Self::Network => "network",
+            Self::InvalidInput => "invalid_input",
+            Self::Unavailable => "unavailable",
+            Self::Failed => "failed",
+        }
+    }
+
+    #[must_use]
+    pub fn category(self) -> ErrorCategory {
+        match self {
+            Self::NotFound | Self::Unavailable => ErrorCategory::State,
+            Self::Permission | Self::SandboxDenied => ErrorCategory::Authorization,
+            Self::Timeout => ErrorCategory::Timeout,
+            Self::Syntax => ErrorCategory::Parse,
+            Self::Network => ErrorCategory::Network,
+            Self::InvalidInput => ErrorCategory::InvalidInput,
+            Self::Failed => ErrorCategory::Tool,
+        }
+    }
+
+    /// Whether repeating the same call, unchanged, can succeed.
+    #[must_use]
+    pub fn retryable(self) -> bool {
+        matches!(self, Self::Timeout | Self::Network)
+    }
+
+    /// Suggested next step for the model.
+    #[must_use]
+    pub fn recovery_hint(self) -> &'static str {
+        match self {
+            Self::NotFound => {
+                "Verify the path or name first: list the directory or search for it, then retry with the correct one."
+            }
+            Self::Permission => {
+                "Do not repeat the call. Use a path inside the workspace, or ask the user for the permission it needs."
+            }
+            Self::Timeout => {
+                "Narrow the scope, raise timeout_ms, or run it in the background and poll for the result."
+            }
+            Self::Syntax => {
+                "Fix the malformed input reported above (quoting, JSON, or code syntax) before retrying."
+            }
+            Self::SandboxDenied => {
+                "The sandbox blocked this (a write outside the workspace or network access). Stay inside the workspace or ask the user to approve elevated access."
+            }
+            Self::Network => {
+                "Check the host or URL; network access may be disabled by policy. Retry at most once."
+            }
+            Self::InvalidInput => "Fix the arguments to match the tool schema and retry.",
+            Self::Unavailable => {
+                "This tool is not available in the current mode or config. Use another tool or ask the user to enable it."
+            }
+            Self::Failed => {
+                "Read the error output, fix the underlying cause, then retry; the identical call will fail the same way."
+            }
+        }
+    }
+
+    /// Classify free-form failure text (stderr, error messages).
+    #[must_use]
+    pub fn from_message(message: &str) -> Self {
+        let lower = message.to_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|needle| lower.contains(needle));
+        if has(&["sandbox"]) && has(&["denied", "blocked", "not permitted", "violation"]) {
+            Self::SandboxDenied
+        } else if has(&["timed out", "timeout"]) {
+            Self::Timeout
+        } else if has(&[
+            "connection refused",
+            "could not resolve",
+            "name resolution",
+            "network is unreachable",
+            "connection reset",
+            "tls handshake",
+            "dns error",
+        ]) {
+            Self::Network
+        } else if has(&[
+            "permission denied",
+            "operation not permitted",
+            "access is denied",
+            "eacces",
+            "escapes workspace",
+            "blocked",
+        ]) {
+            Self::Permission
+        } else if has(&[
+            "no such file",
+            "not found",
+            "does not exist",
+            "cannot find",
+            "enoent",
+        ]) {
+            Self::NotFound
+        } else if has(&[
+            "syntax error",
+            "parse error",
+            "failed to parse",
+            "invalid json",
+            "unexpected token",
+            "unexpected eof",
+            "malformed",
+        ]) {
+            Self::Syntax
+        } else {
+            Self::Failed
+        }
+    }
+}
+
+impl fmt::Display for ToolErrorClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Structured description of a failed tool call, appended to the tool
+/// result the model sees.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ToolFailure {
+    pub code: ToolErrorClass,
+    pub category: ErrorCategory,
+    pub retryable: bool,
+    pub recovery: &'static str,
+}
+
+impl ToolFailure {
+    #[must_use]
+    pub fn new(code: ToolErrorClass) -> Self {
+        Self {
+            code,
+            category: code.category(),
+            retryable: code.retryable(),
+            recovery: code.recovery_hint(),
+        }
+    }
+
+    /// Classify a tool that returned an `Err`.
+    #[must_use]
+    pub fn from_tool_error(err: &ToolError) -> Self {
+        Self::new(match err {
+            ToolError::InvalidInput { message } => {
+                if ToolErrorClass::from_message(message) == ToolErrorClass::Syntax {
+                    ToolErrorClass::Syntax
+                } else {
+                    ToolErrorClass::InvalidInput
+                }
+            }
+            ToolError::MissingField { .. } => ToolErrorClass::InvalidInput,
+            ToolError::PathEscape { .. } => ToolErrorClass::Permission,
+            ToolError::Timeout { .. } => ToolErrorClass::Timeout,
+            ToolError::NotAvailable { .. } => ToolErrorClass::Unavailable,
+            ToolError::PermissionDenied { message } => {
+                if ToolErrorClass::from_message(message) == ToolErrorClass::SandboxDenied {
+                    ToolErrorClass::SandboxDenied
+                } else {
+                    ToolErrorClass::Permission
+                }
+            }
+            ToolError::ExecutionFailed { message } => ToolErrorClass::from_message(message),
+        })
+    }
+
+    /// Classify a tool that ran but reported `success: false`. Shell metadata
+    /// (sandbox denial, timeout status, exit code 127) wins over text
+    /// heuristics.
+    #[must_use]
+    pub fn from_failed_result(result: &ToolResult) -> Self {
+        let metadata = result.metadata.as_ref();
+        let flag = |key: &str| {
+            metadata
+                .and_then(|meta| meta.get(key))
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false)
+        };
+        let status = metadata
+            .and_then(|meta| meta.get("status"))
+            .and_then(serde_json::Value::as_str);
+        let exit_code = metadata
+            .and_then(|meta| meta.get("exit_code"))
+            .and_then(serde_json::Value::as_i64);
+        Self::new(if flag("sandbox_denied") {
+            ToolErrorClass::SandboxDenied
+        } else if status == Some("TimedOut") {
+            ToolErrorClass::Timeout
+        } else if exit_code == Some(127) {
+            ToolErrorClass::NotFound
+        } else {
+            ToolErrorClass::from_message(&result.content)
+        })
+    }
+
+    /// `content` followed by a `tool_error: {…}` line carrying this failure.
+    #[must_use]
+    pub fn annotate(&self, content: &str) -> String {
+        let json = serde_json::to_string(self).unwrap_or_default();
+        format!("{}\n\ntool_error: {json}", content.trim_end())
+    }
+}
+
+/// Per-session counts of tool failures by class.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(transparent)]
+pub struct ToolErrorStats {
+    by_class: BTreeMap<ToolErrorClass, usize>,
+}
+
+impl ToolErrorStats {
+    pub fn record(&mut self, class: ToolErrorClass) {
+        *self.by_class.entry(class).or_default() += 1;
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.by_class.is_empty()
+    }
+
+    /// Counts keyed by error code.
+    #[must_use]
+    pub fn counts(&self) -> BTreeMap<String, usize> {
+        self.by_class
+            .iter()
+            .map(|(class, count)| (class.code().to_string(), *count))
+            .collect()
+    }
+
+    /// `code=count` pairs, most frequent first.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut counts: Vec<_> = self.by_class.iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        counts
+            .into_iter()
+            .map(|(class, count)| format!("{class}={count}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
 /// Stream‑level error discriminated by origin.
 ///
 /// Each variant maps to an `ErrorCategory` so the UI can render
@@ -731,4 +998,88 @@ mod tests {
         assert_eq!(format!("{}", ErrorCategory::InvalidInput), "invalid_input");
         assert_eq!(format!("{}", ErrorSeverity::Critical), "critical");
     }
+
+    #[test]
+    fn tool_failures_classify_from_errors_and_shell_metadata() {
+        let cases = [
+            (
+                ToolError::execution_failed("open foo.rs: No such file or directory"),
+                ToolErrorClass::NotFound,
+            ),
+            (
+                ToolError::invalid_input("Failed to parse 'args' as shell-style tokens"),
+                ToolErrorClass::Syntax,
+            ),
+            (
+                ToolError::missing_field("path"),
+                ToolErrorClass::InvalidInput,
+            ),
+            (ToolError::Timeout { seconds: 30 }, ToolErrorClass::Timeout),
+            (
+                ToolError::permission_denied("sandbox denied write to /etc"),
+                ToolErrorClass::SandboxDenied,
+            ),
+            (
+                ToolError::execution_failed("error sending request: connection refused"),
+                ToolErrorClass::Network,
+            ),
+        ];
+        for (err, class) in cases {
+            assert_eq!(ToolFailure::from_tool_error(&err).code, class, "{err}");
+        }
+
+        let denied = ToolResult {
+            content: "touch: /etc/x: Operation not permitted".to_string(),
+            success: false,
+            metadata: Some(serde_json::json!({ "sandbox_denied": true, "exit_code": 1 })),
+        };
+        assert_eq!(
+            ToolFailure::from_failed_result(&denied).code,
+            ToolErrorClass::SandboxDenied
+        );
+        let missing = ToolResult {
+            content: "sh: cargo-nextest: command not found".to_string(),
+            success: false,
+            metadata: Some(serde_json::json!({ "exit_code": 127, "status": "Failed" })),
+        };
+        assert_eq!(
+            ToolFailure::from_failed_result(&missing).code,
+            ToolErrorClass::NotFound
+        );
+        let test_failure = ToolResult {
+            content: "test result: FAILED. 1 passed; 1 failed".to_string(),
+            success: false,
+            metadata: Some(serde_json::json!({ "exit_code": 101, "status": "Failed" })),
+        };
+        assert_eq!(
+            ToolFailure::from_failed_result(&test_failure).code,
+            ToolErrorClass::Failed
+        );
+    }
+
+    #[test]
+    fn annotated_failure_carries_a_machine_readable_object() {
+        let annotated = ToolFailure::new(ToolErrorClass::Timeout).annotate("Error: slow\n");
+        let (head, tail) = annotated.split_once("\n\ntool_error: ").expect("marker");
+        assert_eq!(head, "Error: slow");
+        let value: serde_json::Value = serde_json::from_str(tail).expect("json");
+        assert_eq!(value["code"], "timeout");
+        assert_eq!(value["category"], "timeout");
+        assert_eq!(value["retryable"], true);
+        assert!(
+            value["recovery"]
+                .as_str()
+                .is_some_and(|hint| !hint.is_empty())
+        );
+
+        let mut stats = ToolErrorStats::default();
+        stats.record(ToolErrorClass::NotFound);
+        stats.record(ToolErrorClass::Timeout);
+        stats.record(ToolErrorClass::NotFound);
+        assert_eq!(stats.summary(), "not_found=2 timeout=1");
+        assert_eq!(
+            serde_json::to_value(&stats).unwrap(),
+            serde_json::json!({ "not_found": 2, "timeout": 1 })
+        );
+    }
 }
@@ -2,6 +2,12 @@
 //!
 //! This module is intentionally self-contained so it can be wired into a CLI
 //! command later without calling the network or any LLM endpoints.
+//!
+//! Besides the built-in scenario, user-defined scenarios are loaded from YAML
+//! (see [`Scenario`]): seed files, scripted tool calls or recorded model
+//! responses to replay, and assertions on outputs and the final workspace.
+//! `deepseek eval --scenario <path>` runs them and can compare the suite
+//! report against a previous run.
 
 use anyhow::{Context, Result, anyhow};
 use ignore::WalkBuilder;
@@ -129,6 +135,11 @@ pub struct ToolStats {
 pub struct EvalMetrics {
     pub success: bool,
     pub tool_errors: usize,
+    /// Failed steps counted by tool error class (`not_found`, `timeout`, …).
+    /// Filled in by the CLI from the shared error taxonomy, which this
+    /// self-contained module does not depend on.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub error_classes: BTreeMap<String, usize>,
     pub steps: usize,
     pub duration: Duration,
     pub per_tool: BTreeMap<ScenarioStepKind, ToolStats>,
@@ -304,6 +315,7 @@ impl EvalHarness {
         let metrics = EvalMetrics {
             success,
             tool_errors,
+            error_classes: BTreeMap::new(),
             steps: steps.len(),
             duration,
             per_tool,
@@ -788,6 +800,470 @@ fn exec_shell(root: &Path, command: &str) -> Result<String> {
     Ok(stdout.trim().to_string())
 }
 
+// === YAML scenarios ===========================================================
+//
+// A scenario file looks like:
+//
+//     name: fix-greeting
+//     files:
+//       src/lib.rs: "pub fn greet() -> &'static str { \"helo\" }\n"
+//     responses:                      # recorded model turns, replayed in order
+//       - text: Fixing the typo.
+//         tool_calls:
+//           - tool: edit_file
+//             input: { path: src/lib.rs, search: helo, replace: hello }
+//       - text: Fixed the greeting.
+//     expected_tool_calls: [edit_file]
+//     assertions:
+//       - file: src/lib.rs
+//         contains: [hello]
+//       - contains: [Fixed]           # no `file`: checks the final response
+//
+// `steps:` lists tool calls directly instead of `responses:`. Both run the
+// same offline tool implementations as the built-in scenario, so a run never
+// touches the network and is deterministic as long as its shell commands are.
+
+/// A user-defined evaluation scenario loaded from YAML.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Workspace-relative files written before the run.
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+    /// Scripted tool calls, run in order.
+    #[serde(default)]
+    pub steps: Vec<ScenarioCall>,
+    /// Recorded model responses whose tool calls are replayed in order.
+    #[serde(default)]
+    pub responses: Vec<RecordedResponse>,
+    /// Tool names the run must call, in this order.
+    #[serde(default)]
+    pub expected_tool_calls: Option<Vec<String>>,
+    /// Checks on the final workspace and response text.
+    #[serde(default)]
+    pub assertions: Vec<ScenarioAssertion>,
+}
+
+/// One tool call in a scenario.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioCall {
+    pub tool: String,
+    #[serde(default)]
+    pub input: serde_json::Value,
+    #[serde(default)]
+    pub expect: StepExpectation,
+}
+
+/// Expectations on a single tool call's result.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StepExpectation {
+    /// Whether the call must succeed (default) or fail.
+    #[serde(default)]
+    pub success: Option<bool>,
+    #[serde(default)]
+    pub contains: Vec<String>,
+    #[serde(default)]
+    pub not_contains: Vec<String>,
+}
+
+/// A recorded model turn: its text and the tool calls it made.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RecordedResponse {
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub tool_calls: Vec<ScenarioCall>,
+}
+
+/// A check on a workspace file, or on the final response text when `file`
+/// is omitted.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioAssertion {
+    #[serde(default)]
+    pub file: Option<String>,
+    #[serde(default)]
+    pub exists: Option<bool>,
+    #[serde(default)]
+    pub contains: Vec<String>,
+    #[serde(default)]
+    pub not_contains: Vec<String>,
+}
+
+impl Scenario {
+    /// Parse and validate a scenario from YAML source.
+    pub fn from_yaml(source: &str) -> Result<Self> {
+        let scenario: Self = serde_yaml::from_str(source).context("invalid scenario YAML")?;
+        if scenario.name.trim().is_empty() {
+            return Err(anyhow!("scenario name must not be empty"));
+        }
+        if !scenario.steps.is_empty() && !scenario.responses.is_empty() {
+            return Err(anyhow!(
+                "scenario '{}' sets both steps and responses; use one",
+                scenario.name
+            ));
+        }
+        for path in scenario.files.keys() {
+            workspace_path(Path::new("."), path)?;
+        }
+        Ok(scenario)
+    }
+
+    /// Tool calls in execution order.
+    fn calls(&self) -> impl Iterator<Item = &ScenarioCall> {
+        self.steps.iter().chain(
+            self.responses
+                .iter()
+                .flat_map(|response| response.tool_calls.iter()),
+        )
+    }
+
+    /// Run the scenario in a fresh temporary workspace.
+    pub fn run(&self, max_output_chars: usize) -> Result<ScenarioReport> {
+        let started_at = Instant::now();
+        let workspace = tempfile::Builder::new()
+            .prefix("deepseek-eval-")
+            .tempdir()
+            .context("failed to create evaluation workspace")?;
+        let root = workspace.path();
+        for (path, content) in &self.files {
+            let target = workspace_path(root, path)?;
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("failed to create {}", parent.display()))?;
+            }
+            fs::write(&target, content)
+                .with_context(|| format!("failed to write {}", target.display()))?;
+        }
+
+        let mut steps = Vec::new();
+        let mut failures = Vec::new();
+        for (index, call) in self.calls().enumerate() {
+            let result = run_scenario_tool(root, &call.tool, &call.input);
+            let mut step_failures = Vec::new();
+            let expect_success = call.expect.success.unwrap_or(true);
+            let (success, output, error) = match result {
+                Ok(output) => (true, output, None),
+                Err(err) => (false, String::new(), Some(format!("{err:#}"))),
+            };
+            if success != expect_success {
+                step_failures.push(match &error {
+                    Some(error) => format!("expected success, got error: {error}"),
+                    None => "expected an error, but the call succeeded".to_string(),
+                });
+            }
+            let text = error.as_deref().unwrap_or(&output);
+            for needle in &call.expect.contains {
+                if !text.contains(needle.as_str()) {
+                    step_failures.push(format!("output does not contain {needle:?}"));
+                }
+            }
+            for needle in &call.expect.not_contains {
+                if text.contains(needle.as_str()) {
+                    step_failures.push(format!("output contains {needle:?}"));
+                }
+            }
+            failures.extend(
+                step_failures
+                    .iter()
+                    .map(|failure| format!("step {} ({}): {failure}", index + 1, call.tool)),
+            );
+            steps.push(ScenarioStepReport {
+                tool: call.tool.clone(),
+                success,
+                output: (!output.is_empty()).then(|| truncate_output(&output, max_output_chars)),
+                error,
+                failures: step_failures,
+            });
+        }
+
+        if let Some(expected) = &self.expected_tool_calls {
+            let actual: Vec<&str> = steps.iter().map(|step| step.tool.as_str()).collect();
+            if actual != expected.iter().map(String::as_str).collect::<Vec<_>>() {
+                failures.push(format!(
+                    "expected tool calls [{}], got [{}]",
+                    expected.join(", "),
+                    actual.join(", ")
+                ));
+            }
+        }
+
+        let final_response = self
+            .responses
+            .last()
+            .map(|response| response.text.as_str())
+            .unwrap_or_default();
+        for assertion in &self.assertions {
+            failures.extend(check_assertion(root, assertion, final_response));
+        }
+
+        Ok(ScenarioReport {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            passed: failures.is_empty(),
+            duration_ms: u64::try_from(started_at.elapsed().as_millis()).unwrap_or(u64::MAX),
+            steps,
+            failures,
+        })
+    }
+}
+
+pub(crate) fn check_assertion(
+    root: &Path,
+    assertion: &ScenarioAssertion,
+    final_response: &str,
+) -> Vec<String> {
+    let mut failures = Vec::new();
+    let (subject, text) = match assertion.file.as_deref() {
+        Some(file) => {
+            let content = workspace_path(root, file)
+                .ok()
+                .and_then(|path| fs::read_to_string(path).ok());
+            match (assertion.exists, &content) {
+                (Some(false), Some(_)) => failures.push(format!("{file}: should not exist")),
+                (Some(true) | None, None) => {
+                    failures.push(format!("{file}: missing"));
+                    return failures;
+                }
+                _ => {}
+            }
+            (file.to_string(), content.unwrap_or_default())
+        }
+        None => ("final response".to_string(), final_response.to_string()),
+    };
+    for needle in &assertion.contains {
+        if !text.contains(needle.as_str()) {
+            failures.push(format!("{subject}: does not contain {needle:?}"));
+        }
+    }
+    for needle in &assertion.not_contains {
+        if text.contains(needle.as_str()) {
+            failures.push(format!("{subject}: contains {needle:?}"));
+        }
+    }
+    failures
+}
+
+/// Resolve a scenario path inside `root`, rejecting absolute paths and `..`.
+pub(crate) fn workspace_path(root: &Path, relative: &str) -> Result<PathBuf> {
+    let path = Path::new(relative);
+    if path.is_absolute()
+        || path
+            .components()
+            .any(|component| matches!(component, std::path::Component::ParentDir))
+    {
+        return Err(anyhow!("path must be workspace-relative: {relative}"));
+    }
+    Ok(root.join(path))
+}
+
+fn input_str<'a>(input: &'a serde_json::Value, key: &str) -> Result<&'a str> {
+    input
+        .get(key)
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| anyhow!("missing string input '{key}'"))
+}
+
+/// Run one scenario tool call with the harness's offline tool implementations.
+fn run_scenario_tool(root: &Path, tool: &str, input: &serde_json::Value) -> Result<String> {
+    let optional_path = |key: &str| -> Result<PathBuf> {
+        match input.get(key).and_then(serde_json::Value::as_str) {
+            Some(path) => workspace_path(root, path),
+            None => Ok(root.to_path_buf()),
+        }
+    };
+    match tool {
+        "list_dir" => Ok(list_dir(&optional_path("path")?)?.join("\n")),
+        "read_file" => read_file(&workspace_path(root, input_str(input, "path")?)?),
+        "grep_files" | "search" => {
+            let result = search_files(&optional_path("path")?, input_str(input, "pattern")?)?;
+            Ok(result
+                .matches
+                .iter()
+                .map(|m| {
+                    let path = m.path.strip_prefix(root).unwrap_or(&m.path);
+                    format!("{}:{}:{}", path.display(), m.line, m.content)
+                })
+                .collect::<Vec<_>>()
+                .join("\n"))
+        }
+        "write_file" => {
+            let path = workspace_path(root, input_str(input, "path")?)?;
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("failed to create {}", parent.display()))?;
+            }
+            let content = input_str(input, "content")?;
+            fs::write(&path, content)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            Ok(format!("wrote {} bytes", content.len()))
+        }
+        "edit_file" => {
+            let path = workspace_path(root, input_str(input, "path")?)?;
+            let search = input_str(input, "search")?;
+            let original = read_file(&path)?;
+            if !original.contains(search) {
+                return Err(anyhow!("search text not found in {}", path.display()));
+            }
+            let updated = original.replacen(search, input_str(input, "replace")?, 1);
+            fs::write(&path, updated)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            Ok("replaced 1 occurrence".to_string())
+        }
+        "apply_patch" => {
+            apply_patch(root, input_str(input, "patch")?)?;
+            Ok("patch applied".to_string())
+        }
+        "exec_shell" => exec_shell(root, input_str(input, "command")?),
+        other => Err(anyhow!("unsupported tool in offline eval: {other}")),
+    }
+}
+
+/// Load one scenario file, or every `.yaml`/`.yml` file in a directory
+/// (sorted by name).
+pub fn load_scenarios(path: &Path) -> Result<Vec<(PathBuf, Scenario)>> {
+    let files = if path.is_dir() {
+        let mut files = Vec::new();
+        for entry in
+            fs::read_dir(path).with_context(|| format!("failed to read {}", path.display()))?
+        {
+            let file = entry?.path();
+            if file
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| matches!(ext, "yaml" | "yml"))
+            {
+                files.push(file);
+            }
+        }
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+    if files.is_empty() {
+        return Err(anyhow!("no .yaml scenarios found in {}", path.display()));
+    }
+    files
+        .into_iter()
+        .map(|file| {
+            let source = fs::read_to_string(&file)
+                .with_context(|| format!("failed to read {}", file.display()))?;
+            let scenario =
+                Scenario::from_yaml(&source).with_context(|| format!("in {}", file.display()))?;
+            Ok((file, scenario))
+        })
+        .collect()
+}
+
+/// Run every scenario and collect the suite report.
+pub fn run_scenarios(scenarios: &[(PathBuf, Scenario)], max_output_chars: usize) -> SuiteReport {
+    let scenarios = scenarios
+        .iter()
+        .map(|(_, scenario)| {
+            scenario
+                .run(max_output_chars)
+                .unwrap_or_else(|err| ScenarioReport {
+                    name: scenario.name.clone(),
+                    description: scenario.description.clone(),
+                    passed: false,
+                    duration_ms: 0,
+                    steps: Vec::new(),
+                    failures: vec![format!("{err:#}")],
+                })
+        })
+        .collect::<Vec<_>>();
+    let passed = scenarios.iter().filter(|s| s.passed).count();
+    SuiteReport {
+        passed,
+        failed: scenarios.len() - passed,
+        scenarios,
+    }
+}
+
+/// Result of one tool call in a scenario run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScenarioStepReport {
+    pub tool: String,
+    pub success: bool,
+    pub output: Option<String>,
+    pub error: Option<String>,
+    /// Unmet expectations for this call.
+    pub failures: Vec<String>,
+}
+
+/// Result of one scenario run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScenarioReport {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub passed: bool,
+    pub duration_ms: u64,
+    pub steps: Vec<ScenarioStepReport>,
+    pub failures: Vec<String>,
+}
+
+/// Report for a scenario suite; written with `--report` and read back with
+/// `--baseline` for regression tracking.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuiteReport {
+    pub passed: usize,
+    pub failed: usize,
+    pub scenarios: Vec<ScenarioReport>,
+}
+
+/// Differences between a baseline suite report and the current one, by
+/// scenario name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SuiteComparison {
+    /// Passed in the baseline, fail now.
+    pub regressions: Vec<String>,
+    /// Failed in the baseline, pass now.
+    pub fixed: Vec<String>,
+    /// Not in the baseline.
+    pub added: Vec<String>,
+    /// In the baseline, not run now.
+    pub removed: Vec<String>,
+}
+
+impl SuiteReport {
+    /// Compare this run against `baseline`.
+    pub fn compare(&self, baseline: &SuiteReport) -> SuiteComparison {
+        let before: BTreeMap<&str, bool> = baseline
+            .scenarios
+            .iter()
+            .map(|s| (s.name.as_str(), s.passed))
+            .collect();
+        let now: BTreeMap<&str, bool> = self
+            .scenarios
+            .iter()
+            .map(|s| (s.name.as_str(), s.passed))
+            .collect();
+        let mut comparison = SuiteComparison::default();
+        for (name, passed) in &now {
+            match before.get(name) {
+                Some(true) if !passed => comparison.regressions.push((*name).to_string()),
+                Some(false) if *passed => comparison.fixed.push((*name).to_string()),
+                Some(_) => {}
+                None => comparison.added.push((*name).to_string()),
+            }
+        }
+        comparison.removed = before
+            .keys()
+            .filter(|name| !now.contains_key(*name))
+            .map(|name| (*name).to_string())
+            .collect();
+        comparison
+    }
+}
+
 fn truncate_output(value: &str, max_chars: usize) -> String {
     if value.chars().count() <= max_chars {
         return value.to_string();
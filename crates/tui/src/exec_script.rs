@@ -0,0 +1,552 @@
+//! `deepseek exec --interactive-script`: run several prompts, in order, in one
+//! engine session.
+//!
+//! A script is either newline-delimited prompts (blank lines and `#` comments
+//! are skipped) or a YAML list whose items are prompt strings or step tables
+//! with expectations:
+//!
+//! ```yaml
+//! - prompt: Add a unit test for parse_range
+//!   expect:
+//!     tools: [edit_file]
+//!     max_tool_errors: 0
+//! - prompt: Run the tests and summarise the result
+//!   expect:
+//!     contains: ["passed"]
+//!     not_contains: ["FAILED"]
+//! - Commit nothing; just list the files you changed.
+//! ```
+//!
+//! When the script comes from stdin in line mode, each prompt is sent as soon
+//! as its line arrives, so another process can drive the session. Output is
+//! JSONL on stdout: one `step` object per prompt, then a `script_done`
+//! summary. The command exits non-zero when any step fails.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, BufReader, Lines, Stdin};
+
+use crate::config::Config;
+use crate::core::engine::EngineHandle;
+use crate::core::events::{Event, TurnOutcomeStatus};
+use crate::error_taxonomy::{ToolErrorClass, ToolErrorStats, ToolFailure};
+use crate::models::{Message, SystemPrompt};
+
+/// One prompt of a script plus what its turn must satisfy.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptStep {
+    pub prompt: String,
+    #[serde(default)]
+    pub expect: StepExpectation,
+}
+
+/// Per-step checks. A step with no expectations passes when its turn
+/// completes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StepExpectation {
+    /// Turn status the step must end with; defaults to `completed`.
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Substrings the assistant's reply must contain.
+    #[serde(default)]
+    pub contains: Vec<String>,
+    /// Substrings the assistant's reply must not contain.
+    #[serde(default)]
+    pub not_contains: Vec<String>,
+    /// Tools that must be called at least once during the step.
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// Most failed tool calls the step may have.
+    #[serde(default)]
+    pub max_tool_errors: Option<usize>,
+}
+
+/// Parse a whole script: a YAML step list, or one prompt per line.
+pub fn parse_script(source: &str) -> Result<Vec<ScriptStep>> {
+    let steps = if is_yaml_list(source) {
+        let items: Vec<serde_yaml::Value> =
+            serde_yaml::from_str(source).context("invalid script YAML")?;
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| match item {
+                serde_yaml::Value::String(prompt) => Ok(ScriptStep {
+                    prompt,
+                    expect: StepExpectation::default(),
+                }),
+                item => serde_yaml::from_value(item)
+                    .with_context(|| format!("invalid script step {}", index + 1)),
+            })
+            .collect::<Result<Vec<_>>>()?
+    } else {
+        source.lines().filter_map(line_step).collect::<Vec<_>>()
+    };
+    if let Some(index) = steps.iter().position(|step| step.prompt.trim().is_empty()) {
+        bail!("script step {} has an empty prompt", index + 1);
+    }
+    Ok(steps)
+}
+
+/// A script is YAML when its first meaningful line starts a list item.
+fn is_yaml_list(source: &str) -> bool {
+    source
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .is_some_and(|line| line == "-" || line.starts_with("- "))
+}
+
+fn line_step(line: &str) -> Option<ScriptStep> {
+    let prompt = line.trim();
+    (!prompt.is_empty() && !prompt.starts_with('#')).then(|| ScriptStep {
+        prompt: prompt.to_string(),
+        expect: StepExpectation::default(),
+    })
+}
+
+/// Yields script steps from a file, or from stdin as lines arrive.
+struct ScriptSource {
+    pending: VecDeque<ScriptStep>,
+    /// Remaining stdin lines in line mode; read lazily, one prompt at a time.
+    stdin: Option<Lines<BufReader<Stdin>>>,
+}
+
+impl ScriptSource {
+    async fn open(path: &Path) -> Result<Self> {
+        if path != Path::new("-") {
+            let source = tokio::fs::read_to_string(path)
+                .await
+                .with_context(|| format!("could not read script {}", path.display()))?;
+            return Ok(Self {
+                pending: parse_script(&source)?.into(),
+                stdin: None,
+            });
+        }
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        // Read up to the first meaningful line to tell the two formats apart.
+        let mut head = String::new();
+        while let Some(line) = lines.next_line().await? {
+            head.push_str(&line);
+            head.push('\n');
+            let trimmed = line.trim();
+            if !trimmed.is_empty() && !trimmed.starts_with('#') {
+                break;
+            }
+        }
+        if is_yaml_list(&head) {
+            while let Some(line) = lines.next_line().await? {
+                head.push_str(&line);
+                head.push('\n');
+            }
+            return Ok(Self {
+                pending: parse_script(&head)?.into(),
+                stdin: None,
+            });
+        }
+        Ok(Self {
+            pending: parse_script(&head)?.into(),
+            stdin: Some(lines),
+        })
+    }
+
+    async fn next(&mut self) -> Result<Option<ScriptStep>> {
+        if let Some(step) = self.pending.pop_front() {
+            return Ok(Some(step));
+        }
+        let Some(lines) = self.stdin.as_mut() else {
+            return Ok(None);
+        };
+        while let Some(line) = lines.next_line().await? {
+            if let Some(step) = line_step(&line) {
+                return Ok(Some(step));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Tool call recorded during a step.
+#[derive(Debug, Clone, Serialize)]
+pub struct StepTool {
+    pub name: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<ToolErrorClass>,
+}
+
+/// What one turn produced.
+#[derive(Debug, Clone, Default)]
+pub struct TurnOutcome {
+    pub status: String,
+    pub output: String,
+    pub tools: Vec<StepTool>,
+    pub error: Option<String>,
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+}
+
+impl TurnOutcome {
+    fn tool_errors(&self) -> usize {
+        self.tools.iter().filter(|tool| !tool.success).count()
+    }
+}
+
+impl StepExpectation {
+    /// Unmet expectations, in a human-readable form.
+    #[must_use]
+    pub fn check(&self, outcome: &TurnOutcome) -> Vec<String> {
+        let mut failures = Vec::new();
+        let status = self.status.as_deref().unwrap_or("completed");
+        if !outcome.status.eq_ignore_ascii_case(status) {
+            failures.push(format!("expected status {status}, got {}", outcome.status));
+        }
+        for needle in &self.contains {
+            if !outcome.output.contains(needle.as_str()) {
+                failures.push(format!("reply does not contain {needle:?}"));
+            }
+        }
+        for needle in &self.not_contains {
+            if outcome.output.contains(needle.as_str()) {
+                failures.push(format!("reply contains {needle:?}"));
+            }
+        }
+        for tool in &self.tools {
+            if !outcome.tools.iter().any(|call| &call.name == tool) {
+                failures.push(format!("tool {tool} was not called"));
+            }
+        }
+        if let Some(max) = self.max_tool_errors
+            && outcome.tool_errors() > max
+        {
+            failures.push(format!(
+                "{} failed tool calls, at most {max} allowed",
+                outcome.tool_errors()
+            ));
+        }
+        failures
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ScriptEvent<'a> {
+    Step {
+        step: usize,
+        prompt: &'a str,
+        passed: bool,
+        failures: &'a [String],
+        status: &'a str,
+        output: &'a str,
+        tools: &'a [StepTool],
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<&'a str>,
+        input_tokens: u32,
+        output_tokens: u32,
+        duration_ms: u64,
+    },
+    ScriptDone {
+        steps: usize,
+        passed: usize,
+        failed: usize,
+        tool_error_classes: &'a ToolErrorStats,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        session_id: Option<&'a str>,
+    },
+}
+
+fn emit(event: &ScriptEvent<'_>) -> Result<()> {
+    println!("{}", serde_json::to_string(event)?);
+    Ok(())
+}
+
+/// Latest engine session state, kept to save the session once the run ends.
+#[derive(Default)]
+pub(crate) struct SessionSnapshot {
+    id: Option<String>,
+    messages: Vec<Message>,
+    system_prompt: Option<SystemPrompt>,
+    model: String,
+    workspace: PathBuf,
+}
+
+impl SessionSnapshot {
+    /// Save the session; returns its ID, or `None` when there is nothing to
+    /// save or saving failed (reported on stderr).
+    pub(crate) fn persist(&self, total_tokens: u64) -> Option<String> {
+        if self.messages.is_empty() {
+            return None;
+        }
+        crate::persist_exec_session(
+            &self.messages,
+            &self.model,
+            &self.workspace,
+            &self.system_prompt,
+            self.id.as_deref(),
+            total_tokens,
+        )
+        .map_err(|err| eprintln!("warning: failed to save session: {err:#}"))
+        .ok()
+    }
+}
+
+/// Options for [`run`].
+pub struct ScriptOptions {
+    pub script: PathBuf,
+    pub model: String,
+    pub workspace: PathBuf,
+    pub max_subagents: usize,
+    pub auto_approve: bool,
+}
+
+/// Run every step of the script in one engine session.
+pub async fn run(config: &Config, options: ScriptOptions) -> Result<()> {
+    let ScriptOptions {
+        script,
+        model,
+        workspace,
+        max_subagents,
+        auto_approve,
+    } = options;
+    let mut source = ScriptSource::open(&script).await?;
+
+    let mut engine: Option<EngineHandle> = None;
+    let mut session = SessionSnapshot::default();
+    let mut error_classes = ToolErrorStats::default();
+    let mut total_tokens = 0u64;
+    let (mut steps, mut passed) = (0usize, 0usize);
+
+    while let Some(step) = source.next().await? {
+        steps += 1;
+        let route = crate::resolve_cli_auto_route(config, &model, &step.prompt).await;
+        let handle = match engine.as_ref() {
+            Some(handle) => handle,
+            None => engine.insert(crate::core::engine::spawn_engine(
+                crate::exec_engine_config(
+                    config,
+                    &route.model,
+                    &workspace,
+                    max_subagents,
+                    auto_approve,
+                    auto_approve,
+                ),
+                config,
+            )),
+        };
+        let started = Instant::now();
+        handle
+            .send(crate::exec_send_message(
+                config,
+                &step.prompt,
+                route,
+                auto_approve,
+                auto_approve,
+            ))
+            .await?;
+        let outcome = drive_turn(handle, auto_approve, &mut session).await?;
+        total_tokens += u64::from(outcome.input_tokens) + u64::from(outcome.output_tokens);
+        for code in outcome.tools.iter().filter_map(|tool| tool.error_code) {
+            error_classes.record(code);
+        }
+
+        let failures = step.expect.check(&outcome);
+        if failures.is_empty() {
+            passed += 1;
+        }
+        emit(&ScriptEvent::Step {
+            step: steps,
+            prompt: &step.prompt,
+            passed: failures.is_empty(),
+            failures: &failures,
+            status: &outcome.status,
+            output: &outcome.output,
+            tools: &outcome.tools,
+            error: outcome.error.as_deref(),
+            input_tokens: outcome.input_tokens,
+            output_tokens: outcome.output_tokens,
+            duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        })?;
+    }
+
+    let session_id = match engine {
+        Some(handle) => {
+            let _ = handle.send(crate::core::ops::Op::Shutdown).await;
+            session.persist(total_tokens)
+        }
+        None => None,
+    };
+    let failed = steps - passed;
+    emit(&ScriptEvent::ScriptDone {
+        steps,
+        passed,
+        failed,
+        tool_error_classes: &error_classes,
+        session_id: session_id.as_deref(),
+    })?;
+    if steps == 0 {
+        bail!("script {} has no prompts", script.display());
+    }
+    if failed > 0 {
+        bail!("{failed} of {steps} script steps failed");
+    }
+    Ok(())
+}
+
+/// Feed engine events until the current turn completes.
+pub(crate) async fn drive_turn(
+    engine: &EngineHandle,
+    auto_approve: bool,
+    session: &mut SessionSnapshot,
+) -> Result<TurnOutcome> {
+    let mut outcome = TurnOutcome::default();
+    loop {
+        let event = {
+            let mut rx = engine.rx_event.write().await;
+            rx.recv().await
+        };
+        let Some(event) = event else {
+            bail!("engine stopped before the turn completed");
+        };
+        match event {
+            Event::MessageDelta { content, .. } => outcome.output.push_str(&content),
+            Event::ToolCallComplete { name, result, .. } => {
+                let (success, error_code) = match &result {
+                    Ok(output) if output.success => (true, None),
+                    Ok(output) => (false, Some(ToolFailure::from_failed_result(output).code)),
+                    Err(err) => (false, Some(ToolFailure::from_tool_error(err).code)),
+                };
+                outcome.tools.push(StepTool {
+                    name,
+                    success,
+                    error_code,
+                });
+            }
+            Event::ApprovalRequired { id, .. } => {
+                if auto_approve {
+                    let _ = engine.approve_tool_call(id).await;
+                } else {
+                    let _ = engine.deny_tool_call(id).await;
+                }
+            }
+            Event::ElevationRequired { tool_id, .. } => {
+                if auto_approve {
+                    let policy = crate::sandbox::SandboxPolicy::DangerFullAccess;
+                    let _ = engine.retry_tool_with_policy(tool_id, policy).await;
+                } else {
+                    let _ = engine.deny_tool_call(tool_id).await;
+                }
+            }
+            Event::Error { envelope, .. } => outcome.error = Some(envelope.message),
+            Event::SessionUpdated {
+                session_id,
+                messages,
+                system_prompt,
+                model,
+                workspace,
+            } => {
+                *session = SessionSnapshot {
+                    id: Some(session_id),
+                    messages,
+                    system_prompt,
+                    model,
+                    workspace,
+                };
+            }
+            Event::TurnComplete {
+                status,
+                error,
+                usage,
+            } => {
+                outcome.status = match status {
+                    TurnOutcomeStatus::Completed => "completed",
+                    TurnOutcomeStatus::Interrupted => "interrupted",
+                    TurnOutcomeStatus::Failed => "failed",
+                }
+                .to_string();
+                if error.is_some() {
+                    outcome.error = error;
+                }
+                outcome.input_tokens = usage.input_tokens;
+                outcome.output_tokens = usage.output_tokens;
+                return Ok(outcome);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_scripts_skip_blanks_and_comments() {
+        let steps = parse_script("# setup\nlist the files\n\n  run the tests  \n").unwrap();
+        let prompts: Vec<_> = steps.iter().map(|step| step.prompt.as_str()).collect();
+        assert_eq!(prompts, ["list the files", "run the tests"]);
+        assert!(
+            steps
+                .iter()
+                .all(|step| step.expect == StepExpectation::default())
+        );
+    }
+
+    #[test]
+    fn yaml_scripts_mix_plain_prompts_and_steps_with_expectations() {
+        let steps = parse_script(
+            "# ci\n- prompt: add a test\n  expect:\n    tools: [edit_file]\n    max_tool_errors: 0\n- summarise: the result\n",
+        );
+        // `summarise: the result` is a mapping without `prompt`, not a string.
+        assert!(steps.is_err());
+
+        let steps = parse_script(
+            "- prompt: add a test\n  expect:\n    tools: [edit_file]\n    contains: [done]\n- \"summarise: the result\"\n",
+        )
+        .unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].expect.tools, ["edit_file"]);
+        assert_eq!(steps[1].prompt, "summarise: the result");
+
+        assert!(parse_script("- prompt: x\n  expect:\n    contain: [typo]\n").is_err());
+        assert!(parse_script("- \"  \"\n").is_err());
+    }
+
+    #[test]
+    fn expectations_report_every_unmet_check() {
+        let expect = StepExpectation {
+            status: None,
+            contains: vec!["passed".to_string()],
+            not_contains: vec!["FAILED".to_string()],
+            tools: vec!["run_tests".to_string()],
+            max_tool_errors: Some(0),
+        };
+        let good = TurnOutcome {
+            status: "completed".to_string(),
+            output: "all 12 tests passed".to_string(),
+            tools: vec![StepTool {
+                name: "run_tests".to_string(),
+                success: true,
+                error_code: None,
+            }],
+            ..TurnOutcome::default()
+        };
+        assert!(expect.check(&good).is_empty());
+
+        let bad = TurnOutcome {
+            status: "failed".to_string(),
+            output: "1 FAILED".to_string(),
+            tools: vec![StepTool {
+                name: "exec_shell".to_string(),
+                success: false,
+                error_code: Some(ToolErrorClass::Failed),
+            }],
+            ..TurnOutcome::default()
+        };
+        assert_eq!(expect.check(&bad).len(), 5);
+    }
+}
@@ -6,6 +6,7 @@ pub mod decision;
 pub mod error;
 pub mod execpolicycheck;
 pub mod matcher;
+pub mod packs;
 pub mod parser;
 pub mod policy;
 pub mod rule;
@@ -17,6 +18,7 @@ pub use decision::Decision;
 pub use error::Error;
 pub use error::Result;
 pub use execpolicycheck::ExecPolicyCheckCommand;
+pub use packs::{ExecPolicyPacksCommand, ExecPolicyTestCommand};
 pub use parser::PolicyParser;
 pub use policy::Evaluation;
 pub use policy::Policy;
@@ -25,4 +27,5 @@ pub use rule::RuleMatch;
 pub use rule::RuleRef;
 pub use rules::{
     ExecPolicyConfig, ExecPolicyDecision, default_execpolicy_path, load_default_policy,
+    load_workspace_policy,
 };
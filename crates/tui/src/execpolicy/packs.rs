@@ -0,0 +1,362 @@
+//! Named execpolicy packs: shareable rule sets with embedded fixtures.
+//!
+//! A pack is a TOML file holding `[rules.<group>]` tables in the same shape
+//! as `execpolicy.toml`, an optional `description`, and `[[tests]]` fixtures
+//! pairing an example command with the decision the pack must reach:
+//!
+//! ```toml
+//! description = "Block history rewrites on shared branches"
+//!
+//! [rules.git]
+//! deny = ["git push --force *", "git push -f *"]
+//! allow = ["git push origin *"]
+//!
+//! [[tests]]
+//! command = "git push --force origin main"
+//! expect = "deny"
+//! ```
+//!
+//! Packs are looked up by name in the workspace's `.deepseek/execpolicy/packs/`
+//! (committed with the repo), then `~/.deepseek/execpolicy/packs/`, then the
+//! org layer's `execpolicy/packs/`. A `packs = [...]` list in
+//! `~/.deepseek/execpolicy.toml` enables packs everywhere; the same list in a
+//! workspace's `.deepseek/execpolicy.toml` enables them for that workspace.
+//! `deepseek execpolicy test <pack>` runs a pack's fixtures.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use clap::Parser;
+use serde::Deserialize;
+
+use super::rules::{ExecPolicyConfig, ExecPolicyDecision, RuleSet};
+
+/// Packs directory, relative to a workspace or `~/.deepseek`.
+pub const PACKS_DIR: &str = "execpolicy/packs";
+
+#[derive(Debug, Deserialize, Default)]
+pub struct PolicyPack {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleSet>,
+    #[serde(default)]
+    pub tests: Vec<PackFixture>,
+}
+
+/// An example command and the decision the pack must reach for it.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct PackFixture {
+    pub command: String,
+    pub expect: ExpectedDecision,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExpectedDecision {
+    Allow,
+    Deny,
+    Ask,
+}
+
+impl ExpectedDecision {
+    fn of(decision: &ExecPolicyDecision) -> Self {
+        match decision {
+            ExecPolicyDecision::Allow => Self::Allow,
+            ExecPolicyDecision::Deny(_) => Self::Deny,
+            ExecPolicyDecision::AskUser(_) => Self::Ask,
+        }
+    }
+}
+
+impl fmt::Display for ExpectedDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::Allow => "allow",
+            Self::Deny => "deny",
+            Self::Ask => "ask",
+        })
+    }
+}
+
+/// Outcome of one fixture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureResult {
+    pub fixture: PackFixture,
+    pub actual: ExecPolicyDecision,
+}
+
+impl FixtureResult {
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        ExpectedDecision::of(&self.actual) == self.fixture.expect
+    }
+}
+
+impl PolicyPack {
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read execpolicy pack {}", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("failed to parse execpolicy pack {}", path.display()))
+    }
+
+    /// The pack's rules on their own, as the fixtures see them.
+    #[must_use]
+    pub fn policy(&self) -> ExecPolicyConfig {
+        ExecPolicyConfig {
+            rules: self.rules.clone(),
+            ..ExecPolicyConfig::default()
+        }
+    }
+
+    /// Evaluate every fixture against the pack's own rules.
+    #[must_use]
+    pub fn run_fixtures(&self) -> Vec<FixtureResult> {
+        let policy = self.policy();
+        self.tests
+            .iter()
+            .map(|fixture| FixtureResult {
+                fixture: fixture.clone(),
+                actual: policy.evaluate(&fixture.command),
+            })
+            .collect()
+    }
+}
+
+/// Directories searched for packs, in priority order.
+#[must_use]
+pub fn pack_dirs(workspace: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![workspace.join(".deepseek").join(PACKS_DIR)];
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join(".deepseek").join(PACKS_DIR));
+    }
+    if let Some(org) = crate::config::org_layer().filter(|layer| layer.is_active()) {
+        dirs.push(org.execpolicy_packs_dir());
+    }
+    dirs
+}
+
+/// Resolve a pack name (or a path to a `.toml` file) to its file.
+pub fn find_pack(name: &str, dirs: &[PathBuf]) -> Result<PathBuf> {
+    let as_path = Path::new(name);
+    if as_path.extension().is_some_and(|ext| ext == "toml") || as_path.components().count() > 1 {
+        if as_path.is_file() {
+            return Ok(as_path.to_path_buf());
+        }
+        bail!("execpolicy pack file {} not found", as_path.display());
+    }
+    dirs.iter()
+        .map(|dir| dir.join(format!("{name}.toml")))
+        .find(|path| path.is_file())
+        .with_context(|| {
+            let searched: Vec<String> = dirs.iter().map(|dir| dir.display().to_string()).collect();
+            format!(
+                "execpolicy pack '{name}' not found (searched {})",
+                searched.join(", ")
+            )
+        })
+}
+
+/// Every pack found in `dirs` as `(name, path)`; a name in an earlier
+/// directory shadows the same name later on.
+#[must_use]
+pub fn available_packs(dirs: &[PathBuf]) -> Vec<(String, PathBuf)> {
+    let mut packs: BTreeMap<String, PathBuf> = BTreeMap::new();
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.extension().is_some_and(|ext| ext == "toml")
+                && let Some(stem) = path.file_stem().and_then(|stem| stem.to_str())
+            {
+                packs.entry(stem.to_string()).or_insert(path);
+            }
+        }
+    }
+    packs.into_iter().collect()
+}
+
+/// Add the rules of each pack in `names` to `policy` under
+/// `pack/<name>/<group>`.
+pub fn apply_packs(
+    policy: &mut ExecPolicyConfig,
+    names: &[String],
+    dirs: &[PathBuf],
+) -> Result<()> {
+    for name in names {
+        let pack = PolicyPack::from_path(&find_pack(name, dirs)?)?;
+        for (group, rules) in pack.rules {
+            policy.rules.insert(format!("pack/{name}/{group}"), rules);
+        }
+    }
+    Ok(())
+}
+
+/// Run the fixtures embedded in an execpolicy pack.
+#[derive(Debug, Parser, Clone)]
+pub struct ExecPolicyTestCommand {
+    /// Pack name, or a path to a pack `.toml` file.
+    #[arg(value_name = "PACK")]
+    pub pack: String,
+
+    /// Workspace whose `.deepseek/execpolicy/packs/` is searched first
+    /// (defaults to the current directory).
+    #[arg(long, value_name = "DIR")]
+    pub workspace: Option<PathBuf>,
+}
+
+impl ExecPolicyTestCommand {
+    pub fn run(&self) -> Result<()> {
+        let workspace = match &self.workspace {
+            Some(dir) => dir.clone(),
+            None => std::env::current_dir()?,
+        };
+        let path = find_pack(&self.pack, &pack_dirs(&workspace))?;
+        let pack = PolicyPack::from_path(&path)?;
+        println!("{} ({})", self.pack, path.display());
+        let results = pack.run_fixtures();
+        if results.is_empty() {
+            println!("  no fixtures; add [[tests]] entries to verify this pack");
+            return Ok(());
+        }
+        for result in &results {
+            print!(
+                "  {:<4}  {:<5}  {}",
+                if result.passed() { "ok" } else { "FAIL" },
+                result.fixture.expect,
+                result.fixture.command
+            );
+            match (&result.actual, result.passed()) {
+                (_, true) => println!(),
+                (ExecPolicyDecision::Allow, false) => println!("  (got allow)"),
+                (ExecPolicyDecision::Deny(reason) | ExecPolicyDecision::AskUser(reason), false) => {
+                    println!("  (got {}: {reason})", ExpectedDecision::of(&result.actual));
+                }
+            }
+        }
+        let failed = results.iter().filter(|result| !result.passed()).count();
+        println!("{} passed, {failed} failed", results.len() - failed);
+        if failed > 0 {
+            bail!("{failed} of {} fixtures failed", results.len());
+        }
+        Ok(())
+    }
+}
+
+/// List the packs available to a workspace and whether each is enabled.
+#[derive(Debug, Parser, Clone)]
+pub struct ExecPolicyPacksCommand {
+    /// Workspace to inspect (defaults to the current directory).
+    #[arg(long, value_name = "DIR")]
+    pub workspace: Option<PathBuf>,
+}
+
+impl ExecPolicyPacksCommand {
+    pub fn run(&self) -> Result<()> {
+        let workspace = match &self.workspace {
+            Some(dir) => dir.clone(),
+            None => std::env::current_dir()?,
+        };
+        let enabled = super::rules::enabled_packs(&workspace)?;
+        let packs = available_packs(&pack_dirs(&workspace));
+        if packs.is_empty() && enabled.is_empty() {
+            println!("No execpolicy packs found.");
+            return Ok(());
+        }
+        for (name, path) in &packs {
+            let description = PolicyPack::from_path(path)
+                .ok()
+                .and_then(|pack| pack.description)
+                .unwrap_or_default();
+            let marker = if enabled.contains(name) { "on " } else { "off" };
+            println!("{marker}  {name:<24} {description}");
+            println!("     {}", path.display());
+        }
+        for name in enabled
+            .iter()
+            .filter(|name| !packs.iter().any(|(n, _)| n == *name))
+        {
+            println!("on   {name:<24} (missing)");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACK: &str = r#"
+description = "No force pushes"
+
+[rules.git]
+deny = ["git push --force *"]
+allow = ["git push origin *"]
+
+[[tests]]
+command = "git push --force origin main"
+expect = "deny"
+
+[[tests]]
+command = "git push origin feature"
+expect = "allow"
+
+[[tests]]
+command = "rm -rf build"
+expect = "allow"
+"#;
+
+    #[test]
+    fn fixtures_report_expected_and_actual_decisions() {
+        let pack: PolicyPack = toml::from_str(PACK).unwrap();
+        let results = pack.run_fixtures();
+        let passed: Vec<bool> = results.iter().map(FixtureResult::passed).collect();
+        assert_eq!(passed, [true, true, false]);
+        assert!(matches!(results[2].actual, ExecPolicyDecision::AskUser(_)));
+        assert!(
+            toml::from_str::<PolicyPack>("[[tests]]\ncommand = \"ls\"\nexpect = \"maybe\"\n")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn packs_resolve_by_name_with_earlier_dirs_winning() {
+        let tmp = tempfile::tempdir().unwrap();
+        let workspace_dir = tmp.path().join("ws");
+        let user_dir = tmp.path().join("user");
+        std::fs::create_dir_all(&workspace_dir).unwrap();
+        std::fs::create_dir_all(&user_dir).unwrap();
+        std::fs::write(workspace_dir.join("git.toml"), PACK).unwrap();
+        std::fs::write(user_dir.join("git.toml"), "").unwrap();
+        std::fs::write(
+            user_dir.join("ci.toml"),
+            "[rules.ci]\ndeny = [\"curl *\"]\n",
+        )
+        .unwrap();
+        let dirs = vec![workspace_dir.clone(), user_dir.clone()];
+
+        assert_eq!(
+            find_pack("git", &dirs).unwrap(),
+            workspace_dir.join("git.toml")
+        );
+        assert!(find_pack("missing", &dirs).is_err());
+        let names: Vec<String> = available_packs(&dirs)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, ["ci", "git"]);
+
+        let mut policy = ExecPolicyConfig::default();
+        apply_packs(&mut policy, &["ci".to_string(), "git".to_string()], &dirs).unwrap();
+        assert!(policy.rules.contains_key("pack/git/git"));
+        assert!(matches!(
+            policy.evaluate("curl https://example.com"),
+            ExecPolicyDecision::Deny(reason) if reason.contains("pack/ci/ci")
+        ));
+        assert!(apply_packs(&mut policy, &["missing".to_string()], &dirs).is_err());
+    }
+}
@@ -7,6 +7,7 @@ use anyhow::{Context, Result};
 use serde::Deserialize;
 
 use super::matcher::pattern_matches;
+use super::packs;
 use crate::command_safety::prefix_allow_matches;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -20,9 +21,12 @@ pub enum ExecPolicyDecision {
 pub struct ExecPolicyConfig {
     #[serde(default)]
     pub rules: BTreeMap<String, RuleSet>,
+    /// Named packs to enable; see [`super::packs`].
+    #[serde(default)]
+    pub packs: Vec<String>,
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Default)]
 pub struct RuleSet {
     #[serde(default)]
     pub allow: Vec<String>,
@@ -73,14 +77,90 @@ pub fn default_execpolicy_path() -> Option<PathBuf> {
     dirs::home_dir().map(|home| home.join(".deepseek").join("execpolicy.toml"))
 }
 
-pub fn load_default_policy() -> Result<Option<ExecPolicyConfig>> {
-    let Some(path) = default_execpolicy_path() else {
+/// Per-workspace `execpolicy.toml`, committed with the repository.
+#[must_use]
+pub fn workspace_execpolicy_path(workspace: &Path) -> PathBuf {
+    workspace.join(".deepseek").join("execpolicy.toml")
+}
+
+/// [`load_default_policy`] plus the workspace's `execpolicy.toml` (groups
+/// added as `workspace/<group>`) and every enabled pack (`pack/<name>/<group>`).
+/// A pack that is enabled but cannot be found is an error, so a missing pack
+/// never silently drops its deny rules.
+pub fn load_workspace_policy(workspace: &Path) -> Result<Option<ExecPolicyConfig>> {
+    let Some(mut policy) = merge_workspace_policy(workspace)? else {
         return Ok(None);
     };
-    if !path.exists() {
-        return Ok(None);
+    let names = std::mem::take(&mut policy.packs);
+    packs::apply_packs(&mut policy, &names, &packs::pack_dirs(workspace))?;
+    Ok(Some(policy))
+}
+
+/// Packs enabled for `workspace`, from the user, org, and workspace files.
+pub fn enabled_packs(workspace: &Path) -> Result<Vec<String>> {
+    Ok(merge_workspace_policy(workspace)?
+        .map(|policy| policy.packs)
+        .unwrap_or_default())
+}
+
+fn merge_workspace_policy(workspace: &Path) -> Result<Option<ExecPolicyConfig>> {
+    let base = load_default_policy()?;
+    let path = workspace_execpolicy_path(workspace);
+    let local = if path.exists() {
+        Some(ExecPolicyConfig::from_path(&path)?)
+    } else {
+        None
+    };
+    let Some(local) = local else {
+        return Ok(base.map(dedupe_packs));
+    };
+    let mut merged = base.unwrap_or_default();
+    for (group, rules) in local.rules {
+        merged.rules.insert(format!("workspace/{group}"), rules);
     }
-    ExecPolicyConfig::from_path(&path).map(Some)
+    merged.packs.extend(local.packs);
+    Ok(Some(dedupe_packs(merged)))
+}
+
+fn dedupe_packs(mut policy: ExecPolicyConfig) -> ExecPolicyConfig {
+    let mut seen = std::collections::HashSet::new();
+    policy.packs.retain(|name| seen.insert(name.clone()));
+    policy
+}
+
+/// Load the user's `execpolicy.toml` plus the org layer's, if synced.
+///
+/// Org rule groups are added under an `org/` prefix so a user group with the
+/// same name cannot replace them; because deny rules are checked across every
+/// group first, org denies always apply.
+pub fn load_default_policy() -> Result<Option<ExecPolicyConfig>> {
+    let user = match default_execpolicy_path() {
+        Some(path) if path.exists() => Some(ExecPolicyConfig::from_path(&path)?),
+        _ => None,
+    };
+    let org = match crate::config::org_layer()
+        .filter(|layer| layer.is_active())
+        .map(|layer| layer.execpolicy_path())
+    {
+        Some(path) if path.exists() => Some(ExecPolicyConfig::from_path(&path)?),
+        _ => None,
+    };
+    Ok(merge_org_policy(user, org))
+}
+
+fn merge_org_policy(
+    user: Option<ExecPolicyConfig>,
+    org: Option<ExecPolicyConfig>,
+) -> Option<ExecPolicyConfig> {
+    let Some(org) = org else {
+        return user;
+    };
+    let mut merged = user.unwrap_or_default();
+    for (group, rules) in org.rules {
+        merged.rules.insert(format!("org/{group}"), rules);
+    }
+    merged.packs.extend(org.packs);
+    Some(merged)
 }
 
 #[cfg(test)]
@@ -106,6 +186,7 @@ mod tests {
                     },
                 ),
             ]),
+            ..Default::default()
         };
 
         assert!(matches!(
@@ -126,6 +207,67 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn org_deny_rules_survive_a_same_named_user_group() {
+        let user = ExecPolicyConfig::from_str("[rules.git]\nallow = [\"git push *\"]\n").unwrap();
+        let org =
+            ExecPolicyConfig::from_str("[rules.git]\ndeny = [\"git push --force *\"]\n").unwrap();
+
+        let merged = merge_org_policy(Some(user), Some(org)).unwrap();
+        assert!(merged.rules.contains_key("org/git"));
+        assert!(matches!(
+            merged.evaluate("git push --force origin main"),
+            ExecPolicyDecision::Deny(reason) if reason.contains("org/git")
+        ));
+        assert_eq!(
+            merged.evaluate("git push origin main"),
+            ExecPolicyDecision::Allow
+        );
+        assert!(merge_org_policy(None, None).is_none());
+    }
+
+    #[test]
+    fn workspace_file_adds_its_rules_and_enabled_packs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let workspace = tmp.path();
+        let packs_dir = workspace.join(".deepseek").join(packs::PACKS_DIR);
+        std::fs::create_dir_all(&packs_dir).unwrap();
+        std::fs::write(
+            packs_dir.join("team.toml"),
+            "[rules.git]\ndeny = [\"git push --force *\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            workspace_execpolicy_path(workspace),
+            "packs = [\"team\"]\n[rules.local]\ndeny = [\"make deploy\"]\n",
+        )
+        .unwrap();
+
+        let policy = load_workspace_policy(workspace).unwrap().unwrap();
+        assert!(matches!(
+            policy.evaluate("git push --force origin main"),
+            ExecPolicyDecision::Deny(reason) if reason.contains("pack/team/git")
+        ));
+        assert!(matches!(
+            policy.evaluate("make deploy"),
+            ExecPolicyDecision::Deny(reason) if reason.contains("workspace/local")
+        ));
+        assert!(
+            enabled_packs(workspace)
+                .unwrap()
+                .contains(&"team".to_string())
+        );
+
+        // An enabled pack that cannot be found fails the load instead of
+        // silently dropping its rules.
+        std::fs::write(
+            workspace_execpolicy_path(workspace),
+            "packs = [\"team\", \"absent\"]\n",
+        )
+        .unwrap();
+        assert!(load_workspace_policy(workspace).is_err());
+    }
+
     #[test]
     fn test_prefix_rule_allows_git_status_with_flags() {
         // Arity-aware: `allow = ["git status"]` must match `git status -s`.
@@ -137,6 +279,7 @@ mod tests {
                     deny: vec![],
                 },
             )]),
+            ..Default::default()
         };
 
         assert!(matches!(
@@ -164,6 +307,7 @@ mod tests {
                     deny: vec![],
                 },
             )]),
+            ..Default::default()
         };
 
         assert!(matches!(
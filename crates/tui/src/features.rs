@@ -46,6 +46,10 @@ pub enum Feature {
     ExecPolicy,
     /// Enable vision model for image analysis.
     VisionModel,
+    /// Enable the `capture_terminal` tmux/screen scrollback tool.
+    TerminalCapture,
+    /// Enable the capacity-controller guardrails (refresh / replan).
+    CapacityController,
 }
 
 impl fmt::Display for Stage {
@@ -67,6 +71,11 @@ impl Feature {
         self.info().default_enabled
     }
 
+    /// Values this feature accepts in its `[features.<key>]` table.
+    pub fn params(self) -> &'static [FeatureParam] {
+        self.info().params
+    }
+
     fn info(self) -> &'static FeatureSpec {
         FEATURES
             .iter()
@@ -75,10 +84,78 @@ impl Feature {
     }
 }
 
-/// Holds the effective set of enabled features.
+/// Type of a value declared in [`FeatureSpec::params`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureValueKind {
+    Bool,
+    Integer,
+    Float,
+    String,
+}
+
+impl FeatureValueKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Bool => "bool",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::String => "string",
+        }
+    }
+}
+
+/// A typed value carried by a feature, e.g. `low_risk_max = 0.4`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum FeatureValue {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
+
+impl FeatureValue {
+    /// Convert a TOML value to `kind`. Integers are accepted for floats.
+    fn from_toml(kind: FeatureValueKind, value: &toml::Value) -> Option<Self> {
+        match (kind, value) {
+            (FeatureValueKind::Bool, toml::Value::Boolean(v)) => Some(Self::Bool(*v)),
+            (FeatureValueKind::Integer, toml::Value::Integer(v)) => Some(Self::Integer(*v)),
+            (FeatureValueKind::Float, toml::Value::Float(v)) => Some(Self::Float(*v)),
+            #[allow(clippy::cast_precision_loss)]
+            (FeatureValueKind::Float, toml::Value::Integer(v)) => Some(Self::Float(*v as f64)),
+            (FeatureValueKind::String, toml::Value::String(v)) => Some(Self::String(v.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FeatureValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(v) => write!(f, "{v}"),
+            Self::Integer(v) => write!(f, "{v}"),
+            Self::Float(v) => write!(f, "{v}"),
+            Self::String(v) => write!(f, "{v:?}"),
+        }
+    }
+}
+
+/// A value a feature accepts alongside `enabled` in its config table.
+#[derive(Debug, Clone, Copy)]
+pub struct FeatureParam {
+    pub key: &'static str,
+    pub kind: FeatureValueKind,
+    pub description: &'static str,
+}
+
+/// Holds the effective set of enabled features and their configured values.
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct Features {
     enabled: BTreeSet<Feature>,
+    /// Features named in config (as a bool or a table), as opposed to
+    /// running on their built-in default.
+    configured: BTreeSet<Feature>,
+    values: BTreeMap<Feature, BTreeMap<&'static str, FeatureValue>>,
 }
 
 impl Features {
@@ -90,7 +167,10 @@ impl Features {
                 set.insert(spec.id);
             }
         }
-        Self { enabled: set }
+        Self {
+            enabled: set,
+            ..Self::default()
+        }
     }
 
     pub fn enabled(&self, feature: Feature) -> bool {
@@ -107,18 +187,101 @@ impl Features {
         self
     }
 
-    pub fn apply_map(&mut self, entries: &BTreeMap<String, bool>) {
-        for (key, enabled) in entries {
-            if let Some(feature) = feature_from_key(key) {
-                if *enabled {
+    /// Apply `[features]` entries. Unknown features, unknown value keys, and
+    /// mistyped values are skipped here; [`validate_feature_entries`] reports
+    /// them when the config is loaded.
+    pub fn apply_map(&mut self, entries: &BTreeMap<String, FeatureEntry>) {
+        for (key, entry) in entries {
+            let Some(feature) = feature_from_key(key) else {
+                continue;
+            };
+            self.configured.insert(feature);
+            match entry.enabled() {
+                Some(true) => {
                     self.enable(feature);
-                } else {
+                }
+                Some(false) => {
                     self.disable(feature);
                 }
+                None => {}
+            }
+            let FeatureEntry::Table(table) = entry else {
+                continue;
+            };
+            for param in feature.params() {
+                if let Some(value) = table
+                    .values
+                    .get(param.key)
+                    .and_then(|raw| FeatureValue::from_toml(param.kind, raw))
+                {
+                    self.values
+                        .entry(feature)
+                        .or_default()
+                        .insert(param.key, value);
+                }
             }
         }
     }
 
+    /// Whether config set this feature explicitly.
+    pub fn is_configured(&self, feature: Feature) -> bool {
+        self.configured.contains(&feature)
+    }
+
+    /// Configured value for `key`, or `None` when it is unset.
+    pub fn value(&self, feature: Feature, key: &str) -> Option<&FeatureValue> {
+        self.values.get(&feature)?.get(key)
+    }
+
+    pub fn bool_value(&self, feature: Feature, key: &str) -> Option<bool> {
+        match self.value(feature, key)? {
+            FeatureValue::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn int_value(&self, feature: Feature, key: &str) -> Option<i64> {
+        match self.value(feature, key)? {
+            FeatureValue::Integer(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn float_value(&self, feature: Feature, key: &str) -> Option<f64> {
+        match self.value(feature, key)? {
+            FeatureValue::Float(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn str_value(&self, feature: Feature, key: &str) -> Option<&str> {
+        match self.value(feature, key)? {
+            FeatureValue::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Configured values of `feature` in declaration order, for display.
+    pub fn values(&self, feature: Feature) -> Vec<(&'static str, &FeatureValue)> {
+        let Some(values) = self.values.get(&feature) else {
+            return Vec::new();
+        };
+        feature
+            .params()
+            .iter()
+            .filter_map(|param| values.get(param.key).map(|value| (param.key, value)))
+            .collect()
+    }
+
+    /// `key=value` pairs joined with spaces; empty when nothing is set.
+    pub fn values_summary(&self, feature: Feature) -> String {
+        self.values(feature)
+            .into_iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     pub fn enabled_features(&self) -> Vec<Feature> {
         let mut list: Vec<_> = self.enabled.iter().copied().collect();
         list.sort();
@@ -143,24 +306,156 @@ pub fn feature_spec_by_key(key: &str) -> Option<&'static FeatureSpec> {
 }
 
 pub fn render_feature_table(features: &Features) -> String {
-    let mut output = String::from("feature\tstage\tenabled\n");
+    let mut output = String::from("feature\tstage\tenabled\tvalues\n");
     for spec in FEATURES {
         let _ = writeln!(
             output,
-            "{}\t{}\t{}",
+            "{}\t{}\t{}\t{}",
             spec.key,
             spec.stage,
-            features.enabled(spec.id)
+            features.enabled(spec.id),
+            features.values_summary(spec.id)
         );
     }
     output
 }
 
+/// Machine-readable form of [`render_feature_table`] for
+/// `deepseek features list --json`.
+pub fn render_feature_json(features: &Features) -> serde_json::Value {
+    let list = FEATURES
+        .iter()
+        .map(|spec| {
+            let values: serde_json::Map<String, serde_json::Value> = features
+                .values(spec.id)
+                .into_iter()
+                .map(|(key, value)| {
+                    (
+                        key.to_string(),
+                        serde_json::to_value(value).unwrap_or_default(),
+                    )
+                })
+                .collect();
+            let params: Vec<_> = spec
+                .params
+                .iter()
+                .map(|param| {
+                    serde_json::json!({
+                        "key": param.key,
+                        "type": param.kind.as_str(),
+                        "description": param.description,
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "key": spec.key,
+                "stage": spec.stage.as_str(),
+                "enabled": features.enabled(spec.id),
+                "default_enabled": spec.default_enabled,
+                "configured": features.is_configured(spec.id),
+                "values": values,
+                "params": params,
+            })
+        })
+        .collect::<Vec<_>>();
+    serde_json::Value::Array(list)
+}
+
+/// Check `[features]` entries against the registry: the feature must exist,
+/// and every table key must be `enabled` or a declared param of the right
+/// type.
+pub fn validate_feature_entries(entries: &BTreeMap<String, FeatureEntry>) -> Result<(), String> {
+    for (key, entry) in entries {
+        let Some(spec) = feature_spec_by_key(key) else {
+            return Err(format!("Unknown feature flag: {key}"));
+        };
+        let FeatureEntry::Table(table) = entry else {
+            continue;
+        };
+        for (name, raw) in &table.values {
+            let Some(param) = spec.params.iter().find(|param| param.key == name) else {
+                let known = std::iter::once("enabled")
+                    .chain(spec.params.iter().map(|param| param.key))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return Err(format!(
+                    "Unknown value features.{key}.{name} (expected one of: {known})"
+                ));
+            };
+            if FeatureValue::from_toml(param.kind, raw).is_none() {
+                return Err(format!(
+                    "features.{key}.{name} must be of type {}",
+                    param.kind.as_str()
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Deserializable features table for TOML.
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct FeaturesToml {
     #[serde(flatten)]
-    pub entries: BTreeMap<String, bool>,
+    pub entries: BTreeMap<String, FeatureEntry>,
+}
+
+/// One `[features]` entry: a plain toggle (`mcp = false`) or a table with
+/// typed values (`capacity_controller = { enabled = true, low_risk_max = 0.4 }`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum FeatureEntry {
+    Toggle(bool),
+    Table(FeatureTable),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct FeatureTable {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(flatten)]
+    pub values: BTreeMap<String, toml::Value>,
+}
+
+impl FeatureEntry {
+    /// Explicit on/off state, if the entry sets one.
+    pub fn enabled(&self) -> Option<bool> {
+        match self {
+            Self::Toggle(enabled) => Some(*enabled),
+            Self::Table(table) => table.enabled,
+        }
+    }
+
+    /// Set the on/off state, keeping any configured values.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        match self {
+            Self::Toggle(current) => *current = enabled,
+            Self::Table(table) => table.enabled = Some(enabled),
+        }
+    }
+
+    /// Layer `other` (e.g. a profile's entry) over `self`: its on/off state
+    /// wins when set, and its values replace same-named ones.
+    pub fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Table(mut base), Self::Table(over)) => {
+                if over.enabled.is_some() {
+                    base.enabled = over.enabled;
+                }
+                base.values.extend(over.values);
+                Self::Table(base)
+            }
+            (Self::Table(mut base), Self::Toggle(enabled)) => {
+                base.enabled = Some(enabled);
+                Self::Table(base)
+            }
+            (Self::Toggle(enabled), Self::Table(mut over)) => {
+                over.enabled = over.enabled.or(Some(enabled));
+                Self::Table(over)
+            }
+            (Self::Toggle(_), over @ Self::Toggle(_)) => over,
+        }
+    }
 }
 
 /// Single registry of all feature definitions.
@@ -170,6 +465,7 @@ pub struct FeatureSpec {
     pub key: &'static str,
     pub stage: Stage,
     pub default_enabled: bool,
+    pub params: &'static [FeatureParam],
 }
 
 pub const FEATURES: &[FeatureSpec] = &[
@@ -178,42 +474,89 @@ pub const FEATURES: &[FeatureSpec] = &[
         key: "shell_tool",
         stage: Stage::Stable,
         default_enabled: true,
+        params: &[],
     },
     FeatureSpec {
         id: Feature::Subagents,
         key: "subagents",
         stage: Stage::Experimental,
         default_enabled: true,
+        params: &[],
     },
     FeatureSpec {
         id: Feature::WebSearch,
         key: "web_search",
         stage: Stage::Experimental,
         default_enabled: true,
+        params: &[],
     },
     FeatureSpec {
         id: Feature::ApplyPatch,
         key: "apply_patch",
         stage: Stage::Experimental,
         default_enabled: true,
+        params: &[],
     },
     FeatureSpec {
         id: Feature::Mcp,
         key: "mcp",
         stage: Stage::Experimental,
         default_enabled: true,
+        params: &[],
     },
     FeatureSpec {
         id: Feature::ExecPolicy,
         key: "exec_policy",
         stage: Stage::Experimental,
         default_enabled: true,
+        params: &[],
     },
     FeatureSpec {
         id: Feature::VisionModel,
         key: "vision_model",
         stage: Stage::Experimental,
         default_enabled: false,
+        params: &[],
+    },
+    FeatureSpec {
+        id: Feature::TerminalCapture,
+        key: "terminal_capture",
+        stage: Stage::Experimental,
+        default_enabled: false,
+        params: &[],
+    },
+    FeatureSpec {
+        id: Feature::CapacityController,
+        key: "capacity_controller",
+        stage: Stage::Experimental,
+        default_enabled: false,
+        params: &[
+            FeatureParam {
+                key: "low_risk_max",
+                kind: FeatureValueKind::Float,
+                description: "Highest risk score treated as low risk",
+            },
+            FeatureParam {
+                key: "medium_risk_max",
+                kind: FeatureValueKind::Float,
+                description: "Highest risk score treated as medium risk",
+            },
+            FeatureParam {
+                key: "refresh_cooldown_turns",
+                kind: FeatureValueKind::Integer,
+                description: "Turns between targeted context refreshes",
+            },
+            FeatureParam {
+                key: "replan_cooldown_turns",
+                kind: FeatureValueKind::Integer,
+                description: "Turns between verify-and-replan interventions",
+            },
+            FeatureParam {
+                key: "min_turns_before_guardrail",
+                kind: FeatureValueKind::Integer,
+                description: "Turns before any intervention may fire",
+            },
+        ],
     },
 ];
 
@@ -225,9 +568,9 @@ mod tests {
     fn apply_map_toggles_known_features_and_ignores_unknown_keys() {
         let mut features = Features::with_defaults();
         let entries = BTreeMap::from([
-            ("mcp".to_string(), false),
-            ("shell_tool".to_string(), false),
-            ("not_real".to_string(), false),
+            ("mcp".to_string(), FeatureEntry::Toggle(false)),
+            ("shell_tool".to_string(), FeatureEntry::Toggle(false)),
+            ("not_real".to_string(), FeatureEntry::Toggle(false)),
         ]);
 
         features.apply_map(&entries);
@@ -245,8 +588,78 @@ mod tests {
         let table = render_feature_table(&features);
         let lines = table.lines().collect::<Vec<_>>();
 
-        assert_eq!(lines.first(), Some(&"feature\tstage\tenabled"));
-        assert!(lines.contains(&"shell_tool\tstable\ttrue"));
-        assert!(lines.contains(&"mcp\texperimental\tfalse"));
+        assert_eq!(lines.first(), Some(&"feature\tstage\tenabled\tvalues"));
+        assert!(lines.contains(&"shell_tool\tstable\ttrue\t"));
+        assert!(lines.contains(&"mcp\texperimental\tfalse\t"));
+    }
+
+    #[test]
+    fn feature_tables_carry_typed_values() {
+        let toml: FeaturesToml = toml::from_str(
+            "mcp = false\ncapacity_controller = { enabled = true, low_risk_max = 1, refresh_cooldown_turns = 4 }\n",
+        )
+        .expect("parse");
+        validate_feature_entries(&toml.entries).expect("valid");
+
+        let mut features = Features::with_defaults();
+        features.apply_map(&toml.entries);
+        assert!(!features.enabled(Feature::Mcp));
+        assert!(features.enabled(Feature::CapacityController));
+        assert!(features.is_configured(Feature::CapacityController));
+        assert!(!features.is_configured(Feature::ShellTool));
+        // Integers are accepted where a float is declared.
+        assert_eq!(
+            features.float_value(Feature::CapacityController, "low_risk_max"),
+            Some(1.0)
+        );
+        assert_eq!(
+            features.int_value(Feature::CapacityController, "refresh_cooldown_turns"),
+            Some(4)
+        );
+        assert_eq!(
+            features.values_summary(Feature::CapacityController),
+            "low_risk_max=1 refresh_cooldown_turns=4"
+        );
+
+        let json = render_feature_json(&features);
+        let entry = json
+            .as_array()
+            .and_then(|list| list.iter().find(|f| f["key"] == "capacity_controller"))
+            .expect("capacity entry");
+        assert_eq!(entry["enabled"], true);
+        assert_eq!(entry["values"]["refresh_cooldown_turns"], 4);
+        assert_eq!(entry["params"][0]["type"], "float");
+    }
+
+    #[test]
+    fn validation_rejects_unknown_or_mistyped_values() {
+        let parse = |raw: &str| toml::from_str::<FeaturesToml>(raw).expect("parse").entries;
+        let err = validate_feature_entries(&parse("capacity_controller = { threshold = 0.8 }"))
+            .unwrap_err();
+        assert!(
+            err.contains("features.capacity_controller.threshold"),
+            "{err}"
+        );
+        let err = validate_feature_entries(&parse(
+            "capacity_controller = { refresh_cooldown_turns = \"often\" }",
+        ))
+        .unwrap_err();
+        assert!(err.contains("must be of type integer"), "{err}");
+        assert!(validate_feature_entries(&parse("mcp = { enabled = false }")).is_ok());
+        assert!(validate_feature_entries(&parse("nope = true")).is_err());
+    }
+
+    #[test]
+    fn entry_merge_layers_profile_over_base() {
+        let base = FeatureEntry::Table(FeatureTable {
+            enabled: Some(true),
+            values: BTreeMap::from([("low_risk_max".to_string(), toml::Value::Float(0.3))]),
+        });
+        let merged = base.merge(FeatureEntry::Toggle(false));
+        assert_eq!(merged.enabled(), Some(false));
+        let FeatureEntry::Table(table) = merged else {
+            panic!("table kept");
+        };
+        assert_eq!(table.values.len(), 1);
     }
 }
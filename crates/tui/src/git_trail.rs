@@ -0,0 +1,341 @@
+//! Turn-level audit trail on a dedicated git branch.
+//!
+//! With `[trail] enabled = true`, every agent turn that ran tools and left
+//! the workspace different from the last trail commit is recorded as one
+//! commit on `deepseek/trail` (configurable). The first trail commit sits on
+//! top of the current `HEAD`, so `git log HEAD..deepseek/trail` shows the
+//! agent's work turn by turn and `git cherry-pick` takes whatever is worth
+//! keeping.
+//!
+//! Commits are built with plumbing against a private index file
+//! (`<git-dir>/deepseek-trail.index`): `HEAD`, the user's index, and the
+//! working tree are never touched, and `.gitignore` is honoured because the
+//! tree comes from `git add -A`. Edits the user makes between turns are
+//! included in the next trail commit, since the trail records workspace
+//! state rather than individual tool writes.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// Branch used when `[trail] branch` is unset.
+pub const DEFAULT_TRAIL_BRANCH: &str = "deepseek/trail";
+
+const TRAIL_AUTHOR_NAME: &str = "DeepSeek TUI";
+const TRAIL_AUTHOR_EMAIL: &str = "trail@deepseek-tui.local";
+const TRAIL_INDEX_NAME: &str = "deepseek-trail.index";
+
+/// What `/trail` reports about the branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrailStatus {
+    pub branch: String,
+    /// `None` when the branch does not exist yet.
+    pub tip: Option<String>,
+    /// Trail commits not reachable from `HEAD`.
+    pub ahead_of_head: usize,
+    /// `<short-sha> <subject>` for the newest trail commits, newest first.
+    pub recent: Vec<String>,
+}
+
+/// Validate a configured branch name with `git check-ref-format`.
+pub fn validate_branch(branch: &str) -> io::Result<()> {
+    let output = Command::new("git")
+        .args(["check-ref-format", "--branch", branch])
+        .output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("'{branch}' is not a valid branch name"),
+        ))
+    }
+}
+
+/// Commit the current workspace tree to `branch`. Returns the new commit id,
+/// or `None` when the workspace is not inside a git repository or nothing
+/// changed since the branch tip.
+pub fn record_turn(workspace: &Path, branch: &str, message: &str) -> io::Result<Option<String>> {
+    let Some(top) = repo_toplevel(workspace) else {
+        return Ok(None);
+    };
+    validate_branch(branch)?;
+    let refname = format!("refs/heads/{branch}");
+    let tip = rev_parse(&top, &format!("{refname}^{{commit}}"));
+    let parent = tip.clone().or_else(|| rev_parse(&top, "HEAD^{commit}"));
+
+    let index =
+        PathBuf::from(git_text(&top, &["rev-parse", "--absolute-git-dir"])?).join(TRAIL_INDEX_NAME);
+    // Seed the private index from the parent tree so `git add -A` only has
+    // to hash what changed.
+    let _ = std::fs::remove_file(&index);
+    if let Some(parent) = parent.as_deref() {
+        git_checked_with_index(&top, &index, &["read-tree", parent])?;
+    }
+    git_checked_with_index(&top, &index, &["add", "-A", "--", "."])?;
+    let tree =
+        String::from_utf8_lossy(&git_checked_with_index(&top, &index, &["write-tree"])?.stdout)
+            .trim()
+            .to_string();
+    let _ = std::fs::remove_file(&index);
+
+    if let Some(parent) = parent.as_deref()
+        && rev_parse(&top, &format!("{parent}^{{tree}}")).as_deref() == Some(tree.as_str())
+    {
+        return Ok(None);
+    }
+
+    let mut commit_tree = vec!["commit-tree", tree.as_str()];
+    if let Some(parent) = parent.as_deref() {
+        commit_tree.extend(["-p", parent]);
+    }
+    commit_tree.extend(["-m", message]);
+    let output = git(&top)
+        .args(&commit_tree)
+        .env("GIT_AUTHOR_NAME", TRAIL_AUTHOR_NAME)
+        .env("GIT_AUTHOR_EMAIL", TRAIL_AUTHOR_EMAIL)
+        .env("GIT_COMMITTER_NAME", TRAIL_AUTHOR_NAME)
+        .env("GIT_COMMITTER_EMAIL", TRAIL_AUTHOR_EMAIL)
+        .output()?;
+    let commit = String::from_utf8_lossy(&checked(output, "commit-tree")?.stdout)
+        .trim()
+        .to_string();
+
+    // Compare-and-swap against the tip we read so two sessions recording at
+    // once cannot drop each other's commits silently.
+    let old = tip.unwrap_or_default();
+    git_text(
+        &top,
+        &[
+            "update-ref",
+            "-m",
+            "deepseek trail",
+            &refname,
+            &commit,
+            &old,
+        ],
+    )?;
+    Ok(Some(commit))
+}
+
+/// Describe the trail branch for `/trail`.
+pub fn status(workspace: &Path, branch: &str, recent: usize) -> io::Result<TrailStatus> {
+    let top = repo_toplevel(workspace).ok_or_else(not_a_repo)?;
+    let refname = format!("refs/heads/{branch}");
+    let tip = rev_parse(&top, &format!("{refname}^{{commit}}"));
+    let mut status = TrailStatus {
+        branch: branch.to_string(),
+        tip: tip.clone(),
+        ahead_of_head: 0,
+        recent: Vec::new(),
+    };
+    if tip.is_none() {
+        return Ok(status);
+    }
+    let range = if rev_parse(&top, "HEAD^{commit}").is_some() {
+        format!("HEAD..{refname}")
+    } else {
+        refname.clone()
+    };
+    status.ahead_of_head = git_text(&top, &["rev-list", "--count", &range])?
+        .parse()
+        .unwrap_or(0);
+    let log = git_text(
+        &top,
+        &[
+            "log",
+            "--format=%h %s",
+            &format!("--max-count={recent}"),
+            &range,
+        ],
+    )?;
+    status.recent = log.lines().map(str::to_string).collect();
+    Ok(status)
+}
+
+/// Delete the trail branch. Returns false when it did not exist. The next
+/// recorded turn starts a fresh trail on top of `HEAD`.
+pub fn clear(workspace: &Path, branch: &str) -> io::Result<bool> {
+    let top = repo_toplevel(workspace).ok_or_else(not_a_repo)?;
+    let refname = format!("refs/heads/{branch}");
+    if rev_parse(&top, &refname).is_none() {
+        return Ok(false);
+    }
+    git_text(&top, &["update-ref", "-d", &refname])?;
+    Ok(true)
+}
+
+/// Commit message for a trail commit: the user's request as the subject,
+/// the tools that ran as the body.
+#[must_use]
+pub fn turn_message(turn_seq: u64, prompt: &str, tools: &[String]) -> String {
+    let first_line = prompt
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("(no prompt)");
+    let subject = crate::utils::truncate_with_ellipsis(first_line, 60, "…");
+    let mut message = format!("turn {turn_seq}: {subject}\n");
+    if !tools.is_empty() {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for tool in tools {
+            match counts.iter_mut().find(|(name, _)| name == tool) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((tool.clone(), 1)),
+            }
+        }
+        let listed = counts
+            .into_iter()
+            .map(|(name, count)| {
+                if count > 1 {
+                    format!("{name} ×{count}")
+                } else {
+                    name
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        message.push_str(&format!("\nTools: {listed}\n"));
+    }
+    message
+}
+
+fn not_a_repo() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        "workspace is not inside a git repository",
+    )
+}
+
+fn git(top: &Path) -> Command {
+    let mut command = Command::new("git");
+    command.arg("-C").arg(top);
+    command
+}
+
+fn repo_toplevel(workspace: &Path) -> Option<PathBuf> {
+    let output = git(workspace)
+        .args(["rev-parse", "--show-toplevel"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()))
+}
+
+fn rev_parse(top: &Path, rev: &str) -> Option<String> {
+    let output = git(top)
+        .args(["rev-parse", "--verify", "--quiet", rev])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn git_text(top: &Path, args: &[&str]) -> io::Result<String> {
+    let output = git(top).args(args).output()?;
+    let output = checked(output, args.first().copied().unwrap_or("git"))?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn git_checked_with_index(top: &Path, index: &Path, args: &[&str]) -> io::Result<Output> {
+    let output = git(top).args(args).env("GIT_INDEX_FILE", index).output()?;
+    checked(output, args.first().copied().unwrap_or("git"))
+}
+
+fn checked(output: Output, what: &str) -> io::Result<Output> {
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(io::Error::other(format!(
+            "git {what} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn run(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .env("GIT_AUTHOR_NAME", "t")
+            .env("GIT_AUTHOR_EMAIL", "t@example.com")
+            .env("GIT_COMMITTER_NAME", "t")
+            .env("GIT_COMMITTER_EMAIL", "t@example.com")
+            .output()
+            .expect("git");
+        assert!(output.status.success(), "git {args:?}: {output:?}");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn trail_commits_stack_on_head_without_touching_branch_or_index() {
+        let tmp = tempdir().expect("tempdir");
+        let repo = tmp.path();
+        run(repo, &["init", "-q", "-b", "main"]);
+        std::fs::write(repo.join(".gitignore"), "target/\n").unwrap();
+        std::fs::write(repo.join("lib.rs"), "fn a() {}\n").unwrap();
+        run(repo, &["add", "-A"]);
+        run(repo, &["commit", "-q", "-m", "base"]);
+        let head = run(repo, &["rev-parse", "HEAD"]);
+
+        std::fs::write(repo.join("lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        std::fs::create_dir(repo.join("target")).unwrap();
+        std::fs::write(repo.join("target/out.bin"), "x").unwrap();
+        let message = turn_message(1, "add b\nmore", &["edit_file".into(), "edit_file".into()]);
+        assert_eq!(message, "turn 1: add b\n\nTools: edit_file ×2\n");
+        let first = record_turn(repo, DEFAULT_TRAIL_BRANCH, &message)
+            .unwrap()
+            .expect("first trail commit");
+        assert_eq!(run(repo, &["rev-parse", &format!("{first}^")]), head);
+        assert_eq!(
+            run(repo, &["ls-tree", "--name-only", &first]),
+            ".gitignore\nlib.rs"
+        );
+        // The user's branch, index, and working tree are untouched.
+        assert_eq!(run(repo, &["rev-parse", "HEAD"]), head);
+        assert_eq!(run(repo, &["symbolic-ref", "--short", "HEAD"]), "main");
+        assert_eq!(run(repo, &["diff", "--cached", "--name-only"]), "");
+        assert_eq!(run(repo, &["status", "--porcelain"]), "M lib.rs");
+
+        assert_eq!(
+            record_turn(repo, DEFAULT_TRAIL_BRANCH, "turn 2: no-op").unwrap(),
+            None
+        );
+        std::fs::write(repo.join("new.rs"), "fn c() {}\n").unwrap();
+        let second = record_turn(repo, DEFAULT_TRAIL_BRANCH, "turn 3: add c")
+            .unwrap()
+            .expect("second trail commit");
+        assert_eq!(run(repo, &["rev-parse", &format!("{second}^")]), first);
+
+        let status = status(repo, DEFAULT_TRAIL_BRANCH, 10).unwrap();
+        assert_eq!(status.ahead_of_head, 2);
+        assert_eq!(status.recent.len(), 2);
+        assert!(status.recent[0].ends_with("turn 3: add c"));
+
+        assert!(clear(repo, DEFAULT_TRAIL_BRANCH).unwrap());
+        assert!(!clear(repo, DEFAULT_TRAIL_BRANCH).unwrap());
+        assert_eq!(run(repo, &["rev-parse", "HEAD"]), head);
+    }
+
+    #[test]
+    fn outside_a_repository_nothing_is_recorded() {
+        let tmp = tempdir().expect("tempdir");
+        std::fs::write(tmp.path().join("a.txt"), "a").unwrap();
+        assert_eq!(
+            record_turn(tmp.path(), DEFAULT_TRAIL_BRANCH, "turn 1: x").unwrap(),
+            None
+        );
+        assert!(status(tmp.path(), DEFAULT_TRAIL_BRANCH, 5).is_err());
+        assert!(validate_branch("bad..name").is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! Verbatim archive of messages removed from context by compaction.
+//!
+//! Summary compaction replaces old messages with a digest. Before they
+//! leave the context, the originals are written to
+//! `~/.deepseek/sessions/<id>/history/<segment>.jsonl`: a
+//! [`SegmentHeader`] line followed by one `Message` per line. Each digest
+//! names the segments it covers, and the `recall_history` tool reads them
+//! back when a digest is not detailed enough.
+//!
+//! Segments are numbered from 1 in the order they were archived and are
+//! never rewritten, so a segment number in a digest stays valid for the
+//! life of the session.
+
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::Message;
+use crate::utils::write_atomic;
+
+const HISTORY_ARCHIVE_SCHEMA_VERSION: u32 = 1;
+
+/// Header record written as the first line of each segment file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentHeader {
+    pub schema_version: u32,
+    pub segment: u32,
+    pub session_id: String,
+    pub archived: DateTime<Utc>,
+    pub message_count: usize,
+}
+
+/// The compaction archive of one session.
+#[derive(Debug, Clone)]
+pub struct HistoryArchive {
+    session_id: String,
+    root: PathBuf,
+}
+
+impl HistoryArchive {
+    /// The archive under `~/.deepseek/sessions/<session_id>/history`.
+    pub fn for_session(session_id: &str) -> Result<Self> {
+        let home =
+            dirs::home_dir().context("Could not resolve home directory for history archive")?;
+        Ok(Self::at(
+            session_id,
+            home.join(".deepseek")
+                .join("sessions")
+                .join(session_id)
+                .join("history"),
+        ))
+    }
+
+    #[must_use]
+    pub fn at(session_id: &str, root: PathBuf) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            root,
+        }
+    }
+
+    /// Write `messages` as the next segment and return its number.
+    pub fn append(&self, messages: &[Message]) -> Result<u32> {
+        std::fs::create_dir_all(&self.root).with_context(|| {
+            format!(
+                "Failed to create history archive directory at {}",
+                self.root.display()
+            )
+        })?;
+        let segment = self
+            .segment_numbers()?
+            .last()
+            .map_or(1, |last| last.saturating_add(1));
+        let header = SegmentHeader {
+            schema_version: HISTORY_ARCHIVE_SCHEMA_VERSION,
+            segment,
+            session_id: self.session_id.clone(),
+            archived: Utc::now(),
+            message_count: messages.len(),
+        };
+        let mut body = serde_json::to_string(&header)?;
+        body.push('\n');
+        for message in messages {
+            body.push_str(&serde_json::to_string(message)?);
+            body.push('\n');
+        }
+        let path = self.segment_path(segment);
+        write_atomic(&path, body.as_bytes())
+            .with_context(|| format!("Failed to write history segment at {}", path.display()))?;
+        Ok(segment)
+    }
+
+    /// Headers of every archived segment, oldest first.
+    pub fn segments(&self) -> Result<Vec<SegmentHeader>> {
+        self.segment_numbers()?
+            .into_iter()
+            .map(|segment| self.read(segment).map(|(header, _)| header))
+            .collect()
+    }
+
+    /// Read one segment back.
+    pub fn read(&self, segment: u32) -> Result<(SegmentHeader, Vec<Message>)> {
+        let path = self.segment_path(segment);
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read history segment at {}", path.display()))?;
+        let mut lines = raw.lines().filter(|line| !line.trim().is_empty());
+        let header: SegmentHeader = lines
+            .next()
+            .and_then(|line| serde_json::from_str(line).ok())
+            .with_context(|| {
+                format!(
+                    "History segment at {} is missing a valid header",
+                    path.display()
+                )
+            })?;
+        if header.schema_version > HISTORY_ARCHIVE_SCHEMA_VERSION {
+            bail!(
+                "History segment schema v{} at {} is newer than supported v{}",
+                header.schema_version,
+                path.display(),
+                HISTORY_ARCHIVE_SCHEMA_VERSION
+            );
+        }
+        let messages = lines
+            .map(|line| {
+                serde_json::from_str::<Message>(line)
+                    .map_err(|e| anyhow::anyhow!("History segment line parse failed: {e}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok((header, messages))
+    }
+
+    fn segment_path(&self, segment: u32) -> PathBuf {
+        self.root.join(format!("{segment}.jsonl"))
+    }
+
+    fn segment_numbers(&self) -> Result<Vec<u32>> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+        let mut numbers = Vec::new();
+        for entry in read_dir(&self.root)? {
+            let path = entry?.path();
+            if let Some(segment) = segment_number(&path) {
+                numbers.push(segment);
+            }
+        }
+        numbers.sort_unstable();
+        Ok(numbers)
+    }
+}
+
+fn segment_number(path: &Path) -> Option<u32> {
+    if path.extension().and_then(|s| s.to_str()) != Some("jsonl") {
+        return None;
+    }
+    path.file_stem()?.to_str()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ContentBlock;
+    use tempfile::TempDir;
+
+    fn text(role: &str, text: &str) -> Message {
+        Message {
+            role: role.to_string(),
+            content: vec![ContentBlock::Text {
+                text: text.to_string(),
+                cache_control: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn segments_append_in_order_and_read_back_verbatim() {
+        let tmp = TempDir::new().expect("tempdir");
+        let archive = HistoryArchive::at("s1", tmp.path().join("history"));
+        assert!(archive.segments().unwrap().is_empty());
+
+        let first = vec![text("user", "fix the parser"), text("assistant", "done")];
+        assert_eq!(archive.append(&first).unwrap(), 1);
+        assert_eq!(archive.append(&[text("user", "now the lexer")]).unwrap(), 2);
+
+        let headers = archive.segments().unwrap();
+        assert_eq!(
+            headers
+                .iter()
+                .map(|h| (h.segment, h.message_count))
+                .collect::<Vec<_>>(),
+            vec![(1, 2), (2, 1)]
+        );
+        let (header, messages) = archive.read(1).unwrap();
+        assert_eq!(header.session_id, "s1");
+        assert_eq!(messages, first);
+        assert!(archive.read(3).is_err());
+    }
+}
@@ -0,0 +1,244 @@
+//! Per-workspace inbox of finished background work.
+//!
+//! Outputs of `/task` background tasks and sub-agents that complete while the
+//! TUI is running are collected here, so they stay reachable after they have
+//! scrolled out of the transcript. Entries carry an unread flag (counted in
+//! the header badge), are listed by `/inbox`, and persist in
+//! `.deepseek/inbox.json` across restarts.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Workspace-relative location of the inbox store.
+pub const INBOX_RELATIVE_PATH: &str = ".deepseek/inbox.json";
+
+/// Workspace-relative directory that `/inbox` saves entries into.
+pub const INBOX_EXPORT_DIR: &str = ".deepseek/inbox";
+
+/// Entries kept on disk; the oldest are dropped beyond this.
+const MAX_STORED_ENTRIES: usize = 50;
+
+/// Longest output kept per entry, in characters.
+const MAX_BODY_CHARS: usize = 64_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InboxSource {
+    Task,
+    SubAgent,
+}
+
+impl InboxSource {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Task => "task",
+            Self::SubAgent => "sub-agent",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InboxEntry {
+    /// Task or agent id.
+    pub id: String,
+    pub source: InboxSource,
+    /// What the work was asked to do, on one line.
+    pub title: String,
+    /// Final status label, e.g. `completed` or `failed`.
+    pub status: String,
+    /// The output, or the error when there was none.
+    pub body: String,
+    /// RFC 3339 timestamp of when the entry arrived.
+    pub finished_at: String,
+    pub unread: bool,
+}
+
+impl InboxEntry {
+    #[must_use]
+    pub fn new(
+        id: impl Into<String>,
+        source: InboxSource,
+        title: &str,
+        status: impl Into<String>,
+        body: &str,
+    ) -> Self {
+        let title = title.lines().next().unwrap_or_default().trim();
+        let body = body.trim();
+        let body = if body.chars().count() > MAX_BODY_CHARS {
+            let kept: String = body.chars().take(MAX_BODY_CHARS).collect();
+            format!("{kept}\n[truncated]")
+        } else {
+            body.to_string()
+        };
+        Self {
+            id: id.into(),
+            source,
+            title: title.to_string(),
+            status: status.into(),
+            body,
+            finished_at: Utc::now().to_rfc3339(),
+            unread: true,
+        }
+    }
+
+    /// The entry as Markdown, for inserting into the composer or saving.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut text = format!(
+            "Result of {} {} ({}): {}",
+            self.source.label(),
+            self.id,
+            self.status,
+            self.title
+        );
+        if !self.body.is_empty() {
+            text.push_str("\n\n");
+            text.push_str(&self.body);
+        }
+        text
+    }
+
+    /// Where `/inbox` saves this entry, relative to the workspace.
+    #[must_use]
+    pub fn export_path(&self) -> PathBuf {
+        let name: String = self
+            .id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        Path::new(INBOX_EXPORT_DIR).join(format!("{name}.md"))
+    }
+}
+
+/// Inbox entries, newest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InboxStore {
+    #[serde(default)]
+    pub entries: Vec<InboxEntry>,
+}
+
+impl InboxStore {
+    /// Load the inbox for `workspace`; a missing or unreadable file yields an
+    /// empty inbox.
+    #[must_use]
+    pub fn load(workspace: &Path) -> Self {
+        fs::read_to_string(workspace.join(INBOX_RELATIVE_PATH))
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, workspace: &Path) -> io::Result<()> {
+        let path = workspace.join(INBOX_RELATIVE_PATH);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+
+    /// Add `entry` at the top, replacing an older entry with the same id.
+    pub fn push(&mut self, entry: InboxEntry) {
+        self.entries.retain(|existing| existing.id != entry.id);
+        self.entries.insert(0, entry);
+        self.entries.truncate(MAX_STORED_ENTRIES);
+    }
+
+    #[must_use]
+    pub fn unread_count(&self) -> usize {
+        self.entries.iter().filter(|entry| entry.unread).count()
+    }
+
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&InboxEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    /// Mark one entry read. Returns whether anything changed.
+    pub fn mark_read(&mut self, id: &str) -> bool {
+        match self.entries.iter_mut().find(|entry| entry.id == id) {
+            Some(entry) if entry.unread => {
+                entry.unread = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove one entry.
+    pub fn dismiss(&mut self, id: &str) -> Option<InboxEntry> {
+        let index = self.entries.iter().position(|entry| entry.id == id)?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Remove every read entry, returning how many went.
+    pub fn clear_read(&mut self) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.unread);
+        before - self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_round_trip_with_unread_state() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut inbox = InboxStore::default();
+        inbox.push(InboxEntry::new(
+            "task_1",
+            InboxSource::Task,
+            "Audit the deps\nmore detail",
+            "completed",
+            "All pinned.\n",
+        ));
+        inbox.push(InboxEntry::new(
+            "agent_2",
+            InboxSource::SubAgent,
+            "Find flaky tests",
+            "completed",
+            "None found.",
+        ));
+        // A rerun of the same task replaces its older entry.
+        inbox.push(InboxEntry::new(
+            "task_1",
+            InboxSource::Task,
+            "Audit the deps",
+            "failed",
+            "timeout",
+        ));
+        assert_eq!(inbox.entries.len(), 2);
+        assert_eq!(inbox.entries[0].status, "failed");
+        assert!(inbox.mark_read("agent_2"));
+        assert!(!inbox.mark_read("agent_2"));
+        assert_eq!(inbox.unread_count(), 1);
+        inbox.save(tmp.path()).unwrap();
+
+        let mut loaded = InboxStore::load(tmp.path());
+        assert_eq!(loaded, inbox);
+        assert_eq!(
+            loaded.get("agent_2").unwrap().to_markdown(),
+            "Result of sub-agent agent_2 (completed): Find flaky tests\n\nNone found."
+        );
+        assert_eq!(loaded.clear_read(), 1);
+        assert!(loaded.dismiss("task_1").is_some());
+        assert!(loaded.entries.is_empty());
+        assert_eq!(
+            InboxEntry::new("a/b c", InboxSource::Task, "x", "completed", "").export_path(),
+            Path::new(".deepseek/inbox/a_b_c.md")
+        );
+    }
+}
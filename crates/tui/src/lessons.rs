@@ -0,0 +1,399 @@
+//! Per-workspace memory of shell commands that failed.
+//!
+//! When `exec_shell` fails, the command and a one-line reason (missing
+//! dependency, wrong path, ...) are recorded in `.deepseek/lessons.json`.
+//! A later successful command with the same program and subcommand (say
+//! `cargo test -p app` after `cargo test --workspace` failed) is stored as
+//! the fix. The newest lessons are rendered into a compact `lessons` layer of
+//! the system prompt, so the next session does not repeat the same broken
+//! invocation.
+//!
+//! On by default; `[memory] command_lessons = false` turns it off.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::spec::ToolResult;
+use crate::utils::truncate_with_ellipsis;
+
+/// Workspace-relative location of the lessons store.
+pub const LESSONS_RELATIVE_PATH: &str = ".deepseek/lessons.json";
+
+/// Lessons kept on disk; the oldest are dropped beyond this.
+const MAX_STORED_LESSONS: usize = 32;
+
+/// Lessons rendered into the system prompt.
+const MAX_PROMPT_LESSONS: usize = 8;
+
+/// Longest command or reason kept per lesson, in bytes.
+const MAX_FIELD_LEN: usize = 160;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    CommandNotFound,
+    MissingDependency,
+    WrongPath,
+    PermissionDenied,
+    Other,
+}
+
+impl FailureKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::CommandNotFound => "command not found",
+            Self::MissingDependency => "missing dependency",
+            Self::WrongPath => "wrong path",
+            Self::PermissionDenied => "permission denied",
+            Self::Other => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lesson {
+    pub command: String,
+    pub kind: FailureKind,
+    /// The output line that best explains the failure.
+    pub reason: String,
+    pub failures: u32,
+    /// RFC 3339 timestamp of the latest failure.
+    pub last_failed: String,
+    /// A similar command that succeeded afterwards.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LessonStore {
+    #[serde(default)]
+    pub lessons: Vec<Lesson>,
+}
+
+impl LessonStore {
+    /// Load the store for `workspace`; a missing or unreadable file yields an
+    /// empty store.
+    #[must_use]
+    pub fn load(workspace: &Path) -> Self {
+        fs::read_to_string(lessons_path(workspace))
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, workspace: &Path) -> io::Result<()> {
+        let path = lessons_path(workspace);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+
+    /// Record a failed run of `command` with its tool output.
+    pub fn record_failure(&mut self, command: &str, output: &str) {
+        let command = truncate_with_ellipsis(command.trim(), MAX_FIELD_LEN, "…");
+        let (kind, reason) = summarize_failure(output);
+        let now = Utc::now().to_rfc3339();
+        if let Some(index) = self.lessons.iter().position(|l| l.command == command) {
+            let mut lesson = self.lessons.remove(index);
+            lesson.failures = lesson.failures.saturating_add(1);
+            lesson.kind = kind;
+            lesson.reason = reason;
+            lesson.last_failed = now;
+            self.lessons.push(lesson);
+        } else {
+            self.lessons.push(Lesson {
+                command,
+                kind,
+                reason,
+                failures: 1,
+                last_failed: now,
+                fix: None,
+            });
+        }
+        let excess = self.lessons.len().saturating_sub(MAX_STORED_LESSONS);
+        self.lessons.drain(..excess);
+    }
+
+    /// Record a successful run of `command`. Returns true when the store
+    /// changed.
+    pub fn record_success(&mut self, command: &str) -> bool {
+        let command = truncate_with_ellipsis(command.trim(), MAX_FIELD_LEN, "…");
+        let before = self.lessons.len();
+        // The command works now, so its own lesson is stale.
+        self.lessons.retain(|lesson| lesson.command != command);
+        let mut changed = self.lessons.len() != before;
+        let Some(key) = command_key(&command) else {
+            return changed;
+        };
+        for lesson in &mut self.lessons {
+            if lesson.fix.is_none() && command_key(&lesson.command).as_deref() == Some(&key) {
+                lesson.fix = Some(command.clone());
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Compact digest of the newest lessons for the system prompt.
+    #[must_use]
+    pub fn digest(&self) -> Option<String> {
+        if self.lessons.is_empty() {
+            return None;
+        }
+        let mut out = String::from(
+            "## Command Lessons\n\n\
+             Shell commands that failed in this workspace in earlier turns or sessions. \
+             Do not repeat a failing invocation unchanged; prefer the command that worked.\n",
+        );
+        for lesson in self.lessons.iter().rev().take(MAX_PROMPT_LESSONS) {
+            let times = if lesson.failures > 1 {
+                format!(" {}\u{00d7}", lesson.failures)
+            } else {
+                String::new()
+            };
+            out.push_str(&format!(
+                "\n- `{}` failed{times} ({}: {})",
+                lesson.command,
+                lesson.kind.label(),
+                lesson.reason
+            ));
+            if let Some(fix) = &lesson.fix {
+                out.push_str(&format!(" \u{2014} worked instead: `{fix}`"));
+            }
+        }
+        Some(out)
+    }
+}
+
+#[must_use]
+pub fn lessons_path(workspace: &Path) -> PathBuf {
+    workspace.join(LESSONS_RELATIVE_PATH)
+}
+
+/// Prompt block for the lessons layer, or `None` when disabled or empty.
+#[must_use]
+pub fn compose_block(enabled: bool, workspace: &Path) -> Option<String> {
+    if !enabled {
+        return None;
+    }
+    LessonStore::load(workspace).digest()
+}
+
+/// Update the store from a finished `exec_shell` call. Background starts,
+/// timeouts and cancellations are not lessons and are ignored.
+pub fn observe_shell_result(workspace: &Path, input: &serde_json::Value, result: &ToolResult) {
+    let Some(command) = input.get("command").and_then(serde_json::Value::as_str) else {
+        return;
+    };
+    let metadata = result.metadata.as_ref();
+    let status = metadata
+        .and_then(|m| m.get("status"))
+        .and_then(serde_json::Value::as_str);
+    let canceled = metadata
+        .and_then(|m| m.get("canceled"))
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false);
+    if canceled {
+        return;
+    }
+
+    let mut store = LessonStore::load(workspace);
+    let changed = match status {
+        Some("Completed") if result.success => store.record_success(command),
+        Some("Failed") => {
+            store.record_failure(command, &result.content);
+            true
+        }
+        _ => false,
+    };
+    if changed && let Err(err) = store.save(workspace) {
+        tracing::debug!("failed to save command lessons: {err}");
+    }
+}
+
+/// Classify a failure and pick the output line that explains it.
+fn summarize_failure(output: &str) -> (FailureKind, String) {
+    let lines: Vec<&str> = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            !line.is_empty()
+                && !line.starts_with("Command failed (exit code")
+                && !matches!(*line, "STDOUT:" | "STDERR:")
+        })
+        .collect();
+    for line in &lines {
+        if let Some(kind) = classify_line(line) {
+            return (kind, truncate_with_ellipsis(line, MAX_FIELD_LEN, "…"));
+        }
+    }
+    let fallback = lines
+        .iter()
+        .find(|line| line.to_ascii_lowercase().contains("error"))
+        .or_else(|| lines.last())
+        .map_or_else(
+            || "non-zero exit status".to_string(),
+            |line| truncate_with_ellipsis(line, MAX_FIELD_LEN, "…"),
+        );
+    (FailureKind::Other, fallback)
+}
+
+fn classify_line(line: &str) -> Option<FailureKind> {
+    let lower = line.to_ascii_lowercase();
+    let has = |needle: &str| lower.contains(needle);
+    if has("command not found") || has("is not recognized as an internal or external command") {
+        Some(FailureKind::CommandNotFound)
+    } else if has("no such command")
+        || has("modulenotfounderror")
+        || has("cannot find module")
+        || has("no matching package")
+        || has("package id specification")
+        || has("unresolved import")
+        || (has("could not find") && (has("package") || has("crate") || has("module")))
+    {
+        Some(FailureKind::MissingDependency)
+    } else if has("no such file or directory")
+        || has("not a directory")
+        || has("cannot find the path")
+        || has("could not find `cargo.toml`")
+    {
+        Some(FailureKind::WrongPath)
+    } else if has("permission denied") || has("operation not permitted") {
+        Some(FailureKind::PermissionDenied)
+    } else {
+        None
+    }
+}
+
+/// Program plus first non-flag argument of the last command in a chain
+/// (`cd app && cargo test -p x | tail` -> `cargo test`), used to match a
+/// later working command to an earlier failure.
+fn command_key(command: &str) -> Option<String> {
+    let last = command
+        .split(';')
+        .flat_map(|part| part.split("&&"))
+        .flat_map(|part| part.split("||"))
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .last()?;
+    let last = last.split('|').next()?.trim();
+    let words = shlex::split(last).unwrap_or_else(|| {
+        last.split_whitespace()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    });
+    // Skip leading `VAR=value` environment assignments.
+    let mut words = words
+        .into_iter()
+        .skip_while(|word| word.contains('=') && !word.starts_with('-'));
+    let program = words.next()?;
+    let program = Path::new(&program)
+        .file_name()
+        .map_or(program.clone(), |name| name.to_string_lossy().into_owned());
+    match words.find(|word| !word.starts_with('-')) {
+        Some(sub) => Some(format!("{program} {sub}")),
+        None => Some(program),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn shell_result(success: bool, status: &str, content: &str) -> ToolResult {
+        ToolResult {
+            content: content.to_string(),
+            success,
+            metadata: Some(json!({ "status": status, "canceled": false })),
+        }
+    }
+
+    #[test]
+    fn failures_persist_with_reason_and_later_fix() {
+        let tmp = TempDir::new().unwrap();
+        let broken = json!({ "command": "cargo test --workspace" });
+        let output = "Command failed (exit code: Some(101))\n\nSTDOUT:\n\n\nSTDERR:\n\
+                      error: package ID specification `foo` did not match any packages";
+        observe_shell_result(tmp.path(), &broken, &shell_result(false, "Failed", output));
+        observe_shell_result(tmp.path(), &broken, &shell_result(false, "Failed", output));
+        // Background starts and unrelated successes do not touch the lesson.
+        observe_shell_result(
+            tmp.path(),
+            &json!({ "command": "cargo build" }),
+            &shell_result(true, "Running", ""),
+        );
+
+        let store = LessonStore::load(tmp.path());
+        assert_eq!(store.lessons.len(), 1);
+        assert_eq!(store.lessons[0].failures, 2);
+        assert_eq!(store.lessons[0].kind, FailureKind::MissingDependency);
+        assert!(store.lessons[0].fix.is_none());
+
+        observe_shell_result(
+            tmp.path(),
+            &json!({ "command": "cd crates/tui && cargo test -p deepseek-tui" }),
+            &shell_result(true, "Completed", "ok"),
+        );
+        let digest = compose_block(true, tmp.path()).expect("digest");
+        assert!(
+            digest.contains(
+                "`cargo test --workspace` failed 2\u{00d7} (missing dependency: error: package ID"
+            ),
+            "{digest}"
+        );
+        assert!(digest.contains("worked instead: `cd crates/tui && cargo test -p deepseek-tui`"));
+        assert!(compose_block(false, tmp.path()).is_none());
+
+        // Once the original command works, its lesson is dropped.
+        observe_shell_result(tmp.path(), &broken, &shell_result(true, "Completed", "ok"));
+        assert!(compose_block(true, tmp.path()).is_none());
+    }
+
+    #[test]
+    fn summarizer_classifies_common_failures() {
+        let cases = [
+            (
+                "sh: 1: pytset: command not found",
+                FailureKind::CommandNotFound,
+            ),
+            (
+                "ModuleNotFoundError: No module named 'requests'",
+                FailureKind::MissingDependency,
+            ),
+            (
+                "cat: src/main.py: No such file or directory",
+                FailureKind::WrongPath,
+            ),
+            (
+                "bash: ./run.sh: Permission denied",
+                FailureKind::PermissionDenied,
+            ),
+        ];
+        for (line, kind) in cases {
+            let (got, reason) = summarize_failure(&format!("noise\n{line}\n"));
+            assert_eq!(got, kind, "{line}");
+            assert_eq!(reason, line);
+        }
+        let (kind, reason) = summarize_failure("running 3 tests\ntest result: FAILED. 1 failed");
+        assert_eq!(kind, FailureKind::Other);
+        assert_eq!(reason, "test result: FAILED. 1 failed");
+
+        assert_eq!(
+            command_key("RUST_LOG=debug cargo test --workspace").as_deref(),
+            Some("cargo test")
+        );
+        assert_eq!(command_key("/usr/bin/make").as_deref(), Some("make"));
+        assert_eq!(
+            command_key("cd app && cargo test 2>&1 | tail -5").as_deref(),
+            Some("cargo test")
+        );
+    }
+}
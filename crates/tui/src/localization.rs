@@ -260,6 +260,7 @@ pub enum MessageId {
     CmdCycleDescription,
     CmdCyclesDescription,
     CmdDiffDescription,
+    CmdChangesDescription,
     CmdEditDescription,
     CmdExitDescription,
     CmdExportDescription,
@@ -273,21 +274,35 @@ pub enum MessageId {
     CmdJobsDescription,
     CmdLinksDescription,
     CmdLoadDescription,
+    CmdImportDescription,
+    CmdBookmarksDescription,
     CmdLogoutDescription,
     CmdMcpDescription,
     CmdMemoryDescription,
     CmdModeDescription,
+    CmdPlanDescription,
     CmdModelDescription,
     CmdModelsDescription,
     CmdNetworkDescription,
     CmdNoteDescription,
+    CmdEnhanceDescription,
     CmdThemeDescription,
     CmdProviderDescription,
     CmdQueueDescription,
     CmdRecallDescription,
     CmdRelayDescription,
     CmdRenameDescription,
+    CmdReportDescription,
+    CmdKeysDescription,
+    CmdShellsDescription,
+    CmdThinkingDescription,
     CmdRestoreDescription,
+    CmdSnapshotDescription,
+    CmdTrailDescription,
+    CmdPreviewDescription,
+    CmdEnvDescription,
+    CmdInboxDescription,
+    CmdRepomapDescription,
     CmdRetryDescription,
     CmdReviewDescription,
     CmdRlmDescription,
@@ -306,6 +321,7 @@ pub enum MessageId {
     CmdTaskDescription,
     CmdTokensDescription,
     CmdTranslateDescription,
+    CmdSmartContextDescription,
     CmdTranslateOff,
     CmdTranslateOn,
     TranslationInProgress,
@@ -323,6 +339,7 @@ pub enum MessageId {
     CmdCacheNoData,
     CmdCacheTotals,
     CmdCostReport,
+    CmdCostCacheSavings,
     CmdTokensCacheBoth,
     CmdTokensCacheHitOnly,
     CmdTokensCacheMissOnly,
@@ -366,6 +383,7 @@ pub enum MessageId {
     KbCompactInspector,
     KbLastMessagePager,
     KbSelectedDetails,
+    KbBookmarkCell,
     KbToolDetailsPager,
     KbThinkingPager,
     KbLiveTranscript,
@@ -374,8 +392,12 @@ pub enum MessageId {
     KbJumpPlanAgentYolo,
     KbAltJumpPlanAgentYolo,
     KbFocusSidebar,
+    KbToolOutputPane,
     KbTogglePlanAgent,
     KbSessionPicker,
+    KbNewSessionTab,
+    KbSwitchSessionTab,
+    KbCloseSessionTab,
     KbPasteAttach,
     KbCopySelection,
     KbContextMenu,
@@ -441,6 +463,7 @@ pub enum MessageId {
     OnboardTrustLocationPrefix,
     OnboardTrustRiskHint,
     OnboardTrustEffectHint,
+    OnboardTrustLevelHint,
     OnboardTrustFooterPrefix,
     OnboardTrustFooterMiddle,
     OnboardTrustFooterSuffix,
@@ -494,6 +517,7 @@ pub const ALL_MESSAGE_IDS: &[MessageId] = &[
     MessageId::CmdCycleDescription,
     MessageId::CmdCyclesDescription,
     MessageId::CmdDiffDescription,
+    MessageId::CmdChangesDescription,
     MessageId::CmdEditDescription,
     MessageId::CmdExitDescription,
     MessageId::CmdExportDescription,
@@ -506,20 +530,34 @@ pub const ALL_MESSAGE_IDS: &[MessageId] = &[
     MessageId::CmdJobsDescription,
     MessageId::CmdLinksDescription,
     MessageId::CmdLoadDescription,
+    MessageId::CmdImportDescription,
+    MessageId::CmdBookmarksDescription,
     MessageId::CmdLogoutDescription,
     MessageId::CmdMcpDescription,
     MessageId::CmdMemoryDescription,
     MessageId::CmdModeDescription,
+    MessageId::CmdPlanDescription,
     MessageId::CmdModelDescription,
     MessageId::CmdModelsDescription,
     MessageId::CmdNetworkDescription,
     MessageId::CmdNoteDescription,
+    MessageId::CmdEnhanceDescription,
     MessageId::CmdProviderDescription,
     MessageId::CmdQueueDescription,
     MessageId::CmdRecallDescription,
     MessageId::CmdRelayDescription,
     MessageId::CmdRenameDescription,
+    MessageId::CmdReportDescription,
+    MessageId::CmdKeysDescription,
+    MessageId::CmdShellsDescription,
+    MessageId::CmdThinkingDescription,
     MessageId::CmdRestoreDescription,
+    MessageId::CmdSnapshotDescription,
+    MessageId::CmdTrailDescription,
+    MessageId::CmdPreviewDescription,
+    MessageId::CmdEnvDescription,
+    MessageId::CmdInboxDescription,
+    MessageId::CmdRepomapDescription,
     MessageId::CmdRetryDescription,
     MessageId::CmdReviewDescription,
     MessageId::CmdRlmDescription,
@@ -537,6 +575,7 @@ pub const ALL_MESSAGE_IDS: &[MessageId] = &[
     MessageId::CmdTaskDescription,
     MessageId::CmdTokensDescription,
     MessageId::CmdTranslateDescription,
+    MessageId::CmdSmartContextDescription,
     MessageId::CmdTranslateOff,
     MessageId::CmdTranslateOn,
     MessageId::TranslationInProgress,
@@ -559,6 +598,7 @@ pub const ALL_MESSAGE_IDS: &[MessageId] = &[
     MessageId::CmdChangeTranslationUnavailable,
     MessageId::CmdChangePreviousVersion,
     MessageId::CmdCostReport,
+    MessageId::CmdCostCacheSavings,
     MessageId::CmdTokensCacheBoth,
     MessageId::CmdTokensCacheHitOnly,
     MessageId::CmdTokensCacheMissOnly,
@@ -602,6 +642,7 @@ pub const ALL_MESSAGE_IDS: &[MessageId] = &[
     MessageId::KbCompactInspector,
     MessageId::KbLastMessagePager,
     MessageId::KbSelectedDetails,
+    MessageId::KbBookmarkCell,
     MessageId::KbToolDetailsPager,
     MessageId::KbThinkingPager,
     MessageId::KbLiveTranscript,
@@ -610,8 +651,12 @@ pub const ALL_MESSAGE_IDS: &[MessageId] = &[
     MessageId::KbJumpPlanAgentYolo,
     MessageId::KbAltJumpPlanAgentYolo,
     MessageId::KbFocusSidebar,
+    MessageId::KbToolOutputPane,
     MessageId::KbTogglePlanAgent,
     MessageId::KbSessionPicker,
+    MessageId::KbNewSessionTab,
+    MessageId::KbSwitchSessionTab,
+    MessageId::KbCloseSessionTab,
     MessageId::KbPasteAttach,
     MessageId::KbCopySelection,
     MessageId::KbContextMenu,
@@ -674,6 +719,7 @@ pub const ALL_MESSAGE_IDS: &[MessageId] = &[
     MessageId::OnboardTrustLocationPrefix,
     MessageId::OnboardTrustRiskHint,
     MessageId::OnboardTrustEffectHint,
+    MessageId::OnboardTrustLevelHint,
     MessageId::OnboardTrustFooterPrefix,
     MessageId::OnboardTrustFooterMiddle,
     MessageId::OnboardTrustFooterSuffix,
@@ -923,6 +969,9 @@ fn english(id: MessageId) -> &'static str {
         MessageId::CmdCycleDescription => "Show the carry-forward briefing for a specific cycle",
         MessageId::CmdCyclesDescription => "List checkpoint-restart cycle handoffs in this session",
         MessageId::CmdDiffDescription => "Show file changes since session start",
+        MessageId::CmdChangesDescription => {
+            "List files changed by tools this session, with diffs against their session-start contents and per-file revert"
+        }
         MessageId::CmdEditDescription => "Revise and resubmit the last message",
         MessageId::CmdExitDescription => "Exit the application",
         MessageId::CmdExportDescription => "Export conversation to markdown",
@@ -940,16 +989,24 @@ fn english(id: MessageId) -> &'static str {
         MessageId::CmdJobsDescription => "Inspect and control background shell jobs",
         MessageId::CmdLinksDescription => "Show DeepSeek dashboard and docs links",
         MessageId::CmdLoadDescription => "Load session from file",
+        MessageId::CmdImportDescription => "Import a markdown conversation or notes",
+        MessageId::CmdBookmarksDescription => "List transcript bookmarks and jump to one",
         MessageId::CmdLogoutDescription => "Clear API key and return to setup",
         MessageId::CmdMcpDescription => "Open or manage MCP servers",
         MessageId::CmdMemoryDescription => "Inspect or manage the persistent user-memory file",
         MessageId::CmdModeDescription => {
             "Switch mode or open picker: /mode [agent|plan|yolo|1|2|3]"
         }
+        MessageId::CmdPlanDescription => {
+            "Show the current plan or export it as a live Markdown/Org document"
+        }
         MessageId::CmdModelDescription => "Switch or view current model",
         MessageId::CmdModelsDescription => "List available models from API",
         MessageId::CmdNetworkDescription => "Manage network allow and deny rules",
         MessageId::CmdNoteDescription => "Add, list, edit, or remove workspace notes",
+        MessageId::CmdEnhanceDescription => {
+            "Rewrite a draft prompt to be more specific, keeping its intent"
+        }
         MessageId::CmdThemeDescription => "Switch theme or open the theme picker",
         MessageId::CmdProviderDescription => {
             "Switch or view the active LLM backend (deepseek | nvidia-nim | ollama)"
@@ -958,9 +1015,35 @@ fn english(id: MessageId) -> &'static str {
         MessageId::CmdRecallDescription => "Search prior cycle archives (BM25 over message text)",
         MessageId::CmdRelayDescription => "Create a session relay (接力) for a fresh thread",
         MessageId::CmdRenameDescription => "Rename the current session",
+        MessageId::CmdReportDescription => {
+            "Write a sanitized bug-report bundle (config, flags, events, logs)"
+        }
+        MessageId::CmdKeysDescription => "Show keybindings, [keys] remaps, and conflicts",
+        MessageId::CmdShellsDescription => "Open, attach to, and kill persistent shell sessions",
+        MessageId::CmdThinkingDescription => {
+            "Show or set the thinking tier (off/low/medium/high/max/auto) or per-turn budget"
+        }
         MessageId::CmdRestoreDescription => {
             "Roll back the workspace to a prior pre/post-turn snapshot. With no arg, lists recent snapshots."
         }
+        MessageId::CmdSnapshotDescription => {
+            "Save, restore, list, or delete named workspace snapshots"
+        }
+        MessageId::CmdTrailDescription => {
+            "Show, toggle, or clear the per-turn git audit trail branch"
+        }
+        MessageId::CmdPreviewDescription => {
+            "Preview the tool plan Agent mode would run, then execute it with one key"
+        }
+        MessageId::CmdEnvDescription => {
+            "List, set, or unset environment variables injected into shell tools"
+        }
+        MessageId::CmdInboxDescription => {
+            "Open the inbox of finished background tasks and sub-agents to insert, save, or dismiss results"
+        }
+        MessageId::CmdRepomapDescription => {
+            "Show or rebuild the repo map injected into the system prompt"
+        }
         MessageId::CmdRetryDescription => "Retry the last request",
         MessageId::CmdReviewDescription => "Run a structured code review on a file, diff, or PR",
         MessageId::CmdRlmDescription => "Open a persistent RLM context: /rlm [0-3] <file_or_text>",
@@ -989,6 +1072,9 @@ fn english(id: MessageId) -> &'static str {
         MessageId::CmdTranslateDescription => {
             "Toggle output translation to the current system language on/off"
         }
+        MessageId::CmdSmartContextDescription => {
+            "Auto-include relevant workspace files with each prompt (on/off)"
+        }
         MessageId::CmdTranslateOff => "Output translation disabled (original model output shown)",
         MessageId::CmdTranslateOn => {
             "Output translation enabled: model responses will be shown in your system language"
@@ -1021,6 +1107,9 @@ fn english(id: MessageId) -> &'static str {
         MessageId::CmdCacheTotals => {
             "Σ in: {sum_in}   Σ hit: {sum_hit}   Σ miss: {sum_miss}   avg hit ratio: {avg}\n"
         }
+        MessageId::CmdCostCacheSavings => {
+            "Cache saved: {saved} ({hit} prompt tokens, {percent}% served from the context cache)"
+        }
         MessageId::CmdCostReport => {
             "Session Cost:\n\
              ─────────────────────────────\n\
@@ -1091,6 +1180,9 @@ fn english(id: MessageId) -> &'static str {
         MessageId::KbSelectedDetails => {
             "Open details for the selected tool or message (when input is empty)"
         }
+        MessageId::KbBookmarkCell => {
+            "Bookmark the selected message; /bookmarks jumps back (when input is empty)"
+        }
         MessageId::KbToolDetailsPager => "Open tool-details pager",
         MessageId::KbThinkingPager => "Open Activity Detail",
         MessageId::KbLiveTranscript => "Open live transcript overlay (sticky-tail auto-scroll)",
@@ -1105,8 +1197,14 @@ fn english(id: MessageId) -> &'static str {
         MessageId::KbFocusSidebar => {
             "Focus Work / Tasks / Agents / Context / Auto sidebar; Ctrl+Alt+0 hides it"
         }
+        MessageId::KbToolOutputPane => {
+            "Open, focus, or close the tool output pane beside the transcript"
+        }
         MessageId::KbTogglePlanAgent => "Toggle between Plan and Agent modes",
         MessageId::KbSessionPicker => "Open the session picker",
+        MessageId::KbNewSessionTab => "Open a new session tab",
+        MessageId::KbSwitchSessionTab => "Switch to session tab 1–9",
+        MessageId::KbCloseSessionTab => "Close the focused session tab",
         MessageId::KbPasteAttach => "Paste text or attach a clipboard image",
         MessageId::KbCopySelection => "Copy the current selection (Cmd+C on macOS)",
         MessageId::KbContextMenu => {
@@ -1188,7 +1286,10 @@ fn english(id: MessageId) -> &'static str {
             "Working with untrusted contents comes with higher risk of prompt injection."
         }
         MessageId::OnboardTrustEffectHint => {
-            "Trusting this directory records it in global config and enables trusted workspace mode."
+            "Your choice is saved to .deepseek/trust.json; change it later with /trust level."
+        }
+        MessageId::OnboardTrustLevelHint => {
+            "Press 3 to allow read-only tools only, or 4 for full access (no sandbox, auto-approve)."
         }
         MessageId::OnboardTrustFooterPrefix => "Press ",
         MessageId::OnboardTrustFooterMiddle => " to trust and continue, ",
@@ -1303,6 +1404,9 @@ fn japanese(id: MessageId) -> Option<&'static str> {
             "セッション内のチェックポイント再起動サイクルの引き継ぎを一覧表示"
         }
         MessageId::CmdDiffDescription => "セッション開始以降のファイル変更を表示",
+        MessageId::CmdChangesDescription => {
+            "このセッションでツールが変更したファイルを一覧表示し、セッション開始時との差分表示とファイル単位の復元を行う"
+        }
         MessageId::CmdEditDescription => "最後のメッセージを編集して再送信",
         MessageId::CmdExitDescription => "アプリを終了",
         MessageId::CmdExportDescription => "会話を Markdown にエクスポート",
@@ -1322,16 +1426,24 @@ fn japanese(id: MessageId) -> Option<&'static str> {
         MessageId::CmdJobsDescription => "バックグラウンドのシェルジョブを確認・制御",
         MessageId::CmdLinksDescription => "DeepSeek ダッシュボードとドキュメントへのリンクを表示",
         MessageId::CmdLoadDescription => "ファイルからセッションを読み込み",
+        MessageId::CmdImportDescription => "Markdown の会話やメモを取り込む",
+        MessageId::CmdBookmarksDescription => {
+            "トランスクリプトのブックマークを一覧表示してジャンプ"
+        }
         MessageId::CmdLogoutDescription => "API キーを消去してセットアップに戻る",
         MessageId::CmdMcpDescription => "MCP サーバを開く・管理する",
         MessageId::CmdMemoryDescription => "永続ユーザーメモリファイルを確認・管理",
         MessageId::CmdModeDescription => {
             "動作モードを切り替え、または選択画面を開く: /mode [agent|plan|yolo|1|2|3]"
         }
+        MessageId::CmdPlanDescription => {
+            "現在のプランを表示、または自動更新される Markdown/Org 文書として書き出す"
+        }
         MessageId::CmdModelDescription => "現在のモデルを切り替え・確認",
         MessageId::CmdModelsDescription => "API から利用可能なモデルを一覧表示",
         MessageId::CmdNetworkDescription => "ネットワーク許可・拒否ルールを管理",
         MessageId::CmdNoteDescription => "ワークスペースノートの追加、一覧、編集、削除",
+        MessageId::CmdEnhanceDescription => "意図を保ったまま下書きプロンプトを具体的に書き直す",
         MessageId::CmdThemeDescription => {
             "テーマを切り替え（ダーク/ライト/グレースケール/システム）"
         }
@@ -1344,9 +1456,31 @@ fn japanese(id: MessageId) -> Option<&'static str> {
         }
         MessageId::CmdRelayDescription => "新しいスレッド用のセッションリレー（接力）を作成",
         MessageId::CmdRenameDescription => "現在のセッションの名前を変更",
+        MessageId::CmdReportDescription => {
+            "バグ報告用の機密情報を除去したバンドル（設定・フラグ・イベント・ログ）を書き出す"
+        }
+        MessageId::CmdKeysDescription => "キーバインド、[keys] の割り当て、競合を表示",
+        MessageId::CmdShellsDescription => "永続シェルセッションを開く・アタッチ・終了",
+        MessageId::CmdThinkingDescription => {
+            "思考レベルまたはターンごとの予算を表示・設定 (off/low/medium/high/max/auto)"
+        }
         MessageId::CmdRestoreDescription => {
             "ワークスペースを以前のターン前/後スナップショットへロールバック。引数なしで最近のスナップショットを一覧表示。"
         }
+        MessageId::CmdSnapshotDescription => {
+            "名前付きワークスペーススナップショットを保存・復元・一覧・削除"
+        }
+        MessageId::CmdTrailDescription => "ターンごとの git 監査トレイルブランチを表示・切替・削除",
+        MessageId::CmdPreviewDescription => {
+            "Agent モードで実行するツール計画をプレビューし、ワンキーで実行"
+        }
+        MessageId::CmdEnvDescription => "シェルツールに注入する環境変数を一覧・設定・削除",
+        MessageId::CmdInboxDescription => {
+            "完了したバックグラウンドタスクとサブエージェントの結果を受信箱で開き、挿入・保存・破棄する"
+        }
+        MessageId::CmdRepomapDescription => {
+            "システムプロンプトに注入するリポジトリマップを表示・再構築"
+        }
         MessageId::CmdRetryDescription => "直前のリクエストを再試行",
         MessageId::CmdReviewDescription => "ファイル・diff・PR に対して構造化コードレビューを実行",
         MessageId::CmdRlmDescription => "永続 RLM コンテキストを開く: /rlm [0-3] <file_or_text>",
@@ -1373,6 +1507,9 @@ fn japanese(id: MessageId) -> Option<&'static str> {
         MessageId::CmdTaskDescription => "バックグラウンドタスクを管理",
         MessageId::CmdTokensDescription => "セッションのトークン使用量を表示",
         MessageId::CmdTranslateDescription => "出力翻訳を現在のシステム言語に切り替え",
+        MessageId::CmdSmartContextDescription => {
+            "関連するワークスペースのファイルを各プロンプトに自動で含める (on/off)"
+        }
         MessageId::CmdTranslateOff => "出力翻訳が無効になりました（元のモデル出力を表示）",
         MessageId::CmdTranslateOn => {
             "出力翻訳が有効になりました：モデル応答は現在のシステム言語で表示されます"
@@ -1404,6 +1541,9 @@ fn japanese(id: MessageId) -> Option<&'static str> {
         MessageId::CmdCacheTotals => {
             "Σ 入力: {sum_in}   Σ ヒット: {sum_hit}   Σ ミス: {sum_miss}   平均ヒット率: {avg}\n"
         }
+        MessageId::CmdCostCacheSavings => {
+            "キャッシュ節約額: {saved}（{hit} プロンプトトークン、{percent}% がコンテキストキャッシュから提供）"
+        }
         MessageId::CmdCostReport => {
             "セッション費用:\n\
              ─────────────────────────────\n\
@@ -1474,6 +1614,9 @@ fn japanese(id: MessageId) -> Option<&'static str> {
         MessageId::KbSelectedDetails => {
             "選択中のツールまたはメッセージの詳細を開く（入力が空の時）"
         }
+        MessageId::KbBookmarkCell => {
+            "選択中のメッセージをブックマーク。/bookmarks で戻る（入力が空の時）"
+        }
         MessageId::KbToolDetailsPager => "ツール詳細のページャーを開く",
         MessageId::KbThinkingPager => "Activity Detail を開く",
         MessageId::KbLiveTranscript => "ライブ会話履歴オーバーレイを開く（自動追尾スクロール）",
@@ -1488,8 +1631,14 @@ fn japanese(id: MessageId) -> Option<&'static str> {
         MessageId::KbFocusSidebar => {
             "Work / Tasks / Agents / Context / Auto / Hidden サイドバーにフォーカス"
         }
+        MessageId::KbToolOutputPane => {
+            "トランスクリプト横のツール出力ペインを開く / フォーカス / 閉じる"
+        }
         MessageId::KbTogglePlanAgent => "Plan モードと Agent モードを切り替え",
         MessageId::KbSessionPicker => "セッションピッカーを開く",
+        MessageId::KbNewSessionTab => "新しいセッションタブを開く",
+        MessageId::KbSwitchSessionTab => "セッションタブ 1〜9 に切り替える",
+        MessageId::KbCloseSessionTab => "表示中のセッションタブを閉じる",
         MessageId::KbPasteAttach => "テキストを貼り付けまたはクリップボード画像を添付",
         MessageId::KbCopySelection => "現在の選択をコピー（macOS は Cmd+C）",
         MessageId::KbContextMenu => {
@@ -1573,7 +1722,10 @@ fn japanese(id: MessageId) -> Option<&'static str> {
             "信頼されていない内容を扱うとプロンプトインジェクションのリスクが高くなります。"
         }
         MessageId::OnboardTrustEffectHint => {
-            "信頼するとグローバル設定に記録され、信頼済みワークスペースモードが有効になります。"
+            "選択は .deepseek/trust.json に保存されます。後から /trust level で変更できます。"
+        }
+        MessageId::OnboardTrustLevelHint => {
+            "3 で読み取り専用ツールのみ、4 でフルアクセス（サンドボックスなし・自動承認）。"
         }
         MessageId::OnboardTrustFooterPrefix => "キー ",
         MessageId::OnboardTrustFooterMiddle => " で信頼して続行、",
@@ -1654,6 +1806,9 @@ fn chinese_simplified(id: MessageId) -> Option<&'static str> {
         MessageId::CmdCycleDescription => "显示指定循环的延续简报",
         MessageId::CmdCyclesDescription => "列出本次会话中的检查点重启循环交接",
         MessageId::CmdDiffDescription => "显示会话开始以来的文件变更",
+        MessageId::CmdChangesDescription => {
+            "列出本次会话中由工具修改的文件，查看相对会话开始时的差异，并可逐个文件还原"
+        }
         MessageId::CmdEditDescription => "修改并重新提交最后一条消息",
         MessageId::CmdExitDescription => "退出应用",
         MessageId::CmdExportDescription => "将对话导出为 Markdown",
@@ -1669,14 +1824,18 @@ fn chinese_simplified(id: MessageId) -> Option<&'static str> {
         MessageId::CmdJobsDescription => "查看并管理后台 shell 作业",
         MessageId::CmdLinksDescription => "显示 DeepSeek 控制台与文档链接",
         MessageId::CmdLoadDescription => "从文件加载会话",
+        MessageId::CmdImportDescription => "导入 Markdown 对话或笔记",
+        MessageId::CmdBookmarksDescription => "列出对话书签并跳转",
         MessageId::CmdLogoutDescription => "清除 API 密钥并返回设置",
         MessageId::CmdMcpDescription => "打开或管理 MCP 服务器",
         MessageId::CmdMemoryDescription => "查看或管理持久用户记忆文件",
         MessageId::CmdModeDescription => "切换运行模式或打开选择器：/mode [agent|plan|yolo|1|2|3]",
+        MessageId::CmdPlanDescription => "显示当前计划，或导出为自动更新的 Markdown/Org 文档",
         MessageId::CmdModelDescription => "切换或查看当前模型",
         MessageId::CmdModelsDescription => "列出 API 中可用的模型",
         MessageId::CmdNetworkDescription => "管理网络允许和拒绝规则",
         MessageId::CmdNoteDescription => "添加、列出、编辑或删除工作区笔记",
+        MessageId::CmdEnhanceDescription => "在保留意图的前提下把草稿提示改写得更具体",
         MessageId::CmdThemeDescription => "切换主题：深色、浅色、灰度或系统",
         MessageId::CmdProviderDescription => {
             "切换或查看当前 LLM 后端（deepseek | nvidia-nim | ollama）"
@@ -1685,9 +1844,23 @@ fn chinese_simplified(id: MessageId) -> Option<&'static str> {
         MessageId::CmdRecallDescription => "搜索此前的循环归档（基于消息文本的 BM25 检索）",
         MessageId::CmdRelayDescription => "为新线程创建会话接力摘要",
         MessageId::CmdRenameDescription => "重命名当前会话",
+        MessageId::CmdReportDescription => "生成已脱敏的问题报告包（配置、功能开关、事件、日志）",
+        MessageId::CmdKeysDescription => "显示快捷键、[keys] 重映射和冲突",
+        MessageId::CmdShellsDescription => "打开、附加和终止持久 shell 会话",
+        MessageId::CmdThinkingDescription => {
+            "查看或设置思考档位 (off/low/medium/high/max/auto) 或每轮预算"
+        }
         MessageId::CmdRestoreDescription => {
             "将工作区回滚到此前的轮次前/后快照。不带参数时列出最近的快照。"
         }
+        MessageId::CmdSnapshotDescription => "保存、恢复、列出或删除命名的工作区快照",
+        MessageId::CmdTrailDescription => "查看、开关或清除按轮次提交的 git 审计分支",
+        MessageId::CmdPreviewDescription => "预览 Agent 模式将执行的工具计划，一键执行",
+        MessageId::CmdEnvDescription => "查看、设置或删除注入 shell 工具的环境变量",
+        MessageId::CmdInboxDescription => {
+            "打开已完成后台任务和子代理结果的收件箱，可插入、保存或忽略"
+        }
+        MessageId::CmdRepomapDescription => "查看或重建注入系统提示词的仓库地图",
         MessageId::CmdRetryDescription => "重试上一次请求",
         MessageId::CmdReviewDescription => "对文件、diff 或 PR 进行结构化代码审查",
         MessageId::CmdRlmDescription => "打开持久 RLM 上下文：/rlm [0-3] <file_or_text>",
@@ -1710,6 +1883,7 @@ fn chinese_simplified(id: MessageId) -> Option<&'static str> {
         MessageId::CmdTaskDescription => "管理后台任务",
         MessageId::CmdTokensDescription => "显示本次会话的 token 用量",
         MessageId::CmdTranslateDescription => "切换输出翻译为当前系统语言的开/关状态",
+        MessageId::CmdSmartContextDescription => "每次提问时自动附带相关的工作区文件 (on/off)",
         MessageId::CmdTranslateOff => "输出翻译已关闭（显示原始模型输出）",
         MessageId::CmdTranslateOn => "输出翻译已开启：模型回复将以当前系统语言显示",
         MessageId::TranslationInProgress => "正在翻译助手输出...",
@@ -1735,6 +1909,9 @@ fn chinese_simplified(id: MessageId) -> Option<&'static str> {
         MessageId::CmdCacheTotals => {
             "Σ 输入：{sum_in}   Σ 命中：{sum_hit}   Σ 未命中：{sum_miss}   平均命中率：{avg}\n"
         }
+        MessageId::CmdCostCacheSavings => {
+            "缓存节省：{saved}（{hit} 个提示词 token，{percent}% 命中上下文缓存）"
+        }
         MessageId::CmdCostReport => {
             "会话费用：\n\
              ─────────────────────────────\n\
@@ -1799,6 +1976,7 @@ fn chinese_simplified(id: MessageId) -> Option<&'static str> {
         MessageId::KbCompactInspector => "打开紧凑会话上下文检查器",
         MessageId::KbLastMessagePager => "打开最后一条消息的分页器（输入框为空时）",
         MessageId::KbSelectedDetails => "打开选中工具或消息的详情（输入框为空时）",
+        MessageId::KbBookmarkCell => "为选中的消息添加书签，/bookmarks 可跳回（输入框为空时）",
         MessageId::KbToolDetailsPager => "打开工具详情分页器",
         MessageId::KbThinkingPager => "打开 Activity Detail",
         MessageId::KbLiveTranscript => "打开实时对话覆盖层（自动滚动尾随）",
@@ -1809,8 +1987,12 @@ fn chinese_simplified(id: MessageId) -> Option<&'static str> {
         MessageId::KbJumpPlanAgentYolo => "直接跳转到 Plan / Agent / YOLO 模式",
         MessageId::KbAltJumpPlanAgentYolo => "替代快捷键跳转到 Plan / Agent / YOLO 模式",
         MessageId::KbFocusSidebar => "聚焦 Work / 任务 / 代理 / Context / 自动 / 隐藏侧边栏",
+        MessageId::KbToolOutputPane => "打开、聚焦或关闭对话旁的工具输出面板",
         MessageId::KbTogglePlanAgent => "在 Plan 和 Agent 模式之间切换",
         MessageId::KbSessionPicker => "打开会话选择器",
+        MessageId::KbNewSessionTab => "打开新的会话标签页",
+        MessageId::KbSwitchSessionTab => "切换到会话标签页 1–9",
+        MessageId::KbCloseSessionTab => "关闭当前会话标签页",
         MessageId::KbPasteAttach => "粘贴文本或附加剪贴板图片",
         MessageId::KbCopySelection => "复制当前选中内容（macOS 为 Cmd+C）",
         MessageId::KbContextMenu => "打开上下文操作菜单，用于粘贴、选择、消息详情、上下文和帮助",
@@ -1884,7 +2066,10 @@ fn chinese_simplified(id: MessageId) -> Option<&'static str> {
         MessageId::OnboardTrustLocationPrefix => "当前位置：",
         MessageId::OnboardTrustRiskHint => "处理不受信任的内容会增加提示词注入的风险。",
         MessageId::OnboardTrustEffectHint => {
-            "信任此目录会记录在全局配置中，并启用受信任工作区模式。"
+            "你的选择会保存到 .deepseek/trust.json；之后可用 /trust level 修改。"
+        }
+        MessageId::OnboardTrustLevelHint => {
+            "按 3 仅允许只读工具，按 4 完全访问（无沙箱、自动批准）。"
         }
         MessageId::OnboardTrustFooterPrefix => "按 ",
         MessageId::OnboardTrustFooterMiddle => " 信任并继续，",
@@ -1971,6 +2156,9 @@ fn portuguese_brazil(id: MessageId) -> Option<&'static str> {
             "Listar as transferências dos ciclos checkpoint-restart desta sessão"
         }
         MessageId::CmdDiffDescription => "Mostrar alterações em arquivos desde o início da sessão",
+        MessageId::CmdChangesDescription => {
+            "Listar arquivos alterados por ferramentas nesta sessão, com diff em relação ao início da sessão e reversão por arquivo"
+        }
         MessageId::CmdEditDescription => "Revisar e reenviar a última mensagem",
         MessageId::CmdExitDescription => "Sair do aplicativo",
         MessageId::CmdExportDescription => "Exportar a conversa para markdown",
@@ -1992,6 +2180,8 @@ fn portuguese_brazil(id: MessageId) -> Option<&'static str> {
         MessageId::CmdJobsDescription => "Inspecionar e controlar jobs de shell em segundo plano",
         MessageId::CmdLinksDescription => "Exibir links do painel e da documentação do DeepSeek",
         MessageId::CmdLoadDescription => "Carregar a sessão de um arquivo",
+        MessageId::CmdImportDescription => "Importar uma conversa ou notas em Markdown",
+        MessageId::CmdBookmarksDescription => "Listar marcadores da conversa e ir até um deles",
         MessageId::CmdLogoutDescription => "Limpar a chave de API e voltar à configuração",
         MessageId::CmdMcpDescription => "Abrir ou gerenciar servidores MCP",
         MessageId::CmdMemoryDescription => {
@@ -2000,10 +2190,16 @@ fn portuguese_brazil(id: MessageId) -> Option<&'static str> {
         MessageId::CmdModeDescription => {
             "Alternar modo ou abrir seletor: /mode [agent|plan|yolo|1|2|3]"
         }
+        MessageId::CmdPlanDescription => {
+            "Mostrar o plano atual ou exportá-lo como documento Markdown/Org atualizado"
+        }
         MessageId::CmdModelDescription => "Trocar ou exibir o modelo atual",
         MessageId::CmdModelsDescription => "Listar os modelos disponíveis pela API",
         MessageId::CmdNetworkDescription => "Gerenciar regras de rede permitidas e bloqueadas",
         MessageId::CmdNoteDescription => "Adicionar, listar, editar ou remover notas do workspace",
+        MessageId::CmdEnhanceDescription => {
+            "Reescrever um rascunho de prompt de forma mais específica, mantendo a intenção"
+        }
         MessageId::CmdThemeDescription => "Alternar tema: escuro, claro, tons de cinza ou sistema",
         MessageId::CmdProviderDescription => {
             "Trocar ou exibir o backend LLM ativo (deepseek | nvidia-nim | ollama)"
@@ -2014,9 +2210,37 @@ fn portuguese_brazil(id: MessageId) -> Option<&'static str> {
         }
         MessageId::CmdRelayDescription => "Criar um relay da sessão para um novo thread",
         MessageId::CmdRenameDescription => "Renomear a sessão atual",
+        MessageId::CmdReportDescription => {
+            "Gerar um pacote de relatório de bug sanitizado (config, flags, eventos, logs)"
+        }
+        MessageId::CmdKeysDescription => {
+            "Mostrar atalhos de teclado, remapeamentos de [keys] e conflitos"
+        }
+        MessageId::CmdShellsDescription => "Abrir, anexar e encerrar sessões de shell persistentes",
+        MessageId::CmdThinkingDescription => {
+            "Mostrar ou definir o nível de raciocínio (off/low/medium/high/max/auto) ou o orçamento por turno"
+        }
         MessageId::CmdRestoreDescription => {
             "Reverter o workspace a um snapshot pré/pós-turno anterior. Sem argumento, lista os snapshots recentes."
         }
+        MessageId::CmdSnapshotDescription => {
+            "Salvar, restaurar, listar ou excluir snapshots nomeados do workspace"
+        }
+        MessageId::CmdTrailDescription => {
+            "Mostrar, alternar ou limpar o branch git de auditoria por turno"
+        }
+        MessageId::CmdPreviewDescription => {
+            "Pré-visualizar o plano de ferramentas do modo Agent e executá-lo com uma tecla"
+        }
+        MessageId::CmdEnvDescription => {
+            "Listar, definir ou remover variáveis de ambiente injetadas nas ferramentas de shell"
+        }
+        MessageId::CmdInboxDescription => {
+            "Abrir a caixa de entrada de tarefas em segundo plano e subagentes concluídos para inserir, salvar ou descartar resultados"
+        }
+        MessageId::CmdRepomapDescription => {
+            "Mostrar ou reconstruir o mapa do repositório injetado no prompt de sistema"
+        }
         MessageId::CmdRetryDescription => "Repetir a última requisição",
         MessageId::CmdReviewDescription => {
             "Executar uma revisão de código estruturada em um arquivo, diff ou PR"
@@ -2049,6 +2273,9 @@ fn portuguese_brazil(id: MessageId) -> Option<&'static str> {
         MessageId::CmdTranslateDescription => {
             "Alternar tradução de saída para o idioma atual do sistema"
         }
+        MessageId::CmdSmartContextDescription => {
+            "Incluir automaticamente arquivos relevantes do workspace em cada prompt (on/off)"
+        }
         MessageId::CmdTranslateOff => {
             "Tradução de saída desativada (saída original do modelo exibida)"
         }
@@ -2082,6 +2309,9 @@ fn portuguese_brazil(id: MessageId) -> Option<&'static str> {
         MessageId::CmdCacheTotals => {
             "Σ entrada: {sum_in}   Σ hit: {sum_hit}   Σ miss: {sum_miss}   taxa média de hit: {avg}\n"
         }
+        MessageId::CmdCostCacheSavings => {
+            "Economia com cache: {saved} ({hit} tokens de prompt, {percent}% servidos pelo cache de contexto)"
+        }
         MessageId::CmdCostReport => {
             "Custo da sessão:\n\
              ─────────────────────────────\n\
@@ -2158,6 +2388,9 @@ fn portuguese_brazil(id: MessageId) -> Option<&'static str> {
         MessageId::KbSelectedDetails => {
             "Abrir detalhes da ferramenta ou mensagem selecionada (quando entrada vazia)"
         }
+        MessageId::KbBookmarkCell => {
+            "Marcar a mensagem selecionada; /bookmarks volta a ela (quando entrada vazia)"
+        }
         MessageId::KbToolDetailsPager => "Abrir paginador de detalhes da ferramenta",
         MessageId::KbThinkingPager => "Abrir Activity Detail",
         MessageId::KbLiveTranscript => "Abrir sobreposição de transcrição ao vivo (auto-scroll)",
@@ -2172,8 +2405,14 @@ fn portuguese_brazil(id: MessageId) -> Option<&'static str> {
         MessageId::KbFocusSidebar => {
             "Focar barra lateral Work / Tasks / Agents / Context / Auto / Ocultar"
         }
+        MessageId::KbToolOutputPane => {
+            "Abrir, focar ou fechar o painel de saída de ferramentas ao lado da conversa"
+        }
         MessageId::KbTogglePlanAgent => "Alternar entre modos Plan e Agent",
         MessageId::KbSessionPicker => "Abrir seletor de sessões",
+        MessageId::KbNewSessionTab => "Abrir nova aba de sessão",
+        MessageId::KbSwitchSessionTab => "Alternar para a aba de sessão 1–9",
+        MessageId::KbCloseSessionTab => "Fechar a aba de sessão atual",
         MessageId::KbPasteAttach => "Colar texto ou anexar imagem da área de transferência",
         MessageId::KbCopySelection => "Copiar seleção atual (Cmd+C no macOS)",
         MessageId::KbContextMenu => {
@@ -2261,7 +2500,10 @@ fn portuguese_brazil(id: MessageId) -> Option<&'static str> {
             "Trabalhar com conteúdo não confiável aumenta o risco de injeção de prompt."
         }
         MessageId::OnboardTrustEffectHint => {
-            "Confiar neste diretório o registra na configuração global e habilita o modo workspace confiável."
+            "Sua escolha é salva em .deepseek/trust.json; altere depois com /trust level."
+        }
+        MessageId::OnboardTrustLevelHint => {
+            "Pressione 3 para permitir só ferramentas de leitura, ou 4 para acesso total (sem sandbox, aprovação automática)."
         }
         MessageId::OnboardTrustFooterPrefix => "Pressione ",
         MessageId::OnboardTrustFooterMiddle => " para confiar e continuar, ",
@@ -2354,6 +2596,9 @@ fn spanish_latin_america(id: MessageId) -> Option<&'static str> {
             "Listar las transferencias de checkpoint-restart de esta sesión"
         }
         MessageId::CmdDiffDescription => "Mostrar cambios en archivos desde el inicio de la sesión",
+        MessageId::CmdChangesDescription => {
+            "Listar archivos cambiados por herramientas en esta sesión, con diff respecto al inicio de la sesión y reversión por archivo"
+        }
         MessageId::CmdEditDescription => "Revisar y reenviar el último mensaje",
         MessageId::CmdExitDescription => "Salir de la aplicación",
         MessageId::CmdExportDescription => "Exportar la conversación a markdown",
@@ -2379,6 +2624,8 @@ fn spanish_latin_america(id: MessageId) -> Option<&'static str> {
         }
         MessageId::CmdLinksDescription => "Mostrar enlaces del panel y documentación de DeepSeek",
         MessageId::CmdLoadDescription => "Cargar la sesión desde un archivo",
+        MessageId::CmdImportDescription => "Importar una conversación o notas en Markdown",
+        MessageId::CmdBookmarksDescription => "Listar marcadores de la conversación y saltar a uno",
         MessageId::CmdLogoutDescription => "Limpiar la clave de API y volver a la configuración",
         MessageId::CmdMcpDescription => "Abrir o gestionar servidores MCP",
         MessageId::CmdMemoryDescription => {
@@ -2387,10 +2634,16 @@ fn spanish_latin_america(id: MessageId) -> Option<&'static str> {
         MessageId::CmdModeDescription => {
             "Alternar modo o abrir selector: /mode [agent|plan|yolo|1|2|3]"
         }
+        MessageId::CmdPlanDescription => {
+            "Mostrar el plan actual o exportarlo como documento Markdown/Org actualizado"
+        }
         MessageId::CmdModelDescription => "Cambiar o mostrar el modelo actual",
         MessageId::CmdModelsDescription => "Listar los modelos disponibles por la API",
         MessageId::CmdNetworkDescription => "Gestionar reglas de red permitidas y bloqueadas",
         MessageId::CmdNoteDescription => "Agregar nota al archivo persistente (.deepseek/notes.md)",
+        MessageId::CmdEnhanceDescription => {
+            "Reescribir un borrador de prompt de forma más específica, manteniendo la intención"
+        }
         MessageId::CmdThemeDescription => "Alternar entre tema claro y oscuro",
         MessageId::CmdProviderDescription => {
             "Cambiar o mostrar el backend LLM activo (deepseek | nvidia-nim | ollama)"
@@ -2401,9 +2654,39 @@ fn spanish_latin_america(id: MessageId) -> Option<&'static str> {
         }
         MessageId::CmdRelayDescription => "Crear un relay de sesión (接力) para un hilo nuevo",
         MessageId::CmdRenameDescription => "Renombrar la sesión actual",
+        MessageId::CmdReportDescription => {
+            "Generar un paquete de informe de errores saneado (config, flags, eventos, logs)"
+        }
+        MessageId::CmdKeysDescription => {
+            "Mostrar atajos de teclado, reasignaciones de [keys] y conflictos"
+        }
+        MessageId::CmdShellsDescription => {
+            "Abrir, adjuntar y cerrar sesiones de shell persistentes"
+        }
+        MessageId::CmdThinkingDescription => {
+            "Mostrar o fijar el nivel de razonamiento (off/low/medium/high/max/auto)"
+        }
         MessageId::CmdRestoreDescription => {
             "Revertir el workspace a un snapshot pre/post-turno anterior. Sin argumento, lista los snapshots recientes."
         }
+        MessageId::CmdSnapshotDescription => {
+            "Guardar, restaurar, listar o eliminar snapshots con nombre del workspace"
+        }
+        MessageId::CmdTrailDescription => {
+            "Mostrar, activar o limpiar la rama git de auditoría por turno"
+        }
+        MessageId::CmdPreviewDescription => {
+            "Previsualizar el plan de herramientas del modo Agent y ejecutarlo con una tecla"
+        }
+        MessageId::CmdEnvDescription => {
+            "Listar, definir o quitar variables de entorno inyectadas en las herramientas de shell"
+        }
+        MessageId::CmdInboxDescription => {
+            "Abrir la bandeja de tareas en segundo plano y subagentes terminados para insertar, guardar o descartar resultados"
+        }
+        MessageId::CmdRepomapDescription => {
+            "Mostrar o reconstruir el mapa del repositorio inyectado en el prompt del sistema"
+        }
         MessageId::CmdRetryDescription => "Repetir la última solicitud",
         MessageId::CmdReviewDescription => {
             "Ejecutar una revisión de código estructurada en un archivo, diff o PR"
@@ -2438,6 +2721,9 @@ fn spanish_latin_america(id: MessageId) -> Option<&'static str> {
         MessageId::CmdTranslateDescription => {
             "Activar o desactivar la traducción de salida al idioma actual del sistema"
         }
+        MessageId::CmdSmartContextDescription => {
+            "Incluir automáticamente archivos relevantes del workspace en cada prompt (on/off)"
+        }
         MessageId::CmdTranslateOff => {
             "Traducción de salida desactivada (se muestra la salida original del modelo)"
         }
@@ -2473,6 +2759,9 @@ fn spanish_latin_america(id: MessageId) -> Option<&'static str> {
         MessageId::CmdCacheTotals => {
             "Σ entrada: {sum_in}   Σ hit: {sum_hit}   Σ miss: {sum_miss}   tasa promedio de hit: {avg}\n"
         }
+        MessageId::CmdCostCacheSavings => {
+            "Ahorro por caché: {saved} ({hit} tokens de prompt, {percent}% servidos desde la caché de contexto)"
+        }
         MessageId::CmdCostReport => {
             "Costo de la sesión:\n\
              ─────────────────────────────\n\
@@ -2549,6 +2838,9 @@ fn spanish_latin_america(id: MessageId) -> Option<&'static str> {
         MessageId::KbSelectedDetails => {
             "Abrir detalles de la herramienta o mensaje seleccionado (cuando la entrada está vacía)"
         }
+        MessageId::KbBookmarkCell => {
+            "Marcar el mensaje seleccionado; /bookmarks vuelve a él (cuando la entrada está vacía)"
+        }
         MessageId::KbToolDetailsPager => "Abrir paginador de detalles de la herramienta",
         MessageId::KbThinkingPager => "Abrir paginador de razonamiento",
         MessageId::KbLiveTranscript => "Abrir superposición de transcripción en vivo (auto-scroll)",
@@ -2563,8 +2855,14 @@ fn spanish_latin_america(id: MessageId) -> Option<&'static str> {
         MessageId::KbFocusSidebar => {
             "Enfocar barra lateral Work / Tasks / Agents / Context / Auto / Ocultar"
         }
+        MessageId::KbToolOutputPane => {
+            "Abrir, enfocar o cerrar el panel de salida de herramientas junto a la conversación"
+        }
         MessageId::KbTogglePlanAgent => "Alternar entre modos Plan y Agent",
         MessageId::KbSessionPicker => "Abrir selector de sesiones",
+        MessageId::KbNewSessionTab => "Abrir una nueva pestaña de sesión",
+        MessageId::KbSwitchSessionTab => "Cambiar a la pestaña de sesión 1–9",
+        MessageId::KbCloseSessionTab => "Cerrar la pestaña de sesión actual",
         MessageId::KbPasteAttach => "Pegar texto o adjuntar imagen del portapapeles",
         MessageId::KbCopySelection => "Copiar selección actual (Cmd+C en macOS)",
         MessageId::KbContextMenu => {
@@ -2651,7 +2949,10 @@ fn spanish_latin_america(id: MessageId) -> Option<&'static str> {
             "Trabajar con contenido no confiable aumenta el riesgo de inyección de prompt."
         }
         MessageId::OnboardTrustEffectHint => {
-            "Confiar en este directorio lo registra en la configuración global y habilita el modo workspace confiable."
+            "Tu elección se guarda en .deepseek/trust.json; cámbiala después con /trust level."
+        }
+        MessageId::OnboardTrustLevelHint => {
+            "Presiona 3 para permitir solo herramientas de lectura, o 4 para acceso total (sin sandbox, aprobación automática)."
         }
         MessageId::OnboardTrustFooterPrefix => "Presiona ",
         MessageId::OnboardTrustFooterMiddle => " para confiar y continuar, ",
@@ -17,8 +17,12 @@ mod artifacts;
 mod audit;
 mod auto_reasoning;
 mod automation_manager;
+mod bench;
 mod child_env;
+mod ci_gate;
 mod client;
+mod command_aliases;
+mod command_guardrail;
 mod command_safety;
 mod commands;
 mod compaction;
@@ -33,10 +37,15 @@ mod deepseek_theme;
 mod dependencies;
 mod error_taxonomy;
 mod eval;
+mod exec_script;
 mod execpolicy;
 mod features;
+mod git_trail;
 mod handoff;
+mod history_archive;
 mod hooks;
+mod inbox;
+mod lessons;
 mod llm_client;
 mod localization;
 mod logging;
@@ -47,38 +56,54 @@ mod memory;
 mod models;
 mod network_policy;
 mod palette;
+mod patch_resolve;
 mod prefix_cache;
 mod pricing;
 mod project_context;
 mod project_doc;
 mod prompts;
 pub mod repl;
+mod repo_map;
+mod report;
 mod retry_status;
+mod review_batch;
 pub mod rlm;
 mod runtime_api;
 mod runtime_log;
+mod runtime_metrics;
 mod runtime_threads;
 mod sandbox;
 mod schema_migration;
 mod seam_manager;
+mod self_report;
+mod session_import;
+mod session_log;
 mod session_manager;
+mod session_summary;
 mod settings;
 mod skill_state;
 mod skills;
 mod snapshot;
+mod stale_reads;
 mod task_manager;
 #[cfg(test)]
 mod test_support;
+mod tool_cli;
 mod tools;
+mod transcript_export;
+mod trust_level;
 mod tui;
 mod utils;
 mod vision;
+mod watch;
 mod working_set;
 mod workspace_trust;
 
 use crate::config::{Config, DEFAULT_TEXT_MODEL, MAX_SUBAGENTS};
-use crate::eval::{EvalHarness, EvalHarnessConfig, ScenarioStepKind};
-use crate::features::{Feature, render_feature_table};
+use crate::eval::{
+    EvalHarness, EvalHarnessConfig, ScenarioStepKind, SuiteReport, load_scenarios, run_scenarios,
+};
+use crate::features::{Feature, render_feature_json, render_feature_table};
 use crate::llm_client::LlmClient;
 use crate::mcp::{McpConfig, McpPool, McpServerConfig};
 use crate::models::{ContentBlock, Message, MessageRequest, SystemPrompt};
@@ -152,6 +177,15 @@ struct Cli {
     #[arg(short = 'c', long = "continue")]
     continue_session: bool,
 
+    /// Start from a markdown file: `## User` / `## Assistant` sections become
+    /// history, and plain notes (or a final user turn) land in the composer
+    #[arg(
+        long = "from-file",
+        value_name = "PATH",
+        conflicts_with_all = ["prompt", "resume", "continue_session"]
+    )]
+    from_file: Option<PathBuf>,
+
     /// Deprecated compatibility flag; the interactive TUI always owns the
     /// alternate screen so terminal scrollback cannot hijack the viewport.
     #[arg(long = "no-alt-screen", hide = true)]
@@ -185,6 +219,8 @@ struct Cli {
 enum Commands {
     /// Run system diagnostics and check configuration
     Doctor(DoctorArgs),
+    /// Write a sanitized bug-report bundle (config, flags, doctor, logs)
+    Report(ReportArgs),
     /// Bootstrap MCP config and/or skills directories
     Setup(SetupArgs),
     /// Generate shell completions
@@ -193,8 +229,10 @@ enum Commands {
         #[arg(value_enum)]
         shell: Shell,
     },
-    /// List saved sessions
+    /// List saved sessions, or import one from another agent CLI
     Sessions {
+        #[command(subcommand)]
+        command: Option<SessionsCommand>,
         /// Maximum number of sessions to display
         #[arg(short, long, default_value = "20")]
         limit: usize,
@@ -218,6 +256,8 @@ enum Commands {
     Exec(ExecArgs),
     /// Run a code review over a git diff
     Review(ReviewArgs),
+    /// Run an agent turn whenever matching files change
+    Watch(WatchArgs),
     /// Open the TUI pre-seeded with a GitHub PR's title, body, and diff (#451)
     Pr {
         /// PR number
@@ -233,15 +273,23 @@ enum Commands {
         #[arg(long, default_value_t = false)]
         checkout: bool,
     },
-    /// Apply a patch file (or stdin) to the working tree
+    /// Apply a patch file (or stdin) to the working tree, falling back to a
+    /// 3-way merge and per-hunk placement when the tree has drifted
     Apply(ApplyArgs),
     /// Run the offline evaluation harness (no network/LLM calls)
     Eval(EvalArgs),
+    /// Run a YAML task suite against several models and compare the scores
+    Bench(BenchArgs),
     /// Manage MCP servers
     Mcp {
         #[command(subcommand)]
         command: McpCommand,
     },
+    /// Run the agent's tools directly, without the model
+    Tool {
+        #[command(subcommand)]
+        command: ToolCommand,
+    },
     /// Execpolicy tooling
     Execpolicy(ExecpolicyCommand),
     /// Inspect feature flags
@@ -275,7 +323,7 @@ struct ExecArgs {
     /// Prompt to send to the model
     #[arg(
         value_name = "PROMPT",
-        required = true,
+        required_unless_present = "interactive_script",
         trailing_var_arg = true,
         allow_hyphen_values = true
     )]
@@ -301,6 +349,118 @@ struct ExecArgs {
     /// Output format for exec mode
     #[arg(long, value_enum, default_value_t = ExecOutputFormat::Text)]
     output_format: ExecOutputFormat,
+    /// Run newline-delimited prompts, or a YAML list of steps with
+    /// expectations, in one session; reads stdin when PATH is omitted or `-`.
+    /// Emits one JSON line per step.
+    #[arg(
+        long = "interactive-script",
+        value_name = "PATH",
+        num_args = 0..=1,
+        default_missing_value = "-",
+        conflicts_with_all = ["prompt", "json", "output_format", "resume", "session_id", "continue_session"]
+    )]
+    interactive_script: Option<PathBuf>,
+    /// Run the prompt as a CI gate: the reply must end with a findings
+    /// report, then the `[gate]` post-conditions in config.toml (maximum
+    /// severity, required checks) set the exit code. Findings print as
+    /// GitHub Actions annotations.
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["interactive_script", "json", "output_format", "resume", "session_id", "continue_session"]
+    )]
+    gate: bool,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum SessionsCommand {
+    /// Convert another tool's conversation export into a saved session
+    Import(SessionsImportArgs),
+    /// Play a saved session back read-only, with its original timing
+    Replay(SessionsReplayArgs),
+    /// Write a saved session as Markdown or JSON, optionally redacted
+    Export(SessionsExportArgs),
+    /// Apply the `[sessions]` retention limits to saved sessions
+    Gc(SessionsGcArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+struct SessionsGcArgs {
+    /// List what would be removed without deleting anything
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+struct SessionsExportArgs {
+    /// Session ID or prefix
+    #[arg(value_name = "ID")]
+    id: String,
+    /// Output format (default: from the output extension, else markdown)
+    #[arg(long, value_enum)]
+    format: Option<transcript_export::ExportFormat>,
+    /// What to strip: comma-separated `thinking`, `secrets`, `shell-output`, or `all`
+    #[arg(long, value_name = "LIST", value_parser = parse_redaction)]
+    redact: Option<transcript_export::Redaction>,
+    /// Write here instead of stdout
+    #[arg(short, long, value_name = "PATH")]
+    output: Option<PathBuf>,
+}
+
+fn parse_redaction(list: &str) -> std::result::Result<transcript_export::Redaction, String> {
+    transcript_export::Redaction::parse(list)
+}
+
+#[derive(Args, Debug, Clone)]
+struct SessionsReplayArgs {
+    /// Session ID or prefix
+    #[arg(value_name = "ID")]
+    id: String,
+    /// Start paused at the first edit of a file whose path contains this
+    #[arg(long, value_name = "PATH")]
+    file: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+struct SessionsImportArgs {
+    /// Transcript to import (JSONL)
+    #[arg(value_name = "PATH")]
+    path: PathBuf,
+    /// Format of the transcript
+    #[arg(long, value_enum)]
+    from: session_import::ImportFormat,
+    /// Model the imported session resumes with (default: configured model)
+    #[arg(long)]
+    model: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+struct ReportArgs {
+    /// Write the bundle here instead of ~/.deepseek/reports/
+    #[arg(short, long, value_name = "PATH")]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args, Debug, Clone)]
+struct WatchArgs {
+    /// Glob (relative to the workspace) whose changes trigger a run; repeatable
+    #[arg(long = "on-change", value_name = "GLOB", required = true)]
+    on_change: Vec<String>,
+    /// Prompt sent on every run; the changed paths are appended to it
+    #[arg(long)]
+    prompt: String,
+    /// Override model for each run
+    #[arg(long)]
+    model: Option<String>,
+    /// Enable agentic mode with tool access and auto-approvals
+    #[arg(long, default_value_t = false)]
+    auto: bool,
+    /// Quiet period (milliseconds) after the last change before a run starts
+    #[arg(long, default_value_t = watch::DEFAULT_DEBOUNCE_MS)]
+    debounce_ms: u64,
+    /// Exit after this many runs instead of watching until interrupted
+    #[arg(long, value_name = "N")]
+    max_runs: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
@@ -439,6 +599,31 @@ struct EvalArgs {
     /// Mock LLM tests can later replay these fixtures.
     #[arg(long, value_name = "DIR")]
     record: Option<PathBuf>,
+    /// Run YAML scenarios from a file or directory instead of the built-in one
+    #[arg(long, value_name = "PATH")]
+    scenario: Option<PathBuf>,
+    /// Write the scenario suite report as JSON to this file
+    #[arg(long, value_name = "FILE", requires = "scenario")]
+    report: Option<PathBuf>,
+    /// Compare against a previous `--report` file; only regressions fail the run
+    #[arg(long, value_name = "FILE", requires = "scenario")]
+    baseline: Option<PathBuf>,
+}
+
+#[derive(Args, Debug, Clone)]
+struct BenchArgs {
+    /// YAML suite of tasks, seed files, and assertions
+    #[arg(long, value_name = "FILE")]
+    suite: PathBuf,
+    /// Comma-separated models to compare
+    #[arg(long, value_name = "MODELS", value_delimiter = ',', required = true)]
+    models: Vec<String>,
+    /// Emit the report as JSON instead of a table
+    #[arg(long, default_value_t = false)]
+    json: bool,
+    /// Also write the JSON report to this file
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
 }
 
 #[derive(Args, Debug, Clone, Default)]
@@ -491,6 +676,18 @@ struct ReviewArgs {
     /// Emit machine-readable JSON output
     #[arg(long, default_value_t = false)]
     json: bool,
+    /// Review commit ranges in parallel (comma-separated, e.g. main..feat,v1..v2)
+    #[arg(long, value_delimiter = ',', conflicts_with_all = ["staged", "base"])]
+    commits: Vec<String>,
+    /// Review GitHub pull requests by number in parallel (comma-separated, via `gh`)
+    #[arg(long, value_delimiter = ',', conflicts_with_all = ["staged", "base"])]
+    prs: Vec<u32>,
+    /// Repository for --prs as OWNER/REPO (defaults to the current repository)
+    #[arg(long, requires = "prs")]
+    repo: Option<String>,
+    /// Maximum reviews in flight for --commits / --prs
+    #[arg(long, default_value_t = 4)]
+    jobs: usize,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -533,6 +730,59 @@ struct ServeArgs {
     /// Disable runtime API auth when no token is configured. Only use on a trusted loopback.
     #[arg(long = "insecure")]
     insecure_no_auth: bool,
+    /// Accept this second bearer token for GET/HEAD routes only. Also reads
+    /// `DEEPSEEK_RUNTIME_READ_TOKEN` and `[runtime_api] read_only_token`.
+    #[arg(long = "read-token", value_name = "TOKEN")]
+    read_token: Option<String>,
+    /// Write the control token here (mode 0600) for local clients. Defaults
+    /// to `[runtime_api] token_file`, then `~/.deepseek/runtime/token`.
+    #[arg(long = "token-file", value_name = "PATH")]
+    token_file: Option<PathBuf>,
+    /// PEM certificate chain; serves HTTPS together with `--tls-key`.
+    #[arg(long = "tls-cert", value_name = "PATH", requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+    /// PEM private key for `--tls-cert`.
+    #[arg(long = "tls-key", value_name = "PATH", requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+    /// PEM CA bundle; require client certificates it signed (mTLS).
+    #[arg(long = "tls-client-ca", value_name = "PATH")]
+    tls_client_ca: Option<PathBuf>,
+    /// Turn-creating requests allowed per minute (0 disables). Defaults to
+    /// `[runtime_api] turns_per_minute`, then 30.
+    #[arg(long = "turns-per-minute", value_name = "N")]
+    turns_per_minute: Option<u32>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ToolCommand {
+    /// List the tools available to `tool run`
+    List {
+        /// Include each tool's parameters
+        #[arg(long, default_value_t = false)]
+        schemas: bool,
+        /// Print the catalog as JSON (with `--schemas`, the full input schemas)
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+    /// Run one tool call against the workspace
+    ///
+    /// Parameters are passed as `--<param> <value>` (e.g. `deepseek tool run
+    /// grep_files --pattern TODO --json`). `--input <JSON|->` supplies the
+    /// whole input object, `--json` prints a structured result, `--full`
+    /// skips the spillover cap, and `--yes` allows tools that would need
+    /// approval in the TUI.
+    Run {
+        /// Tool name, as shown by `deepseek tool list`
+        #[arg(value_name = "TOOL")]
+        name: String,
+        /// Tool parameters and run options
+        #[arg(
+            value_name = "ARGS",
+            trailing_var_arg = true,
+            allow_hyphen_values = true
+        )]
+        args: Vec<String>,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -616,6 +866,10 @@ struct ExecpolicyCommand {
 enum ExecpolicySubcommand {
     /// Check execpolicy files against a command
     Check(execpolicy::ExecPolicyCheckCommand),
+    /// Run the fixtures embedded in an execpolicy pack
+    Test(execpolicy::ExecPolicyTestCommand),
+    /// List execpolicy packs and whether each is enabled
+    Packs(execpolicy::ExecPolicyPacksCommand),
 }
 
 #[derive(Args, Debug, Clone)]
@@ -627,7 +881,11 @@ struct FeaturesCli {
 #[derive(Subcommand, Debug, Clone)]
 enum FeaturesSubcommand {
     /// List known feature flags and their state
-    List,
+    List {
+        /// Emit machine-readable JSON, including per-feature values
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
 }
 
 #[derive(Args, Debug, Clone)]
@@ -744,6 +1002,20 @@ async fn main() -> Result<()> {
                     Ok(())
                 }
             }
+            Commands::Report(args) => {
+                let config = load_config_from_cli(&cli)?;
+                let workspace = resolve_workspace(&cli);
+                let path = report::write_report(
+                    &config,
+                    &workspace,
+                    cli.config.as_deref(),
+                    report::EventSource::AuditLog,
+                    args.output.as_deref(),
+                )?;
+                println!("Report written to {}", path.display());
+                println!("Secrets are redacted, but review it before attaching it to an issue.");
+                Ok(())
+            }
             Commands::Setup(args) => {
                 let config = load_config_from_cli(&cli)?;
                 let workspace = resolve_workspace(&cli);
@@ -753,7 +1025,34 @@ async fn main() -> Result<()> {
                 generate_completions(shell);
                 Ok(())
             }
-            Commands::Sessions { limit, search } => list_sessions(limit, search),
+            Commands::Sessions {
+                command: Some(SessionsCommand::Import(args)),
+                ..
+            } => {
+                let config = load_config_from_cli(&cli)?;
+                let workspace = resolve_workspace(&cli);
+                run_sessions_import(&config, &workspace, args)
+            }
+            Commands::Sessions {
+                command: Some(SessionsCommand::Replay(args)),
+                ..
+            } => run_sessions_replay(&args),
+            Commands::Sessions {
+                command: Some(SessionsCommand::Export(args)),
+                ..
+            } => run_sessions_export(&args),
+            Commands::Sessions {
+                command: Some(SessionsCommand::Gc(args)),
+                ..
+            } => {
+                let config = load_config_from_cli(&cli)?;
+                run_sessions_gc(&config, &args)
+            }
+            Commands::Sessions {
+                command: None,
+                limit,
+                search,
+            } => list_sessions(limit, search),
             Commands::Init => init_project(),
             Commands::Login { api_key } => run_login(api_key),
             Commands::Logout => run_logout(),
@@ -768,10 +1067,44 @@ async fn main() -> Result<()> {
                     .clone()
                     .or_else(|| config.default_text_model.clone())
                     .unwrap_or_else(|| config.default_model());
+                if let Some(script) = args.interactive_script.clone() {
+                    let max_subagents = cli.max_subagents.map_or_else(
+                        || config.max_subagents(),
+                        |value| value.clamp(1, MAX_SUBAGENTS),
+                    );
+                    return exec_script::run(
+                        &config,
+                        exec_script::ScriptOptions {
+                            script,
+                            model,
+                            workspace: resolve_workspace(&cli),
+                            max_subagents,
+                            auto_approve: args.auto || cli.yolo,
+                        },
+                    )
+                    .await;
+                }
                 let prompt = join_prompt_parts(&args.prompt);
                 let workspace = cli.workspace.clone().unwrap_or_else(|| {
                     std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
                 });
+                if args.gate {
+                    let max_subagents = cli.max_subagents.map_or_else(
+                        || config.max_subagents(),
+                        |value| value.clamp(1, MAX_SUBAGENTS),
+                    );
+                    return ci_gate::run(
+                        &config,
+                        ci_gate::GateOptions {
+                            prompt,
+                            model,
+                            workspace,
+                            max_subagents,
+                            auto_approve: args.auto || cli.yolo,
+                        },
+                    )
+                    .await;
+                }
                 let resume_session_id = resolve_exec_resume_session_id(&args, &workspace)?;
                 let needs_engine = args.auto
                     || cli.yolo
@@ -806,6 +1139,10 @@ async fn main() -> Result<()> {
                 let config = load_config_from_cli(&cli)?;
                 run_review(&config, args).await
             }
+            Commands::Watch(args) => {
+                let config = load_config_from_cli(&cli)?;
+                run_watch(&cli, &config, args).await
+            }
             Commands::Pr {
                 number,
                 repo,
@@ -816,10 +1153,29 @@ async fn main() -> Result<()> {
             }
             Commands::Apply(args) => run_apply(args),
             Commands::Eval(args) => run_eval(args),
+            Commands::Bench(args) => {
+                let config = load_config_from_cli(&cli)?;
+                run_bench(&config, args).await
+            }
             Commands::Mcp { command } => {
                 let config = load_config_from_cli(&cli)?;
                 run_mcp_command(&config, command).await
             }
+            Commands::Tool { command } => {
+                let workspace = resolve_workspace(&cli);
+                match command {
+                    ToolCommand::List { schemas, json } => {
+                        tool_cli::run_list(&workspace, schemas, json)
+                    }
+                    ToolCommand::Run { name, args } => {
+                        let code = tool_cli::run_tool(&workspace, &name, &args).await?;
+                        if code != 0 {
+                            std::process::exit(code);
+                        }
+                        Ok(())
+                    }
+                }
+            }
             Commands::Execpolicy(command) => {
                 let config = load_config_from_cli(&cli)?;
                 if !config.features().enabled(Feature::ExecPolicy) {
@@ -846,10 +1202,21 @@ async fn main() -> Result<()> {
                     bail!("Choose exactly one server mode: --mcp, --http, or --acp");
                 }
                 if args.mcp {
-                    mcp_server::run_mcp_server(workspace)
+                    let config = load_config_from_cli(&cli)?;
+                    mcp_server::run_mcp_server(&config, workspace)
                 } else if args.http {
                     let config = load_config_from_cli(&cli)?;
                     let cors_origins = resolve_cors_origins(&config, &args.cors_origin);
+                    let tls = resolve_runtime_tls(&config, &args)?;
+                    let turns_per_minute = args
+                        .turns_per_minute
+                        .or_else(|| {
+                            config
+                                .runtime_api
+                                .as_ref()
+                                .and_then(|rt| rt.turns_per_minute)
+                        })
+                        .unwrap_or(runtime_api::DEFAULT_TURNS_PER_MINUTE);
                     runtime_api::run_http_server(
                         config,
                         workspace,
@@ -860,6 +1227,10 @@ async fn main() -> Result<()> {
                             cors_origins,
                             auth_token: args.auth_token,
                             insecure_no_auth: args.insecure_no_auth,
+                            read_token: args.read_token,
+                            token_file: args.token_file,
+                            tls,
+                            turns_per_minute,
                         },
                     )
                     .await
@@ -894,6 +1265,12 @@ async fn main() -> Result<()> {
         return run_one_shot(&config, &model, &prompt).await;
     }
 
+    if let Some(path) = cli.from_file.clone() {
+        let workspace = resolve_workspace(&cli);
+        let (resume_session_id, draft) = import_markdown_session(&config, &workspace, &path)?;
+        return run_interactive(&cli, &config, resume_session_id, draft).await;
+    }
+
     // Handle session resume. Plain `deepseek` starts fresh: interrupted
     // snapshots are preserved for explicit resume, but never auto-attached.
     let resume_session_id = if cli.continue_session {
@@ -924,6 +1301,10 @@ fn generate_completions(shell: Shell) {
 
 /// Run the offline evaluation harness (no network/LLM calls).
 fn run_eval(args: EvalArgs) -> Result<()> {
+    if let Some(path) = args.scenario.as_deref() {
+        return run_eval_scenarios(path, &args);
+    }
+
     let fail_step = match args.fail_step.as_deref() {
         Some(value) => ScenarioStepKind::parse(value)
             .map(Some)
@@ -942,7 +1323,12 @@ fn run_eval(args: EvalArgs) -> Result<()> {
 
     let harness = EvalHarness::new(config);
     let run = harness.run().context("evaluation harness failed")?;
-    let report = run.to_report();
+    let mut report = run.to_report();
+    let mut error_classes = crate::error_taxonomy::ToolErrorStats::default();
+    for error in report.steps.iter().filter_map(|step| step.error.as_deref()) {
+        error_classes.record(crate::error_taxonomy::ToolErrorClass::from_message(error));
+    }
+    report.metrics.error_classes = error_classes.counts();
 
     if args.json {
         let json = serde_json::to_string_pretty(&report)?;
@@ -954,6 +1340,9 @@ fn run_eval(args: EvalArgs) -> Result<()> {
         println!("success: {}", report.metrics.success);
         println!("steps: {}", report.metrics.steps);
         println!("tool_errors: {}", report.metrics.tool_errors);
+        if !error_classes.is_empty() {
+            println!("error_classes: {}", error_classes.summary());
+        }
         println!("duration_ms: {}", report.metrics.duration.as_millis());
 
         if !report.metrics.per_tool.is_empty() {
@@ -991,6 +1380,194 @@ fn run_eval(args: EvalArgs) -> Result<()> {
     }
 }
 
+fn run_eval_scenarios(path: &Path, args: &EvalArgs) -> Result<()> {
+    let scenarios = load_scenarios(path)?;
+    let report = run_scenarios(&scenarios, args.max_output_chars);
+    let baseline = match args.baseline.as_deref() {
+        Some(file) => {
+            let raw = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read baseline {}", file.display()))?;
+            let baseline: SuiteReport = serde_json::from_str(&raw)
+                .with_context(|| format!("Invalid baseline report {}", file.display()))?;
+            Some(baseline)
+        }
+        None => None,
+    };
+    let comparison = baseline.as_ref().map(|baseline| report.compare(baseline));
+
+    if let Some(file) = args.report.as_deref() {
+        ensure_parent_dir(file)?;
+        std::fs::write(file, serde_json::to_string_pretty(&report)?)
+            .with_context(|| format!("Failed to write report {}", file.display()))?;
+    }
+
+    if args.json {
+        let json = serde_json::json!({ "report": report, "comparison": comparison });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+    } else {
+        println!("Offline Eval Scenarios");
+        for scenario in &report.scenarios {
+            let status = if scenario.passed { "PASS" } else { "FAIL" };
+            println!(
+                "  {status} {} steps={} duration_ms={}",
+                scenario.name,
+                scenario.steps.len(),
+                scenario.duration_ms
+            );
+            for failure in &scenario.failures {
+                println!("       {failure}");
+            }
+        }
+        println!("passed: {} failed: {}", report.passed, report.failed);
+        if let Some(comparison) = &comparison {
+            for (label, names) in [
+                ("regressions", &comparison.regressions),
+                ("fixed", &comparison.fixed),
+                ("added", &comparison.added),
+                ("removed", &comparison.removed),
+            ] {
+                if !names.is_empty() {
+                    println!("{label}: {}", names.join(", "));
+                }
+            }
+        }
+    }
+
+    // With a baseline, only new failures are fatal so known-broken
+    // scenarios can be tracked without failing every run.
+    match comparison {
+        Some(comparison) if !comparison.regressions.is_empty() => {
+            bail!("eval scenarios regressed against the baseline")
+        }
+        None if report.failed > 0 => bail!("{} eval scenario(s) failed", report.failed),
+        _ => Ok(()),
+    }
+}
+
+/// Run a benchmark suite against each model with real model calls.
+async fn run_bench(config: &Config, args: BenchArgs) -> Result<()> {
+    let suite = bench::BenchSuite::load(&args.suite)?;
+    let models: Vec<String> = args
+        .models
+        .iter()
+        .map(|model| model.trim().to_string())
+        .filter(|model| !model.is_empty())
+        .collect();
+    if models.is_empty() {
+        bail!("--models needs at least one model");
+    }
+    eprintln!(
+        "Running {} task(s) x {} model(s); tasks run auto-approved in temporary workspaces.",
+        suite.tasks.len(),
+        models.len()
+    );
+
+    let report = bench::run_suite(
+        &suite,
+        &models,
+        |model, prompt, workspace| async move {
+            run_bench_agent(config, &model, &prompt, workspace).await
+        },
+        |result| {
+            let status = if result.passed { "PASS" } else { "FAIL" };
+            eprintln!(
+                "  {status} {} [{}] {}/{} in {:.1}s",
+                result.task,
+                result.model,
+                result.score,
+                result.total,
+                result.duration_ms as f64 / 1000.0
+            );
+            if let Some(error) = &result.error {
+                eprintln!("       error: {error}");
+            }
+            for failure in &result.failures {
+                eprintln!("       {failure}");
+            }
+        },
+    )
+    .await;
+
+    if let Some(file) = args.report.as_deref() {
+        ensure_parent_dir(file)?;
+        std::fs::write(file, serde_json::to_string_pretty(&report)?)
+            .with_context(|| format!("Failed to write report {}", file.display()))?;
+    }
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", report.table());
+    }
+    Ok(())
+}
+
+/// Drive one auto-approved agent turn for `deepseek bench` and collect what
+/// the scorer needs.
+async fn run_bench_agent(
+    config: &Config,
+    model: &str,
+    prompt: &str,
+    workspace: PathBuf,
+) -> Result<bench::AgentOutcome> {
+    use crate::core::engine::spawn_engine;
+    use crate::core::events::Event;
+    use crate::core::ops::Op;
+
+    let route = resolve_cli_auto_route(config, model, prompt).await;
+    let engine_config = exec_engine_config(
+        config,
+        &route.model,
+        &workspace,
+        config.max_subagents(),
+        true,
+        true,
+    );
+    let engine_handle = spawn_engine(engine_config, config);
+    engine_handle
+        .send(exec_send_message(config, prompt, route, true, true))
+        .await?;
+
+    let mut outcome = bench::AgentOutcome::default();
+    loop {
+        let event = {
+            let mut rx = engine_handle.rx_event.write().await;
+            rx.recv().await
+        };
+        let Some(event) = event else {
+            outcome
+                .error
+                .get_or_insert_with(|| "engine stopped before the turn completed".to_string());
+            break;
+        };
+        match event {
+            Event::MessageDelta { content, .. } => outcome.final_response.push_str(&content),
+            Event::ToolCallComplete { .. } => outcome.tool_calls += 1,
+            Event::ApprovalRequired { id, .. } => {
+                let _ = engine_handle.approve_tool_call(id).await;
+            }
+            Event::ElevationRequired { tool_id, .. } => {
+                let policy = crate::sandbox::SandboxPolicy::DangerFullAccess;
+                let _ = engine_handle.retry_tool_with_policy(tool_id, policy).await;
+            }
+            Event::Error {
+                envelope,
+                recoverable: false,
+            } => outcome.error = Some(envelope.message),
+            Event::TurnComplete { usage, error, .. } => {
+                outcome.input_tokens = u64::from(usage.input_tokens);
+                outcome.output_tokens = u64::from(usage.output_tokens);
+                if error.is_some() {
+                    outcome.error = error;
+                }
+                let _ = engine_handle.send(Op::Shutdown).await;
+                break;
+            }
+            _ => {}
+        }
+    }
+    Ok(outcome)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum WriteStatus {
     Created,
@@ -1045,6 +1622,8 @@ fn mcp_template_json() -> Result<String> {
             enabled_tools: Vec::new(),
             disabled_tools: Vec::new(),
             headers: std::collections::HashMap::new(),
+            resources_enabled: true,
+            prompts_enabled: true,
         },
     );
     serde_json::to_string_pretty(&cfg)
@@ -1206,6 +1785,42 @@ fn resolve_cors_origins(config: &Config, flag_origins: &[String]) -> Vec<String>
     out
 }
 
+/// Resolve TLS termination for `deepseek serve --http`. `--tls-cert` and
+/// `--tls-key` replace `[runtime_api.tls]` as a pair; `--tls-client-ca`
+/// overrides `client_ca` on its own.
+fn resolve_runtime_tls(
+    config: &Config,
+    args: &ServeArgs,
+) -> Result<Option<runtime_api::TlsSettings>> {
+    let configured = config.runtime_api.as_ref().and_then(|rt| rt.tls.as_ref());
+    let config_path = |value: Option<&String>| {
+        value
+            .map(|path| path.trim())
+            .filter(|path| !path.is_empty())
+            .map(crate::config::expand_path)
+    };
+    let (cert, key) = match (&args.tls_cert, &args.tls_key) {
+        (Some(cert), Some(key)) => (Some(cert.clone()), Some(key.clone())),
+        _ => (
+            config_path(configured.and_then(|tls| tls.cert.as_ref())),
+            config_path(configured.and_then(|tls| tls.key.as_ref())),
+        ),
+    };
+    let client_ca = args
+        .tls_client_ca
+        .clone()
+        .or_else(|| config_path(configured.and_then(|tls| tls.client_ca.as_ref())));
+    match (cert, key) {
+        (Some(cert), Some(key)) => Ok(Some(runtime_api::TlsSettings {
+            cert,
+            key,
+            client_ca,
+        })),
+        (None, None) if client_ca.is_none() => Ok(None),
+        _ => bail!("Runtime API TLS needs both a certificate and a key"),
+    }
+}
+
 fn deepseek_home_dir() -> PathBuf {
     dirs::home_dir().map_or_else(|| PathBuf::from(".deepseek"), |h| h.join(".deepseek"))
 }
@@ -2458,8 +3073,9 @@ async fn run_doctor(config: &Config, workspace: &Path, config_path_override: Opt
     // Platform and sandbox checks
     println!();
     println!("{}", "Platform:".bold());
-    println!("  OS: {}", std::env::consts::OS);
-    println!("  Arch: {}", std::env::consts::ARCH);
+    for line in crate::tools::system_info::SystemInfo::collect(workspace).summary_lines() {
+        println!("  {line}");
+    }
 
     let sandbox = crate::sandbox::get_platform_sandbox();
     if let Some(kind) = sandbox {
@@ -2491,18 +3107,36 @@ fn run_doctor_json(
     workspace: &Path,
     config_path_override: Option<&Path>,
 ) -> Result<()> {
-    use serde_json::json;
+    let report = doctor_json_report(config, workspace, config_path_override);
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
 
+/// Config file the doctor reports on: `--config`, then
+/// `$DEEPSEEK_CONFIG_PATH`, then `~/.deepseek/config.toml`.
+fn doctor_config_path(config_path_override: Option<&Path>) -> PathBuf {
     let default_config_dir =
         dirs::home_dir().map_or_else(|| PathBuf::from(".deepseek"), |h| h.join(".deepseek"));
-    let config_path = config_path_override
+    config_path_override
         .map(PathBuf::from)
         .or_else(|| {
             std::env::var("DEEPSEEK_CONFIG_PATH")
                 .ok()
                 .map(PathBuf::from)
         })
-        .unwrap_or_else(|| default_config_dir.join("config.toml"));
+        .unwrap_or_else(|| default_config_dir.join("config.toml"))
+}
+
+/// The `deepseek doctor --json` report. Also embedded in `deepseek report`
+/// bundles.
+fn doctor_json_report(
+    config: &Config,
+    workspace: &Path,
+    config_path_override: Option<&Path>,
+) -> serde_json::Value {
+    use serde_json::json;
+
+    let config_path = doctor_config_path(config_path_override);
 
     let api_key_state = match resolve_api_key_source(config) {
         ApiKeySource::Env => "env",
@@ -2617,7 +3251,7 @@ fn run_doctor_json(
     let api_target = doctor_api_target(config);
     let strict_tool_mode = doctor_strict_tool_mode_status(config);
 
-    let report = json!({
+    json!({
         "version": env!("CARGO_PKG_VERSION"),
         "config_path": config_path.display().to_string(),
         "config_present": config_path.exists(),
@@ -2701,19 +3335,13 @@ fn run_doctor_json(
             Some(kind) => json!({"available": true, "kind": kind.to_string()}),
             None => json!({"available": false, "kind": null}),
         },
-        "platform": {
-            "os": std::env::consts::OS,
-            "arch": std::env::consts::ARCH,
-        },
+        "platform": crate::tools::system_info::SystemInfo::collect(workspace),
         "api_connectivity": {
             "checked": false,
             "note": "Skipped in --json mode; run `deepseek doctor` for a live check.",
         },
         "capability": provider_capability_report(config),
-    });
-
-    println!("{}", serde_json::to_string_pretty(&report)?);
-    Ok(())
+    })
 }
 
 /// Build the `capability` section for the machine-readable doctor report.
@@ -2872,13 +3500,23 @@ fn doctor_timeout_recovery_lines(config: &Config) -> Vec<String> {
 fn run_execpolicy_command(command: ExecpolicyCommand) -> Result<()> {
     match command.command {
         ExecpolicySubcommand::Check(cmd) => cmd.run(),
+        ExecpolicySubcommand::Test(cmd) => cmd.run(),
+        ExecpolicySubcommand::Packs(cmd) => cmd.run(),
     }
 }
 
 fn run_features_command(config: &Config, command: FeaturesCli) -> Result<()> {
     match command.command {
-        FeaturesSubcommand::List => {
-            print!("{}", render_feature_table(&config.features()));
+        FeaturesSubcommand::List { json } => {
+            let features = config.features();
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&render_feature_json(&features))?
+                );
+            } else {
+                print!("{}", render_feature_table(&features));
+            }
             Ok(())
         }
     }
@@ -2965,7 +3603,157 @@ fn rustc_version() -> String {
         .map_or_else(|| "unknown".to_string(), |s| s.trim().to_string())
 }
 
+/// Import a markdown file for `deepseek --from-file`. Earlier turns are
+/// saved as a session to resume; a trailing user turn (or the whole file,
+/// when it has no role markers) comes back as the composer draft.
+fn import_markdown_session(
+    config: &Config,
+    workspace: &Path,
+    path: &Path,
+) -> Result<(Option<String>, Option<String>)> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("could not read {}", path.display()))?;
+    let (mut messages, skipped) = session_import::parse_markdown(&text);
+    if messages.is_empty() {
+        bail!("{} has nothing to import", path.display());
+    }
+    for item in &skipped {
+        tracing::info!("--from-file {}: skipped {item}", path.display());
+    }
+    let draft = session_import::take_trailing_user_text(&mut messages);
+    if messages.is_empty() {
+        return Ok((None, draft));
+    }
+    let model = config
+        .default_text_model
+        .clone()
+        .unwrap_or_else(|| config.default_model());
+    let session = session_manager::create_saved_session_with_mode(
+        &messages, &model, workspace, 0, None, None,
+    );
+    session_manager::SessionManager::default_location()?.save_session(&session)?;
+    Ok((Some(session.metadata.id), draft))
+}
+
 /// List saved sessions
+fn run_sessions_import(config: &Config, workspace: &Path, args: SessionsImportArgs) -> Result<()> {
+    use session_manager::SessionManager;
+
+    /// Skipped items listed before the rest are summarized as a count.
+    const MAX_SKIPPED_SHOWN: usize = 20;
+
+    let model = args
+        .model
+        .clone()
+        .or_else(|| config.default_text_model.clone())
+        .unwrap_or_else(|| config.default_model());
+    let imported = session_import::import_file(&args.path, args.from, &model, workspace)?;
+    let manager = SessionManager::default_location()?;
+    manager.save_session(&imported.session)?;
+
+    let meta = &imported.session.metadata;
+    println!(
+        "Imported {} message(s) from {} as session {} (\"{}\")",
+        meta.message_count,
+        args.path.display(),
+        meta.id,
+        meta.title
+    );
+    println!("Workspace: {}", meta.workspace.display());
+    match imported.source_model.as_deref() {
+        Some(source) => println!("Model: {model} (source used {source})"),
+        None => println!("Model: {model}"),
+    }
+    if !imported.skipped.is_empty() {
+        println!();
+        println!(
+            "{} item(s) have no DeepSeek TUI equivalent and were not imported:",
+            imported.skipped.len()
+        );
+        for item in imported.skipped.iter().take(MAX_SKIPPED_SHOWN) {
+            println!("  - {item}");
+        }
+        if imported.skipped.len() > MAX_SKIPPED_SHOWN {
+            println!(
+                "  ... and {} more",
+                imported.skipped.len() - MAX_SKIPPED_SHOWN
+            );
+        }
+    }
+    println!();
+    println!("Resume with: deepseek --resume {}", meta.id);
+    Ok(())
+}
+
+fn run_sessions_replay(args: &SessionsReplayArgs) -> Result<()> {
+    let manager = session_manager::SessionManager::default_location()?;
+    let session = manager.load_session_by_prefix(&args.id)?;
+    if session.messages.is_empty() {
+        bail!("Session {} has no messages to replay", session.metadata.id);
+    }
+    tui::replay::run_replay(&session, args.file.as_deref())
+}
+
+fn run_sessions_export(args: &SessionsExportArgs) -> Result<()> {
+    use transcript_export::{ExportFormat, Transcript};
+
+    let manager = session_manager::SessionManager::default_location()?;
+    let session = manager.load_session_by_prefix(&args.id)?;
+    let format = args.format.unwrap_or_else(|| {
+        args.output
+            .as_deref()
+            .map_or(ExportFormat::Markdown, ExportFormat::from_path)
+    });
+    let mut transcript = Transcript::new(
+        &session.metadata.model,
+        &session.metadata.workspace,
+        args.redact.unwrap_or_default(),
+    );
+    transcript.title = Some(session.metadata.title.clone());
+    transcript.push_messages(&session.messages);
+    let rendered = transcript.render(format);
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, rendered)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            eprintln!(
+                "Exported session {} to {}",
+                session.metadata.id,
+                path.display()
+            );
+        }
+        None => println!("{rendered}"),
+    }
+    Ok(())
+}
+
+fn run_sessions_gc(config: &Config, args: &SessionsGcArgs) -> Result<()> {
+    let manager = session_manager::SessionManager::default_location()?;
+    let plan = manager.plan_gc(&config.sessions_config().retention(), &[])?;
+    for candidate in &plan.remove {
+        println!(
+            "  {} | {} | {} | {}",
+            session_manager::truncate_id(&candidate.id),
+            candidate.title,
+            artifacts::format_byte_size(candidate.bytes),
+            candidate.reason.label()
+        );
+    }
+    if args.dry_run {
+        println!("{}", plan.summary("Would remove"));
+        return Ok(());
+    }
+    let removed = manager.apply_gc(&plan);
+    println!("{}", plan.summary("Removed"));
+    if removed < plan.remove.len() {
+        bail!(
+            "{} session(s) could not be removed; see the log for details",
+            plan.remove.len() - removed
+        );
+    }
+    Ok(())
+}
+
 fn list_sessions(limit: usize, search: Option<String>) -> Result<()> {
     use crate::palette;
     use colored::Colorize;
@@ -3221,6 +4009,10 @@ fn pick_session_id() -> Result<String> {
 async fn run_review(config: &Config, args: ReviewArgs) -> Result<()> {
     use crate::client::DeepSeekClient;
 
+    if !args.commits.is_empty() || !args.prs.is_empty() {
+        return run_review_batch(config, args).await;
+    }
+
     let diff = collect_diff(&args)?;
     if diff.trim().is_empty() {
         bail!("No diff to review.");
@@ -3289,6 +4081,107 @@ Provide findings ordered by severity with file references, then open questions,
     Ok(())
 }
 
+/// `deepseek review --commits a..b,c..d` / `--prs 12,15`: review each
+/// target concurrently and print one merged report. Exits non-zero when the
+/// verdict is `fail` so CI can gate on it.
+async fn run_review_batch(config: &Config, args: ReviewArgs) -> Result<()> {
+    use crate::client::DeepSeekClient;
+    use crate::review_batch::{BatchReport, ReviewTarget, Verdict};
+    use crate::tools::review::{REVIEW_SYSTEM_PROMPT, ReviewOutput};
+
+    let targets = review_batch::parse_targets(&args.commits, &args.prs)?;
+    if !args.prs.is_empty() && !is_command_available("gh") {
+        bail!(
+            "`gh` CLI not found on PATH. Install GitHub CLI (https://cli.github.com) \
+             and authenticate (`gh auth login`) to review pull requests."
+        );
+    }
+    let model = args
+        .model
+        .clone()
+        .or_else(|| config.default_text_model.clone())
+        .unwrap_or_else(|| config.default_model());
+    let client = DeepSeekClient::new(config)?;
+
+    let review_one = |target: ReviewTarget| {
+        let client = &client;
+        let model = model.clone();
+        let repo = args.repo.clone();
+        let path = args.path.clone();
+        let max_chars = args.max_chars;
+        async move {
+            let label = target.label();
+            let diff = tokio::task::spawn_blocking(move || {
+                review_batch::collect_target_diff(
+                    &target,
+                    repo.as_deref(),
+                    path.as_deref(),
+                    max_chars,
+                )
+            })
+            .await??;
+            if diff.trim().is_empty() {
+                bail!("No diff to review.");
+            }
+            let route = resolve_cli_auto_route(config, &model, &diff).await;
+            let request = MessageRequest {
+                model: route.model,
+                messages: vec![Message {
+                    role: "user".to_string(),
+                    content: vec![ContentBlock::Text {
+                        text: format!(
+                            "Review the following diff ({label}):\n\n{diff}\n\nEnd of diff."
+                        ),
+                        cache_control: None,
+                    }],
+                }],
+                max_tokens: 4096,
+                system: Some(SystemPrompt::Text(REVIEW_SYSTEM_PROMPT.to_string())),
+                tools: None,
+                tool_choice: None,
+                metadata: None,
+                thinking: None,
+                reasoning_effort: route
+                    .reasoning_effort
+                    .map(|effort| effort.as_setting().to_string()),
+                stream: Some(false),
+                temperature: Some(0.2),
+                top_p: Some(0.9),
+            };
+            let response = client.create_message(request).await?;
+            let text = response
+                .content
+                .into_iter()
+                .filter_map(|block| match block {
+                    ContentBlock::Text { text, .. } => Some(text),
+                    _ => None,
+                })
+                .collect::<String>();
+            Ok(ReviewOutput::from_str(&text))
+        }
+    };
+    let results = review_batch::review_all(targets, args.jobs, review_one).await;
+    let report = BatchReport::merge(results);
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "mode": "review",
+                "model": model,
+                "verdict": report.verdict,
+                "targets": report.targets,
+            }))?
+        );
+    } else {
+        print!("{}", report.to_markdown());
+    }
+    if report.verdict == Verdict::Fail {
+        bail!("Review verdict: fail");
+    }
+    Ok(())
+}
+
 /// `deepseek pr <N>` (#451) — fetch a GitHub PR via `gh`, format
 /// title + body + diff as the composer's first message, and launch
 /// the interactive TUI. Falls back gracefully if `gh` is missing.
@@ -3525,6 +4418,8 @@ fn collect_diff(args: &ReviewArgs) -> Result<String> {
 }
 
 fn run_apply(args: ApplyArgs) -> Result<()> {
+    // Hunk placement can only be asked about when stdin is free for answers.
+    let interactive = args.patch_file.is_some() && io::stdin().is_terminal();
     let patch = if let Some(path) = args.patch_file {
         std::fs::read_to_string(&path)
             .map_err(|e| anyhow::anyhow!("Failed to read patch {}: {}", path.display(), e))?
@@ -3546,14 +4441,97 @@ fn run_apply(args: ApplyArgs) -> Result<()> {
         .output()
         .map_err(|e| anyhow::anyhow!("Failed to run git apply: {}", e))?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("git apply failed: {}", stderr.trim());
+    if output.status.success() {
+        println!("Applied patch successfully.");
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    eprintln!("git apply failed: {}", stderr.trim());
+
+    let cwd = std::env::current_dir()?;
+    if patch_resolve::try_three_way(&tmp_path, &cwd)? {
+        println!("Applied patch with a 3-way merge (changes are staged).");
+        return Ok(());
+    }
+
+    eprintln!("Placing hunks individually against the current files...");
+    let resolution = patch_resolve::resolve_hunks(&patch, &cwd, |conflict| {
+        if interactive {
+            prompt_hunk_placement(conflict)
+        } else {
+            eprint!(
+                "{}",
+                patch_resolve::render_conflict(conflict, conflict.placements.first())
+            );
+            patch_resolve::HunkDecision::Abort
+        }
+    })?;
+    let Some(resolution) = resolution else {
+        if interactive {
+            bail!("Aborted; no files were changed.");
+        }
+        bail!(
+            "Patch does not apply. Pass it as a file from an interactive terminal to choose placements."
+        );
+    };
+    resolution.write()?;
+    println!(
+        "Applied patch to {} file(s): {} hunk(s) placed at adjusted positions.",
+        resolution.file_count(),
+        resolution.adjusted
+    );
+    if !resolution.skipped.is_empty() {
+        println!("Skipped: {}", resolution.skipped.join(", "));
     }
-    println!("Applied patch successfully.");
     Ok(())
 }
 
+/// Show a drifted hunk against the file and ask where it should go.
+fn prompt_hunk_placement(
+    conflict: &patch_resolve::HunkConflict<'_>,
+) -> patch_resolve::HunkDecision {
+    use patch_resolve::HunkDecision;
+
+    let mut suggestion = 0usize;
+    let mut placement = conflict.placements.first().copied();
+    loop {
+        eprint!(
+            "{}",
+            patch_resolve::render_conflict(conflict, placement.as_ref())
+        );
+        match placement {
+            Some(p) => eprint!(
+                "[a]ccept line {}, [n]ext suggestion, <line> to try another, [s]kip hunk, [q]uit: ",
+                p.line
+            ),
+            None => eprint!("<line> to try a placement, [s]kip hunk, [q]uit: "),
+        }
+        let _ = io::stderr().flush();
+
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input).unwrap_or(0) == 0 {
+            return HunkDecision::Abort;
+        }
+        match input.trim() {
+            "a" | "y" if placement.is_some() => {
+                return HunkDecision::Place(placement.map_or(1, |p| p.line));
+            }
+            "n" if !conflict.placements.is_empty() => {
+                suggestion = (suggestion + 1) % conflict.placements.len();
+                placement = conflict.placements.get(suggestion).copied();
+            }
+            "s" => return HunkDecision::Skip,
+            "q" => return HunkDecision::Abort,
+            other => match other.parse::<usize>() {
+                Ok(line) if (1..=conflict.lines.len() + 1).contains(&line) => {
+                    placement = Some(patch_resolve::placement_at(conflict, line));
+                }
+                _ => eprintln!("Unrecognized choice `{other}`."),
+            },
+        }
+    }
+}
+
 fn read_patch_from_stdin() -> Result<String> {
     let mut stdin = io::stdin();
     if stdin.is_terminal() {
@@ -3697,6 +4675,8 @@ async fn run_mcp_command(config: &Config, command: McpCommand) -> Result<()> {
                     enabled_tools: Vec::new(),
                     disabled_tools: Vec::new(),
                     headers: std::collections::HashMap::new(),
+                    resources_enabled: true,
+                    prompts_enabled: true,
                 },
             );
             save_mcp_config(&config_path, &cfg)?;
@@ -3783,6 +4763,8 @@ async fn run_mcp_command(config: &Config, command: McpCommand) -> Result<()> {
                     enabled_tools: Vec::new(),
                     disabled_tools: Vec::new(),
                     headers: std::collections::HashMap::new(),
+                    resources_enabled: true,
+                    prompts_enabled: true,
                 },
             );
             save_mcp_config(&config_path, &cfg)?;
@@ -4332,6 +5314,7 @@ async fn run_interactive(
             Err(err) => logging::warn(format!("Failed to create first-run config file: {err}")),
         }
     }
+    crate::config::spawn_org_layer_refresh();
 
     let model = config.default_model();
     let max_subagents = cli.max_subagents.map_or_else(
@@ -4351,12 +5334,19 @@ async fn run_interactive(
         logging::warn(format!("Failed to install system skills: {e}"));
     }
 
+    // Both prunes run on the blocking pool so the first frame never waits
+    // on them.
+    //
     // Prune stale workspace snapshots from prior sessions (7-day default).
     // Non-fatal: a flaky disk, missing `git`, or read-only home should
     // never block the TUI from starting.
     let snapshots = config.snapshots_config();
     if snapshots.enabled {
-        session_manager::prune_workspace_snapshots(&workspace, snapshots.max_age());
+        let workspace = workspace.clone();
+        let max_age = snapshots.max_age();
+        crate::utils::spawn_blocking_supervised("snapshot-prune", move || {
+            session_manager::prune_workspace_snapshots(&workspace, max_age);
+        });
     }
 
     // Prune stale tool-output spillover files (#422). Non-fatal: home
@@ -4364,18 +5354,20 @@ async fn run_interactive(
     // we never block startup. Runs unconditionally because the
     // spillover store is created lazily on first write — there's no
     // user-facing setting to gate.
-    match crate::tools::truncate::prune_older_than(crate::tools::truncate::SPILLOVER_MAX_AGE) {
-        Ok(0) => {}
-        Ok(n) => tracing::debug!(
-            target: "spillover",
-            "boot prune removed {n} spillover file(s)"
-        ),
-        Err(err) => tracing::warn!(
-            target: "spillover",
-            ?err,
-            "spillover prune skipped on boot"
-        ),
-    }
+    crate::utils::spawn_blocking_supervised("spillover-prune", || {
+        match crate::tools::truncate::prune_older_than(crate::tools::truncate::SPILLOVER_MAX_AGE) {
+            Ok(0) => {}
+            Ok(n) => tracing::debug!(
+                target: "spillover",
+                "boot prune removed {n} spillover file(s)"
+            ),
+            Err(err) => tracing::warn!(
+                target: "spillover",
+                ?err,
+                "spillover prune skipped on boot"
+            ),
+        }
+    });
 
     tui::run_tui(
         config,
@@ -4619,34 +5611,64 @@ fn persist_exec_session(
     Ok(id)
 }
 
-#[allow(clippy::too_many_arguments)]
-async fn run_exec_agent(
+async fn run_watch(cli: &Cli, config: &Config, args: WatchArgs) -> Result<()> {
+    let model = args
+        .model
+        .clone()
+        .or_else(|| config.default_text_model.clone())
+        .unwrap_or_else(|| config.default_model());
+    let workspace = cli
+        .workspace
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    let max_subagents = cli.max_subagents.map_or_else(
+        || config.max_subagents(),
+        |value| value.clamp(1, MAX_SUBAGENTS),
+    );
+    let auto_mode = args.auto || cli.yolo;
+    let options = watch::WatchOptions {
+        workspace: workspace.clone(),
+        patterns: args.on_change,
+        prompt: args.prompt,
+        debounce: Duration::from_millis(args.debounce_ms),
+        max_runs: args.max_runs,
+    };
+    watch::run(options, |prompt| {
+        let model = model.clone();
+        let workspace = workspace.clone();
+        async move {
+            run_exec_agent(
+                config,
+                &model,
+                &prompt,
+                workspace,
+                max_subagents,
+                auto_mode,
+                auto_mode,
+                false,
+                None,
+                ExecOutputFormat::StreamJson,
+            )
+            .await
+        }
+    })
+    .await
+}
+
+/// Engine configuration for a non-interactive `deepseek exec` session.
+fn exec_engine_config(
     config: &Config,
     model: &str,
-    prompt: &str,
-    workspace: PathBuf,
+    workspace: &Path,
     max_subagents: usize,
     auto_approve: bool,
     trust_mode: bool,
-    json_output: bool,
-    resume_session_id: Option<String>,
-    output_format: ExecOutputFormat,
-) -> Result<()> {
+) -> crate::core::engine::EngineConfig {
     use crate::compaction::CompactionConfig;
-    use crate::core::engine::{EngineConfig, spawn_engine};
-    use crate::core::events::Event;
-    use crate::core::ops::Op;
+    use crate::core::engine::EngineConfig;
     use crate::models::compaction_threshold_for_model;
     use crate::tools::plan::new_shared_plan_state;
     use crate::tools::todo::new_shared_todo_list;
-    use crate::tui::app::AppMode;
-
-    let route = resolve_cli_auto_route(config, model, prompt).await;
-    let auto_model = route.auto_model;
-    let effective_model = route.model;
-    let effective_reasoning_effort = route
-        .reasoning_effort
-        .map(|effort| effort.as_setting().to_string());
 
     // Compaction defaults to disabled in v0.6.6: the checkpoint-restart cycle
     // architecture (issue #124) handles long-context resets via fresh contexts
@@ -4655,8 +5677,8 @@ async fn run_exec_agent(
     // or direct engine config keep their old behavior.
     let compaction = CompactionConfig {
         enabled: false,
-        model: effective_model.clone(),
-        token_threshold: compaction_threshold_for_model(&effective_model),
+        model: model.to_string(),
+        token_threshold: compaction_threshold_for_model(model),
         ..Default::default()
     };
 
@@ -4669,9 +5691,9 @@ async fn run_exec_agent(
         .clone()
         .map(crate::config::LspConfigToml::into_runtime);
 
-    let engine_config = EngineConfig {
-        model: effective_model.clone(),
-        workspace: workspace.clone(),
+    EngineConfig {
+        model: model.to_string(),
+        workspace: workspace.to_path_buf(),
         allow_shell: auto_approve || config.allow_shell(),
         trust_mode,
         notes_path: config.notes_path(),
@@ -4679,6 +5701,7 @@ async fn run_exec_agent(
         skills_dir: config.skills_dir(),
         instructions: config.instructions_paths(),
         project_context_pack_enabled: config.project_context_pack_enabled(),
+        repo_map_tokens: config.repo_map_tokens(),
         translation_enabled: false,
         max_steps: 100,
         max_subagents,
@@ -4688,6 +5711,10 @@ async fn run_exec_agent(
         capacity: crate::core::capacity::CapacityControllerConfig::from_app_config(config),
         todos: new_shared_todo_list(),
         plan_state: new_shared_plan_state(),
+        session_changes: crate::tools::session_changes::new_shared_session_changes(),
+        exec_profile: crate::tools::exec_profile::new_shared_exec_profile(),
+        session_env: crate::tools::session_env::new_shared_session_env(config.env.as_ref()),
+        shell_limits: config.shell_limits(),
         max_spawn_depth: crate::tools::subagent::DEFAULT_MAX_SPAWN_DEPTH,
         network_policy,
         snapshots_enabled: config.snapshots_config().enabled,
@@ -4695,14 +5722,21 @@ async fn run_exec_agent(
             .snapshots_config()
             .max_workspace_gb
             .saturating_mul(1024 * 1024 * 1024),
+        snapshots_auto_before_write: config.snapshots_config().auto_before_write,
+        tool_cache_scope: config.tool_cache_scope(),
+        prompt_layers: config.prompt_layer_overrides(),
         lsp_config,
         runtime_services: crate::tools::spec::RuntimeToolServices::default(),
         subagent_model_overrides: config.subagent_model_overrides(),
         subagent_api_timeout: std::time::Duration::from_secs(config.subagent_api_timeout_secs()),
+        subagent_max_steps: config.subagent_max_steps(),
+        subagent_allowed_models: config.subagent_allowed_models(),
         memory_enabled: config.memory_enabled(),
         memory_path: config.memory_path(),
+        command_lessons_enabled: config.command_lessons_enabled(),
         vision_config: config.vision_model_config(),
         strict_tool_mode: config.strict_tool_mode.unwrap_or(false),
+        self_report: config.self_report.unwrap_or(false),
         goal_objective: None,
         locale_tag: crate::localization::resolve_locale(
             &crate::settings::Settings::load().unwrap_or_default().locale,
@@ -4716,14 +5750,88 @@ async fn run_exec_agent(
             .and_then(|s| s.provider)
             .unwrap_or_default(),
         search_api_key: config.search.as_ref().and_then(|s| s.api_key.clone()),
-    };
+        edit_validation: config.edit_validation.clone().unwrap_or_default(),
+        tool_filters: config.tool_filters.clone().unwrap_or_default(),
+        trust_level: crate::trust_level::load(workspace).unwrap_or_default(),
+        model_presets: config.models.clone().unwrap_or_default(),
+        model_fallbacks: config.model_fallbacks.clone().unwrap_or_default(),
+        docs_index_on_start: false,
+        trail_enabled: config.trail_enabled(),
+        trail_branch: config.trail_branch(),
+        thinking: config.thinking.clone().unwrap_or_default(),
+    }
+}
+
+/// The `SendMessage` op for one `deepseek exec` prompt.
+fn exec_send_message(
+    config: &Config,
+    prompt: &str,
+    route: CliAutoRoute,
+    auto_approve: bool,
+    trust_mode: bool,
+) -> crate::core::ops::Op {
+    use crate::tui::app::AppMode;
+
+    crate::core::ops::Op::SendMessage {
+        content: prompt.to_string(),
+        mode: if auto_approve {
+            AppMode::Yolo
+        } else {
+            AppMode::Agent
+        },
+        model: route.model,
+        goal_objective: None,
+        reasoning_effort: route
+            .reasoning_effort
+            .map(|effort| effort.as_setting().to_string()),
+        reasoning_effort_auto: route.auto_model,
+        auto_model: route.auto_model,
+        allow_shell: auto_approve || config.allow_shell(),
+        trust_mode,
+        auto_approve,
+        translation_enabled: false,
+        approval_mode: if auto_approve {
+            crate::tui::approval::ApprovalMode::Auto
+        } else {
+            config
+                .approval_policy
+                .as_deref()
+                .and_then(crate::tui::approval::ApprovalMode::from_config_value)
+                .unwrap_or_default()
+        },
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_exec_agent(
+    config: &Config,
+    model: &str,
+    prompt: &str,
+    workspace: PathBuf,
+    max_subagents: usize,
+    auto_approve: bool,
+    trust_mode: bool,
+    json_output: bool,
+    resume_session_id: Option<String>,
+    output_format: ExecOutputFormat,
+) -> Result<()> {
+    use crate::core::engine::spawn_engine;
+    use crate::core::events::Event;
+    use crate::core::ops::Op;
+
+    let route = resolve_cli_auto_route(config, model, prompt).await;
+    let effective_model = route.model.clone();
+
+    let engine_config = exec_engine_config(
+        config,
+        &effective_model,
+        &workspace,
+        max_subagents,
+        auto_approve,
+        trust_mode,
+    );
 
     let engine_handle = spawn_engine(engine_config, config);
-    let mode = if auto_approve {
-        AppMode::Yolo
-    } else {
-        AppMode::Agent
-    };
 
     let mut loaded_session_id = None;
     if let Some(session_id) = resume_session_id.as_deref() {
@@ -4758,28 +5866,13 @@ async fn run_exec_agent(
     }
 
     engine_handle
-        .send(Op::SendMessage {
-            content: prompt.to_string(),
-            mode,
-            model: effective_model.clone(),
-            goal_objective: None,
-            reasoning_effort: effective_reasoning_effort,
-            reasoning_effort_auto: auto_model,
-            auto_model,
-            allow_shell: auto_approve || config.allow_shell(),
-            trust_mode,
+        .send(exec_send_message(
+            config,
+            prompt,
+            route,
             auto_approve,
-            translation_enabled: false,
-            approval_mode: if auto_approve {
-                crate::tui::approval::ApprovalMode::Auto
-            } else {
-                config
-                    .approval_policy
-                    .as_deref()
-                    .and_then(crate::tui::approval::ApprovalMode::from_config_value)
-                    .unwrap_or_default()
-            },
-        })
+            trust_mode,
+        ))
         .await?;
 
     #[derive(serde::Serialize)]
@@ -4787,6 +5880,8 @@ async fn run_exec_agent(
         name: String,
         success: bool,
         output: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error_code: Option<crate::error_taxonomy::ToolErrorClass>,
     }
     #[derive(serde::Serialize, Default)]
     struct ExecSummary {
@@ -4795,6 +5890,8 @@ async fn run_exec_agent(
         prompt: String,
         output: String,
         tools: Vec<ExecToolEntry>,
+        /// Failed tool calls this session, counted by error class.
+        tool_error_classes: crate::error_taxonomy::ToolErrorStats,
         status: Option<String>,
         error: Option<String>,
     }
@@ -4868,10 +5965,17 @@ async fn run_exec_agent(
                 id, name, result, ..
             } => match result {
                 Ok(output) => {
+                    let error_code = (!output.success).then(|| {
+                        crate::error_taxonomy::ToolFailure::from_failed_result(&output).code
+                    });
+                    if let Some(code) = error_code {
+                        summary.tool_error_classes.record(code);
+                    }
                     summary.tools.push(ExecToolEntry {
                         name: name.clone(),
                         success: output.success,
                         output: output.content.clone(),
+                        error_code,
                     });
                     if output_format == ExecOutputFormat::StreamJson {
                         emit_exec_stream_event(&ExecStreamEvent::ToolResult {
@@ -4900,10 +6004,13 @@ async fn run_exec_agent(
                 }
                 Err(err) => {
                     let error_text = err.to_string();
+                    let error_code = crate::error_taxonomy::ToolFailure::from_tool_error(&err).code;
+                    summary.tool_error_classes.record(error_code);
                     summary.tools.push(ExecToolEntry {
                         name: name.clone(),
                         success: false,
                         output: error_text.clone(),
+                        error_code: Some(error_code),
                     });
                     if output_format == ExecOutputFormat::StreamJson {
                         emit_exec_stream_event(&ExecStreamEvent::ToolResult {
@@ -5321,6 +6428,32 @@ mod terminal_mode_tests {
         assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
     }
 
+    #[test]
+    fn exec_interactive_script_replaces_the_prompt() {
+        let cli = parse_cli(&["deepseek", "exec", "--auto", "--interactive-script"]);
+        let Some(Commands::Exec(args)) = cli.command else {
+            panic!("expected exec command");
+        };
+        assert_eq!(args.interactive_script, Some(PathBuf::from("-")));
+        assert!(args.prompt.is_empty());
+
+        let cli = parse_cli(&["deepseek", "exec", "--interactive-script", "steps.yaml"]);
+        let Some(Commands::Exec(args)) = cli.command else {
+            panic!("expected exec command");
+        };
+        assert_eq!(args.interactive_script, Some(PathBuf::from("steps.yaml")));
+
+        let err = Cli::try_parse_from([
+            "deepseek",
+            "exec",
+            "--json",
+            "--interactive-script",
+            "steps.yaml",
+        ])
+        .expect_err("script mode writes its own JSONL");
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
     #[test]
     fn exec_stream_events_are_json_lines() {
         let event = ExecStreamEvent::ToolResult {
@@ -5926,6 +7059,8 @@ mod doctor_mcp_tests {
             enabled_tools: Vec::new(),
             disabled_tools: Vec::new(),
             headers: std::collections::HashMap::new(),
+            resources_enabled: true,
+            prompts_enabled: true,
         }
     }
 
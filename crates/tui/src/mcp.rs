@@ -5,7 +5,7 @@
 //! - Automatic tool discovery via `tools/list`
 //! - Configurable timeouts per-server and globally
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::path::{Component, Path};
 use std::sync::Arc;
@@ -270,12 +270,26 @@ pub struct McpServerConfig {
     #[serde(default)]
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub headers: HashMap<String, String>,
+    /// Discover this server's resources and expose them to the model
+    /// (`list_mcp_resources`, `mcp_read_resource`, `mcp_subscribe_resource`).
+    /// Set to `false` for servers whose resource listing is huge or slow.
+    #[serde(default = "default_enabled", skip_serializing_if = "is_true")]
+    pub resources_enabled: bool,
+    /// Discover this server's prompts and surface them in the command
+    /// palette and `mcp_get_prompt`.
+    #[serde(default = "default_enabled", skip_serializing_if = "is_true")]
+    pub prompts_enabled: bool,
 }
 
 fn default_enabled() -> bool {
     true
 }
 
+#[allow(clippy::trivially_copy_pass_by_ref)] // serde's skip_serializing_if passes `&T`
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
 impl McpServerConfig {
     pub fn effective_connect_timeout(&self, global: &McpTimeouts) -> u64 {
         self.connect_timeout.unwrap_or(global.connect_timeout)
@@ -1130,6 +1144,13 @@ pub struct McpConnection {
     resources: Vec<McpResource>,
     resource_templates: Vec<McpResourceTemplate>,
     prompts: Vec<McpPrompt>,
+    /// Server advertised `capabilities.resources.subscribe` at initialize.
+    supports_resource_subscribe: bool,
+    /// URIs this client subscribed to via `resources/subscribe`.
+    subscribed_resources: HashSet<String>,
+    /// Subscribed URIs the server reported as changed
+    /// (`notifications/resources/updated`) since they were last read.
+    updated_resources: HashSet<String>,
     request_id: AtomicU64,
     state: ConnectionState,
     config: McpServerConfig,
@@ -1295,6 +1316,9 @@ impl McpConnection {
             resources: Vec::new(),
             resource_templates: Vec::new(),
             prompts: Vec::new(),
+            supports_resource_subscribe: false,
+            subscribed_resources: HashSet::new(),
+            updated_resources: HashSet::new(),
             request_id: AtomicU64::new(1),
             state: ConnectionState::Connecting,
             config,
@@ -1340,7 +1364,11 @@ impl McpConnection {
         }))
         .await?;
 
-        self.recv(init_id).await?;
+        let response = self.recv(init_id).await?;
+        self.supports_resource_subscribe = response
+            .pointer("/result/capabilities/resources/subscribe")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
 
         // Send initialized notification (no id, no response expected)
         self.send(serde_json::json!({
@@ -1357,9 +1385,13 @@ impl McpConnection {
         // We use join! to discover everything concurrently if possible,
         // but for now let's keep it sequential for simplicity in error handling
         self.discover_tools().await?;
-        self.discover_resources().await?;
-        self.discover_resource_templates().await?;
-        self.discover_prompts().await?;
+        if self.config.resources_enabled {
+            self.discover_resources().await?;
+            self.discover_resource_templates().await?;
+        }
+        if self.config.prompts_enabled {
+            self.discover_prompts().await?;
+        }
         Ok(())
     }
 
@@ -1575,14 +1607,71 @@ impl McpConnection {
         uri: &str,
         timeout_secs: u64,
     ) -> Result<serde_json::Value> {
-        self.call_method(
-            "resources/read",
-            serde_json::json!({
-                "uri": uri
-            }),
-            timeout_secs,
-        )
-        .await
+        let result = self
+            .call_method(
+                "resources/read",
+                serde_json::json!({
+                    "uri": uri
+                }),
+                timeout_secs,
+            )
+            .await?;
+        self.updated_resources.remove(uri);
+        Ok(result)
+    }
+
+    /// Subscribe to (or unsubscribe from) change notifications for a resource.
+    pub async fn set_resource_subscription(
+        &mut self,
+        uri: &str,
+        subscribe: bool,
+        timeout_secs: u64,
+    ) -> Result<()> {
+        if !self.supports_resource_subscribe {
+            anyhow::bail!(
+                "MCP server '{}' does not support resource subscriptions",
+                self.name
+            );
+        }
+        let method = if subscribe {
+            "resources/subscribe"
+        } else {
+            "resources/unsubscribe"
+        };
+        self.call_method(method, serde_json::json!({ "uri": uri }), timeout_secs)
+            .await?;
+        if subscribe {
+            self.subscribed_resources.insert(uri.to_string());
+        } else {
+            self.subscribed_resources.remove(uri);
+            self.updated_resources.remove(uri);
+        }
+        Ok(())
+    }
+
+    /// Whether `uri` is subscribed on this connection.
+    pub fn is_resource_subscribed(&self, uri: &str) -> bool {
+        self.subscribed_resources.contains(uri)
+    }
+
+    /// Whether the server reported `uri` as changed since it was last read.
+    pub fn is_resource_updated(&self, uri: &str) -> bool {
+        self.updated_resources.contains(uri)
+    }
+
+    /// Track server notifications that carry state this client keeps.
+    /// Notifications are only observed while a request is in flight, so an
+    /// update surfaces the next time this server is queried.
+    fn observe_notification(&mut self, value: &serde_json::Value) {
+        if value.get("method").and_then(serde_json::Value::as_str)
+            == Some("notifications/resources/updated")
+            && let Some(uri) = value
+                .pointer("/params/uri")
+                .and_then(serde_json::Value::as_str)
+            && self.subscribed_resources.contains(uri)
+        {
+            self.updated_resources.insert(uri.to_string());
+        }
     }
 
     /// Get a prompt from this MCP server
@@ -1714,7 +1803,9 @@ impl McpConnection {
             if value.get("id").and_then(serde_json::Value::as_u64) == Some(expected_id) {
                 return Ok(value);
             }
-            // Skip notifications (no id) and responses with different ids
+            // Skip notifications (no id) and responses with different ids,
+            // keeping the resource-update bookkeeping.
+            self.observe_notification(&value);
         }
     }
 
@@ -1975,15 +2066,7 @@ impl McpPool {
             let resources = conn
                 .resources()
                 .iter()
-                .map(|resource| {
-                    serde_json::json!({
-                        "server": server_name.clone(),
-                        "uri": resource.uri,
-                        "name": resource.name,
-                        "description": resource.description,
-                        "mime_type": resource.mime_type,
-                    })
-                })
+                .map(|resource| resource_listing_entry(&server_name, conn, resource))
                 .collect();
             return Ok(resources);
         }
@@ -1992,13 +2075,7 @@ impl McpPool {
         let mut items = Vec::new();
         for (server, conn) in &self.connections {
             for resource in conn.resources() {
-                items.push(serde_json::json!({
-                    "server": server,
-                    "uri": resource.uri,
-                    "name": resource.name,
-                    "description": resource.description,
-                    "mime_type": resource.mime_type,
-                }));
+                items.push(resource_listing_entry(server, conn, resource));
             }
         }
         Ok(items)
@@ -2062,10 +2139,36 @@ impl McpPool {
     ) -> Result<serde_json::Value> {
         let global_timeouts = self.config.timeouts;
         let conn = self.get_or_connect(server_name).await?;
+        if !conn.config().resources_enabled {
+            anyhow::bail!("MCP resources are disabled for server '{server_name}'");
+        }
         let timeout = conn.config().effective_read_timeout(&global_timeouts);
         conn.read_resource(uri, timeout).await
     }
 
+    /// Subscribe to (or unsubscribe from) updates for a resource on a
+    /// specific server.
+    pub async fn set_resource_subscription(
+        &mut self,
+        server_name: &str,
+        uri: &str,
+        subscribe: bool,
+    ) -> Result<serde_json::Value> {
+        let global_timeouts = self.config.timeouts;
+        let conn = self.get_or_connect(server_name).await?;
+        if !conn.config().resources_enabled {
+            anyhow::bail!("MCP resources are disabled for server '{server_name}'");
+        }
+        let timeout = conn.config().effective_execute_timeout(&global_timeouts);
+        conn.set_resource_subscription(uri, subscribe, timeout)
+            .await?;
+        Ok(serde_json::json!({
+            "server": server_name,
+            "uri": uri,
+            "subscribed": subscribe,
+        }))
+    }
+
     /// Get a prompt from a specific server
     pub async fn get_prompt(
         &mut self,
@@ -2075,6 +2178,9 @@ impl McpPool {
     ) -> Result<serde_json::Value> {
         let global_timeouts = self.config.timeouts;
         let conn = self.get_or_connect(server_name).await?;
+        if !conn.config().prompts_enabled {
+            anyhow::bail!("MCP prompts are disabled for server '{server_name}'");
+        }
         let timeout = conn.config().effective_execute_timeout(&global_timeouts);
         conn.get_prompt(prompt_name, arguments, timeout).await
     }
@@ -2186,6 +2292,32 @@ impl McpPool {
             });
         }
 
+        if self
+            .connections
+            .values()
+            .any(|conn| conn.supports_resource_subscribe && !conn.resources().is_empty())
+        {
+            api_tools.push(crate::models::Tool {
+                tool_type: None,
+                name: "mcp_subscribe_resource".to_string(),
+                description: "Subscribe to change notifications for an MCP resource. Changed resources are flagged `updated: true` in list_mcp_resources until read again.".to_string(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "server": { "type": "string", "description": "The name of the MCP server" },
+                        "uri": { "type": "string", "description": "The URI of the resource" },
+                        "unsubscribe": { "type": "boolean", "description": "Stop watching the resource instead" }
+                    },
+                    "required": ["server", "uri"]
+                }),
+                allowed_callers: Some(vec!["direct".to_string()]),
+                defer_loading: Some(false),
+                input_examples: None,
+                strict: None,
+                cache_control: None,
+            });
+        }
+
         // Add prompt getting tools if prompts exist
         let prompts = self.all_prompts();
         if !prompts.is_empty() {
@@ -2268,6 +2400,24 @@ impl McpPool {
             return self.read_resource(server_name, uri).await;
         }
 
+        if prefixed_name == "mcp_subscribe_resource" {
+            let server_name = arguments
+                .get("server")
+                .and_then(|v| v.as_str())
+                .context("Missing 'server' argument")?;
+            let uri = arguments
+                .get("uri")
+                .and_then(|v| v.as_str())
+                .context("Missing 'uri' argument")?;
+            let unsubscribe = arguments
+                .get("unsubscribe")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false);
+            return self
+                .set_resource_subscription(server_name, uri, !unsubscribe)
+                .await;
+        }
+
         if prefixed_name == "mcp_get_prompt" {
             let server_name = arguments
                 .get("server")
@@ -2355,6 +2505,25 @@ impl McpPool {
     }
 }
 
+fn resource_listing_entry(
+    server: &str,
+    conn: &McpConnection,
+    resource: &McpResource,
+) -> serde_json::Value {
+    let mut entry = serde_json::json!({
+        "server": server,
+        "uri": resource.uri,
+        "name": resource.name,
+        "description": resource.description,
+        "mime_type": resource.mime_type,
+    });
+    if conn.is_resource_subscribed(&resource.uri) {
+        entry["subscribed"] = serde_json::json!(true);
+        entry["updated"] = serde_json::json!(conn.is_resource_updated(&resource.uri));
+    }
+    entry
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum McpWriteStatus {
     Created,
@@ -2465,6 +2634,8 @@ fn mcp_template_json() -> Result<String> {
             enabled_tools: Vec::new(),
             disabled_tools: Vec::new(),
             headers: HashMap::new(),
+            resources_enabled: true,
+            prompts_enabled: true,
         },
     );
     serde_json::to_string_pretty(&cfg).context("Failed to render MCP template JSON")
@@ -2517,6 +2688,8 @@ pub fn add_server_config(
             enabled_tools: Vec::new(),
             disabled_tools: Vec::new(),
             headers: HashMap::new(),
+            resources_enabled: true,
+            prompts_enabled: true,
         },
     );
     save_config(path, &cfg)
@@ -2693,6 +2866,49 @@ fn snapshot_from_config(
 
 // === Helper Functions ===
 
+/// Flatten a `prompts/get` result into composer text. Text content is kept
+/// verbatim; embedded resources contribute their text, other content types
+/// leave a short placeholder so nothing is silently dropped.
+pub fn prompt_result_text(result: &serde_json::Value) -> String {
+    let Some(messages) = result.get("messages").and_then(|m| m.as_array()) else {
+        return String::new();
+    };
+    let mut parts = Vec::new();
+    for message in messages {
+        let Some(content) = message.get("content") else {
+            continue;
+        };
+        let blocks = match content.as_array() {
+            Some(items) => items.iter().collect::<Vec<_>>(),
+            None => vec![content],
+        };
+        for block in blocks {
+            let kind = block.get("type").and_then(|t| t.as_str()).unwrap_or("text");
+            let text = match kind {
+                "text" => block
+                    .get("text")
+                    .and_then(|t| t.as_str())
+                    .map(str::to_string),
+                "resource" => block
+                    .pointer("/resource/text")
+                    .and_then(|t| t.as_str())
+                    .map(str::to_string)
+                    .or_else(|| {
+                        block
+                            .pointer("/resource/uri")
+                            .and_then(|u| u.as_str())
+                            .map(|uri| format!("[resource: {uri}]"))
+                    }),
+                other => Some(format!("[{other} content]")),
+            };
+            if let Some(text) = text.filter(|t| !t.trim().is_empty()) {
+                parts.push(text);
+            }
+        }
+    }
+    parts.join("\n\n")
+}
+
 /// Format MCP tool result for display
 #[allow(dead_code)] // Will be used when MCP tool results are displayed in TUI
 pub fn format_tool_result(result: &serde_json::Value) -> String {
@@ -2812,6 +3028,8 @@ mod tests {
             enabled_tools: Vec::new(),
             disabled_tools: Vec::new(),
             headers: HashMap::new(),
+            resources_enabled: true,
+            prompts_enabled: true,
         };
         let serialized = serde_json::to_string(&cfg).unwrap();
         assert!(
@@ -2998,6 +3216,8 @@ mod tests {
             enabled_tools: Vec::new(),
             disabled_tools: Vec::new(),
             headers: HashMap::new(),
+            resources_enabled: true,
+            prompts_enabled: true,
         };
 
         assert_eq!(server_with_override.effective_connect_timeout(&global), 20);
@@ -3108,6 +3328,8 @@ mod tests {
             enabled_tools: Vec::new(),
             disabled_tools: Vec::new(),
             headers: HashMap::new(),
+            resources_enabled: true,
+            prompts_enabled: true,
         }
     }
 
@@ -3119,6 +3341,9 @@ mod tests {
             resources: Vec::new(),
             resource_templates: Vec::new(),
             prompts: Vec::new(),
+            supports_resource_subscribe: false,
+            subscribed_resources: HashSet::new(),
+            updated_resources: HashSet::new(),
             request_id: AtomicU64::new(1),
             state: ConnectionState::Ready,
             config: test_server_config(),
@@ -3188,6 +3413,95 @@ mod tests {
         assert_eq!(sent.lock().unwrap().len(), 1);
     }
 
+    #[tokio::test]
+    async fn resource_update_notifications_flag_subscribed_uris() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let transport = ScriptedValueTransport {
+            sent: Arc::clone(&sent),
+            responses: VecDeque::from([
+                json_frame(serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": {}})),
+                json_frame(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "notifications/resources/updated",
+                    "params": {"uri": "file:///other"}
+                })),
+                json_frame(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "notifications/resources/updated",
+                    "params": {"uri": "file:///watched"}
+                })),
+                json_frame(serde_json::json!({"jsonrpc": "2.0", "id": 2, "result": {}})),
+            ]),
+        };
+        let mut conn = test_connection(Box::new(transport));
+        conn.supports_resource_subscribe = true;
+
+        conn.set_resource_subscription("file:///watched", true, 1)
+            .await
+            .unwrap();
+        assert!(conn.is_resource_subscribed("file:///watched"));
+        conn.call_method("ping", serde_json::json!({}), 1)
+            .await
+            .unwrap();
+
+        assert!(conn.is_resource_updated("file:///watched"));
+        assert!(!conn.is_resource_updated("file:///other"));
+        assert_eq!(sent.lock().unwrap()[0]["method"], "resources/subscribe");
+    }
+
+    #[tokio::test]
+    async fn subscription_requires_server_capability() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let mut conn = test_connection(Box::new(HangingValueTransport {
+            sent: Arc::clone(&sent),
+        }));
+
+        let err = conn
+            .set_resource_subscription("file:///watched", true, 1)
+            .await
+            .expect_err("server without subscribe capability");
+        assert!(
+            err.to_string()
+                .contains("does not support resource subscriptions")
+        );
+        assert!(sent.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn prompt_result_text_flattens_message_content() {
+        let result = serde_json::json!({
+            "messages": [
+                {"role": "user", "content": {"type": "text", "text": "Review this diff"}},
+                {"role": "user", "content": [
+                    {"type": "resource", "resource": {"uri": "file:///a.rs", "text": "fn a() {}"}},
+                    {"type": "resource", "resource": {"uri": "file:///b.png"}},
+                    {"type": "image", "data": "..."}
+                ]}
+            ]
+        });
+        let text = prompt_result_text(&result);
+        assert!(text.contains("Review this diff"));
+        assert!(text.contains("fn a() {}"));
+        assert!(text.contains("[resource: file:///b.png]"));
+        assert!(text.contains("[image content]"));
+        assert_eq!(prompt_result_text(&serde_json::json!({})), "");
+    }
+
+    #[test]
+    fn resource_and_prompt_toggles_default_on_and_serialize_only_when_off() {
+        let cfg: McpServerConfig = serde_json::from_str(r#"{"command": "srv"}"#).unwrap();
+        assert!(cfg.resources_enabled);
+        assert!(cfg.prompts_enabled);
+        let json = serde_json::to_value(&cfg).unwrap();
+        assert!(json.get("resources_enabled").is_none());
+
+        let cfg: McpServerConfig =
+            serde_json::from_str(r#"{"command": "srv", "prompts_enabled": false}"#).unwrap();
+        assert!(!cfg.prompts_enabled);
+        let json = serde_json::to_value(&cfg).unwrap();
+        assert_eq!(json["prompts_enabled"], false);
+    }
+
     #[tokio::test]
     async fn test_mcp_pool_empty_config() {
         let pool = McpPool::new(McpConfig::default());
@@ -3277,6 +3591,8 @@ mod tests {
                 enabled_tools: Vec::new(),
                 disabled_tools: Vec::new(),
                 headers: HashMap::new(),
+                resources_enabled: true,
+                prompts_enabled: true,
             },
         );
         assert_ne!(
@@ -3514,6 +3830,8 @@ mod tests {
             enabled_tools: Vec::new(),
             disabled_tools: Vec::new(),
             headers: HashMap::new(),
+            resources_enabled: true,
+            prompts_enabled: true,
         };
 
         let conn = McpConnection::connect_with_policy(
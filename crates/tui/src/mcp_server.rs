@@ -1,6 +1,15 @@
 //! MCP server implementation for exposing DeepSeek tools over stdio.
-
-use std::collections::{HashMap, HashSet};
+//!
+//! `deepseek serve --mcp` advertises the internal tool registry — file,
+//! search, git, patch, shell, test and review tools — with the same input
+//! schemas the model sees, plus the `deepseek` / `deepseek-reply` chat
+//! tools. Shell commands run under the workspace-write sandbox. Calls to
+//! tools that write files or execute code need approval: when the client
+//! supports MCP elicitation the server asks it with `elicitation/create`,
+//! otherwise the call must be resent with `approved: true`.
+//! `~/.deepseek/mcp_server.toml` narrows the exposed set and tunes approval.
+
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::io::{self, BufRead, Write};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
@@ -12,12 +21,18 @@ use tokio::runtime::Runtime;
 use uuid::Uuid;
 
 use crate::client::DeepSeekClient;
-use crate::config::Config;
+use crate::command_guardrail::scan_tool_call;
+use crate::config::{Config, DEFAULT_TEXT_MODEL, GuardrailRuleConfig};
 use crate::llm_client::LlmClient;
 use crate::models::{ContentBlock, Message, MessageRequest};
+use crate::sandbox::SandboxPolicy;
 use crate::session_manager::SessionManager;
-use crate::tools::spec::{ToolError, ToolResult};
+use crate::tools::spec::{ApprovalRequirement, ToolError, ToolResult};
 use crate::tools::{ToolContext, ToolRegistryBuilder};
+use crate::utils::truncate_with_ellipsis;
+
+/// Chat tools handled by the server itself rather than the registry.
+const CHAT_TOOLS: &[&str] = &["deepseek", "deepseek-reply"];
 
 #[derive(Debug, Default, Deserialize)]
 struct McpServerConfigFile {
@@ -29,38 +44,53 @@ struct McpServerConfigFile {
 struct McpServerSection {
     expose_tools: Option<Vec<String>>,
     require_approval: Option<bool>,
+    auto_approve: Option<bool>,
+    sandbox: Option<bool>,
 }
 
 #[derive(Debug, Clone)]
 struct McpServerSettings {
-    expose_tools: Vec<String>,
+    /// Public tool names to expose; `None` exposes every registry tool.
+    expose_tools: Option<Vec<String>>,
+    /// Every call needs approval, read-only tools included.
     require_approval: bool,
+    /// No call needs approval.
+    auto_approve: bool,
+    /// Run shell commands under the workspace-write sandbox.
+    sandbox: bool,
+}
+
+impl Default for McpServerSettings {
+    fn default() -> Self {
+        Self {
+            expose_tools: None,
+            require_approval: false,
+            auto_approve: false,
+            sandbox: true,
+        }
+    }
 }
 
 impl McpServerSettings {
     fn load() -> Result<Self> {
         let path = default_config_path();
-        if let Some(path) = path.filter(|p| p.exists()) {
-            let contents = std::fs::read_to_string(&path)
-                .with_context(|| format!("Failed to read MCP server config: {}", path.display()))?;
-            let config: McpServerConfigFile = toml::from_str(&contents).with_context(|| {
-                format!("Failed to parse MCP server config: {}", path.display())
-            })?;
-            let expose_tools = config
+        let Some(path) = path.filter(|p| p.exists()) else {
+            return Ok(Self::default());
+        };
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read MCP server config: {}", path.display()))?;
+        let config: McpServerConfigFile = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse MCP server config: {}", path.display()))?;
+        let defaults = Self::default();
+        Ok(Self {
+            expose_tools: config.server.expose_tools,
+            require_approval: config
                 .server
-                .expose_tools
-                .unwrap_or_else(default_expose_tools);
-            let require_approval = config.server.require_approval.unwrap_or(false);
-            Ok(Self {
-                expose_tools,
-                require_approval,
-            })
-        } else {
-            Ok(Self {
-                expose_tools: default_expose_tools(),
-                require_approval: false,
-            })
-        }
+                .require_approval
+                .unwrap_or(defaults.require_approval),
+            auto_approve: config.server.auto_approve.unwrap_or(defaults.auto_approve),
+            sandbox: config.server.sandbox.unwrap_or(defaults.sandbox),
+        })
     }
 }
 
@@ -70,17 +100,99 @@ struct ExposedTool {
     internal: String,
 }
 
-pub fn run_mcp_server(workspace: PathBuf) -> Result<()> {
+pub fn run_mcp_server(config: &Config, workspace: PathBuf) -> Result<()> {
     let settings = McpServerSettings::load()?;
-    let mut server = McpServer::new(workspace, settings)?;
+    let mut server = McpServer::new(config, workspace, settings)?;
     server.run()
 }
 
+/// Line-delimited JSON-RPC over a reader/writer pair. Messages that arrive
+/// while the server waits for the reply to its own request are queued and
+/// handled afterwards.
+struct Channel<R, W> {
+    input: io::Lines<R>,
+    output: W,
+    backlog: VecDeque<Value>,
+    next_request_id: u64,
+}
+
+impl<R: BufRead, W: Write> Channel<R, W> {
+    fn new(input: R, output: W) -> Self {
+        Self {
+            input: input.lines(),
+            output,
+            backlog: VecDeque::new(),
+            next_request_id: 0,
+        }
+    }
+
+    fn read_message(&mut self) -> io::Result<Option<Value>> {
+        for line in self.input.by_ref() {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            if let Ok(message) = serde_json::from_str::<Value>(trimmed) {
+                return Ok(Some(message));
+            }
+        }
+        Ok(None)
+    }
+
+    fn next_message(&mut self) -> io::Result<Option<Value>> {
+        match self.backlog.pop_front() {
+            Some(message) => Ok(Some(message)),
+            None => self.read_message(),
+        }
+    }
+
+    fn send(&mut self, message: &Value) -> io::Result<()> {
+        let payload = serde_json::to_string(message)?;
+        writeln!(self.output, "{payload}")?;
+        self.output.flush()
+    }
+
+    /// Send a request to the client and wait for its reply: `Ok(result)` or
+    /// `Err(error object)`.
+    fn request(&mut self, method: &str, params: Value) -> io::Result<Result<Value, Value>> {
+        let id = format!("deepseek-server-{}", self.next_request_id);
+        self.next_request_id += 1;
+        self.send(&json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        }))?;
+        while let Some(message) = self.read_message()? {
+            let is_reply = message.get("method").is_none()
+                && message.get("id").and_then(Value::as_str) == Some(id.as_str());
+            if !is_reply {
+                self.backlog.push_back(message);
+                continue;
+            }
+            return Ok(match message.get("result") {
+                Some(result) => Ok(result.clone()),
+                None => Err(message.get("error").cloned().unwrap_or(Value::Null)),
+            });
+        }
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "client closed the connection before replying",
+        ))
+    }
+}
+
 struct McpServer {
     workspace: PathBuf,
     registry: crate::tools::ToolRegistry,
     exposed_tools: Vec<ExposedTool>,
     require_approval: bool,
+    auto_approve: bool,
+    /// The client declared the `elicitation` capability in `initialize`, so
+    /// approvals can be asked for with `elicitation/create`.
+    client_elicitation: bool,
+    guardrail_rules: Vec<GuardrailRuleConfig>,
     /// Thread-based conversation state for deepseek/deepseek-reply tools.
     /// Maps thread_id -> ordered list of messages in the conversation.
     threads: Arc<Mutex<HashMap<String, Vec<Message>>>>,
@@ -89,32 +201,34 @@ struct McpServer {
 }
 
 impl McpServer {
-    fn new(workspace: PathBuf, settings: McpServerSettings) -> Result<Self> {
-        let exposed_tools = build_exposed_tools(&settings.expose_tools);
-        let mut internal_names: HashSet<String> = HashSet::new();
-        for tool in &exposed_tools {
-            internal_names.insert(tool.internal.clone());
-        }
-
-        let mut builder = ToolRegistryBuilder::new()
-            .with_file_tools()
-            .with_search_tools();
-
-        if internal_names.contains("apply_patch") {
-            builder = builder.with_patch_tools();
-        }
-        if internal_names.contains("exec_shell") {
-            builder = builder.with_shell_tools();
+    fn new(config: &Config, workspace: PathBuf, settings: McpServerSettings) -> Result<Self> {
+        let mut context = ToolContext::new(workspace.clone());
+        if settings.sandbox {
+            context = context.with_elevated_sandbox_policy(SandboxPolicy::WorkspaceWrite {
+                writable_roots: vec![workspace.clone()],
+                network_access: true,
+                exclude_tmpdir: false,
+                exclude_slash_tmp: false,
+            });
         }
-
-        let context = ToolContext::new(workspace.clone());
-        let registry = builder.build(context);
+        let registry = server_tool_registry(config).build(context);
+        let exposed_tools = match &settings.expose_tools {
+            Some(names) => build_exposed_tools(names),
+            None => all_exposed_tools(&registry),
+        };
 
         Ok(Self {
             workspace,
             registry,
             exposed_tools,
             require_approval: settings.require_approval,
+            auto_approve: settings.auto_approve,
+            client_elicitation: false,
+            guardrail_rules: config
+                .guardrails
+                .as_ref()
+                .map(|guardrails| guardrails.rules.clone())
+                .unwrap_or_default(),
             threads: Arc::new(Mutex::new(HashMap::new())),
             next_notification_id: 0,
         })
@@ -122,50 +236,44 @@ impl McpServer {
 
     fn run(&mut self) -> Result<()> {
         let runtime = Runtime::new().context("Failed to start MCP runtime")?;
-        let stdin = io::stdin();
-        let mut stdout = io::stdout();
-
-        for line in stdin.lock().lines() {
-            let line = line?;
-            let trimmed = line.trim();
-            if trimmed.is_empty() {
-                continue;
-            }
-            let Ok(message) = serde_json::from_str::<Value>(trimmed) else {
-                continue;
-            };
-
-            if let Some(response) = self.handle_message(&runtime, message) {
-                let payload = serde_json::to_string(&response)?;
-                writeln!(stdout, "{payload}")?;
-                stdout.flush()?;
+        let mut channel = Channel::new(io::stdin().lock(), io::stdout());
+        while let Some(message) = channel.next_message()? {
+            if let Some(response) = self.handle_message(&runtime, &mut channel, message) {
+                channel.send(&response)?;
             }
         }
-
         Ok(())
     }
 
-    fn handle_message(&mut self, runtime: &Runtime, message: Value) -> Option<Value> {
+    fn handle_message<R: BufRead, W: Write>(
+        &mut self,
+        runtime: &Runtime,
+        channel: &mut Channel<R, W>,
+        message: Value,
+    ) -> Option<Value> {
         let method = message.get("method").and_then(Value::as_str)?;
         let id = message.get("id").cloned();
+        let params = message.get("params").cloned().unwrap_or_else(|| json!({}));
 
         match method {
-            "initialize" => respond(id.as_ref(), initialize_response()),
-            "tools/list" => respond(id.as_ref(), self.list_tools_response()),
-            "tools/call" => {
-                let params = message.get("params").cloned().unwrap_or_else(|| json!({}));
-                match self.call_tool(runtime, params, id.clone()) {
-                    Ok(result) => respond(id.as_ref(), result),
-                    Err(err) => respond_error(id.as_ref(), err.code, err.message),
-                }
+            "initialize" => {
+                self.client_elicitation = params
+                    .pointer("/capabilities/elicitation")
+                    .is_some_and(|cap| !cap.is_null());
+                let requested = params.get("protocolVersion").and_then(Value::as_str);
+                respond(id.as_ref(), initialize_response(requested))
             }
+            "tools/list" => respond(id.as_ref(), self.list_tools_response()),
+            "tools/call" => match self.call_tool(runtime, channel, params, id.clone()) {
+                Ok(result) => respond(id.as_ref(), result),
+                Err(err) => respond_error(id.as_ref(), err.code, err.message),
+            },
             "resources/list" => respond(id.as_ref(), self.list_resources_response()),
             "ping" => respond(id.as_ref(), json!({})),
             "notifications/initialized" => None,
             _ => respond_error(id.as_ref(), -32601, format!("Method not found: {method}")),
         }
     }
-
     fn list_tools_response(&self) -> Value {
         let mut tools = Vec::new();
         let mut seen = HashSet::new();
@@ -261,9 +369,10 @@ impl McpServer {
         json!({ "resources": resources, "nextCursor": Value::Null })
     }
 
-    fn call_tool(
+    fn call_tool<R: BufRead, W: Write>(
         &mut self,
         runtime: &Runtime,
+        channel: &mut Channel<R, W>,
         params: Value,
         request_id: Option<Value>,
     ) -> Result<Value, RpcError> {
@@ -279,45 +388,104 @@ impl McpServer {
                 message: "Missing tool name".to_string(),
             })?;
 
-        if self.require_approval
-            && !params
-                .get("approved")
-                .and_then(Value::as_bool)
-                .unwrap_or(false)
-        {
-            return Err(RpcError {
-                code: -32001,
-                message: "Approval required. Resend with approved=true.".to_string(),
-            });
-        }
+        let internal = self.resolve_tool(name).ok_or_else(|| RpcError {
+            code: -32602,
+            message: format!("Tool not exposed: {name}"),
+        })?;
+        let arguments = params
+            .get("arguments")
+            .cloned()
+            .unwrap_or_else(|| json!({}));
 
-        let internal = self
-            .exposed_tools
-            .iter()
-            .find(|tool| tool.public == name)
-            .map(|tool| tool.internal.clone())
-            .ok_or_else(|| RpcError {
-                code: -32602,
-                message: format!("Tool not exposed: {name}"),
-            })?;
+        let approved = params
+            .get("approved")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        if !approved && self.needs_approval(&internal) {
+            if !self.client_elicitation {
+                return Err(RpcError {
+                    code: -32001,
+                    message: format!("Approval required for {name}. Resend with approved=true."),
+                });
+            }
+            let message = self.approval_message(name, &internal, &arguments);
+            match ask_approval(channel, &message) {
+                Ok(true) => {}
+                Ok(false) => {
+                    return Ok(json!({
+                        "content": [{ "type": "text", "text": format!("The user declined the {name} call.") }],
+                        "isError": true,
+                    }));
+                }
+                Err(err) => {
+                    return Err(RpcError {
+                        code: -32000,
+                        message: format!("Approval request failed: {err}"),
+                    });
+                }
+            }
+        }
 
         // Handle deepseek and deepseek-reply natively
-        if internal == "deepseek" || internal == "deepseek-reply" {
-            let arguments = params
-                .get("arguments")
-                .cloned()
-                .unwrap_or_else(|| json!({}));
+        if CHAT_TOOLS.contains(&internal.as_str()) {
             return self.handle_deepseek_call(runtime, &internal, &arguments, request_id);
         }
 
-        let arguments = params
-            .get("arguments")
-            .cloned()
-            .unwrap_or_else(|| json!({}));
         let result = runtime.block_on(self.registry.execute_full(&internal, arguments));
         Ok(tool_result_to_mcp(result))
     }
 
+    /// Internal tool behind a public name; aliases such as `shell` resolve
+    /// to an exposed `exec_shell` too.
+    fn resolve_tool(&self, name: &str) -> Option<String> {
+        let internal = internal_tool_name(name);
+        self.exposed_tools
+            .iter()
+            .find(|tool| tool.public == name)
+            .or_else(|| {
+                self.exposed_tools
+                    .iter()
+                    .find(|tool| tool.internal == internal)
+            })
+            .map(|tool| tool.internal.clone())
+    }
+
+    fn needs_approval(&self, internal: &str) -> bool {
+        if self.require_approval {
+            return true;
+        }
+        if self.auto_approve {
+            return false;
+        }
+        self.registry
+            .get(internal)
+            .is_some_and(|tool| tool.approval_requirement() != ApprovalRequirement::Auto)
+    }
+
+    /// Prompt shown by the client: the call, plus the guardrail verdict for
+    /// shell commands.
+    fn approval_message(&self, name: &str, internal: &str, arguments: &Value) -> String {
+        let detail = ["command", "path", "file_path"]
+            .iter()
+            .find_map(|key| arguments.get(*key).and_then(Value::as_str))
+            .map_or_else(
+                || truncate_with_ellipsis(&arguments.to_string(), 400, "…"),
+                str::to_string,
+            );
+        let mut message = format!("Allow DeepSeek tool `{name}`?\n\n{detail}");
+        if let Some(verdict) =
+            scan_tool_call(internal, arguments, &self.workspace, &self.guardrail_rules)
+        {
+            message.push_str(&format!(
+                "\n\nRisk: {} ({}) {}",
+                verdict.risk.label(),
+                verdict.rule,
+                verdict.reason
+            ));
+        }
+        message
+    }
+
     /// Handle a `deepseek` or `deepseek-reply` tool call.
     ///
     /// Uses `DeepSeekClient` directly (not the full engine) to send a prompt
@@ -501,41 +669,105 @@ fn default_config_path() -> Option<PathBuf> {
     dirs::home_dir().map(|home| home.join(".deepseek").join("mcp_server.toml"))
 }
 
-fn default_expose_tools() -> Vec<String> {
-    vec![
-        "file_read".to_string(),
-        "file_write".to_string(),
-        "search".to_string(),
-        "apply_patch".to_string(),
-        "shell".to_string(),
-        "deepseek".to_string(),
-        "deepseek-reply".to_string(),
-    ]
+/// Tools served from the registry: the file, search, git, patch, shell,
+/// test and review surface the agent itself uses.
+fn server_tool_registry(config: &Config) -> ToolRegistryBuilder {
+    let client = DeepSeekClient::new(config).ok();
+    let model = config
+        .default_text_model
+        .clone()
+        .unwrap_or_else(|| DEFAULT_TEXT_MODEL.to_string());
+    ToolRegistryBuilder::new()
+        .with_file_tools()
+        .with_search_tools()
+        .with_git_tools()
+        .with_git_history_tools()
+        .with_diagnostics_tool()
+        .with_project_tools()
+        .with_docs_search_tool()
+        .with_patch_tools()
+        .with_shell_tools()
+        .with_test_runner_tool()
+        .with_validation_tools()
+        .with_review_tool(client, model)
 }
 
-fn build_exposed_tools(names: &[String]) -> Vec<ExposedTool> {
-    let mut tools = Vec::new();
-    for name in names {
-        let trimmed = name.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-        let public = trimmed.to_string();
-        let internal = match trimmed {
-            "file_read" => "read_file",
-            "file_write" => "write_file",
-            "file_edit" => "edit_file",
-            "shell" => "exec_shell",
-            "search" => "grep_files",
-            "file_search" => "file_search",
-            // deepseek and deepseek-reply are handled natively in call_tool
-            "deepseek" | "deepseek-reply" => trimmed,
-            other => other,
-        }
-        .to_string();
-        tools.push(ExposedTool { public, internal });
+/// Every registry tool under its own name, then the chat tools.
+fn all_exposed_tools(registry: &crate::tools::ToolRegistry) -> Vec<ExposedTool> {
+    let mut names = registry
+        .all()
+        .iter()
+        .map(|tool| tool.name().to_string())
+        .collect::<Vec<_>>();
+    names.sort();
+    names.extend(CHAT_TOOLS.iter().map(|name| (*name).to_string()));
+    names
+        .into_iter()
+        .map(|name| ExposedTool {
+            public: name.clone(),
+            internal: name,
+        })
+        .collect()
+}
+
+/// Registry name for a public tool name; short aliases from older
+/// `expose_tools` lists map to their tool.
+fn internal_tool_name(name: &str) -> &str {
+    match name {
+        "file_read" => "read_file",
+        "file_write" => "write_file",
+        "file_edit" => "edit_file",
+        "shell" => "exec_shell",
+        "search" => "grep_files",
+        other => other,
     }
-    tools
+}
+
+fn build_exposed_tools(names: &[String]) -> Vec<ExposedTool> {
+    names
+        .iter()
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .map(|name| ExposedTool {
+            public: name.to_string(),
+            internal: internal_tool_name(name).to_string(),
+        })
+        .collect()
+}
+
+/// Ask the client to approve a call through MCP elicitation.
+fn ask_approval<R: BufRead, W: Write>(
+    channel: &mut Channel<R, W>,
+    message: &str,
+) -> io::Result<bool> {
+    let reply = channel.request(
+        "elicitation/create",
+        json!({
+            "message": message,
+            "requestedSchema": {
+                "type": "object",
+                "properties": {
+                    "approve": {
+                        "type": "boolean",
+                        "title": "Approve",
+                        "description": "Run this tool call",
+                        "default": true,
+                    }
+                },
+                "required": ["approve"],
+            },
+        }),
+    )?;
+    let Ok(result) = reply else {
+        return Ok(false);
+    };
+    Ok(
+        result.get("action").and_then(Value::as_str) == Some("accept")
+            && result
+                .pointer("/content/approve")
+                .and_then(Value::as_bool)
+                .unwrap_or(true),
+    )
 }
 
 fn tool_result_to_mcp(result: Result<ToolResult, ToolError>) -> Value {
@@ -557,9 +789,16 @@ fn tool_result_to_mcp(result: Result<ToolResult, ToolError>) -> Value {
     }
 }
 
-fn initialize_response() -> Value {
+/// Protocol revisions the server speaks, newest first. Elicitation needs
+/// 2025-06-18.
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+fn initialize_response(requested: Option<&str>) -> Value {
+    let version = requested
+        .filter(|version| SUPPORTED_PROTOCOL_VERSIONS.contains(version))
+        .unwrap_or("2024-11-05");
     json!({
-        "protocolVersion": "2024-11-05",
+        "protocolVersion": version,
         "serverInfo": {
             "name": "deepseek-mcp-server",
             "version": env!("CARGO_PKG_VERSION"),
@@ -622,4 +861,93 @@ mod tests {
         );
         assert_eq!(map.get("shell").map(String::as_str), Some("exec_shell"));
     }
+
+    fn server(workspace: &std::path::Path, settings: McpServerSettings) -> McpServer {
+        McpServer::new(&Config::default(), workspace.to_path_buf(), settings).expect("server")
+    }
+
+    fn write_call() -> Value {
+        json!({
+            "name": "write_file",
+            "arguments": { "path": "out.txt", "content": "hello" },
+        })
+    }
+
+    #[test]
+    fn default_listing_advertises_the_registry_with_schemas() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let server = server(tmp.path(), McpServerSettings::default());
+        let listing = server.list_tools_response();
+        let tools = listing["tools"].as_array().expect("tools");
+        for name in [
+            "read_file",
+            "edit_file",
+            "grep_files",
+            "git_status",
+            "git_log",
+            "apply_patch",
+            "exec_shell",
+            "review",
+            "deepseek",
+        ] {
+            let tool = tools
+                .iter()
+                .find(|tool| tool["name"] == name)
+                .unwrap_or_else(|| panic!("{name} missing from tools/list"));
+            assert_eq!(tool["inputSchema"]["type"], "object", "{name}");
+        }
+        // Old aliases still reach the tool they named.
+        assert_eq!(server.resolve_tool("shell").as_deref(), Some("exec_shell"));
+        assert!(server.needs_approval("exec_shell"));
+        assert!(server.needs_approval("write_file"));
+        assert!(!server.needs_approval("read_file"));
+    }
+
+    #[test]
+    fn write_calls_need_approval_and_use_elicitation_when_offered() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let runtime = Runtime::new().expect("runtime");
+        let mut server = server(tmp.path(), McpServerSettings::default());
+        let target = tmp.path().join("out.txt");
+
+        let mut silent = Channel::new(io::Cursor::new(Vec::new()), Vec::new());
+        let err = server
+            .call_tool(&runtime, &mut silent, write_call(), None)
+            .expect_err("approval required");
+        assert_eq!(err.code, -32001);
+        assert!(!target.exists());
+
+        server.client_elicitation = true;
+        let declined =
+            r#"{"jsonrpc":"2.0","id":"deepseek-server-0","result":{"action":"decline"}}"#;
+        let mut channel = Channel::new(io::Cursor::new(format!("{declined}\n")), Vec::new());
+        let result = server
+            .call_tool(&runtime, &mut channel, write_call(), None)
+            .expect("declined result");
+        assert_eq!(result["isError"], true);
+        assert!(!target.exists());
+        let request: Value = serde_json::from_slice(&channel.output).expect("elicitation request");
+        assert_eq!(request["method"], "elicitation/create");
+        assert!(
+            request["params"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("out.txt")
+        );
+
+        // A request that arrives before the reply is queued, not dropped.
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","id":7,"method":"ping"}"#,
+            "\n",
+            r#"{"jsonrpc":"2.0","id":"deepseek-server-0","result":{"action":"accept","content":{"approve":true}}}"#,
+            "\n",
+        );
+        let mut channel = Channel::new(io::Cursor::new(input), Vec::new());
+        let result = server
+            .call_tool(&runtime, &mut channel, write_call(), None)
+            .expect("approved result");
+        assert_eq!(result["isError"], false);
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "hello");
+        assert_eq!(channel.next_message().unwrap().unwrap()["method"], "ping");
+    }
 }
@@ -207,6 +207,35 @@ pub struct Usage {
     pub server_tool_use: Option<ServerToolUsage>,
 }
 
+impl Usage {
+    /// Usage accumulated since `earlier`, a previous snapshot of the same
+    /// running total. Counts that `earlier` lacks are kept as-is.
+    #[must_use]
+    pub fn saturating_sub(&self, earlier: &Usage) -> Usage {
+        fn sub(total: Option<u32>, earlier: Option<u32>) -> Option<u32> {
+            total.map(|total| total.saturating_sub(earlier.unwrap_or(0)))
+        }
+        Usage {
+            input_tokens: self.input_tokens.saturating_sub(earlier.input_tokens),
+            output_tokens: self.output_tokens.saturating_sub(earlier.output_tokens),
+            prompt_cache_hit_tokens: sub(
+                self.prompt_cache_hit_tokens,
+                earlier.prompt_cache_hit_tokens,
+            ),
+            prompt_cache_miss_tokens: sub(
+                self.prompt_cache_miss_tokens,
+                earlier.prompt_cache_miss_tokens,
+            ),
+            reasoning_tokens: sub(self.reasoning_tokens, earlier.reasoning_tokens),
+            reasoning_replay_tokens: sub(
+                self.reasoning_replay_tokens,
+                earlier.reasoning_replay_tokens,
+            ),
+            server_tool_use: self.server_tool_use.clone(),
+        }
+    }
+}
+
 /// Map known models to their approximate context window sizes.
 #[must_use]
 pub fn context_window_for_model(model: &str) -> Option<u32> {
@@ -0,0 +1,368 @@
+//! Fallbacks for `deepseek apply` when `git apply` rejects a patch because
+//! the tree drifted since the patch was generated.
+//!
+//! The first fallback is git's own 3-way merge, which only works when the
+//! patch records blob ids the repository still has. After that, hunks are
+//! placed one at a time with the `apply_patch` tool's fuzzy matcher; a hunk
+//! that still does not fit is handed to the caller together with ranked
+//! placement suggestions, so an interactive resolver can show it against the
+//! current file and accept an adjusted placement. Nothing is written until
+//! every hunk has been placed or skipped.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+
+use crate::tools::apply_patch::{
+    Hunk, HunkLine, HunkPlacement, align_hunk, apply_hunk_at, apply_hunk_fuzzy, parse_file_patches,
+    score_placement, suggest_hunk_placements,
+};
+use crate::utils::truncate_with_ellipsis;
+
+/// Fuzz used for per-hunk placement, matching the `apply_patch` default.
+const HUNK_FUZZ: usize = 3;
+/// Placement suggestions offered per failing hunk.
+const MAX_PLACEMENTS: usize = 5;
+/// Widest column in the side-by-side conflict view.
+const COLUMN_WIDTH: usize = 48;
+
+/// A hunk that neither exact nor fuzzy matching could place.
+pub struct HunkConflict<'a> {
+    pub path: &'a str,
+    /// 1-based hunk number within the file.
+    pub number: usize,
+    pub total: usize,
+    pub hunk: &'a Hunk,
+    /// Current file content, with earlier hunks of this file applied.
+    pub lines: &'a [String],
+    pub placements: &'a [HunkPlacement],
+}
+
+/// What to do with a [`HunkConflict`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HunkDecision {
+    /// Apply the hunk over the window starting at this 1-based line.
+    Place(usize),
+    /// Leave this hunk out and keep going.
+    Skip,
+    /// Stop without writing anything.
+    Abort,
+}
+
+/// Files to write once every hunk has been decided.
+#[derive(Debug, Default)]
+pub struct Resolution {
+    writes: Vec<(PathBuf, Option<String>)>,
+    /// Hunks placed by the fuzzy matcher or an accepted suggestion.
+    pub adjusted: usize,
+    /// `path hunk N/M` labels of hunks left out.
+    pub skipped: Vec<String>,
+}
+
+impl Resolution {
+    /// Write (or delete) every resolved file.
+    pub fn write(&self) -> Result<()> {
+        for (path, content) in &self.writes {
+            match content {
+                Some(content) => {
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(path, content)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                }
+                None => fs::remove_file(path)
+                    .with_context(|| format!("Failed to delete {}", path.display()))?,
+            }
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn file_count(&self) -> usize {
+        self.writes.len()
+    }
+}
+
+/// Try `git apply --3way`. Returns `Ok(true)` only when git merged the patch
+/// cleanly; a merge that would leave conflict markers is never attempted.
+pub fn try_three_way(patch_path: &Path, cwd: &Path) -> Result<bool> {
+    let check = Command::new("git")
+        .args(["apply", "--3way", "--check", "--whitespace=nowarn"])
+        .arg(patch_path)
+        .current_dir(cwd)
+        .output()
+        .context("Failed to run git apply --3way")?;
+    let report = String::from_utf8_lossy(&check.stderr);
+    if !check.status.success() || report.contains("with conflicts") {
+        return Ok(false);
+    }
+    let apply = Command::new("git")
+        .args(["apply", "--3way", "--whitespace=nowarn"])
+        .arg(patch_path)
+        .current_dir(cwd)
+        .output()
+        .context("Failed to run git apply --3way")?;
+    Ok(apply.status.success())
+}
+
+/// Place every hunk of `patch` against the files under `root`, asking
+/// `decide` about hunks that no longer fit. Returns `None` when `decide`
+/// aborts.
+pub fn resolve_hunks(
+    patch: &str,
+    root: &Path,
+    mut decide: impl FnMut(&HunkConflict<'_>) -> HunkDecision,
+) -> Result<Option<Resolution>> {
+    let file_patches = parse_file_patches(patch).map_err(|err| anyhow::anyhow!("{err}"))?;
+    if file_patches.is_empty() {
+        bail!("Patch has no `---`/`+++` file sections.");
+    }
+
+    let mut resolution = Resolution::default();
+    for file_patch in file_patches {
+        let path = safe_join(root, &file_patch.path)?;
+        let original = if path.exists() {
+            Some(
+                fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?,
+            )
+        } else if file_patch.create_if_missing {
+            None
+        } else {
+            bail!("`{}` does not exist.", file_patch.path);
+        };
+        let mut lines: Vec<String> = original
+            .as_deref()
+            .unwrap_or_default()
+            .lines()
+            .map(String::from)
+            .collect();
+
+        let total = file_patch.hunks.len();
+        let mut offset: isize = 0;
+        for (idx, hunk) in file_patch.hunks.iter().enumerate() {
+            match apply_hunk_fuzzy(&mut lines, hunk, HUNK_FUZZ, &mut offset) {
+                Some(0) => continue,
+                Some(_) => {
+                    resolution.adjusted += 1;
+                    continue;
+                }
+                None => {}
+            }
+            let placements = suggest_hunk_placements(&lines, hunk, MAX_PLACEMENTS);
+            let conflict = HunkConflict {
+                path: &file_patch.path,
+                number: idx + 1,
+                total,
+                hunk,
+                lines: &lines,
+                placements: &placements,
+            };
+            match decide(&conflict) {
+                HunkDecision::Place(line) => {
+                    let before = lines.len();
+                    apply_hunk_at(&mut lines, hunk, line);
+                    offset += lines.len() as isize - before as isize;
+                    resolution.adjusted += 1;
+                }
+                HunkDecision::Skip => {
+                    resolution
+                        .skipped
+                        .push(format!("{} hunk {}/{total}", file_patch.path, idx + 1))
+                }
+                HunkDecision::Abort => return Ok(None),
+            }
+        }
+
+        let content = if file_patch.delete_after {
+            None
+        } else {
+            let mut content = lines.join("\n");
+            if original.as_deref().is_none_or(|text| text.ends_with('\n')) && !content.is_empty() {
+                content.push('\n');
+            }
+            Some(content)
+        };
+        resolution.writes.push((path, content));
+    }
+    Ok(Some(resolution))
+}
+
+/// Score a placement the user typed in, for previewing it.
+#[must_use]
+pub fn placement_at(conflict: &HunkConflict<'_>, line: usize) -> HunkPlacement {
+    score_placement(conflict.lines, conflict.hunk, line)
+}
+
+/// Paths in a patch must stay inside the working tree.
+fn safe_join(root: &Path, relative: &str) -> Result<PathBuf> {
+    let relative = Path::new(relative);
+    if relative
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        bail!(
+            "Refusing to patch `{}` outside the working tree.",
+            relative.display()
+        );
+    }
+    Ok(root.join(relative))
+}
+
+/// Side-by-side view of the hunk against the file at `placement`. Marks:
+/// `=` aligned, `!` aligned but edited since, `?` not found, `+` added by
+/// the hunk, `>` a file line the hunk does not mention (kept).
+#[must_use]
+pub fn render_conflict(conflict: &HunkConflict<'_>, placement: Option<&HunkPlacement>) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Hunk {}/{} in {} does not apply (header expects line {}).",
+        conflict.number, conflict.total, conflict.path, conflict.hunk.old_start
+    );
+    let Some(placement) = placement else {
+        let _ = writeln!(
+            out,
+            "No placement in the current file matches half its lines."
+        );
+        return out;
+    };
+    let _ = writeln!(
+        out,
+        "Candidate: line {} ({}/{} lines match)",
+        placement.line, placement.matched, placement.total
+    );
+    let _ = writeln!(out, "  {:>5}   {:<COLUMN_WIDTH$} | file", "line", "patch");
+
+    let alignment = align_hunk(conflict.lines, conflict.hunk, placement.line);
+    let window = &conflict.lines[alignment.start..alignment.start + alignment.span];
+    let row = |out: &mut String, file_idx: Option<usize>, marker: char, left: &str| {
+        let (number, right) = file_idx.map_or((String::new(), ""), |idx| {
+            (
+                (alignment.start + idx + 1).to_string(),
+                window[idx].as_str(),
+            )
+        });
+        let _ = writeln!(
+            out,
+            "  {number:>5} {marker} {:<COLUMN_WIDTH$} | {}",
+            truncate_with_ellipsis(left, COLUMN_WIDTH, "…"),
+            truncate_with_ellipsis(right, COLUMN_WIDTH, "…"),
+        );
+    };
+    let mut old_idx = 0usize;
+    let mut cursor = 0usize;
+    for hunk_line in &conflict.hunk.lines {
+        let (sign, text) = match hunk_line {
+            HunkLine::Add(text) => {
+                row(&mut out, None, '+', &format!("+{text}"));
+                continue;
+            }
+            HunkLine::Context(text) => (' ', text),
+            HunkLine::Remove(text) => ('-', text),
+        };
+        let left = format!("{sign}{text}");
+        let target = alignment.mapped[old_idx];
+        old_idx += 1;
+        let Some(file_idx) = target else {
+            row(&mut out, None, '?', &left);
+            continue;
+        };
+        for kept in cursor..file_idx {
+            row(&mut out, Some(kept), '>', "");
+        }
+        cursor = file_idx + 1;
+        let marker = if window[file_idx].trim() == text.trim() {
+            '='
+        } else {
+            '!'
+        };
+        row(&mut out, Some(file_idx), marker, &left);
+    }
+    for kept in cursor..window.len() {
+        row(&mut out, Some(kept), '>', "");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const DRIFTED_PATCH: &str = "\
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn keep() {}
+-fn old() {}
++fn new() {}
+@@ -20,3 +20,3 @@
+ fn a() {
+-    before();
++    after();
+ }
+";
+
+    #[test]
+    fn drifted_hunk_is_offered_and_placed_at_the_accepted_line() {
+        let tmp = tempdir().expect("tempdir");
+        fs::create_dir_all(tmp.path().join("src")).expect("mkdir");
+        fs::write(
+            tmp.path().join("src/lib.rs"),
+            "fn keep() {}\nfn old() {}\n\nfn a() {\n    before(1);\n}\n",
+        )
+        .expect("write");
+
+        let mut seen = Vec::new();
+        let resolution = resolve_hunks(DRIFTED_PATCH, tmp.path(), |conflict| {
+            seen.push((conflict.number, conflict.placements.to_vec()));
+            let view = render_conflict(conflict, conflict.placements.first());
+            assert!(view.contains("Hunk 2/2 in src/lib.rs"), "{view}");
+            assert!(view.contains("!"), "{view}");
+            HunkDecision::Place(conflict.placements[0].line)
+        })
+        .expect("resolve")
+        .expect("not aborted");
+
+        assert_eq!(seen.len(), 1);
+        assert_eq!(
+            seen[0].1[0],
+            HunkPlacement {
+                line: 4,
+                matched: 2,
+                total: 3
+            }
+        );
+        assert_eq!(resolution.adjusted, 1);
+        resolution.write().expect("write");
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("src/lib.rs")).unwrap(),
+            "fn keep() {}\nfn new() {}\n\nfn a() {\n    after();\n}\n"
+        );
+    }
+
+    #[test]
+    fn abort_writes_nothing_and_escaping_paths_are_refused() {
+        let tmp = tempdir().expect("tempdir");
+        fs::create_dir_all(tmp.path().join("src")).expect("mkdir");
+        let original = "fn keep() {}\nfn old() {}\n\nfn a() {\n    before(1);\n}\n";
+        fs::write(tmp.path().join("src/lib.rs"), original).expect("write");
+
+        let outcome =
+            resolve_hunks(DRIFTED_PATCH, tmp.path(), |_| HunkDecision::Abort).expect("resolve");
+        assert!(outcome.is_none());
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("src/lib.rs")).unwrap(),
+            original
+        );
+
+        let escaping = "--- a/../x\n+++ b/../x\n@@ -1 +1 @@\n-a\n+b\n";
+        let err = resolve_hunks(escaping, tmp.path(), |_| HunkDecision::Skip).unwrap_err();
+        assert!(err.to_string().contains("outside the working tree"));
+    }
+}
@@ -201,6 +201,25 @@ fn calculate_turn_cost_from_usage_with_pricing(pricing: CurrencyPricing, usage:
     hit_cost + miss_cost + output_cost
 }
 
+/// Estimate what the context cache saved on one response: cache-hit tokens
+/// billed at the hit rate instead of the miss rate.
+#[must_use]
+pub fn calculate_cache_savings_estimate_from_usage(
+    model: &str,
+    usage: &Usage,
+) -> Option<CostEstimate> {
+    let pricing = pricing_for_model(model)?;
+    let hit_tokens = f64::from(usage.prompt_cache_hit_tokens.unwrap_or(0));
+    let saved = |pricing: CurrencyPricing| {
+        (hit_tokens / 1_000_000.0)
+            * (pricing.input_cache_miss_per_million - pricing.input_cache_hit_per_million)
+    };
+    Some(CostEstimate {
+        usd: saved(pricing.usd),
+        cny: saved(pricing.cny),
+    })
+}
+
 /// Format a USD cost for compact display.
 #[must_use]
 #[allow(dead_code)]
@@ -232,6 +251,32 @@ pub fn format_cost_amount_precise(cost: f64, currency: CostCurrency) -> String {
     }
 }
 
+/// Input (cache miss) and output rates for `model`, e.g.
+/// `$0.14 in · $0.28 out per 1M`. `None` when the model has no known pricing.
+#[must_use]
+pub fn format_model_rates(model: &str, currency: CostCurrency) -> Option<String> {
+    let pricing = pricing_for_model(model)?;
+    let rates = match currency {
+        CostCurrency::Usd => pricing.usd,
+        CostCurrency::Cny => pricing.cny,
+    };
+    let symbol = currency.symbol();
+    Some(format!(
+        "{symbol}{} in · {symbol}{} out per 1M",
+        format_rate(rates.input_cache_miss_per_million),
+        format_rate(rates.output_per_million)
+    ))
+}
+
+/// A per-million rate with two decimals, or three when needed (`0.435`).
+fn format_rate(rate: f64) -> String {
+    let text = format!("{rate:.3}");
+    match text.strip_suffix('0') {
+        Some(trimmed) => trimmed.to_string(),
+        None => text,
+    }
+}
+
 /// Format a dual-currency estimate using the selected display currency.
 #[must_use]
 pub fn format_cost_estimate(estimate: CostEstimate, currency: CostCurrency) -> String {
@@ -247,6 +292,16 @@ mod tests {
         assert!(calculate_turn_cost("deepseek-ai/deepseek-v4-pro", 1_000, 1_000).is_none());
     }
 
+    #[test]
+    fn model_rates_are_formatted_per_currency() {
+        assert_eq!(
+            format_model_rates("deepseek-v4-flash", CostCurrency::Cny).as_deref(),
+            Some("¥1.00 in · ¥2.00 out per 1M")
+        );
+        assert_eq!(format_rate(0.435), "0.435");
+        assert!(format_model_rates("gpt-4o", CostCurrency::Usd).is_none());
+    }
+
     #[test]
     fn v4_pro_uses_limited_time_discount_before_expiry() {
         let before_expiry = Utc
@@ -312,6 +367,28 @@ mod tests {
         assert_eq!(estimate.cny, 2.0);
     }
 
+    #[test]
+    fn cache_savings_price_hits_at_the_miss_minus_hit_rate() {
+        let usage = Usage {
+            input_tokens: 1_500_000,
+            prompt_cache_hit_tokens: Some(1_000_000),
+            prompt_cache_miss_tokens: Some(500_000),
+            ..Usage::default()
+        };
+        let saved = calculate_cache_savings_estimate_from_usage("deepseek-v4-flash", &usage)
+            .expect("estimate");
+        assert!((saved.usd - 0.1372).abs() < 1e-9);
+        assert!((saved.cny - 0.98).abs() < 1e-9);
+
+        let no_hits = Usage {
+            input_tokens: 10,
+            ..Usage::default()
+        };
+        let saved = calculate_cache_savings_estimate_from_usage("deepseek-v4-flash", &no_hits)
+            .expect("estimate");
+        assert!(!saved.is_positive());
+    }
+
     #[test]
     fn cost_currency_accepts_yuan_aliases() {
         assert_eq!(CostCurrency::from_setting("usd"), Some(CostCurrency::Usd));
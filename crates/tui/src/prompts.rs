@@ -7,17 +7,26 @@
 //! This keeps each concern in its own file and makes prompt tuning
 //! a single-file operation.
 
+pub mod layers;
+
 use crate::models::SystemPrompt;
 use crate::project_context::{ProjectContext, load_project_context_with_parents};
 use crate::tui::app::AppMode;
 use crate::tui::approval::ApprovalMode;
+use layers::PromptLayer;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct PromptSessionContext<'a> {
     pub user_memory_block: Option<&'a str>,
+    /// Digest of failed shell commands in this workspace
+    /// (`crate::lessons::compose_block`).
+    pub command_lessons_block: Option<&'a str>,
     pub goal_objective: Option<&'a str>,
     pub project_context_pack_enabled: bool,
+    /// Token budget for the `repo_map` layer (`crate::repo_map`); `None`
+    /// leaves the layer out.
+    pub repo_map_tokens: Option<usize>,
     /// Resolved BCP-47 locale tag for the `## Environment` block in
     /// the system prompt (e.g. `"en"`, `"zh-Hans"`, `"ja"`). The
     /// caller is responsible for resolving this from `Settings`; no
@@ -28,6 +37,12 @@ pub struct PromptSessionContext<'a> {
     /// to the system prompt instructing the model to respond in
     /// the resolved session locale.
     pub translation_enabled: bool,
+    /// When true, the model is asked to end each final answer with a
+    /// `<self_report>` footer (see [`crate::self_report`]).
+    pub self_report: bool,
+    /// `[prompt_layers]` overrides (disable / replace / reorder / custom).
+    /// `None` keeps the built-in layer stack untouched.
+    pub layer_overrides: Option<&'a layers::PromptLayerOverrides>,
 }
 
 /// Conventional location for the structured session relay artifact (#32).
@@ -366,6 +381,22 @@ pub const COMPACT_TEMPLATE: &str = include_str!("prompts/compact.md");
 /// can override the user's current request (#725).
 pub const MEMORY_GUIDANCE: &str = include_str!("prompts/memory_guidance.md");
 
+/// `## Context Management` guidance appended in Agent / Yolo modes.
+const CONTEXT_MANAGEMENT_GUIDANCE: &str = "## Context Management\n\n\
+When the conversation gets long (you'll see a context usage indicator), you can:\n\
+1. Use `/compact` to summarize earlier context and free up space\n\
+2. The system will preserve important information (files you're working on, recent messages, tool results)\n\
+3. After compaction, you'll see a summary of what was discussed and can continue seamlessly\n\n\
+If you notice context is getting long (>60% during sustained work), proactively suggest using `/compact` to the user.\n\n\
+### Prompt-cache awareness\n\n\
+DeepSeek caches the longest *byte-stable prefix* of every request and charges roughly 100× less for cache-hit tokens than miss tokens. The system prompt above is layered most-static-first specifically so the prefix stays stable turn-over-turn. To keep cache hits high:\n\
+- **Working set location:** the current repo working set is stored on new user messages inside a `<turn_meta>` block. Treat it as high-priority turn metadata, not as a stable system-prompt section.\n\
+- **Append, don't reorder.** New context goes at the end (latest user / tool messages). Reshuffling earlier messages or rewriting their content invalidates the cache for everything after the change.\n\
+- **Don't paraphrase quoted content.** If you've already read a file, refer to it by path or line range instead of re-quoting it with different formatting.\n\
+- **Use `/compact` as a hard reset, not a tweak.** Compaction is meant for when the cache is already losing — it intentionally rewrites the prefix to a shorter summary. Don't trigger it for small wins.\n\
+- **Read once, refer back.** Re-reading the same file produces a different tool-result envelope than the prior read; it's cheaper to scroll back than to re-fetch.\n\
+- **Footer chip:** the `cache hit %` chip turns red below 40% and yellow below 80%. If it's been red for several turns, that's a signal to consolidate.";
+
 // ── Legacy prompt constants (kept for backwards compatibility) ────────
 
 /// Legacy base prompt (agent.txt — now decomposed into base.md + overlays).
@@ -545,10 +576,14 @@ pub fn system_prompt_for_mode_with_context_and_skills(
         instructions,
         PromptSessionContext {
             user_memory_block,
+            command_lessons_block: None,
             goal_objective: None,
             project_context_pack_enabled: true,
+            repo_map_tokens: None,
             locale_tag: "en",
             translation_enabled: false,
+            self_report: false,
+            layer_overrides: None,
         },
     )
 }
@@ -581,7 +616,29 @@ pub fn system_prompt_for_mode_with_context_skills_session_and_approval(
     session_context: PromptSessionContext<'_>,
     approval_mode: ApprovalMode,
 ) -> SystemPrompt {
-    let mode_prompt = compose_mode_prompt_with_approval(mode, approval_mode);
+    let layers = system_prompt_layers(
+        mode,
+        workspace,
+        skills_dir,
+        instructions,
+        session_context,
+        approval_mode,
+    );
+    SystemPrompt::Text(layers::render(&layers))
+}
+
+/// Assemble the named layers that make up the system prompt, with any
+/// `[prompt_layers]` overrides from `session_context` applied. Rendering
+/// the result with [`layers::render`] yields the prompt text.
+pub fn system_prompt_layers(
+    mode: AppMode,
+    workspace: &Path,
+    skills_dir: Option<&Path>,
+    instructions: Option<&[PathBuf]>,
+    session_context: PromptSessionContext<'_>,
+    approval_mode: ApprovalMode,
+) -> Vec<PromptLayer> {
+    let mut prompt_layers = Vec::new();
 
     // Load project context from workspace
     let project_context = load_project_context_with_parents(workspace);
@@ -594,28 +651,37 @@ pub fn system_prompt_for_mode_with_context_skills_session_and_approval(
     // in English even though `lang: zh-Hans` is set" failure mode that
     // PR #1398 partially addressed. English (and unknown) locales get
     // `None` and keep the previous behavior unchanged.
-    let preamble = locale_reinforcement_preamble(session_context.locale_tag);
+    if let Some(preamble) = locale_reinforcement_preamble(session_context.locale_tag) {
+        prompt_layers.push(PromptLayer::builtin(layers::LOCALE_PREAMBLE, preamble));
+    }
 
     // 1–2. Mode prompt + project context.
     // `load_project_context_with_parents` auto-generates .deepseek/instructions.md
     // when no context file exists, so the fallback should always be available.
-    let mut full_prompt = if let Some(project_block) = project_context.as_system_block() {
-        format!("{}\n\n{}", mode_prompt, project_block)
+    prompt_layers.push(PromptLayer::builtin(
+        layers::BASE,
+        compose_mode_prompt_with_approval(mode, approval_mode),
+    ));
+    if let Some(project_block) = project_context.as_system_block() {
+        prompt_layers.push(PromptLayer::builtin(layers::PROJECT_CONTEXT, project_block));
     } else {
         // Extremely unlikely: context generation failed (e.g. filesystem error).
         // Use mode prompt alone rather than panic.
         tracing::warn!("No project context available and auto-generation failed");
-        mode_prompt
-    };
-
-    if let Some(preamble) = preamble {
-        full_prompt = format!("{preamble}\n\n{full_prompt}");
     }
 
     if session_context.project_context_pack_enabled
         && let Some(pack) = crate::project_context::generate_project_context_pack(workspace)
     {
-        full_prompt = format!("{full_prompt}\n\n{pack}");
+        prompt_layers.push(PromptLayer::builtin(layers::PROJECT_PACK, pack));
+    }
+
+    // 2.1. Repo map — keyed on git HEAD, so it only changes (and only
+    // costs a prefix-cache miss) when the checkout moves.
+    if let Some(budget) = session_context.repo_map_tokens
+        && let Some(map) = crate::repo_map::prompt_block(workspace, budget)
+    {
+        prompt_layers.push(PromptLayer::builtin(layers::REPO_MAP, map));
     }
 
     // 2.25. Environment block — locale, platform, shell, pwd. All
@@ -624,10 +690,10 @@ pub fn system_prompt_for_mode_with_context_skills_session_and_approval(
     // come from process env). Inserted above skills so it remains in
     // the workspace-static cache layer alongside the mode prompt and
     // project context.
-    full_prompt = format!(
-        "{full_prompt}\n\n{}",
+    prompt_layers.push(PromptLayer::builtin(
+        layers::ENVIRONMENT,
         render_environment_block(workspace, session_context.locale_tag),
-    );
+    ));
 
     // 2.3a. Translation output instruction — when enabled, instruct
     // the model to respond in the resolved session locale. Stays
@@ -635,10 +701,19 @@ pub fn system_prompt_for_mode_with_context_skills_session_and_approval(
     // flag, not a per-turn one: enabling `/translate` is a session
     // toggle, so the prompt-prefix bytes don't drift turn-over-turn.
     if session_context.translation_enabled {
-        full_prompt = format!(
-            "{full_prompt}\n\n{}",
-            translation_output_instruction(session_context.locale_tag)
-        );
+        prompt_layers.push(PromptLayer::builtin(
+            layers::TRANSLATION,
+            translation_output_instruction(session_context.locale_tag),
+        ));
+    }
+
+    // 2.3b. Self-report footer request. A config-level flag, so it stays
+    // above the volatile boundary like the translation instruction.
+    if session_context.self_report {
+        prompt_layers.push(PromptLayer::builtin(
+            layers::SELF_REPORT,
+            crate::self_report::INSTRUCTIONS,
+        ));
     }
 
     // 3. Skills block. #432: walks every candidate workspace
@@ -653,33 +728,23 @@ pub fn system_prompt_for_mode_with_context_skills_session_and_approval(
     let skills_block = crate::skills::render_available_skills_context_for_workspace(workspace)
         .or_else(|| skills_dir.and_then(crate::skills::render_available_skills_context));
     if let Some(block) = skills_block {
-        full_prompt = format!("{full_prompt}\n\n{block}");
+        prompt_layers.push(PromptLayer::builtin(layers::SKILLS, block));
     }
 
     // 4. Context Management (Agent / Yolo only).
     if matches!(mode, AppMode::Agent | AppMode::Yolo) {
-        full_prompt.push_str(
-            "\n\n## Context Management\n\n\
-             When the conversation gets long (you'll see a context usage indicator), you can:\n\
-             1. Use `/compact` to summarize earlier context and free up space\n\
-             2. The system will preserve important information (files you're working on, recent messages, tool results)\n\
-             3. After compaction, you'll see a summary of what was discussed and can continue seamlessly\n\n\
-             If you notice context is getting long (>60% during sustained work), proactively suggest using `/compact` to the user.\n\n\
-             ### Prompt-cache awareness\n\n\
-             DeepSeek caches the longest *byte-stable prefix* of every request and charges roughly 100× less for cache-hit tokens than miss tokens. The system prompt above is layered most-static-first specifically so the prefix stays stable turn-over-turn. To keep cache hits high:\n\
-             - **Working set location:** the current repo working set is stored on new user messages inside a `<turn_meta>` block. Treat it as high-priority turn metadata, not as a stable system-prompt section.\n\
-             - **Append, don't reorder.** New context goes at the end (latest user / tool messages). Reshuffling earlier messages or rewriting their content invalidates the cache for everything after the change.\n\
-             - **Don't paraphrase quoted content.** If you've already read a file, refer to it by path or line range instead of re-quoting it with different formatting.\n\
-             - **Use `/compact` as a hard reset, not a tweak.** Compaction is meant for when the cache is already losing — it intentionally rewrites the prefix to a shorter summary. Don't trigger it for small wins.\n\
-             - **Read once, refer back.** Re-reading the same file produces a different tool-result envelope than the prior read; it's cheaper to scroll back than to re-fetch.\n\
-             - **Footer chip:** the `cache hit %` chip turns red below 40% and yellow below 80%. If it's been red for several turns, that's a signal to consolidate."
-        );
+        prompt_layers.push(PromptLayer::builtin(
+            layers::CONTEXT_MANAGEMENT,
+            CONTEXT_MANAGEMENT_GUIDANCE,
+        ));
     }
 
     // 5. Compaction relay template — so the model knows the format to use
     //    when writing `.deepseek/handoff.md` on exit / `/compact`.
-    full_prompt.push_str("\n\n");
-    full_prompt.push_str(COMPACT_TEMPLATE);
+    prompt_layers.push(PromptLayer::builtin(
+        layers::COMPACT_TEMPLATE,
+        COMPACT_TEMPLATE,
+    ));
 
     // ── Volatile-content boundary ─────────────────────────────────────────
     // Everything below drifts mid-session and busts the prefix cache for
@@ -687,6 +752,7 @@ pub fn system_prompt_for_mode_with_context_skills_session_and_approval(
     // skills, context management, compact template) live above this line
     // so DeepSeek's KV prefix cache can hit on the entire system prompt
     // regardless of per-session edits to memory, goals, or instructions.
+    // Custom `[prompt_layers]` entries are inserted at this boundary.
 
     // 6a. Configured `instructions = [...]` files (#454). Loaded
     // and concatenated in declared order. Placed below the volatile boundary
@@ -696,7 +762,7 @@ pub fn system_prompt_for_mode_with_context_skills_session_and_approval(
     if let Some(paths) = instructions
         && let Some(block) = render_instructions_block(paths)
     {
-        full_prompt = format!("{full_prompt}\n\n{block}");
+        prompt_layers.push(PromptLayer::builtin(layers::INSTRUCTIONS, block));
     }
 
     // 6b. User memory block (#489). Placed below the volatile boundary
@@ -706,7 +772,10 @@ pub fn system_prompt_for_mode_with_context_skills_session_and_approval(
     if let Some(memory_block) = session_context.user_memory_block
         && !memory_block.trim().is_empty()
     {
-        full_prompt = format!("{full_prompt}\n\n{memory_block}\n\n{MEMORY_GUIDANCE}");
+        prompt_layers.push(PromptLayer::builtin(
+            layers::MEMORY,
+            format!("{memory_block}\n\n{MEMORY_GUIDANCE}"),
+        ));
     }
 
     // 6c. Current session goal. Also volatile: users set / change goals
@@ -715,15 +784,27 @@ pub fn system_prompt_for_mode_with_context_skills_session_and_approval(
     if let Some(goal_objective) = session_context.goal_objective
         && !goal_objective.trim().is_empty()
     {
-        full_prompt = format!(
-            "{full_prompt}\n\n## Current Session Goal\n\n<session_goal>\n{}\n</session_goal>",
-            goal_objective.trim()
-        );
+        prompt_layers.push(PromptLayer::builtin(
+            layers::GOAL,
+            format!(
+                "## Current Session Goal\n\n<session_goal>\n{}\n</session_goal>",
+                goal_objective.trim()
+            ),
+        ));
+    }
+
+    // 6d. Command lessons: shell commands that failed in this workspace and
+    // what worked instead. Rewritten whenever a command fails, so it lives
+    // below the boundary with the other volatile layers.
+    if let Some(lessons) = session_context.command_lessons_block
+        && !lessons.trim().is_empty()
+    {
+        prompt_layers.push(PromptLayer::builtin(layers::LESSONS, lessons));
     }
 
     // 7. Previous-session relay (file-backed, rewritten by `/compact`).
     if let Some(handoff_block) = load_handoff_block(workspace) {
-        full_prompt = format!("{full_prompt}\n\n{handoff_block}");
+        prompt_layers.push(PromptLayer::builtin(layers::HANDOFF, handoff_block));
     }
 
     // 7. Locale-native closing reinforcement (#1118 follow-up #2). The
@@ -738,10 +819,13 @@ pub fn system_prompt_for_mode_with_context_skills_session_and_approval(
     // turn. English (and unknown) locales return `None` and the prompt
     // stays byte-identical to the pre-bookend behavior.
     if let Some(closer) = locale_reinforcement_closer(session_context.locale_tag) {
-        full_prompt = format!("{full_prompt}\n\n{closer}");
+        prompt_layers.push(PromptLayer::builtin(layers::LOCALE_CLOSER, closer));
     }
 
-    SystemPrompt::Text(full_prompt)
+    match session_context.layer_overrides {
+        Some(overrides) => overrides.apply(prompt_layers),
+        None => prompt_layers,
+    }
 }
 
 /// Build a system prompt with explicit project context
@@ -877,10 +961,14 @@ mod tests {
             None,
             PromptSessionContext {
                 user_memory_block: None,
+                command_lessons_block: None,
                 goal_objective: None,
                 project_context_pack_enabled: false,
+                repo_map_tokens: None,
                 locale_tag: "zh-Hans",
                 translation_enabled: false,
+                self_report: false,
+                layer_overrides: None,
             },
             ApprovalMode::Suggest,
         ) {
@@ -946,10 +1034,14 @@ mod tests {
             None,
             PromptSessionContext {
                 user_memory_block: None,
+                command_lessons_block: None,
                 goal_objective: None,
                 project_context_pack_enabled: false,
+                repo_map_tokens: None,
                 locale_tag: "zh-Hans",
                 translation_enabled: false,
+                self_report: false,
+                layer_overrides: None,
             },
             ApprovalMode::Suggest,
         ) {
@@ -990,10 +1082,14 @@ mod tests {
             None,
             PromptSessionContext {
                 user_memory_block: None,
+                command_lessons_block: None,
                 goal_objective: None,
                 project_context_pack_enabled: false,
+                repo_map_tokens: None,
                 locale_tag: "en",
                 translation_enabled: false,
+                self_report: false,
+                layer_overrides: None,
             },
             ApprovalMode::Suggest,
         ) {
@@ -1079,10 +1175,14 @@ mod tests {
             None,
             PromptSessionContext {
                 user_memory_block: None,
+                command_lessons_block: None,
                 goal_objective: None,
                 project_context_pack_enabled: true,
+                repo_map_tokens: None,
                 locale_tag: "ja",
                 translation_enabled: false,
+                self_report: false,
+                layer_overrides: None,
             },
         ) {
             SystemPrompt::Text(text) => text,
@@ -1114,10 +1214,14 @@ mod tests {
             None,
             PromptSessionContext {
                 user_memory_block: None,
+                command_lessons_block: None,
                 goal_objective: None,
                 project_context_pack_enabled: false,
+                repo_map_tokens: None,
                 locale_tag: "en",
                 translation_enabled: false,
+                self_report: false,
+                layer_overrides: None,
             },
         ) {
             SystemPrompt::Text(text) => text,
@@ -1141,10 +1245,14 @@ mod tests {
             None,
             PromptSessionContext {
                 user_memory_block: Some(block),
+                command_lessons_block: None,
                 goal_objective: None,
                 project_context_pack_enabled: false,
+                repo_map_tokens: None,
                 locale_tag: "en",
                 translation_enabled: false,
+                self_report: false,
+                layer_overrides: None,
             },
         ) {
             SystemPrompt::Text(text) => text,
@@ -1170,10 +1278,14 @@ mod tests {
             None,
             PromptSessionContext {
                 user_memory_block: None,
+                command_lessons_block: None,
                 goal_objective: None,
                 project_context_pack_enabled: false,
+                repo_map_tokens: None,
                 locale_tag: "en",
                 translation_enabled: false,
+                self_report: false,
+                layer_overrides: None,
             },
         ) {
             SystemPrompt::Text(text) => text,
@@ -1197,10 +1309,14 @@ mod tests {
             None,
             PromptSessionContext {
                 user_memory_block: None,
+                command_lessons_block: None,
                 goal_objective: None,
                 project_context_pack_enabled: true,
+                repo_map_tokens: None,
                 locale_tag: "en",
                 translation_enabled: false,
+                self_report: false,
+                layer_overrides: None,
             },
         ) {
             SystemPrompt::Text(text) => text,
@@ -1391,10 +1507,14 @@ mod tests {
             None,
             PromptSessionContext {
                 user_memory_block: None,
+                command_lessons_block: None,
                 goal_objective: Some("Fix transcript corruption"),
                 project_context_pack_enabled: true,
+                repo_map_tokens: None,
                 locale_tag: "en",
                 translation_enabled: false,
+                self_report: false,
+                layer_overrides: None,
             },
         ) {
             SystemPrompt::Text(text) => text,
@@ -1413,6 +1533,33 @@ mod tests {
         assert!(!prompt.contains("src/lib.rs"));
     }
 
+    #[test]
+    fn self_report_layer_sits_in_the_static_prefix_when_enabled() {
+        let tmp = tempdir().expect("tempdir");
+        let render =
+            |self_report: bool| match system_prompt_for_mode_with_context_skills_and_session(
+                AppMode::Agent,
+                tmp.path(),
+                None,
+                None,
+                None,
+                PromptSessionContext {
+                    locale_tag: "en",
+                    self_report,
+                    ..PromptSessionContext::default()
+                },
+            ) {
+                SystemPrompt::Text(text) => text,
+                SystemPrompt::Blocks(_) => panic!("expected text system prompt"),
+            };
+
+        assert!(!render(false).contains("<self_report>"));
+        let prompt = render(true);
+        let footer_pos = prompt.find("## Self-Report").expect("self-report layer");
+        let compact_pos = prompt.find("## Compaction Relay").expect("compact block");
+        assert!(footer_pos < compact_pos);
+    }
+
     #[test]
     fn empty_session_goal_is_not_injected() {
         let tmp = tempdir().expect("tempdir");
@@ -1424,10 +1571,14 @@ mod tests {
             None,
             PromptSessionContext {
                 user_memory_block: None,
+                command_lessons_block: None,
                 goal_objective: Some("   "),
                 project_context_pack_enabled: true,
+                repo_map_tokens: None,
                 locale_tag: "en",
                 translation_enabled: false,
+                self_report: false,
+                layer_overrides: None,
             },
         ) {
             SystemPrompt::Text(text) => text,
@@ -0,0 +1,312 @@
+//! Named system prompt layers and user overrides.
+//!
+//! The system prompt is assembled as an ordered list of [`PromptLayer`]s
+//! (mode prompt, project context, skills, memory, ...) that are joined with
+//! blank lines. Naming every layer lets `[prompt_layers]` in
+//! `~/.deepseek/config.toml` disable, replace, reorder, or add layers, and
+//! lets `/system` show where the prompt's tokens come from.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+pub const LOCALE_PREAMBLE: &str = "locale_preamble";
+pub const BASE: &str = "base";
+pub const PROJECT_CONTEXT: &str = "project_context";
+pub const PROJECT_PACK: &str = "project_pack";
+pub const REPO_MAP: &str = "repo_map";
+pub const ENVIRONMENT: &str = "environment";
+pub const TRANSLATION: &str = "translation";
+pub const SELF_REPORT: &str = "self_report";
+pub const SKILLS: &str = "skills";
+pub const CONTEXT_MANAGEMENT: &str = "context_management";
+pub const COMPACT_TEMPLATE: &str = "compact_template";
+pub const INSTRUCTIONS: &str = "instructions";
+pub const MEMORY: &str = "memory";
+pub const GOAL: &str = "goal";
+pub const LESSONS: &str = "lessons";
+pub const HANDOFF: &str = "handoff";
+pub const LOCALE_CLOSER: &str = "locale_closer";
+
+/// Every built-in layer name in default order.
+pub const BUILTIN_LAYERS: &[&str] = &[
+    LOCALE_PREAMBLE,
+    BASE,
+    PROJECT_CONTEXT,
+    PROJECT_PACK,
+    REPO_MAP,
+    ENVIRONMENT,
+    TRANSLATION,
+    SELF_REPORT,
+    SKILLS,
+    CONTEXT_MANAGEMENT,
+    COMPACT_TEMPLATE,
+    INSTRUCTIONS,
+    MEMORY,
+    GOAL,
+    LESSONS,
+    HANDOFF,
+    LOCALE_CLOSER,
+];
+
+/// Where a layer's text came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayerOrigin {
+    Builtin,
+    /// Built-in layer whose text was replaced from a configured file.
+    Replaced,
+    /// User-defined layer from `[[prompt_layers.custom]]`.
+    Custom,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptLayer {
+    pub name: String,
+    pub text: String,
+    pub origin: LayerOrigin,
+    /// Disabled layers stay in the list so `/system` can report them, but
+    /// are skipped when rendering.
+    pub enabled: bool,
+}
+
+impl PromptLayer {
+    #[must_use]
+    pub fn builtin(name: &str, text: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            text: text.into(),
+            origin: LayerOrigin::Builtin,
+            enabled: true,
+        }
+    }
+}
+
+/// A user-defined layer. Exactly one of `text` / `file` is expected; `file`
+/// wins when both are set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CustomLayer {
+    pub name: String,
+    pub text: Option<String>,
+    pub file: Option<PathBuf>,
+}
+
+/// Resolved `[prompt_layers]` settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PromptLayerOverrides {
+    /// Layer names to leave out of the prompt.
+    pub disabled: Vec<String>,
+    /// Layer names to move to the front, in this order. Unlisted layers keep
+    /// their default relative order after the listed ones.
+    pub order: Vec<String>,
+    /// Replace a layer's text with the contents of a file.
+    pub replace: Vec<(String, PathBuf)>,
+    /// Extra layers, inserted after `compact_template` (the end of the
+    /// cache-stable prefix) unless `order` places them elsewhere.
+    pub custom: Vec<CustomLayer>,
+}
+
+impl PromptLayerOverrides {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.disabled.is_empty()
+            && self.order.is_empty()
+            && self.replace.is_empty()
+            && self.custom.is_empty()
+    }
+
+    /// Apply the overrides to the built-in layer list.
+    #[must_use]
+    pub fn apply(&self, mut layers: Vec<PromptLayer>) -> Vec<PromptLayer> {
+        if self.is_empty() {
+            return layers;
+        }
+
+        for (name, path) in &self.replace {
+            let Some(layer) = layers.iter_mut().find(|layer| &layer.name == name) else {
+                continue;
+            };
+            match std::fs::read_to_string(path) {
+                Ok(text) => {
+                    layer.text = text.trim().to_string();
+                    layer.origin = LayerOrigin::Replaced;
+                }
+                Err(err) => tracing::warn!(
+                    "prompt layer '{name}': cannot read replacement {}: {err}",
+                    path.display()
+                ),
+            }
+        }
+
+        let mut insert_at = layers
+            .iter()
+            .position(|layer| layer.name == COMPACT_TEMPLATE)
+            .map_or(layers.len(), |index| index + 1);
+        for custom in &self.custom {
+            if BUILTIN_LAYERS.contains(&custom.name.as_str())
+                || layers.iter().any(|layer| layer.name == custom.name)
+            {
+                tracing::warn!(
+                    "custom prompt layer '{}' duplicates an existing layer name; skipped",
+                    custom.name
+                );
+                continue;
+            }
+            let Some(text) = custom_layer_text(custom) else {
+                continue;
+            };
+            layers.insert(
+                insert_at,
+                PromptLayer {
+                    name: custom.name.clone(),
+                    text,
+                    origin: LayerOrigin::Custom,
+                    enabled: true,
+                },
+            );
+            insert_at += 1;
+        }
+
+        for layer in &mut layers {
+            if self.disabled.contains(&layer.name) {
+                layer.enabled = false;
+            }
+        }
+
+        if !self.order.is_empty() {
+            // Stable sort: listed layers by their position in `order`,
+            // everything else after them in default order.
+            layers.sort_by_key(|layer| {
+                self.order
+                    .iter()
+                    .position(|name| name == &layer.name)
+                    .unwrap_or(usize::MAX)
+            });
+        }
+        layers
+    }
+}
+
+fn custom_layer_text(custom: &CustomLayer) -> Option<String> {
+    let text = match &custom.file {
+        Some(path) => match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) => {
+                tracing::warn!(
+                    "custom prompt layer '{}': cannot read {}: {err}",
+                    custom.name,
+                    path.display()
+                );
+                return None;
+            }
+        },
+        None => custom.text.clone()?,
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Join the enabled layers into the final prompt text.
+#[must_use]
+pub fn render(layers: &[PromptLayer]) -> String {
+    layers
+        .iter()
+        .filter(|layer| layer.enabled)
+        .map(|layer| layer.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Per-layer size report shown by `/system`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptLayerSummary {
+    pub name: String,
+    pub origin: LayerOrigin,
+    pub enabled: bool,
+    /// Rough token estimate (~4 chars/token, the compaction heuristic).
+    pub tokens: usize,
+}
+
+#[must_use]
+pub fn summarize(layers: &[PromptLayer]) -> Vec<PromptLayerSummary> {
+    layers
+        .iter()
+        .map(|layer| PromptLayerSummary {
+            name: layer.name.clone(),
+            origin: layer.origin,
+            enabled: layer.enabled,
+            tokens: layer.text.chars().count().div_ceil(4),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<PromptLayer> {
+        vec![
+            PromptLayer::builtin(BASE, "base"),
+            PromptLayer::builtin(SKILLS, "skills"),
+            PromptLayer::builtin(COMPACT_TEMPLATE, "compact"),
+            PromptLayer::builtin(MEMORY, "memory"),
+        ]
+    }
+
+    #[test]
+    fn empty_overrides_render_layers_joined_by_blank_lines() {
+        let layers = PromptLayerOverrides::default().apply(sample());
+        assert_eq!(render(&layers), "base\n\nskills\n\ncompact\n\nmemory");
+    }
+
+    #[test]
+    fn disabled_layers_are_reported_but_not_rendered() {
+        let overrides = PromptLayerOverrides {
+            disabled: vec![SKILLS.to_string()],
+            ..Default::default()
+        };
+        let layers = overrides.apply(sample());
+        assert_eq!(render(&layers), "base\n\ncompact\n\nmemory");
+        let summary = summarize(&layers);
+        assert!(!summary[1].enabled);
+        assert_eq!(summary[1].name, SKILLS);
+    }
+
+    #[test]
+    fn replace_custom_and_order_compose() {
+        let tmp = tempfile::tempdir().unwrap();
+        let replacement = tmp.path().join("base.md");
+        std::fs::write(&replacement, "my base\n").unwrap();
+        let overrides = PromptLayerOverrides {
+            order: vec![MEMORY.to_string()],
+            replace: vec![(BASE.to_string(), replacement)],
+            custom: vec![CustomLayer {
+                name: "team".to_string(),
+                text: Some("team rules".to_string()),
+                file: None,
+            }],
+            ..Default::default()
+        };
+        let layers = overrides.apply(sample());
+        let names: Vec<_> = layers.iter().map(|layer| layer.name.as_str()).collect();
+        assert_eq!(names, [MEMORY, BASE, SKILLS, COMPACT_TEMPLATE, "team"]);
+        assert_eq!(layers[1].origin, LayerOrigin::Replaced);
+        assert_eq!(layers[1].text, "my base");
+        assert_eq!(layers[4].origin, LayerOrigin::Custom);
+    }
+
+    #[test]
+    fn custom_layer_cannot_shadow_builtin_name() {
+        let overrides = PromptLayerOverrides {
+            custom: vec![CustomLayer {
+                name: BASE.to_string(),
+                text: Some("shadow".to_string()),
+                file: None,
+            }],
+            ..Default::default()
+        };
+        let layers = overrides.apply(sample());
+        assert_eq!(layers.len(), 4);
+        assert_eq!(layers[0].text, "base");
+    }
+}
@@ -0,0 +1,632 @@
+//! Repository map: a condensed, token-budgeted outline of the workspace.
+//!
+//! In a large repo the model's first turn is mostly spent listing
+//! directories and grepping for entry points. The repo map front-loads that:
+//! top-level directories with file/LOC counts, then the most informative
+//! files with the public symbols they declare. It is injected into the
+//! system prompt as the `repo_map` layer, cut to `[context] repo_map_tokens`.
+//!
+//! Symbols come from per-language line patterns (Rust, Python, JS/TS, Go,
+//! Java/Kotlin, C/C++ headers) rather than full parsers. They only need to
+//! be good enough to point the model at the right file.
+//!
+//! Building walks the tree once (respecting `.gitignore`), so results are
+//! cached in memory and under `~/.deepseek/cache/repo_map/`, keyed on the
+//! workspace path and git `HEAD`. A new commit invalidates the map;
+//! uncommitted edits do not — `/repomap refresh` rebuilds on demand. The
+//! TUI also calls [`prewarm`] while idle so a rebuild after a commit does
+//! not land on the next turn.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::tools::large_output_router::estimate_tokens;
+
+/// Default token budget for the prompt block.
+pub const DEFAULT_REPO_MAP_TOKENS: usize = 1_500;
+
+const CACHE_VERSION: u32 = 1;
+const MAX_FILES: usize = 20_000;
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+const MAX_SYMBOLS_PER_FILE: usize = 12;
+const MAX_DIRECTORIES: usize = 24;
+const DIRECTORY_DEPTH: usize = 2;
+const OMITTED_NOTE_TOKENS: usize = 24;
+
+/// Condensed outline of one workspace at one `HEAD`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoMap {
+    pub version: u32,
+    /// Git `HEAD` the map was built at; `None` outside a git repo.
+    pub head: Option<String>,
+    pub total_files: usize,
+    pub total_loc: usize,
+    /// Per-directory totals, largest first.
+    pub directories: Vec<DirectoryStats>,
+    /// Source files, most informative first.
+    pub files: Vec<FileEntry>,
+    /// True when the walk stopped at [`MAX_FILES`].
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectoryStats {
+    pub path: String,
+    pub files: usize,
+    pub loc: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileEntry {
+    pub path: String,
+    pub loc: usize,
+    pub symbols: Vec<String>,
+}
+
+impl RepoMap {
+    #[must_use]
+    pub fn symbol_count(&self) -> usize {
+        self.files.iter().map(|file| file.symbols.len()).sum()
+    }
+
+    /// Render the map as a prompt block of at most roughly `budget_tokens`.
+    /// Directories come first; files are added in rank order until the
+    /// budget runs out.
+    #[must_use]
+    pub fn render(&self, budget_tokens: usize) -> String {
+        let head = self
+            .head
+            .as_deref()
+            .map(|head| head.chars().take(12).collect::<String>())
+            .unwrap_or_else(|| "none".to_string());
+        let header = format!(
+            "## Repository Map\n\n\
+             Condensed outline of this workspace (files, LOC, public symbols). \
+             Use it to pick files before searching; it may lag uncommitted edits.\n\n\
+             <repo_map head=\"{head}\" files=\"{}\" loc=\"{}\">\n",
+            self.total_files, self.total_loc
+        );
+        let footer = "</repo_map>";
+        let mut out = header;
+        // Reserve room for the closing tag and the "more files" note.
+        let mut used = estimate_tokens(&out) + estimate_tokens(footer) + OMITTED_NOTE_TOKENS;
+
+        if !self.directories.is_empty() {
+            let title = "Directories (files / LOC):\n";
+            used += estimate_tokens(title) + 1;
+            out.push_str(title);
+            for dir in &self.directories {
+                let line = format!("  {}/  {} / {}\n", dir.path, dir.files, dir.loc);
+                used += estimate_tokens(&line);
+                out.push_str(&line);
+            }
+            out.push('\n');
+        }
+
+        let title = "Key files:\n";
+        used += estimate_tokens(title);
+        out.push_str(title);
+        let mut shown = 0;
+        for file in &self.files {
+            let line = if file.symbols.is_empty() {
+                format!("  {} ({} LOC)\n", file.path, file.loc)
+            } else {
+                format!(
+                    "  {} ({} LOC): {}\n",
+                    file.path,
+                    file.loc,
+                    file.symbols.join(", ")
+                )
+            };
+            let cost = estimate_tokens(&line);
+            if used + cost > budget_tokens {
+                break;
+            }
+            used += cost;
+            out.push_str(&line);
+            shown += 1;
+        }
+        let omitted = self.files.len() - shown;
+        if omitted > 0 || self.truncated {
+            out.push_str(&format!(
+                "  … {omitted} more source files{} not shown; use file_search / grep_files.\n",
+                if self.truncated { " (walk capped)" } else { "" }
+            ));
+        }
+        out.push_str(footer);
+        out
+    }
+}
+
+/// Build a fresh map for `workspace`, stamped with `head`. Gives up with
+/// `None` once `cancel` is set.
+fn build(workspace: &Path, head: Option<String>, cancel: &AtomicBool) -> Option<RepoMap> {
+    let mut files = Vec::new();
+    let mut directories: BTreeMap<String, DirectoryStats> = BTreeMap::new();
+    let mut truncated = false;
+
+    let walker = ignore::WalkBuilder::new(workspace)
+        .hidden(true)
+        .git_ignore(true)
+        .git_global(false)
+        .require_git(false)
+        .build();
+    for entry in walker.flatten() {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        if !entry.file_type().is_some_and(|kind| kind.is_file()) {
+            continue;
+        }
+        let path = entry.path();
+        let Some(language) = Language::from_path(path) else {
+            continue;
+        };
+        if entry
+            .metadata()
+            .map_or(true, |meta| meta.len() > MAX_FILE_BYTES)
+        {
+            continue;
+        }
+        if files.len() >= MAX_FILES {
+            truncated = true;
+            break;
+        }
+        let Ok(text) = fs::read_to_string(path) else {
+            continue;
+        };
+        let Ok(relative) = path.strip_prefix(workspace) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        let loc = text.lines().filter(|line| !line.trim().is_empty()).count();
+
+        let dir = directory_key(&relative);
+        let stats = directories
+            .entry(dir.clone())
+            .or_insert_with(|| DirectoryStats {
+                path: dir,
+                files: 0,
+                loc: 0,
+            });
+        stats.files += 1;
+        stats.loc += loc;
+
+        files.push(FileEntry {
+            symbols: extract_symbols(language, &text),
+            path: relative,
+            loc,
+        });
+    }
+
+    let total_files = files.len();
+    let total_loc = files.iter().map(|file| file.loc).sum();
+    let mut directories: Vec<DirectoryStats> = directories.into_values().collect();
+    directories.sort_by(|a, b| b.loc.cmp(&a.loc).then_with(|| a.path.cmp(&b.path)));
+    directories.truncate(MAX_DIRECTORIES);
+    files.sort_by(|a, b| {
+        file_rank(b)
+            .cmp(&file_rank(a))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    Some(RepoMap {
+        version: CACHE_VERSION,
+        head,
+        total_files,
+        total_loc,
+        directories,
+        files,
+        truncated,
+    })
+}
+
+/// Prompt block for `workspace`, building or loading the cached map as
+/// needed. Returns `None` for an empty map (no recognised source files).
+#[must_use]
+pub fn prompt_block(workspace: &Path, budget_tokens: usize) -> Option<String> {
+    let map = load_or_build(workspace, false);
+    (map.total_files > 0).then(|| map.render(budget_tokens))
+}
+
+/// Return the map for `workspace` at its current `HEAD`. With `refresh`,
+/// ignore both caches and rebuild.
+pub fn load_or_build(workspace: &Path, refresh: bool) -> Arc<RepoMap> {
+    let head = git_head(workspace);
+    let never = AtomicBool::new(false);
+    load_or_build_at(
+        workspace,
+        head,
+        default_cache_dir().as_deref(),
+        refresh,
+        &never,
+    )
+    .expect("a build only stops early when cancelled")
+}
+
+/// Load or build the map for `workspace` at its current `HEAD` so the next
+/// prompt finds it cached. Returns false, caching nothing, when `cancel` is
+/// set before a needed build finishes.
+pub fn prewarm(workspace: &Path, cancel: &AtomicBool) -> bool {
+    let head = git_head(workspace);
+    load_or_build_at(
+        workspace,
+        head,
+        default_cache_dir().as_deref(),
+        false,
+        cancel,
+    )
+    .is_some()
+}
+
+fn load_or_build_at(
+    workspace: &Path,
+    head: Option<String>,
+    cache_dir: Option<&Path>,
+    refresh: bool,
+    cancel: &AtomicBool,
+) -> Option<Arc<RepoMap>> {
+    let key = workspace.to_path_buf();
+    if !refresh
+        && let Some(map) = memory_cache()
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(&key).cloned())
+        && map.head == head
+    {
+        return Some(map);
+    }
+
+    let cache_file = cache_dir.map(|dir| dir.join(cache_file_name(workspace)));
+    let from_disk = if refresh || head.is_none() {
+        None
+    } else {
+        cache_file
+            .as_deref()
+            .and_then(|path| read_cached(path, &head))
+    };
+    let map = match from_disk {
+        Some(map) => map,
+        None => {
+            let map = build(workspace, head, cancel)?;
+            if map.head.is_some()
+                && let Some(path) = cache_file.as_deref()
+            {
+                write_cached(path, &map);
+            }
+            map
+        }
+    };
+    let map = Arc::new(map);
+
+    if let Ok(mut cache) = memory_cache().lock() {
+        cache.insert(key, Arc::clone(&map));
+    }
+    Some(map)
+}
+
+fn memory_cache() -> &'static Mutex<HashMap<PathBuf, Arc<RepoMap>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<RepoMap>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn default_cache_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".deepseek").join("cache").join("repo_map"))
+}
+
+fn cache_file_name(workspace: &Path) -> String {
+    let digest = Sha256::digest(workspace.to_string_lossy().as_bytes());
+    let hex: String = digest.iter().take(8).map(|b| format!("{b:02x}")).collect();
+    format!("{hex}.json")
+}
+
+fn read_cached(path: &Path, head: &Option<String>) -> Option<RepoMap> {
+    let text = fs::read_to_string(path).ok()?;
+    let map: RepoMap = serde_json::from_str(&text).ok()?;
+    (map.version == CACHE_VERSION && &map.head == head).then_some(map)
+}
+
+fn write_cached(path: &Path, map: &RepoMap) {
+    let Some(parent) = path.parent() else {
+        return;
+    };
+    if let Err(err) = fs::create_dir_all(parent) {
+        tracing::debug!("repo map cache dir {}: {err}", parent.display());
+        return;
+    }
+    match serde_json::to_string(map) {
+        Ok(json) => {
+            if let Err(err) = fs::write(path, json) {
+                tracing::debug!("repo map cache write {}: {err}", path.display());
+            }
+        }
+        Err(err) => tracing::debug!("repo map cache serialize: {err}"),
+    }
+}
+
+fn git_head(workspace: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(workspace)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let head = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!head.is_empty()).then_some(head)
+}
+
+/// First `DIRECTORY_DEPTH` components of the file's parent, or `.` for
+/// files at the root.
+fn directory_key(relative: &str) -> String {
+    let parts: Vec<&str> = relative.split('/').collect();
+    if parts.len() <= 1 {
+        return ".".to_string();
+    }
+    let parents = &parts[..parts.len() - 1];
+    parents[..parents.len().min(DIRECTORY_DEPTH)].join("/")
+}
+
+/// Entry points first, then files that declare more public surface.
+fn file_rank(file: &FileEntry) -> usize {
+    let name = file.path.rsplit('/').next().unwrap_or(&file.path);
+    let entry_point = matches!(
+        name,
+        "main.rs"
+            | "lib.rs"
+            | "mod.rs"
+            | "main.py"
+            | "__init__.py"
+            | "index.ts"
+            | "index.tsx"
+            | "index.js"
+            | "main.go"
+            | "main.ts"
+    );
+    let depth_penalty = file.path.matches('/').count();
+    let loc_bonus = (file.loc.max(1).ilog2() as usize).min(12);
+    file.symbols.len() * 4 + loc_bonus + if entry_point { 40 } else { 0 }
+        - depth_penalty.min(loc_bonus)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    Rust,
+    Python,
+    Script,
+    Go,
+    Jvm,
+    CFamily,
+}
+
+impl Language {
+    fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        Some(match ext.as_str() {
+            "rs" => Self::Rust,
+            "py" => Self::Python,
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => Self::Script,
+            "go" => Self::Go,
+            "java" | "kt" | "kts" | "scala" => Self::Jvm,
+            "c" | "h" | "cc" | "cpp" | "hpp" | "hh" => Self::CFamily,
+            _ => return None,
+        })
+    }
+
+    fn patterns(self) -> &'static [Regex] {
+        static PATTERNS: OnceLock<HashMap<&'static str, Vec<Regex>>> = OnceLock::new();
+        let table = PATTERNS.get_or_init(|| {
+            let compile = |sources: &[&str]| {
+                sources
+                    .iter()
+                    .map(|source| Regex::new(source).expect("repo map pattern"))
+                    .collect::<Vec<_>>()
+            };
+            HashMap::from([
+                (
+                    "rust",
+                    compile(&[
+                        r"^pub(?:\([^)]*\))?\s+(?:async\s+)?(?:unsafe\s+)?(fn|struct|enum|trait|type|mod|const|static)\s+([A-Za-z_][A-Za-z0-9_]*)",
+                    ]),
+                ),
+                (
+                    "python",
+                    compile(&[r"^(?:async\s+)?(def|class)\s+([A-Za-z][A-Za-z0-9_]*)"]),
+                ),
+                (
+                    "script",
+                    compile(&[
+                        r"^export\s+(?:default\s+)?(?:async\s+)?(function|class|interface|type|enum|const)\s+([A-Za-z_$][A-Za-z0-9_$]*)",
+                    ]),
+                ),
+                (
+                    "go",
+                    compile(&[
+                        r"^(func)\s+(?:\([^)]*\)\s*)?([A-Z][A-Za-z0-9_]*)",
+                        r"^(type)\s+([A-Z][A-Za-z0-9_]*)",
+                    ]),
+                ),
+                (
+                    "jvm",
+                    compile(&[
+                        r"^\s*public\s+(?:(?:abstract|final|static|sealed)\s+)*(class|interface|enum|record)\s+([A-Za-z_][A-Za-z0-9_]*)",
+                        r"^(?:(?:data|sealed|abstract|open)\s+)*(class|interface|object|fun)\s+([A-Za-z_][A-Za-z0-9_]*)",
+                    ]),
+                ),
+                (
+                    "c",
+                    compile(&[
+                        r"^(struct|class|enum|namespace)\s+([A-Za-z_][A-Za-z0-9_]*)\s*[{:]",
+                        r"^typedef\s+(struct)\s+[A-Za-z0-9_]*\s*\{?.*?([A-Za-z_][A-Za-z0-9_]*)\s*;$",
+                    ]),
+                ),
+            ])
+        });
+        let key = match self {
+            Self::Rust => "rust",
+            Self::Python => "python",
+            Self::Script => "script",
+            Self::Go => "go",
+            Self::Jvm => "jvm",
+            Self::CFamily => "c",
+        };
+        table.get(key).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Top-level public declarations as `kind name`, in file order, capped at
+/// [`MAX_SYMBOLS_PER_FILE`].
+fn extract_symbols(language: Language, text: &str) -> Vec<String> {
+    let patterns = language.patterns();
+    let mut symbols = Vec::new();
+    for line in text.lines() {
+        for pattern in patterns {
+            if let Some(caps) = pattern.captures(line) {
+                let symbol = format!("{} {}", &caps[1], &caps[2]);
+                if !symbols.contains(&symbol) {
+                    symbols.push(symbol);
+                }
+                break;
+            }
+        }
+        if symbols.len() >= MAX_SYMBOLS_PER_FILE {
+            break;
+        }
+    }
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn extracts_public_symbols_per_language() {
+        let rust = "pub struct Engine;\nfn private() {}\npub(crate) async fn run() {}\n    pub fn method() {}\npub enum Mode {}\n";
+        assert_eq!(
+            extract_symbols(Language::Rust, rust),
+            vec!["struct Engine", "fn run", "enum Mode"]
+        );
+
+        let python =
+            "class Client:\n    def method(self): ...\ndef _helper(): ...\ndef main(): ...\n";
+        assert_eq!(
+            extract_symbols(Language::Python, python),
+            vec!["class Client", "def main"]
+        );
+
+        let script = "export default function App() {}\nexport const api = 1;\nconst hidden = 2;\nexport interface Props {}\n";
+        assert_eq!(
+            extract_symbols(Language::Script, script),
+            vec!["function App", "const api", "interface Props"]
+        );
+
+        let go = "func (s *Server) Serve() {}\nfunc helper() {}\ntype Config struct {}\n";
+        assert_eq!(
+            extract_symbols(Language::Go, go),
+            vec!["func Serve", "type Config"]
+        );
+    }
+
+    #[test]
+    fn build_counts_loc_and_respects_gitignore() {
+        let tmp = tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("target")).unwrap();
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        fs::write(root.join("src/lib.rs"), "pub fn a() {}\n\npub fn b() {}\n").unwrap();
+        fs::write(root.join("target/gen.rs"), "pub fn ignored() {}\n").unwrap();
+        fs::write(root.join("README.md"), "# not source\n").unwrap();
+
+        let map = build(root, None, &AtomicBool::new(false)).unwrap();
+        assert_eq!(map.total_files, 1);
+        assert_eq!(map.total_loc, 2);
+        assert_eq!(map.files[0].path, "src/lib.rs");
+        assert_eq!(map.files[0].symbols, vec!["fn a", "fn b"]);
+        assert_eq!(map.directories[0].path, "src");
+    }
+
+    #[test]
+    fn render_stays_within_budget_and_notes_omitted_files() {
+        let map = RepoMap {
+            version: CACHE_VERSION,
+            head: Some("0123456789abcdef".to_string()),
+            total_files: 200,
+            total_loc: 20_000,
+            directories: vec![DirectoryStats {
+                path: "src".to_string(),
+                files: 200,
+                loc: 20_000,
+            }],
+            files: (0..200)
+                .map(|n| FileEntry {
+                    path: format!("src/module_{n}.rs"),
+                    loc: 100,
+                    symbols: vec![format!("fn handler_{n}"), format!("struct State{n}")],
+                })
+                .collect(),
+            truncated: false,
+        };
+        let budget = 300;
+        let rendered = map.render(budget);
+        assert!(estimate_tokens(&rendered) <= budget);
+        assert!(rendered.contains("head=\"0123456789ab\""));
+        assert!(rendered.contains("src/module_0.rs"));
+        assert!(rendered.contains("more source files"));
+        assert!(rendered.ends_with("</repo_map>"));
+    }
+
+    #[test]
+    fn cache_is_keyed_on_head() {
+        let tmp = tempdir().unwrap();
+        let workspace = tmp.path().join("ws");
+        let cache_dir = tmp.path().join("cache");
+        fs::create_dir_all(&workspace).unwrap();
+        fs::write(workspace.join("main.py"), "def first(): ...\n").unwrap();
+
+        let never = AtomicBool::new(false);
+        let head_a = Some("aaaa".to_string());
+        let first =
+            load_or_build_at(&workspace, head_a.clone(), Some(&cache_dir), false, &never).unwrap();
+        assert_eq!(first.files[0].symbols, vec!["def first"]);
+
+        // Same HEAD: the stale cached map is reused even though the file changed.
+        fs::write(workspace.join("main.py"), "def second(): ...\n").unwrap();
+        let same =
+            load_or_build_at(&workspace, head_a.clone(), Some(&cache_dir), false, &never).unwrap();
+        assert_eq!(same.files[0].symbols, vec!["def first"]);
+
+        // A cancelled rebuild caches nothing; a new HEAD (or an explicit
+        // refresh) rebuilds.
+        let cancelled = AtomicBool::new(true);
+        let bbbb = Some("bbbb".to_string());
+        assert!(
+            load_or_build_at(
+                &workspace,
+                bbbb.clone(),
+                Some(&cache_dir),
+                false,
+                &cancelled
+            )
+            .is_none()
+        );
+        let moved = load_or_build_at(&workspace, bbbb, Some(&cache_dir), false, &never).unwrap();
+        assert_eq!(moved.files[0].symbols, vec!["def second"]);
+        let refreshed =
+            load_or_build_at(&workspace, head_a, Some(&cache_dir), true, &never).unwrap();
+        assert_eq!(refreshed.files[0].symbols, vec!["def second"]);
+        assert!(cache_dir.join(cache_file_name(&workspace)).exists());
+    }
+}
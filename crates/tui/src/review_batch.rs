@@ -0,0 +1,395 @@
+//! Multi-target reviews: `deepseek review --commits a..b,c..d` and
+//! `deepseek review --prs 12,15`.
+//!
+//! Each commit range or pull request is reviewed on its own, at most `jobs`
+//! at a time, with the structured JSON schema the `review` tool uses. The
+//! results are merged into one report: one section per target in the order
+//! given, issues within a section sorted error → warning → info, and an
+//! overall verdict that CI can gate on.
+
+use std::future::Future;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Result, bail};
+use futures_util::stream::{self, StreamExt};
+use serde::Serialize;
+
+use crate::tools::review::{ReviewIssue, ReviewOutput};
+use crate::utils::truncate_with_ellipsis;
+
+/// One thing to review.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewTarget {
+    /// A git revision range such as `main..feature` or `v1.0...HEAD`.
+    Range(String),
+    /// A GitHub pull request number, fetched with `gh pr diff`.
+    Pr(u32),
+}
+
+impl ReviewTarget {
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self {
+            ReviewTarget::Range(range) => range.clone(),
+            ReviewTarget::Pr(number) => format!("PR #{number}"),
+        }
+    }
+}
+
+/// Build the target list from `--commits` ranges and `--prs` numbers.
+pub fn parse_targets(ranges: &[String], prs: &[u32]) -> Result<Vec<ReviewTarget>> {
+    let mut targets = Vec::new();
+    for range in ranges.iter().map(|r| r.trim()).filter(|r| !r.is_empty()) {
+        let Some((from, to)) = range.split_once("..") else {
+            bail!("`{range}` is not a commit range; expected <from>..<to>");
+        };
+        let to = to.strip_prefix('.').unwrap_or(to);
+        if from.is_empty() || to.is_empty() || from.starts_with('-') || to.starts_with('-') {
+            bail!("`{range}` is not a commit range; expected <from>..<to>");
+        }
+        targets.push(ReviewTarget::Range(range.to_string()));
+    }
+    targets.extend(prs.iter().copied().map(ReviewTarget::Pr));
+    targets.dedup();
+    if targets.is_empty() {
+        bail!("No review targets given.");
+    }
+    Ok(targets)
+}
+
+/// Fetch the diff for one target, cut to `max_chars`.
+pub fn collect_target_diff(
+    target: &ReviewTarget,
+    repo: Option<&str>,
+    path: Option<&Path>,
+    max_chars: usize,
+) -> Result<String> {
+    let mut cmd = match target {
+        ReviewTarget::Range(range) => {
+            let mut cmd = Command::new("git");
+            cmd.arg("diff").arg(range);
+            if let Some(path) = path {
+                cmd.arg("--").arg(path);
+            }
+            cmd
+        }
+        ReviewTarget::Pr(number) => {
+            let mut cmd = Command::new("gh");
+            cmd.arg("pr").arg("diff").arg(number.to_string());
+            if let Some(repo) = repo {
+                cmd.arg("--repo").arg(repo);
+            }
+            cmd
+        }
+    };
+    let program = match target {
+        ReviewTarget::Range(_) => "git diff",
+        ReviewTarget::Pr(_) => "gh pr diff",
+    };
+    let output = cmd
+        .output()
+        .map_err(|e| anyhow::anyhow!("Failed to run {program}: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("{program} {} failed: {}", target.label(), stderr.trim());
+    }
+    let mut diff = String::from_utf8_lossy(&output.stdout).to_string();
+    if diff.len() > max_chars {
+        diff = truncate_with_ellipsis(&diff, max_chars, "\n...[truncated]\n");
+    }
+    Ok(diff)
+}
+
+/// Overall outcome of a batch review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// No findings above `info`.
+    Pass,
+    /// Warnings only.
+    Warn,
+    /// At least one error-severity finding, or a target that could not be
+    /// reviewed.
+    Fail,
+}
+
+impl Verdict {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Warn => "warn",
+            Verdict::Fail => "fail",
+        }
+    }
+}
+
+/// Review of one target. `review` is `None` when `error` is set.
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetReport {
+    pub target: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub review: Option<ReviewOutput>,
+}
+
+/// Merged report across all targets.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchReport {
+    pub verdict: Verdict,
+    pub targets: Vec<TargetReport>,
+}
+
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "error" => 0,
+        "warning" => 1,
+        _ => 2,
+    }
+}
+
+fn sort_issues(issues: &mut [ReviewIssue]) {
+    issues.sort_by_key(|issue| severity_rank(&issue.severity));
+}
+
+impl BatchReport {
+    /// Merge per-target outcomes, in target order.
+    #[must_use]
+    pub fn merge(results: Vec<(ReviewTarget, Result<ReviewOutput>)>) -> Self {
+        let mut verdict = Verdict::Pass;
+        let targets = results
+            .into_iter()
+            .map(|(target, result)| match result {
+                Ok(mut review) => {
+                    sort_issues(&mut review.issues);
+                    let target_verdict = match review.issues.first().map(|i| i.severity.as_str()) {
+                        Some("error") => Verdict::Fail,
+                        Some("warning") => Verdict::Warn,
+                        _ => Verdict::Pass,
+                    };
+                    verdict = verdict.max(target_verdict);
+                    TargetReport {
+                        target: target.label(),
+                        success: true,
+                        error: None,
+                        review: Some(review),
+                    }
+                }
+                Err(err) => {
+                    verdict = Verdict::Fail;
+                    TargetReport {
+                        target: target.label(),
+                        success: false,
+                        error: Some(format!("{err:#}")),
+                        review: None,
+                    }
+                }
+            })
+            .collect();
+        Self { verdict, targets }
+    }
+
+    /// Human-readable report: one section per target, then the verdict.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        for report in &self.targets {
+            out.push_str(&format!("## {}\n\n", report.target));
+            if let Some(err) = &report.error {
+                out.push_str(&format!("Review failed: {err}\n\n"));
+                continue;
+            }
+            let Some(review) = &report.review else {
+                continue;
+            };
+            if !review.summary.is_empty() {
+                out.push_str(&format!("{}\n\n", review.summary));
+            }
+            if review.issues.is_empty() {
+                out.push_str("No issues found.\n\n");
+            }
+            for issue in &review.issues {
+                let location = match (&issue.path, issue.line) {
+                    (Some(path), Some(line)) => format!(" ({path}:{line})"),
+                    (Some(path), None) => format!(" ({path})"),
+                    _ => String::new(),
+                };
+                out.push_str(&format!("- [{}] {}{location}", issue.severity, issue.title));
+                if !issue.description.is_empty() {
+                    out.push_str(&format!(" — {}", issue.description));
+                }
+                out.push('\n');
+            }
+            for suggestion in &review.suggestions {
+                if !suggestion.suggestion.is_empty() {
+                    out.push_str(&format!("- suggestion: {}\n", suggestion.suggestion));
+                }
+            }
+            if !review.overall_assessment.is_empty() {
+                out.push_str(&format!("\n{}\n", review.overall_assessment));
+            }
+            out.push('\n');
+        }
+        let (errors, warnings) = self.issue_counts();
+        out.push_str(&format!(
+            "Verdict: {} ({} target{}, {errors} error{}, {warnings} warning{})\n",
+            self.verdict.as_str(),
+            self.targets.len(),
+            if self.targets.len() == 1 { "" } else { "s" },
+            if errors == 1 { "" } else { "s" },
+            if warnings == 1 { "" } else { "s" },
+        ));
+        out
+    }
+
+    fn issue_counts(&self) -> (usize, usize) {
+        let issues = self
+            .targets
+            .iter()
+            .filter_map(|report| report.review.as_ref())
+            .flat_map(|review| review.issues.iter());
+        issues.fold((0, 0), |(errors, warnings), issue| {
+            match issue.severity.as_str() {
+                "error" => (errors + 1, warnings),
+                "warning" => (errors, warnings + 1),
+                _ => (errors, warnings),
+            }
+        })
+    }
+}
+
+/// Review every target with at most `jobs` reviews in flight. Results come
+/// back in target order regardless of completion order.
+pub async fn review_all<F, Fut>(
+    targets: Vec<ReviewTarget>,
+    jobs: usize,
+    review: F,
+) -> Vec<(ReviewTarget, Result<ReviewOutput>)>
+where
+    F: Fn(ReviewTarget) -> Fut,
+    Fut: Future<Output = Result<ReviewOutput>>,
+{
+    stream::iter(targets.into_iter().map(|target| {
+        let fut = review(target.clone());
+        async move { (target, fut.await) }
+    }))
+    .buffered(jobs.max(1))
+    .collect()
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    fn issue(severity: &str, title: &str) -> ReviewIssue {
+        ReviewIssue {
+            severity: severity.to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            path: Some("src/lib.rs".to_string()),
+            line: Some(3),
+        }
+    }
+
+    fn output(issues: Vec<ReviewIssue>) -> ReviewOutput {
+        ReviewOutput {
+            summary: String::new(),
+            issues,
+            suggestions: Vec::new(),
+            overall_assessment: String::new(),
+        }
+    }
+
+    #[test]
+    fn parses_ranges_and_prs_and_rejects_non_ranges() {
+        let targets = parse_targets(
+            &["main..feat".to_string(), " v1...HEAD ".to_string()],
+            &[12],
+        )
+        .unwrap();
+        assert_eq!(
+            targets,
+            vec![
+                ReviewTarget::Range("main..feat".to_string()),
+                ReviewTarget::Range("v1...HEAD".to_string()),
+                ReviewTarget::Pr(12),
+            ]
+        );
+        assert_eq!(targets[2].label(), "PR #12");
+        assert!(parse_targets(&["main".to_string()], &[]).is_err());
+        assert!(parse_targets(&["--output=x..y".to_string()], &[]).is_err());
+        assert!(parse_targets(&[], &[]).is_err());
+    }
+
+    #[test]
+    fn merge_orders_by_severity_and_sets_verdict() {
+        let report = BatchReport::merge(vec![
+            (
+                ReviewTarget::Range("a..b".to_string()),
+                Ok(output(vec![
+                    issue("info", "nit"),
+                    issue("warning", "slow"),
+                    issue("info", "style"),
+                ])),
+            ),
+            (ReviewTarget::Pr(7), Ok(output(Vec::new()))),
+        ]);
+        assert_eq!(report.verdict, Verdict::Warn);
+        let titles: Vec<_> = report.targets[0]
+            .review
+            .as_ref()
+            .unwrap()
+            .issues
+            .iter()
+            .map(|i| i.title.as_str())
+            .collect();
+        assert_eq!(titles, ["slow", "nit", "style"]);
+        let text = report.to_markdown();
+        assert!(text.contains("## a..b"), "{text}");
+        assert!(text.contains("- [warning] slow (src/lib.rs:3)"), "{text}");
+        assert!(text.contains("## PR #7\n\nNo issues found."), "{text}");
+        assert!(text.ends_with("Verdict: warn (2 targets, 0 errors, 1 warning)\n"));
+
+        let failed = BatchReport::merge(vec![(
+            ReviewTarget::Pr(9),
+            Err(anyhow::anyhow!("gh pr diff PR #9 failed")),
+        )]);
+        assert_eq!(failed.verdict, Verdict::Fail);
+        let json = serde_json::to_value(&failed).unwrap();
+        assert_eq!(json["verdict"], "fail");
+        assert_eq!(json["targets"][0]["success"], false);
+    }
+
+    #[tokio::test]
+    async fn review_all_bounds_concurrency_and_keeps_order() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let targets: Vec<_> = (1..=6).map(ReviewTarget::Pr).collect();
+        let results = review_all(targets, 2, |target| {
+            let in_flight = Arc::clone(&in_flight);
+            let peak = Arc::clone(&peak);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                let ReviewTarget::Pr(n) = target else {
+                    unreachable!()
+                };
+                tokio::time::sleep(Duration::from_millis(u64::from(7 - n) * 5)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(output(Vec::new()))
+            }
+        })
+        .await;
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        let order: Vec<_> = results.iter().map(|(t, _)| t.label()).collect();
+        assert_eq!(order[0], "PR #1");
+        assert_eq!(order[5], "PR #6");
+    }
+}
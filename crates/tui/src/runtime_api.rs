@@ -11,8 +11,9 @@ use std::time::Duration;
 
 use anyhow::{Context, Result, anyhow, bail};
 use async_stream::stream;
-use axum::extract::{Path, Query, Request, State};
-use axum::http::{HeaderValue, Method, StatusCode, header};
+use axum::body::{Body, Bytes};
+use axum::extract::{DefaultBodyLimit, Path, Query, Request, State};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode, header};
 use axum::middleware::{self, Next};
 use axum::response::sse::{Event as SseEvent, KeepAlive, Sse};
 use axum::response::{IntoResponse, Response};
@@ -26,12 +27,21 @@ use tokio::sync::Mutex;
 use tokio_util::sync::CancellationToken;
 use tower_http::cors::{Any, CorsLayer};
 
+mod access;
+mod openai_compat;
+pub(crate) mod share;
+mod tls;
+
+pub use access::DEFAULT_TURNS_PER_MINUTE;
+pub use tls::TlsSettings;
+
 use crate::automation_manager::{
     AutomationManager, AutomationRecord, AutomationRunRecord, AutomationSchedulerConfig,
     CreateAutomationRequest, SharedAutomationManager, UpdateAutomationRequest, spawn_scheduler,
 };
 use crate::config::{Config, DEFAULT_TEXT_MODEL};
 use crate::mcp::{McpConfig, McpPool};
+use crate::runtime_metrics::RuntimeGauges;
 use crate::runtime_threads::{
     CompactThreadRequest, CreateThreadRequest, ExternalApprovalDecision, RuntimeThreadManager,
     RuntimeThreadManagerConfig, SharedRuntimeThreadManager, StartTurnRequest, SteerTurnRequest,
@@ -56,8 +66,11 @@ pub struct RuntimeApiState {
     mcp_config_path: PathBuf,
     automations: SharedAutomationManager,
     runtime_token: Option<String>,
+    read_token: Option<String>,
+    turn_limiter: Option<Arc<access::TurnRateLimiter>>,
     skill_state: Arc<Mutex<SkillStateStore>>,
     auth_required: bool,
+    tls_enabled: bool,
     bind_host: String,
     bind_port: u16,
 }
@@ -78,6 +91,17 @@ pub struct RuntimeApiOptions {
     pub auth_token: Option<String>,
     /// Allow `/v1/*` routes without auth when no token is configured.
     pub insecure_no_auth: bool,
+    /// Optional bearer token limited to `GET`/`HEAD` routes. If omitted
+    /// here, `run_http_server` checks `DEEPSEEK_RUNTIME_READ_TOKEN`, then
+    /// `[runtime_api] read_only_token`.
+    pub read_token: Option<String>,
+    /// Where to write the control token for local clients. Falls back to
+    /// `[runtime_api] token_file`, then `~/.deepseek/runtime/token`.
+    pub token_file: Option<PathBuf>,
+    /// Serve HTTPS (optionally mTLS) instead of plain HTTP.
+    pub tls: Option<TlsSettings>,
+    /// Turn-creating requests allowed per minute; `0` disables the limit.
+    pub turns_per_minute: u32,
 }
 
 impl Default for RuntimeApiOptions {
@@ -89,6 +113,10 @@ impl Default for RuntimeApiOptions {
             cors_origins: Vec::new(),
             auth_token: None,
             insecure_no_auth: false,
+            read_token: None,
+            token_file: None,
+            tls: None,
+            turns_per_minute: DEFAULT_TURNS_PER_MINUTE,
         }
     }
 }
@@ -301,6 +329,8 @@ struct RuntimeInfoResponse {
     bind_host: String,
     port: u16,
     auth_required: bool,
+    read_only_token: bool,
+    tls: bool,
     version: &'static str,
 }
 
@@ -356,6 +386,39 @@ struct StartTurnResponse {
     turn: TurnRecord,
 }
 
+/// Largest request body accepted for one upload chunk. Clients split bigger
+/// files into several `POST /v1/workspace/files` calls.
+const MAX_UPLOAD_CHUNK_BYTES: usize = 8 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+struct ArtifactQuery {
+    /// Session that owns an `art_<id>` artifact. Optional: bare tool-call ids
+    /// and SHA refs resolve without it.
+    session_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UploadFileQuery {
+    /// Workspace-relative destination path.
+    path: String,
+    /// Byte offset this chunk starts at. `0` starts (or restarts) an upload.
+    #[serde(default)]
+    offset: u64,
+    /// Expected final size, checked against the limit up front and against
+    /// the received bytes on the last chunk.
+    total_size: Option<u64>,
+    /// Whether this is the last chunk. Defaults to `true` so a single-shot
+    /// upload needs no extra parameters.
+    complete: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+struct UploadFileResponse {
+    path: String,
+    bytes_received: u64,
+    complete: bool,
+}
+
 /// Start the runtime API server.
 pub async fn run_http_server(
     config: Config,
@@ -402,6 +465,24 @@ pub async fn run_http_server(
     );
     let runtime_token = resolved_auth.token.clone();
     let auth_enabled = runtime_token.is_some();
+    let read_token = if auth_enabled {
+        first_nonblank_token(options.read_token.clone())
+            .or_else(|| first_nonblank_token(std::env::var("DEEPSEEK_RUNTIME_READ_TOKEN").ok()))
+            .or_else(|| {
+                first_nonblank_token(
+                    config
+                        .runtime_api
+                        .as_ref()
+                        .and_then(|rt| rt.read_only_token.clone()),
+                )
+            })
+    } else {
+        None
+    };
+    if read_token.is_some() && read_token == runtime_token {
+        bail!("The read-only runtime token must differ from the control token");
+    }
+    let tls_config = options.tls.as_ref().map(tls::server_config).transpose()?;
     let skill_state = SkillStateStore::load_default().unwrap_or_else(|err| {
         tracing::warn!(
             "Failed to load skills_state.toml ({}); treating all skills as enabled",
@@ -419,8 +500,11 @@ pub async fn run_http_server(
         mcp_config_path: config.mcp_config_path(),
         automations,
         runtime_token: runtime_token.clone(),
+        read_token: read_token.clone(),
+        turn_limiter: access::TurnRateLimiter::new(options.turns_per_minute).map(Arc::new),
         skill_state: Arc::new(Mutex::new(skill_state)),
         auth_required: auth_enabled,
+        tls_enabled: tls_config.is_some(),
         bind_host: options.host.clone(),
         bind_port: options.port,
     };
@@ -433,7 +517,12 @@ pub async fn run_http_server(
         .await
         .with_context(|| format!("Failed to bind {addr}"))?;
 
-    println!("Runtime API listening on http://{addr}");
+    let scheme = if tls_config.is_some() {
+        "https"
+    } else {
+        "http"
+    };
+    println!("Runtime API listening on {scheme}://{addr}");
     if resolved_auth.generated {
         if let Some(token) = runtime_token.as_deref() {
             println!("Runtime API auth: generated bearer token for this process.");
@@ -445,6 +534,44 @@ pub async fn run_http_server(
     } else {
         println!("Runtime API auth: disabled by explicit insecure mode.");
     }
+    if read_token.is_some() {
+        println!("  A read-only token is configured; it grants GET/HEAD routes only.");
+    }
+    let token_file = options
+        .token_file
+        .clone()
+        .or_else(|| config.runtime_api.as_ref().and_then(|rt| rt.token_file()))
+        .or_else(access::default_token_file);
+    let written_token_file = match (runtime_token.as_deref(), token_file) {
+        (Some(token), Some(path)) => match access::write_token_file(&path, token) {
+            Ok(()) => {
+                println!("  Token written to {}", path.display());
+                Some(path)
+            }
+            Err(err) => {
+                tracing::warn!("{err:#}");
+                None
+            }
+        },
+        _ => None,
+    };
+    if options.turns_per_minute > 0 {
+        println!(
+            "  Turn-creating routes are limited to {} request(s) per minute.",
+            options.turns_per_minute
+        );
+    }
+    if let Some(tls) = options.tls.as_ref() {
+        println!(
+            "Runtime API TLS: certificate {}{}",
+            tls.cert.display(),
+            if tls.client_ca.is_some() {
+                "; client certificates required (mTLS)"
+            } else {
+                ""
+            }
+        );
+    }
     let is_loopback = options.host == "127.0.0.1" || options.host == "::1";
     if is_loopback {
         println!("Security: this server is local-first. Do not expose it to untrusted networks.");
@@ -465,9 +592,14 @@ pub async fn run_http_server(
             auth = auth_enabled,
         );
     }
-    let serve_result = axum::serve(listener, app)
-        .await
-        .map_err(|e| anyhow!("Runtime API server error: {e}"));
+    let serve_result = match tls_config {
+        Some(tls_config) => axum::serve(tls::TlsListener::new(listener, tls_config)?, app).await,
+        None => axum::serve(listener, app).await,
+    }
+    .map_err(|e| anyhow!("Runtime API server error: {e}"));
+    if let Some(path) = written_token_file {
+        let _ = fs::remove_file(path);
+    }
     scheduler_cancel.cancel();
     scheduler_handle.abort();
     serve_result
@@ -482,7 +614,16 @@ pub fn build_router(state: RuntimeApiState) -> Router {
             post(resume_session_thread),
         )
         .route("/v1/workspace/status", get(workspace_status))
+        .route(
+            "/v1/workspace/files",
+            post(upload_workspace_file).layer(DefaultBodyLimit::max(MAX_UPLOAD_CHUNK_BYTES)),
+        )
+        .route("/v1/artifacts/{id}", get(get_artifact))
         .route("/v1/stream", post(stream_turn))
+        .route(
+            "/v1/chat/completions",
+            post(openai_compat::chat_completions),
+        )
         .route("/v1/threads", get(list_threads).post(create_thread))
         .route("/v1/threads/summary", get(list_threads_summary))
         .route("/v1/threads/{id}", get(get_thread).patch(update_thread))
@@ -522,6 +663,11 @@ pub fn build_router(state: RuntimeApiState) -> Router {
         .route("/v1/automations/{id}/resume", post(resume_automation))
         .route("/v1/automations/{id}/runs", get(list_automation_runs))
         .route("/v1/usage", get(get_usage))
+        .route("/metrics", get(prometheus_metrics))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            limit_turn_creation,
+        ))
         .route_layer(middleware::from_fn_with_state(
             state.clone(),
             require_runtime_token,
@@ -543,35 +689,60 @@ async fn require_runtime_token(
     let Some(expected) = state.runtime_token.as_deref() else {
         return next.run(req).await;
     };
-    let authorized = req
-        .headers()
-        .get(header::AUTHORIZATION)
-        .and_then(|value| value.to_str().ok())
-        .and_then(|raw| raw.strip_prefix("Bearer "))
-        .is_some_and(|token| token == expected)
-        || req
-            .headers()
-            .get("x-deepseek-runtime-token")
+    let read_only = state.read_token.as_deref();
+    let headers = req.headers();
+    let presented = [
+        headers
+            .get(header::AUTHORIZATION)
             .and_then(|value| value.to_str().ok())
-            .is_some_and(|token| token == expected)
-        || token_from_query(req.uri().query()).is_some_and(|token| token == expected);
+            .and_then(|raw| raw.strip_prefix("Bearer ")),
+        headers
+            .get("x-deepseek-runtime-token")
+            .and_then(|value| value.to_str().ok()),
+        token_from_query(req.uri().query()),
+    ];
+    let scope = presented
+        .into_iter()
+        .flatten()
+        .filter_map(|token| access::scope_for_token(token, expected, read_only))
+        .max_by_key(|scope| *scope == access::Scope::Control);
 
-    if authorized {
-        next.run(req).await
-    } else {
-        (
-            StatusCode::UNAUTHORIZED,
-            Json(json!({
-                "error": {
-                    "message": "runtime API bearer token required",
-                    "status": StatusCode::UNAUTHORIZED.as_u16(),
-                }
-            })),
-        )
-            .into_response()
+    match (scope, access::Scope::required_for(req.method())) {
+        (Some(access::Scope::Control), _) | (Some(access::Scope::Read), access::Scope::Read) => {
+            next.run(req).await
+        }
+        (Some(access::Scope::Read), access::Scope::Control) => ApiError {
+            status: StatusCode::FORBIDDEN,
+            message: "runtime API token is read-only".to_string(),
+        }
+        .into_response(),
+        (None, _) => ApiError::unauthorized("runtime API bearer token required").into_response(),
     }
 }
 
+async fn limit_turn_creation(
+    State(state): State<RuntimeApiState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if let Some(limiter) = state.turn_limiter.as_deref()
+        && access::creates_turn(req.method(), req.uri().path())
+        && let Err(wait) = limiter.check(std::time::Instant::now())
+    {
+        let retry_after = wait.as_secs().max(1);
+        let mut response = ApiError {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            message: format!("turn rate limit reached; retry in {retry_after}s"),
+        }
+        .into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        return response;
+    }
+    next.run(req).await
+}
+
 fn token_from_query(query: Option<&str>) -> Option<&str> {
     query.and_then(|query| {
         query.split('&').find_map(|pair| {
@@ -902,6 +1073,290 @@ async fn workspace_status(
     Ok(Json(collect_workspace_status(&state.workspace)))
 }
 
+async fn get_artifact(
+    Path(id): Path<String>,
+    Query(query): Query<ArtifactQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    if !is_valid_artifact_ref(&id) {
+        return Err(ApiError::bad_request(format!("invalid artifact id '{id}'")));
+    }
+    let session_id = query.session_id.unwrap_or_default();
+    let artifact_path =
+        crate::tools::tool_result_retrieval::resolve_spillover_reference(&id, &session_id)
+            .map_err(|_| ApiError::not_found(format!("artifact '{id}' not found")))?;
+    let mut file = tokio::fs::File::open(&artifact_path)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to open artifact: {e}")))?;
+    let len = file
+        .metadata()
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to stat artifact: {e}")))?
+        .len();
+
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .map(|raw| parse_byte_range(raw, len));
+    let (status, start, end) = match range {
+        None | Some(ByteRange::Ignored) => (StatusCode::OK, 0, len),
+        Some(ByteRange::Satisfiable { start, end }) => (StatusCode::PARTIAL_CONTENT, start, end),
+        Some(ByteRange::Unsatisfiable) => {
+            return Ok((
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                [(header::CONTENT_RANGE, format!("bytes */{len}"))],
+            )
+                .into_response());
+        }
+    };
+
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+    file.seek(std::io::SeekFrom::Start(start))
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to seek artifact: {e}")))?;
+    let body = Body::from_stream(tokio_util::io::ReaderStream::new(file.take(end - start)));
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    let response_headers = response.headers_mut();
+    response_headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("text/plain; charset=utf-8"),
+    );
+    response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    response_headers.insert(header::CONTENT_LENGTH, HeaderValue::from(end - start));
+    if status == StatusCode::PARTIAL_CONTENT
+        && let Ok(value) =
+            HeaderValue::from_str(&format!("bytes {start}-{}/{len}", end.saturating_sub(1)))
+    {
+        response_headers.insert(header::CONTENT_RANGE, value);
+    }
+    Ok(response)
+}
+
+/// Artifact ids travel as one URL path segment. Allow the characters the
+/// retrieval resolver understands (`call_…`, `art_…`, `sha:…`, `….txt`) and
+/// nothing that could walk directories.
+fn is_valid_artifact_ref(id: &str) -> bool {
+    !id.is_empty()
+        && !id.contains("..")
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ByteRange {
+    /// Half-open `[start, end)` byte span within the file.
+    Satisfiable {
+        start: u64,
+        end: u64,
+    },
+    Unsatisfiable,
+    /// Malformed or multi-range header; RFC 9110 lets us serve the whole body.
+    Ignored,
+}
+
+fn parse_byte_range(raw: &str, len: u64) -> ByteRange {
+    let Some(spec) = raw.trim().strip_prefix("bytes=") else {
+        return ByteRange::Ignored;
+    };
+    if spec.contains(',') {
+        return ByteRange::Ignored;
+    }
+    let Some((first, last)) = spec.trim().split_once('-') else {
+        return ByteRange::Ignored;
+    };
+    let (first, last) = (first.trim(), last.trim());
+    let (start, end) = if first.is_empty() {
+        // `bytes=-N`: the final N bytes.
+        let Ok(suffix) = last.parse::<u64>() else {
+            return ByteRange::Ignored;
+        };
+        if suffix == 0 {
+            return ByteRange::Unsatisfiable;
+        }
+        (len.saturating_sub(suffix), len)
+    } else {
+        let Ok(start) = first.parse::<u64>() else {
+            return ByteRange::Ignored;
+        };
+        let end = if last.is_empty() {
+            len
+        } else {
+            match last.parse::<u64>() {
+                Ok(last) if last >= start => last.saturating_add(1).min(len),
+                _ => return ByteRange::Ignored,
+            }
+        };
+        (start, end)
+    };
+    if start >= len {
+        return ByteRange::Unsatisfiable;
+    }
+    ByteRange::Satisfiable { start, end }
+}
+
+async fn upload_workspace_file(
+    State(state): State<RuntimeApiState>,
+    Query(query): Query<UploadFileQuery>,
+    body: Bytes,
+) -> Result<Json<UploadFileResponse>, ApiError> {
+    let limit = state
+        .config
+        .runtime_api
+        .as_ref()
+        .map_or(crate::config::DEFAULT_RUNTIME_MAX_UPLOAD_BYTES, |cfg| {
+            cfg.max_upload_bytes()
+        });
+    if let Some(total) = query.total_size
+        && total > limit
+    {
+        return Err(ApiError::payload_too_large(format!(
+            "upload of {total} bytes exceeds the {limit}-byte limit"
+        )));
+    }
+    let received = query.offset.saturating_add(body.len() as u64);
+    if received > limit {
+        return Err(ApiError::payload_too_large(format!(
+            "upload exceeds the {limit}-byte limit"
+        )));
+    }
+
+    let target = resolve_upload_target(&state.workspace, &query.path)?;
+    let complete = query.complete.unwrap_or(true);
+    let offset = query.offset;
+    let expected_total = query.total_size;
+    let relative = query.path.trim().replace('\\', "/");
+    tokio::task::spawn_blocking(move || {
+        write_upload_chunk(&target, offset, &body, complete, expected_total)
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("Upload task failed: {e}")))??;
+
+    Ok(Json(UploadFileResponse {
+        path: relative,
+        bytes_received: received,
+        complete,
+    }))
+}
+
+/// Resolve a client-supplied upload path inside the workspace. Rejects
+/// absolute paths, `..`, writes into `.git/`, and destinations whose nearest
+/// existing ancestor resolves (through symlinks) outside the workspace.
+fn resolve_upload_target(workspace: &std::path::Path, raw: &str) -> Result<PathBuf, ApiError> {
+    use std::path::Component;
+
+    let raw = raw.trim();
+    let relative = std::path::Path::new(raw);
+    if raw.is_empty() || relative.is_absolute() {
+        return Err(ApiError::bad_request(
+            "upload path must be relative to the workspace",
+        ));
+    }
+    let mut normal = PathBuf::new();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => normal.push(part),
+            Component::CurDir => {}
+            _ => {
+                return Err(ApiError::bad_request(format!(
+                    "upload path '{raw}' escapes the workspace"
+                )));
+            }
+        }
+    }
+    if normal.file_name().is_none() || raw.ends_with(['/', '\\']) {
+        return Err(ApiError::bad_request("upload path must name a file"));
+    }
+    if normal
+        .components()
+        .next()
+        .is_some_and(|first| first.as_os_str() == ".git")
+    {
+        return Err(ApiError::bad_request("uploads into .git/ are not allowed"));
+    }
+
+    let workspace = workspace
+        .canonicalize()
+        .map_err(|e| ApiError::internal(format!("Failed to resolve workspace: {e}")))?;
+    let target = workspace.join(&normal);
+    if let Ok(meta) = fs::symlink_metadata(&target)
+        && (meta.file_type().is_symlink() || meta.is_dir())
+    {
+        return Err(ApiError::bad_request(format!(
+            "upload path '{raw}' is not a regular file"
+        )));
+    }
+    let existing_ancestor = target
+        .ancestors()
+        .skip(1)
+        .find(|ancestor| ancestor.exists())
+        .and_then(|ancestor| ancestor.canonicalize().ok());
+    if !existing_ancestor.is_some_and(|ancestor| ancestor.starts_with(&workspace)) {
+        return Err(ApiError::bad_request(format!(
+            "upload path '{raw}' escapes the workspace"
+        )));
+    }
+    Ok(target)
+}
+
+/// Staging file for an in-flight upload: a hidden sibling of the target so
+/// the final rename stays on one filesystem.
+fn upload_staging_path(target: &std::path::Path) -> PathBuf {
+    let name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    target.with_file_name(format!(".{name}.upload"))
+}
+
+fn write_upload_chunk(
+    target: &std::path::Path,
+    offset: u64,
+    chunk: &[u8],
+    complete: bool,
+    expected_total: Option<u64>,
+) -> Result<(), ApiError> {
+    use std::io::Write;
+
+    let staging = upload_staging_path(target);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| ApiError::internal(format!("Failed to create directory: {e}")))?;
+    }
+    let mut file = if offset == 0 {
+        fs::File::create(&staging)
+    } else {
+        let staged = fs::metadata(&staging).map(|meta| meta.len()).unwrap_or(0);
+        if staged != offset {
+            return Err(ApiError::conflict(format!(
+                "upload offset {offset} does not match the {staged} bytes received so far"
+            )));
+        }
+        fs::OpenOptions::new().append(true).open(&staging)
+    }
+    .map_err(|e| ApiError::internal(format!("Failed to open upload staging file: {e}")))?;
+    file.write_all(chunk)
+        .and_then(|()| file.sync_all())
+        .map_err(|e| ApiError::internal(format!("Failed to write upload chunk: {e}")))?;
+    drop(file);
+
+    if !complete {
+        return Ok(());
+    }
+    let written = offset + chunk.len() as u64;
+    if let Some(expected) = expected_total
+        && expected != written
+    {
+        let _ = fs::remove_file(&staging);
+        return Err(ApiError::bad_request(format!(
+            "upload finished with {written} bytes but total_size was {expected}"
+        )));
+    }
+    fs::rename(&staging, target)
+        .map_err(|e| ApiError::internal(format!("Failed to finalize upload: {e}")))
+}
+
 async fn list_skills(
     State(state): State<RuntimeApiState>,
 ) -> Result<Json<SkillsResponse>, ApiError> {
@@ -989,10 +1444,31 @@ async fn runtime_info(State(state): State<RuntimeApiState>) -> Json<RuntimeInfoR
         bind_host: state.bind_host.clone(),
         port: state.bind_port,
         auth_required: state.auth_required,
+        read_only_token: state.read_token.is_some(),
+        tls: state.tls_enabled,
         version: env!("CARGO_PKG_VERSION"),
     })
 }
 
+async fn prometheus_metrics(State(state): State<RuntimeApiState>) -> Response {
+    let (active_sessions, turns_in_progress) = state.runtime_threads.activity_counts().await;
+    let tasks = state.task_manager.counts().await;
+    let body = state.runtime_threads.metrics().render(RuntimeGauges {
+        active_sessions,
+        turns_in_progress,
+        queued_tasks: tasks.queued,
+        running_tasks: tasks.running,
+    });
+    (
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/plain; version=0.0.4; charset=utf-8"),
+        )],
+        body,
+    )
+        .into_response()
+}
+
 async fn list_mcp_servers(
     State(state): State<RuntimeApiState>,
 ) -> Result<Json<McpServersResponse>, ApiError> {
@@ -1832,6 +2308,13 @@ impl ApiError {
         }
     }
 
+    fn unauthorized(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::UNAUTHORIZED,
+            message: message.into(),
+        }
+    }
+
     fn not_found(message: impl Into<String>) -> Self {
         Self {
             status: StatusCode::NOT_FOUND,
@@ -1845,6 +2328,27 @@ impl ApiError {
             message: message.into(),
         }
     }
+
+    fn conflict(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::CONFLICT,
+            message: message.into(),
+        }
+    }
+
+    fn payload_too_large(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::PAYLOAD_TOO_LARGE,
+            message: message.into(),
+        }
+    }
+
+    fn bad_gateway(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_GATEWAY,
+            message: message.into(),
+        }
+    }
 }
 
 impl IntoResponse for ApiError {
@@ -1973,6 +2477,38 @@ mod tests {
             SharedRuntimeThreadManager,
             tokio::task::JoinHandle<()>,
         )>,
+    > {
+        spawn_test_server_in_workspace(root, sessions_dir, runtime_token, PathBuf::from(".")).await
+    }
+
+    async fn spawn_test_server_in_workspace(
+        root: PathBuf,
+        sessions_dir: PathBuf,
+        runtime_token: Option<String>,
+        workspace: PathBuf,
+    ) -> Result<
+        Option<(
+            SocketAddr,
+            SharedRuntimeThreadManager,
+            tokio::task::JoinHandle<()>,
+        )>,
+    > {
+        spawn_test_server_with_access(root, sessions_dir, runtime_token, workspace, None, 0).await
+    }
+
+    async fn spawn_test_server_with_access(
+        root: PathBuf,
+        sessions_dir: PathBuf,
+        runtime_token: Option<String>,
+        workspace: PathBuf,
+        read_token: Option<String>,
+        turns_per_minute: u32,
+    ) -> Result<
+        Option<(
+            SocketAddr,
+            SharedRuntimeThreadManager,
+            tokio::task::JoinHandle<()>,
+        )>,
     > {
         fs::create_dir_all(&sessions_dir)?;
         let manager = TaskManager::start_with_executor(
@@ -2021,7 +2557,7 @@ mod tests {
         let auth_required = runtime_token.is_some();
         let state = RuntimeApiState {
             config: Config::default(),
-            workspace: PathBuf::from("."),
+            workspace,
             task_manager: manager,
             runtime_threads: runtime_threads.clone(),
             cors_origins: Vec::new(),
@@ -2029,10 +2565,13 @@ mod tests {
             mcp_config_path: root.join("mcp.json"),
             automations,
             runtime_token,
+            read_token,
+            turn_limiter: access::TurnRateLimiter::new(turns_per_minute).map(Arc::new),
             skill_state: Arc::new(Mutex::new(
                 SkillStateStore::load_from(root.join("skills_state.toml")).unwrap_or_default(),
             )),
             auth_required,
+            tls_enabled: false,
             bind_host: "127.0.0.1".to_string(),
             bind_port: 0,
         };
@@ -2242,6 +2781,89 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn read_only_token_cannot_call_control_routes() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("deepseek-runtime-api-{}", Uuid::new_v4()));
+        let sessions_dir = root.join("sessions");
+        let Some((addr, _runtime_threads, handle)) = spawn_test_server_with_access(
+            root,
+            sessions_dir,
+            Some("control-token".to_string()),
+            PathBuf::from("."),
+            Some("read-token".to_string()),
+            0,
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+        let client = reqwest::Client::new();
+
+        let read = client
+            .get(format!("http://{addr}/v1/threads/summary"))
+            .bearer_auth("read-token")
+            .send()
+            .await?;
+        assert_eq!(read.status(), StatusCode::OK);
+
+        let write = client
+            .post(format!("http://{addr}/v1/threads"))
+            .bearer_auth("read-token")
+            .json(&json!({}))
+            .send()
+            .await?;
+        assert_eq!(write.status(), StatusCode::FORBIDDEN);
+
+        let control = client
+            .post(format!("http://{addr}/v1/threads"))
+            .bearer_auth("control-token")
+            .json(&json!({}))
+            .send()
+            .await?;
+        assert_ne!(control.status(), StatusCode::FORBIDDEN);
+        assert_ne!(control.status(), StatusCode::UNAUTHORIZED);
+
+        handle.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn turn_creation_is_rate_limited() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("deepseek-runtime-api-{}", Uuid::new_v4()));
+        let sessions_dir = root.join("sessions");
+        let Some((addr, _runtime_threads, handle)) =
+            spawn_test_server_with_access(root, sessions_dir, None, PathBuf::from("."), None, 1)
+                .await?
+        else {
+            return Ok(());
+        };
+        let client = reqwest::Client::new();
+
+        let first = client
+            .post(format!("http://{addr}/v1/threads/missing/turns"))
+            .json(&json!({ "prompt": "hi" }))
+            .send()
+            .await?;
+        assert_ne!(first.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let second = client
+            .post(format!("http://{addr}/v1/threads/missing/turns"))
+            .json(&json!({ "prompt": "hi" }))
+            .send()
+            .await?;
+        assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(second.headers().contains_key(header::RETRY_AFTER));
+
+        let listing = client
+            .get(format!("http://{addr}/v1/threads"))
+            .send()
+            .await?;
+        assert_eq!(listing.status(), StatusCode::OK);
+
+        handle.abort();
+        Ok(())
+    }
+
     #[tokio::test]
     async fn workspace_and_automation_endpoints_work() -> Result<()> {
         let Some((addr, _runtime_threads, handle)) = spawn_test_server().await? else {
@@ -3126,6 +3748,227 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn parse_byte_range_handles_open_suffix_and_invalid_specs() {
+        assert_eq!(
+            parse_byte_range("bytes=0-9", 100),
+            ByteRange::Satisfiable { start: 0, end: 10 }
+        );
+        assert_eq!(
+            parse_byte_range("bytes=90-", 100),
+            ByteRange::Satisfiable {
+                start: 90,
+                end: 100
+            }
+        );
+        assert_eq!(
+            parse_byte_range("bytes=-5", 100),
+            ByteRange::Satisfiable {
+                start: 95,
+                end: 100
+            }
+        );
+        assert_eq!(
+            parse_byte_range("bytes=50-500", 100),
+            ByteRange::Satisfiable {
+                start: 50,
+                end: 100
+            }
+        );
+        assert_eq!(
+            parse_byte_range("bytes=100-", 100),
+            ByteRange::Unsatisfiable
+        );
+        assert_eq!(parse_byte_range("bytes=0-1,5-6", 100), ByteRange::Ignored);
+        assert_eq!(parse_byte_range("items=0-1", 100), ByteRange::Ignored);
+        assert_eq!(parse_byte_range("bytes=9-3", 100), ByteRange::Ignored);
+    }
+
+    #[test]
+    fn upload_target_rejects_paths_outside_workspace() {
+        let workspace =
+            std::env::temp_dir().join(format!("deepseek-upload-sandbox-{}", Uuid::new_v4()));
+        fs::create_dir_all(&workspace).expect("workspace");
+
+        let ok = resolve_upload_target(&workspace, "docs/new/report.pdf").expect("nested path");
+        assert!(ok.ends_with("docs/new/report.pdf"));
+        for bad in [
+            "",
+            "/etc/passwd",
+            "../outside.txt",
+            "a/../../b",
+            ".git/config",
+            "docs/",
+        ] {
+            assert!(
+                resolve_upload_target(&workspace, bad).is_err(),
+                "{bad:?} should be rejected"
+            );
+        }
+        #[cfg(unix)]
+        {
+            let outside =
+                std::env::temp_dir().join(format!("deepseek-upload-outside-{}", Uuid::new_v4()));
+            fs::create_dir_all(&outside).expect("outside");
+            std::os::unix::fs::symlink(&outside, workspace.join("link")).expect("symlink");
+            assert!(resolve_upload_target(&workspace, "link/escape.txt").is_err());
+            let _ = fs::remove_dir_all(&outside);
+        }
+        let _ = fs::remove_dir_all(&workspace);
+    }
+
+    #[tokio::test]
+    async fn workspace_upload_accepts_chunks_and_enforces_offsets() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("deepseek-runtime-api-{}", Uuid::new_v4()));
+        let workspace = root.join("workspace");
+        fs::create_dir_all(&workspace)?;
+        let Some((addr, _runtime_threads, handle)) = spawn_test_server_in_workspace(
+            root.clone(),
+            root.join("sessions"),
+            None,
+            workspace.clone(),
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+        let client = reqwest::Client::new();
+        let url = format!("http://{addr}/v1/workspace/files");
+
+        let first = client
+            .post(format!("{url}?path=inbox/data.bin&offset=0&complete=false"))
+            .body(b"hello ".to_vec())
+            .send()
+            .await?;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert!(!workspace.join("inbox/data.bin").exists());
+
+        let wrong_offset = client
+            .post(format!("{url}?path=inbox/data.bin&offset=3"))
+            .body(b"world".to_vec())
+            .send()
+            .await?;
+        assert_eq!(wrong_offset.status(), StatusCode::CONFLICT);
+
+        let last: Value = client
+            .post(format!("{url}?path=inbox/data.bin&offset=6&total_size=11"))
+            .body(b"world".to_vec())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        assert_eq!(last["bytes_received"], 11);
+        assert_eq!(last["complete"], true);
+        assert_eq!(fs::read(workspace.join("inbox/data.bin"))?, b"hello world");
+        assert!(!workspace.join("inbox/.data.bin.upload").exists());
+
+        let escape = client
+            .post(format!("{url}?path=../escape.txt"))
+            .body(b"x".to_vec())
+            .send()
+            .await?;
+        assert_eq!(escape.status(), StatusCode::BAD_REQUEST);
+
+        let too_large = client
+            .post(format!("{url}?path=big.bin&total_size=999999999999"))
+            .body(b"x".to_vec())
+            .send()
+            .await?;
+        assert_eq!(too_large.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        handle.abort();
+        let _ = fs::remove_dir_all(&root);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn artifact_endpoint_serves_spilled_output_with_ranges() -> Result<()> {
+        // Wrap the lock in a guard struct so clippy's `await_holding_lock`
+        // doesn't fire; dropping it restores the previous spillover root.
+        struct SpillRootGuard {
+            root: PathBuf,
+            previous: Option<PathBuf>,
+            _lock: std::sync::MutexGuard<'static, ()>,
+        }
+        impl Drop for SpillRootGuard {
+            fn drop(&mut self) {
+                crate::tools::truncate::set_test_spillover_root(self.previous.take());
+                let _ = fs::remove_dir_all(&self.root);
+            }
+        }
+        let spill = {
+            let lock = crate::tools::truncate::TEST_SPILLOVER_GUARD
+                .lock()
+                .unwrap_or_else(|err| err.into_inner());
+            let root =
+                std::env::temp_dir().join(format!("deepseek-runtime-spill-{}", Uuid::new_v4()));
+            let previous = crate::tools::truncate::set_test_spillover_root(Some(root.clone()));
+            SpillRootGuard {
+                root,
+                previous,
+                _lock: lock,
+            }
+        };
+        crate::tools::truncate::write_spillover("call_big", "0123456789abcdef")?;
+
+        let Some((addr, _runtime_threads, handle)) = spawn_test_server().await? else {
+            return Ok(());
+        };
+        let client = reqwest::Client::new();
+
+        let full = client
+            .get(format!("http://{addr}/v1/artifacts/call_big"))
+            .send()
+            .await?;
+        assert_eq!(full.status(), StatusCode::OK);
+        assert_eq!(
+            full.headers()
+                .get(header::ACCEPT_RANGES)
+                .map(|v| v.as_bytes()),
+            Some(&b"bytes"[..])
+        );
+        assert_eq!(full.text().await?, "0123456789abcdef");
+
+        let partial = client
+            .get(format!("http://{addr}/v1/artifacts/call_big"))
+            .header(header::RANGE, "bytes=4-7")
+            .send()
+            .await?;
+        assert_eq!(partial.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(
+            partial
+                .headers()
+                .get(header::CONTENT_RANGE)
+                .map(|v| v.as_bytes()),
+            Some(&b"bytes 4-7/16"[..])
+        );
+        assert_eq!(partial.text().await?, "4567");
+
+        let unsatisfiable = client
+            .get(format!("http://{addr}/v1/artifacts/call_big"))
+            .header(header::RANGE, "bytes=99-")
+            .send()
+            .await?;
+        assert_eq!(unsatisfiable.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+
+        let missing = client
+            .get(format!("http://{addr}/v1/artifacts/call_missing"))
+            .send()
+            .await?;
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+        let invalid = client
+            .get(format!("http://{addr}/v1/artifacts/..%2Fsecret"))
+            .send()
+            .await?;
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+
+        handle.abort();
+        drop(spill);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn session_resume_thread_returns_404_for_missing_session() -> Result<()> {
         let Some((addr, _runtime_threads, handle)) = spawn_test_server().await? else {
@@ -3517,6 +4360,30 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn chat_completions_rejects_requests_without_a_user_turn() -> Result<()> {
+        let Some((addr, _runtime_threads, handle)) = spawn_test_server().await? else {
+            return Ok(());
+        };
+        let client = reqwest::Client::new();
+
+        for body in [
+            json!({ "messages": [] }),
+            json!({ "messages": [{ "role": "assistant", "content": "hi" }] }),
+            json!({ "model": "deepseek-v4-pro" }),
+        ] {
+            let resp = client
+                .post(format!("http://{addr}/v1/chat/completions"))
+                .json(&body)
+                .send()
+                .await?;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{body}");
+        }
+
+        handle.abort();
+        Ok(())
+    }
+
     /// #564 / whalescale#261 — `GET /v1/usage` aggregates per-turn token +
     /// cost data. With no threads the response is well-formed and totals are
     /// zero with empty buckets (never a 404).
@@ -3600,6 +4467,30 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn metrics_endpoint_serves_prometheus_text() -> Result<()> {
+        let Some((addr, _runtime_threads, handle)) = spawn_test_server().await? else {
+            return Ok(());
+        };
+        let resp = reqwest::Client::new()
+            .get(format!("http://{addr}/metrics"))
+            .send()
+            .await?
+            .error_for_status()?;
+        assert!(
+            resp.headers()[header::CONTENT_TYPE]
+                .to_str()?
+                .starts_with("text/plain; version=0.0.4")
+        );
+        let body = resp.text().await?;
+        assert!(body.contains("# TYPE deepseek_active_sessions gauge"));
+        assert!(body.contains("deepseek_task_queue_depth{state=\"queued\"} 0"));
+        assert!(body.contains("# TYPE deepseek_turn_duration_seconds histogram"));
+
+        handle.abort();
+        Ok(())
+    }
+
     #[tokio::test]
     async fn decide_approval_404s_when_nothing_pending() -> Result<()> {
         let Some((addr, _runtime_threads, handle)) = spawn_test_server().await? else {
@@ -0,0 +1,208 @@
+//! Access control for the runtime API: token scopes, the token file, and
+//! the rate limit on turn-creating routes.
+//!
+//! Two bearer tokens can be configured. The control token grants every
+//! `/v1/*` route. The optional read-only token grants only `GET`/`HEAD`
+//! routes, so a dashboard or log viewer can watch threads and usage without
+//! being able to start turns, decide approvals, or delete sessions.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use axum::http::Method;
+use sha2::{Digest, Sha256};
+
+/// Default cap on turn-creating requests per minute.
+pub const DEFAULT_TURNS_PER_MINUTE: u32 = 30;
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// What a route needs from the presented token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Read,
+    Control,
+}
+
+impl Scope {
+    /// `GET`/`HEAD`/`OPTIONS` only read state; every other method mutates.
+    #[must_use]
+    pub fn required_for(method: &Method) -> Self {
+        if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            Scope::Read
+        } else {
+            Scope::Control
+        }
+    }
+}
+
+/// The scope a presented token carries, if it matches either configured token.
+#[must_use]
+pub fn scope_for_token(presented: &str, control: &str, read_only: Option<&str>) -> Option<Scope> {
+    if tokens_match(presented, control) {
+        Some(Scope::Control)
+    } else if read_only.is_some_and(|token| tokens_match(presented, token)) {
+        Some(Scope::Read)
+    } else {
+        None
+    }
+}
+
+/// Compare secrets without leaking where they differ through timing. Both
+/// sides are hashed first so the comparison is over fixed-length digests
+/// and does not reveal the expected token's length either.
+#[must_use]
+pub fn tokens_match(presented: &str, expected: &str) -> bool {
+    let presented = Sha256::digest(presented.as_bytes());
+    let expected = Sha256::digest(expected.as_bytes());
+    let diff = presented
+        .iter()
+        .zip(expected.iter())
+        .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+    std::hint::black_box(diff) == 0
+}
+
+/// Routes that start model work: streamed turns, thread turns, the OpenAI
+/// compatible endpoint, background tasks, and manual automation runs.
+#[must_use]
+pub fn creates_turn(method: &Method, path: &str) -> bool {
+    if *method != Method::POST {
+        return false;
+    }
+    matches!(path, "/v1/stream" | "/v1/chat/completions" | "/v1/tasks")
+        || (path.starts_with("/v1/threads/") && path.ends_with("/turns"))
+        || (path.starts_with("/v1/automations/") && path.ends_with("/run"))
+}
+
+/// Sliding one-minute window over turn-creating requests.
+#[derive(Debug)]
+pub struct TurnRateLimiter {
+    per_minute: u32,
+    recent: Mutex<VecDeque<Instant>>,
+}
+
+impl TurnRateLimiter {
+    /// `None` when `per_minute` is zero (limiting disabled).
+    #[must_use]
+    pub fn new(per_minute: u32) -> Option<Self> {
+        (per_minute > 0).then(|| Self {
+            per_minute,
+            recent: Mutex::new(VecDeque::new()),
+        })
+    }
+
+    /// Record a request at `now`, or return how long until one is allowed.
+    pub fn check(&self, now: Instant) -> Result<(), Duration> {
+        let mut recent = self
+            .recent
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        while recent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= RATE_WINDOW)
+        {
+            recent.pop_front();
+        }
+        if recent.len() >= self.per_minute as usize {
+            let oldest = recent.front().copied().unwrap_or(now);
+            return Err(RATE_WINDOW.saturating_sub(now.duration_since(oldest)));
+        }
+        recent.push_back(now);
+        Ok(())
+    }
+}
+
+/// `~/.deepseek/runtime/token`, where desktop shells look for the token.
+#[must_use]
+pub fn default_token_file() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".deepseek").join("runtime").join("token"))
+}
+
+/// Write the control token for local clients, readable only by the owner.
+pub fn write_token_file(path: &Path, token: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("Failed to write runtime token to {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(token.as_bytes())?;
+    file.write_all(b"\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_only_token_covers_only_safe_methods() {
+        assert_eq!(
+            scope_for_token("ctl", "ctl", Some("ro")),
+            Some(Scope::Control)
+        );
+        assert_eq!(scope_for_token("ro", "ctl", Some("ro")), Some(Scope::Read));
+        assert_eq!(scope_for_token("ro", "ctl", None), None);
+        assert_eq!(scope_for_token("ctl2", "ctl", Some("ro")), None);
+        assert!(tokens_match("same", "same"));
+        assert!(!tokens_match("same", "Same"));
+        assert_eq!(Scope::required_for(&Method::GET), Scope::Read);
+        assert_eq!(Scope::required_for(&Method::DELETE), Scope::Control);
+    }
+
+    #[test]
+    fn turn_routes_are_recognised() {
+        assert!(creates_turn(&Method::POST, "/v1/stream"));
+        assert!(creates_turn(&Method::POST, "/v1/threads/thr_1/turns"));
+        assert!(creates_turn(&Method::POST, "/v1/automations/a1/run"));
+        assert!(!creates_turn(&Method::GET, "/v1/tasks"));
+        assert!(!creates_turn(
+            &Method::POST,
+            "/v1/threads/thr_1/turns/t1/steer"
+        ));
+    }
+
+    #[test]
+    fn limiter_refills_after_the_window() {
+        let limiter = TurnRateLimiter::new(2).unwrap();
+        let start = Instant::now();
+        assert!(limiter.check(start).is_ok());
+        assert!(limiter.check(start).is_ok());
+        let wait = limiter.check(start + Duration::from_secs(20)).unwrap_err();
+        assert_eq!(wait, Duration::from_secs(40));
+        assert!(limiter.check(start + RATE_WINDOW).is_ok());
+        assert!(TurnRateLimiter::new(0).is_none());
+    }
+
+    #[test]
+    fn token_file_is_private() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("runtime").join("token");
+        write_token_file(&path, "dst_abc").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "dst_abc\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
@@ -0,0 +1,635 @@
+//! OpenAI-compatible `POST /v1/chat/completions`.
+//!
+//! Lets editors and scripts point an OpenAI SDK at `deepseek serve --http`.
+//! By default each request becomes one agent turn on a fresh archived
+//! runtime thread: the message history is folded into the turn prompt, tools
+//! run server-side, and the assistant text comes back as a `chat.completion`
+//! (or a `chat.completion.chunk` SSE stream when `stream` is set). Tool
+//! activity rides along in a `deepseek` extension field that OpenAI clients
+//! ignore. With `[runtime_api] openai_tools = false` the request is forwarded
+//! unchanged to the configured provider instead.
+
+use std::convert::Infallible;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use async_stream::stream;
+use axum::Json;
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{StatusCode, header};
+use axum::response::sse::{Event as SseEvent, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use futures_util::{Stream, StreamExt};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::{ApiError, RuntimeApiState};
+use crate::client::DeepSeekClient;
+use crate::config::DEFAULT_TEXT_MODEL;
+use crate::runtime_threads::{CreateThreadRequest, RuntimeEventRecord, StartTurnRequest};
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionRequest {
+    #[serde(default)]
+    model: Option<String>,
+    messages: Vec<ChatMessage>,
+    #[serde(default)]
+    stream: bool,
+    #[serde(default)]
+    stream_options: Option<StreamOptions>,
+    // DeepSeek extensions, sent through the SDK's `extra_body`.
+    #[serde(default)]
+    mode: Option<String>,
+    #[serde(default)]
+    workspace: Option<PathBuf>,
+    #[serde(default)]
+    allow_shell: Option<bool>,
+    #[serde(default)]
+    trust_mode: Option<bool>,
+    #[serde(default)]
+    auto_approve: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessage {
+    role: String,
+    #[serde(default)]
+    content: Value,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct StreamOptions {
+    #[serde(default)]
+    include_usage: bool,
+}
+
+pub(super) async fn chat_completions(
+    State(state): State<RuntimeApiState>,
+    Json(body): Json<Value>,
+) -> Result<Response, ApiError> {
+    let default_model = state
+        .config
+        .default_text_model
+        .clone()
+        .unwrap_or_else(|| DEFAULT_TEXT_MODEL.to_string());
+    let tools_enabled = state
+        .config
+        .runtime_api
+        .as_ref()
+        .and_then(|cfg| cfg.openai_tools)
+        .unwrap_or(true);
+    if !tools_enabled {
+        return proxy_chat_completion(&state, body, &default_model).await;
+    }
+
+    let req: ChatCompletionRequest = serde_json::from_value(body)
+        .map_err(|e| ApiError::bad_request(format!("invalid chat completion request: {e}")))?;
+    let prompt = build_turn_prompt(&req.messages).map_err(ApiError::bad_request)?;
+    let model = req
+        .model
+        .clone()
+        .filter(|model| !model.trim().is_empty())
+        .unwrap_or(default_model);
+    let workspace = req
+        .workspace
+        .clone()
+        .unwrap_or_else(|| state.workspace.clone());
+    let mode = req.mode.clone().unwrap_or_else(|| "agent".to_string());
+    let allow_shell = req.allow_shell.unwrap_or(state.config.allow_shell());
+    let trust_mode = req.trust_mode.unwrap_or(false);
+    let auto_approve = req.auto_approve.unwrap_or(false);
+
+    let thread = state
+        .runtime_threads
+        .create_thread(CreateThreadRequest {
+            model: Some(model.clone()),
+            workspace: Some(workspace),
+            mode: Some(mode.clone()),
+            allow_shell: Some(allow_shell),
+            trust_mode: Some(trust_mode),
+            auto_approve: Some(auto_approve),
+            archived: true,
+            system_prompt: None,
+            task_id: None,
+        })
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to create completion thread: {e}")))?;
+    let turn = state
+        .runtime_threads
+        .start_turn(
+            &thread.id,
+            StartTurnRequest {
+                prompt,
+                input_summary: None,
+                model: Some(model.clone()),
+                mode: Some(mode),
+                allow_shell: Some(allow_shell),
+                trust_mode: Some(trust_mode),
+                auto_approve: Some(auto_approve),
+            },
+        )
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to start completion turn: {e}")))?;
+
+    let events = turn_events(&state, thread.id.clone(), turn.id.clone())?;
+    let mut translator = CompletionTranslator::new(&thread.id, &turn.id, &model);
+
+    if req.stream {
+        let include_usage = req.stream_options.unwrap_or_default().include_usage;
+        let stream = stream! {
+            let mut events = std::pin::pin!(events);
+            while let Some(event) = events.next().await {
+                let Some(translation) = translator.translate(&event) else {
+                    continue;
+                };
+                let finished = matches!(translation, Translation::Finished { .. });
+                for payload in translator.stream_payloads(translation, include_usage) {
+                    yield Ok::<_, Infallible>(SseEvent::default().data(payload.to_string()));
+                }
+                if finished {
+                    break;
+                }
+            }
+            yield Ok(SseEvent::default().data("[DONE]"));
+        };
+        return Ok(Sse::new(stream)
+            .keep_alive(KeepAlive::new().interval(Duration::from_secs(15)))
+            .into_response());
+    }
+
+    let mut events = std::pin::pin!(events);
+    while let Some(event) = events.next().await {
+        if let Some(Translation::Finished { usage, error }) = translator.translate(&event) {
+            if let Some(error) = error {
+                return Err(ApiError::internal(error));
+            }
+            return Ok(Json(translator.completion(usage.as_ref())).into_response());
+        }
+    }
+    Err(ApiError::internal(
+        "event channel closed before the turn finished",
+    ))
+}
+
+/// Forward the request body to the provider's `chat/completions` and relay
+/// the response, streaming or not, as-is.
+async fn proxy_chat_completion(
+    state: &RuntimeApiState,
+    mut body: Value,
+    default_model: &str,
+) -> Result<Response, ApiError> {
+    let Some(object) = body.as_object_mut() else {
+        return Err(ApiError::bad_request("request body must be a JSON object"));
+    };
+    if !object.get("model").is_some_and(Value::is_string) {
+        object.insert("model".to_string(), json!(default_model));
+    }
+    let client = DeepSeekClient::new(&state.config)
+        .map_err(|e| ApiError::internal(format!("Failed to create API client: {e}")))?;
+    let upstream = client
+        .forward_chat_completion(&body)
+        .await
+        .map_err(|e| ApiError::bad_gateway(format!("Upstream request failed: {e}")))?;
+    let status = StatusCode::from_u16(upstream.status().as_u16()).unwrap_or(StatusCode::OK);
+    let content_type = upstream
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("application/json")
+        .to_string();
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, content_type)
+        .body(Body::from_stream(upstream.bytes_stream()))
+        .map_err(|e| ApiError::internal(format!("Failed to build proxy response: {e}")))
+}
+
+/// Events of one turn: the stored backlog, then live events until
+/// `turn.completed`.
+fn turn_events(
+    state: &RuntimeApiState,
+    thread_id: String,
+    turn_id: String,
+) -> Result<impl Stream<Item = RuntimeEventRecord> + use<>, ApiError> {
+    let backlog = state
+        .runtime_threads
+        .events_since(&thread_id, None)
+        .map_err(|e| ApiError::internal(format!("Failed to load turn events: {e}")))?;
+    let mut live = state.runtime_threads.subscribe_events();
+    Ok(stream! {
+        let belongs = |event: &RuntimeEventRecord| {
+            event.thread_id == thread_id && event.turn_id.as_deref() == Some(turn_id.as_str())
+        };
+        for event in backlog {
+            if !belongs(&event) {
+                continue;
+            }
+            let done = event.event == "turn.completed";
+            yield event;
+            if done {
+                return;
+            }
+        }
+        while let Ok(event) = live.recv().await {
+            if !belongs(&event) {
+                continue;
+            }
+            let done = event.event == "turn.completed";
+            yield event;
+            if done {
+                return;
+            }
+        }
+    })
+}
+
+/// Fold an OpenAI message list into one turn prompt. System messages become
+/// instructions, earlier messages a transcript, and the final user message
+/// the request itself.
+fn build_turn_prompt(messages: &[ChatMessage]) -> Result<String, String> {
+    let Some((last, earlier)) = messages.split_last() else {
+        return Err("messages must not be empty".to_string());
+    };
+    if last.role != "user" {
+        return Err("the last message must have role `user`".to_string());
+    }
+    let request = message_text(&last.content);
+    if request.trim().is_empty() {
+        return Err("the last user message is empty".to_string());
+    }
+
+    let mut instructions = Vec::new();
+    let mut transcript = Vec::new();
+    for message in earlier {
+        let text = message_text(&message.content);
+        if text.trim().is_empty() {
+            continue;
+        }
+        match message.role.as_str() {
+            "system" | "developer" => instructions.push(text),
+            "user" => transcript.push(format!("User: {text}")),
+            "assistant" => transcript.push(format!("Assistant: {text}")),
+            "tool" => transcript.push(format!("Tool result: {text}")),
+            other => transcript.push(format!("{other}: {text}")),
+        }
+    }
+
+    if instructions.is_empty() && transcript.is_empty() {
+        return Ok(request);
+    }
+    let mut prompt = String::new();
+    if !instructions.is_empty() {
+        prompt.push_str("Instructions from the client:\n");
+        prompt.push_str(&instructions.join("\n\n"));
+        prompt.push_str("\n\n");
+    }
+    if !transcript.is_empty() {
+        prompt.push_str("Conversation so far:\n\n");
+        prompt.push_str(&transcript.join("\n\n"));
+        prompt.push_str("\n\n");
+    }
+    prompt.push_str("Current request:\n");
+    prompt.push_str(&request);
+    Ok(prompt)
+}
+
+/// Text of a message `content`: a string, or the `text` parts of a content
+/// array.
+fn message_text(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Translation {
+    Content(String),
+    /// Tool or approval activity, reported in the `deepseek` extension.
+    Activity(Value),
+    Finished {
+        usage: Option<Value>,
+        error: Option<String>,
+    },
+}
+
+/// Maps runtime events of one turn onto OpenAI completion payloads.
+struct CompletionTranslator {
+    id: String,
+    thread_id: String,
+    turn_id: String,
+    model: String,
+    created: i64,
+    content: String,
+    current_item: Option<String>,
+    activity: Vec<Value>,
+    role_sent: bool,
+}
+
+impl CompletionTranslator {
+    fn new(thread_id: &str, turn_id: &str, model: &str) -> Self {
+        Self {
+            id: format!("chatcmpl-{turn_id}"),
+            thread_id: thread_id.to_string(),
+            turn_id: turn_id.to_string(),
+            model: model.to_string(),
+            created: chrono::Utc::now().timestamp(),
+            content: String::new(),
+            current_item: None,
+            activity: Vec::new(),
+            role_sent: false,
+        }
+    }
+
+    fn translate(&mut self, event: &RuntimeEventRecord) -> Option<Translation> {
+        let payload = &event.payload;
+        match event.event.as_str() {
+            "item.delta"
+                if payload.get("kind").and_then(Value::as_str) == Some("agent_message") =>
+            {
+                let delta = payload.get("delta").and_then(Value::as_str)?;
+                let mut text = String::new();
+                // Separate the messages before and after tool calls.
+                if event.item_id != self.current_item {
+                    if !self.content.is_empty() {
+                        text.push_str("\n\n");
+                    }
+                    self.current_item = event.item_id.clone();
+                }
+                text.push_str(delta);
+                self.content.push_str(&text);
+                Some(Translation::Content(text))
+            }
+            "item.started" => {
+                let tool = payload.get("tool")?;
+                Some(self.record(json!({
+                    "type": "tool.started",
+                    "id": tool.get("id"),
+                    "name": tool.get("name"),
+                    "input": tool.get("input"),
+                })))
+            }
+            "item.completed" | "item.failed" => {
+                let item = payload.get("item")?;
+                let kind = item.get("kind").and_then(Value::as_str)?;
+                if !matches!(kind, "tool_call" | "file_change" | "command_execution") {
+                    return None;
+                }
+                Some(self.record(json!({
+                    "type": "tool.completed",
+                    "id": item.get("id"),
+                    "success": event.event == "item.completed",
+                    "output": item.get("detail").or_else(|| item.get("summary")),
+                })))
+            }
+            "approval.required" => {
+                let mut activity = payload.clone();
+                if let Some(object) = activity.as_object_mut() {
+                    object.insert("type".to_string(), json!("approval.required"));
+                }
+                Some(self.record(activity))
+            }
+            "turn.completed" => {
+                let turn = payload.get("turn");
+                let status = turn
+                    .and_then(|turn| turn.get("status"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("completed");
+                let error = (status != "completed").then(|| {
+                    turn.and_then(|turn| turn.get("error"))
+                        .and_then(Value::as_str)
+                        .map_or_else(|| format!("turn {status}"), str::to_string)
+                });
+                let usage = turn
+                    .and_then(|turn| turn.get("usage"))
+                    .filter(|usage| !usage.is_null())
+                    .map(openai_usage);
+                Some(Translation::Finished { usage, error })
+            }
+            _ => None,
+        }
+    }
+
+    fn record(&mut self, activity: Value) -> Translation {
+        self.activity.push(activity.clone());
+        Translation::Activity(activity)
+    }
+
+    fn chunk(&mut self, mut delta: Value, finish_reason: Option<&str>) -> Value {
+        if !self.role_sent {
+            self.role_sent = true;
+            delta["role"] = json!("assistant");
+        }
+        json!({
+            "id": self.id,
+            "object": "chat.completion.chunk",
+            "created": self.created,
+            "model": self.model,
+            "choices": [{
+                "index": 0,
+                "delta": delta,
+                "finish_reason": finish_reason,
+            }],
+        })
+    }
+
+    /// SSE `data:` payloads for one translated event.
+    fn stream_payloads(&mut self, translation: Translation, include_usage: bool) -> Vec<Value> {
+        match translation {
+            Translation::Content(text) => vec![self.chunk(json!({ "content": text }), None)],
+            Translation::Activity(activity) => {
+                let mut chunk = self.chunk(json!({}), None);
+                chunk["deepseek"] = activity;
+                vec![chunk]
+            }
+            Translation::Finished {
+                error: Some(message),
+                ..
+            } => vec![json!({ "error": { "message": message, "type": "runtime_error" } })],
+            Translation::Finished { usage, error: None } => {
+                let mut payloads = vec![self.chunk(json!({}), Some("stop"))];
+                if include_usage {
+                    payloads.push(json!({
+                        "id": self.id,
+                        "object": "chat.completion.chunk",
+                        "created": self.created,
+                        "model": self.model,
+                        "choices": [],
+                        "usage": usage,
+                    }));
+                }
+                payloads
+            }
+        }
+    }
+
+    /// The non-streaming `chat.completion` response.
+    fn completion(&self, usage: Option<&Value>) -> Value {
+        json!({
+            "id": self.id,
+            "object": "chat.completion",
+            "created": self.created,
+            "model": self.model,
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": self.content },
+                "finish_reason": "stop",
+            }],
+            "usage": usage,
+            "deepseek": {
+                "thread_id": self.thread_id,
+                "turn_id": self.turn_id,
+                "activity": self.activity,
+            },
+        })
+    }
+}
+
+fn openai_usage(usage: &Value) -> Value {
+    let prompt = usage
+        .get("input_tokens")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    let completion = usage
+        .get("output_tokens")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    json!({
+        "prompt_tokens": prompt,
+        "completion_tokens": completion,
+        "total_tokens": prompt + completion,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: &str, content: Value) -> ChatMessage {
+        ChatMessage {
+            role: role.to_string(),
+            content,
+        }
+    }
+
+    fn event(item_id: Option<&str>, name: &str, payload: Value) -> RuntimeEventRecord {
+        RuntimeEventRecord {
+            schema_version: 1,
+            seq: 0,
+            timestamp: chrono::Utc::now(),
+            thread_id: "thr_1".to_string(),
+            turn_id: Some("turn_1".to_string()),
+            item_id: item_id.map(str::to_string),
+            event: name.to_string(),
+            payload,
+        }
+    }
+
+    #[test]
+    fn prompt_folds_history_and_instructions() {
+        assert_eq!(
+            build_turn_prompt(&[message("user", json!("hi"))]).unwrap(),
+            "hi"
+        );
+        let prompt = build_turn_prompt(&[
+            message("system", json!("Be terse.")),
+            message("user", json!("What is in src?")),
+            message("assistant", json!("main.rs")),
+            message(
+                "user",
+                json!([{ "type": "text", "text": "Explain main.rs" }]),
+            ),
+        ])
+        .unwrap();
+        assert!(prompt.starts_with("Instructions from the client:\nBe terse."));
+        assert!(prompt.contains("User: What is in src?\n\nAssistant: main.rs"));
+        assert!(prompt.ends_with("Current request:\nExplain main.rs"));
+
+        assert!(build_turn_prompt(&[]).is_err());
+        assert!(build_turn_prompt(&[message("assistant", json!("x"))]).is_err());
+    }
+
+    #[test]
+    fn translator_maps_a_tool_turn_to_openai_payloads() {
+        let mut translator = CompletionTranslator::new("thr_1", "turn_1", "deepseek-v4-pro");
+        let events = [
+            event(
+                Some("msg_1"),
+                "item.delta",
+                json!({ "kind": "agent_message", "delta": "Checking." }),
+            ),
+            event(
+                Some("tool_1"),
+                "item.started",
+                json!({ "tool": { "id": "call_1", "name": "list_dir", "input": {} } }),
+            ),
+            event(
+                Some("tool_1"),
+                "item.completed",
+                json!({ "item": { "id": "tool_1", "kind": "tool_call", "detail": "src/" } }),
+            ),
+            event(
+                Some("msg_2"),
+                "item.delta",
+                json!({ "kind": "agent_message", "delta": "Found src/." }),
+            ),
+        ];
+        let mut payloads = Vec::new();
+        for event in &events {
+            let translation = translator.translate(event).expect("translated");
+            payloads.extend(translator.stream_payloads(translation, true));
+        }
+        assert_eq!(payloads[0]["object"], "chat.completion.chunk");
+        assert_eq!(payloads[0]["choices"][0]["delta"]["role"], "assistant");
+        assert_eq!(payloads[0]["choices"][0]["delta"]["content"], "Checking.");
+        assert_eq!(payloads[1]["deepseek"]["type"], "tool.started");
+        assert_eq!(payloads[2]["deepseek"]["success"], true);
+        assert!(payloads[3]["choices"][0]["delta"].get("role").is_none());
+        assert_eq!(
+            payloads[3]["choices"][0]["delta"]["content"],
+            "\n\nFound src/."
+        );
+
+        let finished = translator
+            .translate(&event(
+                None,
+                "turn.completed",
+                json!({ "turn": { "status": "completed", "usage": { "input_tokens": 10, "output_tokens": 4 } } }),
+            ))
+            .expect("finished");
+        let Translation::Finished { usage, error } = finished.clone() else {
+            panic!("expected finished");
+        };
+        assert!(error.is_none());
+        let tail = translator.stream_payloads(finished, true);
+        assert_eq!(tail[0]["choices"][0]["finish_reason"], "stop");
+        assert_eq!(tail[1]["usage"]["total_tokens"], 14);
+
+        let completion = translator.completion(usage.as_ref());
+        assert_eq!(completion["object"], "chat.completion");
+        assert_eq!(
+            completion["choices"][0]["message"]["content"],
+            "Checking.\n\nFound src/."
+        );
+        assert_eq!(
+            completion["deepseek"]["activity"].as_array().unwrap().len(),
+            2
+        );
+
+        let failed = translator.translate(&event(
+            None,
+            "turn.completed",
+            json!({ "turn": { "status": "failed", "error": "model overloaded" } }),
+        ));
+        assert!(matches!(
+            failed,
+            Some(Translation::Finished { error: Some(ref message), .. }) if message == "model overloaded"
+        ));
+    }
+}
@@ -0,0 +1,844 @@
+//! Read-only observers for a live session (`/share start`).
+//!
+//! The TUI publishes its transcript into a [`ShareHub`]; a small HTTP server
+//! hands it to anyone holding the share token as an SSE stream, plus a JSON
+//! backlog and a minimal browser viewer. Observers can only read: the router
+//! has no write routes, and the share token grants nothing on the runtime
+//! API itself. The token travels in a header or a path segment, never in the
+//! query string, and the router does not log request paths.
+//!
+//! Every event is redacted before it is stored or sent. Secret-looking text
+//! and values under secret-looking keys are masked, long tool arguments and
+//! outputs are truncated, reasoning is withheld unless the share opted in,
+//! and tool output can be withheld too. Streamed text is redacted over the
+//! accumulated stream, with a short tail held back, so a secret split across
+//! deltas is still masked.
+
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_stream::stream;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, header};
+use axum::response::sse::{Event as SseEvent, KeepAlive, Sse};
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+
+use super::{ApiError, cors_layer, sse_json};
+use crate::core::events::Event as EngineEvent;
+use crate::report::{redact_json, redact_text, secret_spans};
+use crate::utils::truncate_with_ellipsis;
+
+/// Events kept for observers that join mid-session. Consecutive text deltas
+/// are merged, so this covers far more than this many raw deltas.
+const BACKLOG_CAP: usize = 2_000;
+/// Longest string kept from a tool's arguments, in bytes.
+const TOOL_INPUT_MAX_LEN: usize = 2_000;
+/// Longest tool output sent to observers, in bytes.
+const TOOL_OUTPUT_MAX_LEN: usize = 8_000;
+/// Marker ending a truncated string.
+const TRUNCATED: &str = "… [truncated]";
+/// Bytes at the end of a delta stream held back until more text arrives or
+/// the stream ends, so a secret split across deltas is masked whole.
+const STREAM_HOLDBACK: usize = 256;
+
+/// What a share withholds beyond the always-on secret masking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShareRedaction {
+    /// Stream the model's thinking text.
+    pub reasoning: bool,
+    /// Stream tool output (arguments are always sent, redacted).
+    pub tool_output: bool,
+}
+
+impl Default for ShareRedaction {
+    fn default() -> Self {
+        Self {
+            reasoning: false,
+            tool_output: true,
+        }
+    }
+}
+
+/// One redacted transcript event as observers receive it.
+#[derive(Debug, Clone, Serialize)]
+pub struct ShareEvent {
+    pub seq: u64,
+    pub timestamp: String,
+    pub event: String,
+    pub payload: Value,
+}
+
+struct HubState {
+    next_seq: u64,
+    backlog: VecDeque<ShareEvent>,
+    streams: Vec<OpenStream>,
+}
+
+/// Raw text of one delta stream (the reply, the reasoning, or one tool's
+/// output) that has not been published yet.
+struct OpenStream {
+    event: &'static str,
+    id: Option<String>,
+    pending: String,
+}
+
+/// Token, redaction policy, and event fan-out of one share.
+pub struct ShareHub {
+    token: String,
+    redaction: ShareRedaction,
+    live: broadcast::Sender<ShareEvent>,
+    state: std::sync::Mutex<HubState>,
+}
+
+impl ShareHub {
+    #[must_use]
+    pub fn new(redaction: ShareRedaction) -> Arc<Self> {
+        let (live, _) = broadcast::channel(1024);
+        Arc::new(Self {
+            token: format!(
+                "dss_{}{}",
+                uuid::Uuid::new_v4().simple(),
+                uuid::Uuid::new_v4().simple()
+            ),
+            redaction,
+            live,
+            state: std::sync::Mutex::new(HubState {
+                next_seq: 1,
+                backlog: VecDeque::new(),
+                streams: Vec::new(),
+            }),
+        })
+    }
+
+    #[must_use]
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    #[must_use]
+    pub fn redaction(&self) -> ShareRedaction {
+        self.redaction
+    }
+
+    /// Observers currently streaming events.
+    #[must_use]
+    pub fn observer_count(&self) -> usize {
+        self.live.receiver_count()
+    }
+
+    pub fn publish_user_message(&self, content: &str) {
+        self.push("user.message", json!({ "content": redact_text(content) }));
+    }
+
+    /// A finished assistant message, used to seed the backlog with the
+    /// transcript that existed before the share started.
+    pub fn publish_assistant_message(&self, content: &str) {
+        self.push("message", json!({ "content": redact_text(content) }));
+    }
+
+    pub fn publish_engine_event(&self, event: &EngineEvent) {
+        if let Some((name, id, text)) = stream_delta(event, self.redaction) {
+            self.push_delta(name, id, text);
+        } else if let Some((name, payload)) = redact_engine_event(event, self.redaction) {
+            self.push(name, payload);
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, HubState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Add a delta to its stream and publish the part that can no longer
+    /// turn out to be the start of a secret.
+    fn push_delta(&self, name: &'static str, id: Option<&str>, text: &str) {
+        let mut state = self.lock_state();
+        let index = state
+            .streams
+            .iter()
+            .position(|stream| stream.event == name && stream.id.as_deref() == id)
+            .unwrap_or_else(|| {
+                state.streams.push(OpenStream {
+                    event: name,
+                    id: id.map(str::to_string),
+                    pending: String::new(),
+                });
+                state.streams.len() - 1
+            });
+        let stream = &mut state.streams[index];
+        stream.pending.push_str(text);
+        let cut = publishable_len(&stream.pending);
+        if cut == 0 {
+            return;
+        }
+        let ready: String = stream.pending.drain(..cut).collect();
+        Self::record(
+            &mut state,
+            &self.live,
+            name,
+            delta_payload(name, id, &ready),
+        );
+    }
+
+    /// Publish a non-delta event. It ends the open streams, so whatever
+    /// they held back goes out first.
+    fn push(&self, name: &str, payload: Value) {
+        let mut state = self.lock_state();
+        for stream in std::mem::take(&mut state.streams) {
+            if !stream.pending.is_empty() {
+                let payload = delta_payload(stream.event, stream.id.as_deref(), &stream.pending);
+                Self::record(&mut state, &self.live, stream.event, payload);
+            }
+        }
+        Self::record(&mut state, &self.live, name, payload);
+    }
+
+    fn record(
+        state: &mut HubState,
+        live: &broadcast::Sender<ShareEvent>,
+        name: &str,
+        payload: Value,
+    ) {
+        let event = ShareEvent {
+            seq: state.next_seq,
+            timestamp: Utc::now().to_rfc3339(),
+            event: name.to_string(),
+            payload,
+        };
+        state.next_seq += 1;
+        let merged = state
+            .backlog
+            .back_mut()
+            .is_some_and(|last| merge_delta(last, &event));
+        if !merged {
+            state.backlog.push_back(event.clone());
+            while state.backlog.len() > BACKLOG_CAP {
+                state.backlog.pop_front();
+            }
+        }
+        // No receivers is fine: nobody is watching yet.
+        let _ = live.send(event);
+    }
+
+    fn backlog_since(&self, since_seq: u64) -> Vec<ShareEvent> {
+        self.lock_state()
+            .backlog
+            .iter()
+            .filter(|event| event.seq > since_seq)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Bytes of a stream's raw text that are safe to publish: all but the last
+/// [`STREAM_HOLDBACK`], cut back to the start of any secret reaching into
+/// the held-back tail.
+fn publishable_len(pending: &str) -> usize {
+    let Some(mut cut) = pending.len().checked_sub(STREAM_HOLDBACK) else {
+        return 0;
+    };
+    while !pending.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    let spans: Vec<_> = secret_spans(pending).collect();
+    while let Some(span) = spans.iter().find(|span| span.start < cut && span.end > cut) {
+        cut = span.start;
+    }
+    cut
+}
+
+/// Observer payload for a run of `name` delta text, redacted.
+fn delta_payload(name: &str, id: Option<&str>, text: &str) -> Value {
+    if name == "tool.progress" {
+        json!({ "id": id, "output": redact_text(text) })
+    } else {
+        json!({ "content": redact_text(text) })
+    }
+}
+
+fn is_delta(event: &str) -> bool {
+    matches!(event, "message.delta" | "thinking.delta" | "tool.progress")
+}
+
+/// Fold `next` into the backlog entry `last` when both are deltas of the
+/// same stream, keeping the newer seq so `since_seq` resumption stays
+/// monotonic. Returns whether `next` was merged.
+fn merge_delta(last: &mut ShareEvent, next: &ShareEvent) -> bool {
+    if last.event != next.event || !is_delta(&next.event) {
+        return false;
+    }
+    let key = if next.event == "tool.progress" {
+        "output"
+    } else {
+        "content"
+    };
+    let same_tool = last.payload.get("id") == next.payload.get("id");
+    let appended = next.payload.get(key).and_then(Value::as_str);
+    if let (true, Some(appended), Some(Value::String(text))) =
+        (same_tool, appended, last.payload.get_mut(key))
+    {
+        text.push_str(appended);
+        last.seq = next.seq;
+        last.timestamp.clone_from(&next.timestamp);
+        return true;
+    }
+    false
+}
+
+/// Stream name, tool id, and raw text of a delta observers may see. Deltas
+/// are redacted per stream in [`ShareHub::push_delta`], not one by one.
+fn stream_delta(
+    event: &EngineEvent,
+    redaction: ShareRedaction,
+) -> Option<(&'static str, Option<&str>, &str)> {
+    match event {
+        EngineEvent::MessageDelta { content, .. } => Some(("message.delta", None, content)),
+        EngineEvent::ThinkingDelta { content, .. } if redaction.reasoning => {
+            Some(("thinking.delta", None, content))
+        }
+        EngineEvent::ToolCallProgress { id, output } if redaction.tool_output => {
+            Some(("tool.progress", Some(id), output))
+        }
+        _ => None,
+    }
+}
+
+/// Observer view of a non-delta engine event, or `None` for events
+/// observers never see (telemetry, UI plumbing, and anything the share
+/// withholds).
+fn redact_engine_event(
+    event: &EngineEvent,
+    redaction: ShareRedaction,
+) -> Option<(&'static str, Value)> {
+    let mapped = match event {
+        EngineEvent::MessageComplete { .. } => ("message.completed", json!({})),
+        EngineEvent::ThinkingStarted { .. } => ("thinking.started", json!({})),
+        EngineEvent::ThinkingComplete { .. } => ("thinking.completed", json!({})),
+        EngineEvent::ToolCallStarted { id, name, input } => (
+            "tool.started",
+            json!({ "id": id, "name": name, "input": redact_value(input) }),
+        ),
+        EngineEvent::ToolCallComplete { id, name, result } => {
+            let (success, output) = match result {
+                Ok(result) => (result.success, result.content.clone()),
+                Err(err) => (false, err.to_string()),
+            };
+            let mut payload = json!({ "id": id, "name": name, "success": success });
+            if redaction.tool_output {
+                payload["output"] = json!(truncate_with_ellipsis(
+                    &redact_text(&output),
+                    TOOL_OUTPUT_MAX_LEN,
+                    TRUNCATED
+                ));
+            }
+            ("tool.completed", payload)
+        }
+        EngineEvent::TurnStarted { turn_id } => ("turn.started", json!({ "turn_id": turn_id })),
+        EngineEvent::TurnComplete {
+            usage,
+            status,
+            error,
+        } => (
+            "turn.completed",
+            json!({
+                "status": format!("{status:?}").to_ascii_lowercase(),
+                "error": error.as_deref().map(redact_text),
+                "usage": {
+                    "input_tokens": usage.input_tokens,
+                    "output_tokens": usage.output_tokens,
+                },
+            }),
+        ),
+        EngineEvent::ApprovalRequired {
+            id,
+            tool_name,
+            description,
+            ..
+        } => (
+            "approval.required",
+            json!({
+                "id": id,
+                "tool_name": tool_name,
+                "description": redact_text(description),
+            }),
+        ),
+        EngineEvent::UserInputRequired { id, .. } => ("input.required", json!({ "id": id })),
+        EngineEvent::AgentSpawned { id, .. } => ("agent.started", json!({ "id": id })),
+        EngineEvent::AgentComplete { id, .. } => ("agent.completed", json!({ "id": id })),
+        EngineEvent::CompactionCompleted { message, .. } | EngineEvent::Status { message } => {
+            ("status", json!({ "message": redact_text(message) }))
+        }
+        EngineEvent::Error { envelope, .. } => (
+            "error",
+            json!({ "message": redact_text(&envelope.message) }),
+        ),
+        _ => return None,
+    };
+    Some(mapped)
+}
+
+/// Redact a tool's arguments with [`redact_json`] and cap every string.
+fn redact_value(value: &Value) -> Value {
+    let mut value = value.clone();
+    redact_json(&mut value);
+    truncate_strings(&mut value);
+    value
+}
+
+fn truncate_strings(value: &mut Value) {
+    match value {
+        Value::String(text) => *text = truncate_with_ellipsis(text, TOOL_INPUT_MAX_LEN, TRUNCATED),
+        Value::Array(items) => items.iter_mut().for_each(truncate_strings),
+        Value::Object(map) => map.values_mut().for_each(truncate_strings),
+        _ => {}
+    }
+}
+
+/// A running observer server. Dropping it does not stop the server; call
+/// [`ShareServer::stop`].
+pub struct ShareServer {
+    hub: Arc<ShareHub>,
+    addr: SocketAddr,
+    cancel: CancellationToken,
+}
+
+impl ShareServer {
+    #[must_use]
+    pub fn hub(&self) -> &Arc<ShareHub> {
+        &self.hub
+    }
+
+    /// Browser viewer URL, token included.
+    #[must_use]
+    pub fn viewer_url(&self) -> String {
+        format!("http://{}/share/{}", self.addr, self.hub.token())
+    }
+
+    /// SSE endpoint URL, token included.
+    #[must_use]
+    pub fn events_url(&self) -> String {
+        format!("http://{}/v1/share/{}/events", self.addr, self.hub.token())
+    }
+
+    /// Stop serving and end every open observer stream.
+    pub fn stop(&self) {
+        self.cancel.cancel();
+    }
+}
+
+/// Bind `host:port` (port 0 picks a free one) and serve `hub` to observers
+/// in the background. Must be called inside a Tokio runtime.
+pub fn start_share_server(hub: Arc<ShareHub>, host: &str, port: u16) -> Result<ShareServer> {
+    let addr: SocketAddr = format!("{host}:{port}")
+        .parse()
+        .with_context(|| format!("Invalid bind address '{host}:{port}'"))?;
+    let listener =
+        std::net::TcpListener::bind(addr).with_context(|| format!("Failed to bind {addr}"))?;
+    listener.set_nonblocking(true)?;
+    let addr = listener.local_addr()?;
+    let listener = tokio::net::TcpListener::from_std(listener)?;
+
+    let cancel = CancellationToken::new();
+    let router = share_router(ShareState {
+        hub: hub.clone(),
+        cancel: cancel.clone(),
+    });
+    let shutdown = cancel.clone();
+    tokio::spawn(async move {
+        let served = axum::serve(listener, router)
+            .with_graceful_shutdown(shutdown.cancelled_owned())
+            .await;
+        if let Err(err) = served {
+            tracing::warn!("Share server stopped: {err}");
+        }
+    });
+    Ok(ShareServer { hub, addr, cancel })
+}
+
+#[derive(Clone)]
+struct ShareState {
+    hub: Arc<ShareHub>,
+    cancel: CancellationToken,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShareQuery {
+    since_seq: Option<u64>,
+}
+
+/// Clients that can set headers use the plain routes with
+/// `Authorization: Bearer <token>` (or `x-deepseek-share-token`); browsers,
+/// whose `EventSource` cannot, put the token in the path.
+fn share_router(state: ShareState) -> Router {
+    Router::new()
+        .route("/share/{token}", get(share_viewer))
+        .route("/v1/share/events", get(share_events))
+        .route("/v1/share/transcript", get(share_transcript))
+        .route("/v1/share/{token}/events", get(share_events_at))
+        .route("/v1/share/{token}/transcript", get(share_transcript_at))
+        .layer(cors_layer(&[]))
+        .with_state(state)
+}
+
+fn token_from_headers(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|raw| raw.strip_prefix("Bearer "))
+        .or_else(|| {
+            headers
+                .get("x-deepseek-share-token")
+                .and_then(|value| value.to_str().ok())
+        })
+}
+
+fn check_token(state: &ShareState, token: Option<&str>) -> Result<(), ApiError> {
+    if token.is_some_and(|token| super::access::tokens_match(token, state.hub.token())) {
+        Ok(())
+    } else {
+        Err(ApiError::unauthorized("share token required"))
+    }
+}
+
+async fn share_events(
+    State(state): State<ShareState>,
+    headers: HeaderMap,
+    Query(query): Query<ShareQuery>,
+) -> Result<Sse<impl futures_util::Stream<Item = Result<SseEvent, Infallible>>>, ApiError> {
+    check_token(&state, token_from_headers(&headers))?;
+    Ok(event_stream(state, query))
+}
+
+async fn share_events_at(
+    State(state): State<ShareState>,
+    Path(token): Path<String>,
+    Query(query): Query<ShareQuery>,
+) -> Result<Sse<impl futures_util::Stream<Item = Result<SseEvent, Infallible>>>, ApiError> {
+    check_token(&state, Some(&token))?;
+    Ok(event_stream(state, query))
+}
+
+fn event_stream(
+    state: ShareState,
+    query: ShareQuery,
+) -> Sse<impl futures_util::Stream<Item = Result<SseEvent, Infallible>>> {
+    // Subscribe before reading the backlog so nothing published in between
+    // is lost; the seq check drops the overlap.
+    let mut live = state.hub.live.subscribe();
+    let backlog = state.hub.backlog_since(query.since_seq.unwrap_or(0));
+    let mut last_seq = backlog
+        .last()
+        .map_or(query.since_seq.unwrap_or(0), |event| event.seq);
+    let cancel = state.cancel.clone();
+    let stream = stream! {
+        for event in backlog {
+            yield Ok(sse_json(&event.event, json!(event)));
+        }
+        loop {
+            let incoming = tokio::select! {
+                () = cancel.cancelled() => break,
+                incoming = live.recv() => incoming,
+            };
+            let event = match incoming {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    // The observer fell too far behind to replay the gap, so
+                    // replace its view with the current backlog instead.
+                    let snapshot = state.hub.backlog_since(0);
+                    last_seq = snapshot.last().map_or(last_seq, |event| event.seq);
+                    yield Ok(sse_json(
+                        "share.resync",
+                        json!({
+                            "seq": last_seq,
+                            "timestamp": Utc::now().to_rfc3339(),
+                            "event": "share.resync",
+                            "payload": { "events": snapshot },
+                        }),
+                    ));
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            if event.seq <= last_seq {
+                continue;
+            }
+            last_seq = event.seq;
+            yield Ok(sse_json(&event.event, json!(event)));
+        }
+        yield Ok(sse_json("share.stopped", json!({})));
+    };
+    Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(Duration::from_secs(15))
+            .text("keepalive"),
+    )
+}
+
+async fn share_transcript(
+    State(state): State<ShareState>,
+    headers: HeaderMap,
+    Query(query): Query<ShareQuery>,
+) -> Result<Json<Vec<ShareEvent>>, ApiError> {
+    check_token(&state, token_from_headers(&headers))?;
+    Ok(Json(state.hub.backlog_since(query.since_seq.unwrap_or(0))))
+}
+
+async fn share_transcript_at(
+    State(state): State<ShareState>,
+    Path(token): Path<String>,
+    Query(query): Query<ShareQuery>,
+) -> Result<Json<Vec<ShareEvent>>, ApiError> {
+    check_token(&state, Some(&token))?;
+    Ok(Json(state.hub.backlog_since(query.since_seq.unwrap_or(0))))
+}
+
+/// The viewer page carries no data; it reads its token from the URL and
+/// streams through the events route.
+async fn share_viewer(
+    State(state): State<ShareState>,
+    Path(token): Path<String>,
+) -> Result<Response, ApiError> {
+    check_token(&state, Some(&token))?;
+    Ok(Html(VIEWER_HTML).into_response())
+}
+
+const VIEWER_HTML: &str = r#"<!doctype html>
+<html><head><meta charset="utf-8"><title>DeepSeek TUI — shared session</title>
+<style>
+body{font:14px/1.5 ui-monospace,monospace;background:#0b1020;color:#d8dee9;margin:0;padding:1rem 2rem}
+.e{white-space:pre-wrap;margin:.4rem 0}.user{color:#8fbcbb}.tool{color:#81a1c1}
+.muted{color:#6b7280}.err{color:#bf616a}
+</style></head><body><h3>Shared session <span class="muted">(read-only)</span></h3><div id="log"></div>
+<script>
+const token=location.pathname.split("/").pop();
+const log=document.getElementById("log");let cur=null;
+function add(cls,text){const d=document.createElement("div");d.className="e "+cls;d.textContent=text;log.appendChild(d);window.scrollTo(0,document.body.scrollHeight);return d;}
+const es=new EventSource("/v1/share/"+encodeURIComponent(token)+"/events");
+const h={};
+const on=(n,f)=>{h[n]=f;es.addEventListener(n,m=>f(JSON.parse(m.data).payload));};
+on("user.message",p=>{cur=null;add("user","› "+p.content);});
+on("message",p=>{cur=null;add("",p.content);});
+on("message.delta",p=>{if(!cur)cur=add("","");cur.textContent+=p.content;});
+on("message.completed",()=>{cur=null;});
+on("thinking.started",()=>{cur=null;add("muted","thinking…");});
+on("tool.started",p=>{cur=null;add("tool","⚙ "+p.name+" "+JSON.stringify(p.input));});
+on("tool.completed",p=>{add(p.success?"muted":"err",(p.success?"✓ ":"✗ ")+p.name+(p.output?"\n"+p.output:""));});
+on("approval.required",p=>add("muted","waiting for approval: "+p.tool_name));
+on("turn.completed",p=>{cur=null;add("muted","— turn "+p.status+" —");});
+on("status",p=>add("muted",p.message));
+on("error",p=>{if(p)add("err",p.message);});
+on("share.resync",p=>{log.textContent="";cur=null;p.events.forEach(e=>h[e.event]&&h[e.event](e.payload));});
+on("share.stopped",()=>{add("muted","Sharing stopped.");es.close();});
+</script></body></html>"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::spec::ToolResult;
+
+    #[test]
+    fn events_are_redacted_before_they_reach_observers() {
+        let hub = ShareHub::new(ShareRedaction::default());
+        hub.publish_engine_event(&EngineEvent::ThinkingDelta {
+            index: 0,
+            content: "private reasoning".to_string(),
+        });
+        hub.publish_engine_event(&EngineEvent::ToolCallStarted {
+            id: "t1".to_string(),
+            name: "exec_shell".to_string(),
+            input: json!({
+                "command": "curl -H 'api_key=sk-live-123456' example.com",
+                "env": { "GITHUB_TOKEN": "plainvalue" },
+            }),
+        });
+        hub.publish_engine_event(&EngineEvent::ToolCallComplete {
+            id: "t1".to_string(),
+            name: "exec_shell".to_string(),
+            result: Ok(ToolResult::success("x".repeat(TOOL_OUTPUT_MAX_LEN + 10))),
+        });
+
+        let events = hub.backlog_since(0);
+        let names: Vec<&str> = events.iter().map(|e| e.event.as_str()).collect();
+        assert_eq!(names, ["tool.started", "tool.completed"]);
+        let command = events[0].payload["input"]["command"].as_str().unwrap();
+        assert!(!command.contains("sk-live-123456"), "{command}");
+        assert_eq!(
+            events[0].payload["input"]["env"]["GITHUB_TOKEN"],
+            "<redacted>"
+        );
+        let output = events[1].payload["output"].as_str().unwrap();
+        assert!(output.ends_with("[truncated]"));
+
+        let quiet = ShareHub::new(ShareRedaction {
+            reasoning: true,
+            tool_output: false,
+        });
+        quiet.publish_engine_event(&EngineEvent::ThinkingDelta {
+            index: 0,
+            content: "visible".to_string(),
+        });
+        quiet.publish_engine_event(&EngineEvent::ToolCallComplete {
+            id: "t1".to_string(),
+            name: "read_file".to_string(),
+            result: Ok(ToolResult::success("contents")),
+        });
+        let events = quiet.backlog_since(0);
+        assert_eq!(events[0].event, "thinking.delta");
+        assert_eq!(events[0].payload["content"], "visible");
+        assert!(events[1].payload.get("output").is_none());
+    }
+
+    #[test]
+    fn backlog_merges_consecutive_deltas_and_keeps_the_latest_seq() {
+        let hub = ShareHub::new(ShareRedaction::default());
+        hub.publish_user_message("hi");
+        let long = "a".repeat(STREAM_HOLDBACK + 10);
+        for chunk in [long.as_str(), "Hel", "lo"] {
+            hub.publish_engine_event(&EngineEvent::MessageDelta {
+                index: 0,
+                content: chunk.to_string(),
+            });
+        }
+        // Only text older than the held-back tail is out so far.
+        let events = hub.backlog_since(0);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].payload["content"], "a".repeat(15));
+
+        hub.publish_engine_event(&EngineEvent::MessageComplete { index: 0 });
+        let events = hub.backlog_since(0);
+        let names: Vec<&str> = events.iter().map(|e| e.event.as_str()).collect();
+        assert_eq!(
+            names,
+            ["user.message", "message.delta", "message.completed"]
+        );
+        assert_eq!(events[1].payload["content"], format!("{long}Hello"));
+        assert_eq!(events[1].seq, 5);
+        assert!(hub.backlog_since(5).iter().all(|e| e.seq == 6));
+    }
+
+    #[test]
+    fn secrets_split_across_deltas_are_masked() {
+        let hub = ShareHub::new(ShareRedaction::default());
+        let mut live = hub.live.subscribe();
+        let filler = "x ".repeat(STREAM_HOLDBACK);
+        for chunk in [
+            filler.as_str(),
+            "export API_KEY=sk-li",
+            "ve-1234",
+            "5678 done",
+        ] {
+            hub.publish_engine_event(&EngineEvent::MessageDelta {
+                index: 0,
+                content: chunk.to_string(),
+            });
+        }
+        hub.publish_engine_event(&EngineEvent::ToolCallProgress {
+            id: "t1".to_string(),
+            output: "Authorization: Bearer abc".to_string(),
+        });
+        hub.publish_engine_event(&EngineEvent::ToolCallProgress {
+            id: "t1".to_string(),
+            output: "def123\n".to_string(),
+        });
+        hub.publish_engine_event(&EngineEvent::TurnStarted {
+            turn_id: "turn_2".to_string(),
+        });
+
+        let mut streamed = String::new();
+        while let Ok(event) = live.try_recv() {
+            streamed.push_str(&event.payload.to_string());
+        }
+        let backlog = serde_json::to_string(&hub.backlog_since(0)).unwrap();
+        for text in [&streamed, &backlog] {
+            for fragment in ["sk-li", "ve-1234", "5678", "abc", "def123"] {
+                assert!(!text.contains(fragment), "{fragment} leaked: {text}");
+            }
+            assert!(text.contains("<redacted>"), "{text}");
+        }
+        assert!(backlog.contains(" done"));
+    }
+
+    #[tokio::test]
+    async fn server_requires_the_token_and_is_read_only() -> Result<()> {
+        let hub = ShareHub::new(ShareRedaction::default());
+        hub.publish_user_message("watch this");
+        let server = start_share_server(hub.clone(), "127.0.0.1", 0)?;
+        let base = format!("http://{}", server.addr);
+        let client = reqwest::Client::new();
+
+        for url in [
+            format!("{base}/v1/share/wrong/transcript"),
+            format!("{base}/v1/share/transcript?token={}", hub.token()),
+            format!("{base}/share/wrong"),
+        ] {
+            let denied = client.get(url).send().await?;
+            assert_eq!(denied.status(), reqwest::StatusCode::UNAUTHORIZED);
+        }
+
+        let transcript: Vec<Value> = client
+            .get(format!("{base}/v1/share/transcript"))
+            .bearer_auth(hub.token())
+            .send()
+            .await?
+            .json()
+            .await?;
+        assert_eq!(transcript[0]["payload"]["content"], "watch this");
+        let viewer = client.get(server.viewer_url()).send().await?;
+        assert_eq!(viewer.status(), reqwest::StatusCode::OK);
+
+        let write = client
+            .post(format!("{base}/v1/share/{}/events", hub.token()))
+            .send()
+            .await?;
+        assert_eq!(write.status(), reqwest::StatusCode::METHOD_NOT_ALLOWED);
+
+        let mut events = client.get(server.events_url()).send().await?;
+        let first = events.chunk().await?.unwrap_or_default();
+        assert!(String::from_utf8_lossy(&first).contains("event: user.message"));
+        server.stop();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lagging_observers_get_a_snapshot_instead_of_a_gap() -> Result<()> {
+        let hub = ShareHub::new(ShareRedaction::default());
+        hub.publish_user_message("start");
+        let server = start_share_server(hub.clone(), "127.0.0.1", 0)?;
+        let mut events = reqwest::Client::new()
+            .get(server.events_url())
+            .send()
+            .await?;
+        let first = events.chunk().await?.unwrap_or_default();
+        assert!(String::from_utf8_lossy(&first).contains("event: user.message"));
+
+        // Publishing without yielding overruns the observer's channel.
+        for i in 0..1_100 {
+            hub.publish_engine_event(&EngineEvent::Status {
+                message: format!("step {i}"),
+            });
+        }
+        let mut received = String::new();
+        while !received.contains("step 1099") {
+            let chunk = tokio::time::timeout(Duration::from_secs(5), events.chunk())
+                .await??
+                .unwrap_or_default();
+            assert!(!chunk.is_empty(), "stream ended without a resync");
+            received.push_str(&String::from_utf8_lossy(&chunk));
+        }
+        assert!(received.contains("event: share.resync"), "{received}");
+        server.stop();
+        Ok(())
+    }
+}
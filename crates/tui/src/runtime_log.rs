@@ -210,6 +210,27 @@ fn prune_old_logs(log_dir: &Path, retention_days: u64) -> std::io::Result<usize>
     Ok(removed)
 }
 
+/// The newest `limit` TUI log files, most recently modified first.
+pub(crate) fn recent_log_files(limit: usize) -> Vec<PathBuf> {
+    let Some(entries) = log_directory().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(SystemTime, PathBuf)> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| is_tui_log_file_name(&entry.file_name()))
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .collect();
+    files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    files
+        .into_iter()
+        .take(limit)
+        .map(|(_, path)| path)
+        .collect()
+}
+
 fn is_tui_log_file_name(file_name: &std::ffi::OsStr) -> bool {
     file_name
         .to_str()
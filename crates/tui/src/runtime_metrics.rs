@@ -0,0 +1,333 @@
+//! Prometheus metrics for `deepseek serve --http`.
+//!
+//! The thread manager records turn, token, tool, and latency samples as it
+//! drains engine events; the runtime API renders them (plus point-in-time
+//! gauges it reads at scrape time) in the Prometheus text exposition format
+//! at `GET /metrics`. Everything lives in process memory and resets on
+//! restart, which is what Prometheus counters expect.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::models::Usage;
+
+/// Upper bounds (seconds) for time-to-first-token buckets.
+const API_LATENCY_BUCKETS: &[f64] = &[0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0, 60.0];
+/// Upper bounds (seconds) for whole-turn duration buckets.
+const TURN_DURATION_BUCKETS: &[f64] = &[1.0, 5.0, 15.0, 30.0, 60.0, 120.0, 300.0, 900.0];
+
+pub type SharedRuntimeMetrics = Arc<RuntimeMetrics>;
+
+/// Point-in-time values read when `/metrics` is scraped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuntimeGauges {
+    /// Threads with a loaded engine.
+    pub active_sessions: usize,
+    /// Threads with a turn currently running.
+    pub turns_in_progress: usize,
+    /// Durable tasks waiting for a worker.
+    pub queued_tasks: usize,
+    /// Durable tasks currently executing.
+    pub running_tasks: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct RuntimeMetrics {
+    state: Mutex<MetricsState>,
+}
+
+#[derive(Debug)]
+struct MetricsState {
+    turns_started: u64,
+    turns_finished: BTreeMap<&'static str, u64>,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_hit_tokens: u64,
+    tool_calls: BTreeMap<String, u64>,
+    tool_errors: BTreeMap<String, u64>,
+    api_latency: Histogram,
+    turn_duration: Histogram,
+}
+
+impl Default for MetricsState {
+    fn default() -> Self {
+        Self {
+            turns_started: 0,
+            turns_finished: BTreeMap::new(),
+            input_tokens: 0,
+            output_tokens: 0,
+            cache_hit_tokens: 0,
+            tool_calls: BTreeMap::new(),
+            tool_errors: BTreeMap::new(),
+            api_latency: Histogram::new(API_LATENCY_BUCKETS),
+            turn_duration: Histogram::new(TURN_DURATION_BUCKETS),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Histogram {
+    bounds: &'static [f64],
+    /// Non-cumulative count per bucket; the last slot is `+Inf`.
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            buckets: vec![0; bounds.len() + 1],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, seconds: f64) {
+        let slot = self
+            .bounds
+            .iter()
+            .position(|bound| seconds <= *bound)
+            .unwrap_or(self.bounds.len());
+        self.buckets[slot] += 1;
+        self.sum += seconds;
+        self.count += 1;
+    }
+
+    fn render(&self, out: &mut String, name: &str, help: &str) {
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        let mut cumulative = 0;
+        for (bound, count) in self.bounds.iter().zip(&self.buckets) {
+            cumulative += count;
+            let _ = writeln!(out, "{name}_bucket{{le=\"{bound}\"}} {cumulative}");
+        }
+        let _ = writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {}", self.count);
+        let _ = writeln!(out, "{name}_sum {}", self.sum);
+        let _ = writeln!(out, "{name}_count {}", self.count);
+    }
+}
+
+impl RuntimeMetrics {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_state(&self, f: impl FnOnce(&mut MetricsState)) {
+        if let Ok(mut state) = self.state.lock() {
+            f(&mut state);
+        }
+    }
+
+    pub fn record_turn_started(&self) {
+        self.with_state(|state| state.turns_started += 1);
+    }
+
+    /// Time from turn start to the first streamed model output.
+    pub fn record_first_output(&self, latency: Duration) {
+        self.with_state(|state| state.api_latency.observe(latency.as_secs_f64()));
+    }
+
+    pub fn record_tool_call(&self, tool: &str, success: bool) {
+        self.with_state(|state| {
+            *state.tool_calls.entry(tool.to_string()).or_default() += 1;
+            if !success {
+                *state.tool_errors.entry(tool.to_string()).or_default() += 1;
+            }
+        });
+    }
+
+    pub fn record_turn_finished(
+        &self,
+        status: &'static str,
+        usage: Option<&Usage>,
+        duration: Duration,
+    ) {
+        self.with_state(|state| {
+            *state.turns_finished.entry(status).or_default() += 1;
+            state.turn_duration.observe(duration.as_secs_f64());
+            if let Some(usage) = usage {
+                state.input_tokens += u64::from(usage.input_tokens);
+                state.output_tokens += u64::from(usage.output_tokens);
+                state.cache_hit_tokens += u64::from(usage.prompt_cache_hit_tokens.unwrap_or(0));
+            }
+        });
+    }
+
+    /// Render every metric in the Prometheus text exposition format.
+    #[must_use]
+    pub fn render(&self, gauges: RuntimeGauges) -> String {
+        let mut out = String::new();
+        gauge(
+            &mut out,
+            "deepseek_active_sessions",
+            "Runtime threads with a loaded engine.",
+            gauges.active_sessions,
+        );
+        gauge(
+            &mut out,
+            "deepseek_turns_in_progress",
+            "Turns currently running.",
+            gauges.turns_in_progress,
+        );
+        let _ = writeln!(
+            out,
+            "# HELP deepseek_task_queue_depth Durable tasks by scheduler state."
+        );
+        let _ = writeln!(out, "# TYPE deepseek_task_queue_depth gauge");
+        let _ = writeln!(
+            out,
+            "deepseek_task_queue_depth{{state=\"queued\"}} {}",
+            gauges.queued_tasks
+        );
+        let _ = writeln!(
+            out,
+            "deepseek_task_queue_depth{{state=\"running\"}} {}",
+            gauges.running_tasks
+        );
+
+        let Ok(state) = self.state.lock() else {
+            return out;
+        };
+        counter(
+            &mut out,
+            "deepseek_turns_started_total",
+            "Turns started since the server came up.",
+            state.turns_started,
+        );
+        let _ = writeln!(
+            out,
+            "# HELP deepseek_turns_finished_total Finished turns by final status."
+        );
+        let _ = writeln!(out, "# TYPE deepseek_turns_finished_total counter");
+        for (status, count) in &state.turns_finished {
+            let _ = writeln!(
+                out,
+                "deepseek_turns_finished_total{{status=\"{status}\"}} {count}"
+            );
+        }
+        let _ = writeln!(
+            out,
+            "# HELP deepseek_tokens_total Model tokens consumed by finished turns."
+        );
+        let _ = writeln!(out, "# TYPE deepseek_tokens_total counter");
+        for (kind, count) in [
+            ("input", state.input_tokens),
+            ("output", state.output_tokens),
+            ("cache_hit", state.cache_hit_tokens),
+        ] {
+            let _ = writeln!(out, "deepseek_tokens_total{{kind=\"{kind}\"}} {count}");
+        }
+        labelled_counter(
+            &mut out,
+            "deepseek_tool_calls_total",
+            "Completed tool calls by tool.",
+            &state.tool_calls,
+        );
+        labelled_counter(
+            &mut out,
+            "deepseek_tool_errors_total",
+            "Failed tool calls by tool.",
+            &state.tool_errors,
+        );
+        state.api_latency.render(
+            &mut out,
+            "deepseek_api_first_output_seconds",
+            "Time from turn start to the first streamed model output.",
+        );
+        state.turn_duration.render(
+            &mut out,
+            "deepseek_turn_duration_seconds",
+            "Wall-clock duration of finished turns.",
+        );
+        out
+    }
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, value: usize) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} gauge");
+    let _ = writeln!(out, "{name} {value}");
+}
+
+fn counter(out: &mut String, name: &str, help: &str, value: u64) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} counter");
+    let _ = writeln!(out, "{name} {value}");
+}
+
+fn labelled_counter(out: &mut String, name: &str, help: &str, values: &BTreeMap<String, u64>) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} counter");
+    for (tool, count) in values {
+        let _ = writeln!(out, "{name}{{tool=\"{}\"}} {count}", escape_label(tool));
+    }
+}
+
+/// Escape a label value per the exposition format (`\`, `"`, newline).
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_exposes_counters_gauges_and_cumulative_histograms() {
+        let metrics = RuntimeMetrics::new();
+        metrics.record_turn_started();
+        metrics.record_first_output(Duration::from_millis(300));
+        metrics.record_tool_call("exec_shell", true);
+        metrics.record_tool_call("exec_shell", false);
+        metrics.record_tool_call("mcp_\"odd\"", true);
+        metrics.record_turn_finished(
+            "completed",
+            Some(&Usage {
+                input_tokens: 120,
+                output_tokens: 30,
+                prompt_cache_hit_tokens: Some(100),
+                ..Usage::default()
+            }),
+            Duration::from_secs(7),
+        );
+
+        let text = metrics.render(RuntimeGauges {
+            active_sessions: 2,
+            turns_in_progress: 1,
+            queued_tasks: 3,
+            running_tasks: 0,
+        });
+
+        for line in [
+            "deepseek_active_sessions 2",
+            "deepseek_turns_in_progress 1",
+            "deepseek_task_queue_depth{state=\"queued\"} 3",
+            "deepseek_turns_started_total 1",
+            "deepseek_turns_finished_total{status=\"completed\"} 1",
+            "deepseek_tokens_total{kind=\"input\"} 120",
+            "deepseek_tokens_total{kind=\"cache_hit\"} 100",
+            "deepseek_tool_calls_total{tool=\"exec_shell\"} 2",
+            "deepseek_tool_errors_total{tool=\"exec_shell\"} 1",
+            "deepseek_tool_calls_total{tool=\"mcp_\\\"odd\\\"\"} 1",
+            "deepseek_api_first_output_seconds_bucket{le=\"0.25\"} 0",
+            "deepseek_api_first_output_seconds_bucket{le=\"0.5\"} 1",
+            "deepseek_api_first_output_seconds_bucket{le=\"+Inf\"} 1",
+            "deepseek_turn_duration_seconds_bucket{le=\"5\"} 0",
+            "deepseek_turn_duration_seconds_bucket{le=\"15\"} 1",
+            "deepseek_turn_duration_seconds_count 1",
+        ] {
+            assert!(
+                text.lines().any(|l| l == line),
+                "missing `{line}` in:\n{text}"
+            );
+        }
+    }
+}
@@ -15,7 +15,7 @@ use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex as StdMutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result, anyhow, bail};
 use chrono::{DateTime, Utc};
@@ -32,7 +32,9 @@ use crate::core::engine::{EngineConfig, EngineHandle, spawn_engine};
 use crate::core::events::{Event as EngineEvent, TurnOutcomeStatus};
 use crate::core::ops::Op;
 use crate::models::{ContentBlock, Message, SystemPrompt, Usage, compaction_threshold_for_model};
+use crate::runtime_metrics::{RuntimeMetrics, SharedRuntimeMetrics};
 use crate::tools::plan::new_shared_plan_state;
+use crate::tools::session_changes::new_shared_session_changes;
 use crate::tools::subagent::SubAgentStatus;
 use crate::tools::todo::new_shared_todo_list;
 use crate::tui::app::AppMode;
@@ -83,6 +85,21 @@ pub enum RuntimeTurnStatus {
     Canceled,
 }
 
+impl RuntimeTurnStatus {
+    /// Stable snake_case name, matching the serialized form.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::InProgress => "in_progress",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Interrupted => "interrupted",
+            Self::Canceled => "canceled",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum TurnItemKind {
@@ -731,6 +748,7 @@ pub struct RuntimeThreadManager {
     task_manager: Arc<StdMutex<Option<crate::task_manager::SharedTaskManager>>>,
     automations: Arc<StdMutex<Option<crate::automation_manager::SharedAutomationManager>>>,
     pending_approvals: Arc<StdMutex<HashMap<String, oneshot::Sender<ExternalApprovalDecision>>>>,
+    metrics: SharedRuntimeMetrics,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -765,6 +783,7 @@ impl RuntimeThreadManager {
             task_manager: Arc::new(StdMutex::new(None)),
             automations: Arc::new(StdMutex::new(None)),
             pending_approvals: Arc::new(StdMutex::new(HashMap::new())),
+            metrics: Arc::new(RuntimeMetrics::new()),
         };
         manager.recover_interrupted_state()?;
         Ok(manager)
@@ -788,6 +807,23 @@ impl RuntimeThreadManager {
         }
     }
 
+    /// Metrics registry fed by turn monitoring, rendered at `GET /metrics`.
+    #[must_use]
+    pub fn metrics(&self) -> SharedRuntimeMetrics {
+        self.metrics.clone()
+    }
+
+    /// Loaded engine count and how many of them are mid-turn.
+    pub async fn activity_counts(&self) -> (usize, usize) {
+        let active = self.active.lock().await;
+        let running = active
+            .engines
+            .values()
+            .filter(|state| state.active_turn.is_some())
+            .count();
+        (active.engines.len(), running)
+    }
+
     #[allow(dead_code)] // Public API for external callers (runtime API, task manager)
     pub fn shutdown(&self) {
         self.cancel_token.cancel();
@@ -1932,6 +1968,7 @@ impl RuntimeThreadManager {
             skills_dir: self.config.skills_dir(),
             instructions: self.config.instructions_paths(),
             project_context_pack_enabled: self.config.project_context_pack_enabled(),
+            repo_map_tokens: self.config.repo_map_tokens(),
             translation_enabled: false,
             max_steps: 100,
             max_subagents: self.config.max_subagents().clamp(1, MAX_SUBAGENTS),
@@ -1943,6 +1980,12 @@ impl RuntimeThreadManager {
             ),
             todos: new_shared_todo_list(),
             plan_state: new_shared_plan_state(),
+            session_changes: new_shared_session_changes(),
+            exec_profile: crate::tools::exec_profile::new_shared_exec_profile(),
+            session_env: crate::tools::session_env::new_shared_session_env(
+                self.config.env.as_ref(),
+            ),
+            shell_limits: self.config.shell_limits(),
             max_spawn_depth: crate::tools::subagent::DEFAULT_MAX_SPAWN_DEPTH,
             network_policy,
             snapshots_enabled: self.config.snapshots_config().enabled,
@@ -1951,6 +1994,9 @@ impl RuntimeThreadManager {
                 .snapshots_config()
                 .max_workspace_gb
                 .saturating_mul(1024 * 1024 * 1024),
+            snapshots_auto_before_write: self.config.snapshots_config().auto_before_write,
+            tool_cache_scope: self.config.tool_cache_scope(),
+            prompt_layers: self.config.prompt_layer_overrides(),
             lsp_config,
             runtime_services: crate::tools::spec::RuntimeToolServices {
                 task_manager: self.task_manager.lock().ok().and_then(|slot| slot.clone()),
@@ -1967,10 +2013,14 @@ impl RuntimeThreadManager {
             subagent_api_timeout: std::time::Duration::from_secs(
                 self.config.subagent_api_timeout_secs(),
             ),
+            subagent_max_steps: self.config.subagent_max_steps(),
+            subagent_allowed_models: self.config.subagent_allowed_models(),
             memory_enabled: self.config.memory_enabled(),
             memory_path: self.config.memory_path(),
+            command_lessons_enabled: self.config.command_lessons_enabled(),
             vision_config: self.config.vision_model_config(),
             strict_tool_mode: self.config.strict_tool_mode.unwrap_or(false),
+            self_report: self.config.self_report.unwrap_or(false),
             goal_objective: None,
             locale_tag: crate::localization::resolve_locale(
                 &crate::settings::Settings::load().unwrap_or_default().locale,
@@ -1985,6 +2035,15 @@ impl RuntimeThreadManager {
                 .and_then(|s| s.provider)
                 .unwrap_or_default(),
             search_api_key: self.config.search.as_ref().and_then(|s| s.api_key.clone()),
+            edit_validation: self.config.edit_validation.clone().unwrap_or_default(),
+            tool_filters: self.config.tool_filters.clone().unwrap_or_default(),
+            trust_level: crate::trust_level::load(&thread.workspace).unwrap_or_default(),
+            model_presets: self.config.models.clone().unwrap_or_default(),
+            model_fallbacks: self.config.model_fallbacks.clone().unwrap_or_default(),
+            docs_index_on_start: false,
+            trail_enabled: self.config.trail_enabled(),
+            trail_branch: self.config.trail_branch(),
+            thinking: self.config.thinking.clone().unwrap_or_default(),
         };
 
         let engine = spawn_engine(engine_cfg, &self.config);
@@ -2072,6 +2131,9 @@ impl RuntimeThreadManager {
         let mut turn_usage: Option<Usage> = None;
         let mut turn_status = RuntimeTurnStatus::Completed;
         let mut turn_error: Option<String> = None;
+        let monitor_started = Instant::now();
+        let mut first_output_seen = false;
+        self.metrics.record_turn_started();
 
         loop {
             let event = {
@@ -2089,6 +2151,16 @@ impl RuntimeThreadManager {
                 break;
             };
 
+            if !first_output_seen
+                && matches!(
+                    event,
+                    EngineEvent::MessageStarted { .. } | EngineEvent::ThinkingStarted { .. }
+                )
+            {
+                first_output_seen = true;
+                self.metrics.record_first_output(monitor_started.elapsed());
+            }
+
             match event {
                 EngineEvent::TurnStarted { .. } => {
                     self.emit_event(
@@ -2258,6 +2330,10 @@ impl RuntimeThreadManager {
                     }
                 }
                 EngineEvent::ToolCallComplete { id, name, result } => {
+                    self.metrics.record_tool_call(
+                        &name,
+                        result.as_ref().is_ok_and(|output| output.success),
+                    );
                     if let Some(item_id) = tool_items.remove(&id) {
                         let mut item = self.store.load_item(&item_id)?;
                         let now = Utc::now();
@@ -2392,6 +2468,18 @@ impl RuntimeThreadManager {
                     )
                     .await?;
                 }
+                EngineEvent::ModelFallback {
+                    from, to, reason, ..
+                } => {
+                    self.emit_event(
+                        &thread_id,
+                        Some(&turn_id),
+                        None,
+                        "model.fallback",
+                        json!({ "from": from, "to": to, "reason": reason }),
+                    )
+                    .await?;
+                }
                 EngineEvent::CoherenceState {
                     state,
                     label,
@@ -2910,6 +2998,11 @@ impl RuntimeThreadManager {
         turn.status = turn_status;
         turn.ended_at = Some(ended_at);
         turn.duration_ms = turn.started_at.map(|start| duration_ms(start, ended_at));
+        self.metrics.record_turn_finished(
+            turn_status.as_str(),
+            turn_usage.as_ref(),
+            monitor_started.elapsed(),
+        );
         turn.usage = turn_usage;
         turn.error = turn_error;
         self.store.save_turn(&turn)?;
@@ -3112,7 +3205,10 @@ fn parse_mode(mode: &str) -> AppMode {
 
 fn tool_kind_for_name(name: &str) -> TurnItemKind {
     let lower = name.to_ascii_lowercase();
-    if lower == "exec_shell" || lower == "exec_shell_wait" || lower == "exec_shell_interact" {
+    if matches!(
+        lower.as_str(),
+        "exec_shell" | "exec_shell_wait" | "exec_shell_interact" | "shell_open"
+    ) {
         return TurnItemKind::CommandExecution;
     }
     if lower.contains("patch") || lower.contains("write") || lower.contains("edit") {
@@ -3677,6 +3773,13 @@ mod tests {
         let completed = wait_for_terminal_turn(&manager, &turn.id, Duration::from_secs(2)).await?;
         assert_eq!(completed.status, RuntimeTurnStatus::Completed);
 
+        let metrics = manager
+            .metrics()
+            .render(crate::runtime_metrics::RuntimeGauges::default());
+        assert!(metrics.contains("deepseek_turns_finished_total{status=\"completed\"} 1"));
+        assert!(metrics.contains("deepseek_tokens_total{kind=\"output\"} 12"));
+        assert!(metrics.contains("deepseek_api_first_output_seconds_count 1"));
+
         drop(manager);
 
         let reopened = test_manager(runtime_dir)?;
@@ -4582,6 +4685,7 @@ mod tests {
                 denial_reason: "sandbox denied".to_string(),
                 blocked_network: false,
                 blocked_write: false,
+                limit_hit: None,
             })
             .await?;
 
@@ -0,0 +1,389 @@
+//! Per-command resource limits for shell tools.
+//!
+//! Limits are configured per sandbox policy under `[shell_limits]` and
+//! applied to the spawned shell with `setrlimit` just before `exec`, so they
+//! hold for every process the command starts:
+//!
+//! - `cpu_seconds` — CPU time (`RLIMIT_CPU`); the process gets `SIGXCPU`.
+//! - `memory_mb` — address space (`RLIMIT_AS`); allocations start failing.
+//!   Linux enforces it; macOS accepts but ignores it.
+//! - `write_mb` — largest file the command may write (`RLIMIT_FSIZE`); the
+//!   write fails with `SIGXFSZ` / "File too large".
+//!
+//! Limits only exist on Unix and are not applied to TTY sessions, which are
+//! spawned through a PTY without a pre-exec hook.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::SandboxPolicy;
+
+/// Table key whose limits apply to every policy without its own entry.
+pub const DEFAULT_KEY: &str = "default";
+
+/// Keys accepted in the `[shell_limits]` table.
+pub const POLICY_KEYS: &[&str] = &[
+    DEFAULT_KEY,
+    "read-only",
+    "workspace-write",
+    "danger-full-access",
+    "external-sandbox",
+];
+
+/// Factor applied to a limit when the user re-runs with higher limits.
+const RAISE_FACTOR: u64 = 4;
+
+/// Memory-exhaustion messages printed by common runtimes when `RLIMIT_AS`
+/// makes an allocation fail.
+const OOM_MARKERS: &[&str] = &[
+    "cannot allocate memory",
+    "out of memory",
+    "memory allocation of",
+    "memoryerror",
+    "std::bad_alloc",
+    "javascript heap out of memory",
+];
+
+/// Resource caps for one shell command; `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceLimits {
+    /// Maximum CPU time in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_seconds: Option<u64>,
+    /// Maximum address space in MiB.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_mb: Option<u64>,
+    /// Maximum size of any file written, in MiB.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write_mb: Option<u64>,
+}
+
+/// Which limit stopped a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitKind {
+    Cpu,
+    Memory,
+    Disk,
+}
+
+impl LimitKind {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Memory => "memory",
+            Self::Disk => "disk",
+        }
+    }
+
+    /// Human label used in tool output and the elevation dialog.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Cpu => "CPU time",
+            Self::Memory => "memory",
+            Self::Disk => "file size",
+        }
+    }
+}
+
+impl fmt::Display for LimitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A limit a finished command ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LimitHit {
+    pub kind: LimitKind,
+    /// The configured value: seconds for CPU, MiB otherwise.
+    pub limit: u64,
+}
+
+impl fmt::Display for LimitHit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            LimitKind::Cpu => write!(f, "CPU time limit ({}s)", self.limit),
+            LimitKind::Memory => write!(f, "memory limit ({} MiB)", self.limit),
+            LimitKind::Disk => write!(f, "file size limit ({} MiB)", self.limit),
+        }
+    }
+}
+
+impl ResourceLimits {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cpu_seconds.is_none() && self.memory_mb.is_none() && self.write_mb.is_none()
+    }
+
+    #[must_use]
+    pub fn get(&self, kind: LimitKind) -> Option<u64> {
+        match kind {
+            LimitKind::Cpu => self.cpu_seconds,
+            LimitKind::Memory => self.memory_mb,
+            LimitKind::Disk => self.write_mb,
+        }
+    }
+
+    fn slot(&mut self, kind: LimitKind) -> &mut Option<u64> {
+        match kind {
+            LimitKind::Cpu => &mut self.cpu_seconds,
+            LimitKind::Memory => &mut self.memory_mb,
+            LimitKind::Disk => &mut self.write_mb,
+        }
+    }
+
+    /// `self` with unset fields filled from `fallback`.
+    #[must_use]
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            cpu_seconds: self.cpu_seconds.or(fallback.cpu_seconds),
+            memory_mb: self.memory_mb.or(fallback.memory_mb),
+            write_mb: self.write_mb.or(fallback.write_mb),
+        }
+    }
+
+    /// Config error for a zero limit, which would kill every command.
+    pub fn validate(&self, key: &str) -> Result<(), String> {
+        for kind in [LimitKind::Cpu, LimitKind::Memory, LimitKind::Disk] {
+            if self.get(kind) == Some(0) {
+                return Err(format!(
+                    "shell_limits.{key}: {kind} limit must be greater than zero"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Which limit, if any, explains how a command ended. `signal` is the
+    /// terminating signal when the shell itself was killed; `exit_code` covers
+    /// `sh -c` reporting a killed child as `128 + signal`.
+    #[must_use]
+    pub fn detect_hit(
+        &self,
+        exit_code: Option<i32>,
+        signal: Option<i32>,
+        stderr: &str,
+    ) -> Option<LimitHit> {
+        let hit = |kind| self.get(kind).map(|limit| LimitHit { kind, limit });
+        let killed_by = |sig: i32| signal == Some(sig) || exit_code == Some(128 + sig);
+        if exit_code == Some(0) {
+            return None;
+        }
+        if let Some(sig) = SIGXCPU
+            && killed_by(sig)
+        {
+            return hit(LimitKind::Cpu);
+        }
+        if self.write_mb.is_some()
+            && (SIGXFSZ.is_some_and(killed_by) || stderr.contains("File too large"))
+        {
+            return hit(LimitKind::Disk);
+        }
+        if self.memory_mb.is_some() {
+            let lower = stderr.to_ascii_lowercase();
+            if OOM_MARKERS.iter().any(|marker| lower.contains(marker)) {
+                return hit(LimitKind::Memory);
+            }
+        }
+        None
+    }
+
+    /// Install the limits on `cmd`; they take effect in the child before exec.
+    #[cfg(unix)]
+    pub fn apply(&self, cmd: &mut std::process::Command) {
+        use std::os::unix::process::CommandExt;
+
+        if self.is_empty() {
+            return;
+        }
+        let limits = *self;
+        // SAFETY: the hook only calls async-signal-safe `setrlimit`.
+        unsafe {
+            cmd.pre_exec(move || limits.set_rlimits());
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn apply(&self, _cmd: &mut std::process::Command) {}
+
+    #[cfg(unix)]
+    fn set_rlimits(&self) -> std::io::Result<()> {
+        const MIB: u64 = 1024 * 1024;
+        macro_rules! set_limit {
+            ($resource:expr, $soft:expr, $hard:expr) => {{
+                let rlim = libc::rlimit {
+                    rlim_cur: $soft as libc::rlim_t,
+                    rlim_max: $hard as libc::rlim_t,
+                };
+                // SAFETY: `rlim` is a valid, initialized rlimit struct.
+                if unsafe { libc::setrlimit($resource, &rlim) } != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }};
+        }
+        if let Some(secs) = self.cpu_seconds {
+            // One spare second between SIGXCPU and the hard-limit SIGKILL
+            // lets the signal, and so the hit, be reported.
+            set_limit!(libc::RLIMIT_CPU, secs, secs.saturating_add(1));
+        }
+        if let Some(mb) = self.memory_mb {
+            let bytes = mb.saturating_mul(MIB);
+            set_limit!(libc::RLIMIT_AS, bytes, bytes);
+        }
+        if let Some(mb) = self.write_mb {
+            let bytes = mb.saturating_mul(MIB);
+            set_limit!(libc::RLIMIT_FSIZE, bytes, bytes);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+const SIGXCPU: Option<i32> = Some(libc::SIGXCPU);
+#[cfg(not(unix))]
+const SIGXCPU: Option<i32> = None;
+#[cfg(unix)]
+const SIGXFSZ: Option<i32> = Some(libc::SIGXFSZ);
+#[cfg(not(unix))]
+const SIGXFSZ: Option<i32> = None;
+
+/// The `[shell_limits]` table: limits keyed by sandbox policy name, plus a
+/// `default` entry that fills fields a policy leaves unset.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShellLimits {
+    by_policy: BTreeMap<String, ResourceLimits>,
+}
+
+impl ShellLimits {
+    #[must_use]
+    pub fn new(by_policy: BTreeMap<String, ResourceLimits>) -> Self {
+        Self { by_policy }
+    }
+
+    /// Effective limits for commands run under `policy`.
+    #[must_use]
+    pub fn for_policy(&self, policy: &SandboxPolicy) -> ResourceLimits {
+        let default = self.by_policy.get(DEFAULT_KEY).copied().unwrap_or_default();
+        self.by_policy
+            .get(policy_key(policy))
+            .copied()
+            .map_or(default, |limits| limits.or(default))
+    }
+
+    /// Copy with every configured `kind` limit multiplied, for a retry after
+    /// the user picks "re-run with higher limits".
+    #[must_use]
+    pub fn raised(&self, kind: LimitKind) -> Self {
+        let mut raised = self.clone();
+        for limits in raised.by_policy.values_mut() {
+            let slot = limits.slot(kind);
+            *slot = slot.map(|value| value.saturating_mul(RAISE_FACTOR));
+        }
+        raised
+    }
+}
+
+/// `[shell_limits]` key for a policy.
+#[must_use]
+pub fn policy_key(policy: &SandboxPolicy) -> &'static str {
+    match policy {
+        SandboxPolicy::DangerFullAccess => "danger-full-access",
+        SandboxPolicy::ReadOnly => "read-only",
+        SandboxPolicy::ExternalSandbox { .. } => "external-sandbox",
+        SandboxPolicy::WorkspaceWrite { .. } => "workspace-write",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(cpu: Option<u64>, mem: Option<u64>, write: Option<u64>) -> ResourceLimits {
+        ResourceLimits {
+            cpu_seconds: cpu,
+            memory_mb: mem,
+            write_mb: write,
+        }
+    }
+
+    #[test]
+    fn policy_entries_override_default_per_field_and_raise() {
+        let table = ShellLimits::new(BTreeMap::from([
+            (DEFAULT_KEY.to_string(), limits(Some(60), Some(2048), None)),
+            ("read-only".to_string(), limits(Some(10), None, None)),
+        ]));
+        assert_eq!(
+            table.for_policy(&SandboxPolicy::ReadOnly),
+            limits(Some(10), Some(2048), None)
+        );
+        assert_eq!(
+            table.for_policy(&SandboxPolicy::default()),
+            limits(Some(60), Some(2048), None)
+        );
+        assert!(
+            ShellLimits::default()
+                .for_policy(&SandboxPolicy::DangerFullAccess)
+                .is_empty()
+        );
+
+        let raised = table.raised(LimitKind::Cpu);
+        assert_eq!(
+            raised.for_policy(&SandboxPolicy::ReadOnly),
+            limits(Some(40), Some(2048), None)
+        );
+        assert!(limits(Some(0), None, None).validate("default").is_err());
+    }
+
+    #[test]
+    fn hits_are_detected_from_signals_and_stderr() {
+        let all = limits(Some(5), Some(256), Some(1));
+        assert_eq!(all.detect_hit(Some(0), None, "File too large"), None);
+        assert_eq!(
+            all.detect_hit(Some(1), None, "python: MemoryError"),
+            Some(LimitHit {
+                kind: LimitKind::Memory,
+                limit: 256
+            })
+        );
+        assert_eq!(
+            all.detect_hit(Some(1), None, "dd: error writing 'x': File too large")
+                .map(|hit| hit.kind),
+            Some(LimitKind::Disk)
+        );
+        assert_eq!(
+            ResourceLimits::default().detect_hit(Some(1), None, "out of memory"),
+            None
+        );
+        #[cfg(unix)]
+        {
+            assert_eq!(
+                all.detect_hit(None, Some(libc::SIGXCPU), "")
+                    .map(|hit| hit.to_string()),
+                Some("CPU time limit (5s)".to_string())
+            );
+            assert_eq!(
+                all.detect_hit(Some(128 + libc::SIGXCPU), None, "")
+                    .map(|hit| hit.kind),
+                Some(LimitKind::Cpu)
+            );
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn applied_cpu_limit_stops_a_busy_loop() {
+        let mut cmd = std::process::Command::new("sh");
+        cmd.args(["-c", "while :; do :; done"]);
+        let limits = limits(Some(1), None, None);
+        limits.apply(&mut cmd);
+        let output = cmd.output().expect("spawn sh");
+        let signal = std::os::unix::process::ExitStatusExt::signal(&output.status);
+        let hit = limits.detect_hit(output.status.code(), signal, "");
+        assert_eq!(hit.map(|hit| hit.kind), Some(LimitKind::Cpu));
+    }
+}
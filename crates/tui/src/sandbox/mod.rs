@@ -28,6 +28,7 @@
 //! ```
 
 pub mod backend;
+pub mod limits;
 pub mod opensandbox;
 pub mod policy;
 
@@ -44,6 +45,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
+pub use limits::ResourceLimits;
 pub use policy::SandboxPolicy;
 
 /// Specification for a command to be executed, potentially within a sandbox.
@@ -74,6 +76,9 @@ pub struct CommandSpec {
     /// Optional justification for why this command needs to run.
     /// Used for logging and audit purposes.
     pub justification: Option<String>,
+
+    /// CPU, memory, and file-size caps applied to the spawned process.
+    pub resource_limits: ResourceLimits,
 }
 
 impl CommandSpec {
@@ -102,6 +107,7 @@ impl CommandSpec {
             timeout,
             sandbox_policy: SandboxPolicy::default(),
             justification: None,
+            resource_limits: ResourceLimits::default(),
         }
     }
 
@@ -115,6 +121,7 @@ impl CommandSpec {
             timeout,
             sandbox_policy: SandboxPolicy::default(),
             justification: None,
+            resource_limits: ResourceLimits::default(),
         }
     }
 
@@ -124,6 +131,12 @@ impl CommandSpec {
         self
     }
 
+    /// Set the resource limits for this command.
+    pub fn with_resource_limits(mut self, limits: ResourceLimits) -> Self {
+        self.resource_limits = limits;
+        self
+    }
+
     /// Add environment variables for this command.
     pub fn with_env(mut self, env: HashMap<String, String>) -> Self {
         self.env = env;
@@ -226,6 +239,9 @@ pub struct ExecEnv {
 
     /// The original policy (for reference).
     pub policy: SandboxPolicy,
+
+    /// Resource limits to install on the spawned process.
+    pub resource_limits: ResourceLimits,
 }
 
 impl ExecEnv {
@@ -368,6 +384,7 @@ impl SandboxManager {
             timeout: spec.timeout,
             sandbox_type: SandboxType::None,
             policy: spec.sandbox_policy.clone(),
+            resource_limits: spec.resource_limits,
         }
     }
 
@@ -397,6 +414,7 @@ impl SandboxManager {
             timeout: spec.timeout,
             sandbox_type: SandboxType::MacosSeatbelt,
             policy: spec.sandbox_policy.clone(),
+            resource_limits: spec.resource_limits,
         }
     }
 
@@ -429,6 +447,7 @@ impl SandboxManager {
             timeout: spec.timeout,
             sandbox_type: SandboxType::LinuxLandlock,
             policy: spec.sandbox_policy.clone(),
+            resource_limits: spec.resource_limits,
         }
     }
 
@@ -460,6 +479,7 @@ impl SandboxManager {
             timeout: spec.timeout,
             sandbox_type: SandboxType::Windows,
             policy: spec.sandbox_policy.clone(),
+            resource_limits: spec.resource_limits,
         }
     }
 
@@ -681,6 +701,7 @@ mod tests {
             timeout: Duration::from_secs(30),
             sandbox_type: SandboxType::None,
             policy: SandboxPolicy::default(),
+            resource_limits: ResourceLimits::default(),
         };
 
         assert_eq!(env.program(), "sandbox-exec");
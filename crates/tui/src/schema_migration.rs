@@ -208,7 +208,7 @@ pub fn backup_before_migrate(path: &Path, domain: &str) -> PathBuf {
 pub mod registry {
     use super::{MigrationFn, SchemaMigration};
 
-    /// Sessions: `~/.deepseek/sessions/<id>.json` and the latest
+    /// Sessions: `~/.deepseek/sessions/<id>.jsonl.zst` (legacy `<id>.json`) and the latest
     /// checkpoint at `~/.deepseek/sessions/checkpoints/latest.json`.
     pub struct SessionMigration;
     impl SchemaMigration for SessionMigration {
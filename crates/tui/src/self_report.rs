@@ -0,0 +1,348 @@
+//! Per-turn self-report footer (`self_report = true`).
+//!
+//! With the option on, the system prompt asks the model to end each final
+//! answer with a `<self_report>` block naming its confidence, the
+//! assumptions it did not verify, and relevant files it did not open. The
+//! TUI strips the block from the assistant cell and shows a one-line
+//! annotation instead; the session summary and `deepseek bench` reports
+//! aggregate it.
+
+use serde::{Deserialize, Serialize};
+
+const OPEN_TAG: &str = "<self_report>";
+const CLOSE_TAG: &str = "</self_report>";
+
+/// Items listed in an annotation before it says `+N more`.
+const MAX_LISTED: usize = 3;
+
+/// System prompt layer text for the option.
+pub const INSTRUCTIONS: &str = "## Self-Report\n\n\
+End every final answer (not intermediate tool-calling steps) with this footer, \
+once, as the last thing in the message:\n\n\
+<self_report>\n\
+confidence: high | medium | low\n\
+assumptions:\n\
+- <something the answer depends on that you did not verify>\n\
+not_inspected:\n\
+- <a file or area relevant to the task that you did not open>\n\
+</self_report>\n\n\
+Rate confidence by how well the answer was checked (files read, tests run), \
+not by how it sounds. Write `none` under a heading with nothing to list. Keep \
+each item under 15 words.";
+
+/// How sure the model says it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+impl Confidence {
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value
+            .trim()
+            .trim_matches(['*', '`', '.'])
+            .to_ascii_lowercase();
+        match value.split_whitespace().next()? {
+            "low" => Some(Self::Low),
+            "medium" | "moderate" | "med" => Some(Self::Medium),
+            "high" => Some(Self::High),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+/// A parsed `<self_report>` footer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelfReport {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<Confidence>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assumptions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub not_inspected: Vec<String>,
+}
+
+impl SelfReport {
+    /// Parse the body between the tags. Keys take inline values (split on
+    /// `;`) or `-` bullets on the following lines.
+    #[must_use]
+    pub fn parse(body: &str) -> Self {
+        let mut report = Self::default();
+        let mut section = Section::None;
+        for line in body.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if let Some(item) = line
+                .strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))
+                .or_else(|| (line == "-").then_some(""))
+            {
+                push_item(section_list(&mut report, section), item);
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_ascii_lowercase().replace([' ', '-'], "_");
+            section = match key.as_str() {
+                "confidence" => {
+                    report.confidence = Confidence::parse(value);
+                    Section::None
+                }
+                "assumptions" | "assumption" | "key_assumptions" => Section::Assumptions,
+                "not_inspected" | "files_not_inspected" | "not_read" => Section::NotInspected,
+                _ => Section::None,
+            };
+            for item in value.split(';') {
+                push_item(section_list(&mut report, section), item);
+            }
+        }
+        report
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.confidence.is_none() && self.assumptions.is_empty() && self.not_inspected.is_empty()
+    }
+
+    /// One line for under the assistant cell, e.g.
+    /// `confidence medium · assumes: API is v2 · not inspected: src/db.rs`.
+    #[must_use]
+    pub fn annotation(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(confidence) = self.confidence {
+            parts.push(format!("confidence {}", confidence.label()));
+        }
+        if !self.assumptions.is_empty() {
+            parts.push(format!("assumes: {}", list_items(&self.assumptions, "; ")));
+        }
+        if !self.not_inspected.is_empty() {
+            parts.push(format!(
+                "not inspected: {}",
+                list_items(&self.not_inspected, ", ")
+            ));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Footer heading the parser is currently under.
+#[derive(Clone, Copy)]
+enum Section {
+    None,
+    Assumptions,
+    NotInspected,
+}
+
+fn section_list(report: &mut SelfReport, section: Section) -> Option<&mut Vec<String>> {
+    match section {
+        Section::None => None,
+        Section::Assumptions => Some(&mut report.assumptions),
+        Section::NotInspected => Some(&mut report.not_inspected),
+    }
+}
+
+/// Add `item` to `list`, skipping blanks and `none` placeholders.
+fn push_item(list: Option<&mut Vec<String>>, item: &str) {
+    let item = item.trim().trim_matches('`');
+    if let Some(list) = list
+        && !item.is_empty()
+        && !item.eq_ignore_ascii_case("none")
+        && !item.eq_ignore_ascii_case("n/a")
+    {
+        list.push(item.to_string());
+    }
+}
+
+fn list_items(items: &[String], separator: &str) -> String {
+    let mut listed = items
+        .iter()
+        .take(MAX_LISTED)
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(separator);
+    if items.len() > MAX_LISTED {
+        listed.push_str(&format!(" (+{} more)", items.len() - MAX_LISTED));
+    }
+    listed
+}
+
+/// Split a trailing `<self_report>` block off `text`. Returns the text
+/// without it and the parsed report, or `text` unchanged when there is no
+/// complete block (or it is empty).
+#[must_use]
+pub fn split(text: &str) -> (&str, Option<SelfReport>) {
+    let Some(start) = text.rfind(OPEN_TAG) else {
+        return (text, None);
+    };
+    let rest = &text[start + OPEN_TAG.len()..];
+    let Some(end) = rest.find(CLOSE_TAG) else {
+        return (text, None);
+    };
+    if !rest[end + CLOSE_TAG.len()..].trim().is_empty() {
+        return (text, None);
+    }
+    let report = SelfReport::parse(&rest[..end]);
+    if report.is_empty() {
+        return (text, None);
+    }
+    (text[..start].trim_end(), Some(report))
+}
+
+/// Replace a trailing footer in an assistant reply with its one-line
+/// annotation, or `None` when the reply has no footer.
+#[must_use]
+pub fn annotate(text: &str) -> Option<String> {
+    let (body, report) = split(text);
+    let annotation = report?.annotation();
+    // Italic unless the items themselves carry `*`, which would unbalance it.
+    let line = if annotation.contains('*') {
+        format!("◇ {annotation}")
+    } else {
+        format!("*◇ {annotation}*")
+    };
+    Some(if body.is_empty() {
+        line
+    } else {
+        format!("{body}\n\n{line}")
+    })
+}
+
+/// Self-reports across a session or an eval run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelfReportTally {
+    pub reports: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    pub assumptions: usize,
+    /// Distinct entries from every `not_inspected` list, first seen first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub not_inspected: Vec<String>,
+}
+
+impl SelfReportTally {
+    pub fn add(&mut self, report: &SelfReport) {
+        self.reports += 1;
+        match report.confidence {
+            Some(Confidence::High) => self.high += 1,
+            Some(Confidence::Medium) => self.medium += 1,
+            Some(Confidence::Low) => self.low += 1,
+            None => {}
+        }
+        self.assumptions += report.assumptions.len();
+        for item in &report.not_inspected {
+            if !self.not_inspected.contains(item) {
+                self.not_inspected.push(item.clone());
+            }
+        }
+    }
+
+    /// Summary line, e.g. `4 self-reports (2 high, 1 medium, 1 low); 3
+    /// assumptions; not inspected: src/db.rs`.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut counts = Vec::new();
+        for (count, label) in [
+            (self.high, "high"),
+            (self.medium, "medium"),
+            (self.low, "low"),
+        ] {
+            if count > 0 {
+                counts.push(format!("{count} {label}"));
+            }
+        }
+        let mut line = format!(
+            "{} self-report{}",
+            self.reports,
+            if self.reports == 1 { "" } else { "s" }
+        );
+        if !counts.is_empty() {
+            line.push_str(&format!(" ({})", counts.join(", ")));
+        }
+        if self.assumptions > 0 {
+            line.push_str(&format!(
+                "; {} assumption{}",
+                self.assumptions,
+                if self.assumptions == 1 { "" } else { "s" }
+            ));
+        }
+        if !self.not_inspected.is_empty() {
+            line.push_str(&format!(
+                "; not inspected: {}",
+                list_items(&self.not_inspected, ", ")
+            ));
+        }
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPLY: &str = "Fixed the parser.\n\n<self_report>\nconfidence: Medium\n\
+assumptions:\n- callers pass UTF-8\n- none\nnot_inspected: src/lexer.rs; `tests/fixtures`\n\
+</self_report>\n";
+
+    #[test]
+    fn split_strips_a_trailing_footer() {
+        let (body, report) = split(REPLY);
+        assert_eq!(body, "Fixed the parser.");
+        let report = report.expect("footer parsed");
+        assert_eq!(report.confidence, Some(Confidence::Medium));
+        assert_eq!(report.assumptions, vec!["callers pass UTF-8"]);
+        assert_eq!(report.not_inspected, vec!["src/lexer.rs", "tests/fixtures"]);
+        assert_eq!(
+            report.annotation(),
+            "confidence medium · assumes: callers pass UTF-8 · not inspected: src/lexer.rs, tests/fixtures"
+        );
+    }
+
+    #[test]
+    fn annotate_replaces_the_footer_with_one_line() {
+        assert_eq!(
+            annotate("Done.\n<self_report>\nconfidence: low\n</self_report>").as_deref(),
+            Some("Done.\n\n*◇ confidence low*")
+        );
+        assert_eq!(annotate("Done."), None);
+    }
+
+    #[test]
+    fn split_leaves_text_without_a_trailing_footer() {
+        assert_eq!(split("plain answer"), ("plain answer", None));
+        let quoted = "Use <self_report>confidence: high</self_report> like this.";
+        assert_eq!(split(quoted), (quoted, None));
+        let unclosed = "Done.\n<self_report>\nconfidence: high";
+        assert_eq!(split(unclosed), (unclosed, None));
+        let empty = "Done.\n<self_report>\nnotes: none\n</self_report>";
+        assert_eq!(split(empty), (empty, None));
+    }
+
+    #[test]
+    fn tally_counts_confidence_and_dedupes_files() {
+        let mut tally = SelfReportTally::default();
+        tally.add(&split(REPLY).1.unwrap());
+        tally.add(&SelfReport {
+            confidence: Some(Confidence::High),
+            assumptions: Vec::new(),
+            not_inspected: vec!["src/lexer.rs".to_string()],
+        });
+        assert_eq!((tally.high, tally.medium, tally.low), (1, 1, 0));
+        assert_eq!(
+            tally.summary(),
+            "2 self-reports (1 high, 1 medium); 1 assumption; not inspected: src/lexer.rs, tests/fixtures"
+        );
+    }
+}
@@ -0,0 +1,843 @@
+//! Import conversations from other agent CLIs for `deepseek sessions import`.
+//!
+//! Most sources are JSONL transcripts: Claude Code project logs
+//! (`~/.claude/projects/<project>/<session>.jsonl`), Codex rollouts
+//! (`~/.codex/sessions/**/rollout-*.jsonl`), or plain OpenAI chat messages
+//! (one message, or one `{"messages": [...]}` conversation, per line).
+//! Markdown is the exception: `## User` / `## Assistant` headings (or the
+//! `**You:**` labels `/export` writes) split it into turns, and a file with
+//! no markers — notes, a design doc — becomes one user message. `deepseek
+//! --from-file` and `/import` use the markdown path.
+//! Text, reasoning, tool calls, and tool results map onto [`ContentBlock`];
+//! anything without an equivalent (images, system prompts, hosted tool
+//! calls, sidechains) is dropped and listed in [`ImportedSession::skipped`]
+//! so the user knows what did not come across.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+
+use crate::models::{ContentBlock, Message};
+use crate::session_manager::{SavedSession, create_saved_session_with_mode};
+
+/// Source transcript format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImportFormat {
+    /// Claude Code project transcript (`~/.claude/projects/**.jsonl`)
+    Claude,
+    /// Codex rollout (`~/.codex/sessions/**/rollout-*.jsonl`)
+    Codex,
+    /// OpenAI chat messages, one message or `{"messages": [...]}` per line
+    OpenaiJsonl,
+    /// Markdown notes, or a transcript with `## User` / `## Assistant` headings
+    Markdown,
+}
+
+/// A converted session plus what the conversion could not carry over.
+#[derive(Debug)]
+pub struct ImportedSession {
+    pub session: SavedSession,
+    /// Model recorded by the source tool, if any.
+    pub source_model: Option<String>,
+    /// One entry per dropped item, prefixed with its source line.
+    pub skipped: Vec<String>,
+}
+
+/// Accumulates converted messages, merging consecutive blocks from the same
+/// role the way our own transcripts store them.
+#[derive(Default)]
+struct Transcript {
+    messages: Vec<Message>,
+    skipped: Vec<String>,
+    workspace: Option<PathBuf>,
+    model: Option<String>,
+    started_at: Option<DateTime<Utc>>,
+}
+
+impl Transcript {
+    fn push(&mut self, role: &str, block: ContentBlock) {
+        if let Some(last) = self.messages.last_mut()
+            && last.role == role
+        {
+            last.content.push(block);
+            return;
+        }
+        self.messages.push(Message {
+            role: role.to_string(),
+            content: vec![block],
+        });
+    }
+
+    fn push_text(&mut self, role: &str, text: &str) {
+        if !text.trim().is_empty() {
+            self.push(
+                role,
+                ContentBlock::Text {
+                    text: text.to_string(),
+                    cache_control: None,
+                },
+            );
+        }
+    }
+
+    fn skip(&mut self, line: usize, what: impl std::fmt::Display) {
+        self.skipped.push(format!("line {line}: {what}"));
+    }
+
+    fn note_line_meta(&mut self, value: &Value) {
+        if self.workspace.is_none()
+            && let Some(cwd) = value.get("cwd").and_then(Value::as_str)
+        {
+            self.workspace = Some(PathBuf::from(cwd));
+        }
+        if self.started_at.is_none()
+            && let Some(timestamp) = value.get("timestamp").and_then(Value::as_str)
+            && let Ok(parsed) = DateTime::parse_from_rfc3339(timestamp)
+        {
+            self.started_at = Some(parsed.with_timezone(&Utc));
+        }
+    }
+}
+
+/// Text of a string or an array of `{type: text|input_text|output_text}`
+/// parts. Non-text parts are reported through `skip`.
+fn text_parts(content: &Value, mut skip: impl FnMut(&str)) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => {
+            let mut texts = Vec::new();
+            for part in parts {
+                let kind = part.get("type").and_then(Value::as_str).unwrap_or("");
+                match part.get("text").and_then(Value::as_str) {
+                    Some(text) if matches!(kind, "text" | "input_text" | "output_text") => {
+                        texts.push(text.to_string());
+                    }
+                    _ => skip(if kind.is_empty() { "untyped" } else { kind }),
+                }
+            }
+            texts.join("\n")
+        }
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Tool-call arguments arrive as a JSON string in OpenAI-style formats.
+fn parse_arguments(arguments: Option<&Value>) -> Value {
+    match arguments {
+        Some(Value::String(raw)) => {
+            serde_json::from_str(raw).unwrap_or_else(|_| json!({ "raw": raw }))
+        }
+        Some(value) => value.clone(),
+        None => json!({}),
+    }
+}
+
+fn tool_result(tool_use_id: &str, content: String, is_error: Option<bool>) -> ContentBlock {
+    ContentBlock::ToolResult {
+        tool_use_id: tool_use_id.to_string(),
+        content,
+        is_error,
+        content_blocks: None,
+    }
+}
+
+fn import_claude_line(transcript: &mut Transcript, line: usize, value: &Value) {
+    let kind = value.get("type").and_then(Value::as_str).unwrap_or("");
+    match kind {
+        "user" | "assistant" => {}
+        // Bookkeeping records with no conversational content.
+        "summary" | "file-history-snapshot" => return,
+        other => {
+            transcript.skip(line, format!("`{other}` record"));
+            return;
+        }
+    }
+    if value.get("isSidechain").and_then(Value::as_bool) == Some(true) {
+        transcript.skip(line, "sub-agent (sidechain) message");
+        return;
+    }
+    // Local command echoes and caveats injected by the CLI itself.
+    if value.get("isMeta").and_then(Value::as_bool) == Some(true) {
+        return;
+    }
+    transcript.note_line_meta(value);
+    let Some(message) = value.get("message") else {
+        transcript.skip(line, format!("`{kind}` record without a message"));
+        return;
+    };
+    if kind == "assistant"
+        && let Some(model) = message.get("model").and_then(Value::as_str)
+        && !model.starts_with('<')
+    {
+        transcript.model = Some(model.to_string());
+    }
+
+    let content = message.get("content").cloned().unwrap_or(Value::Null);
+    let Value::Array(blocks) = content else {
+        let text = text_parts(&content, |_| {});
+        transcript.push_text(kind, &text);
+        return;
+    };
+    for block in &blocks {
+        match block.get("type").and_then(Value::as_str).unwrap_or("") {
+            "text" => {
+                let text = block.get("text").and_then(Value::as_str).unwrap_or("");
+                transcript.push_text(kind, text);
+            }
+            "thinking" => {
+                let thinking = block.get("thinking").and_then(Value::as_str).unwrap_or("");
+                if !thinking.trim().is_empty() {
+                    transcript.push(
+                        kind,
+                        ContentBlock::Thinking {
+                            thinking: thinking.to_string(),
+                        },
+                    );
+                }
+            }
+            "redacted_thinking" => {}
+            "tool_use" => transcript.push(
+                kind,
+                ContentBlock::ToolUse {
+                    id: block
+                        .get("id")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    name: block
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .unwrap_or("unknown")
+                        .to_string(),
+                    input: block.get("input").cloned().unwrap_or_else(|| json!({})),
+                    caller: None,
+                },
+            ),
+            "tool_result" => {
+                let tool_use_id = block
+                    .get("tool_use_id")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                let mut dropped = Vec::new();
+                let text = text_parts(block.get("content").unwrap_or(&Value::Null), |part| {
+                    dropped.push(part.to_string())
+                });
+                for part in dropped {
+                    transcript.skip(line, format!("`{part}` part of tool result {tool_use_id}"));
+                }
+                let is_error = block.get("is_error").and_then(Value::as_bool);
+                transcript.push("user", tool_result(&tool_use_id, text, is_error));
+            }
+            other => transcript.skip(line, format!("`{other}` block")),
+        }
+    }
+}
+
+fn import_codex_item(transcript: &mut Transcript, line: usize, item: &Value) {
+    let kind = item.get("type").and_then(Value::as_str).unwrap_or("");
+    match kind {
+        "message" => {
+            let role = item.get("role").and_then(Value::as_str).unwrap_or("");
+            let mut dropped = Vec::new();
+            let text = text_parts(item.get("content").unwrap_or(&Value::Null), |part| {
+                dropped.push(part.to_string());
+            });
+            for part in dropped {
+                transcript.skip(line, format!("`{part}` message part"));
+            }
+            match role {
+                "user" if is_codex_injected_context(&text) => {}
+                "user" | "assistant" => transcript.push_text(role, &text),
+                other => transcript.skip(line, format!("`{other}` message (instructions)")),
+            }
+        }
+        "reasoning" => {
+            let summary = item
+                .get("summary")
+                .map(|summary| text_parts(summary, |_| {}))
+                .unwrap_or_default();
+            if !summary.trim().is_empty() {
+                transcript.push("assistant", ContentBlock::Thinking { thinking: summary });
+            }
+        }
+        "function_call" | "custom_tool_call" | "local_shell_call" => {
+            let id = item
+                .get("call_id")
+                .or_else(|| item.get("id"))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            let (name, input) = match kind {
+                "function_call" => (
+                    item.get("name")
+                        .and_then(Value::as_str)
+                        .unwrap_or("unknown"),
+                    parse_arguments(item.get("arguments")),
+                ),
+                "custom_tool_call" => (
+                    item.get("name")
+                        .and_then(Value::as_str)
+                        .unwrap_or("unknown"),
+                    json!({ "input": item.get("input").cloned().unwrap_or(Value::Null) }),
+                ),
+                _ => (
+                    "local_shell",
+                    item.get("action").cloned().unwrap_or_else(|| json!({})),
+                ),
+            };
+            transcript.push(
+                "assistant",
+                ContentBlock::ToolUse {
+                    id,
+                    name: name.to_string(),
+                    input,
+                    caller: None,
+                },
+            );
+        }
+        "function_call_output" | "custom_tool_call_output" => {
+            let call_id = item
+                .get("call_id")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let output = match item.get("output") {
+                Some(Value::String(text)) => text.clone(),
+                Some(Value::Object(object)) => object
+                    .get("content")
+                    .and_then(Value::as_str)
+                    .map_or_else(|| Value::Object(object.clone()).to_string(), str::to_string),
+                Some(other) => other.to_string(),
+                None => String::new(),
+            };
+            transcript.push("user", tool_result(call_id, output, None));
+        }
+        other => transcript.skip(line, format!("`{other}` item")),
+    }
+}
+
+/// Codex prepends environment and AGENTS.md context as user messages.
+fn is_codex_injected_context(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    trimmed.starts_with("<environment_context>")
+        || trimmed.starts_with("<user_instructions>")
+        || trimmed.starts_with("# AGENTS.md instructions")
+}
+
+fn import_codex_line(transcript: &mut Transcript, line: usize, value: &Value) {
+    transcript.note_line_meta(value);
+    match value.get("type").and_then(Value::as_str) {
+        Some("session_meta") => {
+            if let Some(payload) = value.get("payload") {
+                transcript.note_line_meta(payload);
+            }
+        }
+        Some("turn_context") => {
+            if let Some(model) = value
+                .get("payload")
+                .and_then(|payload| payload.get("model"))
+                .and_then(Value::as_str)
+            {
+                transcript.model = Some(model.to_string());
+            }
+        }
+        Some("response_item") => {
+            if let Some(payload) = value.get("payload") {
+                import_codex_item(transcript, line, payload);
+            }
+        }
+        // UI events duplicate the response items; compaction markers carry
+        // nothing to replay.
+        Some("event_msg" | "compacted") => {}
+        // Older rollouts wrote bare response items, after a header line
+        // that carries the session id and instructions.
+        Some(_) => import_codex_item(transcript, line, value),
+        None if value.get("id").is_some() && value.get("instructions").is_some() => {}
+        None => transcript.skip(line, "untyped record"),
+    }
+}
+
+fn import_openai_message(transcript: &mut Transcript, line: usize, message: &Value) {
+    let role = message.get("role").and_then(Value::as_str).unwrap_or("");
+    let content = message.get("content").unwrap_or(&Value::Null);
+    let mut dropped = Vec::new();
+    let text = text_parts(content, |part| dropped.push(part.to_string()));
+    for part in dropped {
+        transcript.skip(line, format!("`{part}` content part"));
+    }
+    match role {
+        "user" => transcript.push_text("user", &text),
+        "assistant" => {
+            if let Some(reasoning) = message.get("reasoning_content").and_then(Value::as_str)
+                && !reasoning.trim().is_empty()
+            {
+                transcript.push(
+                    "assistant",
+                    ContentBlock::Thinking {
+                        thinking: reasoning.to_string(),
+                    },
+                );
+            }
+            transcript.push_text("assistant", &text);
+            for call in message
+                .get("tool_calls")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let function = call.get("function").unwrap_or(&Value::Null);
+                transcript.push(
+                    "assistant",
+                    ContentBlock::ToolUse {
+                        id: call
+                            .get("id")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .to_string(),
+                        name: function
+                            .get("name")
+                            .and_then(Value::as_str)
+                            .unwrap_or("unknown")
+                            .to_string(),
+                        input: parse_arguments(function.get("arguments")),
+                        caller: None,
+                    },
+                );
+            }
+        }
+        "tool" => {
+            let tool_call_id = message
+                .get("tool_call_id")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            transcript.push("user", tool_result(tool_call_id, text, None));
+        }
+        other => transcript.skip(line, format!("`{other}` message")),
+    }
+}
+
+fn import_openai_line(transcript: &mut Transcript, line: usize, value: &Value) {
+    if let Some(model) = value.get("model").and_then(Value::as_str) {
+        transcript.model = Some(model.to_string());
+    }
+    match value.get("messages").and_then(Value::as_array) {
+        Some(messages) => {
+            for message in messages {
+                import_openai_message(transcript, line, message);
+            }
+        }
+        None => import_openai_message(transcript, line, value),
+    }
+}
+
+fn import_jsonl(transcript: &mut Transcript, text: &str, format: ImportFormat) {
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        if raw.trim().is_empty() {
+            continue;
+        }
+        let value: Value = match serde_json::from_str(raw) {
+            Ok(value) => value,
+            Err(err) => {
+                transcript.skip(line, format!("not JSON ({err})"));
+                continue;
+            }
+        };
+        match format {
+            ImportFormat::Claude => import_claude_line(transcript, line, &value),
+            ImportFormat::Codex => import_codex_line(transcript, line, &value),
+            ImportFormat::OpenaiJsonl => import_openai_line(transcript, line, &value),
+            ImportFormat::Markdown => {}
+        }
+    }
+}
+
+/// Who a markdown role marker hands the following text to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MarkdownSection {
+    Role(&'static str),
+    /// A section `/export` writes that has no message equivalent
+    /// (`*Thinking:*`, `**Tool:**`, `## System`, ...).
+    Dropped(String),
+}
+
+/// Section named by a role marker line: a heading (`## User`) or a bold or
+/// italic label on its own line (`**Assistant:**`, as `/export` writes).
+/// Headings that name anything else are ordinary content.
+fn markdown_marker(line: &str) -> Option<MarkdownSection> {
+    let line = line.trim();
+    let label = if let Some(rest) = line.strip_prefix('#') {
+        let rest = rest.trim_start_matches('#');
+        if line.len() - rest.len() > 6 || !rest.starts_with(' ') {
+            return None;
+        }
+        rest
+    } else if line.len() > 2 && line.starts_with('*') && line.ends_with('*') {
+        line.trim_matches('*')
+    } else {
+        return None;
+    };
+    let label = label
+        .trim()
+        .trim_end_matches(':')
+        .trim()
+        .to_ascii_lowercase();
+    match label.as_str() {
+        "user" | "you" | "human" | "me" => Some(MarkdownSection::Role("user")),
+        "assistant" | "ai" | "model" | "deepseek" => Some(MarkdownSection::Role("assistant")),
+        "system" | "thinking" | "reasoning" | "tool" | "tool call" | "tool result"
+        | "sub-agent" | "warning" | "info" | "error" | "archived context" => {
+            Some(MarkdownSection::Dropped(label))
+        }
+        _ => None,
+    }
+}
+
+/// Opening or closing code fence run (```` ``` ```` or `~~~`, 3+ long).
+fn fence_marker(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let ch = line.chars().next().filter(|ch| matches!(ch, '`' | '~'))?;
+    let run = line.len() - line.trim_start_matches(ch).len();
+    (run >= 3).then(|| &line[..run])
+}
+
+/// Add one finished section to the transcript. `/export` separates entries
+/// with horizontal rules, so trailing `---` lines are not content.
+fn flush_markdown_section(
+    transcript: &mut Transcript,
+    section: Option<&(usize, MarkdownSection)>,
+    body: &mut Vec<&str>,
+) {
+    while body
+        .last()
+        .is_some_and(|line| matches!(line.trim(), "" | "---"))
+    {
+        body.pop();
+    }
+    let content = body.join("\n");
+    body.clear();
+    let content = content.trim();
+    if content.is_empty() {
+        return;
+    }
+    match section {
+        Some((_, MarkdownSection::Role(role))) => transcript.push_text(role, content),
+        Some((line, MarkdownSection::Dropped(kind))) => {
+            transcript.skip(*line, format!("`{kind}` section"));
+        }
+        None => transcript.skip(1, "text before the first role marker"),
+    }
+}
+
+/// Split markdown into role sections. A file without role markers is one
+/// user message; with markers, text before the first one (a title or an
+/// `/export` header) is dropped. Markers inside code fences are content.
+fn import_markdown(transcript: &mut Transcript, text: &str) {
+    let mut section: Option<(usize, MarkdownSection)> = None;
+    let mut body: Vec<&str> = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut saw_marker = false;
+
+    for (index, line) in text.lines().enumerate() {
+        if let Some(open) = fence {
+            let closes = fence_marker(line).is_some_and(|close| {
+                close.starts_with(open) && line.trim_start()[close.len()..].trim().is_empty()
+            });
+            if closes {
+                fence = None;
+            }
+        } else if let Some(open) = fence_marker(line) {
+            fence = Some(open);
+        } else if let Some(next) = markdown_marker(line) {
+            flush_markdown_section(transcript, section.as_ref(), &mut body);
+            section = Some((index + 1, next));
+            saw_marker = true;
+            continue;
+        }
+        body.push(line);
+    }
+
+    if saw_marker {
+        flush_markdown_section(transcript, section.as_ref(), &mut body);
+    } else {
+        transcript.push_text("user", text.trim());
+    }
+}
+
+/// Take a trailing user message that is plain text off `messages`, so it
+/// can go into the composer for review instead of sitting unanswered in
+/// the history.
+pub fn take_trailing_user_text(messages: &mut Vec<Message>) -> Option<String> {
+    let last = messages.last()?;
+    if last.role != "user" {
+        return None;
+    }
+    let mut parts = Vec::new();
+    for block in &last.content {
+        match block {
+            ContentBlock::Text { text, .. } => parts.push(text.as_str()),
+            _ => return None,
+        }
+    }
+    let text = parts.join("\n\n");
+    messages.pop();
+    Some(text)
+}
+
+/// Messages parsed from markdown notes or a markdown transcript, plus one
+/// entry per dropped section.
+#[must_use]
+pub fn parse_markdown(text: &str) -> (Vec<Message>, Vec<String>) {
+    let mut transcript = Transcript::default();
+    import_markdown(&mut transcript, text);
+    (transcript.messages, transcript.skipped)
+}
+
+/// Convert transcript text in `format` into a [`SavedSession`]. The session
+/// runs on `model`; `fallback_workspace` is used when the source does not
+/// record a working directory.
+pub fn import_transcript(
+    text: &str,
+    format: ImportFormat,
+    model: &str,
+    fallback_workspace: &Path,
+) -> Result<ImportedSession> {
+    let mut transcript = Transcript::default();
+    match format {
+        ImportFormat::Markdown => import_markdown(&mut transcript, text),
+        _ => import_jsonl(&mut transcript, text, format),
+    }
+
+    if transcript.messages.is_empty() {
+        bail!(
+            "no importable messages found ({} item(s) skipped); is this the right --from format?",
+            transcript.skipped.len()
+        );
+    }
+
+    let workspace = transcript
+        .workspace
+        .unwrap_or_else(|| fallback_workspace.to_path_buf());
+    let mut session =
+        create_saved_session_with_mode(&transcript.messages, model, &workspace, 0, None, None);
+    if let Some(started_at) = transcript.started_at {
+        session.metadata.created_at = started_at;
+    }
+    Ok(ImportedSession {
+        session,
+        source_model: transcript.model,
+        skipped: transcript.skipped,
+    })
+}
+
+/// Read and convert the transcript at `path`.
+pub fn import_file(
+    path: &Path,
+    format: ImportFormat,
+    model: &str,
+    fallback_workspace: &Path,
+) -> Result<ImportedSession> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("could not read {}", path.display()))?;
+    import_transcript(&text, format, model, fallback_workspace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roles(session: &SavedSession) -> Vec<&str> {
+        session
+            .messages
+            .iter()
+            .map(|message| message.role.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn claude_transcript_maps_tool_calls_and_flags_images() {
+        let text = [
+            r#"{"type":"summary","summary":"Fix tests"}"#,
+            r#"{"type":"user","cwd":"/work/repo","timestamp":"2026-01-02T03:04:05Z","message":{"role":"user","content":"run the tests"}}"#,
+            r#"{"type":"assistant","message":{"model":"claude-x","role":"assistant","content":[{"type":"thinking","thinking":"check cargo"}]}}"#,
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","id":"toolu_1","name":"Bash","input":{"command":"cargo test"}}]}}"#,
+            r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":[{"type":"text","text":"ok"},{"type":"image","source":{}}]}]}}"#,
+            r#"{"type":"assistant","isSidechain":true,"message":{"role":"assistant","content":"side"}}"#,
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"All green."}]}}"#,
+        ]
+        .join("\n");
+
+        let imported = import_transcript(
+            &text,
+            ImportFormat::Claude,
+            "deepseek-v4-pro",
+            Path::new("/fallback"),
+        )
+        .expect("import");
+        let session = &imported.session;
+        assert_eq!(roles(session), ["user", "assistant", "user", "assistant"]);
+        assert!(matches!(
+            &session.messages[1].content[1],
+            ContentBlock::ToolUse { id, name, .. } if id == "toolu_1" && name == "Bash"
+        ));
+        assert!(matches!(
+            &session.messages[2].content[0],
+            ContentBlock::ToolResult { tool_use_id, content, .. }
+                if tool_use_id == "toolu_1" && content == "ok"
+        ));
+        assert_eq!(session.metadata.workspace, PathBuf::from("/work/repo"));
+        assert_eq!(session.metadata.model, "deepseek-v4-pro");
+        assert_eq!(session.metadata.title, "run the tests");
+        assert_eq!(imported.source_model.as_deref(), Some("claude-x"));
+        assert_eq!(imported.skipped.len(), 2, "{:?}", imported.skipped);
+        assert!(imported.skipped[0].contains("image"));
+    }
+
+    #[test]
+    fn codex_rollout_maps_function_calls_and_skips_injected_context() {
+        let text = [
+            r#"{"type":"session_meta","payload":{"id":"abc","cwd":"/src/app"}}"#,
+            r#"{"type":"turn_context","payload":{"model":"gpt-x"}}"#,
+            r#"{"type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"<environment_context>cwd</environment_context>"}]}}"#,
+            r#"{"type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"list files"}]}}"#,
+            r#"{"type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{\"command\":[\"ls\"]}","call_id":"call_1"}}"#,
+            r#"{"type":"response_item","payload":{"type":"function_call_output","call_id":"call_1","output":"README.md"}}"#,
+            r#"{"type":"event_msg","payload":{"type":"agent_message","message":"dup"}}"#,
+            r#"{"type":"response_item","payload":{"type":"web_search_call","status":"completed"}}"#,
+        ]
+        .join("\n");
+
+        let imported = import_transcript(
+            &text,
+            ImportFormat::Codex,
+            "deepseek-v4-pro",
+            Path::new("/fallback"),
+        )
+        .expect("import");
+        let session = &imported.session;
+        assert_eq!(roles(session), ["user", "assistant", "user"]);
+        assert!(matches!(
+            &session.messages[1].content[0],
+            ContentBlock::ToolUse { name, input, .. }
+                if name == "shell" && input["command"][0] == "ls"
+        ));
+        assert_eq!(session.metadata.workspace, PathBuf::from("/src/app"));
+        assert_eq!(imported.source_model.as_deref(), Some("gpt-x"));
+        assert_eq!(imported.skipped.len(), 1);
+        assert!(imported.skipped[0].contains("web_search_call"));
+    }
+
+    #[test]
+    fn openai_jsonl_accepts_messages_and_conversations() {
+        let text = [
+            r#"{"messages":[{"role":"system","content":"be brief"},{"role":"user","content":"weather?"}]}"#,
+            r#"{"role":"assistant","content":null,"tool_calls":[{"id":"c1","type":"function","function":{"name":"get_weather","arguments":"{\"city\":\"Oslo\"}"}}]}"#,
+            r#"{"role":"tool","tool_call_id":"c1","content":"rain"}"#,
+            r#"{"role":"assistant","content":"It is raining."}"#,
+            "not json",
+        ]
+        .join("\n");
+
+        let imported = import_transcript(
+            &text,
+            ImportFormat::OpenaiJsonl,
+            "deepseek-v4-flash",
+            Path::new("/fallback"),
+        )
+        .expect("import");
+        let session = &imported.session;
+        assert_eq!(roles(session), ["user", "assistant", "user", "assistant"]);
+        assert!(matches!(
+            &session.messages[1].content[0],
+            ContentBlock::ToolUse { input, .. } if input["city"] == "Oslo"
+        ));
+        assert_eq!(session.metadata.workspace, PathBuf::from("/fallback"));
+        assert_eq!(imported.skipped.len(), 2, "{:?}", imported.skipped);
+        assert!(imported.skipped[0].contains("`system` message"));
+        assert!(imported.skipped[1].starts_with("line 5: not JSON"));
+    }
+
+    #[test]
+    fn markdown_role_markers_split_turns_and_plain_notes_become_one_message() {
+        let text = [
+            "# Chat Export",
+            "",
+            "**Model:** deepseek-v4-pro",
+            "",
+            "---",
+            "",
+            "## User",
+            "",
+            "Why does the build fail?",
+            "",
+            "```md",
+            "## Assistant",
+            "```",
+            "",
+            "**Assistant:**",
+            "",
+            "A missing feature flag.",
+            "",
+            "---",
+            "",
+            "*Thinking:*",
+            "",
+            "check Cargo.toml",
+            "",
+            "## you",
+            "Fix it, then.",
+        ]
+        .join("\n");
+
+        let (mut messages, skipped) = parse_markdown(&text);
+        let parsed: Vec<&str> = messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(parsed, ["user", "assistant", "user"]);
+        assert!(matches!(
+            &messages[0].content[0],
+            ContentBlock::Text { text, .. } if text.ends_with("```md\n## Assistant\n```")
+        ));
+        assert!(matches!(
+            &messages[1].content[0],
+            ContentBlock::Text { text, .. } if text == "A missing feature flag."
+        ));
+        assert_eq!(skipped.len(), 2, "{skipped:?}");
+        assert!(skipped[0].contains("before the first role marker"));
+        assert!(skipped[1].starts_with("line 21: `thinking` section"));
+        assert_eq!(
+            take_trailing_user_text(&mut messages).as_deref(),
+            Some("Fix it, then.")
+        );
+        assert_eq!(messages.len(), 2);
+        assert_eq!(take_trailing_user_text(&mut messages), None);
+
+        let notes = "# Design\n\n## Goals\n\nShip it.\n";
+        let imported = import_transcript(
+            notes,
+            ImportFormat::Markdown,
+            "deepseek-v4-pro",
+            Path::new("/fallback"),
+        )
+        .expect("import");
+        assert_eq!(roles(&imported.session), ["user"]);
+        assert!(matches!(
+            &imported.session.messages[0].content[0],
+            ContentBlock::Text { text, .. } if text == notes.trim()
+        ));
+        assert!(parse_markdown("  \n").0.is_empty());
+    }
+
+    #[test]
+    fn empty_import_is_an_error() {
+        let err = import_transcript(
+            r#"{"type":"summary","summary":"x"}"#,
+            ImportFormat::Claude,
+            "deepseek-v4-pro",
+            Path::new("/"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("--from"));
+    }
+}
@@ -0,0 +1,407 @@
+//! Append-only session log (`~/.deepseek/sessions/<id>.jsonl.zst`).
+//!
+//! A saved session used to be rewritten as one pretty-printed JSON file on
+//! every autosave, which made each save cost O(history). The log instead
+//! holds a sequence of zstd frames, each compressing one JSON line:
+//!
+//! - a `snapshot` record carrying the whole [`SavedSession`], and
+//! - `delta` records that drop messages from the front (the
+//!   [`MAX_PERSISTED_MESSAGES`](crate::session_manager) window sliding),
+//!   keep a prefix of what is left, append new messages, and replace the
+//!   header (metadata, system prompt, references, artifacts).
+//!
+//! A typical autosave after a turn is therefore a few KiB of compressed
+//! delta appended to the file. The log is rewritten as a single snapshot
+//! when a process saves the session for the first time, after
+//! [`MAX_DELTAS_PER_SNAPSHOT`] deltas, or once the deltas outweigh the
+//! snapshot, so loading never replays an unbounded history.
+//!
+//! Appends are not atomic: a crash mid-write leaves a truncated last frame.
+//! Loading keeps every record decoded before the damage, and the next save
+//! from a fresh process starts over with a snapshot.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::session_manager::SavedSession;
+use crate::utils::write_atomic;
+
+/// File extension of a session log; the legacy format is `<id>.json`.
+pub const LOG_EXTENSION: &str = "jsonl.zst";
+/// zstd level for session frames: fast, and JSON still compresses ~5-10x.
+const COMPRESSION_LEVEL: i32 = 3;
+/// Deltas appended before the log is compacted into a fresh snapshot.
+const MAX_DELTAS_PER_SNAPSHOT: usize = 64;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+enum LogRecord {
+    /// The complete session.
+    Snapshot { session: SavedSession },
+    /// Drop `drop_front` messages, keep the next `keep`, then append
+    /// `session.messages`; every other field of `session` replaces the
+    /// current one.
+    Delta {
+        drop_front: usize,
+        keep: usize,
+        session: SavedSession,
+    },
+}
+
+/// What the writer knows about a log it has written in this process.
+#[derive(Debug, Clone)]
+pub struct LogCursor {
+    /// The session as of the last record written.
+    last: SavedSession,
+    /// Log size after that record; a different size on disk means another
+    /// writer touched the file and the next save must snapshot.
+    file_len: u64,
+    snapshot_len: u64,
+    deltas: usize,
+}
+
+/// Persist `session` to the log at `path`: as a delta against `cursor` when
+/// possible, otherwise as a fresh snapshot. Returns the cursor for the next
+/// save.
+pub fn save(
+    path: &Path,
+    cursor: Option<LogCursor>,
+    session: &SavedSession,
+) -> io::Result<LogCursor> {
+    if let Some(cursor) = cursor
+        && let Some(cursor) = try_append(path, cursor, session)?
+    {
+        return Ok(cursor);
+    }
+    write_snapshot(path, session)
+}
+
+fn write_snapshot(path: &Path, session: &SavedSession) -> io::Result<LogCursor> {
+    let frame = encode(&LogRecordRef::Snapshot { session })?;
+    write_atomic(path, &frame)?;
+    let len = frame.len() as u64;
+    Ok(LogCursor {
+        last: session.clone(),
+        file_len: len,
+        snapshot_len: len,
+        deltas: 0,
+    })
+}
+
+fn try_append(
+    path: &Path,
+    mut cursor: LogCursor,
+    session: &SavedSession,
+) -> io::Result<Option<LogCursor>> {
+    if cursor.deltas >= MAX_DELTAS_PER_SNAPSHOT
+        || cursor.file_len - cursor.snapshot_len > cursor.snapshot_len
+    {
+        return Ok(None);
+    }
+    let Some((drop_front, keep)) = message_delta(&cursor.last, session) else {
+        return Ok(None);
+    };
+    match fs::metadata(path) {
+        Ok(meta) if meta.len() == cursor.file_len => {}
+        _ => return Ok(None),
+    }
+
+    let timed = !session.message_timestamps.is_empty();
+    let tail = SavedSession {
+        schema_version: session.schema_version,
+        metadata: session.metadata.clone(),
+        messages: session.messages[keep..].to_vec(),
+        system_prompt: session.system_prompt.clone(),
+        context_references: session.context_references.clone(),
+        artifacts: session.artifacts.clone(),
+        message_timestamps: if timed {
+            session.message_timestamps[keep..].to_vec()
+        } else {
+            Vec::new()
+        },
+        paused_input: session.paused_input.clone(),
+        bookmarks: session.bookmarks.clone(),
+    };
+    let frame = encode(&LogRecordRef::Delta {
+        drop_front,
+        keep,
+        session: &tail,
+    })?;
+    let mut file = OpenOptions::new().append(true).open(path)?;
+    file.write_all(&frame)?;
+    file.sync_data()?;
+
+    cursor.last = session.clone();
+    cursor.file_len += frame.len() as u64;
+    cursor.deltas += 1;
+    Ok(Some(cursor))
+}
+
+/// `(drop_front, keep)` turning `previous.messages` into a prefix of
+/// `next.messages`, or `None` when a delta would not save anything (or the
+/// timestamp layout changed).
+fn message_delta(previous: &SavedSession, next: &SavedSession) -> Option<(usize, usize)> {
+    let timed = |session: &SavedSession| {
+        !session.message_timestamps.is_empty()
+            && session.message_timestamps.len() == session.messages.len()
+    };
+    let untimed = |session: &SavedSession| session.message_timestamps.is_empty();
+    let both_timed = timed(previous) && timed(next);
+    let both_untimed = untimed(previous) && untimed(next);
+    if !(both_timed || both_untimed) {
+        return None;
+    }
+
+    let drop_front = match next.messages.first() {
+        Some(first) => previous
+            .messages
+            .iter()
+            .position(|message| message == first)
+            .unwrap_or(previous.messages.len()),
+        None => previous.messages.len(),
+    };
+    let keep = (drop_front..previous.messages.len())
+        .zip(0..next.messages.len())
+        .take_while(|&(old, new)| {
+            previous.messages[old] == next.messages[new]
+                && (!both_timed || previous.message_timestamps[old] == next.message_timestamps[new])
+        })
+        .count();
+    if keep == 0 && !next.messages.is_empty() {
+        return None;
+    }
+    Some((drop_front, keep))
+}
+
+/// Borrowing twin of [`LogRecord`] so snapshots serialize without a clone.
+#[derive(Serialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+enum LogRecordRef<'a> {
+    Snapshot {
+        session: &'a SavedSession,
+    },
+    Delta {
+        drop_front: usize,
+        keep: usize,
+        session: &'a SavedSession,
+    },
+}
+
+fn encode(record: &LogRecordRef<'_>) -> io::Result<Vec<u8>> {
+    let mut line =
+        serde_json::to_vec(record).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    line.push(b'\n');
+    zstd::encode_all(line.as_slice(), COMPRESSION_LEVEL)
+}
+
+/// Replay the log at `path` into the session it describes.
+pub fn load(path: &Path) -> io::Result<SavedSession> {
+    let compressed = fs::read(path)?;
+    let mut lines = Vec::new();
+    let mut decoder = zstd::stream::read::Decoder::with_buffer(compressed.as_slice())?;
+    if let Err(err) = decoder.read_to_end(&mut lines) {
+        // A frame cut short by a crash mid-append. Everything decoded
+        // before it is intact.
+        tracing::warn!(target: "session", path = %path.display(), %err, "session log has a damaged tail");
+    }
+
+    let mut session: Option<SavedSession> = None;
+    for line in lines.split(|byte| *byte == b'\n') {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        let record: LogRecord = match serde_json::from_slice(line) {
+            Ok(record) => record,
+            Err(err) => {
+                tracing::warn!(target: "session", path = %path.display(), %err, "session log record unreadable; ignoring the rest");
+                break;
+            }
+        };
+        match record {
+            LogRecord::Snapshot { session: snapshot } => session = Some(snapshot),
+            LogRecord::Delta {
+                drop_front,
+                keep,
+                session: tail,
+            } => {
+                let Some(current) = session.as_mut() else {
+                    break;
+                };
+                apply_delta(current, drop_front, keep, tail);
+            }
+        }
+    }
+    session.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("session log {} has no snapshot", path.display()),
+        )
+    })
+}
+
+fn apply_delta(current: &mut SavedSession, drop_front: usize, keep: usize, tail: SavedSession) {
+    let mut messages = std::mem::take(&mut current.messages);
+    messages.drain(..drop_front.min(messages.len()));
+    messages.truncate(keep);
+    messages.extend(tail.messages);
+
+    let mut times = std::mem::take(&mut current.message_timestamps);
+    times.drain(..drop_front.min(times.len()));
+    times.truncate(keep);
+    times.extend(tail.message_timestamps);
+
+    *current = SavedSession {
+        messages,
+        message_timestamps: times,
+        ..tail
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ContentBlock, Message};
+    use crate::session_manager::create_saved_session;
+    use chrono::{TimeZone, Utc};
+
+    fn message(role: &str, text: &str) -> Message {
+        Message {
+            role: role.to_string(),
+            content: vec![ContentBlock::Text {
+                text: text.to_string(),
+                cache_control: None,
+            }],
+        }
+    }
+
+    /// Turns with distinct bodies, so compression cannot hide the size of
+    /// what gets rewritten.
+    fn conversation(turns: usize) -> Vec<Message> {
+        let body = |turn: usize, words: usize| {
+            (0..words)
+                .map(|word| format!("{:x}", (turn * 7919 + word * 104_729) % 1_000_003))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        (0..turns)
+            .flat_map(|turn| {
+                [
+                    message("user", &format!("question {turn}: {}", body(turn, 20))),
+                    message(
+                        "assistant",
+                        &format!("answer {turn}: {}", body(turn + 1, 60)),
+                    ),
+                ]
+            })
+            .collect()
+    }
+
+    fn session_with(messages: &[Message], id: &str) -> SavedSession {
+        let mut session = create_saved_session(messages, "m", Path::new("/tmp"), 0, None);
+        session.metadata.id = id.to_string();
+        // `create_saved_session` stamps every message `now`; untimed keeps
+        // consecutive saves comparable.
+        session.message_timestamps.clear();
+        session
+    }
+
+    #[test]
+    fn appends_small_deltas_and_replays_them() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("s.jsonl.zst");
+        let history = conversation(600);
+
+        let first = session_with(&history[..1000], "s");
+        let cursor = save(&path, None, &first).unwrap();
+        let snapshot_len = fs::metadata(&path).unwrap().len();
+
+        let second = session_with(&history[..1002], "s");
+        let cursor = save(&path, Some(cursor), &second).unwrap();
+        let delta_len = fs::metadata(&path).unwrap().len() - snapshot_len;
+        assert_eq!(cursor.deltas, 1);
+        assert!(
+            delta_len * 20 < snapshot_len,
+            "delta {delta_len} B vs snapshot {snapshot_len} B"
+        );
+
+        // Past the 500-message cap the window slides: still a delta.
+        let mut third = session_with(&history[..1004], "s");
+        third.metadata.title = "renamed".to_string();
+        let cursor = save(&path, Some(cursor), &third).unwrap();
+        assert_eq!(cursor.deltas, 2);
+
+        let loaded = load(&path).unwrap();
+        assert_eq!(loaded.messages, third.messages);
+        assert_eq!(loaded.system_prompt, third.system_prompt);
+        assert_eq!(loaded.metadata.title, "renamed");
+    }
+
+    #[test]
+    fn rewrites_history_edits_and_foreign_writes_as_snapshots() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("s.jsonl.zst");
+        let history = conversation(4);
+
+        let cursor = save(&path, None, &session_with(&history, "s")).unwrap();
+        // Compaction replaced every message: nothing to keep.
+        let compacted = session_with(&[message("user", "summary")], "s");
+        let cursor = save(&path, Some(cursor), &compacted).unwrap();
+        assert_eq!(cursor.deltas, 0);
+
+        // Another writer changed the file underneath this cursor.
+        let other = save(&path, None, &session_with(&history, "s")).unwrap();
+        let grown = session_with(
+            &[message("user", "summary"), message("assistant", "ok")],
+            "s",
+        );
+        let cursor = save(&path, Some(cursor), &grown).unwrap();
+        assert_eq!(cursor.deltas, 0);
+        assert_eq!(other.deltas, 0);
+        assert_eq!(load(&path).unwrap().messages, grown.messages);
+    }
+
+    #[test]
+    fn timestamps_follow_the_messages() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("s.jsonl.zst");
+        let history = conversation(3);
+        let t = |minute| Utc.with_ymd_and_hms(2026, 1, 1, 0, minute, 0).unwrap();
+
+        let mut first = session_with(&history[..4], "s");
+        first.message_timestamps = (0..4).map(t).collect();
+        let cursor = save(&path, None, &first).unwrap();
+        let mut second = session_with(&history, "s");
+        second.message_timestamps = (0..6).map(t).collect();
+        let cursor = save(&path, Some(cursor), &second).unwrap();
+        assert_eq!(cursor.deltas, 1);
+
+        let loaded = load(&path).unwrap();
+        assert_eq!(loaded.message_timestamps, second.message_timestamps);
+        assert_eq!(loaded.message_time(5), Some(t(5)));
+    }
+
+    #[test]
+    fn damaged_tail_keeps_the_records_before_it() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("s.jsonl.zst");
+        let history = conversation(3);
+
+        let cursor = save(&path, None, &session_with(&history[..4], "s")).unwrap();
+        save(&path, Some(cursor), &session_with(&history, "s")).unwrap();
+        let intact = fs::metadata(&path).unwrap().len();
+
+        let partial = encode(&LogRecordRef::Snapshot {
+            session: &session_with(&history[..1], "s"),
+        })
+        .unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&partial[..partial.len() / 2]).unwrap();
+        drop(file);
+        assert!(fs::metadata(&path).unwrap().len() > intact);
+
+        assert_eq!(load(&path).unwrap().messages, history);
+    }
+}
@@ -1,23 +1,27 @@
 //! Session management for resuming conversations.
 //!
 //! This module provides functionality for:
-//! - Saving sessions to disk
+//! - Saving sessions to disk (as append-only logs, see [`crate::session_log`])
 //! - Listing previous sessions
 //! - Resuming sessions by ID
 //! - Managing session lifecycle
 
 use crate::artifacts::ArtifactRecord;
 use crate::models::{ContentBlock, Message, SystemPrompt};
+use crate::session_log::{self, LOG_EXTENSION, LogCursor};
+use crate::tools::user_input::PausedUserInput;
 use crate::tui::file_mention::ContextReference;
 use crate::utils::write_atomic;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Component, Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
 use uuid::Uuid;
 
-/// Maximum number of sessions to retain
-const MAX_SESSIONS: usize = 50;
+/// Sessions kept by default when `[sessions] max_sessions` is unset.
+pub const DEFAULT_MAX_SESSIONS: usize = 50;
 /// Maximum number of messages to persist per session (#402 P0).
 /// Beyond this limit, the oldest messages are dropped and a truncation
 /// note is prepended to the system prompt. Keeps session files bounded
@@ -25,6 +29,9 @@ const MAX_SESSIONS: usize = 50;
 const MAX_PERSISTED_MESSAGES: usize = 500;
 const CURRENT_SESSION_SCHEMA_VERSION: u32 = 1;
 const CURRENT_QUEUE_SCHEMA_VERSION: u32 = 1;
+/// Sidecar holding just a logged session's metadata, so listing sessions
+/// never decompresses a log.
+const METADATA_EXTENSION: &str = "meta.json";
 
 const fn default_session_schema_version() -> u32 {
     CURRENT_SESSION_SCHEMA_VERSION
@@ -65,6 +72,9 @@ pub struct QueuedSessionMessage {
     pub display: String,
     #[serde(default)]
     pub skill_instruction: Option<String>,
+    /// Per-message mode override (`agent`, `plan`, `yolo`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
 }
 
 /// Persisted queue state for recovery after restart/crash.
@@ -100,6 +110,14 @@ pub struct SessionContextReference {
     pub reference: ContextReference,
 }
 
+/// A transcript cell the user bookmarked. The preview finds the cell again
+/// when the transcript is rebuilt on resume.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionBookmark {
+    pub cell_index: usize,
+    pub preview: String,
+}
+
 /// Session metadata stored with each saved session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionMetadata {
@@ -132,6 +150,31 @@ pub struct SessionMetadata {
     /// current saved sessions are linear JSON files, not per-entry trees.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub forked_from_message_count: Option<usize>,
+    /// Short recap shown in the session picker preview. Generated lazily
+    /// the first time the session is highlighted, and again once new
+    /// messages make it stale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<SessionSummary>,
+    /// What the assistant asked when the session was saved mid-question
+    /// (see [`SavedSession::paused_input`]); shown in the session picker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub awaiting_input: Option<String>,
+    /// Pinned sessions are never removed by retention GC.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    /// Free-form labels from `/sessions tag`; a tagged session is also
+    /// protected from retention GC.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Bullet-point recap of a saved session (see [`crate::session_summary`]).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub bullets: Vec<String>,
+    /// `message_count` at generation time; a mismatch marks the summary stale.
+    pub message_count: usize,
+    pub generated_at: DateTime<Utc>,
 }
 
 /// Cost and high-water-mark fields persisted with each session.
@@ -156,6 +199,18 @@ pub struct SessionCostSnapshot {
     /// Max-ever displayed session+subagent cost in CNY.
     #[serde(default)]
     pub displayed_cost_high_water_cny: f64,
+    /// Prompt tokens served from the provider's context cache.
+    #[serde(default)]
+    pub cache_hit_tokens: u64,
+    /// Prompt tokens the provider reported as cache misses.
+    #[serde(default)]
+    pub cache_miss_tokens: u64,
+    /// Estimated USD saved by context-cache hits.
+    #[serde(default)]
+    pub cache_saved_usd: f64,
+    /// Estimated CNY saved by context-cache hits.
+    #[serde(default)]
+    pub cache_saved_cny: f64,
 }
 
 impl SessionCostSnapshot {
@@ -171,6 +226,12 @@ impl SessionCostSnapshot {
 }
 
 impl SessionMetadata {
+    /// Whether retention GC must leave this session alone.
+    #[must_use]
+    pub fn is_protected(&self) -> bool {
+        self.pinned || !self.tags.is_empty()
+    }
+
     /// Copy cost fields from another metadata (used when forking a session).
     #[allow(dead_code)]
     pub fn copy_cost_from(&mut self, other: &SessionMetadata) {
@@ -204,6 +265,65 @@ pub struct SavedSession {
     /// Artifact contents are stored in the session-owned artifact directory.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub artifacts: Vec<ArtifactRecord>,
+    /// When each entry of `messages` was added, index-aligned. Drives
+    /// `deepseek sessions replay`; empty for sessions saved before it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub message_timestamps: Vec<DateTime<Utc>>,
+    /// Unanswered `request_user_input` call the turn is paused on. Resuming
+    /// the session re-opens the prompt and continues the turn with the answer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused_input: Option<PausedUserInput>,
+    /// Transcript bookmarks (`m` / `/bookmarks`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bookmarks: Vec<SessionBookmark>,
+}
+
+impl SavedSession {
+    /// Record (or clear) the question the turn is paused on.
+    pub fn set_paused_input(&mut self, paused: Option<PausedUserInput>) {
+        self.metadata.awaiting_input = paused.as_ref().map(PausedUserInput::label);
+        self.paused_input = paused;
+    }
+
+    /// Take message times tracked alongside the full (uncapped) history.
+    /// The latest ones are aligned with the persisted messages; a list
+    /// shorter than the history is ignored.
+    pub fn set_message_timestamps(&mut self, times: &[DateTime<Utc>]) {
+        if times.len() < self.metadata.message_count {
+            return;
+        }
+        let times = &times[..self.metadata.message_count];
+        self.message_timestamps = times[times.len() - self.messages.len()..].to_vec();
+    }
+
+    /// Timestamp of `messages[index]`, when recorded.
+    #[must_use]
+    pub fn message_time(&self, index: usize) -> Option<DateTime<Utc>> {
+        if self.message_timestamps.len() != self.messages.len() {
+            return None;
+        }
+        self.message_timestamps.get(index).copied()
+    }
+}
+
+/// Times for `current`: entries of the prefix unchanged since `previous`
+/// keep their time from `previous_times`, the rest are stamped `now`.
+#[must_use]
+pub fn stamp_message_times(
+    previous: &[Message],
+    previous_times: &[DateTime<Utc>],
+    current: &[Message],
+    now: DateTime<Utc>,
+) -> Vec<DateTime<Utc>> {
+    let kept = previous
+        .iter()
+        .zip(previous_times)
+        .zip(current)
+        .take_while(|((old, _), new)| old == new)
+        .count();
+    let mut times = previous_times[..kept].to_vec();
+    times.resize(current.len(), now);
+    times
 }
 
 /// Manager for session persistence operations
@@ -211,6 +331,9 @@ pub struct SavedSession {
 pub struct SessionManager {
     /// Directory where sessions are stored
     sessions_dir: PathBuf,
+    /// Logs this manager has written, by session id, so the next save of
+    /// the same session appends a delta instead of rewriting it.
+    logs: Mutex<HashMap<String, LogCursor>>,
 }
 
 impl SessionManager {
@@ -239,7 +362,10 @@ impl SessionManager {
         let sessions_dir = normalize_managed_dir(sessions_dir)?;
         // Ensure the sessions directory exists
         fs::create_dir_all(&sessions_dir)?;
-        Ok(Self { sessions_dir })
+        Ok(Self {
+            sessions_dir,
+            logs: Mutex::default(),
+        })
     }
 
     /// Create a `SessionManager` using the default location (~/.deepseek/sessions)
@@ -247,20 +373,27 @@ impl SessionManager {
         Self::new(default_sessions_dir()?)
     }
 
-    /// Save a session to disk using atomic write (temp file + fsync + rename).
+    /// Save a session to its log (`<id>.jsonl.zst`). The first save from
+    /// this manager writes a compacted snapshot; later saves append only
+    /// what changed. A legacy `<id>.json` is removed once the log exists.
     pub fn save_session(&self, session: &SavedSession) -> std::io::Result<PathBuf> {
-        let path = self.validated_session_path(&session.metadata.id)?;
+        let id = session.metadata.id.trim();
+        let legacy_path = self.validated_session_path(id)?;
+        let log_path = legacy_path.with_extension(LOG_EXTENSION);
 
-        let content = serde_json::to_string_pretty(session)
+        {
+            let mut logs = self.logs.lock().unwrap_or_else(PoisonError::into_inner);
+            let cursor = session_log::save(&log_path, logs.remove(id), session)?;
+            logs.insert(id.to_string(), cursor);
+        }
+        let metadata = serde_json::to_vec_pretty(&session.metadata)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        write_atomic(&legacy_path.with_extension(METADATA_EXTENSION), &metadata)?;
+        if legacy_path.exists() {
+            fs::remove_file(&legacy_path)?;
+        }
 
-        // Atomic write via write_atomic (NamedTempFile + fsync + persist)
-        write_atomic(&path, content.as_bytes())?;
-
-        // Clean up old sessions if we have too many
-        self.cleanup_old_sessions()?;
-
-        Ok(path)
+        Ok(log_path)
     }
 
     /// Save a crash-recovery checkpoint for in-flight turns.
@@ -304,6 +437,15 @@ impl SessionManager {
         Ok(())
     }
 
+    /// Clear the crash-recovery checkpoint unless another session wrote it,
+    /// so a turn finishing in one tab keeps another tab's checkpoint.
+    pub fn clear_checkpoint_for(&self, session_id: &str) -> std::io::Result<()> {
+        match self.load_checkpoint() {
+            Ok(Some(checkpoint)) if checkpoint.metadata.id != session_id => Ok(()),
+            _ => self.clear_checkpoint(),
+        }
+    }
+
     /// Save offline queue state (queued + draft messages).
     pub fn save_offline_queue_state(
         &self,
@@ -357,13 +499,18 @@ impl SessionManager {
         Ok(())
     }
 
-    /// Load a session by ID
+    /// Load a session by ID, from its log or a legacy `<id>.json` file.
     pub fn load_session(&self, id: &str) -> std::io::Result<SavedSession> {
         let path = self.validated_session_path(id)?;
-
-        let content = fs::read_to_string(&path)?;
-        let session: SavedSession = serde_json::from_str(&content)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let log_path = path.with_extension(LOG_EXTENSION);
+
+        let session: SavedSession = if log_path.exists() {
+            session_log::load(&log_path)?
+        } else {
+            let content = fs::read_to_string(&path)?;
+            serde_json::from_str(&content)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?
+        };
         if session.schema_version > CURRENT_SESSION_SCHEMA_VERSION {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
@@ -410,11 +557,35 @@ impl SessionManager {
         for entry in fs::read_dir(&self.sessions_dir)? {
             let entry = entry?;
             let path = entry.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
 
-            if path.extension().is_some_and(|ext| ext == "json")
-                && let Ok(session) = Self::load_session_metadata(&path)
+            let metadata = if name.ends_with(&format!(".{METADATA_EXTENSION}")) {
+                fs::read(&path).and_then(|raw| {
+                    serde_json::from_slice::<SessionMetadata>(&raw)
+                        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+                })
+            } else if name.ends_with(&format!(".{LOG_EXTENSION}")) {
+                // Normally covered by the sidecar; only a log whose sidecar
+                // is missing (crash between the two writes) is read here.
+                let stem = name.trim_end_matches(&format!(".{LOG_EXTENSION}"));
+                if path
+                    .with_file_name(format!("{stem}.{METADATA_EXTENSION}"))
+                    .exists()
+                {
+                    continue;
+                }
+                session_log::load(&path).map(|session| session.metadata)
+            } else if path.extension().is_some_and(|ext| ext == "json")
+                && !path.with_extension(LOG_EXTENSION).exists()
             {
-                sessions.push(session);
+                Self::load_session_metadata(&path)
+            } else {
+                continue;
+            };
+            if let Ok(metadata) = metadata {
+                sessions.push(metadata);
             }
         }
 
@@ -424,7 +595,7 @@ impl SessionManager {
         Ok(sessions)
     }
 
-    /// Load only the metadata from a session file.
+    /// Load only the metadata from a legacy `<id>.json` session file.
     ///
     /// Optimization for #337: previously this called
     /// `serde_json::from_reader` which forces serde to scan every token in
@@ -470,7 +641,28 @@ impl SessionManager {
     /// Delete a session by ID
     pub fn delete_session(&self, id: &str) -> std::io::Result<()> {
         let path = self.validated_session_path(id)?;
-        fs::remove_file(path)?;
+        let mut removed = false;
+        for file in [
+            path.with_extension(LOG_EXTENSION),
+            path.with_extension(METADATA_EXTENSION),
+            path.clone(),
+        ] {
+            match fs::remove_file(&file) {
+                Ok(()) => removed = true,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+        if !removed {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("No saved session '{}'", id.trim()),
+            ));
+        }
+        self.logs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(id.trim());
         let session_dir = self.sessions_dir.join(id.trim());
         if session_dir.exists() {
             fs::remove_dir_all(session_dir)?;
@@ -478,18 +670,67 @@ impl SessionManager {
         Ok(())
     }
 
-    /// Clean up old sessions to stay within `MAX_SESSIONS` limit
-    fn cleanup_old_sessions(&self) -> std::io::Result<()> {
-        let sessions = self.list_sessions()?;
+    /// Bytes on disk for a session: its log, sidecar, legacy file, and the
+    /// `<id>/` artifacts directory.
+    pub fn session_bytes(&self, id: &str) -> u64 {
+        let Ok(path) = self.validated_session_path(id) else {
+            return 0;
+        };
+        let files: u64 = [
+            path.with_extension(LOG_EXTENSION),
+            path.with_extension(METADATA_EXTENSION),
+            path,
+        ]
+        .iter()
+        .filter_map(|file| fs::metadata(file).ok())
+        .map(|meta| meta.len())
+        .sum();
+        files + dir_bytes(&self.sessions_dir.join(id.trim()))
+    }
 
-        if sessions.len() > MAX_SESSIONS {
-            // Delete oldest sessions
-            for session in sessions.iter().skip(MAX_SESSIONS) {
-                let _ = self.delete_session(&session.id);
+    /// Work out which sessions `retention` would remove. Sessions in `keep`
+    /// (the one open in the TUI) are treated like pinned ones.
+    pub fn plan_gc(&self, retention: &SessionRetention, keep: &[&str]) -> std::io::Result<GcPlan> {
+        let sessions = self
+            .list_sessions()?
+            .into_iter()
+            .map(|meta| {
+                let bytes = self.session_bytes(&meta.id);
+                (meta, bytes)
+            })
+            .collect();
+        Ok(plan_retention(sessions, retention, keep, Utc::now()))
+    }
+
+    /// Delete the sessions in `plan`. Returns how many were removed; a
+    /// record that fails to delete is logged and skipped.
+    pub fn apply_gc(&self, plan: &GcPlan) -> usize {
+        let mut removed = 0usize;
+        for candidate in &plan.remove {
+            match self.delete_session(&candidate.id) {
+                Ok(()) => removed += 1,
+                Err(err) => tracing::warn!(
+                    target: "session",
+                    session = candidate.id,
+                    ?err,
+                    "session GC skipped a record",
+                ),
             }
         }
+        removed
+    }
 
-        Ok(())
+    /// Load session `id` (or a unique prefix), apply `edit` to its metadata,
+    /// and save it. Returns the updated metadata.
+    pub fn update_metadata(
+        &self,
+        id: &str,
+        edit: impl FnOnce(&mut SessionMetadata),
+    ) -> std::io::Result<SessionMetadata> {
+        let mut session = self.load_session_by_prefix(id)?;
+        edit(&mut session.metadata);
+        self.save_session(&session)?;
+        Ok(session.metadata)
     }
 
     /// Remove session files whose `updated_at` is older than `max_age`
@@ -501,8 +742,9 @@ impl SessionManager {
     /// Crash-recovery safety: skips the running checkpoint
     /// (`checkpoints/latest.json`) and any file under `checkpoints/`
     /// — those are owned by the checkpoint subsystem and live with
-    /// stricter durability rules. Only top-level `<session_id>.json`
-    /// files are candidates.
+    /// stricter durability rules. Only top-level session records
+    /// (`<session_id>.jsonl.zst` logs and legacy `<session_id>.json`
+    /// files) are candidates.
     ///
     /// `max_age` is checked against the metadata's `updated_at`
     /// timestamp embedded in the JSON, not the filesystem mtime — the
@@ -557,6 +799,164 @@ impl SessionManager {
     }
 }
 
+/// Limits applied by session GC (see [`crate::config::SessionsConfig`]).
+/// `None` disables a limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionRetention {
+    pub max_sessions: Option<usize>,
+    pub max_age: Option<std::time::Duration>,
+    pub max_total_bytes: Option<u64>,
+}
+
+impl Default for SessionRetention {
+    fn default() -> Self {
+        Self {
+            max_sessions: Some(DEFAULT_MAX_SESSIONS),
+            max_age: None,
+            max_total_bytes: None,
+        }
+    }
+}
+
+/// Which limit put a session on the GC list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcReason {
+    Age,
+    Count,
+    Size,
+}
+
+impl GcReason {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            GcReason::Age => "older than max_age_days",
+            GcReason::Count => "beyond max_sessions",
+            GcReason::Size => "over max_total_mb",
+        }
+    }
+}
+
+/// A session GC would remove.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GcCandidate {
+    pub id: String,
+    pub title: String,
+    pub updated_at: DateTime<Utc>,
+    pub bytes: u64,
+    pub reason: GcReason,
+}
+
+/// Result of [`SessionManager::plan_gc`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GcPlan {
+    pub remove: Vec<GcCandidate>,
+    /// Sessions left after the plan runs.
+    pub kept: usize,
+    /// Pinned, tagged, or open sessions that were exempt from the limits.
+    pub protected: usize,
+    pub bytes_before: u64,
+}
+
+impl GcPlan {
+    #[must_use]
+    pub fn bytes_freed(&self) -> u64 {
+        self.remove.iter().map(|candidate| candidate.bytes).sum()
+    }
+
+    /// One-line summary, e.g. `removed 3 sessions (13 MB); 50 kept, 2 protected`.
+    #[must_use]
+    pub fn summary(&self, verb: &str) -> String {
+        let count = self.remove.len();
+        format!(
+            "{verb} {count} session{} ({}); {} kept, {} protected",
+            if count == 1 { "" } else { "s" },
+            crate::artifacts::format_byte_size(self.bytes_freed()),
+            self.kept,
+            self.protected
+        )
+    }
+}
+
+/// Decide what retention removes from `sessions` (newest first, with their
+/// sizes). Protected sessions count toward `max_sessions` and
+/// `max_total_bytes` but are never removed, so the limits can be exceeded
+/// when most sessions are protected.
+fn plan_retention(
+    mut sessions: Vec<(SessionMetadata, u64)>,
+    retention: &SessionRetention,
+    keep: &[&str],
+    now: DateTime<Utc>,
+) -> GcPlan {
+    sessions.sort_by_key(|(meta, _)| std::cmp::Reverse(meta.updated_at));
+    let cutoff = retention.max_age.map(|age| {
+        now - chrono::Duration::from_std(age).unwrap_or(chrono::Duration::days(365 * 10))
+    });
+
+    let mut plan = GcPlan {
+        bytes_before: sessions.iter().map(|(_, bytes)| bytes).sum(),
+        ..GcPlan::default()
+    };
+    let mut kept: Vec<(SessionMetadata, u64, bool)> = Vec::new();
+    for (meta, bytes) in sessions {
+        let protected = meta.is_protected() || keep.contains(&meta.id.as_str());
+        if protected {
+            plan.protected += 1;
+            kept.push((meta, bytes, true));
+            continue;
+        }
+        let reason = if cutoff.is_some_and(|cutoff| meta.updated_at < cutoff) {
+            Some(GcReason::Age)
+        } else if retention.max_sessions.is_some_and(|max| kept.len() >= max) {
+            Some(GcReason::Count)
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => plan.remove.push(candidate(meta, bytes, reason)),
+            None => kept.push((meta, bytes, false)),
+        }
+    }
+
+    if let Some(max_bytes) = retention.max_total_bytes {
+        let mut total: u64 = kept.iter().map(|(_, bytes, _)| bytes).sum();
+        while total > max_bytes {
+            let Some(oldest) = kept.iter().rposition(|(_, _, protected)| !protected) else {
+                break;
+            };
+            let (meta, bytes, _) = kept.remove(oldest);
+            total -= bytes;
+            plan.remove.push(candidate(meta, bytes, GcReason::Size));
+        }
+    }
+    plan.kept = kept.len();
+    plan
+}
+
+fn candidate(meta: SessionMetadata, bytes: u64, reason: GcReason) -> GcCandidate {
+    GcCandidate {
+        id: meta.id,
+        title: meta.title,
+        updated_at: meta.updated_at,
+        bytes,
+        reason,
+    }
+}
+
+fn dir_bytes(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => dir_bytes(&entry.path()),
+            Ok(_) => entry.metadata().map_or(0, |meta| meta.len()),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
 pub(crate) fn workspace_scope_matches(saved_workspace: &Path, current_workspace: &Path) -> bool {
     if paths_equivalent(saved_workspace, current_workspace) {
         return true;
@@ -631,6 +1031,19 @@ pub fn prune_workspace_snapshots(workspace: &Path, max_age: std::time::Duration)
     }
 }
 
+/// Index of the assistant message that issued tool call `tool_id`, when it is
+/// the latest assistant message. Anything after it (results written while
+/// the app was shutting down) is stale once the paused turn resumes.
+#[must_use]
+pub fn paused_call_index(messages: &[Message], tool_id: &str) -> Option<usize> {
+    let index = messages.iter().rposition(|m| m.role == "assistant")?;
+    messages[index]
+        .content
+        .iter()
+        .any(|block| matches!(block, ContentBlock::ToolUse { id, .. } if id == tool_id))
+        .then_some(index)
+}
+
 /// Create a new `SavedSession` from conversation state
 pub fn create_saved_session(
     messages: &[Message],
@@ -701,6 +1114,7 @@ pub fn create_saved_session_with_id_and_mode(
         .unwrap_or_else(|| "New Session".to_string());
 
     let (capped_messages, truncation_note) = cap_messages(messages);
+    let capped_messages_len = capped_messages.len();
 
     SavedSession {
         schema_version: CURRENT_SESSION_SCHEMA_VERSION,
@@ -717,6 +1131,10 @@ pub fn create_saved_session_with_id_and_mode(
             cost: SessionCostSnapshot::default(),
             parent_session_id: None,
             forked_from_message_count: None,
+            summary: None,
+            awaiting_input: None,
+            pinned: false,
+            tags: Vec::new(),
         },
         messages: capped_messages,
         system_prompt: merge_truncation_note(
@@ -725,6 +1143,9 @@ pub fn create_saved_session_with_id_and_mode(
         ),
         context_references: Vec::new(),
         artifacts: Vec::new(),
+        message_timestamps: vec![now; capped_messages_len],
+        paused_input: None,
+        bookmarks: Vec::new(),
     }
 }
 
@@ -737,8 +1158,16 @@ pub fn update_session(
 ) -> SavedSession {
     session.schema_version = CURRENT_SESSION_SCHEMA_VERSION;
     let (capped_messages, truncation_note) = cap_messages(messages);
+    let now = Utc::now();
+    let previous_times = if session.message_timestamps.len() == session.messages.len() {
+        std::mem::take(&mut session.message_timestamps)
+    } else {
+        Vec::new()
+    };
+    session.message_timestamps =
+        stamp_message_times(&session.messages, &previous_times, &capped_messages, now);
     session.messages = capped_messages;
-    session.metadata.updated_at = Utc::now();
+    session.metadata.updated_at = now;
     session.metadata.message_count = messages.len();
     session.metadata.total_tokens = total_tokens;
     session.system_prompt = merge_truncation_note(
@@ -969,13 +1398,21 @@ pub fn format_session_line(meta: &SessionMetadata) -> String {
         .as_deref()
         .map(|parent| format!(" | fork {}", truncate_id(parent)))
         .unwrap_or_default();
+    let mut labels = String::new();
+    if meta.pinned {
+        labels.push_str(" | pinned");
+    }
+    for tag in &meta.tags {
+        labels.push_str(&format!(" #{tag}"));
+    }
 
     format!(
-        "{} | {} | {} msgs{} | {}",
+        "{} | {} | {} msgs{}{} | {}",
         truncate_id(&meta.id),
         truncated_title,
         meta.message_count,
         fork_label,
+        labels,
         age
     )
 }
@@ -1039,10 +1476,17 @@ mod tests {
                 cost: SessionCostSnapshot::default(),
                 parent_session_id: None,
                 forked_from_message_count: None,
+                summary: None,
+                awaiting_input: None,
+                pinned: false,
+                tags: Vec::new(),
             },
             system_prompt: None,
             context_references: Vec::new(),
             artifacts: Vec::new(),
+            message_timestamps: Vec::new(),
+            paused_input: None,
+            bookmarks: Vec::new(),
         };
         manager.save_session(&session).expect("save");
     }
@@ -1069,10 +1513,17 @@ mod tests {
                 cost: SessionCostSnapshot::default(),
                 parent_session_id: None,
                 forked_from_message_count: None,
+                summary: None,
+                awaiting_input: None,
+                pinned: false,
+                tags: Vec::new(),
             },
             system_prompt: None,
             context_references: Vec::new(),
             artifacts: Vec::new(),
+            message_timestamps: Vec::new(),
+            paused_input: None,
+            bookmarks: Vec::new(),
         };
         manager.save_session(&session).expect("save empty");
     }
@@ -1105,6 +1556,39 @@ mod tests {
         assert_eq!(loaded.messages.len(), 2);
     }
 
+    #[test]
+    fn message_timestamps_survive_updates_and_align_with_capped_history() {
+        let tmp = tempdir().expect("tempdir");
+        let first = vec![make_test_message("user", "Hello!")];
+        let mut session = create_saved_session(&first, "test-model", tmp.path(), 0, None);
+        let created = session.message_timestamps[0];
+        assert_eq!(session.message_time(0), Some(created));
+
+        let mut longer = first.clone();
+        longer.push(make_test_message("assistant", "Hi there!"));
+        session = update_session(session, &longer, 0, None);
+        assert_eq!(session.message_timestamps.len(), 2);
+        assert_eq!(session.message_timestamps[0], created);
+
+        let earlier = created - chrono::Duration::minutes(5);
+        let times = vec![earlier, earlier + chrono::Duration::seconds(30)];
+        let edited = vec![make_test_message("user", "Hello again!")];
+        assert_eq!(
+            stamp_message_times(&longer, &times, &edited, created),
+            vec![created]
+        );
+        assert_eq!(
+            stamp_message_times(&longer[..1], &times[..1], &longer, created),
+            vec![earlier, created]
+        );
+
+        session.set_message_timestamps(&times);
+        assert_eq!(session.message_timestamps, times);
+        // A shorter list than the history is ignored.
+        session.set_message_timestamps(&times[..1]);
+        assert_eq!(session.message_timestamps, times);
+    }
+
     #[test]
     fn test_list_sessions() {
         let tmp = tempdir().expect("tempdir");
@@ -1445,10 +1929,12 @@ mod tests {
             messages: vec![QueuedSessionMessage {
                 display: "queued message".to_string(),
                 skill_instruction: Some("Use skill".to_string()),
+                mode: Some("plan".to_string()),
             }],
             draft: Some(QueuedSessionMessage {
                 display: "draft message".to_string(),
                 skill_instruction: None,
+                mode: None,
             }),
             ..OfflineQueueState::default()
         };
@@ -1462,6 +1948,7 @@ mod tests {
             .expect("queue state exists");
         assert_eq!(loaded.messages.len(), 1);
         assert_eq!(loaded.messages[0].display, "queued message");
+        assert_eq!(loaded.messages[0].mode.as_deref(), Some("plan"));
         assert!(loaded.draft.is_some());
 
         manager
@@ -1489,6 +1976,7 @@ mod tests {
             messages: vec![QueuedSessionMessage {
                 display: "first parked".to_string(),
                 skill_instruction: None,
+                mode: None,
             }],
             ..OfflineQueueState::default()
         };
@@ -1593,6 +2081,52 @@ mod tests {
         assert!(err.to_string().contains("newer than supported"));
     }
 
+    #[test]
+    fn legacy_json_session_loads_and_migrates_to_a_log_on_save() {
+        let tmp = tempdir().expect("tempdir");
+        let sessions_dir = tmp.path().join("sessions");
+        let manager = SessionManager::new(sessions_dir.clone()).expect("new");
+
+        let mut session = create_saved_session(
+            &[make_test_message("user", "from an older release")],
+            "test-model",
+            tmp.path(),
+            10,
+            None,
+        );
+        let id = session.metadata.id.clone();
+        let legacy = sessions_dir.join(format!("{id}.json"));
+        fs::write(&legacy, serde_json::to_string_pretty(&session).unwrap()).unwrap();
+
+        assert_eq!(manager.list_sessions().unwrap().len(), 1);
+        assert_eq!(manager.load_session(&id).unwrap().messages.len(), 1);
+
+        session
+            .messages
+            .push(make_test_message("assistant", "now appended"));
+        session.message_timestamps.clear();
+        session.metadata.message_count = 2;
+        manager.save_session(&session).expect("save");
+        assert!(!legacy.exists());
+        assert!(sessions_dir.join(format!("{id}.jsonl.zst")).exists());
+        assert!(sessions_dir.join(format!("{id}.meta.json")).exists());
+
+        session
+            .messages
+            .push(make_test_message("user", "and again"));
+        session.metadata.message_count = 3;
+        manager.save_session(&session).expect("delta save");
+
+        let listed = manager.list_sessions().unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].message_count, 3);
+        let fresh = SessionManager::new(sessions_dir).expect("new");
+        assert_eq!(fresh.load_session(&id).unwrap().messages, session.messages);
+        fresh.delete_session(&id).expect("delete");
+        assert!(fresh.list_sessions().unwrap().is_empty());
+        assert!(fresh.delete_session(&id).is_err());
+    }
+
     #[test]
     fn test_load_session_rejects_newer_schema() {
         let tmp = tempdir().expect("tempdir");
@@ -1889,6 +2423,117 @@ mod tests {
         );
     }
 
+    fn retention_meta(id: &str, hours_ago: i64) -> SessionMetadata {
+        let tmp = tempdir().expect("tempdir");
+        let manager = SessionManager::new(tmp.path().join("sessions")).expect("new");
+        write_session_record(
+            &manager,
+            id,
+            Path::new("/tmp"),
+            Utc::now() - chrono::Duration::hours(hours_ago),
+        );
+        manager.load_session(id).expect("load").metadata
+    }
+
+    #[test]
+    fn retention_plan_spares_pinned_tagged_and_open_sessions() {
+        let mut pinned = retention_meta("pinned", 5);
+        pinned.pinned = true;
+        let mut tagged = retention_meta("tagged", 6);
+        tagged.tags = vec!["release".to_string()];
+        let sessions = vec![
+            (retention_meta("new", 1), 10),
+            (retention_meta("open", 2), 10),
+            (retention_meta("mid", 3), 10),
+            (retention_meta("old", 4), 10),
+            (pinned, 10),
+            (tagged, 10),
+        ];
+        let retention = SessionRetention {
+            max_sessions: Some(2),
+            max_age: None,
+            max_total_bytes: None,
+        };
+        let plan = plan_retention(sessions, &retention, &["open"], Utc::now());
+        let removed: Vec<&str> = plan.remove.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(removed, vec!["mid", "old"]);
+        assert!(plan.remove.iter().all(|c| c.reason == GcReason::Count));
+        assert_eq!((plan.kept, plan.protected), (4, 3));
+        assert_eq!(plan.bytes_before, 60);
+        assert_eq!(plan.bytes_freed(), 20);
+    }
+
+    #[test]
+    fn retention_plan_applies_age_then_size_oldest_first() {
+        let sessions = vec![
+            (retention_meta("a", 1), 400),
+            (retention_meta("b", 2), 400),
+            (retention_meta("c", 3), 400),
+            (retention_meta("stale", 24 * 40), 5),
+        ];
+        let retention = SessionRetention {
+            max_sessions: None,
+            max_age: Some(std::time::Duration::from_secs(30 * 24 * 3600)),
+            max_total_bytes: Some(900),
+        };
+        let plan = plan_retention(sessions, &retention, &[], Utc::now());
+        let removed: Vec<(&str, GcReason)> = plan
+            .remove
+            .iter()
+            .map(|c| (c.id.as_str(), c.reason))
+            .collect();
+        assert_eq!(
+            removed,
+            vec![("stale", GcReason::Age), ("c", GcReason::Size)]
+        );
+        assert_eq!(plan.kept, 2);
+        assert_eq!(
+            plan.summary("would remove"),
+            "would remove 2 sessions (405 B); 2 kept, 0 protected"
+        );
+    }
+
+    #[test]
+    fn gc_counts_artifacts_and_survives_pinning_round_trip() {
+        let tmp = tempdir().expect("tempdir");
+        let manager = SessionManager::new(tmp.path().join("sessions")).expect("new");
+        let now = Utc::now();
+        write_session_record(&manager, "keep", Path::new("/tmp"), now);
+        write_session_record(
+            &manager,
+            "drop",
+            Path::new("/tmp"),
+            now - chrono::Duration::hours(1),
+        );
+        let artifacts = tmp
+            .path()
+            .join("sessions")
+            .join("drop")
+            .join("tool_outputs");
+        fs::create_dir_all(&artifacts).expect("artifacts dir");
+        fs::write(artifacts.join("out.txt"), vec![b'x'; 4096]).expect("artifact");
+        assert!(manager.session_bytes("drop") > 4096);
+
+        let updated = manager
+            .update_metadata("keep", |meta| meta.tags.push("bench".to_string()))
+            .expect("tag");
+        assert_eq!(updated.tags, vec!["bench".to_string()]);
+        let retention = SessionRetention {
+            max_sessions: Some(0),
+            max_age: None,
+            max_total_bytes: None,
+        };
+        let plan = manager.plan_gc(&retention, &[]).expect("plan");
+        assert_eq!(plan.remove.len(), 1);
+        assert_eq!(plan.remove[0].id, "drop");
+        assert_eq!(manager.apply_gc(&plan), 1);
+        assert!(!tmp.path().join("sessions").join("drop").exists());
+        let remaining = manager.list_sessions().expect("list");
+        assert_eq!(remaining.len(), 1);
+        assert!(remaining[0].is_protected());
+        assert!(format_session_line(&remaining[0]).contains("#bench"));
+    }
+
     #[test]
     fn test_load_offline_queue_rejects_newer_schema() {
         let tmp = tempdir().expect("tempdir");
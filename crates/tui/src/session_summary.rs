@@ -0,0 +1,355 @@
+//! Offline recap of a saved session for the session picker preview.
+//!
+//! The summary is extractive: it reads the persisted transcript and
+//! reports what was asked, how much work happened, which files were
+//! touched, how the conversation ended, and what the model's self-reports
+//! (see [`crate::self_report`]) added up to. No model call is involved, so
+//! it is cheap enough to build the first time a session is highlighted and
+//! store it in [`SessionMetadata::summary`](crate::session_manager::SessionMetadata).
+
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use serde_json::Value;
+
+use crate::models::ContentBlock;
+use crate::self_report::SelfReportTally;
+use crate::session_manager::{
+    SavedSession, SessionManager, SessionSummary, extract_user_prompt, strip_thinking_tags,
+};
+use crate::utils::truncate_with_ellipsis;
+
+const PROMPT_CHARS: usize = 100;
+const OUTCOME_CHARS: usize = 140;
+const MAX_LISTED_FILES: usize = 4;
+const MAX_LISTED_TOOLS: usize = 3;
+
+/// Tools whose `path` argument names a file they modify.
+const WRITE_TOOLS: &[&str] = &[
+    "write_file",
+    "edit_file",
+    "apply_patch",
+    "fim_edit",
+    "edit_with_fim",
+];
+
+/// Build a 3–5 bullet recap of `session`.
+#[must_use]
+pub fn summarize_session(session: &SavedSession) -> SessionSummary {
+    let mut prompts = Vec::new();
+    let mut tool_counts: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut tool_errors = 0usize;
+    let mut files: Vec<String> = Vec::new();
+    let mut last_reply: Option<String> = None;
+    let mut self_reports = SelfReportTally::default();
+
+    for message in &session.messages {
+        let is_user = message.role.eq_ignore_ascii_case("user");
+        for block in &message.content {
+            match block {
+                ContentBlock::Text { text, .. } if is_user => {
+                    let prompt = extract_user_prompt(text).trim();
+                    if !prompt.is_empty() {
+                        prompts.push(prompt.to_string());
+                    }
+                }
+                ContentBlock::Text { text, .. } => {
+                    let (text, report) = crate::self_report::split(text);
+                    if let Some(report) = report {
+                        self_reports.add(&report);
+                    }
+                    let reply = strip_thinking_tags(text);
+                    if !reply.trim().is_empty() {
+                        last_reply = Some(reply);
+                    }
+                }
+                ContentBlock::ToolUse { name, input, .. } => {
+                    *tool_counts.entry(name.as_str()).or_default() += 1;
+                    for path in written_paths(name, input) {
+                        if !files.contains(&path) {
+                            files.push(path);
+                        }
+                    }
+                }
+                ContentBlock::ToolResult { is_error, .. } if is_error.unwrap_or(false) => {
+                    tool_errors += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut bullets = Vec::new();
+    match prompts.first() {
+        Some(first) => bullets.push(format!("Asked: {}", clip(first, PROMPT_CHARS))),
+        None => bullets.push("No user prompts recorded".to_string()),
+    }
+
+    let tool_total: usize = tool_counts.values().sum();
+    let mut work = format!(
+        "{} prompt{}, {} tool call{}",
+        prompts.len(),
+        plural(prompts.len()),
+        tool_total,
+        plural(tool_total)
+    );
+    if tool_total > 0 {
+        let mut ranked: Vec<(&str, usize)> = tool_counts.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        let top = ranked
+            .iter()
+            .take(MAX_LISTED_TOOLS)
+            .map(|(name, count)| format!("{name} ×{count}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        work.push_str(&format!(" ({top})"));
+    }
+    if tool_errors > 0 {
+        work.push_str(&format!(
+            "; {tool_errors} failed tool call{}",
+            plural(tool_errors)
+        ));
+    }
+    bullets.push(work);
+
+    if !files.is_empty() {
+        let mut listed = files
+            .iter()
+            .take(MAX_LISTED_FILES)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if files.len() > MAX_LISTED_FILES {
+            listed.push_str(&format!(" (+{} more)", files.len() - MAX_LISTED_FILES));
+        }
+        bullets.push(format!("Files touched: {listed}"));
+    }
+
+    if prompts.len() > 1
+        && let Some(latest) = prompts.last()
+    {
+        bullets.push(format!("Last asked: {}", clip(latest, PROMPT_CHARS)));
+    }
+
+    if let Some(reply) = last_reply {
+        bullets.push(format!("Outcome: {}", clip(&reply, OUTCOME_CHARS)));
+    }
+
+    if self_reports.reports > 0 {
+        bullets.push(format!("Self-report: {}", self_reports.summary()));
+    }
+
+    SessionSummary {
+        bullets,
+        message_count: session.metadata.message_count,
+        generated_at: Utc::now(),
+    }
+}
+
+/// True when `session` carries a summary built from its current messages.
+#[must_use]
+pub fn has_current_summary(session: &SavedSession) -> bool {
+    session
+        .metadata
+        .summary
+        .as_ref()
+        .is_some_and(|summary| summary.message_count == session.metadata.message_count)
+}
+
+/// Attach a fresh summary to the stored session `id` and return it. The
+/// file is re-read right before writing so a concurrent save from the
+/// running TUI is not rolled back by a stale copy.
+pub fn store_summary(manager: &SessionManager, id: &str) -> std::io::Result<SessionSummary> {
+    let mut session = manager.load_session(id)?;
+    let summary = summarize_session(&session);
+    session.metadata.summary = Some(summary.clone());
+    manager.save_session(&session)?;
+    Ok(summary)
+}
+
+/// Files a tool call writes, or nothing for tools that do not modify files.
+pub(crate) fn written_paths(tool: &str, input: &Value) -> Vec<String> {
+    if WRITE_TOOLS.contains(&tool) {
+        touched_paths(input)
+    } else {
+        Vec::new()
+    }
+}
+
+/// File paths a write-type tool call names in its input.
+fn touched_paths(input: &Value) -> Vec<String> {
+    let mut paths = Vec::new();
+    for key in ["path", "file_path"] {
+        if let Some(path) = input.get(key).and_then(Value::as_str) {
+            paths.push(path.to_string());
+        }
+    }
+    if let Some(changes) = input.get("changes").and_then(Value::as_array) {
+        paths.extend(
+            changes
+                .iter()
+                .filter_map(|change| change.get("path").and_then(Value::as_str))
+                .map(str::to_string),
+        );
+    }
+    if let Some(patch) = input.get("patch").and_then(Value::as_str) {
+        for line in patch.lines() {
+            let path = line
+                .strip_prefix("*** Update File: ")
+                .or_else(|| line.strip_prefix("*** Add File: "))
+                .or_else(|| line.strip_prefix("*** Delete File: "))
+                .or_else(|| line.strip_prefix("+++ b/"));
+            if let Some(path) = path.map(str::trim).filter(|path| !path.is_empty()) {
+                paths.push(path.to_string());
+            }
+        }
+    }
+    paths.dedup();
+    paths
+}
+
+/// First non-blank line of `text`, clipped to `max_len` bytes.
+fn clip(text: &str, max_len: usize) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    truncate_with_ellipsis(line, max_len, "…")
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Message;
+    use serde_json::json;
+
+    fn message(role: &str, content: Vec<ContentBlock>) -> Message {
+        Message {
+            role: role.to_string(),
+            content,
+        }
+    }
+
+    fn text(body: &str) -> ContentBlock {
+        ContentBlock::Text {
+            text: body.to_string(),
+            cache_control: None,
+        }
+    }
+
+    fn tool_use(name: &str, input: Value) -> ContentBlock {
+        ContentBlock::ToolUse {
+            id: format!("call_{name}"),
+            name: name.to_string(),
+            input,
+            caller: None,
+        }
+    }
+
+    fn session(messages: Vec<Message>) -> SavedSession {
+        crate::session_manager::create_saved_session(
+            &messages,
+            "deepseek-v4-pro",
+            std::path::Path::new("/tmp"),
+            0,
+            None,
+        )
+    }
+
+    #[test]
+    fn summary_reports_prompt_work_files_and_outcome() {
+        let saved = session(vec![
+            message("user", vec![text("Fix the flaky parser test")]),
+            message(
+                "assistant",
+                vec![
+                    tool_use("read_file", json!({"path": "src/parser.rs"})),
+                    tool_use("edit_file", json!({"path": "src/parser.rs"})),
+                    tool_use(
+                        "apply_patch",
+                        json!({"patch": "*** Begin Patch\n*** Update File: tests/parser.rs\n*** End Patch"}),
+                    ),
+                ],
+            ),
+            message(
+                "user",
+                vec![ContentBlock::ToolResult {
+                    tool_use_id: "call_edit_file".to_string(),
+                    content: "boom".to_string(),
+                    is_error: Some(true),
+                    content_blocks: None,
+                }],
+            ),
+            message("user", vec![text("Now run the tests")]),
+            message(
+                "assistant",
+                vec![text("All parser tests pass now.\nDetails follow.")],
+            ),
+        ]);
+
+        let summary = summarize_session(&saved);
+        assert_eq!(
+            summary.bullets,
+            vec![
+                "Asked: Fix the flaky parser test".to_string(),
+                "2 prompts, 3 tool calls (apply_patch ×1, edit_file ×1, read_file ×1); 1 failed tool call"
+                    .to_string(),
+                "Files touched: src/parser.rs, tests/parser.rs".to_string(),
+                "Last asked: Now run the tests".to_string(),
+                "Outcome: All parser tests pass now.".to_string(),
+            ]
+        );
+        assert_eq!(summary.message_count, saved.metadata.message_count);
+    }
+
+    #[test]
+    fn summary_tallies_self_reports_and_hides_the_footer() {
+        let saved = session(vec![
+            message("user", vec![text("Speed up the cache")]),
+            message(
+                "assistant",
+                vec![text(
+                    "Added an LRU.\n<self_report>\nconfidence: high\n</self_report>",
+                )],
+            ),
+            message(
+                "assistant",
+                vec![text(
+                    "Eviction is untested.\n<self_report>\nconfidence: low\n\
+                     assumptions: keys are small\nnot_inspected: src/store.rs\n</self_report>",
+                )],
+            ),
+        ]);
+
+        let summary = summarize_session(&saved);
+        assert_eq!(
+            summary.bullets[2..],
+            [
+                "Outcome: Eviction is untested.".to_string(),
+                "Self-report: 2 self-reports (1 high, 1 low); 1 assumption; not inspected: src/store.rs"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn summary_staleness_tracks_message_count() {
+        let mut saved = session(vec![message("user", vec![text("hello")])]);
+        assert!(!has_current_summary(&saved));
+        saved.metadata.summary = Some(summarize_session(&saved));
+        assert!(has_current_summary(&saved));
+        saved.metadata.message_count += 2;
+        assert!(!has_current_summary(&saved));
+    }
+
+    #[test]
+    fn clip_keeps_first_line_and_marks_truncation() {
+        assert_eq!(clip("\n  first line  \nsecond", 40), "first line");
+        assert_eq!(clip("abcdefghij", 6), "abc…");
+    }
+}
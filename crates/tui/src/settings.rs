@@ -5,6 +5,7 @@
 //! TUI-specific preferences (theme, keybinds, font_size) that survive project
 //! switches are stored separately at ~/.deepseek/tui.toml. See [`TuiPrefs`].
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
@@ -161,12 +162,45 @@ impl TuiPrefs {
     }
 }
 
+/// Text wrapped around every prompt sent in one mode
+/// (`[mode_prompts.<mode>]` in settings.toml).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModePrompt {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+}
+
+impl ModePrompt {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.prefix.is_none() && self.suffix.is_none()
+    }
+
+    /// `text` with the prefix before it and the suffix after it, each
+    /// separated by a blank line.
+    #[must_use]
+    pub fn apply(&self, text: &str) -> String {
+        let mut parts = Vec::with_capacity(3);
+        parts.extend(self.prefix.as_deref());
+        parts.push(text);
+        parts.extend(self.suffix.as_deref());
+        parts.join("\n\n")
+    }
+}
+
 /// User settings with defaults
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Settings {
     /// Auto-compact conversations when they approach the model limit.
     pub auto_compact: bool,
+    /// Seconds without input or a running turn before idle maintenance
+    /// (session summary, docs index, repo map, early compaction) runs.
+    /// `0` disables it.
+    pub idle_maintenance_secs: u64,
     /// Reduce status noise and collapse details more aggressively
     pub calm_mode: bool,
     /// Streaming pacing mode. `true` pins the chunker to one-character-per-
@@ -189,6 +223,9 @@ pub struct Settings {
     /// Enable rapid-key paste-burst detection for terminals that do not emit
     /// bracketed-paste events. Independent from `bracketed_paste`.
     pub paste_burst_detection: bool,
+    /// Pastes longer than this many lines prompt to inline them as a fenced
+    /// code block or attach them as a file. `0` disables the prompt.
+    pub paste_prompt_lines: usize,
     /// Show thinking blocks from the model
     pub show_thinking: bool,
     /// Show detailed tool output
@@ -207,6 +244,9 @@ pub struct Settings {
     pub composer_density: String,
     /// Show a border around the composer input area
     pub composer_border: bool,
+    /// Flag vague drafts ("fix it") with a hint panel above the composer
+    /// before they are sent.
+    pub prompt_lint: bool,
     /// Composer editing mode: "normal" (default) or "vim" for modal editing.
     /// When set to "vim" the composer starts in Normal mode; press i/a/o to
     /// enter Insert mode and Esc to return to Normal.
@@ -217,7 +257,7 @@ pub struct Settings {
     pub default_mode: String,
     /// Sidebar width as percentage of terminal width
     pub sidebar_width_percent: u16,
-    /// Sidebar focus mode: auto, work, tasks, agents, context, hidden
+    /// Sidebar focus mode: auto, work, tasks, agents, context, files, hidden
     pub sidebar_focus: String,
     /// Enable the session-context panel (#504). Shows working set, tokens,
     /// cost, MCP/LSP status, cycle count, and memory info.
@@ -273,6 +313,10 @@ pub struct Settings {
     /// `binary_unavailable` response with an install hint, matching the
     /// pre-v0.8.32 behavior.
     pub prefer_external_pdftotext: bool,
+    /// Prompt prefix/suffix per mode (`agent`, `plan`, `yolo`), added to
+    /// each message at dispatch. The transcript shows what was typed.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub mode_prompts: BTreeMap<String, ModePrompt>,
 }
 
 impl Default for Settings {
@@ -288,11 +332,13 @@ impl Default for Settings {
             // available for users / agents that decide compaction is
             // worth the cache hit on their workload (#664).
             auto_compact: false,
+            idle_maintenance_secs: 30,
             calm_mode: false,
             low_motion: false,
             fancy_animations: true,
             bracketed_paste: true,
             paste_burst_detection: true,
+            paste_prompt_lines: 200,
             show_thinking: true,
             show_tool_details: true,
             locale: "auto".to_string(),
@@ -300,6 +346,7 @@ impl Default for Settings {
             background_color: None,
             composer_density: "comfortable".to_string(),
             composer_border: true,
+            prompt_lint: false,
             composer_vim_mode: "normal".to_string(),
             transcript_spacing: "comfortable".to_string(),
             default_mode: "agent".to_string(),
@@ -315,6 +362,7 @@ impl Default for Settings {
             status_indicator: "whale".to_string(),
             synchronized_output: "auto".to_string(),
             prefer_external_pdftotext: false,
+            mode_prompts: BTreeMap::new(),
         }
     }
 }
@@ -368,6 +416,7 @@ impl Settings {
                 .reasoning_effort
                 .as_deref()
                 .and_then(|value| normalize_reasoning_effort_setting(value).ok().flatten());
+            s.mode_prompts = normalize_mode_prompts(std::mem::take(&mut s.mode_prompts));
             s
         };
         settings.apply_env_overrides();
@@ -475,6 +524,13 @@ impl Settings {
             "auto_compact" | "compact" => {
                 self.auto_compact = parse_bool(value)?;
             }
+            "idle_maintenance_secs" | "idle_maintenance" => {
+                self.idle_maintenance_secs = value.parse().map_err(|_| {
+                    anyhow::anyhow!(
+                        "Failed to update setting: invalid idle maintenance delay '{value}'. Expected a number of seconds (0 disables)."
+                    )
+                })?;
+            }
             "calm_mode" | "calm" => {
                 self.calm_mode = parse_bool(value)?;
             }
@@ -490,6 +546,13 @@ impl Settings {
             "paste_burst_detection" | "paste_burst" => {
                 self.paste_burst_detection = parse_bool(value)?;
             }
+            "paste_prompt_lines" | "paste_prompt" => {
+                self.paste_prompt_lines = value.parse().map_err(|_| {
+                    anyhow::anyhow!(
+                        "Failed to update setting: invalid paste prompt threshold '{value}'. Expected a number of lines (0 disables)."
+                    )
+                })?;
+            }
             "show_thinking" | "thinking" => {
                 self.show_thinking = parse_bool(value)?;
             }
@@ -535,6 +598,9 @@ impl Settings {
             "composer_border" | "border" => {
                 self.composer_border = parse_bool(value)?;
             }
+            "prompt_lint" | "lint" => {
+                self.prompt_lint = parse_bool(value)?;
+            }
             "composer_vim_mode" | "vim_mode" | "vim" => {
                 let normalized = value.trim().to_ascii_lowercase();
                 if !["vim", "normal"].contains(&normalized.as_str()) {
@@ -605,10 +671,11 @@ impl Settings {
                     "tasks" => "tasks",
                     "agents" | "subagents" | "sub-agents" => "agents",
                     "context" | "session" => "context",
+                    "files" | "tree" | "explorer" => "files",
                     "hidden" | "hide" | "closed" | "off" | "none" => "hidden",
                     _ => {
                         anyhow::bail!(
-                            "Failed to update setting: invalid sidebar focus '{value}'. Expected: auto, work, tasks, agents, context, hidden."
+                            "Failed to update setting: invalid sidebar focus '{value}'. Expected: auto, work, tasks, agents, context, files, hidden."
                         )
                     }
                 };
@@ -659,6 +726,9 @@ impl Settings {
             "reasoning_effort" | "effort" => {
                 self.reasoning_effort = normalize_reasoning_effort_setting(value)?;
             }
+            _ if parse_mode_prompt_key(key).is_some() => {
+                set_mode_prompt(&mut self.mode_prompts, key, value)?;
+            }
             _ => {
                 anyhow::bail!("Failed to update setting: unknown setting '{key}'.");
             }
@@ -673,6 +743,10 @@ impl Settings {
         lines.push(tr(locale, MessageId::SettingsTitle).to_string());
         lines.push("─────────────────────────────".to_string());
         lines.push(format!("  auto_compact:       {}", self.auto_compact));
+        lines.push(format!(
+            "  idle_maintenance:   {}",
+            self.idle_maintenance_secs
+        ));
         lines.push(format!("  calm_mode:          {}", self.calm_mode));
         lines.push(format!("  low_motion:         {}", self.low_motion));
         lines.push(format!("  fancy_animations:   {}", self.fancy_animations));
@@ -681,6 +755,7 @@ impl Settings {
             "  paste_burst_detect: {}",
             self.paste_burst_detection
         ));
+        lines.push(format!("  paste_prompt_lines: {}", self.paste_prompt_lines));
         lines.push(format!("  show_thinking:      {}", self.show_thinking));
         lines.push(format!("  show_tool_details:  {}", self.show_tool_details));
         lines.push(format!("  locale:            {}", self.locale));
@@ -691,6 +766,7 @@ impl Settings {
         ));
         lines.push(format!("  composer_density:   {}", self.composer_density));
         lines.push(format!("  composer_border:    {}", self.composer_border));
+        lines.push(format!("  prompt_lint:        {}", self.prompt_lint));
         lines.push(format!("  composer_vim_mode:  {}", self.composer_vim_mode));
         lines.push(format!("  transcript_spacing: {}", self.transcript_spacing));
         lines.push(format!("  status_indicator:   {}", self.status_indicator));
@@ -721,6 +797,13 @@ impl Settings {
                 .as_deref()
                 .unwrap_or("(config/default)")
         ));
+        for (mode, prompt) in &self.mode_prompts {
+            for (part, text) in [("prefix", &prompt.prefix), ("suffix", &prompt.suffix)] {
+                if let Some(text) = text {
+                    lines.push(format!("  mode_prompts.{mode}.{part}: {text}"));
+                }
+            }
+        }
         lines.push(String::new());
         lines.push(format!(
             "{} {}",
@@ -738,6 +821,10 @@ impl Settings {
                 "auto_compact",
                 "Auto-compact near the hard context limit: on/off (default off)",
             ),
+            (
+                "idle_maintenance_secs",
+                "Run background maintenance after this many idle seconds (0 = off)",
+            ),
             ("calm_mode", "Calmer UI defaults: on/off"),
             (
                 "low_motion",
@@ -755,6 +842,10 @@ impl Settings {
                 "paste_burst_detection",
                 "Fallback rapid-key paste detection: on/off",
             ),
+            (
+                "paste_prompt_lines",
+                "Prompt to fence or attach pastes longer than this many lines (0 = off)",
+            ),
             ("show_thinking", "Show model thinking: on/off"),
             ("show_tool_details", "Show detailed tool output: on/off"),
             (
@@ -777,6 +868,10 @@ impl Settings {
                 "composer_border",
                 "Show a border around the composer input area: on/off",
             ),
+            (
+                "prompt_lint",
+                "Hint at missing files, acceptance criteria, or constraints in vague drafts: on/off",
+            ),
             ("composer_vim_mode", "Composer editing mode: normal, vim"),
             (
                 "transcript_spacing",
@@ -798,7 +893,7 @@ impl Settings {
             ("sidebar_width", "Sidebar width percentage: 10-50"),
             (
                 "sidebar_focus",
-                "Sidebar focus: auto, work, tasks, agents, context, hidden",
+                "Sidebar focus: auto, work, tasks, agents, context, files, hidden",
             ),
             (
                 "context_panel",
@@ -814,6 +909,30 @@ impl Settings {
                 "reasoning_effort",
                 "Default thinking effort: auto, off, low, medium, high, max, or default",
             ),
+            (
+                "mode_prompts.agent.prefix",
+                "Text added before every prompt sent in agent mode (none clears)",
+            ),
+            (
+                "mode_prompts.agent.suffix",
+                "Text added after every prompt sent in agent mode (none clears)",
+            ),
+            (
+                "mode_prompts.plan.prefix",
+                "Text added before every prompt sent in plan mode (none clears)",
+            ),
+            (
+                "mode_prompts.plan.suffix",
+                "Text added after every prompt sent in plan mode (none clears)",
+            ),
+            (
+                "mode_prompts.yolo.prefix",
+                "Text added before every prompt sent in yolo mode (none clears)",
+            ),
+            (
+                "mode_prompts.yolo.suffix",
+                "Text added after every prompt sent in yolo mode (none clears)",
+            ),
         ]
     }
 
@@ -882,6 +1001,75 @@ fn parse_bool(value: &str) -> Result<bool> {
     }
 }
 
+/// Split `mode_prompts.<mode>.<prefix|suffix>` into the normalized mode and
+/// whether it names the prefix.
+pub fn parse_mode_prompt_key(key: &str) -> Option<(&'static str, bool)> {
+    let rest = key
+        .strip_prefix("mode_prompts.")
+        .or_else(|| key.strip_prefix("mode_prompt."))?;
+    let (mode, part) = rest.rsplit_once('.')?;
+    let mode = match normalize_mode(mode) {
+        "agent" => "agent",
+        "plan" => "plan",
+        "yolo" => "yolo",
+        _ => return None,
+    };
+    match part {
+        "prefix" => Some((mode, true)),
+        "suffix" => Some((mode, false)),
+        _ => None,
+    }
+}
+
+/// Set or clear (`none`, `off`, or empty) the part of a mode prompt named
+/// by `key` (`mode_prompts.<mode>.<prefix|suffix>`).
+pub fn set_mode_prompt(
+    prompts: &mut BTreeMap<String, ModePrompt>,
+    key: &str,
+    value: &str,
+) -> Result<()> {
+    let Some((mode, is_prefix)) = parse_mode_prompt_key(key) else {
+        anyhow::bail!(
+            "Failed to update setting: invalid mode prompt key '{key}'. Expected mode_prompts.<agent|plan|yolo>.<prefix|suffix>."
+        );
+    };
+    let text = value.trim();
+    let text = (!text.is_empty()
+        && !matches!(
+            text.to_ascii_lowercase().as_str(),
+            "none" | "off" | "(none)"
+        ))
+    .then(|| text.to_string());
+    let prompt = prompts.entry(mode.to_string()).or_default();
+    if is_prefix {
+        prompt.prefix = text;
+    } else {
+        prompt.suffix = text;
+    }
+    if prompt.is_empty() {
+        prompts.remove(mode);
+    }
+    Ok(())
+}
+
+/// Key mode prompts by normalized mode name and drop unknown modes and
+/// blank entries.
+fn normalize_mode_prompts(prompts: BTreeMap<String, ModePrompt>) -> BTreeMap<String, ModePrompt> {
+    let blank = |text: Option<String>| text.filter(|text| !text.trim().is_empty());
+    prompts
+        .into_iter()
+        .filter_map(|(mode, prompt)| {
+            let mode = normalize_mode(&mode).to_string();
+            let prompt = ModePrompt {
+                prefix: blank(prompt.prefix),
+                suffix: blank(prompt.suffix),
+            };
+            (["agent", "plan", "yolo"].contains(&mode.as_str()) && !prompt.is_empty())
+                .then_some((mode, prompt))
+        })
+        .collect()
+}
+
 fn normalize_mode(value: &str) -> &str {
     match value.trim().to_ascii_lowercase().as_str() {
         "edit" => "agent",
@@ -1020,6 +1208,7 @@ fn normalize_sidebar_focus(value: &str) -> &str {
         "tasks" => "tasks",
         "agents" | "subagents" | "sub-agents" => "agents",
         "context" | "session" => "context",
+        "files" | "tree" | "explorer" => "files",
         "hidden" | "hide" | "closed" | "off" | "none" => "hidden",
         _ => "auto",
     }
@@ -2055,4 +2244,31 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn mode_prompts_set_clear_and_wrap_the_prompt() {
+        let mut settings = Settings::default();
+        settings
+            .set("mode_prompts.plan.suffix", "Don't write code yet.")
+            .expect("set suffix");
+        settings
+            .set("mode_prompts.normal.prefix", "Be brief.")
+            .expect("normal is agent");
+        assert!(settings.set("mode_prompts.chat.prefix", "x").is_err());
+        assert_eq!(
+            settings.mode_prompts["plan"].apply("Add caching"),
+            "Add caching\n\nDon't write code yet."
+        );
+        assert_eq!(
+            settings.mode_prompts["agent"].prefix.as_deref(),
+            Some("Be brief.")
+        );
+
+        let toml = toml::to_string_pretty(&settings).unwrap();
+        let loaded: Settings = toml::from_str(&toml).unwrap();
+        assert_eq!(loaded.mode_prompts, settings.mode_prompts);
+
+        settings.set("mode_prompts.agent.prefix", "none").unwrap();
+        assert!(!settings.mode_prompts.contains_key("agent"));
+    }
 }
@@ -0,0 +1,345 @@
+//! Auto-activation rules and skill dependencies.
+//!
+//! A `SKILL.md` can declare when it applies and what it builds on:
+//!
+//! ```text
+//! ---
+//! name: rust-tests
+//! description: How this repo writes Rust tests
+//! globs: **/*.rs, Cargo.toml
+//! keywords: test, flaky
+//! modes: agent, yolo
+//! requires: rust-style
+//! ---
+//! ```
+//!
+//! At turn start the TUI checks every skill with rules against the prompt,
+//! the files it mentions or touched this session, and the current mode. A
+//! skill matches when any glob or keyword matches; `modes` narrows that (or,
+//! alone, activates the skill in those modes). Matching skills and their
+//! `requires` chain are injected once per conversation, within a token
+//! budget. Skills without rules only run when invoked with `/skill <name>`.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use globset::{Glob, GlobMatcher};
+
+use super::{Skill, SkillRegistry};
+
+/// Default `[skills] auto_activate_tokens`: skill bodies injected per turn.
+pub const DEFAULT_AUTO_ACTIVATE_TOKENS: usize = 4_000;
+
+/// Activation conditions and dependencies parsed from frontmatter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkillRules {
+    /// Workspace-relative path globs. Patterns without `/` match file names.
+    pub globs: Vec<String>,
+    /// Case-insensitive words or phrases matched against the prompt.
+    pub keywords: Vec<String>,
+    /// App modes (`agent`, `plan`, `yolo`) the skill is limited to.
+    pub modes: Vec<String>,
+    /// Skills injected before this one whenever it activates.
+    pub requires: Vec<String>,
+}
+
+impl SkillRules {
+    /// Read the rule fields from parsed frontmatter. List values are
+    /// comma-separated, optionally wrapped in `[...]`.
+    pub(super) fn from_metadata(metadata: &std::collections::HashMap<String, String>) -> Self {
+        let list = |key: &str| -> Vec<String> {
+            metadata.get(key).map_or_else(Vec::new, |raw| {
+                raw.trim()
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .split(',')
+                    .map(|item| item.trim().trim_matches(['"', '\'']).trim().to_string())
+                    .filter(|item| !item.is_empty())
+                    .collect()
+            })
+        };
+        Self {
+            globs: list("globs"),
+            keywords: list("keywords"),
+            modes: list("modes")
+                .into_iter()
+                .map(|mode| mode.to_ascii_lowercase())
+                .collect(),
+            requires: list("requires"),
+        }
+    }
+
+    /// Whether the skill declares any way to activate on its own.
+    #[must_use]
+    pub fn is_automatic(&self) -> bool {
+        !(self.globs.is_empty() && self.keywords.is_empty() && self.modes.is_empty())
+    }
+}
+
+/// What the rules are evaluated against at turn start.
+#[derive(Debug, Clone, Copy)]
+pub struct ActivationContext<'a> {
+    pub prompt: &'a str,
+    /// Workspace-relative paths mentioned in the prompt or touched this
+    /// session.
+    pub files: &'a [String],
+    /// Current mode, lowercase (`agent`, `plan`, `yolo`).
+    pub mode: &'a str,
+}
+
+/// Outcome of [`select_auto_skills`].
+#[derive(Debug, Default)]
+pub struct AutoActivation<'a> {
+    /// Skills to inject this turn, dependencies before dependents.
+    pub skills: Vec<&'a Skill>,
+    /// Matching skills left out because they did not fit the budget.
+    pub over_budget: Vec<String>,
+    /// `requires` entries that name no installed (or an `/skill off`) skill.
+    pub missing: Vec<String>,
+}
+
+/// Rough prompt cost of injecting `skill`.
+#[must_use]
+pub fn skill_tokens(skill: &Skill) -> usize {
+    skill.body.chars().count().div_ceil(4)
+}
+
+/// Pick the skills whose rules match `ctx`, plus their dependencies.
+///
+/// `active` skills were already injected this conversation and cost nothing;
+/// `disabled` skills never auto-activate. Candidates are taken in registry
+/// order until `budget_tokens` is spent; one that does not fit is skipped
+/// whole (with its dependencies) so a later, smaller one can still run.
+#[must_use]
+pub fn select_auto_skills<'a>(
+    registry: &'a SkillRegistry,
+    ctx: &ActivationContext<'_>,
+    active: &HashSet<String>,
+    disabled: &HashSet<String>,
+    budget_tokens: usize,
+) -> AutoActivation<'a> {
+    let mut outcome = AutoActivation::default();
+    let mut chosen: HashSet<&str> = HashSet::new();
+    let mut remaining = budget_tokens;
+    for skill in registry.list() {
+        if active.contains(&skill.name)
+            || disabled.contains(&skill.name)
+            || chosen.contains(skill.name.as_str())
+            || !matches_rules(&skill.rules, ctx)
+        {
+            continue;
+        }
+        let mut chain = Vec::new();
+        collect_chain(
+            registry,
+            skill,
+            disabled,
+            &mut HashSet::new(),
+            &mut chain,
+            &mut outcome.missing,
+        );
+        chain.retain(|dep| !active.contains(&dep.name) && !chosen.contains(dep.name.as_str()));
+        let cost: usize = chain.iter().map(|dep| skill_tokens(dep)).sum();
+        if cost > remaining {
+            outcome.over_budget.push(skill.name.clone());
+            continue;
+        }
+        remaining -= cost;
+        for dep in chain {
+            chosen.insert(dep.name.as_str());
+            outcome.skills.push(dep);
+        }
+    }
+    outcome.missing.sort();
+    outcome.missing.dedup();
+    outcome
+}
+
+/// Depth-first `requires` walk: dependencies land before `skill`, cycles
+/// are cut at the first revisit.
+fn collect_chain<'a>(
+    registry: &'a SkillRegistry,
+    skill: &'a Skill,
+    disabled: &HashSet<String>,
+    visiting: &mut HashSet<String>,
+    chain: &mut Vec<&'a Skill>,
+    missing: &mut Vec<String>,
+) {
+    if !visiting.insert(skill.name.clone()) {
+        return;
+    }
+    for name in &skill.rules.requires {
+        match registry.get(name) {
+            Some(dep) if !disabled.contains(name) => {
+                collect_chain(registry, dep, disabled, visiting, chain, missing);
+            }
+            _ => missing.push(name.clone()),
+        }
+    }
+    if !chain.iter().any(|existing| existing.name == skill.name) {
+        chain.push(skill);
+    }
+}
+
+fn matches_rules(rules: &SkillRules, ctx: &ActivationContext<'_>) -> bool {
+    if !rules.is_automatic() {
+        return false;
+    }
+    let mode_ok = rules.modes.is_empty() || rules.modes.iter().any(|mode| mode == ctx.mode);
+    if !mode_ok {
+        return false;
+    }
+    if rules.globs.is_empty() && rules.keywords.is_empty() {
+        return true;
+    }
+    let prompt = ctx.prompt.to_lowercase();
+    rules
+        .keywords
+        .iter()
+        .any(|keyword| contains_word(&prompt, &keyword.to_lowercase()))
+        || rules.globs.iter().any(|glob| {
+            glob_matcher(glob).is_some_and(|matcher| {
+                ctx.files.iter().any(|file| {
+                    let path = Path::new(file);
+                    matcher.is_match(path)
+                        || (!glob.contains('/')
+                            && path.file_name().is_some_and(|name| matcher.is_match(name)))
+                })
+            })
+        })
+}
+
+fn glob_matcher(pattern: &str) -> Option<GlobMatcher> {
+    Glob::new(pattern).ok().map(|glob| glob.compile_matcher())
+}
+
+/// `needle` occurs in `haystack` bounded by non-alphanumeric characters.
+fn contains_word(haystack: &str, needle: &str) -> bool {
+    if needle.is_empty() {
+        return false;
+    }
+    haystack.match_indices(needle).any(|(start, _)| {
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[start + needle.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+/// Model-visible block that injects auto-activated skills.
+#[must_use]
+pub fn render_auto_skills_block(skills: &[&Skill]) -> String {
+    let mut block = String::from(
+        "These skills were activated automatically because they match this request. \
+         Follow their instructions where they apply:",
+    );
+    for skill in skills {
+        block.push_str(&format!(
+            "\n\n# Skill: {}\n\n{}",
+            skill.name,
+            skill.body.trim()
+        ));
+    }
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn skill(name: &str, frontmatter: &str, body: &str) -> Skill {
+        let content = format!("---\nname: {name}\n{frontmatter}\n---\n{body}");
+        let mut skill = SkillRegistry::parse_skill(Path::new("SKILL.md"), &content).unwrap();
+        skill.path = PathBuf::from(format!("{name}/SKILL.md"));
+        skill
+    }
+
+    fn registry(skills: Vec<Skill>) -> SkillRegistry {
+        SkillRegistry {
+            skills,
+            warnings: Vec::new(),
+        }
+    }
+
+    fn names(outcome: &AutoActivation<'_>) -> Vec<String> {
+        outcome.skills.iter().map(|s| s.name.clone()).collect()
+    }
+
+    #[test]
+    fn frontmatter_rules_parse_lists() {
+        let parsed = skill(
+            "rust",
+            "globs: [\"**/*.rs\", Cargo.toml]\nkeywords: unit test, flaky\nmodes: Agent\nrequires: style",
+            "body",
+        );
+        assert_eq!(parsed.rules.globs, ["**/*.rs", "Cargo.toml"]);
+        assert_eq!(parsed.rules.keywords, ["unit test", "flaky"]);
+        assert_eq!(parsed.rules.modes, ["agent"]);
+        assert_eq!(parsed.rules.requires, ["style"]);
+        assert!(
+            !skill("manual", "description: d", "body")
+                .rules
+                .is_automatic()
+        );
+    }
+
+    #[test]
+    fn rules_match_globs_keywords_and_modes_with_dependencies_first() {
+        let registry = registry(vec![
+            skill("docs", "keywords: changelog", "docs body"),
+            skill("manual", "description: only by name", "manual body"),
+            skill("plan-only", "modes: plan", "plan body"),
+            skill("rust", "globs: *.rs\nrequires: style", "rust body"),
+            skill("style", "description: shared", "style body"),
+        ]);
+        let files = vec!["crates/tui/src/main.rs".to_string()];
+        let ctx = ActivationContext {
+            prompt: "Fix the flaky test",
+            files: &files,
+            mode: "agent",
+        };
+        let none = HashSet::new();
+        let outcome = select_auto_skills(&registry, &ctx, &none, &none, 10_000);
+        assert_eq!(names(&outcome), ["style", "rust"]);
+
+        let ctx = ActivationContext {
+            prompt: "Update the CHANGELOG please",
+            files: &[],
+            mode: "plan",
+        };
+        let outcome = select_auto_skills(&registry, &ctx, &none, &none, 10_000);
+        assert_eq!(names(&outcome), ["docs", "plan-only"]);
+        // "changelogs" is not the keyword "changelog".
+        let ctx = ActivationContext {
+            prompt: "changelogs",
+            ..ctx
+        };
+        let outcome = select_auto_skills(&registry, &ctx, &none, &none, 10_000);
+        assert_eq!(names(&outcome), ["plan-only"]);
+    }
+
+    #[test]
+    fn active_disabled_budget_and_missing_dependencies() {
+        let registry = registry(vec![
+            skill("big", "keywords: go", &"x".repeat(4_000)),
+            skill("needs-ghost", "keywords: go\nrequires: ghost", "small"),
+            skill("rust", "keywords: go\nrequires: style", "rust body"),
+            skill("style", "description: shared", "style body"),
+        ]);
+        let ctx = ActivationContext {
+            prompt: "go",
+            files: &[],
+            mode: "agent",
+        };
+        let active: HashSet<String> = HashSet::from(["style".to_string()]);
+        let disabled: HashSet<String> = HashSet::new();
+        let outcome = select_auto_skills(&registry, &ctx, &active, &disabled, 100);
+        assert_eq!(names(&outcome), ["needs-ghost", "rust"]);
+        assert_eq!(outcome.over_budget, ["big"]);
+        assert_eq!(outcome.missing, ["ghost"]);
+
+        let disabled: HashSet<String> = HashSet::from(["rust".to_string(), "big".to_string()]);
+        let outcome = select_auto_skills(&registry, &ctx, &HashSet::new(), &disabled, 100);
+        assert_eq!(names(&outcome), ["needs-ghost"]);
+    }
+}
@@ -1,11 +1,13 @@
 //! Skill discovery and registry for local SKILL.md files.
 
+pub mod activation;
 pub mod install;
 mod system;
 // Re-exports kept for documentation parity and downstream consumers; the
 // binary itself imports directly from `skills::install`. `#[allow(...)]`
 // silences the dead-code warning that fires because no `bin` source path
 // references these names through `skills::*`.
+pub use activation::SkillRules;
 #[allow(unused_imports)]
 pub use install::{
     DEFAULT_MAX_SIZE_BYTES, DEFAULT_REGISTRY_URL, INSTALLED_FROM_MARKER, InstallOutcome,
@@ -21,6 +23,7 @@ use anyhow::{Context, Result};
 use std::collections::{HashMap, HashSet};
 
 use crate::logging;
+use deepseek_config::org::OrgLayer;
 
 const MAX_SKILL_DESCRIPTION_CHARS: usize = 280;
 const MAX_AVAILABLE_SKILLS_CHARS: usize = 12_000;
@@ -66,6 +69,8 @@ pub struct Skill {
     /// or manually-placed skills, so callers must use this rather than
     /// reconstructing `<dir>/<name>/SKILL.md`.
     pub path: PathBuf,
+    /// Auto-activation conditions and `requires` dependencies.
+    pub rules: SkillRules,
 }
 
 /// Collection of discovered skills.
@@ -284,6 +289,7 @@ impl SkillRegistry {
                 // Filled in by `discover` after parse succeeds; default to an
                 // empty path so direct constructors (e.g. tests) compile.
                 path: PathBuf::new(),
+                rules: SkillRules::from_metadata(&metadata),
             });
         }
 
@@ -304,6 +310,7 @@ impl SkillRegistry {
             description: String::new(),
             body: content.trim().to_string(),
             path: PathBuf::new(),
+            rules: SkillRules::default(),
         })
     }
 
@@ -385,6 +392,8 @@ pub fn resolve_skills_dir(workspace: &Path) -> PathBuf {
 /// 6. [`agents_global_skills_dir`] — agentskills.io global.
 /// 7. [`claude_global_skills_dir`] — Claude-ecosystem global (#902).
 /// 8. [`default_skills_dir`] — DeepSeek global, user-installed.
+/// 9. `~/.deepseek/org/skills` — the shared team layer synced with
+///    `deepseek config sync`, so any local skill can shadow it.
 ///
 /// Only directories that exist on disk are returned — callers don't
 /// need to filter further. Returns an empty vec when nothing is
@@ -407,6 +416,7 @@ fn skills_directories_with_home(workspace: &Path, home_dir: Option<&Path>) -> Ve
         candidates.push(home.join(".agents").join("skills"));
         candidates.push(home.join(".claude").join("skills"));
         candidates.push(home.join(".deepseek").join("skills"));
+        candidates.push(OrgLayer::at(home.join(".deepseek")).skills_dir());
     } else {
         candidates.push(PathBuf::from("/tmp/deepseek/skills"));
     }
@@ -802,6 +812,7 @@ mod tests {
                 .join("skills")
                 .join("workspace-priority")
                 .join("SKILL.md"),
+            rules: super::SkillRules::default(),
         });
 
         let big_desc = "y".repeat(super::MAX_SKILL_DESCRIPTION_CHARS - 20);
@@ -816,6 +827,7 @@ mod tests {
                     .join("skills")
                     .join(format!("aaa-global-{i:03}"))
                     .join("SKILL.md"),
+                rules: super::SkillRules::default(),
             });
         }
 
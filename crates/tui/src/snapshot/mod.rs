@@ -7,6 +7,10 @@
 //! can roll back via `/restore N` (slash command) or, when the model
 //! recognises an "undo my last edit" intent, the `revert_turn` tool.
 //!
+//! Users can also save named snapshots (`/snapshot save <name>`) under
+//! `refs/named/` in the same side repo. They sit outside the turn chain, so
+//! retention pruning leaves them alone until `/snapshot delete <name>`.
+//!
 //! ## Why a side repo?
 //!
 //! - The user's own `.git` is never touched. `--git-dir` and
@@ -46,6 +50,6 @@ pub use prune::{DEFAULT_MAX_AGE, prune_older_than};
 pub const DEFAULT_MAX_SNAPSHOTS: usize = 50;
 #[allow(unused_imports)]
 pub use repo::{
-    DEFAULT_MAX_WORKSPACE_BYTES_FOR_SNAPSHOT, Snapshot, SnapshotId, SnapshotRepo,
-    estimate_workspace_size_bounded,
+    DEFAULT_MAX_WORKSPACE_BYTES_FOR_SNAPSHOT, NamedSnapshot, Snapshot, SnapshotId, SnapshotRepo,
+    estimate_workspace_size_bounded, validate_snapshot_name,
 };
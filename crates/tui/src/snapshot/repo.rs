@@ -42,6 +42,45 @@ pub struct Snapshot {
     pub timestamp: i64,
 }
 
+/// A snapshot saved under a user-chosen name (`/snapshot save <name>`).
+#[derive(Debug, Clone)]
+pub struct NamedSnapshot {
+    pub name: String,
+    /// Commit SHA inside the side repo.
+    pub id: SnapshotId,
+    /// Save timestamp (Unix seconds).
+    pub timestamp: i64,
+}
+
+/// Ref namespace for named snapshots.
+const NAMED_REF_PREFIX: &str = "refs/named/";
+
+/// Longest accepted snapshot name.
+const MAX_SNAPSHOT_NAME_LEN: usize = 64;
+
+/// Check that `name` is usable as a named snapshot: 1–64 ASCII letters,
+/// digits, `-`, `_` or `.`, not starting with `-` or `.`.
+pub fn validate_snapshot_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > MAX_SNAPSHOT_NAME_LEN {
+        return Err(format!(
+            "snapshot name must be 1-{MAX_SNAPSHOT_NAME_LEN} characters"
+        ));
+    }
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid_chars
+        || name.starts_with(['-', '.'])
+        || name.contains("..")
+        || name.ends_with(".lock")
+    {
+        return Err(format!(
+            "invalid snapshot name '{name}': use letters, digits, '-', '_' or '.'"
+        ));
+    }
+    Ok(())
+}
+
 /// Wrapper around the per-workspace side-git repo.
 pub struct SnapshotRepo {
     git_dir: PathBuf,
@@ -344,25 +383,7 @@ impl SnapshotRepo {
                 let _ = self.prune_unreachable_objects();
             }
         }
-        // Stage every tracked + untracked path the workspace exposes.
-        // `--all` here means `add` + `update` + `remove` — the same set
-        // `git status` would show.
-        let add = run_git(&self.git_dir, &self.work_tree, &["add", "-A"])?;
-        if !add.status.success() {
-            return Err(io_other(format!(
-                "git add -A failed: {}",
-                String::from_utf8_lossy(&add.stderr).trim()
-            )));
-        }
-
-        let tree = run_git(&self.git_dir, &self.work_tree, &["write-tree"])?;
-        if !tree.status.success() {
-            return Err(io_other(format!(
-                "git write-tree failed: {}",
-                String::from_utf8_lossy(&tree.stderr).trim()
-            )));
-        }
-        let tree = String::from_utf8_lossy(&tree.stdout).trim().to_string();
+        let tree = self.write_work_tree()?;
 
         let parent = run_git(
             &self.git_dir,
@@ -410,6 +431,140 @@ impl SnapshotRepo {
         Ok(SnapshotId(sha))
     }
 
+    /// Stage the working tree into the side index and return its tree hash.
+    fn write_work_tree(&self) -> io::Result<String> {
+        // Stage every tracked + untracked path the workspace exposes.
+        // `--all` here means `add` + `update` + `remove` — the same set
+        // `git status` would show.
+        let add = run_git(&self.git_dir, &self.work_tree, &["add", "-A"])?;
+        if !add.status.success() {
+            return Err(io_other(format!(
+                "git add -A failed: {}",
+                String::from_utf8_lossy(&add.stderr).trim()
+            )));
+        }
+
+        let tree = run_git(&self.git_dir, &self.work_tree, &["write-tree"])?;
+        if !tree.status.success() {
+            return Err(io_other(format!(
+                "git write-tree failed: {}",
+                String::from_utf8_lossy(&tree.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&tree.stdout).trim().to_string())
+    }
+
+    /// Save the current working tree as the named snapshot `name`,
+    /// replacing any earlier snapshot with that name.
+    ///
+    /// Named snapshots are parentless commits under `refs/named/`, outside
+    /// the `HEAD` chain, so the turn-snapshot pruning never drops them.
+    pub fn save_named(&self, name: &str) -> io::Result<SnapshotId> {
+        validate_snapshot_name(name).map_err(io_other)?;
+        let tree = self.write_work_tree()?;
+        let label = format!("named:{name}");
+        let commit = run_git(
+            &self.git_dir,
+            &self.work_tree,
+            &["commit-tree", &tree, "-m", &label],
+        )?;
+        if !commit.status.success() {
+            return Err(io_other(format!(
+                "git commit-tree failed: {}",
+                String::from_utf8_lossy(&commit.stderr).trim()
+            )));
+        }
+        let sha = String::from_utf8_lossy(&commit.stdout).trim().to_string();
+
+        let ref_name = format!("{NAMED_REF_PREFIX}{name}");
+        let update = run_git(
+            &self.git_dir,
+            &self.work_tree,
+            &["update-ref", &ref_name, &sha],
+        )?;
+        if !update.status.success() {
+            return Err(io_other(format!(
+                "git update-ref {ref_name} failed: {}",
+                String::from_utf8_lossy(&update.stderr).trim()
+            )));
+        }
+        Ok(SnapshotId(sha))
+    }
+
+    /// Look up the named snapshot `name`.
+    pub fn named(&self, name: &str) -> io::Result<Option<NamedSnapshot>> {
+        Ok(self
+            .list_named()?
+            .into_iter()
+            .find(|snapshot| snapshot.name == name))
+    }
+
+    /// List named snapshots, most recently saved first.
+    pub fn list_named(&self) -> io::Result<Vec<NamedSnapshot>> {
+        let refs = run_git(
+            &self.git_dir,
+            &self.work_tree,
+            &[
+                "for-each-ref",
+                "--sort=-committerdate",
+                "--format=%(objectname)%09%(committerdate:unix)%09%(refname)",
+                NAMED_REF_PREFIX,
+            ],
+        )?;
+        if !refs.status.success() {
+            return Err(io_other(format!(
+                "git for-each-ref failed: {}",
+                String::from_utf8_lossy(&refs.stderr).trim()
+            )));
+        }
+        let stdout = String::from_utf8_lossy(&refs.stdout);
+        let mut out = Vec::new();
+        for line in stdout.lines() {
+            let mut parts = line.splitn(3, '\t');
+            let sha = parts.next().unwrap_or("").to_string();
+            let ts = parts
+                .next()
+                .and_then(|s| s.parse::<i64>().ok())
+                .unwrap_or(0);
+            let Some(name) = parts
+                .next()
+                .and_then(|refname| refname.strip_prefix(NAMED_REF_PREFIX))
+            else {
+                continue;
+            };
+            if sha.is_empty() {
+                continue;
+            }
+            out.push(NamedSnapshot {
+                name: name.to_string(),
+                id: SnapshotId(sha),
+                timestamp: ts,
+            });
+        }
+        Ok(out)
+    }
+
+    /// Delete the named snapshot `name`. Returns `false` when it did not
+    /// exist.
+    pub fn delete_named(&self, name: &str) -> io::Result<bool> {
+        if self.named(name)?.is_none() {
+            return Ok(false);
+        }
+        let ref_name = format!("{NAMED_REF_PREFIX}{name}");
+        let delete = run_git(
+            &self.git_dir,
+            &self.work_tree,
+            &["update-ref", "-d", &ref_name],
+        )?;
+        if !delete.status.success() {
+            return Err(io_other(format!(
+                "git update-ref -d {ref_name} failed: {}",
+                String::from_utf8_lossy(&delete.stderr).trim()
+            )));
+        }
+        Ok(true)
+    }
+
     /// Restore the workspace to the state at `id`.
     ///
     /// Uses `git checkout <sha> -- :/` which checks out every path in the
@@ -1026,6 +1181,37 @@ mod tests {
         assert_eq!(after, "original");
     }
 
+    #[test]
+    fn named_snapshots_survive_turn_pruning() {
+        let tmp = tempdir().unwrap();
+        let (repo, _home) = make_repo(tmp.path());
+        let f = repo.work_tree().join("file.txt");
+
+        std::fs::write(&f, b"before-risky").unwrap();
+        let saved = repo.save_named("before-risky").expect("save named");
+        assert!(
+            repo.list(10).unwrap().is_empty(),
+            "named is off the HEAD chain"
+        );
+
+        std::fs::write(&f, b"after").unwrap();
+        repo.snapshot("pre-turn:1").unwrap();
+        repo.snapshot("post-turn:1").unwrap();
+        repo.prune_keep_last_n(1).unwrap();
+        repo.prune_unreachable_objects().unwrap();
+
+        let named = repo.named("before-risky").unwrap().expect("still there");
+        assert_eq!(named.id, saved);
+        assert_eq!(repo.list_named().unwrap().len(), 1);
+        repo.restore(&named.id).expect("restore named");
+        assert_eq!(std::fs::read_to_string(&f).unwrap(), "before-risky");
+
+        assert!(repo.delete_named("before-risky").unwrap());
+        assert!(!repo.delete_named("before-risky").unwrap());
+        assert!(repo.named("before-risky").unwrap().is_none());
+        assert!(repo.save_named("../escape").is_err());
+    }
+
     #[test]
     fn restore_removes_files_added_after_target_snapshot() {
         let tmp = tempdir().unwrap();
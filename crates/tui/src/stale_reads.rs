@@ -0,0 +1,458 @@
+//! Staleness tracking for files the model has read.
+//!
+//! Every successful `read_file` of a workspace file that is not gitignored
+//! stamps the file (length, mtime, content hash) and starts watching its
+//! directory. When the file changes on disk afterwards (an editor save, a
+//! `git pull`) and its content no longer matches the stamp, it turns stale:
+//! the UI badges the old read cells, and the model is told at the start of
+//! its next turn so it re-reads instead of editing from outdated content.
+//!
+//! Like `deepseek watch`, changes that land while a turn is running are not
+//! reported: they are almost always the agent's own edits. Every tracked
+//! file is re-stamped when the turn ends. A stale file stops being tracked
+//! once the model has been told; reading it again starts over.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
+
+use ignore::Match;
+use ignore::gitignore::Gitignore;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Stale files named in the model note before the rest are only counted.
+const MAX_LISTED_FILES: usize = 10;
+
+/// What a file looked like when the model last saw it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileStamp {
+    len: u64,
+    modified: Option<SystemTime>,
+    hash: u64,
+}
+
+impl FileStamp {
+    fn capture(path: &Path) -> Option<Self> {
+        let meta = fs::metadata(path).ok()?;
+        if !meta.is_file() {
+            return None;
+        }
+        Some(Self {
+            len: meta.len(),
+            modified: meta.modified().ok(),
+            hash: content_hash(path)?,
+        })
+    }
+
+    /// Whether `path` still holds the stamped content. A touched file whose
+    /// bytes are unchanged (e.g. a checkout of the same revision) still
+    /// matches.
+    fn matches(&self, path: &Path) -> bool {
+        let Ok(meta) = fs::metadata(path) else {
+            return false;
+        };
+        if meta.len() != self.len {
+            return false;
+        }
+        if meta.modified().ok() == self.modified {
+            return true;
+        }
+        content_hash(path) == Some(self.hash)
+    }
+}
+
+fn content_hash(path: &Path) -> Option<u64> {
+    let bytes = fs::read(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+#[derive(Debug)]
+struct TrackedFile {
+    stamp: FileStamp,
+    stale: bool,
+}
+
+/// Files the model has read, keyed by canonical path.
+#[derive(Debug, Default)]
+pub struct ReadTracker {
+    files: HashMap<PathBuf, TrackedFile>,
+    /// Set while a turn runs; change events are dropped meanwhile.
+    paused: bool,
+}
+
+impl ReadTracker {
+    /// Stamp `path` as the model now knows it. Returns false when the file
+    /// cannot be read.
+    pub fn record(&mut self, path: &Path) -> bool {
+        match FileStamp::capture(path) {
+            Some(stamp) => {
+                self.files.insert(
+                    path.to_path_buf(),
+                    TrackedFile {
+                        stamp,
+                        stale: false,
+                    },
+                );
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Accept the current content of every tracked file, dropping files
+    /// that are gone.
+    pub fn restamp_all(&mut self) {
+        self.files
+            .retain(|path, file| match FileStamp::capture(path) {
+                Some(stamp) => {
+                    file.stamp = stamp;
+                    file.stale = false;
+                    true
+                }
+                None => false,
+            });
+    }
+
+    /// Re-check one file after a change event. Returns true when it just
+    /// turned stale.
+    pub fn check(&mut self, path: &Path) -> bool {
+        if self.paused {
+            return false;
+        }
+        match self.files.get_mut(path) {
+            Some(file) if !file.stale && !file.stamp.matches(path) => {
+                file.stale = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Re-check every tracked file, returning the ones that just turned
+    /// stale. Catches changes the watcher missed.
+    pub fn check_all(&mut self) -> Vec<PathBuf> {
+        let paths: Vec<PathBuf> = self.files.keys().cloned().collect();
+        let mut changed: Vec<PathBuf> = paths.into_iter().filter(|path| self.check(path)).collect();
+        changed.sort();
+        changed
+    }
+
+    /// Remove and return the stale files, sorted.
+    pub fn take_stale(&mut self) -> Vec<PathBuf> {
+        let mut stale: Vec<PathBuf> = self
+            .files
+            .iter()
+            .filter(|(_, file)| file.stale)
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in &stale {
+            self.files.remove(path);
+        }
+        stale.sort();
+        stale
+    }
+
+    pub fn clear(&mut self) {
+        self.files.clear();
+    }
+}
+
+/// Resolve a tool's `path` argument to the canonical path the watcher
+/// reports. A file that no longer exists resolves through its directory.
+#[must_use]
+pub fn resolve_path(workspace: &Path, raw: &str) -> Option<PathBuf> {
+    let joined = workspace.join(raw);
+    if let Ok(path) = joined.canonicalize() {
+        return Some(path);
+    }
+    let parent = joined.parent()?.canonicalize().ok()?;
+    Some(parent.join(joined.file_name()?))
+}
+
+/// Whether `path` falls outside `root` or is ignored by git: anything under
+/// `.git/`, or matched by a `.gitignore` in `root` or any directory between
+/// `root` and the file (deeper files take precedence).
+#[must_use]
+pub fn is_ignored(root: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(root) else {
+        return true;
+    };
+    if relative.starts_with(".git") {
+        return true;
+    }
+    let mut ignored = false;
+    let mut dir = root.to_path_buf();
+    let mut components = relative.components().peekable();
+    while let Some(component) = components.next() {
+        let (gitignore, _) = Gitignore::new(dir.join(".gitignore"));
+        match gitignore.matched_path_or_any_parents(path, false) {
+            Match::Ignore(_) => ignored = true,
+            Match::Whitelist(_) => ignored = false,
+            Match::None => {}
+        }
+        if components.peek().is_none() {
+            break;
+        }
+        dir.push(component);
+    }
+    ignored
+}
+
+/// The line added to the model's next turn about files that changed since
+/// it read them, relative to `workspace` where possible.
+#[must_use]
+pub fn stale_note(paths: &[PathBuf], workspace: &Path) -> Option<String> {
+    if paths.is_empty() {
+        return None;
+    }
+    let root = workspace
+        .canonicalize()
+        .unwrap_or_else(|_| workspace.into());
+    let mut names: Vec<String> = paths
+        .iter()
+        .take(MAX_LISTED_FILES)
+        .map(|path| {
+            path.strip_prefix(&root)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        })
+        .collect();
+    if paths.len() > MAX_LISTED_FILES {
+        names.push(format!("and {} more", paths.len() - MAX_LISTED_FILES));
+    }
+    let (files, pronoun) = if paths.len() == 1 {
+        ("file", "it")
+    } else {
+        ("files", "them")
+    };
+    Some(format!(
+        "{} previously read {files} changed on disk since you read {pronoun}: {}. \
+         Re-read before editing.",
+        paths.len(),
+        names.join(", ")
+    ))
+}
+
+/// Reports newly stale files from the watcher thread.
+pub type StaleCallback = Arc<dyn Fn(Vec<PathBuf>) + Send + Sync>;
+
+/// A [`ReadTracker`] plus the watcher that keeps it current. The watcher
+/// starts on the first recorded read and only watches the directories of
+/// tracked files, non-recursively.
+pub struct StaleReads {
+    tracker: Arc<Mutex<ReadTracker>>,
+    on_stale: StaleCallback,
+    watcher: Option<RecommendedWatcher>,
+    watched_dirs: HashSet<PathBuf>,
+}
+
+impl StaleReads {
+    #[must_use]
+    pub fn new(on_stale: StaleCallback) -> Self {
+        Self {
+            tracker: Arc::default(),
+            on_stale,
+            watcher: None,
+            watched_dirs: HashSet::new(),
+        }
+    }
+
+    fn tracker(&self) -> std::sync::MutexGuard<'_, ReadTracker> {
+        self.tracker.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Track a file the model just read through `raw` (a tool `path`
+    /// argument). Files outside the workspace or ignored by git are skipped.
+    pub fn record_read(&mut self, workspace: &Path, raw: &str) {
+        let Some(path) = resolve_path(workspace, raw) else {
+            return;
+        };
+        let Ok(root) = workspace.canonicalize() else {
+            return;
+        };
+        if is_ignored(&root, &path) || !self.tracker().record(&path) {
+            return;
+        }
+        if let Some(dir) = path.parent() {
+            self.watch_dir(dir);
+        }
+    }
+
+    /// Start of a turn: stop reporting changes, and return the stale files
+    /// to tell the model about, removed from tracking.
+    pub fn begin_turn(&mut self) -> Vec<PathBuf> {
+        let mut tracker = self.tracker();
+        tracker.check_all();
+        tracker.paused = true;
+        tracker.take_stale()
+    }
+
+    /// End of a turn: accept whatever changed during it and resume
+    /// reporting.
+    pub fn end_turn(&mut self) {
+        let mut tracker = self.tracker();
+        tracker.restamp_all();
+        tracker.paused = false;
+    }
+
+    pub fn clear(&mut self) {
+        self.tracker().clear();
+    }
+
+    fn watch_dir(&mut self, dir: &Path) {
+        if self.watched_dirs.contains(dir) {
+            return;
+        }
+        if self.watcher.is_none() {
+            let tracker = Arc::clone(&self.tracker);
+            let on_stale = Arc::clone(&self.on_stale);
+            let watcher =
+                notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                    let Ok(event) = event else {
+                        return;
+                    };
+                    if matches!(event.kind, EventKind::Access(_)) {
+                        return;
+                    }
+                    let stale: Vec<PathBuf> = {
+                        let mut tracker = tracker.lock().unwrap_or_else(PoisonError::into_inner);
+                        event
+                            .paths
+                            .into_iter()
+                            .filter(|path| tracker.check(path))
+                            .collect()
+                    };
+                    if !stale.is_empty() {
+                        on_stale(stale);
+                    }
+                });
+            match watcher {
+                Ok(watcher) => self.watcher = Some(watcher),
+                Err(err) => {
+                    tracing::debug!(target: "stale_reads", "file watcher unavailable: {err}");
+                    return;
+                }
+            }
+        }
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+        match watcher.watch(dir, RecursiveMode::NonRecursive) {
+            Ok(()) => {
+                self.watched_dirs.insert(dir.to_path_buf());
+            }
+            Err(err) => {
+                tracing::debug!(target: "stale_reads", "cannot watch {}: {err}", dir.display());
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for StaleReads {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StaleReads")
+            .field("tracker", &self.tracker)
+            .field("watched_dirs", &self.watched_dirs)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn tracker_reports_changed_content_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("lib.rs");
+        fs::write(&path, "fn a() {}").unwrap();
+        let mut tracker = ReadTracker::default();
+        assert!(tracker.record(&path));
+        assert!(!tracker.record(&tmp.path().join("missing.rs")));
+
+        // Rewriting identical bytes is not a change.
+        fs::write(&path, "fn a() {}").unwrap();
+        assert!(tracker.check_all().is_empty());
+
+        fs::write(&path, "fn b() {}").unwrap();
+        assert_eq!(tracker.check_all(), vec![path.clone()]);
+        assert!(!tracker.check(&path), "already stale");
+        assert_eq!(tracker.take_stale(), vec![path.clone()]);
+        assert!(tracker.take_stale().is_empty());
+    }
+
+    #[test]
+    fn ignored_paths_follow_nested_gitignores() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::write(root.join(".gitignore"), "target/\n*.log\n").unwrap();
+        fs::create_dir_all(root.join("logs")).unwrap();
+        fs::write(root.join("logs/.gitignore"), "!keep.log\n").unwrap();
+
+        assert!(is_ignored(root, &root.join("target/debug/out.rs")));
+        assert!(is_ignored(root, &root.join("build.log")));
+        assert!(is_ignored(root, &root.join(".git/HEAD")));
+        assert!(is_ignored(root, Path::new("/elsewhere/file.rs")));
+        assert!(!is_ignored(root, &root.join("src/lib.rs")));
+        assert!(!is_ignored(root, &root.join("logs/keep.log")));
+    }
+
+    #[test]
+    fn stale_note_lists_workspace_relative_paths() {
+        let root = Path::new("/repo");
+        assert_eq!(stale_note(&[], root), None);
+        assert_eq!(
+            stale_note(&[root.join("src/lib.rs")], root).unwrap(),
+            "1 previously read file changed on disk since you read it: src/lib.rs. \
+             Re-read before editing."
+        );
+        let many: Vec<PathBuf> = (0..12).map(|i| root.join(format!("f{i}.rs"))).collect();
+        let note = stale_note(&many, root).unwrap();
+        assert!(note.starts_with("12 previously read files changed on disk since you read them"));
+        assert!(note.contains("f9.rs, and 2 more."));
+    }
+
+    #[test]
+    fn watcher_reports_changes_between_turns_only() {
+        let tmp = tempfile::tempdir().unwrap();
+        let workspace = tmp.path();
+        fs::write(workspace.join(".gitignore"), "ignored.txt\n").unwrap();
+        fs::write(workspace.join("notes.md"), "one").unwrap();
+        fs::write(workspace.join("ignored.txt"), "one").unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        let mut reads = StaleReads::new(Arc::new(move |paths| {
+            let _ = tx.lock().unwrap().send(paths);
+        }));
+
+        // Reads and edits inside a turn are accepted when it ends.
+        assert!(reads.begin_turn().is_empty());
+        reads.record_read(workspace, "notes.md");
+        reads.record_read(workspace, "ignored.txt");
+        fs::write(workspace.join("notes.md"), "two").unwrap();
+        reads.end_turn();
+        assert!(reads.begin_turn().is_empty());
+        reads.end_turn();
+        while rx.try_recv().is_ok() {}
+
+        fs::write(workspace.join("ignored.txt"), "changed").unwrap();
+        fs::write(workspace.join("notes.md"), "three!").unwrap();
+        let notes = resolve_path(workspace, "notes.md").unwrap();
+        // Watcher delivery is best-effort on some CI filesystems; the
+        // turn-start sweep below still catches the change.
+        if let Ok(paths) = rx.recv_timeout(Duration::from_secs(5)) {
+            assert_eq!(paths, vec![notes.clone()]);
+        }
+        assert_eq!(reads.begin_turn(), vec![notes]);
+    }
+}
@@ -34,6 +34,9 @@ const MAX_WORKERS: usize = 8;
 const TIMELINE_SUMMARY_LIMIT: usize = 240;
 const ARTIFACT_THRESHOLD: usize = 1200;
 const CURRENT_TASK_SCHEMA_VERSION: u32 = 2;
+/// Queue priority bounds. Higher runs first; ties keep FIFO order.
+pub const MIN_TASK_PRIORITY: i32 = -9;
+pub const MAX_TASK_PRIORITY: i32 = 9;
 
 const fn default_task_schema_version() -> u32 {
     CURRENT_TASK_SCHEMA_VERSION
@@ -51,6 +54,17 @@ pub enum TaskStatus {
 }
 
 impl TaskStatus {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Canceled => "canceled",
+        }
+    }
+
     #[cfg(test)]
     #[must_use]
     pub fn is_terminal(self) -> bool {
@@ -191,6 +205,9 @@ pub struct TaskRecord {
     pub trust_mode: bool,
     #[serde(default = "default_auto_approve")]
     pub auto_approve: bool,
+    /// Queue priority (`MIN_TASK_PRIORITY..=MAX_TASK_PRIORITY`).
+    #[serde(default)]
+    pub priority: i32,
     pub status: TaskStatus,
     pub created_at: DateTime<Utc>,
     pub started_at: Option<DateTime<Utc>>,
@@ -230,6 +247,13 @@ pub struct TaskSummary {
     pub prompt_summary: String,
     pub model: String,
     pub mode: String,
+    #[serde(default)]
+    pub priority: i32,
+    /// Checklist completion, the closest thing a task has to progress.
+    #[serde(default)]
+    pub completion_pct: u8,
+    #[serde(default)]
+    pub tool_call_count: usize,
     pub created_at: DateTime<Utc>,
     pub started_at: Option<DateTime<Utc>>,
     pub ended_at: Option<DateTime<Utc>>,
@@ -250,6 +274,9 @@ impl From<&TaskRecord> for TaskSummary {
             prompt_summary: summarize_text(&value.prompt, TIMELINE_SUMMARY_LIMIT),
             model: value.model.clone(),
             mode: value.mode.clone(),
+            priority: value.priority,
+            completion_pct: value.checklist.completion_pct,
+            tool_call_count: value.tool_calls.len(),
             created_at: value.created_at,
             started_at: value.started_at,
             ended_at: value.ended_at,
@@ -705,6 +732,7 @@ pub struct TaskManager {
     executor: Arc<dyn TaskExecutor>,
     tasks_dir: PathBuf,
     artifacts_dir: PathBuf,
+    transcripts_dir: PathBuf,
     queue_path: PathBuf,
     state: Mutex<ManagerState>,
     notify: Notify,
@@ -754,6 +782,7 @@ impl TaskManager {
         let workers = cfg.worker_count.clamp(1, MAX_WORKERS);
         let tasks_dir = cfg.data_dir.join("tasks");
         let artifacts_dir = cfg.data_dir.join("artifacts");
+        let transcripts_dir = cfg.data_dir.join("transcripts");
         let queue_path = cfg.data_dir.join("queue.json");
         fs::create_dir_all(&tasks_dir)
             .with_context(|| format!("Failed to create tasks dir {}", tasks_dir.display()))?;
@@ -763,6 +792,12 @@ impl TaskManager {
                 artifacts_dir.display()
             )
         })?;
+        fs::create_dir_all(&transcripts_dir).with_context(|| {
+            format!(
+                "Failed to create task transcripts dir {}",
+                transcripts_dir.display()
+            )
+        })?;
 
         let (tasks, queue) = load_state(&tasks_dir, &queue_path)?;
 
@@ -774,6 +809,7 @@ impl TaskManager {
             executor,
             tasks_dir,
             artifacts_dir,
+            transcripts_dir,
             queue_path,
             state: Mutex::new(ManagerState {
                 tasks,
@@ -844,6 +880,7 @@ impl TaskManager {
             // Auto-approval must be opted into explicitly
             // (GHSA-72w5-pf8h-xfp4).
             auto_approve: req.auto_approve.unwrap_or(false),
+            priority: 0,
             status: TaskStatus::Queued,
             created_at: Utc::now(),
             started_at: None,
@@ -871,8 +908,8 @@ impl TaskManager {
 
         {
             let mut state = self.state.lock().await;
-            state.queue.push_back(task.id.clone());
             state.tasks.insert(task.id.clone(), task.clone());
+            enqueue_by_priority(&mut state, &task.id);
             self.persist_all_locked(&state)?;
         }
         self.notify.notify_one();
@@ -955,6 +992,72 @@ impl TaskManager {
             .ok_or_else(|| anyhow!("Task not found: {id}"))
     }
 
+    /// Change a queued task's priority and move it within the queue.
+    /// Running and finished tasks are rejected: priority only affects which
+    /// queued task a free worker picks next.
+    pub async fn set_task_priority(&self, id_or_prefix: &str, priority: i32) -> Result<TaskRecord> {
+        let mut state = self.state.lock().await;
+        let id = resolve_task_id(&state.tasks, id_or_prefix)?;
+        let priority = priority.clamp(MIN_TASK_PRIORITY, MAX_TASK_PRIORITY);
+        {
+            let task = state
+                .tasks
+                .get_mut(&id)
+                .ok_or_else(|| anyhow!("Task not found: {id}"))?;
+            if task.status != TaskStatus::Queued {
+                bail!(
+                    "Only queued tasks can be reprioritized ({id} is {:?})",
+                    task.status
+                );
+            }
+            if task.priority == priority {
+                return Ok(task.clone());
+            }
+            task.priority = priority;
+            task.timeline.push(TaskTimelineEntry {
+                timestamp: Utc::now(),
+                kind: "priority".to_string(),
+                summary: format!("Priority set to {priority}"),
+                detail_path: None,
+            });
+        }
+        state.queue.retain(|queued_id| queued_id != &id);
+        enqueue_by_priority(&mut state, &id);
+        self.persist_all_locked(&state)?;
+        state
+            .tasks
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Task not found: {id}"))
+    }
+
+    /// Full streamed transcript of a task (assistant text plus tool
+    /// markers). Empty when the task has not produced output yet.
+    pub async fn task_transcript(&self, id_or_prefix: &str) -> Result<String> {
+        let id = {
+            let state = self.state.lock().await;
+            resolve_task_id(&state.tasks, id_or_prefix)?
+        };
+        let path = self.transcript_path(&id);
+        match fs::read_to_string(&path) {
+            Ok(text) => Ok(text),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+            Err(err) => {
+                Err(err).with_context(|| format!("Failed to read transcript {}", path.display()))
+            }
+        }
+    }
+
+    /// Final output of a finished task: the full result artifact when one
+    /// was written, otherwise the stored summary.
+    #[must_use]
+    pub fn task_output(&self, task: &TaskRecord) -> Option<String> {
+        task.result_detail_path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(self.artifact_absolute_path(path)).ok())
+            .or_else(|| task.result_summary.clone())
+    }
+
     /// Return aggregate status counters.
     pub async fn counts(&self) -> TaskCounts {
         let state = self.state.lock().await;
@@ -1131,6 +1234,10 @@ impl TaskManager {
             return Ok(());
         };
 
+        if let Some(entry) = transcript_entry(&event) {
+            self.append_transcript(task_id, &entry);
+        }
+
         match event {
             TaskExecutionEvent::ThreadLinked { thread_id, turn_id } => {
                 task.thread_id = Some(thread_id.clone());
@@ -1298,6 +1405,7 @@ impl TaskManager {
             result.error = None;
         }
 
+        self.append_transcript(task_id, &format!("\n\n[task {}]\n", result.status.label()));
         task.status = result.status;
         task.mode = mode_label.to_string();
         task.ended_at = Some(now);
@@ -1344,6 +1452,25 @@ impl TaskManager {
         Ok(())
     }
 
+    fn transcript_path(&self, task_id: &str) -> PathBuf {
+        self.transcripts_dir
+            .join(format!("{}.log", sanitize_filename(task_id)))
+    }
+
+    /// Best-effort append; a failed transcript write never fails the task.
+    fn append_transcript(&self, task_id: &str, text: &str) {
+        use std::io::Write as _;
+        let path = self.transcript_path(task_id);
+        let result = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(text.as_bytes()));
+        if let Err(err) = result {
+            tracing::warn!("Failed to append task transcript {}: {err}", path.display());
+        }
+    }
+
     fn artifact_if_large(
         &self,
         task_id: &str,
@@ -1557,6 +1684,44 @@ fn load_state(
     Ok((tasks, queue))
 }
 
+/// Insert `id` behind every queued task of equal or higher priority.
+fn enqueue_by_priority(state: &mut ManagerState, id: &str) {
+    let priority_of = |task_id: &str| state.tasks.get(task_id).map_or(0, |task| task.priority);
+    let priority = priority_of(id);
+    let position = state
+        .queue
+        .iter()
+        .position(|queued| priority_of(queued) < priority)
+        .unwrap_or(state.queue.len());
+    state.queue.insert(position, id.to_string());
+}
+
+/// Transcript text for an execution event, if it produces any.
+fn transcript_entry(event: &TaskExecutionEvent) -> Option<String> {
+    match event {
+        TaskExecutionEvent::MessageDelta { content } => Some(content.clone()),
+        TaskExecutionEvent::ToolStarted { name, input, .. } => Some(match summarize_json(input) {
+            Some(input) => format!("\n\n> {name} {input}\n"),
+            None => format!("\n\n> {name}\n"),
+        }),
+        TaskExecutionEvent::ToolCompleted {
+            name,
+            success,
+            output,
+            ..
+        } => Some(format!(
+            "< {name} {}: {}\n\n",
+            if *success { "ok" } else { "failed" },
+            summarize_text(output, TIMELINE_SUMMARY_LIMIT)
+        )),
+        TaskExecutionEvent::Error { message } => Some(format!("\n[error] {message}\n")),
+        TaskExecutionEvent::ThreadLinked { .. }
+        | TaskExecutionEvent::Status { .. }
+        | TaskExecutionEvent::ToolProgress { .. }
+        | TaskExecutionEvent::RuntimeEvent { .. } => None,
+    }
+}
+
 fn resolve_task_id(tasks: &HashMap<String, TaskRecord>, id_or_prefix: &str) -> Result<String> {
     if tasks.contains_key(id_or_prefix) {
         return Ok(id_or_prefix.to_string());
@@ -1851,6 +2016,40 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn raised_priority_runs_first_and_transcript_is_recorded() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("deepseek-task-test-{}", Uuid::new_v4()));
+        let manager =
+            TaskManager::start_with_executor(test_config(root), Arc::new(MockExecutor)).await?;
+
+        let first = manager
+            .add_task(NewTaskRequest::from_prompt("first"))
+            .await?;
+        let second = manager
+            .add_task(NewTaskRequest::from_prompt("second"))
+            .await?;
+        let third = manager
+            .add_task(NewTaskRequest::from_prompt("third"))
+            .await?;
+
+        let bumped = manager.set_task_priority(&third.id, 42).await?;
+        assert_eq!(bumped.priority, MAX_TASK_PRIORITY);
+
+        for id in [&first.id, &second.id, &third.id] {
+            wait_for_terminal_state(&manager, id, Duration::from_secs(10)).await?;
+        }
+        let second = manager.get_task(&second.id).await?;
+        let third = manager.get_task(&third.id).await?;
+        assert!(third.started_at < second.started_at);
+        assert!(manager.set_task_priority(&third.id, 1).await.is_err());
+
+        let transcript = manager.task_transcript(&third.id).await?;
+        assert!(transcript.contains("> read_file"));
+        assert!(transcript.contains("< read_file ok: read ok"));
+        assert!(transcript.contains("[task completed]"));
+        Ok(())
+    }
+
     // GHSA-72w5-pf8h-xfp4 — regression: omitted optional fields must not
     // silently elevate the spawned task's privileges.
     #[tokio::test]
@@ -0,0 +1,523 @@
+//! `deepseek tool`: run the agent's tools directly, without the model.
+//!
+//! `deepseek tool list` prints the catalog (`--schemas` adds each input
+//! schema) and `deepseek tool run <NAME> --<param> <value> ...` executes one
+//! call. Flags are mapped onto the tool's JSON input through its schema:
+//! `--max-results 5` becomes `{"max_results": 5}`, a bare boolean flag is
+//! `true`, and repeating an array flag appends. `--input <JSON|->` supplies a
+//! whole input object that flags then override.
+//!
+//! Calls run with the same guarantees the agent gets: a workspace-write
+//! sandbox for shell commands, each tool's own result caps, and the 100 KiB
+//! spillover that keeps the head inline and writes the rest under
+//! `~/.deepseek/tool_outputs/`. Tools that would ask for approval in the TUI
+//! refuse to run unless `--yes` is passed.
+
+use std::io::Read;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result, bail};
+use serde_json::{Map, Value, json};
+
+use crate::sandbox::SandboxPolicy;
+use crate::tools::spec::{ApprovalRequirement, ToolSpec};
+use crate::tools::{ToolContext, ToolRegistry, ToolRegistryBuilder};
+
+/// Options of `tool run` itself. They are recognized anywhere after the tool
+/// name unless the tool has a parameter of the same name.
+const RUN_OPTIONS: &[&str] = &["json", "yes", "full", "input"];
+
+/// Tools runnable from the command line: the workspace surface the agent
+/// uses, minus anything that needs a model client or a live TUI session.
+fn standalone_registry(workspace: &Path) -> ToolRegistry {
+    let context = ToolContext::new(workspace.to_path_buf()).with_elevated_sandbox_policy(
+        SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![workspace.to_path_buf()],
+            network_access: true,
+            exclude_tmpdir: false,
+            exclude_slash_tmp: false,
+        },
+    );
+    ToolRegistryBuilder::new()
+        .with_file_tools()
+        .with_search_tools()
+        .with_git_tools()
+        .with_git_history_tools()
+        .with_diagnostics_tool()
+        .with_project_tools()
+        .with_docs_search_tool()
+        .with_patch_tools()
+        .with_shell_tools()
+        .with_test_runner_tool()
+        .with_validation_tools()
+        .with_snippet_tool()
+        .build(context)
+}
+
+fn sorted_tools(registry: &ToolRegistry) -> Vec<Arc<dyn ToolSpec>> {
+    let mut tools = registry.all();
+    tools.sort_by(|a, b| a.name().cmp(b.name()));
+    tools
+}
+
+fn approval_label(requirement: ApprovalRequirement) -> &'static str {
+    match requirement {
+        ApprovalRequirement::Auto => "auto",
+        ApprovalRequirement::Suggest => "suggest",
+        ApprovalRequirement::Required => "required",
+    }
+}
+
+/// `deepseek tool list`.
+pub fn run_list(workspace: &Path, schemas: bool, json_output: bool) -> Result<()> {
+    let registry = standalone_registry(workspace);
+    let tools = sorted_tools(&registry);
+    if json_output {
+        let entries = tools
+            .iter()
+            .map(|tool| {
+                let mut entry = json!({
+                    "name": tool.name(),
+                    "description": tool.description(),
+                    "approval": approval_label(tool.approval_requirement()),
+                    "read_only": tool.is_read_only(),
+                });
+                if schemas {
+                    entry["input_schema"] = tool.input_schema();
+                }
+                entry
+            })
+            .collect::<Vec<_>>();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    let width = tools
+        .iter()
+        .map(|tool| tool.name().len())
+        .max()
+        .unwrap_or(0);
+    for tool in &tools {
+        let first_line = tool.description().lines().next().unwrap_or_default();
+        let summary = first_line
+            .split_once(". ")
+            .map_or(first_line, |(sentence, _)| sentence);
+        let marker = match tool.approval_requirement() {
+            ApprovalRequirement::Auto => "",
+            _ => "  [needs --yes]",
+        };
+        println!("{:width$}  {summary}{marker}", tool.name());
+        if schemas {
+            for line in flag_help(&tool.input_schema()) {
+                println!("{:width$}    {line}", "");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// One line per schema property, e.g. `--pattern <string> (required)`.
+fn flag_help(schema: &Value) -> Vec<String> {
+    let required = required_keys(schema);
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    properties
+        .iter()
+        .map(|(key, property)| {
+            let kind = property_type(property).unwrap_or("json");
+            let mut line = format!("--{} <{kind}>", key.replace('_', "-"));
+            if required.iter().any(|name| name == key) {
+                line.push_str(" (required)");
+            }
+            if let Some(description) = property.get("description").and_then(Value::as_str) {
+                line.push_str("  ");
+                line.push_str(description.lines().next().unwrap_or_default());
+            }
+            line
+        })
+        .collect()
+}
+
+/// `deepseek tool run`. Returns the process exit code: `0` when the tool
+/// succeeded, `1` when it ran and reported failure.
+pub async fn run_tool(workspace: &Path, name: &str, raw_args: &[String]) -> Result<i32> {
+    let registry = standalone_registry(workspace);
+    let Some(tool) = registry.get(name) else {
+        let names = sorted_tools(&registry)
+            .iter()
+            .map(|tool| tool.name().to_string())
+            .collect::<Vec<_>>();
+        bail!("unknown tool `{name}`; available: {}", names.join(", "));
+    };
+    let schema = tool.input_schema();
+    let invocation = parse_run_args(&schema, raw_args)?;
+    if tool.approval_requirement() != ApprovalRequirement::Auto && !invocation.yes {
+        bail!(
+            "`{name}` needs approval when the agent calls it; pass --yes to run it from the command line"
+        );
+    }
+
+    let mut result = match registry.execute_full(name, invocation.input).await {
+        Ok(result) => result,
+        Err(err) => {
+            if invocation.json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json!({
+                        "tool": name,
+                        "success": false,
+                        "error": err.to_string(),
+                    }))?
+                );
+            } else {
+                eprintln!("{name}: {err}");
+            }
+            return Ok(1);
+        }
+    };
+    if !invocation.full {
+        let call_id = format!("cli-{}-{}", name, std::process::id());
+        crate::tools::truncate::apply_spillover(&mut result, &call_id);
+    }
+
+    if invocation.json {
+        let mut output = json!({
+            "tool": name,
+            "success": result.success,
+            "content": result.content,
+        });
+        if let Some(metadata) = result.metadata.take() {
+            output["metadata"] = metadata;
+        }
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else if result.success {
+        println!("{}", result.content);
+    } else {
+        eprintln!("{}", result.content);
+    }
+    Ok(if result.success { 0 } else { 1 })
+}
+
+/// A parsed `tool run` command line.
+#[derive(Debug, Clone, PartialEq)]
+struct Invocation {
+    input: Value,
+    json: bool,
+    yes: bool,
+    full: bool,
+}
+
+fn parse_run_args(schema: &Value, raw_args: &[String]) -> Result<Invocation> {
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    let mut invocation = Invocation {
+        input: Value::Object(Map::new()),
+        json: false,
+        yes: false,
+        full: false,
+    };
+    let mut base: Option<Value> = None;
+    let mut flags = Map::new();
+
+    let mut args = raw_args.iter().peekable();
+    while let Some(arg) = args.next() {
+        let Some(flag) = arg.strip_prefix("--") else {
+            bail!("unexpected argument `{arg}`; tool parameters are passed as --<name> <value>");
+        };
+        let (flag, inline_value) = match flag.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (flag, None),
+        };
+        let key = flag.replace('-', "_");
+
+        if !properties.contains_key(&key) && RUN_OPTIONS.contains(&key.as_str()) {
+            match key.as_str() {
+                "json" => invocation.json = true,
+                "yes" => invocation.yes = true,
+                "full" => invocation.full = true,
+                _ => {
+                    let value = inline_value
+                        .or_else(|| args.next().cloned())
+                        .context("--input needs a JSON object or `-` for stdin")?;
+                    base = Some(read_input_object(&value)?);
+                }
+            }
+            continue;
+        }
+
+        if let Some(negated) = key.strip_prefix("no_")
+            && inline_value.is_none()
+            && properties
+                .get(negated)
+                .is_some_and(|property| property_type(property) == Some("boolean"))
+        {
+            flags.insert(negated.to_string(), Value::Bool(false));
+            continue;
+        }
+
+        let Some(property) = properties.get(&key) else {
+            let known = properties
+                .keys()
+                .map(|key| format!("--{}", key.replace('_', "-")))
+                .collect::<Vec<_>>();
+            bail!(
+                "unknown parameter --{flag}; expected one of: {}",
+                known.join(", ")
+            );
+        };
+        let kind = property_type(property);
+        let value = match inline_value {
+            Some(value) => value,
+            None if kind == Some("boolean")
+                && args.peek().is_none_or(|next| next.starts_with("--")) =>
+            {
+                flags.insert(key, Value::Bool(true));
+                continue;
+            }
+            None => args
+                .next()
+                .cloned()
+                .with_context(|| format!("--{flag} needs a value"))?,
+        };
+        let value = convert_value(property, &value).with_context(|| format!("--{flag}"))?;
+        if kind != Some("array") {
+            flags.insert(key, value);
+            continue;
+        }
+        let items = match value {
+            Value::Array(items) => items,
+            item => vec![item],
+        };
+        match flags.get_mut(&key) {
+            Some(Value::Array(existing)) => existing.extend(items),
+            _ => {
+                flags.insert(key, Value::Array(items));
+            }
+        }
+    }
+
+    let mut input = match base {
+        Some(Value::Object(map)) => map,
+        _ => Map::new(),
+    };
+    input.extend(flags);
+    let missing = required_keys(schema)
+        .into_iter()
+        .filter(|key| !input.contains_key(key))
+        .map(|key| format!("--{}", key.replace('_', "-")))
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        bail!("missing required parameter(s): {}", missing.join(", "));
+    }
+    invocation.input = Value::Object(input);
+    Ok(invocation)
+}
+
+fn read_input_object(value: &str) -> Result<Value> {
+    let raw = if value == "-" {
+        let mut raw = String::new();
+        std::io::stdin()
+            .read_to_string(&mut raw)
+            .context("failed to read --input from stdin")?;
+        raw
+    } else {
+        value.to_string()
+    };
+    let parsed: Value = serde_json::from_str(&raw).context("--input is not valid JSON")?;
+    if !parsed.is_object() {
+        bail!("--input must be a JSON object");
+    }
+    Ok(parsed)
+}
+
+fn required_keys(schema: &Value) -> Vec<String> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|keys| {
+            keys.iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The property's JSON type; the first non-null entry of a type list.
+fn property_type(property: &Value) -> Option<&str> {
+    match property.get("type")? {
+        Value::String(kind) => Some(kind.as_str()),
+        Value::Array(kinds) => kinds
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|kind| *kind != "null"),
+        _ => None,
+    }
+}
+
+/// Convert one flag value to the JSON type the schema declares. Arrays take
+/// either a JSON array or a single item; untyped properties accept JSON and
+/// fall back to a string.
+fn convert_value(property: &Value, raw: &str) -> Result<Value> {
+    match property_type(property) {
+        Some("string") | None if !looks_like_json(raw) => Ok(Value::String(raw.to_string())),
+        Some("string") => Ok(Value::String(raw.to_string())),
+        Some("integer") => raw
+            .parse::<i64>()
+            .map(Value::from)
+            .with_context(|| format!("expected an integer, got `{raw}`")),
+        Some("number") => raw
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .with_context(|| format!("expected a number, got `{raw}`")),
+        Some("boolean") => match raw {
+            "true" | "yes" | "1" => Ok(Value::Bool(true)),
+            "false" | "no" | "0" => Ok(Value::Bool(false)),
+            _ => bail!("expected true or false, got `{raw}`"),
+        },
+        Some("array") => {
+            if raw.trim_start().starts_with('[') {
+                return serde_json::from_str(raw).context("invalid JSON array");
+            }
+            let items = property.get("items").cloned().unwrap_or(Value::Null);
+            convert_value(&items, raw)
+        }
+        Some("object") => serde_json::from_str(raw).context("expected a JSON object"),
+        _ => Ok(serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))),
+    }
+}
+
+fn looks_like_json(raw: &str) -> bool {
+    matches!(raw.trim_start().chars().next(), Some('{' | '['))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pattern": { "type": "string", "description": "Regex to search for" },
+                "max_results": { "type": "integer" },
+                "case_sensitive": { "type": "boolean" },
+                "paths": { "type": "array", "items": { "type": "string" } },
+                "json": { "type": ["boolean", "null"] }
+            },
+            "required": ["pattern"]
+        })
+    }
+
+    fn args(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|arg| (*arg).to_string()).collect()
+    }
+
+    #[test]
+    fn flags_map_onto_schema_types() {
+        let invocation = parse_run_args(
+            &schema(),
+            &args(&[
+                "--pattern",
+                "fn main",
+                "--max-results=5",
+                "--case-sensitive",
+                "--paths",
+                "src",
+                "--paths",
+                "[\"tests\",\"benches\"]",
+                "--yes",
+            ]),
+        )
+        .unwrap();
+        assert_eq!(
+            invocation.input,
+            json!({
+                "pattern": "fn main",
+                "max_results": 5,
+                "case_sensitive": true,
+                "paths": ["src", "tests", "benches"],
+            })
+        );
+        assert!(invocation.yes);
+        assert!(!invocation.json);
+    }
+
+    #[test]
+    fn tool_parameters_shadow_run_options_and_input_is_overridden() {
+        let invocation = parse_run_args(
+            &schema(),
+            &args(&[
+                "--input",
+                "{\"pattern\":\"old\",\"max_results\":2}",
+                "--pattern",
+                "new",
+                "--json",
+                "--no-case-sensitive",
+            ]),
+        )
+        .unwrap();
+        assert_eq!(
+            invocation.input,
+            json!({
+                "pattern": "new",
+                "max_results": 2,
+                "json": true,
+                "case_sensitive": false,
+            })
+        );
+        assert!(!invocation.json, "the tool's own `json` parameter wins");
+    }
+
+    #[test]
+    fn rejects_unknown_missing_and_mistyped_parameters() {
+        let err = parse_run_args(&schema(), &args(&["--patern", "x"])).unwrap_err();
+        assert!(err.to_string().contains("--pattern"), "{err}");
+        let err = parse_run_args(&schema(), &args(&["--max-results", "3"])).unwrap_err();
+        assert!(err.to_string().contains("missing required"), "{err}");
+        let err = parse_run_args(
+            &schema(),
+            &args(&["--pattern", "x", "--max-results", "lots"]),
+        )
+        .unwrap_err();
+        assert!(format!("{err:#}").contains("integer"), "{err:#}");
+        assert!(parse_run_args(&schema(), &args(&["stray"])).is_err());
+    }
+
+    #[test]
+    fn flag_help_lists_required_parameters() {
+        let help = flag_help(&schema());
+        assert!(
+            help.iter()
+                .any(|line| line == "--pattern <string> (required)  Regex to search for"),
+            "{help:?}"
+        );
+        assert!(
+            help.iter()
+                .any(|line| line.starts_with("--max-results <integer>"))
+        );
+    }
+
+    #[tokio::test]
+    async fn runs_a_read_only_tool_against_the_workspace() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("notes.txt"), "alpha\nbeta\n").unwrap();
+        let code = run_tool(tmp.path(), "read_file", &args(&["--path", "notes.txt"]))
+            .await
+            .unwrap();
+        assert_eq!(code, 0);
+
+        let err = run_tool(tmp.path(), "exec_shell", &args(&["--command", "true"]))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("--yes"), "{err}");
+        assert!(run_tool(tmp.path(), "no_such_tool", &[]).await.is_err());
+    }
+}
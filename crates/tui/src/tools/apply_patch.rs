@@ -12,10 +12,12 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use thiserror::Error;
 
+use super::edit_validation::{display_path, validate_writes};
 use super::spec::{
     ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolResult, ToolSpec,
     lsp_diagnostics_for_paths, optional_bool, optional_str, optional_u64, required_str,
 };
+use super::write_audit::{FileWriteAudit, WriteAudit};
 
 /// Maximum lines of context for fuzzy matching (increased for better tolerance)
 const MAX_FUZZ: usize = 50;
@@ -23,6 +25,8 @@ const MAX_FUZZ: usize = 50;
 const HUNK_PREVIEW_LINES: usize = 4;
 const SNIPPET_RADIUS: usize = 2;
 const FILE_LIST_LIMIT: usize = 6;
+/// Placement suggestions listed when a hunk's context no longer matches.
+const PLACEMENT_SUGGESTIONS: usize = 3;
 
 // === Types ===
 
@@ -77,15 +81,27 @@ pub enum HunkLine {
     Remove(String),
 }
 
+/// A window of the current file where a drifted hunk could land.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HunkPlacement {
+    /// 1-based first line of the window the hunk's old side would cover.
+    pub line: usize,
+    /// Old-side lines (context and removals) that match the window after
+    /// whitespace trimming.
+    pub matched: usize,
+    pub total: usize,
+}
+
 /// Tool for applying unified diff patches to files
 pub struct ApplyPatchTool;
 
+/// One file section of a multi-file unified diff.
 #[derive(Debug, Clone)]
-struct FilePatch {
-    path: String,
-    hunks: Vec<Hunk>,
-    delete_after: bool,
-    create_if_missing: bool,
+pub struct FilePatch {
+    pub path: String,
+    pub hunks: Vec<Hunk>,
+    pub delete_after: bool,
+    pub create_if_missing: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -215,7 +231,8 @@ impl ToolSpec for ApplyPatchTool {
 
         if let Some(changes_value) = input.get("changes") {
             let (pending, stats) = build_pending_writes_from_changes(changes_value, context)?;
-            apply_pending_writes(&pending)?;
+            validate_pending_writes(context, &pending).await?;
+            apply_pending_writes(context, &pending)?;
             // Resolve absolute paths for LSP diagnostics query.
             let abs_paths: Vec<PathBuf> = pending.iter().map(|p| p.path.clone()).collect();
             let diag_block = lsp_diagnostics_for_paths(context, &abs_paths).await;
@@ -237,7 +254,7 @@ impl ToolSpec for ApplyPatchTool {
                 tool_result.content.push('\n');
                 tool_result.content.push_str(&diag_block);
             }
-            return Ok(tool_result);
+            return Ok(write_audit(context, &pending).attach(tool_result));
         }
 
         let patch_text = required_str(&input, "patch")?;
@@ -272,7 +289,8 @@ impl ToolSpec for ApplyPatchTool {
         if stats.header_path_mismatch.is_none() {
             stats.header_path_mismatch = mismatch_note;
         }
-        apply_pending_writes(&pending)?;
+        validate_pending_writes(context, &pending).await?;
+        apply_pending_writes(context, &pending)?;
         // Resolve absolute paths for LSP diagnostics query.
         let abs_paths: Vec<PathBuf> = pending
             .iter()
@@ -298,7 +316,7 @@ impl ToolSpec for ApplyPatchTool {
             tool_result.content.push('\n');
             tool_result.content.push_str(&diag_block);
         }
-        Ok(tool_result)
+        Ok(write_audit(context, &pending).attach(tool_result))
     }
 }
 
@@ -326,6 +344,11 @@ fn parse_unified_diff(patch: &str) -> Result<Vec<Hunk>, ToolError> {
     Ok(hunks)
 }
 
+/// Parse a multi-file unified diff (`---`/`+++` headers required).
+pub fn parse_file_patches(patch: &str) -> Result<Vec<FilePatch>, ToolError> {
+    parse_unified_diff_files(patch, false)
+}
+
 fn parse_unified_diff_files(
     patch: &str,
     create_if_missing: bool,
@@ -768,10 +791,27 @@ fn build_pending_writes_from_patches(
     Ok((pending, stats))
 }
 
-fn apply_pending_writes(pending: &[PendingWrite]) -> Result<(), ToolError> {
+/// Syntax-check every file the patch writes (deletions are skipped) before
+/// any of them lands.
+async fn validate_pending_writes(
+    context: &ToolContext,
+    pending: &[PendingWrite],
+) -> Result<(), ToolError> {
+    let writes: Vec<_> = pending
+        .iter()
+        .filter_map(|entry| {
+            let content = entry.content.as_deref()?;
+            Some((entry.path.as_path(), entry.original.as_deref(), content))
+        })
+        .collect();
+    validate_writes(context, &writes).await
+}
+
+fn apply_pending_writes(context: &ToolContext, pending: &[PendingWrite]) -> Result<(), ToolError> {
     let mut applied = Vec::new();
 
     for entry in pending {
+        context.note_before_write(&entry.path);
         let result = if let Some(content) = entry.content.as_ref() {
             if let Some(parent) = entry.path.parent() {
                 fs::create_dir_all(parent).map_err(|e| {
@@ -812,6 +852,23 @@ fn apply_pending_writes(pending: &[PendingWrite]) -> Result<(), ToolError> {
     Ok(())
 }
 
+/// Before/after diff of every file the patch wrote, for the tool-details
+/// pager.
+fn write_audit(context: &ToolContext, pending: &[PendingWrite]) -> WriteAudit {
+    WriteAudit::new(
+        pending
+            .iter()
+            .map(|entry| {
+                FileWriteAudit::new(
+                    display_path(&context.workspace, &entry.path),
+                    entry.original.as_deref(),
+                    entry.content.as_deref(),
+                )
+            })
+            .collect(),
+    )
+}
+
 fn rollback_pending_writes(applied: &[PendingWrite]) {
     for entry in applied.iter().rev() {
         match entry.original.as_ref() {
@@ -884,8 +941,19 @@ fn format_hunk_no_match_error(
         } => {
             let expected_preview = preview_expected_lines(hunk, HUNK_PREVIEW_LINES).join("\n");
             let file_preview = snippet_around(lines, *adjusted_line, SNIPPET_RADIUS).join("\n");
+            let placements = suggest_hunk_placements(lines, hunk, PLACEMENT_SUGGESTIONS);
+            let placement_note = if placements.is_empty() {
+                String::new()
+            } else {
+                let list = placements
+                    .iter()
+                    .map(|p| format!("line {} ({}/{} lines match)", p.line, p.matched, p.total))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("\nClosest placements: {list}")
+            };
             format!(
-                "could not find matching context near line {expected_line} (searched around line {adjusted_line} with offset {offset:+} and fuzz up to {max_fuzz}). Expected context preview:\n{expected_preview}\nFile snippet near line {adjusted_line}:\n{file_preview}\nHints: ensure the patch matches the current file contents, increase `fuzz`, or regenerate the patch."
+                "could not find matching context near line {expected_line} (searched around line {adjusted_line} with offset {offset:+} and fuzz up to {max_fuzz}). Expected context preview:\n{expected_preview}\nFile snippet near line {adjusted_line}:\n{file_preview}{placement_note}\nHints: ensure the patch matches the current file contents, increase `fuzz`, or regenerate the patch."
             )
         }
     }
@@ -925,22 +993,212 @@ fn apply_hunks_to_lines(
     Ok(stats)
 }
 
-/// Apply a hunk to the file content with fuzzy matching
-fn apply_hunk(
+/// Apply one hunk with the same offset tracking and fuzz search the tool uses.
+/// Returns the fuzz needed, or `None` when the context no longer matches.
+pub fn apply_hunk_fuzzy(
     lines: &mut Vec<String>,
     hunk: &Hunk,
     max_fuzz: usize,
     cumulative_offset: &mut isize,
-) -> Result<usize, ApplyHunkError> {
-    // Build expected old lines from hunk
-    let old_lines: Vec<&str> = hunk
-        .lines
+) -> Option<usize> {
+    apply_hunk(lines, hunk, max_fuzz, cumulative_offset).ok()
+}
+
+fn hunk_old_lines(hunk: &Hunk) -> Vec<&str> {
+    hunk.lines
         .iter()
         .filter_map(|line| match line {
             HunkLine::Context(s) | HunkLine::Remove(s) => Some(s.as_str()),
             HunkLine::Add(_) => None,
         })
+        .collect()
+}
+
+/// How a hunk's old side lines up with the file from a given start line.
+/// Lines are aligned by longest common subsequence, so lines inserted into
+/// the file since the patch was made stay inside the span untouched; when a
+/// gap between aligned lines has the same length on both sides, its lines
+/// are paired up as edits of each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HunkAlignment {
+    /// 0-based index of the first file line the hunk covers.
+    pub start: usize,
+    /// Number of file lines the hunk covers.
+    pub span: usize,
+    /// For each old-side line, the file line (relative to `start`) it lines
+    /// up with, if any.
+    pub mapped: Vec<Option<usize>>,
+    /// Old-side lines whose counterpart matches after whitespace trimming.
+    pub matched: usize,
+}
+
+/// Align `hunk` against `lines` starting at 1-based `line`.
+#[must_use]
+pub fn align_hunk(lines: &[String], hunk: &Hunk, line: usize) -> HunkAlignment {
+    let old_lines = hunk_old_lines(hunk);
+    let start = line.saturating_sub(1).min(lines.len());
+    // Leave room for lines added to the file inside the hunk since the
+    // patch was generated.
+    let window_end = (start + old_lines.len() * 3 / 2 + 2).min(lines.len());
+    let window = &lines[start..window_end];
+
+    let pairs = lcs_pairs(&old_lines, window);
+    let mut mapped = vec![None; old_lines.len()];
+    let mut prev = (0usize, 0usize);
+    let mut span = 0usize;
+    for &(old_idx, file_idx) in &pairs {
+        pair_gap(&mut mapped, prev, (old_idx, file_idx));
+        mapped[old_idx] = Some(file_idx);
+        prev = (old_idx + 1, file_idx + 1);
+        span = file_idx + 1;
+    }
+    // Trailing old-side lines claim at most as many file lines.
+    let trailing = old_lines.len() - prev.0;
+    let end = (prev.1 + trailing).min(window.len());
+    if end - prev.1 == trailing {
+        pair_gap(&mut mapped, prev, (old_lines.len(), end));
+        span = end;
+    }
+
+    let matched = mapped
+        .iter()
+        .zip(&old_lines)
+        .filter(|(file_idx, expected)| {
+            file_idx.is_some_and(|idx| window[idx].trim() == expected.trim())
+        })
+        .count();
+    HunkAlignment {
+        start,
+        span,
+        mapped,
+        matched,
+    }
+}
+
+fn pair_gap(mapped: &mut [Option<usize>], from: (usize, usize), to: (usize, usize)) {
+    if to.0 - from.0 == to.1 - from.1 {
+        for offset in 0..to.0 - from.0 {
+            mapped[from.0 + offset] = Some(from.1 + offset);
+        }
+    }
+}
+
+fn lcs_pairs(old_lines: &[&str], window: &[String]) -> Vec<(usize, usize)> {
+    let (n, m) = (old_lines.len(), window.len());
+    let mut table = vec![vec![0u16; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i][j] = if old_lines[i].trim() == window[j].trim() {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut pairs = Vec::new();
+    while i < n && j < m {
+        if old_lines[i].trim() == window[j].trim() {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// Score the placement of `hunk` at 1-based `line`.
+#[must_use]
+pub fn score_placement(lines: &[String], hunk: &Hunk, line: usize) -> HunkPlacement {
+    HunkPlacement {
+        line,
+        matched: align_hunk(lines, hunk, line).matched,
+        total: hunk_old_lines(hunk).len(),
+    }
+}
+
+/// Rank start lines by how many of the hunk's old-side lines align there,
+/// best first; ties go to the line nearest the header's. Only starts whose
+/// first line aligns are considered, and placements matching fewer than
+/// half the lines are not suggested.
+pub fn suggest_hunk_placements(lines: &[String], hunk: &Hunk, limit: usize) -> Vec<HunkPlacement> {
+    let old_lines = hunk_old_lines(hunk);
+    let total = old_lines.len();
+    if total == 0 {
+        return Vec::new();
+    }
+    let mut placements: Vec<HunkPlacement> = (0..lines.len())
+        .filter(|&pos| {
+            old_lines
+                .iter()
+                .any(|expected| expected.trim() == lines[pos].trim())
+        })
+        .filter_map(|pos| {
+            let alignment = align_hunk(lines, hunk, pos + 1);
+            let starts_here = alignment.mapped.iter().flatten().next() == Some(&0);
+            (starts_here && alignment.matched * 2 >= total).then_some(HunkPlacement {
+                line: pos + 1,
+                matched: alignment.matched,
+                total,
+            })
+        })
         .collect();
+    placements.sort_by_key(|p| {
+        (
+            std::cmp::Reverse(p.matched),
+            p.line.abs_diff(hunk.old_start.max(1)),
+        )
+    });
+    placements.truncate(limit);
+    placements
+}
+
+/// Apply `hunk` at 1-based `line` even though the file has drifted: aligned
+/// context lines keep the file's current text, aligned removals drop their
+/// file line, additions are inserted, and file lines the hunk does not
+/// account for are kept in place.
+pub fn apply_hunk_at(lines: &mut Vec<String>, hunk: &Hunk, line: usize) {
+    let alignment = align_hunk(lines, hunk, line);
+    let window = &lines[alignment.start..alignment.start + alignment.span];
+    let mut replacement = Vec::with_capacity(hunk.lines.len() + window.len());
+    let mut old_idx = 0usize;
+    let mut cursor = 0usize;
+    for hunk_line in &hunk.lines {
+        if let HunkLine::Add(added) = hunk_line {
+            replacement.push(added.clone());
+            continue;
+        }
+        let target = alignment.mapped[old_idx];
+        old_idx += 1;
+        let Some(file_idx) = target else {
+            continue;
+        };
+        replacement.extend(window[cursor..file_idx].iter().cloned());
+        cursor = file_idx + 1;
+        if matches!(hunk_line, HunkLine::Context(_)) {
+            replacement.push(window[file_idx].clone());
+        }
+    }
+    replacement.extend(window[cursor..].iter().cloned());
+    lines.splice(
+        alignment.start..alignment.start + alignment.span,
+        replacement,
+    );
+}
+
+/// Apply a hunk to the file content with fuzzy matching
+fn apply_hunk(
+    lines: &mut Vec<String>,
+    hunk: &Hunk,
+    max_fuzz: usize,
+    cumulative_offset: &mut isize,
+) -> Result<usize, ApplyHunkError> {
+    // Build expected old lines from hunk
+    let old_lines = hunk_old_lines(hunk);
 
     // Build new lines from hunk
     let new_lines: Vec<String> = hunk
@@ -1136,6 +1394,97 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_drifted_hunk_gets_placement_suggestion_and_applies_there() {
+        let mut lines: Vec<String> = ["header", "fn a() {", "    one();", "    two();", "}"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        // The patch was made against `one_old()` and its header points at
+        // line 10, so neither exact nor fuzzy matching can place it.
+        let hunk = Hunk {
+            old_start: 10,
+            old_count: 3,
+            new_start: 10,
+            new_count: 3,
+            lines: vec![
+                HunkLine::Context("fn a() {".to_string()),
+                HunkLine::Context("  one_old();".to_string()),
+                HunkLine::Remove("    two();".to_string()),
+                HunkLine::Add("    three();".to_string()),
+            ],
+        };
+
+        let mut offset = 0;
+        assert_eq!(apply_hunk_fuzzy(&mut lines, &hunk, 3, &mut offset), None);
+        let placements = suggest_hunk_placements(&lines, &hunk, 3);
+        assert_eq!(
+            placements[0],
+            HunkPlacement {
+                line: 2,
+                matched: 2,
+                total: 3
+            }
+        );
+
+        let err = format_hunk_no_match_error(
+            &lines,
+            &hunk,
+            &apply_hunk(&mut lines.clone(), &hunk, 0, &mut 0).unwrap_err(),
+            0,
+        );
+        assert!(
+            err.contains("Closest placements: line 2 (2/3 lines match)"),
+            "{err}"
+        );
+
+        apply_hunk_at(&mut lines, &hunk, placements[0].line);
+        assert_eq!(
+            lines,
+            vec!["header", "fn a() {", "    one();", "    three();", "}"]
+        );
+    }
+
+    #[test]
+    fn test_apply_hunk_at_keeps_lines_inserted_since_the_patch() {
+        let mut lines: Vec<String> = [
+            "fn keep() {}",
+            "fn old() {}",
+            "// added later",
+            "// added later",
+            "",
+            "fn a() {",
+            "    before(1);",
+            "}",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        let hunk = parse_unified_diff(
+            "@@ -1,6 +1,6 @@\n fn keep() {}\n-fn old() {}\n+fn new() {}\n \n fn a() {\n-    before();\n+    after();\n }\n",
+        )
+        .unwrap()
+        .remove(0);
+
+        let placements = suggest_hunk_placements(&lines, &hunk, 3);
+        assert_eq!(placements[0].line, 1);
+        assert_eq!(placements[0].matched, 5);
+        apply_hunk_at(&mut lines, &hunk, 1);
+        assert_eq!(
+            lines,
+            vec![
+                "fn keep() {}",
+                "fn new() {}",
+                "// added later",
+                "// added later",
+                "",
+                "fn a() {",
+                "    after();",
+                "}",
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_apply_patch_tool() {
         let tmp = tempdir().expect("tempdir");
@@ -140,11 +140,12 @@ impl ApprovalCache {
 #[must_use]
 pub fn build_approval_key(tool_name: &str, input: &serde_json::Value) -> ApprovalKey {
     let fingerprint = match tool_name {
-        "apply_patch" | "write_file" | "edit_file" | "fim_edit" => {
+        "apply_patch" | "write_file" | "edit_file" | "fim_edit" | "edit_with_fim" => {
             format!("file:{tool_name}:{}", hash_json_value(input))
         }
         "exec_shell"
         | "task_shell_start"
+        | "shell_open"
         | "exec_shell_wait"
         | "exec_shell_interact"
         | "exec_wait"
@@ -175,6 +176,7 @@ pub fn build_approval_grouping_key(tool_name: &str, input: &serde_json::Value) -
         }
         "exec_shell"
         | "task_shell_start"
+        | "shell_open"
         | "exec_shell_wait"
         | "exec_shell_interact"
         | "exec_wait"
@@ -251,7 +253,7 @@ fn parse_host(input: &serde_json::Value) -> String {
     }
 }
 
-fn hash_json_value(value: &Value) -> String {
+pub(crate) fn hash_json_value(value: &Value) -> String {
     let mut canonical = String::new();
     push_canonical_json(value, &mut canonical);
 
@@ -0,0 +1,165 @@
+//! Project command aliases exposed to the model as tools.
+//!
+//! Each alias from [`crate::command_aliases`] becomes a tool of the same
+//! name. A shell alias call is delegated to `exec_shell` with the expanded
+//! command before planning, so the approval card, command guardrail, and
+//! sandbox all see the command that actually runs; prompt aliases return
+//! their expanded instructions for the model to follow.
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use super::shell::ExecShellTool;
+use super::spec::{
+    ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolResult, ToolSpec, optional_str,
+};
+use crate::command_aliases::{AliasAction, CommandAlias};
+
+/// One project alias as a tool.
+pub struct CommandAliasTool {
+    alias: CommandAlias,
+    description: String,
+}
+
+impl CommandAliasTool {
+    #[must_use]
+    pub fn new(alias: CommandAlias) -> Self {
+        let description = match &alias.action {
+            AliasAction::Shell(command) => format!(
+                "Project alias `/{}`: {}. Runs `{command}` in the workspace; `arguments` are appended or fill its placeholders.",
+                alias.name,
+                alias.summary()
+            ),
+            AliasAction::Prompt(_) => format!(
+                "Project alias `/{}`: {}. Returns the project's instructions for this task; follow them.",
+                alias.name,
+                alias.summary()
+            ),
+        };
+        Self { alias, description }
+    }
+
+    fn is_shell(&self) -> bool {
+        matches!(self.alias.action, AliasAction::Shell(_))
+    }
+
+    fn expand(&self, input: &Value) -> String {
+        self.alias
+            .expand(optional_str(input, "arguments").unwrap_or(""))
+    }
+}
+
+#[async_trait]
+impl ToolSpec for CommandAliasTool {
+    fn name(&self) -> &str {
+        &self.alias.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "arguments": {
+                    "type": "string",
+                    "description": "Optional arguments, as typed after the slash command."
+                }
+            },
+            "additionalProperties": false
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        if self.is_shell() {
+            vec![
+                ToolCapability::ExecutesCode,
+                ToolCapability::Sandboxable,
+                ToolCapability::RequiresApproval,
+            ]
+        } else {
+            vec![ToolCapability::ReadOnly]
+        }
+    }
+
+    fn approval_requirement(&self) -> ApprovalRequirement {
+        if self.is_shell() {
+            ApprovalRequirement::Required
+        } else {
+            ApprovalRequirement::Auto
+        }
+    }
+
+    fn delegate_call(&self, input: &Value) -> Option<(String, Value)> {
+        self.is_shell().then(|| {
+            (
+                "exec_shell".to_string(),
+                json!({ "command": self.expand(input) }),
+            )
+        })
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let expanded = self.expand(&input);
+        match self.alias.action {
+            AliasAction::Shell(_) => {
+                ExecShellTool
+                    .execute(json!({ "command": expanded }), context)
+                    .await
+            }
+            AliasAction::Prompt(_) => Ok(ToolResult::success(expanded)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn prompt_alias_returns_expanded_instructions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let tool = CommandAliasTool::new(CommandAlias {
+            name: "notes".to_string(),
+            action: AliasAction::Prompt("Draft release notes since $1.".to_string()),
+            description: None,
+        });
+        assert_eq!(tool.name(), "notes");
+        assert!(tool.is_read_only());
+        assert!(tool.description().starts_with("Project alias `/notes`"));
+
+        let result = tool
+            .execute(
+                json!({ "arguments": "v0.8" }),
+                &ToolContext::new(tmp.path().to_path_buf()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.content, "Draft release notes since v0.8.");
+    }
+
+    #[test]
+    fn shell_alias_delegates_to_exec_shell_with_the_expanded_command() {
+        let tool = CommandAliasTool::new(CommandAlias {
+            name: "test".to_string(),
+            action: AliasAction::Shell("cargo test".to_string()),
+            description: None,
+        });
+        assert_eq!(
+            tool.delegate_call(&json!({ "arguments": "-p core; rm -rf x" })),
+            Some((
+                "exec_shell".to_string(),
+                json!({ "command": "cargo test -p 'core;' rm -rf x" })
+            ))
+        );
+
+        let prompt = CommandAliasTool::new(CommandAlias {
+            name: "notes".to_string(),
+            action: AliasAction::Prompt("Draft notes.".to_string()),
+            description: None,
+        });
+        assert_eq!(prompt.delegate_call(&json!({})), None);
+    }
+}
@@ -0,0 +1,868 @@
+//! `crate_docs` tool — API documentation for the exact dependency versions
+//! the workspace builds against.
+//!
+//! The version comes from `Cargo.lock`. Packages without a `source` in the
+//! lockfile are the workspace's own crates, so their `dependencies` lists
+//! say which version of a crate is a direct dependency when several are
+//! locked. Pages are read from local `cargo doc` output under `target/doc`
+//! when it covers the crate and from docs.rs otherwise; docs.rs pages for a
+//! pinned version never change, so they are cached under
+//! `~/.deepseek/cache/crate_docs/<crate>/<version>/`.
+//!
+//! Items resolve through rustdoc's `all.html` listing, so `Mutex`,
+//! `sync::Mutex`, and `tokio::sync::Mutex` all find
+//! `sync/struct.Mutex.html`; `Mutex::lock` narrows the page to that method.
+
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::docs_search::{between, html_to_text};
+use super::spec::{
+    ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolInput, ToolResult, ToolSpec,
+    input_schema_for, parse_input,
+};
+use crate::network_policy::Decision;
+
+const DOCS_RS: &str = "https://docs.rs";
+const DOCS_RS_HOST: &str = "docs.rs";
+const FETCH_TIMEOUT: Duration = Duration::from_secs(20);
+const USER_AGENT: &str =
+    "Mozilla/5.0 (compatible; deepseek-tui/0.5; +https://github.com/Hmbown/DeepSeek-TUI)";
+/// Page text returned per call. Long trait and struct pages are truncated;
+/// ask for `Type::method` to get one member in full.
+const MAX_PAGE_CHARS: usize = 24_000;
+/// Alternatives listed when an item name is ambiguous or unknown.
+const MAX_SUGGESTIONS: usize = 10;
+
+/// `crate_docs` arguments.
+#[derive(Debug, Deserialize, JsonSchema)]
+struct CrateDocsInput {
+    /// Crate name as written in Cargo.toml (e.g. `tokio`, `serde_json`)
+    #[serde(rename = "crate")]
+    krate: String,
+    /// Item to look up: a path such as `sync::Mutex`, a bare name such as `Mutex`, a member such as `Mutex::lock`, or a module such as `sync`. Omit for the crate's front page
+    item: Option<String>,
+    /// Where to read docs: `auto` (default; local target/doc, then docs.rs), `local`, or `docs.rs`
+    source: Option<String>,
+}
+
+impl ToolInput for CrateDocsInput {
+    fn examples() -> Vec<Value> {
+        vec![
+            json!({"crate": "tokio", "item": "sync::Mutex"}),
+            json!({"crate": "serde_json", "item": "Value::as_str", "source": "docs.rs"}),
+            json!({"crate": "regex"}),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocsSource {
+    Auto,
+    Local,
+    DocsRs,
+}
+
+impl DocsSource {
+    fn parse(value: Option<&str>) -> Result<Self, ToolError> {
+        match value
+            .map_or("auto", str::trim)
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "" | "auto" => Ok(Self::Auto),
+            "local" | "cargo doc" | "target/doc" => Ok(Self::Local),
+            "docs.rs" | "docsrs" | "remote" => Ok(Self::DocsRs),
+            other => Err(ToolError::invalid_input(format!(
+                "unknown source `{other}` (allowed: auto, local, docs.rs)"
+            ))),
+        }
+    }
+}
+
+/// Tool implementing `crate_docs`.
+pub struct CrateDocsTool;
+
+#[async_trait]
+impl ToolSpec for CrateDocsTool {
+    fn name(&self) -> &'static str {
+        "crate_docs"
+    }
+
+    fn description(&self) -> &'static str {
+        "Read the API documentation of a Rust dependency at the exact version pinned in Cargo.lock, from local `cargo doc` output when available or docs.rs otherwise. Look up an item (`sync::Mutex`, `Mutex`), a member (`Mutex::lock`), or a module to check real signatures and methods before writing code against a crate."
+    }
+
+    fn input_schema(&self) -> Value {
+        input_schema_for::<CrateDocsInput>()
+    }
+
+    fn input_examples(&self) -> Vec<Value> {
+        CrateDocsInput::examples()
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::ReadOnly, ToolCapability::Network]
+    }
+
+    fn approval_requirement(&self) -> ApprovalRequirement {
+        ApprovalRequirement::Auto
+    }
+
+    fn supports_parallel(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let input: CrateDocsInput = parse_input(&input)?;
+        let krate = input.krate.trim().to_string();
+        if krate.is_empty()
+            || !krate
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(ToolError::invalid_input(format!(
+                "`{krate}` is not a crate name"
+            )));
+        }
+        let source = DocsSource::parse(input.source.as_deref())?;
+        let lib = krate.replace('-', "_");
+        let item = input
+            .item
+            .as_deref()
+            .map(|item| normalize_item(item, &lib))
+            .filter(|item| !item.is_empty());
+
+        let workspace = context.workspace.clone();
+        let lookup_krate = krate.clone();
+        let resolved =
+            tokio::task::spawn_blocking(move || resolve_version(&workspace, &lookup_krate))
+                .await
+                .map_err(|err| {
+                    ToolError::execution_failed(format!("Cargo.lock task failed: {err}"))
+                })??;
+
+        let local_root = local_doc_root(&context.workspace, resolved.lock_dir.as_deref(), &lib);
+        let mut reader = match (source, local_root) {
+            (DocsSource::Local | DocsSource::Auto, Some(root)) => PageReader::Local(root),
+            (DocsSource::Local, None) => {
+                return Err(ToolError::execution_failed(format!(
+                    "no local docs for `{krate}` under target/doc; run `cargo doc -p {krate}` or use source `docs.rs`"
+                )));
+            }
+            (DocsSource::Auto | DocsSource::DocsRs, _) => {
+                check_network_policy(context)?;
+                PageReader::DocsRs {
+                    krate: krate.clone(),
+                    version: resolved.version.clone(),
+                    lib: lib.clone(),
+                    cache: resolved
+                        .pinned
+                        .then(|| cache_dir(&krate, &resolved.version))
+                        .flatten(),
+                }
+            }
+        };
+
+        let (page, anchor, other_matches) = match item.as_deref() {
+            None => ("index.html".to_string(), None, Vec::new()),
+            Some(item) => {
+                let listing = reader.read("all.html").await?;
+                match resolve_item(&parse_all_items(&listing), item) {
+                    ItemMatch::Found {
+                        href,
+                        anchor,
+                        others,
+                    } => (href, anchor, others),
+                    ItemMatch::Module(href) => (href, None, Vec::new()),
+                    ItemMatch::Missing(suggestions) => {
+                        return Ok(ToolResult::error(
+                            json!({
+                                "crate": krate,
+                                "version": resolved.version,
+                                "error": format!("no item `{item}` in {krate} {}", resolved.version),
+                                "did_you_mean": suggestions,
+                            })
+                            .to_string(),
+                        ));
+                    }
+                }
+            }
+        };
+
+        let html = match reader.read(&page).await {
+            Ok(html) => html,
+            // Module paths are guessed from the query; a miss is "no such
+            // item", not a transport failure.
+            Err(_) if page.ends_with("/index.html") && item.is_some() => {
+                return Ok(ToolResult::error(
+                    json!({
+                        "crate": krate,
+                        "version": resolved.version,
+                        "error": format!("no item or module `{}` in {krate} {}", item.unwrap_or_default(), resolved.version),
+                    })
+                    .to_string(),
+                ));
+            }
+            Err(err) => return Err(err),
+        };
+        let text = anchor
+            .as_deref()
+            .and_then(|anchor| member_text(&html, anchor))
+            .unwrap_or_else(|| page_text(&html));
+        let truncated = text.chars().count() > MAX_PAGE_CHARS;
+        let content: String = text.chars().take(MAX_PAGE_CHARS).collect();
+
+        ToolResult::json(&CrateDocsResponse {
+            krate,
+            version: resolved.version,
+            version_from: resolved.version_from,
+            dependency: resolved.dependency,
+            source: reader.label(),
+            item,
+            location: reader.location(&page, anchor.as_deref()),
+            content,
+            truncated,
+            other_matches,
+        })
+        .map_err(|err| ToolError::execution_failed(format!("failed to serialize docs: {err}")))
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CrateDocsResponse {
+    #[serde(rename = "crate")]
+    krate: String,
+    version: String,
+    /// `Cargo.lock`, or `unpinned` when there is no lockfile entry.
+    version_from: &'static str,
+    /// `direct`, `transitive`, or `unknown` (no lockfile).
+    dependency: &'static str,
+    source: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item: Option<String>,
+    location: String,
+    content: String,
+    truncated: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    other_matches: Vec<String>,
+}
+
+/// Version to document and how it was chosen.
+#[derive(Debug, PartialEq, Eq)]
+struct ResolvedVersion {
+    version: String,
+    version_from: &'static str,
+    dependency: &'static str,
+    /// Whether `version` is exact (and so safe to cache).
+    pinned: bool,
+    /// Directory holding `Cargo.lock`, which is also where `target/` lives.
+    lock_dir: Option<PathBuf>,
+}
+
+/// Find `Cargo.lock` in the workspace or an ancestor and pick the version of
+/// `krate` to document: the highest version a workspace crate depends on
+/// directly, else the highest locked version. With no lockfile at all the
+/// latest docs.rs release is used and reported as unpinned.
+fn resolve_version(workspace: &Path, krate: &str) -> Result<ResolvedVersion, ToolError> {
+    let Some(lock_path) = workspace
+        .ancestors()
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|path| path.is_file())
+    else {
+        return Ok(ResolvedVersion {
+            version: "latest".to_string(),
+            version_from: "unpinned",
+            dependency: "unknown",
+            pinned: false,
+            lock_dir: None,
+        });
+    };
+    let raw = fs::read_to_string(&lock_path).map_err(|err| {
+        ToolError::execution_failed(format!("failed to read {}: {err}", lock_path.display()))
+    })?;
+    let (version, dependency) = locked_version(&raw, krate)?.ok_or_else(|| {
+        ToolError::invalid_input(format!(
+            "`{krate}` is not in {}; check the crate name or run `cargo update -p {krate}` after adding it to Cargo.toml",
+            lock_path.display()
+        ))
+    })?;
+    Ok(ResolvedVersion {
+        version,
+        version_from: "Cargo.lock",
+        dependency,
+        pinned: true,
+        lock_dir: lock_path.parent().map(Path::to_path_buf),
+    })
+}
+
+/// `(version, "direct" | "transitive")` for `krate` in lockfile text.
+fn locked_version(
+    lockfile: &str,
+    krate: &str,
+) -> Result<Option<(String, &'static str)>, ToolError> {
+    #[derive(Deserialize)]
+    struct Lockfile {
+        #[serde(default)]
+        package: Vec<LockedPackage>,
+    }
+    #[derive(Deserialize)]
+    struct LockedPackage {
+        name: String,
+        version: String,
+        source: Option<String>,
+        #[serde(default)]
+        dependencies: Vec<String>,
+    }
+
+    let lock: Lockfile = toml::from_str(lockfile)
+        .map_err(|err| ToolError::execution_failed(format!("failed to parse Cargo.lock: {err}")))?;
+    let same = |name: &str| name.replace('-', "_") == krate.replace('-', "_");
+    let locked: Vec<&str> = lock
+        .package
+        .iter()
+        .filter(|pkg| same(&pkg.name))
+        .map(|pkg| pkg.version.as_str())
+        .collect();
+    if locked.is_empty() {
+        return Ok(None);
+    }
+
+    // Workspace members list dependencies as `name` when only one version
+    // is locked and `name version` (plus a source when ambiguous) otherwise.
+    let direct: Vec<&str> = lock
+        .package
+        .iter()
+        .filter(|pkg| pkg.source.is_none())
+        .flat_map(|pkg| pkg.dependencies.iter())
+        .filter_map(|dep| {
+            let mut parts = dep.split_whitespace();
+            let name = parts.next()?;
+            if !same(name) {
+                return None;
+            }
+            Some(parts.next().unwrap_or(locked[0]))
+        })
+        .collect();
+
+    let (candidates, dependency) = if direct.is_empty() {
+        (locked, "transitive")
+    } else {
+        (direct, "direct")
+    };
+    let version = candidates
+        .into_iter()
+        .max_by(|a, b| compare_versions(a, b))
+        .map(str::to_string);
+    Ok(version.map(|version| (version, dependency)))
+}
+
+/// Semver precedence for the numeric core; a pre-release sorts below the
+/// release it precedes. Build metadata is ignored.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let split = |version: &str| {
+        let version = version.split('+').next().unwrap_or(version);
+        let (core, pre) = version.split_once('-').unwrap_or((version, ""));
+        let numbers: Vec<u64> = core.split('.').map(|n| n.parse().unwrap_or(0)).collect();
+        (numbers, pre.to_string())
+    };
+    let (a_core, a_pre) = split(a);
+    let (b_core, b_pre) = split(b);
+    a_core
+        .cmp(&b_core)
+        .then_with(|| match (a_pre.is_empty(), b_pre.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => a_pre.cmp(&b_pre),
+        })
+}
+
+/// Local rustdoc output for `lib`, when `cargo doc` has produced it.
+fn local_doc_root(workspace: &Path, lock_dir: Option<&Path>, lib: &str) -> Option<PathBuf> {
+    let mut targets = Vec::new();
+    if let Some(dir) = std::env::var_os("CARGO_TARGET_DIR") {
+        let dir = PathBuf::from(dir);
+        targets.push(if dir.is_absolute() {
+            dir
+        } else {
+            workspace.join(dir)
+        });
+    }
+    if let Some(lock_dir) = lock_dir {
+        targets.push(lock_dir.join("target"));
+    }
+    targets.push(workspace.join("target"));
+    targets
+        .into_iter()
+        .map(|target| target.join("doc").join(lib))
+        .find(|root| root.join("all.html").is_file())
+}
+
+fn cache_dir(krate: &str, version: &str) -> Option<PathBuf> {
+    dirs::home_dir().map(|home| {
+        home.join(".deepseek")
+            .join("cache")
+            .join("crate_docs")
+            .join(krate)
+            .join(version)
+    })
+}
+
+fn check_network_policy(context: &ToolContext) -> Result<(), ToolError> {
+    let Some(decider) = context.network_policy.as_ref() else {
+        return Ok(());
+    };
+    match decider.evaluate(DOCS_RS_HOST, "crate_docs") {
+        Decision::Allow => Ok(()),
+        Decision::Deny => Err(ToolError::permission_denied(format!(
+            "network call to '{DOCS_RS_HOST}' blocked by network policy; build local docs with `cargo doc` instead"
+        ))),
+        Decision::Prompt => Err(ToolError::permission_denied(format!(
+            "network call to '{DOCS_RS_HOST}' requires approval; \
+             re-run after `/network allow {DOCS_RS_HOST}`, or build local docs with `cargo doc`"
+        ))),
+    }
+}
+
+/// Reads rustdoc pages by path relative to the crate's doc root.
+enum PageReader {
+    Local(PathBuf),
+    DocsRs {
+        krate: String,
+        version: String,
+        lib: String,
+        cache: Option<PathBuf>,
+    },
+}
+
+impl PageReader {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Local(_) => "local",
+            Self::DocsRs { .. } => "docs.rs",
+        }
+    }
+
+    fn location(&self, page: &str, anchor: Option<&str>) -> String {
+        let base = match self {
+            Self::Local(root) => root.join(page).display().to_string(),
+            Self::DocsRs {
+                krate,
+                version,
+                lib,
+                ..
+            } => format!("{DOCS_RS}/{krate}/{version}/{lib}/{page}"),
+        };
+        match anchor {
+            Some(anchor) => format!("{base}#{anchor}"),
+            None => base,
+        }
+    }
+
+    async fn read(&mut self, page: &str) -> Result<String, ToolError> {
+        if page.split('/').any(|part| part == "..") {
+            return Err(ToolError::invalid_input(format!(
+                "invalid docs page `{page}`"
+            )));
+        }
+        match self {
+            Self::Local(root) => fs::read_to_string(root.join(page)).map_err(|err| {
+                ToolError::execution_failed(format!(
+                    "failed to read {}: {err}",
+                    root.join(page).display()
+                ))
+            }),
+            Self::DocsRs { cache, .. } => {
+                let cached = cache.as_ref().map(|dir| dir.join(page));
+                if let Some(html) = cached
+                    .as_ref()
+                    .and_then(|path| fs::read_to_string(path).ok())
+                {
+                    return Ok(html);
+                }
+                let url = self.location(page, None);
+                let html = fetch_page(&url).await?;
+                if let Some(path) = cached {
+                    // Best effort: a failed cache write only costs a refetch.
+                    let _ = path
+                        .parent()
+                        .map_or(Ok(()), fs::create_dir_all)
+                        .and_then(|()| fs::write(&path, &html));
+                }
+                Ok(html)
+            }
+        }
+    }
+}
+
+async fn fetch_page(url: &str) -> Result<String, ToolError> {
+    let client = reqwest::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .user_agent(USER_AGENT)
+        .build()
+        .map_err(|err| {
+            ToolError::execution_failed(format!("failed to build HTTP client: {err}"))
+        })?;
+    let resp =
+        client.get(url).send().await.map_err(|err| {
+            ToolError::execution_failed(format!("request to {url} failed: {err}"))
+        })?;
+    let status = resp.status();
+    if !status.is_success() {
+        return Err(ToolError::execution_failed(format!(
+            "{url} returned HTTP {status}"
+        )));
+    }
+    resp.text()
+        .await
+        .map_err(|err| ToolError::execution_failed(format!("failed to read {url}: {err}")))
+}
+
+/// Strip a leading `crate::` or `<lib>::` and surrounding whitespace.
+fn normalize_item(item: &str, lib: &str) -> String {
+    let item = item.trim().trim_matches('`');
+    let item = item.strip_prefix("crate::").unwrap_or(item);
+    item.strip_prefix(&format!("{lib}::"))
+        .unwrap_or(item)
+        .to_string()
+}
+
+/// `(path, href)` pairs from rustdoc's `all.html`, e.g.
+/// `("sync::Mutex", "sync/struct.Mutex.html")`.
+fn parse_all_items(html: &str) -> Vec<(String, String)> {
+    let mut items = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<a href=\"") {
+        rest = &rest[start + "<a href=\"".len()..];
+        let Some((href, after)) = rest.split_once('"') else {
+            break;
+        };
+        let Some(label) = after
+            .split_once('>')
+            .and_then(|(_, tail)| tail.split_once("</a>"))
+            .map(|(label, _)| label.trim())
+        else {
+            continue;
+        };
+        let is_item_page = href.rsplit('/').next().is_some_and(|file| {
+            file.contains('.') && file.ends_with(".html") && file != "index.html"
+        });
+        if is_item_page && !href.contains("://") && !href.starts_with("../") && !label.contains('<')
+        {
+            items.push((label.to_string(), href.to_string()));
+        }
+    }
+    items
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ItemMatch {
+    Found {
+        href: String,
+        /// Member anchor (`method.lock`) when the query named one.
+        anchor: Option<String>,
+        others: Vec<String>,
+    },
+    /// No item matched but the query is shaped like a module path.
+    Module(String),
+    Missing(Vec<String>),
+}
+
+/// Match `query` against the item listing: exact path, then path suffix,
+/// then case-insensitive name. `Type::member` resolves `Type` and anchors
+/// the member; anything else that looks like a path is tried as a module.
+fn resolve_item(items: &[(String, String)], query: &str) -> ItemMatch {
+    if let Some(found) = match_item(items, query) {
+        return found;
+    }
+    if let Some((parent, member)) = query.rsplit_once("::")
+        && let Some(ItemMatch::Found { href, others, .. }) = match_item(items, parent)
+    {
+        return ItemMatch::Found {
+            href,
+            anchor: Some(member_anchor(member)),
+            others,
+        };
+    }
+    let is_path = !query.is_empty()
+        && query.split("::").all(|part| {
+            !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+    if is_path && query.chars().next().is_some_and(|c| c.is_ascii_lowercase()) {
+        return ItemMatch::Module(format!("{}/index.html", query.replace("::", "/")));
+    }
+    let needle = query
+        .rsplit("::")
+        .next()
+        .unwrap_or(query)
+        .to_ascii_lowercase();
+    let suggestions = items
+        .iter()
+        .filter(|(path, _)| path.to_ascii_lowercase().contains(&needle))
+        .map(|(path, _)| path.clone())
+        .take(MAX_SUGGESTIONS)
+        .collect();
+    ItemMatch::Missing(suggestions)
+}
+
+fn match_item(items: &[(String, String)], query: &str) -> Option<ItemMatch> {
+    let suffix = format!("::{query}");
+    let tiers: [&dyn Fn(&str) -> bool; 3] = [
+        &|path: &str| path == query,
+        &|path: &str| path.ends_with(&suffix),
+        &|path: &str| {
+            path.rsplit("::")
+                .next()
+                .is_some_and(|name| name.eq_ignore_ascii_case(query))
+        },
+    ];
+    for matches in tiers {
+        let mut hits: Vec<&(String, String)> =
+            items.iter().filter(|(path, _)| matches(path)).collect();
+        if hits.is_empty() {
+            continue;
+        }
+        // Shallowest path first: `Mutex` means `sync::Mutex`, not
+        // `sync::mutex::Mutex` re-exported deeper down.
+        hits.sort_by_key(|(path, _)| (path.matches("::").count(), path.clone()));
+        let (_, href) = hits[0];
+        return Some(ItemMatch::Found {
+            href: href.clone(),
+            anchor: None,
+            others: hits
+                .iter()
+                .skip(1)
+                .take(MAX_SUGGESTIONS)
+                .map(|(path, _)| path.clone())
+                .collect(),
+        });
+    }
+    None
+}
+
+/// rustdoc anchor for a member name: methods are `method.x`, associated
+/// constants `associatedconstant.X`, enum variants `variant.X`.
+fn member_anchor(member: &str) -> String {
+    if member.chars().next().is_some_and(char::is_uppercase) {
+        if member
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        {
+            format!("associatedconstant.{member}")
+        } else {
+            format!("variant.{member}")
+        }
+    } else {
+        format!("method.{member}")
+    }
+}
+
+/// Readable text of a rustdoc page: the page title, then `<main>`.
+fn page_text(html: &str) -> String {
+    let title = between(html, "<title>", "</title>")
+        .map(|title| html_to_text(title.trim()))
+        .unwrap_or_default();
+    let main = between(html, "<main", "</main>")
+        .and_then(|main| main.split_once('>'))
+        .map_or(html, |(_, body)| body);
+    let body = html_to_text(main);
+    if title.is_empty() {
+        body
+    } else {
+        format!("{title}\n\n{body}")
+    }
+}
+
+/// Text of one member's signature and docs: from its `id="<anchor>"` to the
+/// next member of any kind. `None` when the page has no such anchor.
+fn member_text(html: &str, anchor: &str) -> Option<String> {
+    let marker = format!("id=\"{anchor}\"");
+    let start = html.find(&marker)?;
+    let start = html[..start].rfind('<').unwrap_or(start);
+    let after = start + marker.len();
+    let end = [
+        "id=\"method.",
+        "id=\"tymethod.",
+        "id=\"variant.",
+        "id=\"associatedconstant.",
+        "id=\"associatedtype.",
+        "id=\"impl-",
+    ]
+    .iter()
+    .filter_map(|next| html[after..].find(next).map(|pos| after + pos))
+    .min()
+    .map_or(html.len(), |end| html[..end].rfind('<').unwrap_or(end));
+    let text = html_to_text(&html[start..end]);
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCKFILE: &str = r#"
+version = 4
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = [
+ "rand 0.8.5",
+ "serde-json",
+]
+
+[[package]]
+name = "rand"
+version = "0.7.3"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "rand"
+version = "0.8.5"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "rand"
+version = "0.9.0-alpha.1"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde-json"
+version = "1.0.9"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "itoa"
+version = "1.0.10"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+    #[test]
+    fn lockfile_prefers_direct_dependency_versions() {
+        assert_eq!(
+            locked_version(LOCKFILE, "rand").unwrap(),
+            Some(("0.8.5".to_string(), "direct"))
+        );
+        assert_eq!(
+            locked_version(LOCKFILE, "serde_json").unwrap(),
+            Some(("1.0.9".to_string(), "direct"))
+        );
+        assert_eq!(
+            locked_version(LOCKFILE, "itoa").unwrap(),
+            Some(("1.0.10".to_string(), "transitive"))
+        );
+        assert_eq!(locked_version(LOCKFILE, "tokio").unwrap(), None);
+        assert_eq!(compare_versions("0.9.0-alpha.1", "0.9.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.10", "1.0.9"), Ordering::Greater);
+    }
+
+    #[test]
+    fn items_resolve_by_path_name_member_and_module() {
+        let listing = r#"<ul class="all-items">
+            <li><a href="sync/struct.Mutex.html">sync::Mutex</a></li>
+            <li><a href="sync/mutex/struct.Mutex.html">sync::mutex::Mutex</a></li>
+            <li><a href="sync/struct.MutexGuard.html">sync::MutexGuard</a></li>
+            <li><a href="fn.spawn.html">spawn</a></li>
+            <li><a href="../std/index.html">std</a></li>
+        </ul>"#;
+        let items = parse_all_items(listing);
+        assert_eq!(items.len(), 4);
+
+        let found = |href: &str, anchor: Option<&str>, others: &[&str]| ItemMatch::Found {
+            href: href.to_string(),
+            anchor: anchor.map(str::to_string),
+            others: others.iter().map(|s| (*s).to_string()).collect(),
+        };
+        assert_eq!(
+            resolve_item(&items, "sync::mutex::Mutex"),
+            found("sync/mutex/struct.Mutex.html", None, &[])
+        );
+        assert_eq!(
+            resolve_item(&items, "Mutex"),
+            found("sync/struct.Mutex.html", None, &["sync::mutex::Mutex"])
+        );
+        assert_eq!(
+            resolve_item(&items, "mutexguard"),
+            found("sync/struct.MutexGuard.html", None, &[])
+        );
+        assert_eq!(
+            resolve_item(&items, "sync::Mutex::lock"),
+            found("sync/struct.Mutex.html", Some("method.lock"), &[])
+        );
+        assert_eq!(
+            resolve_item(&items, "sync"),
+            ItemMatch::Module("sync/index.html".to_string())
+        );
+        assert_eq!(
+            resolve_item(&items, "RwLock"),
+            ItemMatch::Missing(Vec::new())
+        );
+        assert_eq!(
+            normalize_item(" tokio::sync::Mutex ", "tokio"),
+            "sync::Mutex"
+        );
+    }
+
+    #[tokio::test]
+    async fn local_cargo_doc_output_is_read_at_the_locked_version() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join("Cargo.lock"), LOCKFILE).unwrap();
+        let docs = tmp.path().join("target/doc/serde_json");
+        fs::create_dir_all(&docs).unwrap();
+        fs::write(
+            docs.join("all.html"),
+            r#"<a href="enum.Value.html">Value</a>"#,
+        )
+        .unwrap();
+        fs::write(
+            docs.join("enum.Value.html"),
+            r#"<html><title>Value in serde_json - Rust</title><main>
+            <h1>Enum Value</h1><p>Any valid JSON value.</p>
+            <section id="method.as_str" class="method"><h4>pub fn as_str(&amp;self) -&gt; Option&lt;&amp;str&gt;</h4></section>
+            <div class="docblock"><p>Returns the string if this is a String.</p></div>
+            <section id="method.as_i64" class="method"><h4>pub fn as_i64(&amp;self)</h4></section>
+            </main></html>"#,
+        )
+        .unwrap();
+
+        let context = ToolContext::new(tmp.path().to_path_buf());
+        let result = CrateDocsTool
+            .execute(
+                json!({"crate": "serde-json", "item": "Value::as_str"}),
+                &context,
+            )
+            .await
+            .unwrap();
+        let value: Value = serde_json::from_str(&result.content).unwrap();
+        assert_eq!(value["version"], "1.0.9");
+        assert_eq!(value["dependency"], "direct");
+        assert_eq!(value["source"], "local");
+        let content = value["content"].as_str().unwrap();
+        assert!(
+            content.contains("pub fn as_str(&self) -> Option<&str>"),
+            "{content}"
+        );
+        assert!(content.contains("Returns the string"), "{content}");
+        assert!(!content.contains("as_i64"), "{content}");
+
+        let missing = CrateDocsTool
+            .execute(json!({"crate": "serde_json", "item": "Map"}), &context)
+            .await
+            .unwrap();
+        assert!(!missing.success);
+
+        let err = CrateDocsTool
+            .execute(json!({"crate": "tokio"}), &context)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not in"), "{err}");
+    }
+}
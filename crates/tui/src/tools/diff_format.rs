@@ -9,6 +9,9 @@
 //! The diff is also a strict UX upgrade for the model — it sees exactly
 //! which lines changed instead of a one-line summary.
 
+use std::path::Path;
+
+use serde_json::Value;
 use similar::TextDiff;
 
 /// Build a unified diff between `old` and `new` keyed at `path`.
@@ -31,10 +34,64 @@ pub fn make_unified_diff(path: &str, old: &str, new: &str) -> String {
         .to_string()
 }
 
+/// Diff a pending `edit_file`, `write_file`, or `apply_patch` call would
+/// produce, for the approval prompt.
+///
+/// Reads the target under `workspace` without touching it. An `edit_file`
+/// whose `search` text is not found verbatim (a fuzzy match, or a stale
+/// file) falls back to diffing `search` against `replace` on their own.
+#[must_use]
+pub fn preview_tool_diff(workspace: &Path, tool_name: &str, params: &Value) -> Option<String> {
+    let text = |key: &str| params.get(key).and_then(Value::as_str);
+    let read = |path: &str| std::fs::read_to_string(workspace.join(path)).ok();
+    let diff = match tool_name {
+        "edit_file" => {
+            let (path, search, replace) = (text("path")?, text("search")?, text("replace")?);
+            match read(path).filter(|contents| contents.contains(search)) {
+                Some(contents) => {
+                    make_unified_diff(path, &contents, &contents.replacen(search, replace, 1))
+                }
+                None => make_unified_diff(path, search, replace),
+            }
+        }
+        "write_file" => {
+            let path = text("path")?;
+            make_unified_diff(path, &read(path).unwrap_or_default(), text("content")?)
+        }
+        "apply_patch" => text("patch")?.to_string(),
+        _ => return None,
+    };
+    (!diff.trim().is_empty()).then_some(diff)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn previews_pending_edits_against_the_file_on_disk() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        std::fs::write(tmp.path().join("a.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+
+        let edit =
+            serde_json::json!({"path": "a.rs", "search": "fn b() {}", "replace": "fn c() {}"});
+        let diff = preview_tool_diff(tmp.path(), "edit_file", &edit).expect("diff");
+        assert!(diff.contains("@@ -1,2 +1,2 @@"), "{diff}");
+        assert!(
+            diff.contains("-fn b() {}") && diff.contains("+fn c() {}"),
+            "{diff}"
+        );
+
+        let stale = serde_json::json!({"path": "a.rs", "search": "gone\n", "replace": "here\n"});
+        let diff = preview_tool_diff(tmp.path(), "edit_file", &stale).expect("diff");
+        assert!(diff.contains("-gone") && diff.contains("+here"), "{diff}");
+
+        let write = serde_json::json!({"path": "new.rs", "content": "x\n"});
+        let diff = preview_tool_diff(tmp.path(), "write_file", &write).expect("diff");
+        assert!(diff.contains("+x"), "{diff}");
+        assert!(preview_tool_diff(tmp.path(), "exec_shell", &write).is_none());
+    }
+
     #[test]
     fn identical_inputs_emit_empty_diff() {
         let s = "hello\nworld\n";
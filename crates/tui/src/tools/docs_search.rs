@@ -0,0 +1,718 @@
+//! `docs_search` tool — ranked full-text search over project documentation.
+//!
+//! The index covers documentation files at the workspace root (`README.md`,
+//! `CONTRIBUTING.md`, ...), everything under `docs/`, the `docs_dir` named
+//! in `mkdocs.yml`, and rustdoc HTML under `target/doc/`. Each file is split
+//! into heading-level sections so a hit points at the part of the page that
+//! answers the query.
+//!
+//! The index lives at `.deepseek/index/docs.json` in the workspace. The TUI
+//! refreshes it in the background at session start and while idle, and every
+//! call refreshes it again; only files whose mtime or size changed are
+//! re-read, and files that disappeared are dropped.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::UNIX_EPOCH;
+
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::recall_archive::{best_window, round_score, tokenize};
+use super::spec::{
+    ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolInput, ToolResult, ToolSpec,
+    input_schema_for, parse_input,
+};
+use crate::utils::truncate_with_ellipsis;
+
+const INDEX_VERSION: u32 = 1;
+const DEFAULT_MAX_RESULTS: usize = 5;
+const HARD_MAX_RESULTS: usize = 20;
+const EXCERPT_CHARS: usize = 320;
+/// Files larger than this are skipped; generated changelogs and API dumps
+/// drown out the prose the index is for.
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+/// Section text kept per heading, in bytes. Longer sections are truncated.
+const MAX_SECTION_LEN: usize = 6000;
+/// Cap on rustdoc pages; `cargo doc` without `--no-deps` emits thousands.
+const MAX_RUSTDOC_PAGES: usize = 2000;
+/// Heading tokens count this many times when scoring, so a section titled
+/// "Configuration" outranks one that mentions configuration in passing.
+const HEADING_WEIGHT: usize = 3;
+const K1: f64 = 1.5;
+const B: f64 = 0.75;
+
+const DOC_EXTENSIONS: &[&str] = &["md", "markdown", "mdx", "rst", "adoc", "txt"];
+
+/// `docs_search` arguments.
+#[derive(Debug, Deserialize, JsonSchema)]
+struct DocsSearchInput {
+    /// What to look for, in plain words (e.g. `how do I configure MCP servers`)
+    query: String,
+    /// Only search documents whose workspace-relative path starts with this prefix (e.g. `docs/`, `target/doc/`)
+    path_prefix: Option<String>,
+    /// Maximum sections to return (default: 5, max: 20)
+    max_results: Option<u64>,
+}
+
+impl ToolInput for DocsSearchInput {
+    fn examples() -> Vec<Value> {
+        vec![
+            json!({"query": "sandbox configuration"}),
+            json!({"query": "release checklist", "path_prefix": "docs/", "max_results": 3}),
+        ]
+    }
+}
+
+/// Tool implementing `docs_search`.
+pub struct DocsSearchTool;
+
+#[async_trait]
+impl ToolSpec for DocsSearchTool {
+    fn name(&self) -> &'static str {
+        "docs_search"
+    }
+
+    fn description(&self) -> &'static str {
+        "Search the project's documentation (README and other root docs, docs/, the mkdocs docs_dir, and rustdoc output in target/doc) and return the best-matching sections with their path, heading, and line. Prefer this over grep_files for questions about how the project is meant to be used or configured."
+    }
+
+    fn input_schema(&self) -> Value {
+        input_schema_for::<DocsSearchInput>()
+    }
+
+    fn input_examples(&self) -> Vec<Value> {
+        DocsSearchInput::examples()
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::ReadOnly]
+    }
+
+    fn approval_requirement(&self) -> ApprovalRequirement {
+        ApprovalRequirement::Auto
+    }
+
+    fn supports_parallel(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let input: DocsSearchInput = parse_input(&input)?;
+        let query = input.query.trim().to_string();
+        let query_tokens = tokenize(&query);
+        if query_tokens.is_empty() {
+            return Err(ToolError::invalid_input(
+                "query has no searchable words after tokenization",
+            ));
+        }
+        let max_results = input
+            .max_results
+            .map_or(DEFAULT_MAX_RESULTS, |n| n as usize)
+            .clamp(1, HARD_MAX_RESULTS);
+        let prefix = input
+            .path_prefix
+            .map(|prefix| prefix.trim().trim_start_matches("./").to_string())
+            .filter(|prefix| !prefix.is_empty());
+
+        let workspace = context.workspace.clone();
+        let index = tokio::task::spawn_blocking(move || refresh_index(&workspace))
+            .await
+            .map_err(|err| ToolError::execution_failed(format!("Docs index task failed: {err}")))?
+            .map_err(|err| {
+                ToolError::execution_failed(format!("Failed to index documentation: {err}"))
+            })?;
+
+        if index.files.is_empty() {
+            return Ok(ToolResult::success(
+                json!({
+                    "hits": [],
+                    "note": "No documentation found (looked for root README-style files, docs/, the mkdocs docs_dir, and target/doc)."
+                })
+                .to_string(),
+            ));
+        }
+
+        let hits = index.search(&query_tokens, prefix.as_deref(), max_results);
+        Ok(ToolResult::success(
+            json!({
+                "query": query,
+                "files_indexed": index.files.len(),
+                "sections_indexed": index.section_count(),
+                "hits": hits,
+            })
+            .to_string(),
+        ))
+    }
+}
+
+/// Persisted documentation index: workspace-relative path → sections.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DocsIndex {
+    version: u32,
+    files: BTreeMap<String, IndexedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedFile {
+    modified_ms: u64,
+    len: u64,
+    sections: Vec<DocSection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct DocSection {
+    heading: String,
+    /// 1-based line of the heading (1 for rustdoc pages).
+    line: usize,
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct DocsHit {
+    path: String,
+    heading: String,
+    line: usize,
+    score: f64,
+    excerpt: String,
+}
+
+impl DocsIndex {
+    fn section_count(&self) -> usize {
+        self.files.values().map(|file| file.sections.len()).sum()
+    }
+
+    /// BM25 over sections, heading tokens weighted by [`HEADING_WEIGHT`].
+    fn search(&self, query_tokens: &[String], prefix: Option<&str>, limit: usize) -> Vec<DocsHit> {
+        let docs: Vec<(&str, &DocSection, HashMap<&str, usize>, usize)> = self
+            .files
+            .iter()
+            .filter(|(path, _)| prefix.is_none_or(|prefix| path.starts_with(prefix)))
+            .flat_map(|(path, file)| {
+                file.sections
+                    .iter()
+                    .map(move |section| (path.as_str(), section))
+            })
+            .map(|(path, section)| {
+                let body = tokenize(&section.text);
+                let heading = tokenize(&section.heading);
+                let mut counts: HashMap<&str, usize> = HashMap::new();
+                let mut len = body.len();
+                for token in query_tokens {
+                    let in_body = body.iter().filter(|t| *t == token).count();
+                    let in_heading = heading.iter().filter(|t| *t == token).count();
+                    let tf = in_body + in_heading * HEADING_WEIGHT;
+                    if tf > 0 {
+                        counts.insert(token.as_str(), tf);
+                    }
+                }
+                len += heading.len() * HEADING_WEIGHT;
+                (path, section, counts, len)
+            })
+            .collect();
+        if docs.is_empty() {
+            return Vec::new();
+        }
+
+        let n = docs.len() as f64;
+        let avgdl = docs.iter().map(|doc| doc.3 as f64).sum::<f64>() / n;
+        let df: HashMap<&str, f64> = query_tokens
+            .iter()
+            .map(|token| {
+                let count = docs
+                    .iter()
+                    .filter(|doc| doc.2.contains_key(token.as_str()))
+                    .count();
+                (token.as_str(), count as f64)
+            })
+            .collect();
+
+        let mut scored: Vec<(f64, &str, &DocSection)> = docs
+            .iter()
+            .map(|(path, section, counts, len)| {
+                let dl = *len as f64;
+                let score = counts
+                    .iter()
+                    .map(|(token, tf)| {
+                        let tf = *tf as f64;
+                        let df_t = df.get(token).copied().unwrap_or(0.0);
+                        let idf = ((n - df_t + 0.5) / (df_t + 0.5) + 1.0).ln();
+                        let denom = tf + K1 * (1.0 - B + B * (dl / avgdl.max(1.0)));
+                        idf * (tf * (K1 + 1.0)) / denom.max(f64::EPSILON)
+                    })
+                    .sum::<f64>();
+                (score, *path, *section)
+            })
+            .filter(|(score, _, _)| *score > 0.0)
+            .collect();
+        scored.sort_by(|a, b| {
+            b.0.partial_cmp(&a.0)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.1.cmp(b.1))
+                .then_with(|| a.2.line.cmp(&b.2.line))
+        });
+        scored.truncate(limit);
+
+        scored
+            .into_iter()
+            .map(|(score, path, section)| DocsHit {
+                path: path.to_string(),
+                heading: section.heading.clone(),
+                line: section.line,
+                score: round_score(score),
+                excerpt: best_window(&section.text, query_tokens, EXCERPT_CHARS),
+            })
+            .collect()
+    }
+}
+
+/// Where the index for `workspace` is stored.
+#[must_use]
+pub fn index_path(workspace: &Path) -> PathBuf {
+    workspace.join(".deepseek").join("index").join("docs.json")
+}
+
+/// Load the persisted index, re-read documentation files whose mtime or size
+/// changed, drop files that no longer exist, and save the result when
+/// anything changed. An unreadable or outdated index is rebuilt from scratch.
+pub fn refresh_index(workspace: &Path) -> io::Result<DocsIndex> {
+    refresh_index_until(workspace, &AtomicBool::new(false))
+}
+
+/// [`refresh_index`], abandoned without saving once `cancel` is set. The
+/// error is [`io::ErrorKind::Interrupted`].
+pub fn refresh_index_until(workspace: &Path, cancel: &AtomicBool) -> io::Result<DocsIndex> {
+    let path = index_path(workspace);
+    let mut index = fs::read(&path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<DocsIndex>(&bytes).ok())
+        .filter(|index| index.version == INDEX_VERSION)
+        .unwrap_or_default();
+    let had_index = path.exists() && index.version == INDEX_VERSION;
+    index.version = INDEX_VERSION;
+
+    let mut changed = false;
+    let mut seen = BTreeMap::new();
+    for file in discover_doc_files(workspace) {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "docs index refresh cancelled",
+            ));
+        }
+        let Ok(meta) = fs::metadata(&file) else {
+            continue;
+        };
+        if meta.len() > MAX_FILE_BYTES {
+            continue;
+        }
+        let Some(rel) = relative_key(workspace, &file) else {
+            continue;
+        };
+        let modified_ms = meta
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |age| age.as_millis() as u64);
+        if let Some(existing) = index.files.remove(&rel)
+            && existing.modified_ms == modified_ms
+            && existing.len == meta.len()
+        {
+            seen.insert(rel, existing);
+            continue;
+        }
+        let Ok(raw) = fs::read(&file) else {
+            continue;
+        };
+        let text = String::from_utf8_lossy(&raw);
+        let sections = if is_html(&file) {
+            rustdoc_sections(&text)
+        } else {
+            text_sections(&text)
+        };
+        changed = true;
+        seen.insert(
+            rel,
+            IndexedFile {
+                modified_ms,
+                len: meta.len(),
+                sections,
+            },
+        );
+    }
+    // Anything left in the old map was deleted or moved out of scope.
+    changed |= !index.files.is_empty();
+    index.files = seen;
+
+    if changed || (!had_index && !index.files.is_empty()) {
+        save_index(&path, &index)?;
+    }
+    Ok(index)
+}
+
+fn save_index(path: &Path, index: &DocsIndex) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // Write-then-rename so a concurrent refresh never reads a torn file.
+    let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    fs::write(&tmp, serde_json::to_vec(index).map_err(io::Error::other)?)?;
+    fs::rename(&tmp, path)
+}
+
+fn relative_key(workspace: &Path, file: &Path) -> Option<String> {
+    let rel = file.strip_prefix(workspace).ok()?;
+    Some(
+        rel.components()
+            .map(|part| part.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+/// Documentation files in index order: root docs, `docs/`, the mkdocs
+/// `docs_dir`, then rustdoc pages.
+fn discover_doc_files(workspace: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    if let Ok(entries) = fs::read_dir(workspace) {
+        let mut root: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && is_root_doc(path))
+            .collect();
+        root.sort();
+        files.extend(root);
+    }
+
+    let mut doc_dirs = vec![workspace.join("docs")];
+    if let Some(dir) = mkdocs_docs_dir(workspace) {
+        let dir = workspace.join(dir);
+        if !doc_dirs.contains(&dir) {
+            doc_dirs.push(dir);
+        }
+    }
+    for dir in doc_dirs {
+        let mut found = Vec::new();
+        walk_files(&dir, &mut found, &|path| has_doc_extension(path));
+        found.sort();
+        files.extend(found);
+    }
+
+    let mut rustdoc = Vec::new();
+    let doc_root = workspace.join("target").join("doc");
+    if let Ok(crates) = fs::read_dir(&doc_root) {
+        let mut crate_dirs: Vec<PathBuf> = crates
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.join("index.html").is_file())
+            .collect();
+        crate_dirs.sort();
+        for dir in crate_dirs {
+            walk_files(&dir, &mut rustdoc, &is_rustdoc_page);
+        }
+    }
+    rustdoc.sort();
+    rustdoc.truncate(MAX_RUSTDOC_PAGES);
+    files.extend(rustdoc);
+
+    let mut unique = std::collections::HashSet::new();
+    files.retain(|path| unique.insert(path.clone()));
+    files
+}
+
+fn walk_files(dir: &Path, out: &mut Vec<PathBuf>, keep: &dyn Fn(&Path) -> bool) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if hidden || file_type.is_symlink() {
+            continue;
+        }
+        if file_type.is_dir() {
+            walk_files(&path, out, keep);
+        } else if keep(&path) {
+            out.push(path);
+        }
+    }
+}
+
+fn has_doc_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| DOC_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// Root files count when they are markup (`*.md`, `*.rst`, ...) or an
+/// extensionless README-style file. Plain `*.txt` at the root is usually
+/// something like `requirements.txt`, so it is left out.
+fn is_root_doc(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let upper = name.to_ascii_uppercase();
+    if upper.starts_with("README") {
+        return true;
+    }
+    has_doc_extension(path) && !upper.ends_with(".TXT")
+}
+
+fn is_html(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "html")
+}
+
+/// Item and module pages; skips rustdoc's index, settings, and help views.
+/// Rendered source lives under `target/doc/src/`, which is never walked.
+fn is_rustdoc_page(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    is_html(path)
+        && !matches!(name, "all.html" | "settings.html" | "help.html")
+        && !name.starts_with("sidebar-")
+}
+
+/// `docs_dir:` from `mkdocs.yml` / `mkdocs.yaml`, when it names something
+/// other than the default `docs`.
+fn mkdocs_docs_dir(workspace: &Path) -> Option<String> {
+    let raw = ["mkdocs.yml", "mkdocs.yaml"]
+        .iter()
+        .find_map(|name| fs::read_to_string(workspace.join(name)).ok())?;
+    raw.lines().find_map(|line| {
+        let value = line.strip_prefix("docs_dir:")?;
+        let value = value.split('#').next()?.trim().trim_matches(['"', '\'']);
+        (!value.is_empty() && !value.contains("..")).then(|| value.to_string())
+    })
+}
+
+/// Split Markdown, reStructuredText, or AsciiDoc into sections at each
+/// heading. Text before the first heading is its own section with an empty
+/// heading. Headings inside fenced code blocks are ignored.
+fn text_sections(text: &str) -> Vec<DocSection> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut sections = Vec::new();
+    let mut heading = String::new();
+    let mut start_line = 1;
+    let mut body = String::new();
+    let mut in_fence = false;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        let next_heading = if in_fence {
+            None
+        } else if let Some(title) = atx_heading(line) {
+            Some((title, 1))
+        } else {
+            lines
+                .get(i + 1)
+                .filter(|next| is_underline(next, line))
+                .map(|_| (line.trim().to_string(), 2))
+        };
+        if let Some((title, consumed)) = next_heading {
+            push_section(&mut sections, &heading, start_line, &body);
+            heading = title;
+            start_line = i + 1;
+            body.clear();
+            i += consumed;
+            continue;
+        }
+        body.push_str(line);
+        body.push('\n');
+        i += 1;
+    }
+    push_section(&mut sections, &heading, start_line, &body);
+    sections
+}
+
+/// `# Title`, `## Title ##`, or AsciiDoc `== Title`.
+fn atx_heading(line: &str) -> Option<String> {
+    let marker = line.chars().next().filter(|c| matches!(c, '#' | '='))?;
+    let level = line.chars().take_while(|c| *c == marker).count();
+    let rest = &line[level..];
+    if level > 6 || !rest.starts_with(' ') {
+        return None;
+    }
+    let title = rest.trim().trim_end_matches(marker).trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Setext / reStructuredText underline (`=====`, `-----`, `~~~~`) at least as
+/// long as the title above it.
+fn is_underline(line: &str, title: &str) -> bool {
+    let line = line.trim_end();
+    let title = title.trim();
+    let Some(marker) = line.chars().next() else {
+        return false;
+    };
+    !title.is_empty()
+        && matches!(marker, '=' | '-' | '~' | '^' | '*')
+        && line.chars().all(|c| c == marker)
+        && line.chars().count() >= title.chars().count().min(3)
+        && !title.starts_with(['-', '*', '|', '>'])
+}
+
+fn push_section(sections: &mut Vec<DocSection>, heading: &str, line: usize, body: &str) {
+    let text = body.trim();
+    if heading.is_empty() && text.is_empty() {
+        return;
+    }
+    sections.push(DocSection {
+        heading: heading.to_string(),
+        line,
+        text: truncate_with_ellipsis(text, MAX_SECTION_LEN, "…"),
+    });
+}
+
+/// A rustdoc page becomes one section: the `<title>` as heading and the
+/// visible text of `<main>` (or the whole body) as text.
+fn rustdoc_sections(html: &str) -> Vec<DocSection> {
+    let title = between(html, "<title>", "</title>")
+        .map(|title| decode_entities(title.trim()))
+        .unwrap_or_default();
+    let main = between(html, "<main", "</main>")
+        .and_then(|main| main.split_once('>'))
+        .map_or(html, |(_, body)| body);
+    let text = html_to_text(main);
+    if text.trim().is_empty() {
+        return Vec::new();
+    }
+    vec![DocSection {
+        heading: title,
+        line: 1,
+        text: truncate_with_ellipsis(text.trim(), MAX_SECTION_LEN, "…"),
+    }]
+}
+
+pub(super) fn between<'a>(haystack: &'a str, open: &str, close: &str) -> Option<&'a str> {
+    let start = haystack.find(open)? + open.len();
+    let end = haystack[start..].find(close)? + start;
+    Some(&haystack[start..end])
+}
+
+/// Strip tags, `<script>`/`<style>` bodies, and collapse whitespace.
+pub(super) fn html_to_text(html: &str) -> String {
+    let mut out = String::with_capacity(html.len() / 2);
+    let mut rest = html;
+    while let Some(open) = rest.find('<') {
+        out.push_str(&rest[..open]);
+        out.push(' ');
+        let tag = &rest[open..];
+        let skip_to = ["script", "style"].iter().find_map(|name| {
+            tag.get(1..=name.len())
+                .filter(|candidate| candidate.eq_ignore_ascii_case(name))
+                .and_then(|_| {
+                    tag.find(&format!("</{name}>"))
+                        .map(|end| end + name.len() + 3)
+                })
+        });
+        let consumed = skip_to.or_else(|| tag.find('>').map(|end| end + 1));
+        match consumed {
+            Some(len) => rest = &tag[len..],
+            None => {
+                rest = "";
+                break;
+            }
+        }
+    }
+    out.push_str(rest);
+    decode_entities(&out.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn sections_split_on_markdown_and_underline_headings_outside_fences() {
+        let sections = text_sections(
+            "Intro line.\n\n# Install\nRun cargo install.\n```sh\n# not a heading\n```\nUsage\n-----\nCall it.\n",
+        );
+        let headings: Vec<(&str, usize)> = sections
+            .iter()
+            .map(|section| (section.heading.as_str(), section.line))
+            .collect();
+        assert_eq!(headings, vec![("", 1), ("Install", 3), ("Usage", 8)]);
+        assert!(sections[1].text.contains("# not a heading"));
+    }
+
+    #[test]
+    fn rustdoc_pages_index_main_text_without_scripts() {
+        let sections = rustdoc_sections(
+            "<html><head><title>Config in my_crate - Rust</title></head><body><script>var x = 1;</script><main><h1>Struct Config</h1><p>Loads &amp; merges settings.</p></main></body></html>",
+        );
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].heading, "Config in my_crate - Rust");
+        assert_eq!(sections[0].text, "Struct Config Loads & merges settings.");
+    }
+
+    #[test]
+    fn refresh_persists_index_and_picks_up_changed_and_deleted_files() {
+        let tmp = tempdir().expect("tempdir");
+        let root = tmp.path();
+        fs::write(root.join("README.md"), "# Overview\nA terminal agent.\n").unwrap();
+        fs::create_dir_all(root.join("guide")).unwrap();
+        fs::write(
+            root.join("mkdocs.yml"),
+            "site_name: demo\ndocs_dir: guide # custom\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("guide/sandbox.md"),
+            "# Sandbox\nThe sandbox limits writes to the workspace.\n",
+        )
+        .unwrap();
+        fs::write(root.join("requirements.txt"), "sandbox==1.0\n").unwrap();
+
+        let index = refresh_index(root).expect("index");
+        assert!(index_path(root).is_file());
+        assert_eq!(
+            index.files.keys().cloned().collect::<Vec<_>>(),
+            vec!["README.md".to_string(), "guide/sandbox.md".to_string()]
+        );
+        let tokens = tokenize("sandbox writes");
+        let hits = index.search(&tokens, None, 5);
+        assert_eq!(hits[0].path, "guide/sandbox.md");
+        assert_eq!(hits[0].heading, "Sandbox");
+        assert!(index.search(&tokens, Some("docs/"), 5).is_empty());
+
+        fs::remove_file(root.join("guide/sandbox.md")).unwrap();
+        fs::write(
+            root.join("README.md"),
+            "# Overview\nA terminal agent.\n\n## Sandbox\nWrites stay inside the workspace, longer now.\n",
+        )
+        .unwrap();
+        let cancelled = refresh_index_until(root, &AtomicBool::new(true)).unwrap_err();
+        assert_eq!(cancelled.kind(), io::ErrorKind::Interrupted);
+        let reloaded = refresh_index(root).expect("refresh");
+        assert_eq!(reloaded.files.len(), 1);
+        let hits = reloaded.search(&tokens, None, 5);
+        assert_eq!((hits[0].path.as_str(), hits[0].line), ("README.md", 4));
+
+        let persisted: DocsIndex =
+            serde_json::from_slice(&fs::read(index_path(root)).unwrap()).unwrap();
+        assert_eq!(persisted.files.len(), 1);
+    }
+}
@@ -0,0 +1,451 @@
+//! Syntax check run by `write_file`, `edit_file`, and `apply_patch` before
+//! anything touches disk.
+//!
+//! The checker is picked from the file extension: JSON and TOML parse
+//! in-process, Rust goes through `rustfmt`, Python through `ast.parse`, and
+//! shell scripts through `bash -n`. `[edit_validation.commands]` adds or
+//! replaces checkers per extension. When the proposed contents fail to parse
+//! the tool returns the checker's errors and writes nothing.
+//!
+//! A check never blocks on its own infrastructure: a missing checker binary or
+//! a timeout lets the write through. Neither does a file whose current
+//! contents already fail the same check, so a broken file can still be
+//! repaired one edit at a time.
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use serde::Deserialize;
+use wait_timeout::ChildExt;
+
+use super::spec::{ToolContext, ToolError};
+
+const DEFAULT_TIMEOUT_MS: u64 = 5_000;
+/// Cap on checker output quoted back to the model.
+const MAX_ERROR_CHARS: usize = 2_000;
+
+/// Prints `path:line:col: message` for the first syntax error on stdin.
+const PYTHON_CHECK: &str = "import ast, sys
+try:
+    ast.parse(sys.stdin.read(), sys.argv[1])
+except SyntaxError as e:
+    print(f'{sys.argv[1]}:{e.lineno}:{e.offset}: {e.msg}', file=sys.stderr)
+    sys.exit(1)
+";
+
+/// `[edit_validation]` table.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EditValidationConfig {
+    /// Check syntax before writing (default: true).
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// Per-check timeout in milliseconds (default: 5000). A check that runs
+    /// longer is abandoned and the write goes ahead.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// File extension → checker command, run through the shell. `{file}` is
+    /// replaced with a temporary copy of the proposed contents; without it the
+    /// contents arrive on stdin. A non-zero exit rejects the write. An empty
+    /// command turns off the built-in check for that extension.
+    #[serde(default)]
+    pub commands: BTreeMap<String, String>,
+}
+
+impl EditValidationConfig {
+    fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS))
+    }
+
+    fn checker_for(&self, path: &Path) -> Option<Checker> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        if let Some(command) = self.commands.get(&ext) {
+            let command = command.trim();
+            return (!command.is_empty()).then(|| Checker::Command(command.to_string()));
+        }
+        match ext.as_str() {
+            "json" => Some(Checker::Json),
+            "toml" => Some(Checker::Toml),
+            "rs" => Some(Checker::Rust),
+            "py" | "pyi" => Some(Checker::Python),
+            "sh" | "bash" => Some(Checker::Shell),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Checker {
+    Json,
+    Toml,
+    Rust,
+    Python,
+    Shell,
+    Command(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Verdict {
+    Valid,
+    Invalid(String),
+    /// The checker could not give an answer (missing binary, timeout).
+    Unknown,
+}
+
+impl Checker {
+    fn label(&self) -> &str {
+        match self {
+            Self::Json => "JSON parse",
+            Self::Toml => "TOML parse",
+            Self::Rust => "rustfmt",
+            Self::Python => "python3 ast.parse",
+            Self::Shell => "bash -n",
+            Self::Command(command) => command,
+        }
+    }
+
+    fn check(&self, display: &str, contents: &str, timeout: Duration) -> Verdict {
+        match self {
+            Self::Json => match serde_json::from_str::<serde_json::Value>(contents) {
+                Ok(_) => Verdict::Valid,
+                Err(err) => Verdict::Invalid(format!("{display}: {err}")),
+            },
+            Self::Toml => match toml::from_str::<toml::Value>(contents) {
+                Ok(_) => Verdict::Valid,
+                Err(err) => Verdict::Invalid(format!("{display}: {err}")),
+            },
+            Self::Rust => {
+                let mut cmd = Command::new("rustfmt");
+                cmd.args(["--edition", "2024", "--emit", "stdout"]);
+                run_checker(cmd, Some(contents), timeout, &[("<stdin>", display)])
+            }
+            Self::Python => {
+                let mut cmd = Command::new("python3");
+                cmd.arg("-c").arg(PYTHON_CHECK).arg(display);
+                run_checker(cmd, Some(contents), timeout, &[])
+            }
+            Self::Shell => {
+                let mut cmd = Command::new("bash");
+                cmd.arg("-n");
+                run_checker(cmd, Some(contents), timeout, &[])
+            }
+            Self::Command(command) => run_custom_checker(command, display, contents, timeout),
+        }
+    }
+}
+
+/// Check the files a tool is about to write. Each entry is the target path,
+/// its current contents (`None` for a new file), and the proposed contents.
+/// Returns an error naming every file that would not parse.
+pub async fn validate_writes(
+    context: &ToolContext,
+    writes: &[(&Path, Option<&str>, &str)],
+) -> Result<(), ToolError> {
+    let config = &context.edit_validation;
+    if !config.enabled() {
+        return Ok(());
+    }
+    let jobs: Vec<(String, Checker, Option<String>, String)> = writes
+        .iter()
+        .filter_map(|(path, original, contents)| {
+            let checker = config.checker_for(path)?;
+            Some((
+                display_path(&context.workspace, path),
+                checker,
+                original.map(str::to_string),
+                (*contents).to_string(),
+            ))
+        })
+        .collect();
+    if jobs.is_empty() {
+        return Ok(());
+    }
+
+    let timeout = config.timeout();
+    let failures = tokio::task::spawn_blocking(move || {
+        jobs.into_iter()
+            .filter_map(|(display, checker, original, contents)| {
+                let Verdict::Invalid(errors) = checker.check(&display, &contents, timeout) else {
+                    return None;
+                };
+                let already_broken = original.is_some_and(|original| {
+                    matches!(
+                        checker.check(&display, &original, timeout),
+                        Verdict::Invalid(_)
+                    )
+                });
+                (!already_broken).then(|| {
+                    format!(
+                        "{display} would not parse ({}):\n{}",
+                        checker.label(),
+                        truncate_errors(&errors)
+                    )
+                })
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|err| ToolError::execution_failed(format!("edit validation failed: {err}")))?;
+
+    if failures.is_empty() {
+        return Ok(());
+    }
+    Err(ToolError::execution_failed(format!(
+        "Not written: the change leaves a syntax error, so no files were modified.\n\n{}\n\n\
+         Fix the syntax and retry. If the file only parses once several edits land, make them in \
+         one apply_patch or write_file call.",
+        failures.join("\n\n")
+    )))
+}
+
+pub(super) fn display_path(workspace: &Path, path: &Path) -> String {
+    path.strip_prefix(workspace)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn truncate_errors(errors: &str) -> String {
+    let errors = errors.trim();
+    if errors.chars().count() <= MAX_ERROR_CHARS {
+        return errors.to_string();
+    }
+    let kept: String = errors.chars().take(MAX_ERROR_CHARS).collect();
+    format!("{kept}\n[... checker output truncated]")
+}
+
+fn run_custom_checker(command: &str, display: &str, contents: &str, timeout: Duration) -> Verdict {
+    if !command.contains("{file}") {
+        return run_checker(shell_command(command), Some(contents), timeout, &[]);
+    }
+    // Keep the original file name so extension-driven tools behave the same.
+    let file_name = Path::new(display)
+        .file_name()
+        .map_or_else(|| PathBuf::from("checked"), PathBuf::from);
+    let Ok(dir) = tempfile::tempdir() else {
+        return Verdict::Unknown;
+    };
+    let temp_path = dir.path().join(file_name);
+    if std::fs::write(&temp_path, contents).is_err() {
+        return Verdict::Unknown;
+    }
+    let temp_display = temp_path.display().to_string();
+    let command = command.replace("{file}", &temp_display);
+    run_checker(
+        shell_command(&command),
+        None,
+        timeout,
+        &[(temp_display.as_str(), display)],
+    )
+}
+
+fn shell_command(command: &str) -> Command {
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    }
+    #[cfg(not(windows))]
+    {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+}
+
+/// Run a checker process; a non-zero exit is a rejection and its output the
+/// reason. `rewrites` maps placeholder names in the output (`<stdin>`, a temp
+/// path) back to the workspace path.
+fn run_checker(
+    mut cmd: Command,
+    stdin: Option<&str>,
+    timeout: Duration,
+    rewrites: &[(&str, &str)],
+) -> Verdict {
+    cmd.stdin(if stdin.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    })
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped());
+    let Ok(mut child) = cmd.spawn() else {
+        return Verdict::Unknown;
+    };
+
+    // Feed and drain on threads so a chatty checker cannot fill a pipe and
+    // stall until the timeout.
+    let writer = stdin.zip(child.stdin.take()).map(|(input, mut pipe)| {
+        let input = input.to_string();
+        std::thread::spawn(move || {
+            let _ = pipe.write_all(input.as_bytes());
+        })
+    });
+    let stdout = child.stdout.take().map(drain_pipe);
+    let stderr = child.stderr.take().map(drain_pipe);
+
+    let status = match child.wait_timeout(timeout) {
+        Ok(Some(status)) => status,
+        Ok(None) | Err(_) => {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Verdict::Unknown;
+        }
+    };
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+    if status.success() {
+        return Verdict::Valid;
+    }
+    // The shell's "command not found": the checker itself is missing.
+    if status.code() == Some(127) {
+        return Verdict::Unknown;
+    }
+
+    let collect = |handle: Option<std::thread::JoinHandle<String>>| {
+        handle
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or_default()
+    };
+    let stderr = collect(stderr);
+    // rustfmt echoes the input on stdout; only read stdout when stderr is
+    // silent.
+    let mut output = if stderr.trim().is_empty() {
+        collect(stdout)
+    } else {
+        stderr
+    };
+    if output.trim().is_empty() {
+        output = match status.code() {
+            Some(code) => format!("checker exited with status {code}"),
+            None => "checker was terminated by a signal".to_string(),
+        };
+    }
+    for (from, to) in rewrites {
+        output = output.replace(from, to);
+    }
+    Verdict::Invalid(output)
+}
+
+fn drain_pipe(mut pipe: impl Read + Send + 'static) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        String::from_utf8_lossy(&buf).into_owned()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context_in(dir: &Path) -> ToolContext {
+        ToolContext::new(dir.to_path_buf())
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_json_and_toml_with_parser_errors() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let ctx = context_in(tmp.path());
+        let json_path = tmp.path().join("data.json");
+        let toml_path = tmp.path().join("Cargo.toml");
+
+        let err = validate_writes(
+            &ctx,
+            &[
+                (json_path.as_path(), None, "{\"a\": 1,}"),
+                (toml_path.as_path(), None, "[package\nname = 1"),
+            ],
+        )
+        .await
+        .expect_err("invalid data is rejected");
+        let message = err.to_string();
+        assert!(message.contains("data.json would not parse"), "{message}");
+        assert!(message.contains("Cargo.toml would not parse"), "{message}");
+
+        validate_writes(&ctx, &[(json_path.as_path(), None, "{\"a\": 1}")])
+            .await
+            .expect("valid JSON passes");
+    }
+
+    #[tokio::test]
+    async fn allows_edits_to_files_that_were_already_broken() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let ctx = context_in(tmp.path());
+        let path = tmp.path().join("data.json");
+
+        validate_writes(&ctx, &[(path.as_path(), Some("{oops"), "{still: oops")])
+            .await
+            .expect("previously invalid file may stay invalid");
+        assert!(
+            validate_writes(&ctx, &[(path.as_path(), Some("{}"), "{oops")])
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn disabled_config_and_unknown_extensions_skip_checks() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let mut ctx = context_in(tmp.path());
+        let notes = tmp.path().join("notes.md");
+        validate_writes(&ctx, &[(notes.as_path(), None, "{")])
+            .await
+            .expect("no checker for markdown");
+
+        ctx.edit_validation.enabled = Some(false);
+        let json_path = tmp.path().join("data.json");
+        validate_writes(&ctx, &[(json_path.as_path(), None, "{")])
+            .await
+            .expect("validation disabled");
+    }
+
+    #[test]
+    fn configured_commands_override_and_disable_builtins() {
+        let mut config = EditValidationConfig::default();
+        config.commands.insert("rs".to_string(), String::new());
+        config
+            .commands
+            .insert("ts".to_string(), "tsc --noEmit {file}".to_string());
+
+        assert_eq!(config.checker_for(Path::new("src/lib.rs")), None);
+        assert_eq!(
+            config.checker_for(Path::new("web/app.TS")),
+            Some(Checker::Command("tsc --noEmit {file}".to_string()))
+        );
+        assert_eq!(
+            config.checker_for(Path::new("pyproject.toml")),
+            Some(Checker::Toml)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn custom_command_sees_temp_copy_and_reports_workspace_path() {
+        let verdict =
+            Checker::Command("grep -q ok {file} || { echo \"bad: {file}\"; exit 1; }".into())
+                .check("src/app.ts", "nope", Duration::from_secs(5));
+        assert_eq!(verdict, Verdict::Invalid("bad: src/app.ts\n".to_string()));
+
+        let verdict = Checker::Command("grep -q ok".into()).check(
+            "src/app.ts",
+            "ok\n",
+            Duration::from_secs(5),
+        );
+        assert_eq!(verdict, Verdict::Valid);
+
+        let missing = Checker::Command("definitely-not-a-checker {file}".into());
+        assert_eq!(
+            missing.check("src/app.ts", "", Duration::from_secs(5)),
+            Verdict::Unknown
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Exec profiles: run `exec_shell` commands through a wrapper such as
+//! `docker exec dev` or `ssh buildbox`.
+//!
+//! Profiles are declared under `[exec_profiles.<name>]` and switched with
+//! `/profile use <name>`. File tools keep editing the host tree (typically a
+//! bind mount into the container); only shell commands are wrapped. The
+//! active profile is shared between the TUI and the engine's tool context,
+//! so a switch applies to the next shell call without restarting the engine.
+
+use std::sync::{Arc, Mutex};
+
+use crate::config::ExecProfileConfig;
+
+/// A resolved, ready-to-use exec profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecProfile {
+    pub name: String,
+    pub command_prefix: Vec<String>,
+    pub join_command: bool,
+}
+
+impl ExecProfile {
+    /// Build a profile from its config table.
+    pub fn from_config(name: &str, config: &ExecProfileConfig) -> Result<Self, String> {
+        let command_prefix: Vec<String> = config
+            .command_prefix
+            .iter()
+            .map(|arg| arg.trim().to_string())
+            .filter(|arg| !arg.is_empty())
+            .collect();
+        let Some(program) = command_prefix.first() else {
+            return Err(format!("exec profile '{name}' has an empty command_prefix"));
+        };
+        let is_ssh = std::path::Path::new(program)
+            .file_name()
+            .and_then(|file| file.to_str())
+            .is_some_and(|file| file == "ssh");
+        Ok(Self {
+            name: name.to_string(),
+            join_command: config.join_command.unwrap_or(is_ssh),
+            command_prefix,
+        })
+    }
+
+    /// The command line the host shell runs in place of `command`:
+    /// `<prefix…> sh -c '<command>'`. `None` when an argument cannot be
+    /// quoted (embedded NUL).
+    #[must_use]
+    pub fn wrap(&self, command: &str) -> Option<String> {
+        let inner = ["sh", "-c", command];
+        let tail = if self.join_command {
+            vec![quote(&shlex::try_join(inner).ok()?)?]
+        } else {
+            inner.iter().map(|arg| quote(arg)).collect::<Option<_>>()?
+        };
+        let mut parts = self
+            .command_prefix
+            .iter()
+            .map(|arg| quote(arg))
+            .collect::<Option<Vec<_>>>()?;
+        parts.extend(tail);
+        Some(parts.join(" "))
+    }
+
+    /// Prefix as the user would type it, for status lines.
+    #[must_use]
+    pub fn display_prefix(&self) -> String {
+        shlex::try_join(self.command_prefix.iter().map(String::as_str))
+            .unwrap_or_else(|_| self.command_prefix.join(" "))
+    }
+}
+
+fn quote(arg: &str) -> Option<String> {
+    shlex::try_quote(arg).ok().map(|quoted| quoted.into_owned())
+}
+
+/// The currently selected exec profile, if any.
+#[derive(Debug, Default)]
+pub struct ActiveExecProfile {
+    profile: Mutex<Option<ExecProfile>>,
+}
+
+impl ActiveExecProfile {
+    #[must_use]
+    pub fn get(&self) -> Option<ExecProfile> {
+        self.profile.lock().ok()?.clone()
+    }
+
+    /// Select `profile`, or return to plain host execution with `None`.
+    pub fn set(&self, profile: Option<ExecProfile>) {
+        if let Ok(mut active) = self.profile.lock() {
+            *active = profile;
+        }
+    }
+}
+
+pub type SharedExecProfile = Arc<ActiveExecProfile>;
+
+/// Create a shared handle with no profile selected.
+#[must_use]
+pub fn new_shared_exec_profile() -> SharedExecProfile {
+    Arc::new(ActiveExecProfile::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(prefix: &[&str], join_command: Option<bool>) -> ExecProfile {
+        ExecProfile::from_config(
+            "test",
+            &ExecProfileConfig {
+                command_prefix: prefix.iter().map(|arg| (*arg).to_string()).collect(),
+                join_command,
+            },
+        )
+        .expect("profile")
+    }
+
+    #[test]
+    fn wrap_quotes_the_command_for_the_wrapper() {
+        let docker = profile(&["docker", "exec", "-w", "/work dir", "dev"], None);
+        assert!(!docker.join_command);
+        let wrapped = docker.wrap("cargo test && echo 'ok'").expect("wrap");
+        let argv = shlex::split(&wrapped).expect("split");
+        assert_eq!(
+            argv,
+            vec![
+                "docker",
+                "exec",
+                "-w",
+                "/work dir",
+                "dev",
+                "sh",
+                "-c",
+                "cargo test && echo 'ok'"
+            ]
+        );
+
+        // ssh re-parses its arguments remotely, so the whole `sh -c …` is
+        // passed as one word.
+        let ssh = profile(&["/usr/bin/ssh", "buildbox"], None);
+        assert!(ssh.join_command);
+        let wrapped = ssh.wrap("ls -la").expect("wrap");
+        let argv = shlex::split(&wrapped).expect("split");
+        assert_eq!(argv, vec!["/usr/bin/ssh", "buildbox", "sh -c 'ls -la'"]);
+    }
+
+    #[test]
+    fn empty_prefix_is_rejected() {
+        let err = ExecProfile::from_config(
+            "broken",
+            &ExecProfileConfig {
+                command_prefix: vec![" ".to_string()],
+                join_command: None,
+            },
+        )
+        .unwrap_err();
+        assert!(err.contains("broken"), "{err}");
+    }
+}
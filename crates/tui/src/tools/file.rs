@@ -4,11 +4,15 @@
 //! with path validation to prevent escaping the workspace boundary.
 
 use super::diff_format::make_unified_diff;
+use super::edit_validation::{display_path, validate_writes};
 use super::spec::{
-    ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolResult, ToolSpec,
-    lsp_diagnostics_for_paths, optional_bool, optional_str, required_str,
+    ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolInput, ToolResult, ToolSpec,
+    input_schema_for, lsp_diagnostics_for_paths, parse_input,
 };
+use super::write_audit::{FileWriteAudit, WriteAudit};
 use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::Deserialize;
 use serde_json::{Value, json};
 use std::fs;
 use std::path::Path;
@@ -19,6 +23,29 @@ use std::process::{Command, Stdio};
 /// Tool for reading UTF-8 files from the workspace.
 pub struct ReadFileTool;
 
+/// `read_file` arguments.
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ReadFileInput {
+    /// Path to the file (relative to workspace or absolute)
+    path: String,
+    /// Starting line (1-based, default 1)
+    start_line: Option<u64>,
+    /// Maximum lines to return (default 200, max 500)
+    max_lines: Option<u64>,
+    /// PDF only: page range to extract, e.g. "1-5" or "10". Ignored for non-PDF files.
+    pages: Option<String>,
+}
+
+impl ToolInput for ReadFileInput {
+    fn examples() -> Vec<Value> {
+        vec![
+            json!({"path": "src/main.rs"}),
+            json!({"path": "src/main.rs", "start_line": 200, "max_lines": 100}),
+            json!({"path": "docs/spec.pdf", "pages": "1-5"}),
+        ]
+    }
+}
+
 #[async_trait]
 impl ToolSpec for ReadFileTool {
     fn name(&self) -> &'static str {
@@ -30,28 +57,11 @@ impl ToolSpec for ReadFileTool {
     }
 
     fn input_schema(&self) -> Value {
-        json!({
-            "type": "object",
-            "properties": {
-                "path": {
-                    "type": "string",
-                    "description": "Path to the file (relative to workspace or absolute)"
-                },
-                "start_line": {
-                    "type": "integer",
-                    "description": "Starting line (1-based, default 1)"
-                },
-                "max_lines": {
-                    "type": "integer",
-                    "description": "Maximum lines to return (default 200, max 500)"
-                },
-                "pages": {
-                    "type": "string",
-                    "description": "PDF only: page range to extract, e.g. \"1-5\" or \"10\". Ignored for non-PDF files."
-                }
-            },
-            "required": ["path"]
-        })
+        input_schema_for::<ReadFileInput>()
+    }
+
+    fn input_examples(&self) -> Vec<Value> {
+        ReadFileInput::examples()
     }
 
     fn capabilities(&self) -> Vec<ToolCapability> {
@@ -77,9 +87,10 @@ impl ToolSpec for ReadFileTool {
         const SMALL_FILE_LINES: usize = 200;
         const SMALL_FILE_BYTES: usize = 16 * 1024;
 
-        let path_str = required_str(&input, "path")?;
+        let args: ReadFileInput = parse_input(&input)?;
+        let path_str = args.path.as_str();
         let file_path = context.resolve_path(path_str)?;
-        let pages = optional_str(&input, "pages");
+        let pages = args.pages.as_deref();
 
         if is_pdf(&file_path)? {
             return read_pdf(&file_path, pages);
@@ -94,10 +105,7 @@ impl ToolSpec for ReadFileTool {
 
         let total_lines = contents.lines().count();
         let total_bytes = contents.len();
-        let explicit_range = input
-            .get("start_line")
-            .or_else(|| input.get("max_lines"))
-            .is_some();
+        let explicit_range = args.start_line.is_some() || args.max_lines.is_some();
 
         // Small-file fast path. Only applies when the caller didn't pass an
         // explicit range — otherwise an explicit `start_line = 5` on a
@@ -106,7 +114,7 @@ impl ToolSpec for ReadFileTool {
             return Ok(ToolResult::success(contents));
         }
 
-        let start_line = match input.get("start_line").and_then(Value::as_u64) {
+        let start_line = match args.start_line {
             Some(0) => {
                 return Err(ToolError::invalid_input(
                     "start_line must be 1-based and greater than 0".to_string(),
@@ -116,7 +124,7 @@ impl ToolSpec for ReadFileTool {
             None => 1,
         };
 
-        let max_lines = match input.get("max_lines").and_then(Value::as_u64) {
+        let max_lines = match args.max_lines {
             Some(0) => {
                 return Err(ToolError::invalid_input(
                     "max_lines must be greater than 0".to_string(),
@@ -388,6 +396,21 @@ fn read_pdf_via_pdftotext(
 /// Tool for writing UTF-8 files to the workspace.
 pub struct WriteFileTool;
 
+/// `write_file` arguments.
+#[derive(Debug, Deserialize, JsonSchema)]
+struct WriteFileInput {
+    /// Path to the file
+    path: String,
+    /// Content to write
+    content: String,
+}
+
+impl ToolInput for WriteFileInput {
+    fn examples() -> Vec<Value> {
+        vec![json!({"path": "notes/todo.md", "content": "# TODO\n- ship it\n"})]
+    }
+}
+
 #[async_trait]
 impl ToolSpec for WriteFileTool {
     fn name(&self) -> &'static str {
@@ -399,20 +422,11 @@ impl ToolSpec for WriteFileTool {
     }
 
     fn input_schema(&self) -> Value {
-        json!({
-            "type": "object",
-            "properties": {
-                "path": {
-                    "type": "string",
-                    "description": "Path to the file"
-                },
-                "content": {
-                    "type": "string",
-                    "description": "Content to write"
-                }
-            },
-            "required": ["path", "content"]
-        })
+        input_schema_for::<WriteFileInput>()
+    }
+
+    fn input_examples(&self) -> Vec<Value> {
+        WriteFileInput::examples()
     }
 
     fn capabilities(&self) -> Vec<ToolCapability> {
@@ -428,8 +442,9 @@ impl ToolSpec for WriteFileTool {
     }
 
     async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
-        let path_str = required_str(&input, "path")?;
-        let file_content = required_str(&input, "content")?;
+        let args: WriteFileInput = parse_input(&input)?;
+        let path_str = args.path.as_str();
+        let file_content = args.content.as_str();
 
         let file_path = context.resolve_path(path_str)?;
 
@@ -441,6 +456,15 @@ impl ToolSpec for WriteFileTool {
         } else {
             String::new()
         };
+        validate_writes(
+            context,
+            &[(
+                file_path.as_path(),
+                existed_before.then_some(prior_contents.as_str()),
+                file_content,
+            )],
+        )
+        .await?;
 
         // Create parent directories if needed
         if let Some(parent) = file_path.parent() {
@@ -453,10 +477,16 @@ impl ToolSpec for WriteFileTool {
             })?;
         }
 
+        context.note_before_write(&file_path);
         fs::write(&file_path, file_content).map_err(|e| {
             ToolError::execution_failed(format!("Failed to write {}: {}", file_path.display(), e))
         })?;
 
+        let audit = WriteAudit::new(vec![FileWriteAudit::new(
+            display_path(&context.workspace, &file_path),
+            existed_before.then_some(prior_contents.as_str()),
+            Some(file_content),
+        )]);
         let display = file_path.display().to_string();
         let diff = make_unified_diff(&display, &prior_contents, file_content);
         let summary = if existed_before {
@@ -478,7 +508,7 @@ impl ToolSpec for WriteFileTool {
             format!("{body}\n{diag_block}")
         };
 
-        Ok(ToolResult::success(full_body))
+        Ok(audit.attach(ToolResult::success(full_body)))
     }
 }
 
@@ -487,6 +517,34 @@ impl ToolSpec for WriteFileTool {
 /// Tool for search/replace editing of files.
 pub struct EditFileTool;
 
+/// `edit_file` arguments.
+#[derive(Debug, Deserialize, JsonSchema)]
+struct EditFileInput {
+    /// Path to the file
+    path: String,
+    /// Exact text to search for, including whitespace, indentation, and newlines
+    search: String,
+    /// Text to replace with
+    replace: String,
+    /// When true, tolerate leading whitespace differences on each searched line (default false)
+    #[serde(default)]
+    fuzz: bool,
+}
+
+impl ToolInput for EditFileInput {
+    fn examples() -> Vec<Value> {
+        vec![
+            json!({"path": "src/lib.rs", "search": "fn old()", "replace": "fn new()"}),
+            json!({
+                "path": "src/lib.rs",
+                "search": "let x = 1;\nlet y = 2;",
+                "replace": "let x = 1;",
+                "fuzz": true
+            }),
+        ]
+    }
+}
+
 #[async_trait]
 impl ToolSpec for EditFileTool {
     fn name(&self) -> &'static str {
@@ -498,28 +556,11 @@ impl ToolSpec for EditFileTool {
     }
 
     fn input_schema(&self) -> Value {
-        json!({
-            "type": "object",
-            "properties": {
-                "path": {
-                    "type": "string",
-                    "description": "Path to the file"
-                },
-                "search": {
-                    "type": "string",
-                    "description": "Exact text to search for, including whitespace, indentation, and newlines"
-                },
-                "replace": {
-                    "type": "string",
-                    "description": "Text to replace with"
-                },
-                "fuzz": {
-                    "type": "boolean",
-                    "description": "When true, tolerate leading whitespace differences on each searched line (default false)"
-                }
-            },
-            "required": ["path", "search", "replace"]
-        })
+        input_schema_for::<EditFileInput>()
+    }
+
+    fn input_examples(&self) -> Vec<Value> {
+        EditFileInput::examples()
     }
 
     fn capabilities(&self) -> Vec<ToolCapability> {
@@ -535,10 +576,11 @@ impl ToolSpec for EditFileTool {
     }
 
     async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
-        let path_str = required_str(&input, "path")?;
-        let search = required_str(&input, "search")?;
-        let replace = required_str(&input, "replace")?;
-        let fuzz = optional_bool(&input, "fuzz", false);
+        let args: EditFileInput = parse_input(&input)?;
+        let path_str = args.path.as_str();
+        let search = args.search.as_str();
+        let replace = args.replace.as_str();
+        let fuzz = args.fuzz;
 
         if search == replace {
             return Err(ToolError::invalid_input(
@@ -606,11 +648,26 @@ impl ToolSpec for EditFileTool {
         } else {
             (contents.replace(search, replace), count, None)
         };
+        validate_writes(
+            context,
+            &[(
+                file_path.as_path(),
+                Some(contents.as_str()),
+                updated.as_str(),
+            )],
+        )
+        .await?;
 
+        context.note_before_write(&file_path);
         fs::write(&file_path, &updated).map_err(|e| {
             ToolError::execution_failed(format!("Failed to write {}: {}", file_path.display(), e))
         })?;
 
+        let audit = WriteAudit::new(vec![FileWriteAudit::new(
+            display_path(&context.workspace, &file_path),
+            Some(contents.as_str()),
+            Some(updated.as_str()),
+        )]);
         let display = file_path.display().to_string();
         let diff = make_unified_diff(&display, &contents, &updated);
         let summary = if count > 1 {
@@ -644,7 +701,7 @@ impl ToolSpec for EditFileTool {
             format!("{body}\n{diag_block}")
         };
 
-        Ok(ToolResult::success(full_body))
+        Ok(audit.attach(ToolResult::success(full_body)))
     }
 }
 
@@ -761,6 +818,19 @@ fn punctuation_normalized_matches(contents: &str, search: &str) -> Vec<(usize, u
 /// Tool for listing directory contents.
 pub struct ListDirTool;
 
+/// `list_dir` arguments.
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ListDirInput {
+    /// Relative path (default: .)
+    path: Option<String>,
+}
+
+impl ToolInput for ListDirInput {
+    fn examples() -> Vec<Value> {
+        vec![json!({}), json!({"path": "src"})]
+    }
+}
+
 #[async_trait]
 impl ToolSpec for ListDirTool {
     fn name(&self) -> &'static str {
@@ -772,16 +842,11 @@ impl ToolSpec for ListDirTool {
     }
 
     fn input_schema(&self) -> Value {
-        json!({
-            "type": "object",
-            "properties": {
-                "path": {
-                    "type": "string",
-                    "description": "Relative path (default: .)"
-                }
-            },
-            "required": []
-        })
+        input_schema_for::<ListDirInput>()
+    }
+
+    fn input_examples(&self) -> Vec<Value> {
+        ListDirInput::examples()
     }
 
     fn capabilities(&self) -> Vec<ToolCapability> {
@@ -793,7 +858,8 @@ impl ToolSpec for ListDirTool {
     }
 
     async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
-        let path_str = optional_str(&input, "path").unwrap_or(".");
+        let args: ListDirInput = parse_input(&input)?;
+        let path_str = args.path.as_deref().unwrap_or(".");
         let dir_path = context.resolve_path(path_str)?;
 
         let mut entries = Vec::new();
@@ -827,6 +893,37 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    fn assert_examples_parse<T: ToolInput>() {
+        for example in T::examples() {
+            if let Err(err) = parse_input::<T>(&example) {
+                panic!("example {example} does not parse: {err}");
+            }
+        }
+    }
+
+    #[test]
+    fn typed_inputs_parse_their_examples() {
+        assert_examples_parse::<ReadFileInput>();
+        assert_examples_parse::<WriteFileInput>();
+        assert_examples_parse::<EditFileInput>();
+        assert_examples_parse::<ListDirInput>();
+    }
+
+    #[tokio::test]
+    async fn test_read_file_rejects_mistyped_range() {
+        let tmp = tempdir().expect("tempdir");
+        fs::write(tmp.path().join("a.txt"), "one\ntwo\n").expect("write");
+        let ctx = ToolContext::new(tmp.path().to_path_buf());
+
+        let err = ReadFileTool
+            .execute(json!({"path": "a.txt", "start_line": "2"}), &ctx)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("invalid type"), "{err}");
+        assert!(err.contains("Example input:"), "{err}");
+    }
+
     #[tokio::test]
     async fn test_read_file_tool() {
         let tmp = tempdir().expect("tempdir");
@@ -1329,6 +1426,12 @@ mod tests {
             result.content
         );
 
+        // The per-call audit names the file relative to the workspace.
+        let audit = WriteAudit::from_result(&result).expect("write audit");
+        assert_eq!(audit.files[0].path, "output.txt");
+        assert!(audit.files[0].created);
+        assert_eq!(audit.added(), 1);
+
         // Verify file was written
         let written = fs::read_to_string(tmp.path().join("output.txt")).expect("read");
         assert_eq!(written, "test content");
@@ -1,8 +1,13 @@
-//! FIM (Fill-in-the-Middle) edit tool.
+//! FIM (Fill-in-the-Middle) edit tools.
 //!
-//! Reads a file, finds `prefix_anchor` and `suffix_anchor`, calls the
-//! DeepSeek `/beta/completions` FIM endpoint, and writes the generated
+//! `fim_edit` reads a file, finds `prefix_anchor` and `suffix_anchor`, calls
+//! the DeepSeek `/beta/completions` FIM endpoint, and writes the generated
 //! middle content back into the file.
+//!
+//! `edit_with_fim` rewrites a line range and only sends a window of lines on
+//! either side of it, so a targeted edit costs a few hundred tokens instead
+//! of the whole file. When the provider has no FIM endpoint, or the FIM call
+//! fails, it falls back to a one-shot chat request over the same window.
 
 use std::fs;
 
@@ -11,11 +16,14 @@ use serde_json::{Value, json};
 use thiserror::Error;
 
 use crate::client::DeepSeekClient;
+use crate::llm_client::LlmClient;
+use crate::models::{ContentBlock, Message, MessageRequest, SystemPrompt, Usage};
 
 use super::spec::{
     ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolResult, ToolSpec,
-    optional_u64, required_str,
+    optional_str, optional_u64, required_str, required_u64,
 };
+use super::write_audit::{FileWriteAudit, WriteAudit};
 
 /// Result of a FIM edit operation
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -162,6 +170,7 @@ impl ToolSpec for FimEditTool {
         // 7. Build the new content and write it back
         let generated_len = generated_text.len();
         let new_content = format!("{}{}{}", fim_prompt, generated_text, fim_suffix);
+        context.note_before_write(&resolved);
         fs::write(&resolved, &new_content).map_err(|e| {
             ToolError::execution_failed(format!("Failed to write {}: {}", resolved.display(), e))
         })?;
@@ -178,6 +187,370 @@ impl ToolSpec for FimEditTool {
             ),
         };
 
-        ToolResult::json(&result).map_err(|e| ToolError::execution_failed(e.to_string()))
+        let audit = WriteAudit::new(vec![FileWriteAudit::new(
+            path,
+            Some(content.as_str()),
+            Some(new_content.as_str()),
+        )]);
+        ToolResult::json(&result)
+            .map(|tool_result| audit.attach(tool_result))
+            .map_err(|e| ToolError::execution_failed(e.to_string()))
+    }
+}
+
+// === edit_with_fim ===
+
+/// Lines of surrounding code sent on each side of the region by default.
+const DEFAULT_CONTEXT_LINES: u64 = 80;
+const MAX_CONTEXT_LINES: u64 = 400;
+const DEFAULT_MAX_TOKENS: u64 = 1024;
+
+const CHAT_FALLBACK_SYSTEM_PROMPT: &str = "You rewrite one region of a source file. \
+Reply with only the new text for the region: no explanation and no code fences. \
+Match the indentation and style of the surrounding code.";
+
+/// Tool that fills a line range from the code around it, via FIM when the
+/// provider supports it and a chat request otherwise.
+pub struct EditWithFimTool {
+    pub client: Option<DeepSeekClient>,
+    pub model: String,
+}
+
+impl EditWithFimTool {
+    #[must_use]
+    pub fn new(client: Option<DeepSeekClient>, model: String) -> Self {
+        Self { client, model }
+    }
+}
+
+/// A file split around the lines being rewritten. `prefix` and `suffix` are
+/// the context windows sent to the model; `head` and `tail` stay local.
+#[derive(Debug, PartialEq, Eq)]
+struct EditRegion<'a> {
+    head: &'a str,
+    prefix: &'a str,
+    original: &'a str,
+    suffix: &'a str,
+    tail: &'a str,
+}
+
+/// Split `content` around lines `start_line..=end_line` (1-based). An
+/// `end_line` of `start_line - 1` inserts before `start_line` without
+/// replacing anything.
+fn split_region(
+    content: &str,
+    start_line: usize,
+    end_line: usize,
+    context_lines: usize,
+) -> Result<EditRegion<'_>, String> {
+    let mut line_starts = vec![0];
+    line_starts.extend(
+        content
+            .match_indices('\n')
+            .map(|(idx, _)| idx + 1)
+            .filter(|&idx| idx < content.len()),
+    );
+    let line_count = line_starts.len();
+    let offset = |line: usize| line_starts.get(line).copied().unwrap_or(content.len());
+
+    if start_line == 0 || start_line > line_count + 1 {
+        return Err(format!(
+            "start_line {start_line} is outside the file (1..={})",
+            line_count + 1
+        ));
+    }
+    if end_line + 1 < start_line || end_line > line_count {
+        return Err(format!(
+            "end_line {end_line} must be between {} and {line_count}",
+            start_line - 1
+        ));
+    }
+
+    let region_start = offset(start_line - 1);
+    let region_end = offset(end_line);
+    let window_start = offset((start_line - 1).saturating_sub(context_lines));
+    let window_end = offset(end_line.saturating_add(context_lines).min(line_count));
+    Ok(EditRegion {
+        head: &content[..window_start],
+        prefix: &content[window_start..region_start],
+        original: &content[region_start..region_end],
+        suffix: &content[region_end..window_end],
+        tail: &content[window_end..],
+    })
+}
+
+/// Drop a Markdown fence the chat model wrapped its answer in anyway.
+fn strip_code_fence(text: &str) -> &str {
+    let trimmed = text.trim_matches('\n');
+    let Some(body) = trimmed.strip_prefix("```") else {
+        return text;
+    };
+    let Some((_, body)) = body.split_once('\n') else {
+        return text;
+    };
+    body.trim_end()
+        .strip_suffix("```")
+        .map_or(text, |inner| inner.trim_end_matches('\n'))
+}
+
+fn chat_fallback_prompt(
+    path: &str,
+    region: &EditRegion<'_>,
+    instruction: Option<&str>,
+    lead: &str,
+) -> String {
+    let instruction = instruction.unwrap_or("Rewrite the region so it fits the code around it.");
+    let mut prompt = format!(
+        "File: {path}\nInstruction: {instruction}\n\n<before>\n{}</before>\n<region>\n{}</region>\n<after>\n{}</after>\n",
+        region.prefix, region.original, region.suffix
+    );
+    if !lead.is_empty() {
+        prompt.push_str(&format!("\nThe new region must start with:\n{lead}\n"));
+    }
+    prompt
+}
+
+fn chat_usage_metadata(model: &str, usage: &Usage) -> Value {
+    json!({
+        "tool": "edit_with_fim",
+        "input_tokens": usage.input_tokens,
+        "output_tokens": usage.output_tokens,
+        "child_model": model,
+        "child_input_tokens": usage.input_tokens,
+        "child_output_tokens": usage.output_tokens,
+        "child_prompt_cache_hit_tokens": usage.prompt_cache_hit_tokens,
+        "child_prompt_cache_miss_tokens": usage.prompt_cache_miss_tokens,
+        "child_reasoning_tokens": usage.reasoning_tokens,
+    })
+}
+
+#[async_trait]
+impl ToolSpec for EditWithFimTool {
+    fn name(&self) -> &'static str {
+        "edit_with_fim"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rewrite a line range of a file from the code around it. Only `context_lines` \
+         lines on each side are sent (prefix/suffix fill-in-the-middle), so targeted \
+         edits stay cheap and local. Use `lead` to fix how the new text starts; \
+         `instruction` steers the chat fallback used when the provider has no FIM \
+         endpoint. Set end_line = start_line - 1 to insert without replacing."
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Path to the file to edit (relative to workspace)"
+                },
+                "start_line": {
+                    "type": "integer",
+                    "description": "First line to rewrite (1-based)"
+                },
+                "end_line": {
+                    "type": "integer",
+                    "description": "Last line to rewrite (inclusive). Use start_line - 1 to insert before start_line."
+                },
+                "lead": {
+                    "type": "string",
+                    "description": "Text the new region starts with, such as a signature or comment; generation continues from it"
+                },
+                "instruction": {
+                    "type": "string",
+                    "description": "What the new region should do; used by the chat fallback"
+                },
+                "context_lines": {
+                    "type": "integer",
+                    "description": "Lines of surrounding code sent on each side (default: 80, max: 400)"
+                },
+                "max_tokens": {
+                    "type": "integer",
+                    "description": "Maximum tokens to generate (default: 1024)"
+                }
+            },
+            "required": ["path", "start_line", "end_line"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![
+            ToolCapability::WritesFiles,
+            ToolCapability::RequiresApproval,
+        ]
+    }
+
+    fn approval_requirement(&self) -> ApprovalRequirement {
+        ApprovalRequirement::Suggest
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let Some(client) = self.client.as_ref() else {
+            return Err(ToolError::not_available(
+                "edit_with_fim requires an active DeepSeek client".to_string(),
+            ));
+        };
+        let path = required_str(&input, "path")?;
+        let start_line = usize::try_from(required_u64(&input, "start_line")?).unwrap_or(usize::MAX);
+        let end_line = usize::try_from(required_u64(&input, "end_line")?).unwrap_or(usize::MAX);
+        let lead = optional_str(&input, "lead").unwrap_or_default();
+        let instruction =
+            optional_str(&input, "instruction").filter(|text| !text.trim().is_empty());
+        let context_lines = optional_u64(&input, "context_lines", DEFAULT_CONTEXT_LINES)
+            .min(MAX_CONTEXT_LINES) as usize;
+        let max_tokens = optional_u64(&input, "max_tokens", DEFAULT_MAX_TOKENS) as u32;
+
+        let resolved = context.resolve_path(path)?;
+        let content = fs::read_to_string(&resolved).map_err(|e| {
+            ToolError::execution_failed(format!("Failed to read {}: {}", resolved.display(), e))
+        })?;
+        let region = split_region(&content, start_line, end_line, context_lines)
+            .map_err(ToolError::invalid_input)?;
+
+        let mut fallback_reason = None;
+        let mut metadata = None;
+        let fim_result = if client.supports_fim() {
+            let prompt = format!("{}{lead}", region.prefix);
+            match client
+                .fim_completion(&self.model, &prompt, region.suffix, max_tokens)
+                .await
+            {
+                Ok(text) => Some(format!("{lead}{text}")),
+                Err(err) => {
+                    fallback_reason = Some(FimError::ApiFailed(err.to_string()).to_string());
+                    None
+                }
+            }
+        } else {
+            fallback_reason = Some("provider has no FIM endpoint".to_string());
+            None
+        };
+        let (backend, mut generated) = match fim_result {
+            Some(text) => ("fim", text),
+            None => {
+                let request = MessageRequest {
+                    model: self.model.clone(),
+                    messages: vec![Message {
+                        role: "user".to_string(),
+                        content: vec![ContentBlock::Text {
+                            text: chat_fallback_prompt(path, &region, instruction, lead),
+                            cache_control: None,
+                        }],
+                    }],
+                    max_tokens,
+                    system: Some(SystemPrompt::Text(CHAT_FALLBACK_SYSTEM_PROMPT.to_string())),
+                    tools: None,
+                    tool_choice: None,
+                    metadata: None,
+                    thinking: None,
+                    reasoning_effort: None,
+                    stream: Some(false),
+                    temperature: Some(0.2),
+                    top_p: Some(0.9),
+                };
+                let response = client.create_message(request).await.map_err(|e| {
+                    ToolError::execution_failed(format!(
+                        "Chat fallback failed after {}: {e}",
+                        fallback_reason.as_deref().unwrap_or("FIM was unavailable")
+                    ))
+                })?;
+                metadata = Some(chat_usage_metadata(&response.model, &response.usage));
+                let text: String = response
+                    .content
+                    .iter()
+                    .filter_map(|block| match block {
+                        ContentBlock::Text { text, .. } => Some(text.as_str()),
+                        _ => None,
+                    })
+                    .collect();
+                let text = strip_code_fence(&text).to_string();
+                let text = if text.starts_with(lead) {
+                    text
+                } else {
+                    format!("{lead}{text}")
+                };
+                ("chat", text)
+            }
+        };
+        // The region covered whole lines; keep the line after it on its own.
+        if region.original.ends_with('\n') && !generated.is_empty() && !generated.ends_with('\n') {
+            generated.push('\n');
+        }
+
+        let new_content = format!(
+            "{}{}{generated}{}{}",
+            region.head, region.prefix, region.suffix, region.tail
+        );
+        context.note_before_write(&resolved);
+        fs::write(&resolved, &new_content).map_err(|e| {
+            ToolError::execution_failed(format!("Failed to write {}: {}", resolved.display(), e))
+        })?;
+
+        let replaced = if end_line < start_line {
+            format!("inserted before line {start_line}")
+        } else {
+            format!("replaced lines {start_line}-{end_line}")
+        };
+        let result = json!({
+            "success": true,
+            "path": path,
+            "backend": backend,
+            "fallback_reason": fallback_reason,
+            "generated_text": generated,
+            "message": format!(
+                "edit_with_fim {replaced} in `{path}` via {backend}, sending {} context lines each side.",
+                context_lines
+            ),
+        });
+        let audit = WriteAudit::new(vec![FileWriteAudit::new(
+            path,
+            Some(content.as_str()),
+            Some(new_content.as_str()),
+        )]);
+        let mut tool_result =
+            ToolResult::json(&result).map_err(|e| ToolError::execution_failed(e.to_string()))?;
+        if let Some(metadata) = metadata {
+            tool_result = tool_result.with_metadata(metadata);
+        }
+        Ok(audit.attach(tool_result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "a\nb\nc\nd\ne\n";
+
+    #[test]
+    fn split_region_sends_only_the_context_window() {
+        let region = split_region(FILE, 3, 3, 1).expect("region");
+        assert_eq!(
+            region,
+            EditRegion {
+                head: "a\n",
+                prefix: "b\n",
+                original: "c\n",
+                suffix: "d\n",
+                tail: "e\n",
+            }
+        );
+
+        let insert = split_region(FILE, 6, 5, 2).expect("append at end");
+        assert_eq!(insert.prefix, "d\ne\n");
+        assert_eq!(insert.original, "");
+        assert_eq!(insert.suffix, "");
+
+        assert!(split_region(FILE, 0, 1, 1).is_err());
+        assert!(split_region(FILE, 2, 9, 1).is_err());
+        assert!(split_region(FILE, 4, 2, 1).is_err());
+    }
+
+    #[test]
+    fn chat_fallback_output_is_unfenced() {
+        assert_eq!(strip_code_fence("```rust\nfn x() {}\n```\n"), "fn x() {}");
+        assert_eq!(strip_code_fence("fn x() {}\n"), "fn x() {}\n");
     }
 }
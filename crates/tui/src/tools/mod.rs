@@ -12,8 +12,13 @@ pub mod apply_patch;
 pub mod approval_cache;
 pub mod arg_repair;
 pub mod automation;
+pub mod command_alias;
+pub mod crate_docs;
 pub mod diagnostics;
 pub mod diff_format;
+pub mod docs_search;
+pub mod edit_validation;
+pub mod exec_profile;
 pub mod file;
 pub mod file_search;
 pub mod finance;
@@ -28,24 +33,35 @@ pub mod image_ocr;
 pub mod js_execution;
 pub mod large_output_router;
 pub mod notify;
+pub mod output_filter;
 pub mod pandoc;
 pub mod parallel;
 pub mod plan;
 pub mod project;
+pub mod read_result_page;
+pub mod read_symbol;
 pub mod recall_archive;
+pub mod recall_history;
 pub mod registry;
 pub mod remember;
+pub mod result_cache;
 pub mod revert_turn;
 pub mod review;
 pub mod rlm;
 pub mod schema_sanitize;
 pub mod search;
+pub mod session_changes;
+pub mod session_env;
 pub mod shell;
 mod shell_output;
+pub mod shell_session;
 pub mod skill;
+pub mod snippet;
 pub mod spec;
 pub mod subagent;
+pub mod system_info;
 pub mod tasks;
+pub mod terminal_capture;
 pub mod test_runner;
 pub mod todo;
 pub mod tool_result_retrieval;
@@ -54,6 +70,7 @@ pub mod user_input;
 pub mod validate_data;
 pub mod web_run;
 pub mod web_search;
+pub mod write_audit;
 
 pub use registry::{ToolRegistry, ToolRegistryBuilder};
 pub use review::ReviewOutput;
@@ -0,0 +1,317 @@
+//! Per-tool output filters (`[tool_filters.<tool>]`).
+//!
+//! A filter trims a tool result before it enters the model's context: strip
+//! ANSI escapes and carriage-return progress redraws, drop lines matching a
+//! pattern, fold runs of identical lines, then keep only the first and/or
+//! last lines. The stages run in that order.
+//!
+//! Nothing is lost: when a filter changes a result, the raw output is written
+//! to the spillover file for the tool call (see [`super::truncate`]) and the
+//! filtered text ends with a footer pointing at `retrieve_tool_result`.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use super::spec::ToolResult;
+use super::truncate::{SPILLOVER_HEAD_BYTES, SPILLOVER_THRESHOLD_BYTES, write_spillover};
+
+/// `[tool_filters]` table: tool name → filter.
+pub type ToolFilters = BTreeMap<String, ToolOutputFilter>;
+
+/// One `[tool_filters.<tool>]` entry.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct ToolOutputFilter {
+    /// Remove ANSI escape sequences and keep only the last redraw of lines
+    /// rewritten with `\r` (progress bars).
+    #[serde(default)]
+    pub strip_ansi: bool,
+    /// Drop lines matching any of these regular expressions.
+    #[serde(default)]
+    pub drop_matching: Vec<String>,
+    /// Fold consecutive identical lines into one plus a repeat count.
+    #[serde(default)]
+    pub dedupe_repeats: bool,
+    /// Keep only the first N lines.
+    #[serde(default)]
+    pub head_lines: Option<usize>,
+    /// Keep only the last N lines. With `head_lines`, both ends are kept.
+    #[serde(default)]
+    pub tail_lines: Option<usize>,
+}
+
+/// Result of running a filter over some text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilteredOutput {
+    pub text: String,
+    pub lines_in: usize,
+    /// Output lines kept, not counting the filter's own `[…]` notes.
+    pub lines_out: usize,
+}
+
+impl ToolOutputFilter {
+    #[must_use]
+    pub fn is_noop(&self) -> bool {
+        !self.strip_ansi
+            && self.drop_matching.is_empty()
+            && !self.dedupe_repeats
+            && self.head_lines.is_none()
+            && self.tail_lines.is_none()
+    }
+
+    #[must_use]
+    pub fn apply(&self, content: &str) -> FilteredOutput {
+        let lines_in = content.lines().count();
+        let mut lines: Vec<String> = if self.strip_ansi {
+            let mut stripped = String::with_capacity(content.len());
+            crate::tui::osc8::strip_ansi_into(content, &mut stripped);
+            stripped
+                .lines()
+                .map(last_redraw)
+                .map(str::to_string)
+                .collect()
+        } else {
+            content.lines().map(str::to_string).collect()
+        };
+
+        let patterns = self.compiled_patterns();
+        if !patterns.is_empty() {
+            lines.retain(|line| !patterns.iter().any(|re| re.is_match(line)));
+        }
+        let mut lines: Vec<OutLine> = lines.into_iter().map(OutLine::Kept).collect();
+        if self.dedupe_repeats {
+            lines = dedupe_repeats(lines);
+        }
+        lines = keep_ends(lines, self.head_lines, self.tail_lines);
+
+        let lines_out = lines
+            .iter()
+            .filter(|line| matches!(line, OutLine::Kept(_)))
+            .count();
+        let mut text = lines
+            .into_iter()
+            .map(OutLine::into_text)
+            .collect::<Vec<_>>()
+            .join("\n");
+        if content.ends_with('\n') && !text.is_empty() {
+            text.push('\n');
+        }
+        FilteredOutput {
+            text,
+            lines_in,
+            lines_out,
+        }
+    }
+
+    fn compiled_patterns(&self) -> Vec<Regex> {
+        self.drop_matching
+            .iter()
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(re) => Some(re),
+                Err(err) => {
+                    tracing::warn!(pattern, %err, "ignoring invalid tool_filters pattern");
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// A line of filtered output: original text, or a note the filter added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OutLine {
+    Kept(String),
+    Note(String),
+}
+
+impl OutLine {
+    fn into_text(self) -> String {
+        match self {
+            Self::Kept(text) | Self::Note(text) => text,
+        }
+    }
+}
+
+/// A line redrawn with `\r` shows only its final state on a terminal.
+fn last_redraw(line: &str) -> &str {
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    line.rsplit('\r').next().unwrap_or(line)
+}
+
+fn dedupe_repeats(lines: Vec<OutLine>) -> Vec<OutLine> {
+    let mut out: Vec<OutLine> = Vec::with_capacity(lines.len());
+    let mut repeats = 0usize;
+    for line in lines {
+        if out.last() == Some(&line) {
+            repeats += 1;
+            continue;
+        }
+        push_repeat_note(&mut out, repeats);
+        repeats = 0;
+        out.push(line);
+    }
+    push_repeat_note(&mut out, repeats);
+    out
+}
+
+fn push_repeat_note(out: &mut Vec<OutLine>, repeats: usize) {
+    if repeats > 0 {
+        out.push(OutLine::Note(format!(
+            "[previous line repeated {repeats} more time{}]",
+            if repeats == 1 { "" } else { "s" }
+        )));
+    }
+}
+
+fn keep_ends(lines: Vec<OutLine>, head: Option<usize>, tail: Option<usize>) -> Vec<OutLine> {
+    let (head, tail) = match (head, tail) {
+        (None, None) => return lines,
+        (head, tail) => (head.unwrap_or(0), tail.unwrap_or(0)),
+    };
+    if lines.len() <= head + tail {
+        return lines;
+    }
+    let omitted = lines.len() - head - tail;
+    let mut out = Vec::with_capacity(head + tail + 1);
+    let mut lines = lines.into_iter();
+    out.extend(lines.by_ref().take(head));
+    out.push(OutLine::Note(format!("[… {omitted} lines omitted …]")));
+    out.extend(lines.skip(omitted));
+    out
+}
+
+/// Run the filter configured for `tool_name` over `result`, in place.
+///
+/// When the filter changes anything, the raw output is saved as the
+/// spillover file for `tool_id` first; if that write fails the result is
+/// left untouched so no output is lost. Returns the spillover path when the
+/// result was filtered.
+pub fn apply_tool_filter(
+    filters: &ToolFilters,
+    result: &mut ToolResult,
+    tool_id: &str,
+    tool_name: &str,
+) -> Option<PathBuf> {
+    let filter = filters.get(tool_name).filter(|filter| !filter.is_noop())?;
+    let filtered = filter.apply(&result.content);
+    if filtered.text == result.content {
+        return None;
+    }
+    let path = match write_spillover(tool_id, &result.content) {
+        Ok(path) => path,
+        Err(err) => {
+            tracing::warn!(
+                target: "spillover",
+                ?err,
+                tool_id,
+                "could not save raw output; skipping tool_filters"
+            );
+            return None;
+        }
+    };
+
+    // Spillover would overwrite the raw file with the filtered text, so a
+    // filtered result that is still too large is cut here instead.
+    let mut text = filtered.text;
+    if text.len() > SPILLOVER_THRESHOLD_BYTES {
+        let cut = (0..=SPILLOVER_HEAD_BYTES)
+            .rev()
+            .find(|&i| text.is_char_boundary(i))
+            .unwrap_or(0);
+        text.truncate(cut);
+    }
+    result.content = format!(
+        "{text}\n[Filtered by [tool_filters.{tool_name}]: {} of {} lines kept. Full output: \
+         `retrieve_tool_result ref={tool_id} mode=tail` or \
+         `retrieve_tool_result ref={tool_id} mode=query query=<text>`.]",
+        filtered.lines_out, filtered.lines_in,
+    );
+
+    let metadata = result.metadata.get_or_insert_with(|| serde_json::json!({}));
+    if !metadata.is_object() {
+        let prior = std::mem::replace(metadata, serde_json::json!({}));
+        metadata["_prior"] = prior;
+    }
+    metadata["spillover_path"] = serde_json::Value::String(path.display().to_string());
+    metadata["output_filter"] = serde_json::json!({
+        "lines_in": filtered.lines_in,
+        "lines_out": filtered.lines_out,
+    });
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::truncate::{TEST_SPILLOVER_GUARD, set_test_spillover_root};
+
+    #[test]
+    fn stages_strip_drop_dedupe_then_keep_ends() {
+        let filter = ToolOutputFilter {
+            strip_ansi: true,
+            drop_matching: vec![r"^\s*Compiling ".to_string()],
+            dedupe_repeats: true,
+            head_lines: Some(1),
+            tail_lines: Some(3),
+        };
+        let raw = "\u{1b}[1mstart\u{1b}[0m\n   Compiling foo v0.1.0\n\
+                   10%\r50%\r100%\nwarn\nwarn\nwarn\nmiddle\nerror: \u{1b}[31mboom\u{1b}[0m\n";
+        let out = filter.apply(raw);
+        assert_eq!(
+            out.text,
+            "start\n[… 2 lines omitted …]\n[previous line repeated 2 more times]\nmiddle\nerror: boom\n"
+        );
+        assert_eq!((out.lines_in, out.lines_out), (8, 3));
+
+        let tail_only = ToolOutputFilter {
+            tail_lines: Some(2),
+            ..ToolOutputFilter::default()
+        };
+        assert_eq!(
+            tail_only.apply("a\nb\nc").text,
+            "[… 1 lines omitted …]\nb\nc"
+        );
+        assert!(ToolOutputFilter::default().is_noop());
+    }
+
+    #[test]
+    fn filtered_result_keeps_raw_output_in_spillover() {
+        let _guard = TEST_SPILLOVER_GUARD
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let prior = set_test_spillover_root(Some(tmp.path().to_path_buf()));
+
+        let mut filters = ToolFilters::new();
+        filters.insert(
+            "exec_shell".to_string(),
+            ToolOutputFilter {
+                tail_lines: Some(2),
+                ..ToolOutputFilter::default()
+            },
+        );
+        let raw = (1..=50).map(|n| format!("line {n}\n")).collect::<String>();
+        let mut result = ToolResult::success(raw.clone());
+        let path =
+            apply_tool_filter(&filters, &mut result, "call_1", "exec_shell").expect("filtered");
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), raw);
+        assert!(
+            result
+                .content
+                .starts_with("[… 48 lines omitted …]\nline 49\nline 50\n")
+        );
+        assert!(result.content.contains("2 of 50 lines kept"));
+        assert!(result.content.contains("retrieve_tool_result ref=call_1"));
+        let metadata = result.metadata.as_ref().unwrap();
+        assert_eq!(metadata["output_filter"]["lines_in"], 50);
+
+        let mut untouched = ToolResult::success(raw.clone());
+        assert!(apply_tool_filter(&filters, &mut untouched, "call_2", "read_file").is_none());
+        assert_eq!(untouched.content, raw);
+
+        set_test_spillover_root(prior);
+    }
+}
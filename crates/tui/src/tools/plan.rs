@@ -59,6 +59,12 @@ pub struct UpdatePlanArgs {
     #[serde(default)]
     pub explanation: Option<String>,
     pub plan: Vec<PlanItemArg>,
+    /// Replaces the open questions when present; `None` keeps them.
+    #[serde(default)]
+    pub open_questions: Option<Vec<String>>,
+    /// Replaces the recorded decisions when present; `None` keeps them.
+    #[serde(default)]
+    pub decisions: Option<Vec<String>>,
 }
 
 // === Plan State ===
@@ -119,6 +125,10 @@ impl PlanStep {
 pub struct PlanSnapshot {
     pub explanation: Option<String>,
     pub items: Vec<PlanItemArg>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub open_questions: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub decisions: Vec<String>,
 }
 
 /// State tracking for the current plan
@@ -126,6 +136,8 @@ pub struct PlanSnapshot {
 pub struct PlanState {
     explanation: Option<String>,
     steps: Vec<PlanStep>,
+    open_questions: Vec<String>,
+    decisions: Vec<String>,
 }
 
 impl PlanState {
@@ -137,6 +149,12 @@ impl PlanState {
 
     pub fn update(&mut self, args: UpdatePlanArgs) {
         self.explanation = args.explanation.filter(|s| !s.trim().is_empty());
+        if let Some(questions) = args.open_questions {
+            self.open_questions = non_empty_lines(questions);
+        }
+        if let Some(decisions) = args.decisions {
+            self.decisions = non_empty_lines(decisions);
+        }
 
         let now = Instant::now();
         let mut new_steps = Vec::new();
@@ -195,6 +213,8 @@ impl PlanState {
                     status: s.status.clone(),
                 })
                 .collect(),
+            open_questions: self.open_questions.clone(),
+            decisions: self.decisions.clone(),
         }
     }
 
@@ -236,6 +256,14 @@ impl PlanState {
     }
 }
 
+fn non_empty_lines(lines: Vec<String>) -> Vec<String> {
+    lines
+        .into_iter()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
 /// Validation result for plan transitions
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -306,7 +334,7 @@ impl ToolSpec for UpdatePlanTool {
     }
 
     fn description(&self) -> &'static str {
-        "Update the implementation plan with steps and their status. Use this to track progress on implementation tasks. Each step has a description and status (pending, in_progress, completed). Optionally include an explanation of the overall approach."
+        "Update the implementation plan with steps and their status. Use this to track progress on implementation tasks. Each step has a description and status (pending, in_progress, completed). Optionally include an explanation of the overall approach, the open questions still blocking the plan, and the decisions already made."
     }
 
     fn input_schema(&self) -> serde_json::Value {
@@ -317,6 +345,16 @@ impl ToolSpec for UpdatePlanTool {
                     "type": "string",
                     "description": "Optional high-level explanation of the plan or approach"
                 },
+                "open_questions": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Optional unresolved questions; replaces the previous list, omit to keep it"
+                },
+                "decisions": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Optional decisions made so far; replaces the previous list, omit to keep it"
+                },
                 "plan": {
                     "type": "array",
                     "description": "List of plan steps",
@@ -384,9 +422,20 @@ impl ToolSpec for UpdatePlanTool {
             });
         }
 
+        let string_list = |key: &str| {
+            input.get(key).and_then(|v| v.as_array()).map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(str::to_string))
+                    .collect::<Vec<_>>()
+            })
+        };
+
         let args = UpdatePlanArgs {
             explanation,
             plan: plan_args,
+            open_questions: string_list("open_questions"),
+            decisions: string_list("decisions"),
         };
 
         let mut state = self.plan_state.lock().await;
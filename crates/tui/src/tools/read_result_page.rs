@@ -0,0 +1,268 @@
+//! `read_result_page` - cursor-based paging over spilled tool results.
+//!
+//! `retrieve_tool_result` answers targeted questions (head, tail, a line
+//! range, a query). Some outputs have to be read end to end — a long test
+//! log, a JSON dump — and fetching them whole would blow the context. This
+//! tool walks a spilled result in bounded pages: each call returns one page
+//! plus a `next_cursor`, and the model keeps calling until `done` is true.
+//!
+//! Cursors are byte offsets into the spilled file, handed out as strings so
+//! the model treats them as opaque. Pages end on a line break whenever one
+//! falls in the back half of the page, so a line is rarely split across two
+//! calls.
+
+use std::fs;
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use super::spec::{
+    ToolCapability, ToolContext, ToolError, ToolResult, ToolSpec, optional_u64, required_str,
+};
+use super::tool_result_retrieval::resolve_spillover_reference;
+
+const DEFAULT_PAGE_BYTES: usize = 16 * 1024;
+const HARD_MAX_PAGE_BYTES: usize = 64 * 1024;
+const MIN_PAGE_BYTES: usize = 256;
+
+/// Page through a spilled tool result with an opaque cursor.
+pub struct ReadResultPageTool;
+
+#[async_trait]
+impl ToolSpec for ReadResultPageTool {
+    fn name(&self) -> &'static str {
+        "read_result_page"
+    }
+
+    fn description(&self) -> &'static str {
+        "Read a large spilled tool result page by page. Pass the `result_id` from the truncated result (artifact id `art_...`, tool call id, or `sha:<hex>`) and the `next_cursor` from the previous page; omit `cursor` for the first page. Stop when `done` is true."
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "result_id": {
+                    "type": "string",
+                    "description": "Id of the spilled result: artifact id (`art_<id>`), tool call id, or `sha:<64-hex>`."
+                },
+                "cursor": {
+                    "type": "string",
+                    "description": "Opaque cursor from the previous page's `next_cursor`. Omit for the first page."
+                },
+                "max_bytes": {
+                    "type": "integer",
+                    "description": "Maximum bytes of content in this page. Default 16384, min 256, hard cap 65536."
+                }
+            },
+            "required": ["result_id"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::ReadOnly]
+    }
+
+    fn supports_parallel(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let result_id = required_str(&input, "result_id")?.trim();
+        if result_id.is_empty() {
+            return Err(ToolError::invalid_input("result_id cannot be empty"));
+        }
+        let cursor = parse_cursor(input.get("cursor"))?;
+        let max_bytes = (optional_u64(&input, "max_bytes", DEFAULT_PAGE_BYTES as u64) as usize)
+            .clamp(MIN_PAGE_BYTES, HARD_MAX_PAGE_BYTES);
+
+        let path = resolve_spillover_reference(result_id, &context.state_namespace)?;
+        let content = fs::read_to_string(&path).map_err(|err| {
+            ToolError::execution_failed(format!("failed to read {}: {err}", path.display()))
+        })?;
+
+        let page = page_at(&content, cursor, max_bytes)?;
+        let payload = json!({
+            "result_id": result_id,
+            "cursor": cursor.to_string(),
+            "next_cursor": page.next.map(|offset| offset.to_string()),
+            "done": page.next.is_none(),
+            "total_bytes": content.len(),
+            "page_bytes": page.text.len(),
+            "start_line": page.start_line,
+            "end_line": page.end_line,
+            "content": page.text,
+        });
+        ToolResult::json(&payload).map_err(|err| {
+            ToolError::execution_failed(format!("failed to serialize result: {err}"))
+        })
+    }
+}
+
+/// Accept the cursor as the string we hand out, or as a bare integer for
+/// models that unquote it.
+fn parse_cursor(value: Option<&Value>) -> Result<usize, ToolError> {
+    match value {
+        None | Some(Value::Null) => Ok(0),
+        Some(Value::Number(number)) => number
+            .as_u64()
+            .map(|offset| offset as usize)
+            .ok_or_else(|| ToolError::invalid_input("cursor must be a non-negative integer")),
+        Some(Value::String(text)) if text.trim().is_empty() => Ok(0),
+        Some(Value::String(text)) => text.trim().parse::<usize>().map_err(|_| {
+            ToolError::invalid_input(format!(
+                "invalid cursor `{text}`; pass the `next_cursor` value from the previous page"
+            ))
+        }),
+        Some(_) => Err(ToolError::invalid_input(
+            "cursor must be the `next_cursor` string from the previous page",
+        )),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Page<'a> {
+    text: &'a str,
+    next: Option<usize>,
+    start_line: usize,
+    end_line: usize,
+}
+
+fn page_at(content: &str, cursor: usize, max_bytes: usize) -> Result<Page<'_>, ToolError> {
+    if cursor > content.len() {
+        return Err(ToolError::invalid_input(format!(
+            "cursor {cursor} is past the end of the result ({} bytes)",
+            content.len()
+        )));
+    }
+    if !content.is_char_boundary(cursor) {
+        return Err(ToolError::invalid_input(format!(
+            "cursor {cursor} does not point at a page boundary; pass `next_cursor` unchanged"
+        )));
+    }
+
+    let mut end = (cursor + max_bytes).min(content.len());
+    if end < content.len() {
+        while !content.is_char_boundary(end) {
+            end -= 1;
+        }
+        let window = &content[cursor..end];
+        if let Some(newline) = window.rfind('\n')
+            && newline + 1 >= window.len() / 2
+        {
+            end = cursor + newline + 1;
+        }
+    }
+
+    let text = &content[cursor..end];
+    let start_line = content[..cursor].matches('\n').count() + 1;
+    let end_line = start_line + text.trim_end_matches('\n').matches('\n').count();
+    Ok(Page {
+        text,
+        next: (end < content.len()).then_some(end),
+        start_line,
+        end_line,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::sync::MutexGuard;
+    use tempfile::tempdir;
+
+    struct SpilloverRootGuard {
+        prior: Option<PathBuf>,
+    }
+
+    impl Drop for SpilloverRootGuard {
+        fn drop(&mut self) {
+            crate::tools::truncate::set_test_spillover_root(self.prior.take());
+        }
+    }
+
+    fn set_spillover_root(path: PathBuf) -> SpilloverRootGuard {
+        let prior = crate::tools::truncate::set_test_spillover_root(Some(path));
+        SpilloverRootGuard { prior }
+    }
+
+    fn test_lock() -> MutexGuard<'static, ()> {
+        crate::tools::truncate::TEST_SPILLOVER_GUARD
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn execute_tool(input: Value) -> Result<ToolResult, ToolError> {
+        let tmp = tempdir().unwrap();
+        let context = ToolContext::new(tmp.path());
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(ReadResultPageTool.execute(input, &context))
+    }
+
+    #[test]
+    fn pages_end_on_line_breaks_and_cover_the_whole_result() {
+        let content: String = (1..=200).map(|n| format!("line {n:03}\n")).collect();
+        let mut cursor = 0;
+        let mut rebuilt = String::new();
+        let mut pages = 0;
+        loop {
+            let page = page_at(&content, cursor, 300).unwrap();
+            assert!(page.text.ends_with('\n'));
+            rebuilt.push_str(page.text);
+            pages += 1;
+            match page.next {
+                Some(next) => cursor = next,
+                None => break,
+            }
+        }
+        assert_eq!(rebuilt, content);
+        assert!(pages > 1);
+    }
+
+    #[test]
+    fn page_never_splits_a_multibyte_char() {
+        let content = "é".repeat(400);
+        let page = page_at(&content, 0, 301).unwrap();
+        assert_eq!(page.text.len(), 300);
+        assert!(page_at(&content, 1, 300).is_err());
+    }
+
+    #[test]
+    fn cursor_past_end_is_rejected() {
+        assert!(page_at("abc", 4, 300).is_err());
+        let last = page_at("abc", 3, 300).unwrap();
+        assert_eq!(last.text, "");
+        assert_eq!(last.next, None);
+    }
+
+    #[test]
+    fn tool_walks_spilled_result_with_cursor() {
+        let _lock = test_lock();
+        let tmp = tempdir().unwrap();
+        let _guard = set_spillover_root(tmp.path().join("tool_outputs"));
+        let content: String = (1..=100).map(|n| format!("row {n}\n")).collect();
+        crate::tools::truncate::write_spillover("call-pages", &content).unwrap();
+
+        let first = execute_tool(json!({"result_id": "call-pages", "max_bytes": 256})).unwrap();
+        let first: Value = serde_json::from_str(&first.content).unwrap();
+        assert_eq!(first["start_line"], 1);
+        assert_eq!(first["done"], false);
+        let next = first["next_cursor"].as_str().unwrap().to_string();
+
+        let second = execute_tool(json!({
+            "result_id": "call-pages",
+            "cursor": next,
+            "max_bytes": 65536
+        }))
+        .unwrap();
+        let second: Value = serde_json::from_str(&second.content).unwrap();
+        assert_eq!(second["done"], true);
+        assert!(second["next_cursor"].is_null());
+        assert_eq!(second["end_line"], 100);
+        assert!(second["content"].as_str().unwrap().ends_with("row 100\n"));
+    }
+}
@@ -0,0 +1,661 @@
+//! `read_symbol` — read one function, type, or impl block instead of a range.
+//!
+//! The target is `path#name` (optionally qualified: `path#Type::method` or
+//! `path#Class.method`) or a line anchor `path:line`. Supported languages
+//! (Rust, Python, JavaScript, TypeScript, Go) are parsed with tree-sitter and
+//! the tool returns the enclosing definition, its leading doc comments and
+//! attributes, plus a few lines of context. Anything else — unknown
+//! extension, parse failure, or a name the parser cannot find — falls back to
+//! a plain numbered line range so the call still makes progress.
+
+use super::spec::{
+    ToolCapability, ToolContext, ToolError, ToolInput, ToolResult, ToolSpec, input_schema_for,
+    parse_input,
+};
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::fs;
+use std::path::Path;
+use tree_sitter::{Language, Node, Parser};
+
+const DEFAULT_CONTEXT_LINES: usize = 2;
+const MAX_CONTEXT_LINES: usize = 20;
+/// Lines shown around an anchor when no syntax tree is available.
+const FALLBACK_WINDOW: usize = 40;
+const MAX_SYMBOL_LINES: usize = 400;
+const MAX_VISIBLE_BYTES: usize = 24 * 1024;
+/// Other definitions with the same name listed after the returned one.
+const MAX_OTHER_MATCHES: usize = 5;
+
+/// Tool returning the source of a single symbol.
+pub struct ReadSymbolTool;
+
+/// `read_symbol` arguments.
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ReadSymbolInput {
+    /// `path#symbol` (e.g. `src/lib.rs#parse`, `src/app.py#App.run`) or
+    /// `path:line` (e.g. `src/lib.rs:120`)
+    target: String,
+    /// Lines of context before and after the symbol (default 2, max 20)
+    context_lines: Option<u64>,
+}
+
+impl ToolInput for ReadSymbolInput {
+    fn examples() -> Vec<Value> {
+        vec![
+            json!({"target": "src/main.rs#run"}),
+            json!({"target": "src/config.rs#Config::load"}),
+            json!({"target": "app/models.py:88", "context_lines": 0}),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Anchor {
+    Name(String),
+    Line(usize),
+}
+
+/// Split `path#name` / `path:line` into its parts.
+fn parse_target(target: &str) -> Result<(&str, Anchor), ToolError> {
+    let target = target.trim();
+    if let Some((path, name)) = target.rsplit_once('#') {
+        let name = name.trim();
+        if path.is_empty() || name.is_empty() {
+            return Err(ToolError::invalid_input(format!(
+                "target '{target}' must look like path#symbol"
+            )));
+        }
+        return Ok((path, Anchor::Name(name.to_string())));
+    }
+    if let Some((path, line)) = target.rsplit_once(':')
+        && let Ok(line) = line.trim().parse::<usize>()
+    {
+        if path.is_empty() || line == 0 {
+            return Err(ToolError::invalid_input(format!(
+                "target '{target}' must look like path:line with a 1-based line"
+            )));
+        }
+        return Ok((path, Anchor::Line(line)));
+    }
+    Err(ToolError::invalid_input(format!(
+        "target '{target}' needs a symbol or line anchor: path#symbol or path:line"
+    )))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lang {
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    Tsx,
+    Go,
+}
+
+impl Lang {
+    fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        Some(match ext.as_str() {
+            "rs" => Self::Rust,
+            "py" | "pyi" => Self::Python,
+            "js" | "jsx" | "mjs" | "cjs" => Self::JavaScript,
+            "ts" | "mts" | "cts" => Self::TypeScript,
+            "tsx" => Self::Tsx,
+            "go" => Self::Go,
+            _ => return None,
+        })
+    }
+
+    fn grammar(self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+        }
+    }
+
+    /// Node kinds that count as a readable definition.
+    fn is_symbol_kind(self, kind: &str) -> bool {
+        match self {
+            Self::Rust => matches!(
+                kind,
+                "function_item"
+                    | "function_signature_item"
+                    | "struct_item"
+                    | "enum_item"
+                    | "union_item"
+                    | "trait_item"
+                    | "impl_item"
+                    | "mod_item"
+                    | "const_item"
+                    | "static_item"
+                    | "type_item"
+                    | "macro_definition"
+            ),
+            Self::Python => matches!(kind, "function_definition" | "class_definition"),
+            Self::JavaScript | Self::TypeScript | Self::Tsx => matches!(
+                kind,
+                "function_declaration"
+                    | "generator_function_declaration"
+                    | "class_declaration"
+                    | "abstract_class_declaration"
+                    | "method_definition"
+                    | "interface_declaration"
+                    | "type_alias_declaration"
+                    | "enum_declaration"
+                    | "variable_declarator"
+            ),
+            Self::Go => matches!(
+                kind,
+                "function_declaration" | "method_declaration" | "type_spec" | "const_spec"
+            ),
+        }
+    }
+
+    /// Node kinds glued to the front of a definition (docs, attributes).
+    fn is_leading_kind(self, kind: &str) -> bool {
+        match self {
+            Self::Rust => matches!(kind, "line_comment" | "block_comment" | "attribute_item"),
+            Self::Python => false,
+            _ => kind == "comment",
+        }
+    }
+}
+
+/// One definition found in the syntax tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Symbol {
+    kind: String,
+    name: String,
+    /// Name of the closest enclosing definition (`impl Foo`, `class Bar`).
+    parent: Option<String>,
+    /// 0-based inclusive line span, including leading docs/attributes.
+    start: usize,
+    end: usize,
+}
+
+impl Symbol {
+    fn qualified_name(&self) -> String {
+        match &self.parent {
+            Some(parent) => format!("{parent}::{}", self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+fn node_text<'a>(node: Node<'_>, source: &'a str) -> &'a str {
+    source.get(node.byte_range()).unwrap_or_default()
+}
+
+/// Type name without generics or references: `&'a mut Foo<T>` → `Foo`.
+fn bare_type_name(text: &str) -> String {
+    let text = text.split('<').next().unwrap_or(text);
+    text.rsplit(|ch: char| ch.is_whitespace() || ch == '&' || ch == ':')
+        .find(|part| !part.is_empty() && !part.starts_with('\''))
+        .unwrap_or(text)
+        .to_string()
+}
+
+fn symbol_name(lang: Lang, node: Node<'_>, source: &str) -> Option<String> {
+    match node.kind() {
+        "impl_item" => node
+            .child_by_field_name("type")
+            .map(|ty| bare_type_name(node_text(ty, source))),
+        "variable_declarator" => {
+            // Only `const handler = () => {}` style bindings are definitions.
+            let value = node.child_by_field_name("value")?;
+            matches!(
+                value.kind(),
+                "arrow_function" | "function_expression" | "function" | "class"
+            )
+            .then(|| node.child_by_field_name("name"))
+            .flatten()
+            .map(|name| node_text(name, source).to_string())
+        }
+        _ => {
+            let name = node.child_by_field_name("name")?;
+            let text = node_text(name, source);
+            Some(if lang == Lang::Rust {
+                bare_type_name(text)
+            } else {
+                text.to_string()
+            })
+        }
+    }
+}
+
+/// The node whose lines should be shown for a definition: Python decorators,
+/// JS `export`/`const` wrappers, and Go `type (...)` groups with one spec
+/// widen the span to the statement that owns it.
+fn display_node(node: Node<'_>) -> Node<'_> {
+    let mut node = node;
+    while let Some(parent) = node.parent() {
+        let widen = match parent.kind() {
+            "decorated_definition" | "export_statement" => true,
+            "lexical_declaration"
+            | "variable_declaration"
+            | "type_declaration"
+            | "const_declaration" => parent.named_child_count() == 1,
+            _ => false,
+        };
+        if !widen {
+            break;
+        }
+        node = parent;
+    }
+    node
+}
+
+/// First line of the contiguous doc-comment/attribute block above `node`.
+fn leading_start(lang: Lang, node: Node<'_>) -> usize {
+    let mut start = node.start_position().row;
+    let mut prev = node.prev_sibling();
+    while let Some(sibling) = prev {
+        if !lang.is_leading_kind(sibling.kind()) || sibling.end_position().row + 1 < start {
+            break;
+        }
+        start = sibling.start_position().row;
+        prev = sibling.prev_sibling();
+    }
+    start
+}
+
+fn collect_symbols(lang: Lang, source: &str) -> Option<Vec<Symbol>> {
+    let mut parser = Parser::new();
+    parser.set_language(&lang.grammar()).ok()?;
+    let tree = parser.parse(source, None)?;
+    let mut symbols = Vec::new();
+    walk(lang, tree.root_node(), source, None, &mut symbols);
+    Some(symbols)
+}
+
+fn walk(lang: Lang, node: Node<'_>, source: &str, parent: Option<&str>, out: &mut Vec<Symbol>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let mut next_parent = parent.map(str::to_string);
+        if lang.is_symbol_kind(child.kind())
+            && let Some(name) = symbol_name(lang, child, source)
+        {
+            let shown = display_node(child);
+            out.push(Symbol {
+                kind: child.kind().to_string(),
+                name: name.clone(),
+                parent: parent.map(str::to_string),
+                start: leading_start(lang, shown),
+                end: shown.end_position().row,
+            });
+            if matches!(
+                child.kind(),
+                "impl_item"
+                    | "trait_item"
+                    | "mod_item"
+                    | "class_definition"
+                    | "class_declaration"
+                    | "abstract_class_declaration"
+                    | "interface_declaration"
+            ) {
+                next_parent = Some(name);
+            }
+        }
+        walk(lang, child, source, next_parent.as_deref(), out);
+    }
+}
+
+/// Definitions named `query`. `Type::method` and `Type.method` also require
+/// the enclosing definition to be `Type`; Go methods match on their
+/// receiver type.
+fn find_by_name<'a>(symbols: &'a [Symbol], query: &str, source: &str) -> Vec<&'a Symbol> {
+    let segments: Vec<&str> = query
+        .split("::")
+        .flat_map(|part| part.split('.'))
+        .filter(|part| !part.is_empty())
+        .collect();
+    let Some((name, qualifiers)) = segments.split_last() else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = source.lines().collect();
+    symbols
+        .iter()
+        .filter(|symbol| symbol.name == *name)
+        .filter(|symbol| match qualifiers.last() {
+            None => true,
+            Some(owner) => {
+                symbol.parent.as_deref() == Some(*owner)
+                    || (symbol.kind == "method_declaration"
+                        && lines.get(symbol.start..=symbol.end).is_some_and(|span| {
+                            span.iter()
+                                .find(|line| line.trim_start().starts_with("func"))
+                                .is_some_and(|line| line.contains(owner))
+                        }))
+            }
+        })
+        .collect()
+}
+
+/// Innermost definition whose span contains 0-based `line`.
+fn find_by_line(symbols: &[Symbol], line: usize) -> Option<&Symbol> {
+    symbols
+        .iter()
+        .filter(|symbol| symbol.start <= line && line <= symbol.end)
+        .min_by_key(|symbol| symbol.end - symbol.start)
+}
+
+/// First line that mentions `name` as a whole word, for the text fallback.
+fn find_text_line(lines: &[&str], name: &str) -> Option<usize> {
+    let is_word = |ch: char| ch == '_' || ch.is_alphanumeric();
+    lines.iter().position(|line| {
+        line.match_indices(name).any(|(idx, _)| {
+            let before = line[..idx].chars().next_back();
+            let after = line[idx + name.len()..].chars().next();
+            !before.is_some_and(is_word) && !after.is_some_and(is_word)
+        })
+    })
+}
+
+fn render_lines(lines: &[&str], start: usize, end: usize) -> (String, usize, bool) {
+    let end = end.min(start + MAX_SYMBOL_LINES - 1);
+    let mut numbered = String::new();
+    let mut last = start;
+    for (idx, line) in lines.iter().enumerate().take(end + 1).skip(start) {
+        let rendered = format!("{:>6}│ {line}\n", idx + 1);
+        if numbered.len() + rendered.len() > MAX_VISIBLE_BYTES {
+            return (numbered, last, true);
+        }
+        numbered.push_str(&rendered);
+        last = idx;
+    }
+    (numbered, last, false)
+}
+
+struct Slice<'a> {
+    attrs: String,
+    start: usize,
+    end: usize,
+    note: Option<String>,
+    also: &'a [&'a Symbol],
+}
+
+fn render(path_str: &str, lines: &[&str], slice: Slice<'_>) -> String {
+    let total_lines = lines.len();
+    let (numbered, last, by_bytes) = render_lines(lines, slice.start, slice.end);
+    let truncated = by_bytes || last < slice.end;
+    let mut out = format!(
+        "<symbol path=\"{path_str}\" {} lines=\"{}-{}\" total_lines=\"{total_lines}\" truncated=\"{truncated}\">\n",
+        slice.attrs,
+        slice.start + 1,
+        last + 1
+    );
+    if let Some(note) = slice.note {
+        out.push_str(&format!("[NOTE] {note}\n"));
+    }
+    out.push_str(&numbered);
+    if truncated {
+        out.push_str(&format!(
+            "\n[TRUNCATED] The symbol continues to line {}. Continue with read_file path=\"{path_str}\" start_line={}\n",
+            slice.end + 1,
+            last + 2
+        ));
+    }
+    if !slice.also.is_empty() {
+        let others: Vec<String> = slice
+            .also
+            .iter()
+            .take(MAX_OTHER_MATCHES)
+            .map(|symbol| {
+                format!(
+                    "{} ({}) at lines {}-{}",
+                    symbol.qualified_name(),
+                    symbol.kind,
+                    symbol.start + 1,
+                    symbol.end + 1
+                )
+            })
+            .collect();
+        out.push_str(&format!(
+            "\n[ALSO DEFINED] {}. Qualify the target (Type::name) or use path:line to pick one.\n",
+            others.join("; ")
+        ));
+    }
+    out.push_str("</symbol>");
+    out
+}
+
+fn read_symbol(
+    path_str: &str,
+    path: &Path,
+    source: &str,
+    anchor: &Anchor,
+    context: usize,
+) -> Result<String, ToolError> {
+    let lines: Vec<&str> = source.lines().collect();
+    if lines.is_empty() {
+        return Err(ToolError::execution_failed(format!("{path_str} is empty")));
+    }
+    let last_line = lines.len() - 1;
+    if let Anchor::Line(line) = anchor
+        && *line > lines.len()
+    {
+        return Err(ToolError::invalid_input(format!(
+            "line {line} is beyond the end of {path_str} ({} lines)",
+            lines.len()
+        )));
+    }
+
+    let lang = Lang::from_path(path);
+    let symbols = lang.and_then(|lang| collect_symbols(lang, source));
+    let with_context = |symbol: &Symbol| {
+        (
+            symbol.start.saturating_sub(context),
+            (symbol.end + context).min(last_line),
+        )
+    };
+
+    if let Some(symbols) = &symbols {
+        let found: Vec<&Symbol> = match anchor {
+            Anchor::Name(name) => find_by_name(symbols, name, source),
+            Anchor::Line(line) => find_by_line(symbols, line - 1).into_iter().collect(),
+        };
+        if let Some((symbol, also)) = found.split_first() {
+            let (start, end) = with_context(symbol);
+            let attrs = format!(
+                "kind=\"{}\" name=\"{}\"",
+                symbol.kind,
+                symbol.qualified_name()
+            );
+            return Ok(render(
+                path_str,
+                &lines,
+                Slice {
+                    attrs,
+                    start,
+                    end,
+                    note: None,
+                    also,
+                },
+            ));
+        }
+    }
+
+    // Plain-range fallback: no grammar, or no definition matched.
+    let reason = match (&symbols, anchor) {
+        (None, _) => "no syntax tree for this file type".to_string(),
+        (Some(_), Anchor::Name(name)) => format!("no definition named '{name}' was parsed"),
+        (Some(_), Anchor::Line(_)) => "line is not inside a definition".to_string(),
+    };
+    let center = match anchor {
+        Anchor::Line(line) => line - 1,
+        Anchor::Name(name) => {
+            let bare = name.rsplit([':', '.']).next().unwrap_or(name);
+            find_text_line(&lines, bare).ok_or_else(|| {
+                ToolError::execution_failed(format!(
+                    "'{name}' does not appear in {path_str} ({reason})"
+                ))
+            })?
+        }
+    };
+    let half = FALLBACK_WINDOW / 2;
+    let start = center.saturating_sub(context.max(half / 2));
+    let end = (center + half).min(last_line);
+    Ok(render(
+        path_str,
+        &lines,
+        Slice {
+            attrs: "kind=\"range\"".to_string(),
+            start,
+            end,
+            note: Some(format!(
+                "Plain line range: {reason}. Widen it with read_file if the definition is cut off."
+            )),
+            also: &[],
+        },
+    ))
+}
+
+#[async_trait]
+impl ToolSpec for ReadSymbolTool {
+    fn name(&self) -> &'static str {
+        "read_symbol"
+    }
+
+    fn description(&self) -> &'static str {
+        "Read one definition instead of a whole file or a guessed range. `target` is `path#name` (qualify with `Type::method` or `Class.method` when names repeat) or `path:line` for the innermost function/struct/class containing that line. Rust, Python, JavaScript, TypeScript, and Go are parsed, so the result is exactly the definition plus its doc comments, attributes, and `context_lines` of surrounding code. Other files, or names the parser cannot find, fall back to a numbered line range around the first match. Use this before targeted edits; use `read_file` when you need the whole file."
+    }
+
+    fn input_schema(&self) -> Value {
+        input_schema_for::<ReadSymbolInput>()
+    }
+
+    fn input_examples(&self) -> Vec<Value> {
+        ReadSymbolInput::examples()
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::ReadOnly, ToolCapability::Sandboxable]
+    }
+
+    fn supports_parallel(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let args: ReadSymbolInput = parse_input(&input)?;
+        let (path_str, anchor) = parse_target(&args.target)?;
+        let file_path = context.resolve_path(path_str)?;
+        let context_lines = args
+            .context_lines
+            .map_or(DEFAULT_CONTEXT_LINES, |n| {
+                usize::try_from(n).unwrap_or(usize::MAX)
+            })
+            .min(MAX_CONTEXT_LINES);
+
+        let source = fs::read_to_string(&file_path).map_err(|e| {
+            ToolError::execution_failed(format!("Failed to read {}: {}", file_path.display(), e))
+        })?;
+        let output = read_symbol(path_str, &file_path, &source, &anchor, context_lines)?;
+        Ok(ToolResult::success(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUST_SRC: &str = "use std::fmt;
+
+/// A point.
+#[derive(Debug)]
+pub struct Point {
+    x: i32,
+}
+
+impl Point {
+    /// Build one.
+    pub fn new(x: i32) -> Self {
+        Self { x }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, \"{}\", self.x)
+    }
+}
+
+fn new() {}
+";
+
+    fn read(path: &str, src: &str, target: &str) -> String {
+        let (_, anchor) = parse_target(target).unwrap();
+        read_symbol(path, Path::new(path), src, &anchor, 0).unwrap()
+    }
+
+    #[test]
+    fn targets_parse_names_and_line_anchors() {
+        assert_eq!(
+            parse_target("src/a.rs#Foo::bar").unwrap(),
+            ("src/a.rs", Anchor::Name("Foo::bar".to_string()))
+        );
+        assert_eq!(
+            parse_target("C:/x/a.rs:12").unwrap(),
+            ("C:/x/a.rs", Anchor::Line(12))
+        );
+        assert!(parse_target("src/a.rs").is_err());
+        assert!(parse_target("src/a.rs#").is_err());
+        assert!(parse_target("src/a.rs:0").is_err());
+    }
+
+    #[test]
+    fn rust_symbols_include_docs_and_resolve_qualified_names() {
+        let out = read("p.rs", RUST_SRC, "p.rs#Point");
+        assert!(out.contains("kind=\"struct_item\" name=\"Point\""), "{out}");
+        assert!(out.contains("lines=\"3-7\""), "{out}");
+        assert!(out.contains("/// A point."), "{out}");
+        assert!(!out.contains("impl Point"), "{out}");
+
+        let out = read("p.rs", RUST_SRC, "p.rs#Point::new");
+        assert!(out.contains("name=\"Point::new\" lines=\"10-13\""), "{out}");
+        assert!(!out.contains("[ALSO DEFINED]"), "{out}");
+
+        let out = read("p.rs", RUST_SRC, "p.rs#new");
+        assert!(out.contains("[ALSO DEFINED] new (function_item)"), "{out}");
+
+        let out = read("p.rs", RUST_SRC, "p.rs:18");
+        assert!(out.contains("name=\"Point::fmt\" lines=\"17-19\""), "{out}");
+    }
+
+    #[test]
+    fn python_and_typescript_definitions_widen_to_their_wrappers() {
+        let py = "import x\n\nclass App:\n    @property\n    def run(self):\n        return 1\n";
+        let out = read("a.py", py, "a.py#App.run");
+        assert!(out.contains("lines=\"4-6\""), "{out}");
+        assert!(out.contains("@property"), "{out}");
+
+        let ts = "// Adds.\nexport const add = (a: number, b: number) => a + b;\nlet x = 1;\n";
+        let out = read("a.ts", ts, "a.ts#add");
+        assert!(out.contains("lines=\"1-2\""), "{out}");
+        assert!(!out.contains("let x"), "{out}");
+    }
+
+    #[test]
+    fn unsupported_files_and_unknown_names_fall_back_to_ranges() {
+        let text: String = (1..=100).map(|n| format!("line {n}\n")).collect();
+        let out = read("notes.txt", &text, "notes.txt:50");
+        assert!(out.contains("kind=\"range\""), "{out}");
+        assert!(out.contains("no syntax tree"), "{out}");
+        assert!(out.contains("    50│ line 50"), "{out}");
+
+        let src = "fn main() {}\n// mentions helper here\n";
+        let out = read("m.rs", src, "m.rs#helper");
+        assert!(out.contains("no definition named 'helper'"), "{out}");
+        assert!(out.contains("mentions helper"), "{out}");
+
+        let (_, anchor) = parse_target("m.rs#missing").unwrap();
+        assert!(read_symbol("m.rs", Path::new("m.rs"), src, &anchor, 0).is_err());
+    }
+}
@@ -231,7 +231,7 @@ fn load_messages(
 }
 
 /// Concatenate all text-bearing content blocks of a message.
-fn message_text(message: &Message) -> String {
+pub(super) fn message_text(message: &Message) -> String {
     let mut out = String::new();
     let mut push = |s: &str| {
         if !out.is_empty() {
@@ -267,7 +267,7 @@ fn message_text(message: &Message) -> String {
 
 /// Lower-case, split on non-alphanumerics, drop short tokens. Same recipe as
 /// most lightweight BM25 implementations.
-fn tokenize(text: &str) -> Vec<String> {
+pub(super) fn tokenize(text: &str) -> Vec<String> {
     text.to_ascii_lowercase()
         .split(|c: char| !c.is_alphanumeric())
         .filter(|s| s.len() >= 2)
@@ -342,14 +342,14 @@ fn bm25_doc_score(
     score
 }
 
-fn round_score(score: f64) -> f64 {
+pub(super) fn round_score(score: f64) -> f64 {
     (score * 1000.0).round() / 1000.0
 }
 
 /// Find the substring of `text` of at most `window_chars` characters that
 /// contains the densest cluster of query tokens. Returns it with `…` markers
 /// when truncated. Falls back to a head-of-text excerpt when no tokens hit.
-fn best_window(text: &str, query_tokens: &[String], window_chars: usize) -> String {
+pub(super) fn best_window(text: &str, query_tokens: &[String], window_chars: usize) -> String {
     let lower = text.to_ascii_lowercase();
     let mut hit_positions: Vec<usize> = Vec::new();
     for token in query_tokens {
@@ -0,0 +1,301 @@
+//! `recall_history` tool — read messages that compaction archived.
+//!
+//! Companion to tiered compaction: each digest in the system prompt names
+//! the archive segments it summarizes. When a digest is too coarse, the
+//! model calls this tool to list segments, page through one verbatim, or
+//! find the archived messages that mention a phrase.
+
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::recall_archive::message_text;
+use super::spec::{
+    ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolResult, ToolSpec,
+    optional_str, optional_u64,
+};
+use crate::history_archive::HistoryArchive;
+use crate::models::Message;
+
+const DEFAULT_COUNT: usize = 20;
+const HARD_MAX_COUNT: usize = 50;
+/// Output budget per call; long segments are paged with `next_start`.
+const MAX_OUTPUT_CHARS: usize = 32_000;
+const PREVIEW_CHARS: usize = 160;
+
+pub struct RecallHistoryTool;
+
+#[derive(Debug, Clone, Serialize)]
+struct RecalledMessage {
+    segment: u32,
+    /// 0-based message index within the segment.
+    index: usize,
+    role: String,
+    text: String,
+}
+
+#[async_trait]
+impl ToolSpec for RecallHistoryTool {
+    fn name(&self) -> &'static str {
+        "recall_history"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fetch verbatim messages that context compaction summarized away. Each conversation \
+         digest lists its archived segments; pass `segment` to page through one, `query` to \
+         find messages mentioning a phrase, or nothing to list segments. Use when a digest \
+         lacks a detail you need (exact code, error text, a decision's rationale)."
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "segment": {
+                    "type": "integer",
+                    "description": "Archive segment to read (from a digest header). Omit to list segments, or with `query` to search all of them."
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Optional: only return messages containing every word of this phrase (case-insensitive)."
+                },
+                "start": {
+                    "type": "integer",
+                    "description": "0-based message index to start from within the segment (default 0). Use `next_start` from a previous call to continue."
+                },
+                "count": {
+                    "type": "integer",
+                    "description": "Maximum messages to return (default 20, hard-capped at 50)."
+                }
+            }
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::ReadOnly]
+    }
+
+    fn approval_requirement(&self) -> ApprovalRequirement {
+        ApprovalRequirement::Auto
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let archive = HistoryArchive::for_session(&context.state_namespace).map_err(|err| {
+            ToolError::execution_failed(format!("Failed to open history archive: {err}"))
+        })?;
+        let payload = recall(&archive, &input)?;
+        Ok(ToolResult::success(payload.to_string()))
+    }
+}
+
+fn recall(archive: &HistoryArchive, input: &Value) -> Result<Value, ToolError> {
+    let segment = input
+        .get("segment")
+        .and_then(Value::as_u64)
+        .map(|n| u32::try_from(n).unwrap_or(u32::MAX));
+    let query = optional_str(input, "query")
+        .map(str::trim)
+        .filter(|q| !q.is_empty());
+    let start = optional_u64(input, "start", 0) as usize;
+    let count =
+        (optional_u64(input, "count", DEFAULT_COUNT as u64) as usize).clamp(1, HARD_MAX_COUNT);
+
+    let headers = archive.segments().map_err(|err| {
+        ToolError::execution_failed(format!("Failed to read history archive: {err}"))
+    })?;
+    if headers.is_empty() {
+        return Ok(json!({
+            "messages": [],
+            "note": "Nothing has been archived yet. Compaction has not summarized any messages in this session."
+        }));
+    }
+
+    if segment.is_none() && query.is_none() {
+        let mut segments = Vec::new();
+        for header in &headers {
+            let (_, messages) = read_segment(archive, header.segment)?;
+            let preview = messages
+                .iter()
+                .find(|m| m.role == "user")
+                .map(|m| preview(&message_text(m)))
+                .unwrap_or_default();
+            segments.push(json!({
+                "segment": header.segment,
+                "messages": header.message_count,
+                "archived": header.archived.to_rfc3339(),
+                "first_user_message": preview,
+            }));
+        }
+        return Ok(json!({ "segments": segments }));
+    }
+
+    let targets: Vec<u32> = match segment {
+        Some(segment) => {
+            if !headers.iter().any(|h| h.segment == segment) {
+                let known: Vec<u32> = headers.iter().map(|h| h.segment).collect();
+                return Err(ToolError::invalid_input(format!(
+                    "segment {segment} does not exist; archived segments: {known:?}"
+                )));
+            }
+            vec![segment]
+        }
+        None => headers.iter().map(|h| h.segment).collect(),
+    };
+    let words: Vec<String> = query
+        .map(|q| q.split_whitespace().map(str::to_lowercase).collect())
+        .unwrap_or_default();
+
+    let mut out: Vec<RecalledMessage> = Vec::new();
+    let mut used_chars = 0usize;
+    let mut next: Option<(u32, usize)> = None;
+    'segments: for target in targets {
+        let (_, messages) = read_segment(archive, target)?;
+        let first = if Some(target) == segment { start } else { 0 };
+        for (index, message) in messages.iter().enumerate().skip(first) {
+            let text = message_text(message);
+            if !words.is_empty() {
+                let haystack = text.to_lowercase();
+                if !words.iter().all(|w| haystack.contains(w.as_str())) {
+                    continue;
+                }
+            }
+            let chars = text.chars().count();
+            if out.len() == count || (!out.is_empty() && used_chars + chars > MAX_OUTPUT_CHARS) {
+                next = Some((target, index));
+                break 'segments;
+            }
+            used_chars += chars;
+            out.push(recalled(target, index, message, text));
+        }
+    }
+
+    let mut payload = json!({ "messages": out });
+    if let Some(segment) = segment {
+        payload["segment"] = json!(segment);
+    }
+    if let Some(query) = query {
+        payload["query"] = json!(query);
+    }
+    if let Some((next_segment, next_start)) = next {
+        payload["next_segment"] = json!(next_segment);
+        payload["next_start"] = json!(next_start);
+    }
+    if out.is_empty() {
+        payload["note"] = json!(match query {
+            Some(_) => "No archived message matches the query.",
+            None => "No messages at or after `start` in this segment.",
+        });
+    }
+    Ok(payload)
+}
+
+fn read_segment(archive: &HistoryArchive, segment: u32) -> Result<(u32, Vec<Message>), ToolError> {
+    archive
+        .read(segment)
+        .map(|(header, messages)| (header.segment, messages))
+        .map_err(|err| {
+            ToolError::execution_failed(format!("Failed to read segment {segment}: {err}"))
+        })
+}
+
+fn recalled(segment: u32, index: usize, message: &Message, text: String) -> RecalledMessage {
+    // A single message larger than the whole budget is still returned, cut
+    // at the budget, so paging always makes progress.
+    let text = if text.chars().count() > MAX_OUTPUT_CHARS {
+        let cut: String = text.chars().take(MAX_OUTPUT_CHARS).collect();
+        format!("{cut}\n[… message truncated at {MAX_OUTPUT_CHARS} chars]")
+    } else {
+        text
+    };
+    RecalledMessage {
+        segment,
+        index,
+        role: message.role.clone(),
+        text,
+    }
+}
+
+fn preview(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default();
+    if line.chars().count() > PREVIEW_CHARS {
+        format!("{}…", line.chars().take(PREVIEW_CHARS).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ContentBlock;
+    use tempfile::TempDir;
+
+    fn msg(role: &str, text: &str) -> Message {
+        Message {
+            role: role.to_string(),
+            content: vec![ContentBlock::Text {
+                text: text.to_string(),
+                cache_control: None,
+            }],
+        }
+    }
+
+    fn archive(tmp: &TempDir) -> HistoryArchive {
+        let archive = HistoryArchive::at("s1", tmp.path().join("history"));
+        archive
+            .append(&[
+                msg("user", "Fix the tokenizer panic on empty input"),
+                msg(
+                    "assistant",
+                    "The panic comes from `chars().nth(0).unwrap()` in lexer.rs",
+                ),
+                msg("user", "ship it"),
+            ])
+            .unwrap();
+        archive
+            .append(&[msg("user", "Now rename Lexer to Scanner")])
+            .unwrap();
+        archive
+    }
+
+    #[test]
+    fn lists_pages_and_searches_segments() {
+        let tmp = TempDir::new().unwrap();
+        let empty = HistoryArchive::at("s1", tmp.path().join("none"));
+        let payload = recall(&empty, &json!({})).unwrap();
+        assert!(
+            payload["note"]
+                .as_str()
+                .unwrap()
+                .contains("Nothing has been archived")
+        );
+
+        let archive = archive(&tmp);
+        let listing = recall(&archive, &json!({})).unwrap();
+        assert_eq!(listing["segments"][0]["segment"], 1);
+        assert_eq!(listing["segments"][0]["messages"], 3);
+        assert_eq!(
+            listing["segments"][1]["first_user_message"],
+            "Now rename Lexer to Scanner"
+        );
+
+        let page = recall(&archive, &json!({"segment": 1, "start": 1, "count": 1})).unwrap();
+        assert_eq!(page["messages"][0]["index"], 1);
+        assert_eq!(
+            page["messages"][0]["text"],
+            "The panic comes from `chars().nth(0).unwrap()` in lexer.rs"
+        );
+        assert_eq!(page["next_start"], 2);
+
+        let hits = recall(&archive, &json!({"query": "LEXER"})).unwrap();
+        let found: Vec<(u64, u64)> = hits["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| (m["segment"].as_u64().unwrap(), m["index"].as_u64().unwrap()))
+            .collect();
+        assert_eq!(found, vec![(1, 1), (2, 0)]);
+
+        assert!(recall(&archive, &json!({"segment": 9})).is_err());
+    }
+}
@@ -132,7 +132,7 @@ impl ToolRegistry {
             .ok_or_else(|| ToolError::not_available(format!("tool '{name}' is not registered")))?;
 
         let ctx = context_override.unwrap_or(&self.context);
-        let result = tool.execute(input.clone(), ctx).await?;
+        let result = ctx.mask_session_secrets(tool.execute(input.clone(), ctx).await?);
 
         // Large-output routing (#548): if the result exceeds the threshold and
         // the caller did not request `raw=true`, synthesise via the workshop.
@@ -402,6 +402,15 @@ impl ToolRegistryBuilder {
         Self { tools: Vec::new() }
     }
 
+    /// Names of the tools added so far, in registration order.
+    #[must_use]
+    pub fn tool_names(&self) -> Vec<String> {
+        self.tools
+            .iter()
+            .map(|tool| tool.name().to_string())
+            .collect()
+    }
+
     /// Add a custom tool.
     #[must_use]
     pub fn with_tool(mut self, tool: Arc<dyn ToolSpec>) -> Self {
@@ -414,6 +423,7 @@ impl ToolRegistryBuilder {
     pub fn with_file_tools(self) -> Self {
         use super::file::{EditFileTool, ListDirTool, ReadFileTool, WriteFileTool};
         self.with_tool(Arc::new(ReadFileTool))
+            .with_tool(Arc::new(super::read_symbol::ReadSymbolTool))
             .with_tool(Arc::new(WriteFileTool))
             .with_tool(Arc::new(EditFileTool))
             .with_tool(Arc::new(ListDirTool))
@@ -424,22 +434,27 @@ impl ToolRegistryBuilder {
     pub fn with_read_only_file_tools(self) -> Self {
         use super::file::{ListDirTool, ReadFileTool};
         self.with_tool(Arc::new(ReadFileTool))
+            .with_tool(Arc::new(super::read_symbol::ReadSymbolTool))
             .with_tool(Arc::new(ListDirTool))
             .with_tool(Arc::new(
                 super::tool_result_retrieval::RetrieveToolResultTool,
             ))
+            .with_tool(Arc::new(super::read_result_page::ReadResultPageTool))
     }
 
     /// Include shell execution tool.
     #[must_use]
     pub fn with_shell_tools(self) -> Self {
         use super::shell::{ExecShellTool, ShellCancelTool, ShellInteractTool, ShellWaitTool};
+        use super::shell_session::{ShellListTool, ShellOpenTool};
         self.with_tool(Arc::new(ExecShellTool))
             .with_tool(Arc::new(ShellWaitTool::new("exec_shell_wait")))
             .with_tool(Arc::new(ShellInteractTool::new("exec_shell_interact")))
             .with_tool(Arc::new(ShellCancelTool))
             .with_tool(Arc::new(ShellWaitTool::new("exec_wait")))
             .with_tool(Arc::new(ShellInteractTool::new("exec_interact")))
+            .with_tool(Arc::new(ShellOpenTool))
+            .with_tool(Arc::new(ShellListTool))
     }
 
     /// Include search tools (`grep_files`).
@@ -468,11 +483,14 @@ impl ToolRegistryBuilder {
             .with_tool(Arc::new(GitBlameTool))
     }
 
-    /// Include workspace diagnostics tool.
+    /// Include the environment diagnostics tools (`diagnostics`,
+    /// `system_info`).
     #[must_use]
     pub fn with_diagnostics_tool(self) -> Self {
         use super::diagnostics::DiagnosticsTool;
+        use super::system_info::SystemInfoTool;
         self.with_tool(Arc::new(DiagnosticsTool))
+            .with_tool(Arc::new(SystemInfoTool))
     }
 
     /// Include the `pandoc_convert` tool only when the `pandoc`
@@ -503,6 +521,15 @@ impl ToolRegistryBuilder {
         }
     }
 
+    /// Include the `capture_terminal` tool. Opt-in through
+    /// `[features] terminal_capture`: the engine only calls this when the
+    /// user has enabled it, and every call still needs approval.
+    #[must_use]
+    pub fn with_terminal_capture_tool(self) -> Self {
+        use super::terminal_capture::CaptureTerminalTool;
+        self.with_tool(Arc::new(CaptureTerminalTool))
+    }
+
     /// Include the `load_skill` tool (#434) so the model can pull a
     /// SKILL.md body + companion file list into context with one
     /// call instead of `read_file` + `list_dir` against the path
@@ -520,6 +547,13 @@ impl ToolRegistryBuilder {
         self.with_tool(Arc::new(ProjectMapTool))
     }
 
+    /// Include `docs_search`, ranked search over the project's documentation.
+    #[must_use]
+    pub fn with_docs_search_tool(self) -> Self {
+        use super::docs_search::DocsSearchTool;
+        self.with_tool(Arc::new(DocsSearchTool))
+    }
+
     /// Include cargo test runner tool.
     #[must_use]
     pub fn with_test_runner_tool(self) -> Self {
@@ -534,11 +568,25 @@ impl ToolRegistryBuilder {
         self.with_tool(Arc::new(ValidateDataTool))
     }
 
-    /// Include retrieval for spilled historical tool results.
+    /// Include `run_snippet`, the sandboxed Python/JavaScript scratchpad,
+    /// when this host can keep it off the network.
+    #[must_use]
+    pub fn with_snippet_tool(self) -> Self {
+        use super::snippet::RunSnippetTool;
+        if !super::snippet::is_available() {
+            return self;
+        }
+        self.with_tool(Arc::new(RunSnippetTool))
+    }
+
+    /// Include retrieval and cursor paging for spilled historical tool
+    /// results.
     #[must_use]
     pub fn with_tool_result_retrieval_tool(self) -> Self {
+        use super::read_result_page::ReadResultPageTool;
         use super::tool_result_retrieval::RetrieveToolResultTool;
         self.with_tool(Arc::new(RetrieveToolResultTool))
+            .with_tool(Arc::new(ReadResultPageTool))
     }
 
     /// Include durable task, gate, PR-attempt, GitHub, and automation tools.
@@ -604,6 +652,7 @@ impl ToolRegistryBuilder {
     /// Include web search tools.
     #[must_use]
     pub fn with_web_tools(self) -> Self {
+        use super::crate_docs::CrateDocsTool;
         use super::fetch_url::FetchUrlTool;
         use super::finance::FinanceTool;
         use super::web_run::WebRunTool;
@@ -612,6 +661,30 @@ impl ToolRegistryBuilder {
             .with_tool(Arc::new(FetchUrlTool))
             .with_tool(Arc::new(FinanceTool::new()))
             .with_tool(Arc::new(WebRunTool))
+            .with_tool(Arc::new(CrateDocsTool))
+    }
+
+    /// Register project command aliases as tools named after them. Aliases
+    /// that would shadow an already-registered tool are skipped, as are
+    /// shell aliases when `allow_shell` is false.
+    #[must_use]
+    pub fn with_command_alias_tools(
+        mut self,
+        aliases: Vec<crate::command_aliases::CommandAlias>,
+        allow_shell: bool,
+    ) -> Self {
+        use super::command_alias::CommandAliasTool;
+        use crate::command_aliases::AliasAction;
+        for alias in aliases {
+            if matches!(alias.action, AliasAction::Shell(_)) && !allow_shell {
+                continue;
+            }
+            if self.tools.iter().any(|tool| tool.name() == alias.name) {
+                continue;
+            }
+            self = self.with_tool(Arc::new(CommandAliasTool::new(alias)));
+        }
+        self
     }
 
     /// Register the `image_analyze` vision tool.
@@ -691,6 +764,14 @@ impl ToolRegistryBuilder {
         self.with_tool(Arc::new(RecallArchiveTool))
     }
 
+    /// Include the `recall_history` tool — reads the verbatim messages that
+    /// summary compaction archived.
+    #[must_use]
+    pub fn with_recall_history_tool(self) -> Self {
+        use super::recall_history::RecallHistoryTool;
+        self.with_tool(Arc::new(RecallHistoryTool))
+    }
+
     /// Include note tool.
     #[must_use]
     pub fn with_note_tool(self) -> Self {
@@ -698,11 +779,12 @@ impl ToolRegistryBuilder {
         self.with_tool(Arc::new(NoteTool))
     }
 
-    /// Include the FIM (Fill-in-the-Middle) edit tool.
+    /// Include the FIM (Fill-in-the-Middle) edit tools.
     #[must_use]
     pub fn with_fim_tool(self, client: Option<DeepSeekClient>, model: String) -> Self {
-        use super::fim::FimEditTool;
-        self.with_tool(Arc::new(FimEditTool::new(client, model)))
+        use super::fim::{EditWithFimTool, FimEditTool};
+        self.with_tool(Arc::new(FimEditTool::new(client.clone(), model.clone())))
+            .with_tool(Arc::new(EditWithFimTool::new(client, model)))
     }
 
     /// Include the `remember` tool — model-callable bullet-add into the
@@ -770,9 +852,11 @@ impl ToolRegistryBuilder {
             .with_git_history_tools()
             .with_diagnostics_tool()
             .with_project_tools()
+            .with_docs_search_tool()
             .with_skill_tools()
             .with_test_runner_tool()
             .with_validation_tools()
+            .with_snippet_tool()
             .with_tool_result_retrieval_tool()
             .with_handle_tools()
             .with_runtime_task_tools()
@@ -815,6 +899,7 @@ impl ToolRegistryBuilder {
             .with_review_tool(client.clone(), model.clone())
             .with_rlm_tool(client, model)
             .with_recall_archive_tool()
+            .with_recall_history_tool()
             .with_subagent_tools(manager, runtime)
     }
 
@@ -1354,6 +1439,159 @@ mod tests {
         assert!(registry.contains("finance"));
     }
 
+    /// Check `value` against the JSON Schema subset tool schemas use (`type`,
+    /// `enum`, `properties`, `required`, `additionalProperties: false`,
+    /// `items`, `minimum`/`maximum`, `anyOf`/`oneOf`).
+    fn schema_violation(schema: &Value, value: &Value, at: &str) -> Option<String> {
+        if let Some(types) = schema.get("type") {
+            let allowed: Vec<&str> = match types {
+                Value::String(ty) => vec![ty.as_str()],
+                Value::Array(tys) => tys.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            let matches = |ty: &str| match ty {
+                "object" => value.is_object(),
+                "array" => value.is_array(),
+                "string" => value.is_string(),
+                "boolean" => value.is_boolean(),
+                "integer" => value.is_i64() || value.is_u64(),
+                "number" => value.is_number(),
+                "null" => value.is_null(),
+                _ => true,
+            };
+            if !allowed.is_empty() && !allowed.iter().any(|ty| matches(ty)) {
+                return Some(format!("{at}: expected {allowed:?}, got {value}"));
+            }
+        }
+        if let Some(Value::Array(options)) = schema.get("enum")
+            && !options.contains(value)
+        {
+            return Some(format!("{at}: {value} is not one of {options:?}"));
+        }
+        if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64)
+            && value.as_f64().is_some_and(|number| number < minimum)
+        {
+            return Some(format!("{at}: {value} is below {minimum}"));
+        }
+        if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64)
+            && value.as_f64().is_some_and(|number| number > maximum)
+        {
+            return Some(format!("{at}: {value} is above {maximum}"));
+        }
+        for key in ["anyOf", "oneOf"] {
+            if let Some(Value::Array(branches)) = schema.get(key)
+                && branches
+                    .iter()
+                    .all(|branch| schema_violation(branch, value, at).is_some())
+            {
+                return Some(format!("{at}: matches no {key} branch"));
+            }
+        }
+        if let Some(object) = value.as_object() {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for required in schema
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+            {
+                if !object.contains_key(required) {
+                    return Some(format!("{at}: missing required '{required}'"));
+                }
+            }
+            for (key, child) in object {
+                match properties.and_then(|properties| properties.get(key)) {
+                    Some(child_schema) => {
+                        if let Some(violation) =
+                            schema_violation(child_schema, child, &format!("{at}.{key}"))
+                        {
+                            return Some(violation);
+                        }
+                    }
+                    None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                        return Some(format!("{at}: unexpected property '{key}'"));
+                    }
+                    None => {}
+                }
+            }
+        }
+        if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+            for (index, item) in array.iter().enumerate() {
+                if let Some(violation) = schema_violation(items, item, &format!("{at}[{index}]")) {
+                    return Some(violation);
+                }
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn every_registered_tool_schema_accepts_its_examples() {
+        let tmp = tempdir().expect("tempdir");
+        let ctx = ToolContext::new(tmp.path().to_path_buf());
+        let registry = ToolRegistryBuilder::new()
+            .with_agent_tools(true)
+            .with_todo_tool(crate::tools::todo::new_shared_todo_list())
+            .with_plan_tool(crate::tools::plan::new_shared_plan_state())
+            .with_review_tool(None, "deepseek-v4-flash".to_string())
+            .with_rlm_tool(None, "deepseek-v4-flash".to_string())
+            .with_recall_archive_tool()
+            .with_recall_history_tool()
+            .with_remember_tool()
+            .with_notify_tool()
+            .with_terminal_capture_tool()
+            .build(ctx);
+
+        let mut typed_tools = Vec::new();
+        for tool in registry.all() {
+            let schema = tool.input_schema();
+            let examples = tool.input_examples();
+            for example in &examples {
+                if let Some(violation) = schema_violation(&schema, example, tool.name()) {
+                    panic!(
+                        "{} rejects its own example {example}: {violation}",
+                        tool.name()
+                    );
+                }
+            }
+            if !examples.is_empty() {
+                typed_tools.push(tool.name().to_string());
+            }
+        }
+        typed_tools.sort();
+        assert_eq!(
+            typed_tools,
+            [
+                "capture_terminal",
+                "crate_docs",
+                "docs_search",
+                "edit_file",
+                "list_dir",
+                "read_file",
+                "read_symbol",
+                "shell_open",
+                "write_file"
+            ]
+        );
+    }
+
+    #[test]
+    fn schema_violation_catches_wrong_types_and_missing_fields() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "path": { "type": "string" },
+                "mode": { "type": "string", "enum": ["a", "b"] }
+            },
+            "required": ["path"]
+        });
+        assert!(schema_violation(&schema, &json!({"path": "x", "mode": "a"}), "t").is_none());
+        assert!(schema_violation(&schema, &json!({"mode": "a"}), "t").is_some());
+        assert!(schema_violation(&schema, &json!({"path": 1}), "t").is_some());
+        assert!(schema_violation(&schema, &json!({"path": "x", "mode": "c"}), "t").is_some());
+    }
+
     #[test]
     fn test_builder_with_agent_tools_includes_finance() {
         let tmp = tempdir().expect("tempdir");
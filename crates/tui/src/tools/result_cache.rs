@@ -0,0 +1,374 @@
+//! Result cache for repeated `read_file`, `grep_files`, and `git_status`
+//! calls.
+//!
+//! Models routinely re-invoke these with identical arguments inside one turn
+//! (and across stream retries). Just before a tool batch runs, the engine
+//! consults this cache: a hit skips execution and replays the stored
+//! [`ToolResult`] tagged with `"cache_hit": true` so the transcript can mark
+//! it.
+//!
+//! Keys combine the tool name, a canonical digest of the arguments (shared
+//! with [`crate::tools::approval_cache`]), and a stamp of the working tree
+//! the call depends on, taken when the call is about to execute:
+//!
+//! - `read_file`: mtime and size of the file being read.
+//! - `grep_files`: path, mtime, and size of every file the search would read.
+//! - `git_status`: path, mtime, and size of every non-ignored file in the
+//!   repository, plus `HEAD`, the ref it points at, and the index.
+//!
+//! Any edit, new file, deletion, commit, or staging change therefore yields a
+//! new key. The engine additionally drops every entry whenever a
+//! non-read-only tool (a write, shell command, or sub-agent) runs.
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::approval_cache::hash_json_value;
+use super::spec::ToolResult;
+
+/// Upper bound on cached results so a long session-scoped cache cannot grow
+/// without limit. New results are simply not cached once it is full.
+const MAX_CACHED_RESULTS: usize = 256;
+
+/// Tools whose output depends only on their arguments and the working tree
+/// state captured by their key.
+const CACHEABLE_TOOLS: &[&str] = &["read_file", "grep_files", "git_status"];
+
+/// How long cached tool results live. Configured via
+/// `[tool_cache] scope` in `~/.deepseek/config.toml`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolCacheScope {
+    /// Never cache tool results.
+    Off,
+    /// Reuse results within a single user turn (default).
+    #[default]
+    Turn,
+    /// Keep results across turns until the workspace changes or the user
+    /// runs `/cache clear`.
+    Session,
+}
+
+/// Cache key for one tool invocation against one workspace state.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResultCacheKey(String);
+
+#[derive(Debug, Default)]
+pub struct ToolResultCache {
+    scope: ToolCacheScope,
+    entries: HashMap<ResultCacheKey, ToolResult>,
+}
+
+impl ToolResultCache {
+    #[must_use]
+    pub fn new(scope: ToolCacheScope) -> Self {
+        Self {
+            scope,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Called at the start of every user turn; turn-scoped caches reset here.
+    pub fn begin_turn(&mut self) {
+        if self.scope == ToolCacheScope::Turn {
+            self.entries.clear();
+        }
+    }
+
+    /// Build the key for a call against the workspace as it is right now,
+    /// or `None` when the call is not cacheable (including when the files it
+    /// depends on can't be stat'ed).
+    #[must_use]
+    pub fn key_for(
+        &self,
+        tool_name: &str,
+        input: &Value,
+        workspace: &Path,
+    ) -> Option<ResultCacheKey> {
+        if self.scope == ToolCacheScope::Off || !is_cacheable_tool(tool_name) {
+            return None;
+        }
+        let stamp = match tool_name {
+            "read_file" => {
+                let path = input.get("path").and_then(Value::as_str)?;
+                let (modified, len) = stat_stamp(&workspace.join(path))?;
+                format!("{modified}:{len}")
+            }
+            "grep_files" => grep_stamp(input, workspace)?,
+            "git_status" => git_stamp(workspace)?,
+            _ => return None,
+        };
+        Some(ResultCacheKey(format!(
+            "{tool_name}:{}:{stamp}",
+            hash_json_value(input),
+        )))
+    }
+
+    /// Return the cached result for `key`, annotated as a cache hit.
+    #[must_use]
+    pub fn lookup(&self, key: &ResultCacheKey) -> Option<ToolResult> {
+        let cached = self.entries.get(key)?;
+        Some(mark_cache_hit(cached.clone()))
+    }
+
+    /// Store a freshly executed result. Failed results are never cached.
+    pub fn insert(&mut self, key: ResultCacheKey, result: &ToolResult) {
+        if !result.success || self.entries.len() >= MAX_CACHED_RESULTS {
+            return;
+        }
+        self.entries.insert(key, result.clone());
+    }
+
+    /// Drop every entry, returning how many were removed.
+    pub fn clear(&mut self) -> usize {
+        let removed = self.entries.len();
+        self.entries.clear();
+        removed
+    }
+}
+
+/// Whether `tool_name` is on the deterministic read-only allowlist.
+#[must_use]
+pub fn is_cacheable_tool(tool_name: &str) -> bool {
+    CACHEABLE_TOOLS.contains(&tool_name)
+}
+
+/// Whether a tool result was served from the cache.
+#[must_use]
+pub fn is_cache_hit(result: &ToolResult) -> bool {
+    result
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get("cache_hit"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+fn mark_cache_hit(mut result: ToolResult) -> ToolResult {
+    match result.metadata.as_mut() {
+        Some(Value::Object(map)) => {
+            map.insert("cache_hit".to_string(), Value::Bool(true));
+        }
+        _ => result.metadata = Some(serde_json::json!({ "cache_hit": true })),
+    }
+    result
+}
+
+/// Stamp of the files a `grep_files` call would read.
+fn grep_stamp(input: &Value, workspace: &Path) -> Option<String> {
+    let root = workspace.join(input.get("path").and_then(Value::as_str).unwrap_or("."));
+    let (include, exclude) = super::search::scope_patterns(input);
+    let files = super::search::collect_files(&root, &include, &exclude).ok()?;
+    Some(tree_stamp(files))
+}
+
+/// Stamp of everything `git status` looks at: non-ignored files in the
+/// repository containing `workspace`, `HEAD` and the ref it names, and the
+/// index. Repositories whose `.git` is a file (worktrees, submodules) are not
+/// cached.
+fn git_stamp(workspace: &Path) -> Option<String> {
+    let repo = workspace
+        .ancestors()
+        .find(|dir| dir.join(".git").is_dir())?;
+    let git_dir = repo.join(".git");
+    let head = std::fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let mut files = vec![
+        git_dir.join("HEAD"),
+        git_dir.join("index"),
+        git_dir.join("packed-refs"),
+    ];
+    if let Some(reference) = head.trim().strip_prefix("ref: ") {
+        files.push(git_dir.join(reference));
+    }
+    let walker = ignore::WalkBuilder::new(repo)
+        .hidden(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+    for entry in walker {
+        let entry = entry.ok()?;
+        if entry.file_type().is_some_and(|kind| kind.is_file()) {
+            files.push(entry.into_path());
+        }
+    }
+    let mut hasher = DefaultHasher::new();
+    head.hash(&mut hasher);
+    Some(format!("{:016x}:{}", hasher.finish(), tree_stamp(files)))
+}
+
+/// Digest of each file's path, mtime, and size. Files that vanish between
+/// listing and stat hash as absent rather than failing the key.
+fn tree_stamp(mut files: Vec<PathBuf>) -> String {
+    files.sort();
+    let mut hasher = DefaultHasher::new();
+    for file in &files {
+        file.hash(&mut hasher);
+        stat_stamp(file).hash(&mut hasher);
+    }
+    format!("{}:{:016x}", files.len(), hasher.finish())
+}
+
+fn stat_stamp(path: &Path) -> Option<(u128, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_nanos();
+    Some((modified, metadata.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn identical_calls_hit_and_are_annotated() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("a.txt"), "hello").unwrap();
+        let mut cache = ToolResultCache::new(ToolCacheScope::Turn);
+        let input = json!({"path": "a.txt", "start_line": 1});
+
+        let key = cache.key_for("read_file", &input, tmp.path()).unwrap();
+        assert!(cache.lookup(&key).is_none());
+        cache.insert(key, &ToolResult::success("hello"));
+
+        let reordered = json!({"start_line": 1, "path": "a.txt"});
+        let key = cache.key_for("read_file", &reordered, tmp.path()).unwrap();
+        let hit = cache.lookup(&key).expect("cache hit");
+        assert_eq!(hit.content, "hello");
+        assert!(is_cache_hit(&hit));
+    }
+
+    #[test]
+    fn file_changes_produce_a_new_key() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("a.txt");
+        std::fs::write(&file, "hello").unwrap();
+        let cache = ToolResultCache::new(ToolCacheScope::Session);
+        let input = json!({"path": "a.txt"});
+        let before = cache.key_for("read_file", &input, tmp.path()).unwrap();
+
+        std::fs::write(&file, "hello, world").unwrap();
+        let after = cache.key_for("read_file", &input, tmp.path()).unwrap();
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn grep_keys_follow_every_file_the_search_reads() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("src/nested")).unwrap();
+        std::fs::write(tmp.path().join("src/nested/a.rs"), "fn a() {}").unwrap();
+        std::fs::create_dir_all(tmp.path().join("target")).unwrap();
+        let cache = ToolResultCache::new(ToolCacheScope::Session);
+        let input = json!({"pattern": "fn"});
+        let key = || cache.key_for("grep_files", &input, tmp.path()).unwrap();
+
+        let before = key();
+        assert_eq!(before, key());
+        // Excluded by default, so the search would not see it.
+        std::fs::write(tmp.path().join("target/out.rs"), "fn x() {}").unwrap();
+        assert_eq!(before, key());
+
+        std::fs::write(tmp.path().join("src/nested/a.rs"), "fn a() { 1 }").unwrap();
+        let edited = key();
+        assert_ne!(before, edited);
+        std::fs::write(tmp.path().join("src/b.rs"), "fn b() {}").unwrap();
+        assert_ne!(edited, key());
+    }
+
+    #[test]
+    fn git_status_keys_follow_the_tree_and_the_index() {
+        let tmp = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(tmp.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {args:?}");
+        };
+        git(&["init", "--quiet"]);
+        std::fs::write(tmp.path().join(".gitignore"), "ignored/\n").unwrap();
+        std::fs::write(tmp.path().join("a.txt"), "one").unwrap();
+        let cache = ToolResultCache::new(ToolCacheScope::Session);
+        let key = || cache.key_for("git_status", &json!({}), tmp.path()).unwrap();
+
+        let before = key();
+        std::fs::create_dir_all(tmp.path().join("ignored")).unwrap();
+        std::fs::write(tmp.path().join("ignored/x.txt"), "x").unwrap();
+        assert_eq!(before, key());
+
+        git(&["add", "a.txt"]);
+        let staged = key();
+        assert_ne!(before, staged);
+        std::fs::write(tmp.path().join("a.txt"), "two").unwrap();
+        assert_ne!(staged, key());
+    }
+
+    #[test]
+    fn non_deterministic_tools_and_failures_are_not_cached() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut cache = ToolResultCache::new(ToolCacheScope::Turn);
+        assert!(
+            cache
+                .key_for("exec_shell", &json!({"command": "ls"}), tmp.path())
+                .is_none()
+        );
+
+        assert!(
+            cache
+                .key_for("list_dir", &json!({"path": "."}), tmp.path())
+                .is_none()
+        );
+        // Outside a repository there is nothing for git_status to key on.
+        assert!(
+            cache
+                .key_for("git_status", &json!({}), tmp.path())
+                .is_none()
+        );
+        // A file that doesn't exist yet has nothing to key on.
+        assert!(
+            cache
+                .key_for("read_file", &json!({"path": "missing.txt"}), tmp.path())
+                .is_none()
+        );
+
+        std::fs::write(tmp.path().join("a.txt"), "hello").unwrap();
+        let key = cache
+            .key_for("read_file", &json!({"path": "a.txt"}), tmp.path())
+            .unwrap();
+        cache.insert(key.clone(), &ToolResult::error("boom"));
+        assert!(cache.lookup(&key).is_none());
+    }
+
+    #[test]
+    fn scope_controls_turn_reset_and_off_disables() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("a.txt"), "hello").unwrap();
+        let input = json!({"path": "a.txt"});
+
+        let mut turn = ToolResultCache::new(ToolCacheScope::Turn);
+        let key = turn.key_for("read_file", &input, tmp.path()).unwrap();
+        turn.insert(key.clone(), &ToolResult::success("match"));
+        turn.begin_turn();
+        assert!(turn.lookup(&key).is_none());
+
+        let mut session = ToolResultCache::new(ToolCacheScope::Session);
+        session.insert(key.clone(), &ToolResult::success("match"));
+        session.begin_turn();
+        assert!(session.lookup(&key).is_some());
+        assert_eq!(session.clear(), 1);
+
+        let off = ToolResultCache::new(ToolCacheScope::Off);
+        assert!(off.key_for("read_file", &input, tmp.path()).is_none());
+    }
+}
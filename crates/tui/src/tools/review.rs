@@ -23,7 +23,7 @@ const MAX_MAX_CHARS: usize = 1_000_000;
 const REVIEW_MAX_TOKENS: u32 = 2048;
 const FALLBACK_MAX_CHARS: usize = 4000;
 
-const REVIEW_SYSTEM_PROMPT: &str = "You are a senior code reviewer. Return ONLY valid JSON with \
+pub(crate) const REVIEW_SYSTEM_PROMPT: &str = "You are a senior code reviewer. Return ONLY valid JSON with \
 the following schema:\n\
 {\n\
   \"summary\": \"short overview\",\n\
@@ -86,15 +86,15 @@ pub struct ReviewOutput {
 impl ReviewOutput {
     #[must_use]
     pub fn from_str(raw: &str) -> Self {
-        if let Some(parsed) = parse_review_output_json(raw) {
-            return parsed.normalize();
-        }
-        if let Some(json_block) = extract_json_block(raw)
-            && let Some(parsed) = parse_review_output_json(json_block)
-        {
-            return parsed.normalize();
-        }
-        ReviewOutput::fallback(raw)
+        Self::parse(raw).unwrap_or_else(|| ReviewOutput::fallback(raw))
+    }
+
+    /// Structured review JSON in `raw`, or `None` when there is none.
+    #[must_use]
+    pub fn parse(raw: &str) -> Option<Self> {
+        parse_review_output_json(raw)
+            .or_else(|| extract_json_block(raw).and_then(parse_review_output_json))
+            .map(Self::normalize)
     }
 
     fn fallback(raw: &str) -> Self {
@@ -99,41 +99,7 @@ impl ToolSpec for GrepFilesTool {
         let max_results = usize::try_from(optional_u64(&input, "max_results", MAX_RESULTS as u64))
             .unwrap_or(MAX_RESULTS);
 
-        // Parse include patterns
-        let include_patterns: Vec<String> = input
-            .get("include")
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| v.as_str().map(String::from))
-                    .collect()
-            })
-            .unwrap_or_default();
-
-        // Parse exclude patterns
-        let exclude_patterns: Vec<String> =
-            input.get("exclude").and_then(|v| v.as_array()).map_or_else(
-                || {
-                    // Default exclusions for common non-code directories
-                    vec![
-                        "node_modules/*".to_string(),
-                        ".git/*".to_string(),
-                        "target/*".to_string(),
-                        "*.min.js".to_string(),
-                        "*.min.css".to_string(),
-                        "dist/*".to_string(),
-                        "build/*".to_string(),
-                        "__pycache__/*".to_string(),
-                        ".venv/*".to_string(),
-                        "venv/*".to_string(),
-                    ]
-                },
-                |arr| {
-                    arr.iter()
-                        .filter_map(|v| v.as_str().map(String::from))
-                        .collect()
-                },
-            );
+        let (include_patterns, exclude_patterns) = scope_patterns(&input);
 
         // Build regex
         let regex_pattern = if case_insensitive {
@@ -246,8 +212,46 @@ fn grep_match_to_json(item: &GrepMatch, context_lines: usize) -> Value {
     }
 }
 
+/// `include` and `exclude` globs of a `grep_files` call, with the default
+/// exclusions for common non-code directories when `exclude` is absent.
+pub(crate) fn scope_patterns(input: &Value) -> (Vec<String>, Vec<String>) {
+    let include_patterns: Vec<String> = input
+        .get("include")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let exclude_patterns: Vec<String> =
+        input.get("exclude").and_then(|v| v.as_array()).map_or_else(
+            || {
+                vec![
+                    "node_modules/*".to_string(),
+                    ".git/*".to_string(),
+                    "target/*".to_string(),
+                    "*.min.js".to_string(),
+                    "*.min.css".to_string(),
+                    "dist/*".to_string(),
+                    "build/*".to_string(),
+                    "__pycache__/*".to_string(),
+                    ".venv/*".to_string(),
+                    "venv/*".to_string(),
+                ]
+            },
+            |arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            },
+        );
+    (include_patterns, exclude_patterns)
+}
+
 /// Collect files to search based on include/exclude patterns
-fn collect_files(
+pub(crate) fn collect_files(
     root: &Path,
     include_patterns: &[String],
     exclude_patterns: &[String],
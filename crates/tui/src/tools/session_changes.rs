@@ -0,0 +1,276 @@
+//! Session-scoped registry of files changed by tools.
+//!
+//! Write tools (`write_file`, `edit_file`, `apply_patch`, `fim_edit`,
+//! `edit_with_fim`) call [`SessionChanges::record_before_write`] just before
+//! they touch a file. The first call for a path captures its session-start
+//! contents (or notes that it did not exist), so `/changes` can diff against
+//! what the file looked like before this session — not against git `HEAD` —
+//! and revert a single file without touching anything else.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use super::diff_format::make_unified_diff;
+
+/// Files larger than this keep no baseline copy; they are still listed but
+/// cannot be diffed or reverted.
+const MAX_BASELINE_BYTES: u64 = 4 * 1024 * 1024;
+
+pub type SharedSessionChanges = Arc<SessionChanges>;
+
+/// Create an empty shared registry.
+#[must_use]
+pub fn new_shared_session_changes() -> SharedSessionChanges {
+    Arc::new(SessionChanges::default())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Baseline {
+    /// The file did not exist when the session first wrote it.
+    Missing,
+    Content(Vec<u8>),
+    /// Too large to keep a copy of.
+    Oversized,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+impl ChangeKind {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Modified => "modified",
+            Self::Deleted => "deleted",
+        }
+    }
+}
+
+/// One file whose current contents differ from its session-start state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionFileChange {
+    pub path: PathBuf,
+    pub kind: ChangeKind,
+    pub added_lines: usize,
+    pub removed_lines: usize,
+    /// False when the baseline was too large to keep.
+    pub revertible: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct SessionChanges {
+    baselines: Mutex<BTreeMap<PathBuf, Baseline>>,
+}
+
+impl SessionChanges {
+    /// Capture `path`'s current contents as its session-start baseline,
+    /// unless one was already recorded.
+    pub fn record_before_write(&self, path: &Path) {
+        let Ok(mut baselines) = self.baselines.lock() else {
+            return;
+        };
+        if baselines.contains_key(path) {
+            return;
+        }
+        let baseline = match fs::metadata(path) {
+            Ok(meta) if meta.len() > MAX_BASELINE_BYTES => Baseline::Oversized,
+            Ok(_) => match fs::read(path) {
+                Ok(bytes) => Baseline::Content(bytes),
+                Err(_) => Baseline::Oversized,
+            },
+            Err(_) => Baseline::Missing,
+        };
+        baselines.insert(path.to_path_buf(), baseline);
+    }
+
+    /// Forget every baseline (new session or workspace).
+    pub fn clear(&self) {
+        if let Ok(mut baselines) = self.baselines.lock() {
+            baselines.clear();
+        }
+    }
+
+    /// Files whose current state differs from the baseline, sorted by path.
+    /// Files written and later restored to their original bytes are skipped.
+    #[must_use]
+    pub fn changes(&self) -> Vec<SessionFileChange> {
+        let Ok(baselines) = self.baselines.lock() else {
+            return Vec::new();
+        };
+        baselines
+            .iter()
+            .filter_map(|(path, baseline)| describe_change(path, baseline))
+            .collect()
+    }
+
+    /// Unified diff from the baseline to the current contents of `path`.
+    #[must_use]
+    pub fn diff(&self, path: &Path, display: &str) -> Option<String> {
+        let baseline = self.baselines.lock().ok()?.get(path)?.clone();
+        let current = fs::read(path).ok();
+        let old = match &baseline {
+            Baseline::Missing => Vec::new(),
+            Baseline::Content(bytes) => bytes.clone(),
+            Baseline::Oversized => {
+                return Some(format!(
+                    "{display} was larger than {} MiB at session start; no baseline was kept.",
+                    MAX_BASELINE_BYTES / (1024 * 1024)
+                ));
+            }
+        };
+        let new = current.unwrap_or_default();
+        let (Ok(old), Ok(new)) = (std::str::from_utf8(&old), std::str::from_utf8(&new)) else {
+            return Some(format!("Binary file {display} changed."));
+        };
+        Some(make_unified_diff(display, old, new))
+    }
+
+    /// Put `path` back to its session-start state: rewrite the original
+    /// bytes, or delete the file when the session created it.
+    pub fn revert(&self, path: &Path) -> io::Result<()> {
+        let baseline = self
+            .baselines
+            .lock()
+            .map_err(|_| io::Error::other("change registry poisoned"))?
+            .get(path)
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no baseline recorded"))?;
+        match baseline {
+            Baseline::Missing => match fs::remove_file(path) {
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+                result => result,
+            },
+            Baseline::Content(bytes) => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, bytes)
+            }
+            Baseline::Oversized => Err(io::Error::other(
+                "file was too large to keep a session-start copy",
+            )),
+        }
+    }
+}
+
+fn describe_change(path: &Path, baseline: &Baseline) -> Option<SessionFileChange> {
+    let current = fs::read(path).ok();
+    let (kind, old, new): (ChangeKind, &[u8], &[u8]) = match (baseline, current.as_deref()) {
+        (Baseline::Missing, None) => return None,
+        (Baseline::Missing, Some(new)) => (ChangeKind::Created, &[], new),
+        (Baseline::Content(old), None) => (ChangeKind::Deleted, old, &[]),
+        (Baseline::Content(old), Some(new)) if old.as_slice() == new => return None,
+        (Baseline::Content(old), Some(new)) => (ChangeKind::Modified, old, new),
+        (Baseline::Oversized, None) => {
+            return Some(SessionFileChange {
+                path: path.to_path_buf(),
+                kind: ChangeKind::Deleted,
+                added_lines: 0,
+                removed_lines: 0,
+                revertible: false,
+            });
+        }
+        (Baseline::Oversized, Some(_)) => {
+            return Some(SessionFileChange {
+                path: path.to_path_buf(),
+                kind: ChangeKind::Modified,
+                added_lines: 0,
+                removed_lines: 0,
+                revertible: false,
+            });
+        }
+    };
+    let (added_lines, removed_lines) = line_counts(old, new);
+    Some(SessionFileChange {
+        path: path.to_path_buf(),
+        kind,
+        added_lines,
+        removed_lines,
+        revertible: true,
+    })
+}
+
+fn line_counts(old: &[u8], new: &[u8]) -> (usize, usize) {
+    let (Ok(old), Ok(new)) = (std::str::from_utf8(old), std::str::from_utf8(new)) else {
+        return (0, 0);
+    };
+    let diff = similar::TextDiff::from_lines(old, new);
+    diff.iter_all_changes()
+        .fold((0, 0), |(added, removed), change| match change.tag() {
+            similar::ChangeTag::Insert => (added + 1, removed),
+            similar::ChangeTag::Delete => (added, removed + 1),
+            similar::ChangeTag::Equal => (added, removed),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn changes_track_session_start_state_and_revert_restores_it() {
+        let tmp = TempDir::new().expect("tempdir");
+        let edited = tmp.path().join("edited.txt");
+        let created = tmp.path().join("new/created.txt");
+        let untouched = tmp.path().join("same.txt");
+        fs::write(&edited, "one\ntwo\n").expect("write");
+        fs::write(&untouched, "same\n").expect("write");
+
+        let changes = SessionChanges::default();
+        changes.record_before_write(&edited);
+        fs::write(&edited, "one\nTWO\nthree\n").expect("write");
+        // A second write keeps the original baseline.
+        changes.record_before_write(&edited);
+        fs::write(&edited, "one\nTWO\nthree\nfour\n").expect("write");
+        changes.record_before_write(&created);
+        fs::create_dir_all(created.parent().unwrap()).expect("mkdir");
+        fs::write(&created, "hello\n").expect("write");
+        changes.record_before_write(&untouched);
+
+        let listed = changes.changes();
+        assert_eq!(listed.len(), 2, "{listed:?}");
+        assert_eq!(listed[0].path, edited);
+        assert_eq!(listed[0].kind, ChangeKind::Modified);
+        assert_eq!((listed[0].added_lines, listed[0].removed_lines), (3, 1));
+        assert_eq!(listed[1].path, created);
+        assert_eq!(listed[1].kind, ChangeKind::Created);
+
+        let diff = changes.diff(&edited, "edited.txt").expect("diff");
+        assert!(diff.contains("-two"), "{diff}");
+        assert!(diff.contains("+four"), "{diff}");
+
+        changes.revert(&edited).expect("revert edited");
+        changes.revert(&created).expect("revert created");
+        assert_eq!(fs::read_to_string(&edited).unwrap(), "one\ntwo\n");
+        assert!(!created.exists());
+        assert!(changes.changes().is_empty());
+    }
+
+    #[test]
+    fn deleted_files_are_listed_and_restored() {
+        let tmp = TempDir::new().expect("tempdir");
+        let doomed = tmp.path().join("doomed.rs");
+        fs::write(&doomed, "fn main() {}\n").expect("write");
+
+        let changes = SessionChanges::default();
+        changes.record_before_write(&doomed);
+        fs::remove_file(&doomed).expect("remove");
+
+        let listed = changes.changes();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].kind, ChangeKind::Deleted);
+        assert_eq!(listed[0].removed_lines, 1);
+
+        changes.revert(&doomed).expect("revert");
+        assert_eq!(fs::read_to_string(&doomed).unwrap(), "fn main() {}\n");
+    }
+}
@@ -0,0 +1,465 @@
+//! Session environment: variables injected into every shell-tool process.
+//!
+//! Seeded from the config `[env]` table and edited with `/env set` /
+//! `/env unset`. The set is shared between the TUI and the engine's tool
+//! context, so a change applies to the next `exec_shell`, `shell_open`, or
+//! `run_tests` call without restarting the engine. Values of secret-looking
+//! variables (or ones set with `/env set --secret`) are replaced with `***`
+//! wherever they show up in tool output, so they never reach the transcript
+//! or the model.
+//!
+//! When a shell command fails because a variable is missing, the TUI asks for
+//! it in a masked prompt. Those values are sealed with a per-process key and
+//! only decrypted while a child environment or the output mask is built;
+//! they are never written to config or the saved session.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use regex::Regex;
+use ring::aead::{Aad, CHACHA20_POLY1305, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use zeroize::Zeroizing;
+
+/// Placeholder written in place of a masked value.
+pub const MASK: &str = "***";
+
+/// Values shorter than this are never masked in tool output: replacing every
+/// `1` or `on` would shred unrelated text.
+const MIN_MASKED_LEN: usize = 4;
+
+const SECRET_MARKERS: &[&str] = &[
+    "TOKEN",
+    "SECRET",
+    "PASSWORD",
+    "PASSWD",
+    "CREDENTIAL",
+    "PRIVATE",
+    "AUTH",
+    "API_KEY",
+    "ACCESS_KEY",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionVar {
+    pub value: String,
+    pub secret: bool,
+}
+
+/// Whether `key` is usable as an environment variable name.
+#[must_use]
+pub fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|first| first == '_' || first.is_ascii_alphabetic())
+        && chars.all(|ch| ch == '_' || ch.is_ascii_alphanumeric())
+}
+
+/// Whether a variable name suggests its value is a credential.
+#[must_use]
+pub fn looks_secret(key: &str) -> bool {
+    let upper = key.to_ascii_uppercase();
+    SECRET_MARKERS.iter().any(|marker| upper.contains(marker)) || upper.ends_with("_KEY")
+}
+
+/// Name of the variable a failed command's output says is missing, if any.
+#[must_use]
+pub fn detect_missing_var(output: &str) -> Option<String> {
+    static PATTERNS: OnceLock<Vec<Regex>> = OnceLock::new();
+    let patterns = PATTERNS.get_or_init(|| {
+        [
+            // bash `set -u`: `line 3: API_TOKEN: unbound variable`
+            r"\b([A-Z][A-Z0-9_]{2,}): unbound variable",
+            // `environment variable FOO is not set`, `env var `FOO` not set`
+            r"(?i:env(?:ironment)?\s+var(?:iable)?)\s+[`'\x22]?([A-Z][A-Z0-9_]{2,})[`'\x22]?\s+(?i:is\s+)?(?i:not\s+set|not\s+defined|missing|required|must\s+be\s+set)",
+            // `missing environment variable: FOO`, `missing required env var FOO`
+            r"(?i:missing\s+(?:required\s+)?env(?:ironment)?\s+var(?:iable)?s?)[:\s]+[`'\x22]?([A-Z][A-Z0-9_]{2,})",
+            // `FOO is not set`, `FOO must be set`, `FOO environment variable is required`
+            r"\b([A-Z][A-Z0-9]*_[A-Z0-9_]+)[`'\x22]?\s+(?i:(?:env(?:ironment)?\s+var(?:iable)?\s+)?(?:is\s+)?(?:not\s+set|unset|must\s+be\s+set|is\s+required|required))",
+            // Python: `KeyError: 'FOO'` from `os.environ['FOO']`
+            r"os\.environ\[['\x22]([A-Z][A-Z0-9_]{2,})['\x22]\][\s\S]*?KeyError",
+            r"KeyError: '([A-Z][A-Z0-9]*_[A-Z0-9_]+)'",
+        ]
+        .iter()
+        .map(|pattern| Regex::new(pattern).expect("missing-variable pattern"))
+        .collect()
+    });
+    patterns
+        .iter()
+        .find_map(|pattern| pattern.captures(output))
+        .and_then(|captures| captures.get(1))
+        .map(|name| name.as_str().to_string())
+        .filter(|name| is_valid_key(name))
+}
+
+/// A value sealed with the session key.
+struct Sealed {
+    nonce: [u8; NONCE_LEN],
+    ciphertext: Vec<u8>,
+}
+
+impl fmt::Debug for Sealed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Sealed(..)")
+    }
+}
+
+/// Per-process ChaCha20-Poly1305 key for prompted values. It only lives in
+/// memory, so sealed values cannot outlive the session.
+struct SessionKey {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl fmt::Debug for SessionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SessionKey(..)")
+    }
+}
+
+impl SessionKey {
+    fn generate() -> Option<Self> {
+        let rng = SystemRandom::new();
+        let mut bytes = Zeroizing::new([0u8; 32]);
+        rng.fill(bytes.as_mut()).ok()?;
+        let key = UnboundKey::new(&CHACHA20_POLY1305, bytes.as_ref()).ok()?;
+        Some(Self {
+            key: LessSafeKey::new(key),
+            rng,
+        })
+    }
+
+    fn seal(&self, value: &str) -> Option<Sealed> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng.fill(&mut nonce).ok()?;
+        let mut ciphertext = value.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut ciphertext,
+            )
+            .ok()?;
+        Some(Sealed { nonce, ciphertext })
+    }
+
+    fn open(&self, sealed: &Sealed) -> Option<Zeroizing<String>> {
+        let mut buf = Zeroizing::new(sealed.ciphertext.clone());
+        let plain = self
+            .key
+            .open_in_place(
+                Nonce::assume_unique_for_key(sealed.nonce),
+                Aad::empty(),
+                buf.as_mut_slice(),
+            )
+            .ok()?;
+        std::str::from_utf8(plain)
+            .ok()
+            .map(|text| Zeroizing::new(text.to_string()))
+    }
+}
+
+/// The variables currently injected into shell tools.
+#[derive(Debug, Default)]
+pub struct SessionEnv {
+    vars: Mutex<BTreeMap<String, SessionVar>>,
+    /// Values typed into the missing-variable prompt, kept encrypted.
+    sealed: Mutex<BTreeMap<String, Sealed>>,
+    key: OnceLock<Option<SessionKey>>,
+}
+
+impl SessionEnv {
+    /// Snapshot of every variable, sorted by name. Sealed values are listed
+    /// as [`MASK`].
+    #[must_use]
+    pub fn vars(&self) -> BTreeMap<String, SessionVar> {
+        let mut vars = self.plain_vars();
+        if let Ok(sealed) = self.sealed.lock() {
+            for key in sealed.keys() {
+                vars.insert(
+                    key.clone(),
+                    SessionVar {
+                        value: MASK.to_string(),
+                        secret: true,
+                    },
+                );
+            }
+        }
+        vars
+    }
+
+    fn plain_vars(&self) -> BTreeMap<String, SessionVar> {
+        self.vars
+            .lock()
+            .map(|vars| vars.clone())
+            .unwrap_or_default()
+    }
+
+    /// Sealed values, decrypted for the caller's immediate use.
+    fn opened(&self) -> Vec<(String, Zeroizing<String>)> {
+        let Some(key) = self.key.get().and_then(Option::as_ref) else {
+            return Vec::new();
+        };
+        self.sealed
+            .lock()
+            .map(|sealed| {
+                sealed
+                    .iter()
+                    .filter_map(|(name, value)| Some((name.clone(), key.open(value)?)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether `key` is set, plainly or sealed.
+    #[must_use]
+    pub fn contains(&self, key: &str) -> bool {
+        self.vars.lock().is_ok_and(|vars| vars.contains_key(key))
+            || self
+                .sealed
+                .lock()
+                .is_ok_and(|sealed| sealed.contains_key(key))
+    }
+
+    /// `KEY → value` pairs to add to a child process environment.
+    #[must_use]
+    pub fn env_pairs(&self) -> HashMap<String, String> {
+        let mut pairs: HashMap<String, String> = self
+            .plain_vars()
+            .into_iter()
+            .map(|(key, var)| (key, var.value))
+            .collect();
+        for (key, value) in self.opened() {
+            pairs.insert(key, value.to_string());
+        }
+        pairs
+    }
+
+    /// Store `value` for `key` encrypted for the rest of the session. It is
+    /// always masked and replaces any plain value of the same name.
+    pub fn set_sealed(&self, key: &str, value: &str) -> Result<(), String> {
+        let sealed = self
+            .key
+            .get_or_init(SessionKey::generate)
+            .as_ref()
+            .and_then(|session_key| session_key.seal(value))
+            .ok_or_else(|| "could not encrypt the value".to_string())?;
+        if let Ok(mut vars) = self.vars.lock() {
+            vars.remove(key);
+        }
+        if let Ok(mut map) = self.sealed.lock() {
+            map.insert(key.to_string(), sealed);
+        }
+        Ok(())
+    }
+
+    /// Set `key`; `secret` forces masking even when the name looks harmless.
+    pub fn set(&self, key: &str, value: &str, secret: bool) {
+        if let Ok(mut sealed) = self.sealed.lock() {
+            sealed.remove(key);
+        }
+        if let Ok(mut vars) = self.vars.lock() {
+            vars.insert(
+                key.to_string(),
+                SessionVar {
+                    value: value.to_string(),
+                    secret: secret || looks_secret(key),
+                },
+            );
+        }
+    }
+
+    /// Remove `key`; `false` when it was not set.
+    pub fn unset(&self, key: &str) -> bool {
+        let sealed = self
+            .sealed
+            .lock()
+            .map(|mut sealed| sealed.remove(key).is_some())
+            .unwrap_or(false);
+        let plain = self
+            .vars
+            .lock()
+            .map(|mut vars| vars.remove(key).is_some())
+            .unwrap_or(false);
+        sealed || plain
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut vars) = self.vars.lock() {
+            vars.clear();
+        }
+        if let Ok(mut sealed) = self.sealed.lock() {
+            sealed.clear();
+        }
+    }
+
+    /// Replace the config-backed set with the `[env]` table. Values typed
+    /// into the missing-variable prompt survive a config reload.
+    pub fn replace_from_config(&self, table: &BTreeMap<String, String>) {
+        if let Ok(mut vars) = self.vars.lock() {
+            vars.clear();
+        }
+        for (key, value) in table {
+            self.set(key, value, false);
+        }
+    }
+
+    /// `text` with every secret value replaced by [`MASK`].
+    #[must_use]
+    pub fn mask(&self, text: &str) -> String {
+        let mut secrets: Vec<Zeroizing<String>> = self
+            .plain_vars()
+            .into_values()
+            .filter(|var| var.secret)
+            .map(|var| Zeroizing::new(var.value))
+            .chain(self.opened().into_iter().map(|(_, value)| value))
+            .filter(|value| value.len() >= MIN_MASKED_LEN)
+            .collect();
+        // Longest first, so a secret that contains another is masked whole.
+        secrets.sort_by_key(|value| std::cmp::Reverse(value.len()));
+        let mut masked = text.to_string();
+        for secret in secrets {
+            if masked.contains(secret.as_str()) {
+                masked = masked.replace(secret.as_str(), MASK);
+            }
+            // Tool output is often serialized JSON, where a value holding
+            // `"` or `\` only appears in its escaped form.
+            let escaped = Zeroizing::new(json_escaped(&secret));
+            if escaped.as_str() != secret.as_str() && masked.contains(escaped.as_str()) {
+                masked = masked.replace(escaped.as_str(), MASK);
+            }
+        }
+        masked
+    }
+}
+
+/// `value` as it appears inside a JSON string literal, without the quotes.
+fn json_escaped(value: &str) -> String {
+    let quoted = serde_json::to_string(value).unwrap_or_default();
+    quoted
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .unwrap_or(value)
+        .to_string()
+}
+
+impl SessionVar {
+    /// Value as shown by `/env list`.
+    #[must_use]
+    pub fn display_value(&self) -> &str {
+        if self.secret { MASK } else { &self.value }
+    }
+}
+
+pub type SharedSessionEnv = Arc<SessionEnv>;
+
+/// Create a shared handle seeded from the config `[env]` table.
+#[must_use]
+pub fn new_shared_session_env(table: Option<&BTreeMap<String, String>>) -> SharedSessionEnv {
+    let env = SessionEnv::default();
+    if let Some(table) = table {
+        env.replace_from_config(table);
+    }
+    Arc::new(env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_values_are_masked_in_output() {
+        let env = SessionEnv::default();
+        env.set("GITHUB_TOKEN", "ghp_abcdef123456", false);
+        env.set("FEATURE_FLAG", "enabled", false);
+        env.set("DB_URL", "postgres://u:hunter22@db", true);
+        env.set("SHORT_TOKEN", "ab", false);
+
+        let masked =
+            env.mask("token=ghp_abcdef123456 url=postgres://u:hunter22@db flag=enabled ab");
+        assert_eq!(masked, "token=*** url=*** flag=enabled ab");
+        assert_eq!(env.vars()["GITHUB_TOKEN"].display_value(), MASK);
+        assert_eq!(env.vars()["FEATURE_FLAG"].display_value(), "enabled");
+    }
+
+    #[test]
+    fn keys_are_validated_and_config_seeds_the_set() {
+        assert!(is_valid_key("_FOO1"));
+        assert!(!is_valid_key("1FOO"));
+        assert!(!is_valid_key("FOO-BAR"));
+        assert!(!is_valid_key(""));
+        assert!(looks_secret("npm_auth"));
+        assert!(looks_secret("STRIPE_KEY"));
+        assert!(!looks_secret("RUST_LOG"));
+
+        let table = BTreeMap::from([("RUST_LOG".to_string(), "debug".to_string())]);
+        let env = new_shared_session_env(Some(&table));
+        assert_eq!(
+            env.env_pairs().get("RUST_LOG").map(String::as_str),
+            Some("debug")
+        );
+        assert!(env.unset("RUST_LOG"));
+        assert!(!env.unset("RUST_LOG"));
+    }
+
+    #[test]
+    fn sealed_values_reach_the_child_env_but_not_listings_or_output() {
+        let env = SessionEnv::default();
+        env.set("DEPLOY_HOST", "example.com", false);
+        env.set_sealed("DEPLOY_HOST", "s3cr3t-value").expect("seal");
+        assert!(env.contains("DEPLOY_HOST"));
+        assert_eq!(
+            env.env_pairs().get("DEPLOY_HOST").map(String::as_str),
+            Some("s3cr3t-value")
+        );
+        assert_eq!(env.vars()["DEPLOY_HOST"].value, MASK);
+        assert_eq!(env.mask("using s3cr3t-value now"), "using *** now");
+        {
+            let sealed = env.sealed.lock().expect("sealed");
+            let stored = &sealed["DEPLOY_HOST"].ciphertext;
+            assert!(!stored.windows(12).any(|bytes| bytes == b"s3cr3t-value"));
+        }
+
+        env.replace_from_config(&BTreeMap::new());
+        assert!(env.contains("DEPLOY_HOST"), "config reload keeps it");
+        assert!(env.unset("DEPLOY_HOST"));
+        assert!(!env.contains("DEPLOY_HOST"));
+    }
+
+    #[test]
+    fn missing_variables_are_detected_from_common_error_shapes() {
+        let cases = [
+            (
+                "./deploy.sh: line 3: DEPLOY_TOKEN: unbound variable",
+                "DEPLOY_TOKEN",
+            ),
+            (
+                "Error: environment variable `OPENAI_API_KEY` is not set",
+                "OPENAI_API_KEY",
+            ),
+            (
+                "error: missing required env var DATABASE_URL",
+                "DATABASE_URL",
+            ),
+            (
+                "thread 'main' panicked: GITHUB_TOKEN must be set",
+                "GITHUB_TOKEN",
+            ),
+            ("KeyError: 'AWS_REGION'", "AWS_REGION"),
+        ];
+        for (output, expected) in cases {
+            assert_eq!(
+                detect_missing_var(output).as_deref(),
+                Some(expected),
+                "{output}"
+            );
+        }
+        assert_eq!(
+            detect_missing_var("error[E0425]: cannot find value `x`"),
+            None
+        );
+        assert_eq!(detect_missing_var("test result: FAILED. 2 passed"), None);
+    }
+}
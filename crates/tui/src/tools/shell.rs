@@ -32,6 +32,7 @@ use crate::sandbox::{
     SandboxManager,
     SandboxPolicy as ExecutionSandboxPolicy, // Rename to avoid conflict with spec::SandboxPolicy
     SandboxType,
+    limits::{LimitHit, ResourceLimits, ShellLimits},
 };
 
 /// Status of a shell process
@@ -80,6 +81,9 @@ pub struct ShellResult {
     /// Whether the command was blocked by sandbox restrictions.
     #[serde(default)]
     pub sandbox_denied: bool,
+    /// Resource limit (`[shell_limits]`) the command ran into, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit_hit: Option<LimitHit>,
 }
 
 /// Compact, UI-oriented view of a tracked background shell job.
@@ -99,6 +103,9 @@ pub struct ShellJobSnapshot {
     pub stdin_available: bool,
     pub stale: bool,
     pub linked_task_id: Option<String>,
+    /// Name of a persistent shell session; `None` for ordinary jobs.
+    #[serde(default)]
+    pub name: Option<String>,
 }
 
 /// Full output view used by `/jobs show <id>`.
@@ -109,6 +116,20 @@ pub struct ShellJobDetail {
     pub stderr: String,
 }
 
+/// New output from a persistent shell session since the TUI last streamed
+/// it. Kept separate from the agent's delta cursor so streaming into the
+/// transcript never steals output from `exec_shell_wait`.
+#[derive(Debug, Clone)]
+pub struct ShellSessionOutput {
+    pub id: String,
+    pub name: String,
+    pub command: String,
+    pub status: ShellStatus,
+    pub exit_code: Option<i32>,
+    pub elapsed_ms: u64,
+    pub output: String,
+}
+
 pub struct ShellDeltaResult {
     pub command: String,
     pub result: ShellResult,
@@ -226,6 +247,8 @@ fn install_parent_death_signal(_cmd: &mut Command) {
 #[derive(Clone, Copy, Debug)]
 struct ShellExitStatus {
     code: Option<i32>,
+    /// Terminating signal, when the shell itself was killed by one.
+    signal: Option<i32>,
     success: bool,
 }
 
@@ -233,6 +256,7 @@ impl ShellExitStatus {
     fn from_std(status: std::process::ExitStatus) -> Self {
         Self {
             code: status.code(),
+            signal: exit_signal(&status),
             success: status.success(),
         }
     }
@@ -241,11 +265,22 @@ impl ShellExitStatus {
         let code = i32::try_from(status.exit_code()).unwrap_or(i32::MAX);
         Self {
             code: Some(code),
+            signal: None,
             success: status.success(),
         }
     }
 }
 
+#[cfg(unix)]
+fn exit_signal(status: &std::process::ExitStatus) -> Option<i32> {
+    std::os::unix::process::ExitStatusExt::signal(status)
+}
+
+#[cfg(not(unix))]
+fn exit_signal(_status: &std::process::ExitStatus) -> Option<i32> {
+    None
+}
+
 impl ShellChild {
     fn try_wait(&mut self) -> std::io::Result<Option<ShellExitStatus>> {
         match self {
@@ -324,13 +359,19 @@ pub struct BackgroundShell {
     pub working_dir: PathBuf,
     pub status: ShellStatus,
     pub exit_code: Option<i32>,
+    exit_signal: Option<i32>,
     pub started_at: Instant,
     pub sandbox_type: SandboxType,
+    resource_limits: ResourceLimits,
     pub linked_task_id: Option<String>,
+    /// Set for persistent sessions opened with [`ShellManager::open_session`].
+    pub name: Option<String>,
     stdout_buffer: Arc<Mutex<Vec<u8>>>,
     stderr_buffer: Option<Arc<Mutex<Vec<u8>>>>,
     stdout_cursor: usize,
     stderr_cursor: usize,
+    stream_cursor: usize,
+    stream_exit_reported: bool,
     stdin: Option<StdinWriter>,
     child: Option<ShellChild>,
     stdout_thread: Option<std::thread::JoinHandle<()>>,
@@ -348,6 +389,7 @@ impl BackgroundShell {
             match child.try_wait() {
                 Ok(Some(status)) => {
                     self.exit_code = status.code;
+                    self.exit_signal = status.signal;
                     self.status = if status.success {
                         ShellStatus::Completed
                     } else {
@@ -464,7 +506,18 @@ impl BackgroundShell {
             self.sandbox_type,
             self.exit_code.unwrap_or(-1),
             &stderr_full,
-        )
+        ) || self.limit_hit().is_some()
+    }
+
+    fn limit_hit(&self) -> Option<LimitHit> {
+        if self.resource_limits.is_empty()
+            || matches!(self.status, ShellStatus::Running | ShellStatus::Killed)
+        {
+            return None;
+        }
+        let (_, stderr_full, _, _) = self.full_output();
+        self.resource_limits
+            .detect_hit(self.exit_code, self.exit_signal, &stderr_full)
     }
 
     /// Kill the process
@@ -505,6 +558,7 @@ impl BackgroundShell {
                 None
             },
             sandbox_denied: self.sandbox_denied(),
+            limit_hit: self.limit_hit(),
         }
     }
 
@@ -535,6 +589,7 @@ impl BackgroundShell {
             stdin_available: self.stdin.is_some() && self.status == ShellStatus::Running,
             stale: false,
             linked_task_id: self.linked_task_id.clone(),
+            name: self.name.clone(),
         }
     }
 
@@ -703,11 +758,13 @@ impl ShellManager {
             tty,
             policy_override,
             HashMap::new(),
+            None,
         )
     }
 
     /// Same as `execute_with_options`, plus an extra env-var map that is
-    /// merged into the spawned process environment. Used by the `shell_env`
+    /// merged into the spawned process environment and the `[shell_limits]`
+    /// table resolved against the effective policy. Used by the `shell_env`
     /// hook injection path (#456); other callers should use the simpler
     /// wrapper above.
     #[allow(clippy::too_many_arguments)]
@@ -721,6 +778,7 @@ impl ShellManager {
         tty: bool,
         policy_override: Option<ExecutionSandboxPolicy>,
         extra_env: HashMap<String, String>,
+        shell_limits: Option<&ShellLimits>,
     ) -> Result<ShellResult> {
         let work_dir = working_dir.map_or_else(|| self.default_workspace.clone(), PathBuf::from);
 
@@ -731,9 +789,12 @@ impl ShellManager {
         let policy = policy_override.unwrap_or_else(|| self.sandbox_policy.clone());
 
         // Create command spec and prepare sandboxed environment
+        let limits =
+            shell_limits.map_or_else(ResourceLimits::default, |limits| limits.for_policy(&policy));
         let spec = CommandSpec::shell(command, work_dir.clone(), Duration::from_millis(timeout_ms))
             .with_policy(policy)
-            .with_env(extra_env);
+            .with_env(extra_env)
+            .with_resource_limits(limits);
         let exec_env = self.sandbox_manager.prepare(&spec);
 
         if background {
@@ -773,6 +834,7 @@ impl ShellManager {
             timeout_ms,
             policy_override,
             HashMap::new(),
+            None,
         )
     }
 
@@ -784,15 +846,19 @@ impl ShellManager {
         timeout_ms: u64,
         policy_override: Option<ExecutionSandboxPolicy>,
         extra_env: HashMap<String, String>,
+        shell_limits: Option<&ShellLimits>,
     ) -> Result<ShellResult> {
         let work_dir = working_dir.map_or_else(|| self.default_workspace.clone(), PathBuf::from);
 
         let timeout_ms = timeout_ms.clamp(1000, 600_000);
         let policy = policy_override.unwrap_or_else(|| self.sandbox_policy.clone());
+        let limits =
+            shell_limits.map_or_else(ResourceLimits::default, |limits| limits.for_policy(&policy));
 
         let spec = CommandSpec::shell(command, work_dir.clone(), Duration::from_millis(timeout_ms))
             .with_policy(policy)
-            .with_env(extra_env);
+            .with_env(extra_env)
+            .with_resource_limits(limits);
         let exec_env = self.sandbox_manager.prepare(&spec);
 
         Self::execute_interactive_sandboxed(command, &work_dir, timeout_ms, &exec_env)
@@ -825,6 +891,7 @@ impl ShellManager {
             cmd.process_group(0);
         }
         install_parent_death_signal(&mut cmd);
+        exec_env.resource_limits.apply(&mut cmd);
 
         if stdin_data.is_some() {
             cmd.stdin(Stdio::piped());
@@ -872,7 +939,13 @@ impl ShellManager {
             let exit_code = status.code().unwrap_or(-1);
 
             // Check if sandbox denied the operation
-            let sandbox_denied = SandboxManager::was_denied(sandbox_type, exit_code, &stderr_str);
+            let limit_hit = exec_env.resource_limits.detect_hit(
+                status.code(),
+                exit_signal(&status),
+                &stderr_str,
+            );
+            let sandbox_denied = SandboxManager::was_denied(sandbox_type, exit_code, &stderr_str)
+                || limit_hit.is_some();
             let (stdout, stdout_meta) = truncate_with_meta(&stdout_str);
             let (stderr, stderr_meta) = truncate_with_meta(&stderr_str);
 
@@ -900,6 +973,7 @@ impl ShellManager {
                     None
                 },
                 sandbox_denied,
+                limit_hit,
             })
         } else {
             // Timeout - kill the process
@@ -935,6 +1009,7 @@ impl ShellManager {
                     None
                 },
                 sandbox_denied: false,
+                limit_hit: None,
             })
         }
     }
@@ -965,6 +1040,7 @@ impl ShellManager {
             cmd.process_group(0);
         }
         install_parent_death_signal(&mut cmd);
+        exec_env.resource_limits.apply(&mut cmd);
 
         child_env::apply_to_command(&mut cmd, child_env::string_map_env(&exec_env.env));
 
@@ -997,6 +1073,7 @@ impl ShellManager {
                     None
                 },
                 sandbox_denied: false,
+                limit_hit: None,
             })
         } else {
             #[cfg(unix)]
@@ -1025,6 +1102,7 @@ impl ShellManager {
                     None
                 },
                 sandbox_denied: false,
+                limit_hit: None,
             })
         }
     }
@@ -1105,6 +1183,7 @@ impl ShellManager {
             {
                 cmd.process_group(0);
             }
+            exec_env.resource_limits.apply(&mut cmd);
 
             child_env::apply_to_command(&mut cmd, child_env::string_map_env(&exec_env.env));
 
@@ -1138,13 +1217,18 @@ impl ShellManager {
             working_dir: working_dir.to_path_buf(),
             status: ShellStatus::Running,
             exit_code: None,
+            exit_signal: None,
             started_at: started,
             sandbox_type,
+            resource_limits: exec_env.resource_limits,
             linked_task_id: None,
+            name: None,
             stdout_buffer,
             stderr_buffer,
             stdout_cursor: 0,
             stderr_cursor: 0,
+            stream_cursor: 0,
+            stream_exit_reported: false,
             stdin,
             child: Some(child),
             stdout_thread,
@@ -1177,6 +1261,7 @@ impl ShellManager {
                 None
             },
             sandbox_denied: false,
+            limit_hit: None,
         })
     }
 
@@ -1188,9 +1273,10 @@ impl ShellManager {
         block: bool,
         timeout_ms: u64,
     ) -> Result<ShellResult> {
+        let task_id = self.resolve_id(task_id);
         let shell = self
             .processes
-            .get_mut(task_id)
+            .get_mut(&task_id)
             .ok_or_else(|| anyhow!("Task {task_id} not found"))?;
 
         if block && shell.status == ShellStatus::Running {
@@ -1217,9 +1303,10 @@ impl ShellManager {
 
     /// Write data to stdin of a background process.
     pub fn write_stdin(&mut self, task_id: &str, input: &str, close: bool) -> Result<()> {
+        let task_id = self.resolve_id(task_id);
         let shell = self
             .processes
-            .get_mut(task_id)
+            .get_mut(&task_id)
             .ok_or_else(|| anyhow!("Task {task_id} not found"))?;
         shell.write_stdin(input, close)?;
         Ok(())
@@ -1232,9 +1319,10 @@ impl ShellManager {
         wait: bool,
         timeout_ms: u64,
     ) -> Result<ShellDeltaResult> {
+        let task_id = self.resolve_id(task_id);
         let shell = self
             .processes
-            .get_mut(task_id)
+            .get_mut(&task_id)
             .ok_or_else(|| anyhow!("Task {task_id} not found"))?;
 
         if wait && shell.status == ShellStatus::Running {
@@ -1284,6 +1372,7 @@ impl ShellManager {
                 None
             },
             sandbox_denied: shell.sandbox_denied(),
+            limit_hit: shell.limit_hit(),
         };
 
         Ok(ShellDeltaResult {
@@ -1296,9 +1385,10 @@ impl ShellManager {
 
     /// Kill a running background process
     pub fn kill(&mut self, task_id: &str) -> Result<ShellResult> {
+        let task_id = self.resolve_id(task_id);
         let shell = self
             .processes
-            .get_mut(task_id)
+            .get_mut(&task_id)
             .ok_or_else(|| anyhow!("Task {task_id} not found"))?;
 
         shell.kill()?;
@@ -1333,9 +1423,10 @@ impl ShellManager {
 
     /// Attach durable task context to a live shell job.
     pub fn tag_linked_task(&mut self, task_id: &str, linked_task_id: Option<String>) -> Result<()> {
+        let task_id = self.resolve_id(task_id);
         let shell = self
             .processes
-            .get_mut(task_id)
+            .get_mut(&task_id)
             .ok_or_else(|| anyhow!("Task {task_id} not found"))?;
         shell.linked_task_id = linked_task_id;
         Ok(())
@@ -1343,7 +1434,8 @@ impl ShellManager {
 
     /// Inspect full output for a live or stale job.
     pub fn inspect_job(&mut self, task_id: &str) -> Result<ShellJobDetail> {
-        if let Some(shell) = self.processes.get_mut(task_id) {
+        let id = self.resolve_id(task_id);
+        if let Some(shell) = self.processes.get_mut(&id) {
             shell.poll();
             return Ok(shell.job_detail());
         }
@@ -1379,6 +1471,103 @@ impl ShellManager {
         jobs
     }
 
+    /// Open a persistent PTY shell session under `name`. Without a `command`
+    /// the user's shell is started, so the session behaves like a terminal
+    /// tab that stays up across turns until it exits or is killed. The name
+    /// works anywhere a task id does.
+    pub fn open_session(
+        &mut self,
+        name: &str,
+        command: Option<&str>,
+        working_dir: Option<&str>,
+        policy_override: Option<ExecutionSandboxPolicy>,
+        extra_env: HashMap<String, String>,
+    ) -> Result<ShellResult> {
+        validate_session_name(name)?;
+        for shell in self.processes.values_mut() {
+            if shell.name.as_deref() == Some(name) && !shell.poll() {
+                return Err(anyhow!(
+                    "shell session `{name}` is already running as {}",
+                    shell.id
+                ));
+            }
+        }
+        let command = command
+            .map(str::trim)
+            .filter(|command| !command.is_empty())
+            .map_or_else(default_session_command, str::to_string);
+        let result = self.execute_with_options_env(
+            &command,
+            working_dir,
+            600_000,
+            true,
+            None,
+            true,
+            policy_override,
+            extra_env,
+            None,
+        )?;
+        if let Some(shell) = result
+            .task_id
+            .as_ref()
+            .and_then(|id| self.processes.get_mut(id))
+        {
+            shell.name = Some(name.to_string());
+        }
+        Ok(result)
+    }
+
+    /// Persistent shell sessions, running ones first.
+    pub fn list_sessions(&mut self) -> Vec<ShellJobSnapshot> {
+        self.list_jobs()
+            .into_iter()
+            .filter(|job| job.name.is_some())
+            .collect()
+    }
+
+    /// Drain the output each persistent session produced since the last
+    /// call, plus one final update when a session exits.
+    pub fn take_session_output(&mut self) -> Vec<ShellSessionOutput> {
+        let mut updates = Vec::new();
+        for shell in self.processes.values_mut() {
+            let Some(name) = shell.name.clone() else {
+                continue;
+            };
+            shell.poll();
+            let output = take_utf8_delta(&shell.stdout_buffer, &mut shell.stream_cursor);
+            let exited = shell.status != ShellStatus::Running;
+            if output.is_empty() && (!exited || shell.stream_exit_reported) {
+                continue;
+            }
+            shell.stream_exit_reported = exited;
+            updates.push(ShellSessionOutput {
+                id: shell.id.clone(),
+                name,
+                command: shell.command.clone(),
+                status: shell.status.clone(),
+                exit_code: shell.exit_code,
+                elapsed_ms: u64::try_from(shell.started_at.elapsed().as_millis())
+                    .unwrap_or(u64::MAX),
+                output,
+            });
+        }
+        updates.sort_by(|a, b| a.name.cmp(&b.name));
+        updates
+    }
+
+    /// Map a session name to the id of its newest process, preferring a
+    /// running one. Ids and unknown names pass through unchanged.
+    fn resolve_id(&self, id_or_name: &str) -> String {
+        if self.processes.contains_key(id_or_name) {
+            return id_or_name.to_string();
+        }
+        self.processes
+            .values()
+            .filter(|shell| shell.name.as_deref() == Some(id_or_name))
+            .max_by_key(|shell| (shell.status == ShellStatus::Running, shell.started_at))
+            .map_or_else(|| id_or_name.to_string(), |shell| shell.id.clone())
+    }
+
     /// Remember a restart-stale job so the UI can show it instead of hiding it.
     #[allow(dead_code)]
     pub fn remember_stale_job(
@@ -1406,6 +1595,7 @@ impl ShellManager {
                 stdin_available: false,
                 stale: true,
                 linked_task_id,
+                name: None,
             },
         );
     }
@@ -1435,6 +1625,50 @@ fn take_delta_from_buffer(buffer: &Arc<Mutex<Vec<u8>>>, cursor: &mut usize) -> (
     (delta, total)
 }
 
+/// Like [`take_delta_from_buffer`], but leaves a trailing partial UTF-8
+/// sequence in the buffer so a multibyte character split across reads is
+/// not rendered as replacement characters.
+fn take_utf8_delta(buffer: &Arc<Mutex<Vec<u8>>>, cursor: &mut usize) -> String {
+    let start = *cursor;
+    let (bytes, _) = take_delta_from_buffer(buffer, cursor);
+    let complete = match std::str::from_utf8(&bytes) {
+        Ok(_) => bytes.len(),
+        Err(err) if err.error_len().is_none() => err.valid_up_to(),
+        Err(_) => bytes.len(),
+    };
+    *cursor = start + complete;
+    String::from_utf8_lossy(&bytes[..complete]).into_owned()
+}
+
+/// Session names double as task ids, so keep them short and shell-safe.
+fn validate_session_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 32
+        && !name.starts_with("shell_")
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "invalid shell session name `{name}`: use 1-32 letters, digits, `-` or `_` (not starting with `shell_`)"
+        ))
+    }
+}
+
+fn default_session_command() -> String {
+    let (var, fallback) = if cfg!(windows) {
+        ("COMSPEC", "cmd.exe")
+    } else {
+        ("SHELL", "sh")
+    };
+    std::env::var(var)
+        .ok()
+        .filter(|shell| !shell.trim().is_empty())
+        .unwrap_or_else(|| fallback.to_string())
+}
+
 /// Read only the tail of a byte buffer and return (total_len, tail_string).
 ///
 /// Avoids cloning the full buffer when only a trailing excerpt is needed
@@ -1494,7 +1728,7 @@ pub fn new_shared_shell_manager(workspace: PathBuf) -> SharedShellManager {
 // === ToolSpec Implementations ===
 
 use crate::command_safety::{SafetyLevel, analyze_command, extract_primary_command};
-use crate::execpolicy::{ExecPolicyDecision, load_default_policy};
+use crate::execpolicy::{ExecPolicyDecision, load_workspace_policy};
 use crate::features::Feature;
 use crate::tools::spec::{
     ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolResult, ToolSpec,
@@ -1524,6 +1758,13 @@ pub(crate) fn looks_like_macos_provenance_failure(result: &ShellResult) -> bool
         || (combined.contains("buildx/activity") && combined.contains("operation not permitted"))
 }
 
+/// Notice prepended to the output of a command stopped by `[shell_limits]`.
+fn limit_hit_notice(hit: &LimitHit) -> String {
+    format!(
+        "Resource limit hit: the command exceeded its {hit} and was stopped. Make the command cheaper, or ask the user to re-run it with higher limits (raise `[shell_limits]` in config.toml)."
+    )
+}
+
 fn macos_provenance_hint(result: &ShellResult) -> Option<&'static str> {
     if looks_like_macos_provenance_failure(result) {
         Some(MACOS_PROVENANCE_HINT)
@@ -1650,6 +1891,7 @@ async fn execute_foreground_via_background(
             tty,
             policy_override,
             extra_env,
+            context.shell_limits.as_ref(),
         )?
     };
     let task_id = spawned
@@ -1811,7 +2053,7 @@ impl ToolSpec for ExecShellTool {
 
         let mut execpolicy_decision: Option<ExecPolicyDecision> = None;
         if context.features.enabled(Feature::ExecPolicy)
-            && let Some(policy) = load_default_policy()
+            && let Some(policy) = load_workspace_policy(&context.workspace)
                 .map_err(|e| ToolError::execution_failed(format!("execpolicy load failed: {e}")))?
         {
             let decision = policy.evaluate(command);
@@ -1860,7 +2102,7 @@ impl ToolSpec for ExecShellTool {
             }
         }
 
-        let policy_override = context.elevated_sandbox_policy.clone();
+        let mut policy_override = context.elevated_sandbox_policy.clone();
         let working_dir = match input
             .get("cwd")
             .or_else(|| input.get("working_dir"))
@@ -1878,7 +2120,7 @@ impl ToolSpec for ExecShellTool {
         // synchronously, captures stdout, parses `KEY=VAL` lines, audit-logs
         // the keys (never the values). Empty / no-op when no hook is
         // configured.
-        let extra_env = if let Some(hook_executor) = &context.runtime.hook_executor {
+        let mut extra_env = if let Some(hook_executor) = &context.runtime.hook_executor {
             let hook_ctx = crate::hooks::HookContext::new()
                 .with_tool_name("exec_shell")
                 .with_tool_args(&input);
@@ -1886,6 +2128,40 @@ impl ToolSpec for ExecShellTool {
         } else {
             std::collections::HashMap::new()
         };
+        // `[env]` / `/env set` variables win over hook output: they are the
+        // user's explicit choice for this session.
+        extra_env.extend(context.session_env_pairs());
+
+        // `/profile use` wrappers (docker exec, ssh, ...) move execution into
+        // the container or remote host, which becomes the sandbox boundary.
+        // The local OS sandbox would only confine the wrapper client itself
+        // (and usually blocks the docker socket or ssh connection), so it is
+        // lifted for wrapped commands — except in read-only Plan mode, where
+        // nothing can be proven read-only on the other side.
+        let exec_profile = context.active_exec_profile();
+        let wrapped_command;
+        let command = match exec_profile.as_ref() {
+            Some(profile) => {
+                if context.sandbox_backend.is_some() {
+                    return Ok(ToolResult::error(format!(
+                        "Exec profile '{}' cannot be combined with an external sandbox backend. Run `/profile use off` to use the backend.",
+                        profile.name
+                    )));
+                }
+                if matches!(policy_override, Some(ExecutionSandboxPolicy::ReadOnly)) {
+                    return Ok(ToolResult::error(format!(
+                        "Shell command blocked: exec profile '{}' cannot run inside the read-only Plan sandbox. Switch to Agent mode (`/mode agent`) or run `/profile use off`.",
+                        profile.name
+                    )));
+                }
+                wrapped_command = profile.wrap(command).ok_or_else(|| {
+                    ToolError::invalid_input("command contains a NUL byte and cannot be wrapped")
+                })?;
+                policy_override = Some(ExecutionSandboxPolicy::DangerFullAccess);
+                wrapped_command.as_str()
+            }
+            None => command,
+        };
 
         // Route through external sandbox backend when configured.
         if let Some(backend) = &context.sandbox_backend {
@@ -1933,6 +2209,7 @@ impl ToolSpec for ExecShellTool {
                         sandboxed: true,
                         sandbox_type: Some("opensandbox".to_string()),
                         sandbox_denied: false,
+                        limit_hit: None,
                     }
                 }
                 Err(e) => {
@@ -1997,6 +2274,7 @@ impl ToolSpec for ExecShellTool {
                 timeout_ms,
                 policy_override,
                 extra_env,
+                context.shell_limits.as_ref(),
             )
         } else if background {
             let mut manager = context
@@ -2012,6 +2290,7 @@ impl ToolSpec for ExecShellTool {
                 tty,
                 policy_override,
                 extra_env,
+                context.shell_limits.as_ref(),
             )
         } else {
             execute_foreground_via_background(
@@ -2098,6 +2377,9 @@ impl ToolSpec for ExecShellTool {
                 if let Some(hint) = provenance_hint {
                     output = format!("{hint}\n\n{output}");
                 }
+                if let Some(hit) = result.limit_hit.as_ref() {
+                    output = format!("{}\n\n{output}", limit_hit_notice(hit));
+                }
 
                 let mut metadata = json!({
                     "exit_code": result.exit_code,
@@ -2135,6 +2417,12 @@ impl ToolSpec for ExecShellTool {
                     }),
                 });
                 metadata["backgrounded"] = json!(background || backgrounded_foreground);
+                if let Some(profile) = exec_profile.as_ref() {
+                    metadata["exec_profile"] = json!({
+                        "name": profile.name,
+                        "command_prefix": profile.command_prefix,
+                    });
+                }
                 if result.status == ShellStatus::TimedOut && !background && !interactive {
                     metadata["foreground_timeout_recovery"] = json!({
                         "process_killed": true,
@@ -2156,6 +2444,9 @@ impl ToolSpec for ExecShellTool {
                 if provenance_hint.is_some() {
                     metadata["macos_provenance_restricted"] = json!(true);
                 }
+                if let Some(hit) = result.limit_hit {
+                    metadata["limit_hit"] = json!(hit);
+                }
 
                 Ok(ToolResult {
                     content: output,
@@ -2229,6 +2520,9 @@ fn build_shell_delta_tool_result(delta: ShellDeltaResult, context: &ToolContext)
     if let Some(hint) = provenance_hint {
         output = format!("{hint}\n\n{output}");
     }
+    if let Some(hit) = result.limit_hit.as_ref() {
+        output = format!("{}\n\n{output}", limit_hit_notice(hit));
+    }
 
     let mut tool_result = ToolResult {
         content: output,
@@ -2268,6 +2562,12 @@ fn build_shell_delta_tool_result(delta: ShellDeltaResult, context: &ToolContext)
     {
         object.insert("macos_provenance_restricted".to_string(), json!(true));
     }
+    if let Some(hit) = result.limit_hit
+        && let Some(metadata) = tool_result.metadata.as_mut()
+        && let Some(object) = metadata.as_object_mut()
+    {
+        object.insert("limit_hit".to_string(), json!(hit));
+    }
     tool_result
 }
 
@@ -2402,7 +2702,7 @@ impl ToolSpec for ShellCancelTool {
             "properties": {
                 "task_id": {
                     "type": "string",
-                    "description": "Task ID returned by exec_shell or task_shell_start"
+                    "description": "Task ID returned by exec_shell or task_shell_start, or a shell_open session name"
                 },
                 "id": {
                     "type": "string",
@@ -2508,7 +2808,7 @@ impl ToolSpec for ShellWaitTool {
             "properties": {
                 "task_id": {
                     "type": "string",
-                    "description": "Task ID returned by exec_shell"
+                    "description": "Task ID returned by exec_shell, or a shell_open session name"
                 },
                 "timeout_ms": {
                     "type": "integer",
@@ -2589,7 +2889,7 @@ impl ToolSpec for ShellInteractTool {
             "properties": {
                 "task_id": {
                     "type": "string",
-                    "description": "Task ID returned by exec_shell"
+                    "description": "Task ID returned by exec_shell, or a shell_open session name"
                 },
                 "input": {
                     "type": "string",
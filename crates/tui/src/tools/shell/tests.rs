@@ -85,6 +85,7 @@ fn failed_network_shell_result(stdout: &str, stderr: &str) -> ShellResult {
         sandboxed: true,
         sandbox_type: Some("seatbelt".to_string()),
         sandbox_denied: false,
+        limit_hit: None,
     }
 }
 
@@ -115,6 +116,7 @@ fn shell_execution_scrubs_parent_env_and_keeps_explicit_env() {
             false,
             None,
             extra,
+            None,
         )
         .expect("execute");
 
@@ -398,6 +400,42 @@ async fn test_exec_shell_metadata_includes_summaries() {
 }
 
 #[cfg(not(windows))]
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_exec_shell_reports_cpu_limit_hit() {
+    use crate::sandbox::limits::{DEFAULT_KEY, LimitKind};
+
+    let tmp = tempdir().expect("tempdir");
+    let limits = ShellLimits::new(std::collections::BTreeMap::from([(
+        DEFAULT_KEY.to_string(),
+        ResourceLimits {
+            cpu_seconds: Some(1),
+            ..ResourceLimits::default()
+        },
+    )]));
+    let ctx = ToolContext::new(tmp.path()).with_shell_limits(limits);
+
+    let result = ExecShellTool
+        .execute(
+            json!({"command": "while :; do :; done", "timeout_ms": 20000}),
+            &ctx,
+        )
+        .await
+        .expect("execute");
+    assert!(!result.success);
+    assert!(
+        result.content.starts_with("Resource limit hit"),
+        "{}",
+        result.content
+    );
+    let meta = result.metadata.expect("metadata");
+    assert_eq!(meta["sandbox_denied"], json!(true));
+    assert_eq!(
+        meta["limit_hit"],
+        json!({"kind": LimitKind::Cpu.as_str(), "limit": 1})
+    );
+}
+
 #[tokio::test]
 async fn test_exec_shell_combined_output_uses_single_stream() {
     let tmp = tempdir().expect("tempdir");
@@ -723,6 +761,7 @@ fn make_failed_result(stderr: &str) -> ShellResult {
         sandboxed: false,
         sandbox_type: None,
         sandbox_denied: false,
+        limit_hit: None,
         stderr_truncated: false,
     }
 }
@@ -857,3 +896,51 @@ fn issue_1691_quoted_commit_message_round_trips() {
         assert_eq!(got, spec.args);
     }
 }
+
+#[cfg(not(windows))]
+#[tokio::test]
+async fn test_exec_shell_runs_through_active_exec_profile() {
+    use crate::config::ExecProfileConfig;
+    use crate::tools::exec_profile::{ExecProfile, new_shared_exec_profile};
+
+    let tmp = tempdir().expect("tempdir");
+    let active = new_shared_exec_profile();
+    let ctx = ToolContext::new(tmp.path()).with_exec_profile(active.clone());
+    // `env VAR=1 sh -c <command>` stands in for `docker exec <container>`.
+    let profile = ExecProfile::from_config(
+        "marked",
+        &ExecProfileConfig {
+            command_prefix: vec!["env".to_string(), "EXEC_PROFILE_MARK=wrapped".to_string()],
+            join_command: None,
+        },
+    )
+    .expect("profile");
+    active.set(Some(profile));
+
+    let result = ExecShellTool
+        .execute(json!({"command": "echo \"$EXEC_PROFILE_MARK\""}), &ctx)
+        .await
+        .expect("execute");
+    assert!(result.success, "{}", result.content);
+    assert!(result.content.contains("wrapped"), "{}", result.content);
+    let meta = result.metadata.expect("metadata");
+    assert_eq!(meta["exec_profile"]["name"], json!("marked"));
+
+    // Plan mode's read-only sandbox cannot vouch for the wrapped side.
+    let plan_ctx = ToolContext::new(tmp.path())
+        .with_exec_profile(active.clone())
+        .with_elevated_sandbox_policy(ExecutionSandboxPolicy::ReadOnly);
+    let blocked = ExecShellTool
+        .execute(json!({"command": "echo hi"}), &plan_ctx)
+        .await
+        .expect("execute");
+    assert!(!blocked.success);
+    assert!(blocked.content.contains("read-only"), "{}", blocked.content);
+
+    active.set(None);
+    let host = ExecShellTool
+        .execute(json!({"command": "echo \"[$EXEC_PROFILE_MARK]\""}), &ctx)
+        .await
+        .expect("execute");
+    assert!(host.content.contains("[]"), "{}", host.content);
+}
@@ -0,0 +1,285 @@
+//! Persistent shell sessions: `shell_open` and `shell_list`.
+//!
+//! A session is a named PTY job in the shared [`ShellManager`] that stays up
+//! across turns (a dev server, a REPL, a login shell). The name works as a
+//! `task_id` for `exec_shell_interact`, `exec_shell_wait`, and
+//! `exec_shell_cancel`, and the TUI streams each session's output into its
+//! own transcript cell and lists them under `/shells`.
+//!
+//! [`ShellManager`]: super::shell::ShellManager
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::shell::ShellStatus;
+use super::spec::{
+    ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolInput, ToolResult, ToolSpec,
+    input_schema_for, parse_input,
+};
+use crate::command_safety::{SafetyLevel, analyze_command};
+use crate::execpolicy::{ExecPolicyDecision, load_workspace_policy};
+use crate::features::Feature;
+
+/// Default wait for startup output before `shell_open` returns.
+const OPEN_WAIT_MS: u64 = 1_500;
+const MAX_OPEN_WAIT_MS: u64 = 30_000;
+
+/// Tool that starts a named, long-lived PTY shell session.
+pub struct ShellOpenTool;
+
+/// `shell_open` arguments.
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ShellOpenInput {
+    /// Session name (letters, digits, `-`, `_`); use it as task_id afterwards
+    name: String,
+    /// Command to run, e.g. `npm run dev` or `python3`. Defaults to the user's shell
+    command: Option<String>,
+    /// Working directory (default: workspace root)
+    cwd: Option<String>,
+    /// Milliseconds to wait for startup output (default: 1500, max: 30000)
+    wait_ms: Option<u64>,
+}
+
+impl ToolInput for ShellOpenInput {
+    fn examples() -> Vec<Value> {
+        vec![
+            json!({"name": "dev", "command": "npm run dev"}),
+            json!({"name": "repl", "command": "python3", "wait_ms": 500}),
+            json!({"name": "sh"}),
+        ]
+    }
+}
+
+#[async_trait]
+impl ToolSpec for ShellOpenTool {
+    fn name(&self) -> &'static str {
+        "shell_open"
+    }
+
+    fn description(&self) -> &'static str {
+        "Open a persistent PTY shell session (dev server, REPL, or shell) that keeps running across turns. Drive it with exec_shell_interact / exec_shell_wait / exec_shell_cancel using the session name as task_id; shell_list shows open sessions."
+    }
+
+    fn input_schema(&self) -> Value {
+        input_schema_for::<ShellOpenInput>()
+    }
+
+    fn input_examples(&self) -> Vec<Value> {
+        ShellOpenInput::examples()
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![
+            ToolCapability::ExecutesCode,
+            ToolCapability::Sandboxable,
+            ToolCapability::RequiresApproval,
+        ]
+    }
+
+    fn approval_requirement(&self) -> ApprovalRequirement {
+        ApprovalRequirement::Required
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let input: ShellOpenInput = parse_input(&input)?;
+        if context.sandbox_backend.is_some() {
+            return Ok(ToolResult::error(
+                "Persistent shell sessions are not supported with external sandbox backends.",
+            ));
+        }
+
+        if let Some(command) = input.command.as_deref() {
+            if context.features.enabled(Feature::ExecPolicy)
+                && let Some(policy) = load_workspace_policy(&context.workspace).map_err(|e| {
+                    ToolError::execution_failed(format!("execpolicy load failed: {e}"))
+                })?
+                && let ExecPolicyDecision::Deny(reason) = policy.evaluate(command)
+            {
+                return Ok(ToolResult::error(format!("BLOCKED: {reason}")));
+            }
+            let safety = analyze_command(command);
+            if !context.auto_approve && safety.level == SafetyLevel::Dangerous {
+                return Ok(ToolResult::error(format!(
+                    "BLOCKED: This command was blocked for safety reasons.\n\nReasons: {}",
+                    safety.reasons.join("; ")
+                )));
+            }
+        }
+
+        let working_dir = match input.cwd.as_deref() {
+            Some(dir) => Some(context.resolve_path(dir)?.to_string_lossy().to_string()),
+            None => None,
+        };
+        let mut extra_env = match &context.runtime.hook_executor {
+            Some(hook_executor) => {
+                let hook_ctx = crate::hooks::HookContext::new()
+                    .with_tool_name("shell_open")
+                    .with_tool_args(&json!({ "command": input.command }));
+                hook_executor.collect_shell_env(&hook_ctx)
+            }
+            None => std::collections::HashMap::new(),
+        };
+        extra_env.extend(context.session_env_pairs());
+
+        let opened = context
+            .shell_manager
+            .lock()
+            .map_err(|_| ToolError::execution_failed("shell manager lock poisoned"))?
+            .open_session(
+                &input.name,
+                input.command.as_deref(),
+                working_dir.as_deref(),
+                context.elevated_sandbox_policy.clone(),
+                extra_env,
+            )
+            .map_err(|err| ToolError::execution_failed(err.to_string()))?;
+        let task_id = opened.task_id.unwrap_or_default();
+
+        let wait_ms = input.wait_ms.unwrap_or(OPEN_WAIT_MS).min(MAX_OPEN_WAIT_MS);
+        tokio::time::sleep(Duration::from_millis(wait_ms)).await;
+        let delta = context
+            .shell_manager
+            .lock()
+            .map_err(|_| ToolError::execution_failed("shell manager lock poisoned"))?
+            .poll_delta(&task_id, false, 0)
+            .map_err(|err| ToolError::execution_failed(err.to_string()))?;
+
+        let status = delta.result.status;
+        let mut content = format!(
+            "Opened shell session `{}` ({task_id}): {}",
+            input.name, delta.command
+        );
+        if status != ShellStatus::Running {
+            content.push_str(&format!(
+                "\nThe session already exited ({status:?}, exit code {:?}).",
+                delta.result.exit_code
+            ));
+        } else {
+            content.push_str(&format!(
+                "\nSend input with exec_shell_interact task_id=\"{}\" (end lines with \\n); it keeps running across turns until exec_shell_cancel.",
+                input.name
+            ));
+        }
+        if !delta.result.stdout.trim().is_empty() {
+            content.push_str("\n\n");
+            content.push_str(delta.result.stdout.trim_end());
+        }
+
+        Ok(ToolResult {
+            content,
+            success: status == ShellStatus::Running,
+            metadata: Some(json!({
+                "task_id": task_id,
+                "name": input.name,
+                "command": delta.command,
+                "status": format!("{status:?}"),
+                "exit_code": delta.result.exit_code,
+            })),
+        })
+    }
+}
+
+/// Tool that lists persistent shell sessions.
+pub struct ShellListTool;
+
+#[async_trait]
+impl ToolSpec for ShellListTool {
+    fn name(&self) -> &'static str {
+        "shell_list"
+    }
+
+    fn description(&self) -> &'static str {
+        "List persistent shell sessions opened with shell_open: name, task id, status, command, and the tail of recent output."
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {},
+            "required": []
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::ReadOnly]
+    }
+
+    async fn execute(&self, _input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let sessions = context
+            .shell_manager
+            .lock()
+            .map_err(|_| ToolError::execution_failed("shell manager lock poisoned"))?
+            .list_sessions();
+        if sessions.is_empty() {
+            return Ok(ToolResult::success(
+                "No persistent shell sessions. Open one with shell_open.",
+            ));
+        }
+        let entries: Vec<Value> = sessions
+            .iter()
+            .map(|session| {
+                json!({
+                    "name": session.name,
+                    "task_id": session.id,
+                    "status": format!("{:?}", session.status),
+                    "exit_code": session.exit_code,
+                    "command": session.command,
+                    "cwd": session.cwd,
+                    "elapsed_ms": session.elapsed_ms,
+                    "output_tail": session.stdout_tail,
+                })
+            })
+            .collect();
+        ToolResult::json(&entries).map_err(|err| ToolError::execution_failed(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_input_parses_examples_and_requires_name() {
+        for example in ShellOpenInput::examples() {
+            parse_input::<ShellOpenInput>(&example).expect("example parses");
+        }
+        assert!(parse_input::<ShellOpenInput>(&json!({"command": "python3"})).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn open_session_is_addressable_by_name_and_listed() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let context = ToolContext::new(tmp.path().to_path_buf());
+
+        let opened = ShellOpenTool
+            .execute(
+                json!({"name": "echoer", "command": "cat", "wait_ms": 100}),
+                &context,
+            )
+            .await
+            .expect("shell_open");
+        assert!(opened.success, "{}", opened.content);
+        assert!(opened.content.contains("`echoer`"), "{}", opened.content);
+
+        let listed = ShellListTool
+            .execute(json!({}), &context)
+            .await
+            .expect("shell_list");
+        assert!(listed.content.contains("\"echoer\""), "{}", listed.content);
+
+        let duplicate = ShellOpenTool
+            .execute(json!({"name": "echoer", "command": "cat"}), &context)
+            .await;
+        assert!(duplicate.is_err());
+
+        let mut manager = context.shell_manager.lock().unwrap();
+        manager.write_stdin("echoer", "ping\n", false).unwrap();
+        let killed = manager.kill("echoer").expect("kill by name");
+        assert_eq!(killed.status, ShellStatus::Killed);
+    }
+}
@@ -0,0 +1,417 @@
+//! `run_snippet` tool: run a short Python or JavaScript snippet in a
+//! throwaway sandbox and return what it printed.
+//!
+//! Meant for ad-hoc data crunching — parsing a JSON dump, computing stats
+//! over a CSV — without writing helper scripts into the workspace. Every run
+//! gets:
+//!
+//! - a fresh temp directory as its working directory, removed afterwards;
+//! - no network: the platform sandbox denies it on macOS (Seatbelt), and on
+//!   Linux the interpreter runs in an empty network namespace (`unshare -rn`);
+//! - a wall-clock timeout plus CPU-time, memory, and file-size limits.
+//!
+//! Files outside the temp directory stay readable, so a snippet can load
+//! workspace data by absolute path. Seatbelt also confines writes to the temp
+//! directory, so on macOS a snippet runs without an approval prompt. On Linux
+//! nothing confines the filesystem — the snippet writes with the user's own
+//! permissions — so every run needs explicit approval, like `exec_shell`.
+//! The tool is only registered on hosts where the network can actually be
+//! cut off.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use super::spec::{
+    ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolResult, ToolSpec,
+    optional_u64, required_str,
+};
+use crate::sandbox::{CommandSpec, ResourceLimits, SandboxManager, SandboxPolicy};
+
+/// Default wall-clock limit for one snippet, in seconds.
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+/// Largest wall-clock limit a call may ask for, in seconds.
+const MAX_TIMEOUT_SECS: u64 = 60;
+/// Address-space cap for Python. Node reserves far more address space than
+/// it uses, so it gets a V8 heap cap instead.
+const PYTHON_MEMORY_MB: u64 = 512;
+const NODE_HEAP_MB: u64 = 256;
+/// Largest file a snippet may write into its temp directory.
+const WRITE_LIMIT_MB: u64 = 16;
+/// Longest stdout / stderr returned to the model, in bytes.
+const MAX_STREAM_BYTES: usize = 32_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SnippetLanguage {
+    Python,
+    JavaScript,
+}
+
+impl SnippetLanguage {
+    fn parse(raw: &str) -> Result<Self, ToolError> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "python" | "py" => Ok(Self::Python),
+            "javascript" | "js" | "node" => Ok(Self::JavaScript),
+            other => Err(ToolError::invalid_input(format!(
+                "Unsupported language '{other}'. Expected python or javascript"
+            ))),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Python => "python",
+            Self::JavaScript => "javascript",
+        }
+    }
+
+    fn script_name(self) -> &'static str {
+        match self {
+            Self::Python => "snippet.py",
+            Self::JavaScript => "snippet.js",
+        }
+    }
+
+    /// Interpreter program and the arguments that precede the script path.
+    fn interpreter(self) -> Option<(String, Vec<String>)> {
+        match self {
+            Self::Python => {
+                let spec = crate::dependencies::resolve_python_interpreter()?;
+                let (program, mut args) = crate::dependencies::split_interpreter_spec(&spec);
+                // Isolated mode: ignore PYTHON* variables and the user site dir.
+                args.push("-I".to_string());
+                Some((program, args))
+            }
+            Self::JavaScript => {
+                let node = crate::dependencies::resolve_node()?;
+                Some((node, vec![format!("--max-old-space-size={NODE_HEAP_MB}")]))
+            }
+        }
+    }
+
+    fn resource_limits(self, timeout_secs: u64) -> ResourceLimits {
+        ResourceLimits {
+            cpu_seconds: Some(timeout_secs),
+            memory_mb: match self {
+                Self::Python => Some(PYTHON_MEMORY_MB),
+                Self::JavaScript => None,
+            },
+            write_mb: Some(WRITE_LIMIT_MB),
+        }
+    }
+}
+
+/// How a snippet is kept off the network on this host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NetworkIsolation {
+    /// The platform sandbox denies network access itself.
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    PlatformSandbox,
+    /// The interpreter runs in a new, empty network namespace.
+    NetNamespace,
+}
+
+fn network_isolation() -> Option<NetworkIsolation> {
+    static CACHE: OnceLock<Option<NetworkIsolation>> = OnceLock::new();
+    *CACHE.get_or_init(|| {
+        #[cfg(target_os = "macos")]
+        if crate::sandbox::get_platform_sandbox()
+            == Some(crate::sandbox::SandboxType::MacosSeatbelt)
+        {
+            return Some(NetworkIsolation::PlatformSandbox);
+        }
+        // Unprivileged user namespaces can be disabled by the distro or a
+        // container runtime, so try one instead of assuming.
+        #[cfg(target_os = "linux")]
+        if std::process::Command::new("unshare")
+            .args(["-rn", "true"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+        {
+            return Some(NetworkIsolation::NetNamespace);
+        }
+        None
+    })
+}
+
+/// Only a platform sandbox that also confines writes makes a snippet safe to
+/// run unprompted; a network namespace alone leaves the user's files exposed.
+fn snippet_approval(isolation: Option<NetworkIsolation>) -> ApprovalRequirement {
+    match isolation {
+        Some(NetworkIsolation::PlatformSandbox) => ApprovalRequirement::Auto,
+        Some(NetworkIsolation::NetNamespace) | None => ApprovalRequirement::Required,
+    }
+}
+
+/// Whether `run_snippet` can run here: the network can be cut off and at
+/// least one interpreter is installed.
+pub fn is_available() -> bool {
+    network_isolation().is_some()
+        && (crate::dependencies::resolve_python_interpreter().is_some()
+            || crate::dependencies::resolve_node().is_some())
+}
+
+pub struct RunSnippetTool;
+
+#[async_trait]
+impl ToolSpec for RunSnippetTool {
+    fn name(&self) -> &'static str {
+        "run_snippet"
+    }
+
+    fn description(&self) -> &'static str {
+        "Run a short Python or JavaScript snippet in a throwaway sandbox (fresh temp dir, no network, CPU/memory/time limits) and return its stdout/stderr. Use it for ad-hoc data analysis such as parsing JSON or computing stats over a CSV; workspace files can be read by absolute path. Print the results you need."
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "language": {
+                    "type": "string",
+                    "enum": ["python", "javascript"],
+                    "description": "Interpreter to run the snippet with."
+                },
+                "code": {
+                    "type": "string",
+                    "description": "Source code of the snippet."
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_TIMEOUT_SECS,
+                    "default": DEFAULT_TIMEOUT_SECS,
+                    "description": "Wall-clock and CPU-time limit in seconds."
+                }
+            },
+            "required": ["language", "code"],
+            "additionalProperties": false
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::ExecutesCode, ToolCapability::Sandboxable]
+    }
+
+    fn approval_requirement(&self) -> ApprovalRequirement {
+        snippet_approval(network_isolation())
+    }
+
+    fn supports_parallel(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, input: Value, _context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let language = SnippetLanguage::parse(required_str(&input, "language")?)?;
+        let code = required_str(&input, "code")?;
+        let timeout_secs =
+            optional_u64(&input, "timeout_secs", DEFAULT_TIMEOUT_SECS).clamp(1, MAX_TIMEOUT_SECS);
+        run_snippet(language, code, timeout_secs).await
+    }
+}
+
+async fn run_snippet(
+    language: SnippetLanguage,
+    code: &str,
+    timeout_secs: u64,
+) -> Result<ToolResult, ToolError> {
+    let isolation = network_isolation().ok_or_else(|| {
+        ToolError::execution_failed(
+            "run_snippet: this host can't cut snippets off the network (no Seatbelt or \
+             unprivileged network namespaces)",
+        )
+    })?;
+    let (program, mut args) = language.interpreter().ok_or_else(|| {
+        ToolError::execution_failed(format!(
+            "run_snippet: no {} interpreter found on PATH",
+            language.as_str()
+        ))
+    })?;
+
+    let dir = tempfile::tempdir()
+        .map_err(|e| ToolError::execution_failed(format!("tempdir failed: {e}")))?;
+    let script = dir.path().join(language.script_name());
+    tokio::fs::write(&script, code)
+        .await
+        .map_err(|e| ToolError::execution_failed(format!("snippet write failed: {e}")))?;
+    args.push(script.to_string_lossy().into_owned());
+
+    let (program, args) = match isolation {
+        NetworkIsolation::PlatformSandbox => (program, args),
+        NetworkIsolation::NetNamespace => {
+            let mut wrapped = vec!["-rn".to_string(), "--".to_string(), program];
+            wrapped.extend(args);
+            ("unshare".to_string(), wrapped)
+        }
+    };
+    let policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: Vec::new(),
+        network_access: false,
+        exclude_tmpdir: true,
+        exclude_slash_tmp: true,
+    };
+    let env = snippet_env(dir.path());
+    let spec = CommandSpec::program(
+        &program,
+        args,
+        dir.path().to_path_buf(),
+        Duration::from_secs(timeout_secs),
+    )
+    .with_policy(policy)
+    .with_env(env)
+    .with_resource_limits(language.resource_limits(timeout_secs));
+    let exec_env = SandboxManager::new().prepare(&spec);
+
+    let mut cmd = std::process::Command::new(exec_env.program());
+    cmd.args(exec_env.args())
+        .current_dir(dir.path())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    exec_env.resource_limits.apply(&mut cmd);
+    crate::child_env::apply_to_command(&mut cmd, crate::child_env::string_map_env(&exec_env.env));
+    let mut cmd = tokio::process::Command::from(cmd);
+    cmd.kill_on_drop(true);
+
+    let started = std::time::Instant::now();
+    let output = tokio::time::timeout(exec_env.timeout, cmd.output()).await;
+    let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    let Ok(output) = output else {
+        let payload = json!({
+            "language": language.as_str(),
+            "timed_out": true,
+            "duration_ms": duration_ms,
+            "stdout": "",
+            "stderr": format!("snippet killed after {timeout_secs}s"),
+        });
+        return Ok(snippet_result(payload, false));
+    };
+    let output = output
+        .map_err(|e| ToolError::execution_failed(format!("run_snippet spawn failed: {e}")))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    #[cfg(unix)]
+    let signal = std::os::unix::process::ExitStatusExt::signal(&output.status);
+    #[cfg(not(unix))]
+    let signal = None;
+    let limit_hit = exec_env
+        .resource_limits
+        .detect_hit(output.status.code(), signal, &stderr);
+    let payload = json!({
+        "language": language.as_str(),
+        "exit_code": output.status.code(),
+        "timed_out": false,
+        "limit_hit": limit_hit.map(|hit| hit.to_string()),
+        "duration_ms": duration_ms,
+        "stdout": cap_stream(&stdout),
+        "stderr": cap_stream(&stderr),
+    });
+    Ok(snippet_result(payload, output.status.success()))
+}
+
+/// Environment for the interpreter: home and temp point into the snippet's
+/// own directory so caches and scratch files are thrown away with it.
+fn snippet_env(dir: &Path) -> HashMap<String, String> {
+    let dir = dir.to_string_lossy().into_owned();
+    HashMap::from([
+        ("HOME".to_string(), dir.clone()),
+        ("TMPDIR".to_string(), dir),
+        ("PYTHONDONTWRITEBYTECODE".to_string(), "1".to_string()),
+    ])
+}
+
+fn cap_stream(text: &str) -> String {
+    crate::utils::truncate_with_ellipsis(text, MAX_STREAM_BYTES, "\n[output truncated]")
+}
+
+fn snippet_result(payload: Value, success: bool) -> ToolResult {
+    ToolResult {
+        content: serde_json::to_string(&payload).unwrap_or_else(|_| payload.to_string()),
+        success,
+        metadata: Some(payload),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn can_run(language: SnippetLanguage) -> bool {
+        network_isolation().is_some() && language.interpreter().is_some()
+    }
+
+    #[test]
+    fn language_parsing_accepts_aliases() {
+        assert_eq!(
+            SnippetLanguage::parse("Python").unwrap(),
+            SnippetLanguage::Python
+        );
+        assert_eq!(
+            SnippetLanguage::parse("js").unwrap(),
+            SnippetLanguage::JavaScript
+        );
+        assert!(SnippetLanguage::parse("ruby").is_err());
+    }
+
+    #[test]
+    fn only_write_confining_sandbox_skips_approval() {
+        assert_eq!(
+            snippet_approval(Some(NetworkIsolation::PlatformSandbox)),
+            ApprovalRequirement::Auto
+        );
+        assert_eq!(
+            snippet_approval(Some(NetworkIsolation::NetNamespace)),
+            ApprovalRequirement::Required
+        );
+        assert_eq!(snippet_approval(None), ApprovalRequirement::Required);
+    }
+
+    #[tokio::test]
+    async fn python_snippet_prints_without_network_or_workspace_writes() {
+        if !can_run(SnippetLanguage::Python) {
+            return;
+        }
+        let code = r#"
+import json, os, socket
+print(sum(json.loads("[1, 2, 3]")))
+print(os.getcwd() != "/")
+try:
+    socket.create_connection(("1.1.1.1", 53), timeout=2)
+    print("network: open")
+except OSError:
+    print("network: blocked")
+"#;
+        let result = run_snippet(SnippetLanguage::Python, code, 10)
+            .await
+            .expect("run");
+        assert!(result.success, "{}", result.content);
+        let payload = result.metadata.expect("payload");
+        let stdout = payload["stdout"].as_str().unwrap_or_default();
+        assert!(stdout.starts_with("6\nTrue\n"), "{stdout}");
+        assert!(stdout.contains("network: blocked"), "{stdout}");
+    }
+
+    #[tokio::test]
+    async fn runaway_snippet_is_stopped() {
+        if !can_run(SnippetLanguage::Python) {
+            return;
+        }
+        let result = run_snippet(SnippetLanguage::Python, "while True:\n    pass\n", 1)
+            .await
+            .expect("run");
+        assert!(!result.success);
+        let payload = result.metadata.expect("payload");
+        assert!(
+            payload["timed_out"] == json!(true) || payload["limit_hit"].is_string(),
+            "{payload}"
+        );
+    }
+}
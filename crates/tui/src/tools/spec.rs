@@ -5,11 +5,15 @@
 //! - `ToolContext`: Execution context passed to tools
 //! - `ToolResult`: Unified result type for tool execution
 //! - `ToolCapability`: Capabilities and requirements of tools
+//! - `ToolInput`: Typed tool arguments whose JSON schema is generated from the struct
 
 use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use schemars::JsonSchema;
+use schemars::generate::SchemaSettings;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
 use tokio_util::sync::CancellationToken;
 
@@ -114,6 +118,9 @@ pub struct ToolContext {
     /// Optional user-facing hint for shell commands that fail because the
     /// active sandbox policy intentionally denies outbound network access.
     pub shell_network_denied_hint: Option<String>,
+    /// `[shell_limits]` CPU/memory/file-size caps for shell commands, keyed
+    /// by sandbox policy. `None` runs commands without resource limits.
+    pub shell_limits: Option<crate::sandbox::limits::ShellLimits>,
     /// Whether tools should auto-approve without safety checks (YOLO mode).
     /// When true, command safety analysis is skipped for shell execution.
     pub auto_approve: bool,
@@ -169,6 +176,20 @@ pub struct ToolContext {
     pub workshop_vars: Option<
         std::sync::Arc<tokio::sync::Mutex<crate::tools::large_output_router::WorkshopVariables>>,
     >,
+
+    /// Pre-write syntax checks for `write_file`, `edit_file`, and
+    /// `apply_patch`. Set via `[edit_validation]` in config.toml.
+    pub edit_validation: crate::tools::edit_validation::EditValidationConfig,
+
+    /// Session-start baselines for files changed by write tools, backing
+    /// `/changes`. `None` outside an interactive session.
+    pub session_changes: Option<crate::tools::session_changes::SharedSessionChanges>,
+
+    /// Active `/profile use` wrapper for `exec_shell` commands.
+    pub exec_profile: Option<crate::tools::exec_profile::SharedExecProfile>,
+
+    /// `[env]` / `/env set` variables injected into shell tools.
+    pub session_env: Option<crate::tools::session_env::SharedSessionEnv>,
 }
 
 impl ToolContext {
@@ -187,6 +208,7 @@ impl ToolContext {
             notes_path,
             mcp_config_path,
             elevated_sandbox_policy: None,
+            shell_limits: None,
             shell_network_denied_hint: None,
             auto_approve: false,
             features: Features::with_defaults(),
@@ -202,6 +224,10 @@ impl ToolContext {
             search_provider: crate::config::SearchProvider::default(),
             search_api_key: None,
             workshop_vars: None,
+            edit_validation: crate::tools::edit_validation::EditValidationConfig::default(),
+            session_changes: None,
+            exec_profile: None,
+            session_env: None,
         }
     }
 
@@ -223,6 +249,7 @@ impl ToolContext {
             notes_path: notes_path.into(),
             mcp_config_path: mcp_config_path.into(),
             elevated_sandbox_policy: None,
+            shell_limits: None,
             shell_network_denied_hint: None,
             auto_approve: false,
             features: Features::with_defaults(),
@@ -238,6 +265,10 @@ impl ToolContext {
             search_provider: crate::config::SearchProvider::default(),
             search_api_key: None,
             workshop_vars: None,
+            edit_validation: crate::tools::edit_validation::EditValidationConfig::default(),
+            session_changes: None,
+            exec_profile: None,
+            session_env: None,
         }
     }
 
@@ -259,6 +290,7 @@ impl ToolContext {
             notes_path: notes_path.into(),
             mcp_config_path: mcp_config_path.into(),
             elevated_sandbox_policy: None,
+            shell_limits: None,
             shell_network_denied_hint: None,
             auto_approve,
             features: Features::with_defaults(),
@@ -274,6 +306,10 @@ impl ToolContext {
             search_provider: crate::config::SearchProvider::default(),
             search_api_key: None,
             workshop_vars: None,
+            edit_validation: crate::tools::edit_validation::EditValidationConfig::default(),
+            session_changes: None,
+            exec_profile: None,
+            session_env: None,
         }
     }
 
@@ -291,6 +327,92 @@ impl ToolContext {
         self
     }
 
+    /// Record session-start baselines for files changed by write tools.
+    #[must_use]
+    pub fn with_session_changes(
+        mut self,
+        changes: crate::tools::session_changes::SharedSessionChanges,
+    ) -> Self {
+        self.session_changes = Some(changes);
+        self
+    }
+
+    /// Share the `/profile use` selection with `exec_shell`.
+    #[must_use]
+    pub fn with_exec_profile(
+        mut self,
+        profile: crate::tools::exec_profile::SharedExecProfile,
+    ) -> Self {
+        self.exec_profile = Some(profile);
+        self
+    }
+
+    /// Exec profile `exec_shell` should wrap commands with, if one is active.
+    #[must_use]
+    pub fn active_exec_profile(&self) -> Option<crate::tools::exec_profile::ExecProfile> {
+        self.exec_profile.as_ref()?.get()
+    }
+
+    /// Share the `/env` variable set with the shell tools.
+    #[must_use]
+    pub fn with_session_env(mut self, env: crate::tools::session_env::SharedSessionEnv) -> Self {
+        self.session_env = Some(env);
+        self
+    }
+
+    /// Session variables to add to a shell tool's child environment.
+    #[must_use]
+    pub fn session_env_pairs(&self) -> std::collections::HashMap<String, String> {
+        self.session_env
+            .as_ref()
+            .map(|env| env.env_pairs())
+            .unwrap_or_default()
+    }
+
+    /// Replace secret session-variable values in tool output with `***`.
+    #[must_use]
+    pub fn mask_session_secrets(&self, mut result: ToolResult) -> ToolResult {
+        let Some(env) = self.session_env.as_ref() else {
+            return result;
+        };
+        result.content = env.mask(&result.content);
+        if let Some(metadata) = result.metadata.as_mut() {
+            Self::mask_value_strings(env, metadata);
+        }
+        result
+    }
+
+    /// Mask every string (keys included) inside `value`. Working on the parsed
+    /// value rather than its serialization catches secrets that JSON escapes.
+    fn mask_value_strings(env: &crate::tools::session_env::SessionEnv, value: &mut Value) {
+        match value {
+            Value::String(text) => *text = env.mask(text),
+            Value::Array(items) => {
+                for item in items {
+                    Self::mask_value_strings(env, item);
+                }
+            }
+            Value::Object(map) => {
+                *map = std::mem::take(map)
+                    .into_iter()
+                    .map(|(key, mut child)| {
+                        Self::mask_value_strings(env, &mut child);
+                        (env.mask(&key), child)
+                    })
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+
+    /// Note that `path` is about to be written or deleted so `/changes` can
+    /// diff and revert it against its session-start contents.
+    pub fn note_before_write(&self, path: &Path) {
+        if let Some(changes) = self.session_changes.as_ref() {
+            changes.record_before_write(path);
+        }
+    }
+
     /// Attach the active engine cancellation token.
     #[must_use]
     pub fn with_cancel_token(mut self, cancel_token: CancellationToken) -> Self {
@@ -491,6 +613,12 @@ impl ToolContext {
         self
     }
 
+    /// Set the per-policy resource limits for shell commands.
+    pub fn with_shell_limits(mut self, limits: crate::sandbox::limits::ShellLimits) -> Self {
+        self.shell_limits = Some(limits);
+        self
+    }
+
     /// Set the shell network-denial hint used by network-restricted modes.
     pub fn with_shell_network_denied_hint(mut self, hint: impl Into<String>) -> Self {
         self.shell_network_denied_hint = Some(hint.into());
@@ -646,10 +774,115 @@ pub trait ToolSpec: Send + Sync {
         false
     }
 
+    /// Returns example inputs this tool accepts. Typed tools return
+    /// [`ToolInput::examples`]; the registry tests check every example
+    /// against `input_schema()`.
+    #[allow(dead_code)]
+    fn input_examples(&self) -> Vec<Value> {
+        Vec::new()
+    }
+
+    /// Returns the built-in call this invocation stands for, if any, as a
+    /// `(tool name, input)` pair. The engine plans, approves, and runs that
+    /// call in place of this one, so approval prompts and guardrails see
+    /// exactly what will execute.
+    fn delegate_call(&self, _input: &Value) -> Option<(String, Value)> {
+        None
+    }
+
     /// Execute the tool with the given input and context.
     async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError>;
 }
 
+// === Typed Tool Inputs ===
+
+/// Tool arguments deserialized into a Rust struct whose JSON schema is
+/// generated from that same struct, so the schema the model sees cannot
+/// drift from what `execute` accepts.
+///
+/// Derive `Deserialize` and `JsonSchema`; field doc comments become property
+/// descriptions, `Option` and `#[serde(default)]` fields are optional. Wire
+/// a typed tool up with [`input_schema_for`] in `input_schema`,
+/// [`parse_input`] in `execute`, and `T::examples()` in `input_examples`.
+pub trait ToolInput: DeserializeOwned + JsonSchema {
+    /// Representative inputs, checked against the generated schema in tests.
+    fn examples() -> Vec<Value>;
+}
+
+/// Generate the model-facing input schema for `T`.
+///
+/// Subschemas are inlined and the root `$schema`, `title`, and
+/// `description` are dropped (the tool description covers them), and the
+/// root always has a `required` array. Optional fields are left out of
+/// `required` rather than typed as nullable, and Rust integer formats
+/// (`uint64`, ...) are removed.
+#[must_use]
+pub fn input_schema_for<T: JsonSchema>() -> Value {
+    let mut schema = SchemaSettings::draft07()
+        .with(|settings| settings.inline_subschemas = true)
+        .into_generator()
+        .into_root_schema_for::<T>()
+        .to_value();
+    if let Some(root) = schema.as_object_mut() {
+        for key in ["$schema", "title", "description"] {
+            root.remove(key);
+        }
+        // Hand-written schemas always carry `required`, even when empty.
+        root.entry("required")
+            .or_insert_with(|| Value::Array(Vec::new()));
+    }
+    simplify_generated_schema(&mut schema);
+    schema
+}
+
+fn simplify_generated_schema(schema: &mut Value) {
+    match schema {
+        Value::Object(map) => {
+            if let Some(Value::Array(types)) = map.get_mut("type") {
+                types.retain(|ty| ty != "null");
+                if types.len() == 1 {
+                    let only = types.remove(0);
+                    map.insert("type".to_string(), only);
+                }
+            }
+            if map
+                .get("format")
+                .and_then(Value::as_str)
+                .is_some_and(|format| format.starts_with("int") || format.starts_with("uint"))
+            {
+                map.remove("format");
+            }
+            for child in map.values_mut() {
+                simplify_generated_schema(child);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(simplify_generated_schema),
+        _ => {}
+    }
+}
+
+/// Deserialize a tool's typed input. A missing field produces the same
+/// error (including the "Input provided" hint) as [`required_str`]; other
+/// mismatches name the problem and show an example input.
+pub fn parse_input<T: ToolInput>(input: &Value) -> Result<T, ToolError> {
+    T::deserialize(input).map_err(|err| {
+        let message = err.to_string();
+        if let Some(field) = message
+            .strip_prefix("missing field `")
+            .and_then(|rest| rest.split('`').next())
+            && let Err(missing) = required_str(input, field)
+        {
+            return missing;
+        }
+        match T::examples().first() {
+            Some(example) => {
+                ToolError::invalid_input(format!("{message}. Example input: {example}"))
+            }
+            None => ToolError::invalid_input(message),
+        }
+    })
+}
+
 // === Unit Tests ===
 
 #[cfg(test)]
@@ -681,6 +914,25 @@ mod tests {
         assert!(result.content.contains("key"));
     }
 
+    #[test]
+    fn session_secrets_with_json_escapes_are_masked() {
+        let tmp = tempdir().expect("tempdir");
+        let env = crate::tools::session_env::new_shared_session_env(None);
+        env.set("DB_PASSWORD", r#"pa"ss\word"#, true);
+        let ctx = ToolContext::new(tmp.path().to_path_buf()).with_session_env(env);
+
+        let leaked = json!({ "stdout": r#"pa"ss\word"#, "args": [r#"-p pa"ss\word"#] });
+        let result = ToolResult::json(&leaked)
+            .unwrap()
+            .with_metadata(leaked.clone());
+        let masked = ctx.mask_session_secrets(result);
+        assert!(!masked.content.contains("ss\\\\word"), "{}", masked.content);
+        assert_eq!(
+            masked.metadata.unwrap(),
+            json!({ "stdout": "***", "args": ["-p ***"] })
+        );
+    }
+
     #[test]
     fn test_tool_result_with_metadata() {
         let result = ToolResult::success("content").with_metadata(json!({"extra": true}));
@@ -824,4 +1076,57 @@ mod tests {
         let level = ApprovalRequirement::default();
         assert_eq!(level, ApprovalRequirement::Auto);
     }
+
+    /// Example typed input.
+    #[derive(Debug, serde::Deserialize, JsonSchema)]
+    struct SampleInput {
+        /// Target path
+        path: String,
+        /// Line limit
+        limit: Option<u64>,
+        #[serde(default)]
+        recursive: bool,
+    }
+
+    impl ToolInput for SampleInput {
+        fn examples() -> Vec<Value> {
+            vec![json!({"path": "src", "limit": 10})]
+        }
+    }
+
+    #[test]
+    fn test_input_schema_for_generates_plain_object_schema() {
+        let schema = input_schema_for::<SampleInput>();
+        assert_eq!(schema["type"], json!("object"));
+        assert_eq!(schema["required"], json!(["path"]));
+        assert_eq!(
+            schema["properties"]["path"]["description"],
+            json!("Target path")
+        );
+        assert_eq!(schema["properties"]["limit"]["type"], json!("integer"));
+        assert!(schema["properties"]["limit"].get("format").is_none());
+        assert_eq!(schema["properties"]["recursive"]["type"], json!("boolean"));
+        assert!(schema.get("$schema").is_none());
+        assert!(schema.get("title").is_none());
+        assert!(schema.get("description").is_none());
+    }
+
+    #[test]
+    fn test_parse_input_reports_missing_and_mistyped_fields() {
+        let parsed: SampleInput = parse_input(&json!({"path": "src", "limit": 5})).unwrap();
+        assert_eq!(parsed.path, "src");
+        assert_eq!(parsed.limit, Some(5));
+        assert!(!parsed.recursive);
+
+        let err = parse_input::<SampleInput>(&json!({"file": "src"})).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to validate input: missing required field 'path'. Input provided: file"
+        );
+
+        let err = parse_input::<SampleInput>(&json!({"path": "src", "limit": "ten"})).unwrap_err();
+        let err = err.to_string();
+        assert!(err.contains("invalid type"), "{err}");
+        assert!(err.contains("Example input: {"), "{err}");
+    }
 }
@@ -29,6 +29,7 @@ use crate::config::MAX_SUBAGENTS;
 use crate::core::events::Event;
 use crate::llm_client::LlmClient;
 use crate::models::{ContentBlock, Message, MessageRequest, SystemPrompt, Tool};
+use crate::sandbox::SandboxPolicy;
 use crate::tools::handle::VarHandle;
 use crate::tools::plan::{PlanState, SharedPlanState};
 use crate::tools::registry::{ToolRegistry, ToolRegistryBuilder};
@@ -62,7 +63,7 @@ fn release_resident_leases_for(agent_id: &str) {
     }
 }
 
-const DEFAULT_MAX_STEPS: u32 = 100;
+const DEFAULT_MAX_STEPS: u32 = crate::config::DEFAULT_SUBAGENT_MAX_STEPS;
 const TOOL_TIMEOUT: Duration = Duration::from_secs(30);
 /// Per-step LLM API call timeout. Each `create_message` request must complete
 /// within this window or the step is treated as timed out. Prevents a single
@@ -75,6 +76,9 @@ const TOOL_TIMEOUT: Duration = Duration::from_secs(30);
 const DEFAULT_STEP_API_TIMEOUT: Duration =
     Duration::from_secs(crate::config::DEFAULT_SUBAGENT_API_TIMEOUT_SECS);
 const RESULT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// How long a cancelled sub-agent gets to notice its token and report a
+/// partial result before its task is aborted outright.
+const CANCEL_GRACE: Duration = Duration::from_secs(3);
 const DEFAULT_RESULT_TIMEOUT_MS: u64 = 30_000;
 #[allow(dead_code)] // Legacy agent_wait clamp; new agent_eval uses DEFAULT/MAX.
 const MIN_WAIT_TIMEOUT_MS: u64 = 10_000;
@@ -410,6 +414,7 @@ impl SubAgentType {
                 "exec_interact",
                 "run_tests",
                 "diagnostics",
+                "system_info",
                 "note",
             ],
             Self::ToolAgent => vec![
@@ -478,6 +483,44 @@ pub(crate) struct SubAgentSpawnOptions {
     pub model: Option<String>,
     pub nickname: Option<String>,
     pub fork_context: bool,
+    /// Step budget for this child; `None` uses the manager default.
+    pub max_steps: Option<u32>,
+    /// Shell sandbox for this child; `None` inherits the runtime's.
+    pub sandbox: Option<SandboxPolicy>,
+}
+
+/// Per-spawn shell sandbox request. It can only tighten the parent's
+/// sandbox; see [`clamp_sandbox`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SandboxRequest {
+    ReadOnly,
+    WorkspaceWrite,
+}
+
+fn sandbox_label(policy: &SandboxPolicy) -> &'static str {
+    match policy {
+        SandboxPolicy::ReadOnly => "read-only",
+        SandboxPolicy::WorkspaceWrite { .. } => "workspace-write",
+        SandboxPolicy::ExternalSandbox { .. } => "external-sandbox",
+        SandboxPolicy::DangerFullAccess => "danger-full-access",
+    }
+}
+
+/// Resolve a child's sandbox: the requested policy, unless the parent's is
+/// already stricter. Network access is never granted beyond the parent's.
+fn clamp_sandbox(parent: Option<&SandboxPolicy>, requested: SandboxRequest) -> SandboxPolicy {
+    match (requested, parent) {
+        (SandboxRequest::ReadOnly, _) | (_, Some(SandboxPolicy::ReadOnly)) => {
+            SandboxPolicy::ReadOnly
+        }
+        (SandboxRequest::WorkspaceWrite, Some(policy @ SandboxPolicy::WorkspaceWrite { .. })) => {
+            policy.clone()
+        }
+        (SandboxRequest::WorkspaceWrite, parent) => SandboxPolicy::workspace_with_roots(
+            Vec::new(),
+            parent.is_some_and(SandboxPolicy::has_network_access),
+        ),
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -545,6 +588,10 @@ struct SpawnRequest {
     /// Optional recursion budget for descendants opened by this child.
     /// `0` means the child may not call `agent_open` recursively.
     max_depth: Option<u32>,
+    /// Requested step budget, clamped to the runtime's `max_steps`.
+    max_steps: Option<u32>,
+    /// Requested shell sandbox, clamped to the parent's.
+    sandbox: Option<SandboxRequest>,
 }
 
 #[derive(Debug, Clone)]
@@ -635,9 +682,9 @@ pub struct SubAgentForkContext {
 ///
 /// Carries everything a child needs to (a) build its own tool registry —
 /// including the manager so grandchildren can spawn — and (b) cooperate with
-/// lifecycle cancellation and depth caps. `child_runtime()` links cancellation
-/// tokens, while `background_runtime()` deliberately detaches long-running
-/// `agent_open` sessions from the caller's turn token.
+/// lifecycle cancellation and depth caps. Both `child_runtime()` and
+/// `background_runtime()` derive child tokens, so cancelling the turn that
+/// spawned an agent cancels the agent (and its descendants) too.
 #[derive(Clone)]
 pub struct SubAgentRuntime {
     pub client: DeepSeekClient,
@@ -659,9 +706,9 @@ pub struct SubAgentRuntime {
     /// exceed this is rejected at the spawn entry. Use `>` (strictly
     /// greater than) so equality is allowed — matches codex's pattern.
     pub max_spawn_depth: u32,
-    /// Cooperative cancellation token. Direct `child_runtime()` callers derive
-    /// a child token from the parent; model-visible `agent_open` uses
-    /// `background_runtime()` to replace that token with a detached one.
+    /// Cooperative cancellation token, derived from the parent's. The manager
+    /// keeps a clone per agent so a single agent can be cancelled without
+    /// touching its siblings.
     pub cancel_token: CancellationToken,
     /// Structured progress / lifecycle stream. Cloned across children so the
     /// whole spawn tree publishes into one ordered, fan-out-able mailbox.
@@ -681,6 +728,12 @@ pub struct SubAgentRuntime {
     /// false-timeout the child mid-thinking. `child_runtime()` and
     /// `background_runtime()` preserve the parent's value (#1806, #1808).
     pub step_api_timeout: Duration,
+    /// Step budget for children spawned from this runtime, and the ceiling
+    /// for per-spawn `max_steps` overrides (`[subagents] max_steps`).
+    pub max_steps: u32,
+    /// Models the spawn tool may request (`[subagents] allowed_models`).
+    /// `None` allows any DeepSeek model.
+    pub allowed_models: Option<Vec<String>>,
 }
 
 impl SubAgentRuntime {
@@ -715,6 +768,8 @@ impl SubAgentRuntime {
             parent_completion_tx: None,
             fork_context: None,
             step_api_timeout: DEFAULT_STEP_API_TIMEOUT,
+            max_steps: DEFAULT_MAX_STEPS,
+            allowed_models: None,
         }
     }
 
@@ -777,6 +832,20 @@ impl SubAgentRuntime {
         self
     }
 
+    /// Set the child step budget and per-spawn `max_steps` ceiling.
+    #[must_use]
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// Restrict the models the spawn tool may request. `None` allows any.
+    #[must_use]
+    pub fn with_allowed_models(mut self, allowed_models: Option<Vec<String>>) -> Self {
+        self.allowed_models = allowed_models;
+        self
+    }
+
     /// Attach raw role/type model overrides. Values are intentionally
     /// validated at spawn time so bad config fails before a partial spawn.
     #[must_use]
@@ -806,16 +875,15 @@ impl SubAgentRuntime {
         self
     }
 
-    /// Return a child runtime that is deliberately detached from the parent
-    /// turn cancellation token. Background sub-agents should keep running when
-    /// the parent turn is cancelled; explicit agent cancellation still
-    /// aborts their task handles through the manager.
+    /// Return a child runtime for a background sub-agent. The agent outlives
+    /// the turn that spawned it when that turn ends normally (the engine
+    /// swaps in a fresh token per turn rather than cancelling the old one),
+    /// but cancelling the spawning turn with Esc cancels the agent too.
+    /// The tool context shares the agent's token so running tools see it.
     #[must_use]
     pub fn background_runtime(&self) -> Self {
         let mut runtime = self.child_runtime();
-        let token = CancellationToken::new();
-        runtime.cancel_token = token.clone();
-        runtime.context.cancel_token = Some(token);
+        runtime.context.cancel_token = Some(runtime.cancel_token.clone());
         runtime
     }
 
@@ -849,6 +917,8 @@ impl SubAgentRuntime {
             parent_completion_tx: self.parent_completion_tx.clone(),
             fork_context: self.fork_context.clone(),
             step_api_timeout: self.step_api_timeout,
+            max_steps: self.max_steps,
+            allowed_models: self.allowed_models.clone(),
         }
     }
 
@@ -881,8 +951,15 @@ pub struct SubAgent {
     /// against the manager's `current_session_boot_id` to classify the
     /// agent as in-session vs prior-session at list time.
     pub session_boot_id: String,
+    /// Step budget the agent was spawned with; reused on resume.
+    pub max_steps: u32,
+    /// Shell sandbox override from the spawn request; reapplied on resume.
+    pub sandbox: Option<SandboxPolicy>,
     input_tx: Option<mpsc::UnboundedSender<SubAgentInput>>,
     task_handle: Option<JoinHandle<()>>,
+    /// The running task's cancellation token; cancelling it makes the loop
+    /// stop at its next await point and report a partial result.
+    cancel_token: Option<CancellationToken>,
 }
 
 impl SubAgent {
@@ -916,8 +993,11 @@ impl SubAgent {
             started_at: Instant::now(),
             allowed_tools,
             session_boot_id,
+            max_steps: DEFAULT_MAX_STEPS,
+            sandbox: None,
             input_tx: Some(input_tx),
             task_handle: None,
+            cancel_token: None,
         }
     }
 
@@ -1104,8 +1184,11 @@ impl SubAgentManager {
                 // manager treats that the same as a non-matching id —
                 // i.e. agent classified as prior-session.
                 session_boot_id: persisted.session_boot_id,
+                max_steps: self.max_steps,
+                sandbox: None,
                 input_tx: None,
                 task_handle: None,
+                cancel_token: None,
             };
             self.agents.insert(persisted.id, agent);
         }
@@ -1198,6 +1281,9 @@ impl SubAgentManager {
         if let Some(model) = options.model.as_deref() {
             runtime.model = model.to_string();
         }
+        if let Some(policy) = options.sandbox.clone() {
+            runtime.context.elevated_sandbox_policy = Some(policy);
+        }
         let effective_model = runtime.model.clone();
         let nickname = options
             .nickname
@@ -1230,9 +1316,12 @@ impl SubAgentManager {
             agent.session_name = name.to_string();
         }
         agent.fork_context = options.fork_context;
+        agent.cancel_token = Some(runtime.cancel_token.clone());
+        agent.max_steps = options.max_steps.unwrap_or(self.max_steps);
+        agent.sandbox = options.sandbox;
         let agent_id = agent.id.clone();
         let started_at = agent.started_at;
-        let max_steps = self.max_steps;
+        let max_steps = agent.max_steps;
 
         if let Some(event_tx) = runtime.event_tx.clone() {
             let _ = event_tx.try_send(Event::AgentSpawned {
@@ -1303,6 +1392,11 @@ impl SubAgentManager {
     }
 
     /// Cancel a running sub-agent.
+    ///
+    /// Cancellation is cooperative first: the agent's token is cancelled so
+    /// its loop stops at the next model request or tool call and reports
+    /// whatever it had so far as a partial, `cancelled` result. The task is
+    /// aborted outright if it has not finished after [`CANCEL_GRACE`].
     pub fn cancel(&mut self, agent_id: &str) -> Result<SubAgentResult> {
         let (snapshot, changed) = {
             let agent = self
@@ -1314,8 +1408,11 @@ impl SubAgentManager {
             if agent.status == SubAgentStatus::Running {
                 agent.status = SubAgentStatus::Cancelled;
                 release_resident_leases_for(&agent.id);
+                if let Some(token) = agent.cancel_token.take() {
+                    token.cancel();
+                }
                 if let Some(handle) = agent.task_handle.take() {
-                    handle.abort();
+                    abort_after_grace(handle);
                 }
                 changed = true;
             }
@@ -1368,9 +1465,13 @@ impl SubAgentManager {
             let (input_tx, input_rx) = mpsc::unbounded_channel();
             let restarted_at = Instant::now();
             let mut restart_runtime = runtime.clone();
+            agent.cancel_token = Some(restart_runtime.cancel_token.clone());
             if !agent.model.trim().is_empty() && agent.model != "unknown" {
                 restart_runtime.model.clone_from(&agent.model);
             }
+            if let Some(policy) = agent.sandbox.clone() {
+                restart_runtime.context.elevated_sandbox_policy = Some(policy);
+            }
             let task = SubAgentTask {
                 manager_handle,
                 runtime: restart_runtime,
@@ -1381,7 +1482,7 @@ impl SubAgentManager {
                 allowed_tools: agent.allowed_tools.clone(),
                 fork_context: false,
                 started_at: restarted_at,
-                max_steps: self.max_steps,
+                max_steps: agent.max_steps,
                 input_rx,
             };
             let handle = spawn_supervised(
@@ -1609,6 +1710,7 @@ impl SubAgentManager {
             agent.result = result.result;
             agent.steps_taken = result.steps_taken;
             agent.task_handle = None;
+            agent.cancel_token = None;
             changed = true;
         }
         if changed {
@@ -1622,6 +1724,7 @@ impl SubAgentManager {
             agent.status = SubAgentStatus::Failed(error);
             release_resident_leases_for(agent_id);
             agent.task_handle = None;
+            agent.cancel_token = None;
             changed = true;
         }
         if changed {
@@ -1630,6 +1733,23 @@ impl SubAgentManager {
     }
 }
 
+/// Abort `handle` unless it finishes within [`CANCEL_GRACE`]. Outside a
+/// Tokio runtime there is nothing to wait on, so abort immediately.
+fn abort_after_grace(handle: JoinHandle<()>) {
+    if handle.is_finished() {
+        return;
+    }
+    match tokio::runtime::Handle::try_current() {
+        Ok(rt) => {
+            rt.spawn(async move {
+                tokio::time::sleep(CANCEL_GRACE).await;
+                handle.abort();
+            });
+        }
+        Err(_) => handle.abort(),
+    }
+}
+
 /// Thread-safe wrapper for `SubAgentManager`.
 pub type SharedSubAgentManager = Arc<RwLock<SubAgentManager>>;
 
@@ -1874,6 +1994,16 @@ impl ToolSpec for AgentOpenTool {
                     "minimum": 0,
                     "maximum": 3,
                     "description": "Recursive child-agent budget for this session. 0 blocks agent_open from the child; 1-3 allow that many descendant levels."
+                },
+                "max_steps": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Optional step budget for this child. Clamped to the user's [subagents] max_steps (default 100); use a small budget for quick exploration."
+                },
+                "sandbox": {
+                    "type": "string",
+                    "enum": ["inherit", "read-only", "workspace-write"],
+                    "description": "Optional shell sandbox for this child. Can only tighten the parent's sandbox. read-only also blocks file-writing tools."
                 }
             }
         })
@@ -2122,7 +2252,7 @@ impl ToolSpec for AgentSpawnTool {
                 },
                 "model": {
                     "type": "string",
-                    "description": "Optional DeepSeek model id for this child. Explicit model wins over role/type defaults; omit to inherit."
+                    "description": "Optional DeepSeek model id for this child, e.g. deepseek-v4-flash for cheap exploration. Explicit model wins over role/type defaults; omit to inherit. Must be in the user's [subagents] allowed_models when that list is set."
                 },
                 "cwd": {
                     "type": "string",
@@ -2135,6 +2265,16 @@ impl ToolSpec for AgentSpawnTool {
                 "fork_context": {
                     "type": "boolean",
                     "description": "When true, inherit the parent's system prompt and conversation prefix before appending this task. This preserves DeepSeek prefix-cache reuse and gives the child full parent context. Defaults to false for independent exploration."
+                },
+                "max_steps": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Optional step budget for this child. Clamped to the user's [subagents] max_steps (default 100); use a small budget for quick exploration."
+                },
+                "sandbox": {
+                    "type": "string",
+                    "enum": ["inherit", "read-only", "workspace-write"],
+                    "description": "Optional shell sandbox for this child. Can only tighten the parent's sandbox. read-only also blocks file-writing tools."
                 }
             }
         })
@@ -2199,7 +2339,34 @@ impl ToolSpec for AgentSpawnTool {
         // its own cancellation token, inherits the parent approval state, and
         // optionally overrides cwd if the caller passed one (used for the
         // parallel-worktree pattern).
+        if let (Some(model), Some(allowed)) = (
+            spawn_request.model.as_deref(),
+            self.runtime.allowed_models.as_deref(),
+        ) && !allowed.iter().any(|entry| entry == model)
+        {
+            return Err(ToolError::invalid_input(format!(
+                "Model '{model}' is not allowed for sub-agents (allowed: {}). Omit model to use the configured default.",
+                if allowed.is_empty() {
+                    "none".to_string()
+                } else {
+                    allowed.join(", ")
+                }
+            )));
+        }
+
         let mut child_runtime = self.runtime.background_runtime();
+        let max_steps = spawn_request
+            .max_steps
+            .map_or(self.runtime.max_steps, |steps| {
+                steps.min(self.runtime.max_steps)
+            });
+        child_runtime.max_steps = max_steps;
+        let sandbox = spawn_request.sandbox.map(|requested| {
+            clamp_sandbox(
+                self.runtime.context.elevated_sandbox_policy.as_ref(),
+                requested,
+            )
+        });
         if let Some(max_depth) = spawn_request.max_depth {
             child_runtime.max_spawn_depth = child_runtime.spawn_depth.saturating_add(max_depth);
         }
@@ -2276,6 +2443,8 @@ impl ToolSpec for AgentSpawnTool {
                     model: Some(effective_model),
                     nickname: None,
                     fork_context: spawn_request.fork_context,
+                    max_steps: Some(max_steps),
+                    sandbox: sandbox.clone(),
                 },
             )
             .map_err(|e| ToolError::execution_failed(format!("Failed to spawn sub-agent: {e}")))?;
@@ -2304,6 +2473,15 @@ impl ToolSpec for AgentSpawnTool {
                 payload["resident_conflict"] = json!(warning);
             }
             ToolResult::json(&payload).map_err(|e| ToolError::execution_failed(e.to_string()))?
+        } else if spawn_request.max_steps.is_some() || spawn_request.sandbox.is_some() {
+            // Echo the effective limits so the parent sees any clamping.
+            let mut payload = serde_json::to_value(&result)
+                .map_err(|e| ToolError::execution_failed(e.to_string()))?;
+            payload["limits"] = json!({
+                "max_steps": max_steps,
+                "sandbox": sandbox.as_ref().map_or("inherit", sandbox_label),
+            });
+            ToolResult::json(&payload).map_err(|e| ToolError::execution_failed(e.to_string()))?
         } else {
             ToolResult::json(&result).map_err(|e| ToolError::execution_failed(e.to_string()))?
         };
@@ -3566,55 +3744,22 @@ async fn run_subagent(
 
     for _step in 0..max_steps {
         // Cooperative cancellation: bail if this session's token was cancelled
-        // while we were between steps. Top-level model-visible sub-agents use
-        // a detached token so parent turn cancellation does not stop them.
+        // while we were between steps (a per-agent kill, or Esc on the turn
+        // that spawned it).
         if runtime.cancel_token.is_cancelled() {
-            emit_agent_progress(
-                runtime.event_tx.as_ref(),
-                runtime.mailbox.as_ref(),
-                &agent_id,
-                format!("step {steps}/{max_steps}: cancelled"),
-            );
-            if let Some(mb) = runtime.mailbox.as_ref() {
-                let _ = mb.send(MailboxMessage::Cancelled {
-                    agent_id: agent_id.clone(),
-                });
-            }
-            let status = SubAgentStatus::Cancelled;
-            let duration_ms = u64::try_from(started_at.elapsed().as_millis()).unwrap_or(u64::MAX);
-            insert_subagent_full_transcript_handle(
+            return Ok(finish_cancelled(
                 runtime,
                 &agent_id,
                 &agent_type,
                 &assignment,
-                &status,
-                None,
                 &messages,
+                final_result.as_deref(),
                 steps,
-                duration_ms,
+                started_at,
                 fork_context_enabled,
+                format!("step {steps}/{max_steps}: cancelled"),
             )
-            .await;
-            return Ok(SubAgentResult {
-                name: agent_id.clone(),
-                agent_id: agent_id.clone(),
-                context_mode: if fork_context_enabled {
-                    "forked"
-                } else {
-                    "fresh"
-                }
-                .to_string(),
-                fork_context: fork_context_enabled,
-                agent_type: agent_type.clone(),
-                assignment: assignment.clone(),
-                model: runtime.model.clone(),
-                nickname: None,
-                status,
-                result: None,
-                steps_taken: steps,
-                duration_ms,
-                from_prior_session: false,
-            });
+            .await);
         }
 
         steps += 1;
@@ -3665,47 +3810,19 @@ async fn run_subagent(
         let response = tokio::select! {
             biased;
             () = runtime.cancel_token.cancelled() => {
-                emit_agent_progress(
-                    runtime.event_tx.as_ref(),
-                    runtime.mailbox.as_ref(),
-                    &agent_id,
-                    format!("step {steps}/{max_steps}: cancelled mid-request"),
-                );
-                if let Some(mb) = runtime.mailbox.as_ref() {
-                    let _ = mb.send(MailboxMessage::Cancelled {
-                        agent_id: agent_id.clone(),
-                    });
-                }
-                let status = SubAgentStatus::Cancelled;
-                let duration_ms = u64::try_from(started_at.elapsed().as_millis()).unwrap_or(u64::MAX);
-                insert_subagent_full_transcript_handle(
+                return Ok(finish_cancelled(
                     runtime,
                     &agent_id,
                     &agent_type,
                     &assignment,
-                    &status,
-                    None,
                     &messages,
+                    final_result.as_deref(),
                     steps,
-                    duration_ms,
+                    started_at,
                     fork_context_enabled,
+                    format!("step {steps}/{max_steps}: cancelled mid-request"),
                 )
-                .await;
-                return Ok(SubAgentResult {
-                    name: agent_id.clone(),
-                    agent_id: agent_id.clone(),
-                    context_mode: if fork_context_enabled { "forked" } else { "fresh" }.to_string(),
-                    fork_context: fork_context_enabled,
-                    agent_type: agent_type.clone(),
-                    assignment: assignment.clone(),
-                    model: runtime.model.clone(),
-                    nickname: None,
-                    status,
-                    result: None,
-                    steps_taken: steps,
-                    duration_ms,
-                    from_prior_session: false,
-                });
+                .await);
             }
             api = tokio::time::timeout(runtime.step_api_timeout, runtime.client.create_message(request)) => {
                 api.map_err(|_| anyhow!("API call timed out after {}s", runtime.step_api_timeout.as_secs()))??
@@ -3785,16 +3902,22 @@ async fn run_subagent(
                     step: steps,
                 });
             }
-            let result = match tokio::time::timeout(TOOL_TIMEOUT, async {
-                tool_registry
-                    .execute(&agent_id, &tool_name, tool_input)
-                    .await
-            })
-            .await
-            {
-                Ok(Ok(output)) => output,
-                Ok(Err(e)) => format!("Error: {e}"),
-                Err(_) => format!("Error: Tool {tool_name} timed out"),
+            let result = tokio::select! {
+                biased;
+                () = runtime.cancel_token.cancelled() => {
+                    // The next loop iteration sees the cancelled token and
+                    // returns the partial result.
+                    format!("Error: Tool {tool_name} cancelled")
+                }
+                outcome = tokio::time::timeout(TOOL_TIMEOUT, async {
+                    tool_registry
+                        .execute(&agent_id, &tool_name, tool_input)
+                        .await
+                }) => match outcome {
+                    Ok(Ok(output)) => output,
+                    Ok(Err(e)) => format!("Error: {e}"),
+                    Err(_) => format!("Error: Tool {tool_name} timed out"),
+                },
             };
             let tool_ok = !result.starts_with("Error:");
             emit_agent_progress(
@@ -3867,6 +3990,83 @@ async fn run_subagent(
     })
 }
 
+/// Build the `cancelled` result for a sub-agent whose token fired. The last
+/// non-empty assistant text is kept as a partial result, labelled so the
+/// parent does not mistake it for a finished answer; the full transcript
+/// (including tool results so far) is stored behind the usual handle.
+#[allow(clippy::too_many_arguments)]
+async fn finish_cancelled(
+    runtime: &SubAgentRuntime,
+    agent_id: &str,
+    agent_type: &SubAgentType,
+    assignment: &SubAgentAssignment,
+    messages: &[Message],
+    last_text: Option<&str>,
+    steps: u32,
+    started_at: Instant,
+    fork_context_enabled: bool,
+    progress: String,
+) -> SubAgentResult {
+    emit_agent_progress(
+        runtime.event_tx.as_ref(),
+        runtime.mailbox.as_ref(),
+        agent_id,
+        progress,
+    );
+    if let Some(mb) = runtime.mailbox.as_ref() {
+        let _ = mb.send(MailboxMessage::Cancelled {
+            agent_id: agent_id.to_string(),
+        });
+    }
+    release_resident_leases_for(agent_id);
+    let status = SubAgentStatus::Cancelled;
+    let result = cancelled_partial_result(last_text, steps);
+    let duration_ms = u64::try_from(started_at.elapsed().as_millis()).unwrap_or(u64::MAX);
+    insert_subagent_full_transcript_handle(
+        runtime,
+        agent_id,
+        agent_type,
+        assignment,
+        &status,
+        result.as_ref(),
+        messages,
+        steps,
+        duration_ms,
+        fork_context_enabled,
+    )
+    .await;
+    SubAgentResult {
+        name: agent_id.to_string(),
+        agent_id: agent_id.to_string(),
+        context_mode: if fork_context_enabled {
+            "forked"
+        } else {
+            "fresh"
+        }
+        .to_string(),
+        fork_context: fork_context_enabled,
+        agent_type: agent_type.clone(),
+        assignment: assignment.clone(),
+        model: runtime.model.clone(),
+        nickname: None,
+        status,
+        result,
+        steps_taken: steps,
+        duration_ms,
+        from_prior_session: false,
+    }
+}
+
+/// Label whatever the agent had said before it was cancelled. `None` when it
+/// had not produced any text yet.
+fn cancelled_partial_result(last_text: Option<&str>, steps: u32) -> Option<String> {
+    let text = last_text.map(str::trim).filter(|text| !text.is_empty())?;
+    let plural = if steps == 1 { "" } else { "s" };
+    Some(format!(
+        "[cancelled after {steps} step{plural}; partial result]\n{text}"
+    ))
+}
+
 async fn wait_for_result(
     manager: &SharedSubAgentManager,
     agent_id: &str,
@@ -4200,6 +4400,27 @@ fn parse_spawn_request(input: &Value) -> Result<SpawnRequest, ToolError> {
                 })
         })
         .transpose()?;
+    let max_steps = input
+        .get("max_steps")
+        .or_else(|| input.get("maxSteps"))
+        .map(|value| {
+            value
+                .as_u64()
+                .filter(|steps| *steps >= 1)
+                .map(|steps| u32::try_from(steps).unwrap_or(u32::MAX))
+                .ok_or_else(|| ToolError::invalid_input("max_steps must be a positive integer"))
+        })
+        .transpose()?;
+    let sandbox = match optional_input_str(input, &["sandbox"]) {
+        None | Some("inherit") => None,
+        Some("read-only" | "read_only" | "readonly") => Some(SandboxRequest::ReadOnly),
+        Some("workspace-write" | "workspace_write") => Some(SandboxRequest::WorkspaceWrite),
+        Some(other) => {
+            return Err(ToolError::invalid_input(format!(
+                "Invalid sandbox '{other}'. Use: inherit, read-only, workspace-write"
+            )));
+        }
+    };
 
     Ok(SpawnRequest {
         session_name,
@@ -4212,6 +4433,8 @@ fn parse_spawn_request(input: &Value) -> Result<SpawnRequest, ToolError> {
         resident_file,
         fork_context,
         max_depth,
+        max_steps,
+        sandbox,
     })
 }
 
@@ -4595,6 +4818,9 @@ struct SubAgentToolRegistry {
     /// decide whether `Suggest`-level tools (write/edit/patch) may run inside
     /// the child without the parent runtime being auto-approved (#1828, #1833).
     agent_type: SubAgentType,
+    /// Spawned with `sandbox: read-only`: file-writing tools are hidden and
+    /// rejected, matching the read-only shell sandbox.
+    read_only: bool,
     registry: ToolRegistry,
 }
 
@@ -4627,6 +4853,10 @@ impl SubAgentToolRegistry {
             allowed_tools: explicit_allowed_tools,
             auto_approve: runtime.context.auto_approve,
             agent_type,
+            read_only: matches!(
+                runtime.context.elevated_sandbox_policy,
+                Some(SandboxPolicy::ReadOnly)
+            ),
             registry,
         }
     }
@@ -4651,6 +4881,12 @@ impl SubAgentToolRegistry {
         }
     }
 
+    fn writes_files(&self, name: &str) -> bool {
+        self.registry
+            .get(name)
+            .is_some_and(|spec| spec.capabilities().contains(&ToolCapability::WritesFiles))
+    }
+
     fn tools_for_model(&self, agent_type: &SubAgentType) -> Vec<Tool> {
         let disallowed = match agent_type {
             // Review and tool-executor agents should not spawn or manage
@@ -4669,7 +4905,10 @@ impl SubAgentToolRegistry {
             ][..],
             _ => &[][..],
         };
-        let api_tools = self.registry.to_api_tools();
+        let mut api_tools = self.registry.to_api_tools();
+        if self.read_only {
+            api_tools.retain(|tool| !self.writes_files(&tool.name));
+        }
         let filtered = match &self.allowed_tools {
             None => api_tools,
             Some(list) => api_tools
@@ -4702,6 +4941,11 @@ impl SubAgentToolRegistry {
         if !self.is_tool_allowed(name) {
             return Err(anyhow!("Tool {name} not allowed for this sub-agent"));
         }
+        if self.read_only && self.writes_files(name) {
+            return Err(anyhow!(
+                "Tool {name} writes files and this sub-agent was spawned with sandbox=read-only"
+            ));
+        }
         if !self.auto_approve {
             let Some(spec) = self.registry.get(name) else {
                 return Err(anyhow!("Tool {name} is not registered"));
@@ -4807,7 +5051,8 @@ fn summarize_subagent_result(result: &SubAgentResult) -> String {
         (SubAgentStatus::Completed, Some(text)) => truncate_preview(text),
         (SubAgentStatus::Completed, None) => "Completed (no output)".to_string(),
         (SubAgentStatus::Interrupted(error), _) => format!("Interrupted: {error}"),
-        (SubAgentStatus::Cancelled, _) => "Cancelled".to_string(),
+        (SubAgentStatus::Cancelled, Some(partial)) => truncate_preview(partial),
+        (SubAgentStatus::Cancelled, None) => "Cancelled".to_string(),
         (SubAgentStatus::Failed(error), _) => format!("Failed: {error}"),
         (SubAgentStatus::Running, _) => "Running".to_string(),
     }
@@ -777,6 +777,91 @@ fn test_subagent_tool_registry_reports_unavailable_tools() {
     );
 }
 
+#[test]
+fn spawn_overrides_parse_and_sandbox_only_tightens() {
+    let request = parse_spawn_request(&json!({
+        "prompt": "scan",
+        "max_steps": 12,
+        "sandbox": "read-only"
+    }))
+    .expect("valid overrides");
+    assert_eq!(request.max_steps, Some(12));
+    assert_eq!(request.sandbox, Some(SandboxRequest::ReadOnly));
+    assert!(parse_spawn_request(&json!({"prompt": "x", "max_steps": 0})).is_err());
+    assert!(parse_spawn_request(&json!({"prompt": "x", "sandbox": "none"})).is_err());
+
+    let full = SandboxPolicy::DangerFullAccess;
+    assert_eq!(
+        clamp_sandbox(Some(&full), SandboxRequest::WorkspaceWrite),
+        SandboxPolicy::workspace_with_roots(Vec::new(), true)
+    );
+    assert_eq!(
+        clamp_sandbox(
+            Some(&SandboxPolicy::ReadOnly),
+            SandboxRequest::WorkspaceWrite
+        ),
+        SandboxPolicy::ReadOnly
+    );
+    let parent = SandboxPolicy::workspace_with_roots(vec![PathBuf::from("/cache")], false);
+    assert_eq!(
+        clamp_sandbox(Some(&parent), SandboxRequest::WorkspaceWrite),
+        parent
+    );
+    assert_eq!(
+        clamp_sandbox(None, SandboxRequest::ReadOnly),
+        SandboxPolicy::ReadOnly
+    );
+}
+
+#[test]
+fn read_only_sandbox_hides_and_rejects_file_writes() {
+    let tmp = tempdir().expect("tempdir");
+    let mut runtime = stub_runtime();
+    runtime.context = ToolContext::new(tmp.path().to_path_buf())
+        .with_elevated_sandbox_policy(SandboxPolicy::ReadOnly);
+    let registry = SubAgentToolRegistry::new(
+        runtime,
+        SubAgentType::Implementer,
+        None,
+        Arc::new(Mutex::new(TodoList::new())),
+        Arc::new(Mutex::new(PlanState::default())),
+    );
+    let tools = registry.tools_for_model(&SubAgentType::Implementer);
+    let names: Vec<_> = tools.iter().map(|t| t.name.as_str()).collect();
+    assert!(names.contains(&"read_file"), "tools: {names:?}");
+    assert!(!names.contains(&"write_file"), "tools: {names:?}");
+    assert!(!names.contains(&"edit_file"), "tools: {names:?}");
+
+    let err = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(registry.execute(
+            "agent_test",
+            "write_file",
+            json!({"path": "x.txt", "content": "x"}),
+        ))
+        .expect_err("read-only child must not write");
+    assert!(err.to_string().contains("sandbox=read-only"), "{err}");
+    assert!(!tmp.path().join("x.txt").exists());
+}
+
+#[tokio::test]
+async fn spawn_rejects_models_outside_the_configured_allowlist() {
+    let runtime = stub_runtime().with_allowed_models(Some(vec!["deepseek-v4-flash".to_string()]));
+    let manager = runtime.manager.clone();
+    let tool = AgentSpawnTool::new(manager, runtime.clone());
+    let err = tool
+        .execute(
+            json!({"prompt": "explore", "model": "deepseek-v4-pro"}),
+            &runtime.context,
+        )
+        .await
+        .expect_err("pro is not allowed");
+    assert!(
+        err.to_string().contains("not allowed for sub-agents"),
+        "{err}"
+    );
+}
+
 #[test]
 fn test_review_agent_tools_exclude_agent_spawn() {
     let tmp = tempdir().expect("tempdir");
@@ -856,6 +941,63 @@ async fn test_running_count_counts_only_agents_with_live_task_handles() {
         .abort();
 }
 
+#[tokio::test]
+async fn cancel_fires_agent_token_so_the_task_can_report_partial_output() {
+    let mut manager = SubAgentManager::new(PathBuf::from("."), 1);
+    let (input_tx, _input_rx) = mpsc::unbounded_channel();
+    let mut agent = SubAgent::new(
+        SubAgentType::Explore,
+        "prompt".to_string(),
+        make_assignment(),
+        "deepseek-v4-flash".to_string(),
+        None,
+        None,
+        input_tx,
+        "boot_test".to_string(),
+    );
+    let token = CancellationToken::new();
+    let observed = token.clone();
+    let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+    agent.cancel_token = Some(token);
+    agent.task_handle = Some(tokio::spawn(async move {
+        observed.cancelled().await;
+        let _ = done_tx.send(());
+    }));
+    let agent_id = agent.id.clone();
+    manager.agents.insert(agent.id.clone(), agent);
+
+    let snapshot = manager.cancel(&agent_id).expect("cancel");
+    assert_eq!(snapshot.status, SubAgentStatus::Cancelled);
+    // The task was not aborted: it saw the token and ran to completion.
+    tokio::time::timeout(Duration::from_secs(1), done_rx)
+        .await
+        .expect("task observed cancellation before the grace abort")
+        .expect("task completed");
+}
+
+#[test]
+fn background_runtime_is_cancelled_with_the_spawning_turn() {
+    let parent = stub_runtime();
+    let background = parent.background_runtime();
+    let tool_token = background
+        .context
+        .cancel_token
+        .clone()
+        .expect("tool context shares the agent token");
+    parent.cancel_token.cancel();
+    assert!(background.cancel_token.is_cancelled());
+    assert!(tool_token.is_cancelled());
+}
+
+#[test]
+fn cancelled_partial_result_is_labelled() {
+    assert_eq!(cancelled_partial_result(None, 3), None);
+    assert_eq!(cancelled_partial_result(Some("   "), 3), None);
+    let partial = cancelled_partial_result(Some("Found two callers so far."), 1).unwrap();
+    assert!(partial.starts_with("[cancelled after 1 step; partial result]"));
+    assert!(partial.ends_with("Found two callers so far."));
+}
+
 #[test]
 fn test_running_count_ignores_running_status_without_task_handle() {
     let mut manager = SubAgentManager::new(PathBuf::from("."), 1);
@@ -1671,6 +1813,8 @@ fn stub_runtime() -> SubAgentRuntime {
         parent_completion_tx: None,
         fork_context: None,
         step_api_timeout: DEFAULT_STEP_API_TIMEOUT,
+        max_steps: DEFAULT_MAX_STEPS,
+        allowed_models: None,
     }
 }
 
@@ -2016,7 +2160,7 @@ fn child_runtime_preserves_step_api_timeout() {
     assert_eq!(
         background.step_api_timeout.as_secs(),
         900,
-        "background_runtime (detached) must also preserve the parent's timeout"
+        "background_runtime must also preserve the parent's timeout"
     );
 }
 
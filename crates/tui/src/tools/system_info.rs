@@ -0,0 +1,575 @@
+//! Host environment tool: `system_info`.
+//!
+//! Reports the OS version, CPU, memory, free disk space, GPUs, installed
+//! toolchains, and whether the process runs inside a container or on a CI
+//! runner. Every probe is best-effort: a missing command or unreadable file
+//! leaves its field empty instead of failing the call. `deepseek doctor`
+//! prints its platform section from the same [`SystemInfo::collect`].
+
+use std::collections::BTreeMap;
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::spec::{
+    ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolResult, ToolSpec,
+};
+
+/// A program and the arguments that print its version.
+type VersionProbe = (&'static str, &'static [&'static str]);
+
+/// Toolchains probed by name. The first probe of each group that runs wins
+/// (`python3` before `python`).
+const TOOLCHAINS: &[(&str, &[VersionProbe])] = &[
+    ("rustc", &[("rustc", &["--version"])]),
+    ("cargo", &[("cargo", &["--version"])]),
+    ("node", &[("node", &["--version"])]),
+    (
+        "python",
+        &[("python3", &["--version"]), ("python", &["--version"])],
+    ),
+    ("go", &[("go", &["version"])]),
+];
+
+/// CI providers detected from the environment variable their runners set.
+const CI_PROVIDERS: &[(&str, &str)] = &[
+    ("GITHUB_ACTIONS", "github-actions"),
+    ("GITLAB_CI", "gitlab-ci"),
+    ("BUILDKITE", "buildkite"),
+    ("CIRCLECI", "circleci"),
+    ("JENKINS_URL", "jenkins"),
+    ("TF_BUILD", "azure-pipelines"),
+    ("BITBUCKET_BUILD_NUMBER", "bitbucket-pipelines"),
+    ("TRAVIS", "travis-ci"),
+];
+
+/// Tool for describing the host the agent runs on.
+pub struct SystemInfoTool;
+
+/// Best-effort description of the host.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SystemInfo {
+    /// `std::env::consts::OS` (`linux`, `macos`, `windows`, ...).
+    pub os: String,
+    pub arch: String,
+    /// Distribution or product version, e.g. `Ubuntu 24.04.1 LTS`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kernel: Option<String>,
+    pub cpu: CpuInfo,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemoryInfo>,
+    /// Space on the filesystem holding the workspace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk: Option<DiskInfo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gpus: Vec<String>,
+    /// Installed toolchains by name; absent ones are omitted.
+    #[serde(default)]
+    pub toolchains: BTreeMap<String, String>,
+    /// `docker`, `podman`, `kubernetes`, `wsl`, or another runtime name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+    /// CI provider, or `unknown` when only `CI` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ci: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CpuInfo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logical_cores: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryInfo {
+    pub total_bytes: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiskInfo {
+    pub path: String,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+impl SystemInfo {
+    /// Probe the host; `workspace` picks the filesystem for the disk figures
+    /// and the directory toolchain commands run in (so `rust-toolchain.toml`
+    /// and similar pins apply).
+    #[must_use]
+    pub fn collect(workspace: &Path) -> Self {
+        let (os_version, kernel) = probe_os_version(workspace);
+        Self {
+            os: env::consts::OS.to_string(),
+            arch: env::consts::ARCH.to_string(),
+            os_version,
+            kernel,
+            cpu: CpuInfo {
+                model: probe_cpu_model(workspace),
+                logical_cores: std::thread::available_parallelism()
+                    .ok()
+                    .map(std::num::NonZero::get),
+            },
+            memory: probe_memory(workspace),
+            disk: probe_disk(workspace),
+            gpus: probe_gpus(workspace),
+            toolchains: probe_toolchains(workspace),
+            container: detect_container(),
+            ci: detect_ci(|key| env::var_os(key).is_some_and(|v| !v.is_empty())),
+        }
+    }
+
+    /// One-line summaries for human-readable output such as `deepseek doctor`.
+    #[must_use]
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut os = format!(
+            "OS: {} ({})",
+            self.os_version.as_deref().unwrap_or(&self.os),
+            self.arch
+        );
+        if let Some(kernel) = &self.kernel {
+            os.push_str(&format!(", kernel {kernel}"));
+        }
+        lines.push(os);
+        let cores = self.cpu.logical_cores.map(|n| format!("{n} logical cores"));
+        match (&self.cpu.model, cores) {
+            (Some(model), Some(cores)) => lines.push(format!("CPU: {model}, {cores}")),
+            (Some(model), None) => lines.push(format!("CPU: {model}")),
+            (None, Some(cores)) => lines.push(format!("CPU: {cores}")),
+            (None, None) => {}
+        }
+        if let Some(memory) = &self.memory {
+            lines.push(match memory.available_bytes {
+                Some(available) => format!(
+                    "Memory: {} available of {}",
+                    format_gib(available),
+                    format_gib(memory.total_bytes)
+                ),
+                None => format!("Memory: {}", format_gib(memory.total_bytes)),
+            });
+        }
+        if let Some(disk) = &self.disk {
+            lines.push(format!(
+                "Disk: {} free of {} ({})",
+                format_gib(disk.available_bytes),
+                format_gib(disk.total_bytes),
+                disk.path
+            ));
+        }
+        if !self.gpus.is_empty() {
+            lines.push(format!("GPU: {}", self.gpus.join("; ")));
+        }
+        if !self.toolchains.is_empty() {
+            let toolchains = self
+                .toolchains
+                .iter()
+                .map(|(name, version)| format!("{name} {version}"))
+                .collect::<Vec<_>>();
+            lines.push(format!("Toolchains: {}", toolchains.join(", ")));
+        }
+        if let Some(container) = &self.container {
+            lines.push(format!("Container: {container}"));
+        }
+        if let Some(ci) = &self.ci {
+            lines.push(format!("CI: {ci}"));
+        }
+        lines
+    }
+}
+
+#[async_trait]
+impl ToolSpec for SystemInfoTool {
+    fn name(&self) -> &'static str {
+        "system_info"
+    }
+
+    fn description(&self) -> &'static str {
+        "Report the host environment: OS version, CPU, memory, free disk space, GPUs, \
+         installed toolchains (rustc, cargo, node, python, go), and container/CI detection. \
+         Call this instead of guessing when a task depends on the machine."
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {},
+            "required": [],
+            "additionalProperties": false
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::ReadOnly]
+    }
+
+    fn approval_requirement(&self) -> ApprovalRequirement {
+        ApprovalRequirement::Auto
+    }
+
+    fn supports_parallel(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, _input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let workspace = context.workspace.clone();
+        let info = tokio::task::spawn_blocking(move || SystemInfo::collect(&workspace))
+            .await
+            .map_err(|e| ToolError::execution_failed(e.to_string()))?;
+        ToolResult::json(&info).map_err(|e| ToolError::execution_failed(e.to_string()))
+    }
+}
+
+// === Helpers ===
+
+fn format_gib(bytes: u64) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let gib = bytes as f64 / (1024.0 * 1024.0 * 1024.0);
+    format!("{gib:.1} GiB")
+}
+
+fn run(program: &str, args: &[&str], cwd: &Path) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // `python --version` printed to stderr before 3.4.
+    let text = if output.stdout.is_empty() {
+        String::from_utf8_lossy(&output.stderr)
+    } else {
+        String::from_utf8_lossy(&output.stdout)
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn probe_toolchains(cwd: &Path) -> BTreeMap<String, String> {
+    TOOLCHAINS
+        .iter()
+        .filter_map(|(name, candidates)| {
+            candidates
+                .iter()
+                .find_map(|(program, args)| run(program, args, cwd))
+                .map(|version| ((*name).to_string(), toolchain_version(&version)))
+        })
+        .collect()
+}
+
+/// First line of a `--version` banner with the program name dropped:
+/// `rustc 1.88.0 (6b00bc388 2025-06-23)` → `1.88.0 (6b00bc388 2025-06-23)`,
+/// `go version go1.22.1 linux/amd64` → `go1.22.1 linux/amd64`.
+fn toolchain_version(banner: &str) -> String {
+    let line = banner.lines().next().unwrap_or_default().trim();
+    let mut words = line.split_whitespace().peekable();
+    while let Some(word) = words.peek() {
+        if word.chars().next().is_some_and(|c| c.is_ascii_digit())
+            || word.starts_with('v') && word[1..].starts_with(|c: char| c.is_ascii_digit())
+            || word.starts_with("go") && word[2..].starts_with(|c: char| c.is_ascii_digit())
+        {
+            break;
+        }
+        words.next();
+    }
+    let version = words.collect::<Vec<_>>().join(" ");
+    if version.is_empty() {
+        line.to_string()
+    } else {
+        version
+    }
+}
+
+fn probe_os_version(cwd: &Path) -> (Option<String>, Option<String>) {
+    match env::consts::OS {
+        "linux" => {
+            let pretty = std::fs::read_to_string("/etc/os-release")
+                .ok()
+                .and_then(|raw| os_release_pretty_name(&raw));
+            (pretty, run("uname", &["-r"], cwd))
+        }
+        "macos" => {
+            let version = run("sw_vers", &["-productVersion"], cwd).map(|v| format!("macOS {v}"));
+            (version, run("uname", &["-r"], cwd))
+        }
+        "windows" => (run("cmd", &["/C", "ver"], cwd), None),
+        _ => (None, run("uname", &["-r"], cwd)),
+    }
+}
+
+fn os_release_pretty_name(raw: &str) -> Option<String> {
+    let field = |key: &str| {
+        raw.lines().find_map(|line| {
+            let value = line.strip_prefix(key)?.strip_prefix('=')?;
+            let value = value.trim().trim_matches('"').trim();
+            (!value.is_empty()).then(|| value.to_string())
+        })
+    };
+    field("PRETTY_NAME").or_else(|| field("NAME"))
+}
+
+fn probe_cpu_model(cwd: &Path) -> Option<String> {
+    match env::consts::OS {
+        "linux" => std::fs::read_to_string("/proc/cpuinfo")
+            .ok()
+            .and_then(|raw| {
+                raw.lines().find_map(|line| {
+                    let (key, value) = line.split_once(':')?;
+                    matches!(key.trim(), "model name" | "Model" | "Hardware")
+                        .then(|| value.trim().to_string())
+                })
+            }),
+        "macos" => run("sysctl", &["-n", "machdep.cpu.brand_string"], cwd),
+        _ => env::var("PROCESSOR_IDENTIFIER").ok(),
+    }
+}
+
+fn probe_memory(cwd: &Path) -> Option<MemoryInfo> {
+    match env::consts::OS {
+        "linux" => std::fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|raw| parse_meminfo(&raw)),
+        "macos" => run("sysctl", &["-n", "hw.memsize"], cwd)
+            .and_then(|raw| raw.parse().ok())
+            .map(|total_bytes| MemoryInfo {
+                total_bytes,
+                available_bytes: None,
+            }),
+        _ => None,
+    }
+}
+
+fn parse_meminfo(raw: &str) -> Option<MemoryInfo> {
+    let kib = |key: &str| {
+        raw.lines().find_map(|line| {
+            let rest = line.strip_prefix(key)?.strip_prefix(':')?;
+            let value = rest.split_whitespace().next()?.parse::<u64>().ok()?;
+            Some(value.saturating_mul(1024))
+        })
+    };
+    Some(MemoryInfo {
+        total_bytes: kib("MemTotal")?,
+        available_bytes: kib("MemAvailable"),
+    })
+}
+
+#[cfg(unix)]
+fn probe_disk(workspace: &Path) -> Option<DiskInfo> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(workspace.as_os_str().as_bytes()).ok()?;
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `path` is a valid NUL-terminated string and `stat` points to
+    // writable memory of the right size; it is only read after success.
+    let stat = unsafe {
+        if libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stat.assume_init()
+    };
+    // Field widths differ between platforms (`u32` block counts on macOS).
+    #[allow(clippy::unnecessary_cast)]
+    let (fragment, blocks, available) = (
+        stat.f_frsize as u64,
+        stat.f_blocks as u64,
+        stat.f_bavail as u64,
+    );
+    Some(DiskInfo {
+        path: workspace.display().to_string(),
+        total_bytes: blocks.saturating_mul(fragment),
+        available_bytes: available.saturating_mul(fragment),
+    })
+}
+
+#[cfg(not(unix))]
+fn probe_disk(_workspace: &Path) -> Option<DiskInfo> {
+    None
+}
+
+fn probe_gpus(cwd: &Path) -> Vec<String> {
+    let nvidia = run(
+        "nvidia-smi",
+        &[
+            "--query-gpu=name,memory.total,driver_version",
+            "--format=csv,noheader",
+        ],
+        cwd,
+    );
+    if let Some(raw) = nvidia {
+        return raw
+            .lines()
+            .map(|line| {
+                let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+                match parts.as_slice() {
+                    [name, memory, driver] => format!("{name} ({memory}, driver {driver})"),
+                    _ => line.trim().to_string(),
+                }
+            })
+            .filter(|line| !line.is_empty())
+            .collect();
+    }
+    if env::consts::OS == "macos" {
+        return run("system_profiler", &["SPDisplaysDataType"], cwd)
+            .map(|raw| {
+                raw.lines()
+                    .filter_map(|line| line.trim().strip_prefix("Chipset Model:"))
+                    .map(|name| name.trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+    Vec::new()
+}
+
+fn detect_container() -> Option<String> {
+    if env::var_os("KUBERNETES_SERVICE_HOST").is_some() {
+        return Some("kubernetes".to_string());
+    }
+    if Path::new("/.dockerenv").exists() {
+        return Some("docker".to_string());
+    }
+    if Path::new("/run/.containerenv").exists() {
+        return Some("podman".to_string());
+    }
+    if let Ok(runtime) = env::var("container")
+        && !runtime.trim().is_empty()
+    {
+        return Some(runtime.trim().to_string());
+    }
+    if let Ok(cgroup) = std::fs::read_to_string("/proc/1/cgroup")
+        && let Some(runtime) = container_from_cgroup(&cgroup)
+    {
+        return Some(runtime.to_string());
+    }
+    if env::var_os("WSL_DISTRO_NAME").is_some() {
+        return Some("wsl".to_string());
+    }
+    None
+}
+
+fn container_from_cgroup(cgroup: &str) -> Option<&'static str> {
+    if cgroup.contains("kubepods") {
+        Some("kubernetes")
+    } else if cgroup.contains("docker") {
+        Some("docker")
+    } else if cgroup.contains("containerd") {
+        Some("containerd")
+    } else if cgroup.contains("libpod") {
+        Some("podman")
+    } else if cgroup.contains("lxc") {
+        Some("lxc")
+    } else {
+        None
+    }
+}
+
+fn detect_ci(is_set: impl Fn(&str) -> bool) -> Option<String> {
+    CI_PROVIDERS
+        .iter()
+        .find(|(key, _)| is_set(key))
+        .map(|(_, name)| (*name).to_string())
+        .or_else(|| is_set("CI").then(|| "unknown".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn toolchain_banners_reduce_to_versions() {
+        assert_eq!(
+            toolchain_version("rustc 1.88.0 (6b00bc388 2025-06-23)"),
+            "1.88.0 (6b00bc388 2025-06-23)"
+        );
+        assert_eq!(toolchain_version("v22.3.0\n"), "v22.3.0");
+        assert_eq!(toolchain_version("Python 3.12.4"), "3.12.4");
+        assert_eq!(
+            toolchain_version("go version go1.22.1 linux/amd64"),
+            "go1.22.1 linux/amd64"
+        );
+        assert_eq!(toolchain_version("weird banner"), "weird banner");
+    }
+
+    #[test]
+    fn parses_linux_probe_files() {
+        let os_release = "NAME=\"Ubuntu\"\nPRETTY_NAME=\"Ubuntu 24.04.1 LTS\"\nID=ubuntu\n";
+        assert_eq!(
+            os_release_pretty_name(os_release).as_deref(),
+            Some("Ubuntu 24.04.1 LTS")
+        );
+        assert_eq!(
+            os_release_pretty_name("NAME=Alpine Linux\n").as_deref(),
+            Some("Alpine Linux")
+        );
+
+        let meminfo =
+            "MemTotal:       16303452 kB\nMemFree:  1000 kB\nMemAvailable:    8151726 kB\n";
+        assert_eq!(
+            parse_meminfo(meminfo),
+            Some(MemoryInfo {
+                total_bytes: 16_303_452 * 1024,
+                available_bytes: Some(8_151_726 * 1024),
+            })
+        );
+        assert_eq!(parse_meminfo("MemFree: 1 kB\n"), None);
+
+        assert_eq!(
+            container_from_cgroup("0::/kubepods/besteffort/pod1/abc"),
+            Some("kubernetes")
+        );
+        assert_eq!(
+            container_from_cgroup("0::/system.slice/docker-abc.scope"),
+            Some("docker")
+        );
+        assert_eq!(container_from_cgroup("0::/init.scope"), None);
+    }
+
+    #[test]
+    fn ci_provider_wins_over_generic_flag() {
+        assert_eq!(
+            detect_ci(|key| matches!(key, "CI" | "GITHUB_ACTIONS")).as_deref(),
+            Some("github-actions")
+        );
+        assert_eq!(detect_ci(|key| key == "CI").as_deref(), Some("unknown"));
+        assert_eq!(detect_ci(|_| false), None);
+    }
+
+    #[tokio::test]
+    async fn system_info_reports_platform_best_effort() {
+        let tmp = tempdir().expect("tempdir");
+        let ctx = ToolContext::new(tmp.path());
+        let result = SystemInfoTool
+            .execute(json!({}), &ctx)
+            .await
+            .expect("execute");
+        assert!(result.success);
+
+        let parsed: SystemInfo =
+            serde_json::from_str(&result.content).expect("tool result should be json");
+        assert_eq!(parsed.os, env::consts::OS);
+        assert_eq!(parsed.arch, env::consts::ARCH);
+        assert!(parsed.cpu.logical_cores.is_some_and(|n| n > 0));
+        #[cfg(unix)]
+        assert!(
+            parsed
+                .disk
+                .as_ref()
+                .is_some_and(|disk| disk.total_bytes > 0)
+        );
+        assert!(parsed.summary_lines()[0].starts_with("OS: "));
+    }
+}
@@ -3,6 +3,7 @@
 //! `cargo test` runs workspace code, so this tool follows the same explicit
 //! approval policy as the other code-executing tools.
 
+use std::collections::HashMap;
 use std::path::Path;
 use std::process::Command;
 
@@ -84,7 +85,7 @@ impl ToolSpec for RunTestsTool {
         }
 
         let command_str = format_command(&context.workspace, &args);
-        let output = run_cargo(&context.workspace, &args)?;
+        let output = run_cargo(&context.workspace, &args, &context.session_env_pairs())?;
 
         let exit_code = output.status.code().unwrap_or(-1);
         let stdout_raw = String::from_utf8_lossy(&output.stdout);
@@ -106,9 +107,13 @@ impl ToolSpec for RunTestsTool {
 
 // === Helpers ===
 
-fn run_cargo(workspace: &Path, args: &[String]) -> Result<std::process::Output, ToolError> {
+fn run_cargo(
+    workspace: &Path,
+    args: &[String],
+    env: &HashMap<String, String>,
+) -> Result<std::process::Output, ToolError> {
     let mut cmd = Command::new("cargo");
-    cmd.args(args).current_dir(workspace);
+    cmd.args(args).current_dir(workspace).envs(env);
     cmd.output().map_err(|e| {
         if e.kind() == std::io::ErrorKind::NotFound {
             ToolError::not_available("cargo is not installed or not in PATH")
@@ -153,7 +153,10 @@ impl ToolSpec for RetrieveToolResultTool {
 ///
 /// The error message on a miss enumerates which forms were tried so the
 /// model can correct course without a second blind guess.
-fn resolve_spillover_reference(reference: &str, session_id: &str) -> Result<PathBuf, ToolError> {
+pub(crate) fn resolve_spillover_reference(
+    reference: &str,
+    session_id: &str,
+) -> Result<PathBuf, ToolError> {
     let root = crate::tools::truncate::spillover_root()
         .ok_or_else(|| ToolError::execution_failed("could not resolve ~/.deepseek/tool_outputs"))?;
     let root_canonical = root.canonicalize().ok();
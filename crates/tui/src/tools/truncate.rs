@@ -25,6 +25,8 @@
 //!   path (`turn_loop.rs`) so any successful tool result over
 //!   [`SPILLOVER_THRESHOLD_BYTES`] spills to disk and the model
 //!   receives a [`SPILLOVER_HEAD_BYTES`] head plus a pointer footer.
+//! * [`super::output_filter::apply_tool_filter`] saves the raw output
+//!   here before a `[tool_filters]` entry trims what the model sees.
 //! * Boot prune in `main.rs` deletes files older than
 //!   [`SPILLOVER_MAX_AGE`].
 //!
@@ -376,7 +378,8 @@ fn apply_spillover_inner(
              Full output saved to {path_str}. Use \
              `retrieve_tool_result ref={tool_id} mode=tail` or \
              `retrieve_tool_result ref={tool_id} mode=query query=<text>` \
-             if you need the elided output.]",
+             if you need the elided output, or page through all of it with \
+             `read_result_page result_id={tool_id}`.]",
             head_kib = head.len() / 1024,
             total_kib = total / 1024,
         );
@@ -474,6 +477,14 @@ fn apply_spillover_inner(
             }
         }
     }
+    // `result_id` is what `read_result_page` takes: the artifact id when one
+    // was written, otherwise the tool call id of the legacy spillover file.
+    let result_id = artifact_path
+        .as_ref()
+        .map_or_else(|| tool_id.to_string(), |(_, _, record)| record.id.clone());
+    if let Some(obj) = result.metadata.as_mut().and_then(|m| m.as_object_mut()) {
+        obj.insert("result_id".into(), serde_json::Value::String(result_id));
+    }
     artifact_path
         .map(|(absolute_path, _, _)| absolute_path)
         .or(Some(path))
@@ -793,6 +804,11 @@ mod tests {
                 &result.content[result.content.len().saturating_sub(200)..]
             );
             assert!(result.content.contains("retrieve_tool_result ref=call-big"));
+            assert!(
+                result
+                    .content
+                    .contains("read_result_page result_id=call-big")
+            );
 
             // Full bytes are on disk at the returned path.
             assert!(path.exists(), "spillover file missing: {path:?}");
@@ -806,6 +822,12 @@ mod tests {
                 .and_then(serde_json::Value::as_str)
                 .expect("spillover_path key present");
             assert_eq!(stamped, path.display().to_string());
+            assert_eq!(
+                metadata
+                    .get("result_id")
+                    .and_then(serde_json::Value::as_str),
+                Some("call-big")
+            );
         });
     }
 
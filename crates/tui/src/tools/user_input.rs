@@ -4,8 +4,11 @@ use super::spec::{
     ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolResult, ToolSpec,
 };
 use async_trait::async_trait;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use serde_json::{Value, json};
+use serde_json::{Map, Value, json};
+use std::collections::HashSet;
+use std::path::Path;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserInputOption {
@@ -21,9 +24,210 @@ pub struct UserInputQuestion {
     pub options: Vec<UserInputOption>,
 }
 
+/// Widget type for a form field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserInputFieldKind {
+    Text,
+    Select,
+    MultiSelect,
+    Boolean,
+    Path,
+}
+
+impl UserInputFieldKind {
+    fn has_options(self) -> bool {
+        matches!(self, Self::Select | Self::MultiSelect)
+    }
+}
+
+/// One typed field of a form-style request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserInputField {
+    pub id: String,
+    pub label: String,
+    #[serde(rename = "type")]
+    pub kind: UserInputFieldKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    /// Choices for `select` and `multi_select` fields.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    /// Initial value: a string for text/path/select, an array for
+    /// multi_select, a bool for boolean.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<String>,
+    /// Regex a non-empty text value must match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// For `path` fields: the path must exist relative to the workspace.
+    #[serde(default)]
+    pub must_exist: bool,
+}
+
+impl UserInputField {
+    /// Check a submitted value against the field's rules. `workspace`
+    /// resolves relative paths for `must_exist`.
+    pub fn check_value(&self, value: &Value, workspace: Option<&Path>) -> Result<(), String> {
+        let empty = match value {
+            Value::Null => true,
+            Value::String(text) => text.trim().is_empty(),
+            Value::Array(items) => items.is_empty(),
+            _ => false,
+        };
+        if empty {
+            return if self.required {
+                Err(format!("{} is required", self.label))
+            } else {
+                Ok(())
+            };
+        }
+        match self.kind {
+            UserInputFieldKind::Text | UserInputFieldKind::Path => {
+                let text = value
+                    .as_str()
+                    .ok_or_else(|| format!("{} must be text", self.label))?
+                    .trim();
+                if let Some(pattern) = &self.pattern {
+                    let re = Regex::new(pattern)
+                        .map_err(|e| format!("{} has an invalid pattern: {e}", self.label))?;
+                    if !re.is_match(text) {
+                        return Err(format!("{} must match {pattern}", self.label));
+                    }
+                }
+                if self.kind == UserInputFieldKind::Path {
+                    if text.chars().any(char::is_control) {
+                        return Err(format!("{} contains control characters", self.label));
+                    }
+                    if self.must_exist {
+                        let path = Path::new(text);
+                        let resolved = match workspace {
+                            Some(root) if path.is_relative() => root.join(path),
+                            _ => path.to_path_buf(),
+                        };
+                        if !resolved.exists() {
+                            return Err(format!("{} does not exist: {text}", self.label));
+                        }
+                    }
+                }
+                Ok(())
+            }
+            UserInputFieldKind::Select => match value.as_str() {
+                Some(choice) if self.options.iter().any(|o| o == choice) => Ok(()),
+                _ => Err(format!("{} must be one of the listed options", self.label)),
+            },
+            UserInputFieldKind::MultiSelect => {
+                let valid = value.as_array().is_some_and(|items| {
+                    items.iter().all(|item| {
+                        item.as_str()
+                            .is_some_and(|choice| self.options.iter().any(|o| o == choice))
+                    })
+                });
+                if valid {
+                    Ok(())
+                } else {
+                    Err(format!("{} must only use the listed options", self.label))
+                }
+            }
+            UserInputFieldKind::Boolean => {
+                if value.is_boolean() {
+                    Ok(())
+                } else {
+                    Err(format!("{} must be true or false", self.label))
+                }
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), ToolError> {
+        if self.id.trim().is_empty() {
+            return Err(ToolError::invalid_input(
+                "request_user_input.fields.id cannot be empty",
+            ));
+        }
+        if self.label.trim().is_empty() {
+            return Err(ToolError::invalid_input(
+                "request_user_input.fields.label cannot be empty",
+            ));
+        }
+        if self.kind.has_options() {
+            if self.options.len() < 2 || self.options.len() > MAX_FIELD_OPTIONS {
+                return Err(ToolError::invalid_input(format!(
+                    "request_user_input field '{}' needs 2 to {MAX_FIELD_OPTIONS} options",
+                    self.id
+                )));
+            }
+            if self.options.iter().any(|o| o.trim().is_empty()) {
+                return Err(ToolError::invalid_input(format!(
+                    "request_user_input field '{}' has an empty option",
+                    self.id
+                )));
+            }
+        } else if !self.options.is_empty() {
+            return Err(ToolError::invalid_input(format!(
+                "request_user_input field '{}' only select fields take options",
+                self.id
+            )));
+        }
+        if let Some(pattern) = &self.pattern {
+            if !matches!(
+                self.kind,
+                UserInputFieldKind::Text | UserInputFieldKind::Path
+            ) {
+                return Err(ToolError::invalid_input(format!(
+                    "request_user_input field '{}' pattern applies to text and path fields only",
+                    self.id
+                )));
+            }
+            Regex::new(pattern).map_err(|e| {
+                ToolError::invalid_input(format!(
+                    "request_user_input field '{}' has an invalid pattern: {e}",
+                    self.id
+                ))
+            })?;
+        }
+        if let Some(default) = &self.default {
+            let shape_ok = match self.kind {
+                UserInputFieldKind::Text | UserInputFieldKind::Path => default.is_string(),
+                UserInputFieldKind::Select => default
+                    .as_str()
+                    .is_some_and(|choice| self.options.iter().any(|o| o == choice)),
+                UserInputFieldKind::MultiSelect => default.as_array().is_some_and(|items| {
+                    items.iter().all(|item| {
+                        item.as_str()
+                            .is_some_and(|choice| self.options.iter().any(|o| o == choice))
+                    })
+                }),
+                UserInputFieldKind::Boolean => default.is_boolean(),
+            };
+            if !shape_ok {
+                return Err(ToolError::invalid_input(format!(
+                    "request_user_input field '{}' default does not fit its type",
+                    self.id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+const MAX_FORM_FIELDS: usize = 8;
+const MAX_FIELD_OPTIONS: usize = 10;
+
+/// A request carries either multiple-choice `questions` or a form of typed
+/// `fields`, never both.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UserInputRequest {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub questions: Vec<UserInputQuestion>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<UserInputField>,
 }
 
 impl UserInputRequest {
@@ -35,10 +239,19 @@ impl UserInputRequest {
         Ok(request)
     }
 
+    /// True when the request should be shown as a form.
+    #[must_use]
+    pub fn is_form(&self) -> bool {
+        !self.fields.is_empty()
+    }
+
     pub fn validate(&self) -> Result<(), ToolError> {
+        if self.is_form() {
+            return self.validate_form();
+        }
         if self.questions.is_empty() {
             return Err(ToolError::invalid_input(
-                "request_user_input.questions must be non-empty",
+                "request_user_input needs non-empty questions or fields",
             ));
         }
         if self.questions.len() > 3 {
@@ -82,6 +295,30 @@ impl UserInputRequest {
         }
         Ok(())
     }
+
+    fn validate_form(&self) -> Result<(), ToolError> {
+        if !self.questions.is_empty() {
+            return Err(ToolError::invalid_input(
+                "request_user_input takes either questions or fields, not both",
+            ));
+        }
+        if self.fields.len() > MAX_FORM_FIELDS {
+            return Err(ToolError::invalid_input(format!(
+                "request_user_input.fields must contain 1 to {MAX_FORM_FIELDS} items"
+            )));
+        }
+        let mut seen = HashSet::new();
+        for field in &self.fields {
+            field.validate()?;
+            if !seen.insert(field.id.as_str()) {
+                return Err(ToolError::invalid_input(format!(
+                    "request_user_input field id '{}' is duplicated",
+                    field.id
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -93,7 +330,37 @@ pub struct UserInputAnswer {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserInputResponse {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub answers: Vec<UserInputAnswer>,
+    /// Form answers keyed by field id.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub values: Map<String, Value>,
+}
+
+/// A `request_user_input` call the turn was paused on when the session was
+/// saved. Resuming the session re-opens the prompt; the answer becomes the
+/// call's tool result and the turn continues from there.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PausedUserInput {
+    pub tool_id: String,
+    pub request: UserInputRequest,
+}
+
+impl PausedUserInput {
+    /// Short description of what is being asked, for session listings.
+    #[must_use]
+    pub fn label(&self) -> String {
+        self.request
+            .title
+            .clone()
+            .or_else(|| {
+                self.request
+                    .questions
+                    .first()
+                    .map(|question| question.question.clone())
+            })
+            .unwrap_or_else(|| "form input".to_string())
+    }
 }
 
 pub struct RequestUserInputTool;
@@ -105,7 +372,8 @@ impl ToolSpec for RequestUserInputTool {
     }
 
     fn description(&self) -> &'static str {
-        "Ask the user 1-3 short questions and return their selections."
+        "Ask the user 1-3 short multiple-choice questions, or show a form of typed fields \
+         (text, select, multi_select, boolean, path), and return their answers."
     }
 
     fn input_schema(&self) -> Value {
@@ -138,9 +406,49 @@ impl ToolSpec for RequestUserInputTool {
                     },
                     "minItems": 1,
                     "maxItems": 3
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Form heading (fields mode only)."
+                },
+                "fields": {
+                    "type": "array",
+                    "description": "Use instead of questions to collect several typed values at once.",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "id": { "type": "string" },
+                            "label": { "type": "string" },
+                            "type": {
+                                "type": "string",
+                                "enum": ["text", "select", "multi_select", "boolean", "path"]
+                            },
+                            "description": { "type": "string" },
+                            "required": { "type": "boolean" },
+                            "options": {
+                                "type": "array",
+                                "items": { "type": "string" },
+                                "description": "Choices for select and multi_select (2-10)."
+                            },
+                            "default": {
+                                "description": "String, array of strings (multi_select), or boolean."
+                            },
+                            "placeholder": { "type": "string" },
+                            "pattern": {
+                                "type": "string",
+                                "description": "Regex a text or path value must match."
+                            },
+                            "must_exist": {
+                                "type": "boolean",
+                                "description": "Path fields: require the path to exist."
+                            }
+                        },
+                        "required": ["id", "label", "type"]
+                    },
+                    "minItems": 1,
+                    "maxItems": MAX_FORM_FIELDS
                 }
-            },
-            "required": ["questions"]
+            }
         })
     }
 
@@ -185,6 +493,7 @@ mod tests {
                     },
                 ],
             }],
+            ..Default::default()
         };
         assert!(request.validate().is_ok());
     }
@@ -254,7 +563,81 @@ mod tests {
                     ],
                 },
             ],
+            ..Default::default()
         };
         assert!(request.validate().is_err());
     }
+
+    fn release_form() -> Value {
+        json!({
+            "title": "Release",
+            "fields": [
+                {"id": "version", "label": "Version", "type": "text", "required": true,
+                 "pattern": "^\\d+\\.\\d+\\.\\d+$"},
+                {"id": "channel", "label": "Channel", "type": "select",
+                 "options": ["stable", "beta"], "default": "beta"},
+                {"id": "targets", "label": "Targets", "type": "multi_select",
+                 "options": ["linux", "macos", "windows"]},
+                {"id": "publish", "label": "Publish", "type": "boolean", "default": true},
+                {"id": "notes", "label": "Notes file", "type": "path", "must_exist": true}
+            ]
+        })
+    }
+
+    #[test]
+    fn parses_and_validates_form_fields() {
+        let request = UserInputRequest::from_value(&release_form()).expect("valid form");
+        assert!(request.is_form());
+        assert_eq!(request.fields.len(), 5);
+        assert_eq!(request.fields[2].kind, UserInputFieldKind::MultiSelect);
+
+        let mut bad = release_form();
+        bad["fields"][1]["default"] = json!("nightly");
+        assert!(UserInputRequest::from_value(&bad).is_err());
+
+        let mut bad = release_form();
+        bad["fields"][3]["options"] = json!(["yes", "no"]);
+        assert!(UserInputRequest::from_value(&bad).is_err());
+
+        let mut bad = release_form();
+        bad["fields"][4]["id"] = json!("version");
+        assert!(UserInputRequest::from_value(&bad).is_err());
+
+        let mut bad = release_form();
+        bad["questions"] = json!([{
+            "header": "Q", "id": "q", "question": "?",
+            "options": [{"label": "A", "description": "A"}, {"label": "B", "description": "B"}]
+        }]);
+        assert!(UserInputRequest::from_value(&bad).is_err());
+    }
+
+    #[test]
+    fn field_values_are_checked_per_type() {
+        let request = UserInputRequest::from_value(&release_form()).expect("valid form");
+        let [version, channel, targets, publish, notes] = &request.fields[..] else {
+            panic!("expected five fields");
+        };
+
+        assert!(version.check_value(&json!(""), None).is_err());
+        assert!(version.check_value(&json!("1.2"), None).is_err());
+        assert!(version.check_value(&json!("1.2.3"), None).is_ok());
+        assert!(channel.check_value(&json!("stable"), None).is_ok());
+        assert!(channel.check_value(&json!("nightly"), None).is_err());
+        assert!(targets.check_value(&json!([]), None).is_ok());
+        assert!(targets.check_value(&json!(["linux", "bsd"]), None).is_err());
+        assert!(publish.check_value(&json!(false), None).is_ok());
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("NOTES.md"), "notes").expect("write");
+        assert!(
+            notes
+                .check_value(&json!("NOTES.md"), Some(dir.path()))
+                .is_ok()
+        );
+        assert!(
+            notes
+                .check_value(&json!("MISSING.md"), Some(dir.path()))
+                .is_err()
+        );
+    }
 }
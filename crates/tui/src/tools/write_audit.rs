@@ -0,0 +1,177 @@
+//! Per-call audit of what a file-writing tool changed.
+//!
+//! `write_file`, `edit_file`, `apply_patch`, `fim_edit`, and `edit_with_fim`
+//! attach a [`WriteAudit`] to their result metadata under [`METADATA_KEY`].
+//! The model-facing output of each tool is phrased differently (a diff, a
+//! JSON patch report, a one-line FIM note), so the audit gives the TUI one
+//! shape to read: the exact before/after diff per file for the tool-details
+//! pager, and line counts to roll into the end-of-turn summary.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use similar::{ChangeTag, TextDiff};
+
+use super::diff_format::make_unified_diff;
+use super::spec::ToolResult;
+
+/// Metadata key the audit is stored under.
+pub const METADATA_KEY: &str = "write_audit";
+
+/// Diffs longer than this are cut short in the audit. The line counts still
+/// cover the whole change.
+const MAX_DIFF_BYTES: usize = 256 * 1024;
+
+/// What one tool call did to one file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileWriteAudit {
+    pub path: String,
+    pub added: usize,
+    pub removed: usize,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub created: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deleted: bool,
+    pub diff: String,
+}
+
+impl FileWriteAudit {
+    /// Audit a write of `path` from `before` to `after`. `None` before means
+    /// the file was created; `None` after means it was deleted.
+    #[must_use]
+    pub fn new(path: impl Into<String>, before: Option<&str>, after: Option<&str>) -> Self {
+        let path = path.into();
+        let old = before.unwrap_or_default();
+        let new = after.unwrap_or_default();
+        let (added, removed) = TextDiff::from_lines(old, new).iter_all_changes().fold(
+            (0, 0),
+            |(added, removed), change| match change.tag() {
+                ChangeTag::Insert => (added + 1, removed),
+                ChangeTag::Delete => (added, removed + 1),
+                ChangeTag::Equal => (added, removed),
+            },
+        );
+        let mut diff = make_unified_diff(&path, old, new);
+        if diff.len() > MAX_DIFF_BYTES {
+            let mut cut = MAX_DIFF_BYTES;
+            while !diff.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            diff.truncate(cut);
+            diff.push_str("\n… (diff truncated)\n");
+        }
+        Self {
+            path,
+            added,
+            removed,
+            created: before.is_none() && after.is_some(),
+            deleted: after.is_none(),
+            diff,
+        }
+    }
+}
+
+/// Everything one tool call wrote.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WriteAudit {
+    pub files: Vec<FileWriteAudit>,
+}
+
+impl WriteAudit {
+    #[must_use]
+    pub fn new(files: Vec<FileWriteAudit>) -> Self {
+        Self { files }
+    }
+
+    #[must_use]
+    pub fn added(&self) -> usize {
+        self.files.iter().map(|file| file.added).sum()
+    }
+
+    #[must_use]
+    pub fn removed(&self) -> usize {
+        self.files.iter().map(|file| file.removed).sum()
+    }
+
+    /// Store the audit in `result`'s metadata, keeping any keys already set.
+    #[must_use]
+    pub fn attach(self, mut result: ToolResult) -> ToolResult {
+        let Ok(audit) = serde_json::to_value(self) else {
+            return result;
+        };
+        match result.metadata.as_mut().and_then(Value::as_object_mut) {
+            Some(metadata) => {
+                metadata.insert(METADATA_KEY.to_string(), audit);
+            }
+            None => {
+                result.metadata = Some(serde_json::json!({ METADATA_KEY: audit }));
+            }
+        }
+        result
+    }
+
+    /// Read the audit back out of a tool result, if the tool wrote one.
+    #[must_use]
+    pub fn from_result(result: &ToolResult) -> Option<Self> {
+        let audit = result.metadata.as_ref()?.get(METADATA_KEY)?;
+        serde_json::from_value(audit.clone()).ok()
+    }
+
+    /// Plain-text section for the tool-details pager.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut out = format!(
+            "── Changes ({} file(s), +{} -{}) ──",
+            self.files.len(),
+            self.added(),
+            self.removed()
+        );
+        for file in &self.files {
+            let kind = if file.created {
+                " (created)"
+            } else if file.deleted {
+                " (deleted)"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "\n\n{}{kind}  +{} -{}\n",
+                file.path, file.added, file.removed
+            ));
+            if file.diff.is_empty() {
+                out.push_str("(no textual changes)");
+            } else {
+                out.push_str(file.diff.trim_end());
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audit_counts_lines_and_round_trips_through_metadata() {
+        let edit = FileWriteAudit::new("src/lib.rs", Some("a\nb\nc\n"), Some("a\nB\nc\nd\n"));
+        assert_eq!((edit.added, edit.removed), (2, 1));
+        assert!(edit.diff.contains("-b"), "{}", edit.diff);
+        assert!(edit.diff.contains("+B"), "{}", edit.diff);
+        let created = FileWriteAudit::new("new.txt", None, Some("hello\n"));
+        assert!(created.created);
+        let deleted = FileWriteAudit::new("old.txt", Some("bye\n"), None);
+        assert!(deleted.deleted);
+        assert_eq!(deleted.removed, 1);
+
+        let result = ToolResult::success("done").with_metadata(serde_json::json!({"keep": 1}));
+        let result = WriteAudit::new(vec![edit, created, deleted]).attach(result);
+        assert_eq!(result.metadata.as_ref().unwrap()["keep"], 1);
+
+        let audit = WriteAudit::from_result(&result).expect("audit");
+        assert_eq!((audit.added(), audit.removed()), (3, 2));
+        let rendered = audit.render();
+        assert!(rendered.contains("3 file(s), +3 -2"), "{rendered}");
+        assert!(rendered.contains("new.txt (created)"), "{rendered}");
+        assert!(WriteAudit::from_result(&ToolResult::success("x")).is_none());
+    }
+}
@@ -0,0 +1,420 @@
+//! Transcript export shared by `/export` and `deepseek sessions export`.
+//!
+//! Producers push [`ExportEntry`] values into a [`Transcript`]; redaction is
+//! applied as they go so both exporters strip the same things the same way:
+//!
+//! - `thinking` drops reasoning blocks.
+//! - `secrets` masks API keys, bearer tokens, and `key=value` secrets in all
+//!   text (see [`crate::report::redact_text`]), and blanks tool-argument
+//!   fields whose names look secret (`api_key`, `token`, ...).
+//! - `shell-output` replaces the output of shell tools with a marker, keeping
+//!   the command that produced it.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::models::{ContentBlock, Message};
+use crate::report::{redact_json, redact_text};
+
+/// Stands in for shell output removed by `--redact shell-output`.
+pub const SHELL_OUTPUT_REMOVED: &str = "[shell output removed]";
+
+/// What to strip from an exported transcript.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Redaction {
+    pub thinking: bool,
+    pub secrets: bool,
+    pub shell_output: bool,
+}
+
+impl Redaction {
+    /// Parse a comma-separated list: `thinking`, `secrets`, `shell-output`,
+    /// or `all`.
+    pub fn parse(list: &str) -> Result<Self, String> {
+        let mut redaction = Self::default();
+        for item in list
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+        {
+            match item.to_ascii_lowercase().replace('_', "-").as_str() {
+                "thinking" | "reasoning" => redaction.thinking = true,
+                "secrets" | "secret" => redaction.secrets = true,
+                "shell-output" | "shell" => redaction.shell_output = true,
+                "all" => {
+                    redaction = Self {
+                        thinking: true,
+                        secrets: true,
+                        shell_output: true,
+                    };
+                }
+                other => {
+                    return Err(format!(
+                        "unknown redaction `{other}` (expected thinking, secrets, shell-output, or all)"
+                    ));
+                }
+            }
+        }
+        Ok(redaction)
+    }
+
+    /// Names of the enabled redactions, for the export header.
+    #[must_use]
+    pub fn labels(self) -> Vec<&'static str> {
+        [
+            (self.thinking, "thinking"),
+            (self.secrets, "secrets"),
+            (self.shell_output, "shell-output"),
+        ]
+        .into_iter()
+        .filter_map(|(on, label)| on.then_some(label))
+        .collect()
+    }
+}
+
+/// Output format, picked with `--format` or from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+}
+
+impl ExportFormat {
+    /// `.json` exports JSON; anything else is Markdown.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        {
+            Self::Json
+        } else {
+            Self::Markdown
+        }
+    }
+}
+
+/// Kind of a transcript entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    User,
+    Assistant,
+    Thinking,
+    System,
+    Warning,
+    Info,
+    Error,
+    /// A tool call as rendered in the TUI (arguments and output together).
+    Tool,
+    ToolCall,
+    ToolResult,
+    SubAgent,
+    ArchivedContext,
+}
+
+impl EntryKind {
+    fn markdown_label(self) -> &'static str {
+        match self {
+            Self::User => "**You:**",
+            Self::Assistant => "**Assistant:**",
+            Self::Thinking => "*Thinking:*",
+            Self::System => "*System:*",
+            Self::Warning => "**Warning:**",
+            Self::Info => "*Info:*",
+            Self::Error => "**Error:**",
+            Self::Tool => "**Tool:**",
+            Self::ToolCall => "**Tool call:**",
+            Self::ToolResult => "**Tool result:**",
+            Self::SubAgent => "**Sub-agent:**",
+            Self::ArchivedContext => "**Archived Context:**",
+        }
+    }
+}
+
+/// One exported block.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportEntry {
+    pub kind: EntryKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<Value>,
+    pub text: String,
+}
+
+impl ExportEntry {
+    #[must_use]
+    pub fn text(kind: EntryKind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            tool: None,
+            input: None,
+            text: text.into(),
+        }
+    }
+}
+
+/// A transcript being exported.
+#[derive(Debug, Serialize)]
+pub struct Transcript {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub model: String,
+    pub workspace: String,
+    pub exported_at: DateTime<Local>,
+    pub redacted: Vec<&'static str>,
+    pub entries: Vec<ExportEntry>,
+    #[serde(skip)]
+    redaction: Redaction,
+}
+
+impl Transcript {
+    #[must_use]
+    pub fn new(model: &str, workspace: &Path, redaction: Redaction) -> Self {
+        Self {
+            title: None,
+            model: model.to_string(),
+            workspace: workspace.display().to_string(),
+            exported_at: Local::now(),
+            redacted: redaction.labels(),
+            entries: Vec::new(),
+            redaction,
+        }
+    }
+
+    /// Add an entry, dropping or masking it per the redaction settings.
+    pub fn push(&mut self, mut entry: ExportEntry) {
+        if self.redaction.thinking && entry.kind == EntryKind::Thinking {
+            return;
+        }
+        if self.redaction.secrets {
+            entry.text = redact_text(&entry.text);
+            if let Some(input) = entry.input.as_mut() {
+                redact_json(input);
+            }
+        }
+        self.entries.push(entry);
+    }
+
+    /// Add a saved session's messages. Tool results are labelled with the
+    /// tool that produced them so shell output can be recognised.
+    pub fn push_messages(&mut self, messages: &[Message]) {
+        let mut tool_names = std::collections::HashMap::new();
+        for message in messages {
+            for block in &message.content {
+                let entry = match block {
+                    ContentBlock::Text { text, .. } => {
+                        let kind = if message.role == "user" {
+                            EntryKind::User
+                        } else {
+                            EntryKind::Assistant
+                        };
+                        ExportEntry::text(kind, text.clone())
+                    }
+                    ContentBlock::Thinking { thinking } => {
+                        ExportEntry::text(EntryKind::Thinking, thinking.clone())
+                    }
+                    ContentBlock::ToolUse {
+                        id, name, input, ..
+                    }
+                    | ContentBlock::ServerToolUse { id, name, input } => {
+                        tool_names.insert(id.clone(), name.clone());
+                        ExportEntry {
+                            kind: EntryKind::ToolCall,
+                            tool: Some(name.clone()),
+                            input: Some(input.clone()),
+                            text: String::new(),
+                        }
+                    }
+                    ContentBlock::ToolResult {
+                        tool_use_id,
+                        content,
+                        ..
+                    } => {
+                        let tool = tool_names.get(tool_use_id).cloned();
+                        let text = if self.redaction.shell_output
+                            && tool.as_deref().is_some_and(is_shell_tool)
+                        {
+                            SHELL_OUTPUT_REMOVED.to_string()
+                        } else {
+                            content.clone()
+                        };
+                        ExportEntry {
+                            kind: EntryKind::ToolResult,
+                            tool,
+                            input: None,
+                            text,
+                        }
+                    }
+                    ContentBlock::ToolSearchToolResult {
+                        tool_use_id,
+                        content,
+                    }
+                    | ContentBlock::CodeExecutionToolResult {
+                        tool_use_id,
+                        content,
+                    } => ExportEntry {
+                        kind: EntryKind::ToolResult,
+                        tool: tool_names.get(tool_use_id).cloned(),
+                        input: None,
+                        text: content.to_string(),
+                    },
+                };
+                self.push(entry);
+            }
+        }
+    }
+
+    /// Render as Markdown or pretty JSON.
+    #[must_use]
+    pub fn render(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Json => {
+                serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+            }
+            ExportFormat::Markdown => self.render_markdown(),
+        }
+    }
+
+    fn render_markdown(&self) -> String {
+        let mut out = String::from("# Chat Export\n\n");
+        if let Some(title) = &self.title {
+            let _ = writeln!(out, "**Title:** {title}");
+        }
+        let _ = write!(
+            out,
+            "**Model:** {}\n**Workspace:** {}\n**Date:** {}\n",
+            self.model,
+            self.workspace,
+            self.exported_at.format("%Y-%m-%d %H:%M:%S")
+        );
+        if !self.redacted.is_empty() {
+            let _ = writeln!(out, "**Redacted:** {}", self.redacted.join(", "));
+        }
+        out.push_str("\n---\n\n");
+
+        for entry in &self.entries {
+            let body = match (&entry.tool, &entry.input) {
+                (Some(tool), Some(input)) => format!(
+                    "`{tool}`\n\n```json\n{}\n```",
+                    serde_json::to_string_pretty(input).unwrap_or_default()
+                ),
+                (Some(tool), None) => format!("`{tool}`\n\n```\n{}\n```", entry.text.trim()),
+                _ => entry.text.trim().to_string(),
+            };
+            let _ = write!(
+                out,
+                "{}\n\n{}\n\n---\n\n",
+                entry.kind.markdown_label(),
+                body
+            );
+        }
+        out
+    }
+}
+
+/// Tools whose results are shell output.
+#[must_use]
+pub fn is_shell_tool(name: &str) -> bool {
+    name.starts_with("exec_") || name.contains("shell")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn message(role: &str, content: Vec<ContentBlock>) -> Message {
+        Message {
+            role: role.to_string(),
+            content,
+        }
+    }
+
+    #[test]
+    fn redaction_list_parses_and_rejects_unknown_names() {
+        assert_eq!(
+            Redaction::parse("thinking, shell_output").unwrap(),
+            Redaction {
+                thinking: true,
+                secrets: false,
+                shell_output: true,
+            }
+        );
+        assert_eq!(
+            Redaction::parse("all").unwrap().labels(),
+            ["thinking", "secrets", "shell-output"]
+        );
+        assert!(Redaction::parse("thinking,tools").is_err());
+    }
+
+    #[test]
+    fn session_messages_export_with_each_redaction() {
+        let messages = vec![
+            message(
+                "user",
+                vec![ContentBlock::Text {
+                    text: "deploy with token=abc123".to_string(),
+                    cache_control: None,
+                }],
+            ),
+            message(
+                "assistant",
+                vec![
+                    ContentBlock::Thinking {
+                        thinking: "private reasoning".to_string(),
+                    },
+                    ContentBlock::ToolUse {
+                        id: "t1".to_string(),
+                        name: "exec_shell".to_string(),
+                        input: json!({"command": "env", "api_key": "sk-live-0123456789"}),
+                        caller: None,
+                    },
+                ],
+            ),
+            message(
+                "user",
+                vec![ContentBlock::ToolResult {
+                    tool_use_id: "t1".to_string(),
+                    content: "HOME=/root\nSECRET=hunter2".to_string(),
+                    is_error: None,
+                    content_blocks: None,
+                }],
+            ),
+        ];
+
+        let mut plain = Transcript::new("m", Path::new("/w"), Redaction::default());
+        plain.push_messages(&messages);
+        let markdown = plain.render(ExportFormat::Markdown);
+        assert!(markdown.contains("private reasoning"));
+        assert!(markdown.contains("token=abc123"));
+        assert!(markdown.contains("HOME=/root"));
+        assert!(!markdown.contains("**Redacted:**"));
+
+        let mut redacted = Transcript::new("m", Path::new("/w"), Redaction::parse("all").unwrap());
+        redacted.push_messages(&messages);
+        let markdown = redacted.render(ExportFormat::Markdown);
+        assert!(markdown.contains("**Redacted:** thinking, secrets, shell-output"));
+        assert!(!markdown.contains("private reasoning"));
+        assert!(!markdown.contains("abc123"), "{markdown}");
+        assert!(!markdown.contains("sk-live"), "{markdown}");
+        assert!(markdown.contains("\"command\": \"env\""), "{markdown}");
+        assert!(!markdown.contains("HOME=/root"));
+        assert!(markdown.contains(SHELL_OUTPUT_REMOVED));
+
+        let json: Value = serde_json::from_str(&redacted.render(ExportFormat::Json)).unwrap();
+        let kinds: Vec<&str> = json["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["kind"].as_str().unwrap())
+            .collect();
+        assert_eq!(kinds, ["user", "tool_call", "tool_result"]);
+        assert_eq!(json["entries"][2]["tool"], "exec_shell");
+    }
+}
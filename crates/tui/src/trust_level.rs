@@ -0,0 +1,299 @@
+//! Per-workspace trust levels (`.deepseek/trust.json`).
+//!
+//! A trust level scopes what the agent may do in a workspace. Each level
+//! picks the shell sandbox policy, which tools the model is offered, and the
+//! approval mode a session starts in:
+//!
+//! - `untrusted`: read-only tools and sandbox; tools that need approval
+//!   never run.
+//! - `read-only`: read-only tools and sandbox; approvals on request.
+//! - `standard`: every tool, the sandbox follows the mode, and approvals
+//!   follow `approval_policy`. This is the default.
+//! - `full`: every tool, no sandbox outside Plan mode, auto-approve, and
+//!   file tools may reach outside the workspace.
+//!
+//! The authoritative copy of the level lives in the global config as
+//! `[projects."<path>"] trust_level`, which a repository cannot write.
+//! `trust.json` records the canonical workspace path alongside the level and
+//! is only honored when that path matches; on its own it never grants more
+//! than `standard`, so a `trust.json` committed with a guessable checkout
+//! path cannot turn on auto-approve or drop the sandbox.
+//!
+//! Workspaces trusted before levels existed (`trust_level = "trusted"` in
+//! the global config, or a `.deepseek/trusted` marker) load as `standard`.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::tui::approval::ApprovalMode;
+use crate::utils::write_atomic;
+
+const TRUST_FILE_NAME: &str = "trust.json";
+
+/// How far the agent is trusted in one workspace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TrustLevel {
+    Untrusted,
+    ReadOnly,
+    #[default]
+    Standard,
+    Full,
+}
+
+impl TrustLevel {
+    pub const ALL: [TrustLevel; 4] = [
+        TrustLevel::Untrusted,
+        TrustLevel::ReadOnly,
+        TrustLevel::Standard,
+        TrustLevel::Full,
+    ];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TrustLevel::Untrusted => "untrusted",
+            TrustLevel::ReadOnly => "read-only",
+            TrustLevel::Standard => "standard",
+            TrustLevel::Full => "full",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "untrusted" | "none" => Some(TrustLevel::Untrusted),
+            "read-only" | "readonly" | "read_only" | "ro" => Some(TrustLevel::ReadOnly),
+            "standard" | "default" | "trusted" => Some(TrustLevel::Standard),
+            "full" => Some(TrustLevel::Full),
+            _ => None,
+        }
+    }
+
+    /// One-line summary of what the level allows, for `/trust` output.
+    #[must_use]
+    pub fn summary(self) -> &'static str {
+        match self {
+            TrustLevel::Untrusted => {
+                "read-only tools, read-only sandbox, tools that need approval never run"
+            }
+            TrustLevel::ReadOnly => "read-only tools, read-only sandbox, approvals on request",
+            TrustLevel::Standard => "all tools, sandbox follows the mode, configured approvals",
+            TrustLevel::Full => {
+                "all tools, unrestricted sandbox outside Plan mode, auto-approve, external paths allowed"
+            }
+        }
+    }
+
+    /// Whether the model is limited to the read-only tool set (no writes,
+    /// no shell), whatever the mode.
+    #[must_use]
+    pub fn restricts_tools(self) -> bool {
+        matches!(self, TrustLevel::Untrusted | TrustLevel::ReadOnly)
+    }
+
+    /// The approval mode a session starts in, or `None` to keep
+    /// `approval_policy`.
+    #[must_use]
+    pub fn approval_mode(self) -> Option<ApprovalMode> {
+        match self {
+            TrustLevel::Untrusted => Some(ApprovalMode::Never),
+            TrustLevel::ReadOnly => Some(ApprovalMode::Suggest),
+            TrustLevel::Standard => None,
+            TrustLevel::Full => Some(ApprovalMode::Auto),
+        }
+    }
+
+    /// Whether file tools may touch paths outside the workspace (the
+    /// session's trust mode).
+    #[must_use]
+    pub fn allows_external_paths(self) -> bool {
+        self == TrustLevel::Full
+    }
+}
+
+impl fmt::Display for TrustLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TrustFile {
+    level: TrustLevel,
+    /// Canonical path of the workspace the level was granted for.
+    workspace: String,
+}
+
+/// Path of the trust file for `workspace`.
+#[must_use]
+pub fn trust_file_path(workspace: &Path) -> PathBuf {
+    workspace.join(".deepseek").join(TRUST_FILE_NAME)
+}
+
+/// The trust level recorded for `workspace`, or `None` when the user has not
+/// been asked yet.
+#[must_use]
+pub fn load(workspace: &Path) -> Option<TrustLevel> {
+    crate::config::workspace_trust_level(workspace)
+        .or_else(|| load_recorded(workspace).map(|level| level.min(TrustLevel::Standard)))
+        .or_else(|| {
+            workspace
+                .join(".deepseek")
+                .join("trusted")
+                .exists()
+                .then_some(TrustLevel::Standard)
+        })
+}
+
+fn load_recorded(workspace: &Path) -> Option<TrustLevel> {
+    let raw = std::fs::read_to_string(trust_file_path(workspace)).ok()?;
+    let file: TrustFile = match serde_json::from_str(&raw) {
+        Ok(file) => file,
+        Err(err) => {
+            tracing::warn!(%err, "ignoring malformed .deepseek/trust.json");
+            return None;
+        }
+    };
+    if file.workspace != workspace_key(workspace) {
+        tracing::warn!(
+            recorded = %file.workspace,
+            "ignoring .deepseek/trust.json recorded for another workspace"
+        );
+        return None;
+    }
+    Some(file.level)
+}
+
+/// Record `level` for `workspace` in the global config and the trust file.
+/// Returns the trust file path.
+pub fn save(workspace: &Path, level: TrustLevel) -> Result<PathBuf> {
+    crate::config::save_workspace_trust(workspace, level.as_str())?;
+    let path = trust_file_path(workspace);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("create dir {}", parent.display()))?;
+    }
+    let file = TrustFile {
+        level,
+        workspace: workspace_key(workspace),
+    };
+    let json = serde_json::to_string_pretty(&file).context("serialize trust file")?;
+    write_atomic(&path, json.as_bytes()).with_context(|| format!("write {}", path.display()))?;
+    Ok(path)
+}
+
+fn workspace_key(workspace: &Path) -> String {
+    workspace
+        .canonicalize()
+        .unwrap_or_else(|_| workspace.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Points `DEEPSEEK_CONFIG_PATH` at a scratch config for the test's
+    /// lifetime so `save` never touches the real global config.
+    struct ConfigPathGuard {
+        prior: Option<std::ffi::OsString>,
+        _lock: std::sync::MutexGuard<'static, ()>,
+    }
+
+    impl ConfigPathGuard {
+        fn new(path: &Path) -> Self {
+            let lock = crate::test_support::lock_test_env();
+            let prior = std::env::var_os("DEEPSEEK_CONFIG_PATH");
+            // Safety: test-only environment mutation guarded by the env mutex.
+            unsafe { std::env::set_var("DEEPSEEK_CONFIG_PATH", path) };
+            Self { prior, _lock: lock }
+        }
+    }
+
+    impl Drop for ConfigPathGuard {
+        fn drop(&mut self) {
+            // Safety: test-only environment mutation guarded by the env mutex.
+            match &self.prior {
+                Some(value) => unsafe { std::env::set_var("DEEPSEEK_CONFIG_PATH", value) },
+                None => unsafe { std::env::remove_var("DEEPSEEK_CONFIG_PATH") },
+            }
+        }
+    }
+
+    #[test]
+    fn level_round_trips_and_is_bound_to_its_workspace() {
+        let tmp = TempDir::new().expect("tempdir");
+        let _guard = ConfigPathGuard::new(&tmp.path().join("config.toml"));
+        let workspace = tmp.path().join("ws");
+        std::fs::create_dir_all(&workspace).unwrap();
+        assert_eq!(load_recorded(&workspace), None);
+
+        let path = save(&workspace, TrustLevel::ReadOnly).expect("save");
+        assert_eq!(path, workspace.join(".deepseek").join("trust.json"));
+        assert_eq!(load_recorded(&workspace), Some(TrustLevel::ReadOnly));
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(raw.contains("\"level\": \"read-only\""), "{raw}");
+
+        // The same file copied into another checkout grants nothing.
+        let other = tmp.path().join("other");
+        std::fs::create_dir_all(other.join(".deepseek")).unwrap();
+        std::fs::copy(&path, trust_file_path(&other)).unwrap();
+        assert_eq!(load_recorded(&other), None);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(load_recorded(&workspace), None);
+        // The global config still holds the authoritative level.
+        assert_eq!(load(&workspace), Some(TrustLevel::ReadOnly));
+    }
+
+    #[test]
+    fn repo_local_trust_file_never_grants_more_than_standard() {
+        let tmp = TempDir::new().expect("tempdir");
+        let _guard = ConfigPathGuard::new(&tmp.path().join("config.toml"));
+        let workspace = tmp.path().join("ws");
+        std::fs::create_dir_all(workspace.join(".deepseek")).unwrap();
+        // A committed file naming the checkout path, with no global entry.
+        let file = TrustFile {
+            level: TrustLevel::Full,
+            workspace: workspace_key(&workspace),
+        };
+        std::fs::write(
+            trust_file_path(&workspace),
+            serde_json::to_string(&file).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(load(&workspace), Some(TrustLevel::Standard));
+
+        save(&workspace, TrustLevel::Full).expect("save");
+        assert_eq!(load(&workspace), Some(TrustLevel::Full));
+        let config = std::fs::read_to_string(tmp.path().join("config.toml")).unwrap();
+        assert!(config.contains("trust_level = \"full\""), "{config}");
+    }
+
+    #[test]
+    fn levels_parse_and_scope_capabilities() {
+        for level in TrustLevel::ALL {
+            assert_eq!(TrustLevel::parse(level.as_str()), Some(level));
+        }
+        assert_eq!(TrustLevel::parse("readonly"), Some(TrustLevel::ReadOnly));
+        assert_eq!(TrustLevel::parse("yolo"), None);
+
+        assert!(TrustLevel::Untrusted.restricts_tools());
+        assert!(TrustLevel::ReadOnly.restricts_tools());
+        assert!(!TrustLevel::Standard.restricts_tools());
+        assert_eq!(
+            TrustLevel::Untrusted.approval_mode(),
+            Some(ApprovalMode::Never)
+        );
+        assert_eq!(TrustLevel::Standard.approval_mode(), None);
+        assert_eq!(TrustLevel::Full.approval_mode(), Some(ApprovalMode::Auto));
+        assert!(TrustLevel::Full.allows_external_paths());
+        assert!(!TrustLevel::Standard.allows_external_paths());
+    }
+}
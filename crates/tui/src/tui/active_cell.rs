@@ -335,6 +335,7 @@ mod tests {
             duration_ms: None,
             source: ExecSource::Assistant,
             interaction: None,
+            session: None,
             output_summary: None,
         }))
     }
@@ -344,6 +345,8 @@ mod tests {
             entries: vec![ExploringEntry {
                 label: label.to_string(),
                 status: ToolStatus::Running,
+                read_path: None,
+                stale: false,
             }],
         }))
     }
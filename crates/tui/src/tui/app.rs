@@ -1,6 +1,6 @@
 //! Application state for the `DeepSeek` TUI.
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
@@ -19,22 +19,28 @@ use crate::core::coherence::CoherenceState;
 use crate::cycle_manager::{CycleBriefing, CycleConfig};
 use crate::hooks::{HookContext, HookEvent, HookExecutor, HookResult};
 use crate::localization::{Locale, MessageId, resolve_locale, tr};
-use crate::models::{Message, SystemPrompt, compaction_threshold_for_model_and_effort};
+use crate::models::{Message, SystemPrompt, Usage, compaction_threshold_for_model_and_effort};
 use crate::palette::{self, UiTheme};
 use crate::pricing::{CostCurrency, CostEstimate};
 use crate::session_manager::SessionContextReference;
-use crate::settings::Settings;
+use crate::settings::{ModePrompt, Settings};
+use crate::tools::exec_profile::{SharedExecProfile, new_shared_exec_profile};
 use crate::tools::plan::{SharedPlanState, new_shared_plan_state};
+use crate::tools::session_changes::{SharedSessionChanges, new_shared_session_changes};
+use crate::tools::session_env::{SharedSessionEnv, new_shared_session_env};
 use crate::tools::shell::new_shared_shell_manager;
 use crate::tools::spec::RuntimeToolServices;
 use crate::tools::subagent::SubAgentResult;
 use crate::tools::todo::{SharedTodoList, new_shared_todo_list};
+use crate::tools::user_input::PausedUserInput;
+use crate::tools::write_audit::WriteAudit;
 use crate::tui::active_cell::ActiveCell;
 use crate::tui::approval::ApprovalMode;
 use crate::tui::clipboard::{ClipboardContent, ClipboardHandler};
 use crate::tui::file_mention::ContextReference;
 use crate::tui::history::{HistoryCell, TranscriptRenderOptions};
 use crate::tui::paste_burst::{FlushResult, PasteBurst};
+use crate::tui::paste_prompt::{PasteChoice, PastePromptView, extension_for, fence_paste};
 use crate::tui::scrolling::{MouseScrollState, TranscriptLineMeta, TranscriptScroll};
 use crate::tui::selection::{SelectionAutoscroll, TranscriptSelection};
 use crate::tui::streaming::StreamingState;
@@ -52,6 +58,10 @@ pub enum OnboardingState {
     /// land in `~/.deepseek/settings.toml` via `Settings::set("locale", …)`.
     Language,
     ApiKey,
+    /// Default model (fetched live), mode, and approval mode.
+    Preferences,
+    /// Optional one-line prompt that checks connectivity end to end.
+    TestPrompt,
     TrustDirectory,
     Tips,
     None,
@@ -243,6 +253,7 @@ pub enum SidebarFocus {
     Tasks,
     Agents,
     Context,
+    Files,
     Hidden,
 }
 
@@ -290,6 +301,7 @@ impl SidebarFocus {
             "tasks" => Self::Tasks,
             "agents" | "subagents" | "sub-agents" => Self::Agents,
             "context" | "session" => Self::Context,
+            "files" | "tree" | "explorer" => Self::Files,
             "hidden" | "hide" | "closed" | "off" | "none" => Self::Hidden,
             _ => Self::Auto,
         }
@@ -304,6 +316,7 @@ impl SidebarFocus {
             Self::Tasks => "tasks",
             Self::Agents => "agents",
             Self::Context => "context",
+            Self::Files => "files",
             Self::Hidden => "hidden",
         }
     }
@@ -568,6 +581,7 @@ fn looks_like_raw_mouse_report_fragment(run: &[char]) -> bool {
 
 const MAX_SUBMITTED_INPUT_CHARS: usize = 16_000;
 const MAX_DRAFT_HISTORY: usize = 50;
+const MAX_AGENT_TOUCHED_FILES: usize = 50;
 
 impl AppMode {
     #[must_use]
@@ -830,6 +844,34 @@ pub struct SessionState {
     pub total_conversation_tokens: u32,
     pub turn_cache_history: VecDeque<TurnCacheRecord>,
     pub last_cache_inspection: Option<PromptInspection>,
+    /// Session totals of cache-hit/miss prompt tokens as reported by the
+    /// provider.
+    pub cache_hit_tokens_total: u64,
+    pub cache_miss_tokens_total: u64,
+    /// Estimated savings from cache hits, per currency.
+    pub cache_saved: f64,
+    pub cache_saved_cny: f64,
+    /// Size of the request the model is answering, while a turn streams
+    /// (`Event::ContextUsage`). `None` between turns.
+    pub live_context: Option<LiveContextUsage>,
+}
+
+/// In-flight request size reported by the engine during a turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiveContextUsage {
+    pub prompt_tokens: u32,
+    pub output_tokens: u32,
+    /// The prompt size is a client-side estimate (no provider usage yet).
+    pub estimated: bool,
+}
+
+impl LiveContextUsage {
+    /// Prompt plus streamed output: what the context holds once this
+    /// response lands.
+    #[must_use]
+    pub fn total_tokens(self) -> u32 {
+        self.prompt_tokens.saturating_add(self.output_tokens)
+    }
 }
 
 impl Default for SessionState {
@@ -851,6 +893,11 @@ impl Default for SessionState {
             total_conversation_tokens: 0,
             turn_cache_history: VecDeque::new(),
             last_cache_inspection: None,
+            cache_hit_tokens_total: 0,
+            cache_miss_tokens_total: 0,
+            cache_saved: 0.0,
+            cache_saved_cny: 0.0,
+            live_context: None,
         }
     }
 }
@@ -874,6 +921,9 @@ pub struct App {
     /// Monotonic counter used to issue fresh per-cell revisions.
     pub next_history_revision: u64,
     pub api_messages: Vec<Message>,
+    /// When each `api_messages` entry arrived from the engine; persisted as
+    /// `SavedSession::message_timestamps` for replay.
+    pub api_message_times: Vec<chrono::DateTime<chrono::Utc>>,
     pub is_loading: bool,
     /// Degraded connectivity mode; new user inputs are queued for later retry.
     pub offline_mode: bool,
@@ -898,6 +948,9 @@ pub struct App {
     pub auto_model: bool,
     /// Last concrete model chosen while `auto_model` is active.
     pub last_effective_model: Option<String>,
+    /// Fallback model the running turn switched to (`model_fallbacks`),
+    /// with the turn usage already billed to the models before it.
+    pub turn_model_fallback: Option<(String, Usage)>,
     /// Current API provider (mirrors `Config::api_provider`).
     /// Updated by `/provider` switches so the UI/commands can read the
     /// active backend without re-deriving it from the live config.
@@ -907,6 +960,17 @@ pub struct App {
     pub reasoning_effort: ReasoningEffort,
     /// Last concrete thinking tier chosen while `reasoning_effort` is auto.
     pub last_effective_reasoning_effort: Option<ReasoningEffort>,
+    /// Per-turn cap on estimated thinking tokens (`/thinking budget`);
+    /// initialized from `[thinking] budget_tokens`.
+    pub thinking_budget: Option<u32>,
+    /// Per-turn git audit trail (`/trail`); initialized from `[trail]`.
+    pub trail_enabled: bool,
+    pub trail_branch: String,
+    /// `[models."<id>"]` presets; their thinking tier is applied on model
+    /// switches (the engine applies the request parameters itself).
+    pub model_presets: BTreeMap<String, crate::config::ModelPreset>,
+    /// Effective feature flags and their `[features]` values.
+    pub features: crate::features::Features,
     pub workspace: PathBuf,
     pub config_path: Option<PathBuf>,
     pub config_profile: Option<String>,
@@ -930,6 +994,13 @@ pub struct App {
     pub composer_arrows_scroll: bool,
     pub use_bracketed_paste: bool,
     pub use_paste_burst_detection: bool,
+    /// Pastes longer than this many lines open the large-paste prompt
+    /// instead of landing in the composer. `0` disables the prompt.
+    pub paste_prompt_lines: usize,
+    /// Idle clock and background maintenance jobs (`idle_maintenance_secs`).
+    pub idle_maintenance: crate::tui::idle_maintenance::IdleMaintenance,
+    /// Startup jobs still loading after the first frame.
+    pub startup: crate::tui::startup::StartupProgress,
     /// Set to `true` the first time a real `Event::Paste` arrives during a
     /// session. Once set, `handle_paste_burst_key` short-circuits — there's
     /// no point running the rapid-keypress heuristic on a terminal that
@@ -938,8 +1009,22 @@ pub struct App {
     /// fast typing or IME commits could otherwise be mis-classified as a
     /// paste burst (#1322 follow-up).
     pub bracketed_paste_seen: bool,
+    /// Whether the terminal last reported focus (`FocusGained` /
+    /// `FocusLost`). Terminals that never report focus stay `true`.
+    pub terminal_focused: bool,
     #[allow(dead_code)]
     pub system_prompt: Option<SystemPrompt>,
+    /// Per-layer breakdown of the engine's current system prompt, refreshed
+    /// whenever the engine rebuilds it. Shown by `/system`.
+    pub prompt_layers: Vec<crate::prompts::layers::PromptLayerSummary>,
+    /// Tool catalog the engine assembled for the latest turn. Shown by
+    /// `/context`.
+    pub tool_catalog: Option<crate::core::events::ToolCatalogSummary>,
+    /// One-line summaries of recent engine events, newest last, capped at
+    /// [`App::RECENT_ENGINE_EVENTS_CAP`]. Included in `/report` bundles.
+    pub recent_engine_events: VecDeque<String>,
+    /// `[keys]` remaps applied to every key press before dispatch.
+    pub keymap: crate::tui::keymap::Keymap,
     pub auto_compact: bool,
     pub calm_mode: bool,
     pub low_motion: bool,
@@ -967,6 +1052,13 @@ pub struct App {
     pub cost_currency: CostCurrency,
     pub composer_density: ComposerDensity,
     pub composer_border: bool,
+    /// Pre-send lint for vague drafts (`prompt_lint` setting).
+    pub prompt_lint: bool,
+    /// Hint panel for the draft the lint last held back; a second Enter on
+    /// the same draft sends it.
+    pub prompt_hint: Option<crate::tui::prompt_lint::PromptHint>,
+    /// Per-mode prompt prefix/suffix (`mode_prompts` setting).
+    pub mode_prompts: BTreeMap<String, ModePrompt>,
     pub transcript_spacing: TranscriptSpacing,
     pub sidebar_width_percent: u16,
     pub sidebar_focus: SidebarFocus,
@@ -974,6 +1066,14 @@ pub struct App {
     pub context_panel: bool,
     /// File-tree pane state. `None` when hidden; `Some` when visible.
     pub file_tree: Option<crate::tui::file_tree::FileTreeState>,
+    /// Tree shown by the `Files` sidebar focus; built on first render.
+    pub sidebar_file_tree: Option<crate::tui::file_tree::FileTreeState>,
+    /// Whether arrow keys drive the `Files` sidebar instead of the composer.
+    pub sidebar_tree_focused: bool,
+    /// Split pane beside the transcript with live / last tool output.
+    pub tool_output_pane: crate::tui::tool_output_pane::ToolOutputPane,
+    /// Paths the agent wrote this session, most recent first.
+    pub agent_touched_files: Vec<String>,
     #[allow(dead_code)]
     pub compact_threshold: usize,
     pub max_input_history: usize,
@@ -988,6 +1088,12 @@ pub struct App {
     /// into, so successive mailbox envelopes mutate the same cell rather
     /// than spawning duplicates.
     pub subagent_card_index: HashMap<String, usize>,
+    /// Transcript cell per persistent shell session, keyed by task id, so
+    /// streamed output updates one cell instead of appending new ones.
+    pub shell_card_index: HashMap<String, usize>,
+    /// Shell session the composer is attached to. Submitted lines go to its
+    /// stdin instead of the model until `/shells detach`.
+    pub attached_shell: Option<String>,
     /// History index of the most recent FanoutCard. Sibling sub-agents
     /// spawned by the same `rlm` invocation route into this card; reset
     /// when a fresh fanout-family tool call starts.
@@ -1008,6 +1114,10 @@ pub struct App {
     pub onboarding: OnboardingState,
     pub onboarding_needs_api_key: bool,
     pub onboarding_workspace_trust_gate: bool,
+    /// First-run flow: show the default model/mode/approval step.
+    pub onboarding_configure_defaults: bool,
+    pub onboarding_preferences: crate::tui::onboarding::preferences::PreferencesDraft,
+    pub onboarding_test_prompt: crate::tui::onboarding::test_prompt::TestPromptStatus,
     pub api_key_env_only: bool,
     pub api_key_input: String,
     pub api_key_cursor: usize,
@@ -1039,10 +1149,17 @@ pub struct App {
     pub session_artifacts: Vec<ArtifactRecord>,
     /// Trust mode - allow access outside workspace
     pub trust_mode: bool,
+    /// Workspace trust level (`.deepseek/trust.json`), shown in the header.
+    pub trust_level: crate::trust_level::TrustLevel,
     /// Translation mode — when enabled, the model is instructed to respond in
     /// the current locale and a post-hoc translation layer replaces any
     /// remaining English output before it reaches the user.
     pub translation_enabled: bool,
+    /// Auto-include relevant workspace snippets with each prompt
+    /// (`/smartcontext`).
+    pub smart_context_enabled: bool,
+    /// Files auto-included per prompt while smart context is on.
+    pub smart_context_max_files: usize,
     /// Ordered list of footer items the user wants visible. Sourced from
     /// `tui.status_items` in `~/.deepseek/config.toml` at startup; mutated
     /// live by `/statusline`. The renderer iterates this slice; no item is
@@ -1053,10 +1170,40 @@ pub struct App {
     pub project_doc: Option<String>,
     /// Plan state for tracking tasks
     pub plan_state: SharedPlanState,
+    /// Live `/plan export` document, rewritten on every plan update.
+    pub plan_export: Option<crate::tui::plan_export::PlanExport>,
+    /// Session-start baselines of files changed by tools, shared with the
+    /// engine's tool context (`/changes`).
+    pub session_changes: SharedSessionChanges,
+    /// Review findings marked addressed in `/review show`, by finding key.
+    pub review_addressed: HashSet<String>,
+    /// Live read-only share of this session (`/share start`).
+    pub share: Option<crate::runtime_api::share::ShareServer>,
+    /// `[exec_profiles.*]` wrappers available to `/profile use`.
+    pub exec_profiles: BTreeMap<String, crate::config::ExecProfileConfig>,
+    /// Whether shell approvals are guardrail-scanned (`[guardrails]`).
+    pub guardrails_enabled: bool,
+    /// Org rules from `[[guardrails.rules]]`.
+    pub guardrail_rules: Vec<crate::config::GuardrailRuleConfig>,
+    /// Active exec profile, shared with the engine's `exec_shell`.
+    pub exec_profile: SharedExecProfile,
+    /// `[env]` / `/env set` variables, shared with the engine's shell tools.
+    pub session_env: SharedSessionEnv,
+    /// Variables the missing-variable prompt already asked for, so a retry
+    /// loop does not ask twice.
+    pub env_prompted: HashSet<String>,
     /// Whether a plan follow-up prompt is waiting for user input
     pub plan_prompt_pending: bool,
     /// Whether update_plan was called during the current turn
     pub plan_tool_used_in_turn: bool,
+    /// Line changes write tools made during the current turn
+    pub turn_write_stats: TurnWriteStats,
+    /// Request text of an in-flight `/preview` turn; parsed into
+    /// `plan_preview` when the turn completes.
+    pub plan_preview_request: Option<String>,
+    /// Latest parsed `/preview` result, kept so `/preview run` can execute
+    /// it after the modal is closed.
+    pub plan_preview: Option<crate::tui::plan_preview::PlanPreview>,
     /// Todo list for `TodoWriteTool`
     #[allow(dead_code)] // For future engine integration
     pub todos: SharedTodoList,
@@ -1074,10 +1221,19 @@ pub struct App {
     pub tool_log: Vec<String>,
     /// Active skill to apply to next user message
     pub active_skill: Option<String>,
+    /// Skills auto-activated by their frontmatter rules this conversation,
+    /// in injection order. Each is injected once; the header lists them.
+    pub auto_skills: Vec<String>,
+    /// Skills excluded from auto-activation with `/skill off <name>`.
+    pub skills_off: HashSet<String>,
     /// Cached (name, description) pairs from the skill registry.
-    /// Populated once at startup and refreshed on install/uninstall so
-    /// the slash menu can show skills without filesystem I/O on every keystroke.
+    /// Filled by the deferred startup scan and refreshed on install/uninstall
+    /// so the slash menu can show skills without filesystem I/O on every
+    /// keystroke.
     pub cached_skills: Vec<(String, String)>,
+    /// Saved sessions listed in the background at startup. The first
+    /// session picker takes it; later pickers list the directory again.
+    pub session_index: Option<Vec<crate::session_manager::SessionMetadata>>,
     /// Tool call cells by tool id (for cells already finalized in `history`).
     /// While a tool call is in flight inside `active_cell`, it is tracked by
     /// `active_tool_entries` instead and migrated here at flush time.
@@ -1124,6 +1280,12 @@ pub struct App {
     /// the cancelled turn are ignored so text does not keep appearing after
     /// Ctrl+C/Esc returns focus to the composer.
     pub suppress_stream_events_until_turn_complete: bool,
+    /// Esc requested a soft cancel: the engine finishes the running tool and
+    /// then stops. A second Esc while this is set hard-cancels.
+    pub soft_cancel_pending: bool,
+    /// The last turn ended through a soft cancel with its tool results kept,
+    /// so `/retry` resumes it instead of replaying the prompt.
+    pub resumable_interrupted_turn: bool,
     /// Index into `active_cell.entries` of the thinking entry currently being
     /// streamed. `None` when no thinking block is in flight. P2.3 routes
     /// thinking into the active cell so it groups visually with tool calls
@@ -1139,6 +1301,12 @@ pub struct App {
     pub last_reasoning: Option<String>,
     /// Tool calls captured for the pending assistant message
     pub pending_tool_uses: Vec<(String, String, Value)>,
+    /// Open `request_user_input` prompt, persisted with the session so a
+    /// quit mid-question can be resumed.
+    pub paused_user_input: Option<PausedUserInput>,
+    /// True when `paused_user_input` was restored from a saved session: no
+    /// engine turn is waiting, so the answer resumes the turn instead.
+    pub paused_user_input_restored: bool,
     /// User messages queued while a turn is running
     pub queued_messages: VecDeque<QueuedMessage>,
     /// Draft queued message being edited
@@ -1178,6 +1346,17 @@ pub struct App {
     pub workspace_context_refreshed_at: Option<Instant>,
     /// Cached background tasks for sidebar rendering.
     pub task_panel: Vec<TaskPanelEntry>,
+    /// Background tasks whose output is added to the transcript once they
+    /// finish (toggled from the `/task` manager).
+    pub pinned_task_ids: BTreeSet<String>,
+    /// Finished background work collected for `/inbox`.
+    pub inbox: crate::inbox::InboxStore,
+    /// Background tasks seen queued or running; they go to the inbox when
+    /// they finish.
+    pub inbox_watched_tasks: BTreeSet<String>,
+    /// Sub-agents that completed but whose full result has not been listed
+    /// yet.
+    pub inbox_pending_agents: BTreeSet<String>,
     /// Whether the UI needs to be redrawn.
     pub needs_redraw: bool,
     /// When the current thinking block started (for duration tracking).
@@ -1235,6 +1414,9 @@ pub struct App {
     /// back to original indices for the `HideCell` / `ShowCell` actions.
     pub collapsed_cell_map: Vec<usize>,
 
+    /// Bookmarked history cells, ordered by index (`m` / `/bookmarks`).
+    pub bookmarks: Vec<crate::session_manager::SessionBookmark>,
+
     /// Whether `/edit` has loaded the last user message into the composer and
     /// the next submit should replace (not append to) the last exchange.
     pub edit_in_progress: bool,
@@ -1245,6 +1427,14 @@ pub struct App {
     /// Derived title for the current session shown in the composer border.
     /// Updated when `EngineEvent::SessionUpdated` fires or a saved session is loaded.
     pub session_title: Option<String>,
+    /// Header tab strip, rebuilt by `SessionTabs` on every tab switch and
+    /// refreshed every event-loop tick.
+    /// Empty while only one tab is open so the header stays unchanged.
+    pub session_tabs: Vec<crate::tui::session_tabs::TabLabel>,
+    /// Live attention flags of the parked tabs behind `session_tabs`.
+    pub session_tab_attention: crate::tui::session_tabs::TabAttention,
+    /// Zero-based index of this session within `session_tabs`.
+    pub active_session_tab: usize,
 }
 
 /// Message queued while the engine is busy.
@@ -1252,6 +1442,9 @@ pub struct App {
 pub struct QueuedMessage {
     pub display: String,
     pub skill_instruction: Option<String>,
+    /// Mode to send this message in; `None` uses whatever mode is active
+    /// when it is dispatched. Set from the `/queue` modal.
+    pub mode: Option<AppMode>,
 }
 
 /// How a freshly-typed user input should be sent.
@@ -1280,6 +1473,27 @@ pub struct ToolDetailRecord {
     pub tool_name: String,
     pub input: Value,
     pub output: Option<String>,
+    /// Before/after diff reported by a file-writing tool.
+    pub write_audit: Option<WriteAudit>,
+}
+
+/// Files changed by write tools during the current turn, for the summary
+/// line posted when the turn completes.
+#[derive(Debug, Clone, Default)]
+pub struct TurnWriteStats {
+    pub files: BTreeSet<String>,
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl TurnWriteStats {
+    pub fn record(&mut self, audit: &WriteAudit) {
+        for file in &audit.files {
+            self.files.insert(file.path.clone());
+        }
+        self.added += audit.added();
+        self.removed += audit.removed();
+    }
 }
 
 /// Lightweight task view for sidebar rendering.
@@ -1296,6 +1510,7 @@ impl QueuedMessage {
         Self {
             display,
             skill_instruction,
+            mode: None,
         }
     }
 
@@ -1364,12 +1579,29 @@ impl App {
         }
     }
 
+    /// Cap on [`Self::recent_engine_events`].
+    pub const RECENT_ENGINE_EVENTS_CAP: usize = 200;
+
+    /// Remember a one-line summary of `event` for `/report`.
+    pub fn record_engine_event(&mut self, event: &crate::core::events::Event) {
+        let Some(line) = event.report_line() else {
+            return;
+        };
+        let stamp = chrono::Local::now().format("%H:%M:%S");
+        self.recent_engine_events
+            .push_back(format!("{stamp} {line}"));
+        while self.recent_engine_events.len() > Self::RECENT_ENGINE_EVENTS_CAP {
+            self.recent_engine_events.pop_front();
+        }
+    }
+
     pub(crate) fn clear_model_scoped_telemetry(&mut self) {
         self.session.last_prompt_tokens = None;
         self.session.last_completion_tokens = None;
         self.session.last_prompt_cache_hit_tokens = None;
         self.session.last_prompt_cache_miss_tokens = None;
         self.session.last_reasoning_replay_tokens = None;
+        self.session.live_context = None;
         self.session.turn_cache_history.clear();
     }
 
@@ -1435,6 +1667,8 @@ impl App {
         };
         let composer_density = ComposerDensity::from_setting(&settings.composer_density);
         let composer_border = settings.composer_border;
+        let prompt_lint = settings.prompt_lint;
+        let mode_prompts = settings.mode_prompts.clone();
         let composer_vim_enabled = settings
             .composer_vim_mode
             .trim()
@@ -1444,6 +1678,11 @@ impl App {
         let sidebar_focus = SidebarFocus::from_setting(&settings.sidebar_focus);
         let max_input_history = settings.max_input_history;
         let use_paste_burst_detection = settings.paste_burst_detection;
+        let paste_prompt_lines = settings.paste_prompt_lines;
+        let idle_maintenance = crate::tui::idle_maintenance::IdleMaintenance::new(
+            settings.idle_maintenance_secs,
+            Instant::now(),
+        );
         // Resolve the named theme from settings; unknown values were already
         // normalised to "system" in Settings::load. The background_color
         // setting still overlays on top.
@@ -1502,6 +1741,8 @@ impl App {
         };
         let needs_workspace_trust =
             initial_mode != AppMode::Yolo && crate::tui::onboarding::needs_trust(&workspace);
+        let trust_level = crate::trust_level::load(&workspace).unwrap_or_default();
+        let inbox = crate::inbox::InboxStore::load(&workspace);
         let onboarding = initial_onboarding_state(
             skip_onboarding,
             was_onboarded,
@@ -1518,12 +1759,14 @@ impl App {
         let yolo_restore = if initial_mode == AppMode::Yolo {
             Some(YoloRestoreState {
                 allow_shell: config.allow_shell(),
-                trust_mode: false,
-                approval_mode: config
-                    .approval_policy
-                    .as_deref()
-                    .and_then(ApprovalMode::from_config_value)
-                    .unwrap_or_default(),
+                trust_mode: trust_level.allows_external_paths(),
+                approval_mode: trust_level.approval_mode().unwrap_or_else(|| {
+                    config
+                        .approval_policy
+                        .as_deref()
+                        .and_then(ApprovalMode::from_config_value)
+                        .unwrap_or_default()
+                }),
             })
         } else {
             None
@@ -1539,7 +1782,6 @@ impl App {
         let plan_state = new_shared_plan_state();
 
         let skills_dir = resolve_skills_dir(&workspace, &global_skills_dir, config);
-        let cached_skills = Self::discover_cached_skills(&workspace);
 
         let input_history = crate::composer_history::load_history();
         let (initial_input_text, initial_input_cursor) = match initial_input {
@@ -1583,6 +1825,7 @@ impl App {
             history_revisions: Vec::new(),
             next_history_revision: 1,
             api_messages: Vec::new(),
+            api_message_times: Vec::new(),
             is_loading: false,
             offline_mode: false,
             turn_error_posted: false,
@@ -1593,9 +1836,19 @@ impl App {
             model,
             auto_model,
             last_effective_model: None,
+            turn_model_fallback: None,
             api_provider: provider,
             reasoning_effort,
             last_effective_reasoning_effort: None,
+            thinking_budget: config
+                .thinking
+                .as_ref()
+                .and_then(|thinking| thinking.budget_tokens)
+                .filter(|budget| *budget > 0),
+            trail_enabled: config.trail_enabled(),
+            trail_branch: config.trail_branch(),
+            model_presets: config.models.clone().unwrap_or_default(),
+            features: config.features(),
             workspace,
             config_path,
             config_profile,
@@ -1607,8 +1860,16 @@ impl App {
             use_mouse_capture,
             use_bracketed_paste,
             use_paste_burst_detection,
+            paste_prompt_lines,
+            idle_maintenance,
+            startup: crate::tui::startup::StartupProgress::default(),
             bracketed_paste_seen: false,
+            terminal_focused: true,
             system_prompt: None,
+            prompt_layers: Vec::new(),
+            tool_catalog: None,
+            recent_engine_events: VecDeque::new(),
+            keymap: config.keymap(),
             auto_compact,
             calm_mode,
             low_motion,
@@ -1622,11 +1883,18 @@ impl App {
             cost_currency,
             composer_density,
             composer_border,
+            prompt_lint,
+            prompt_hint: None,
+            mode_prompts,
             transcript_spacing,
             sidebar_width_percent,
             sidebar_focus,
             context_panel: settings.context_panel,
             file_tree: None,
+            sidebar_file_tree: None,
+            sidebar_tree_focused: false,
+            tool_output_pane: crate::tui::tool_output_pane::ToolOutputPane::default(),
+            agent_touched_files: Vec::new(),
             compact_threshold,
             max_input_history,
             allow_shell,
@@ -1634,6 +1902,8 @@ impl App {
             subagent_cache: Vec::new(),
             agent_progress: HashMap::new(),
             subagent_card_index: HashMap::new(),
+            shell_card_index: HashMap::new(),
+            attached_shell: None,
             last_fanout_card_index: None,
             pending_subagent_dispatch: None,
             agent_activity_started_at: None,
@@ -1642,6 +1912,9 @@ impl App {
             onboarding,
             onboarding_needs_api_key: needs_api_key,
             onboarding_workspace_trust_gate,
+            onboarding_configure_defaults: onboarding == OnboardingState::Welcome,
+            onboarding_preferences: Default::default(),
+            onboarding_test_prompt: Default::default(),
             api_key_env_only,
             api_key_input: String::new(),
             api_key_cursor: 0,
@@ -1653,6 +1926,8 @@ impl App {
             approval_session_denied: HashSet::new(),
             approval_mode: if matches!(initial_mode, AppMode::Yolo) {
                 ApprovalMode::Auto
+            } else if let Some(mode) = trust_level.approval_mode() {
+                mode
             } else {
                 config
                     .approval_policy
@@ -1664,8 +1939,14 @@ impl App {
             backtrack: crate::tui::backtrack::BacktrackState::new(),
             current_session_id: None,
             session_artifacts: Vec::new(),
-            trust_mode: initial_mode == AppMode::Yolo,
+            trust_mode: initial_mode == AppMode::Yolo || trust_level.allows_external_paths(),
+            trust_level,
             translation_enabled: false,
+            smart_context_enabled: config
+                .smart_context
+                .as_ref()
+                .is_some_and(crate::config::SmartContextConfig::enabled),
+            smart_context_max_files: config.smart_context.clone().unwrap_or_default().max_files(),
             status_items: config
                 .tui
                 .as_ref()
@@ -1673,8 +1954,25 @@ impl App {
                 .unwrap_or_else(crate::config::StatusItem::default_footer),
             project_doc: None,
             plan_state,
+            plan_export: None,
+            session_changes: new_shared_session_changes(),
+            review_addressed: HashSet::new(),
+            share: None,
+            exec_profiles: config.exec_profiles.clone().unwrap_or_default(),
+            guardrails_enabled: config.guardrails_enabled(),
+            guardrail_rules: config
+                .guardrails
+                .as_ref()
+                .map(|guardrails| guardrails.rules.clone())
+                .unwrap_or_default(),
+            exec_profile: new_shared_exec_profile(),
+            session_env: new_shared_session_env(config.env.as_ref()),
+            env_prompted: HashSet::new(),
             plan_prompt_pending: false,
             plan_tool_used_in_turn: false,
+            turn_write_stats: TurnWriteStats::default(),
+            plan_preview_request: None,
+            plan_preview: None,
             todos: new_shared_todo_list(),
             runtime_services: RuntimeToolServices {
                 shell_manager: Some(shell_manager),
@@ -1692,7 +1990,10 @@ impl App {
             mcp_restart_required: false,
             tool_log: Vec::new(),
             active_skill: None,
-            cached_skills,
+            auto_skills: Vec::new(),
+            skills_off: HashSet::new(),
+            cached_skills: Vec::new(),
+            session_index: None,
             tool_cells: HashMap::new(),
             tool_details_by_cell: HashMap::new(),
             context_references_by_cell: HashMap::new(),
@@ -1707,12 +2008,16 @@ impl App {
             last_exec_wait_command: None,
             streaming_message_index: None,
             suppress_stream_events_until_turn_complete: false,
+            soft_cancel_pending: false,
+            resumable_interrupted_turn: false,
             streaming_thinking_active_entry: None,
             streaming_state: StreamingState::new(),
             reasoning_buffer: String::new(),
             reasoning_header: None,
             last_reasoning: None,
             pending_tool_uses: Vec::new(),
+            paused_user_input: None,
+            paused_user_input_restored: false,
             queued_messages: VecDeque::new(),
             queued_draft: None,
             pending_steers: VecDeque::new(),
@@ -1727,6 +2032,10 @@ impl App {
             workspace_context_cell: std::sync::Arc::new(std::sync::Mutex::new(None)),
             workspace_context_refreshed_at: None,
             task_panel: Vec::new(),
+            pinned_task_ids: BTreeSet::new(),
+            inbox,
+            inbox_watched_tasks: BTreeSet::new(),
+            inbox_pending_agents: BTreeSet::new(),
             needs_redraw: true,
             thinking_started_at: None,
             is_compacting: false,
@@ -1744,6 +2053,7 @@ impl App {
             cycle: CycleConfig::default(),
             collapsed_cells: HashSet::new(),
             collapsed_cell_map: Vec::new(),
+            bookmarks: Vec::new(),
             edit_in_progress: false,
             lsp_enabled: config.lsp.as_ref().and_then(|l| l.enabled).unwrap_or(true),
             composer_arrows_scroll: config
@@ -1752,10 +2062,13 @@ impl App {
                 .and_then(|tui| tui.composer_arrows_scroll)
                 .unwrap_or_else(|| default_composer_arrows_scroll(use_mouse_capture)),
             session_title: None,
+            session_tabs: Vec::new(),
+            session_tab_attention: crate::tui::session_tabs::TabAttention::default(),
+            active_session_tab: 0,
         }
     }
 
-    fn discover_cached_skills(workspace: &std::path::Path) -> Vec<(String, String)> {
+    pub(crate) fn discover_cached_skills(workspace: &std::path::Path) -> Vec<(String, String)> {
         crate::skills::discover_in_workspace(workspace)
             .list()
             .iter()
@@ -1793,6 +2106,34 @@ impl App {
         self.needs_redraw = true;
     }
 
+    /// Switch the session to a workspace trust level: approval mode and
+    /// external-path access follow the level. In YOLO mode the level takes
+    /// effect when YOLO is left.
+    pub fn set_trust_level(&mut self, level: crate::trust_level::TrustLevel) {
+        self.trust_level = level;
+        let approval_mode = level.approval_mode().unwrap_or_default();
+        let trust_mode = level.allows_external_paths();
+        if let Some(restore) = self.yolo_restore.as_mut() {
+            restore.approval_mode = approval_mode;
+            restore.trust_mode = trust_mode;
+        } else {
+            self.approval_mode = approval_mode;
+            self.trust_mode = trust_mode;
+        }
+        self.needs_redraw = true;
+    }
+
+    /// Set the session approval mode. In YOLO mode the choice takes effect
+    /// when YOLO is left.
+    pub fn set_approval_mode(&mut self, mode: ApprovalMode) {
+        if let Some(restore) = self.yolo_restore.as_mut() {
+            restore.approval_mode = mode;
+        } else {
+            self.approval_mode = mode;
+        }
+        self.needs_redraw = true;
+    }
+
     /// Apply a locale tag selected from the onboarding language picker (#566).
     /// Persists the value to `~/.deepseek/settings.toml` and immediately
     /// re-resolves `ui_locale` so the rest of onboarding renders in the new
@@ -1971,6 +2312,62 @@ impl App {
         self.refresh_displayed_cost_high_water();
     }
 
+    /// Model the running turn's requests are billed to: the active
+    /// fallback, else the auto-selected model, else the configured one.
+    pub fn turn_pricing_model(&self) -> &str {
+        if let Some((model, _)) = self.turn_model_fallback.as_ref() {
+            return model;
+        }
+        if self.auto_model {
+            self.last_effective_model.as_deref().unwrap_or(&self.model)
+        } else {
+            &self.model
+        }
+    }
+
+    /// Accrue the cost and cache savings of `usage` priced as `model`,
+    /// returning the cost estimate when the model has known pricing.
+    pub fn accrue_model_usage(&mut self, model: &str, usage: &Usage) -> Option<CostEstimate> {
+        let cost = crate::pricing::calculate_turn_cost_estimate_from_usage(model, usage);
+        let savings = crate::pricing::calculate_cache_savings_estimate_from_usage(model, usage);
+        if let Some(cost) = cost {
+            self.accrue_session_cost_estimate(cost);
+        }
+        self.accrue_cache_usage(usage, savings);
+        cost
+    }
+
+    /// Fold one response's cache hit/miss counts and estimated savings into
+    /// the session totals. Responses without cache telemetry are ignored.
+    pub fn accrue_cache_usage(&mut self, usage: &Usage, savings: Option<CostEstimate>) {
+        let Some(hit) = usage.prompt_cache_hit_tokens else {
+            return;
+        };
+        let miss = usage
+            .prompt_cache_miss_tokens
+            .unwrap_or_else(|| usage.input_tokens.saturating_sub(hit));
+        self.session.cache_hit_tokens_total += u64::from(hit);
+        self.session.cache_miss_tokens_total += u64::from(miss);
+        if let Some(savings) = savings {
+            self.session.cache_saved += savings.usd;
+            self.session.cache_saved_cny += savings.cny;
+        }
+    }
+
+    /// Estimated session savings from cache hits in the chosen currency.
+    pub fn cache_saved_for_currency(&self, currency: CostCurrency) -> f64 {
+        match currency {
+            CostCurrency::Usd => self.session.cache_saved,
+            CostCurrency::Cny => self.session.cache_saved_cny,
+        }
+    }
+
+    /// Share of reported prompt tokens served from cache this session.
+    pub fn session_cache_hit_percent(&self) -> Option<f64> {
+        let total = self.session.cache_hit_tokens_total + self.session.cache_miss_tokens_total;
+        (total > 0).then(|| self.session.cache_hit_tokens_total as f64 / total as f64 * 100.0)
+    }
+
     /// Copy current session/subagent cost accumulators into session metadata
     /// for persistence.
     pub fn sync_cost_to_metadata(&self, metadata: &mut crate::session_manager::SessionMetadata) {
@@ -1980,6 +2377,10 @@ impl App {
         metadata.cost.subagent_cost_cny = self.session.subagent_cost_cny;
         metadata.cost.displayed_cost_high_water_usd = self.session.displayed_cost_high_water;
         metadata.cost.displayed_cost_high_water_cny = self.session.displayed_cost_high_water_cny;
+        metadata.cost.cache_hit_tokens = self.session.cache_hit_tokens_total;
+        metadata.cost.cache_miss_tokens = self.session.cache_miss_tokens_total;
+        metadata.cost.cache_saved_usd = self.session.cache_saved;
+        metadata.cost.cache_saved_cny = self.session.cache_saved_cny;
     }
 
     /// Recompute the displayed cost high-water mark. Called any time a cost
@@ -2137,6 +2538,15 @@ impl App {
             }
         });
 
+        self.shell_card_index.retain(|_, idx| {
+            if *idx >= n {
+                *idx -= n;
+                true
+            } else {
+                false
+            }
+        });
+
         // last_fanout_card_index
         if let Some(ref mut idx) = self.last_fanout_card_index {
             if *idx >= n {
@@ -2152,6 +2562,16 @@ impl App {
             .filter_map(|idx| if idx >= n { Some(idx - n) } else { None })
             .collect();
         self.collapsed_cell_map.clear();
+
+        // bookmarks
+        self.bookmarks.retain_mut(|bookmark| {
+            if bookmark.cell_index >= n {
+                bookmark.cell_index -= n;
+                true
+            } else {
+                false
+            }
+        });
     }
 
     pub fn mark_history_updated(&mut self) {
@@ -2246,6 +2666,7 @@ impl App {
         self.session_artifacts.clear();
         self.collapsed_cells.clear();
         self.collapsed_cell_map.clear();
+        self.bookmarks.clear();
         self.history_version = self.history_version.wrapping_add(1);
         self.needs_redraw = true;
     }
@@ -2257,6 +2678,8 @@ impl App {
             self.history_revisions.pop();
             self.context_references_by_cell.remove(&self.history.len());
             self.rebuild_session_context_references();
+            let len = self.history.len();
+            self.bookmarks.retain(|bookmark| bookmark.cell_index < len);
             self.history_version = self.history_version.wrapping_add(1);
             self.needs_redraw = true;
         }
@@ -2286,6 +2709,7 @@ impl App {
             .retain(|idx, _| *idx < new_len);
         self.rebuild_session_context_references();
         self.subagent_card_index.retain(|_, idx| *idx < new_len);
+        self.shell_card_index.retain(|_, idx| *idx < new_len);
         if self
             .last_fanout_card_index
             .is_some_and(|idx| idx >= new_len)
@@ -2295,6 +2719,8 @@ impl App {
         // Drop collapsed cells that reference indices past the new tail.
         self.collapsed_cells.retain(|idx| *idx < new_len);
         self.collapsed_cell_map.clear();
+        self.bookmarks
+            .retain(|bookmark| bookmark.cell_index < new_len);
         self.history_version = self.history_version.wrapping_add(1);
         self.needs_redraw = true;
     }
@@ -2628,9 +3054,32 @@ impl App {
 
     pub fn set_sidebar_focus(&mut self, focus: SidebarFocus) {
         self.sidebar_focus = focus;
+        if focus != SidebarFocus::Files {
+            self.sidebar_tree_focused = false;
+        }
         self.needs_redraw = true;
     }
 
+    /// Remember that the agent wrote `paths`, keeping the newest
+    /// `MAX_AGENT_TOUCHED_FILES` entries and syncing open file trees.
+    pub fn record_agent_touched_files(&mut self, paths: Vec<String>) {
+        if paths.is_empty() {
+            return;
+        }
+        for path in paths {
+            self.agent_touched_files
+                .retain(|existing| *existing != path);
+            self.agent_touched_files.insert(0, path);
+        }
+        self.agent_touched_files.truncate(MAX_AGENT_TOUCHED_FILES);
+        for tree in [self.file_tree.as_mut(), self.sidebar_file_tree.as_mut()]
+            .into_iter()
+            .flatten()
+        {
+            tree.set_touched(&self.agent_touched_files);
+        }
+    }
+
     pub fn close_slash_menu(&mut self) {
         self.slash_menu_hidden = true;
         self.needs_redraw = true;
@@ -2830,6 +3279,56 @@ impl App {
         self.needs_redraw = true;
     }
 
+    /// Entry point for explicit pastes (bracketed, clipboard, or a flushed
+    /// paste burst). Pastes over [`Self::paste_prompt_lines`] open the
+    /// large-paste prompt; everything else goes straight to the composer.
+    pub fn accept_paste(&mut self, text: &str) {
+        if !self.paste_needs_prompt(text) {
+            self.insert_paste_text(text);
+            return;
+        }
+        if let Some(pending) = self.paste_burst.flush_before_modified_input() {
+            self.insert_str(&pending);
+        }
+        self.paste_burst.clear_after_explicit_paste();
+        self.view_stack
+            .push(PastePromptView::new(normalize_paste_text(text)));
+    }
+
+    fn paste_needs_prompt(&self, text: &str) -> bool {
+        self.paste_prompt_lines > 0 && text.lines().count() > self.paste_prompt_lines
+    }
+
+    /// Apply the user's choice from the large-paste prompt.
+    pub fn resolve_paste(&mut self, text: &str, choice: PasteChoice, language: Option<&str>) {
+        match choice {
+            PasteChoice::Inline => {
+                // The opening fence must start its own line; `insert_block`
+                // only breaks the line after non-whitespace.
+                let before = &self.input[..self.cursor_byte_index()];
+                if before.ends_with(|ch: char| ch.is_whitespace() && ch != '\n') {
+                    self.insert_str("\n");
+                }
+                self.insert_block(&fence_paste(text, language));
+                self.consolidate_large_input_if_oversized();
+            }
+            PasteChoice::Attach => match self.write_paste_file(text, extension_for(language)) {
+                Ok(rel_path) => {
+                    self.insert_block(&format!("@{rel_path}"));
+                    self.status_message = Some(format!(
+                        "Attached {} pasted lines as @{rel_path}",
+                        text.lines().count()
+                    ));
+                }
+                Err(e) => self.push_status_toast(
+                    format!("Failed to write paste file: {e}"),
+                    StatusToastLevel::Error,
+                    Some(8_000),
+                ),
+            },
+        }
+    }
+
     pub fn insert_paste_text(&mut self, text: &str) {
         if let Some(pending) = self.paste_burst.flush_before_modified_input() {
             self.insert_str(&pending);
@@ -2851,6 +3350,12 @@ impl App {
 
     pub fn insert_media_attachment(&mut self, kind: &str, path: &Path, description: Option<&str>) {
         let reference = media_attachment_reference(kind, path, description);
+        self.insert_block(&reference);
+    }
+
+    /// Insert `block` at the cursor on lines of its own, adding newlines
+    /// around it only where the surrounding text needs them.
+    fn insert_block(&mut self, block: &str) {
         let cursor = self.cursor_position.min(char_count(&self.input));
         let byte_index = byte_index_at_char(&self.input, cursor);
         let needs_prefix_newline = self.input[..byte_index]
@@ -2866,7 +3371,7 @@ impl App {
         if needs_prefix_newline {
             inserted.push('\n');
         }
-        inserted.push_str(&reference);
+        inserted.push_str(block);
         if needs_suffix_newline || self.input[byte_index..].is_empty() {
             inserted.push('\n');
         }
@@ -2969,7 +3474,11 @@ impl App {
     pub fn flush_paste_burst_if_due(&mut self, now: Instant) -> bool {
         match self.paste_burst.flush_if_due(now) {
             FlushResult::Paste(text) => {
-                self.insert_str(&text);
+                if self.paste_needs_prompt(&text) {
+                    self.view_stack.push(PastePromptView::new(text));
+                } else {
+                    self.insert_str(&text);
+                }
                 true
             }
             FlushResult::Typed(ch) => {
@@ -3038,7 +3547,7 @@ impl App {
     pub fn apply_clipboard_content(&mut self, content: ClipboardContent) {
         match content {
             ClipboardContent::Text(text) => {
-                self.insert_paste_text(&text);
+                self.accept_paste(&text);
             }
             ClipboardContent::Image(pasted) => {
                 let description = format!("{} ({})", pasted.short_label(), pasted.size_label());
@@ -3889,9 +4398,48 @@ impl App {
                 return None;
             }
         }
+        if self.hold_vague_draft() {
+            return None;
+        }
         self.submit_input()
     }
 
+    /// The lint hint panel, while the composer still holds the draft it
+    /// was raised for.
+    pub fn visible_prompt_hint(&self) -> Option<&crate::tui::prompt_lint::PromptHint> {
+        self.prompt_hint
+            .as_ref()
+            .filter(|hint| hint.draft == self.input.trim())
+    }
+
+    /// Prefix/suffix configured for the active mode, if any.
+    pub fn active_mode_prompt(&self) -> Option<&ModePrompt> {
+        self.mode_prompts
+            .get(self.mode.as_setting())
+            .filter(|prompt| !prompt.is_empty())
+    }
+
+    /// Pre-send lint (`prompt_lint`): hold a vague draft back once and show
+    /// the hint panel. Enter on the unchanged draft sends it as-is.
+    fn hold_vague_draft(&mut self) -> bool {
+        if !self.prompt_lint {
+            return false;
+        }
+        if self.input.trim_start().starts_with(['/', '#']) {
+            return false;
+        }
+        let held = self.prompt_hint.take();
+        let draft = self.input.trim();
+        if held.is_some_and(|hint| hint.draft == draft) {
+            return false;
+        }
+        self.prompt_hint = crate::tui::prompt_lint::lint(draft);
+        if self.prompt_hint.is_some() {
+            self.needs_redraw = true;
+        }
+        self.prompt_hint.is_some()
+    }
+
     /// Public wrapper around [`Self::consolidate_large_input`] that no-ops
     /// when the current input fits inside the safety cap. Both the paste-
     /// insert path (visible-before-submit) and the submit-time safety net
@@ -3912,44 +4460,44 @@ impl App {
         let full_input = std::mem::take(&mut self.input);
         self.cursor_position = 0;
 
-        let now = chrono::Local::now();
-        let suffix = uuid::Uuid::new_v4().to_string()[..8].to_string();
-        let filename = format!("paste-{}-{}.md", now.format("%Y-%m-%d-%H%M%S"), suffix);
-        let rel_path = format!(".deepseek/pastes/{filename}");
-
-        let pastes_dir = self.workspace.join(".deepseek/pastes");
-        if let Err(e) = std::fs::create_dir_all(&pastes_dir) {
-            // Fallback: keep a truncated version so we don't lose the
-            // user's input entirely when the filesystem is unhappy.
-            self.input = full_input.chars().take(MAX_SUBMITTED_INPUT_CHARS).collect();
-            self.cursor_position = char_count(&self.input);
-            self.push_status_toast(
-                format!("Failed to create paste directory: {e}"),
-                StatusToastLevel::Error,
-                Some(8_000),
-            );
-            return;
-        }
-
-        let file_path = self.workspace.join(&rel_path);
-        if let Err(e) = std::fs::write(&file_path, &full_input) {
-            self.input = full_input.chars().take(MAX_SUBMITTED_INPUT_CHARS).collect();
-            self.cursor_position = char_count(&self.input);
-            self.push_status_toast(
-                format!("Failed to write paste file: {e}"),
-                StatusToastLevel::Error,
-                Some(8_000),
-            );
-            return;
+        match self.write_paste_file(&full_input, "md") {
+            Ok(rel_path) => {
+                self.input = format!("@{rel_path}");
+                self.cursor_position = char_count(&self.input);
+                self.push_status_toast(
+                    "Large paste consolidated — sent as @mention",
+                    StatusToastLevel::Info,
+                    Some(5_000),
+                );
+            }
+            Err(e) => {
+                // Fallback: keep a truncated version so we don't lose the
+                // user's input entirely when the filesystem is unhappy.
+                self.input = full_input.chars().take(MAX_SUBMITTED_INPUT_CHARS).collect();
+                self.cursor_position = char_count(&self.input);
+                self.push_status_toast(
+                    format!("Failed to write paste file: {e}"),
+                    StatusToastLevel::Error,
+                    Some(8_000),
+                );
+            }
         }
+    }
 
-        self.input = format!("@{rel_path}");
-        self.cursor_position = char_count(&self.input);
-        self.push_status_toast(
-            "Large paste consolidated — sent as @mention",
-            StatusToastLevel::Info,
-            Some(5_000),
+    /// Write `content` to a fresh `.deepseek/pastes/paste-<ts>-<id>.<ext>`
+    /// file and return its workspace-relative path.
+    fn write_paste_file(&self, content: &str, extension: &str) -> std::io::Result<String> {
+        let now = chrono::Local::now();
+        let suffix = uuid::Uuid::new_v4().to_string()[..8].to_string();
+        let filename = format!(
+            "paste-{}-{}.{extension}",
+            now.format("%Y-%m-%d-%H%M%S"),
+            suffix
         );
+        let rel_path = format!(".deepseek/pastes/{filename}");
+        std::fs::create_dir_all(self.workspace.join(".deepseek/pastes"))?;
+        std::fs::write(self.workspace.join(&rel_path), content)?;
+        Ok(rel_path)
     }
 
     pub fn queue_message(&mut self, message: QueuedMessage) {
@@ -4163,6 +4711,22 @@ impl App {
         self.last_effective_model = None;
     }
 
+    /// Apply the thinking tier from the selected model's `[models."<id>"]`
+    /// preset. Returns the preset summary for the status line, or `None`
+    /// when the model has no preset (or auto routing is on).
+    pub fn apply_model_preset(&mut self) -> Option<String> {
+        if self.auto_model {
+            return None;
+        }
+        let preset = crate::config::model_preset(&self.model_presets, &self.model)?;
+        if let Some(thinking) = preset.thinking_budget.as_deref() {
+            self.reasoning_effort = ReasoningEffort::from_setting(thinking);
+            self.last_effective_reasoning_effort = None;
+        }
+        let summary = preset.summary();
+        (!summary.is_empty()).then_some(summary)
+    }
+
     pub fn model_selection_for_persistence(&self) -> String {
         if self.auto_model || self.model.trim().eq_ignore_ascii_case("auto") {
             "auto".to_string()
@@ -4259,10 +4823,18 @@ pub enum AppAction {
     OpenProviderPicker,
     /// Open the `/mode` picker modal for Agent / Plan / YOLO.
     OpenModePicker,
+    /// Open the `/queue` modal for editing and reordering queued messages.
+    OpenQueueEditor,
+    /// Reopen the latest `/preview` plan.
+    OpenPlanPreview,
+    /// Switch to Agent mode and run the latest `/preview` plan.
+    ExecutePlanPreview,
     /// Open the `/statusline` multi-select picker for footer items.
     OpenStatusPicker,
     /// Open the `/feedback` picker for GitHub issue/security destinations.
     OpenFeedbackPicker,
+    /// Open the `/bookmarks` jump list.
+    OpenBookmarks,
     /// Open the `/theme` picker modal with live preview of every preset.
     OpenThemePicker,
     /// Open an external URL in the system browser.
@@ -4275,6 +4847,18 @@ pub enum AppAction {
     ListSubAgents,
     FetchModels,
     CacheWarmup,
+    /// Rewrite a draft prompt with a fast model call (`/enhance`).
+    EnhancePrompt(String),
+    /// Drop the engine's cached read-only tool results (`/cache clear`).
+    ClearToolResultCache,
+    /// Replace the engine's per-turn thinking-token cap (`/thinking budget`).
+    SetThinkingBudget(Option<u32>),
+    /// Turn the engine's per-turn git audit trail on or off (`/trail`).
+    SetTrail(bool),
+    /// Apply a new workspace trust level to the engine (`/trust level`).
+    SetTrustLevel(crate::trust_level::TrustLevel),
+    /// Write a `/report` bug-report bundle.
+    GenerateReport,
     /// Switch the active LLM backend (DeepSeek vs NVIDIA NIM) without
     /// restarting the process. The runtime rebuilds its API client from
     /// the updated config. `model` overrides the post-switch model
@@ -4290,6 +4874,8 @@ pub enum AppAction {
         prompt: String,
     },
     TaskList,
+    /// Open the interactive task manager modal.
+    TaskPanel,
     TaskShow {
         id: String,
     },
@@ -4297,6 +4883,7 @@ pub enum AppAction {
         id: String,
     },
     ShellJob(ShellJobAction),
+    ShellSession(ShellSessionAction),
     Mcp(McpUiAction),
     /// Switch to a different config profile without restarting.
     SwitchProfile {
@@ -4318,6 +4905,12 @@ pub enum AppAction {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ShellJobAction {
     List,
+    /// Start `command` as a background job; `label` names what asked for it
+    /// (e.g. a project alias like `/build`).
+    Run {
+        label: String,
+        command: String,
+    },
     Show {
         id: String,
     },
@@ -4336,6 +4929,22 @@ pub enum ShellJobAction {
     CancelAll,
 }
 
+/// `/shells` actions for persistent sessions. Showing and killing a session
+/// reuse [`ShellJobAction`] because session names resolve like job ids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShellSessionAction {
+    List,
+    Open {
+        name: String,
+        command: Option<String>,
+    },
+    /// Route plain composer input to the session's stdin.
+    Attach {
+        name: String,
+    },
+    Detach,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum McpUiAction {
     Show,
@@ -4362,6 +4971,12 @@ pub enum McpUiAction {
     },
     Validate,
     Reload,
+    /// Fetch a server-declared prompt and drop its text into the composer.
+    Prompt {
+        server: String,
+        name: String,
+        arguments: Vec<(String, String)>,
+    },
 }
 
 #[cfg(test)]
@@ -4415,6 +5030,33 @@ mod tests {
         assert!(default_composer_arrows_scroll_for_platform(true, true));
     }
 
+    #[test]
+    fn recent_engine_events_skip_deltas_and_stay_capped() {
+        use crate::core::events::Event;
+
+        let mut app = App::new(test_options(false), &Config::default());
+        app.record_engine_event(&Event::MessageDelta {
+            index: 0,
+            content: "private text".to_string(),
+        });
+        assert!(app.recent_engine_events.is_empty());
+
+        for turn in 0..App::RECENT_ENGINE_EVENTS_CAP + 5 {
+            app.record_engine_event(&Event::TurnStarted {
+                turn_id: format!("turn-{turn}"),
+            });
+        }
+        assert_eq!(
+            app.recent_engine_events.len(),
+            App::RECENT_ENGINE_EVENTS_CAP
+        );
+        assert!(
+            app.recent_engine_events
+                .back()
+                .is_some_and(|line| line.ends_with("turn_started turn-204"))
+        );
+    }
+
     #[test]
     fn move_cursor_line_start_multiline() {
         let mut app = App::new(test_options(false), &Config::default());
@@ -4548,6 +5190,8 @@ mod tests {
         assert_eq!(SidebarFocus::from_setting("tasks"), SidebarFocus::Tasks);
         assert_eq!(SidebarFocus::from_setting("agents"), SidebarFocus::Agents);
         assert_eq!(SidebarFocus::from_setting("context"), SidebarFocus::Context);
+        assert_eq!(SidebarFocus::from_setting("tree"), SidebarFocus::Files);
+        assert_eq!(SidebarFocus::Files.as_setting(), "files");
         assert_eq!(SidebarFocus::from_setting("hidden"), SidebarFocus::Hidden);
         assert_eq!(SidebarFocus::from_setting("off"), SidebarFocus::Hidden);
         assert_eq!(SidebarFocus::Work.as_setting(), "work");
@@ -4780,7 +5424,7 @@ mod tests {
     }
 
     #[test]
-    fn new_caches_workspace_skills_for_slash_menu() {
+    fn skill_cache_holds_workspace_skills_for_slash_menu() {
         let tmp = tempfile::TempDir::new().expect("tempdir");
         let workspace = tmp.path().join("workspace");
         let skill_dir = workspace.join(".agents").join("skills").join("local-skill");
@@ -4794,7 +5438,9 @@ mod tests {
         let mut options = test_options(false);
         options.workspace = workspace.clone();
         options.skills_dir = tmp.path().join("global-skills");
-        let app = App::new(options, &Config::default());
+        let mut app = App::new(options, &Config::default());
+        // The startup scan runs in the background; refresh synchronously.
+        app.refresh_skill_cache();
 
         assert_eq!(app.skills_dir, workspace.join(".agents").join("skills"));
         assert!(app.cached_skills.iter().any(|(name, description)| {
@@ -4825,7 +5471,8 @@ mod tests {
         let mut options = test_options(false);
         options.workspace = workspace.clone();
         options.skills_dir = tmp.path().join("global-skills");
-        let app = App::new(options, &Config::default());
+        let mut app = App::new(options, &Config::default());
+        app.refresh_skill_cache();
 
         assert!(
             app.cached_skills
@@ -4896,6 +5543,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn long_paste_opens_prompt_and_choices_fence_or_attach_it() {
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let mut opts = test_options(false);
+        opts.workspace = tmp.path().to_path_buf();
+        let mut app = App::new(opts, &Config::default());
+        app.paste_prompt_lines = 2;
+
+        app.accept_paste("short paste");
+        assert_eq!(app.input, "short paste");
+        assert!(app.view_stack.is_empty());
+
+        let code = "fn main() {\r\n    let mut x = 1;\r\n}\r\n";
+        app.accept_paste(code);
+        assert_eq!(
+            app.input, "short paste",
+            "long paste must not hit the composer"
+        );
+        assert_eq!(
+            app.view_stack.top_kind(),
+            Some(crate::tui::views::ModalKind::PastePrompt)
+        );
+
+        app.resolve_paste("fn main() {}\n", PasteChoice::Inline, Some("rust"));
+        assert_eq!(app.input, "short paste\n```rust\nfn main() {}\n```\n");
+
+        app.resolve_paste("fn main() {}\n", PasteChoice::Attach, Some("rust"));
+        let mention = app.input.lines().last().unwrap().to_string();
+        assert!(
+            mention.starts_with("@.deepseek/pastes/paste-") && mention.ends_with(".rs"),
+            "expected an @mention of the paste file, got: {mention}"
+        );
+        let written = std::fs::read_to_string(tmp.path().join(&mention[1..])).expect("read");
+        assert_eq!(written, "fn main() {}\n");
+    }
+
     #[test]
     fn submit_input_consolidates_oversized_input_into_paste_file() {
         let tmp = tempfile::TempDir::new().expect("tempdir");
@@ -4982,6 +5665,8 @@ mod tests {
                     step: "step 1".to_string(),
                     status: StepStatus::InProgress,
                 }],
+                open_questions: None,
+                decisions: None,
             });
             assert!(!plan.is_empty());
         }
@@ -5484,6 +6169,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn prompt_lint_holds_a_vague_draft_once() {
+        let mut app = App::new(test_options(false), &Config::default());
+        app.use_paste_burst_detection = false;
+        app.prompt_lint = true;
+        app.input = "fix it".to_string();
+        app.cursor_position = "fix it".chars().count();
+
+        assert!(app.handle_composer_enter().is_none());
+        assert_eq!(app.input, "fix it");
+        assert!(app.prompt_hint.is_some());
+
+        assert_eq!(app.handle_composer_enter().as_deref(), Some("fix it"));
+        assert!(app.prompt_hint.is_none());
+
+        app.prompt_lint = false;
+        app.input = "fix it".to_string();
+        assert_eq!(app.handle_composer_enter().as_deref(), Some("fix it"));
+    }
+
     #[test]
     fn enter_with_paste_burst_detection_disabled_submits_normally() {
         // When the user has explicitly turned off paste-burst detection
@@ -6023,4 +6728,35 @@ mod tests {
         assert_eq!(app.input, "café 你好");
         assert_eq!(app.cursor_position, 7);
     }
+
+    #[test]
+    fn model_fallback_bills_usage_to_the_model_that_served_it() {
+        let mut app = App::new(test_options(false), &Config::default());
+        app.model = "deepseek-v4-pro".to_string();
+        assert_eq!(app.turn_pricing_model(), "deepseek-v4-pro");
+
+        let before_switch = Usage {
+            input_tokens: 1_000,
+            output_tokens: 200,
+            ..Usage::default()
+        };
+        app.turn_model_fallback = Some(("deepseek-v4-flash".to_string(), before_switch.clone()));
+        assert_eq!(app.turn_pricing_model(), "deepseek-v4-flash");
+
+        let turn_total = Usage {
+            input_tokens: 1_500,
+            output_tokens: 260,
+            ..Usage::default()
+        };
+        let unbilled = turn_total.saturating_sub(&before_switch);
+        assert_eq!((unbilled.input_tokens, unbilled.output_tokens), (500, 60));
+        let expected =
+            crate::pricing::calculate_turn_cost_estimate_from_usage("deepseek-v4-flash", &unbilled)
+                .expect("flash pricing");
+        let cost = app
+            .accrue_model_usage("deepseek-v4-flash", &unbilled)
+            .expect("cost");
+        assert!((cost.usd - expected.usd).abs() < f64::EPSILON);
+        assert!((app.session.session_cost - expected.usd).abs() < f64::EPSILON);
+    }
 }
@@ -0,0 +1,539 @@
+//! Approvals queue shown when several tool calls wait for approval at once.
+//!
+//! The engine announces every approval a step needs up front, so parallel
+//! calls arrive together. Instead of stacking one takeover card per call,
+//! the second pending request turns the open [`ApprovalView`] into this
+//! list: each entry can be expanded in place, opened as the full card, or
+//! settled with a batch action. Decisions are released to the engine in
+//! the order it asked for them — an entry decided early waits until every
+//! entry above it has an answer.
+//!
+//! [`ApprovalView`]: crate::tui::approval::ApprovalView
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, Widget, Wrap};
+
+use crate::localization::Locale;
+use crate::palette;
+use crate::tui::approval::{ApprovalRequest, ReviewDecision, RiskLevel, ToolCategory};
+use crate::tui::views::{ModalKind, ModalView, ViewAction, ViewEvent};
+
+/// Diff lines shown in an expanded entry; the full card has the rest.
+const DIFF_PREVIEW_LINES: usize = 8;
+
+/// A settled queue entry, ready to be sent to the engine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedDecision {
+    pub tool_id: String,
+    pub tool_name: String,
+    pub decision: ReviewDecision,
+    pub timed_out: bool,
+    pub approval_key: String,
+    pub approval_grouping_key: String,
+}
+
+#[derive(Debug, Clone)]
+struct QueueEntry {
+    request: ApprovalRequest,
+    decision: Option<ReviewDecision>,
+    timed_out: bool,
+    expanded: bool,
+}
+
+impl QueueEntry {
+    fn new(request: ApprovalRequest) -> Self {
+        Self {
+            request,
+            decision: None,
+            timed_out: false,
+            expanded: false,
+        }
+    }
+
+    /// Read-only calls that can be approved without the two-key confirm.
+    fn is_read_only(&self) -> bool {
+        matches!(
+            self.request.category,
+            ToolCategory::Safe | ToolCategory::McpRead
+        ) && self.quick_approvable()
+    }
+
+    fn quick_approvable(&self) -> bool {
+        self.request.risk == RiskLevel::Benign && !self.request.requires_typed_confirm()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ApprovalQueueView {
+    entries: Vec<QueueEntry>,
+    selected: usize,
+    locale: Locale,
+}
+
+impl ApprovalQueueView {
+    #[must_use]
+    pub fn new(requests: Vec<ApprovalRequest>, locale: Locale) -> Self {
+        Self {
+            entries: requests.into_iter().map(QueueEntry::new).collect(),
+            selected: 0,
+            locale,
+        }
+    }
+
+    pub fn push(&mut self, request: ApprovalRequest) {
+        if !self.contains(&request.id) {
+            self.entries.push(QueueEntry::new(request));
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, tool_id: &str) -> bool {
+        self.entries.iter().any(|entry| entry.request.id == tool_id)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries still waiting for an answer.
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.decision.is_none())
+            .count()
+    }
+
+    /// Drop an entry the engine no longer waits on (the call finished or
+    /// the turn ended).
+    pub fn remove(&mut self, tool_id: &str) {
+        self.entries.retain(|entry| entry.request.id != tool_id);
+        self.clamp_selection();
+    }
+
+    /// Record the answer for one entry. Approving for the session also
+    /// settles the other undecided entries in the same approval group.
+    pub fn record(&mut self, tool_id: &str, decision: ReviewDecision, timed_out: bool) {
+        let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.request.id == tool_id)
+        else {
+            return;
+        };
+        entry.decision = Some(decision.clone());
+        entry.timed_out = timed_out;
+        if decision == ReviewDecision::ApprovedForSession {
+            let group = entry.request.approval_grouping_key.clone();
+            for other in &mut self.entries {
+                if other.decision.is_none()
+                    && other.request.approval_grouping_key == group
+                    && !other.request.requires_typed_confirm()
+                {
+                    other.decision = Some(ReviewDecision::Approved);
+                }
+            }
+        }
+    }
+
+    /// Remove and return the decided entries at the front of the queue, in
+    /// the order the engine requested them.
+    pub fn take_ready(&mut self) -> Vec<QueuedDecision> {
+        let ready = self
+            .entries
+            .iter()
+            .take_while(|entry| entry.decision.is_some())
+            .count();
+        let decisions = self
+            .entries
+            .drain(..ready)
+            .filter_map(|entry| {
+                let decision = entry.decision?;
+                Some(QueuedDecision {
+                    tool_id: entry.request.id,
+                    tool_name: entry.request.tool_name,
+                    decision,
+                    timed_out: entry.timed_out,
+                    approval_key: entry.request.approval_key,
+                    approval_grouping_key: entry.request.approval_grouping_key,
+                })
+            })
+            .collect();
+        self.clamp_selection();
+        decisions
+    }
+
+    fn clamp_selection(&mut self) {
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+    }
+
+    /// Move the selection to the next undecided entry after a decision.
+    fn advance(&mut self) {
+        let len = self.entries.len();
+        if let Some(next) = (0..len)
+            .map(|offset| (self.selected + offset) % len)
+            .find(|&idx| self.entries[idx].decision.is_none())
+        {
+            self.selected = next;
+        }
+    }
+
+    /// Apply `decision` to every undecided entry matching `filter`.
+    fn decide_where(
+        &mut self,
+        decision: ReviewDecision,
+        filter: impl Fn(&QueueEntry) -> bool,
+    ) -> ViewAction {
+        let mut changed = false;
+        for entry in &mut self.entries {
+            if entry.decision.is_none() && filter(entry) {
+                entry.decision = Some(decision.clone());
+                changed = true;
+            }
+        }
+        if changed {
+            self.resolve()
+        } else {
+            ViewAction::None
+        }
+    }
+
+    fn decide_selected(&mut self, decision: ReviewDecision) -> ViewAction {
+        let Some(entry) = self.entries.get_mut(self.selected) else {
+            return ViewAction::None;
+        };
+        if entry.decision.is_some() {
+            return ViewAction::None;
+        }
+        entry.decision = Some(decision);
+        self.resolve()
+    }
+
+    fn resolve(&mut self) -> ViewAction {
+        let decisions = self.take_ready();
+        if self.entries.is_empty() {
+            return ViewAction::EmitAndClose(ViewEvent::ApprovalQueueResolved { decisions });
+        }
+        self.advance();
+        if decisions.is_empty() {
+            ViewAction::None
+        } else {
+            ViewAction::Emit(ViewEvent::ApprovalQueueResolved { decisions })
+        }
+    }
+
+    fn open_selected(&self) -> ViewAction {
+        match self.entries.get(self.selected) {
+            Some(entry) if entry.decision.is_none() => {
+                ViewAction::Emit(ViewEvent::ApprovalQueueOpen {
+                    request: Box::new(entry.request.clone()),
+                })
+            }
+            _ => ViewAction::None,
+        }
+    }
+
+    fn entry_lines(&self, idx: usize, entry: &QueueEntry) -> Vec<Line<'static>> {
+        let selected = idx == self.selected;
+        let (mark, mark_style) = match entry.decision {
+            None => ("·", Style::default().fg(palette::TEXT_MUTED)),
+            Some(ReviewDecision::Approved | ReviewDecision::ApprovedForSession) => {
+                ("✓", Style::default().fg(palette::STATUS_SUCCESS))
+            }
+            Some(ReviewDecision::Denied | ReviewDecision::Abort) => {
+                ("✗", Style::default().fg(palette::STATUS_ERROR))
+            }
+        };
+        let row_style = if selected {
+            Style::default()
+                .fg(palette::SELECTION_TEXT)
+                .bg(palette::SELECTION_BG)
+                .bold()
+        } else {
+            Style::default().fg(palette::TEXT_PRIMARY)
+        };
+        let detail_style = if selected {
+            row_style
+        } else {
+            Style::default().fg(palette::TEXT_MUTED)
+        };
+        let prefix = if selected { ">" } else { " " };
+        let description = entry.request.description_for_locale(self.locale);
+        let summary = description.lines().next().unwrap_or_default().to_string();
+        let mut lines = vec![Line::from(vec![
+            Span::styled(format!("{prefix} "), row_style),
+            Span::styled(mark, mark_style),
+            Span::styled(format!(" {}", entry.request.tool_name), row_style),
+            Span::styled(
+                format!(" [{}]", category_label(entry.request.category)),
+                detail_style,
+            ),
+            Span::styled(format!("  {summary}"), detail_style),
+        ])];
+
+        if entry.expanded {
+            let muted = Style::default().fg(palette::TEXT_MUTED);
+            for line in description.lines().skip(1) {
+                lines.push(Line::from(Span::styled(format!("    {line}"), muted)));
+            }
+            for impact in entry.request.impacts_for_locale(self.locale) {
+                lines.push(Line::from(Span::styled(format!("    • {impact}"), muted)));
+            }
+            lines.push(Line::from(Span::styled(
+                format!("    {}", entry.request.params_display()),
+                muted,
+            )));
+            if let Some(diff) = entry.request.diff_preview.as_deref() {
+                for line in diff.lines().take(DIFF_PREVIEW_LINES) {
+                    let style = if line.starts_with('+') && !line.starts_with("+++") {
+                        Style::default().fg(palette::STATUS_SUCCESS)
+                    } else if line.starts_with('-') && !line.starts_with("---") {
+                        Style::default().fg(palette::STATUS_ERROR)
+                    } else {
+                        muted
+                    };
+                    lines.push(Line::from(Span::styled(format!("    {line}"), style)));
+                }
+                if diff.lines().count() > DIFF_PREVIEW_LINES {
+                    lines.push(Line::from(Span::styled(
+                        "    … Enter opens the full diff",
+                        muted,
+                    )));
+                }
+            }
+        }
+        lines
+    }
+}
+
+fn category_label(category: ToolCategory) -> &'static str {
+    match category {
+        ToolCategory::Safe => "read",
+        ToolCategory::FileWrite => "write",
+        ToolCategory::Shell => "shell",
+        ToolCategory::Network => "network",
+        ToolCategory::McpRead => "mcp read",
+        ToolCategory::McpAction => "mcp",
+        ToolCategory::Unknown => "other",
+    }
+}
+
+impl ModalView for ApprovalQueueView {
+    fn kind(&self) -> ModalKind {
+        ModalKind::ApprovalQueue
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                ViewAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.entries.len().saturating_sub(1));
+                ViewAction::None
+            }
+            KeyCode::Char(' ') | KeyCode::Tab => {
+                if let Some(entry) = self.entries.get_mut(self.selected) {
+                    entry.expanded = !entry.expanded;
+                }
+                ViewAction::None
+            }
+            KeyCode::Enter | KeyCode::Char('o') => self.open_selected(),
+            KeyCode::Char('y') => {
+                // Anything that needs the staged or typed confirm goes
+                // through the full card.
+                match self.entries.get(self.selected) {
+                    Some(entry) if entry.quick_approvable() => {
+                        self.decide_selected(ReviewDecision::Approved)
+                    }
+                    _ => self.open_selected(),
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Char('d') => self.decide_selected(ReviewDecision::Denied),
+            KeyCode::Char('r') => {
+                self.decide_where(ReviewDecision::Approved, QueueEntry::is_read_only)
+            }
+            KeyCode::Char('s') => self.decide_where(ReviewDecision::Denied, |entry| {
+                entry.request.category == ToolCategory::Shell
+            }),
+            KeyCode::Esc => self.decide_where(ReviewDecision::Denied, |_| true),
+            _ => ViewAction::None,
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let mut lines: Vec<Line> = Vec::new();
+        let mut selected_line = 0;
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if idx == self.selected {
+                selected_line = lines.len();
+            }
+            lines.extend(self.entry_lines(idx, entry));
+        }
+
+        let key = |label: &'static str| {
+            Span::styled(label, Style::default().fg(palette::DEEPSEEK_SKY).bold())
+        };
+        let hint =
+            |label: &'static str| Span::styled(label, Style::default().fg(palette::TEXT_MUTED));
+        let footer = Line::from(vec![
+            key(" y/n"),
+            hint(" approve/deny  "),
+            key("Space"),
+            hint(" details  "),
+            key("Enter"),
+            hint(" card  "),
+            key("r"),
+            hint(" approve read-only  "),
+            key("s"),
+            hint(" deny shell  "),
+            key("Esc"),
+            hint(" deny rest "),
+        ]);
+
+        let height = u16::try_from(lines.len() + 4)
+            .unwrap_or(u16::MAX)
+            .min(area.height);
+        let width = area.width.saturating_sub(4).min(100);
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+        // Keep the selected row on screen when the list outgrows the popup.
+        let visible = usize::from(height.saturating_sub(4)).max(1);
+        let scroll = u16::try_from(selected_line.saturating_sub(visible - 1)).unwrap_or(0);
+        Clear.render(popup_area, buf);
+        Paragraph::new(lines)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0))
+            .block(
+                Block::default()
+                    .title(Line::from(Span::styled(
+                        format!(" Approvals ({} pending) ", self.pending()),
+                        Style::default().fg(palette::STATUS_WARNING).bold(),
+                    )))
+                    .title_bottom(footer)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(palette::BORDER_COLOR))
+                    .padding(Padding::new(1, 1, 1, 1)),
+            )
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use serde_json::json;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn request(id: &str, tool: &str, params: serde_json::Value) -> ApprovalRequest {
+        ApprovalRequest::new(id, tool, tool, &params, &format!("key-{id}"))
+    }
+
+    fn resolved(action: ViewAction) -> Vec<(String, ReviewDecision)> {
+        match action {
+            ViewAction::Emit(ViewEvent::ApprovalQueueResolved { decisions })
+            | ViewAction::EmitAndClose(ViewEvent::ApprovalQueueResolved { decisions }) => decisions
+                .into_iter()
+                .map(|decision| (decision.tool_id, decision.decision))
+                .collect(),
+            other => panic!("expected resolved decisions, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decisions_are_released_in_request_order() {
+        let mut view = ApprovalQueueView::new(
+            vec![
+                request("a", "exec_shell", json!({"command": "rm -rf build"})),
+                request("b", "read_file", json!({"path": "README.md"})),
+            ],
+            Locale::En,
+        );
+        view.push(request("c", "exec_shell", json!({"command": "ls"})));
+
+        // Approving the read-only call settles `b`, but `a` is still open,
+        // so nothing is released yet.
+        assert!(matches!(
+            view.handle_key(key(KeyCode::Char('r'))),
+            ViewAction::None
+        ));
+        assert_eq!(view.pending(), 2);
+
+        assert_eq!(
+            resolved(view.handle_key(key(KeyCode::Char('s')))),
+            vec![
+                ("a".to_string(), ReviewDecision::Denied),
+                ("b".to_string(), ReviewDecision::Approved),
+                ("c".to_string(), ReviewDecision::Denied),
+            ]
+        );
+        assert!(view.is_empty());
+    }
+
+    #[test]
+    fn destructive_entries_open_the_full_card_and_session_approval_covers_the_group() {
+        let mut view = ApprovalQueueView::new(
+            vec![
+                request("a", "write_file", json!({"path": "a.txt", "content": "x"})),
+                request("b", "read_file", json!({"path": "a.txt"})),
+                request("c", "read_file", json!({"path": "a.txt"})),
+            ],
+            Locale::En,
+        );
+
+        assert!(matches!(
+            view.handle_key(key(KeyCode::Char('y'))),
+            ViewAction::Emit(ViewEvent::ApprovalQueueOpen { request }) if request.id == "a"
+        ));
+
+        view.record("b", ReviewDecision::ApprovedForSession, false);
+        assert!(view.take_ready().is_empty());
+        assert_eq!(view.pending(), 1);
+
+        view.record("a", ReviewDecision::Denied, false);
+        let ready = view.take_ready();
+        assert_eq!(
+            ready
+                .iter()
+                .map(|decision| decision.tool_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(ready[2].decision, ReviewDecision::Approved);
+    }
+
+    #[test]
+    fn escape_denies_everything_left_and_closes() {
+        let mut view = ApprovalQueueView::new(
+            vec![
+                request("a", "exec_shell", json!({"command": "make"})),
+                request("b", "write_file", json!({"path": "a.txt", "content": "x"})),
+            ],
+            Locale::En,
+        );
+        view.remove("a");
+        let action = view.handle_key(key(KeyCode::Esc));
+        assert!(matches!(action, ViewAction::EmitAndClose(_)));
+        assert_eq!(
+            resolved(action),
+            vec![("b".to_string(), ReviewDecision::Denied)]
+        );
+    }
+}
@@ -0,0 +1,401 @@
+//! Inline ghost-text autocomplete for the composer.
+//!
+//! While the cursor sits at the end of the draft, the composer renders a
+//! dimmed suffix that would complete what the user is typing. `Right` or
+//! `Tab` accepts it. Suggestions come from a small ordered set of
+//! providers:
+//!
+//! - `!<partial>` completes a tool name from the built-in tool catalog;
+//! - `@<partial>` completes the file path currently selected in the
+//!   mention popup, so the ghost and the popup never disagree;
+//! - anything else completes from the persisted input history
+//!   (`~/.deepseek/composer_history.txt`), newest first.
+//!
+//! Slash commands keep their own popup (`tui::slash_menu`) and never get a
+//! ghost suffix.
+
+use std::sync::OnceLock;
+
+use crate::tools::registry::ToolRegistryBuilder;
+
+use super::app::{App, VimMode, looks_like_slash_command_input};
+use super::file_mention::{apply_mention_menu_selection, partial_file_mention_at_cursor};
+
+/// Minimum number of non-whitespace characters before history suggestions
+/// kick in. One letter matches nearly every prompt and just adds noise.
+const HISTORY_MIN_CHARS: usize = 2;
+
+/// Which provider produced a ghost suggestion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GhostSource {
+    History,
+    FilePath,
+    ToolName,
+}
+
+/// A completion suffix to render after the cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostSuggestion {
+    pub source: GhostSource,
+    /// Text appended to the input when the suggestion is accepted.
+    pub suffix: String,
+}
+
+impl GhostSuggestion {
+    /// The single-line portion of the suffix shown in the composer. Multi-line
+    /// history entries are cut at the first newline and marked with `…`.
+    #[must_use]
+    pub fn display_text(&self) -> String {
+        match self.suffix.split_once('\n') {
+            Some((first, _)) => format!("{first}…"),
+            None => self.suffix.clone(),
+        }
+    }
+}
+
+/// Everything a provider may look at. Kept free of `App` so providers are
+/// cheap to unit-test.
+pub struct AutocompleteContext<'a> {
+    /// Full composer input; the cursor is known to be at its end.
+    pub input: &'a str,
+    /// Submitted prompts, oldest first.
+    pub history: &'a [String],
+    /// Entries of the visible `@` mention popup.
+    pub mention_entries: &'a [String],
+    /// Selected index within `mention_entries`.
+    pub mention_selected: usize,
+}
+
+/// A source of ghost-text completions.
+pub trait AutocompleteProvider {
+    fn source(&self) -> GhostSource;
+
+    /// Return the suffix that completes `ctx.input`, or `None` when this
+    /// provider has nothing to offer.
+    fn suggest(&self, ctx: &AutocompleteContext<'_>) -> Option<String>;
+}
+
+/// Completes `!<partial>` tokens with tool names.
+pub struct ToolNameProvider<'a> {
+    names: Option<&'a [String]>,
+}
+
+#[cfg(test)]
+impl<'a> ToolNameProvider<'a> {
+    /// Provider over an explicit tool list (sorted or not).
+    #[must_use]
+    pub fn with_names(names: &'a [String]) -> Self {
+        Self { names: Some(names) }
+    }
+}
+
+impl ToolNameProvider<'static> {
+    /// Provider over the built-in tool catalog. The catalog is only built
+    /// the first time a `!` token is completed.
+    #[must_use]
+    pub fn builtin() -> Self {
+        Self { names: None }
+    }
+}
+
+impl AutocompleteProvider for ToolNameProvider<'_> {
+    fn source(&self) -> GhostSource {
+        GhostSource::ToolName
+    }
+
+    fn suggest(&self, ctx: &AutocompleteContext<'_>) -> Option<String> {
+        let token = trailing_token(ctx.input);
+        let partial = token.strip_prefix('!')?;
+        if partial.is_empty() {
+            return None;
+        }
+        let names = self.names.unwrap_or_else(|| builtin_tool_names());
+        names
+            .iter()
+            .filter(|name| name.len() > partial.len() && name.starts_with(partial))
+            .min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+            .map(|name| name[partial.len()..].to_string())
+    }
+}
+
+/// Completes `@<partial>` tokens with the selected mention-popup entry.
+pub struct FilePathProvider;
+
+impl AutocompleteProvider for FilePathProvider {
+    fn source(&self) -> GhostSource {
+        GhostSource::FilePath
+    }
+
+    fn suggest(&self, ctx: &AutocompleteContext<'_>) -> Option<String> {
+        if ctx.mention_entries.is_empty() {
+            return None;
+        }
+        let (_, partial) = partial_file_mention_at_cursor(ctx.input, ctx.input.chars().count())?;
+        let selected = ctx
+            .mention_selected
+            .min(ctx.mention_entries.len().saturating_sub(1));
+        let entry = &ctx.mention_entries[selected];
+        (entry.len() > partial.len() && entry.starts_with(&partial))
+            .then(|| entry[partial.len()..].to_string())
+    }
+}
+
+/// Completes the whole draft from previously submitted prompts.
+pub struct HistoryProvider;
+
+impl AutocompleteProvider for HistoryProvider {
+    fn source(&self) -> GhostSource {
+        GhostSource::History
+    }
+
+    fn suggest(&self, ctx: &AutocompleteContext<'_>) -> Option<String> {
+        let typed = ctx.input.chars().filter(|c| !c.is_whitespace()).count();
+        if typed < HISTORY_MIN_CHARS {
+            return None;
+        }
+        // Newest exact-case prefix wins; otherwise fall back to the newest
+        // case-insensitive prefix so "Fix the" still finds "fix the tests".
+        let exact = ctx.history.iter().rev().find_map(|entry| {
+            entry
+                .strip_prefix(ctx.input)
+                .filter(|rest| !rest.is_empty())
+                .map(str::to_string)
+        });
+        exact.or_else(|| {
+            ctx.history
+                .iter()
+                .rev()
+                .find_map(|entry| suffix_after_caseless_prefix(entry, ctx.input))
+        })
+    }
+}
+
+/// Compute the ghost suggestion for the current composer state, if any.
+///
+/// Suggestions only appear while the cursor is at the end of a non-slash
+/// draft, outside history search and vim normal/visual mode.
+#[must_use]
+pub fn ghost_suggestion(app: &App, mention_entries: &[String]) -> Option<GhostSuggestion> {
+    if app.input.is_empty()
+        || app.cursor_position != app.input.chars().count()
+        || app.is_history_search_active()
+        || app.history_index.is_some()
+        || looks_like_slash_command_input(&app.input)
+        || (app.composer.vim_enabled && app.composer.vim_mode != VimMode::Insert)
+    {
+        return None;
+    }
+    let ctx = AutocompleteContext {
+        input: &app.input,
+        history: &app.input_history,
+        mention_entries,
+        mention_selected: app.mention_menu_selected,
+    };
+    suggest_with(
+        &[
+            &ToolNameProvider::builtin(),
+            &FilePathProvider,
+            &HistoryProvider,
+        ],
+        &ctx,
+    )
+}
+
+/// Run `providers` in order and return the first suggestion.
+#[must_use]
+pub fn suggest_with(
+    providers: &[&dyn AutocompleteProvider],
+    ctx: &AutocompleteContext<'_>,
+) -> Option<GhostSuggestion> {
+    providers.iter().find_map(|provider| {
+        provider.suggest(ctx).map(|suffix| GhostSuggestion {
+            source: provider.source(),
+            suffix,
+        })
+    })
+}
+
+/// Accept the current ghost suggestion, if one is showing. Returns `true`
+/// when the input changed so the key handler can stop there.
+pub fn accept_ghost_suggestion(app: &mut App, mention_entries: &[String]) -> bool {
+    let Some(ghost) = ghost_suggestion(app, mention_entries) else {
+        return false;
+    };
+    if ghost.source == GhostSource::FilePath {
+        // Route through the popup path so frecency and the status line
+        // behave exactly as if the entry had been picked from the menu.
+        return apply_mention_menu_selection(app, mention_entries);
+    }
+    app.insert_str(&ghost.suffix);
+    true
+}
+
+fn builtin_tool_names() -> &'static [String] {
+    static NAMES: OnceLock<Vec<String>> = OnceLock::new();
+    NAMES.get_or_init(|| {
+        let mut names = ToolRegistryBuilder::new()
+            .with_file_tools()
+            .with_shell_tools()
+            .with_search_tools()
+            .with_git_tools()
+            .with_git_history_tools()
+            .with_diagnostics_tool()
+            .with_skill_tools()
+            .with_project_tools()
+            .with_test_runner_tool()
+            .with_validation_tools()
+            .with_tool_result_retrieval_tool()
+            .with_runtime_task_tools()
+            .with_web_tools()
+            .with_user_input_tool()
+            .with_patch_tools()
+            .with_revert_turn_tool()
+            .with_handle_tools()
+            .with_recall_archive_tool()
+            .with_recall_history_tool()
+            .with_note_tool()
+            .with_remember_tool()
+            .with_notify_tool()
+            .tool_names();
+        names.sort();
+        names.dedup();
+        names
+    })
+}
+
+/// The whitespace-delimited token that ends `input`.
+fn trailing_token(input: &str) -> &str {
+    input.rsplit(char::is_whitespace).next().unwrap_or_default()
+}
+
+/// If `entry` starts with `prefix` ignoring case, return the rest of `entry`.
+fn suffix_after_caseless_prefix(entry: &str, prefix: &str) -> Option<String> {
+    let mut entry_chars = entry.char_indices();
+    for expected in prefix.chars() {
+        let (_, actual) = entry_chars.next()?;
+        if !actual.to_lowercase().eq(expected.to_lowercase()) {
+            return None;
+        }
+    }
+    let rest_start = entry_chars.next().map(|(idx, _)| idx)?;
+    Some(entry[rest_start..].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx<'a>(
+        input: &'a str,
+        history: &'a [String],
+        mention_entries: &'a [String],
+    ) -> AutocompleteContext<'a> {
+        AutocompleteContext {
+            input,
+            history,
+            mention_entries,
+            mention_selected: 0,
+        }
+    }
+
+    #[test]
+    fn history_provider_prefers_newest_exact_prefix_then_caseless() {
+        let history = vec![
+            "fix the failing tests".to_string(),
+            "fix the flaky test in ci".to_string(),
+            "Explain the parser".to_string(),
+        ];
+        assert_eq!(
+            HistoryProvider.suggest(&ctx("fix the f", &history, &[])),
+            Some("laky test in ci".to_string())
+        );
+        assert_eq!(
+            HistoryProvider.suggest(&ctx("explain", &history, &[])),
+            Some(" the parser".to_string())
+        );
+        assert_eq!(HistoryProvider.suggest(&ctx("f", &history, &[])), None);
+        assert_eq!(
+            HistoryProvider.suggest(&ctx("Explain the parser", &history, &[])),
+            None
+        );
+    }
+
+    #[test]
+    fn tool_provider_completes_bang_token_with_shortest_match() {
+        let names = vec![
+            "grep_files".to_string(),
+            "git_status".to_string(),
+            "git_diff".to_string(),
+        ];
+        let provider = ToolNameProvider::with_names(&names);
+        assert_eq!(
+            provider.suggest(&ctx("use !gi", &[], &[])),
+            Some("t_diff".to_string())
+        );
+        assert_eq!(
+            provider.suggest(&ctx("!gr", &[], &[])),
+            Some("ep_files".to_string())
+        );
+        assert_eq!(provider.suggest(&ctx("hello gi", &[], &[])), None);
+        assert_eq!(provider.suggest(&ctx("!", &[], &[])), None);
+    }
+
+    #[test]
+    fn builtin_tool_catalog_includes_core_tools() {
+        let names = builtin_tool_names();
+        assert!(names.iter().any(|name| name == "read_file"));
+        assert!(names.iter().any(|name| name == "exec_shell"));
+        assert_eq!(
+            ToolNameProvider::builtin().suggest(&ctx("!read_fi", &[], &[])),
+            Some("le".to_string())
+        );
+    }
+
+    #[test]
+    fn file_provider_follows_selected_mention_entry() {
+        let entries = vec!["src/main.rs".to_string(), "src/lib.rs".to_string()];
+        let mut context = ctx("open @src/", &[], &entries);
+        assert_eq!(
+            FilePathProvider.suggest(&context),
+            Some("main.rs".to_string())
+        );
+        context.mention_selected = 1;
+        assert_eq!(
+            FilePathProvider.suggest(&context),
+            Some("lib.rs".to_string())
+        );
+        assert_eq!(FilePathProvider.suggest(&ctx("open @src/", &[], &[])), None);
+    }
+
+    #[test]
+    fn providers_run_in_order_and_tag_their_source() {
+        let history = vec!["!git_status please".to_string()];
+        let names = vec!["git_status".to_string()];
+        let tool = ToolNameProvider::with_names(&names);
+        let suggestion = suggest_with(&[&tool, &HistoryProvider], &ctx("!git", &history, &[]));
+        assert_eq!(
+            suggestion,
+            Some(GhostSuggestion {
+                source: GhostSource::ToolName,
+                suffix: "_status".to_string(),
+            })
+        );
+        let suggestion = suggest_with(
+            &[&tool, &HistoryProvider],
+            &ctx("!git_status", &history, &[]),
+        );
+        assert_eq!(
+            suggestion.map(|ghost| ghost.source),
+            Some(GhostSource::History)
+        );
+    }
+
+    #[test]
+    fn display_text_cuts_multiline_suffixes() {
+        let ghost = GhostSuggestion {
+            source: GhostSource::History,
+            suffix: " first line\nsecond".to_string(),
+        };
+        assert_eq!(ghost.display_text(), " first line…");
+    }
+}
@@ -0,0 +1,414 @@
+//! Transcript bookmarks: `m` marks the selected cell, `/bookmarks` lists the
+//! marks with previews and jumps back to one.
+//!
+//! Bookmarks are keyed by history cell index and saved with the session
+//! together with a preview of the cell. Resuming rebuilds the transcript
+//! from the saved messages, so indices can shift; each saved bookmark is
+//! re-attached to the cell with the same preview nearest its old index and
+//! dropped when no cell matches.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Widget},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::palette;
+use crate::session_manager::SessionBookmark;
+use crate::tui::app::App;
+use crate::tui::history::HistoryCell;
+use crate::tui::scrolling::TranscriptScroll;
+use crate::tui::views::{ModalKind, ModalView, ViewAction, ViewEvent};
+
+/// Render width used for previews, wide enough that wrapping rarely
+/// splits the first line.
+const PREVIEW_RENDER_WIDTH: u16 = 200;
+const PREVIEW_CHARS: usize = 80;
+
+/// Short kind label shown next to a bookmark.
+#[must_use]
+pub fn cell_label(cell: &HistoryCell) -> &'static str {
+    match cell {
+        HistoryCell::User { .. } => "you",
+        HistoryCell::Assistant { .. } => "assistant",
+        HistoryCell::System { .. } => "note",
+        HistoryCell::Error { .. } => "error",
+        HistoryCell::Thinking { .. } => "thinking",
+        HistoryCell::ArchivedContext { .. } => "archived",
+        HistoryCell::Tool(_) => "tool",
+        HistoryCell::SubAgent(_) => "agent",
+    }
+}
+
+/// First non-blank rendered line of `cell`, trimmed and shortened.
+#[must_use]
+pub fn cell_preview(cell: &HistoryCell) -> String {
+    let text = crate::tui::ui_text::history_cell_to_text(cell, PREVIEW_RENDER_WIDTH);
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() <= PREVIEW_CHARS {
+        return line.to_string();
+    }
+    let mut preview: String = line.chars().take(PREVIEW_CHARS - 1).collect();
+    preview.push('…');
+    preview
+}
+
+/// Bookmark `cell_index`, or remove its bookmark. Returns `Some(true)` when
+/// added, `Some(false)` when removed, and `None` when the index is not a
+/// finished history cell.
+pub fn toggle(app: &mut App, cell_index: usize) -> Option<bool> {
+    if let Some(pos) = app
+        .bookmarks
+        .iter()
+        .position(|bookmark| bookmark.cell_index == cell_index)
+    {
+        app.bookmarks.remove(pos);
+        app.needs_redraw = true;
+        return Some(false);
+    }
+    let preview = cell_preview(app.history.get(cell_index)?);
+    let pos = app
+        .bookmarks
+        .partition_point(|bookmark| bookmark.cell_index < cell_index);
+    app.bookmarks.insert(
+        pos,
+        SessionBookmark {
+            cell_index,
+            preview,
+        },
+    );
+    app.needs_redraw = true;
+    Some(true)
+}
+
+/// Re-attach saved bookmarks to a rebuilt transcript: each goes to the cell
+/// with the same preview nearest its saved index. Bookmarks with no match
+/// are dropped.
+#[must_use]
+pub fn restore(history: &[HistoryCell], saved: &[SessionBookmark]) -> Vec<SessionBookmark> {
+    let previews: Vec<String> = history.iter().map(cell_preview).collect();
+    let mut restored: Vec<SessionBookmark> = Vec::new();
+    for bookmark in saved {
+        let found = previews
+            .iter()
+            .enumerate()
+            .filter(|(index, preview)| {
+                **preview == bookmark.preview
+                    && !restored.iter().any(|taken| taken.cell_index == *index)
+            })
+            .min_by_key(|(index, _)| index.abs_diff(bookmark.cell_index));
+        if let Some((cell_index, _)) = found {
+            restored.push(SessionBookmark {
+                cell_index,
+                preview: bookmark.preview.clone(),
+            });
+        }
+    }
+    restored.sort_by_key(|bookmark| bookmark.cell_index);
+    restored
+}
+
+/// History index of the cell under the transcript selection.
+#[must_use]
+pub fn selected_cell_index(app: &App) -> Option<usize> {
+    let (start, _) = app.viewport.transcript_selection.ordered_endpoints()?;
+    let (filtered, _) = app
+        .viewport
+        .transcript_cache
+        .line_meta()
+        .get(start.line_index)?
+        .cell_line()?;
+    Some(crate::tui::mouse_ui::unfiltered_cell_index(app, filtered))
+}
+
+/// Scroll the transcript so the bookmarked cell is at the top. Returns
+/// false when the cell is hidden or no longer rendered.
+pub fn jump_to(app: &mut App, cell_index: usize) -> bool {
+    let filtered = if app.collapsed_cell_map.is_empty() {
+        Some(cell_index)
+    } else {
+        app.collapsed_cell_map
+            .iter()
+            .position(|&original| original == cell_index)
+    };
+    let Some(filtered) = filtered else {
+        return false;
+    };
+    let line_meta = app.viewport.transcript_cache.line_meta();
+    let Some(line) = line_meta
+        .iter()
+        .position(|meta| meta.cell_line().is_some_and(|(idx, _)| idx == filtered))
+    else {
+        return false;
+    };
+    let Some(anchor) = TranscriptScroll::anchor_for(line_meta, line) else {
+        return false;
+    };
+    app.viewport.transcript_scroll = anchor;
+    app.viewport.pending_scroll_delta = 0;
+    app.viewport.transcript_selection.clear();
+    app.needs_redraw = true;
+    true
+}
+
+#[derive(Debug, Clone)]
+struct BookmarkEntry {
+    cell_index: usize,
+    label: &'static str,
+    preview: String,
+}
+
+/// `/bookmarks` list: Enter jumps, `d` removes.
+pub struct BookmarksView {
+    entries: Vec<BookmarkEntry>,
+    selected: usize,
+}
+
+impl BookmarksView {
+    #[must_use]
+    pub fn new(app: &App) -> Self {
+        let entries = app
+            .bookmarks
+            .iter()
+            .map(|bookmark| BookmarkEntry {
+                cell_index: bookmark.cell_index,
+                label: app.history.get(bookmark.cell_index).map_or("", cell_label),
+                preview: bookmark.preview.clone(),
+            })
+            .collect();
+        Self {
+            entries,
+            selected: 0,
+        }
+    }
+
+    fn remove_selected(&mut self) -> ViewAction {
+        if self.selected >= self.entries.len() {
+            return ViewAction::None;
+        }
+        let entry = self.entries.remove(self.selected);
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+        let event = ViewEvent::BookmarkRemoved {
+            cell_index: entry.cell_index,
+        };
+        if self.entries.is_empty() {
+            ViewAction::EmitAndClose(event)
+        } else {
+            ViewAction::Emit(event)
+        }
+    }
+}
+
+impl ModalView for BookmarksView {
+    fn kind(&self) -> ModalKind {
+        ModalKind::Bookmarks
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+        match key.code {
+            KeyCode::Esc => ViewAction::Close,
+            KeyCode::Enter => match self.entries.get(self.selected) {
+                Some(entry) => ViewAction::EmitAndClose(ViewEvent::BookmarkJump {
+                    cell_index: entry.cell_index,
+                }),
+                None => ViewAction::Close,
+            },
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                ViewAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected + 1 < self.entries.len() {
+                    self.selected += 1;
+                }
+                ViewAction::None
+            }
+            KeyCode::Char('d') | KeyCode::Delete => self.remove_selected(),
+            _ => ViewAction::None,
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let popup_width = 96.min(area.width.saturating_sub(4)).max(44);
+        let rows = u16::try_from(self.entries.len().max(1)).unwrap_or(u16::MAX);
+        let popup_height = rows
+            .saturating_add(4)
+            .min(area.height.saturating_sub(4))
+            .max(6);
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(popup_width)) / 2,
+            y: area.y + (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(Line::from(Span::styled(
+                " Bookmarks ",
+                Style::default()
+                    .fg(palette::DEEPSEEK_SKY)
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .title_bottom(Line::from(vec![
+                Span::styled(" Enter ", Style::default().fg(palette::TEXT_MUTED)),
+                Span::raw("jump "),
+                Span::styled(" d ", Style::default().fg(palette::TEXT_MUTED)),
+                Span::raw("remove "),
+                Span::styled(" Esc ", Style::default().fg(palette::TEXT_MUTED)),
+                Span::raw("close "),
+            ]))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(palette::BORDER_COLOR))
+            .style(Style::default().bg(palette::DEEPSEEK_INK))
+            .padding(Padding::horizontal(1));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        if self.entries.is_empty() {
+            Paragraph::new(Line::from(Span::styled(
+                "No bookmarks. Select a message and press m to add one.",
+                Style::default().fg(palette::TEXT_MUTED),
+            )))
+            .render(inner, buf);
+            return;
+        }
+
+        // Keep the selection in view when the list is taller than the popup.
+        let visible = usize::from(inner.height).max(1);
+        let first = self.selected.saturating_sub(visible - 1);
+        let lines: Vec<Line> = self
+            .entries
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .map(|(idx, entry)| {
+                let is_selected = idx == self.selected;
+                let (row_style, muted_style) = if is_selected {
+                    let style = Style::default()
+                        .fg(palette::SELECTION_TEXT)
+                        .bg(palette::SELECTION_BG);
+                    (style.add_modifier(Modifier::BOLD), style)
+                } else {
+                    (
+                        Style::default().fg(palette::TEXT_PRIMARY),
+                        Style::default().fg(palette::TEXT_MUTED),
+                    )
+                };
+                let pointer = if is_selected { ">" } else { " " };
+                let prefix = format!(" {pointer} {:>2}. ", idx + 1);
+                let label = format!("{:<10}", entry.label);
+                let room = usize::from(inner.width).saturating_sub(prefix.width() + label.width());
+                let preview: String = entry.preview.chars().take(room).collect();
+                Line::from(vec![
+                    Span::styled(prefix, row_style),
+                    Span::styled(label, muted_style),
+                    Span::styled(preview, row_style),
+                ])
+            })
+            .collect();
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn user(text: &str) -> HistoryCell {
+        HistoryCell::User {
+            content: text.to_string(),
+        }
+    }
+
+    fn assistant(text: &str) -> HistoryCell {
+        HistoryCell::Assistant {
+            content: text.to_string(),
+            streaming: false,
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn restore_reattaches_bookmarks_to_the_nearest_matching_cell() {
+        let history = vec![
+            user("fix the parser"),
+            assistant("done"),
+            user("now add tests"),
+            assistant("done"),
+        ];
+        let done = cell_preview(&history[1]);
+        let tests = cell_preview(&history[2]);
+        assert!(tests.contains("now add tests"));
+        let saved = vec![
+            // Saved live with an extra status cell above; resumed without it.
+            SessionBookmark {
+                cell_index: 4,
+                preview: done.clone(),
+            },
+            SessionBookmark {
+                cell_index: 3,
+                preview: tests.clone(),
+            },
+            SessionBookmark {
+                cell_index: 0,
+                preview: "a note that was never saved".to_string(),
+            },
+        ];
+        let restored = restore(&history, &saved);
+        let indices: Vec<usize> = restored.iter().map(|b| b.cell_index).collect();
+        assert_eq!(indices, [2, 3]);
+        assert_eq!(restored[1].preview, done);
+    }
+
+    #[test]
+    fn view_jumps_to_and_removes_bookmarks() {
+        let mut view = BookmarksView {
+            entries: vec![
+                BookmarkEntry {
+                    cell_index: 2,
+                    label: "you",
+                    preview: "now add tests".to_string(),
+                },
+                BookmarkEntry {
+                    cell_index: 7,
+                    label: "tool",
+                    preview: "cargo test".to_string(),
+                },
+            ],
+            selected: 0,
+        };
+        view.handle_key(key(KeyCode::Down));
+        assert!(matches!(
+            view.handle_key(key(KeyCode::Char('d'))),
+            ViewAction::Emit(ViewEvent::BookmarkRemoved { cell_index: 7 })
+        ));
+        assert!(matches!(
+            view.handle_key(key(KeyCode::Enter)),
+            ViewAction::EmitAndClose(ViewEvent::BookmarkJump { cell_index: 2 })
+        ));
+        assert!(matches!(
+            view.handle_key(key(KeyCode::Delete)),
+            ViewAction::EmitAndClose(ViewEvent::BookmarkRemoved { cell_index: 2 })
+        ));
+    }
+}
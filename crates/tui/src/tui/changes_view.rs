@@ -0,0 +1,285 @@
+//! `/changes` modal: files the agent changed this session, diffed against
+//! their session-start contents.
+//!
+//! Rows come from the shared [`SessionChanges`] registry. Opening a diff or
+//! reverting a file is emitted as a [`SessionChangesAction`]; the host runs
+//! it and pushes fresh rows back through [`ChangesView::set_changes`].
+//!
+//! [`SessionChanges`]: crate::tools::session_changes::SessionChanges
+
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::palette;
+use crate::tools::session_changes::{ChangeKind, SessionFileChange};
+use crate::tui::views::{ModalKind, ModalView, SessionChangesAction, ViewAction, ViewEvent};
+use crate::utils::{truncate_start_with_ellipsis, truncate_with_ellipsis};
+
+pub struct ChangesView {
+    workspace: PathBuf,
+    changes: Vec<SessionFileChange>,
+    selected: usize,
+    notice: Option<String>,
+}
+
+impl ChangesView {
+    #[must_use]
+    pub fn new(workspace: PathBuf, changes: Vec<SessionFileChange>) -> Self {
+        Self {
+            workspace,
+            changes,
+            selected: 0,
+            notice: None,
+        }
+    }
+
+    /// Replace the rows, keeping the cursor in range.
+    pub fn set_changes(&mut self, changes: Vec<SessionFileChange>) {
+        self.changes = changes;
+        self.selected = self.selected.min(self.changes.len().saturating_sub(1));
+    }
+
+    /// One-line result of the last action, shown above the list.
+    pub fn set_notice(&mut self, notice: impl Into<String>) {
+        self.notice = Some(notice.into());
+    }
+
+    fn selected_change(&self) -> Option<&SessionFileChange> {
+        self.changes.get(self.selected)
+    }
+
+    fn emit_for_selected(
+        &self,
+        build: impl FnOnce(&SessionFileChange) -> Option<SessionChangesAction>,
+    ) -> ViewAction {
+        match self.selected_change().and_then(build) {
+            Some(action) => ViewAction::Emit(ViewEvent::SessionChangesRequested { action }),
+            None => ViewAction::None,
+        }
+    }
+
+    fn row(&self, idx: usize, change: &SessionFileChange, width: usize) -> Line<'static> {
+        let is_selected = idx == self.selected;
+        let kind_color = match change.kind {
+            ChangeKind::Created => palette::STATUS_SUCCESS,
+            ChangeKind::Modified => palette::STATUS_WARNING,
+            ChangeKind::Deleted => palette::DEEPSEEK_RED,
+        };
+        let base = if is_selected {
+            Style::default()
+                .fg(palette::SELECTION_TEXT)
+                .bg(palette::SELECTION_BG)
+        } else {
+            Style::default().fg(palette::TEXT_PRIMARY)
+        };
+        let kind_style = if is_selected {
+            base.add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(kind_color)
+        };
+
+        let pointer = if is_selected { ">" } else { " " };
+        let lead = format!(" {pointer} {:<9}", change.kind.label());
+        let counts = format!(
+            " {:>6} {:>6} ",
+            format!("+{}", change.added_lines),
+            format!("-{}", change.removed_lines)
+        );
+        let used = lead.chars().count() + counts.chars().count();
+        let path = display_path(&self.workspace, &change.path);
+        let path = truncate_start_with_ellipsis(&path, width.saturating_sub(used), "…");
+
+        Line::from(vec![
+            Span::styled(lead, kind_style),
+            Span::styled(counts, base.fg(palette::TEXT_MUTED)),
+            Span::styled(path, base),
+        ])
+    }
+}
+
+impl ModalView for ChangesView {
+    fn kind(&self) -> ModalKind {
+        ModalKind::SessionChanges
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return ViewAction::None;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => ViewAction::Close,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                ViewAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected + 1 < self.changes.len() {
+                    self.selected += 1;
+                }
+                ViewAction::None
+            }
+            KeyCode::Enter | KeyCode::Char('d') => self.emit_for_selected(|change| {
+                Some(SessionChangesAction::ShowDiff {
+                    path: change.path.clone(),
+                })
+            }),
+            KeyCode::Char('r') => self.emit_for_selected(|change| {
+                change.revertible.then(|| SessionChangesAction::Revert {
+                    path: change.path.clone(),
+                })
+            }),
+            _ => ViewAction::None,
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let popup_width = 96.min(area.width.saturating_sub(4));
+        let popup_height = 22.min(area.height.saturating_sub(4));
+        if popup_width == 0 || popup_height == 0 {
+            return;
+        }
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(popup_width)) / 2,
+            y: area.y + (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        Clear.render(popup_area, buf);
+
+        let hint = |key: &'static str, label: &'static str| {
+            [
+                Span::styled(key, Style::default().fg(palette::TEXT_MUTED)),
+                Span::raw(label),
+            ]
+        };
+        let title = format!(" Session changes ({}) ", self.changes.len());
+        let block = Block::default()
+            .title(Line::from(Span::styled(
+                title,
+                Style::default()
+                    .fg(palette::DEEPSEEK_SKY)
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .title_bottom(Line::from(
+                [
+                    hint(" Enter ", "diff "),
+                    hint(" r ", "revert file "),
+                    hint(" Esc ", "close "),
+                ]
+                .concat(),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(palette::BORDER_COLOR))
+            .style(Style::default().bg(palette::DEEPSEEK_INK))
+            .padding(Padding::horizontal(1));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let width = usize::from(inner.width);
+        let mut lines = Vec::new();
+        if let Some(notice) = self.notice.as_deref() {
+            lines.push(Line::from(Span::styled(
+                truncate_with_ellipsis(notice, width, "…"),
+                Style::default().fg(palette::DEEPSEEK_SKY),
+            )));
+        }
+        if self.changes.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No files changed by tools this session.",
+                Style::default().fg(palette::TEXT_MUTED),
+            )));
+        } else {
+            // Keep the cursor row visible on short terminals.
+            let visible = usize::from(inner.height).saturating_sub(lines.len()).max(1);
+            let first = self.selected.saturating_sub(visible.saturating_sub(1));
+            for (idx, change) in self.changes.iter().enumerate().skip(first).take(visible) {
+                lines.push(self.row(idx, change, width));
+            }
+        }
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+/// Workspace-relative path for display, falling back to the full path.
+#[must_use]
+pub fn display_path(workspace: &Path, path: &Path) -> String {
+    path.strip_prefix(workspace)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(path: &str, kind: ChangeKind, revertible: bool) -> SessionFileChange {
+        SessionFileChange {
+            path: PathBuf::from("/ws").join(path),
+            kind,
+            added_lines: 1,
+            removed_lines: 0,
+            revertible,
+        }
+    }
+
+    fn requested(action: ViewAction) -> Option<SessionChangesAction> {
+        match action {
+            ViewAction::Emit(ViewEvent::SessionChangesRequested { action }) => Some(action),
+            _ => None,
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn keys_target_the_selected_file() {
+        let mut view = ChangesView::new(
+            PathBuf::from("/ws"),
+            vec![
+                change("a.rs", ChangeKind::Modified, true),
+                change("big.bin", ChangeKind::Modified, false),
+            ],
+        );
+
+        assert_eq!(
+            requested(view.handle_key(key(KeyCode::Char('r')))),
+            Some(SessionChangesAction::Revert {
+                path: PathBuf::from("/ws/a.rs")
+            })
+        );
+        view.handle_key(key(KeyCode::Down));
+        assert_eq!(
+            requested(view.handle_key(key(KeyCode::Enter))),
+            Some(SessionChangesAction::ShowDiff {
+                path: PathBuf::from("/ws/big.bin")
+            })
+        );
+        // Files without a kept baseline cannot be reverted.
+        assert_eq!(requested(view.handle_key(key(KeyCode::Char('r')))), None);
+
+        view.set_changes(vec![change("a.rs", ChangeKind::Modified, true)]);
+        assert_eq!(
+            view.selected_change()
+                .map(|c| display_path(&view.workspace, &c.path)),
+            Some("a.rs".to_string())
+        );
+    }
+}
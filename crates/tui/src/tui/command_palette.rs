@@ -1,6 +1,12 @@
-//! Command palette modal for quick command/skill insertion.
+//! Command palette modal: a launcher over several sources.
+//!
+//! Each source is a [`PaletteProvider`]. Commands, skills, tools, MCP
+//! servers, the agent's recently edited files and active tasks are cheap and
+//! load when the palette opens; saved sessions are read from disk on a
+//! background thread and merged in when they arrive (`Ctrl+R` reloads them).
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, mpsc};
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
@@ -19,16 +25,26 @@ use crate::skills::SkillRegistry;
 use crate::tools::spec::ApprovalRequirement;
 use crate::tools::spec::ToolCapability;
 use crate::tools::{ToolContext, ToolRegistryBuilder};
+use crate::tui::app::{App, TaskPanelEntry};
 use crate::tui::views::{CommandPaletteAction, ModalKind, ModalView, ViewAction, ViewEvent};
 
+/// Saved sessions listed in the palette, newest first.
+const MAX_PALETTE_SESSIONS: usize = 20;
+
+/// Sections in display order.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum PaletteSection {
     Command,
+    Task,
+    File,
+    Session,
     Skill,
     Tool,
     Mcp,
 }
 
+const SECTION_COUNT: usize = 7;
+
 #[derive(Debug, Clone)]
 pub struct CommandPaletteEntry {
     section: PaletteSection,
@@ -36,6 +52,19 @@ pub struct CommandPaletteEntry {
     pub description: String,
     pub command: String,
     pub action: CommandPaletteAction,
+    /// Order within the source (e.g. recency); breaks ties between equally
+    /// good matches before the label does.
+    rank: usize,
+}
+
+/// A source of palette entries.
+trait PaletteProvider: Send + Sync {
+    fn section(&self) -> PaletteSection;
+    fn entries(&self) -> Vec<CommandPaletteEntry>;
+    /// Load on a background thread instead of while the palette opens.
+    fn background(&self) -> bool {
+        false
+    }
 }
 
 pub struct CommandPaletteView {
@@ -43,17 +72,285 @@ pub struct CommandPaletteView {
     filtered: Vec<usize>,
     query: String,
     selected: usize,
+    background: Vec<Arc<dyn PaletteProvider>>,
+    pending: Option<mpsc::Receiver<(PaletteSection, Vec<CommandPaletteEntry>)>>,
+    /// Background sources still loading.
+    loading: usize,
 }
 
-pub fn build_entries(
+#[cfg(test)]
+fn build_entries(
     locale: Locale,
     skills_dir: &Path,
     workspace: &Path,
     mcp_config_path: &Path,
     mcp_snapshot: Option<&crate::mcp::McpManagerSnapshot>,
 ) -> Vec<CommandPaletteEntry> {
-    let mut entries = Vec::new();
+    collect_entries(&builtin_providers(
+        locale,
+        skills_dir,
+        workspace,
+        mcp_config_path,
+        mcp_snapshot,
+    ))
+}
+
+fn collect_entries(providers: &[Arc<dyn PaletteProvider>]) -> Vec<CommandPaletteEntry> {
+    let mut entries = providers
+        .iter()
+        .flat_map(|provider| provider.entries())
+        .collect::<Vec<_>>();
+    sort_entries(&mut entries);
+    entries
+}
+
+fn sort_entries(entries: &mut [CommandPaletteEntry]) {
+    entries.sort_by(|a, b| (a.section, a.rank, &a.label).cmp(&(b.section, b.rank, &b.label)));
+}
+
+fn builtin_providers(
+    locale: Locale,
+    skills_dir: &Path,
+    workspace: &Path,
+    mcp_config_path: &Path,
+    mcp_snapshot: Option<&crate::mcp::McpManagerSnapshot>,
+) -> Vec<Arc<dyn PaletteProvider>> {
+    vec![
+        Arc::new(CommandProvider { locale }),
+        Arc::new(SkillProvider {
+            skills_dir: skills_dir.to_path_buf(),
+        }),
+        Arc::new(ToolProvider {
+            workspace: workspace.to_path_buf(),
+        }),
+        Arc::new(McpProvider {
+            config_path: mcp_config_path.to_path_buf(),
+            snapshot: mcp_snapshot.cloned(),
+        }),
+    ]
+}
+
+/// Every provider for the palette opened from the TUI.
+fn app_providers(app: &App) -> Vec<Arc<dyn PaletteProvider>> {
+    let mut providers = builtin_providers(
+        app.ui_locale,
+        &app.skills_dir,
+        &app.workspace,
+        &app.mcp_config_path,
+        app.mcp_snapshot.as_ref(),
+    );
+    providers.push(Arc::new(TaskProvider {
+        tasks: app.task_panel.clone(),
+    }));
+    providers.push(Arc::new(RecentFileProvider {
+        files: app.agent_touched_files.clone(),
+    }));
+    providers.push(Arc::new(SessionProvider {
+        sessions_dir: None,
+        workspace: app.workspace.clone(),
+    }));
+    providers
+}
+
+struct CommandProvider {
+    locale: Locale,
+}
+
+impl PaletteProvider for CommandProvider {
+    fn section(&self) -> PaletteSection {
+        PaletteSection::Command
+    }
+
+    fn entries(&self) -> Vec<CommandPaletteEntry> {
+        command_entries(self.locale)
+    }
+}
+
+struct SkillProvider {
+    skills_dir: PathBuf,
+}
+
+impl PaletteProvider for SkillProvider {
+    fn section(&self) -> PaletteSection {
+        PaletteSection::Skill
+    }
+
+    fn entries(&self) -> Vec<CommandPaletteEntry> {
+        SkillRegistry::discover(&self.skills_dir)
+            .list()
+            .iter()
+            .map(|skill| CommandPaletteEntry {
+                section: PaletteSection::Skill,
+                label: format!("skill:{}", skill.name),
+                description: skill.description.clone(),
+                command: format!("/skill {}", skill.name),
+                action: CommandPaletteAction::ExecuteCommand {
+                    command: format!("/skill {}", skill.name),
+                },
+                rank: 0,
+            })
+            .collect()
+    }
+}
+
+struct ToolProvider {
+    workspace: PathBuf,
+}
+
+impl PaletteProvider for ToolProvider {
+    fn section(&self) -> PaletteSection {
+        PaletteSection::Tool
+    }
+
+    fn entries(&self) -> Vec<CommandPaletteEntry> {
+        tool_entries(&self.workspace)
+    }
+}
+
+struct McpProvider {
+    config_path: PathBuf,
+    snapshot: Option<crate::mcp::McpManagerSnapshot>,
+}
+
+impl PaletteProvider for McpProvider {
+    fn section(&self) -> PaletteSection {
+        PaletteSection::Mcp
+    }
+
+    fn entries(&self) -> Vec<CommandPaletteEntry> {
+        build_mcp_entries(&self.config_path, self.snapshot.as_ref())
+    }
+}
+
+/// Queued and running tasks and shell jobs from the task panel.
+struct TaskProvider {
+    tasks: Vec<TaskPanelEntry>,
+}
+
+impl PaletteProvider for TaskProvider {
+    fn section(&self) -> PaletteSection {
+        PaletteSection::Task
+    }
+
+    fn entries(&self) -> Vec<CommandPaletteEntry> {
+        self.tasks
+            .iter()
+            .enumerate()
+            .filter_map(|(rank, task)| {
+                let command = if task.prompt_summary.starts_with("shell: ") {
+                    format!("/jobs show {}", task.id)
+                } else if task.id.starts_with("task_") {
+                    format!("/task show {}", task.id)
+                } else {
+                    // In-turn RLM work has no command to inspect it.
+                    return None;
+                };
+                Some(CommandPaletteEntry {
+                    section: PaletteSection::Task,
+                    label: format!("task:{}", task.id),
+                    description: format!("[{}] {}", task.status, task.prompt_summary),
+                    command: command.clone(),
+                    action: CommandPaletteAction::ExecuteCommand { command },
+                    rank,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Files the agent wrote this session, most recent first: open in the pager,
+/// or insert as an `@` mention.
+struct RecentFileProvider {
+    files: Vec<String>,
+}
+
+impl PaletteProvider for RecentFileProvider {
+    fn section(&self) -> PaletteSection {
+        PaletteSection::File
+    }
+
+    fn entries(&self) -> Vec<CommandPaletteEntry> {
+        let mut entries = Vec::with_capacity(self.files.len() * 2);
+        for (rank, path) in self.files.iter().enumerate() {
+            entries.push(CommandPaletteEntry {
+                section: PaletteSection::File,
+                label: format!("file:{path}"),
+                description: "Recently edited by the agent \u{2014} open in the pager".to_string(),
+                command: path.clone(),
+                action: CommandPaletteAction::OpenFile { path: path.clone() },
+                rank: rank * 2,
+            });
+            entries.push(CommandPaletteEntry {
+                section: PaletteSection::File,
+                label: format!("file:{path} > insert"),
+                description: format!("Insert @{path} into the composer"),
+                command: path.clone(),
+                action: CommandPaletteAction::InsertPath { path: path.clone() },
+                rank: rank * 2 + 1,
+            });
+        }
+        entries
+    }
+}
+
+/// Saved sessions, newest first, with this workspace's sessions ahead of
+/// others. Reads every session file's metadata, so it loads in the
+/// background.
+struct SessionProvider {
+    /// Overrides the default sessions directory (tests).
+    sessions_dir: Option<PathBuf>,
+    workspace: PathBuf,
+}
+
+impl PaletteProvider for SessionProvider {
+    fn section(&self) -> PaletteSection {
+        PaletteSection::Session
+    }
+
+    fn background(&self) -> bool {
+        true
+    }
+
+    fn entries(&self) -> Vec<CommandPaletteEntry> {
+        let manager = match &self.sessions_dir {
+            Some(dir) => crate::session_manager::SessionManager::new(dir.clone()),
+            None => crate::session_manager::SessionManager::default_location(),
+        };
+        let Ok(mut sessions) = manager.and_then(|manager| manager.list_sessions()) else {
+            return Vec::new();
+        };
+        // `list_sessions` is newest first; the stable sort keeps that order
+        // within each group.
+        sessions.sort_by_key(|session| session.workspace != self.workspace);
+        sessions
+            .into_iter()
+            .take(MAX_PALETTE_SESSIONS)
+            .enumerate()
+            .map(|(rank, session)| {
+                let title: String = session.title.chars().take(48).collect();
+                CommandPaletteEntry {
+                    section: PaletteSection::Session,
+                    label: format!("session:{title}"),
+                    description: format!(
+                        "{} \u{00b7} {} \u{00b7} {} msgs \u{00b7} {}",
+                        &session.id[..8.min(session.id.len())],
+                        crate::tui::session_picker::format_relative_time(&session.updated_at),
+                        session.message_count,
+                        session.workspace.display()
+                    ),
+                    command: session.id.clone(),
+                    action: CommandPaletteAction::ResumeSession {
+                        session_id: session.id,
+                    },
+                    rank,
+                }
+            })
+            .collect()
+    }
+}
 
+fn command_entries(locale: Locale) -> Vec<CommandPaletteEntry> {
+    let mut entries = Vec::new();
     for command in commands::COMMANDS {
         let mut description = command.palette_description_for(locale);
         if command.requires_argument() {
@@ -75,22 +372,13 @@ pub fn build_entries(
             description,
             command: command.palette_command(),
             action,
+            rank: 0,
         });
     }
+    entries
+}
 
-    let skills = SkillRegistry::discover(skills_dir);
-    for skill in skills.list() {
-        entries.push(CommandPaletteEntry {
-            section: PaletteSection::Skill,
-            label: format!("skill:{}", skill.name),
-            description: skill.description.clone(),
-            command: format!("/skill {}", skill.name),
-            action: CommandPaletteAction::ExecuteCommand {
-                command: format!("/skill {}", skill.name),
-            },
-        });
-    }
-
+fn tool_entries(workspace: &Path) -> Vec<CommandPaletteEntry> {
     let context = ToolContext::new(workspace);
     let registry = ToolRegistryBuilder::new()
         .with_file_tools()
@@ -107,7 +395,7 @@ pub fn build_entries(
         .with_test_runner_tool()
         .build(context);
 
-    let mut tool_entries = registry
+    registry
         .all()
         .into_iter()
         .filter_map(|tool| {
@@ -155,17 +443,10 @@ pub fn build_entries(
                     title: format!("Tool: {}", tool.name()),
                     content: format_tool_details(tool.name(), tool.description(), &tags),
                 },
+                rank: 0,
             })
         })
-        .collect::<Vec<_>>();
-    tool_entries.sort_by(|a, b| a.label.cmp(&b.label));
-    entries.extend(tool_entries);
-
-    entries.extend(build_mcp_entries(mcp_config_path, mcp_snapshot));
-
-    entries.sort_by(|a, b| a.label.cmp(&b.label));
-    entries.sort_by_key(|entry| entry.section);
-    entries
+        .collect()
 }
 
 fn build_mcp_entries(
@@ -186,6 +467,7 @@ fn build_mcp_entries(
         action: CommandPaletteAction::ExecuteCommand {
             command: "/mcp".to_string(),
         },
+        rank: 0,
     }];
 
     let Some(snapshot) = snapshot else {
@@ -221,6 +503,7 @@ fn build_mcp_entries(
                 title: format!("MCP Server: {}", server.name),
                 content: format_mcp_server_details(snapshot, server),
             },
+            rank: 0,
         });
 
         for tool in &server.tools {
@@ -244,6 +527,7 @@ fn build_mcp_entries(
                         tool.description.as_deref().unwrap_or("(no description)")
                     ),
                 },
+                rank: 0,
             });
             // Add a "use" entry that inserts the tool's model_name into the input
             // so users can quickly reference the tool in their message to the AI.
@@ -262,6 +546,7 @@ fn build_mcp_entries(
                     action: CommandPaletteAction::InsertText {
                         text: tool.model_name.clone(),
                     },
+                    rank: 0,
                 });
             }
         }
@@ -282,6 +567,7 @@ fn build_mcp_entries(
                         server.name, resource.name
                     ),
                 },
+                rank: 0,
             });
         }
 
@@ -301,10 +587,28 @@ fn build_mcp_entries(
                 action: CommandPaletteAction::OpenTextPager {
                     title: format!("MCP Prompt: {}", prompt.model_name),
                     content: format!(
-                        "Server: {}\nRuntime name: {}\nKind: prompt",
-                        server.name, prompt.model_name
+                        "Server: {}\nRuntime name: {}\nKind: prompt\n\nInsert into the composer with:\n  /mcp prompt {} {} [key=value...]",
+                        server.name, prompt.model_name, server.name, prompt.name
                     ),
                 },
+                rank: 0,
+            });
+            // "use" entry: pre-fill `/mcp prompt` so the user can add
+            // arguments and press Enter to pull the prompt text in.
+            let use_command = format!("/mcp prompt {} {} ", server.name, prompt.name);
+            entries.push(CommandPaletteEntry {
+                section: PaletteSection::Mcp,
+                label: format!("mcp:{}:prompt:{} > use", server.name, prompt.name),
+                description: format!(
+                    "Insert the server prompt into the composer{}",
+                    prompt
+                        .description
+                        .as_ref()
+                        .map_or(String::new(), |desc| format!(" ({desc})"))
+                ),
+                command: use_command.clone(),
+                action: CommandPaletteAction::InsertText { text: use_command },
+                rank: 0,
             });
         }
     }
@@ -367,6 +671,9 @@ fn parse_section_term(term: &str) -> Option<(PaletteSection, String)> {
         "s" | "skill" | "skills" => PaletteSection::Skill,
         "t" | "tool" | "tools" => PaletteSection::Tool,
         "m" | "mcp" => PaletteSection::Mcp,
+        "f" | "file" | "files" => PaletteSection::File,
+        "r" | "session" | "sessions" | "resume" => PaletteSection::Session,
+        "task" | "tasks" | "job" | "jobs" => PaletteSection::Task,
         _ => return None,
     };
 
@@ -376,6 +683,9 @@ fn parse_section_term(term: &str) -> Option<(PaletteSection, String)> {
 fn section_tag(section: PaletteSection) -> &'static str {
     match section {
         PaletteSection::Command => "command",
+        PaletteSection::Task => "task",
+        PaletteSection::File => "file",
+        PaletteSection::Session => "session",
         PaletteSection::Skill => "skill",
         PaletteSection::Tool => "tool",
         PaletteSection::Mcp => "mcp",
@@ -385,9 +695,25 @@ fn section_tag(section: PaletteSection) -> &'static str {
 fn section_rank(section: PaletteSection) -> usize {
     match section {
         PaletteSection::Command => 0,
-        PaletteSection::Skill => 1,
-        PaletteSection::Tool => 2,
-        PaletteSection::Mcp => 3,
+        PaletteSection::Task => 1,
+        PaletteSection::File => 2,
+        PaletteSection::Session => 3,
+        PaletteSection::Skill => 4,
+        PaletteSection::Tool => 5,
+        PaletteSection::Mcp => 6,
+    }
+}
+
+/// Type-ahead marker drawn before each entry's label.
+fn section_icon(section: PaletteSection) -> &'static str {
+    match section {
+        PaletteSection::Command => "\u{203a}",
+        PaletteSection::Task => "\u{25b6}",
+        PaletteSection::File => "\u{25a4}",
+        PaletteSection::Session => "\u{21ba}",
+        PaletteSection::Skill => "\u{2726}",
+        PaletteSection::Tool => "\u{2692}",
+        PaletteSection::Mcp => "\u{29c9}",
     }
 }
 
@@ -525,11 +851,64 @@ impl CommandPaletteView {
             filtered: Vec::new(),
             query: String::new(),
             selected: 0,
+            background: Vec::new(),
+            pending: None,
+            loading: 0,
         };
         view.refilter();
         view
     }
 
+    /// The palette opened from the TUI, with every source.
+    pub fn for_app(app: &App) -> Self {
+        Self::with_providers(app_providers(app))
+    }
+
+    fn with_providers(providers: Vec<Arc<dyn PaletteProvider>>) -> Self {
+        let (background, immediate): (Vec<_>, Vec<_>) = providers
+            .into_iter()
+            .partition(|provider| provider.background());
+        let mut view = Self::new(collect_entries(&immediate));
+        view.background = background;
+        view.load_background();
+        view
+    }
+
+    /// (Re)load the background sources, replacing their current entries
+    /// when the new ones arrive.
+    fn load_background(&mut self) {
+        if self.background.is_empty() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        for provider in &self.background {
+            let provider = Arc::clone(provider);
+            let tx = tx.clone();
+            std::thread::spawn(move || {
+                let _ = tx.send((provider.section(), provider.entries()));
+            });
+        }
+        self.loading = self.background.len();
+        self.pending = Some(rx);
+    }
+
+    /// Merge `entries` for `section`, keeping the selected entry selected.
+    fn merge_section(&mut self, section: PaletteSection, entries: Vec<CommandPaletteEntry>) {
+        let selected_label = self.selected_entry().map(|entry| entry.label.clone());
+        self.entries.retain(|entry| entry.section != section);
+        self.entries.extend(entries);
+        sort_entries(&mut self.entries);
+        self.refilter();
+        if let Some(label) = selected_label
+            && let Some(position) = self
+                .filtered
+                .iter()
+                .position(|idx| self.entries[*idx].label == label)
+        {
+            self.selected = position;
+        }
+    }
+
     fn refilter(&mut self) {
         let query = self.query.trim().to_ascii_lowercase();
         let terms: Vec<&str> = query
@@ -546,7 +925,12 @@ impl CommandPaletteView {
 
         filtered.sort_by_key(|(idx, score)| {
             let entry = &self.entries[*idx];
-            (section_rank(entry.section), *score, &entry.label)
+            (
+                section_rank(entry.section),
+                *score,
+                entry.rank,
+                &entry.label,
+            )
         });
         self.filtered = filtered.into_iter().map(|(idx, _)| idx).collect();
         if self.selected >= self.filtered.len() {
@@ -555,7 +939,8 @@ impl CommandPaletteView {
     }
 
     fn scope_hint_lines() -> Line<'static> {
-        let hint = "scope: c:/cmd: , s:/skill: , t:/tool: , m:/mcp:";
+        let hint =
+            "scope: c:/cmd: , s:/skill: , t:/tool: , m:/mcp: , f:/file: , r:/session: , task:";
         Line::from(Span::styled(
             hint,
             Style::default()
@@ -567,6 +952,9 @@ impl CommandPaletteView {
     fn format_section_label(section: PaletteSection, count: usize) -> Line<'static> {
         let title = match section {
             PaletteSection::Command => "Commands",
+            PaletteSection::Task => "Active tasks",
+            PaletteSection::File => "Recent files",
+            PaletteSection::Session => "Sessions",
             PaletteSection::Skill => "Skills",
             PaletteSection::Tool => "Tools",
             PaletteSection::Mcp => "MCP",
@@ -623,6 +1011,30 @@ impl ModalView for CommandPaletteView {
         ModalKind::CommandPalette
     }
 
+    fn poll_updates(&mut self) -> bool {
+        let Some(rx) = self.pending.as_ref() else {
+            return false;
+        };
+        let mut arrived = Vec::new();
+        loop {
+            match rx.try_recv() {
+                Ok(batch) => arrived.push(batch),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.pending = None;
+                    self.loading = 0;
+                    break;
+                }
+            }
+        }
+        let changed = !arrived.is_empty();
+        for (section, entries) in arrived {
+            self.loading = self.loading.saturating_sub(1);
+            self.merge_section(section, entries);
+        }
+        changed
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -660,6 +1072,10 @@ impl ModalView for CommandPaletteView {
                 self.refilter();
                 ViewAction::None
             }
+            KeyCode::Char('r') if key.modifiers == KeyModifiers::CONTROL => {
+                self.load_background();
+                ViewAction::None
+            }
             KeyCode::Char(c)
                 if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT =>
             {
@@ -693,11 +1109,14 @@ impl ModalView for CommandPaletteView {
             query_label,
             Style::default().fg(palette::TEXT_MUTED),
         )));
-        let match_count = if self.query.is_empty() {
+        let mut match_count = if self.query.is_empty() {
             format!("{} entries", self.entries.len())
         } else {
             format!("{} / {} matches", self.filtered.len(), self.entries.len())
         };
+        if self.loading > 0 {
+            match_count.push_str(" \u{00b7} loading sessions\u{2026}");
+        }
         lines.push(Line::from(Span::styled(
             match_count,
             Style::default().fg(palette::TEXT_DIM).italic(),
@@ -707,17 +1126,9 @@ impl ModalView for CommandPaletteView {
         lines.push(Line::from(""));
 
         let visible = popup_height.saturating_sub(7) as usize;
-        let mut command_count = 0usize;
-        let mut skill_count = 0usize;
-        let mut tool_count = 0usize;
-        let mut mcp_count = 0usize;
+        let mut section_counts = [0usize; SECTION_COUNT];
         for idx in &self.filtered {
-            match self.entries[*idx].section {
-                PaletteSection::Command => command_count += 1,
-                PaletteSection::Skill => skill_count += 1,
-                PaletteSection::Tool => tool_count += 1,
-                PaletteSection::Mcp => mcp_count += 1,
-            }
+            section_counts[section_rank(self.entries[*idx].section)] += 1;
         }
         if self.filtered.is_empty() {
             lines.push(Line::from(Span::styled(
@@ -738,12 +1149,7 @@ impl ModalView for CommandPaletteView {
                     if slot > 0 {
                         lines.push(Line::from(""));
                     }
-                    let count = match entry.section {
-                        PaletteSection::Command => command_count,
-                        PaletteSection::Skill => skill_count,
-                        PaletteSection::Tool => tool_count,
-                        PaletteSection::Mcp => mcp_count,
-                    };
+                    let count = section_counts[section_rank(entry.section)];
                     lines.push(Self::format_section_label(entry.section, count));
                     active_section = Some(entry.section);
                 }
@@ -756,7 +1162,8 @@ impl ModalView for CommandPaletteView {
                     Style::default().fg(palette::TEXT_PRIMARY)
                 };
 
-                let mut line = format!("  {:<label_width$}", entry.label);
+                let label = format!("{} {}", section_icon(entry.section), entry.label);
+                let mut line = format!("  {label:<label_width$}");
                 let desc_capacity = popup_width as usize - (label_width + 4);
                 let desc = if entry.description.width() > desc_capacity {
                     let mut shortened = String::new();
@@ -771,7 +1178,7 @@ impl ModalView for CommandPaletteView {
                     entry.description.clone()
                 };
                 if is_selected {
-                    line = format!("> {:<label_width$}", entry.label);
+                    line = format!("> {label:<label_width$}");
                 }
                 line.push_str("  ");
                 line.push_str(&desc);
@@ -784,6 +1191,7 @@ impl ModalView for CommandPaletteView {
             .title_bottom(Line::from(vec![
                 Span::styled(" ↑/↓/j/k move  ", Style::default().fg(palette::TEXT_MUTED)),
                 Span::styled("Enter run/open  ", Style::default().fg(palette::TEXT_MUTED)),
+                Span::styled("Ctrl+R reload  ", Style::default().fg(palette::TEXT_MUTED)),
                 Span::styled("Esc close", Style::default().fg(palette::TEXT_MUTED)),
             ]));
 
@@ -813,6 +1221,7 @@ mod tests {
             action: CommandPaletteAction::InsertText {
                 text: command.to_string(),
             },
+            rank: 0,
         }
     }
 
@@ -1108,6 +1517,7 @@ mod tests {
             action: CommandPaletteAction::ExecuteCommand {
                 command: "/config".to_string(),
             },
+            rank: 0,
         }];
         let mut view = CommandPaletteView::new(entries);
 
@@ -1119,4 +1529,111 @@ mod tests {
             })
         ));
     }
+
+    #[test]
+    fn command_palette_lists_tasks_and_recent_files() {
+        let tasks = TaskProvider {
+            tasks: vec![
+                TaskPanelEntry {
+                    id: "job_1a2b".to_string(),
+                    status: "running".to_string(),
+                    prompt_summary: "shell: cargo test".to_string(),
+                    duration_ms: None,
+                },
+                TaskPanelEntry {
+                    id: "task_9".to_string(),
+                    status: "queued".to_string(),
+                    prompt_summary: "review the diff".to_string(),
+                    duration_ms: None,
+                },
+                TaskPanelEntry {
+                    id: "rlm_3".to_string(),
+                    status: "running".to_string(),
+                    prompt_summary: "rlm: summarize".to_string(),
+                    duration_ms: None,
+                },
+            ],
+        };
+        let files = RecentFileProvider {
+            files: vec!["src/new.rs".to_string(), "src/old.rs".to_string()],
+        };
+        let mut view = CommandPaletteView::with_providers(vec![Arc::new(tasks), Arc::new(files)]);
+
+        view.query = "task:".to_string();
+        view.refilter();
+        let commands = view
+            .filtered
+            .iter()
+            .map(|idx| view.entries[*idx].command.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(commands, vec!["/jobs show job_1a2b", "/task show task_9"]);
+
+        // Recency beats the label: the newest file comes first.
+        view.query = "f:src".to_string();
+        view.refilter();
+        let first = view.selected_entry().expect("file entry");
+        assert_eq!(first.label, "file:src/new.rs");
+        assert!(matches!(
+            view.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty())),
+            ViewAction::EmitAndClose(ViewEvent::CommandPaletteSelected {
+                action: CommandPaletteAction::OpenFile { ref path }
+            }) if path == "src/new.rs"
+        ));
+
+        view.query = "f:old insert".to_string();
+        view.refilter();
+        assert!(matches!(
+            &view.selected_entry().expect("insert entry").action,
+            CommandPaletteAction::InsertPath { path } if path == "src/old.rs"
+        ));
+    }
+
+    #[test]
+    fn command_palette_loads_sessions_in_the_background() {
+        use crate::models::{ContentBlock, Message};
+        use crate::session_manager::{SessionManager, create_saved_session};
+
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let sessions_dir = tmp.path().join("sessions");
+        let manager = SessionManager::new(sessions_dir.clone()).expect("manager");
+        let message = Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::Text {
+                text: "Fix the flaky login test".to_string(),
+                cache_control: None,
+            }],
+        };
+        let session = create_saved_session(&[message], "test-model", tmp.path(), 10, None);
+        manager.save_session(&session).expect("save");
+
+        let mut view = CommandPaletteView::with_providers(vec![
+            Arc::new(CommandProvider { locale: Locale::En }),
+            Arc::new(SessionProvider {
+                sessions_dir: Some(sessions_dir),
+                workspace: tmp.path().to_path_buf(),
+            }),
+        ]);
+        assert_eq!(view.loading, 1);
+        assert!(
+            view.entries
+                .iter()
+                .all(|entry| entry.section != PaletteSection::Session)
+        );
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while view.loading > 0 && std::time::Instant::now() < deadline {
+            view.poll_updates();
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(view.loading, 0);
+
+        view.query = "r:flaky".to_string();
+        view.refilter();
+        let entry = view.selected_entry().expect("session entry");
+        assert!(entry.label.starts_with("session:"), "{}", entry.label);
+        assert!(matches!(
+            &entry.action,
+            CommandPaletteAction::ResumeSession { session_id } if *session_id == session.metadata.id
+        ));
+    }
 }
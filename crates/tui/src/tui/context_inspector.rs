@@ -108,6 +108,7 @@ pub fn build_context_inspector_text(app: &App) -> String {
         out,
         "Context: {status} - ~{used}/{max} tokens ({percent:.1}%)"
     );
+    let _ = writeln!(out, "Toolset: {}", toolset_line(app));
     let _ = writeln!(
         out,
         "Transcript: {} cells, {} API messages",
@@ -132,6 +133,27 @@ pub fn build_context_inspector_text(app: &App) -> String {
     out
 }
 
+fn toolset_line(app: &App) -> String {
+    let Some(catalog) = app.tool_catalog.as_ref() else {
+        return "not assembled yet (sent with the next turn)".to_string();
+    };
+    let surface = if catalog.read_only {
+        "read-only"
+    } else {
+        "full"
+    };
+    let mut line = format!(
+        "{} {surface} - {} tools loaded (~{} schema tokens)",
+        catalog.mode.label(),
+        catalog.loaded,
+        catalog.schema_tokens
+    );
+    if catalog.deferred > 0 {
+        let _ = write!(line, ", {} deferred behind tool search", catalog.deferred);
+    }
+    line
+}
+
 fn context_usage(app: &App) -> (usize, u32, f64) {
     let max = context_window_for_model(&app.model).unwrap_or(LEGACY_DEEPSEEK_CONTEXT_WINDOW_TOKENS);
     let estimated =
@@ -313,6 +335,7 @@ fn push_references(out: &mut String, references: &[SessionContextReference]) {
         let prefix = match reference.source {
             ContextReferenceSource::AtMention => "@",
             ContextReferenceSource::Attachment => "/attach ",
+            ContextReferenceSource::SmartContext => "auto ",
         };
         let state = if reference.included {
             if reference.expanded {
@@ -410,9 +433,10 @@ fn short_tool_id(id: &str) -> String {
 mod tests {
     use super::*;
     use crate::config::Config;
+    use crate::core::events::ToolCatalogSummary;
     use crate::models::{ContentBlock, Message};
     use crate::session_manager::SessionContextReference;
-    use crate::tui::app::TuiOptions;
+    use crate::tui::app::{AppMode, TuiOptions};
     use crate::tui::file_mention::{
         ContextReference, ContextReferenceKind, ContextReferenceSource,
     };
@@ -480,6 +504,27 @@ mod tests {
         assert!(text.contains("[file] @src/main.rs -> /tmp/project/src/main.rs"));
     }
 
+    #[test]
+    fn inspector_reports_active_toolset_size() {
+        let mut app = test_app();
+        assert!(build_context_inspector_text(&app).contains("Toolset: not assembled yet"));
+
+        app.tool_catalog = Some(ToolCatalogSummary {
+            mode: AppMode::Plan,
+            read_only: true,
+            loaded: 18,
+            deferred: 3,
+            schema_tokens: 4_200,
+        });
+        let text = build_context_inspector_text(&app);
+        assert!(
+            text.contains(
+                "Toolset: PLAN read-only - 18 tools loaded (~4200 schema tokens), 3 deferred behind tool search"
+            ),
+            "{text}"
+        );
+    }
+
     #[test]
     fn inspector_marks_high_context_pressure() {
         let mut app = test_app();
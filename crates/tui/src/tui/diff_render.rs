@@ -1,12 +1,20 @@
 //! Diff rendering helpers for TUI previews.
 
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
+use similar::{ChangeTag, TextDiff};
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::palette;
 
 const LINE_NUMBER_WIDTH: usize = 4;
+/// Areas at least this wide render diffs side by side.
+pub const SIDE_BY_SIDE_MIN_WIDTH: u16 = 120;
+/// Unchanged lines kept on each side of a collapsed run.
+const CONTEXT_KEEP: usize = 3;
+/// Paired lines sharing less than this fraction of their words are shown
+/// as whole-line changes.
+const WORD_HIGHLIGHT_MIN_RATIO: f32 = 0.4;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DiffFileSummary {
@@ -16,95 +24,482 @@ pub struct DiffFileSummary {
     pub hunks: usize,
 }
 
+/// How a diff is laid out. [`render_diff`] picks one from the width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLayout {
+    /// One column, removals above additions.
+    Unified,
+    /// Old lines on the left, new lines on the right.
+    SideBySide,
+}
+
+impl DiffLayout {
+    #[must_use]
+    pub fn for_width(width: u16) -> Self {
+        if width >= SIDE_BY_SIDE_MIN_WIDTH {
+            Self::SideBySide
+        } else {
+            Self::Unified
+        }
+    }
+}
+
+/// Render a unified diff: per-file summary, line-number gutters, word-level
+/// highlights inside changed lines, and long unchanged runs collapsed. Wide
+/// areas get the side-by-side layout.
 pub fn render_diff(diff: &str, width: u16) -> Vec<Line<'static>> {
+    render_diff_with_layout(diff, width, DiffLayout::for_width(width))
+}
+
+pub fn render_diff_with_layout(diff: &str, width: u16, layout: DiffLayout) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
-    let mut old_line: Option<usize> = None;
-    let mut new_line: Option<usize> = None;
     let summaries = summarize_diff(diff);
-
     if !summaries.is_empty() {
         lines.extend(render_diff_summary(&summaries, width));
     }
 
-    for raw in diff.lines() {
-        if raw.starts_with("diff --git") || raw.starts_with("index ") {
-            lines.extend(render_header_line(raw, width));
-            continue;
+    for block in group_blocks(parse_rows(diff)) {
+        match block {
+            DiffBlock::Header(raw) => lines.extend(render_header_line(&raw, width)),
+            DiffBlock::Hunk(raw) => lines.extend(render_hunk_header(&raw, width)),
+            DiffBlock::Context(rows) => {
+                for item in collapse_context(rows) {
+                    match item {
+                        ContextItem::Row(row) => {
+                            let text = vec![(row.text, false)];
+                            match layout {
+                                DiffLayout::Unified => lines.extend(render_unified_line(
+                                    &text,
+                                    width,
+                                    row.old,
+                                    row.new,
+                                    ' ',
+                                    context_style(),
+                                )),
+                                DiffLayout::SideBySide => lines.extend(render_side_by_side(
+                                    width,
+                                    Some((row.old, ' ', text.as_slice(), context_style())),
+                                    Some((row.new, ' ', text.as_slice(), context_style())),
+                                )),
+                            }
+                        }
+                        ContextItem::Collapsed(count) => lines.extend(wrap_with_style(
+                            &format!(
+                                "  \u{22ef} {count} unchanged line{}",
+                                if count == 1 { "" } else { "s" }
+                            ),
+                            Style::default().fg(palette::TEXT_MUTED),
+                            width,
+                        )),
+                    }
+                }
+            }
+            DiffBlock::Change { removed, added } => {
+                let (removed_segments, added_segments) = highlight_change(&removed, &added);
+                match layout {
+                    DiffLayout::Unified => {
+                        for (row, segments) in removed.iter().zip(&removed_segments) {
+                            lines.extend(render_unified_line(
+                                segments,
+                                width,
+                                row.line,
+                                None,
+                                '-',
+                                removed_style(),
+                            ));
+                        }
+                        for (row, segments) in added.iter().zip(&added_segments) {
+                            lines.extend(render_unified_line(
+                                segments,
+                                width,
+                                None,
+                                row.line,
+                                '+',
+                                added_style(),
+                            ));
+                        }
+                    }
+                    DiffLayout::SideBySide => {
+                        for idx in 0..removed.len().max(added.len()) {
+                            let left = removed.get(idx).map(|row| {
+                                (
+                                    row.line,
+                                    '-',
+                                    removed_segments[idx].as_slice(),
+                                    removed_style(),
+                                )
+                            });
+                            let right = added.get(idx).map(|row| {
+                                (row.line, '+', added_segments[idx].as_slice(), added_style())
+                            });
+                            lines.extend(render_side_by_side(width, left, right));
+                        }
+                    }
+                }
+            }
         }
+    }
 
-        if raw.starts_with("--- ") || raw.starts_with("+++ ") {
-            lines.extend(render_header_line(raw, width));
-            continue;
-        }
+    lines
+}
 
-        if raw.starts_with("@@") {
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ContextRow {
+    old: Option<usize>,
+    new: Option<usize>,
+    text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ChangedRow {
+    line: Option<usize>,
+    text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DiffRow {
+    Header(String),
+    Hunk(String),
+    Context(ContextRow),
+    Removed(ChangedRow),
+    Added(ChangedRow),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DiffBlock {
+    Header(String),
+    Hunk(String),
+    Context(Vec<ContextRow>),
+    /// A run of removals followed by the additions that replace them.
+    Change {
+        removed: Vec<ChangedRow>,
+        added: Vec<ChangedRow>,
+    },
+}
+
+enum ContextItem {
+    Row(ContextRow),
+    Collapsed(usize),
+}
+
+/// A piece of a changed line; `true` marks words that differ from the
+/// paired line on the other side.
+type Segment = (String, bool);
+
+fn parse_rows(diff: &str) -> Vec<DiffRow> {
+    let mut rows = Vec::new();
+    let mut old_line: Option<usize> = None;
+    let mut new_line: Option<usize> = None;
+
+    for raw in diff.lines() {
+        if raw.starts_with("diff --git")
+            || raw.starts_with("index ")
+            || raw.starts_with("--- ")
+            || raw.starts_with("+++ ")
+        {
+            rows.push(DiffRow::Header(raw.to_string()));
+        } else if raw.starts_with("@@") {
             if let Some((old_start, new_start)) = parse_hunk_header(raw) {
                 old_line = Some(old_start);
                 new_line = Some(new_start);
             }
-            lines.extend(render_hunk_header(raw, width));
-            continue;
+            rows.push(DiffRow::Hunk(raw.to_string()));
+        } else if let Some(text) = raw.strip_prefix('+') {
+            rows.push(DiffRow::Added(ChangedRow {
+                line: new_line,
+                text: text.to_string(),
+            }));
+            advance(&mut new_line);
+        } else if let Some(text) = raw.strip_prefix('-') {
+            rows.push(DiffRow::Removed(ChangedRow {
+                line: old_line,
+                text: text.to_string(),
+            }));
+            advance(&mut old_line);
+        } else if let Some(text) = raw.strip_prefix(' ') {
+            rows.push(DiffRow::Context(ContextRow {
+                old: old_line,
+                new: new_line,
+                text: text.to_string(),
+            }));
+            advance(&mut old_line);
+            advance(&mut new_line);
+        } else {
+            rows.push(DiffRow::Header(raw.to_string()));
         }
+    }
+    rows
+}
 
-        if raw.starts_with('+') && !raw.starts_with("+++") {
-            let content = raw.trim_start_matches('+');
-            lines.extend(render_diff_line(
-                content,
-                width,
-                old_line,
-                new_line,
-                '+',
-                Style::default()
-                    .fg(palette::DIFF_ADDED)
-                    .bg(palette::DIFF_ADDED_BG),
-            ));
-            if let Some(line) = new_line.as_mut() {
-                *line = line.saturating_add(1);
-            }
-            continue;
+fn advance(line: &mut Option<usize>) {
+    if let Some(line) = line.as_mut() {
+        *line = line.saturating_add(1);
+    }
+}
+
+fn group_blocks(rows: Vec<DiffRow>) -> Vec<DiffBlock> {
+    let mut blocks: Vec<DiffBlock> = Vec::new();
+    for row in rows {
+        match row {
+            DiffRow::Header(raw) => blocks.push(DiffBlock::Header(raw)),
+            DiffRow::Hunk(raw) => blocks.push(DiffBlock::Hunk(raw)),
+            DiffRow::Context(row) => match blocks.last_mut() {
+                Some(DiffBlock::Context(rows)) => rows.push(row),
+                _ => blocks.push(DiffBlock::Context(vec![row])),
+            },
+            DiffRow::Removed(row) => match blocks.last_mut() {
+                // A removal after additions starts a new change block.
+                Some(DiffBlock::Change { removed, added }) if added.is_empty() => {
+                    removed.push(row);
+                }
+                _ => blocks.push(DiffBlock::Change {
+                    removed: vec![row],
+                    added: Vec::new(),
+                }),
+            },
+            DiffRow::Added(row) => match blocks.last_mut() {
+                Some(DiffBlock::Change { added, .. }) => added.push(row),
+                _ => blocks.push(DiffBlock::Change {
+                    removed: Vec::new(),
+                    added: vec![row],
+                }),
+            },
         }
+    }
+    blocks
+}
 
-        if raw.starts_with('-') && !raw.starts_with("---") {
-            let content = raw.trim_start_matches('-');
-            lines.extend(render_diff_line(
-                content,
-                width,
-                old_line,
-                new_line,
-                '-',
-                Style::default()
-                    .fg(palette::STATUS_ERROR)
-                    .bg(palette::DIFF_DELETED_BG),
-            ));
-            if let Some(line) = old_line.as_mut() {
-                *line = line.saturating_add(1);
+/// Keep [`CONTEXT_KEEP`] lines on each side of a long unchanged run and
+/// replace the middle with a single marker.
+fn collapse_context(rows: Vec<ContextRow>) -> Vec<ContextItem> {
+    if rows.len() <= CONTEXT_KEEP * 2 + 1 {
+        return rows.into_iter().map(ContextItem::Row).collect();
+    }
+    let hidden = rows.len() - CONTEXT_KEEP * 2;
+    let mut items = Vec::with_capacity(CONTEXT_KEEP * 2 + 1);
+    let mut rows = rows.into_iter();
+    items.extend(rows.by_ref().take(CONTEXT_KEEP).map(ContextItem::Row));
+    items.push(ContextItem::Collapsed(hidden));
+    items.extend(rows.skip(hidden).map(ContextItem::Row));
+    items
+}
+
+/// Split each changed line into segments, marking the words that differ
+/// from its counterpart. Lines pair up in order; a pair that shares too
+/// little is shown as a whole-line change rather than a confetti of
+/// highlights.
+fn highlight_change(
+    removed: &[ChangedRow],
+    added: &[ChangedRow],
+) -> (Vec<Vec<Segment>>, Vec<Vec<Segment>>) {
+    let mut removed_segments: Vec<Vec<Segment>> = removed
+        .iter()
+        .map(|row| vec![(row.text.clone(), false)])
+        .collect();
+    let mut added_segments: Vec<Vec<Segment>> = added
+        .iter()
+        .map(|row| vec![(row.text.clone(), false)])
+        .collect();
+
+    for (idx, (old, new)) in removed.iter().zip(added).enumerate() {
+        let diff = TextDiff::from_words(old.text.as_str(), new.text.as_str());
+        let mut old_segments = Vec::new();
+        let mut new_segments = Vec::new();
+        // Whitespace always matches, so similarity counts visible chars only.
+        let mut shared = 0;
+        for change in diff.iter_all_changes() {
+            match change.tag() {
+                ChangeTag::Equal => {
+                    shared += visible_len(change.value());
+                    push_segment(&mut old_segments, change.value(), false);
+                    push_segment(&mut new_segments, change.value(), false);
+                }
+                ChangeTag::Delete => push_segment(&mut old_segments, change.value(), true),
+                ChangeTag::Insert => push_segment(&mut new_segments, change.value(), true),
             }
+        }
+        let total = visible_len(&old.text) + visible_len(&new.text);
+        if total == 0 || (2 * shared) as f32 / (total as f32) < WORD_HIGHLIGHT_MIN_RATIO {
             continue;
         }
+        removed_segments[idx] = old_segments;
+        added_segments[idx] = new_segments;
+    }
+
+    (removed_segments, added_segments)
+}
+
+fn visible_len(text: &str) -> usize {
+    text.chars().filter(|ch| !ch.is_whitespace()).count()
+}
+
+fn push_segment(segments: &mut Vec<Segment>, text: &str, emphasized: bool) {
+    // Whitespace between two changed words joins the highlight so the
+    // emphasis reads as one run.
+    let emphasized = emphasized && !text.trim().is_empty();
+    match segments.last_mut() {
+        Some((last, last_emphasized)) if *last_emphasized == emphasized => last.push_str(text),
+        _ => segments.push((text.to_string(), emphasized)),
+    }
+}
+
+fn context_style() -> Style {
+    Style::default().fg(palette::TEXT_PRIMARY)
+}
+
+fn added_style() -> Style {
+    Style::default()
+        .fg(palette::DIFF_ADDED)
+        .bg(palette::DIFF_ADDED_BG)
+}
+
+fn removed_style() -> Style {
+    Style::default()
+        .fg(palette::STATUS_ERROR)
+        .bg(palette::DIFF_DELETED_BG)
+}
+
+/// Changed words flip foreground and background so they stand out from the
+/// tinted line under every theme and on 16-colour terminals.
+fn emphasis(style: Style) -> Style {
+    style.add_modifier(Modifier::REVERSED | Modifier::BOLD)
+}
+
+fn styled_segments(segments: &[Segment], style: Style) -> Vec<(String, Style)> {
+    segments
+        .iter()
+        .map(|(text, emphasized)| {
+            let text = text.replace('\t', "    ").replace('\r', "");
+            (text, if *emphasized { emphasis(style) } else { style })
+        })
+        .collect()
+}
 
-        if raw.starts_with(' ') {
-            let content = raw.trim_start_matches(' ');
-            lines.extend(render_diff_line(
-                content,
-                width,
-                old_line,
-                new_line,
-                ' ',
-                Style::default().fg(palette::TEXT_PRIMARY),
+fn render_unified_line(
+    segments: &[Segment],
+    width: u16,
+    old_line: Option<usize>,
+    new_line: Option<usize>,
+    marker: char,
+    style: Style,
+) -> Vec<Line<'static>> {
+    let prefix = format_line_numbers(old_line, new_line, marker);
+    let prefix_width = prefix.width();
+    let available = (width as usize).saturating_sub(prefix_width).max(1);
+    wrap_segments(&styled_segments(segments, style), available)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, chunk)| {
+            let lead = if idx == 0 {
+                Span::styled(prefix.clone(), Style::default().fg(palette::TEXT_MUTED))
+            } else {
+                Span::raw(" ".repeat(prefix_width))
+            };
+            let mut spans = vec![lead];
+            spans.extend(chunk);
+            Line::from(spans)
+        })
+        .collect()
+}
+
+type SideCell<'a> = (Option<usize>, char, &'a [Segment], Style);
+
+fn render_side_by_side(
+    width: u16,
+    left: Option<SideCell<'_>>,
+    right: Option<SideCell<'_>>,
+) -> Vec<Line<'static>> {
+    let total = width as usize;
+    let left_width = total.saturating_sub(1) / 2;
+    let right_width = total.saturating_sub(1 + left_width);
+    let left_rows = render_side_cell(left, left_width);
+    let right_rows = render_side_cell(right, right_width);
+    let rows = left_rows.len().max(right_rows.len());
+
+    (0..rows)
+        .map(|idx| {
+            let mut spans = left_rows
+                .get(idx)
+                .cloned()
+                .unwrap_or_else(|| vec![Span::raw(" ".repeat(left_width))]);
+            spans.push(Span::styled(
+                "\u{2502}",
+                Style::default().fg(palette::TEXT_MUTED),
             ));
-            if let Some(line) = old_line.as_mut() {
-                *line = line.saturating_add(1);
+            spans.extend(
+                right_rows
+                    .get(idx)
+                    .cloned()
+                    .unwrap_or_else(|| vec![Span::raw(" ".repeat(right_width))]),
+            );
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// One half of a side-by-side row, padded to exactly `width` columns.
+fn render_side_cell(cell: Option<SideCell<'_>>, width: usize) -> Vec<Vec<Span<'static>>> {
+    let Some((line, marker, segments, style)) = cell else {
+        return vec![vec![Span::raw(" ".repeat(width))]];
+    };
+    let number = line
+        .map(|value| format!("{value:>LINE_NUMBER_WIDTH$}"))
+        .unwrap_or_else(|| " ".repeat(LINE_NUMBER_WIDTH));
+    let prefix = format!("{number} {marker} ");
+    let prefix_width = prefix.width();
+    let available = width.saturating_sub(prefix_width).max(1);
+    let pad_style = Style::default().bg(style.bg.unwrap_or(Color::Reset));
+
+    wrap_segments(&styled_segments(segments, style), available)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, chunk)| {
+            let used: usize = chunk.iter().map(|span| span.content.width()).sum();
+            let mut spans = vec![if idx == 0 {
+                Span::styled(prefix.clone(), Style::default().fg(palette::TEXT_MUTED))
+            } else {
+                Span::raw(" ".repeat(prefix_width))
+            }];
+            spans.extend(chunk);
+            let pad = width.saturating_sub(prefix_width + used);
+            if pad > 0 {
+                spans.push(Span::styled(" ".repeat(pad), pad_style));
             }
-            if let Some(line) = new_line.as_mut() {
-                *line = line.saturating_add(1);
+            spans
+        })
+        .collect()
+}
+
+/// Hard-wrap styled text at `width` columns, keeping whitespace intact so
+/// indentation survives. Always yields at least one (possibly empty) row.
+fn wrap_segments(segments: &[(String, Style)], width: usize) -> Vec<Vec<Span<'static>>> {
+    let width = width.max(1);
+    let mut rows: Vec<Vec<Span<'static>>> = Vec::new();
+    let mut row: Vec<Span<'static>> = Vec::new();
+    let mut row_width = 0;
+
+    for (text, style) in segments {
+        let mut current = String::new();
+        for ch in text.chars() {
+            let char_width = ch.width().unwrap_or(1);
+            if row_width + char_width > width && row_width > 0 {
+                if !current.is_empty() {
+                    row.push(Span::styled(std::mem::take(&mut current), *style));
+                }
+                rows.push(std::mem::take(&mut row));
+                row_width = 0;
             }
-            continue;
+            current.push(ch);
+            row_width += char_width;
+        }
+        if !current.is_empty() {
+            row.push(Span::styled(current, *style));
         }
-
-        lines.extend(render_header_line(raw, width));
     }
-
-    lines
+    if !row.is_empty() || rows.is_empty() {
+        rows.push(row);
+    }
+    rows
 }
 
 #[must_use]
@@ -278,44 +673,6 @@ fn render_hunk_header(line: &str, width: u16) -> Vec<Line<'static>> {
     wrap_with_style(line, style, width)
 }
 
-fn render_diff_line(
-    content: &str,
-    width: u16,
-    old_line: Option<usize>,
-    new_line: Option<usize>,
-    marker: char,
-    style: Style,
-) -> Vec<Line<'static>> {
-    let prefix = format_line_numbers(old_line, new_line, marker);
-    let prefix_width = prefix.width();
-    let available = width.saturating_sub(prefix_width as u16).max(1) as usize;
-    let wrapped = wrap_text(content, available);
-
-    let mut out = Vec::new();
-    for (idx, chunk) in wrapped.into_iter().enumerate() {
-        if idx == 0 {
-            out.push(Line::from(vec![
-                Span::styled(prefix.clone(), Style::default().fg(palette::TEXT_MUTED)),
-                Span::styled(chunk, style),
-            ]));
-        } else {
-            out.push(Line::from(vec![
-                Span::raw(" ".repeat(prefix_width)),
-                Span::styled(chunk, style),
-            ]));
-        }
-    }
-
-    if out.is_empty() {
-        out.push(Line::from(vec![Span::styled(
-            prefix,
-            Style::default().fg(palette::TEXT_MUTED),
-        )]));
-    }
-
-    out
-}
-
 fn format_line_numbers(old_line: Option<usize>, new_line: Option<usize>, marker: char) -> String {
     let old = old_line
         .map(|value| {
@@ -477,6 +834,83 @@ diff --git a/src/a.rs b/src/a.rs
         );
     }
 
+    #[test]
+    fn changed_words_are_emphasized_within_paired_lines() {
+        let diff = "\
+@@ -1,1 +1,1 @@
+-let timeout = 30;
++let timeout = 60;
+";
+        let rendered = render_diff_with_layout(diff, 80, DiffLayout::Unified);
+        let emphasized = |needle: &str| {
+            rendered.iter().any(|line| {
+                line.spans.iter().any(|span| {
+                    span.content.contains(needle)
+                        && span.style.add_modifier.contains(Modifier::REVERSED)
+                })
+            })
+        };
+        assert!(emphasized("30;"), "removed word should be highlighted");
+        assert!(emphasized("60;"), "added word should be highlighted");
+        assert!(!emphasized("timeout"), "shared words stay plain");
+
+        // Unrelated lines are whole-line changes, not word confetti.
+        let rewrite = "@@ -1,1 +1,1 @@\n-alpha beta gamma\n+one two three\n";
+        let rendered = render_diff_with_layout(rewrite, 80, DiffLayout::Unified);
+        assert!(rendered.iter().all(|line| {
+            line.spans
+                .iter()
+                .all(|span| !span.style.add_modifier.contains(Modifier::REVERSED))
+        }));
+    }
+
+    #[test]
+    fn long_unchanged_runs_collapse_and_keep_line_numbers() {
+        let mut diff = String::from("@@ -1,12 +1,12 @@\n");
+        for n in 1..=11 {
+            diff.push_str(&format!(" same {n}\n"));
+        }
+        diff.push_str("-old\n+new\n");
+
+        let text = render_diff_with_layout(&diff, 80, DiffLayout::Unified)
+            .iter()
+            .map(line_text)
+            .collect::<Vec<_>>();
+        assert!(
+            text.iter().any(|line| line.contains("5 unchanged lines")),
+            "{text:?}"
+        );
+        assert!(
+            !text.iter().any(|line| line.ends_with("same 6")),
+            "{text:?}"
+        );
+        assert!(text.iter().any(|line| line.contains("  11   11   same 11")));
+        assert!(text.iter().any(|line| line.contains("  12      - old")));
+        assert!(text.iter().any(|line| line.contains("       12 + new")));
+    }
+
+    #[test]
+    fn wide_areas_render_old_and_new_side_by_side() {
+        let diff = "@@ -1,2 +1,2 @@\n keep\n-old line\n+new line\n";
+        assert_eq!(DiffLayout::for_width(80), DiffLayout::Unified);
+        assert_eq!(DiffLayout::for_width(140), DiffLayout::SideBySide);
+
+        let rendered = render_diff(diff, 140);
+        for line in rendered
+            .iter()
+            .filter(|line| line_text(line).contains('\u{2502}'))
+        {
+            assert_eq!(line.width(), 140, "{:?}", line_text(line));
+        }
+        let text = rendered.iter().map(line_text).collect::<Vec<_>>();
+        let row = text
+            .iter()
+            .find(|line| line.contains("- old line"))
+            .expect("removed row");
+        assert!(row.contains("+ new line"), "{row:?}");
+        assert!(text.iter().any(|line| line.matches("keep").count() == 2));
+    }
+
     #[test]
     fn wrap_text_breaks_overlong_cjk_runs() {
         let text = "这是一个非常长的中文字符串".repeat(10);
@@ -0,0 +1,193 @@
+//! Masked prompt for a variable a shell command reported missing.
+//!
+//! The typed value goes straight into the sealed part of the session
+//! environment (see `tools::session_env`): it is injected into later shell
+//! commands, masked in their output, and never shown in the transcript or
+//! sent to the model.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, Widget, Wrap};
+use zeroize::Zeroizing;
+
+use crate::palette;
+use crate::tui::views::{ModalKind, ModalView, ViewAction, ViewEvent};
+
+/// A value typed into the prompt. `Debug` never prints it.
+#[derive(Clone)]
+pub struct PromptedValue(Zeroizing<String>);
+
+impl PromptedValue {
+    #[must_use]
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for PromptedValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PromptedValue(***)")
+    }
+}
+
+pub struct EnvPromptView {
+    key: String,
+    tool: String,
+    value: Zeroizing<String>,
+}
+
+impl EnvPromptView {
+    #[must_use]
+    pub fn new(key: String, tool: String) -> Self {
+        Self {
+            key,
+            tool,
+            value: Zeroizing::new(String::new()),
+        }
+    }
+
+    fn submit(&mut self) -> ViewAction {
+        if self.value.is_empty() {
+            return ViewAction::None;
+        }
+        let value = std::mem::replace(&mut self.value, Zeroizing::new(String::new()));
+        ViewAction::EmitAndClose(ViewEvent::SessionEnvProvided {
+            key: self.key.clone(),
+            value: PromptedValue(value),
+        })
+    }
+}
+
+impl ModalView for EnvPromptView {
+    fn kind(&self) -> ModalKind {
+        ModalKind::EnvPrompt
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+        match key.code {
+            KeyCode::Esc => ViewAction::Close,
+            KeyCode::Enter => self.submit(),
+            KeyCode::Backspace => {
+                self.value.pop();
+                ViewAction::None
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.value.clear();
+                ViewAction::None
+            }
+            KeyCode::Char(ch)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.value.push(ch);
+                ViewAction::None
+            }
+            _ => ViewAction::None,
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) -> bool {
+        self.value.push_str(text.trim_end_matches(['\r', '\n']));
+        true
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let muted = Style::default().fg(palette::TEXT_MUTED);
+        let masked = "•".repeat(self.value.chars().count());
+        let lines = vec![
+            Line::from(Span::styled(
+                format!("`{}` failed because {} is not set", self.tool, self.key),
+                Style::default().fg(palette::TEXT_PRIMARY).bold(),
+            )),
+            Line::from(Span::styled(
+                "The value is kept encrypted for this session only, injected into later shell commands, and masked in their output.",
+                muted,
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(
+                    format!("{} = ", self.key),
+                    Style::default().fg(palette::TEXT_PRIMARY),
+                ),
+                Span::styled(masked, Style::default().fg(palette::DEEPSEEK_SKY)),
+                Span::styled("▏", muted),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Enter", Style::default().fg(palette::DEEPSEEK_SKY).bold()),
+                Span::styled(" set for session", muted),
+                Span::raw("  "),
+                Span::styled("Esc", Style::default().fg(palette::DEEPSEEK_SKY).bold()),
+                Span::styled(" skip", muted),
+            ]),
+        ];
+
+        let height = u16::try_from(lines.len() + 4)
+            .unwrap_or(u16::MAX)
+            .min(area.height);
+        let width = area.width.saturating_sub(4).min(80);
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+        Clear.render(popup_area, buf);
+        Paragraph::new(lines)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .title(Line::from(Span::styled(
+                        " Missing environment variable ",
+                        Style::default().fg(palette::STATUS_WARNING).bold(),
+                    )))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(palette::BORDER_COLOR))
+                    .padding(Padding::horizontal(1)),
+            )
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_value_is_masked_and_emitted_on_enter() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let mut view = EnvPromptView::new("DEPLOY_TOKEN".to_string(), "exec_shell".to_string());
+        assert!(matches!(
+            view.handle_key(key(KeyCode::Enter)),
+            ViewAction::None
+        ));
+        for ch in "abc".chars() {
+            view.handle_key(key(KeyCode::Char(ch)));
+        }
+        assert!(view.handle_paste("def\n"));
+        view.handle_key(key(KeyCode::Backspace));
+
+        let area = Rect::new(0, 0, 80, 12);
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+        let screen: String = buf.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("•••••"), "{screen}");
+        assert!(!screen.contains("abcde"));
+
+        let ViewAction::EmitAndClose(ViewEvent::SessionEnvProvided { key: name, value }) =
+            view.handle_key(key(KeyCode::Enter))
+        else {
+            panic!("enter should submit");
+        };
+        assert_eq!(name, "DEPLOY_TOKEN");
+        assert_eq!(value.expose(), "abcde");
+        assert_eq!(format!("{value:?}"), "PromptedValue(***)");
+    }
+}
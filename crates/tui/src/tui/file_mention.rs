@@ -92,6 +92,8 @@ pub enum ContextReferenceKind {
 pub enum ContextReferenceSource {
     AtMention,
     Attachment,
+    /// Picked by the per-prompt retrieval pass (`/smartcontext`).
+    SmartContext,
 }
 
 // ---------------------------------------------------------------------------
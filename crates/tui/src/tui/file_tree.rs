@@ -1,17 +1,26 @@
-//! File-tree pane — Ctrl+Shift+E toggles a left-side workspace file navigator.
+//! File-tree model and renderer for the workspace navigator.
 //!
-//! Shows the workspace directory tree with expandable directories. Up/Down
-//! navigate, Enter expands/collapses directories or inserts `@path` for files,
-//! Esc closes the pane.
+//! The tree shows in two places: the left pane toggled by Ctrl+Shift+E, and
+//! the right sidebar's `Files` focus (Ctrl+Alt+5). Both share
+//! [`FileTreeState`], which caches directory listings and only re-reads a
+//! directory when its mtime changes, so expand/collapse and post-turn
+//! refreshes stay cheap on large trees. Entries carry `git status` markers
+//! and files the agent wrote this session are highlighted.
+//!
+//! Up/Down navigate, Right/Left expand or collapse, Enter expands/collapses
+//! directories or inserts `@path` for files, Esc closes the pane (or hands
+//! focus back to the composer in the sidebar).
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 use ratatui::{
     Frame,
     layout::Rect,
-    style::Style,
+    style::{Color, Style},
     text::{Line, Span},
     widgets::{Block, Paragraph, Wrap},
 };
@@ -34,7 +43,75 @@ pub struct FileTreeEntry {
     pub expanded: bool,
 }
 
-/// Mutable state for the file-tree pane.
+/// Working-tree state reported by `git status` for one path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitMarker {
+    Modified,
+    Added,
+    Deleted,
+    Renamed,
+    Untracked,
+    Conflicted,
+}
+
+impl GitMarker {
+    /// Map a porcelain `XY` status pair to a marker.
+    fn from_xy(x: char, y: char) -> Option<Self> {
+        if x == '?' {
+            return Some(Self::Untracked);
+        }
+        if x == 'U' || y == 'U' || (x == 'A' && y == 'A') || (x == 'D' && y == 'D') {
+            return Some(Self::Conflicted);
+        }
+        let code = if y == ' ' { x } else { y };
+        match code {
+            'M' | 'T' => Some(Self::Modified),
+            'A' => Some(Self::Added),
+            'D' => Some(Self::Deleted),
+            'R' | 'C' => Some(Self::Renamed),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn symbol(self) -> char {
+        match self {
+            Self::Modified => 'M',
+            Self::Added => 'A',
+            Self::Deleted => 'D',
+            Self::Renamed => 'R',
+            Self::Untracked => '?',
+            Self::Conflicted => 'U',
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::Modified | Self::Renamed => palette::STATUS_WARNING,
+            Self::Added | Self::Untracked => palette::STATUS_SUCCESS,
+            Self::Deleted | Self::Conflicted => palette::STATUS_ERROR,
+        }
+    }
+}
+
+/// One cached child of a directory.
+#[derive(Debug, Clone)]
+struct ChildEntry {
+    name: String,
+    path: PathBuf,
+    is_dir: bool,
+}
+
+/// Cached listing of one directory, keyed by its mtime at read time.
+#[derive(Debug, Clone)]
+struct CachedDir {
+    modified: Option<SystemTime>,
+    children: Vec<ChildEntry>,
+}
+
+type GitStatusMap = HashMap<PathBuf, GitMarker>;
+
+/// Mutable state for the file-tree pane and sidebar panel.
 #[derive(Debug, Clone)]
 pub struct FileTreeState {
     /// Flat list of visible entries (respects expanded/collapsed state).
@@ -50,36 +127,53 @@ pub struct FileTreeState {
     /// Whether the tree is still building (async initial walk in progress).
     pub is_loading: bool,
     /// Shared cell for async tree-building results (#399 S3).
-    loading_cell: Option<Arc<Mutex<Option<Vec<FileTreeEntry>>>>>,
+    loading_cell: Option<Arc<Mutex<Option<CachedDir>>>>,
+    /// Directory listings read so far, keyed by absolute path.
+    dir_cache: HashMap<PathBuf, CachedDir>,
+    /// `git status` markers keyed by workspace-relative path.
+    git_status: GitStatusMap,
+    /// Workspace-relative directories containing at least one changed path.
+    dirty_dirs: HashSet<PathBuf>,
+    /// Pending background `git status` result.
+    git_cell: Option<Arc<Mutex<Option<GitStatusMap>>>>,
+    /// Workspace-relative files the agent wrote this session.
+    touched: HashSet<PathBuf>,
 }
 
 impl FileTreeState {
     /// Build a fresh tree state by walking `workspace`.
     /// Spawns the initial walk on a background thread (#399 S3).
     pub fn new(workspace: &Path) -> Self {
-        let expanded_dirs = HashSet::new();
         let loading_cell = Arc::new(Mutex::new(None));
         let cell = loading_cell.clone();
         let ws = workspace.to_path_buf();
         crate::utils::spawn_blocking_supervised("file-tree-build", move || {
-            let entries = build_file_tree_inner(&ws, &HashSet::new(), None);
+            let root = read_dir_listing(&ws);
             if let Ok(mut guard) = cell.lock() {
-                *guard = Some(entries);
+                *guard = Some(root);
             }
         });
-        Self {
+        let mut state = Self {
             entries: Vec::new(),
             cursor: 0,
             scroll_offset: 0,
-            expanded_dirs,
+            expanded_dirs: HashSet::new(),
             workspace: workspace.to_path_buf(),
             is_loading: true,
             loading_cell: Some(loading_cell),
-        }
+            dir_cache: HashMap::new(),
+            git_status: HashMap::new(),
+            dirty_dirs: HashSet::new(),
+            git_cell: None,
+            touched: HashSet::new(),
+        };
+        state.refresh_git_status();
+        state
     }
 
     /// Poll for async build results. Call from the render loop.
     pub fn poll_loading(&mut self) {
+        self.poll_git_status();
         if !self.is_loading {
             return;
         }
@@ -90,11 +184,11 @@ impl FileTreeState {
         };
         let mut done = false;
         if let Ok(mut guard) = cell.lock()
-            && let Some(entries) = guard.take()
+            && let Some(root) = guard.take()
         {
-            self.entries = entries;
+            self.dir_cache.insert(self.workspace.clone(), root);
             self.is_loading = false;
-            self.clamp_cursor();
+            self.rebuild();
             done = true;
         }
         if !done {
@@ -103,17 +197,132 @@ impl FileTreeState {
         }
     }
 
-    /// Rebuild the flat entry list from the current `expanded_dirs` set.
+    /// Rebuild the flat entry list from the current `expanded_dirs` set,
+    /// reading only directories that are not cached yet.
     /// When loading is in progress, the rebuild is deferred.
     pub fn rebuild(&mut self) {
         if self.is_loading {
             // Defer rebuild until async load completes
             return;
         }
-        self.entries = build_file_tree_inner(&self.workspace, &self.expanded_dirs, None);
+        let mut entries = Vec::new();
+        flatten_tree(
+            &self.workspace.clone(),
+            0,
+            &self.expanded_dirs,
+            &mut self.dir_cache,
+            &mut entries,
+        );
+        self.entries = entries;
         self.clamp_cursor();
     }
 
+    /// Incremental refresh: drop cached listings whose directory changed on
+    /// disk (or vanished), rebuild from the cache, and re-run `git status`
+    /// in the background.
+    pub fn refresh(&mut self) {
+        self.dir_cache
+            .retain(|dir, cached| dir_modified(dir).is_some_and(|m| Some(m) == cached.modified));
+        self.expanded_dirs.retain(|dir| dir.is_dir());
+        if !self.is_loading && !self.dir_cache.contains_key(&self.workspace) {
+            let root = read_dir_listing(&self.workspace);
+            self.dir_cache.insert(self.workspace.clone(), root);
+        }
+        self.rebuild();
+        self.refresh_git_status();
+    }
+
+    /// Replace the set of agent-written files (workspace-relative or
+    /// absolute paths inside the workspace).
+    pub fn set_touched<I, S>(&mut self, paths: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.touched = paths
+            .into_iter()
+            .filter_map(|raw| {
+                let path = Path::new(raw.as_ref());
+                if path.is_absolute() {
+                    path.strip_prefix(&self.workspace).ok().map(normalize_path)
+                } else {
+                    Some(normalize_path(path))
+                }
+            })
+            .collect();
+    }
+
+    /// Git marker for `entry`, if its file changed.
+    #[must_use]
+    pub fn git_marker(&self, entry: &FileTreeEntry) -> Option<GitMarker> {
+        let rel = self.relative(&entry.path)?;
+        self.git_status.get(&rel).copied()
+    }
+
+    /// True when `entry` is a directory with changed paths beneath it.
+    #[must_use]
+    pub fn has_dirty_children(&self, entry: &FileTreeEntry) -> bool {
+        entry.is_dir
+            && self
+                .relative(&entry.path)
+                .is_some_and(|rel| self.dirty_dirs.contains(&rel))
+    }
+
+    /// True when the agent wrote `entry` (or, for a directory, something
+    /// inside it) this session.
+    #[must_use]
+    pub fn is_touched(&self, entry: &FileTreeEntry) -> bool {
+        let Some(rel) = self.relative(&entry.path) else {
+            return false;
+        };
+        if entry.is_dir {
+            self.touched.iter().any(|path| path.starts_with(&rel))
+        } else {
+            self.touched.contains(&rel)
+        }
+    }
+
+    fn relative(&self, path: &Path) -> Option<PathBuf> {
+        path.strip_prefix(&self.workspace).ok().map(normalize_path)
+    }
+
+    fn refresh_git_status(&mut self) {
+        let cell = Arc::new(Mutex::new(None));
+        let slot = cell.clone();
+        let ws = self.workspace.clone();
+        crate::utils::spawn_blocking_supervised("file-tree-git-status", move || {
+            let status = load_git_status(&ws);
+            if let Ok(mut guard) = slot.lock() {
+                *guard = Some(status);
+            }
+        });
+        self.git_cell = Some(cell);
+    }
+
+    fn poll_git_status(&mut self) {
+        let Some(cell) = self.git_cell.as_ref() else {
+            return;
+        };
+        let status = match cell.lock() {
+            Ok(mut guard) => guard.take(),
+            Err(_) => None,
+        };
+        if let Some(status) = status {
+            self.apply_git_status(status);
+            self.git_cell = None;
+        }
+    }
+
+    fn apply_git_status(&mut self, status: GitStatusMap) {
+        self.dirty_dirs = status
+            .keys()
+            .flat_map(|path| path.ancestors().skip(1))
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .collect();
+        self.git_status = status;
+    }
+
     /// Move the cursor up by one.
     pub fn cursor_up(&mut self) {
         if self.cursor > 0 {
@@ -130,6 +339,46 @@ impl FileTreeState {
         self.clamp_scroll();
     }
 
+    /// Expand the directory under the cursor, or step into it when it is
+    /// already open.
+    pub fn expand(&mut self) {
+        let Some(entry) = self.entries.get(self.cursor) else {
+            return;
+        };
+        if !entry.is_dir {
+            return;
+        }
+        if entry.expanded {
+            self.cursor_down();
+        } else {
+            self.expanded_dirs.insert(normalize_path(&entry.path));
+            self.rebuild();
+        }
+    }
+
+    /// Collapse the directory under the cursor, or jump to the parent
+    /// directory of the current entry.
+    pub fn collapse(&mut self) {
+        let Some(entry) = self.entries.get(self.cursor) else {
+            return;
+        };
+        if entry.is_dir && entry.expanded {
+            self.expanded_dirs.remove(&normalize_path(&entry.path));
+            self.rebuild();
+            return;
+        }
+        let Some(parent) = entry.path.parent().map(Path::to_path_buf) else {
+            return;
+        };
+        if let Some(idx) = self.entries[..self.cursor]
+            .iter()
+            .rposition(|candidate| candidate.is_dir && candidate.path == parent)
+        {
+            self.cursor = idx;
+            self.clamp_scroll();
+        }
+    }
+
     /// Activate the entry under the cursor.
     ///
     /// Returns `Some(path)` when the entry is a file that should be
@@ -177,14 +426,19 @@ impl FileTreeState {
     }
 
     /// Adjust scroll for a given visible height.
-    #[allow(dead_code)]
     pub fn adjust_scroll(&mut self, visible: usize) {
-        if self.cursor < self.scroll_offset {
-            self.scroll_offset = self.cursor;
-        }
-        if visible > 0 && self.cursor >= self.scroll_offset + visible {
-            self.scroll_offset = self.cursor.saturating_add(1).saturating_sub(visible);
-        }
+        self.scroll_offset = scroll_for_cursor(self.scroll_offset, self.cursor, visible);
+    }
+}
+
+/// Scroll offset that keeps `cursor` inside a window of `visible` rows.
+fn scroll_for_cursor(offset: usize, cursor: usize, visible: usize) -> usize {
+    if cursor < offset {
+        cursor
+    } else if visible > 0 && cursor >= offset + visible {
+        cursor.saturating_add(1).saturating_sub(visible)
+    } else {
+        offset
     }
 }
 
@@ -192,25 +446,16 @@ impl FileTreeState {
 // Tree building
 // ---------------------------------------------------------------------------
 
-/// Build the flat visible-entry list.
-///
-/// Walks the workspace directory recursively. Directories in `expanded_dirs`
-/// have their children included; collapsed directories show only the directory
-/// entry itself. Entries are sorted: directories first, then files, each group
-/// alphabetically.
-fn build_file_tree_inner(
-    workspace: &Path,
-    expanded_dirs: &HashSet<PathBuf>,
-    single_root: Option<&Path>,
-) -> Vec<FileTreeEntry> {
-    let mut entries: Vec<FileTreeEntry> = Vec::new();
-
-    // Determine which root to scan.
-    let scan_root = single_root.unwrap_or(workspace);
+fn dir_modified(dir: &Path) -> Option<SystemTime> {
+    std::fs::metadata(dir).and_then(|meta| meta.modified()).ok()
+}
 
-    // Collect children of `scan_root`.
-    let mut children: Vec<(String, PathBuf, bool)> = Vec::new();
-    if let Ok(read_dir) = std::fs::read_dir(scan_root) {
+/// Read one directory's children. Entries are sorted: directories first,
+/// then files, each group alphabetically.
+fn read_dir_listing(dir: &Path) -> CachedDir {
+    let modified = dir_modified(dir);
+    let mut children: Vec<ChildEntry> = Vec::new();
+    if let Ok(read_dir) = std::fs::read_dir(dir) {
         for entry in read_dir.flatten() {
             let path = entry.path();
             // Skip well-known ignored directories.
@@ -223,53 +468,56 @@ fn build_file_tree_inner(
                 Ok(ft) => ft,
                 Err(_) => continue,
             };
-            let is_dir = ft.is_dir();
             let name = path
                 .file_name()
                 .and_then(|n| n.to_str())
                 .map(|n| n.to_string())
                 .unwrap_or_default();
-            children.push((name, path, is_dir));
+            children.push(ChildEntry {
+                name,
+                path,
+                is_dir: ft.is_dir(),
+            });
         }
     }
 
-    // Sort: dirs first, then files, alphabetical within each group.
-    children.sort_by(
-        |(a_name, _, a_dir), (b_name, _, b_dir)| match (a_dir, b_dir) {
-            (true, false) => std::cmp::Ordering::Less,
-            (false, true) => std::cmp::Ordering::Greater,
-            _ => a_name.to_lowercase().cmp(&b_name.to_lowercase()),
-        },
-    );
-
-    // Compute depth for the current level.
-    let depth = if single_root.is_some() {
-        let rel = scan_root.strip_prefix(workspace).unwrap_or(scan_root);
-        rel.components().count()
-    } else {
-        0
-    };
-
-    for (name, path, is_dir) in &children {
-        let norm = normalize_path(path);
-        let is_expanded = *is_dir && expanded_dirs.contains(&norm);
+    children.sort_by(|a, b| match (a.is_dir, b.is_dir) {
+        (true, false) => std::cmp::Ordering::Less,
+        (false, true) => std::cmp::Ordering::Greater,
+        _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+    });
+    CachedDir { modified, children }
+}
 
-        entries.push(FileTreeEntry {
-            name: name.clone(),
-            path: path.clone(),
-            is_dir: *is_dir,
+/// Append the visible entries under `dir` to `out`. Directories in
+/// `expanded_dirs` have their children included; collapsed directories show
+/// only the directory entry itself. Listings come from `cache`, which is
+/// filled on first visit.
+fn flatten_tree(
+    dir: &Path,
+    depth: usize,
+    expanded_dirs: &HashSet<PathBuf>,
+    cache: &mut HashMap<PathBuf, CachedDir>,
+    out: &mut Vec<FileTreeEntry>,
+) {
+    let children = cache
+        .entry(dir.to_path_buf())
+        .or_insert_with(|| read_dir_listing(dir))
+        .children
+        .clone();
+    for child in children {
+        let is_expanded = child.is_dir && expanded_dirs.contains(&normalize_path(&child.path));
+        out.push(FileTreeEntry {
+            name: child.name,
+            path: child.path.clone(),
+            is_dir: child.is_dir,
             depth,
             expanded: is_expanded,
         });
-
-        // If it's an expanded directory, recurse.
         if is_expanded {
-            let sub = build_file_tree_inner(workspace, expanded_dirs, Some(path));
-            entries.extend(sub);
+            flatten_tree(&child.path, depth + 1, expanded_dirs, cache, out);
         }
     }
-
-    entries
 }
 
 /// Normalise a path for use as a HashSet key.
@@ -279,6 +527,64 @@ fn normalize_path(path: &Path) -> PathBuf {
     PathBuf::from_iter(components.iter().map(|c| c.as_os_str()))
 }
 
+/// Run `git status` in `workspace` and key the markers by
+/// workspace-relative path. Returns an empty map outside a repository.
+fn load_git_status(workspace: &Path) -> GitStatusMap {
+    let run = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(workspace)
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+    };
+    let Some(toplevel) = run(&["rev-parse", "--show-toplevel"]) else {
+        return HashMap::new();
+    };
+    let Some(status) = run(&["status", "--porcelain=v1", "-z", "--untracked-files=all"]) else {
+        return HashMap::new();
+    };
+    let toplevel = PathBuf::from(String::from_utf8_lossy(&toplevel.stdout).trim());
+    let toplevel = toplevel.canonicalize().unwrap_or(toplevel);
+    let workspace = workspace
+        .canonicalize()
+        .unwrap_or_else(|_| workspace.to_path_buf());
+    parse_porcelain_status(&String::from_utf8_lossy(&status.stdout))
+        .into_iter()
+        .filter_map(|(path, marker)| {
+            let rel = toplevel
+                .join(path)
+                .strip_prefix(&workspace)
+                .ok()?
+                .to_path_buf();
+            Some((normalize_path(&rel), marker))
+        })
+        .collect()
+}
+
+/// Parse `git status --porcelain=v1 -z` output into repo-relative paths.
+fn parse_porcelain_status(raw: &str) -> Vec<(PathBuf, GitMarker)> {
+    let mut out = Vec::new();
+    let mut records = raw.split('\0');
+    while let Some(record) = records.next() {
+        let mut chars = record.chars();
+        let (Some(x), Some(y), Some(' ')) = (chars.next(), chars.next(), chars.next()) else {
+            continue;
+        };
+        let path = chars.as_str();
+        // Renames and copies carry the original path as the next record.
+        if matches!(x, 'R' | 'C') {
+            records.next();
+        }
+        if let Some(marker) = GitMarker::from_xy(x, y)
+            && !path.is_empty()
+        {
+            out.push((PathBuf::from(path.trim_end_matches('/')), marker));
+        }
+    }
+    out
+}
+
 // ---------------------------------------------------------------------------
 // Rendering
 // ---------------------------------------------------------------------------
@@ -294,6 +600,20 @@ pub fn render_file_tree(
     mode: palette::PaletteMode,
 ) {
     state.poll_loading();
+    state.adjust_scroll(area.height.saturating_sub(3) as usize);
+    draw_file_tree(f, area, state, mode, " Files ", true);
+}
+
+/// Draw `state` without mutating it. `show_cursor` controls the selection
+/// highlight, which the sidebar only shows while it has keyboard focus.
+pub fn draw_file_tree(
+    f: &mut Frame,
+    area: Rect,
+    state: &FileTreeState,
+    mode: palette::PaletteMode,
+    title: &str,
+    show_cursor: bool,
+) {
     if area.width < FILE_TREE_MIN_WIDTH || area.height < 3 {
         return;
     }
@@ -301,8 +621,8 @@ pub fn render_file_tree(
     let content_width = area.width.saturating_sub(4) as usize;
     let visible_rows = area.height.saturating_sub(3) as usize;
 
-    let scroll = state.scroll_offset;
     let max_visible = visible_rows.max(1);
+    let scroll = scroll_for_cursor(state.scroll_offset, state.cursor, max_visible);
 
     let mut lines: Vec<Line<'static>> = Vec::with_capacity(max_visible + 1);
 
@@ -320,7 +640,15 @@ pub fn render_file_tree(
         let render_end = (scroll + max_visible).min(state.entries.len());
         for idx in scroll..render_end {
             let entry = &state.entries[idx];
-            let is_selected = idx == state.cursor;
+            let is_selected = show_cursor && idx == state.cursor;
+
+            // Status column: git marker for files, a dot for directories
+            // with changes below them.
+            let (status, status_color) = match state.git_marker(entry) {
+                Some(marker) => (marker.symbol(), marker.color()),
+                None if state.has_dirty_children(entry) => ('\u{2022}', palette::TEXT_MUTED),
+                None => (' ', palette::TEXT_MUTED),
+            };
 
             // Build the line prefix: indent + expand/collapse marker + icon.
             let indent = "  ".repeat(entry.depth);
@@ -339,17 +667,22 @@ pub fn render_file_tree(
 
             // Build the display text.
             let raw = format!("{indent}{expand_marker}{}", entry.name);
-            let display = truncate_line_to_width(&raw, content_width.max(1));
+            let display = truncate_line_to_width(&raw, content_width.saturating_sub(2).max(1));
 
             let style = if is_selected {
                 Style::default()
                     .fg(palette::SELECTION_TEXT)
                     .bg(palette::SELECTION_BG)
+            } else if state.is_touched(entry) {
+                Style::default().fg(palette::DEEPSEEK_SKY).bold()
             } else {
                 Style::default().fg(palette::TEXT_PRIMARY)
             };
 
-            lines.push(Line::from(Span::styled(display, style)));
+            lines.push(Line::from(vec![
+                Span::styled(format!("{status} "), Style::default().fg(status_color)),
+                Span::styled(display, style),
+            ]));
         }
     }
 
@@ -358,7 +691,7 @@ pub fn render_file_tree(
     let section = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
         Block::default()
             .title(Line::from(Span::styled(
-                " Files ",
+                title.to_string(),
                 Style::default().fg(theme.section_title_color).bold(),
             )))
             .borders(theme.section_borders)
@@ -370,3 +703,106 @@ pub fn render_file_tree(
 
     f.render_widget(section, area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loaded_state(root: &Path) -> FileTreeState {
+        let mut state = FileTreeState::new(root);
+        for _ in 0..200 {
+            state.poll_loading();
+            if !state.is_loading {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        assert!(!state.is_loading, "tree never finished loading");
+        state
+    }
+
+    fn names(state: &FileTreeState) -> Vec<String> {
+        state
+            .entries
+            .iter()
+            .map(|entry| format!("{}{}", "  ".repeat(entry.depth), entry.name))
+            .collect()
+    }
+
+    #[test]
+    fn porcelain_parsing_maps_status_codes_and_skips_rename_sources() {
+        let raw =
+            " M src/lib.rs\0A  new.rs\0R  moved.rs\0old.rs\0?? notes/todo.md\0UU conflict.rs\0";
+        let parsed = parse_porcelain_status(raw);
+        assert_eq!(
+            parsed,
+            vec![
+                (PathBuf::from("src/lib.rs"), GitMarker::Modified),
+                (PathBuf::from("new.rs"), GitMarker::Added),
+                (PathBuf::from("moved.rs"), GitMarker::Renamed),
+                (PathBuf::from("notes/todo.md"), GitMarker::Untracked),
+                (PathBuf::from("conflict.rs"), GitMarker::Conflicted),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn expand_collapse_and_refresh_reuse_the_listing_cache() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).expect("mkdir");
+        std::fs::write(root.join("src/lib.rs"), "").expect("write");
+        std::fs::write(root.join("README.md"), "").expect("write");
+
+        let mut state = loaded_state(root);
+        assert_eq!(names(&state), vec!["src", "README.md"]);
+
+        state.expand();
+        assert_eq!(names(&state), vec!["src", "  lib.rs", "README.md"]);
+        state.expand();
+        assert_eq!(state.cursor, 1, "Right on an open directory steps inside");
+        state.collapse();
+        assert_eq!(state.cursor, 0, "Left on a file jumps to its parent");
+        state.collapse();
+        assert_eq!(names(&state), vec!["src", "README.md"]);
+
+        state.expand();
+        std::fs::write(root.join("src/main.rs"), "").expect("write");
+        // Force a visible mtime change on filesystems with coarse timestamps.
+        let src = std::fs::File::open(root.join("src")).expect("open src");
+        src.set_modified(SystemTime::now() + std::time::Duration::from_secs(5))
+            .expect("touch src");
+        state.refresh();
+        assert_eq!(
+            names(&state),
+            vec!["src", "  lib.rs", "  main.rs", "README.md"]
+        );
+    }
+
+    #[tokio::test]
+    async fn touched_and_git_markers_attach_to_entries_and_parents() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).expect("mkdir");
+        std::fs::write(root.join("src/lib.rs"), "").expect("write");
+        std::fs::write(root.join("README.md"), "").expect("write");
+
+        let mut state = loaded_state(root);
+        state.expand();
+        state.set_touched([root.join("src/lib.rs").to_string_lossy().to_string()]);
+        state.apply_git_status(HashMap::from([(
+            PathBuf::from("src/lib.rs"),
+            GitMarker::Modified,
+        )]));
+
+        let src = state.entries[0].clone();
+        let lib = state.entries[1].clone();
+        let readme = state.entries[2].clone();
+        assert!(state.is_touched(&src));
+        assert!(state.is_touched(&lib));
+        assert!(!state.is_touched(&readme));
+        assert!(state.has_dirty_children(&src));
+        assert_eq!(state.git_marker(&lib), Some(GitMarker::Modified));
+        assert_eq!(state.git_marker(&readme), None);
+    }
+}
@@ -26,6 +26,12 @@
 //!   - if the result == `now`, it's safe to draw immediately.
 //!   - if the result > `now`, the loop should sleep / shorten its poll
 //!     timeout to wake up at exactly that instant.
+//! - `record_render_time(d)` feeds a smoothed per-draw cost back in. When
+//!   drawing is slow (a laggy SSH link blocks the terminal flush), the
+//!   interval stretches to `RENDER_COST_MULTIPLIER` times that cost, capped
+//!   at `MAX_ADAPTIVE_FRAME_INTERVAL`. Stream deltas that arrive inside the
+//!   longer window are appended to the transcript without drawing, so they
+//!   land together in the next frame instead of queueing one redraw each.
 //!
 //! See `crates/tui/src/tui/ui.rs` (`run_app`) for the integration point.
 
@@ -38,6 +44,14 @@ pub const MIN_FRAME_INTERVAL: Duration = Duration::from_nanos(8_333_334);
 /// 30 FPS minimum frame interval (≈33.33ms) used in low-motion mode.
 pub const LOW_MOTION_MIN_FRAME_INTERVAL: Duration = Duration::from_nanos(33_333_333);
 
+/// Upper bound on the render-time-driven interval (4 FPS). Past this the
+/// stream would feel stalled; better to spend the time drawing.
+pub const MAX_ADAPTIVE_FRAME_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The adaptive interval is this many times the smoothed draw cost, which
+/// keeps the terminal busy at most about a third of the time.
+const RENDER_COST_MULTIPLIER: u32 = 2;
+
 /// Remembers the most recent emitted draw, allowing deadlines to be clamped
 /// forward so the next draw never lands sooner than `MIN_FRAME_INTERVAL`
 /// after the last one.
@@ -46,6 +60,8 @@ pub struct FrameRateLimiter {
     last_emitted_at: Option<Instant>,
     /// When true, use the 30 FPS cap instead of 120 FPS.
     low_motion: bool,
+    /// Exponentially smoothed duration of recent draws.
+    render_cost: Option<Duration>,
 }
 
 impl FrameRateLimiter {
@@ -67,6 +83,16 @@ impl FrameRateLimiter {
         self.last_emitted_at = Some(emitted_at);
     }
 
+    /// Records how long the last draw took, including the terminal flush.
+    /// Smoothed (7/8 old, 1/8 new) so one slow frame — a resize, a big
+    /// paste — doesn't throttle the next second of streaming.
+    pub fn record_render_time(&mut self, elapsed: Duration) {
+        self.render_cost = Some(match self.render_cost {
+            None => elapsed,
+            Some(cost) => (cost * 7 + elapsed) / 8,
+        });
+    }
+
     /// `Some(d)` if the next draw must wait `d` from `now`. `None` if a draw
     /// is allowed right now. Used by the render loop to shorten its poll
     /// timeout so it wakes up exactly when drawing is allowed.
@@ -86,11 +112,15 @@ impl FrameRateLimiter {
     }
 
     fn interval(&self) -> Duration {
-        if self.low_motion {
+        let base = if self.low_motion {
             LOW_MOTION_MIN_FRAME_INTERVAL
         } else {
             MIN_FRAME_INTERVAL
-        }
+        };
+        let adaptive = self.render_cost.map_or(Duration::ZERO, |cost| {
+            (cost * RENDER_COST_MULTIPLIER).min(MAX_ADAPTIVE_FRAME_INTERVAL)
+        });
+        base.max(adaptive)
     }
 }
 
@@ -183,4 +213,31 @@ mod tests {
             "expected ~23.33ms remaining, got {remaining:?}"
         );
     }
+
+    #[test]
+    fn slow_renders_stretch_the_interval_up_to_the_cap() {
+        let t0 = Instant::now();
+        let mut limiter = FrameRateLimiter::default();
+
+        // Fast draws leave the 120 FPS cap in charge.
+        limiter.record_render_time(Duration::from_millis(1));
+        limiter.mark_emitted(t0);
+        assert_eq!(limiter.clamp_deadline(t0), t0 + MIN_FRAME_INTERVAL);
+
+        // A slow link: 40 ms draws push the window to ~80 ms.
+        let mut limiter = FrameRateLimiter::default();
+        limiter.record_render_time(Duration::from_millis(40));
+        limiter.mark_emitted(t0);
+        assert_eq!(limiter.clamp_deadline(t0), t0 + Duration::from_millis(80));
+
+        // One outlier barely moves the smoothed cost.
+        limiter.record_render_time(Duration::from_millis(360));
+        assert_eq!(limiter.clamp_deadline(t0), t0 + Duration::from_millis(160));
+
+        // Very slow draws are capped so the stream never stalls.
+        for _ in 0..32 {
+            limiter.record_render_time(Duration::from_secs(1));
+        }
+        assert_eq!(limiter.clamp_deadline(t0), t0 + MAX_ADAPTIVE_FRAME_INTERVAL);
+    }
 }
@@ -306,6 +306,35 @@ impl HistoryCell {
         }
     }
 
+    /// Live render that reuses `markdown`'s already-rendered prefix for a
+    /// streaming assistant cell, so each delta only re-renders the tail of
+    /// the answer. Other cells render as [`Self::lines_with_options`].
+    pub fn lines_with_streaming_markdown(
+        &self,
+        width: u16,
+        options: TranscriptRenderOptions,
+        markdown: &mut markdown_render::StreamingMarkdown,
+    ) -> Vec<Line<'static>> {
+        match self {
+            HistoryCell::Assistant {
+                content,
+                streaming: true,
+            } => {
+                let rendered = markdown.render(
+                    content,
+                    message_content_width(ASSISTANT_GLYPH, width),
+                    message_body_style(),
+                );
+                prefix_message_lines(
+                    ASSISTANT_GLYPH,
+                    assistant_label_style_for(true, options.low_motion),
+                    rendered,
+                )
+            }
+            _ => self.lines_with_options(width, options),
+        }
+    }
+
     /// Render the cell in transcript mode: full content, no caps, no
     /// "Alt+V for details" affordances.
     ///
@@ -549,14 +578,16 @@ pub fn history_cells_from_message(msg: &Message) -> Vec<HistoryCell> {
                         }
                     }
                     "assistant" => {
+                        let text =
+                            crate::self_report::annotate(text).unwrap_or_else(|| text.clone());
                         if let Some(HistoryCell::Assistant { content, .. }) = cells.last_mut() {
                             if !content.is_empty() {
                                 content.push('\n');
                             }
-                            content.push_str(text);
+                            content.push_str(&text);
                         } else {
                             cells.push(HistoryCell::Assistant {
-                                content: text.clone(),
+                                content: text,
                                 streaming: false,
                             });
                         }
@@ -665,6 +696,8 @@ pub struct ExecCell {
     pub duration_ms: Option<u64>,
     pub source: ExecSource,
     pub interaction: Option<String>,
+    /// Name of the persistent shell session this cell streams (`/shells`).
+    pub session: Option<String>,
     /// Cached output summary — avoids re-parsing JSON every frame.
     pub output_summary: Option<String>,
 }
@@ -697,6 +730,20 @@ impl ExecCell {
             low_motion,
         ));
 
+        if let Some(session) = self.session.as_ref() {
+            let hint = if self.status == ToolStatus::Running {
+                format!("{session} · /shells attach {session}")
+            } else {
+                session.clone()
+            };
+            lines.extend(render_compact_kv(
+                "session",
+                &hint,
+                Style::default().fg(palette::TEXT_MUTED),
+                width,
+            ));
+        }
+
         if self.status == ToolStatus::Success && self.source == ExecSource::User {
             lines.extend(render_compact_kv(
                 "source",
@@ -789,6 +836,15 @@ impl ExploringCell {
         ));
 
         for entry in &self.entries {
+            if entry.stale {
+                lines.extend(render_compact_kv(
+                    "stale",
+                    &format!("{} (changed on disk)", entry.label),
+                    Style::default().fg(palette::STATUS_WARNING),
+                    width,
+                ));
+                continue;
+            }
             let prefix = match entry.status {
                 ToolStatus::Running => "live",
                 ToolStatus::Success => "done",
@@ -817,6 +873,10 @@ impl ExploringCell {
 pub struct ExploringEntry {
     pub label: String,
     pub status: ToolStatus,
+    /// Canonical path of the file a `read_file` entry read.
+    pub read_path: Option<std::path::PathBuf>,
+    /// The read file changed on disk since; rendered with a badge.
+    pub stale: bool,
 }
 
 /// Cell for plan updates emitted by the plan tool.
@@ -1055,6 +1115,15 @@ impl ReviewCell {
             ));
         }
 
+        if !output.issues.is_empty() || !output.suggestions.is_empty() {
+            lines.push(Line::from(""));
+            lines.extend(wrap_plain_line(
+                "/review show annotates these findings on the diff",
+                Style::default().fg(palette::TEXT_MUTED),
+                width,
+            ));
+        }
+
         lines
     }
 }
@@ -2196,12 +2265,30 @@ fn render_message(
     content: &str,
     width: u16,
 ) -> Vec<Line<'static>> {
+    let rendered = markdown_render::render_markdown_tagged(
+        content,
+        message_content_width(prefix, width),
+        body_style,
+    );
+    prefix_message_lines(prefix, label_style, rendered)
+}
+
+/// Width left for the message body after the glyph prefix and its gap.
+fn message_content_width(prefix: &str, width: u16) -> u16 {
     let prefix_width = UnicodeWidthStr::width(prefix);
     let prefix_width_u16 = u16::try_from(prefix_width.saturating_add(2)).unwrap_or(u16::MAX);
-    let content_width = usize::from(width.saturating_sub(prefix_width_u16).max(1));
+    width.saturating_sub(prefix_width_u16).max(1)
+}
+
+/// Put the glyph prefix on the first body line and the continuation rail on
+/// the rest.
+fn prefix_message_lines(
+    prefix: &str,
+    label_style: Style,
+    rendered: Vec<markdown_render::RenderedMarkdownLine>,
+) -> Vec<Line<'static>> {
+    let prefix_width = UnicodeWidthStr::width(prefix);
     let mut lines = Vec::new();
-    let rendered =
-        markdown_render::render_markdown_tagged(content, content_width as u16, body_style);
     for (idx, rendered_line) in rendered.into_iter().enumerate() {
         if idx == 0 {
             let mut spans = Vec::new();
@@ -3740,6 +3827,7 @@ mod tests {
             duration_ms: None,
             source: ExecSource::Assistant,
             interaction: None,
+            session: None,
             output_summary: None,
         }));
 
@@ -3957,6 +4045,7 @@ mod tests {
             duration_ms: Some(10),
             source: ExecSource::Assistant,
             interaction: None,
+            session: None,
             output_summary: None,
         };
         let header = &cell.lines_with_motion(80, true)[0];
@@ -3987,6 +4076,7 @@ mod tests {
             duration_ms: None,
             source: ExecSource::Assistant,
             interaction: None,
+            session: None,
             output_summary: None,
         };
 
@@ -4255,6 +4345,7 @@ mod tests {
             duration_ms: Some(42),
             source: ExecSource::Assistant,
             interaction: None,
+            session: None,
             output_summary: None,
         };
 
@@ -4426,6 +4517,7 @@ mod tests {
             duration_ms: Some(120),
             source: ExecSource::Assistant,
             interaction: None,
+            session: None,
             output_summary: None,
         }));
 
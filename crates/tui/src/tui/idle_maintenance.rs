@@ -0,0 +1,314 @@
+//! Opportunistic maintenance while the TUI sits idle.
+//!
+//! Once no turn, compaction, or sub-agent has run and no key has been
+//! pressed for `idle_maintenance_secs`, background jobs run one at a time,
+//! cheapest first: the saved session's picker summary, the `docs_search`
+//! index, the repo map for the current `HEAD`, and — with `auto_compact` on
+//! and context past the warning level — a compaction, so the pre-send
+//! compaction never has to run in front of a turn. Each job runs at most
+//! once per idle stretch.
+//!
+//! The next key press, paste, or click cancels the running job. File jobs
+//! stop at their next checkpoint and discard partial work; an idle
+//! compaction is abandoned through the engine's cancel token and leaves
+//! the history untouched.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::core::engine::{CancelReason, EngineHandle};
+use crate::core::ops::Op;
+use crate::tui::app::App;
+
+/// One kind of background job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleJob {
+    SessionSummary,
+    DocsIndex,
+    RepoMap,
+    Compaction,
+}
+
+impl IdleJob {
+    /// Run order. Compaction is a model call, so it goes last.
+    const ORDER: [Self; 4] = [
+        Self::SessionSummary,
+        Self::DocsIndex,
+        Self::RepoMap,
+        Self::Compaction,
+    ];
+}
+
+#[derive(Debug)]
+struct RunningJob {
+    job: IdleJob,
+    cancel: Arc<AtomicBool>,
+    finished: Arc<AtomicBool>,
+}
+
+/// Idle clock and job bookkeeping, owned by [`App`].
+#[derive(Debug)]
+pub struct IdleMaintenance {
+    idle_after: Option<Duration>,
+    idle_since: Instant,
+    /// Jobs finished or found unnecessary during the current idle stretch.
+    done: Vec<IdleJob>,
+    running: Option<RunningJob>,
+}
+
+impl IdleMaintenance {
+    /// Start the idle clock at `now`; `idle_secs == 0` disables maintenance.
+    #[must_use]
+    pub fn new(idle_secs: u64, now: Instant) -> Self {
+        let mut maintenance = Self {
+            idle_after: None,
+            idle_since: now,
+            done: Vec::new(),
+            running: None,
+        };
+        maintenance.set_idle_secs(idle_secs);
+        maintenance
+    }
+
+    pub fn set_idle_secs(&mut self, idle_secs: u64) {
+        self.idle_after = (idle_secs > 0).then(|| Duration::from_secs(idle_secs));
+    }
+
+    #[must_use]
+    pub fn idle_secs(&self) -> u64 {
+        self.idle_after.map_or(0, |after| after.as_secs())
+    }
+
+    /// A key press, paste, or click: cancel the running job and restart the
+    /// idle clock. Returns true when the cancelled job was an idle
+    /// compaction, which the caller abandons through the engine.
+    pub fn user_activity(&mut self, now: Instant) -> bool {
+        self.idle_since = now;
+        self.done.clear();
+        let Some(running) = self.running.take() else {
+            return false;
+        };
+        running.cancel.store(true, Ordering::Relaxed);
+        running.job == IdleJob::Compaction
+    }
+
+    /// The engine reported the end of a compaction.
+    pub fn compaction_finished(&mut self) {
+        if let Some(running) = &self.running
+            && running.job == IdleJob::Compaction
+        {
+            running.finished.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// True while an idle compaction this module started is in flight.
+    #[must_use]
+    pub fn is_compacting(&self) -> bool {
+        self.running
+            .as_ref()
+            .is_some_and(|running| running.job == IdleJob::Compaction)
+    }
+
+    /// The next job to start, or `None` when something else keeps the UI
+    /// `busy`, the idle delay has not passed, a job is still running, or
+    /// every job has had its turn. Jobs `applies` rejects count as done for
+    /// this stretch. The returned job is marked running; its cancel and
+    /// finished flags are returned with it.
+    fn next_job(
+        &mut self,
+        now: Instant,
+        busy: bool,
+        applies: impl Fn(IdleJob) -> bool,
+    ) -> Option<(IdleJob, Arc<AtomicBool>, Arc<AtomicBool>)> {
+        if busy {
+            self.idle_since = now;
+            self.done.clear();
+            return None;
+        }
+        let idle_after = self.idle_after?;
+        if let Some(running) = &self.running {
+            if !running.finished.load(Ordering::Relaxed) {
+                return None;
+            }
+            self.done.push(running.job);
+            self.running = None;
+        }
+        if now.saturating_duration_since(self.idle_since) < idle_after {
+            return None;
+        }
+        for job in IdleJob::ORDER {
+            if self.done.contains(&job) {
+                continue;
+            }
+            if !applies(job) {
+                self.done.push(job);
+                continue;
+            }
+            let cancel = Arc::new(AtomicBool::new(false));
+            let finished = Arc::new(AtomicBool::new(false));
+            self.running = Some(RunningJob {
+                job,
+                cancel: Arc::clone(&cancel),
+                finished: Arc::clone(&finished),
+            });
+            return Some((job, cancel, finished));
+        }
+        None
+    }
+}
+
+/// Forward user input to the idle clock, abandoning an idle compaction in
+/// the engine if one was running.
+pub(super) fn note_user_activity(app: &mut App, engine_handle: &EngineHandle, now: Instant) {
+    if app.idle_maintenance.user_activity(now) {
+        engine_handle.cancel_with_reason(CancelReason::Preempted);
+    }
+}
+
+/// Start the next due job, if any. `busy` covers turns and sub-agents;
+/// `compaction_due` says whether context is large enough to compact early.
+pub(super) async fn tick(
+    app: &mut App,
+    engine_handle: &EngineHandle,
+    config: &Config,
+    now: Instant,
+    busy: bool,
+    compaction_due: bool,
+) {
+    let busy = busy || (app.is_compacting && !app.idle_maintenance.is_compacting());
+    let session_id = app.current_session_id.clone();
+    let has_session = session_id.is_some();
+    let repo_map = config.repo_map_tokens().is_some();
+    let compaction = compaction_due && app.auto_compact && !app.offline_mode;
+    let Some((job, cancel, finished)) = app.idle_maintenance.next_job(now, busy, |job| match job {
+        IdleJob::SessionSummary => has_session,
+        IdleJob::DocsIndex => true,
+        IdleJob::RepoMap => repo_map,
+        IdleJob::Compaction => compaction,
+    }) else {
+        return;
+    };
+
+    tracing::debug!(?job, "idle maintenance job started");
+    if job == IdleJob::Compaction {
+        if engine_handle.send(Op::IdleCompactContext).await.is_err() {
+            app.idle_maintenance.compaction_finished();
+        }
+        return;
+    }
+    let workspace = app.workspace.clone();
+    crate::utils::spawn_blocking_supervised("idle-maintenance", move || {
+        run_file_job(job, &workspace, session_id.as_deref(), &cancel);
+        finished.store(true, Ordering::Relaxed);
+    });
+}
+
+fn run_file_job(job: IdleJob, workspace: &Path, session_id: Option<&str>, cancel: &AtomicBool) {
+    match job {
+        IdleJob::SessionSummary => {
+            let Some(id) = session_id else {
+                return;
+            };
+            let Ok(manager) = crate::session_manager::SessionManager::default_location() else {
+                return;
+            };
+            let needs_summary = manager
+                .load_session(id)
+                .is_ok_and(|saved| !crate::session_summary::has_current_summary(&saved));
+            if needs_summary
+                && !cancel.load(Ordering::Relaxed)
+                && let Err(err) = crate::session_summary::store_summary(&manager, id)
+            {
+                tracing::debug!("idle session summary failed: {err}");
+            }
+        }
+        IdleJob::DocsIndex => {
+            if let Err(err) = crate::tools::docs_search::refresh_index_until(workspace, cancel) {
+                tracing::debug!("idle docs index refresh stopped: {err}");
+            }
+        }
+        IdleJob::RepoMap => {
+            crate::repo_map::prewarm(workspace, cancel);
+        }
+        IdleJob::Compaction => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all(_: IdleJob) -> bool {
+        true
+    }
+
+    #[test]
+    fn jobs_run_in_order_once_idle_and_activity_cancels_them() {
+        let start = Instant::now();
+        let later = start + Duration::from_secs(31);
+        let mut idle = IdleMaintenance::new(30, start);
+
+        assert!(
+            idle.next_job(start + Duration::from_secs(5), false, all)
+                .is_none()
+        );
+        // A busy tick restarts the clock.
+        assert!(
+            idle.next_job(start + Duration::from_secs(20), true, all)
+                .is_none()
+        );
+        assert!(idle.next_job(later, false, all).is_none());
+
+        let later = start + Duration::from_secs(51);
+        let (job, cancel, finished) = idle
+            .next_job(later, false, |job| job != IdleJob::SessionSummary)
+            .unwrap();
+        assert_eq!(job, IdleJob::DocsIndex);
+        // One job at a time.
+        assert!(idle.next_job(later, false, all).is_none());
+        finished.store(true, Ordering::Relaxed);
+        let (job, cancel_repo, _) = idle.next_job(later, false, all).unwrap();
+        assert_eq!(job, IdleJob::RepoMap);
+        assert!(!cancel.load(Ordering::Relaxed));
+
+        // Input cancels the running job; the next stretch starts over.
+        assert!(!idle.user_activity(later));
+        assert!(cancel_repo.load(Ordering::Relaxed));
+        let again = later + Duration::from_secs(30);
+        let (job, _, _) = idle.next_job(again, false, all).unwrap();
+        assert_eq!(job, IdleJob::SessionSummary);
+    }
+
+    #[test]
+    fn compaction_runs_last_and_reports_cancellation() {
+        let start = Instant::now();
+        let later = start + Duration::from_secs(10);
+        let mut idle = IdleMaintenance::new(10, start);
+        let only_compaction = |job| job == IdleJob::Compaction;
+
+        let (job, _, _) = idle.next_job(later, false, only_compaction).unwrap();
+        assert_eq!(job, IdleJob::Compaction);
+        assert!(idle.is_compacting());
+        idle.compaction_finished();
+        assert!(idle.next_job(later, false, only_compaction).is_none());
+        assert!(!idle.is_compacting());
+
+        let resumed = later + Duration::from_secs(1);
+        assert!(!idle.user_activity(resumed));
+        let (_, cancel, _) = idle
+            .next_job(resumed + Duration::from_secs(10), false, only_compaction)
+            .unwrap();
+        assert!(idle.user_activity(resumed + Duration::from_secs(11)));
+        assert!(cancel.load(Ordering::Relaxed));
+
+        idle.set_idle_secs(0);
+        assert_eq!(idle.idle_secs(), 0);
+        assert!(
+            idle.next_job(resumed + Duration::from_secs(60), false, all)
+                .is_none()
+        );
+    }
+}
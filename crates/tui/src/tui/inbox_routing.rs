@@ -0,0 +1,171 @@
+//! Feeding finished background tasks and sub-agents into the `/inbox`, and
+//! running the inbox modal's actions.
+
+use crate::inbox::{InboxEntry, InboxSource};
+use crate::task_manager::{SharedTaskManager, TaskStatus, TaskSummary};
+use crate::tools::subagent::{SubAgentResult, SubAgentStatus};
+use crate::tui::app::App;
+use crate::tui::inbox_view::InboxView;
+use crate::tui::views::InboxAction;
+
+/// Add `entry` to the inbox and persist it.
+fn record_inbox_entry(app: &mut App, entry: InboxEntry) {
+    app.inbox.push(entry);
+    save_inbox(app);
+}
+
+fn save_inbox(app: &mut App) {
+    if let Err(err) = app.inbox.save(&app.workspace) {
+        tracing::warn!(target: "inbox", "failed to save inbox: {err}");
+    }
+    refresh_inbox_view(app);
+    app.needs_redraw = true;
+}
+
+/// Watch queued and running tasks, and add the ones that since finished to
+/// the inbox. Tasks already finished before this session are not collected.
+pub(super) async fn collect_finished_tasks(
+    app: &mut App,
+    task_manager: &SharedTaskManager,
+    tasks: &[TaskSummary],
+) {
+    let mut finished = Vec::new();
+    for task in tasks {
+        if matches!(task.status, TaskStatus::Queued | TaskStatus::Running) {
+            app.inbox_watched_tasks.insert(task.id.clone());
+        } else if app.inbox_watched_tasks.remove(&task.id) {
+            finished.push(task.id.clone());
+        }
+    }
+    app.inbox_watched_tasks
+        .retain(|id| tasks.iter().any(|task| &task.id == id));
+    for id in finished {
+        let Ok(task) = task_manager.get_task(&id).await else {
+            continue;
+        };
+        let output = task_manager.task_output(&task);
+        let body = output
+            .as_deref()
+            .map(str::trim)
+            .filter(|output| !output.is_empty())
+            .or(task.error.as_deref())
+            .unwrap_or_default();
+        record_inbox_entry(
+            app,
+            InboxEntry::new(
+                task.id.clone(),
+                InboxSource::Task,
+                &task.prompt,
+                task.status.label(),
+                body,
+            ),
+        );
+    }
+}
+
+/// Add sub-agents that completed since the last listing. `AgentComplete`
+/// only carries a summary, so the full result is taken from the agent list
+/// the UI requests right after it.
+pub(super) fn collect_finished_subagents(app: &mut App, agents: &[SubAgentResult]) {
+    for agent in agents {
+        if matches!(agent.status, SubAgentStatus::Running)
+            || !app.inbox_pending_agents.remove(&agent.agent_id)
+        {
+            continue;
+        }
+        let (status, error) = match &agent.status {
+            SubAgentStatus::Completed => ("completed", None),
+            SubAgentStatus::Interrupted(reason) => ("interrupted", Some(reason.as_str())),
+            SubAgentStatus::Failed(reason) => ("failed", Some(reason.as_str())),
+            SubAgentStatus::Cancelled | SubAgentStatus::Running => ("canceled", None),
+        };
+        let body = agent
+            .result
+            .as_deref()
+            .filter(|result| !result.trim().is_empty())
+            .or(error)
+            .unwrap_or_default();
+        let title = if agent.assignment.objective.trim().is_empty() {
+            agent.name.as_str()
+        } else {
+            agent.assignment.objective.as_str()
+        };
+        record_inbox_entry(
+            app,
+            InboxEntry::new(
+                agent.agent_id.clone(),
+                InboxSource::SubAgent,
+                title,
+                status,
+                body,
+            ),
+        );
+    }
+}
+
+pub(super) fn handle_inbox_action(app: &mut App, action: InboxAction) {
+    match action {
+        InboxAction::MarkRead { id } => {
+            if app.inbox.mark_read(&id) {
+                save_inbox(app);
+            }
+        }
+        InboxAction::Insert { id } => {
+            let Some(text) = app.inbox.get(&id).map(InboxEntry::to_markdown) else {
+                return;
+            };
+            app.inbox.mark_read(&id);
+            save_inbox(app);
+            if !app.input.trim().is_empty() {
+                app.input.push_str("\n\n");
+            }
+            app.input.push_str(&text);
+            app.cursor_position = app.input.chars().count();
+            app.status_message =
+                Some("Inbox result inserted into the composer. Edit it or press Enter.".into());
+        }
+        InboxAction::Save { id } => {
+            let Some(entry) = app.inbox.get(&id).cloned() else {
+                return;
+            };
+            let relative = entry.export_path();
+            let path = app.workspace.join(&relative);
+            let written = path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|()| std::fs::write(&path, entry.to_markdown()));
+            let notice = match written {
+                Ok(()) => format!("Saved to {}", relative.display()),
+                Err(err) => format!("Save failed: {err}"),
+            };
+            app.inbox.mark_read(&id);
+            save_inbox(app);
+            update_inbox_view(app, |view| view.set_notice(notice));
+        }
+        InboxAction::Dismiss { id } => {
+            if app.inbox.dismiss(&id).is_some() {
+                save_inbox(app);
+                update_inbox_view(app, |view| view.set_notice(format!("Dismissed {id}")));
+            }
+        }
+    }
+}
+
+/// Push the current entries into the inbox modal when it is open.
+fn refresh_inbox_view(app: &mut App) {
+    let entries = app.inbox.entries.clone();
+    update_inbox_view(app, |view| view.set_entries(entries));
+}
+
+/// Apply `update` to the inbox modal when it is the top view.
+fn update_inbox_view(app: &mut App, update: impl FnOnce(&mut InboxView)) {
+    // Pop+push for the same aliasing reason as the task manager modal.
+    let Some(mut view) = app.view_stack.pop() else {
+        return;
+    };
+    if let Some(typed) = view.as_any_mut().downcast_mut::<InboxView>() {
+        update(typed);
+        app.needs_redraw = true;
+    }
+    app.view_stack.push_boxed(view);
+}
@@ -0,0 +1,322 @@
+//! `/inbox` modal: finished background tasks and sub-agents with per-entry
+//! actions.
+//!
+//! The view holds a snapshot of the [`InboxStore`] entries. Inserting,
+//! saving, dismissing, and marking an entry read are emitted as
+//! [`InboxAction`]s; the host applies them to `App::inbox` and pushes the
+//! updated entries back through [`InboxView::set_entries`].
+//!
+//! [`InboxStore`]: crate::inbox::InboxStore
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Widget, Wrap},
+};
+
+use crate::inbox::InboxEntry;
+use crate::palette;
+use crate::tui::views::{InboxAction, ModalKind, ModalView, ViewAction, ViewEvent};
+
+/// List rows shown above the preview.
+const LIST_ROWS: usize = 8;
+
+pub struct InboxView {
+    entries: Vec<InboxEntry>,
+    selected: usize,
+    notice: Option<String>,
+}
+
+impl InboxView {
+    #[must_use]
+    pub fn new(entries: Vec<InboxEntry>) -> Self {
+        Self {
+            entries,
+            selected: 0,
+            notice: None,
+        }
+    }
+
+    /// Replace the entries, keeping the cursor on the same entry when it is
+    /// still listed.
+    pub fn set_entries(&mut self, entries: Vec<InboxEntry>) {
+        let selected_id = self.selected_entry().map(|entry| entry.id.clone());
+        self.entries = entries;
+        self.selected = selected_id
+            .and_then(|id| self.entries.iter().position(|entry| entry.id == id))
+            .unwrap_or(self.selected)
+            .min(self.entries.len().saturating_sub(1));
+    }
+
+    /// One-line result of the last action, shown above the list.
+    pub fn set_notice(&mut self, notice: impl Into<String>) {
+        self.notice = Some(notice.into());
+    }
+
+    fn selected_entry(&self) -> Option<&InboxEntry> {
+        self.entries.get(self.selected)
+    }
+
+    fn emit_for_selected(&self, build: impl FnOnce(String) -> InboxAction) -> ViewAction {
+        match self.selected_entry() {
+            Some(entry) => ViewAction::Emit(ViewEvent::InboxRequested {
+                action: build(entry.id.clone()),
+            }),
+            None => ViewAction::None,
+        }
+    }
+
+    /// Move the cursor; landing on an unread entry marks it read.
+    fn select(&mut self, index: usize) -> ViewAction {
+        self.selected = index;
+        match self.selected_entry() {
+            Some(entry) if entry.unread => {
+                self.emit_for_selected(|id| InboxAction::MarkRead { id })
+            }
+            _ => ViewAction::None,
+        }
+    }
+
+    fn row(&self, idx: usize, entry: &InboxEntry, width: usize) -> Line<'static> {
+        let is_selected = idx == self.selected;
+        let base = if is_selected {
+            Style::default()
+                .fg(palette::SELECTION_TEXT)
+                .bg(palette::SELECTION_BG)
+        } else {
+            Style::default().fg(palette::TEXT_PRIMARY)
+        };
+        let status_color = match entry.status.as_str() {
+            "completed" => palette::STATUS_SUCCESS,
+            "failed" | "canceled" | "interrupted" => palette::DEEPSEEK_RED,
+            _ => palette::STATUS_WARNING,
+        };
+        let status_style = if is_selected {
+            base.add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(status_color)
+        };
+        let pointer = if is_selected { ">" } else { " " };
+        let dot = if entry.unread { "●" } else { " " };
+        let lead = format!(" {pointer} {dot} ");
+        let status = format!("{:<10} ", entry.status);
+        let source = format!("{:<10} ", entry.source.label());
+        let used = lead.chars().count() + status.chars().count() + source.chars().count();
+        let title = crate::utils::truncate_with_ellipsis(
+            &entry.title,
+            width.saturating_sub(used).max(1),
+            "…",
+        );
+        let title_style = if entry.unread {
+            base.add_modifier(Modifier::BOLD)
+        } else {
+            base
+        };
+        Line::from(vec![
+            Span::styled(lead, base.fg(palette::DEEPSEEK_SKY)),
+            Span::styled(status, status_style),
+            Span::styled(source, base.fg(palette::TEXT_MUTED)),
+            Span::styled(title, title_style),
+        ])
+    }
+}
+
+impl ModalView for InboxView {
+    fn kind(&self) -> ModalKind {
+        ModalKind::Inbox
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return ViewAction::None;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => ViewAction::Close,
+            KeyCode::Up | KeyCode::Char('k') => self.select(self.selected.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last = self.entries.len().saturating_sub(1);
+                self.select((self.selected + 1).min(last))
+            }
+            KeyCode::Enter | KeyCode::Char('i') => match self.selected_entry() {
+                Some(entry) => ViewAction::EmitAndClose(ViewEvent::InboxRequested {
+                    action: InboxAction::Insert {
+                        id: entry.id.clone(),
+                    },
+                }),
+                None => ViewAction::None,
+            },
+            KeyCode::Char('s') => self.emit_for_selected(|id| InboxAction::Save { id }),
+            KeyCode::Char('d') | KeyCode::Delete => {
+                self.emit_for_selected(|id| InboxAction::Dismiss { id })
+            }
+            _ => ViewAction::None,
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let popup_width = 100.min(area.width.saturating_sub(4));
+        let popup_height = 28.min(area.height.saturating_sub(4));
+        if popup_width == 0 || popup_height == 0 {
+            return;
+        }
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(popup_width)) / 2,
+            y: area.y + (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        Clear.render(popup_area, buf);
+
+        let hint = |key: &'static str, label: &'static str| {
+            [
+                Span::styled(key, Style::default().fg(palette::TEXT_MUTED)),
+                Span::raw(label),
+            ]
+        };
+        let unread = self.entries.iter().filter(|entry| entry.unread).count();
+        let title = format!(" Inbox ({} · {unread} unread) ", self.entries.len());
+        let block = Block::default()
+            .title(Line::from(Span::styled(
+                title,
+                Style::default()
+                    .fg(palette::DEEPSEEK_SKY)
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .title_bottom(Line::from(
+                [
+                    hint(" Enter ", "insert "),
+                    hint(" s ", "save "),
+                    hint(" d ", "dismiss "),
+                    hint(" Esc ", "close "),
+                ]
+                .concat(),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(palette::BORDER_COLOR))
+            .style(Style::default().bg(palette::DEEPSEEK_INK))
+            .padding(Padding::horizontal(1));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let width = usize::from(inner.width);
+        let mut lines = Vec::new();
+        if let Some(notice) = self.notice.as_deref() {
+            lines.push(Line::from(Span::styled(
+                crate::utils::truncate_with_ellipsis(notice, width.max(1), "…"),
+                Style::default().fg(palette::DEEPSEEK_SKY),
+            )));
+        }
+        if self.entries.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No finished background work. Task and sub-agent results land here.",
+                Style::default().fg(palette::TEXT_MUTED),
+            )));
+            Paragraph::new(lines).render(inner, buf);
+            return;
+        }
+
+        let visible = LIST_ROWS.min(self.entries.len());
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+        for (idx, entry) in self.entries.iter().enumerate().skip(first).take(visible) {
+            lines.push(self.row(idx, entry, width));
+        }
+        let list_height = u16::try_from(lines.len()).unwrap_or(u16::MAX);
+        Paragraph::new(lines).render(inner, buf);
+
+        // Preview of the selected entry below the list.
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        let preview_area = Rect {
+            y: inner.y + list_height + 1,
+            height: inner.height.saturating_sub(list_height + 1),
+            ..inner
+        };
+        if preview_area.height == 0 {
+            return;
+        }
+        let mut preview = vec![Line::from(Span::styled(
+            format!(
+                "{} {} · {}",
+                entry.source.label(),
+                entry.id,
+                entry.finished_at.get(..16).unwrap_or(&entry.finished_at)
+            ),
+            Style::default().fg(palette::TEXT_MUTED),
+        ))];
+        let body = if entry.body.is_empty() {
+            "(no output)"
+        } else {
+            entry.body.as_str()
+        };
+        preview.extend(
+            body.lines()
+                .take(usize::from(preview_area.height))
+                .map(|line| Line::from(line.to_string())),
+        );
+        Paragraph::new(preview)
+            .wrap(Wrap { trim: false })
+            .render(preview_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inbox::InboxSource;
+
+    fn entry(id: &str, unread: bool) -> InboxEntry {
+        let mut entry = InboxEntry::new(id, InboxSource::Task, "Check", "completed", "ok");
+        entry.unread = unread;
+        entry
+    }
+
+    fn requested(action: ViewAction) -> Option<InboxAction> {
+        match action {
+            ViewAction::Emit(ViewEvent::InboxRequested { action })
+            | ViewAction::EmitAndClose(ViewEvent::InboxRequested { action }) => Some(action),
+            _ => None,
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn keys_act_on_the_selected_entry_and_mark_it_read() {
+        let mut view = InboxView::new(vec![entry("a", false), entry("b", true)]);
+
+        assert_eq!(
+            requested(view.handle_key(key(KeyCode::Down))),
+            Some(InboxAction::MarkRead { id: "b".into() })
+        );
+        assert_eq!(
+            requested(view.handle_key(key(KeyCode::Char('s')))),
+            Some(InboxAction::Save { id: "b".into() })
+        );
+        // Moving onto an already-read entry emits nothing.
+        assert_eq!(requested(view.handle_key(key(KeyCode::Up))), None);
+        assert!(matches!(
+            view.handle_key(key(KeyCode::Enter)),
+            ViewAction::EmitAndClose(ViewEvent::InboxRequested {
+                action: InboxAction::Insert { .. }
+            })
+        ));
+
+        view.set_entries(vec![entry("b", false)]);
+        assert_eq!(
+            requested(view.handle_key(key(KeyCode::Char('d')))),
+            Some(InboxAction::Dismiss { id: "b".into() })
+        );
+    }
+}
@@ -11,6 +11,9 @@
 //! 2. New contributors have one place to look when answering "which keys are
 //!    bound, and where do they go?"
 //!
+//! Users can remap a subset of these through `[keys]`; see `tui/keymap.rs`,
+//! which rewrites remapped chords back to the defaults listed here.
+//!
 //! When you add or change a binding in `ui.rs`, **add or update the matching
 //! entry here**. The compile-only side-effect of forgetting is a stale help
 //! screen; there is no runtime crash, so the discipline lives in code review.
@@ -210,6 +213,11 @@ pub const KEYBINDINGS: &[KeybindingEntry] = &[
         description_id: crate::localization::MessageId::KbSelectedDetails,
         section: KeybindingSection::Submission,
     },
+    KeybindingEntry {
+        chord: "m",
+        description_id: crate::localization::MessageId::KbBookmarkCell,
+        section: KeybindingSection::Submission,
+    },
     KeybindingEntry {
         chord: "Alt+V",
         description_id: crate::localization::MessageId::KbToolDetailsPager,
@@ -221,7 +229,7 @@ pub const KEYBINDINGS: &[KeybindingEntry] = &[
         section: KeybindingSection::Submission,
     },
     KeybindingEntry {
-        chord: "Ctrl+T",
+        chord: "Alt+T",
         description_id: crate::localization::MessageId::KbLiveTranscript,
         section: KeybindingSection::Submission,
     },
@@ -251,6 +259,11 @@ pub const KEYBINDINGS: &[KeybindingEntry] = &[
         description_id: crate::localization::MessageId::KbFocusSidebar,
         section: KeybindingSection::Modes,
     },
+    KeybindingEntry {
+        chord: "Alt+O",
+        description_id: crate::localization::MessageId::KbToolOutputPane,
+        section: KeybindingSection::Modes,
+    },
     KeybindingEntry {
         chord: "Ctrl+X",
         description_id: crate::localization::MessageId::KbTogglePlanAgent,
@@ -262,6 +275,21 @@ pub const KEYBINDINGS: &[KeybindingEntry] = &[
         description_id: crate::localization::MessageId::KbSessionPicker,
         section: KeybindingSection::Sessions,
     },
+    KeybindingEntry {
+        chord: "Ctrl+T",
+        description_id: crate::localization::MessageId::KbNewSessionTab,
+        section: KeybindingSection::Sessions,
+    },
+    KeybindingEntry {
+        chord: "Ctrl+1..9",
+        description_id: crate::localization::MessageId::KbSwitchSessionTab,
+        section: KeybindingSection::Sessions,
+    },
+    KeybindingEntry {
+        chord: "Alt+W",
+        description_id: crate::localization::MessageId::KbCloseSessionTab,
+        section: KeybindingSection::Sessions,
+    },
     // --- Clipboard ---
     KeybindingEntry {
         chord: "Ctrl+V",
@@ -0,0 +1,569 @@
+//! User-remappable keybindings (`[keys]` in `config.toml`).
+//!
+//! The key handlers in `tui/ui.rs` match on fixed default chords and depend
+//! heavily on context (composer contents, open menus, modals). Rather than
+//! threading a lookup through every handler, the keymap sits in front of
+//! them: a configured chord is rewritten into its action's default chord
+//! before the event reaches `ui.rs`, so a remapped key behaves exactly like
+//! the key it stands in for.
+//!
+//! Rules:
+//! - Actions missing from `[keys]` keep their defaults.
+//! - Configured chords are added on top of the default, which keeps working
+//!   unless another action claims it.
+//! - Plain printable keys (with or without Shift) are rejected so a binding
+//!   can never swallow typed text, and `Ctrl+C`, `Esc` and `Enter` are
+//!   reserved.
+//!
+//! `/keys` renders [`Keymap::describe`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::config::KeyChordsToml;
+
+/// One remappable action and the chord `ui.rs` handles for it.
+#[derive(Debug, Clone, Copy)]
+pub struct KeyAction {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+/// Every action `[keys]` can remap, in `/keys` display order.
+pub const KEY_ACTIONS: &[KeyAction] = &[
+    KeyAction {
+        name: "open_palette",
+        default: "ctrl+k",
+        description: "Command palette (kills to end of line while typing)",
+    },
+    KeyAction {
+        name: "open_help",
+        default: "f1",
+        description: "Toggle the help overlay",
+    },
+    KeyAction {
+        name: "cycle_mode",
+        default: "tab",
+        description: "Complete, queue, or cycle Plan/Agent/YOLO",
+    },
+    KeyAction {
+        name: "cycle_effort",
+        default: "shift+tab",
+        description: "Cycle reasoning effort",
+    },
+    KeyAction {
+        name: "toggle_plan",
+        default: "ctrl+x",
+        description: "Toggle between Plan and Agent mode",
+    },
+    KeyAction {
+        name: "mode_plan",
+        default: "alt+p",
+        description: "Switch to Plan mode",
+    },
+    KeyAction {
+        name: "mode_agent",
+        default: "alt+a",
+        description: "Switch to Agent mode",
+    },
+    KeyAction {
+        name: "mode_yolo",
+        default: "alt+y",
+        description: "Switch to YOLO mode",
+    },
+    KeyAction {
+        name: "scroll_up",
+        default: "alt+up",
+        description: "Scroll the transcript up",
+    },
+    KeyAction {
+        name: "scroll_down",
+        default: "alt+down",
+        description: "Scroll the transcript down",
+    },
+    KeyAction {
+        name: "page_up",
+        default: "pageup",
+        description: "Scroll the transcript up one page",
+    },
+    KeyAction {
+        name: "page_down",
+        default: "pagedown",
+        description: "Scroll the transcript down one page",
+    },
+    KeyAction {
+        name: "file_picker",
+        default: "ctrl+p",
+        description: "Fuzzy file picker",
+    },
+    KeyAction {
+        name: "file_tree",
+        default: "ctrl+shift+e",
+        description: "Toggle the file tree",
+    },
+    KeyAction {
+        name: "files_sidebar",
+        default: "ctrl+alt+5",
+        description: "Focus the Files sidebar",
+    },
+    KeyAction {
+        name: "session_picker",
+        default: "ctrl+r",
+        description: "Resume a saved session",
+    },
+    KeyAction {
+        name: "history_search",
+        default: "alt+r",
+        description: "Search prompt history",
+    },
+    KeyAction {
+        name: "new_tab",
+        default: "ctrl+t",
+        description: "Open a new session tab",
+    },
+    KeyAction {
+        name: "close_tab",
+        default: "alt+w",
+        description: "Close the focused session tab",
+    },
+    KeyAction {
+        name: "live_transcript",
+        default: "alt+t",
+        description: "Toggle the live transcript overlay",
+    },
+    KeyAction {
+        name: "tool_details",
+        default: "alt+v",
+        description: "Open tool details",
+    },
+    KeyAction {
+        name: "context_inspector",
+        default: "alt+c",
+        description: "Open the context inspector",
+    },
+    KeyAction {
+        name: "shell_control",
+        default: "ctrl+b",
+        description: "Background shell controls",
+    },
+];
+
+fn find_action(name: &str) -> Option<&'static KeyAction> {
+    KEY_ACTIONS.iter().find(|action| action.name == name)
+}
+
+fn default_chord(action: &KeyAction) -> KeyChord {
+    KeyChord::parse(action.default).expect("built-in default chords parse")
+}
+
+/// A normalized key chord. Letters are stored lowercase with Shift as a
+/// modifier, Shift is dropped from symbols (terminals disagree on whether
+/// `!` reports it), and BackTab is stored as Shift+Tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    fn normalized(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers = modifiers
+            & (KeyModifiers::CONTROL
+                | KeyModifiers::ALT
+                | KeyModifiers::SHIFT
+                | KeyModifiers::SUPER);
+        let code = match code {
+            KeyCode::BackTab => {
+                modifiers |= KeyModifiers::SHIFT;
+                KeyCode::Tab
+            }
+            KeyCode::Char(ch) if ch.is_uppercase() => {
+                modifiers |= KeyModifiers::SHIFT;
+                KeyCode::Char(ch.to_lowercase().next().unwrap_or(ch))
+            }
+            KeyCode::Char(ch) if !ch.is_alphabetic() => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(ch)
+            }
+            other => other,
+        };
+        Self { code, modifiers }
+    }
+
+    #[must_use]
+    pub fn from_event(key: &KeyEvent) -> Self {
+        Self::normalized(key.code, key.modifiers)
+    }
+
+    /// Parse `ctrl+shift+e`, `alt+up`, `f5`, `pagedown`, `ctrl++`, …
+    /// Modifier and key names are case-insensitive; a bare uppercase letter
+    /// implies Shift.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let text = raw.trim();
+        let (modifier_part, key_part) = if text == "+" {
+            ("", "+")
+        } else if let Some(prefix) = text.strip_suffix("++") {
+            (prefix, "+")
+        } else {
+            text.rsplit_once('+').unwrap_or(("", text))
+        };
+        if key_part.is_empty() {
+            return Err(format!("`{raw}` has no key"));
+        }
+
+        let mut modifiers = KeyModifiers::NONE;
+        for name in modifier_part.split('+').filter(|name| !name.is_empty()) {
+            modifiers |= match name.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "option" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                "super" | "cmd" | "command" | "win" => KeyModifiers::SUPER,
+                _ => return Err(format!("`{raw}`: unknown modifier `{name}`")),
+            };
+        }
+
+        let mut chars = key_part.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(ch), None) => KeyCode::Char(ch),
+            _ => match key_part.to_ascii_lowercase().as_str() {
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=24) => KeyCode::F(n),
+                    _ => return Err(format!("`{raw}`: unknown key `{key_part}`")),
+                },
+            },
+        };
+        Ok(Self::normalized(code, modifiers))
+    }
+
+    /// The event `ui.rs` expects for this chord.
+    #[must_use]
+    pub fn to_event(self) -> KeyEvent {
+        let shifted = self.modifiers.contains(KeyModifiers::SHIFT);
+        let code = match self.code {
+            KeyCode::Tab if shifted => KeyCode::BackTab,
+            KeyCode::Char(ch) if shifted => KeyCode::Char(ch.to_uppercase().next().unwrap_or(ch)),
+            other => other,
+        };
+        KeyEvent::new(code, self.modifiers)
+    }
+
+    /// Chords that would eat typed text or break cancel/submit.
+    fn validate(self, raw: &str) -> Result<Self, String> {
+        let command_modifier = self
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER);
+        if matches!(self.code, KeyCode::Char(_)) && !command_modifier {
+            return Err(format!(
+                "`{raw}` would swallow typed text; add ctrl, alt, or super"
+            ));
+        }
+        let reserved = (self.code == KeyCode::Char('c') && self.modifiers == KeyModifiers::CONTROL)
+            || (matches!(self.code, KeyCode::Esc | KeyCode::Enter) && self.modifiers.is_empty());
+        if reserved {
+            return Err(format!("`{raw}` is reserved"));
+        }
+        Ok(self)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, label) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+            (KeyModifiers::SUPER, "Super+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(label)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(ch) => write!(f, "{}", ch.to_uppercase()),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            KeyCode::PageUp => f.write_str("PageUp"),
+            KeyCode::PageDown => f.write_str("PageDown"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// Resolved `[keys]` table.
+#[derive(Debug, Clone, Default)]
+pub struct Keymap {
+    /// Configured chord → default chord of the action it triggers.
+    remaps: HashMap<KeyChord, KeyChord>,
+    /// Configured chords per action, in config order.
+    configured: BTreeMap<&'static str, Vec<KeyChord>>,
+    /// Unknown actions, invalid chords, and duplicate bindings.
+    issues: Vec<String>,
+}
+
+impl Keymap {
+    #[must_use]
+    pub fn from_config(keys: &BTreeMap<String, KeyChordsToml>) -> Self {
+        let mut keymap = Self::default();
+        let mut owners: HashMap<KeyChord, &'static str> = HashMap::new();
+        for (name, chords) in keys {
+            let Some(action) = find_action(name) else {
+                keymap
+                    .issues
+                    .push(format!("unknown action `{name}` (see /keys for the list)"));
+                continue;
+            };
+            let default = default_chord(action);
+            for raw in chords.chords() {
+                let chord = match KeyChord::parse(&raw).and_then(|chord| chord.validate(&raw)) {
+                    Ok(chord) => chord,
+                    Err(err) => {
+                        keymap.issues.push(format!("{name}: {err}"));
+                        continue;
+                    }
+                };
+                if let Some(owner) = owners.get(&chord) {
+                    if *owner != action.name {
+                        keymap.issues.push(format!(
+                            "{chord} is bound to both {owner} and {name}; {owner} keeps it"
+                        ));
+                    }
+                    continue;
+                }
+                owners.insert(chord, action.name);
+                keymap
+                    .configured
+                    .entry(action.name)
+                    .or_default()
+                    .push(chord);
+                if chord != default {
+                    keymap.remaps.insert(chord, default);
+                }
+            }
+        }
+        keymap
+    }
+
+    /// Rewrite a configured chord into the default chord of its action.
+    /// Anything else passes through untouched.
+    #[must_use]
+    pub fn translate(&self, key: KeyEvent) -> KeyEvent {
+        if self.remaps.is_empty() {
+            return key;
+        }
+        match self.remaps.get(&KeyChord::from_event(&key)) {
+            Some(target) => KeyEvent {
+                kind: key.kind,
+                state: key.state,
+                ..target.to_event()
+            },
+            None => key,
+        }
+    }
+
+    /// The action a configured chord took this action's default from.
+    fn default_taken_by(&self, action: &KeyAction) -> Option<&'static str> {
+        let default = default_chord(action);
+        self.remaps.get(&default).and_then(|target| {
+            KEY_ACTIONS
+                .iter()
+                .find(|other| default_chord(other) == *target)
+                .map(|other| other.name)
+        })
+    }
+
+    /// Binding table plus conflicts, for `/keys`.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let mut out = String::from("Keybindings (remap with [keys] in config.toml)\n\n");
+        for action in KEY_ACTIONS {
+            let mut chords: Vec<String> = self
+                .configured
+                .get(action.name)
+                .into_iter()
+                .flatten()
+                .map(|chord| format!("{chord} (config)"))
+                .collect();
+            let default = default_chord(action);
+            let default_listed = self
+                .configured
+                .get(action.name)
+                .is_some_and(|configured| configured.contains(&default));
+            if !default_listed && self.default_taken_by(action).is_none() {
+                chords.push(default.to_string());
+            }
+            if chords.is_empty() {
+                chords.push("unbound".to_string());
+            }
+            out.push_str(&format!(
+                "  {:<18} {:<28} {}\n",
+                action.name,
+                chords.join(", "),
+                action.description
+            ));
+        }
+
+        let mut conflicts = self.issues.clone();
+        for action in KEY_ACTIONS {
+            if let Some(owner) = self.default_taken_by(action) {
+                conflicts.push(format!(
+                    "{} now runs {owner}, replacing the {} default",
+                    default_chord(action),
+                    action.name
+                ));
+            }
+        }
+        if conflicts.is_empty() {
+            out.push_str("\nNo conflicts.");
+        } else {
+            out.push_str("\nConflicts:\n");
+            for conflict in conflicts {
+                out.push_str(&format!("  - {conflict}\n"));
+            }
+        }
+        out.trim_end().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keymap(entries: &[(&str, &[&str])]) -> Keymap {
+        let keys = entries
+            .iter()
+            .map(|(name, chords)| {
+                (
+                    (*name).to_string(),
+                    KeyChordsToml::Many(chords.iter().map(|c| (*c).to_string()).collect()),
+                )
+            })
+            .collect();
+        Keymap::from_config(&keys)
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn default_chords_parse_and_are_unique() {
+        let mut seen = HashMap::new();
+        for action in KEY_ACTIONS {
+            let chord = default_chord(action);
+            assert!(
+                seen.insert(chord, action.name).is_none(),
+                "{} shares its default with {}",
+                action.name,
+                seen[&chord]
+            );
+        }
+    }
+
+    #[test]
+    fn parse_normalizes_shift_case_and_backtab() {
+        assert_eq!(
+            KeyChord::parse("Ctrl+Shift+E").unwrap(),
+            KeyChord::from_event(&key(
+                KeyCode::Char('E'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            ))
+        );
+        assert_eq!(
+            KeyChord::parse("shift+tab").unwrap(),
+            KeyChord::from_event(&key(KeyCode::BackTab, KeyModifiers::SHIFT))
+        );
+        assert_eq!(
+            KeyChord::parse("alt+!").unwrap(),
+            KeyChord::from_event(&key(
+                KeyCode::Char('!'),
+                KeyModifiers::ALT | KeyModifiers::SHIFT
+            ))
+        );
+        assert_eq!(KeyChord::parse("ctrl++").unwrap().to_string(), "Ctrl++");
+        assert_eq!(KeyChord::parse("F5").unwrap().to_string(), "F5");
+        assert!(KeyChord::parse("hyper+k").is_err());
+        assert!(KeyChord::parse("ctrl+nope").is_err());
+    }
+
+    #[test]
+    fn configured_chord_translates_to_the_default_event() {
+        let keymap = keymap(&[("open_palette", &["ctrl+j"]), ("cycle_effort", &["alt+e"])]);
+
+        let palette = keymap.translate(key(KeyCode::Char('j'), KeyModifiers::CONTROL));
+        assert_eq!(palette.code, KeyCode::Char('k'));
+        assert_eq!(palette.modifiers, KeyModifiers::CONTROL);
+
+        let effort = keymap.translate(key(KeyCode::Char('e'), KeyModifiers::ALT));
+        assert_eq!(effort.code, KeyCode::BackTab);
+
+        // Defaults and unrelated keys pass through unchanged.
+        let default = key(KeyCode::Char('k'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.translate(default), default);
+        let typed = key(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(keymap.translate(typed), typed);
+    }
+
+    #[test]
+    fn text_and_reserved_chords_are_rejected() {
+        let keymap = keymap(&[
+            ("scroll_up", &["k", "shift+k"]),
+            ("open_help", &["ctrl+c", "esc"]),
+        ]);
+        assert!(keymap.remaps.is_empty());
+        assert_eq!(keymap.issues.len(), 4, "{:?}", keymap.issues);
+        let typed = key(KeyCode::Char('k'), KeyModifiers::NONE);
+        assert_eq!(keymap.translate(typed), typed);
+    }
+
+    #[test]
+    fn describe_reports_unknown_duplicate_and_shadowed_bindings() {
+        let keymap = keymap(&[
+            ("scroll_up", &["ctrl+k"]),
+            ("new_tab", &["alt+n"]),
+            ("open_help", &["alt+n"]),
+            ("teleport", &["ctrl+q"]),
+        ]);
+        let text = keymap.describe();
+        assert!(text.contains("unknown action `teleport`"), "{text}");
+        assert!(
+            text.contains("Alt+N is bound to both new_tab and open_help; new_tab keeps it"),
+            "{text}"
+        );
+        assert!(
+            text.contains("Ctrl+K now runs scroll_up, replacing the open_palette default"),
+            "{text}"
+        );
+        assert!(text.contains("Ctrl+K (config), Alt+Up"), "{text}");
+
+        let translated = keymap.translate(key(KeyCode::Char('k'), KeyModifiers::CONTROL));
+        assert_eq!(translated.code, KeyCode::Up);
+        assert_eq!(translated.modifiers, KeyModifiers::ALT);
+    }
+
+    #[test]
+    fn empty_keymap_describes_defaults_without_conflicts() {
+        let text = Keymap::default().describe();
+        assert!(text.contains("open_palette"));
+        assert!(text.contains("Ctrl+Shift+E"));
+        assert!(text.ends_with("No conflicts."));
+    }
+}
@@ -1,6 +1,6 @@
 //! Full-screen live transcript overlay with sticky-bottom auto-scroll (#94).
 //!
-//! Toggled with `Ctrl+T` while the engine is streaming. Behaviour:
+//! Toggled with `Alt+T` while the engine is streaming. Behaviour:
 //!
 //! - At-bottom (`sticky_to_bottom = true`) — every refresh re-pins scroll to
 //!   the new tail, so streaming output appears to flow off the bottom edge.
@@ -38,7 +38,7 @@ use crate::tui::history::{HistoryCell, TranscriptRenderOptions};
 use crate::tui::transcript_cache::{CellId, TranscriptCache};
 use crate::tui::views::{ModalKind, ModalView, ViewAction, ViewEvent};
 
-/// Render mode for the overlay. `Tail` is the original Ctrl+T sticky-tail
+/// Render mode for the overlay. `Tail` is the original Alt+T sticky-tail
 /// behaviour (#94). `BacktrackPreview` (#133) highlights the Nth-from-tail
 /// `HistoryCell::User` so the user can see which turn Esc-Esc-Enter will
 /// roll back to. The mode also disables sticky-tail (we want the user to
@@ -425,12 +425,17 @@ impl ModalView for LiveTranscriptOverlay {
                     self.pending_g = false;
                     return ViewAction::None;
                 }
-                // Ctrl+T toggles the overlay closed when already open.
-                KeyCode::Char('t') | KeyCode::Char('T') => return ViewAction::Close,
                 _ => {}
             }
         }
 
+        // Alt+T toggles the overlay closed when already open.
+        if key.modifiers.contains(KeyModifiers::ALT)
+            && matches!(key.code, KeyCode::Char('t') | KeyCode::Char('T'))
+        {
+            return ViewAction::Close;
+        }
+
         match key.code {
             KeyCode::Esc | KeyCode::Char('q') => ViewAction::Close,
             KeyCode::Up | KeyCode::Char('k') => {
@@ -696,9 +701,9 @@ mod tests {
     }
 
     #[test]
-    fn ctrl_t_closes_when_already_open() {
+    fn alt_t_closes_when_already_open() {
         let mut v = LiveTranscriptOverlay::new();
-        let action = v.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL));
+        let action = v.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::ALT));
         assert!(matches!(action, ViewAction::Close));
     }
 
@@ -0,0 +1,178 @@
+//! Prompt shown when the engine reports a tool-call loop.
+//!
+//! The engine has already nudged the model and blocked the exact repeat
+//! (`Event::LoopDetected`); this lets the user decide whether the turn gets
+//! another chance or is cancelled outright.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, Widget, Wrap};
+
+use crate::palette;
+use crate::tui::views::{ModalKind, ModalView, ViewAction, ViewEvent};
+
+const OPTIONS: [(&str, &str); 2] = [
+    (
+        "Keep going",
+        "Let the model change course; the exact repeat stays blocked",
+    ),
+    ("Abort turn", "Cancel the running turn"),
+];
+
+#[derive(Debug, Clone)]
+pub struct LoopPromptView {
+    tool: String,
+    failures: u32,
+    selected: usize,
+}
+
+impl LoopPromptView {
+    #[must_use]
+    pub fn new(tool: String, failures: u32) -> Self {
+        Self {
+            tool,
+            failures,
+            selected: 0,
+        }
+    }
+
+    fn submit(&self, index: usize) -> ViewAction {
+        if index == 1 {
+            ViewAction::EmitAndClose(ViewEvent::LoopAbortTurn)
+        } else {
+            ViewAction::Close
+        }
+    }
+}
+
+impl ModalView for LoopPromptView {
+    fn kind(&self) -> ModalKind {
+        ModalKind::LoopPrompt
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                ViewAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(OPTIONS.len() - 1);
+                ViewAction::None
+            }
+            KeyCode::Char('1') | KeyCode::Char('c') | KeyCode::Char('C') | KeyCode::Esc => {
+                self.submit(0)
+            }
+            KeyCode::Char('2') | KeyCode::Char('a') | KeyCode::Char('A') => self.submit(1),
+            KeyCode::Enter => self.submit(self.selected),
+            _ => ViewAction::None,
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let mut lines: Vec<Line> = vec![
+            Line::from(Span::styled(
+                format!(
+                    "`{}` failed {} times with identical arguments",
+                    self.tool, self.failures
+                ),
+                Style::default().fg(palette::TEXT_PRIMARY).bold(),
+            )),
+            Line::from(Span::styled(
+                "The model was told to change approach and the exact call is blocked.",
+                Style::default().fg(palette::TEXT_MUTED),
+            )),
+            Line::from(""),
+        ];
+
+        for (idx, (label, description)) in OPTIONS.iter().enumerate() {
+            let selected = self.selected == idx;
+            let row_style = if selected {
+                Style::default()
+                    .fg(palette::SELECTION_TEXT)
+                    .bg(palette::SELECTION_BG)
+                    .bold()
+            } else {
+                Style::default().fg(palette::TEXT_PRIMARY)
+            };
+            let detail_style = if selected {
+                row_style
+            } else {
+                Style::default().fg(palette::TEXT_MUTED)
+            };
+            let prefix = if selected { ">" } else { " " };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{prefix} {}) {label}", idx + 1), row_style),
+                Span::styled(format!("  {description}"), detail_style),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("c / a", Style::default().fg(palette::DEEPSEEK_SKY).bold()),
+            Span::styled(" quick pick", Style::default().fg(palette::TEXT_MUTED)),
+            Span::raw("  "),
+            Span::styled("Enter", Style::default().fg(palette::DEEPSEEK_SKY).bold()),
+            Span::styled(" confirm", Style::default().fg(palette::TEXT_MUTED)),
+            Span::raw("  "),
+            Span::styled("Esc", Style::default().fg(palette::DEEPSEEK_SKY).bold()),
+            Span::styled(" keep going", Style::default().fg(palette::TEXT_MUTED)),
+        ]));
+
+        let height = u16::try_from(lines.len() + 4)
+            .unwrap_or(u16::MAX)
+            .min(area.height);
+        let width = area.width.saturating_sub(4).min(80);
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+        Clear.render(popup_area, buf);
+        Paragraph::new(lines)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .title(Line::from(Span::styled(
+                        " Loop detected ",
+                        Style::default().fg(palette::STATUS_WARNING).bold(),
+                    )))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(palette::BORDER_COLOR))
+                    .padding(Padding::horizontal(1)),
+            )
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn abort_emits_cancel_and_esc_keeps_going() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let mut view = LoopPromptView::new("exec_shell".to_string(), 2);
+        assert!(matches!(
+            view.handle_key(key(KeyCode::Esc)),
+            ViewAction::Close
+        ));
+        assert!(matches!(
+            view.handle_key(key(KeyCode::Char('a'))),
+            ViewAction::EmitAndClose(ViewEvent::LoopAbortTurn)
+        ));
+        view.handle_key(key(KeyCode::Down));
+        assert!(matches!(
+            view.handle_key(key(KeyCode::Enter)),
+            ViewAction::EmitAndClose(ViewEvent::LoopAbortTurn)
+        ));
+    }
+}
@@ -23,6 +23,17 @@
 //! The transcript cache layer (see `tui/transcript.rs`) caches the parsed AST per
 //! cell and re-runs only the render step on width changes. That makes resize a
 //! re-flow operation rather than a re-parse + re-flow operation.
+//!
+//! ## Streaming
+//!
+//! A streaming assistant cell changes on every delta, so the per-cell cache
+//! alone still re-parses and re-wraps the whole answer each time.
+//! [`StreamingMarkdown`] keeps the rendered lines of the source up to the last
+//! block boundary (a complete line outside any code fence or table) and only
+//! re-renders the tail after it. The parser is line-oriented, so rendering the
+//! prefix and tail separately gives the same lines as rendering the whole
+//! source. While streaming, a half-received fence marker or table row at the
+//! very end is held back instead of flashing as paragraph text.
 
 #[cfg(test)]
 use std::cell::Cell;
@@ -54,6 +65,13 @@ pub fn reset_parse_invocation_count() {
     PARSE_INVOCATIONS.with(|c| c.set(0));
 }
 
+// Source bytes fed through `parse`, so tests can measure how much work the
+// streaming renderer saves.
+#[cfg(test)]
+thread_local! {
+    static PARSED_BYTES: Cell<usize> = const { Cell::new(0) };
+}
+
 /// One classified line of markdown source, width-independent.
 ///
 /// All decisions that depend only on the source text (heading level, bullet
@@ -114,18 +132,19 @@ pub struct RenderedMarkdownLine {
 pub fn parse(content: &str) -> ParsedMarkdown {
     #[cfg(test)]
     PARSE_INVOCATIONS.with(|c| c.set(c.get() + 1));
+    #[cfg(test)]
+    PARSED_BYTES.with(|c| c.set(c.get() + content.len()));
 
     let mut blocks = Vec::new();
-    let mut in_code_block = false;
+    let mut fence: Option<Fence> = None;
 
     for raw_line in content.lines() {
         let trimmed = raw_line.trim_start();
-        if trimmed.starts_with("```") {
-            in_code_block = !in_code_block;
+        if advance_fence(&mut fence, trimmed) {
             continue;
         }
 
-        if in_code_block {
+        if fence.is_some() {
             blocks.push(Block::Code {
                 line: raw_line.to_string(),
             });
@@ -309,6 +328,165 @@ pub fn render_parsed_tagged(
     out
 }
 
+/// Incremental renderer for a markdown cell that is still streaming.
+///
+/// Holds the rendered lines for the source prefix up to the last block
+/// boundary. [`StreamingMarkdown::render`] reuses them while the new source
+/// still starts with that prefix, parsing and wrapping only the tail. A
+/// width or style change, or a source that no longer extends the prefix,
+/// starts over.
+#[derive(Debug, Clone, Default)]
+pub struct StreamingMarkdown {
+    width: u16,
+    base_style: Style,
+    /// Source already rendered into `stable_lines`; always ends at a block
+    /// boundary outside any code fence.
+    stable_source: String,
+    stable_lines: Vec<RenderedMarkdownLine>,
+}
+
+impl StreamingMarkdown {
+    /// Render `content`, re-rendering only what follows the cached prefix.
+    pub fn render(
+        &mut self,
+        content: &str,
+        width: u16,
+        base_style: Style,
+    ) -> Vec<RenderedMarkdownLine> {
+        if self.width != width
+            || self.base_style != base_style
+            || !content.starts_with(self.stable_source.as_str())
+        {
+            *self = Self {
+                width,
+                base_style,
+                ..Self::default()
+            };
+        }
+
+        let start = self.stable_source.len();
+        let boundary = start + last_block_boundary(&content[start..]);
+        if boundary > start {
+            let settled = &content[start..boundary];
+            let parsed = parse(settled);
+            // Fence-only slices parse to nothing; skip them so the
+            // empty-output placeholder line is not appended mid-answer.
+            if !parsed.blocks.is_empty() {
+                self.stable_lines
+                    .extend(render_parsed_tagged(&parsed, width, base_style));
+            }
+            self.stable_source.push_str(settled);
+        }
+
+        let mut out = self.stable_lines.clone();
+        let tail = parse(without_partial_tail(&content[boundary..]));
+        if !tail.blocks.is_empty() {
+            out.extend(render_parsed_tagged(&tail, width, base_style));
+        }
+        if out.is_empty() {
+            out.push(RenderedMarkdownLine {
+                line: Line::from(""),
+                is_code: false,
+            });
+        }
+        out
+    }
+}
+
+/// Byte offset just past the last complete line of `text` after which the
+/// parser state is clean: outside any code fence and not inside a table,
+/// whose rows are laid out as one group.
+fn last_block_boundary(text: &str) -> usize {
+    let mut fence: Option<Fence> = None;
+    let mut offset = 0;
+    let mut boundary = 0;
+    for line in text.split_inclusive('\n') {
+        offset += line.len();
+        if !line.ends_with('\n') {
+            break;
+        }
+        let trimmed = line.trim_start();
+        if advance_fence(&mut fence, trimmed.trim_end_matches(['\n', '\r'])) {
+            if fence.is_none() {
+                boundary = offset;
+            }
+            continue;
+        }
+        if fence.is_none() && !trimmed.starts_with('|') {
+            boundary = offset;
+        }
+    }
+    boundary
+}
+
+/// Drop an unterminated last line that would render misleadingly until the
+/// rest of it arrives: a fence marker still being typed (which would flash
+/// as paragraph or code text) or a table row missing its later cells.
+fn without_partial_tail(text: &str) -> &str {
+    if text.ends_with('\n') {
+        return text;
+    }
+    let last_start = text.rfind('\n').map_or(0, |idx| idx + 1);
+    let mut fence: Option<Fence> = None;
+    for line in text[..last_start].lines() {
+        advance_fence(&mut fence, line.trim_start());
+    }
+    let last = text[last_start..].trim();
+    let typing_fence = !last.is_empty() && last.chars().all(|c| matches!(c, '`' | '~'));
+    let partial_row = fence.is_none() && last.starts_with('|');
+    if typing_fence || partial_row {
+        &text[..last_start]
+    } else {
+        text
+    }
+}
+
+/// An open fenced code block: the fence character and its run length.
+#[derive(Debug, Clone, Copy)]
+struct Fence {
+    marker: char,
+    len: usize,
+}
+
+/// Leading run of three or more backticks or tildes.
+fn fence_run(trimmed: &str) -> Option<(char, usize)> {
+    let marker = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let len = trimmed.chars().take_while(|c| *c == marker).count();
+    (len >= 3).then_some((marker, len))
+}
+
+/// Update the fence state for one (left-trimmed) line. Returns true when the
+/// line opens or closes a fence and is not content itself.
+///
+/// A fence closes only on the same marker, at least as long as the opener,
+/// with nothing after it, so a "```rust" line inside a longer fence (a
+/// markdown example, say) stays code.
+fn advance_fence(fence: &mut Option<Fence>, trimmed: &str) -> bool {
+    let Some((marker, len)) = fence_run(trimmed) else {
+        return false;
+    };
+    match fence {
+        Some(open) => {
+            let rest = &trimmed[len * marker.len_utf8()..];
+            if marker == open.marker && len >= open.len && rest.trim().is_empty() {
+                *fence = None;
+                true
+            } else {
+                false
+            }
+        }
+        None => {
+            let info = &trimmed[len * marker.len_utf8()..];
+            if marker == '`' && info.contains('`') {
+                // "```inline```" is inline code, not a fence.
+                return false;
+            }
+            *fence = Some(Fence { marker, len });
+            true
+        }
+    }
+}
+
 /// Convenience wrapper: parse + render in one call.
 ///
 /// Equivalent to `render_parsed(&parse(content), width, base_style)`. Callers
@@ -1245,6 +1423,116 @@ mod tests {
     /// Render with the OSC 8 flag pinned to `enabled`, then restore the prior
     /// value. We serialize through a static mutex because `osc8::ENABLED` is
     /// process-wide state and other tests touching it would race otherwise.
+    fn tagged_text(lines: &[RenderedMarkdownLine]) -> Vec<(String, bool)> {
+        lines
+            .iter()
+            .map(|l| {
+                let text = l.line.spans.iter().map(|s| s.content.as_ref()).collect();
+                (text, l.is_code)
+            })
+            .collect()
+    }
+
+    fn long_answer() -> String {
+        let mut source = String::from("# Plan\n\n");
+        for i in 0..60 {
+            source.push_str(&format!(
+                "Step {i} explains the change in enough words to wrap at narrow widths.\n"
+            ));
+            source.push_str(&format!("- item {i}\n- another item\n\n"));
+            source.push_str("| col | value |\n|---|---|\n| a | 1 |\n\n");
+            source.push_str("````markdown\n```rust\nfn main() {}\n```\n````\n\n");
+        }
+        source
+    }
+
+    #[test]
+    fn streaming_render_matches_full_render_and_parses_less() {
+        with_osc8(false, || {
+            let source = long_answer();
+            let mut streaming = StreamingMarkdown::default();
+            let mut incremental_bytes = 0;
+            let mut full_bytes = 0;
+            let mut end = 0;
+            for line in source.split_inclusive('\n') {
+                end += line.len();
+                let prefix = &source[..end];
+
+                PARSED_BYTES.with(|c| c.set(0));
+                let incremental = streaming.render(prefix, 40, Style::default());
+                incremental_bytes += PARSED_BYTES.with(Cell::get);
+
+                PARSED_BYTES.with(|c| c.set(0));
+                let full = render_markdown_tagged(prefix, 40, Style::default());
+                full_bytes += PARSED_BYTES.with(Cell::get);
+
+                assert_eq!(tagged_text(&incremental), tagged_text(&full), "at {end}");
+            }
+            assert!(
+                incremental_bytes * 20 < full_bytes,
+                "incremental parsed {incremental_bytes} bytes vs {full_bytes} for full renders"
+            );
+        });
+    }
+
+    #[test]
+    fn streaming_render_holds_back_partial_fences_and_rows() {
+        with_osc8(false, || {
+            let mut streaming = StreamingMarkdown::default();
+            let text = |source: &str, streaming: &mut StreamingMarkdown| {
+                tagged_text(&streaming.render(source, 80, Style::default()))
+            };
+
+            // An opening fence still being typed does not flash as text.
+            assert_eq!(
+                text("Intro\n``", &mut streaming),
+                vec![("Intro".to_string(), false)]
+            );
+            // Nor does a closing fence inside an open block.
+            let open = text("Intro\n```\nlet x = 1;\n``", &mut streaming);
+            let (last, is_code) = open.last().expect("lines");
+            assert_eq!((last.trim(), *is_code), ("let x = 1;", true));
+            // An unterminated fence renders its body as code.
+            let unterminated = text("Intro\n```\nlet x = 1;\nlet y", &mut streaming);
+            let (last, is_code) = unterminated.last().expect("lines");
+            assert_eq!((last.trim(), *is_code), ("let y", true));
+            // A half-received table row waits for its newline.
+            let table = text("| a | b |\n|---|---|\n| 1 |", &mut streaming);
+            assert!(
+                table.iter().all(|(line, _)| !line.contains('1')),
+                "{table:?}"
+            );
+        });
+    }
+
+    #[test]
+    fn longer_fence_is_not_closed_by_inner_fence() {
+        let parsed = parse("````md\n```rust\nfn x() {}\n```\n````\nafter");
+        assert_eq!(
+            parsed.blocks,
+            vec![
+                Block::Code {
+                    line: "```rust".to_string()
+                },
+                Block::Code {
+                    line: "fn x() {}".to_string()
+                },
+                Block::Code {
+                    line: "```".to_string()
+                },
+                Block::Paragraph {
+                    text: "after".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            parse("~~~\ncode\n~~~").blocks,
+            vec![Block::Code {
+                line: "code".to_string()
+            }]
+        );
+    }
+
     fn render_with_osc8(enabled: bool, source: &str) -> String {
         render_with_osc8_width(enabled, source, 80)
     }
@@ -14,8 +14,12 @@
 pub mod active_cell;
 pub mod app;
 pub mod approval;
+pub mod approval_queue;
 pub mod auto_router;
+pub mod autocomplete;
 pub mod backtrack;
+pub mod bookmarks;
+pub mod changes_view;
 pub mod clipboard;
 pub mod color_compat;
 pub mod command_palette;
@@ -23,6 +27,7 @@ pub mod composer_ui;
 pub mod context_inspector;
 pub mod context_menu;
 pub mod diff_render;
+pub mod env_prompt;
 pub mod event_broker;
 pub mod external_editor;
 pub mod feedback_picker;
@@ -35,9 +40,14 @@ pub mod footer_ui;
 pub mod format_helpers;
 pub mod frame_rate_limiter;
 pub mod history;
+pub mod idle_maintenance;
+mod inbox_routing;
+pub mod inbox_view;
 pub mod key_shortcuts;
 pub mod keybindings;
+pub mod keymap;
 pub mod live_transcript;
+pub mod loop_prompt;
 pub mod markdown_render;
 mod mcp_routing;
 pub mod model_picker;
@@ -48,19 +58,30 @@ pub mod osc8;
 pub mod pager;
 pub mod paste;
 pub mod paste_burst;
+pub mod paste_prompt;
 pub mod persistence_actor;
+pub mod plan_export;
+pub mod plan_preview;
 pub mod plan_prompt;
+pub mod prompt_lint;
 pub mod provider_picker;
+pub mod replay;
+pub mod review_diff_view;
 pub mod scrolling;
 pub mod selection;
 pub mod session_picker;
+pub mod session_tabs;
 mod shell_job_routing;
 pub mod sidebar;
 pub mod slash_menu;
+pub mod smart_context;
+pub mod startup;
 pub mod streaming;
 pub mod streaming_thinking;
 mod subagent_routing;
+pub mod task_manager_view;
 pub mod theme_picker;
+pub mod tool_output_pane;
 mod tool_routing;
 pub mod transcript;
 pub mod transcript_cache;
@@ -1,9 +1,16 @@
-//! `/model` picker modal: pick a DeepSeek model and a thinking-effort tier
-//! and apply both at once (#39).
+//! `/model` picker modal: pick a model and a thinking-effort tier and apply
+//! both at once (#39).
 //!
 //! Two side-by-side panes — Models on the left, Thinking effort on the
-//! right. Tab swaps focus, ↑/↓ moves within the focused pane, Enter applies
-//! both and closes the modal, Esc cancels.
+//! right. Typing filters the model list, Tab swaps focus, ↑/↓ moves within
+//! the focused pane, Enter applies both for this session, Ctrl+S applies
+//! them and saves them as the default, Esc clears the filter or cancels.
+//!
+//! The model list starts with the built-in DeepSeek rows and merges in the
+//! provider's live model list once it arrives. The selected model is
+//! described below the panes from local metadata: context window, pricing,
+//! thinking support, and recommended use. When the session already holds
+//! more context than the model's window, the description carries a warning.
 //!
 //! The effort pane intentionally only exposes `Off / High / Max`. Per
 //! DeepSeek's [Thinking Mode docs](https://api-docs.deepseek.com/guides/reasoning_model),
@@ -14,29 +21,30 @@
 //!
 //! On apply we emit a [`ViewEvent::ModelPickerApplied`] with the resolved
 //! model id and effort tier; the UI handler updates `App` state, persists
-//! the choice via `Settings`, and forwards `Op::SetModel` so the running
-//! engine picks up the change without a restart.
+//! the choice via `Settings` when it was set as the default, and forwards
+//! `Op::SetModel` so the running engine picks up the change without a
+//! restart.
+
+use std::sync::mpsc;
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph, Widget},
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
 };
 
 use crate::palette;
+use crate::pricing::CostCurrency;
 use crate::tui::app::{App, ReasoningEffort};
+use crate::tui::footer_ui::format_token_count_compact;
 use crate::tui::views::{ModalKind, ModalView, ViewAction, ViewEvent};
 
-/// Models the picker exposes by default. Kept short on purpose — power
-/// users can still type `/model <id>` for anything else.
-const PICKER_MODELS: &[(&str, &str)] = &[
-    ("auto", "select per turn"),
-    ("deepseek-v4-pro", "flagship"),
-    ("deepseek-v4-flash", "fast / cheap"),
-];
+/// Models the picker lists before the live list arrives. Kept short on
+/// purpose — power users can still type `/model <id>` for anything else.
+const PICKER_MODELS: &[&str] = &["auto", "deepseek-v4-pro", "deepseek-v4-flash"];
 
 /// Thinking-effort rows shown in the picker, in the order DeepSeek
 /// behaviorally distinguishes them.
@@ -53,43 +61,152 @@ enum Pane {
     Effort,
 }
 
+/// Where a model row came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowSource {
+    BuiltIn,
+    /// Listed by the provider's models endpoint.
+    Api,
+    /// The active model, kept so the picker doesn't quietly forget an id
+    /// nothing else lists.
+    Custom,
+}
+
+#[derive(Debug, Clone)]
+struct ModelRow {
+    id: String,
+    source: RowSource,
+}
+
+/// Progress of the live model-list fetch.
+enum LiveModels {
+    /// No fetch was started (the provider doesn't list models).
+    Off,
+    Fetching(mpsc::Receiver<Result<Vec<String>, String>>),
+    Loaded(usize),
+    Failed(String),
+}
+
+/// What the picker knows about a model without asking the API.
+#[derive(Debug, Clone, PartialEq)]
+struct ModelInfo {
+    context_window: Option<u32>,
+    rates: Option<String>,
+    thinking: bool,
+    recommended_use: &'static str,
+}
+
+impl ModelInfo {
+    fn for_model(model: &str, currency: CostCurrency) -> Self {
+        Self {
+            context_window: crate::models::context_window_for_model(model),
+            rates: crate::pricing::format_model_rates(model, currency),
+            thinking: crate::client::model_supports_thinking(model),
+            recommended_use: recommended_use(model),
+        }
+    }
+}
+
+/// One-line guidance on what a model is good for; empty when unknown.
+fn recommended_use(model: &str) -> &'static str {
+    let lower = model.to_ascii_lowercase();
+    if lower == "auto" {
+        "picks the model and thinking tier per turn"
+    } else if lower.contains("v4-pro") || lower.contains("reasoner") {
+        "flagship: hard reasoning, long agent runs"
+    } else if lower.contains("v4-flash") || lower.contains("deepseek-chat") {
+        "fast / cheap: quick edits, sub-agents"
+    } else {
+        ""
+    }
+}
+
+/// Rank how well a lowercase `query` matches a model: an id prefix beats an
+/// id substring, which beats a recommended-use match, which beats a fuzzy
+/// (in-order subsequence) match of the id.
+fn match_rank(query: &str, model: &str) -> Option<u8> {
+    let id = model.to_ascii_lowercase();
+    if id.starts_with(query) {
+        Some(0)
+    } else if id.contains(query) {
+        Some(1)
+    } else if recommended_use(&id).contains(query) {
+        Some(2)
+    } else {
+        let mut haystack = id.chars();
+        query.chars().all(|c| haystack.any(|h| h == c)).then_some(3)
+    }
+}
+
+/// Tokens the session's context currently holds, when known.
+pub(crate) fn current_context_tokens(app: &App) -> Option<u64> {
+    crate::tui::ui::context_usage_snapshot(app)
+        .and_then(|(used, _, _)| u64::try_from(used).ok())
+        .filter(|used| *used > 0)
+}
+
+/// Warning for switching to `model` while the session already holds `used`
+/// tokens, when that is more than the model's context window.
+pub(crate) fn context_downsize_warning(model: &str, used: Option<u64>) -> Option<String> {
+    if model.trim().eq_ignore_ascii_case("auto") {
+        return None;
+    }
+    let used = used?;
+    let window = u64::from(crate::models::context_window_for_model(model)?);
+    (used > window).then(|| {
+        format!(
+            "{model} has a {} context window but this session already holds ~{} tokens; \
+             /compact or start a new session before the next turn",
+            format_token_count_compact(window),
+            format_token_count_compact(used)
+        )
+    })
+}
+
 pub struct ModelPickerView {
     initial_model: String,
     initial_effort: ReasoningEffort,
+    rows: Vec<ModelRow>,
+    /// Indices into `rows` matching `query`, best match first.
+    filtered: Vec<usize>,
     /// Working selection (separate from the initial values so we can offer a
-    /// clean Esc-to-cancel without mutating App state).
+    /// clean Esc-to-cancel without mutating App state). The model index
+    /// points into `filtered`.
     selected_model_idx: usize,
     selected_effort_idx: usize,
     focus: Pane,
-    /// True when the active model is one we don't list — we still show it
-    /// so the picker doesn't quietly forget the user's chosen IDs.
-    show_custom_model_row: bool,
-    /// When true, hide DeepSeek-specific model rows (pass-through providers
-    /// like openai don't support them).
-    hide_deepseek_models: bool,
+    query: String,
+    live: LiveModels,
+    currency: CostCurrency,
+    /// Tokens the session already holds, for the smaller-window warning.
+    context_used: Option<u64>,
 }
 
 impl ModelPickerView {
     #[must_use]
     pub fn new(app: &App) -> Self {
+        // Pass-through providers (e.g. openai) don't serve the DeepSeek
+        // model ids, so only "auto" and the custom row are offered there.
         let hide_deepseek_models = crate::config::provider_passes_model_through(app.api_provider);
         let initial_model = if app.auto_model {
             "auto".to_string()
         } else {
             app.model.clone()
         };
-        // On pass-through providers, only show "auto" and the custom row.
-        let visible_models: Vec<&str> = if hide_deepseek_models {
-            vec!["auto"]
-        } else {
-            PICKER_MODELS.iter().map(|(id, _)| *id).collect()
-        };
-        let mut selected_model_idx = visible_models.iter().position(|id| *id == initial_model);
-        let show_custom_model_row = selected_model_idx.is_none();
-        if show_custom_model_row {
-            selected_model_idx = Some(visible_models.len());
+        let mut rows: Vec<ModelRow> = PICKER_MODELS
+            .iter()
+            .filter(|id| !hide_deepseek_models || **id == "auto")
+            .map(|id| ModelRow {
+                id: (*id).to_string(),
+                source: RowSource::BuiltIn,
+            })
+            .collect();
+        if !rows.iter().any(|row| row.id == initial_model) {
+            rows.push(ModelRow {
+                id: initial_model.clone(),
+                source: RowSource::Custom,
+            });
         }
-        let selected_model_idx = selected_model_idx.unwrap_or(0);
 
         let initial_effort = app.reasoning_effort;
         // Map low/medium → high, xhigh → max for picker purposes.
@@ -102,41 +219,101 @@ impl ModelPickerView {
             .position(|e| *e == normalized)
             .unwrap_or(2); // default to High if somehow unknown
 
-        Self {
+        let mut view = Self {
             initial_model,
             initial_effort,
-            selected_model_idx,
+            rows,
+            filtered: Vec::new(),
+            selected_model_idx: 0,
             selected_effort_idx,
             focus: Pane::Model,
-            show_custom_model_row,
-            hide_deepseek_models,
-        }
+            query: String::new(),
+            live: LiveModels::Off,
+            currency: app.cost_currency,
+            context_used: current_context_tokens(app),
+        };
+        view.refilter();
+        view.select_model(&view.initial_model.clone());
+        view
     }
 
-    fn visible_model_ids(&self) -> Vec<&'static str> {
-        if self.hide_deepseek_models {
-            vec!["auto"]
-        } else {
-            PICKER_MODELS.iter().map(|(id, _)| *id).collect()
+    /// Merge in the provider's model list once `models` delivers it.
+    #[must_use]
+    pub fn with_live_models(mut self, models: mpsc::Receiver<Result<Vec<String>, String>>) -> Self {
+        self.live = LiveModels::Fetching(models);
+        self
+    }
+
+    fn selected_row(&self) -> Option<&ModelRow> {
+        self.filtered
+            .get(self.selected_model_idx)
+            .and_then(|idx| self.rows.get(*idx))
+    }
+
+    /// Put the model cursor on `id` when it is listed.
+    fn select_model(&mut self, id: &str) {
+        if let Some(pos) = self
+            .filtered
+            .iter()
+            .position(|idx| self.rows[*idx].id == id)
+        {
+            self.selected_model_idx = pos;
         }
     }
 
-    fn model_row_count(&self) -> usize {
-        self.visible_model_ids().len() + if self.show_custom_model_row { 1 } else { 0 }
+    /// Recompute `filtered` from `query` and move the cursor to the best
+    /// match.
+    fn refilter(&mut self) {
+        let query = self.query.trim().to_ascii_lowercase();
+        let mut matches: Vec<(u8, usize)> = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, row)| match_rank(&query, &row.id).map(|rank| (rank, idx)))
+            .collect();
+        matches.sort_unstable();
+        self.filtered = matches.into_iter().map(|(_, idx)| idx).collect();
+        self.selected_model_idx = 0;
     }
 
-    /// Resolve the currently highlighted model row to a model id. If the
-    /// custom row is selected we return the original model from the App so
-    /// "Apply" doesn't blow away an unrecognised id.
-    fn resolved_model(&self) -> String {
-        let visible = self.visible_model_ids();
-        if self.show_custom_model_row && self.selected_model_idx == visible.len() {
-            self.initial_model.clone()
-        } else if self.selected_model_idx < visible.len() {
-            visible[self.selected_model_idx].to_string()
-        } else {
-            self.initial_model.clone()
+    /// Add API-listed models that no row has yet, ahead of the custom row.
+    fn merge_api_models(&mut self, models: Vec<String>) {
+        let selected = self.selected_row().map(|row| row.id.clone());
+        let mut insert_at = self
+            .rows
+            .iter()
+            .position(|row| row.source == RowSource::Custom)
+            .unwrap_or(self.rows.len());
+        for id in models {
+            match self.rows.iter_mut().find(|row| row.id == id) {
+                Some(row) => {
+                    if row.source == RowSource::Custom {
+                        row.source = RowSource::Api;
+                    }
+                }
+                None => {
+                    self.rows.insert(
+                        insert_at,
+                        ModelRow {
+                            id,
+                            source: RowSource::Api,
+                        },
+                    );
+                    insert_at += 1;
+                }
+            }
         }
+        self.refilter();
+        if let Some(id) = selected {
+            self.select_model(&id);
+        }
+    }
+
+    /// Resolve the highlighted model row to a model id, falling back to the
+    /// original model when the filter hides every row.
+    fn resolved_model(&self) -> String {
+        self.selected_row()
+            .map_or_else(|| self.initial_model.clone(), |row| row.id.clone())
     }
 
     fn resolved_effort(&self) -> ReasoningEffort {
@@ -164,7 +341,7 @@ impl ModelPickerView {
     fn move_down(&mut self) {
         match self.focus {
             Pane::Model => {
-                let max = self.model_row_count().saturating_sub(1);
+                let max = self.filtered.len().saturating_sub(1);
                 if self.selected_model_idx < max {
                     self.selected_model_idx += 1;
                 }
@@ -185,13 +362,98 @@ impl ModelPickerView {
         };
     }
 
-    fn build_event(&self) -> ViewEvent {
-        ViewEvent::ModelPickerApplied {
+    fn apply(&self, set_default: bool) -> ViewAction {
+        if self.selected_row().is_none() {
+            return ViewAction::None;
+        }
+        ViewAction::EmitAndClose(ViewEvent::ModelPickerApplied {
             model: self.resolved_model(),
             effort: self.resolved_effort(),
             previous_model: self.initial_model.clone(),
             previous_effort: self.initial_effort,
+            set_default,
+        })
+    }
+
+    fn row_hint(&self, row: &ModelRow) -> String {
+        let mut parts = Vec::new();
+        if let Some(window) = crate::models::context_window_for_model(&row.id) {
+            parts.push(format_token_count_compact(u64::from(window)));
+        }
+        match row.source {
+            RowSource::BuiltIn => {}
+            RowSource::Api => parts.push("api".to_string()),
+            RowSource::Custom => parts.push("custom".to_string()),
         }
+        if row.id == self.initial_model {
+            parts.push("current".to_string());
+        }
+        parts.join(" · ")
+    }
+
+    /// Description of the highlighted model, shown under the panes.
+    fn detail_lines(&self) -> Vec<Line<'static>> {
+        let muted = Style::default().fg(palette::TEXT_MUTED);
+        let Some(row) = self.selected_row() else {
+            return vec![Line::from(Span::styled(
+                format!("No model matches \"{}\".", self.query),
+                muted,
+            ))];
+        };
+        let info = ModelInfo::for_model(&row.id, self.currency);
+        let heading = if info.recommended_use.is_empty() {
+            row.id.clone()
+        } else {
+            format!("{}: {}", row.id, info.recommended_use)
+        };
+        let mut lines = vec![Line::from(Span::styled(
+            heading,
+            Style::default().fg(palette::TEXT_PRIMARY),
+        ))];
+        if !row.id.eq_ignore_ascii_case("auto") {
+            let context = info.context_window.map_or_else(
+                || "context unknown".to_string(),
+                |window| format!("context {}", format_token_count_compact(u64::from(window))),
+            );
+            let rates = info.rates.unwrap_or_else(|| "pricing unknown".to_string());
+            let thinking = if info.thinking {
+                "thinking"
+            } else {
+                "no thinking"
+            };
+            lines.push(Line::from(Span::styled(
+                format!("{context} · {rates} · {thinking}"),
+                muted,
+            )));
+        }
+        if let Some(warning) = context_downsize_warning(&row.id, self.context_used) {
+            lines.push(Line::from(Span::styled(
+                format!("⚠ {warning}"),
+                Style::default().fg(palette::STATUS_WARNING),
+            )));
+        }
+        lines
+    }
+
+    fn search_line(&self) -> Line<'static> {
+        let muted = Style::default().fg(palette::TEXT_MUTED);
+        let mut spans = vec![
+            Span::styled("Search: ", muted),
+            Span::styled(
+                format!("{}▏", self.query),
+                Style::default().fg(palette::TEXT_PRIMARY),
+            ),
+        ];
+        let status = match &self.live {
+            LiveModels::Off => None,
+            LiveModels::Fetching(_) => Some("fetching models from the API…".to_string()),
+            LiveModels::Loaded(count) => Some(format!("{count} model(s) from the API")),
+            LiveModels::Failed(err) => Some(format!("API model list unavailable: {err}")),
+        };
+        if let Some(status) = status {
+            spans.push(Span::styled(format!("   {status}"), muted));
+        }
+        Line::from(spans)
     }
 
     fn render_pane(
@@ -219,8 +481,11 @@ impl ModelPickerView {
         let inner = block.inner(area);
         block.render(area, buf);
 
-        let mut lines = Vec::with_capacity(rows.len());
-        for (idx, (label, hint)) in rows.iter().enumerate() {
+        // Scroll so the selected row stays visible.
+        let visible = usize::from(inner.height).max(1);
+        let first = selected.saturating_sub(visible - 1);
+        let mut lines = Vec::with_capacity(visible);
+        for (idx, (label, hint)) in rows.iter().enumerate().skip(first).take(visible) {
             let is_selected = idx == selected;
             let marker = if is_selected { "▸" } else { " " };
             let label_style = if is_selected {
@@ -259,14 +524,42 @@ impl ModalView for ModelPickerView {
         ModalKind::ModelPicker
     }
 
+    fn poll_updates(&mut self) -> bool {
+        let received = match &self.live {
+            LiveModels::Fetching(rx) => rx.try_recv(),
+            _ => return false,
+        };
+        self.live = match received {
+            Ok(Ok(models)) => {
+                let count = models.len();
+                self.merge_api_models(models);
+                LiveModels::Loaded(count)
+            }
+            Ok(Err(err)) => LiveModels::Failed(err),
+            Err(mpsc::TryRecvError::Empty) => return false,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                LiveModels::Failed("the fetch stopped".to_string())
+            }
+        };
+        true
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
 
     fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
         match key.code {
+            KeyCode::Esc if !self.query.is_empty() => {
+                self.query.clear();
+                self.refilter();
+                self.select_model(&self.initial_model.clone());
+                ViewAction::None
+            }
             KeyCode::Esc => ViewAction::Close,
-            KeyCode::Enter => ViewAction::EmitAndClose(self.build_event()),
+            KeyCode::Enter => self.apply(false),
+            KeyCode::Char('s') if ctrl => self.apply(true),
             KeyCode::Up => {
                 self.move_up();
                 ViewAction::None
@@ -279,13 +572,25 @@ impl ModalView for ModelPickerView {
                 self.toggle_focus();
                 ViewAction::None
             }
+            KeyCode::Backspace => {
+                if self.query.pop().is_some() {
+                    self.refilter();
+                }
+                ViewAction::None
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.query.push(c);
+                self.focus = Pane::Model;
+                self.refilter();
+                ViewAction::None
+            }
             _ => ViewAction::None,
         }
     }
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        let popup_width = 64.min(area.width.saturating_sub(4)).max(40);
-        let popup_height = 14.min(area.height.saturating_sub(4)).max(10);
+        let popup_width = 96.min(area.width.saturating_sub(4)).max(40);
+        let popup_height = 22.min(area.height.saturating_sub(4)).max(14);
         let popup_area = Rect {
             x: area.x + (area.width.saturating_sub(popup_width)) / 2,
             y: area.y + (area.height.saturating_sub(popup_height)) / 2,
@@ -304,12 +609,16 @@ impl ModalView for ModelPickerView {
                     .add_modifier(Modifier::BOLD),
             )))
             .title_bottom(Line::from(vec![
+                Span::styled(" type ", Style::default().fg(palette::TEXT_MUTED)),
+                Span::raw("filter "),
                 Span::styled(" ↑↓ ", Style::default().fg(palette::TEXT_MUTED)),
                 Span::raw("move "),
                 Span::styled(" Tab ", Style::default().fg(palette::TEXT_MUTED)),
                 Span::raw("switch "),
                 Span::styled(" Enter ", Style::default().fg(palette::TEXT_MUTED)),
                 Span::raw("apply "),
+                Span::styled(" Ctrl+S ", Style::default().fg(palette::TEXT_MUTED)),
+                Span::raw("set default "),
                 Span::styled(" Esc ", Style::default().fg(palette::TEXT_MUTED)),
                 Span::raw("cancel "),
             ]))
@@ -319,22 +628,29 @@ impl ModalView for ModelPickerView {
         let inner = outer.inner(popup_area);
         outer.render(popup_area, buf);
 
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Min(6),
+                Constraint::Length(4),
+            ])
+            .split(inner);
+        Paragraph::new(self.search_line()).render(sections[0], buf);
+
         let columns = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-            .split(inner);
+            .constraints([Constraint::Percentage(64), Constraint::Percentage(36)])
+            .split(sections[1]);
 
-        let mut model_rows: Vec<(String, String)> = if self.hide_deepseek_models {
-            vec![("auto".to_string(), "select per turn".to_string())]
-        } else {
-            PICKER_MODELS
-                .iter()
-                .map(|(id, hint)| ((*id).to_string(), (*hint).to_string()))
-                .collect()
-        };
-        if self.show_custom_model_row {
-            model_rows.push((self.initial_model.clone(), "current (custom)".to_string()));
-        }
+        let model_rows: Vec<(String, String)> = self
+            .filtered
+            .iter()
+            .map(|idx| {
+                let row = &self.rows[*idx];
+                (row.id.clone(), self.row_hint(row))
+            })
+            .collect();
         self.render_pane(
             columns[0],
             buf,
@@ -366,6 +682,10 @@ impl ModalView for ModelPickerView {
             self.selected_effort_idx,
             self.focus == Pane::Effort,
         );
+
+        Paragraph::new(self.detail_lines())
+            .wrap(Wrap { trim: true })
+            .render(sections[2], buf);
     }
 }
 
@@ -472,9 +792,8 @@ mod tests {
 
     #[test]
     fn picker_exposes_auto_and_distinct_thinking_tiers() {
-        let model_labels: Vec<_> = PICKER_MODELS.iter().map(|(id, _)| *id).collect();
         assert_eq!(
-            model_labels,
+            PICKER_MODELS.to_vec(),
             vec!["auto", "deepseek-v4-pro", "deepseek-v4-flash"]
         );
 
@@ -491,7 +810,10 @@ mod tests {
         app.model = "deepseek-v4-pro-2026-04-XX".to_string();
         app.auto_model = false;
         let view = ModelPickerView::new(&app);
-        assert!(view.show_custom_model_row);
+        assert_eq!(
+            view.selected_row().map(|row| row.source),
+            Some(RowSource::Custom)
+        );
         assert_eq!(view.resolved_model(), "deepseek-v4-pro-2026-04-XX");
     }
 
@@ -556,16 +878,96 @@ mod tests {
                 model,
                 effort,
                 previous_effort,
+                set_default,
                 ..
             }) => {
                 assert_eq!(model, "deepseek-v4-pro");
                 assert_eq!(effort, ReasoningEffort::Max);
                 assert_eq!(previous_effort, ReasoningEffort::High);
+                assert!(!set_default, "Enter applies for the session only");
             }
             other => panic!("expected ModelPickerApplied EmitAndClose, got {other:?}"),
         }
     }
 
+    fn type_query(view: &mut ModelPickerView, query: &str) {
+        for c in query.chars() {
+            view.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn typing_filters_models_and_live_list_merges_in() {
+        let (app, _lock) = create_test_app();
+        let (tx, rx) = mpsc::channel();
+        let mut view = ModelPickerView::new(&app).with_live_models(rx);
+
+        type_query(&mut view, "flsh");
+        assert_eq!(view.resolved_model(), "deepseek-v4-flash");
+        view.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        // Recommended use is searchable too.
+        type_query(&mut view, "cheap");
+        assert_eq!(view.resolved_model(), "deepseek-v4-flash");
+        type_query(&mut view, "zzz");
+        assert!(view.selected_row().is_none());
+        assert!(matches!(
+            view.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            ViewAction::None
+        ));
+        view.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(view.resolved_model(), "deepseek-v4-pro");
+
+        assert!(!view.poll_updates());
+        tx.send(Ok(vec![
+            "deepseek-v4-flash".to_string(),
+            "deepseek-v4-pro-0601".to_string(),
+        ]))
+        .unwrap();
+        assert!(view.poll_updates());
+        // The cursor stays on the model it was on.
+        assert_eq!(view.resolved_model(), "deepseek-v4-pro");
+        assert_eq!(view.rows.len(), 4);
+        type_query(&mut view, "0601");
+        assert_eq!(view.resolved_model(), "deepseek-v4-pro-0601");
+        assert_eq!(
+            view.selected_row().map(|row| row.source),
+            Some(RowSource::Api)
+        );
+    }
+
+    #[test]
+    fn ctrl_s_applies_and_sets_default() {
+        let (app, _lock) = create_test_app();
+        let mut view = ModelPickerView::new(&app);
+        type_query(&mut view, "flash");
+        match view.handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)) {
+            ViewAction::EmitAndClose(ViewEvent::ModelPickerApplied {
+                model, set_default, ..
+            }) => {
+                assert_eq!(model, "deepseek-v4-flash");
+                assert!(set_default);
+            }
+            other => panic!("expected ModelPickerApplied EmitAndClose, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn warns_when_model_window_is_smaller_than_context_in_use() {
+        assert!(context_downsize_warning("deepseek-v4-flash", Some(500_000)).is_none());
+        assert!(context_downsize_warning("auto", Some(5_000_000)).is_none());
+        assert!(context_downsize_warning("deepseek-coder", None).is_none());
+        let warning = context_downsize_warning("deepseek-coder", Some(500_000))
+            .expect("128K window is smaller than 500K in use");
+        assert!(warning.contains("128.0k"), "{warning}");
+        assert!(warning.contains("500.0k"), "{warning}");
+
+        let info = ModelInfo::for_model("deepseek-v4-flash", CostCurrency::Usd);
+        assert_eq!(info.context_window, Some(1_000_000));
+        assert_eq!(info.rates.as_deref(), Some("$0.14 in · $0.28 out per 1M"));
+        assert!(info.thinking);
+        assert!(!info.recommended_use.is_empty());
+    }
+
     #[test]
     fn esc_closes_without_emitting() {
         let (app, _lock) = create_test_app();
@@ -4,11 +4,10 @@ use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use ratatui::layout::Rect;
 
 use crate::tui::app::App;
-use crate::tui::command_palette::{
-    CommandPaletteView, build_entries as build_command_palette_entries,
-};
+use crate::tui::command_palette::CommandPaletteView;
 use crate::tui::context_menu::{ContextMenuEntry, ContextMenuView};
-use crate::tui::history::HistoryCell;
+use crate::tui::history::{GenericToolCell, HistoryCell, ToolCell};
+use crate::tui::pager::PagerView;
 use crate::tui::scrolling::{ScrollDirection, TranscriptScroll};
 use crate::tui::selection::{SelectionAutoscroll, TranscriptSelectionPoint};
 use crate::tui::ui_text::{
@@ -332,23 +331,21 @@ pub(crate) fn build_context_menu_entries(app: &App, mouse: MouseEvent) -> Vec<Co
     }
 
     if let Some(filtered_cell_index) = transcript_cell_index_from_mouse(app, mouse) {
-        // Convert filtered index → original virtual index using the
-        // mapping built in ChatWidget::new. When no cells are collapsed
-        // this is an identity mapping.
-        let cell_index = app
-            .collapsed_cell_map
-            .get(filtered_cell_index)
-            .copied()
-            .unwrap_or(filtered_cell_index);
-
-        let target = detail_target_label(app, cell_index)
-            .map(|label| truncate_line_to_width(label.as_str(), 28))
-            .unwrap_or_else(|| "message".to_string());
-        entries.push(ContextMenuEntry {
-            label: "Open details".to_string(),
-            description: target,
-            action: ContextMenuAction::OpenDetails { cell_index },
-        });
+        let cell_index = unfiltered_cell_index(app, filtered_cell_index);
+
+        // Tool cells lead with their own actions, which include the pager.
+        let tool_entries = tool_context_menu_entries(app, cell_index);
+        if tool_entries.is_empty() {
+            let target = detail_target_label(app, cell_index)
+                .map(|label| truncate_line_to_width(label.as_str(), 28))
+                .unwrap_or_else(|| "message".to_string());
+            entries.push(ContextMenuEntry {
+                label: "Open details".to_string(),
+                description: target,
+                action: ContextMenuAction::OpenDetails { cell_index },
+            });
+        }
+        entries.extend(tool_entries);
         entries.push(ContextMenuEntry {
             label: "Copy message".to_string(),
             description: "write clicked transcript cell".to_string(),
@@ -409,6 +406,150 @@ pub(crate) fn build_context_menu_entries(app: &App, mouse: MouseEvent) -> Vec<Co
     entries
 }
 
+/// Convert a filtered transcript cell index to the original virtual index
+/// using the mapping built in `ChatWidget::new`. When no cells are collapsed
+/// this is an identity mapping.
+pub(crate) fn unfiltered_cell_index(app: &App, filtered_cell_index: usize) -> usize {
+    app.collapsed_cell_map
+        .get(filtered_cell_index)
+        .copied()
+        .unwrap_or(filtered_cell_index)
+}
+
+/// Tools whose re-run would need the per-turn sub-agent runtime.
+fn tool_supports_rerun(tool_name: &str) -> bool {
+    !matches!(
+        tool_name,
+        "agent_open"
+            | "agent_spawn"
+            | "agent_eval"
+            | "agent_close"
+            | "rlm_open"
+            | "rlm_eval"
+            | "rlm"
+            | "delegate"
+    )
+}
+
+/// Actions for a transcript cell that maps back to a recorded tool call.
+pub(crate) fn tool_context_menu_entries(app: &App, cell_index: usize) -> Vec<ContextMenuEntry> {
+    let Some(detail) = app.tool_detail_record_for_cell(cell_index) else {
+        return Vec::new();
+    };
+    let mut entries = Vec::new();
+    if detail.output.is_some() {
+        entries.push(ContextMenuEntry {
+            label: "Copy output".to_string(),
+            description: "full tool output to clipboard".to_string(),
+            action: ContextMenuAction::CopyToolOutput { cell_index },
+        });
+        entries.push(ContextMenuEntry {
+            label: "Save output".to_string(),
+            description: "write full output to a workspace file".to_string(),
+            action: ContextMenuAction::SaveToolOutput { cell_index },
+        });
+    }
+    entries.push(ContextMenuEntry {
+        label: "Open in pager".to_string(),
+        description: "tool input and output".to_string(),
+        action: ContextMenuAction::OpenDetails { cell_index },
+    });
+    if tool_supports_rerun(&detail.tool_name) {
+        entries.push(ContextMenuEntry {
+            label: "Re-run tool".to_string(),
+            description: format!("{} with the same arguments", detail.tool_name),
+            action: ContextMenuAction::RerunTool { cell_index },
+        });
+    }
+    if spilled_output_path(app, cell_index).is_some() {
+        entries.push(ContextMenuEntry {
+            label: "Full output".to_string(),
+            description: "open the spilled (untruncated) output".to_string(),
+            action: ContextMenuAction::OpenSpilledOutput { cell_index },
+        });
+    }
+    entries
+}
+
+fn spilled_output_path(app: &App, cell_index: usize) -> Option<&std::path::Path> {
+    match app.cell_at_virtual_index(cell_index)? {
+        HistoryCell::Tool(ToolCell::Generic(GenericToolCell {
+            spillover_path: Some(path),
+            ..
+        })) => Some(path.as_path()),
+        _ => None,
+    }
+}
+
+/// The tool output as the user would want it outside the transcript: the
+/// spilled file when the result was truncated, otherwise the recorded output.
+fn full_tool_output(app: &App, cell_index: usize) -> Option<String> {
+    if let Some(path) = spilled_output_path(app, cell_index)
+        && let Ok(text) = std::fs::read_to_string(path)
+    {
+        return Some(text);
+    }
+    app.tool_detail_record_for_cell(cell_index)?.output.clone()
+}
+
+/// Cell of the recorded tool call under the transcript selection, used by
+/// the `.` shortcut.
+pub(crate) fn selected_tool_cell_index(app: &App) -> Option<usize> {
+    let (start, _) = app.viewport.transcript_selection.ordered_endpoints()?;
+    let (filtered, _) = app
+        .viewport
+        .transcript_cache
+        .line_meta()
+        .get(start.line_index)?
+        .cell_line()?;
+    let cell_index = unfiltered_cell_index(app, filtered);
+    app.tool_detail_record_for_cell(cell_index)
+        .is_some()
+        .then_some(cell_index)
+}
+
+/// Open the tool action menu for a cell at the top-left of the transcript.
+pub(crate) fn open_tool_context_menu(app: &mut App, cell_index: usize) -> bool {
+    let entries = tool_context_menu_entries(app, cell_index);
+    if entries.is_empty() {
+        return false;
+    }
+    let (column, row) = app.viewport.last_transcript_area.map_or((0, 0), |area| {
+        (area.x.saturating_add(2), area.y.saturating_add(1))
+    });
+    app.view_stack
+        .push(ContextMenuView::new(entries, column, row));
+    app.needs_redraw = true;
+    true
+}
+
+fn save_tool_output(app: &mut App, cell_index: usize) {
+    let (Some(output), Some(tool_name)) = (
+        full_tool_output(app, cell_index),
+        app.tool_detail_record_for_cell(cell_index)
+            .map(|detail| detail.tool_name.clone()),
+    ) else {
+        app.status_message = Some("No tool output to save".to_string());
+        return;
+    };
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let file_name: String = format!("tool_output_{tool_name}_{timestamp}.txt")
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let path = app.workspace.join(file_name);
+    app.status_message = Some(match std::fs::write(&path, output) {
+        Ok(()) => format!("Saved output to {}", crate::utils::display_path(&path)),
+        Err(err) => format!("Failed to save output: {err}"),
+    });
+}
+
 pub(crate) fn transcript_cell_index_from_mouse(app: &App, mouse: MouseEvent) -> Option<usize> {
     let point = selection_point_from_mouse(app, mouse)?;
     app.viewport
@@ -445,20 +586,14 @@ pub(crate) fn handle_context_menu_action(app: &mut App, action: ContextMenuActio
             app.paste_from_clipboard();
         }
         ContextMenuAction::OpenCommandPalette => {
-            app.view_stack
-                .push(CommandPaletteView::new(build_command_palette_entries(
-                    app.ui_locale,
-                    &app.skills_dir,
-                    &app.workspace,
-                    &app.mcp_config_path,
-                    app.mcp_snapshot.as_ref(),
-                )));
+            app.view_stack.push(CommandPaletteView::for_app(app));
         }
         ContextMenuAction::OpenContextInspector => {
             open_context_inspector(app);
         }
         ContextMenuAction::OpenHelp => {
-            app.view_stack.push(HelpView::new_for_locale(app.ui_locale));
+            app.view_stack
+                .push(HelpView::new_for_locale(app.ui_locale).with_project_aliases(&app.workspace));
         }
         ContextMenuAction::OpenFileAtLine { cell_index } => {
             let width = app
@@ -492,6 +627,39 @@ pub(crate) fn handle_context_menu_action(app: &mut App, action: ContextMenuActio
             app.collapsed_cells.clear();
             app.status_message = Some(format!("{count} hidden cell(s) restored"));
         }
+        ContextMenuAction::CopyToolOutput { cell_index } => {
+            app.status_message = Some(match full_tool_output(app, cell_index) {
+                Some(output) if app.clipboard.write_text(&output).is_ok() => {
+                    "Tool output copied".to_string()
+                }
+                Some(_) => "Copy failed".to_string(),
+                None => "No tool output to copy".to_string(),
+            });
+        }
+        ContextMenuAction::SaveToolOutput { cell_index } => {
+            save_tool_output(app, cell_index);
+        }
+        ContextMenuAction::OpenSpilledOutput { cell_index } => {
+            match spilled_output_path(app, cell_index).map(std::path::Path::to_path_buf) {
+                Some(path) => {
+                    let body = std::fs::read_to_string(&path)
+                        .unwrap_or_else(|err| format!("(could not read spillover file: {err})"));
+                    let width = app
+                        .viewport
+                        .last_transcript_area
+                        .map(|area| area.width)
+                        .unwrap_or(80);
+                    app.view_stack.push(PagerView::from_text(
+                        format!("Full output: {}", path.display()),
+                        &body,
+                        width.saturating_sub(2),
+                    ));
+                }
+                None => app.status_message = Some("No spilled output for that cell".to_string()),
+            }
+        }
+        // Needs the engine handle; `handle_view_events` routes it first.
+        ContextMenuAction::RerunTool { .. } => {}
     }
     app.needs_redraw = true;
 }
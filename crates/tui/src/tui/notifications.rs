@@ -10,6 +10,12 @@
 //! When `method = "auto"`, the resolver picks the best method for the
 //! current terminal; Windows falls back to `Off` to avoid the error chime
 //! (#583).
+//!
+//! On top of the terminal escape, [`dispatch`] can ring the bell and raise
+//! an OS desktop notification (`notify-send` / `osascript`). It fires when
+//! the event took longer than the threshold or the terminal has lost focus,
+//! with per-event overrides in `[notifications.turn]` and
+//! `[notifications.subagent]`.
 
 #[cfg(target_os = "windows")]
 use windows::Win32::System::Diagnostics::Debug::MessageBeep;
@@ -17,6 +23,7 @@ use windows::Win32::System::Diagnostics::Debug::MessageBeep;
 use windows::Win32::UI::WindowsAndMessaging::MESSAGEBOX_STYLE;
 
 use std::io::{self, Write};
+use std::process::{Command, Stdio};
 use std::time::Duration;
 
 /// Notification delivery method.
@@ -207,6 +214,144 @@ pub fn notify_done(
     notify_done_to(method, in_tmux, msg, threshold, elapsed, &mut io::stdout());
 }
 
+/// Event a notification is for; each has its own
+/// `[notifications.<event>]` overrides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyEvent {
+    /// A turn finished successfully.
+    Turn,
+    /// The last running sub-agent finished.
+    SubAgent,
+}
+
+/// Resolved delivery settings for one [`NotifyEvent`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delivery {
+    pub method: Method,
+    pub threshold: Duration,
+    pub include_summary: bool,
+    pub when_unfocused: bool,
+    pub desktop: bool,
+    pub bell: bool,
+}
+
+impl Delivery {
+    /// Fire when the event ran past the threshold, or whenever the terminal
+    /// is unfocused (if `when_unfocused` is on).
+    #[must_use]
+    pub fn should_fire(&self, elapsed: Duration, focused: bool) -> bool {
+        elapsed >= self.threshold || (self.when_unfocused && !focused)
+    }
+}
+
+/// Resolve delivery for `event`: the `[notifications]` block, then the
+/// `[notifications.<event>]` overrides. `None` means "do not notify".
+pub fn delivery(config: &crate::config::Config, event: NotifyEvent) -> Option<Delivery> {
+    let (method, threshold, include_summary) = settings(config)?;
+    let notif = config.notifications_config();
+    let overrides = match event {
+        NotifyEvent::Turn => &notif.turn,
+        NotifyEvent::SubAgent => &notif.subagent,
+    };
+    if overrides.enabled == Some(false) {
+        return None;
+    }
+    // `notification_condition = "always"` wins over a per-event threshold.
+    let always = config
+        .tui
+        .as_ref()
+        .and_then(|tui| tui.notification_condition)
+        == Some(crate::config::NotificationCondition::Always);
+    let threshold = match overrides.threshold_secs {
+        Some(secs) if !always => Duration::from_secs(secs),
+        _ => threshold,
+    };
+    Some(Delivery {
+        method,
+        threshold,
+        include_summary,
+        when_unfocused: overrides
+            .when_unfocused
+            .or(notif.when_unfocused)
+            .unwrap_or(true),
+        desktop: overrides.desktop.unwrap_or(notif.desktop),
+        bell: overrides.bell.unwrap_or(notif.bell),
+    })
+}
+
+/// Deliver `msg` through every channel `delivery` enables, writing terminal
+/// bytes to `sink`. Returns whether anything fired.
+pub fn dispatch_to<W: Write>(
+    delivery: &Delivery,
+    in_tmux: bool,
+    msg: &str,
+    elapsed: Duration,
+    focused: bool,
+    sink: &mut W,
+) -> bool {
+    if !delivery.should_fire(elapsed, focused) {
+        return false;
+    }
+    notify_done_to(delivery.method, in_tmux, msg, Duration::ZERO, elapsed, sink);
+    if delivery.bell && delivery.method != Method::Bel {
+        let _ = sink.write_all(b"\x07");
+        let _ = sink.flush();
+    }
+    if delivery.desktop {
+        send_desktop("DeepSeek TUI", msg);
+    }
+    true
+}
+
+/// [`dispatch_to`] on **stdout**.
+pub fn dispatch(delivery: &Delivery, in_tmux: bool, msg: &str, elapsed: Duration, focused: bool) {
+    dispatch_to(delivery, in_tmux, msg, elapsed, focused, &mut io::stdout());
+}
+
+/// Raise an OS desktop notification without blocking the UI. Best-effort:
+/// a missing `notify-send` / `osascript` is logged and otherwise ignored.
+fn send_desktop(title: &str, body: &str) {
+    let Some(mut cmd) = desktop_command(title, body) else {
+        return;
+    };
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    match cmd.spawn() {
+        // Reap the child off the UI thread.
+        Ok(mut child) => {
+            std::thread::spawn(move || {
+                let _ = child.wait();
+            });
+        }
+        Err(err) => tracing::debug!(%err, "desktop notification unavailable"),
+    }
+}
+
+/// The platform notifier command. Windows has no command-line notifier in
+/// the base install, so desktop notifications are skipped there.
+fn desktop_command(title: &str, body: &str) -> Option<Command> {
+    if cfg!(target_os = "windows") {
+        return None;
+    }
+    if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("osascript");
+        cmd.arg("-e").arg(format!(
+            "display notification {} with title {}",
+            applescript_string(body),
+            applescript_string(title)
+        ));
+        return Some(cmd);
+    }
+    let mut cmd = Command::new("notify-send");
+    cmd.arg(format!("--app-name={title}")).arg(title).arg(body);
+    Some(cmd)
+}
+
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
 /// Return a human-readable duration string, capped at two units so
 /// it stays compact in headers and notifications.
 ///
@@ -885,4 +1030,121 @@ mod tests {
             "3w 2d"
         );
     }
+
+    fn config(toml_src: &str) -> crate::config::Config {
+        toml::from_str(toml_src).expect("config parses")
+    }
+
+    #[test]
+    fn delivery_applies_per_event_overrides() {
+        let cfg = config(
+            r#"
+[notifications]
+method = "osc9"
+threshold_secs = 60
+desktop = true
+
+[notifications.turn]
+threshold_secs = 5
+bell = true
+
+[notifications.subagent]
+enabled = false
+"#,
+        );
+        let turn = delivery(&cfg, NotifyEvent::Turn).expect("turn enabled");
+        assert_eq!(turn.method, Method::Osc9);
+        assert_eq!(turn.threshold, Duration::from_secs(5));
+        assert!(turn.when_unfocused);
+        assert!(turn.desktop);
+        assert!(turn.bell);
+        assert_eq!(delivery(&cfg, NotifyEvent::SubAgent), None);
+
+        // `notification_condition = "always"` wins over per-event thresholds.
+        let cfg = config(
+            r#"
+[tui]
+notification_condition = "always"
+
+[notifications.turn]
+threshold_secs = 5
+"#,
+        );
+        let turn = delivery(&cfg, NotifyEvent::Turn).expect("turn enabled");
+        assert_eq!(turn.threshold, Duration::ZERO);
+    }
+
+    #[test]
+    fn unfocused_terminal_fires_below_threshold() {
+        let cfg = config(
+            r#"
+[notifications]
+method = "osc9"
+threshold_secs = 30
+"#,
+        );
+        let turn = delivery(&cfg, NotifyEvent::Turn).expect("turn enabled");
+        let short = Duration::from_secs(2);
+        assert!(!turn.should_fire(short, true));
+        assert!(turn.should_fire(short, false));
+        assert!(turn.should_fire(Duration::from_secs(30), true));
+
+        let cfg = config(
+            r#"
+[notifications]
+method = "osc9"
+threshold_secs = 30
+when_unfocused = false
+"#,
+        );
+        let turn = delivery(&cfg, NotifyEvent::Turn).expect("turn enabled");
+        assert!(!turn.should_fire(short, false));
+    }
+
+    #[test]
+    fn dispatch_writes_escape_and_optional_bell() {
+        let mut delivery = Delivery {
+            method: Method::Osc9,
+            threshold: Duration::from_secs(30),
+            include_summary: false,
+            when_unfocused: true,
+            desktop: false,
+            bell: true,
+        };
+        let mut buf = Vec::new();
+        assert!(dispatch_to(
+            &delivery,
+            false,
+            "done",
+            Duration::from_secs(1),
+            false,
+            &mut buf
+        ));
+        assert_eq!(buf, b"\x1b]9;done\x07\x07");
+
+        // Focused and under the threshold: nothing is written.
+        let mut buf = Vec::new();
+        assert!(!dispatch_to(
+            &delivery,
+            false,
+            "done",
+            Duration::from_secs(1),
+            true,
+            &mut buf
+        ));
+        assert!(buf.is_empty());
+
+        // BEL method plus `bell = true` rings once, not twice.
+        delivery.method = Method::Bel;
+        let mut buf = Vec::new();
+        dispatch_to(
+            &delivery,
+            false,
+            "done",
+            Duration::from_secs(60),
+            true,
+            &mut buf,
+        );
+        assert_eq!(buf, b"\x07");
+    }
 }
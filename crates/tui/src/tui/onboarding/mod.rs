@@ -2,6 +2,8 @@
 
 pub mod api_key;
 pub mod language;
+pub mod preferences;
+pub mod test_prompt;
 pub mod trust_directory;
 pub mod welcome;
 
@@ -35,6 +37,8 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         OnboardingState::Welcome => welcome::lines(),
         OnboardingState::Language => language::lines(app),
         OnboardingState::ApiKey => api_key::lines(app),
+        OnboardingState::Preferences => preferences::lines(app),
+        OnboardingState::TestPrompt => test_prompt::lines(app),
         OnboardingState::TrustDirectory => trust_directory::lines(app),
         OnboardingState::Tips => tips_lines(app),
         OnboardingState::None => Vec::new(),
@@ -70,23 +74,24 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
 
 fn onboarding_step(app: &App) -> (usize, usize) {
     let needs_trust = !app.trust_mode && needs_trust(&app.workspace);
-    // Welcome + Language + Tips are always shown.
-    let mut total = 3;
+    // Welcome + Language + Preferences + TestPrompt + Tips are always shown.
+    let mut total = 5;
     if app.onboarding_needs_api_key {
         total += 1;
     }
     if needs_trust {
         total += 1;
     }
+    let api_key_offset = usize::from(app.onboarding_needs_api_key);
 
     let step = match app.onboarding {
         OnboardingState::Welcome => 1,
         OnboardingState::Language => 2,
         OnboardingState::ApiKey => 3,
-        OnboardingState::TrustDirectory => {
-            // Welcome (1) + Language (2) + optional ApiKey
-            if app.onboarding_needs_api_key { 4 } else { 3 }
-        }
+        // Welcome (1) + Language (2) + optional ApiKey
+        OnboardingState::Preferences => 3 + api_key_offset,
+        OnboardingState::TestPrompt => 4 + api_key_offset,
+        OnboardingState::TrustDirectory => 5 + api_key_offset,
         OnboardingState::Tips => total,
         OnboardingState::None => total,
     };
@@ -146,19 +151,14 @@ pub fn mark_onboarded() -> std::io::Result<PathBuf> {
 }
 
 pub fn needs_trust(workspace: &Path) -> bool {
-    if crate::config::is_workspace_trusted(workspace) {
-        return false;
-    }
-
-    let markers = [
-        workspace.join(".deepseek").join("trusted"),
-        workspace.join(".deepseek").join("trust.json"),
-    ];
-    !markers.iter().any(|path| path.exists())
+    crate::trust_level::load(workspace).is_none()
 }
 
-pub fn mark_trusted(workspace: &Path) -> anyhow::Result<PathBuf> {
-    crate::config::save_workspace_trust(workspace)
+pub fn mark_trusted(
+    workspace: &Path,
+    level: crate::trust_level::TrustLevel,
+) -> anyhow::Result<PathBuf> {
+    crate::trust_level::save(workspace, level)
 }
 
 // ── API key validation and state-machine transitions ─────────────────
@@ -214,18 +214,62 @@ pub fn advance_onboarding_from_welcome(app: &mut App) {
 }
 
 /// Language → next step. Routes to ApiKey when the session lacks a key,
-/// to TrustDirectory when the workspace is untrusted, otherwise to Tips.
+/// to Preferences on a first run, otherwise to the trust prompt or Tips.
 pub fn advance_onboarding_after_language(app: &mut App) {
     app.status_message = None;
     if app.onboarding_needs_api_key {
         app.onboarding = OnboardingState::ApiKey;
-    } else if !app.trust_mode && needs_trust(&app.workspace) {
+    } else if app.onboarding_configure_defaults {
+        app.onboarding_preferences = preferences::PreferencesDraft::from_app(app);
+        app.onboarding = OnboardingState::Preferences;
+    } else {
+        advance_onboarding_to_trust_or_tips(app);
+    }
+}
+
+/// Preferences → TestPrompt. Called once the picks have been saved.
+pub fn advance_onboarding_after_preferences(app: &mut App) {
+    app.status_message = None;
+    app.onboarding_configure_defaults = false;
+    app.onboarding_test_prompt = test_prompt::TestPromptStatus::Idle;
+    app.onboarding = OnboardingState::TestPrompt;
+}
+
+/// Routes to TrustDirectory when the workspace is untrusted, otherwise to
+/// Tips. Used after the test-prompt screen and whenever the defaults step is
+/// skipped.
+pub fn advance_onboarding_to_trust_or_tips(app: &mut App) {
+    app.status_message = None;
+    if !app.trust_mode && needs_trust(&app.workspace) {
         app.onboarding = OnboardingState::TrustDirectory;
     } else {
         app.onboarding = OnboardingState::Tips;
     }
 }
 
+/// Apply the preferences picked during onboarding to the running session
+/// and persist them: model and mode to `settings.toml`, approval mode to
+/// `config.toml` as `approval_policy`.
+pub fn apply_onboarding_preferences(app: &mut App) -> anyhow::Result<()> {
+    let draft = app.onboarding_preferences.clone();
+    let model = draft.selected_model().to_string();
+    let mode = draft.selected_mode();
+    let (approval, approval_value) = draft.selected_approval();
+
+    let mut settings =
+        crate::settings::Settings::load().unwrap_or_else(|_| crate::settings::Settings::default());
+    settings.set("default_model", &model)?;
+    settings.set("default_mode", mode.as_setting())?;
+    settings.save()?;
+    crate::commands::persist_root_string_key("approval_policy", approval_value)?;
+
+    app.set_model_selection(model);
+    app.update_model_compaction_budget();
+    let _ = app.set_mode(mode);
+    app.set_approval_mode(approval);
+    Ok(())
+}
+
 /// Re-validate the current `api_key_input` and project the result onto
 /// `app.status_message`. `show_empty_error` reports the "cannot be empty"
 /// message even when the input has not been touched yet (used right
@@ -0,0 +1,304 @@
+//! Default model / mode / approval picker for first-run onboarding.
+//!
+//! The model list is fetched live from the provider when the step opens;
+//! until it arrives (or when the fetch fails) the picker offers the current
+//! model plus `auto`. Choices are written on Enter: model and mode land in
+//! `~/.deepseek/settings.toml`, the approval mode in `config.toml` as
+//! `approval_policy`.
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::palette;
+use crate::tui::app::{App, AppMode};
+use crate::tui::approval::ApprovalMode;
+
+/// Mode options shown in the picker, as `(mode, blurb)`.
+pub const MODE_OPTIONS: &[(AppMode, &str)] = &[
+    (
+        AppMode::Agent,
+        "works with tools, asks before risky actions",
+    ),
+    (AppMode::Plan, "read-only investigation and planning first"),
+    (AppMode::Yolo, "full tool access without approvals"),
+];
+
+/// Approval options shown in the picker, as `(mode, config value, blurb)`.
+pub const APPROVAL_OPTIONS: &[(ApprovalMode, &str, &str)] = &[
+    (
+        ApprovalMode::Suggest,
+        "suggest",
+        "ask before writes and shell commands",
+    ),
+    (ApprovalMode::Auto, "auto", "approve every tool call"),
+    (
+        ApprovalMode::Never,
+        "never",
+        "refuse tools that need approval",
+    ),
+];
+
+/// Row currently focused in the picker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PreferenceField {
+    #[default]
+    Model,
+    Mode,
+    Approval,
+}
+
+impl PreferenceField {
+    fn next(self) -> Self {
+        match self {
+            Self::Model => Self::Mode,
+            Self::Mode => Self::Approval,
+            Self::Approval => Self::Model,
+        }
+    }
+
+    fn prev(self) -> Self {
+        match self {
+            Self::Model => Self::Approval,
+            Self::Mode => Self::Model,
+            Self::Approval => Self::Mode,
+        }
+    }
+}
+
+/// Progress of the live model-list fetch.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ModelListStatus {
+    #[default]
+    NotFetched,
+    Fetching,
+    Loaded,
+    Failed(String),
+}
+
+/// In-progress selections for the preferences step.
+#[derive(Debug, Clone, Default)]
+pub struct PreferencesDraft {
+    pub field: PreferenceField,
+    pub models: Vec<String>,
+    pub model_index: usize,
+    pub mode_index: usize,
+    pub approval_index: usize,
+    pub model_status: ModelListStatus,
+}
+
+impl PreferencesDraft {
+    /// Seed the draft from the session's current model, mode, and approval
+    /// mode so pressing Enter straight away keeps today's behavior.
+    #[must_use]
+    pub fn from_app(app: &App) -> Self {
+        let current_model = app.model_selection_for_persistence();
+        let mut draft = Self {
+            mode_index: MODE_OPTIONS
+                .iter()
+                .position(|(mode, _)| *mode == app.mode)
+                .unwrap_or(0),
+            approval_index: APPROVAL_OPTIONS
+                .iter()
+                .position(|(mode, _, _)| *mode == app.approval_mode)
+                .unwrap_or(0),
+            ..Self::default()
+        };
+        draft.set_models(Vec::new(), &current_model);
+        draft
+    }
+
+    /// Replace the model list, keeping `auto` and `current` selectable and
+    /// selecting `current`.
+    pub fn set_models(&mut self, fetched: Vec<String>, current: &str) {
+        let mut models = vec!["auto".to_string()];
+        if !current.is_empty() && !current.eq_ignore_ascii_case("auto") {
+            models.push(current.to_string());
+        }
+        for id in fetched {
+            if !models.iter().any(|existing| existing == &id) {
+                models.push(id);
+            }
+        }
+        self.model_index = models
+            .iter()
+            .position(|id| id.eq_ignore_ascii_case(current))
+            .unwrap_or(0);
+        self.models = models;
+    }
+
+    #[must_use]
+    pub fn selected_model(&self) -> &str {
+        self.models
+            .get(self.model_index)
+            .map_or("auto", String::as_str)
+    }
+
+    #[must_use]
+    pub fn selected_mode(&self) -> AppMode {
+        MODE_OPTIONS[self.mode_index.min(MODE_OPTIONS.len() - 1)].0
+    }
+
+    #[must_use]
+    pub fn selected_approval(&self) -> (ApprovalMode, &'static str) {
+        let (mode, value, _) =
+            APPROVAL_OPTIONS[self.approval_index.min(APPROVAL_OPTIONS.len() - 1)];
+        (mode, value)
+    }
+
+    pub fn focus_next(&mut self) {
+        self.field = self.field.next();
+    }
+
+    pub fn focus_prev(&mut self) {
+        self.field = self.field.prev();
+    }
+
+    /// Cycle the focused row's value by `delta` (wrapping).
+    pub fn cycle(&mut self, delta: isize) {
+        let (index, len) = match self.field {
+            PreferenceField::Model => (&mut self.model_index, self.models.len()),
+            PreferenceField::Mode => (&mut self.mode_index, MODE_OPTIONS.len()),
+            PreferenceField::Approval => (&mut self.approval_index, APPROVAL_OPTIONS.len()),
+        };
+        if len == 0 {
+            return;
+        }
+        let len = len as isize;
+        *index = ((*index as isize + delta).rem_euclid(len)) as usize;
+    }
+}
+
+pub fn lines(app: &App) -> Vec<Line<'static>> {
+    let draft = &app.onboarding_preferences;
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Defaults",
+            Style::default()
+                .fg(palette::DEEPSEEK_SKY)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Pick what new sessions start with. You can change these later with /config.",
+            Style::default().fg(palette::TEXT_MUTED),
+        )),
+        Line::from(""),
+    ];
+
+    let model_note = match &draft.model_status {
+        ModelListStatus::NotFetched | ModelListStatus::Fetching => {
+            " (fetching models…)".to_string()
+        }
+        ModelListStatus::Loaded => format!(" ({} available)", draft.models.len()),
+        ModelListStatus::Failed(_) => " (offline list)".to_string(),
+    };
+    let (_, mode_blurb) = MODE_OPTIONS[draft.mode_index.min(MODE_OPTIONS.len() - 1)];
+    let (approval, _) = draft.selected_approval();
+    let approval_blurb = APPROVAL_OPTIONS
+        .iter()
+        .find(|(mode, _, _)| *mode == approval)
+        .map_or("", |(_, _, blurb)| *blurb);
+
+    lines.push(row(
+        draft.field == PreferenceField::Model,
+        "Model",
+        draft.selected_model().to_string(),
+        model_note,
+    ));
+    lines.push(row(
+        draft.field == PreferenceField::Mode,
+        "Mode",
+        draft.selected_mode().as_setting().to_string(),
+        format!(" — {mode_blurb}"),
+    ));
+    lines.push(row(
+        draft.field == PreferenceField::Approval,
+        "Approvals",
+        approval.label().to_ascii_lowercase(),
+        format!(" — {approval_blurb}"),
+    ));
+
+    if let ModelListStatus::Failed(err) = &draft.model_status {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("Could not fetch models: {err}"),
+            Style::default().fg(palette::STATUS_WARNING),
+        )));
+    }
+    if let Some(message) = app.status_message.as_deref() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            message.to_string(),
+            Style::default().fg(palette::STATUS_WARNING),
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓ choose a row, ←/→ change it, Enter saves and continues.",
+        Style::default().fg(palette::TEXT_MUTED),
+    )));
+    lines
+}
+
+fn row(focused: bool, label: &str, value: String, note: String) -> Line<'static> {
+    let marker = if focused { "›" } else { " " };
+    let value_style = if focused {
+        Style::default()
+            .fg(palette::DEEPSEEK_BLUE)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(palette::TEXT_PRIMARY)
+    };
+    Line::from(vec![
+        Span::styled(
+            format!(" {marker} {label:<10} "),
+            Style::default().fg(palette::TEXT_MUTED),
+        ),
+        Span::styled(format!("‹ {value} ›"), value_style),
+        Span::styled(note, Style::default().fg(palette::TEXT_MUTED)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_models_keeps_auto_and_current_and_selects_current() {
+        let mut draft = PreferencesDraft::default();
+        draft.set_models(
+            vec![
+                "deepseek-v4-flash".to_string(),
+                "deepseek-v4-pro".to_string(),
+            ],
+            "deepseek-v4-pro",
+        );
+        assert_eq!(draft.models[0], "auto");
+        assert_eq!(draft.selected_model(), "deepseek-v4-pro");
+        assert_eq!(
+            draft
+                .models
+                .iter()
+                .filter(|m| *m == "deepseek-v4-pro")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn cycle_wraps_within_the_focused_row() {
+        let mut draft = PreferencesDraft::default();
+        draft.set_models(vec!["a".to_string()], "auto");
+        draft.cycle(-1);
+        assert_eq!(draft.selected_model(), "a");
+        draft.focus_next();
+        draft.cycle(1);
+        assert_eq!(draft.selected_mode(), AppMode::Plan);
+        draft.focus_prev();
+        draft.focus_prev();
+        assert_eq!(draft.field, PreferenceField::Approval);
+        draft.cycle(-1);
+        assert_eq!(draft.selected_approval().1, "never");
+    }
+}
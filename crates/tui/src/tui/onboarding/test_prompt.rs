@@ -0,0 +1,138 @@
+//! "Test a one-line prompt" screen, the last check before the main TUI.
+//!
+//! Sends a tiny non-streaming request with the chosen default model so a bad
+//! key, base URL, or proxy shows up here instead of on the first real turn.
+
+use std::time::Duration;
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::palette;
+use crate::tui::app::App;
+
+/// Prompt sent by the connectivity check.
+pub const TEST_PROMPT: &str = "Reply with the single word: ready";
+
+/// Outcome of the connectivity check.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TestPromptStatus {
+    #[default]
+    Idle,
+    Running,
+    Passed {
+        model: String,
+        reply: String,
+        elapsed: Duration,
+    },
+    Failed(String),
+}
+
+pub fn lines(app: &App) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Test a prompt",
+            Style::default()
+                .fg(palette::DEEPSEEK_SKY)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Send a one-line prompt to check the key, endpoint, and model before you start.",
+            Style::default().fg(palette::TEXT_PRIMARY),
+        )),
+        Line::from(Span::styled(
+            format!("  > {TEST_PROMPT}"),
+            Style::default().fg(palette::TEXT_MUTED),
+        )),
+        Line::from(""),
+    ];
+
+    match &app.onboarding_test_prompt {
+        TestPromptStatus::Idle => {}
+        TestPromptStatus::Running => lines.push(Line::from(Span::styled(
+            "Waiting for a reply…",
+            Style::default().fg(palette::TEXT_MUTED),
+        ))),
+        TestPromptStatus::Passed {
+            model,
+            reply,
+            elapsed,
+        } => {
+            lines.push(Line::from(Span::styled(
+                format!("✓ {model} answered in {:.1}s", elapsed.as_secs_f64()),
+                Style::default()
+                    .fg(palette::STATUS_SUCCESS)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            lines.push(Line::from(Span::styled(
+                format!("  {}", truncate_reply(reply)),
+                Style::default().fg(palette::TEXT_PRIMARY),
+            )));
+        }
+        TestPromptStatus::Failed(err) => {
+            lines.push(Line::from(Span::styled(
+                format!("✗ Request failed: {err}"),
+                Style::default().fg(palette::STATUS_ERROR),
+            )));
+            lines.push(Line::from(Span::styled(
+                "Check the key and base_url in ~/.deepseek/config.toml, or run `deepseek doctor`.",
+                Style::default().fg(palette::TEXT_MUTED),
+            )));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(
+            "T",
+            Style::default()
+                .fg(palette::TEXT_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            " send the test   ",
+            Style::default().fg(palette::TEXT_MUTED),
+        ),
+        Span::styled(
+            "Enter",
+            Style::default()
+                .fg(palette::TEXT_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" continue", Style::default().fg(palette::TEXT_MUTED)),
+    ]));
+    lines
+}
+
+/// Keep the reply to one short line so an unexpectedly chatty model does not
+/// push the footer out of the panel.
+fn truncate_reply(reply: &str) -> String {
+    const MAX_CHARS: usize = 120;
+    let first_line = reply
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("");
+    let trimmed = first_line.trim();
+    if trimmed.chars().count() > MAX_CHARS {
+        let mut out: String = trimmed.chars().take(MAX_CHARS).collect();
+        out.push('…');
+        out
+    } else if trimmed.is_empty() {
+        "(empty reply)".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_reply_keeps_first_non_empty_line() {
+        assert_eq!(truncate_reply("\n  ready \nmore"), "ready");
+        assert_eq!(truncate_reply(""), "(empty reply)");
+        assert!(truncate_reply(&"x".repeat(300)).ends_with('…'));
+    }
+}
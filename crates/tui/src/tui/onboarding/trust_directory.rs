@@ -37,6 +37,10 @@ pub fn lines(app: &App) -> Vec<Line<'static>> {
         app.tr(MessageId::OnboardTrustEffectHint).to_string(),
         Style::default().fg(palette::TEXT_MUTED),
     )));
+    lines.push(Line::from(Span::styled(
+        app.tr(MessageId::OnboardTrustLevelHint).to_string(),
+        Style::default().fg(palette::TEXT_MUTED),
+    )));
     if let Some(message) = app.status_message.as_deref() {
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
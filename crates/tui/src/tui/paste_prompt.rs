@@ -0,0 +1,473 @@
+//! Prompt shown when a paste is too large to drop into the composer as-is.
+//!
+//! Pastes longer than the `paste_prompt_lines` setting open this modal
+//! instead of flooding the composer. The user can inline the text as a
+//! fenced code block (tagged with a guessed language), attach it as a file
+//! under `.deepseek/pastes/` that the model reads through the normal
+//! `@`-mention path, or drop the paste entirely.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, Widget, Wrap};
+
+use crate::palette;
+use crate::tui::views::{ModalKind, ModalView, ViewAction, ViewEvent};
+
+/// What to do with a large paste.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteChoice {
+    /// Insert into the composer wrapped in a fenced code block.
+    Inline,
+    /// Write to `.deepseek/pastes/` and insert an `@`-mention.
+    Attach,
+}
+
+/// Lines previewed at the top of the prompt.
+const PREVIEW_LINES: usize = 6;
+
+/// Best-effort language guess for a fenced block / file extension. Returns
+/// `None` when nothing scores convincingly (the paste is likely prose or a
+/// log).
+#[must_use]
+pub fn guess_language(text: &str) -> Option<&'static str> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    if let Some(first) = trimmed.lines().next()
+        && let Some(interpreter) = first.strip_prefix("#!")
+    {
+        let interpreter = interpreter.rsplit('/').next().unwrap_or(interpreter);
+        let interpreter = interpreter
+            .strip_prefix("env ")
+            .unwrap_or(interpreter)
+            .trim();
+        return Some(match interpreter.split_whitespace().next().unwrap_or("") {
+            name if name.starts_with("python") => "python",
+            "node" | "deno" | "bun" => "javascript",
+            "ruby" => "ruby",
+            "perl" => "perl",
+            _ => "bash",
+        });
+    }
+    if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && serde_json::from_str::<serde_json::Value>(trimmed).is_ok()
+    {
+        return Some("json");
+    }
+    if trimmed.starts_with("diff --git")
+        || (trimmed.starts_with("--- ") && trimmed.contains("\n+++ ") && trimmed.contains("\n@@"))
+    {
+        return Some("diff");
+    }
+    let lower_head: String = trimmed.chars().take(64).collect::<String>().to_lowercase();
+    if lower_head.starts_with("<!doctype html") || lower_head.starts_with("<html") {
+        return Some("html");
+    }
+    if lower_head.starts_with("<?xml") {
+        return Some("xml");
+    }
+
+    // Line-prefix markers: each hit adds a point to its language and the
+    // best score wins if it is clear of the runner-up.
+    const MARKERS: &[(&str, &[&str])] = &[
+        (
+            "rust",
+            &[
+                "fn ",
+                "pub fn ",
+                "pub(crate) ",
+                "impl ",
+                "impl<",
+                "use std::",
+                "use crate::",
+                "let mut ",
+                "#[derive",
+                "#[test]",
+                "mod ",
+                "pub struct ",
+                "pub enum ",
+                "match ",
+            ],
+        ),
+        (
+            "python",
+            &[
+                "def ",
+                "async def ",
+                "class ",
+                "import ",
+                "from ",
+                "elif ",
+                "self.",
+                "if __name__",
+                "print(",
+                "return ",
+            ],
+        ),
+        (
+            "typescript",
+            &[
+                "interface ",
+                "export interface ",
+                "type ",
+                "export type ",
+                "export const ",
+                "import type ",
+                "enum ",
+                "readonly ",
+            ],
+        ),
+        (
+            "javascript",
+            &[
+                "const ",
+                "let ",
+                "function ",
+                "export ",
+                "import ",
+                "module.exports",
+                "require(",
+                "console.log",
+                "async function ",
+            ],
+        ),
+        (
+            "go",
+            &[
+                "package ",
+                "func ",
+                "import (",
+                "type ",
+                "defer ",
+                "if err != nil",
+            ],
+        ),
+        (
+            "sql",
+            &[
+                "select ",
+                "insert into ",
+                "update ",
+                "delete from ",
+                "create table ",
+                "alter table ",
+                "from ",
+                "where ",
+                "join ",
+                "group by ",
+                "order by ",
+            ],
+        ),
+        (
+            "bash",
+            &["$ ", "export ", "echo ", "cd ", "sudo ", "set -e"],
+        ),
+        (
+            "yaml",
+            &["- name:", "---", "apiVersion:", "kind:", "steps:", "jobs:"],
+        ),
+        (
+            "toml",
+            &["[package]", "[dependencies]", "[workspace]", "[["],
+        ),
+    ];
+
+    let mut scores = [0usize; MARKERS.len()];
+    for line in trimmed.lines().take(400) {
+        let line = line.trim_start();
+        let lower = line.to_ascii_lowercase();
+        for (idx, (language, markers)) in MARKERS.iter().enumerate() {
+            let haystack = if *language == "sql" { &lower } else { line };
+            if markers.iter().any(|marker| haystack.starts_with(marker)) {
+                scores[idx] += 1;
+            }
+        }
+        // Signature shapes count double: `-> T {` is Rust, a `def`/`class`
+        // header ending in `:` is Python, and bare block keywords are shell.
+        if line.ends_with(" {") && line.contains("->") {
+            scores[0] += 1;
+        }
+        if line.ends_with(':') && (line.starts_with("def ") || line.starts_with("class ")) {
+            scores[1] += 1;
+        }
+        if matches!(line.trim_end(), "fi" | "done" | "esac" | "then" | "do") {
+            scores[6] += 1;
+        }
+    }
+
+    let mut ranked: Vec<(usize, &str)> = scores
+        .iter()
+        .zip(MARKERS)
+        .map(|(score, (language, _))| (*score, *language))
+        .collect();
+    ranked.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    let (best, language) = ranked[0];
+    let runner_up = ranked[1].0;
+    if best >= 2 && best > runner_up {
+        Some(language)
+    } else {
+        None
+    }
+}
+
+/// File extension for a guessed language (`txt` when unknown).
+#[must_use]
+pub fn extension_for(language: Option<&str>) -> &'static str {
+    match language {
+        Some("rust") => "rs",
+        Some("python") => "py",
+        Some("typescript") => "ts",
+        Some("javascript") => "js",
+        Some("go") => "go",
+        Some("sql") => "sql",
+        Some("bash") => "sh",
+        Some("yaml") => "yaml",
+        Some("toml") => "toml",
+        Some("json") => "json",
+        Some("diff") => "diff",
+        Some("html") => "html",
+        Some("xml") => "xml",
+        Some("ruby") => "rb",
+        Some("perl") => "pl",
+        _ => "txt",
+    }
+}
+
+/// Wrap `text` in a fenced block, using a fence longer than any backtick run
+/// inside it so embedded fences cannot close the block early.
+#[must_use]
+pub fn fence_paste(text: &str, language: Option<&str>) -> String {
+    let longest_run = text.split(|ch| ch != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    let body = text.strip_suffix('\n').unwrap_or(text);
+    format!("{fence}{}\n{body}\n{fence}", language.unwrap_or(""))
+}
+
+const OPTIONS: [(&str, &str); 3] = [
+    ("Inline as code block", "Insert into the composer, fenced"),
+    (
+        "Attach as file",
+        "Save under .deepseek/pastes/ and @mention it",
+    ),
+    ("Cancel", "Discard the paste"),
+];
+
+#[derive(Debug, Clone)]
+pub struct PastePromptView {
+    text: String,
+    line_count: usize,
+    language: Option<&'static str>,
+    selected: usize,
+}
+
+impl PastePromptView {
+    #[must_use]
+    pub fn new(text: String) -> Self {
+        let line_count = text.lines().count();
+        let language = guess_language(&text);
+        Self {
+            text,
+            line_count,
+            language,
+            selected: 0,
+        }
+    }
+
+    fn submit(&mut self, index: usize) -> ViewAction {
+        let choice = match index {
+            0 => PasteChoice::Inline,
+            1 => PasteChoice::Attach,
+            _ => return ViewAction::Close,
+        };
+        ViewAction::EmitAndClose(ViewEvent::PasteResolved {
+            text: std::mem::take(&mut self.text),
+            choice,
+            language: self.language,
+        })
+    }
+}
+
+impl ModalView for PastePromptView {
+    fn kind(&self) -> ModalKind {
+        ModalKind::PastePrompt
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                ViewAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(OPTIONS.len() - 1);
+                ViewAction::None
+            }
+            KeyCode::Char('1') | KeyCode::Char('i') | KeyCode::Char('I') => self.submit(0),
+            KeyCode::Char('2') | KeyCode::Char('a') | KeyCode::Char('A') => self.submit(1),
+            KeyCode::Char('3') | KeyCode::Char('c') | KeyCode::Char('C') | KeyCode::Esc => {
+                ViewAction::Close
+            }
+            KeyCode::Enter => self.submit(self.selected),
+            _ => ViewAction::None,
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let mut lines: Vec<Line> = Vec::new();
+        let language = self.language.unwrap_or("plain text");
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "Large paste: {} lines, looks like {language}",
+                self.line_count
+            ),
+            Style::default().fg(palette::TEXT_PRIMARY).bold(),
+        )]));
+        lines.push(Line::from(""));
+        for preview in self.text.lines().take(PREVIEW_LINES) {
+            let preview: String = preview.chars().take(120).collect();
+            lines.push(Line::from(Span::styled(
+                format!("  {preview}"),
+                Style::default().fg(palette::TEXT_MUTED),
+            )));
+        }
+        if self.line_count > PREVIEW_LINES {
+            lines.push(Line::from(Span::styled(
+                format!("  … {} more lines", self.line_count - PREVIEW_LINES),
+                Style::default().fg(palette::TEXT_DIM),
+            )));
+        }
+        lines.push(Line::from(""));
+
+        for (idx, (label, description)) in OPTIONS.iter().enumerate() {
+            let selected = self.selected == idx;
+            let row_style = if selected {
+                Style::default()
+                    .fg(palette::SELECTION_TEXT)
+                    .bg(palette::SELECTION_BG)
+                    .bold()
+            } else {
+                Style::default().fg(palette::TEXT_PRIMARY)
+            };
+            let detail_style = if selected {
+                row_style
+            } else {
+                Style::default().fg(palette::TEXT_MUTED)
+            };
+            let prefix = if selected { ">" } else { " " };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{prefix} {}) {label}", idx + 1), row_style),
+                Span::styled(format!("  {description}"), detail_style),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(
+                "i / a / c",
+                Style::default().fg(palette::DEEPSEEK_SKY).bold(),
+            ),
+            Span::styled(" quick pick", Style::default().fg(palette::TEXT_MUTED)),
+            Span::raw("  "),
+            Span::styled("Enter", Style::default().fg(palette::DEEPSEEK_SKY).bold()),
+            Span::styled(" confirm", Style::default().fg(palette::TEXT_MUTED)),
+            Span::raw("  "),
+            Span::styled("Esc", Style::default().fg(palette::DEEPSEEK_SKY).bold()),
+            Span::styled(" discard", Style::default().fg(palette::TEXT_MUTED)),
+        ]));
+
+        let height = u16::try_from(lines.len() + 4)
+            .unwrap_or(u16::MAX)
+            .min(area.height);
+        let width = area.width.saturating_sub(4).min(96);
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+        Clear.render(popup_area, buf);
+        Paragraph::new(lines)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .title(Line::from(Span::styled(
+                        " Paste ",
+                        Style::default().fg(palette::DEEPSEEK_BLUE).bold(),
+                    )))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(palette::BORDER_COLOR))
+                    .padding(Padding::horizontal(1)),
+            )
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn guesses_common_languages_and_leaves_prose_alone() {
+        let rust = "use std::fmt;\n\npub fn main() -> Result<()> {\n    let mut x = 1;\n}\n";
+        assert_eq!(guess_language(rust), Some("rust"));
+        let python = "import os\n\ndef main():\n    print(os.getcwd())\n";
+        assert_eq!(guess_language(python), Some("python"));
+        assert_eq!(
+            guess_language("#!/usr/bin/env bash\nset -e\n"),
+            Some("bash")
+        );
+        assert_eq!(guess_language("{\"a\": [1, 2]}"), Some("json"));
+        assert_eq!(
+            guess_language("SELECT id\nFROM users\nWHERE id = 1;"),
+            Some("sql")
+        );
+        assert_eq!(
+            guess_language("diff --git a/x b/x\n--- a/x\n+++ b/x\n@@ -1 +1 @@\n"),
+            Some("diff")
+        );
+        assert_eq!(
+            guess_language("The build failed yesterday.\nI think it was the cache."),
+            None
+        );
+        assert_eq!(extension_for(Some("rust")), "rs");
+        assert_eq!(extension_for(None), "txt");
+    }
+
+    #[test]
+    fn fences_outlast_backtick_runs_in_the_paste() {
+        assert_eq!(fence_paste("a\n", Some("rust")), "```rust\na\n```");
+        assert_eq!(
+            fence_paste("text\n```\ncode\n```", None),
+            "````\ntext\n```\ncode\n```\n````"
+        );
+    }
+
+    #[test]
+    fn choices_emit_the_paste_and_cancel_drops_it() {
+        let mut view = PastePromptView::new("fn a() {}\nfn b() {}\n".to_string());
+        let action = view.handle_key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+        assert!(matches!(
+            action,
+            ViewAction::EmitAndClose(ViewEvent::PasteResolved {
+                choice: PasteChoice::Attach,
+                language: Some("rust"),
+                ref text,
+            }) if text == "fn a() {}\nfn b() {}\n"
+        ));
+
+        let mut view = PastePromptView::new("x".to_string());
+        assert!(matches!(
+            view.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+            ViewAction::Close
+        ));
+    }
+}
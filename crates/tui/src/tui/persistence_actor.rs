@@ -16,14 +16,17 @@
 //!   to this task. The UI merely `try_send`s a request (non-blocking,
 //!   bounded-channel drop) and returns immediately — keystrokes are never
 //!   gated on write completion.
-//! - **Latest-wins coalescing**: when multiple `Checkpoint` or
-//!   `SessionSnapshot` requests pile up before the actor's next write cycle,
-//!   only the most recent one is written. `ClearCheckpoint` requests
-//!   accumulate normally (they're cheap and commutative).
+//! - **Latest-wins coalescing per session**: when multiple `Checkpoint` or
+//!   `SessionSnapshot` requests for the same session pile up before the
+//!   actor's next write cycle, only the most recent one is written. Session
+//!   tabs share the actor, so requests for different sessions never replace
+//!   each other. `ClearCheckpoint` names its session, drops that session's
+//!   queued checkpoint, and only removes the file if that session wrote it.
 //! - **Unbounded channel** for `try_send` to always succeed; the actor
 //!   naturally backpressures via the spawn pool. A few outstanding
 //!   `SavedSession` values in the channel (< 1 MB) is negligible pressure.
 
+use std::collections::HashSet;
 use std::sync::OnceLock;
 
 use tokio::sync::mpsc;
@@ -42,8 +45,9 @@ pub enum PersistRequest {
     Checkpoint(SavedSession),
     /// Write a full session snapshot (completed turn, durable save).
     SessionSnapshot(SavedSession),
-    /// Remove the crash-recovery checkpoint file.
-    ClearCheckpoint,
+    /// Remove the crash-recovery checkpoint file if this session wrote it;
+    /// `None` removes it whichever session did.
+    ClearCheckpoint(Option<String>),
     /// Graceful shutdown — flush pending writes, then exit the actor loop.
     Shutdown,
 }
@@ -104,78 +108,25 @@ pub fn spawn_persistence_actor(manager: SessionManager) -> PersistActorHandle {
         "persistence-actor",
         std::panic::Location::caller(),
         async move {
-            let mut latest_checkpoint: Option<SavedSession> = None;
-            let mut latest_session: Option<SavedSession> = None;
-            let mut should_clear: bool = false;
-
+            let mut pending = PendingWrites::default();
             loop {
-                // Drain everything waiting, keeping only the latest of each kind.
+                // Drain everything waiting, keeping only the latest of each
+                // kind per session.
                 while let Ok(req) = rx.try_recv() {
-                    match req {
-                        PersistRequest::Checkpoint(session) => {
-                            latest_checkpoint = Some(session);
-                        }
-                        PersistRequest::SessionSnapshot(session) => {
-                            latest_session = Some(session);
-                        }
-                        PersistRequest::ClearCheckpoint => {
-                            should_clear = true;
-                        }
-                        PersistRequest::Shutdown => {
-                            flush_inner(
-                                &manager,
-                                latest_checkpoint.as_ref(),
-                                latest_session.as_ref(),
-                                should_clear,
-                            );
-                            return;
-                        }
-                    }
-                }
-
-                // Write coalesced work.
-                if should_clear {
-                    let _ = manager.clear_checkpoint();
-                    should_clear = false;
-                }
-                if let Some(ref session) = latest_checkpoint.take() {
-                    let _ = manager.save_checkpoint(session);
-                }
-                if let Some(ref session) = latest_session.take() {
-                    let _ = manager.save_session(session);
-                }
-
-                // Block until the next request arrives.
-                match rx.recv().await {
-                    Some(PersistRequest::Checkpoint(session)) => {
-                        latest_checkpoint = Some(session);
-                    }
-                    Some(PersistRequest::SessionSnapshot(session)) => {
-                        latest_session = Some(session);
-                    }
-                    Some(PersistRequest::ClearCheckpoint) => {
-                        should_clear = true;
-                    }
-                    Some(PersistRequest::Shutdown) => {
-                        flush_inner(
-                            &manager,
-                            latest_checkpoint.as_ref(),
-                            latest_session.as_ref(),
-                            should_clear,
-                        );
-                        return;
-                    }
-                    None => {
-                        // Channel closed — final flush and exit.
-                        flush_inner(
-                            &manager,
-                            latest_checkpoint.as_ref(),
-                            latest_session.as_ref(),
-                            should_clear,
-                        );
+                    if !pending.absorb(req) {
+                        pending.flush(&manager);
                         return;
                     }
                 }
+                pending.flush(&manager);
+
+                // Block until the next request arrives. A closed channel
+                // ends the actor like `Shutdown`.
+                let next = rx.recv().await;
+                if !next.is_some_and(|req| pending.absorb(req)) {
+                    pending.flush(&manager);
+                    return;
+                }
             }
         },
     );
@@ -183,20 +134,115 @@ pub fn spawn_persistence_actor(manager: SessionManager) -> PersistActorHandle {
     handle
 }
 
-/// Write any pending work to disk (used on shutdown).
-fn flush_inner(
-    manager: &SessionManager,
-    checkpoint: Option<&SavedSession>,
-    session: Option<&SavedSession>,
-    should_clear: bool,
-) {
-    if should_clear {
-        let _ = manager.clear_checkpoint();
+/// Work queued since the last write cycle.
+#[derive(Debug, Default)]
+struct PendingWrites {
+    /// Latest checkpoint per session, in arrival order.
+    checkpoints: Vec<SavedSession>,
+    /// Latest snapshot per session, in arrival order.
+    sessions: Vec<SavedSession>,
+    /// Sessions whose checkpoint should be removed (`None`: any session).
+    clears: HashSet<Option<String>>,
+}
+
+impl PendingWrites {
+    /// Queue `request`. Returns `false` for `Shutdown`.
+    fn absorb(&mut self, request: PersistRequest) -> bool {
+        match request {
+            PersistRequest::Checkpoint(session) => replace_latest(&mut self.checkpoints, session),
+            PersistRequest::SessionSnapshot(session) => replace_latest(&mut self.sessions, session),
+            PersistRequest::ClearCheckpoint(session_id) => {
+                self.checkpoints.retain(|checkpoint| {
+                    session_id
+                        .as_ref()
+                        .is_some_and(|id| *id != checkpoint.metadata.id)
+                });
+                self.clears.insert(session_id);
+            }
+            PersistRequest::Shutdown => return false,
+        }
+        true
     }
-    if let Some(s) = checkpoint {
-        let _ = manager.save_checkpoint(s);
+
+    /// Write the queued work to disk and reset.
+    fn flush(&mut self, manager: &SessionManager) {
+        for session_id in self.clears.drain() {
+            let _ = match session_id {
+                Some(id) => manager.clear_checkpoint_for(&id),
+                None => manager.clear_checkpoint(),
+            };
+        }
+        for checkpoint in self.checkpoints.drain(..) {
+            let _ = manager.save_checkpoint(&checkpoint);
+        }
+        for session in self.sessions.drain(..) {
+            let _ = manager.save_session(&session);
+        }
     }
-    if let Some(s) = session {
-        let _ = manager.save_session(s);
+}
+
+/// Replace the queued entry for `session`'s id, or append it.
+fn replace_latest(queue: &mut Vec<SavedSession>, session: SavedSession) {
+    queue.retain(|queued| queued.metadata.id != session.metadata.id);
+    queue.push(session);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session_manager::create_saved_session;
+
+    fn saved(id: &str, text: &str) -> SavedSession {
+        let message = crate::models::Message {
+            role: "user".to_string(),
+            content: vec![crate::models::ContentBlock::Text {
+                text: text.to_string(),
+                cache_control: None,
+            }],
+        };
+        let mut session =
+            create_saved_session(&[message], "test-model", std::path::Path::new("."), 1, None);
+        session.metadata.id = id.to_string();
+        session
+    }
+
+    #[test]
+    fn coalescing_keeps_the_latest_write_of_every_session() {
+        let tmp = tempfile::tempdir().unwrap();
+        let manager = SessionManager::new(tmp.path().join("sessions")).unwrap();
+        let mut pending = PendingWrites::default();
+        for request in [
+            PersistRequest::SessionSnapshot(saved("tab-a", "first")),
+            PersistRequest::SessionSnapshot(saved("tab-b", "other tab")),
+            PersistRequest::SessionSnapshot(saved("tab-a", "second")),
+        ] {
+            assert!(pending.absorb(request));
+        }
+        assert!(!pending.absorb(PersistRequest::Shutdown));
+        pending.flush(&manager);
+
+        let a = manager.load_session("tab-a").unwrap();
+        let b = manager.load_session("tab-b").unwrap();
+        assert!(format!("{:?}", a.messages).contains("second"));
+        assert!(format!("{:?}", b.messages).contains("other tab"));
+    }
+
+    #[test]
+    fn clearing_one_session_keeps_another_sessions_checkpoint() {
+        let tmp = tempfile::tempdir().unwrap();
+        let manager = SessionManager::new(tmp.path().join("sessions")).unwrap();
+        let mut pending = PendingWrites::default();
+        pending.absorb(PersistRequest::Checkpoint(saved("tab-b", "in flight")));
+        pending.flush(&manager);
+
+        pending.absorb(PersistRequest::Checkpoint(saved("tab-a", "done soon")));
+        pending.absorb(PersistRequest::ClearCheckpoint(Some("tab-a".to_string())));
+        pending.flush(&manager);
+        let checkpoint = manager.load_checkpoint().unwrap().unwrap();
+        assert_eq!(checkpoint.metadata.id, "tab-b");
+
+        pending.absorb(PersistRequest::ClearCheckpoint(None));
+        pending.flush(&manager);
+        assert!(manager.load_checkpoint().unwrap().is_none());
     }
 }
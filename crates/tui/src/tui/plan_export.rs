@@ -0,0 +1,244 @@
+//! `/plan export`: keep a reviewable plan document in the workspace.
+//!
+//! The document is rendered from the shared `update_plan` state (goal,
+//! numbered steps with checkboxes, open questions, decisions) and rewritten
+//! every time the model updates the plan, so it can be committed next to the
+//! code and reviewed in a PR. The first line marks the file as generated;
+//! an existing file without that marker is never overwritten.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::tools::plan::{PlanSnapshot, StepStatus};
+use crate::tui::app::App;
+
+/// Text every exported document starts with, used to recognise files this
+/// command owns.
+const GENERATED_MARKER: &str = "Generated by deepseek /plan export";
+
+/// Default file name when `/plan export` gets no path.
+pub const DEFAULT_FILE: &str = "PLAN.md";
+
+/// Output syntax for the plan document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanFormat {
+    Markdown,
+    Org,
+}
+
+impl PlanFormat {
+    /// `.org` files get Org syntax; everything else is Markdown.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("org") => Self::Org,
+            _ => Self::Markdown,
+        }
+    }
+
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Markdown => "markdown",
+            Self::Org => "org",
+        }
+    }
+}
+
+/// Where the live plan document is written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanExport {
+    pub path: PathBuf,
+    pub format: PlanFormat,
+}
+
+impl PlanExport {
+    /// Write `snapshot` to the export path, creating parent directories.
+    pub fn write(&self, snapshot: &PlanSnapshot) -> Result<(), String> {
+        if let Ok(existing) = std::fs::read_to_string(&self.path) {
+            let owned = existing
+                .lines()
+                .next()
+                .is_some_and(|line| line.contains(GENERATED_MARKER));
+            if !owned && !existing.trim().is_empty() {
+                return Err(format!(
+                    "{} exists and was not written by /plan export; pick another path",
+                    self.path.display()
+                ));
+            }
+        }
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| format!("create {}: {err}", parent.display()))?;
+        }
+        std::fs::write(&self.path, render(snapshot, self.format))
+            .map_err(|err| format!("write {}: {err}", self.path.display()))
+    }
+}
+
+/// Render the plan document.
+#[must_use]
+pub fn render(snapshot: &PlanSnapshot, format: PlanFormat) -> String {
+    let heading = |level: usize, text: &str| match format {
+        PlanFormat::Markdown => format!("{} {text}", "#".repeat(level)),
+        PlanFormat::Org => format!("{} {text}", "*".repeat(level)),
+    };
+    let none = match format {
+        PlanFormat::Markdown => "_None._",
+        PlanFormat::Org => "/None./",
+    };
+    let done = snapshot
+        .items
+        .iter()
+        .filter(|item| item.status == StepStatus::Completed)
+        .count();
+
+    let mut out = String::new();
+    let _ = match format {
+        PlanFormat::Markdown => {
+            writeln!(out, "<!-- {GENERATED_MARKER}; edits are overwritten. -->")
+        }
+        PlanFormat::Org => writeln!(out, "# {GENERATED_MARKER}; edits are overwritten."),
+    };
+    let _ = writeln!(out, "{}\n", heading(1, "Plan"));
+
+    let _ = writeln!(out, "{}\n", heading(2, "Goal"));
+    let _ = writeln!(
+        out,
+        "{}\n",
+        snapshot.explanation.as_deref().unwrap_or(none).trim()
+    );
+
+    let _ = writeln!(
+        out,
+        "{}\n",
+        heading(2, &format!("Steps ({done}/{} done)", snapshot.items.len()))
+    );
+    if snapshot.items.is_empty() {
+        let _ = writeln!(out, "{none}");
+    }
+    for (index, item) in snapshot.items.iter().enumerate() {
+        let number = index + 1;
+        let step = item.step.trim();
+        let _ = match (format, &item.status) {
+            (PlanFormat::Markdown, StepStatus::Completed) => writeln!(out, "{number}. [x] {step}"),
+            (PlanFormat::Markdown, StepStatus::InProgress) => {
+                writeln!(out, "{number}. [ ] {step} _(in progress)_")
+            }
+            (PlanFormat::Markdown, StepStatus::Pending) => writeln!(out, "{number}. [ ] {step}"),
+            (PlanFormat::Org, StepStatus::Completed) => writeln!(out, "{number}. [X] {step}"),
+            (PlanFormat::Org, StepStatus::InProgress) => writeln!(out, "{number}. [-] {step}"),
+            (PlanFormat::Org, StepStatus::Pending) => writeln!(out, "{number}. [ ] {step}"),
+        };
+    }
+
+    for (title, entries) in [
+        ("Open questions", &snapshot.open_questions),
+        ("Decisions", &snapshot.decisions),
+    ] {
+        let _ = writeln!(out, "\n{}\n", heading(2, title));
+        if entries.is_empty() {
+            let _ = writeln!(out, "{none}");
+        }
+        for entry in entries {
+            let _ = writeln!(out, "- {}", entry.trim());
+        }
+    }
+    out
+}
+
+/// Rewrite the exported document after a plan change. Returns an error
+/// message for the status line when the write fails.
+pub fn sync(app: &mut App) -> Option<String> {
+    let export = app.plan_export.as_ref()?;
+    let snapshot = app.plan_state.try_lock().ok()?.snapshot();
+    if snapshot_is_empty(&snapshot) {
+        return None;
+    }
+    export
+        .write(&snapshot)
+        .err()
+        .map(|err| format!("Plan export failed: {err}"))
+}
+
+#[must_use]
+pub fn snapshot_is_empty(snapshot: &PlanSnapshot) -> bool {
+    snapshot.explanation.is_none()
+        && snapshot.items.is_empty()
+        && snapshot.open_questions.is_empty()
+        && snapshot.decisions.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::plan::PlanItemArg;
+
+    fn snapshot() -> PlanSnapshot {
+        PlanSnapshot {
+            explanation: Some("Ship the exporter".to_string()),
+            items: vec![
+                PlanItemArg {
+                    step: "Render markdown".to_string(),
+                    status: StepStatus::Completed,
+                },
+                PlanItemArg {
+                    step: "Wire the command".to_string(),
+                    status: StepStatus::InProgress,
+                },
+                PlanItemArg {
+                    step: "Write docs".to_string(),
+                    status: StepStatus::Pending,
+                },
+            ],
+            open_questions: vec!["Org support?".to_string()],
+            decisions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn render_numbers_steps_with_checkboxes_in_both_formats() {
+        let markdown = render(&snapshot(), PlanFormat::Markdown);
+        assert!(markdown.starts_with("<!-- Generated by deepseek /plan export"));
+        assert!(
+            markdown.contains("## Goal\n\nShip the exporter"),
+            "{markdown}"
+        );
+        assert!(markdown.contains("## Steps (1/3 done)"), "{markdown}");
+        assert!(markdown.contains("1. [x] Render markdown"), "{markdown}");
+        assert!(
+            markdown.contains("2. [ ] Wire the command _(in progress)_"),
+            "{markdown}"
+        );
+        assert!(markdown.contains("## Open questions\n\n- Org support?"));
+        assert!(markdown.contains("## Decisions\n\n_None._"), "{markdown}");
+
+        let org = render(&snapshot(), PlanFormat::Org);
+        assert!(org.contains("** Steps (1/3 done)"), "{org}");
+        assert!(org.contains("2. [-] Wire the command"), "{org}");
+        assert_eq!(
+            PlanFormat::from_path(Path::new("docs/plan.org")),
+            PlanFormat::Org
+        );
+    }
+
+    #[test]
+    fn write_refuses_to_clobber_files_it_did_not_create() {
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        let path = dir.path().join("notes").join("PLAN.md");
+        let export = PlanExport {
+            path: path.clone(),
+            format: PlanFormat::Markdown,
+        };
+        export.write(&snapshot()).expect("first write");
+        export.write(&snapshot()).expect("rewrite owned file");
+
+        std::fs::write(&path, "# My own plan\n").expect("user file");
+        let err = export.write(&snapshot()).expect_err("foreign file");
+        assert!(err.contains("not written by /plan export"), "{err}");
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read"),
+            "# My own plan\n"
+        );
+    }
+}
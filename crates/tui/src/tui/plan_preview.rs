@@ -0,0 +1,389 @@
+//! `/preview` — ask the model what it would do in Agent mode, without
+//! running anything.
+//!
+//! The preview turn runs in Plan mode (read-only tools only) with an
+//! instruction that asks for a numbered tool plan tagged with a risk level
+//! per step. When the turn completes, the reply is parsed into a
+//! [`PlanPreview`] and shown in [`PlanPreviewView`]; one key switches to
+//! Agent mode and sends the plan back as pinned context for execution.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, Widget, Wrap};
+
+use crate::palette;
+use crate::tui::views::{ModalKind, ModalView, ViewAction, ViewEvent};
+
+/// Instruction prepended to the `/preview` request.
+pub const PREVIEW_INSTRUCTION: &str = "Plan preview: the user wants to see what you would do in Agent mode before anything runs. Do not edit files, run shell commands, or call any tool with side effects; read-only lookups are fine if they ground the plan. Reply with a numbered list of the tool calls you would make, one step per line, in this form:\n\n1. [risk: low] read_file — inspect src/lib.rs to find the parser entry point\n\nUse `low` for read-only or trivially reversible steps, `medium` for workspace edits, and `high` for shell commands with side effects, deletions, network access, or anything hard to undo. After the list, add one line starting with `Overall risk:`.";
+
+/// Estimated blast radius of one previewed step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepRisk {
+    Low,
+    Medium,
+    High,
+    Unknown,
+}
+
+impl StepRisk {
+    fn parse(label: &str) -> Self {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "low" | "none" | "safe" => Self::Low,
+            "medium" | "med" | "moderate" => Self::Medium,
+            "high" | "critical" | "dangerous" => Self::High,
+            _ => Self::Unknown,
+        }
+    }
+
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Unknown => "?",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::Low => palette::STATUS_SUCCESS,
+            Self::Medium => palette::STATUS_WARNING,
+            Self::High => palette::STATUS_ERROR,
+            Self::Unknown => palette::TEXT_MUTED,
+        }
+    }
+}
+
+/// One numbered step from the preview reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewStep {
+    pub number: usize,
+    pub risk: StepRisk,
+    pub text: String,
+}
+
+/// A parsed `/preview` reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanPreview {
+    /// The request the user previewed.
+    pub request: String,
+    pub steps: Vec<PreviewStep>,
+    /// The model's `Overall risk:` line, if it gave one.
+    pub overall: Option<String>,
+}
+
+impl PlanPreview {
+    /// Parse the assistant reply. Returns `None` when it has no numbered
+    /// steps (the model answered in prose or refused).
+    #[must_use]
+    pub fn parse(request: &str, reply: &str) -> Option<Self> {
+        let mut steps = Vec::new();
+        let mut overall = None;
+        for line in reply.lines() {
+            let line = line.trim();
+            if let Some(rest) =
+                strip_prefix_ignore_case(line.trim_start_matches('*'), "overall risk:")
+            {
+                overall = Some(rest.trim().trim_matches('*').trim().to_string());
+                continue;
+            }
+            if let Some((number, rest)) = split_step_number(line) {
+                let (risk, text) = extract_risk(rest);
+                steps.push(PreviewStep { number, risk, text });
+            }
+        }
+        if steps.is_empty() {
+            return None;
+        }
+        Some(Self {
+            request: request.to_string(),
+            steps,
+            overall,
+        })
+    }
+
+    /// Highest risk across all steps.
+    #[must_use]
+    pub fn max_risk(&self) -> StepRisk {
+        let rank = |risk: StepRisk| match risk {
+            StepRisk::Unknown => 0,
+            StepRisk::Low => 1,
+            StepRisk::Medium => 2,
+            StepRisk::High => 3,
+        };
+        self.steps
+            .iter()
+            .map(|step| step.risk)
+            .max_by_key(|risk| rank(*risk))
+            .unwrap_or(StepRisk::Unknown)
+    }
+
+    /// The plan rendered as numbered lines, for pinning into the execution
+    /// request.
+    #[must_use]
+    pub fn numbered(&self) -> String {
+        self.steps
+            .iter()
+            .map(|step| {
+                format!(
+                    "{}. [risk: {}] {}",
+                    step.number,
+                    step.risk.label(),
+                    step.text
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Instruction sent with the "execute this plan" follow-up so the
+    /// previewed steps stay in front of the model while it works.
+    #[must_use]
+    pub fn execution_instruction(&self) -> String {
+        format!(
+            "The user reviewed and approved this previewed plan for: {}\n\n{}\n\nCarry it out in order. If a step turns out to be wrong or riskier than estimated, stop and explain before deviating.",
+            self.request,
+            self.numbered()
+        )
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &text[prefix.len()..])
+}
+
+/// `"3. foo"` / `"3) foo"` → `(3, "foo")`.
+fn split_step_number(line: &str) -> Option<(usize, &str)> {
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    let number = line[..digits].parse().ok()?;
+    let rest = line[digits..].strip_prefix(['.', ')'])?;
+    let rest = rest.trim();
+    (!rest.is_empty()).then_some((number, rest))
+}
+
+/// Pull a `[risk: x]` / `(risk: x)` / `risk: x —` tag out of a step.
+fn extract_risk(text: &str) -> (StepRisk, String) {
+    let lower = text.to_ascii_lowercase();
+    let Some(start) = lower.find("risk:") else {
+        return (StepRisk::Unknown, text.to_string());
+    };
+    let after = &text[start + "risk:".len()..];
+    let label: String = after
+        .trim_start()
+        .chars()
+        .take_while(char::is_ascii_alphabetic)
+        .collect();
+    let risk = StepRisk::parse(&label);
+
+    // Strip the enclosing bracket when the tag is bracketed.
+    let open = text[..start].trim_end();
+    let (cut_start, closer) = match open.chars().last() {
+        Some('[') => (open.len() - 1, Some(']')),
+        Some('(') => (open.len() - 1, Some(')')),
+        _ => (start, None),
+    };
+    let label_end = start + "risk:".len() + (after.len() - after.trim_start().len()) + label.len();
+    let cut_end = match closer {
+        Some(closer) => text[label_end..]
+            .find(closer)
+            .map_or(label_end, |offset| label_end + offset + 1),
+        None => label_end,
+    };
+    let mut stripped = String::with_capacity(text.len());
+    stripped.push_str(text[..cut_start].trim_end());
+    if !stripped.is_empty() {
+        stripped.push(' ');
+    }
+    stripped.push_str(
+        text[cut_end..]
+            .trim_start()
+            .trim_start_matches(['—', '-', ':'])
+            .trim_start(),
+    );
+    (risk, stripped.trim().to_string())
+}
+
+/// Modal showing the parsed preview with a one-key "execute" action.
+#[derive(Debug, Clone)]
+pub struct PlanPreviewView {
+    preview: PlanPreview,
+    scroll: usize,
+}
+
+impl PlanPreviewView {
+    #[must_use]
+    pub fn new(preview: PlanPreview) -> Self {
+        Self { preview, scroll: 0 }
+    }
+}
+
+impl ModalView for PlanPreviewView {
+    fn kind(&self) -> ModalKind {
+        ModalKind::PlanPreview
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('x') | KeyCode::Char('X') => {
+                ViewAction::EmitAndClose(ViewEvent::PlanPreviewExecute)
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.scroll = self.scroll.saturating_sub(1);
+                ViewAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.scroll = (self.scroll + 1).min(self.preview.steps.len().saturating_sub(1));
+                ViewAction::None
+            }
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => ViewAction::Close,
+            _ => ViewAction::None,
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let mut lines: Vec<Line> = Vec::new();
+        lines.push(Line::from(vec![Span::styled(
+            "What Agent mode would do",
+            Style::default().fg(palette::DEEPSEEK_SKY).bold(),
+        )]));
+        lines.push(Line::from(Span::styled(
+            self.preview.request.clone(),
+            Style::default().fg(palette::TEXT_MUTED),
+        )));
+        lines.push(Line::from(""));
+
+        for step in self.preview.steps.iter().skip(self.scroll) {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:>2}. ", step.number),
+                    Style::default().fg(palette::TEXT_PRIMARY).bold(),
+                ),
+                Span::styled(
+                    format!("[{:<6}] ", step.risk.label()),
+                    Style::default().fg(step.risk.color()).bold(),
+                ),
+                Span::styled(
+                    step.text.clone(),
+                    Style::default().fg(palette::TEXT_PRIMARY),
+                ),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        let max = self.preview.max_risk();
+        let overall = self
+            .preview
+            .overall
+            .clone()
+            .unwrap_or_else(|| max.label().to_string());
+        lines.push(Line::from(vec![
+            Span::styled("Overall risk: ", Style::default().fg(palette::TEXT_MUTED)),
+            Span::styled(overall, Style::default().fg(max.color()).bold()),
+        ]));
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("Enter/x", Style::default().fg(palette::DEEPSEEK_SKY).bold()),
+            Span::styled(
+                " execute in Agent mode",
+                Style::default().fg(palette::TEXT_MUTED),
+            ),
+            Span::raw("  "),
+            Span::styled("Up/Down", Style::default().fg(palette::DEEPSEEK_SKY).bold()),
+            Span::styled(" scroll", Style::default().fg(palette::TEXT_MUTED)),
+            Span::raw("  "),
+            Span::styled("Esc", Style::default().fg(palette::DEEPSEEK_SKY).bold()),
+            Span::styled(" close", Style::default().fg(palette::TEXT_MUTED)),
+        ]));
+
+        let block = Block::default()
+            .title(Line::from(vec![Span::styled(
+                " Plan Preview ",
+                Style::default().fg(palette::DEEPSEEK_BLUE).bold(),
+            )]))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(palette::BORDER_COLOR))
+            .padding(Padding::uniform(1));
+        let paragraph = Paragraph::new(lines)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true })
+            .block(block);
+
+        let width = area.width.saturating_mul(4) / 5;
+        let height = area.height.saturating_mul(7) / 10;
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+        Clear.render(popup_area, buf);
+        paragraph.render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    const REPLY: &str = "Here is what I would do:\n\n1. [risk: low] read_file — inspect src/lib.rs\n2. (risk: medium) edit_file: rename the parser entry point\n3) exec_shell — run `cargo test` [risk: HIGH]\n4. ask the user to confirm\n\n**Overall risk:** medium";
+
+    #[test]
+    fn parses_numbered_steps_with_risk_tags() {
+        let preview = PlanPreview::parse("rename parser", REPLY).expect("steps");
+        assert_eq!(preview.steps.len(), 4);
+        assert_eq!(preview.steps[0].risk, StepRisk::Low);
+        assert_eq!(preview.steps[0].text, "read_file — inspect src/lib.rs");
+        assert_eq!(preview.steps[1].risk, StepRisk::Medium);
+        assert_eq!(
+            preview.steps[1].text,
+            "edit_file: rename the parser entry point"
+        );
+        assert_eq!(preview.steps[2].number, 3);
+        assert_eq!(preview.steps[2].risk, StepRisk::High);
+        assert_eq!(preview.steps[2].text, "exec_shell — run `cargo test`");
+        assert_eq!(preview.steps[3].risk, StepRisk::Unknown);
+        assert_eq!(preview.overall.as_deref(), Some("medium"));
+        assert_eq!(preview.max_risk(), StepRisk::High);
+    }
+
+    #[test]
+    fn prose_reply_is_not_a_preview() {
+        assert!(PlanPreview::parse("x", "I would need more context first.").is_none());
+    }
+
+    #[test]
+    fn execution_instruction_pins_the_plan() {
+        let preview = PlanPreview::parse("rename parser", REPLY).unwrap();
+        let instruction = preview.execution_instruction();
+        assert!(instruction.contains("rename parser"));
+        assert!(instruction.contains("1. [risk: low] read_file — inspect src/lib.rs"));
+        assert!(instruction.contains("3. [risk: high] exec_shell"));
+    }
+
+    #[test]
+    fn enter_executes_and_escape_closes() {
+        let preview = PlanPreview::parse("x", REPLY).unwrap();
+        let mut view = PlanPreviewView::new(preview);
+        assert!(matches!(
+            view.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            ViewAction::EmitAndClose(ViewEvent::PlanPreviewExecute)
+        ));
+        assert!(matches!(
+            view.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+            ViewAction::Close
+        ));
+    }
+}
@@ -0,0 +1,289 @@
+//! Pre-send prompt lint and `/enhance` rewrites.
+//!
+//! With the `prompt_lint` setting on, pressing Enter on a short draft that
+//! leans on vague phrasing ("fix it", "doesn't work") holds the draft back
+//! and shows a hint panel above the composer naming what would make it
+//! concrete: target files, acceptance criteria, constraints. Enter again
+//! sends the draft unchanged; Esc dismisses the panel.
+//!
+//! `/enhance [draft]` asks a fast model to rewrite the draft (or the one the
+//! lint held back) and puts the result back in the composer for review.
+
+use crate::models::{ContentBlock, Message, MessageRequest, MessageResponse, SystemPrompt};
+
+/// Model used for `/enhance` on DeepSeek providers; other providers reuse
+/// the session model.
+pub const ENHANCE_MODEL: &str = "deepseek-v4-flash";
+
+/// Drafts longer than this are assumed to carry their own context.
+const MAX_VAGUE_WORDS: usize = 16;
+
+const VAGUE_PHRASES: &[&str] = &[
+    "fix it",
+    "fix this",
+    "fix that",
+    "fix the bug",
+    "make it work",
+    "doesn't work",
+    "does not work",
+    "not working",
+    "it's broken",
+    "is broken",
+    "it's wrong",
+    "clean it up",
+    "clean this up",
+    "improve it",
+    "improve this",
+    "do it",
+    "do the thing",
+    "same as before",
+    "like before",
+    "try again",
+    "you know",
+];
+
+const CRITERIA_WORDS: &[&str] = &[
+    "should", "expect", "expected", "so that", "until", "pass", "passes", "return", "returns",
+    "instead", "output",
+];
+
+const CONSTRAINT_WORDS: &[&str] = &[
+    "don't", "do not", "without", "only", "keep", "avoid", "never", "preserve", "must",
+];
+
+const ENHANCE_SYSTEM: &str = "You rewrite a developer's request to a coding agent so it is concrete. \
+Keep the intent, scope, and voice; do not invent requirements, file names, or facts that the draft \
+does not imply. Where the draft is vague, make the ask explicit and add short placeholders in angle \
+brackets (for example <file>, <expected behaviour>) the developer can fill in. Reply with the \
+rewritten request only, no preamble and no code fence.";
+
+/// A concrete addition the lint suggests for a vague draft.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintHint {
+    TargetFiles,
+    AcceptanceCriteria,
+    Constraints,
+}
+
+impl LintHint {
+    #[must_use]
+    pub fn suggestion(self) -> &'static str {
+        match self {
+            LintHint::TargetFiles => "Name the file, function, or @path to work on",
+            LintHint::AcceptanceCriteria => {
+                "Say what done looks like (expected behaviour, a test that should pass)"
+            }
+            LintHint::Constraints => "Add constraints (what must not change, APIs to keep)",
+        }
+    }
+}
+
+/// Hint panel state for the draft the lint held back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptHint {
+    pub draft: String,
+    pub hints: Vec<LintHint>,
+}
+
+/// Lint `draft`, returning a hint panel when it reads as vague and is
+/// missing at least one concrete addition.
+#[must_use]
+pub fn lint(draft: &str) -> Option<PromptHint> {
+    let draft = draft.trim();
+    if draft.is_empty() || draft.split_whitespace().count() > MAX_VAGUE_WORDS {
+        return None;
+    }
+    let normalized = normalize(draft);
+    if !VAGUE_PHRASES
+        .iter()
+        .any(|phrase| normalized.contains(&format!(" {phrase} ")))
+    {
+        return None;
+    }
+
+    let mut hints = Vec::new();
+    if !draft.split_whitespace().any(looks_like_target) {
+        hints.push(LintHint::TargetFiles);
+    }
+    if !mentions_any(&normalized, CRITERIA_WORDS) {
+        hints.push(LintHint::AcceptanceCriteria);
+    }
+    if !mentions_any(&normalized, CONSTRAINT_WORDS) {
+        hints.push(LintHint::Constraints);
+    }
+    (!hints.is_empty()).then(|| PromptHint {
+        draft: draft.to_string(),
+        hints,
+    })
+}
+
+/// Lowercase, fold curly apostrophes, and pad words with single spaces so
+/// phrases can be matched on word boundaries.
+fn normalize(text: &str) -> String {
+    let folded: String = text
+        .to_lowercase()
+        .chars()
+        .map(|ch| match ch {
+            '\u{2019}' => '\'',
+            ch if ch.is_alphanumeric() || ch == '\'' => ch,
+            _ => ' ',
+        })
+        .collect();
+    let words: Vec<&str> = folded.split_whitespace().collect();
+    format!(" {} ", words.join(" "))
+}
+
+fn mentions_any(normalized: &str, words: &[&str]) -> bool {
+    words
+        .iter()
+        .any(|word| normalized.contains(&format!(" {word} ")))
+}
+
+/// Paths, `@mentions`, backticked code, `a::b` paths, `snake_case` or
+/// `camelCase` identifiers, and calls like `foo()`.
+fn looks_like_target(token: &str) -> bool {
+    let token = token.trim_end_matches(['.', ',', ';', ':', '!', '?', ')']);
+    if token.starts_with('@') || token.contains('`') || token.contains("::") {
+        return true;
+    }
+    if token.contains('/') || token.contains('\\') || token.ends_with("()") {
+        return true;
+    }
+    if let Some((stem, ext)) = token.rsplit_once('.')
+        && !stem.is_empty()
+        && !ext.is_empty()
+        && ext.chars().all(|ch| ch.is_ascii_alphanumeric())
+    {
+        return true;
+    }
+    let chars: Vec<char> = token.chars().collect();
+    chars.windows(2).any(|pair| {
+        (pair[0].is_ascii_alphanumeric() && pair[1] == '_')
+            || (pair[0].is_ascii_lowercase() && pair[1].is_ascii_uppercase())
+    })
+}
+
+/// Build the single-turn request `/enhance` sends.
+#[must_use]
+pub fn enhance_request(draft: &str, model: &str) -> MessageRequest {
+    MessageRequest {
+        model: model.to_string(),
+        messages: vec![Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::Text {
+                text: format!("<draft>\n{}\n</draft>", draft.trim()),
+                cache_control: None,
+            }],
+        }],
+        max_tokens: 1024,
+        system: Some(SystemPrompt::Text(ENHANCE_SYSTEM.to_string())),
+        tools: None,
+        tool_choice: None,
+        metadata: None,
+        thinking: None,
+        reasoning_effort: Some("off".to_string()),
+        stream: None,
+        temperature: None,
+        top_p: None,
+    }
+}
+
+/// Extract the rewritten draft from an `/enhance` response.
+#[must_use]
+pub fn enhanced_draft(response: &MessageResponse) -> Option<String> {
+    let text: String = response
+        .content
+        .iter()
+        .filter_map(|block| match block {
+            ContentBlock::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    let text = strip_code_fence(text.trim());
+    let text = text
+        .strip_prefix("<draft>")
+        .and_then(|rest| rest.strip_suffix("</draft>"))
+        .unwrap_or(text)
+        .trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn strip_code_fence(text: &str) -> &str {
+    let Some(body) = text.strip_prefix("```") else {
+        return text;
+    };
+    let Some((_, body)) = body.split_once('\n') else {
+        return text;
+    };
+    body.trim_end().strip_suffix("```").unwrap_or(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vague_draft_gets_all_three_hints() {
+        let hint = lint("fix it").expect("vague draft is flagged");
+        assert_eq!(hint.draft, "fix it");
+        assert_eq!(
+            hint.hints,
+            vec![
+                LintHint::TargetFiles,
+                LintHint::AcceptanceCriteria,
+                LintHint::Constraints
+            ]
+        );
+        assert!(lint("It doesn\u{2019}t work, fix this!").is_some());
+    }
+
+    #[test]
+    fn concrete_parts_drop_their_hints() {
+        let hint = lint("fix it in src/main.rs so the parser test passes").unwrap();
+        assert_eq!(hint.hints, vec![LintHint::Constraints]);
+        assert!(
+            lint("fix it in `parse_args` so it returns early, don't touch the CLI flags").is_none()
+        );
+    }
+
+    #[test]
+    fn specific_or_long_drafts_are_not_flagged() {
+        assert!(lint("").is_none());
+        assert!(lint("hello").is_none());
+        assert!(lint("add a --json flag to the status command").is_none());
+        assert!(
+            lint(
+                "fix it: the login form submits twice when you press enter quickly, \
+                 and then the session token gets overwritten by the second response"
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn enhanced_draft_strips_fences_and_tags() {
+        let response = |text: &str| MessageResponse {
+            id: String::new(),
+            r#type: "message".to_string(),
+            role: "assistant".to_string(),
+            content: vec![ContentBlock::Text {
+                text: text.to_string(),
+                cache_control: None,
+            }],
+            model: ENHANCE_MODEL.to_string(),
+            stop_reason: None,
+            stop_sequence: None,
+            container: None,
+            usage: crate::models::Usage::default(),
+        };
+        assert_eq!(
+            enhanced_draft(&response("```\nFix <file> so it builds.\n```")).as_deref(),
+            Some("Fix <file> so it builds.")
+        );
+        assert_eq!(
+            enhanced_draft(&response("<draft>\nFix <file>.\n</draft>")).as_deref(),
+            Some("Fix <file>.")
+        );
+        assert_eq!(enhanced_draft(&response("  ")), None);
+    }
+}
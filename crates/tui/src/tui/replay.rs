@@ -0,0 +1,614 @@
+//! Read-only playback of a saved session (`deepseek sessions replay <id>`).
+//!
+//! Each persisted message is one replay step. Steps are revealed on the
+//! session's own clock — the gap between two messages' timestamps, with
+//! long idle stretches compressed — or one at a time with Space. Tool calls
+//! show as running until the step carrying their result is revealed, so the
+//! tool timing of the original run is visible. `/` jumps to the next step
+//! that edited a file matching the typed path. Nothing is sent to the model
+//! and the session file is never written.
+
+use std::collections::HashMap;
+use std::io::{self, Stdout};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use ratatui::{
+    Frame, Terminal,
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use serde_json::Value;
+
+use crate::models::ContentBlock;
+use crate::palette;
+use crate::session_manager::SavedSession;
+use crate::session_summary::written_paths;
+use crate::tui::color_compat::ColorCompatBackend;
+use crate::tui::history::{
+    GenericToolCell, HistoryCell, ToolCell, ToolStatus, history_cells_from_message,
+    summarize_tool_args,
+};
+
+/// Shortest pause between steps at 1x, so bursts stay readable.
+const MIN_STEP_DELAY: Duration = Duration::from_millis(150);
+/// Longest pause between steps at 1x; idle gaps (overnight waits, slow
+/// approvals) are compressed to this.
+const MAX_STEP_DELAY: Duration = Duration::from_secs(4);
+/// Pause used when a session has no recorded timestamps.
+const UNTIMED_STEP_DELAY: Duration = Duration::from_millis(800);
+const SPEEDS: &[f64] = &[0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+const DEFAULT_SPEED_INDEX: usize = 1;
+
+/// A tool result delivered by a step, applied to the matching tool cell.
+#[derive(Debug, Clone)]
+struct ToolOutcome {
+    tool_use_id: String,
+    output: String,
+    is_error: bool,
+}
+
+#[derive(Debug, Clone)]
+struct ReplayStep {
+    time: Option<DateTime<Utc>>,
+    cells: Vec<HistoryCell>,
+    /// `(tool_use_id, index into cells)` for tool calls made in this step.
+    tool_calls: Vec<(String, usize)>,
+    outcomes: Vec<ToolOutcome>,
+    edited_paths: Vec<String>,
+}
+
+impl ReplayStep {
+    fn from_message(session: &SavedSession, index: usize) -> Self {
+        let message = &session.messages[index];
+        let mut cells = history_cells_from_message(message);
+        let mut tool_calls = Vec::new();
+        let mut outcomes = Vec::new();
+        let mut edited_paths = Vec::new();
+        for block in &message.content {
+            match block {
+                ContentBlock::ToolUse {
+                    id, name, input, ..
+                } => {
+                    edited_paths.extend(written_paths(name, input));
+                    tool_calls.push((id.clone(), cells.len()));
+                    cells.push(running_tool_cell(name, input));
+                }
+                ContentBlock::ToolResult {
+                    tool_use_id,
+                    content,
+                    is_error,
+                    ..
+                } => outcomes.push(ToolOutcome {
+                    tool_use_id: tool_use_id.clone(),
+                    output: content.clone(),
+                    is_error: is_error.unwrap_or(false),
+                }),
+                _ => {}
+            }
+        }
+        Self {
+            time: session.message_time(index),
+            cells,
+            tool_calls,
+            outcomes,
+            edited_paths,
+        }
+    }
+}
+
+fn running_tool_cell(name: &str, input: &Value) -> HistoryCell {
+    HistoryCell::Tool(ToolCell::Generic(GenericToolCell {
+        name: name.to_string(),
+        status: ToolStatus::Running,
+        input_summary: summarize_tool_args(input),
+        output: None,
+        prompts: None,
+        spillover_path: None,
+        output_summary: None,
+        is_diff: false,
+    }))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReplayAction {
+    Continue,
+    Quit,
+}
+
+/// Playback state for one session.
+#[derive(Debug)]
+pub struct Replay {
+    title: String,
+    steps: Vec<ReplayStep>,
+    /// Number of steps revealed so far.
+    shown: usize,
+    playing: bool,
+    speed_index: usize,
+    next_at: Option<Instant>,
+    /// Lines scrolled up from the bottom; 0 follows the newest step.
+    scroll_from_bottom: usize,
+    /// Path query being typed after `/`.
+    query_input: Option<String>,
+    last_query: Option<String>,
+    notice: Option<String>,
+}
+
+impl Replay {
+    #[must_use]
+    pub fn new(session: &SavedSession) -> Self {
+        let steps = (0..session.messages.len())
+            .map(|index| ReplayStep::from_message(session, index))
+            .collect();
+        Self {
+            title: session.metadata.title.clone(),
+            steps,
+            shown: 0,
+            playing: true,
+            speed_index: DEFAULT_SPEED_INDEX,
+            next_at: None,
+            scroll_from_bottom: 0,
+            query_input: None,
+            last_query: None,
+            notice: None,
+        }
+    }
+
+    fn speed(&self) -> f64 {
+        SPEEDS[self.speed_index]
+    }
+
+    fn is_finished(&self) -> bool {
+        self.shown >= self.steps.len()
+    }
+
+    /// Pause before revealing step `index`, at the current speed.
+    fn delay_before(&self, index: usize) -> Duration {
+        if index == 0 {
+            return Duration::ZERO;
+        }
+        let times = (
+            self.steps.get(index - 1).and_then(|step| step.time),
+            self.steps.get(index).and_then(|step| step.time),
+        );
+        let gap = match times {
+            (Some(prev), Some(next)) => (next - prev)
+                .to_std()
+                .unwrap_or_default()
+                .clamp(MIN_STEP_DELAY, MAX_STEP_DELAY),
+            _ => UNTIMED_STEP_DELAY,
+        };
+        gap.div_f64(self.speed())
+    }
+
+    fn schedule_next(&mut self, now: Instant) {
+        self.next_at =
+            (self.playing && !self.is_finished()).then(|| now + self.delay_before(self.shown));
+    }
+
+    /// Reveal one more step. Returns false at the end of the session.
+    fn advance(&mut self) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        self.shown += 1;
+        self.scroll_from_bottom = 0;
+        true
+    }
+
+    /// Reveal the next step when it is due. Returns true when the view
+    /// changed.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if !self.playing {
+            return false;
+        }
+        let Some(due) = self.next_at else {
+            self.schedule_next(now);
+            return false;
+        };
+        if now < due {
+            return false;
+        }
+        let changed = self.advance();
+        if self.is_finished() {
+            self.playing = false;
+        }
+        self.schedule_next(now);
+        changed
+    }
+
+    fn set_playing(&mut self, playing: bool) {
+        self.playing = playing && !self.is_finished();
+        self.next_at = None;
+    }
+
+    /// Reveal through the first step after the current position that edited
+    /// a path containing `query`, wrapping to the start. Pauses playback.
+    fn jump_to_edit(&mut self, query: &str) -> bool {
+        let query = query.trim();
+        if query.is_empty() {
+            return false;
+        }
+        let count = self.steps.len();
+        let found = (0..count)
+            .map(|offset| (self.shown + offset) % count)
+            .find(|&index| {
+                self.steps[index]
+                    .edited_paths
+                    .iter()
+                    .any(|path| path.contains(query))
+            });
+        self.set_playing(false);
+        self.last_query = Some(query.to_string());
+        match found {
+            Some(index) => {
+                self.shown = index + 1;
+                self.scroll_from_bottom = 0;
+                self.notice = Some(format!("Step {}: edited {query}", index + 1));
+                true
+            }
+            None => {
+                self.notice = Some(format!("No edit of a path matching '{query}'"));
+                false
+            }
+        }
+    }
+
+    /// Cells of the revealed steps, with tool results applied to the tool
+    /// calls they answer.
+    fn visible_cells(&self) -> Vec<HistoryCell> {
+        let mut cells: Vec<HistoryCell> = Vec::new();
+        let mut tool_cells: HashMap<&str, usize> = HashMap::new();
+        for step in &self.steps[..self.shown] {
+            let base = cells.len();
+            cells.extend(step.cells.iter().cloned());
+            for (id, offset) in &step.tool_calls {
+                tool_cells.insert(id.as_str(), base + offset);
+            }
+            for outcome in &step.outcomes {
+                let Some(&index) = tool_cells.get(outcome.tool_use_id.as_str()) else {
+                    continue;
+                };
+                if let HistoryCell::Tool(ToolCell::Generic(cell)) = &mut cells[index] {
+                    cell.status = if outcome.is_error {
+                        ToolStatus::Failed
+                    } else {
+                        ToolStatus::Success
+                    };
+                    cell.output = Some(outcome.output.clone());
+                }
+            }
+        }
+        cells
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, now: Instant) -> ReplayAction {
+        if let Some(input) = self.query_input.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.query_input = None,
+                KeyCode::Enter => {
+                    let query = self.query_input.take().unwrap_or_default();
+                    self.jump_to_edit(&query);
+                }
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(ch) => input.push(ch),
+                _ => {}
+            }
+            return ReplayAction::Continue;
+        }
+
+        self.notice = None;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return ReplayAction::Quit,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return ReplayAction::Quit;
+            }
+            KeyCode::Char(' ') | KeyCode::Right => {
+                self.set_playing(false);
+                self.advance();
+            }
+            KeyCode::Left | KeyCode::Char('b') => {
+                self.set_playing(false);
+                self.shown = self.shown.saturating_sub(1);
+                self.scroll_from_bottom = 0;
+            }
+            KeyCode::Char('p') | KeyCode::Enter => {
+                if self.is_finished() {
+                    self.shown = 0;
+                }
+                self.set_playing(!self.playing);
+                self.schedule_next(now);
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.speed_index = (self.speed_index + 1).min(SPEEDS.len() - 1);
+                self.schedule_next(now);
+            }
+            KeyCode::Char('-') => {
+                self.speed_index = self.speed_index.saturating_sub(1);
+                self.schedule_next(now);
+            }
+            KeyCode::Home | KeyCode::Char('g') => {
+                self.set_playing(false);
+                self.shown = 0;
+            }
+            KeyCode::End | KeyCode::Char('G') => {
+                self.set_playing(false);
+                self.shown = self.steps.len();
+                self.scroll_from_bottom = 0;
+            }
+            KeyCode::Char('/') => self.query_input = Some(String::new()),
+            KeyCode::Char('n') => {
+                if let Some(query) = self.last_query.clone() {
+                    self.jump_to_edit(&query);
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.scroll_from_bottom = self.scroll_from_bottom.saturating_add(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.scroll_from_bottom = self.scroll_from_bottom.saturating_sub(1);
+            }
+            KeyCode::PageUp => {
+                self.scroll_from_bottom = self.scroll_from_bottom.saturating_add(10);
+            }
+            KeyCode::PageDown => {
+                self.scroll_from_bottom = self.scroll_from_bottom.saturating_sub(10);
+            }
+            _ => {}
+        }
+        ReplayAction::Continue
+    }
+
+    fn render(&self, f: &mut Frame) {
+        let [header, body, footer] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .areas(f.area());
+
+        let state = if self.playing {
+            format!("\u{25b6} {}x", self.speed())
+        } else if self.is_finished() {
+            "end".to_string()
+        } else {
+            "paused".to_string()
+        };
+        let time = self
+            .shown
+            .checked_sub(1)
+            .and_then(|index| self.steps[index].time)
+            .map(|at| {
+                at.with_timezone(&Local)
+                    .format(" \u{00b7} %Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        f.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(
+                    " Replay ",
+                    Style::default()
+                        .fg(palette::DEEPSEEK_BLUE)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(
+                    "{} \u{00b7} step {}/{}{time} \u{00b7} ",
+                    self.title,
+                    self.shown,
+                    self.steps.len()
+                )),
+                Span::styled(state, Style::default().fg(palette::DEEPSEEK_SKY)),
+            ])),
+            header,
+        );
+
+        let lines: Vec<Line<'static>> = self
+            .visible_cells()
+            .iter()
+            .flat_map(|cell| cell.lines(body.width))
+            .collect();
+        let rows = usize::from(body.height);
+        let max_scroll = lines.len().saturating_sub(rows);
+        let top = max_scroll.saturating_sub(self.scroll_from_bottom.min(max_scroll));
+        f.render_widget(
+            Paragraph::new(lines).scroll((u16::try_from(top).unwrap_or(u16::MAX), 0)),
+            body,
+        );
+
+        let footer_line = if let Some(input) = &self.query_input {
+            Line::from(vec![
+                Span::styled(
+                    " Jump to edit of: ",
+                    Style::default().fg(palette::DEEPSEEK_SKY),
+                ),
+                Span::raw(format!("{input}\u{2588}")),
+            ])
+        } else if let Some(notice) = &self.notice {
+            Line::from(Span::styled(
+                format!(" {notice}"),
+                Style::default().fg(palette::STATUS_WARNING),
+            ))
+        } else {
+            Line::from(Span::styled(
+                " Space step \u{00b7} \u{2190} back \u{00b7} p play/pause \u{00b7} +/- speed \u{00b7} / jump to file edit \u{00b7} n next edit \u{00b7} q quit",
+                Style::default().fg(palette::TEXT_MUTED),
+            ))
+        };
+        f.render_widget(Paragraph::new(footer_line), footer);
+    }
+}
+
+/// Play `session` back in the alternate screen until the user quits.
+/// `jump_to` starts paused at the first edit of a matching path.
+pub fn run_replay(session: &SavedSession, jump_to: Option<&str>) -> Result<()> {
+    let mut replay = Replay::new(session);
+    if let Some(query) = jump_to {
+        replay.jump_to_edit(query);
+    }
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    if let Err(err) = execute!(stdout, EnterAlternateScreen) {
+        let _ = disable_raw_mode();
+        return Err(err.into());
+    }
+    let backend = ColorCompatBackend::new(
+        stdout,
+        palette::ColorDepth::detect(),
+        palette::PaletteMode::detect(),
+    );
+    let result = Terminal::new(backend)
+        .map_err(anyhow::Error::from)
+        .and_then(|mut terminal| {
+            let result = replay_loop(&mut terminal, &mut replay);
+            let _ = terminal.show_cursor();
+            result
+        });
+
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen);
+    result
+}
+
+fn replay_loop(
+    terminal: &mut Terminal<ColorCompatBackend<Stdout>>,
+    replay: &mut Replay,
+) -> Result<()> {
+    let mut dirty = true;
+    loop {
+        if dirty {
+            terminal.draw(|f| replay.render(f))?;
+            dirty = false;
+        }
+        if event::poll(Duration::from_millis(50))? {
+            match event::read()? {
+                Event::Key(key) if key.kind != KeyEventKind::Release => {
+                    if replay.handle_key(key, Instant::now()) == ReplayAction::Quit {
+                        return Ok(());
+                    }
+                    dirty = true;
+                }
+                Event::Resize(..) => dirty = true,
+                _ => {}
+            }
+        }
+        dirty |= replay.tick(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Message;
+    use crate::session_manager::create_saved_session;
+    use serde_json::json;
+
+    fn message(role: &str, content: Vec<ContentBlock>) -> Message {
+        Message {
+            role: role.to_string(),
+            content,
+        }
+    }
+
+    fn text(text: &str) -> ContentBlock {
+        ContentBlock::Text {
+            text: text.to_string(),
+            cache_control: None,
+        }
+    }
+
+    fn sample_session() -> SavedSession {
+        let messages = vec![
+            message("user", vec![text("fix the bug")]),
+            message(
+                "assistant",
+                vec![ContentBlock::ToolUse {
+                    id: "call-1".to_string(),
+                    name: "edit_file".to_string(),
+                    input: json!({"path": "src/lib.rs", "search": "a", "replace": "b"}),
+                    caller: None,
+                }],
+            ),
+            message(
+                "user",
+                vec![ContentBlock::ToolResult {
+                    tool_use_id: "call-1".to_string(),
+                    content: "edited".to_string(),
+                    is_error: None,
+                    content_blocks: None,
+                }],
+            ),
+            message("assistant", vec![text("done")]),
+        ];
+        let mut session =
+            create_saved_session(&messages, "deepseek-v4-pro", "/tmp".as_ref(), 0, None);
+        let start = Utc::now();
+        session.message_timestamps = [0, 1, 3_600, 3_601]
+            .into_iter()
+            .map(|secs| start + chrono::Duration::seconds(secs))
+            .collect();
+        session
+    }
+
+    #[test]
+    fn steps_follow_the_session_clock_and_compress_idle_gaps() {
+        let mut replay = Replay::new(&sample_session());
+        assert_eq!(replay.delay_before(0), Duration::ZERO);
+        assert_eq!(replay.delay_before(1), Duration::from_secs(1));
+        assert_eq!(replay.delay_before(2), MAX_STEP_DELAY);
+        replay.speed_index = SPEEDS.iter().position(|&s| s == 2.0).unwrap();
+        assert_eq!(replay.delay_before(1), Duration::from_millis(500));
+
+        let start = Instant::now();
+        assert!(!replay.tick(start));
+        assert!(replay.tick(start));
+        assert_eq!(replay.shown, 1);
+        assert!(!replay.tick(start + Duration::from_millis(100)));
+        assert!(replay.tick(start + Duration::from_millis(500)));
+        assert_eq!(replay.shown, 2);
+
+        let mut untimed = sample_session();
+        untimed.message_timestamps.clear();
+        assert_eq!(Replay::new(&untimed).delay_before(1), UNTIMED_STEP_DELAY);
+    }
+
+    #[test]
+    fn tool_calls_run_until_their_result_step_and_edits_are_searchable() {
+        let mut replay = Replay::new(&sample_session());
+        let now = Instant::now();
+        replay.handle_key(KeyEvent::from(KeyCode::Char(' ')), now);
+        replay.handle_key(KeyEvent::from(KeyCode::Char(' ')), now);
+        assert!(!replay.playing);
+        let tool_status = |replay: &Replay| {
+            replay
+                .visible_cells()
+                .into_iter()
+                .find_map(|cell| match cell {
+                    HistoryCell::Tool(ToolCell::Generic(cell)) => Some(cell.status),
+                    _ => None,
+                })
+        };
+        assert_eq!(tool_status(&replay), Some(ToolStatus::Running));
+        replay.handle_key(KeyEvent::from(KeyCode::Char(' ')), now);
+        assert_eq!(tool_status(&replay), Some(ToolStatus::Success));
+
+        replay.handle_key(KeyEvent::from(KeyCode::Home), now);
+        assert!(replay.jump_to_edit("lib.rs"));
+        assert_eq!(replay.shown, 2);
+        assert!(!replay.jump_to_edit("main.rs"));
+        assert_eq!(replay.shown, 2);
+    }
+}
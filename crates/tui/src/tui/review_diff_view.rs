@@ -0,0 +1,962 @@
+//! `/review show` modal: review findings attached inline to the diff.
+//!
+//! Findings come from the latest structured `review` tool result, or, for
+//! prose reviews (the `/review <target>` skill), from `path:line` mentions in
+//! the last assistant reply. Each finding is placed under the diff line it
+//! points at; findings outside the diff are listed after it.
+//!
+//! Marking a finding addressed and asking the agent to fix the selected ones
+//! are emitted as [`ReviewDiffAction`]s; the host keeps the addressed set on
+//! `App` so it survives reopening the view.
+
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Widget},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::palette;
+use crate::tools::ReviewOutput;
+use crate::tui::views::{ModalKind, ModalView, ReviewDiffAction, ViewAction, ViewEvent};
+use crate::utils::truncate_with_ellipsis;
+
+/// One review finding with an optional location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewFinding {
+    /// `error`, `warning`, or `info`.
+    pub severity: String,
+    pub title: String,
+    pub detail: String,
+    pub path: Option<String>,
+    pub line: Option<u32>,
+}
+
+impl ReviewFinding {
+    /// Identity used to remember addressed findings across reopenings.
+    #[must_use]
+    pub fn key(&self) -> String {
+        format!("{}\u{1f}{}", self.location(), self.title)
+    }
+
+    fn location(&self) -> String {
+        match (self.path.as_deref(), self.line) {
+            (Some(path), Some(line)) => format!("{path}:{line}"),
+            (Some(path), None) => path.to_string(),
+            (None, _) => String::new(),
+        }
+    }
+}
+
+/// Findings of a structured review: issues first, then suggestions.
+#[must_use]
+pub fn findings_from_review(output: &ReviewOutput) -> Vec<ReviewFinding> {
+    let issues = output.issues.iter().map(|issue| ReviewFinding {
+        severity: issue.severity.clone(),
+        title: issue.title.clone(),
+        detail: issue.description.clone(),
+        path: issue.path.clone(),
+        line: issue.line,
+    });
+    let suggestions = output.suggestions.iter().map(|suggestion| ReviewFinding {
+        severity: "info".to_string(),
+        title: suggestion.suggestion.clone(),
+        detail: String::new(),
+        path: suggestion.path.clone(),
+        line: suggestion.line,
+    });
+    issues.chain(suggestions).collect()
+}
+
+/// Findings of a prose review: every line that mentions a `path:line`
+/// location becomes a finding titled with that line.
+#[must_use]
+pub fn findings_from_text(text: &str) -> Vec<ReviewFinding> {
+    let mut findings = Vec::new();
+    let mut seen = HashSet::new();
+    for raw in text.lines() {
+        let Some((path, line)) = raw.split_whitespace().find_map(parse_location) else {
+            continue;
+        };
+        let title = raw
+            .trim()
+            .trim_start_matches(['-', '*', '+', '#', '>', ' '])
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .trim_start_matches(['.', ')', ' '])
+            .replace("**", "")
+            .trim()
+            .to_string();
+        let lower = title.to_ascii_lowercase();
+        let severity = if ["error", "critical", "bug"]
+            .iter()
+            .any(|w| lower.contains(w))
+        {
+            "error"
+        } else if lower.contains("warn") {
+            "warning"
+        } else {
+            "info"
+        };
+        let finding = ReviewFinding {
+            severity: severity.to_string(),
+            title,
+            detail: String::new(),
+            path: Some(path),
+            line: Some(line),
+        };
+        if seen.insert(finding.key()) {
+            findings.push(finding);
+        }
+    }
+    findings
+}
+
+/// `src/lib.rs:42`, optionally wrapped in backticks/brackets and followed by
+/// a column or range (`:42:7`, `:42-50`).
+fn parse_location(token: &str) -> Option<(String, u32)> {
+    let token = token.trim_matches(|c: char| {
+        matches!(
+            c,
+            '`' | '(' | ')' | '[' | ']' | '"' | '\'' | ',' | ';' | '*'
+        )
+    });
+    if token.contains("://") {
+        return None;
+    }
+    let (path, rest) = token.split_once(':')?;
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    let line = digits.parse::<u32>().ok().filter(|line| *line > 0)?;
+    // Require a file extension with a letter so `1.2:3` is not a path.
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let (_, extension) = file_name.rsplit_once('.')?;
+    if !extension.chars().any(|c| c.is_ascii_alphabetic())
+        || !extension.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        return None;
+    }
+    Some((path.to_string(), line))
+}
+
+/// Prompt asking the agent to fix `findings`.
+#[must_use]
+pub fn fix_prompt(findings: &[&ReviewFinding]) -> String {
+    let mut prompt = String::from("Fix these code review findings:\n");
+    for (idx, finding) in findings.iter().enumerate() {
+        let location = finding.location();
+        prompt.push_str(&format!("\n{}. [{}] ", idx + 1, finding.severity));
+        if !location.is_empty() {
+            prompt.push_str(&format!("{location}: "));
+        }
+        prompt.push_str(finding.title.trim());
+        if !finding.detail.trim().is_empty() {
+            prompt.push_str(&format!("\n   {}", finding.detail.trim()));
+        }
+    }
+    prompt.push_str(
+        "\n\nKeep each change minimal and leave other findings alone. \
+         Run the relevant tests afterwards.",
+    );
+    prompt
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Row {
+    File(String),
+    Hunk(String),
+    Diff {
+        marker: char,
+        old: Option<usize>,
+        new: Option<usize>,
+        text: String,
+    },
+    Heading(String),
+    Finding(usize),
+}
+
+/// A diff line with the last new-side line number of its hunk.
+struct ParsedLine {
+    row: Row,
+    new: Option<usize>,
+    hunk_end: usize,
+}
+
+struct ParsedFile {
+    path: String,
+    lines: Vec<ParsedLine>,
+}
+
+fn parse_diff(diff: &str) -> Vec<ParsedFile> {
+    let mut files: Vec<ParsedFile> = Vec::new();
+    let (mut old, mut new, mut hunk_end) = (0usize, 0usize, 0usize);
+    for line in diff.lines() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            let path = rest
+                .rsplit(' ')
+                .next()
+                .unwrap_or(rest)
+                .trim_start_matches("b/")
+                .to_string();
+            files.push(ParsedFile {
+                path,
+                lines: Vec::new(),
+            });
+            continue;
+        }
+        if let Some(path) = line.strip_prefix("+++ ") {
+            if let Some(file) = files.last_mut()
+                && path != "/dev/null"
+            {
+                file.path = path.trim_start_matches("b/").to_string();
+            }
+            continue;
+        }
+        if line.starts_with("--- ")
+            || line.starts_with("index ")
+            || line.starts_with("new file")
+            || line.starts_with("deleted file")
+            || line.starts_with("similarity ")
+            || line.starts_with("rename ")
+        {
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+        if line.starts_with("@@") {
+            let (old_start, new_start, new_count) = parse_hunk(line).unwrap_or((0, 0, 0));
+            old = old_start;
+            new = new_start;
+            hunk_end = new_start + new_count.saturating_sub(1);
+            file.lines.push(ParsedLine {
+                row: Row::Hunk(line.to_string()),
+                new: None,
+                hunk_end,
+            });
+            continue;
+        }
+        let (marker, text) = match line.chars().next() {
+            Some(marker @ ('+' | '-' | ' ')) => (marker, &line[1..]),
+            Some('\\') => continue,
+            _ => (' ', line),
+        };
+        let (old_line, new_line) = match marker {
+            '+' => (None, Some(new)),
+            '-' => (Some(old), None),
+            _ => (Some(old), Some(new)),
+        };
+        if old_line.is_some() {
+            old += 1;
+        }
+        if new_line.is_some() {
+            new += 1;
+        }
+        file.lines.push(ParsedLine {
+            row: Row::Diff {
+                marker,
+                old: old_line,
+                new: new_line,
+                text: text.replace('\t', "    "),
+            },
+            new: new_line,
+            hunk_end,
+        });
+    }
+    files
+}
+
+/// `(old_start, new_start, new_count)` of an `@@ -a,b +c,d @@` header.
+fn parse_hunk(line: &str) -> Option<(usize, usize, usize)> {
+    let mut parts = line.split_whitespace().skip(1);
+    let old = parts.next()?.trim_start_matches('-');
+    let new = parts.next()?.trim_start_matches('+');
+    let old_start = old.split(',').next()?.parse().ok()?;
+    let mut new_parts = new.split(',');
+    let new_start = new_parts.next()?.parse().ok()?;
+    let new_count = new_parts
+        .next()
+        .map_or(Some(1), |count| count.parse().ok())?;
+    Some((old_start, new_start, new_count))
+}
+
+fn paths_match(diff_path: &str, finding_path: &str, workspace: &Path) -> bool {
+    let workspace = format!("{}/", workspace.display());
+    let finding = finding_path
+        .trim()
+        .trim_start_matches(workspace.as_str())
+        .trim_start_matches("./")
+        .trim_start_matches("a/")
+        .trim_start_matches("b/");
+    diff_path == finding
+        || diff_path.ends_with(&format!("/{finding}"))
+        || finding.ends_with(&format!("/{diff_path}"))
+}
+
+/// Where a finding attaches in a parsed file: after the diff line it names,
+/// or under the file header (`None`) when the line is outside every hunk.
+fn anchor_in(file: &ParsedFile, line: Option<u32>) -> Option<usize> {
+    let target = usize::try_from(line?).ok()?;
+    file.lines
+        .iter()
+        .enumerate()
+        .filter(|(_, parsed)| {
+            parsed.new.is_some_and(|new| new <= target) && target <= parsed.hunk_end
+        })
+        .max_by_key(|(_, parsed)| parsed.new)
+        .map(|(idx, _)| idx)
+}
+
+fn build_rows(diff: &str, findings: &[ReviewFinding], workspace: &Path) -> Vec<Row> {
+    let files = parse_diff(diff);
+    // (file, anchor line) for each finding; `None` file means outside the diff.
+    let placement: Vec<Option<(usize, Option<usize>)>> = findings
+        .iter()
+        .map(|finding| {
+            let path = finding.path.as_deref()?;
+            let file = files
+                .iter()
+                .position(|file| paths_match(&file.path, path, workspace))?;
+            Some((file, anchor_in(&files[file], finding.line)))
+        })
+        .collect();
+    let attached = |file: usize, anchor: Option<usize>| {
+        placement
+            .iter()
+            .enumerate()
+            .filter(move |(_, place)| **place == Some((file, anchor)))
+            .map(|(idx, _)| Row::Finding(idx))
+    };
+
+    let mut rows = Vec::new();
+    for (file_idx, file) in files.into_iter().enumerate() {
+        rows.push(Row::File(file.path));
+        rows.extend(attached(file_idx, None));
+        for (line_idx, parsed) in file.lines.into_iter().enumerate() {
+            rows.push(parsed.row);
+            rows.extend(attached(file_idx, Some(line_idx)));
+        }
+    }
+    let outside: Vec<Row> = placement
+        .iter()
+        .enumerate()
+        .filter(|(_, place)| place.is_none())
+        .map(|(idx, _)| Row::Finding(idx))
+        .collect();
+    if !outside.is_empty() {
+        rows.push(Row::Heading(if rows.is_empty() {
+            "No uncommitted changes; findings:".to_string()
+        } else {
+            "Not in the current diff:".to_string()
+        }));
+        rows.extend(outside);
+    }
+    rows
+}
+
+pub struct ReviewDiffView {
+    title: String,
+    findings: Vec<ReviewFinding>,
+    addressed: Vec<bool>,
+    marked: Vec<bool>,
+    rows: Vec<Row>,
+    /// Row indices of findings, in display order.
+    finding_rows: Vec<usize>,
+    /// Position in `finding_rows` of the current finding.
+    current: usize,
+    scroll: Cell<usize>,
+    /// Scroll the current finding into view on the next render.
+    follow_current: Cell<bool>,
+    page_height: Cell<usize>,
+}
+
+impl ReviewDiffView {
+    #[must_use]
+    pub fn new(
+        title: impl Into<String>,
+        diff: &str,
+        findings: Vec<ReviewFinding>,
+        addressed: &HashSet<String>,
+        workspace: &Path,
+    ) -> Self {
+        let rows = build_rows(diff, &findings, workspace);
+        let finding_rows = rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| matches!(row, Row::Finding(_)))
+            .map(|(idx, _)| idx)
+            .collect();
+        Self {
+            title: title.into(),
+            addressed: findings
+                .iter()
+                .map(|finding| addressed.contains(&finding.key()))
+                .collect(),
+            marked: vec![false; findings.len()],
+            findings,
+            rows,
+            finding_rows,
+            current: 0,
+            scroll: Cell::new(0),
+            follow_current: Cell::new(true),
+            page_height: Cell::new(10),
+        }
+    }
+
+    fn current_finding(&self) -> Option<usize> {
+        let row = *self.finding_rows.get(self.current)?;
+        match self.rows[row] {
+            Row::Finding(idx) => Some(idx),
+            _ => None,
+        }
+    }
+
+    fn jump(&mut self, forward: bool) {
+        let count = self.finding_rows.len();
+        if count == 0 {
+            return;
+        }
+        self.current = if forward {
+            (self.current + 1) % count
+        } else {
+            (self.current + count - 1) % count
+        };
+        self.follow_current.set(true);
+    }
+
+    fn scroll_by(&self, delta: isize) {
+        let next = self.scroll.get().saturating_add_signed(delta);
+        self.scroll.set(next);
+        self.follow_current.set(false);
+    }
+
+    fn fix_request(&self) -> Option<ReviewDiffAction> {
+        let mut chosen: Vec<&ReviewFinding> = self
+            .findings
+            .iter()
+            .zip(&self.marked)
+            .filter(|(_, marked)| **marked)
+            .map(|(finding, _)| finding)
+            .collect();
+        if chosen.is_empty() {
+            chosen.push(&self.findings[self.current_finding()?]);
+        }
+        Some(ReviewDiffAction::Fix {
+            prompt: fix_prompt(&chosen),
+        })
+    }
+
+    /// Rendered lines plus the first line of the current finding.
+    fn lines(&self, width: usize) -> (Vec<Line<'static>>, Option<usize>) {
+        let current = self.current_finding();
+        let mut lines = Vec::new();
+        let mut current_line = None;
+        for row in &self.rows {
+            match row {
+                Row::File(path) => {
+                    if !lines.is_empty() {
+                        lines.push(Line::from(""));
+                    }
+                    lines.push(Line::from(Span::styled(
+                        truncate_with_ellipsis(path, width, "…"),
+                        Style::default()
+                            .fg(palette::DEEPSEEK_SKY)
+                            .add_modifier(Modifier::BOLD),
+                    )));
+                }
+                Row::Hunk(header) => lines.push(Line::from(Span::styled(
+                    truncate_with_ellipsis(header, width, "…"),
+                    Style::default().fg(palette::DEEPSEEK_BLUE),
+                ))),
+                Row::Diff {
+                    marker,
+                    old,
+                    new,
+                    text,
+                } => lines.push(diff_line(*marker, *old, *new, text, width)),
+                Row::Heading(text) => {
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(Span::styled(
+                        text.clone(),
+                        Style::default()
+                            .fg(palette::TEXT_MUTED)
+                            .add_modifier(Modifier::BOLD),
+                    )));
+                }
+                Row::Finding(idx) => {
+                    let is_current = current == Some(*idx);
+                    if is_current {
+                        current_line = Some(lines.len());
+                    }
+                    lines.extend(self.finding_lines(*idx, is_current, width));
+                }
+            }
+        }
+        (lines, current_line)
+    }
+
+    fn finding_lines(&self, idx: usize, is_current: bool, width: usize) -> Vec<Line<'static>> {
+        let finding = &self.findings[idx];
+        let addressed = self.addressed[idx];
+        let color = if addressed {
+            palette::TEXT_MUTED
+        } else {
+            severity_color(&finding.severity)
+        };
+        let bar_style = if is_current {
+            Style::default()
+                .fg(palette::DEEPSEEK_SKY)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(color)
+        };
+        let bar = if is_current { " ▶ " } else { " ┃ " };
+        let check = if self.marked[idx] { "[x] " } else { "[ ] " };
+        let mut head = format!("{check}{} ", finding.severity);
+        let location = finding.location();
+        if !location.is_empty() {
+            head.push_str(&format!("{location} "));
+        }
+        let title_style = if addressed {
+            Style::default()
+                .fg(palette::TEXT_MUTED)
+                .add_modifier(Modifier::CROSSED_OUT)
+        } else {
+            Style::default().fg(palette::TEXT_PRIMARY)
+        };
+        let available = width.saturating_sub(bar.width()).max(8);
+        let mut lines = Vec::new();
+        let mut title = finding.title.clone();
+        if addressed {
+            title.push_str("  (addressed)");
+        }
+        let head_width = head.width();
+        for (line_idx, chunk) in wrap(&title, available.saturating_sub(head_width).max(8))
+            .into_iter()
+            .enumerate()
+        {
+            let lead = if line_idx == 0 {
+                Span::styled(
+                    head.clone(),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                )
+            } else {
+                Span::raw(" ".repeat(head_width))
+            };
+            lines.push(Line::from(vec![
+                Span::styled(bar, bar_style),
+                lead,
+                Span::styled(chunk, title_style),
+            ]));
+        }
+        for chunk in wrap(finding.detail.trim(), available.saturating_sub(4).max(8)) {
+            lines.push(Line::from(vec![
+                Span::styled(bar, bar_style),
+                Span::styled(
+                    format!("    {chunk}"),
+                    Style::default().fg(palette::TEXT_MUTED),
+                ),
+            ]));
+        }
+        lines
+    }
+}
+
+impl ModalView for ReviewDiffView {
+    fn kind(&self) -> ModalKind {
+        ModalKind::ReviewDiff
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return ViewAction::None;
+        }
+        let page = isize::try_from(self.page_height.get().max(1)).unwrap_or(10);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => ViewAction::Close,
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.scroll_by(1);
+                ViewAction::None
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.scroll_by(-1);
+                ViewAction::None
+            }
+            KeyCode::PageDown | KeyCode::Char(' ') => {
+                self.scroll_by(page);
+                ViewAction::None
+            }
+            KeyCode::PageUp => {
+                self.scroll_by(-page);
+                ViewAction::None
+            }
+            KeyCode::Char('g') | KeyCode::Home => {
+                self.scroll.set(0);
+                self.follow_current.set(false);
+                ViewAction::None
+            }
+            KeyCode::Char('G') | KeyCode::End => {
+                self.scroll.set(usize::MAX);
+                self.follow_current.set(false);
+                ViewAction::None
+            }
+            KeyCode::Char('n') | KeyCode::Char(']') | KeyCode::Tab => {
+                self.jump(true);
+                ViewAction::None
+            }
+            KeyCode::Char('N') | KeyCode::Char('p') | KeyCode::Char('[') | KeyCode::BackTab => {
+                self.jump(false);
+                ViewAction::None
+            }
+            KeyCode::Char('x') => {
+                if let Some(idx) = self.current_finding() {
+                    self.marked[idx] = !self.marked[idx];
+                }
+                ViewAction::None
+            }
+            KeyCode::Char('a') => {
+                let Some(idx) = self.current_finding() else {
+                    return ViewAction::None;
+                };
+                self.addressed[idx] = !self.addressed[idx];
+                self.marked[idx] = false;
+                ViewAction::Emit(ViewEvent::ReviewDiffRequested {
+                    action: ReviewDiffAction::SetAddressed {
+                        key: self.findings[idx].key(),
+                        addressed: self.addressed[idx],
+                    },
+                })
+            }
+            KeyCode::Enter | KeyCode::Char('f') => match self.fix_request() {
+                Some(action) => ViewAction::EmitAndClose(ViewEvent::ReviewDiffRequested { action }),
+                None => ViewAction::None,
+            },
+            _ => ViewAction::None,
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let popup_width = 120.min(area.width.saturating_sub(4));
+        let popup_height = area.height.saturating_sub(4);
+        if popup_width == 0 || popup_height == 0 {
+            return;
+        }
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(popup_width)) / 2,
+            y: area.y + (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        Clear.render(popup_area, buf);
+
+        let hint = |key: &'static str, label: &'static str| {
+            [
+                Span::styled(key, Style::default().fg(palette::TEXT_MUTED)),
+                Span::raw(label),
+            ]
+        };
+        let open = self.addressed.iter().filter(|done| !**done).count();
+        let position = if self.finding_rows.is_empty() {
+            String::new()
+        } else {
+            format!(" {}/{} ·", self.current + 1, self.finding_rows.len())
+        };
+        let title = format!(" {} —{position} {open} open ", self.title);
+        let block = Block::default()
+            .title(Line::from(Span::styled(
+                title,
+                Style::default()
+                    .fg(palette::DEEPSEEK_SKY)
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .title_bottom(Line::from(
+                [
+                    hint(" n/N ", "next/prev "),
+                    hint(" x ", "select "),
+                    hint(" a ", "addressed "),
+                    hint(" Enter ", "fix selected "),
+                    hint(" Esc ", "close "),
+                ]
+                .concat(),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(palette::BORDER_COLOR))
+            .style(Style::default().bg(palette::DEEPSEEK_INK))
+            .padding(Padding::horizontal(1));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let height = usize::from(inner.height).max(1);
+        self.page_height.set(height.saturating_sub(1).max(1));
+        let (mut lines, current_line) = self.lines(usize::from(inner.width));
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No findings and no uncommitted changes.",
+                Style::default().fg(palette::TEXT_MUTED),
+            )));
+        }
+        let max_scroll = lines.len().saturating_sub(height);
+        if self.follow_current.get()
+            && let Some(line) = current_line
+        {
+            // Keep a little diff context above the finding.
+            self.scroll.set(line.saturating_sub(height / 3));
+        }
+        let scroll = self.scroll.get().min(max_scroll);
+        self.scroll.set(scroll);
+
+        let visible: Vec<Line<'static>> = lines.into_iter().skip(scroll).take(height).collect();
+        Paragraph::new(visible).render(inner, buf);
+    }
+}
+
+fn severity_color(severity: &str) -> Color {
+    match severity {
+        "error" => palette::STATUS_ERROR,
+        "warning" => palette::STATUS_WARNING,
+        _ => palette::STATUS_INFO,
+    }
+}
+
+fn diff_line(
+    marker: char,
+    old: Option<usize>,
+    new: Option<usize>,
+    text: &str,
+    width: usize,
+) -> Line<'static> {
+    let number = |value: Option<usize>| value.map_or_else(|| " ".repeat(4), |v| format!("{v:>4}"));
+    let prefix = format!("{} {} {marker} ", number(old), number(new));
+    let style = match marker {
+        '+' => Style::default()
+            .fg(palette::DIFF_ADDED)
+            .bg(palette::DIFF_ADDED_BG),
+        '-' => Style::default()
+            .fg(palette::STATUS_ERROR)
+            .bg(palette::DIFF_DELETED_BG),
+        _ => Style::default().fg(palette::TEXT_PRIMARY),
+    };
+    let body = truncate_with_ellipsis(text, width.saturating_sub(prefix.width()), "…");
+    Line::from(vec![
+        Span::styled(prefix, Style::default().fg(palette::TEXT_MUTED)),
+        Span::styled(body, style),
+    ])
+}
+
+/// Greedy word wrap to `width` columns; overlong words are split.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let mut word = word.to_string();
+        while word.width() > width {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            let split = word
+                .char_indices()
+                .scan(0, |cols, (idx, ch)| {
+                    *cols += unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
+                    Some((idx, *cols))
+                })
+                .find(|(_, cols)| *cols > width)
+                .map_or(word.len(), |(idx, _)| idx);
+            lines.push(word[..split].to_string());
+            word = word[split..].to_string();
+        }
+        if !current.is_empty() && current.width() + 1 + word.width() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::review::{ReviewIssue, ReviewSuggestion};
+
+    const DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs\n\
+index 111..222 100644\n\
+--- a/src/lib.rs\n\
++++ b/src/lib.rs\n\
+@@ -10,4 +10,5 @@ fn main() {\n\
+ let a = 1;\n\
+-let b = 2;\n\
++let b = 3;\n\
++let c = b.unwrap();\n\
+ let d = 4;\n\
+ let e = 5;\n";
+
+    fn finding(path: &str, line: Option<u32>, title: &str) -> ReviewFinding {
+        ReviewFinding {
+            severity: "error".to_string(),
+            title: title.to_string(),
+            detail: "detail".to_string(),
+            path: Some(path.to_string()),
+            line,
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn findings_attach_under_the_line_they_name() {
+        let findings = vec![
+            finding("src/lib.rs", Some(12), "unwrap on a value"),
+            finding("./src/lib.rs", None, "file-level note"),
+            finding("src/other.rs", Some(3), "elsewhere"),
+        ];
+        let rows = build_rows(DIFF, &findings, Path::new("/ws"));
+        let position = |row: &Row| rows.iter().position(|r| r == row).unwrap();
+        let added = position(&Row::Diff {
+            marker: '+',
+            old: None,
+            new: Some(12),
+            text: "let c = b.unwrap();".to_string(),
+        });
+        assert_eq!(rows[added + 1], Row::Finding(0));
+        assert_eq!(
+            rows[1],
+            Row::Finding(1),
+            "file-level findings follow the header"
+        );
+        assert_eq!(
+            &rows[rows.len() - 2..],
+            &[
+                Row::Heading("Not in the current diff:".to_string()),
+                Row::Finding(2)
+            ]
+        );
+    }
+
+    #[test]
+    fn keys_navigate_mark_and_request_fixes() {
+        let findings = vec![
+            finding("src/lib.rs", Some(11), "first"),
+            finding("src/lib.rs", Some(13), "second"),
+        ];
+        let mut view = ReviewDiffView::new(
+            "Review",
+            DIFF,
+            findings.clone(),
+            &HashSet::from([findings[0].key()]),
+            Path::new("/ws"),
+        );
+        assert_eq!(view.addressed, vec![true, false]);
+
+        view.handle_key(key(KeyCode::Char('n')));
+        assert_eq!(view.current_finding(), Some(1));
+        match view.handle_key(key(KeyCode::Char('a'))) {
+            ViewAction::Emit(ViewEvent::ReviewDiffRequested {
+                action: ReviewDiffAction::SetAddressed { key, addressed },
+            }) => {
+                assert_eq!(key, findings[1].key());
+                assert!(addressed);
+            }
+            other => panic!("unexpected action {other:?}"),
+        }
+
+        view.handle_key(key(KeyCode::Char('n')));
+        view.handle_key(key(KeyCode::Char('x')));
+        match view.handle_key(key(KeyCode::Enter)) {
+            ViewAction::EmitAndClose(ViewEvent::ReviewDiffRequested {
+                action: ReviewDiffAction::Fix { prompt },
+            }) => {
+                assert!(
+                    prompt.contains("1. [error] src/lib.rs:11: first"),
+                    "{prompt}"
+                );
+                assert!(!prompt.contains("second"), "{prompt}");
+            }
+            other => panic!("unexpected action {other:?}"),
+        }
+    }
+
+    #[test]
+    fn prose_and_structured_reviews_yield_findings() {
+        let text = "Summary\n\
+- **Bug**: `src/lib.rs:12` unwraps a value that can be None\n\
+- Consider renaming (src/util/mod.rs:40-44)\n\
+- See https://example.com:443 for details\n\
+- version 1.2:3 is not a path\n";
+        let findings = findings_from_text(text);
+        assert_eq!(findings.len(), 2, "{findings:?}");
+        assert_eq!(findings[0].path.as_deref(), Some("src/lib.rs"));
+        assert_eq!(findings[0].line, Some(12));
+        assert_eq!(findings[0].severity, "error");
+        assert_eq!(findings[1].line, Some(40));
+        assert_eq!(findings[1].severity, "info");
+
+        let output = ReviewOutput {
+            summary: String::new(),
+            issues: vec![ReviewIssue {
+                severity: "warning".to_string(),
+                title: "t".to_string(),
+                description: "d".to_string(),
+                path: Some("a.rs".to_string()),
+                line: Some(1),
+            }],
+            suggestions: vec![ReviewSuggestion {
+                path: None,
+                line: None,
+                suggestion: "s".to_string(),
+            }],
+            overall_assessment: String::new(),
+        };
+        let findings = findings_from_review(&output);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[1].severity, "info");
+        assert_eq!(findings[1].title, "s");
+    }
+
+    #[test]
+    fn render_scrolls_the_current_finding_into_view() {
+        let mut diff = String::from(
+            "diff --git a/big.rs b/big.rs\n--- a/big.rs\n+++ b/big.rs\n@@ -1,200 +1,200 @@\n",
+        );
+        for idx in 0..200 {
+            diff.push_str(&format!(" line {idx}\n"));
+        }
+        let mut view = ReviewDiffView::new(
+            "Review",
+            &diff,
+            vec![finding("big.rs", Some(150), "deep finding")],
+            &HashSet::new(),
+            Path::new("/ws"),
+        );
+        let area = Rect::new(0, 0, 100, 30);
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+        let text: String = (0..area.height)
+            .flat_map(|y| (0..area.width).map(move |x| (x, y)))
+            .map(|(x, y)| buf[(x, y)].symbol().to_string())
+            .collect();
+        assert!(text.contains("deep finding"));
+
+        view.handle_key(key(KeyCode::Char('g')));
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+        assert_eq!(view.scroll.get(), 0);
+    }
+}
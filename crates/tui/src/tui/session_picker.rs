@@ -73,10 +73,15 @@ impl SessionPickerView {
     /// Construct a picker scoped to `workspace`. Sessions belonging to
     /// other workspaces are hidden by default — press `a` inside the
     /// picker to expand to all workspaces (#1395).
-    pub fn new(workspace: &Path) -> Self {
-        let sessions = SessionManager::default_location()
-            .and_then(|manager| manager.list_sessions())
-            .unwrap_or_default();
+    ///
+    /// `index` is a session list loaded at startup; `None` reads the
+    /// sessions directory now.
+    pub fn from_index(workspace: &Path, index: Option<Vec<SessionMetadata>>) -> Self {
+        let sessions = index.unwrap_or_else(|| {
+            SessionManager::default_location()
+                .and_then(|manager| manager.list_sessions())
+                .unwrap_or_default()
+        });
 
         let mut view = Self {
             sessions,
@@ -312,6 +317,21 @@ impl SessionPickerView {
     }
 
     fn refresh_preview(&mut self) {
+        self.load_preview(false);
+    }
+
+    /// Rebuild the highlighted session's summary even if the stored one is
+    /// still current (bound to `r`).
+    fn regenerate_summary(&mut self) {
+        let Some(id) = self.selected_session().map(|session| session.id.clone()) else {
+            return;
+        };
+        self.preview_cache.remove(&id);
+        self.load_preview(true);
+        self.status = Some("Summary regenerated".to_string());
+    }
+
+    fn load_preview(&mut self, regenerate_summary: bool) {
         let Some(session) = self.selected_session() else {
             self.current_preview = vec!["No sessions found.".to_string()];
             self.scroll_history_to_latest();
@@ -333,7 +353,7 @@ impl SessionPickerView {
             }
         };
 
-        let saved = match manager.load_session(&session.id) {
+        let mut saved = match manager.load_session(&session.id) {
             Ok(saved) => saved,
             Err(_) => {
                 self.current_preview = vec!["Failed to load session preview.".to_string()];
@@ -342,9 +362,24 @@ impl SessionPickerView {
             }
         };
 
+        // Summaries are built lazily the first time a session is
+        // highlighted and persisted so later opens are free. A failed write
+        // (read-only sessions dir) still shows the freshly built summary.
+        if regenerate_summary || !crate::session_summary::has_current_summary(&saved) {
+            let summary = crate::session_summary::store_summary(&manager, &saved.metadata.id)
+                .unwrap_or_else(|_| crate::session_summary::summarize_session(&saved));
+            saved.metadata.summary = Some(summary);
+            let id = saved.metadata.id.clone();
+            for meta in self.sessions.iter_mut().chain(self.filtered.iter_mut()) {
+                if meta.id == id {
+                    meta.summary.clone_from(&saved.metadata.summary);
+                }
+            }
+        }
+
         let preview = build_preview_lines(&saved);
         self.preview_cache
-            .insert(session.id.clone(), preview.clone());
+            .insert(saved.metadata.id.clone(), preview.clone());
         self.current_preview = preview;
         self.scroll_history_to_latest();
     }
@@ -438,6 +473,10 @@ impl ModalView for SessionPickerView {
                 self.toggle_all_workspaces();
                 ViewAction::None
             }
+            KeyCode::Char('r') | KeyCode::Char('R') => {
+                self.regenerate_summary();
+                ViewAction::None
+            }
             KeyCode::Char('d') | KeyCode::Char('D') => {
                 self.confirm_delete = true;
                 self.status = Some("Delete session? (y/n)".to_string());
@@ -546,7 +585,7 @@ fn build_list_lines(
         format!("/{}", search_input)
     } else {
         format!(
-            "1-9 history | PgUp/PgDn scroll | Enter resume | / search | s sort | a all | d delete | Sort: {sort_label}"
+            "1-9 history | PgUp/PgDn scroll | Enter resume | / search | s sort | a all | r summary | d delete | Sort: {sort_label}"
         )
     };
     lines.push(Line::from(Span::styled(
@@ -629,12 +668,18 @@ fn format_session_line(session: &SessionMetadata) -> String {
         .as_deref()
         .map(|parent| format!(" | fork {}", crate::session_manager::truncate_id(parent)))
         .unwrap_or_default();
+    let paused_label = if session.awaiting_input.is_some() {
+        " | awaiting answer"
+    } else {
+        ""
+    };
     format!(
-        "{} | {} | {} msgs{} | {} | {}",
+        "{} | {} | {} msgs{}{} | {} | {}",
         crate::session_manager::truncate_id(&session.id),
         title,
         session.message_count,
         fork_label,
+        paused_label,
         mode,
         updated
     )
@@ -658,6 +703,15 @@ fn build_preview_lines(session: &SavedSession) -> Vec<String> {
     if let Some(mode) = session.metadata.mode.as_deref() {
         out.push(format!("Mode: {}", mode));
     }
+    if let Some(question) = session.metadata.awaiting_input.as_deref() {
+        out.push(format!("Paused on a question: {question}"));
+    }
+    if let Some(summary) = session.metadata.summary.as_ref() {
+        out.push("Summary:".to_string());
+        for bullet in &summary.bullets {
+            out.push(format!("  • {bullet}"));
+        }
+    }
     out.push("".to_string());
 
     for message in &session.messages {
@@ -779,7 +833,7 @@ fn visible_preview_lines(lines: &[String], scroll: usize, visible_rows: usize) -
     out
 }
 
-fn format_relative_time(dt: &DateTime<chrono::Utc>) -> String {
+pub(crate) fn format_relative_time(dt: &DateTime<chrono::Utc>) -> String {
     let now = chrono::Utc::now();
     let duration = now.signed_duration_since(*dt);
     if duration.num_minutes() < 1 {
@@ -872,6 +926,10 @@ mod tests {
             cost: crate::session_manager::SessionCostSnapshot::default(),
             parent_session_id: None,
             forked_from_message_count: None,
+            summary: None,
+            awaiting_input: None,
+            pinned: false,
+            tags: Vec::new(),
         }
     }
 
@@ -1171,6 +1229,22 @@ mod tests {
         assert!(!lines.contains("hidden reasoning"));
     }
 
+    #[test]
+    fn build_preview_lines_pins_summary_bullets_in_header() {
+        let mut session = saved_session_with_messages(vec![
+            text_message("user", "Rename the config loader"),
+            text_message("assistant", "Renamed and updated callers."),
+        ]);
+        session.metadata.summary = Some(crate::session_summary::summarize_session(&session));
+        let lines = build_preview_lines(&session);
+        let header_end = lines.iter().position(String::is_empty).expect("header");
+        let header = lines[..header_end].join("\n");
+
+        assert!(header.contains("Summary:"));
+        assert!(header.contains("  • Asked: Rename the config loader"));
+        assert!(header.contains("  • Outcome: Renamed and updated callers."));
+    }
+
     #[test]
     fn ensure_selected_visible_updates_scroll_window() {
         let sessions = (0..10)
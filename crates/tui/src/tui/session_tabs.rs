@@ -0,0 +1,496 @@
+//! Parallel session tabs inside one TUI process.
+//!
+//! Each tab owns a full `App` + `EngineHandle` pair, so history, queue, and
+//! in-flight turns are independent. Only the active tab is driven by
+//! `ui::run_event_loop`; the loop returns a [`LoopExit`] when the user asks
+//! for a new tab (`Ctrl+T`), switches (`Ctrl+1..9`), or closes the focused
+//! tab (`Alt+W`), and `run_tui` swaps the active pair before re-entering the
+//! loop.
+//!
+//! Background tabs keep their engine running. Because the engine event
+//! channel is bounded, a parked tab gets an [`EventRelay`] task that drains
+//! the channel into a replay buffer; re-activating the tab stops the relay
+//! and the loop replays the buffered events in order before live ones.
+//! The relay's attention flags are shared with the active `App`, and the
+//! event loop calls [`refresh_tab_strip`] every tick so a background prompt
+//! badges its tab as soon as it arrives. Closing a tab or quitting stops the
+//! relays first, so `run_tui` can replay what they buffered into each `App`
+//! and save the session before shutting its engine down.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::core::engine::EngineHandle;
+use crate::core::events::Event as EngineEvent;
+use crate::tui::app::App;
+
+/// `Ctrl+1..9` addresses at most nine tabs.
+pub const MAX_SESSION_TABS: usize = 9;
+
+/// Longest title rendered in the header tab strip before truncation.
+const TAB_TITLE_MAX_CHARS: usize = 16;
+
+/// Most events a parked tab buffers. Streaming deltas are merged, so this is
+/// only reached by a long unattended run; the relay then stops draining and
+/// the engine waits on its channel until the tab is focused again.
+const MAX_RELAY_BUFFERED_EVENTS: usize = 4096;
+
+/// Why `run_event_loop` handed control back to `run_tui`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopExit {
+    /// The user quit the TUI.
+    Quit,
+    /// Open a fresh session in a new tab and focus it.
+    NewTab,
+    /// Focus the tab at this zero-based index.
+    SwitchTab(usize),
+    /// Close the focused tab and focus its left neighbour.
+    CloseTab,
+}
+
+/// Header-strip entry for one tab.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TabLabel {
+    pub title: String,
+    /// Background tab received an approval prompt, a user-input request, or
+    /// finished its turn since it was parked, or filled its replay buffer.
+    pub needs_attention: bool,
+}
+
+/// Live attention flags of the parked tabs, indexed like the tab strip.
+/// Handed to the active `App` so the strip can be refreshed every tick.
+#[derive(Debug, Clone, Default)]
+pub struct TabAttention(Vec<Option<Arc<AtomicBool>>>);
+
+impl TabAttention {
+    /// Copy the live flags onto `labels`. Returns whether any badge changed.
+    fn refresh(&self, labels: &mut [TabLabel]) -> bool {
+        let mut changed = false;
+        for (label, flag) in labels.iter_mut().zip(&self.0) {
+            let needs_attention = flag
+                .as_ref()
+                .is_some_and(|flag| flag.load(Ordering::SeqCst));
+            if label.needs_attention != needs_attention {
+                label.needs_attention = needs_attention;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// Drains a parked tab's engine events so the engine never blocks on a full
+/// channel while the tab is off screen.
+struct EventRelay {
+    stop: CancellationToken,
+    attention: Arc<AtomicBool>,
+    task: JoinHandle<VecDeque<EngineEvent>>,
+}
+
+impl EventRelay {
+    fn spawn(engine: &EngineHandle) -> Self {
+        let stop = CancellationToken::new();
+        let attention = Arc::new(AtomicBool::new(false));
+        let rx_event = engine.rx_event.clone();
+        let task_stop = stop.clone();
+        let task_attention = attention.clone();
+        let task = tokio::spawn(async move {
+            let mut buffered = VecDeque::new();
+            let mut rx = rx_event.write().await;
+            loop {
+                if buffered.len() >= MAX_RELAY_BUFFERED_EVENTS {
+                    task_attention.store(true, Ordering::SeqCst);
+                    task_stop.cancelled().await;
+                    break;
+                }
+                tokio::select! {
+                    biased;
+                    () = task_stop.cancelled() => break,
+                    event = rx.recv() => {
+                        let Some(event) = event else { break };
+                        if event_needs_attention(&event) {
+                            task_attention.store(true, Ordering::SeqCst);
+                        }
+                        buffer_event(&mut buffered, event);
+                    }
+                }
+            }
+            buffered
+        });
+        Self {
+            stop,
+            attention,
+            task,
+        }
+    }
+
+    fn needs_attention(&self) -> bool {
+        self.attention.load(Ordering::SeqCst)
+    }
+
+    async fn finish(self) -> VecDeque<EngineEvent> {
+        self.stop.cancel();
+        self.task.await.unwrap_or_default()
+    }
+}
+
+/// Queue `event` for replay, appending a streaming delta to the previous
+/// one when they continue the same block.
+fn buffer_event(buffered: &mut VecDeque<EngineEvent>, event: EngineEvent) {
+    match (buffered.back_mut(), event) {
+        (
+            Some(EngineEvent::MessageDelta {
+                index: last,
+                content: text,
+            }),
+            EngineEvent::MessageDelta { index, content },
+        )
+        | (
+            Some(EngineEvent::ThinkingDelta {
+                index: last,
+                content: text,
+            }),
+            EngineEvent::ThinkingDelta { index, content },
+        ) if *last == index => text.push_str(&content),
+        (_, event) => buffered.push_back(event),
+    }
+}
+
+fn event_needs_attention(event: &EngineEvent) -> bool {
+    matches!(
+        event,
+        EngineEvent::ApprovalRequired { .. }
+            | EngineEvent::UserInputRequired { .. }
+            | EngineEvent::ElevationRequired { .. }
+            | EngineEvent::TurnComplete { .. }
+    )
+}
+
+/// One independent session: UI state, engine, and the per-tab stream state
+/// that `run_event_loop` would otherwise keep in locals.
+pub struct SessionTab {
+    pub app: App,
+    pub engine: EngineHandle,
+    /// Events captured while the tab was parked, replayed before live events.
+    pub pending_events: VecDeque<EngineEvent>,
+    /// Assistant text accumulated for the in-flight message.
+    pub streaming_text: String,
+    relay: Option<EventRelay>,
+}
+
+impl SessionTab {
+    #[must_use]
+    pub fn new(app: App, engine: EngineHandle) -> Self {
+        Self {
+            app,
+            engine,
+            pending_events: VecDeque::new(),
+            streaming_text: String::new(),
+            relay: None,
+        }
+    }
+
+    fn park(&mut self) {
+        if self.relay.is_none() {
+            self.relay = Some(EventRelay::spawn(&self.engine));
+        }
+    }
+
+    async fn resume(&mut self) {
+        if let Some(relay) = self.relay.take() {
+            let buffered = relay.finish().await;
+            self.pending_events.extend(buffered);
+        }
+        self.app.needs_redraw = true;
+    }
+
+    /// Stop the relay for good, keeping everything it buffered and anything
+    /// still queued on the engine channel in `pending_events`.
+    async fn stop_relay(&mut self) {
+        self.resume().await;
+        let mut rx = self.engine.rx_event.write().await;
+        while let Ok(event) = rx.try_recv() {
+            buffer_event(&mut self.pending_events, event);
+        }
+    }
+
+    fn attention_flag(&self) -> Option<Arc<AtomicBool>> {
+        self.relay.as_ref().map(|relay| relay.attention.clone())
+    }
+
+    fn label(&self) -> TabLabel {
+        TabLabel {
+            title: tab_title(&self.app),
+            needs_attention: self.relay.as_ref().is_some_and(EventRelay::needs_attention),
+        }
+    }
+}
+
+fn tab_title(app: &App) -> String {
+    let raw = app
+        .session_title
+        .as_deref()
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .unwrap_or("new session");
+    if raw.chars().count() <= TAB_TITLE_MAX_CHARS {
+        return raw.to_string();
+    }
+    let mut truncated: String = raw.chars().take(TAB_TITLE_MAX_CHARS - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Ordered set of tabs with exactly one active entry.
+pub struct SessionTabs {
+    tabs: Vec<SessionTab>,
+    active: usize,
+}
+
+impl SessionTabs {
+    #[must_use]
+    pub fn new(first: SessionTab) -> Self {
+        let mut tabs = Self {
+            tabs: vec![first],
+            active: 0,
+        };
+        tabs.sync_tab_strip();
+        tabs
+    }
+
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.tabs.len() >= MAX_SESSION_TABS
+    }
+
+    pub fn active_mut(&mut self) -> &mut SessionTab {
+        &mut self.tabs[self.active]
+    }
+
+    #[must_use]
+    pub fn active(&self) -> &SessionTab {
+        &self.tabs[self.active]
+    }
+
+    /// Park the active tab and focus `tab`, returning its index. Callers
+    /// check [`Self::is_full`] first.
+    pub fn push(&mut self, tab: SessionTab) -> usize {
+        debug_assert!(!self.is_full(), "session tab limit exceeded");
+        self.tabs[self.active].park();
+        self.tabs.push(tab);
+        self.active = self.tabs.len() - 1;
+        self.sync_tab_strip();
+        self.active
+    }
+
+    /// Focus the tab at `index`. Returns `false` when no such tab exists.
+    pub async fn activate(&mut self, index: usize) -> bool {
+        if index >= self.tabs.len() {
+            return false;
+        }
+        if index != self.active {
+            self.tabs[self.active].park();
+            self.active = index;
+            self.tabs[index].resume().await;
+        }
+        self.sync_tab_strip();
+        true
+    }
+
+    /// Close the tab at `index`, stopping its relay, and focus the left
+    /// neighbour if it was the active one. Returns the closed tab, whose
+    /// `pending_events` still need replaying, or `None` when `index` is out
+    /// of range or names the last tab (only quitting closes that).
+    pub async fn close(&mut self, index: usize) -> Option<SessionTab> {
+        if index >= self.tabs.len() || self.tabs.len() == 1 {
+            return None;
+        }
+        let mut closed = self.tabs.remove(index);
+        closed.stop_relay().await;
+        if index < self.active {
+            self.active -= 1;
+        } else if index == self.active {
+            self.active = index.saturating_sub(1);
+            self.tabs[self.active].resume().await;
+        }
+        self.sync_tab_strip();
+        Some(closed)
+    }
+
+    /// Stop every background relay before shutdown. Returns the background
+    /// tabs with their buffered events in `pending_events`, for the caller to
+    /// replay and save before shutting their engines down.
+    pub async fn drain_background(&mut self) -> Vec<&mut SessionTab> {
+        let active = self.active;
+        let mut background = Vec::new();
+        for (index, tab) in self.tabs.iter_mut().enumerate() {
+            if index != active {
+                tab.stop_relay().await;
+                background.push(tab);
+            }
+        }
+        background
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SessionTab> {
+        self.tabs.iter()
+    }
+
+    #[must_use]
+    pub fn labels(&self) -> Vec<TabLabel> {
+        self.tabs.iter().map(SessionTab::label).collect()
+    }
+
+    /// Copy the current tab strip into the active `App` so the header can
+    /// render it without reaching back into `SessionTabs`, along with the
+    /// parked tabs' live attention flags for [`refresh_tab_strip`].
+    pub fn sync_tab_strip(&mut self) {
+        let (labels, attention) = if self.tabs.len() > 1 {
+            (
+                self.labels(),
+                self.tabs.iter().map(SessionTab::attention_flag).collect(),
+            )
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        let active = self.active;
+        let app = &mut self.tabs[active].app;
+        app.session_tabs = labels;
+        app.session_tab_attention = TabAttention(attention);
+        app.active_session_tab = active;
+    }
+}
+
+/// Bring the active `App`'s tab strip up to date: its own title and the
+/// parked tabs' attention badges. Returns whether anything changed.
+pub fn refresh_tab_strip(app: &mut App) -> bool {
+    if app.session_tabs.is_empty() {
+        return false;
+    }
+    let mut changed = false;
+    let title = tab_title(app);
+    if let Some(label) = app.session_tabs.get_mut(app.active_session_tab)
+        && label.title != title
+    {
+        label.title = title;
+        changed = true;
+    }
+    app.session_tab_attention.refresh(&mut app.session_tabs) || changed
+}
+
+/// Map `Ctrl+1..9` to a zero-based tab index.
+#[must_use]
+pub fn tab_index_for_digit(ch: char) -> Option<usize> {
+    let digit = ch.to_digit(10)? as usize;
+    (1..=MAX_SESSION_TABS).contains(&digit).then(|| digit - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::engine::mock_engine_handle;
+
+    #[test]
+    fn digit_shortcuts_map_to_zero_based_tabs() {
+        assert_eq!(tab_index_for_digit('1'), Some(0));
+        assert_eq!(tab_index_for_digit('9'), Some(8));
+        assert_eq!(tab_index_for_digit('0'), None);
+        assert_eq!(tab_index_for_digit('x'), None);
+    }
+
+    #[tokio::test]
+    async fn relay_buffers_events_and_flags_attention() {
+        let mock = mock_engine_handle();
+        let relay = EventRelay::spawn(&mock.handle);
+        mock.tx_event
+            .send(EngineEvent::Status {
+                message: "working".to_string(),
+            })
+            .await
+            .unwrap();
+        mock.tx_event
+            .send(EngineEvent::TurnStarted {
+                turn_id: "t1".to_string(),
+            })
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(!relay.needs_attention());
+
+        let buffered = relay.finish().await;
+        assert_eq!(buffered.len(), 2);
+        assert!(matches!(buffered[0], EngineEvent::Status { .. }));
+
+        // The relay released the receiver, so the live loop can read again.
+        mock.tx_event
+            .send(EngineEvent::Status {
+                message: "after".to_string(),
+            })
+            .await
+            .unwrap();
+        let mut rx = mock.handle.rx_event.write().await;
+        assert!(matches!(rx.try_recv(), Ok(EngineEvent::Status { .. })));
+    }
+
+    #[tokio::test]
+    async fn relay_merges_streaming_deltas() {
+        let mock = mock_engine_handle();
+        let relay = EventRelay::spawn(&mock.handle);
+        for (index, content) in [(0, "hel"), (0, "lo"), (1, "next")] {
+            mock.tx_event
+                .send(EngineEvent::MessageDelta {
+                    index,
+                    content: content.to_string(),
+                })
+                .await
+                .unwrap();
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        let buffered = relay.finish().await;
+        assert_eq!(buffered.len(), 2);
+        assert!(matches!(
+            &buffered[0],
+            EngineEvent::MessageDelta { index: 0, content } if content == "hello"
+        ));
+    }
+
+    #[tokio::test]
+    async fn parked_tab_badge_appears_without_switching_tabs() {
+        let mock = mock_engine_handle();
+        let relay = EventRelay::spawn(&mock.handle);
+        let attention = TabAttention(vec![Some(relay.attention.clone()), None]);
+        let mut labels = vec![TabLabel::default(), TabLabel::default()];
+        assert!(!attention.refresh(&mut labels));
+
+        mock.tx_event
+            .send(EngineEvent::TurnComplete {
+                usage: crate::models::Usage::default(),
+                status: crate::core::events::TurnOutcomeStatus::Completed,
+                error: None,
+            })
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        assert!(attention.refresh(&mut labels));
+        assert!(labels[0].needs_attention);
+        assert!(!labels[1].needs_attention);
+        assert!(!attention.refresh(&mut labels));
+        let _ = relay.finish().await;
+    }
+
+    #[test]
+    fn attention_events_are_the_ones_that_block_or_finish_a_turn() {
+        assert!(!event_needs_attention(&EngineEvent::Status {
+            message: String::new(),
+        }));
+        assert!(!event_needs_attention(&EngineEvent::TurnStarted {
+            turn_id: String::new(),
+        }));
+    }
+}
@@ -1,10 +1,16 @@
-//! Background shell job-center helpers for slash commands and pagers.
+//! Background shell job-center helpers for slash commands and pagers, plus
+//! the transcript streaming for persistent `/shells` sessions.
 
-use crate::tools::shell::{ShellJobDetail, ShellJobSnapshot, ShellResult, ShellStatus};
+use crate::tools::shell::{
+    ShellJobDetail, ShellJobSnapshot, ShellResult, ShellSessionOutput, ShellStatus,
+};
 use crate::tui::app::App;
-use crate::tui::history::HistoryCell;
+use crate::tui::history::{ExecCell, ExecSource, HistoryCell, ToolCell, ToolStatus};
 use crate::tui::pager::PagerView;
 
+/// Lines of session output kept in a streaming transcript cell.
+const SESSION_CELL_MAX_LINES: usize = 400;
+
 fn status_label(status: &ShellStatus, stale: bool) -> &'static str {
     if stale {
         return "stale";
@@ -150,6 +156,133 @@ pub(super) fn add_shell_job_message(app: &mut App, content: String) {
     app.add_message(HistoryCell::System { content });
 }
 
+pub(super) fn format_shell_session_list(
+    sessions: &[ShellJobSnapshot],
+    attached: Option<&str>,
+) -> String {
+    if sessions.is_empty() {
+        return "No persistent shell sessions. Start one with /shells open <name> [command]."
+            .to_string();
+    }
+
+    let mut lines = vec![
+        format!("Shell sessions ({})", sessions.len()),
+        "----------------------------------------".to_string(),
+    ];
+    for session in sessions {
+        let name = session.name.as_deref().unwrap_or(&session.id);
+        let marker = if attached == Some(name) {
+            " (attached)"
+        } else {
+            ""
+        };
+        lines.push(format!(
+            "{name}{marker}  {}  {:8}  {}  exit={:?}",
+            session.id,
+            status_label(&session.status, session.stale),
+            format_elapsed(session.elapsed_ms),
+            session.exit_code,
+        ));
+        lines.push(format!("  cmd: {}", session.command));
+    }
+    lines.push(
+        "Controls: /shells attach <name>, /shells detach, /shells show <name>, /shells kill <name>."
+            .to_string(),
+    );
+    lines.join("\n")
+}
+
+/// Fold raw PTY output into transcript text: CRLF becomes LF, and a bare CR
+/// (progress bars, prompts redrawing) keeps only what was written after it.
+fn normalize_pty_output(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .split('\n')
+        .map(|line| line.rsplit('\r').next().unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn append_session_output(cell_output: &mut Option<String>, chunk: &str) {
+    let mut combined = cell_output.take().unwrap_or_default();
+    combined.push_str(&normalize_pty_output(chunk));
+    let line_count = combined.lines().count();
+    if line_count > SESSION_CELL_MAX_LINES {
+        combined = combined
+            .lines()
+            .skip(line_count - SESSION_CELL_MAX_LINES)
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+    *cell_output = Some(combined);
+}
+
+fn apply_session_update(app: &mut App, update: ShellSessionOutput) {
+    let finished = match update.status {
+        ShellStatus::Running => None,
+        ShellStatus::Completed if update.exit_code.unwrap_or(0) == 0 => Some(ToolStatus::Success),
+        _ => Some(ToolStatus::Failed),
+    };
+
+    let existing = app.shell_card_index.get(&update.id).copied().filter(|idx| {
+        matches!(
+            app.history.get(*idx),
+            Some(HistoryCell::Tool(ToolCell::Exec(cell)))
+                if cell.session.as_deref() == Some(update.name.as_str())
+        )
+    });
+    match existing {
+        Some(idx) => {
+            if let Some(HistoryCell::Tool(ToolCell::Exec(cell))) = app.history.get_mut(idx) {
+                append_session_output(&mut cell.output, &update.output);
+                if let Some(status) = finished {
+                    cell.status = status;
+                    cell.duration_ms = Some(update.elapsed_ms);
+                }
+            }
+            app.bump_history_cell(idx);
+        }
+        None => {
+            let mut output = None;
+            append_session_output(&mut output, &update.output);
+            app.add_message(HistoryCell::Tool(ToolCell::Exec(ExecCell {
+                command: update.command.clone(),
+                status: finished.unwrap_or(ToolStatus::Running),
+                output,
+                started_at: None,
+                duration_ms: finished.map(|_| update.elapsed_ms),
+                source: ExecSource::Assistant,
+                interaction: None,
+                session: Some(update.name.clone()),
+                output_summary: None,
+            })));
+            app.shell_card_index
+                .insert(update.id.clone(), app.history.len().saturating_sub(1));
+        }
+    }
+
+    if finished.is_some() && app.attached_shell.as_deref() == Some(update.name.as_str()) {
+        app.attached_shell = None;
+        app.status_message = Some(format!("Shell session `{}` exited; detached", update.name));
+    }
+}
+
+/// Stream new output from persistent shell sessions into their transcript
+/// cells. Returns true when anything changed.
+pub(super) fn stream_shell_sessions(app: &mut App) -> bool {
+    let Some(shell_manager) = app.runtime_services.shell_manager.clone() else {
+        return false;
+    };
+    let updates = match shell_manager.lock() {
+        Ok(mut manager) => manager.take_session_output(),
+        Err(_) => return false,
+    };
+    let changed = !updates.is_empty();
+    for update in updates {
+        apply_session_update(app, update);
+    }
+    changed
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,6 +305,7 @@ mod tests {
             stdin_available: false,
             stale: true,
             linked_task_id: Some("task_1".to_string()),
+            name: None,
         }];
         let formatted = format_shell_job_list(&jobs);
         assert!(formatted.contains("shell_dead"));
@@ -179,4 +313,10 @@ mod tests {
         assert!(formatted.contains("/jobs poll <id>"));
         assert!(formatted.contains("task=task_1"));
     }
+
+    #[test]
+    fn pty_output_folds_carriage_returns() {
+        assert_eq!(normalize_pty_output("a\r\nb\r\n"), "a\nb\n");
+        assert_eq!(normalize_pty_output("10%\r50%\r100%\ndone"), "100%\ndone");
+    }
 }
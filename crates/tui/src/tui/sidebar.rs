@@ -1,4 +1,4 @@
-//! Sidebar rendering — Work / Tasks / Agents / Context panels.
+//! Sidebar rendering — Work / Tasks / Agents / Context / Files panels.
 //!
 //! Extracted from `tui/ui.rs` (P1.2). The sidebar appears to the right of
 //! the chat transcript when the available width allows it. Each section
@@ -51,6 +51,7 @@ pub fn render_sidebar(f: &mut Frame, area: Rect, app: &App) {
         SidebarFocus::Tasks => render_sidebar_tasks(f, area, app),
         SidebarFocus::Agents => render_sidebar_subagents(f, area, app),
         SidebarFocus::Context => render_context_panel(f, area, app),
+        SidebarFocus::Files => render_sidebar_files(f, area, app),
         SidebarFocus::Hidden => Block::default()
             .style(Style::default().bg(app.ui_theme.surface_bg))
             .render(area, f.buffer_mut()),
@@ -1606,6 +1607,29 @@ fn agent_status_marker(status: &str) -> (&'static str, ratatui::style::Color) {
 /// cost, MCP server count, LSP toggle state, cycle count, and memory
 /// file size + mtime. Each section is a compact one-liner so the panel
 /// reads as a dashboard rather than a scrolling list.
+/// Workspace tree for the `Files` focus. The main loop builds and polls
+/// `app.sidebar_file_tree` before each draw.
+fn render_sidebar_files(f: &mut Frame, area: Rect, app: &App) {
+    let title = if app.sidebar_tree_focused {
+        " Files \u{00b7} Esc to leave "
+    } else {
+        " Files "
+    };
+    match app.sidebar_file_tree.as_ref() {
+        Some(tree) => super::file_tree::draw_file_tree(
+            f,
+            area,
+            tree,
+            app.ui_theme.mode,
+            title,
+            app.sidebar_tree_focused,
+        ),
+        None => Block::default()
+            .style(Style::default().bg(app.ui_theme.surface_bg))
+            .render(area, f.buffer_mut()),
+    }
+}
+
 fn render_context_panel(f: &mut Frame, area: Rect, app: &App) {
     if area.height < 3 {
         return;
@@ -2153,6 +2177,7 @@ mod tests {
                     duration_ms: Some(ACTIVE_TOOL_STALE_RUNNING_ROW_TTL.as_millis() as u64 + 1),
                     source: ExecSource::Assistant,
                     interaction: None,
+                    session: None,
                     output_summary: None,
                 })),
             );
@@ -2185,6 +2210,7 @@ mod tests {
                 duration_ms: None,
                 source: ExecSource::Assistant,
                 interaction: None,
+                session: None,
                 output_summary: None,
             })),
         );
@@ -2295,6 +2321,7 @@ mod tests {
                 duration_ms: Some(15_000),
                 source: ExecSource::Assistant,
                 interaction: None,
+                session: None,
                 output_summary: Some("2 checks pending".to_string()),
             })));
         }
@@ -2335,6 +2362,7 @@ mod tests {
             duration_ms: Some(1_250),
             source: ExecSource::Assistant,
             interaction: None,
+            session: None,
             output_summary: Some("test failed".to_string()),
         })));
 
@@ -2364,6 +2392,7 @@ mod tests {
             duration_ms: Some(1_250),
             source: ExecSource::Assistant,
             interaction: None,
+            session: None,
             output_summary: None,
         })));
 
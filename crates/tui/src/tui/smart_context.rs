@@ -0,0 +1,482 @@
+//! Automatic per-prompt context selection ("smart context").
+//!
+//! When enabled (`/smartcontext on` or `[smart_context] enabled = true`),
+//! every submitted prompt runs a cheap retrieval pass before it is sent:
+//! workspace files are scored against the prompt's terms and the best few
+//! are inlined as snippets, the same way `@mentions` inline whole files.
+//!
+//! Scoring combines three signals:
+//!
+//! 1. **Path match** — a prompt term equals a file stem (`parser` →
+//!    `src/parser.rs`) or appears in one of its path components.
+//! 2. **Symbol index** — a prompt term names a definition (`fn`, `struct`,
+//!    `class`, `def`, …) found by a line-oriented scan. The index is cached
+//!    per file and refreshed when the file's mtime changes.
+//! 3. **Edit recency** — files modified in the last hour or day get a
+//!    boost, but recency alone never pulls a file in.
+//!
+//! Everything is bounded (files walked, bytes read, snippet size) so the
+//! pass stays well under the latency of the request it precedes.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+
+use ignore::WalkBuilder;
+use regex::Regex;
+
+use crate::tui::file_mention::{ContextReference, ContextReferenceKind, ContextReferenceSource};
+
+/// Files auto-included per prompt when the config does not say otherwise.
+pub const DEFAULT_SMART_CONTEXT_FILES: usize = 3;
+/// Hard ceiling on `[smart_context] max_files`.
+pub const MAX_SMART_CONTEXT_FILES: usize = 8;
+
+/// Workspace files considered per prompt. Large monorepos are truncated in
+/// walk order rather than stalling the send.
+const MAX_SCANNED_FILES: usize = 4_000;
+const MAX_WALK_DEPTH: usize = 12;
+/// Files larger than this are matched by path only.
+const MAX_INDEXED_FILE_BYTES: u64 = 256 * 1024;
+const SNIPPET_LINES: usize = 40;
+/// Lines of lead-in kept above a matched definition.
+const SNIPPET_LEAD_LINES: usize = 3;
+const MAX_SNIPPET_CHARS: usize = 4_000;
+const MAX_PROMPT_TERMS: usize = 24;
+const MIN_TERM_CHARS: usize = 3;
+
+const STEM_MATCH_SCORE: u32 = 6;
+const PATH_MATCH_SCORE: u32 = 2;
+const SYMBOL_MATCH_SCORE: u32 = 8;
+const PARTIAL_SYMBOL_SCORE: u32 = 2;
+const RECENT_HOUR_SCORE: u32 = 3;
+const RECENT_DAY_SCORE: u32 = 1;
+
+/// Extensions whose contents feed the symbol index.
+const INDEXED_EXTENSIONS: &[&str] = &[
+    "rs", "py", "js", "jsx", "ts", "tsx", "go", "java", "kt", "swift", "rb", "c", "h", "cc", "cpp",
+    "hpp", "cs", "php", "scala", "lua", "sh", "toml", "md",
+];
+
+/// Prompt words too common to say anything about which file is meant.
+const STOPWORDS: &[&str] = &[
+    "about", "add", "all", "and", "any", "are", "but", "can", "code", "could", "does", "file",
+    "files", "fix", "for", "from", "get", "has", "have", "how", "into", "its", "make", "new",
+    "not", "now", "one", "out", "please", "set", "should", "that", "the", "then", "there", "this",
+    "use", "was", "what", "when", "where", "which", "why", "will", "with", "would", "you", "your",
+];
+
+/// One file chosen for auto-inclusion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmartContextFile {
+    /// Workspace-relative path with `/` separators.
+    pub path: String,
+    pub score: u32,
+    /// Human-readable signals that selected the file, strongest first.
+    pub reasons: Vec<String>,
+    /// 1-based inclusive line range of `snippet`.
+    pub start_line: usize,
+    pub end_line: usize,
+    pub snippet: String,
+}
+
+impl SmartContextFile {
+    fn line_range(&self) -> String {
+        format!("{}-{}", self.start_line, self.end_line)
+    }
+}
+
+/// A scored file before its snippet is read.
+struct Candidate {
+    score: u32,
+    reasons: Vec<String>,
+    path: PathBuf,
+    relative: String,
+    /// 1-based line of the best matching definition.
+    anchor: Option<usize>,
+}
+
+/// Score workspace files against `prompt` and return up to `max_files`
+/// snippets, best first. Paths in `exclude` (already attached via
+/// `@mentions`) are skipped.
+#[must_use]
+pub fn select_context_files(
+    prompt: &str,
+    workspace: &Path,
+    max_files: usize,
+    exclude: &HashSet<PathBuf>,
+) -> Vec<SmartContextFile> {
+    let terms = prompt_terms(prompt);
+    if terms.is_empty() || max_files == 0 {
+        return Vec::new();
+    }
+    let now = SystemTime::now();
+
+    let mut candidates = Vec::new();
+    let walker = WalkBuilder::new(workspace)
+        .hidden(true)
+        .max_depth(Some(MAX_WALK_DEPTH))
+        .build();
+    for entry in walker.flatten().take(MAX_SCANNED_FILES) {
+        if !entry.file_type().is_some_and(|kind| kind.is_file()) {
+            continue;
+        }
+        let path = entry.path();
+        if exclude.contains(path) {
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(workspace) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        let metadata = entry.metadata().ok();
+
+        let (mut score, mut reasons) = path_score(&relative, &terms);
+        let mut anchor = None;
+        if let Some(metadata) = metadata.as_ref()
+            && metadata.len() <= MAX_INDEXED_FILE_BYTES
+            && is_indexed(path)
+        {
+            let modified = metadata.modified().ok();
+            let (symbol_score, symbol_reasons, symbol_line) =
+                symbol_score(&cached_symbols(path, modified), &terms);
+            score += symbol_score;
+            reasons.extend(symbol_reasons);
+            anchor = symbol_line;
+        }
+        if score == 0 {
+            continue;
+        }
+        if let Some(age) = metadata
+            .and_then(|metadata| metadata.modified().ok())
+            .and_then(|modified| now.duration_since(modified).ok())
+        {
+            if age <= Duration::from_secs(60 * 60) {
+                score += RECENT_HOUR_SCORE;
+                reasons.push("edited in the last hour".to_string());
+            } else if age <= Duration::from_secs(24 * 60 * 60) {
+                score += RECENT_DAY_SCORE;
+                reasons.push("edited today".to_string());
+            }
+        }
+        candidates.push(Candidate {
+            score,
+            reasons,
+            path: path.to_path_buf(),
+            relative,
+            anchor,
+        });
+    }
+
+    candidates.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.relative.cmp(&b.relative))
+    });
+    candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let (start_line, end_line, snippet) = read_snippet(&candidate.path, candidate.anchor)?;
+            Some(SmartContextFile {
+                path: candidate.relative,
+                score: candidate.score,
+                reasons: candidate.reasons,
+                start_line,
+                end_line,
+                snippet,
+            })
+        })
+        .take(max_files.min(MAX_SMART_CONTEXT_FILES))
+        .collect()
+}
+
+/// Context block appended to the outgoing user message.
+#[must_use]
+pub fn render_context_block(files: &[SmartContextFile]) -> String {
+    let mut out = String::from("\n\n---\n\nAuto-included context (smart context):");
+    for file in files {
+        let _ = write!(
+            out,
+            "\n\n<file path=\"{}\" lines=\"{}\" reason=\"{}\">\n{}\n</file>",
+            file.path,
+            file.line_range(),
+            file.reasons.join("; "),
+            file.snippet
+        );
+    }
+    out
+}
+
+/// Transcript note listing exactly what was auto-included.
+#[must_use]
+pub fn transcript_note(files: &[SmartContextFile]) -> String {
+    let mut out = format!(
+        "Smart context: auto-included {} file{}",
+        files.len(),
+        if files.len() == 1 { "" } else { "s" }
+    );
+    for file in files {
+        let _ = write!(
+            out,
+            "\n  {}:{} — {}",
+            file.path,
+            file.line_range(),
+            file.reasons.join(", ")
+        );
+    }
+    out
+}
+
+/// Context-inspector records for the auto-included files.
+#[must_use]
+pub fn context_references(files: &[SmartContextFile], workspace: &Path) -> Vec<ContextReference> {
+    files
+        .iter()
+        .map(|file| ContextReference {
+            kind: ContextReferenceKind::File,
+            source: ContextReferenceSource::SmartContext,
+            badge: "auto".to_string(),
+            label: file.path.clone(),
+            target: workspace.join(&file.path).display().to_string(),
+            included: true,
+            expanded: true,
+            detail: Some(format!("lines {}", file.line_range())),
+        })
+        .collect()
+}
+
+/// Lowercased, de-duplicated search terms from the prompt. `@mentions` and
+/// slash-prefixed tokens are skipped: the former are attached already and
+/// the latter are commands, not topics.
+fn prompt_terms(prompt: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for word in prompt.split_whitespace() {
+        if word.starts_with('@') || word.starts_with('/') {
+            continue;
+        }
+        for token in word.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+            let token = token.trim_matches('_').to_lowercase();
+            if token.chars().count() < MIN_TERM_CHARS
+                || token.chars().all(|c| c.is_ascii_digit())
+                || STOPWORDS.contains(&token.as_str())
+                || terms.contains(&token)
+            {
+                continue;
+            }
+            terms.push(token);
+            if terms.len() == MAX_PROMPT_TERMS {
+                return terms;
+            }
+        }
+    }
+    terms
+}
+
+fn path_score(relative: &str, terms: &[String]) -> (u32, Vec<String>) {
+    let lower = relative.to_lowercase();
+    let file_name = lower.rsplit('/').next().unwrap_or(&lower);
+    let stem = file_name.split('.').next().unwrap_or(file_name);
+    let mut score = 0;
+    let mut matched = Vec::new();
+    for term in terms {
+        if stem == term {
+            score += STEM_MATCH_SCORE;
+            matched.push(term.as_str());
+        } else if lower
+            .split('/')
+            .any(|component| component.contains(term.as_str()))
+        {
+            score += PATH_MATCH_SCORE;
+            matched.push(term.as_str());
+        }
+    }
+    let reasons = if matched.is_empty() {
+        Vec::new()
+    } else {
+        vec![format!("path matches {}", matched.join(", "))]
+    };
+    (score, reasons)
+}
+
+/// Returns the symbol score, its reasons, and the 1-based line of the
+/// strongest matching definition.
+fn symbol_score(
+    symbols: &[(String, usize)],
+    terms: &[String],
+) -> (u32, Vec<String>, Option<usize>) {
+    let mut score = 0;
+    let mut exact: Vec<&str> = Vec::new();
+    let mut anchor = None;
+    let mut partial_anchor = None;
+    for (name, line) in symbols {
+        let lower = name.to_lowercase();
+        if terms.contains(&lower) {
+            if !exact.contains(&name.as_str()) {
+                score += SYMBOL_MATCH_SCORE;
+                exact.push(name);
+            }
+            anchor.get_or_insert(*line);
+        } else if partial_anchor.is_none()
+            && terms
+                .iter()
+                .any(|term| term.len() > MIN_TERM_CHARS && lower.contains(term.as_str()))
+        {
+            score += PARTIAL_SYMBOL_SCORE;
+            partial_anchor = Some(*line);
+        }
+    }
+    let mut reasons = Vec::new();
+    if !exact.is_empty() {
+        reasons.push(format!("defines {}", exact.join(", ")));
+    } else if partial_anchor.is_some() {
+        reasons.push("related symbol".to_string());
+    }
+    (score, reasons, anchor.or(partial_anchor))
+}
+
+fn is_indexed(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| INDEXED_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+type SymbolCache = HashMap<PathBuf, (Option<SystemTime>, Vec<(String, usize)>)>;
+
+/// Definitions in `path` as `(name, line)`, cached until the mtime changes.
+fn cached_symbols(path: &Path, modified: Option<SystemTime>) -> Vec<(String, usize)> {
+    static CACHE: OnceLock<Mutex<SymbolCache>> = OnceLock::new();
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(guard) = cache.lock()
+        && let Some((cached_at, symbols)) = guard.get(path)
+        && modified.is_some()
+        && *cached_at == modified
+    {
+        return symbols.clone();
+    }
+    let symbols = std::fs::read_to_string(path)
+        .map(|text| extract_symbols(&text))
+        .unwrap_or_default();
+    if let Ok(mut guard) = cache.lock() {
+        guard.insert(path.to_path_buf(), (modified, symbols.clone()));
+    }
+    symbols
+}
+
+fn extract_symbols(text: &str) -> Vec<(String, usize)> {
+    static DEFINITION: OnceLock<Regex> = OnceLock::new();
+    let definition = DEFINITION.get_or_init(|| {
+        Regex::new(
+            r"^\s*(?:(?:pub(?:\([^)]*\))?|export|default|async|unsafe|static|public|private|protected|abstract|final)\s+)*(?:fn|struct|enum|trait|mod|type|const|class|def|function|interface|func|impl(?:<[^>]*>)?)\s+([A-Za-z_][A-Za-z0-9_]*)",
+        )
+        .expect("definition regex")
+    });
+    text.lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            definition
+                .captures(line)
+                .and_then(|caps| caps.get(1))
+                .map(|name| (name.as_str().to_string(), idx + 1))
+        })
+        .collect()
+}
+
+/// Read a snippet around `anchor` (1-based), or the top of the file.
+/// Returns `None` for unreadable or non-UTF-8 files.
+fn read_snippet(path: &Path, anchor: Option<usize>) -> Option<(usize, usize, String)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if metadata.len() > MAX_INDEXED_FILE_BYTES * 4 {
+        return None;
+    }
+    let text = std::fs::read_to_string(path).ok()?;
+    let lines: Vec<&str> = text.lines().collect();
+    if lines.is_empty() {
+        return None;
+    }
+    let start = anchor
+        .map(|line| line.saturating_sub(SNIPPET_LEAD_LINES + 1))
+        .unwrap_or(0)
+        .min(lines.len() - 1);
+    let mut snippet = String::new();
+    let mut end = start;
+    for line in &lines[start..lines.len().min(start + SNIPPET_LINES)] {
+        if snippet.len() + line.len() + 1 > MAX_SNIPPET_CHARS && end > start {
+            break;
+        }
+        if end > start {
+            snippet.push('\n');
+        }
+        snippet.push_str(line);
+        end += 1;
+    }
+    Some((start + 1, end, snippet))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn prompt_terms_skip_stopwords_mentions_and_short_words() {
+        assert_eq!(
+            prompt_terms("Fix the parse_config bug in @src/lib.rs for v2 /model 2024"),
+            vec!["parse_config".to_string(), "bug".to_string()]
+        );
+    }
+
+    #[test]
+    fn selects_files_by_symbol_and_path_and_skips_excluded() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let root = tmp.path();
+        fs::create_dir_all(root.join("src")).expect("mkdir");
+        let mut config = (1..=10)
+            .map(|n| format!("// filler {n}"))
+            .collect::<Vec<_>>();
+        config.push("pub fn parse_config(raw: &str) -> Config {".to_string());
+        config.push("    todo!()".to_string());
+        config.push("}".to_string());
+        fs::write(root.join("src/config.rs"), config.join("\n")).expect("write");
+        fs::write(root.join("src/parser.rs"), "pub struct Parser;\n").expect("write");
+        fs::write(root.join("src/unrelated.rs"), "pub fn other() {}\n").expect("write");
+
+        let files = select_context_files(
+            "Why does parse_config reject the parser output?",
+            root,
+            3,
+            &HashSet::new(),
+        );
+        let paths: Vec<&str> = files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/parser.rs", "src/config.rs"]);
+        let config = &files[1];
+        assert_eq!(config.start_line, 8);
+        assert!(config.snippet.contains("pub fn parse_config"));
+        assert!(config.reasons.iter().any(|r| r == "defines parse_config"));
+
+        let exclude = HashSet::from([root.join("src/config.rs")]);
+        let files = select_context_files("parse_config", root, 3, &exclude);
+        assert!(files.is_empty());
+    }
+
+    #[test]
+    fn render_and_note_list_the_same_files() {
+        let file = SmartContextFile {
+            path: "src/config.rs".to_string(),
+            score: 9,
+            reasons: vec!["defines parse_config".to_string()],
+            start_line: 8,
+            end_line: 13,
+            snippet: "pub fn parse_config() {}".to_string(),
+        };
+        let block = render_context_block(std::slice::from_ref(&file));
+        assert!(block.contains(
+            "<file path=\"src/config.rs\" lines=\"8-13\" reason=\"defines parse_config\">"
+        ));
+        assert_eq!(
+            transcript_note(&[file]),
+            "Smart context: auto-included 1 file\n  src/config.rs:8-13 — defines parse_config"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Startup work deferred until after the first frame.
+//!
+//! The TUI paints as soon as the terminal is ready; the skills scan, the
+//! saved-session index, and MCP server connections run in the background
+//! and a header chip lists whatever is still loading. Skills and sessions
+//! are file scans on the blocking pool whose results come back over a
+//! channel; MCP warms the engine's own server pool (`Op::WarmMcp`) so the
+//! first turn does not pay for the connections, and reports back through
+//! `Event::McpWarmed`.
+//!
+//! The first tab also applies `[sessions]` retention before listing the
+//! index, and a toast reports what it removed.
+
+use tokio::sync::mpsc;
+
+use crate::core::engine::EngineHandle;
+use crate::core::ops::Op;
+use crate::session_manager::{GcPlan, SessionManager, SessionMetadata, SessionRetention};
+use crate::tui::app::{App, StatusToastLevel};
+
+/// One piece of deferred startup work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartupJob {
+    Skills,
+    Mcp,
+    Sessions,
+}
+
+impl StartupJob {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Skills => "skills",
+            Self::Mcp => "MCP",
+            Self::Sessions => "sessions",
+        }
+    }
+}
+
+/// A finished file scan.
+#[derive(Debug)]
+enum StartupResult {
+    Skills(Vec<(String, String)>),
+    Sessions(Vec<SessionMetadata>, Option<GcPlan>),
+}
+
+/// Jobs still loading, owned by [`App`].
+#[derive(Debug, Default)]
+pub struct StartupProgress {
+    pending: Vec<StartupJob>,
+    rx: Option<mpsc::UnboundedReceiver<StartupResult>>,
+}
+
+impl StartupProgress {
+    /// Header chip text while anything is still loading.
+    #[must_use]
+    pub fn header_label(&self) -> Option<String> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        let jobs: Vec<&str> = self.pending.iter().map(|job| job.label()).collect();
+        Some(format!("loading {}…", jobs.join(", ")))
+    }
+
+    fn finish(&mut self, job: StartupJob) {
+        self.pending.retain(|pending| *pending != job);
+    }
+}
+
+/// Kick off the deferred startup jobs for `app`. With `retention`, session
+/// GC runs before the index is listed; the open session is never collected.
+pub async fn start(app: &mut App, engine: &EngineHandle, retention: Option<SessionRetention>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let mut pending = vec![StartupJob::Skills, StartupJob::Sessions];
+
+    let workspace = app.workspace.clone();
+    let skills_tx = tx.clone();
+    crate::utils::spawn_blocking_supervised("startup-skills", move || {
+        let _ = skills_tx.send(StartupResult::Skills(App::discover_cached_skills(
+            &workspace,
+        )));
+    });
+    let open_session = app.current_session_id.clone();
+    crate::utils::spawn_blocking_supervised("startup-sessions", move || {
+        let Ok(manager) = SessionManager::default_location() else {
+            let _ = tx.send(StartupResult::Sessions(Vec::new(), None));
+            return;
+        };
+        let gc = retention.and_then(|retention| {
+            let keep: Vec<&str> = open_session.as_deref().into_iter().collect();
+            let plan = manager.plan_gc(&retention, &keep).ok()?;
+            (!plan.remove.is_empty()).then(|| {
+                manager.apply_gc(&plan);
+                plan
+            })
+        });
+        let sessions = manager.list_sessions().unwrap_or_default();
+        let _ = tx.send(StartupResult::Sessions(sessions, gc));
+    });
+
+    if app.mcp_configured_count > 0 && engine.send(Op::WarmMcp).await.is_ok() {
+        pending.push(StartupJob::Mcp);
+    }
+
+    app.startup = StartupProgress {
+        pending,
+        rx: Some(rx),
+    };
+    app.needs_redraw = true;
+}
+
+/// Apply finished scans. Returns true when anything changed.
+pub fn poll(app: &mut App) -> bool {
+    let mut changed = false;
+    while let Some(result) = app.startup.rx.as_mut().and_then(|rx| rx.try_recv().ok()) {
+        match result {
+            StartupResult::Skills(skills) => {
+                app.cached_skills = skills;
+                app.startup.finish(StartupJob::Skills);
+            }
+            StartupResult::Sessions(sessions, gc) => {
+                app.session_index = Some(sessions);
+                app.startup.finish(StartupJob::Sessions);
+                if let Some(plan) = gc {
+                    app.push_status_toast(
+                        format!("Session GC: {}", plan.summary("removed")),
+                        StatusToastLevel::Info,
+                        Some(4_000),
+                    );
+                }
+            }
+        }
+        changed = true;
+    }
+    changed
+}
+
+/// The engine finished connecting MCP servers.
+pub fn mcp_warmed(app: &mut App, connected: usize, failures: &[String]) {
+    app.startup.finish(StartupJob::Mcp);
+    if failures.is_empty() {
+        tracing::debug!("MCP warm-up connected {connected} server(s)");
+    } else {
+        app.status_message = Some(format!(
+            "MCP: {connected} connected, {} failed ({})",
+            failures.len(),
+            failures.join("; ")
+        ));
+    }
+    app.needs_redraw = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_label_lists_jobs_until_they_finish() {
+        let mut progress = StartupProgress {
+            pending: vec![StartupJob::Skills, StartupJob::Mcp],
+            rx: None,
+        };
+        assert_eq!(
+            progress.header_label().as_deref(),
+            Some("loading skills, MCP…")
+        );
+        progress.finish(StartupJob::Skills);
+        assert_eq!(progress.header_label().as_deref(), Some("loading MCP…"));
+        progress.finish(StartupJob::Mcp);
+        assert_eq!(progress.header_label(), None);
+    }
+}
@@ -2,7 +2,7 @@
 
 use std::time::Instant;
 
-use crate::task_manager::{TaskRecord, TaskStatus, TaskSummary};
+use crate::task_manager::{TaskRecord, TaskSummary};
 use crate::tools::subagent::{MailboxMessage, SubAgentResult, SubAgentStatus};
 use crate::tui::app::{App, AppMode, TaskPanelEntry};
 use crate::tui::history::{HistoryCell, SubAgentCell, summarize_tool_output};
@@ -181,22 +181,12 @@ pub(super) fn task_mode_label(mode: AppMode) -> &'static str {
 pub(super) fn task_summary_to_panel_entry(summary: TaskSummary) -> TaskPanelEntry {
     TaskPanelEntry {
         id: summary.id,
-        status: task_status_label(summary.status).to_string(),
+        status: summary.status.label().to_string(),
         prompt_summary: summary.prompt_summary,
         duration_ms: summary.duration_ms,
     }
 }
 
-fn task_status_label(status: TaskStatus) -> &'static str {
-    match status {
-        TaskStatus::Queued => "queued",
-        TaskStatus::Running => "running",
-        TaskStatus::Completed => "completed",
-        TaskStatus::Failed => "failed",
-        TaskStatus::Canceled => "canceled",
-    }
-}
-
 pub(super) fn format_task_list(tasks: &[TaskSummary]) -> String {
     if tasks.is_empty() {
         return "No tasks found.".to_string();
@@ -215,7 +205,7 @@ pub(super) fn format_task_list(tasks: &[TaskSummary]) -> String {
         lines.push(format!(
             "{:<13}  {:<9}  {:>8}  {}",
             task.id,
-            task_status_label(task.status),
+            task.status.label(),
             duration,
             task.prompt_summary
         ));
@@ -238,10 +228,48 @@ pub(super) fn open_task_pager(app: &mut App, task: &TaskRecord) {
     ));
 }
 
+pub(super) fn open_task_transcript_pager(app: &mut App, id: &str, transcript: &str) {
+    let width = app
+        .viewport
+        .last_transcript_area
+        .map(|area| area.width)
+        .unwrap_or(100)
+        .saturating_sub(4);
+    let body = if transcript.trim().is_empty() {
+        "(no output yet)"
+    } else {
+        transcript.trim()
+    };
+    app.view_stack.push(PagerView::from_text(
+        format!("Task {id} transcript"),
+        body,
+        width.max(60),
+    ));
+}
+
+/// Transcript cell for a pinned task that has finished.
+pub(super) fn format_pinned_task_output(task: &TaskRecord, output: Option<&str>) -> String {
+    let mut text = format!(
+        "Task {} {}: {}",
+        task.id,
+        task.status.label(),
+        task.prompt.lines().next().unwrap_or_default().trim()
+    );
+    let body = output
+        .map(str::trim)
+        .filter(|output| !output.is_empty())
+        .or(task.error.as_deref());
+    if let Some(body) = body {
+        text.push_str("\n\n");
+        text.push_str(body);
+    }
+    text
+}
+
 fn format_task_detail(task: &TaskRecord) -> String {
     let mut lines = Vec::new();
     lines.push(format!("Task: {}", task.id));
-    lines.push(format!("Status: {}", task_status_label(task.status)));
+    lines.push(format!("Status: {}", task.status.label()));
     lines.push(format!("Mode: {}", task.mode));
     lines.push(format!("Model: {}", task.model));
     lines.push(format!(
@@ -347,6 +375,9 @@ mod tests {
             prompt_summary: "Fix task list output".to_string(),
             model: "deepseek-v4-pro".to_string(),
             mode: "agent".to_string(),
+            priority: 0,
+            completion_pct: 0,
+            tool_call_count: 0,
             created_at: Utc::now(),
             started_at: None,
             ended_at: None,
@@ -368,4 +399,36 @@ mod tests {
         assert!(output.contains("task_12345678  running           -  Fix task list output"));
         assert!(output.contains("task_abcdef12  completed     1.23s  Fix task list output"));
     }
+
+    #[test]
+    fn pinned_output_falls_back_to_error_text() {
+        let mut task: TaskRecord = serde_json::from_value(serde_json::json!({
+            "id": "task_12345678",
+            "prompt": "Audit the parser\nwith details",
+            "model": "deepseek-v4-pro",
+            "workspace": ".",
+            "mode": "agent",
+            "allow_shell": false,
+            "trust_mode": false,
+            "status": "failed",
+            "created_at": Utc::now(),
+            "started_at": null,
+            "ended_at": null,
+            "duration_ms": null,
+            "error": "executor crashed",
+            "tool_calls": [],
+            "timeline": []
+        }))
+        .expect("task record");
+
+        assert_eq!(
+            format_pinned_task_output(&task, Some("  ")),
+            "Task task_12345678 failed: Audit the parser\n\nexecutor crashed"
+        );
+        task.error = None;
+        assert_eq!(
+            format_pinned_task_output(&task, Some("parser is fine\n")),
+            "Task task_12345678 failed: Audit the parser\n\nparser is fine"
+        );
+    }
 }
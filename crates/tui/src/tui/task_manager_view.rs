@@ -0,0 +1,399 @@
+//! `/task` manager modal: live list of background tasks with per-task
+//! actions.
+//!
+//! The view only holds a snapshot of [`TaskSummary`] rows. Every action is
+//! emitted as a [`TaskManagerAction`]; the host runs it against the shared
+//! `TaskManager` and pushes fresh rows back through [`TaskManagerView::set_tasks`]
+//! (also on the periodic task-panel refresh), so status, progress and
+//! priority stay live while the modal is open.
+
+use std::collections::BTreeSet;
+
+use chrono::Utc;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::palette;
+use crate::task_manager::{MAX_TASK_PRIORITY, MIN_TASK_PRIORITY, TaskStatus, TaskSummary};
+use crate::tui::views::{ModalKind, ModalView, TaskManagerAction, ViewAction, ViewEvent};
+use crate::utils::truncate_with_ellipsis;
+
+pub struct TaskManagerView {
+    tasks: Vec<TaskSummary>,
+    /// Task ids whose output is pinned into the conversation on finish.
+    pinned: BTreeSet<String>,
+    selected: usize,
+    notice: Option<String>,
+}
+
+impl TaskManagerView {
+    #[must_use]
+    pub fn new(tasks: Vec<TaskSummary>, pinned: BTreeSet<String>) -> Self {
+        Self {
+            tasks,
+            pinned,
+            selected: 0,
+            notice: None,
+        }
+    }
+
+    /// Replace the rows, keeping the cursor on the same task when it is
+    /// still listed.
+    pub fn set_tasks(&mut self, tasks: Vec<TaskSummary>, pinned: BTreeSet<String>) {
+        let selected_id = self.selected_task().map(|task| task.id.clone());
+        self.tasks = tasks;
+        self.pinned = pinned;
+        self.selected = selected_id
+            .and_then(|id| self.tasks.iter().position(|task| task.id == id))
+            .unwrap_or(self.selected)
+            .min(self.tasks.len().saturating_sub(1));
+    }
+
+    /// One-line result of the last action, shown above the key hints.
+    pub fn set_notice(&mut self, notice: impl Into<String>) {
+        self.notice = Some(notice.into());
+    }
+
+    fn selected_task(&self) -> Option<&TaskSummary> {
+        self.tasks.get(self.selected)
+    }
+
+    fn emit_for_selected(
+        &self,
+        build: impl FnOnce(&TaskSummary) -> Option<TaskManagerAction>,
+    ) -> ViewAction {
+        match self.selected_task().and_then(build) {
+            Some(action) => ViewAction::Emit(ViewEvent::TaskManagerRequested { action }),
+            None => ViewAction::None,
+        }
+    }
+
+    fn bump_priority(&self, delta: i32) -> ViewAction {
+        self.emit_for_selected(|task| {
+            let priority = (task.priority + delta).clamp(MIN_TASK_PRIORITY, MAX_TASK_PRIORITY);
+            (task.status == TaskStatus::Queued && priority != task.priority).then(|| {
+                TaskManagerAction::SetPriority {
+                    id: task.id.clone(),
+                    priority,
+                }
+            })
+        })
+    }
+
+    fn row(&self, idx: usize, task: &TaskSummary, width: usize) -> Line<'static> {
+        let is_selected = idx == self.selected;
+        let status_color = match task.status {
+            TaskStatus::Queued => palette::TEXT_MUTED,
+            TaskStatus::Running => palette::STATUS_WARNING,
+            TaskStatus::Completed => palette::STATUS_SUCCESS,
+            TaskStatus::Failed => palette::DEEPSEEK_RED,
+            TaskStatus::Canceled => palette::TEXT_DIM,
+        };
+        let base = if is_selected {
+            Style::default()
+                .fg(palette::SELECTION_TEXT)
+                .bg(palette::SELECTION_BG)
+        } else {
+            Style::default().fg(palette::TEXT_PRIMARY)
+        };
+        let status_style = if is_selected {
+            base.add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(status_color)
+        };
+
+        let pointer = if is_selected { ">" } else { " " };
+        let priority = match task.priority {
+            0 => "  ".to_string(),
+            p if p > 0 => format!("+{p}"),
+            p => p.to_string(),
+        };
+        let pin = if self.pinned.contains(&task.id) {
+            " pin"
+        } else {
+            ""
+        };
+        let meta = format!(
+            " {priority} {:>4} {:>3}t {:>6}{pin} ",
+            progress_label(task),
+            task.tool_call_count,
+            elapsed_label(task)
+        );
+        let lead = format!(" {pointer} {:<9} {:<13}", task.status.label(), task.id);
+        let used = lead.chars().count() + meta.chars().count();
+        let title = first_line_clipped(&task.prompt_summary, width.saturating_sub(used));
+
+        Line::from(vec![
+            Span::styled(lead, status_style),
+            Span::styled(meta, base.fg(palette::TEXT_MUTED)),
+            Span::styled(title, base),
+        ])
+    }
+}
+
+impl ModalView for TaskManagerView {
+    fn kind(&self) -> ModalKind {
+        ModalKind::TaskManager
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return ViewAction::None;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => ViewAction::Close,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                ViewAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected + 1 < self.tasks.len() {
+                    self.selected += 1;
+                }
+                ViewAction::None
+            }
+            KeyCode::Enter | KeyCode::Char('v') => self.emit_for_selected(|task| {
+                Some(TaskManagerAction::ViewTranscript {
+                    id: task.id.clone(),
+                })
+            }),
+            KeyCode::Char('c') | KeyCode::Char('x') => self.emit_for_selected(|task| {
+                matches!(task.status, TaskStatus::Queued | TaskStatus::Running).then(|| {
+                    TaskManagerAction::Cancel {
+                        id: task.id.clone(),
+                    }
+                })
+            }),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.bump_priority(1),
+            KeyCode::Char('-') => self.bump_priority(-1),
+            KeyCode::Char('p') => self.emit_for_selected(|task| {
+                Some(TaskManagerAction::TogglePin {
+                    id: task.id.clone(),
+                })
+            }),
+            KeyCode::Char('r') => ViewAction::Emit(ViewEvent::TaskManagerRequested {
+                action: TaskManagerAction::Refresh,
+            }),
+            _ => ViewAction::None,
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let popup_width = 96.min(area.width.saturating_sub(4));
+        let popup_height = 22.min(area.height.saturating_sub(4));
+        if popup_width == 0 || popup_height == 0 {
+            return;
+        }
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(popup_width)) / 2,
+            y: area.y + (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        Clear.render(popup_area, buf);
+
+        let hint = |key: &'static str, label: &'static str| {
+            [
+                Span::styled(key, Style::default().fg(palette::TEXT_MUTED)),
+                Span::raw(label),
+            ]
+        };
+        let block = Block::default()
+            .title(Line::from(Span::styled(
+                " Tasks ",
+                Style::default()
+                    .fg(palette::DEEPSEEK_SKY)
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .title_bottom(Line::from(
+                [
+                    hint(" Enter ", "transcript "),
+                    hint(" c ", "cancel "),
+                    hint(" +/- ", "priority "),
+                    hint(" p ", "pin output "),
+                    hint(" r ", "refresh "),
+                    hint(" Esc ", "close "),
+                ]
+                .concat(),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(palette::BORDER_COLOR))
+            .style(Style::default().bg(palette::DEEPSEEK_INK))
+            .padding(Padding::horizontal(1));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let width = usize::from(inner.width);
+        let mut lines = Vec::new();
+        if let Some(notice) = self.notice.as_deref() {
+            lines.push(Line::from(Span::styled(
+                first_line_clipped(notice, width),
+                Style::default().fg(palette::DEEPSEEK_SKY),
+            )));
+        }
+        if self.tasks.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No tasks yet. Queue one with /task add <prompt>.",
+                Style::default().fg(palette::TEXT_MUTED),
+            )));
+        } else {
+            // Keep the cursor row visible on short terminals.
+            let visible = usize::from(inner.height).saturating_sub(lines.len()).max(1);
+            let first = self.selected.saturating_sub(visible.saturating_sub(1));
+            for (idx, task) in self.tasks.iter().enumerate().skip(first).take(visible) {
+                lines.push(self.row(idx, task, width));
+            }
+        }
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+fn progress_label(task: &TaskSummary) -> String {
+    match task.status {
+        TaskStatus::Completed => "done".to_string(),
+        _ if task.completion_pct > 0 => format!("{}%", task.completion_pct),
+        _ => "-".to_string(),
+    }
+}
+
+fn elapsed_label(task: &TaskSummary) -> String {
+    let ms = task.duration_ms.or_else(|| {
+        task.started_at.map(|started| {
+            (Utc::now() - started)
+                .num_milliseconds()
+                .max(0)
+                .unsigned_abs()
+        })
+    });
+    match ms {
+        Some(ms) if ms >= 60_000 => format!("{}m{:02}s", ms / 60_000, (ms / 1000) % 60),
+        Some(ms) => format!("{:.1}s", ms as f64 / 1000.0),
+        None => "-".to_string(),
+    }
+}
+
+/// First line of `text`, clipped to `max_len` bytes.
+fn first_line_clipped(text: &str, max_len: usize) -> String {
+    let first_line = text.lines().next().unwrap_or_default();
+    truncate_with_ellipsis(first_line, max_len, "…")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(id: &str, status: TaskStatus, priority: i32) -> TaskSummary {
+        TaskSummary {
+            id: id.to_string(),
+            status,
+            prompt_summary: format!("prompt for {id}"),
+            model: "deepseek-v4-pro".to_string(),
+            mode: "agent".to_string(),
+            priority,
+            completion_pct: 0,
+            tool_call_count: 0,
+            created_at: Utc::now(),
+            started_at: None,
+            ended_at: None,
+            duration_ms: None,
+            error: None,
+            thread_id: None,
+            turn_id: None,
+        }
+    }
+
+    fn requested(action: ViewAction) -> Option<TaskManagerAction> {
+        match action {
+            ViewAction::Emit(ViewEvent::TaskManagerRequested { action }) => Some(action),
+            _ => None,
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn actions_target_the_selected_task() {
+        let mut view = TaskManagerView::new(
+            vec![
+                summary("task_run", TaskStatus::Running, 0),
+                summary("task_wait", TaskStatus::Queued, 0),
+            ],
+            BTreeSet::new(),
+        );
+
+        assert_eq!(
+            requested(view.handle_key(key(KeyCode::Enter))),
+            Some(TaskManagerAction::ViewTranscript {
+                id: "task_run".to_string()
+            })
+        );
+        // Running tasks cannot be reprioritized.
+        assert_eq!(requested(view.handle_key(key(KeyCode::Char('+')))), None);
+
+        view.handle_key(key(KeyCode::Down));
+        assert_eq!(
+            requested(view.handle_key(key(KeyCode::Char('+')))),
+            Some(TaskManagerAction::SetPriority {
+                id: "task_wait".to_string(),
+                priority: 1
+            })
+        );
+        assert_eq!(
+            requested(view.handle_key(key(KeyCode::Char('c')))),
+            Some(TaskManagerAction::Cancel {
+                id: "task_wait".to_string()
+            })
+        );
+        assert_eq!(
+            requested(view.handle_key(key(KeyCode::Char('p')))),
+            Some(TaskManagerAction::TogglePin {
+                id: "task_wait".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn refresh_keeps_cursor_on_the_same_task() {
+        let mut view = TaskManagerView::new(
+            vec![
+                summary("task_a", TaskStatus::Queued, 0),
+                summary("task_b", TaskStatus::Queued, 0),
+            ],
+            BTreeSet::new(),
+        );
+        view.handle_key(key(KeyCode::Down));
+        view.set_tasks(
+            vec![
+                summary("task_new", TaskStatus::Queued, 0),
+                summary("task_a", TaskStatus::Queued, 0),
+                summary("task_b", TaskStatus::Running, 0),
+            ],
+            BTreeSet::from(["task_b".to_string()]),
+        );
+        assert_eq!(
+            view.selected_task().map(|task| task.id.as_str()),
+            Some("task_b")
+        );
+        assert_eq!(
+            requested(view.handle_key(key(KeyCode::Char('x')))),
+            Some(TaskManagerAction::Cancel {
+                id: "task_b".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,328 @@
+//! Optional split pane beside the transcript for tool output.
+//!
+//! While a tool runs, the pane shows its output as it arrives — for
+//! `exec_shell` the job's stdout/stderr is read from the shared shell
+//! manager on the loop's shell-stream tick. Once the tool finishes, the pane
+//! keeps its full result, so long command output can be read here instead of
+//! flooding the conversational transcript.
+
+use std::time::Instant;
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+
+use crate::deepseek_theme::Theme;
+use crate::palette;
+use crate::tools::shell::ShellStatus;
+use crate::tools::spec::{ToolError, ToolResult};
+use crate::tui::app::App;
+use crate::tui::osc8::strip_ansi_into;
+
+/// Narrowest chat area that still gets split; below it the pane stays hidden.
+pub const TOOL_OUTPUT_PANE_MIN_CHAT_WIDTH: u16 = 100;
+
+/// Tools whose live output comes from a shell job.
+const SHELL_TOOLS: &[&str] = &["exec_shell", "task_shell_start"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl PaneStatus {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Succeeded => "done",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ToolOutputPane {
+    pub visible: bool,
+    /// Scroll keys go to the pane instead of the composer.
+    pub focused: bool,
+    tool_id: Option<String>,
+    tool_name: String,
+    summary: String,
+    status: Option<PaneStatus>,
+    output: String,
+    started_at: Option<Instant>,
+    shell_job_id: Option<String>,
+    /// Lines scrolled up from the bottom; 0 follows the tail.
+    scroll_from_bottom: usize,
+}
+
+impl ToolOutputPane {
+    /// Show `id` as the running tool, replacing whatever was displayed.
+    pub fn tool_started(&mut self, id: &str, name: &str, input: &serde_json::Value) {
+        self.tool_id = Some(id.to_string());
+        self.tool_name = name.to_string();
+        self.summary = input
+            .get("command")
+            .or_else(|| input.get("path"))
+            .or_else(|| input.get("query"))
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        self.status = Some(PaneStatus::Running);
+        self.output.clear();
+        self.started_at = Some(Instant::now());
+        self.shell_job_id = None;
+        self.scroll_from_bottom = 0;
+    }
+
+    /// Replace the live output with the tool's full result. Completions for
+    /// tools other than the displayed one are ignored.
+    pub fn tool_completed(&mut self, id: &str, result: &Result<ToolResult, ToolError>) {
+        if self.tool_id.as_deref() != Some(id) {
+            return;
+        }
+        let (status, text) = match result {
+            Ok(result) if result.success => (PaneStatus::Succeeded, result.content.as_str()),
+            Ok(result) => (PaneStatus::Failed, result.content.as_str()),
+            Err(err) => {
+                self.status = Some(PaneStatus::Failed);
+                self.output = format!("Error: {err}");
+                return;
+            }
+        };
+        self.status = Some(status);
+        self.output = sanitize(text);
+        self.shell_job_id = None;
+    }
+
+    /// Set the streamed output of the running tool. Returns true when the
+    /// text changed.
+    pub fn set_live_output(&mut self, output: &str) -> bool {
+        let output = sanitize(output);
+        if output == self.output {
+            return false;
+        }
+        self.output = output;
+        true
+    }
+
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.status == Some(PaneStatus::Running)
+    }
+
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll_from_bottom = self
+            .scroll_from_bottom
+            .saturating_add(lines)
+            .min(self.output.lines().count());
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll_from_bottom = self.scroll_from_bottom.saturating_sub(lines);
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_from_bottom = self.output.lines().count();
+    }
+
+    /// Jump back to the tail and keep following new output.
+    pub fn follow(&mut self) {
+        self.scroll_from_bottom = 0;
+    }
+
+    /// Output lines to draw in `rows` rows, honouring the scroll offset.
+    fn visible_lines(&self, rows: usize) -> &str {
+        let total = self.output.lines().count();
+        let end = total.saturating_sub(self.scroll_from_bottom.min(total.saturating_sub(rows)));
+        let start = end.saturating_sub(rows);
+        let mut offsets = self
+            .output
+            .match_indices('\n')
+            .map(|(idx, _)| idx + 1)
+            .collect::<Vec<_>>();
+        offsets.insert(0, 0);
+        let from = offsets.get(start).copied().unwrap_or(self.output.len());
+        let to = offsets
+            .get(end)
+            .map_or(self.output.len(), |idx| idx.saturating_sub(1));
+        self.output.get(from..to.max(from)).unwrap_or_default()
+    }
+}
+
+fn sanitize(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    strip_ansi_into(text, &mut out);
+    out.replace('\r', "")
+}
+
+/// Pull the running shell job's output into the pane. Returns true when the
+/// pane changed and needs a redraw.
+pub(super) fn poll_live_shell_output(app: &mut App) -> bool {
+    let pane = &app.tool_output_pane;
+    if !pane.visible || !pane.is_running() || !SHELL_TOOLS.contains(&pane.tool_name.as_str()) {
+        return false;
+    }
+    let Some(shell_manager) = app.runtime_services.shell_manager.clone() else {
+        return false;
+    };
+    let Ok(mut manager) = shell_manager.lock() else {
+        return false;
+    };
+    let pane = &mut app.tool_output_pane;
+    if pane.shell_job_id.is_none() {
+        // The tool call and the job it spawns are not linked by id; the job
+        // started most recently, after the tool call, is the one to follow.
+        let since_start = pane
+            .started_at
+            .map_or(u64::MAX, |started| {
+                u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+            })
+            .saturating_add(250);
+        pane.shell_job_id = manager
+            .list_jobs()
+            .into_iter()
+            .filter(|job| job.status == ShellStatus::Running && job.name.is_none())
+            .filter(|job| job.elapsed_ms <= since_start)
+            .min_by_key(|job| job.elapsed_ms)
+            .map(|job| job.id);
+    }
+    let Some(job_id) = pane.shell_job_id.clone() else {
+        return false;
+    };
+    let Ok(detail) = manager.inspect_job(&job_id) else {
+        return false;
+    };
+    let output = if detail.stderr.is_empty() {
+        detail.stdout
+    } else if detail.stdout.is_empty() {
+        detail.stderr
+    } else {
+        format!("{}\n{}", detail.stdout, detail.stderr)
+    };
+    pane.set_live_output(&output)
+}
+
+pub fn render_tool_output_pane(
+    f: &mut Frame,
+    area: Rect,
+    pane: &ToolOutputPane,
+    mode: palette::PaletteMode,
+) {
+    if area.width < 20 || area.height < 4 {
+        return;
+    }
+    let theme = Theme::for_palette_mode(mode);
+    let title = match pane.status {
+        Some(status) => format!(
+            " Output \u{00b7} {} \u{00b7} {} ",
+            pane.tool_name,
+            status.label()
+        ),
+        None => " Output ".to_string(),
+    };
+    let border_color = if pane.focused {
+        palette::DEEPSEEK_SKY
+    } else {
+        theme.section_border_color
+    };
+
+    // Borders + padding take 3 rows; the summary line takes one more.
+    let rows = usize::from(area.height.saturating_sub(4)).max(1);
+    let mut lines: Vec<Line<'static>> = Vec::with_capacity(rows + 1);
+    if pane.status.is_none() {
+        lines.push(Line::from(Span::styled(
+            "No tool has run yet.",
+            Style::default().fg(palette::TEXT_MUTED),
+        )));
+    } else {
+        let summary = if pane.summary.is_empty() {
+            pane.tool_name.clone()
+        } else {
+            format!("$ {}", pane.summary.lines().next().unwrap_or_default())
+        };
+        lines.push(Line::from(Span::styled(
+            summary,
+            Style::default().fg(palette::TEXT_MUTED),
+        )));
+        if pane.output.is_empty() {
+            let waiting = if pane.is_running() {
+                "(waiting for output…)"
+            } else {
+                "(no output)"
+            };
+            lines.push(Line::from(Span::styled(
+                waiting,
+                Style::default().fg(palette::TEXT_MUTED),
+            )));
+        } else {
+            lines.extend(
+                pane.visible_lines(rows)
+                    .lines()
+                    .map(|line| Line::from(line.to_string())),
+            );
+        }
+    }
+
+    let mut block = Block::default()
+        .title(Line::from(Span::styled(
+            title,
+            Style::default().fg(theme.section_title_color).bold(),
+        )))
+        .borders(theme.section_borders)
+        .border_type(theme.section_border_type)
+        .border_style(Style::default().fg(border_color))
+        .style(Style::default().bg(theme.section_bg))
+        .padding(theme.section_padding);
+    if pane.scroll_from_bottom > 0 {
+        block = block.title_bottom(Line::from(Span::styled(
+            format!(
+                " \u{2191}{} lines \u{00b7} End follows ",
+                pane.scroll_from_bottom
+            ),
+            Style::default().fg(palette::TEXT_MUTED),
+        )));
+    }
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn pane_follows_the_tail_and_keeps_the_full_result() {
+        let mut pane = ToolOutputPane::default();
+        pane.tool_started("call-1", "exec_shell", &json!({"command": "cargo test"}));
+        assert!(pane.is_running());
+        assert!(pane.set_live_output("one\ntwo\n\u{1b}[32mthree\u{1b}[0m\nfour"));
+        assert!(!pane.set_live_output("one\ntwo\n\u{1b}[32mthree\u{1b}[0m\nfour"));
+        assert_eq!(pane.visible_lines(2), "three\nfour");
+
+        pane.scroll_up(1);
+        assert_eq!(pane.visible_lines(2), "two\nthree");
+        pane.scroll_up(50);
+        assert_eq!(pane.visible_lines(2), "one\ntwo");
+        pane.follow();
+
+        // Another tool's completion does not replace the displayed one.
+        pane.tool_completed("call-2", &Ok(ToolResult::success("other")));
+        assert!(pane.is_running());
+
+        let full = (1..=5)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        pane.tool_completed("call-1", &Ok(ToolResult::success(full)));
+        assert_eq!(pane.status, Some(PaneStatus::Succeeded));
+        assert_eq!(pane.visible_lines(3), "3\n4\n5");
+        assert_eq!(pane.visible_lines(10), "1\n2\n3\n4\n5");
+    }
+}
@@ -6,6 +6,7 @@ use std::time::Instant;
 use crate::hooks::HookEvent;
 use crate::tools::ReviewOutput;
 use crate::tools::spec::{ToolError, ToolResult};
+use crate::tools::write_audit::WriteAudit;
 use crate::tui::active_cell::ActiveCell;
 use crate::tui::app::{App, ToolDetailRecord};
 use crate::tui::history::{
@@ -36,6 +37,9 @@ pub(super) fn handle_tool_call_started(
         let _ = app.execute_hooks(HookEvent::ToolCallBefore, &context);
     }
 
+    app.record_agent_touched_files(crate::session_summary::written_paths(name, input));
+    app.tool_output_pane.tool_started(id, name, input);
+
     let id = id.to_string();
 
     // All in-flight tool work for the current turn lives in `app.active_cell`
@@ -49,6 +53,10 @@ pub(super) fn handle_tool_call_started(
 
     if is_exploring_tool(name) {
         let label = exploring_label(name, input);
+        let read_path = (name == "read_file")
+            .then(|| input.get("path").and_then(|v| v.as_str()))
+            .flatten()
+            .and_then(|path| crate::stale_reads::resolve_path(&app.workspace, path));
         // ensure_exploring + append_to_exploring keeps all parallel exploring
         // starts in a single ExploringCell entry.
         let active = app.active_cell.as_mut().expect("active_cell just ensured");
@@ -60,6 +68,8 @@ pub(super) fn handle_tool_call_started(
                 ExploringEntry {
                     label,
                     status: ToolStatus::Running,
+                    read_path,
+                    stale: false,
                 },
             )
             .map_or(0, |(_, inner)| inner);
@@ -111,6 +121,7 @@ pub(super) fn handle_tool_call_started(
                     duration_ms: None,
                     source,
                     interaction: Some(summary.clone()),
+                    session: None,
                     output_summary: None,
                 })),
             );
@@ -143,6 +154,7 @@ pub(super) fn handle_tool_call_started(
                 duration_ms: None,
                 source,
                 interaction: None,
+                session: None,
                 output_summary: None,
             })),
         );
@@ -301,6 +313,7 @@ fn register_tool_cell(
         tool_name: tool_name.to_string(),
         input: input.clone(),
         output: None,
+        write_audit: None,
     };
     if cell_index < app.history.len() {
         app.tool_details_by_cell.insert(cell_index, record);
@@ -323,16 +336,22 @@ fn store_tool_detail_output(
         Ok(tool_result) => tool_result.content.clone(),
         Err(err) => err.to_string(),
     });
+    let write_audit = result.as_ref().ok().and_then(WriteAudit::from_result);
+    if let Some(audit) = write_audit.as_ref() {
+        app.turn_write_stats.record(audit);
+    }
     if cell_index < app.history.len()
         && let Some(detail) = app.tool_details_by_cell.get_mut(&cell_index)
     {
         detail.output = payload.clone();
+        detail.write_audit = write_audit.clone();
     }
     // Also write to the active table while the entry might still live there;
     // some callsites pre-rewrite cell_index but the active_tool_details map is
     // the canonical source for in-flight outputs.
     if let Some(detail) = app.active_tool_details.get_mut(tool_id) {
         detail.output = payload;
+        detail.write_audit = write_audit;
     }
 }
 
@@ -461,6 +480,7 @@ pub(super) fn handle_tool_call_complete(
     if app.ignored_tool_calls.remove(id) {
         return;
     }
+    app.tool_output_pane.tool_completed(id, result);
     // Roll any child-LLM token usage the tool reports into the
     // session-cost counter. Runs unconditionally so future tools that
     // spawn their own LLM calls (RLM, summarizers, retrieval helpers)
@@ -481,6 +501,9 @@ pub(super) fn handle_tool_call_complete(
                 Ok(tool_result) if tool_result.success => ToolStatus::Success,
                 Ok(_) | Err(_) => ToolStatus::Failed,
             };
+            if result_was_cached(result) && !entry.label.ends_with(CACHED_SUFFIX) {
+                entry.label.push_str(CACHED_SUFFIX);
+            }
             app.mark_history_updated();
             // Mutating the in-flight exploring cell needs an active-cell
             // revision bump so the transcript cache invalidates the synthetic
@@ -626,8 +649,13 @@ pub(super) fn handle_tool_call_complete(
                 generic.status = status;
                 match result.as_ref() {
                     Ok(tool_result) => {
+                        let summary = summarize_tool_output(&tool_result.content);
                         generic.output = Some(tool_result.content.clone());
-                        generic.output_summary = Some(summarize_tool_output(&tool_result.content));
+                        generic.output_summary = Some(if result_was_cached(result) {
+                            format!("(cached) {summary}")
+                        } else {
+                            summary
+                        });
                         generic.is_diff = output_looks_like_diff(&tool_result.content);
                     }
                     Err(err) => {
@@ -672,6 +700,15 @@ pub(super) fn handle_tool_call_complete(
     }
 }
 
+/// Suffix appended to exploring entries replayed from the tool result cache.
+const CACHED_SUFFIX: &str = " (cached)";
+
+fn result_was_cached(result: &Result<ToolResult, ToolError>) -> bool {
+    result
+        .as_ref()
+        .is_ok_and(crate::tools::result_cache::is_cache_hit)
+}
+
 fn refresh_active_tool_completion_timestamp(app: &mut App, cell_index: usize) {
     if cell_index < app.history.len() {
         return;
@@ -779,6 +816,7 @@ fn push_orphan_tool_completion(
                 Ok(tool_result) => Some(tool_result.content.clone()),
                 Err(err) => Some(err.to_string()),
             },
+            write_audit: result.as_ref().ok().and_then(WriteAudit::from_result),
         },
     );
 
@@ -805,8 +843,50 @@ fn push_orphan_tool_completion(
     }
 }
 
+/// Badge the read entries of files that changed on disk since the model
+/// read them (`Event::ReadFilesChanged`).
+pub(super) fn mark_stale_reads(app: &mut App, paths: &[PathBuf]) {
+    for idx in 0..app.history.len() {
+        if mark_stale_entries(&mut app.history[idx], paths) {
+            app.bump_history_cell(idx);
+        }
+    }
+    if let Some(active) = app.active_cell.as_mut() {
+        for idx in 0..active.entry_count() {
+            if let Some(cell) = active.entry_mut(idx) {
+                mark_stale_entries(cell, paths);
+            }
+        }
+    }
+    app.status_message = Some(match paths {
+        [path] => format!(
+            "{} changed on disk since it was read",
+            path.strip_prefix(&app.workspace).unwrap_or(path).display()
+        ),
+        _ => format!("{} previously read files changed on disk", paths.len()),
+    });
+    app.needs_redraw = true;
+}
+
+fn mark_stale_entries(cell: &mut HistoryCell, paths: &[PathBuf]) -> bool {
+    let HistoryCell::Tool(ToolCell::Exploring(explore)) = cell else {
+        return false;
+    };
+    let mut marked = false;
+    for entry in &mut explore.entries {
+        if !entry.stale && entry.read_path.as_ref().is_some_and(|p| paths.contains(p)) {
+            entry.stale = true;
+            marked = true;
+        }
+    }
+    marked
+}
+
 fn is_exploring_tool(name: &str) -> bool {
-    matches!(name, "read_file" | "list_dir" | "grep_files" | "list_files")
+    matches!(
+        name,
+        "read_file" | "read_symbol" | "list_dir" | "grep_files" | "list_files"
+    )
 }
 
 fn is_exec_tool(name: &str) -> bool {
@@ -824,6 +904,10 @@ pub(super) fn exploring_label(name: &str, input: &serde_json::Value) -> String {
             .and_then(|o| o.get("path"))
             .and_then(|v| v.as_str())
             .map_or(fallback, |path| format!("Reading {path}")),
+        "read_symbol" => obj
+            .and_then(|o| o.get("target"))
+            .and_then(|v| v.as_str())
+            .map_or(fallback, |target| format!("Reading {target}")),
         "list_dir" => obj
             .and_then(|o| o.get("path"))
             .and_then(|v| v.as_str())
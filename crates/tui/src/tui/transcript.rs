@@ -15,6 +15,16 @@
 //!
 //! Width or render-option changes still bust the entire cache (correct: wrap
 //! layout depends on width and which cells are visible at all).
+//!
+//! While the user is scrolled up, [`TranscriptViewCache::set_offscreen_floor`]
+//! lets a dirty cell that starts below the viewport keep its stale lines.
+//! Its old revision stays cached, so it re-wraps as soon as it scrolls into
+//! view (or the view returns to the tail) — a long stream under a scrolled-up
+//! reader costs nothing until it is looked at.
+//!
+//! The streaming assistant cell itself is rendered through
+//! [`StreamingMarkdown`], which keeps the answer's settled prefix rendered
+//! and re-renders only the tail on each delta.
 
 use std::sync::Arc;
 
@@ -25,6 +35,7 @@ use ratatui::{
 
 use crate::tui::app::TranscriptSpacing;
 use crate::tui::history::{HistoryCell, TranscriptRenderOptions};
+use crate::tui::markdown_render::StreamingMarkdown;
 use crate::tui::scrolling::TranscriptLineMeta;
 
 /// Per-cell cached render output. Reused across `ensure` calls when the
@@ -78,6 +89,12 @@ pub struct TranscriptViewCache {
     /// columns past visual-only decoration glyphs without guessing which
     /// spans are decorative (#1163).
     rail_prefix_widths: Vec<usize>,
+    /// Incremental markdown state for the streaming assistant cell, so a
+    /// delta re-renders only the tail of the answer instead of all of it.
+    streaming_markdown: StreamingMarkdown,
+    /// First transcript line past the viewport, when the view is detached
+    /// from the tail. Dirty cells starting at or after it are not re-wrapped.
+    offscreen_floor: Option<usize>,
 }
 
 impl TranscriptViewCache {
@@ -91,9 +108,18 @@ impl TranscriptViewCache {
             lines: Vec::new(),
             line_meta: Vec::new(),
             rail_prefix_widths: Vec::new(),
+            streaming_markdown: StreamingMarkdown::default(),
+            offscreen_floor: None,
         }
     }
 
+    /// Set the line below which dirty cells may keep stale output until the
+    /// next `ensure` that can see them. Pass `None` when following the tail
+    /// or when the viewport is about to move.
+    pub fn set_offscreen_floor(&mut self, floor: Option<usize>) {
+        self.offscreen_floor = floor;
+    }
+
     /// Ensure cached lines match the provided cells/widths/per-cell revisions.
     ///
     /// Reuses rendered lines for cells whose `cell_revisions[i]` matches the
@@ -151,6 +177,9 @@ impl TranscriptViewCache {
         let mut new_per_cell: Vec<CachedCell> = Vec::with_capacity(total_cells);
         let revisions_match = cell_revisions.len() == total_cells;
 
+        // Lines of the cells before `idx`, spacers excluded: a lower bound on
+        // where cell `idx` starts, so a cell past the floor is surely hidden.
+        let mut lines_before: usize = 0;
         let mut idx: usize = 0;
         for shard in cell_shards {
             for cell in *shard {
@@ -170,6 +199,24 @@ impl TranscriptViewCache {
                     && prev.revision == current_rev
                     && revisions_match
                 {
+                    lines_before += prev.lines.len();
+                    new_per_cell.push(prev.clone());
+                    idx += 1;
+                    continue;
+                }
+
+                // Off-screen below a detached viewport: keep the stale render
+                // (and its old revision, so it is retried next time). Only
+                // while cells are appended: after a removal, the entry at
+                // `idx` may belong to a different cell.
+                if let Some(prev) = self.per_cell.get(idx)
+                    && !layout_changed
+                    && old_len <= total_cells
+                    && self
+                        .offscreen_floor
+                        .is_some_and(|floor| lines_before >= floor)
+                {
+                    lines_before += prev.lines.len();
                     new_per_cell.push(prev.clone());
                     idx += 1;
                     continue;
@@ -183,8 +230,13 @@ impl TranscriptViewCache {
                 } else {
                     width
                 };
-                let rendered = cell.lines_with_options(render_width, options);
+                let rendered = cell.lines_with_streaming_markdown(
+                    render_width,
+                    options,
+                    &mut self.streaming_markdown,
+                );
                 let is_empty = rendered.is_empty();
+                lines_before += rendered.len();
                 new_per_cell.push(CachedCell {
                     revision: current_rev,
                     lines: Arc::new(rendered),
@@ -564,6 +616,7 @@ mod tests {
             duration_ms: None,
             source: ExecSource::Assistant,
             interaction: None,
+            session: None,
             output_summary: None,
         }))
     }
@@ -745,6 +798,64 @@ mod tests {
         assert_eq!(incremental, plain_lines(&fresh));
     }
 
+    #[test]
+    fn offscreen_floor_defers_rewrap_until_cell_is_visible() {
+        let mut cells = vec![
+            user_cell("first message"),
+            assistant_cell("streaming tail", true),
+        ];
+        let mut revisions = vec![1u64, 1];
+        let mut cache = TranscriptViewCache::new();
+        let options = TranscriptRenderOptions::default();
+        cache.ensure(&cells, &revisions, 40, options);
+        let before = plain_lines(&cache);
+
+        // Viewport ends inside the first cell: the tail delta is deferred.
+        cache.set_offscreen_floor(Some(1));
+        if let HistoryCell::Assistant { content, .. } = &mut cells[1] {
+            content.push_str(" plus delta");
+        }
+        revisions[1] += 1;
+        cache.ensure(&cells, &revisions, 40, options);
+        assert_eq!(plain_lines(&cache), before);
+
+        // Back at the tail the cell re-wraps, catching up fully.
+        cache.set_offscreen_floor(None);
+        cache.ensure(&cells, &revisions, 40, options);
+        let mut fresh = TranscriptViewCache::new();
+        fresh.ensure(&cells, &revisions, 40, options);
+        assert_eq!(plain_lines(&cache), plain_lines(&fresh));
+        assert_ne!(plain_lines(&cache), before);
+    }
+
+    #[test]
+    fn streaming_markdown_tail_matches_full_cell_render() {
+        let answer = "Intro line\n\n```rust\nfn main() {\n}\n```\n\n- one\n- two\n";
+        let mut cells = vec![user_cell("prompt"), assistant_cell("", true)];
+        let mut revisions = vec![1u64, 1];
+        let mut cache = TranscriptViewCache::new();
+        let options = TranscriptRenderOptions::default();
+
+        for line in answer.split_inclusive('\n') {
+            if let HistoryCell::Assistant { content, .. } = &mut cells[1] {
+                content.push_str(line);
+            }
+            revisions[1] += 1;
+            cache.ensure(&cells, &revisions, 40, options);
+            let streamed: Vec<String> = cache.per_cell[1]
+                .lines
+                .iter()
+                .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect();
+            let full: Vec<String> = cells[1]
+                .lines_with_options(40, options)
+                .iter()
+                .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect();
+            assert_eq!(streamed, full);
+        }
+    }
+
     #[test]
     fn width_change_rerenders_all_cells() {
         let cells = vec![
@@ -11,7 +11,8 @@ use anyhow::Result;
 use crossterm::{
     event::{
         self, DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
-        EnableFocusChange, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+        EnableFocusChange, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyModifiers,
     },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
@@ -51,18 +52,19 @@ use crate::palette;
 use crate::prompts;
 use crate::session_manager::{
     OfflineQueueState, QueuedSessionMessage, SavedSession, SessionManager,
-    create_saved_session_with_id_and_mode, create_saved_session_with_mode, update_session,
+    create_saved_session_with_id_and_mode, create_saved_session_with_mode, paused_call_index,
+    stamp_message_times, update_session,
 };
 use crate::task_manager::{
-    NewTaskRequest, SharedTaskManager, TaskManager, TaskManagerConfig, TaskStatus,
+    NewTaskRequest, SharedTaskManager, TaskManager, TaskManagerConfig, TaskStatus, TaskSummary,
 };
-use crate::tools::spec::RuntimeToolServices;
+use crate::tools::exec_profile::ExecProfile;
+use crate::tools::spec::{RuntimeToolServices, ToolError, ToolResult};
 use crate::tools::subagent::SubAgentStatus;
+use crate::tools::user_input::PausedUserInput;
 use crate::tui::auto_router;
 use crate::tui::color_compat::ColorCompatBackend;
-use crate::tui::command_palette::{
-    CommandPaletteView, build_entries as build_command_palette_entries,
-};
+use crate::tui::command_palette::CommandPaletteView;
 use crate::tui::composer_ui::*;
 use crate::tui::context_inspector::build_context_inspector_text;
 use crate::tui::event_broker::EventBroker;
@@ -71,45 +73,57 @@ use crate::tui::footer_ui::{
     friendly_subagent_progress, is_noisy_subagent_progress, one_line_summary, render_footer,
 };
 use crate::tui::format_helpers;
+use crate::tui::idle_maintenance;
 use crate::tui::key_shortcuts;
 use crate::tui::live_transcript::LiveTranscriptOverlay;
 use crate::tui::mcp_routing::{add_mcp_message, open_mcp_manager_pager};
 use crate::tui::mouse_ui::*;
-use crate::tui::notifications;
+use crate::tui::notifications::{self, NotifyEvent};
 use crate::tui::onboarding;
 use crate::tui::pager::PagerView;
 use crate::tui::persistence_actor::{self, PersistRequest};
 use crate::tui::plan_prompt::PlanPromptView;
 use crate::tui::scrolling::TranscriptScroll;
 // SelectionAutoscroll unused
+use crate::tui::changes_view::{ChangesView, display_path};
+use crate::tui::inbox_routing::{
+    collect_finished_subagents, collect_finished_tasks, handle_inbox_action,
+};
 use crate::tui::session_picker::SessionPickerView;
+use crate::tui::session_tabs::{self, LoopExit, SessionTab, SessionTabs};
 use crate::tui::shell_job_routing::{
-    add_shell_job_message, format_shell_job_list, format_shell_poll, open_shell_job_pager,
+    add_shell_job_message, format_shell_job_list, format_shell_poll, format_shell_session_list,
+    open_shell_job_pager, stream_shell_sessions,
 };
+use crate::tui::startup;
 use crate::tui::streaming_thinking;
 use crate::tui::subagent_routing::{
-    format_task_list, handle_subagent_mailbox, open_task_pager, reconcile_subagent_activity_state,
-    running_agent_count, sort_subagents_in_place, task_mode_label, task_summary_to_panel_entry,
+    format_pinned_task_output, format_task_list, handle_subagent_mailbox, open_task_pager,
+    open_task_transcript_pager, reconcile_subagent_activity_state, running_agent_count,
+    sort_subagents_in_place, task_mode_label, task_summary_to_panel_entry,
 };
+use crate::tui::task_manager_view::TaskManagerView;
+use crate::tui::tool_output_pane;
 #[cfg(test)]
 use crate::tui::tool_routing::exploring_label;
 use crate::tui::tool_routing::{
-    handle_tool_call_complete, handle_tool_call_started, maybe_add_patch_preview,
+    handle_tool_call_complete, handle_tool_call_started, mark_stale_reads, maybe_add_patch_preview,
 };
 use crate::tui::ui_text::{history_cell_to_text, line_to_plain, truncate_line_to_width};
 use crate::tui::user_input::UserInputView;
-use crate::tui::views::subagent_view_agents;
+use crate::tui::views::{ContextMenuAction, subagent_view_agents};
 use crate::tui::vim_mode;
 use crate::tui::workspace_context;
 
 use super::app::{
-    App, AppAction, AppMode, OnboardingState, QueuedMessage, ReasoningEffort, SidebarFocus,
-    StatusToastLevel, SubmitDisposition, TaskPanelEntry, TuiOptions,
+    App, AppAction, AppMode, LiveContextUsage, OnboardingState, QueuedMessage, ReasoningEffort,
+    SidebarFocus, StatusToastLevel, SubmitDisposition, TaskPanelEntry, TuiOptions, TurnWriteStats,
     looks_like_slash_command_input,
 };
 use super::approval::{
     ApprovalMode, ApprovalRequest, ApprovalView, ElevationRequest, ElevationView, ReviewDecision,
 };
+use super::approval_queue::{ApprovalQueueView, QueuedDecision};
 use super::history::{
     HistoryCell, ToolCell, ToolStatus, TranscriptRenderOptions, history_cells_from_message,
     summarize_tool_output,
@@ -117,7 +131,10 @@ use super::history::{
 use super::slash_menu::{
     apply_slash_menu_selection, try_autocomplete_slash_command, visible_slash_menu_entries,
 };
-use super::views::{ConfigView, HelpView, ModalKind, ShellControlView, ViewEvent};
+use super::views::{
+    ConfigView, HelpView, ModalKind, ReviewDiffAction, SessionChangesAction, ShellControlView,
+    TaskManagerAction, ViewEvent,
+};
 use super::widgets::pending_input_preview::{ContextPreviewItem, PendingInputPreview};
 use super::widgets::{ChatWidget, ComposerWidget, HeaderData, HeaderWidget, Renderable};
 
@@ -159,6 +176,125 @@ fn is_session_denied_for_key(app: &App, approval_key: &str) -> bool {
     app.approval_session_denied.contains(approval_key)
 }
 
+/// Show an approval prompt. A second request while a card is open turns
+/// the card into the approvals queue, so parallel calls are listed together
+/// instead of surfacing one at a time.
+fn enqueue_approval(app: &mut App, request: ApprovalRequest) {
+    if let Some(queue) = app.view_stack.find_mut::<ApprovalQueueView>() {
+        queue.push(request);
+        return;
+    }
+    if app.view_stack.top_kind() == Some(ModalKind::Approval)
+        && let Some(mut open) = app.view_stack.pop()
+    {
+        if let Some(card) = open.as_any_mut().downcast_mut::<ApprovalView>() {
+            let requests = vec![card.request().clone(), request];
+            app.view_stack
+                .push(ApprovalQueueView::new(requests, app.ui_locale));
+            return;
+        }
+        app.view_stack.push_boxed(open);
+    }
+    app.view_stack
+        .push(ApprovalView::new_for_locale(request, app.ui_locale));
+}
+
+/// Drop a queued approval the engine no longer waits on.
+fn forget_queued_approval(app: &mut App, tool_id: &str) {
+    if let Some(queue) = app.view_stack.find_mut::<ApprovalQueueView>() {
+        queue.remove(tool_id);
+        if queue.is_empty() {
+            app.view_stack.remove_kind(ModalKind::ApprovalQueue);
+        }
+    }
+}
+
+/// Offer the masked variable prompt when a failed shell command says a
+/// variable is missing. Each name is asked for at most once per session.
+fn maybe_prompt_missing_env(
+    app: &mut App,
+    tool_name: &str,
+    result: &Result<ToolResult, ToolError>,
+) {
+    if !matches!(
+        tool_name,
+        "exec_shell" | "exec_shell_wait" | "exec_shell_interact" | "run_tests"
+    ) || !app.view_stack.is_empty()
+    {
+        return;
+    }
+    let output = match result {
+        Ok(output) => {
+            let exit_code = output
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.get("exit_code"))
+                .and_then(serde_json::Value::as_i64);
+            if output.success && exit_code.is_none_or(|code| code == 0) {
+                return;
+            }
+            output.content.clone()
+        }
+        Err(err) => err.to_string(),
+    };
+    let Some(key) = crate::tools::session_env::detect_missing_var(&output) else {
+        return;
+    };
+    if app.session_env.contains(&key) || !app.env_prompted.insert(key.clone()) {
+        return;
+    }
+    app.status_message = Some(format!("{key} is not set; enter it to use it this session"));
+    app.view_stack
+        .push(crate::tui::env_prompt::EnvPromptView::new(
+            key,
+            tool_name.to_string(),
+        ));
+}
+
+async fn apply_approval_decision(
+    app: &mut App,
+    engine_handle: &EngineHandle,
+    queued: QueuedDecision,
+) {
+    let QueuedDecision {
+        tool_id,
+        tool_name,
+        decision,
+        timed_out,
+        approval_key,
+        approval_grouping_key,
+    } = queued;
+    if decision == ReviewDecision::ApprovedForSession {
+        // Store the tool name (backward compat) and the lossy grouping key
+        // so later flag variants of the same command family are also
+        // auto-approved (v0.8.37).
+        app.approval_session_approved.insert(tool_name);
+        app.approval_session_approved.insert(approval_grouping_key);
+    }
+
+    match decision {
+        ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {
+            let _ = engine_handle.approve_tool_call(tool_id).await;
+        }
+        ReviewDecision::Denied | ReviewDecision::Abort => {
+            // Cache the denial so the model retry-loop doesn't re-prompt for
+            // the exact same approval_key (#360). Only the key (per-call
+            // unique) is stored — NOT the tool_name, which would block all
+            // future invocations of the same tool type (#1377).
+            if !timed_out {
+                app.approval_session_denied.insert(approval_key);
+            }
+            let _ = engine_handle.deny_tool_call(tool_id).await;
+        }
+    }
+
+    if timed_out {
+        app.add_message(HistoryCell::System {
+            content: "Approval request timed out - denied".to_string(),
+        });
+    }
+}
+
 fn sidebar_width_for_chat_area(app: &App, chat_width: u16) -> Option<u16> {
     if app.sidebar_focus == SidebarFocus::Hidden || chat_width < SIDEBAR_VISIBLE_MIN_WIDTH {
         return None;
@@ -189,6 +325,17 @@ enum TranslationEvent {
         translated: anyhow::Result<String>,
     },
 }
+
+/// Results of background requests issued by the onboarding defaults and
+/// test-prompt steps.
+enum OnboardingEvent {
+    ModelsFetched(anyhow::Result<Vec<String>>),
+    TestPromptFinished {
+        model: String,
+        started: Instant,
+        reply: anyhow::Result<String>,
+    },
+}
 // Reset scroll region (`\x1b[r`), origin mode (`\x1b[?6l`), and home the cursor
 // (`\x1b[H`) before letting ratatui's diff renderer repaint. The destructive
 // `\x1b[2J\x1b[3J` pair was previously appended here to also wipe the visible
@@ -452,30 +599,22 @@ pub async fn run_tui(config: &Config, options: TuiOptions) -> Result<()> {
         automation_cancel.clone(),
         AutomationSchedulerConfig::default(),
     );
-    let shell_manager = app
-        .runtime_services
-        .shell_manager
-        .clone()
-        .unwrap_or_else(|| crate::tools::shell::new_shared_shell_manager(app.workspace.clone()));
-    app.runtime_services = RuntimeToolServices {
-        shell_manager: Some(shell_manager),
-        task_manager: Some(task_manager.clone()),
-        automations: Some(automations),
-        task_data_dir: Some(task_manager.data_dir()),
-        active_task_id: None,
-        active_thread_id: None,
-        // #456: plumb the App's HookExecutor so `exec_shell` can surface
-        // the configured `shell_env` hooks. Wrapped in Arc once and shared.
-        hook_executor: Some(std::sync::Arc::new(app.hooks.clone())),
-        handle_store: app.runtime_services.handle_store.clone(),
-        rlm_sessions: app.runtime_services.rlm_sessions.clone(),
-    };
+    attach_runtime_services(&mut app, &task_manager, &automations);
     refresh_active_task_panel(&mut app, &task_manager).await;
 
     let engine_config = build_engine_config(&app, config);
 
     // Spawn the Engine - it will handle all API communication
     let engine_handle = spawn_engine(engine_config, config);
+    // Skills, the session index, and MCP connections load after the first
+    // frame; the header lists whatever is still pending.
+    let sessions = config.sessions_config();
+    startup::start(
+        &mut app,
+        &engine_handle,
+        sessions.auto_gc.then(|| sessions.retention()),
+    )
+    .await;
     // The translation client is optional: it never crashes the TUI on
     // startup, even when the API key is missing, the base URL is malformed,
     // or the network is unavailable.
@@ -517,27 +656,74 @@ pub async fn run_tui(config: &Config, options: TuiOptions) -> Result<()> {
         persistence_actor::init_actor(handle);
     }
 
-    let result = run_event_loop(
-        &mut terminal,
-        &mut app,
-        config,
-        engine_handle,
-        task_manager,
-        &event_broker,
-        translation_client,
-    )
-    .await;
+    // New tabs start fresh: no resume target, no pre-filled composer, and
+    // onboarding already handled by the first tab.
+    let tab_options = TuiOptions {
+        resume_session_id: None,
+        initial_input: None,
+        skip_onboarding: true,
+        ..options.clone()
+    };
+    let mut tabs = SessionTabs::new(SessionTab::new(app, engine_handle));
+    let result = loop {
+        let exit = run_event_loop(
+            &mut terminal,
+            tabs.active_mut(),
+            config,
+            task_manager.clone(),
+            &event_broker,
+            translation_client.clone(),
+        )
+        .await;
+        match exit {
+            Ok(LoopExit::Quit) => break Ok(()),
+            Err(err) => break Err(err),
+            Ok(LoopExit::NewTab) => {
+                if tabs.is_full() {
+                    tabs.active_mut().app.status_message = Some(format!(
+                        "Tab limit reached ({})",
+                        session_tabs::MAX_SESSION_TABS
+                    ));
+                    continue;
+                }
+                let tab = open_session_tab(&tab_options, config, &task_manager, &automations).await;
+                let index = tabs.push(tab);
+                tabs.active_mut().app.status_message =
+                    Some(format!("Opened tab {} — Ctrl+1..9 to switch", index + 1));
+            }
+            Ok(LoopExit::SwitchTab(index)) => {
+                tabs.activate(index).await;
+            }
+            Ok(LoopExit::CloseTab) => {
+                let index = tabs.active().app.active_session_tab;
+                if let Some(mut closed) = tabs.close(index).await {
+                    retire_session_tab(&mut closed).await;
+                    let context = closed.app.base_hook_context();
+                    let _ = closed.app.execute_hooks(HookEvent::SessionEnd, &context);
+                    tabs.active_mut().app.status_message =
+                        Some(format!("Closed tab {}", index + 1));
+                }
+            }
+        }
+        // The previous tab's frame is still on screen; force a full repaint.
+        terminal.clear()?;
+    };
     automation_cancel.cancel();
     automation_scheduler.abort();
 
+    for tab in tabs.drain_background().await {
+        retire_session_tab(tab).await;
+    }
+
     // Fire session end hook
-    {
-        let context = app.base_hook_context();
-        let _ = app.execute_hooks(HookEvent::SessionEnd, &context);
+    for tab in tabs.iter() {
+        let context = tab.app.base_hook_context();
+        let _ = tab.app.execute_hooks(HookEvent::SessionEnd, &context);
     }
+    let app = &tabs.active().app;
 
     // Flush the persistence actor: clear checkpoint + graceful shutdown.
-    persistence_actor::persist(PersistRequest::ClearCheckpoint);
+    persistence_actor::persist(PersistRequest::ClearCheckpoint(None));
     persistence_actor::persist(PersistRequest::Shutdown);
 
     cleanup_guard.defused = true;
@@ -571,6 +757,51 @@ pub async fn run_tui(config: &Config, options: TuiOptions) -> Result<()> {
     result
 }
 
+/// Wire the shared task manager, automations, and per-app tool services
+/// into `app`. Each tab keeps its own shell manager so background jobs stay
+/// scoped to the session that started them.
+fn attach_runtime_services(
+    app: &mut App,
+    task_manager: &SharedTaskManager,
+    automations: &Arc<tokio::sync::Mutex<AutomationManager>>,
+) {
+    let shell_manager = app
+        .runtime_services
+        .shell_manager
+        .clone()
+        .unwrap_or_else(|| crate::tools::shell::new_shared_shell_manager(app.workspace.clone()));
+    app.runtime_services = RuntimeToolServices {
+        shell_manager: Some(shell_manager),
+        task_manager: Some(task_manager.clone()),
+        automations: Some(automations.clone()),
+        task_data_dir: Some(task_manager.data_dir()),
+        active_task_id: None,
+        active_thread_id: None,
+        // #456: plumb the App's HookExecutor so `exec_shell` can surface
+        // the configured `shell_env` hooks. Wrapped in Arc once and shared.
+        hook_executor: Some(std::sync::Arc::new(app.hooks.clone())),
+        handle_store: app.runtime_services.handle_store.clone(),
+        rlm_sessions: app.runtime_services.rlm_sessions.clone(),
+    };
+}
+
+/// Build a fresh session (own `App`, engine, history, and queue) for a new tab.
+async fn open_session_tab(
+    options: &TuiOptions,
+    config: &Config,
+    task_manager: &SharedTaskManager,
+    automations: &Arc<tokio::sync::Mutex<AutomationManager>>,
+) -> SessionTab {
+    let mut app = App::new(options.clone(), config);
+    attach_runtime_services(&mut app, task_manager, automations);
+    refresh_active_task_panel(&mut app, task_manager).await;
+    let engine = spawn_engine(build_engine_config(&app, config), config);
+    startup::start(&mut app, &engine, None).await;
+    let context = app.base_hook_context();
+    let _ = app.execute_hooks(HookEvent::SessionStart, &context);
+    SessionTab::new(app, engine)
+}
+
 fn should_show_resume_hint(session_id: Option<&str>) -> bool {
     session_id.is_some_and(|id| !id.trim().is_empty())
 }
@@ -675,6 +906,7 @@ fn build_engine_config(app: &App, config: &Config) -> EngineConfig {
         skills_dir: app.skills_dir.clone(),
         instructions: config.instructions_paths(),
         project_context_pack_enabled: config.project_context_pack_enabled(),
+        repo_map_tokens: config.repo_map_tokens(),
         translation_enabled: app.translation_enabled,
         // Effectively unlimited. V4 has a 1M context window and the user
         // wants the model running until it's actually done. The previous cap
@@ -692,6 +924,10 @@ fn build_engine_config(app: &App, config: &Config) -> EngineConfig {
         capacity: crate::core::capacity::CapacityControllerConfig::from_app_config(config),
         todos: app.todos.clone(),
         plan_state: app.plan_state.clone(),
+        session_changes: app.session_changes.clone(),
+        exec_profile: app.exec_profile.clone(),
+        session_env: app.session_env.clone(),
+        shell_limits: config.shell_limits(),
         max_spawn_depth: crate::tools::subagent::DEFAULT_MAX_SPAWN_DEPTH,
         network_policy: config.network.clone().map(|toml_cfg| {
             crate::network_policy::NetworkPolicyDecider::with_default_audit(toml_cfg.into_runtime())
@@ -701,6 +937,9 @@ fn build_engine_config(app: &App, config: &Config) -> EngineConfig {
             .snapshots_config()
             .max_workspace_gb
             .saturating_mul(1024 * 1024 * 1024),
+        snapshots_auto_before_write: config.snapshots_config().auto_before_write,
+        tool_cache_scope: config.tool_cache_scope(),
+        prompt_layers: config.prompt_layer_overrides(),
         lsp_config: config
             .lsp
             .clone()
@@ -708,10 +947,14 @@ fn build_engine_config(app: &App, config: &Config) -> EngineConfig {
         runtime_services: app.runtime_services.clone(),
         subagent_model_overrides: config.subagent_model_overrides(),
         subagent_api_timeout: Duration::from_secs(config.subagent_api_timeout_secs()),
+        subagent_max_steps: config.subagent_max_steps(),
+        subagent_allowed_models: config.subagent_allowed_models(),
         memory_enabled: config.memory_enabled(),
         memory_path: config.memory_path(),
+        command_lessons_enabled: config.command_lessons_enabled(),
         vision_config: config.vision_model_config(),
         strict_tool_mode: config.strict_tool_mode.unwrap_or(false),
+        self_report: config.self_report.unwrap_or(false),
         goal_objective: app.goal.goal_objective.clone(),
         locale_tag: app.ui_locale.tag().to_string(),
         workshop: config.workshop.clone(),
@@ -721,11 +964,30 @@ fn build_engine_config(app: &App, config: &Config) -> EngineConfig {
             .and_then(|s| s.provider)
             .unwrap_or_default(),
         search_api_key: config.search.as_ref().and_then(|s| s.api_key.clone()),
+        edit_validation: config.edit_validation.clone().unwrap_or_default(),
+        tool_filters: config.tool_filters.clone().unwrap_or_default(),
+        trust_level: app.trust_level,
+        model_presets: config.models.clone().unwrap_or_default(),
+        model_fallbacks: config.model_fallbacks.clone().unwrap_or_default(),
+        docs_index_on_start: true,
+        trail_enabled: app.trail_enabled,
+        trail_branch: app.trail_branch.clone(),
+        thinking: crate::config::ThinkingConfig {
+            budget_tokens: app.thinking_budget,
+            ..config.thinking.clone().unwrap_or_default()
+        },
     }
 }
 
 async fn refresh_active_task_panel(app: &mut App, task_manager: &SharedTaskManager) {
     let tasks = task_manager.list_tasks(None).await;
+    flush_pinned_task_outputs(app, task_manager, &tasks).await;
+    collect_finished_tasks(app, task_manager, &tasks).await;
+    if app.view_stack.top_kind() == Some(ModalKind::TaskManager) {
+        let rows = tasks.clone();
+        let pinned = app.pinned_task_ids.clone();
+        update_task_manager_view(app, |view| view.set_tasks(rows, pinned));
+    }
     let mut entries: Vec<TaskPanelEntry> = tasks
         .into_iter()
         .filter(|task| matches!(task.status, TaskStatus::Queued | TaskStatus::Running))
@@ -753,6 +1015,179 @@ async fn refresh_active_task_panel(app: &mut App, task_manager: &SharedTaskManag
     app.task_panel = entries;
 }
 
+/// Add the output of pinned tasks that reached a terminal state to the
+/// transcript, then unpin them.
+async fn flush_pinned_task_outputs(
+    app: &mut App,
+    task_manager: &SharedTaskManager,
+    tasks: &[TaskSummary],
+) {
+    if app.pinned_task_ids.is_empty() {
+        return;
+    }
+    let finished: Vec<String> = tasks
+        .iter()
+        .filter(|task| {
+            app.pinned_task_ids.contains(&task.id)
+                && !matches!(task.status, TaskStatus::Queued | TaskStatus::Running)
+        })
+        .map(|task| task.id.clone())
+        .collect();
+    for id in finished {
+        app.pinned_task_ids.remove(&id);
+        let Ok(task) = task_manager.get_task(&id).await else {
+            continue;
+        };
+        let output = task_manager.task_output(&task);
+        app.add_message(HistoryCell::System {
+            content: format_pinned_task_output(&task, output.as_deref()),
+        });
+        app.needs_redraw = true;
+    }
+}
+
+/// Apply `update` to the task manager modal when it is the top view.
+fn update_task_manager_view(app: &mut App, update: impl FnOnce(&mut TaskManagerView)) {
+    // Pop+push for the same aliasing reason as the live transcript overlay.
+    let Some(mut view) = app.view_stack.pop() else {
+        return;
+    };
+    if let Some(typed) = view.as_any_mut().downcast_mut::<TaskManagerView>() {
+        update(typed);
+        app.needs_redraw = true;
+    }
+    app.view_stack.push_boxed(view);
+}
+
+/// Refresh the `/queue` modal's copy of the queue when it is the top view.
+fn sync_queue_editor_view(app: &mut App) {
+    if app.view_stack.top_kind() != Some(ModalKind::QueueEditor) {
+        return;
+    }
+    // Pop+push for the same aliasing reason as the live transcript overlay.
+    let Some(mut view) = app.view_stack.pop() else {
+        return;
+    };
+    if let Some(typed) = view
+        .as_any_mut()
+        .downcast_mut::<crate::tui::views::queue_editor::QueueEditorView>()
+    {
+        typed.sync(app.queued_messages.iter().cloned().collect());
+    }
+    app.view_stack.push_boxed(view);
+}
+
+fn update_changes_view(app: &mut App, update: impl FnOnce(&mut ChangesView)) {
+    // Pop+push for the same aliasing reason as the live transcript overlay.
+    let Some(mut view) = app.view_stack.pop() else {
+        return;
+    };
+    if let Some(typed) = view.as_any_mut().downcast_mut::<ChangesView>() {
+        update(typed);
+        app.needs_redraw = true;
+    }
+    app.view_stack.push_boxed(view);
+}
+
+fn open_diff_pager(app: &mut App, title: String, diff: &str) {
+    let width = app
+        .viewport
+        .last_transcript_area
+        .map(|area| area.width)
+        .unwrap_or(80);
+    let lines = crate::tui::diff_render::render_diff(diff, width.saturating_sub(2));
+    app.view_stack.push(PagerView::new(title, lines));
+}
+
+fn handle_session_changes_action(app: &mut App, action: SessionChangesAction) {
+    match action {
+        SessionChangesAction::ShowDiff { path } => {
+            let display = display_path(&app.workspace, &path);
+            let diff = app
+                .session_changes
+                .diff(&path, &display)
+                .unwrap_or_default();
+            if diff.is_empty() {
+                update_changes_view(app, |view| {
+                    view.set_notice(format!("{display} matches its session-start contents"));
+                });
+                return;
+            }
+            let title = format!("Changes: {display}");
+            if diff.starts_with("--- ") {
+                open_diff_pager(app, title, &diff);
+            } else {
+                open_text_pager(app, title, diff);
+            }
+        }
+        SessionChangesAction::Revert { path } => {
+            let display = display_path(&app.workspace, &path);
+            let notice = match app.session_changes.revert(&path) {
+                Ok(()) => {
+                    app.add_message(HistoryCell::System {
+                        content: format!("Reverted {display} to its session-start contents"),
+                    });
+                    for tree in [app.file_tree.as_mut(), app.sidebar_file_tree.as_mut()]
+                        .into_iter()
+                        .flatten()
+                    {
+                        tree.refresh();
+                    }
+                    format!("Reverted {display}")
+                }
+                Err(err) => format!("Revert failed for {display}: {err}"),
+            };
+            let changes = app.session_changes.changes();
+            update_changes_view(app, |view| {
+                view.set_changes(changes);
+                view.set_notice(notice);
+            });
+        }
+    }
+}
+
+async fn handle_task_manager_action(
+    app: &mut App,
+    task_manager: &SharedTaskManager,
+    action: TaskManagerAction,
+) {
+    let notice = match action {
+        TaskManagerAction::Refresh => None,
+        TaskManagerAction::ViewTranscript { id } => match task_manager.task_transcript(&id).await {
+            Ok(transcript) => {
+                open_task_transcript_pager(app, &id, &transcript);
+                return;
+            }
+            Err(err) => Some(format!("Transcript unavailable: {err}")),
+        },
+        TaskManagerAction::Cancel { id } => match task_manager.cancel_task(&id).await {
+            Ok(task) if task.status == TaskStatus::Running => {
+                Some(format!("Cancellation requested for {}", task.id))
+            }
+            Ok(task) => Some(format!("{} {}", task.id, task.status.label())),
+            Err(err) => Some(format!("Cancel failed: {err}")),
+        },
+        TaskManagerAction::SetPriority { id, priority } => {
+            match task_manager.set_task_priority(&id, priority).await {
+                Ok(task) => Some(format!("{} priority {:+}", task.id, task.priority)),
+                Err(err) => Some(format!("Reprioritize failed: {err}")),
+            }
+        }
+        TaskManagerAction::TogglePin { id } => {
+            if app.pinned_task_ids.remove(&id) {
+                Some(format!("Unpinned {id}"))
+            } else {
+                app.pinned_task_ids.insert(id.clone());
+                Some(format!("{id} output will be added to the conversation"))
+            }
+        }
+    };
+    refresh_active_task_panel(app, task_manager).await;
+    if let Some(notice) = notice {
+        update_task_manager_view(app, |view| view.set_notice(notice));
+    }
+}
+
 fn active_rlm_task_entries(app: &App) -> Vec<TaskPanelEntry> {
     let Some(active) = app.active_cell.as_ref() else {
         return Vec::new();
@@ -793,23 +1228,32 @@ fn active_rlm_task_entries(app: &App) -> Vec<TaskPanelEntry> {
 #[allow(clippy::too_many_lines)]
 async fn run_event_loop(
     terminal: &mut AppTerminal,
-    app: &mut App,
+    tab: &mut SessionTab,
     config: &mut Config,
-    mut engine_handle: EngineHandle,
     task_manager: SharedTaskManager,
     event_broker: &EventBroker,
     translation_client: Option<Arc<DeepSeekClient>>,
-) -> Result<()> {
-    // Track streaming state
-    let mut current_streaming_text = String::new();
+) -> Result<LoopExit> {
+    // Streaming state lives on the tab so switching away mid-stream and back
+    // resumes the same assistant message.
+    let SessionTab {
+        app,
+        engine: engine_handle,
+        pending_events,
+        streaming_text: current_streaming_text,
+        ..
+    } = tab;
     let (translation_tx, mut translation_rx) =
         tokio::sync::mpsc::unbounded_channel::<TranslationEvent>();
+    let (onboarding_tx, mut onboarding_rx) =
+        tokio::sync::mpsc::unbounded_channel::<OnboardingEvent>();
     let mut pending_translations = 0usize;
     let mut pending_thinking_translations = 0usize;
     let mut last_queue_state = (app.queued_messages.clone(), app.queued_draft.clone());
     let mut last_task_refresh = Instant::now()
         .checked_sub(Duration::from_secs(2))
         .unwrap_or_else(Instant::now);
+    let mut last_shell_stream = Instant::now();
     let mut last_status_frame = Instant::now()
         .checked_sub(Duration::from_millis(UI_STATUS_ANIMATION_MS))
         .unwrap_or_else(Instant::now);
@@ -832,10 +1276,22 @@ async fn run_event_loop(
         .unwrap_or_else(Instant::now);
 
     loop {
-        if !drain_web_config_events(&mut web_config_session, app, config, &engine_handle).await {
+        if !drain_web_config_events(&mut web_config_session, app, config, engine_handle).await {
             web_config_session = None;
         }
 
+        while let Ok(event) = onboarding_rx.try_recv() {
+            handle_onboarding_event(app, event);
+        }
+
+        if startup::poll(app) {
+            app.needs_redraw = true;
+        }
+
+        if session_tabs::refresh_tab_strip(app) {
+            app.needs_redraw = true;
+        }
+
         while let Ok(event) = translation_rx.try_recv() {
             match event {
                 TranslationEvent::AssistantMessage {
@@ -929,14 +1385,27 @@ async fn run_event_loop(
             app.needs_redraw = true;
         }
 
+        if last_shell_stream.elapsed() >= Duration::from_millis(250) {
+            last_shell_stream = Instant::now();
+            if stream_shell_sessions(app) {
+                app.needs_redraw = true;
+            }
+            if tool_output_pane::poll_live_shell_output(app) {
+                app.needs_redraw = true;
+            }
+        }
+
         // First, poll for engine events (non-blocking)
         let mut received_engine_event = false;
         let mut transcript_batch_updated = false;
         let mut queued_to_send: Option<QueuedMessage> = None;
         {
             let mut rx = engine_handle.rx_event.write().await;
-            while let Ok(event) = rx.try_recv() {
+            // Events buffered while this tab was in the background replay
+            // first so the transcript sees them in engine order.
+            while let Some(event) = pending_events.pop_front().or_else(|| rx.try_recv().ok()) {
                 received_engine_event = true;
+                app.record_engine_event(&event);
                 if app.suppress_stream_events_until_turn_complete {
                     if matches!(event, EngineEvent::TurnStarted { .. }) {
                         // Ctrl+C can race with the engine's per-turn token
@@ -953,6 +1422,9 @@ async fn run_event_loop(
                 } else if !app.is_loading && ignore_stale_stream_event_while_idle(&event) {
                     continue;
                 }
+                if let Some(share) = app.share.as_ref() {
+                    share.hub().publish_engine_event(&event);
+                }
                 match event {
                     EngineEvent::MessageStarted { .. } => {
                         // Assistant text starting after parallel tool work
@@ -1012,10 +1484,13 @@ async fn run_event_loop(
                             if !remaining.is_empty() {
                                 append_streaming_text(app, index, &remaining);
                             }
-                            if let Some(HistoryCell::Assistant { streaming, .. }) =
+                            if let Some(HistoryCell::Assistant { content, streaming }) =
                                 app.history.get_mut(index)
                             {
                                 *streaming = false;
+                                if let Some(annotated) = crate::self_report::annotate(content) {
+                                    *content = annotated;
+                                }
                             }
                             // Streaming flag flipped — the cell's compact /
                             // transcript variants render slightly
@@ -1031,7 +1506,7 @@ async fn run_event_loop(
 
                         if app.translation_enabled
                             && !current_streaming_text.is_empty()
-                            && crate::tui::translation::needs_translation(&current_streaming_text)
+                            && crate::tui::translation::needs_translation(current_streaming_text)
                             && let Some(translation_client) = translation_client.as_ref()
                         {
                             app.status_message = Some(
@@ -1205,8 +1680,15 @@ async fn run_event_loop(
                         handle_tool_call_started(app, &id, &name, &input);
                     }
                     EngineEvent::ToolCallComplete { id, name, result } => {
+                        forget_queued_approval(app, &id);
+                        maybe_prompt_missing_env(app, &name, &result);
                         if name == "update_plan" {
                             app.plan_tool_used_in_turn = true;
+                            if result.is_ok()
+                                && let Some(err) = crate::tui::plan_export::sync(app)
+                            {
+                                app.status_message = Some(err);
+                            }
                         }
                         let tool_content = match &result {
                             Ok(output) => sanitize_stream_chunk(
@@ -1260,9 +1742,12 @@ async fn run_event_loop(
                     }
                     EngineEvent::TurnStarted { turn_id } => {
                         app.suppress_stream_events_until_turn_complete = false;
+                        app.soft_cancel_pending = false;
+                        app.resumable_interrupted_turn = false;
                         app.is_loading = true;
                         app.offline_mode = false;
                         app.turn_error_posted = false;
+                        app.turn_model_fallback = None;
                         app.dispatch_started_at = None;
                         current_streaming_text.clear();
                         app.streaming_state.reset();
@@ -1286,6 +1771,7 @@ async fn run_event_loop(
                         app.last_reasoning = None;
                         app.pending_tool_uses.clear();
                         app.plan_tool_used_in_turn = false;
+                        app.turn_write_stats = TurnWriteStats::default();
                         last_status_frame = Instant::now();
                     }
                     EngineEvent::TurnComplete {
@@ -1295,6 +1781,24 @@ async fn run_event_loop(
                     } => {
                         let was_locally_cancelled = app.suppress_stream_events_until_turn_complete;
                         app.suppress_stream_events_until_turn_complete = false;
+                        app.paused_user_input = None;
+                        app.paused_user_input_restored = false;
+                        app.session.live_context = None;
+                        app.resumable_interrupted_turn = app.soft_cancel_pending
+                            && !was_locally_cancelled
+                            && matches!(
+                                status,
+                                crate::core::events::TurnOutcomeStatus::Interrupted
+                            );
+                        app.soft_cancel_pending = false;
+                        // The turn may have written files; re-read changed
+                        // directories and git status for any open tree.
+                        for tree in [app.file_tree.as_mut(), app.sidebar_file_tree.as_mut()]
+                            .into_iter()
+                            .flatten()
+                        {
+                            tree.refresh();
+                        }
                         if !matches!(status, crate::core::events::TurnOutcomeStatus::Completed)
                             || draws_since_last_full_repaint >= PERIODIC_FULL_REPAINT_EVERY_N
                         {
@@ -1318,6 +1822,12 @@ async fn run_event_loop(
                         } else {
                             app.flush_active_cell();
                         }
+                        let write_stats = std::mem::take(&mut app.turn_write_stats);
+                        if !write_stats.files.is_empty() {
+                            app.add_message(HistoryCell::System {
+                                content: turn_write_summary(&write_stats),
+                            });
+                        }
                         app.is_loading = false;
                         app.dispatch_started_at = None;
                         app.offline_mode = false;
@@ -1388,39 +1898,37 @@ async fn run_event_loop(
                             }
                         }
 
-                        // Update session cost
-                        let pricing_model = if app.auto_model {
-                            app.last_effective_model.as_deref().unwrap_or(&app.model)
-                        } else {
-                            &app.model
+                        // Update session cost. After a model fallback only
+                        // the usage since the switch is billed here; the
+                        // earlier part was billed to the failed-over model.
+                        let pricing_model = app.turn_pricing_model().to_string();
+                        let unbilled = match app.turn_model_fallback.take() {
+                            Some((_, billed)) => usage.saturating_sub(&billed),
+                            None => usage.clone(),
                         };
-                        let turn_cost = crate::pricing::calculate_turn_cost_estimate_from_usage(
-                            pricing_model,
-                            &usage,
-                        );
-                        if let Some(cost) = turn_cost {
-                            app.accrue_session_cost_estimate(cost);
-                        }
+                        let turn_cost = app.accrue_model_usage(&pricing_model, &unbilled);
 
-                        // Emit OSC 9 / BEL desktop notification for long turns.
+                        // Notify for long turns, or any turn finishing
+                        // while the terminal is unfocused.
                         if status == crate::core::events::TurnOutcomeStatus::Completed
-                            && let Some((method, threshold, include_summary)) =
-                                notifications::settings(config)
+                            && let Some(delivery) =
+                                notifications::delivery(config, NotifyEvent::Turn)
+                            && delivery.should_fire(turn_elapsed, app.terminal_focused)
                         {
                             let in_tmux = std::env::var("TMUX").is_ok_and(|v| !v.is_empty());
                             let msg = notifications::completed_turn_message(
                                 app,
-                                &current_streaming_text,
-                                include_summary,
+                                current_streaming_text,
+                                delivery.include_summary,
                                 turn_elapsed,
                                 turn_cost,
                             );
-                            crate::tui::notifications::notify_done(
-                                method,
+                            notifications::dispatch(
+                                &delivery,
                                 in_tmux,
                                 &msg,
-                                threshold,
                                 turn_elapsed,
+                                app.terminal_focused,
                             );
                         }
 
@@ -1431,10 +1939,17 @@ async fn run_event_loop(
                             let session = build_session_snapshot(app, &manager);
                             app.current_session_id = Some(session.metadata.id.clone());
                             persistence_actor::persist(PersistRequest::SessionSnapshot(session));
+                            // The startup session index is stale once this one is saved.
+                            app.session_index = None;
                         }
-                        persistence_actor::persist(PersistRequest::ClearCheckpoint);
+                        persistence_actor::persist(PersistRequest::ClearCheckpoint(
+                            app.current_session_id.clone(),
+                        ));
 
+                        let preview_shown =
+                            finish_plan_preview(app, status, current_streaming_text);
                         if app.mode == AppMode::Plan
+                            && !preview_shown
                             && app.plan_tool_used_in_turn
                             && !app.plan_prompt_pending
                             && app.queued_message_count() == 0
@@ -1484,6 +1999,40 @@ async fn run_event_loop(
                     EngineEvent::Status { message } => {
                         app.status_message = Some(message);
                     }
+                    EngineEvent::PromptLayers { layers } => {
+                        app.prompt_layers = layers;
+                    }
+                    EngineEvent::ToolCatalog { summary } => {
+                        app.tool_catalog = Some(summary);
+                    }
+                    EngineEvent::LoopDetected { tool, failures } => {
+                        app.add_message(HistoryCell::Error {
+                            message: format!(
+                                "Loop detected: `{tool}` failed {failures} times with identical arguments. The model was asked to change approach and the exact repeat is blocked."
+                            ),
+                            severity: crate::error_taxonomy::ErrorSeverity::Warning,
+                        });
+                        if app.view_stack.is_empty() {
+                            app.view_stack
+                                .push(crate::tui::loop_prompt::LoopPromptView::new(tool, failures));
+                        }
+                    }
+                    EngineEvent::McpWarmed {
+                        connected,
+                        failures,
+                    } => {
+                        startup::mcp_warmed(app, connected, &failures);
+                    }
+                    EngineEvent::McpPromptFetched {
+                        server,
+                        name,
+                        result,
+                    } => {
+                        mcp_prompt_fetched(app, &server, &name, result);
+                    }
+                    EngineEvent::ReadFilesChanged { paths } => {
+                        mark_stale_reads(app, &paths);
+                    }
                     EngineEvent::SessionUpdated {
                         session_id,
                         messages,
@@ -1492,6 +2041,12 @@ async fn run_event_loop(
                         workspace,
                     } => {
                         app.current_session_id = Some(session_id);
+                        app.api_message_times = stamp_message_times(
+                            &app.api_messages,
+                            &app.api_message_times,
+                            &messages,
+                            chrono::Utc::now(),
+                        );
                         app.api_messages = messages;
                         app.system_prompt = system_prompt;
                         if app.auto_model {
@@ -1531,10 +2086,13 @@ async fn run_event_loop(
                     }
                     EngineEvent::CompactionCompleted { message, .. } => {
                         app.is_compacting = false;
+                        app.idle_maintenance.compaction_finished();
+                        app.session.live_context = None;
                         app.status_message = Some(message);
                     }
                     EngineEvent::CompactionFailed { message, .. } => {
                         app.is_compacting = false;
+                        app.idle_maintenance.compaction_finished();
                         app.status_message = Some(message);
                     }
                     EngineEvent::CycleAdvanced { from, to, briefing } => {
@@ -1552,9 +2110,39 @@ async fn run_event_loop(
                             "↻ context refreshed (cycle {from} → {to}, briefing: {briefing_tokens} tokens carried)"
                         ));
                     }
+                    EngineEvent::ModelFallback {
+                        from,
+                        to,
+                        reason,
+                        usage,
+                    } => {
+                        let pricing_model = app.turn_pricing_model().to_string();
+                        let billed = app
+                            .turn_model_fallback
+                            .take()
+                            .map(|(_, billed)| billed)
+                            .unwrap_or_default();
+                        app.accrue_model_usage(&pricing_model, &usage.saturating_sub(&billed));
+                        app.turn_model_fallback = Some((to.clone(), usage));
+                        app.add_message(HistoryCell::System {
+                            content: format!("Model {from} failed ({reason}); retrying with {to}"),
+                        });
+                        app.status_message = Some(format!("Model fallback: {from} → {to}"));
+                    }
                     EngineEvent::CoherenceState { state, .. } => {
                         app.coherence_state = state;
                     }
+                    EngineEvent::ContextUsage {
+                        prompt_tokens,
+                        output_tokens,
+                        estimated,
+                    } => {
+                        app.session.live_context = Some(LiveContextUsage {
+                            prompt_tokens,
+                            output_tokens,
+                            estimated,
+                        });
+                    }
                     EngineEvent::PrefixCacheChange {
                         description,
                         stability_pct,
@@ -1655,6 +2243,7 @@ async fn run_event_loop(
                                         && matches!(agent.status, SubAgentStatus::Running)
                                 });
                         app.agent_progress.remove(&id);
+                        app.inbox_pending_agents.insert(id.clone());
                         app.status_message = Some(format!(
                             "Sub-agent {id} completed: {}",
                             summarize_tool_output(&result)
@@ -1662,22 +2251,23 @@ async fn run_event_loop(
                         let should_recapture_terminal =
                             !has_other_running_subagents && app.use_alt_screen;
                         if !has_other_running_subagents
-                            && let Some((method, threshold, include_summary)) =
-                                notifications::settings(config)
+                            && let Some(delivery) =
+                                notifications::delivery(config, NotifyEvent::SubAgent)
+                            && delivery.should_fire(subagent_elapsed, app.terminal_focused)
                         {
                             let in_tmux = std::env::var("TMUX").is_ok_and(|v| !v.is_empty());
                             let msg = notifications::subagent_completion_message(
                                 &id,
                                 &result,
-                                include_summary,
+                                delivery.include_summary,
                                 subagent_elapsed,
                             );
-                            crate::tui::notifications::notify_done(
-                                method,
+                            notifications::dispatch(
+                                &delivery,
                                 in_tmux,
                                 &msg,
-                                threshold,
                                 subagent_elapsed,
+                                app.terminal_focused,
                             );
                         }
                         if should_recapture_terminal {
@@ -1695,6 +2285,7 @@ async fn run_event_loop(
                         let _ = engine_handle.send(Op::ListSubAgents).await;
                     }
                     EngineEvent::AgentList { agents } => {
+                        collect_finished_subagents(app, &agents);
                         let mut sorted = agents.clone();
                         sort_subagents_in_place(&mut sorted);
                         sorted.retain(|a| !a.from_prior_session);
@@ -1722,6 +2313,27 @@ async fn run_event_loop(
                         let session_approved =
                             is_session_approved_for_tool(app, &tool_name, &approval_grouping_key);
                         let session_denied = is_session_denied_for_key(app, &approval_key);
+                        let tool_input = app
+                            .pending_tool_uses
+                            .iter()
+                            .find(|(tool_id, _, _)| tool_id == &id)
+                            .map(|(_, _, input)| input.clone())
+                            .unwrap_or_else(|| serde_json::json!({}));
+                        let guardrail = if app.guardrails_enabled {
+                            crate::command_guardrail::scan_tool_call(
+                                &tool_name,
+                                &tool_input,
+                                &app.workspace,
+                                &app.guardrail_rules,
+                            )
+                        } else {
+                            None
+                        };
+                        // High-risk commands always reach the prompt, even
+                        // in YOLO mode or after an approve-for-session.
+                        let high_risk = guardrail
+                            .as_ref()
+                            .is_some_and(crate::command_guardrail::GuardrailVerdict::is_high);
                         if session_denied {
                             // The user already said no to this exact tool /
                             // approval key in this session; auto-deny so the
@@ -1736,7 +2348,9 @@ async fn run_event_loop(
                                 }),
                             );
                             let _ = engine_handle.deny_tool_call(id.clone()).await;
-                        } else if session_approved || app.approval_mode == ApprovalMode::Auto {
+                        } else if !high_risk
+                            && (session_approved || app.approval_mode == ApprovalMode::Auto)
+                        {
                             log_sensitive_event(
                                 "tool.approval.auto_approve",
                                 serde_json::json!({
@@ -1760,13 +2374,6 @@ async fn run_event_loop(
                             app.status_message =
                                 Some(format!("Blocked tool '{tool_name}' (approval_mode=never)"));
                         } else {
-                            let tool_input = app
-                                .pending_tool_uses
-                                .iter()
-                                .find(|(tool_id, _, _)| tool_id == &id)
-                                .map(|(_, _, input)| input.clone())
-                                .unwrap_or_else(|| serde_json::json!({}));
-
                             if tool_name == "apply_patch" {
                                 maybe_add_patch_preview(app, &tool_input);
                             }
@@ -1778,7 +2385,9 @@ async fn run_event_loop(
                                 &description,
                                 &tool_input,
                                 &approval_key,
-                            );
+                            )
+                            .with_guardrail(guardrail.clone())
+                            .with_diff_preview(&app.workspace);
                             log_sensitive_event(
                                 "tool.approval.prompted",
                                 serde_json::json!({
@@ -1786,21 +2395,40 @@ async fn run_event_loop(
                                     "description": description,
                                     "session_id": app.current_session_id,
                                     "mode": app.mode.label(),
+                                    "guardrail_risk": guardrail.as_ref().map(|g| g.risk.label()),
+                                    "guardrail_rule": guardrail.as_ref().map(|g| g.rule.as_str()),
                                 }),
                             );
-                            app.view_stack
-                                .push(ApprovalView::new_for_locale(request, app.ui_locale));
-                            app.status_message = Some(format!(
-                                "Approval required for '{tool_name}': {description}"
-                            ));
+                            enqueue_approval(app, request);
+                            app.status_message =
+                                Some(match app.view_stack.find_mut::<ApprovalQueueView>() {
+                                    Some(queue) => {
+                                        format!("{} approvals pending", queue.pending())
+                                    }
+                                    None => format!(
+                                        "Approval required for '{tool_name}': {description}"
+                                    ),
+                                });
                         }
                     }
                     EngineEvent::UserInputRequired { id, request } => {
-                        app.view_stack.push(UserInputView::new(id.clone(), request));
-                        app.status_message = Some(
-                            "Action required: answer the popup with 1-4, arrows, or Enter"
-                                .to_string(),
-                        );
+                        // Save now so quitting while the question is open
+                        // leaves a session that resumes at this prompt.
+                        app.paused_user_input = Some(PausedUserInput {
+                            tool_id: id.clone(),
+                            request: request.clone(),
+                        });
+                        app.paused_user_input_restored = false;
+                        if let Ok(manager) = SessionManager::default_location() {
+                            let session = build_session_snapshot(app, &manager);
+                            app.current_session_id = Some(session.metadata.id.clone());
+                            persistence_actor::persist(PersistRequest::SessionSnapshot(session));
+                            app.session_index = None;
+                        }
+                        let view = UserInputView::new(id.clone(), request)
+                            .with_workspace(app.workspace.clone());
+                        app.status_message = Some(view.status_hint().to_string());
+                        app.view_stack.push(view);
                     }
                     EngineEvent::ToolCallProgress { id, output } => {
                         app.status_message =
@@ -1813,6 +2441,7 @@ async fn run_event_loop(
                         denial_reason,
                         blocked_network,
                         blocked_write,
+                        limit_hit,
                     } => {
                         // In YOLO mode, auto-elevate to full access
                         if app.approval_mode == ApprovalMode::Auto {
@@ -1850,7 +2479,8 @@ async fn run_event_loop(
                                 &denial_reason,
                                 blocked_network,
                                 blocked_write,
-                            );
+                            )
+                            .with_limit_hit(limit_hit);
                             app.view_stack.push(ElevationView::new(request));
                             app.status_message =
                                 Some(format!("Sandbox blocked {tool_name}: {denial_reason}"));
@@ -1884,7 +2514,7 @@ async fn run_event_loop(
         }
 
         if let Some(next) = queued_to_send {
-            if let Err(err) = dispatch_user_message(app, config, &engine_handle, next.clone()).await
+            if let Err(err) = dispatch_user_message(app, config, engine_handle, next.clone()).await
             {
                 app.queue_message(next);
                 app.status_message = Some(format!(
@@ -1899,11 +2529,15 @@ async fn run_event_loop(
         let queue_state = (app.queued_messages.clone(), app.queued_draft.clone());
         if queue_state != last_queue_state {
             persist_offline_queue_state(app);
+            sync_queue_editor_view(app);
             last_queue_state = queue_state;
             app.needs_redraw = true;
         }
 
         if !app.view_stack.is_empty() {
+            if app.view_stack.poll_updates() {
+                app.needs_redraw = true;
+            }
             let events = app.view_stack.tick();
             if !events.is_empty() {
                 app.needs_redraw = true;
@@ -1913,13 +2547,13 @@ async fn run_event_loop(
                 app,
                 config,
                 &task_manager,
-                &mut engine_handle,
+                engine_handle,
                 &mut web_config_session,
                 events,
             )
             .await?
             {
-                return Ok(());
+                return Ok(LoopExit::Quit);
             }
         }
 
@@ -1990,8 +2624,19 @@ async fn run_event_loop(
         let allow_workspace_context_refresh =
             !app.is_loading && !has_running_agents && !app.is_compacting;
         workspace_context::refresh_if_needed(app, now, allow_workspace_context_refresh);
+        let compaction_due = should_compact_while_idle(app);
+        idle_maintenance::tick(
+            app,
+            engine_handle,
+            config,
+            now,
+            app.is_loading || has_running_agents,
+            compaction_due,
+        )
+        .await;
 
-        // Draw is gated by the frame-rate limiter (120 FPS cap). When a
+        // Draw is gated by the frame-rate limiter (120 FPS cap, stretched
+        // while draws are slow so stream deltas batch per frame). When a
         // redraw is needed but the limiter says we're inside the cooldown
         // window, leave `needs_redraw = true` and shorten the poll timeout
         // so the loop wakes up exactly when drawing is allowed.
@@ -2009,14 +2654,21 @@ async fn run_event_loop(
         };
         if app.needs_redraw && draw_wait.is_none() {
             let was_full_repaint = force_terminal_repaint;
+            let draw_started = Instant::now();
             draw_app_frame_inner(terminal, app, force_terminal_repaint)?;
+            let draw_finished = Instant::now();
             force_terminal_repaint = false;
             if was_full_repaint {
                 draws_since_last_full_repaint = 0;
             } else {
                 draws_since_last_full_repaint = draws_since_last_full_repaint.saturating_add(1);
             }
-            frame_rate_limiter.mark_emitted(Instant::now());
+            // Full repaints are deliberate one-offs; don't let them inflate
+            // the render cost that paces streaming frames.
+            if !was_full_repaint {
+                frame_rate_limiter.record_render_time(draw_finished - draw_started);
+            }
+            frame_rate_limiter.mark_emitted(draw_finished);
             app.needs_redraw = false;
         }
 
@@ -2058,6 +2710,9 @@ async fn run_event_loop(
         if event::poll(poll_timeout)? {
             let evt = event::read()?;
             app.needs_redraw = true;
+            if matches!(evt, Event::Key(_) | Event::Paste(_) | Event::Mouse(_)) {
+                idle_maintenance::note_user_activity(app, engine_handle, Instant::now());
+            }
 
             // Handle bracketed paste events
             if let Event::Paste(text) = &evt {
@@ -2083,12 +2738,18 @@ async fn run_event_loop(
                 } else if !app.view_stack.is_empty() {
                     // A non-consumed modal is open — don't leak paste into composer
                 } else {
-                    // Paste into main input
-                    app.insert_paste_text(text);
+                    // Paste into main input (or the large-paste prompt)
+                    app.accept_paste(text);
                 }
                 continue;
             }
 
+            match evt {
+                Event::FocusGained => app.terminal_focused = true,
+                Event::FocusLost => app.terminal_focused = false,
+                _ => {}
+            }
+
             // Re-establish terminal mode flags on focus-gain and force a full
             // viewport reset before repainting. App-switching and interactive
             // handoffs can leave the host terminal scrolled away from row 0
@@ -2203,13 +2864,13 @@ async fn run_event_loop(
                     app,
                     config,
                     &task_manager,
-                    &mut engine_handle,
+                    engine_handle,
                     &mut web_config_session,
                     events,
                 )
                 .await?
                 {
-                    return Ok(());
+                    return Ok(LoopExit::Quit);
                 }
                 continue;
             }
@@ -2221,13 +2882,14 @@ async fn run_event_loop(
             if key.kind != KeyEventKind::Press {
                 continue;
             }
+            let key = app.keymap.translate(key);
 
             // Handle onboarding flow
             if app.onboarding != OnboardingState::None {
                 match key.code {
                     KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                         let _ = engine_handle.send(Op::Shutdown).await;
-                        return Ok(());
+                        return Ok(LoopExit::Quit);
                     }
                     KeyCode::Esc if app.onboarding == OnboardingState::ApiKey => {
                         app.onboarding = OnboardingState::Welcome;
@@ -2268,6 +2930,27 @@ async fn run_event_loop(
                             }
                         }
                     }
+                    KeyCode::Up | KeyCode::BackTab
+                        if app.onboarding == OnboardingState::Preferences =>
+                    {
+                        app.onboarding_preferences.focus_prev();
+                    }
+                    KeyCode::Down | KeyCode::Tab
+                        if app.onboarding == OnboardingState::Preferences =>
+                    {
+                        app.onboarding_preferences.focus_next();
+                    }
+                    KeyCode::Left if app.onboarding == OnboardingState::Preferences => {
+                        app.onboarding_preferences.cycle(-1);
+                    }
+                    KeyCode::Right if app.onboarding == OnboardingState::Preferences => {
+                        app.onboarding_preferences.cycle(1);
+                    }
+                    KeyCode::Char('t') | KeyCode::Char('T')
+                        if app.onboarding == OnboardingState::TestPrompt =>
+                    {
+                        start_onboarding_test_prompt(app, config, &onboarding_tx);
+                    }
                     KeyCode::Enter => match app.onboarding {
                         OnboardingState::Welcome => {
                             onboarding::advance_onboarding_from_welcome(app);
@@ -2313,7 +2996,7 @@ async fn run_event_loop(
                                     let mut refreshed_config = config.clone();
                                     refreshed_config.api_key = Some(key);
                                     let engine_config = build_engine_config(app, &refreshed_config);
-                                    engine_handle = spawn_engine(engine_config, &refreshed_config);
+                                    *engine_handle = spawn_engine(engine_config, &refreshed_config);
                                     app.offline_mode = false;
                                     app.api_key_env_only = false;
 
@@ -2337,18 +3020,43 @@ async fn run_event_loop(
                                 }
                             }
                         }
+                        OnboardingState::Preferences => {
+                            match onboarding::apply_onboarding_preferences(app) {
+                                Ok(()) => {
+                                    app.push_status_toast(
+                                        "Defaults saved".to_string(),
+                                        StatusToastLevel::Info,
+                                        Some(2_500),
+                                    );
+                                    onboarding::advance_onboarding_after_preferences(app);
+                                }
+                                Err(err) => {
+                                    app.status_message =
+                                        Some(format!("Failed to save defaults: {err}"));
+                                }
+                            }
+                        }
+                        OnboardingState::TestPrompt => {
+                            onboarding::advance_onboarding_to_trust_or_tips(app);
+                        }
                         OnboardingState::TrustDirectory => {}
                         OnboardingState::Tips => {
                             app.finish_onboarding();
                         }
                         OnboardingState::None => {}
                     },
-                    KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Char('1')
+                    KeyCode::Char(c @ ('y' | 'Y' | '1' | '3' | '4'))
                         if app.onboarding == OnboardingState::TrustDirectory =>
                     {
-                        match onboarding::mark_trusted(&app.workspace) {
+                        let level = match c {
+                            '3' => crate::trust_level::TrustLevel::ReadOnly,
+                            '4' => crate::trust_level::TrustLevel::Full,
+                            _ => crate::trust_level::TrustLevel::Standard,
+                        };
+                        match onboarding::mark_trusted(&app.workspace, level) {
                             Ok(_) => {
-                                app.trust_mode = true;
+                                app.set_trust_level(level);
+                                let _ = engine_handle.send(Op::SetTrustLevel { level }).await;
                                 app.status_message = None;
                                 if app.onboarding_workspace_trust_gate {
                                     app.onboarding_workspace_trust_gate = false;
@@ -2367,7 +3075,7 @@ async fn run_event_loop(
                         if app.onboarding == OnboardingState::TrustDirectory =>
                     {
                         let _ = engine_handle.send(Op::Shutdown).await;
-                        return Ok(());
+                        return Ok(LoopExit::Quit);
                     }
                     KeyCode::Backspace if app.onboarding == OnboardingState::ApiKey => {
                         app.delete_api_key_char();
@@ -2396,6 +3104,12 @@ async fn run_event_loop(
                     }
                     _ => {}
                 }
+                if app.onboarding == OnboardingState::Preferences
+                    && app.onboarding_preferences.model_status
+                        == onboarding::preferences::ModelListStatus::NotFetched
+                {
+                    start_onboarding_model_fetch(app, config, &onboarding_tx);
+                }
                 continue;
             }
 
@@ -2403,7 +3117,10 @@ async fn run_event_loop(
                 if app.view_stack.top_kind() == Some(ModalKind::Help) {
                     app.view_stack.pop();
                 } else {
-                    app.view_stack.push(HelpView::new_for_locale(app.ui_locale));
+                    app.view_stack.push(
+                        HelpView::new_for_locale(app.ui_locale)
+                            .with_project_aliases(&app.workspace),
+                    );
                 }
                 continue;
             }
@@ -2412,7 +3129,10 @@ async fn run_event_loop(
                 if app.view_stack.top_kind() == Some(ModalKind::Help) {
                     app.view_stack.pop();
                 } else {
-                    app.view_stack.push(HelpView::new_for_locale(app.ui_locale));
+                    app.view_stack.push(
+                        HelpView::new_for_locale(app.ui_locale)
+                            .with_project_aliases(&app.workspace),
+                    );
                 }
                 continue;
             }
@@ -2438,14 +3158,7 @@ async fn run_event_loop(
                 if app.view_stack.is_empty() && app.kill_to_end_of_line() {
                     continue;
                 }
-                app.view_stack
-                    .push(CommandPaletteView::new(build_command_palette_entries(
-                        app.ui_locale,
-                        &app.skills_dir,
-                        &app.workspace,
-                        &app.mcp_config_path,
-                        app.mcp_snapshot.as_ref(),
-                    )));
+                app.view_stack.push(CommandPaletteView::for_app(app));
                 continue;
             }
 
@@ -2507,13 +3220,13 @@ async fn run_event_loop(
                     app,
                     config,
                     &task_manager,
-                    &mut engine_handle,
+                    engine_handle,
                     &mut web_config_session,
                     events,
                 )
                 .await?
                 {
-                    return Ok(());
+                    return Ok(LoopExit::Quit);
                 }
                 continue;
             }
@@ -2561,6 +3274,19 @@ async fn run_event_loop(
                 }
             }
 
+            if app.tool_output_pane.focused && handle_tool_output_pane_key(app, key) {
+                app.needs_redraw = true;
+                continue;
+            }
+
+            if app.sidebar_tree_focused
+                && app.sidebar_focus == SidebarFocus::Files
+                && handle_sidebar_tree_key(app, key)
+            {
+                app.needs_redraw = true;
+                continue;
+            }
+
             if app.is_history_search_active() {
                 handle_history_search_key(app, key);
                 continue;
@@ -2659,9 +3385,33 @@ async fn run_event_loop(
                 KeyCode::Char('t') | KeyCode::Char('T')
                     if key.modifiers == KeyModifiers::CONTROL =>
                 {
+                    return Ok(LoopExit::NewTab);
+                }
+                KeyCode::Char('t') | KeyCode::Char('T') if key.modifiers == KeyModifiers::ALT => {
                     toggle_live_transcript_overlay(app);
                     continue;
                 }
+                KeyCode::Char('w') | KeyCode::Char('W') if key.modifiers == KeyModifiers::ALT => {
+                    if app.session_tabs.len() > 1 {
+                        return Ok(LoopExit::CloseTab);
+                    }
+                    app.status_message = Some("Only one tab is open".to_string());
+                    continue;
+                }
+                KeyCode::Char(ch)
+                    if key.modifiers == KeyModifiers::CONTROL
+                        && session_tabs::tab_index_for_digit(ch).is_some() =>
+                {
+                    if let Some(index) = session_tabs::tab_index_for_digit(ch)
+                        && index != app.active_session_tab
+                    {
+                        if index < app.session_tabs.len() {
+                            return Ok(LoopExit::SwitchTab(index));
+                        }
+                        app.status_message = Some(format!("No tab {}", index + 1));
+                    }
+                    continue;
+                }
                 KeyCode::Char('1') if key.modifiers.contains(KeyModifiers::ALT) => {
                     if key.modifiers.contains(KeyModifiers::CONTROL) {
                         app.set_sidebar_focus(SidebarFocus::Work);
@@ -2680,6 +3430,17 @@ async fn run_event_loop(
                     }
                     continue;
                 }
+                KeyCode::Char('5')
+                    if key.modifiers.contains(KeyModifiers::ALT)
+                        && key.modifiers.contains(KeyModifiers::CONTROL) =>
+                {
+                    focus_files_sidebar(app);
+                    continue;
+                }
+                KeyCode::Char('o') | KeyCode::Char('O') if key.modifiers == KeyModifiers::ALT => {
+                    cycle_tool_output_pane(app);
+                    continue;
+                }
                 KeyCode::Char('3') if key.modifiers.contains(KeyModifiers::ALT) => {
                     if key.modifiers.contains(KeyModifiers::CONTROL) {
                         app.set_sidebar_focus(SidebarFocus::Agents);
@@ -2729,7 +3490,10 @@ async fn run_event_loop(
                     // never restores a different project's history by
                     // surprise (#1395). Press `a` inside the picker to
                     // broaden to every saved session.
-                    app.view_stack.push(SessionPickerView::new(&app.workspace));
+                    app.view_stack.push(SessionPickerView::from_index(
+                        &app.workspace,
+                        app.session_index.take(),
+                    ));
                     continue;
                 }
                 KeyCode::Char('c') | KeyCode::Char('C')
@@ -2765,7 +3529,7 @@ async fn run_event_loop(
                         }
                         CtrlCDisposition::ConfirmExit => {
                             let _ = engine_handle.send(Op::Shutdown).await;
-                            return Ok(());
+                            return Ok(LoopExit::Quit);
                         }
                         CtrlCDisposition::ArmExit => {
                             app.arm_quit();
@@ -2776,7 +3540,7 @@ async fn run_event_loop(
                     if key.modifiers.contains(KeyModifiers::CONTROL) && app.input.is_empty() =>
                 {
                     let _ = engine_handle.send(Op::Shutdown).await;
-                    return Ok(());
+                    return Ok(LoopExit::Quit);
                 }
                 // Vim composer mode: Esc from Insert/Visual → Normal.
                 // This arm runs before the generic Esc handler so Insert mode
@@ -2791,6 +3555,11 @@ async fn run_event_loop(
                 KeyCode::Esc if app.clear_composer_attachment_selection() => {
                     continue;
                 }
+                KeyCode::Esc if app.visible_prompt_hint().is_some() => {
+                    app.prompt_hint = None;
+                    app.needs_redraw = true;
+                    continue;
+                }
                 KeyCode::Esc if mention_menu_open => {
                     app.mention_menu_hidden = true;
                     app.mention_menu_selected = 0;
@@ -2806,10 +3575,21 @@ async fn run_event_loop(
                         }
                         EscapeAction::CancelRequest => {
                             app.backtrack.reset();
-                            engine_handle.cancel();
-                            mark_active_turn_cancelled_locally(app);
-                            current_streaming_text.clear();
-                            app.status_message = Some("Request cancelled".to_string());
+                            // First Esc during a tool batch lets the running
+                            // tool finish; a second Esc (or any Esc outside
+                            // a tool batch) aborts immediately.
+                            if !app.soft_cancel_pending && engine_handle.soft_cancel() {
+                                app.soft_cancel_pending = true;
+                                app.status_message = Some(
+                                    "Stopping after the running tool finishes (Esc again to abort)"
+                                        .to_string(),
+                                );
+                            } else {
+                                engine_handle.cancel();
+                                mark_active_turn_cancelled_locally(app);
+                                current_streaming_text.clear();
+                                app.status_message = Some("Request cancelled".to_string());
+                            }
                         }
                         EscapeAction::DiscardQueuedDraft => {
                             app.backtrack.reset();
@@ -2943,6 +3723,10 @@ async fn run_event_loop(
                     {
                         continue;
                     }
+                    if crate::tui::autocomplete::accept_ghost_suggestion(app, &mention_menu_entries)
+                    {
+                        continue;
+                    }
                     if try_autocomplete_slash_command(app) {
                         continue;
                     }
@@ -3008,6 +3792,33 @@ async fn run_event_loop(
                 {
                     app.status_message = Some("No next tool output".to_string());
                 }
+                // `.` opens the tool action menu (copy, save, re-run) for a
+                // selected tool cell. Only with an empty composer and a
+                // transcript selection, so a message can still start with `.`.
+                KeyCode::Char('.')
+                    if key.modifiers.is_empty()
+                        && app.input.is_empty()
+                        && !slash_menu_open
+                        && selected_tool_cell_index(app).is_some() =>
+                {
+                    if let Some(cell_index) = selected_tool_cell_index(app) {
+                        open_tool_context_menu(app, cell_index);
+                    }
+                    continue;
+                }
+                // `m` bookmarks the selected cell, or removes its bookmark.
+                // Same guard as `.`, so a message can still start with `m`.
+                KeyCode::Char('m')
+                    if key.modifiers.is_empty()
+                        && app.input.is_empty()
+                        && !slash_menu_open
+                        && crate::tui::bookmarks::selected_cell_index(app).is_some() =>
+                {
+                    if let Some(cell_index) = crate::tui::bookmarks::selected_cell_index(app) {
+                        toggle_bookmark(app, cell_index);
+                    }
+                    continue;
+                }
                 // `Alt+?` opens the searchable help overlay (#93). F1 and
                 // Ctrl+/ are also bound; bare `?` is reserved as text input
                 // so users can start a message with "?" without losing the
@@ -3018,7 +3829,10 @@ async fn run_event_loop(
                         && !slash_menu_open =>
                 {
                     if app.view_stack.top_kind() != Some(ModalKind::Help) {
-                        app.view_stack.push(HelpView::new_for_locale(app.ui_locale));
+                        app.view_stack.push(
+                            HelpView::new_for_locale(app.ui_locale)
+                                .with_project_aliases(&app.workspace),
+                        );
                     }
                     continue;
                 }
@@ -3036,7 +3850,7 @@ async fn run_event_loop(
                             if execute_command_input(
                                 terminal,
                                 app,
-                                &mut engine_handle,
+                                engine_handle,
                                 &task_manager,
                                 config,
                                 &mut web_config_session,
@@ -3044,7 +3858,7 @@ async fn run_event_loop(
                             )
                             .await?
                             {
-                                return Ok(());
+                                return Ok(LoopExit::Quit);
                             }
                         } else {
                             let queued = if let Some(mut draft) = app.queued_draft.take() {
@@ -3054,7 +3868,7 @@ async fn run_event_loop(
                                 build_queued_message(app, input)
                             };
                             if let Err(err) =
-                                steer_user_message(app, &engine_handle, queued.clone()).await
+                                steer_user_message(app, engine_handle, queued.clone()).await
                             {
                                 app.queue_message(queued);
                                 app.status_message = Some(format!(
@@ -3085,7 +3899,7 @@ async fn run_event_loop(
                             if execute_command_input(
                                 terminal,
                                 app,
-                                &mut engine_handle,
+                                engine_handle,
                                 &task_manager,
                                 config,
                                 &mut web_config_session,
@@ -3093,7 +3907,7 @@ async fn run_event_loop(
                             )
                             .await?
                             {
-                                return Ok(());
+                                return Ok(LoopExit::Quit);
                             }
                         } else {
                             let queued = if let Some(mut draft) = app.queued_draft.take() {
@@ -3105,7 +3919,7 @@ async fn run_event_loop(
                             if app.is_loading {
                                 // Engine is busy — steer into the current turn.
                                 if let Err(err) =
-                                    steer_user_message(app, &engine_handle, queued.clone()).await
+                                    steer_user_message(app, engine_handle, queued.clone()).await
                                 {
                                     app.queue_message(queued);
                                     app.status_message = Some(format!(
@@ -3117,8 +3931,7 @@ async fn run_event_loop(
                                 // Engine is idle — send as a regular message
                                 // so the content is not lost to rx_steer's
                                 // stale-drain in handle_send_message (#1331).
-                                submit_or_steer_message(app, config, &engine_handle, queued)
-                                    .await?;
+                                submit_or_steer_message(app, config, engine_handle, queued).await?;
                             }
                         }
                     }
@@ -3138,7 +3951,7 @@ async fn run_event_loop(
                         app.close_slash_menu();
                     }
                     if let Some(input) = app.handle_composer_enter() {
-                        if handle_plan_choice(app, config, &engine_handle, &input).await? {
+                        if handle_plan_choice(app, config, engine_handle, &input).await? {
                             continue;
                         }
                         // `# foo` quick-add (#492) — when memory is enabled,
@@ -3152,11 +3965,17 @@ async fn run_event_loop(
                             handle_memory_quick_add(app, &input, config);
                             continue;
                         }
+                        // `/shells attach`: plain lines go to the session's stdin.
+                        if !looks_like_slash_command_input(&input)
+                            && send_input_to_attached_shell(app, &input)
+                        {
+                            continue;
+                        }
                         if looks_like_slash_command_input(&input) {
                             if execute_command_input(
                                 terminal,
                                 app,
-                                &mut engine_handle,
+                                engine_handle,
                                 &task_manager,
                                 config,
                                 &mut web_config_session,
@@ -3164,7 +3983,7 @@ async fn run_event_loop(
                             )
                             .await?
                             {
-                                return Ok(());
+                                return Ok(LoopExit::Quit);
                             }
                         } else {
                             let queued = if let Some(mut draft) = app.queued_draft.take() {
@@ -3191,7 +4010,7 @@ async fn run_event_loop(
                                     })
                                     .await;
                             }
-                            submit_or_steer_message(app, config, &engine_handle, queued).await?;
+                            submit_or_steer_message(app, config, engine_handle, queued).await?;
                         }
                     }
                 }
@@ -3254,6 +4073,12 @@ async fn run_event_loop(
                 KeyCode::Right if is_word_cursor_modifier(key.modifiers) => {
                     app.move_cursor_word_forward();
                 }
+                KeyCode::Right
+                    if key.modifiers.is_empty()
+                        && crate::tui::autocomplete::accept_ghost_suggestion(
+                            app,
+                            &mention_menu_entries,
+                        ) => {}
                 KeyCode::Right => {
                     app.move_cursor_right();
                 }
@@ -3449,6 +4274,103 @@ async fn run_event_loop(
     }
 }
 
+/// Show the `Files` sidebar and give it keyboard focus.
+fn focus_files_sidebar(app: &mut App) {
+    app.set_sidebar_focus(SidebarFocus::Files);
+    app.sidebar_tree_focused = true;
+    app.status_message = Some(
+        "Files: \u{2191}/\u{2193} move  \u{2192}/\u{2190} expand/collapse  Enter insert  r refresh  Esc back"
+            .to_string(),
+    );
+}
+
+/// Drive the focused `Files` sidebar. Returns false for keys the tree does
+/// not use; those hand focus back to the composer and are handled there.
+fn handle_sidebar_tree_key(app: &mut App, key: KeyEvent) -> bool {
+    let Some(tree) = app.sidebar_file_tree.as_mut() else {
+        app.sidebar_tree_focused = false;
+        return false;
+    };
+    let plain = !key
+        .modifiers
+        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER);
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') if plain => tree.cursor_up(),
+        KeyCode::Down | KeyCode::Char('j') if plain => tree.cursor_down(),
+        KeyCode::Right | KeyCode::Char('l') if plain => tree.expand(),
+        KeyCode::Left | KeyCode::Char('h') if plain => tree.collapse(),
+        KeyCode::Char('r') if plain => {
+            tree.refresh();
+            app.status_message = Some("File tree refreshed".to_string());
+        }
+        KeyCode::Enter => {
+            if let Some(rel_path) = tree.activate() {
+                let path_str = rel_path.to_string_lossy().to_string();
+                app.sidebar_tree_focused = false;
+                app.status_message = Some(format!("Attached @{path_str}"));
+                app.insert_str(&format!("@{path_str} "));
+            }
+        }
+        KeyCode::Esc => {
+            app.sidebar_tree_focused = false;
+            app.status_message = Some("Focus returned to composer".to_string());
+        }
+        _ => {
+            app.sidebar_tree_focused = false;
+            return false;
+        }
+    }
+    true
+}
+
+/// Alt+O: open the tool-output pane with focus, refocus it when it is open
+/// but unfocused, and close it when it already has focus.
+fn cycle_tool_output_pane(app: &mut App) {
+    let pane = &mut app.tool_output_pane;
+    if pane.visible && pane.focused {
+        pane.visible = false;
+        pane.focused = false;
+        app.status_message = Some("Tool output pane closed".to_string());
+        return;
+    }
+    pane.visible = true;
+    pane.focused = true;
+    app.sidebar_tree_focused = false;
+    app.status_message = Some(
+        "Output: \u{2191}/\u{2193} scroll  PgUp/PgDn page  Home top  End follow  Esc composer  Alt+O close"
+            .to_string(),
+    );
+}
+
+/// Scroll the focused tool-output pane. Returns false for keys the pane does
+/// not use; those hand focus back to the composer and are handled there.
+fn handle_tool_output_pane_key(app: &mut App, key: KeyEvent) -> bool {
+    let pane = &mut app.tool_output_pane;
+    let plain = !key
+        .modifiers
+        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER);
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') if plain => pane.scroll_up(1),
+        KeyCode::Down | KeyCode::Char('j') if plain => pane.scroll_down(1),
+        KeyCode::PageUp => pane.scroll_up(10),
+        KeyCode::PageDown => pane.scroll_down(10),
+        KeyCode::Home | KeyCode::Char('g') if plain => pane.scroll_to_top(),
+        KeyCode::End | KeyCode::Char('G') => pane.follow(),
+        KeyCode::Char('o') | KeyCode::Char('O') if key.modifiers == KeyModifiers::ALT => {
+            cycle_tool_output_pane(app);
+        }
+        KeyCode::Esc => {
+            pane.focused = false;
+            app.status_message = Some("Focus returned to composer".to_string());
+        }
+        _ => {
+            pane.focused = false;
+            return false;
+        }
+    }
+    true
+}
+
 fn apply_alt_4_shortcut(app: &mut App, _modifiers: KeyModifiers) {
     app.set_sidebar_focus(SidebarFocus::Agents);
     app.status_message = Some("Sidebar focus: agents".to_string());
@@ -3480,6 +4402,126 @@ async fn fetch_available_models(config: &Config) -> Result<Vec<String>> {
     Ok(ids)
 }
 
+/// Kick off the live model-list fetch for the onboarding defaults step.
+fn start_onboarding_model_fetch(
+    app: &mut App,
+    config: &Config,
+    tx: &tokio::sync::mpsc::UnboundedSender<OnboardingEvent>,
+) {
+    use onboarding::preferences::ModelListStatus;
+
+    if crate::config::provider_passes_model_through(config.api_provider()) {
+        app.onboarding_preferences.model_status = ModelListStatus::Failed(format!(
+            "the {} provider does not list models",
+            config.api_provider().display_name()
+        ));
+        return;
+    }
+    app.onboarding_preferences.model_status = ModelListStatus::Fetching;
+    let config = config.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let models = fetch_available_models(&config).await;
+        let _ = tx.send(OnboardingEvent::ModelsFetched(models));
+    });
+}
+
+/// Send the onboarding connectivity prompt with the chosen default model.
+fn start_onboarding_test_prompt(
+    app: &mut App,
+    config: &Config,
+    tx: &tokio::sync::mpsc::UnboundedSender<OnboardingEvent>,
+) {
+    use onboarding::test_prompt::{TEST_PROMPT, TestPromptStatus};
+
+    if app.onboarding_test_prompt == TestPromptStatus::Running {
+        return;
+    }
+    app.onboarding_test_prompt = TestPromptStatus::Running;
+    let model = if app.auto_model {
+        config.default_model()
+    } else {
+        app.model.clone()
+    };
+    let config = config.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let started = Instant::now();
+        let request = MessageRequest {
+            model: model.clone(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: vec![ContentBlock::Text {
+                    text: TEST_PROMPT.to_string(),
+                    cache_control: None,
+                }],
+            }],
+            max_tokens: 64,
+            system: None,
+            tools: None,
+            tool_choice: None,
+            metadata: None,
+            thinking: None,
+            reasoning_effort: Some("off".to_string()),
+            stream: None,
+            temperature: None,
+            top_p: None,
+        };
+        let reply = async {
+            let client = DeepSeekClient::new(&config)?;
+            let response =
+                tokio::time::timeout(Duration::from_secs(30), client.create_message(request))
+                    .await??;
+            Ok(response
+                .content
+                .iter()
+                .filter_map(|block| match block {
+                    ContentBlock::Text { text, .. } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join(""))
+        }
+        .await;
+        let _ = tx.send(OnboardingEvent::TestPromptFinished {
+            model,
+            started,
+            reply,
+        });
+    });
+}
+
+fn handle_onboarding_event(app: &mut App, event: OnboardingEvent) {
+    use onboarding::preferences::ModelListStatus;
+    use onboarding::test_prompt::TestPromptStatus;
+
+    match event {
+        OnboardingEvent::ModelsFetched(Ok(models)) => {
+            let current = app.onboarding_preferences.selected_model().to_string();
+            app.onboarding_preferences.set_models(models, &current);
+            app.onboarding_preferences.model_status = ModelListStatus::Loaded;
+        }
+        OnboardingEvent::ModelsFetched(Err(err)) => {
+            app.onboarding_preferences.model_status = ModelListStatus::Failed(err.to_string());
+        }
+        OnboardingEvent::TestPromptFinished {
+            model,
+            started,
+            reply,
+        } => {
+            app.onboarding_test_prompt = match reply {
+                Ok(reply) => TestPromptStatus::Passed {
+                    model,
+                    reply,
+                    elapsed: started.elapsed(),
+                },
+                Err(err) => TestPromptStatus::Failed(err.to_string()),
+            };
+        }
+    }
+    app.needs_redraw = true;
+}
+
 async fn run_cache_warmup(app: &App, config: &Config) -> Result<Usage> {
     let client = DeepSeekClient::new(config)?;
     let reasoning_effort = if app.reasoning_effort == ReasoningEffort::Auto {
@@ -3509,6 +4551,23 @@ async fn run_cache_warmup(app: &App, config: &Config) -> Result<Usage> {
     Ok(response.usage)
 }
 
+async fn run_prompt_enhance(app: &App, config: &Config, draft: &str) -> Result<String> {
+    let client = DeepSeekClient::new(config)?;
+    let model = if matches!(
+        config.api_provider(),
+        crate::config::ApiProvider::Deepseek | crate::config::ApiProvider::DeepseekCN
+    ) {
+        crate::tui::prompt_lint::ENHANCE_MODEL
+    } else {
+        app.model.as_str()
+    };
+    let request = crate::tui::prompt_lint::enhance_request(draft, model);
+    let response =
+        tokio::time::timeout(Duration::from_secs(30), client.create_message(request)).await??;
+    crate::tui::prompt_lint::enhanced_draft(&response)
+        .ok_or_else(|| anyhow::anyhow!("the model returned an empty draft"))
+}
+
 // `format_*` chip/message builders moved to `tui/format_helpers.rs`.
 
 fn build_session_snapshot(app: &App, manager: &SessionManager) -> SavedSession {
@@ -3527,6 +4586,9 @@ fn build_session_snapshot(app: &App, manager: &SessionManager) -> SavedSession {
         app.sync_cost_to_metadata(&mut updated.metadata);
         updated.context_references = app.session_context_references.clone();
         updated.artifacts = app.session_artifacts.clone();
+        updated.bookmarks = app.bookmarks.clone();
+        updated.set_message_timestamps(&app.api_message_times);
+        updated.set_paused_input(app.paused_user_input.clone());
         updated
     } else {
         let mut session = if let Some(existing_id) = app.current_session_id.as_ref() {
@@ -3552,14 +4614,79 @@ fn build_session_snapshot(app: &App, manager: &SessionManager) -> SavedSession {
         app.sync_cost_to_metadata(&mut session.metadata);
         session.context_references = app.session_context_references.clone();
         session.artifacts = app.session_artifacts.clone();
+        session.bookmarks = app.bookmarks.clone();
+        session.set_message_timestamps(&app.api_message_times);
+        session.set_paused_input(app.paused_user_input.clone());
         session
     }
 }
 
+/// Replay what a tab buffered while parked into its `App`, save its session,
+/// and shut its engine down. Used when a tab closes and, for background
+/// tabs, at quit.
+async fn retire_session_tab(tab: &mut SessionTab) {
+    for event in tab.pending_events.drain(..) {
+        absorb_parked_event(&mut tab.app, event);
+    }
+    let app = &mut tab.app;
+    if !app.api_messages.is_empty()
+        && let Ok(manager) = SessionManager::default_location()
+    {
+        let session = build_session_snapshot(app, &manager);
+        app.current_session_id = Some(session.metadata.id.clone());
+        persistence_actor::persist(PersistRequest::SessionSnapshot(session));
+    }
+    persistence_actor::persist(PersistRequest::ClearCheckpoint(
+        app.current_session_id.clone(),
+    ));
+    let _ = tab.engine.send(Op::Shutdown).await;
+}
+
+/// Apply the parts of a buffered engine event that end up in the saved
+/// session (conversation state and turn cost) without rendering anything.
+fn absorb_parked_event(app: &mut App, event: EngineEvent) {
+    app.record_engine_event(&event);
+    match event {
+        EngineEvent::SessionUpdated {
+            session_id,
+            messages,
+            system_prompt,
+            model,
+            workspace,
+        } => {
+            app.current_session_id = Some(session_id);
+            app.api_message_times = stamp_message_times(
+                &app.api_messages,
+                &app.api_message_times,
+                &messages,
+                chrono::Utc::now(),
+            );
+            app.api_messages = messages;
+            app.system_prompt = system_prompt;
+            if app.auto_model {
+                app.last_effective_model = Some(model);
+            } else {
+                app.set_model_selection(model);
+            }
+            app.workspace = workspace;
+        }
+        EngineEvent::TurnComplete { usage, .. } => {
+            let pricing_model = app.turn_pricing_model().to_string();
+            let unbilled = match app.turn_model_fallback.take() {
+                Some((_, billed)) => usage.saturating_sub(&billed),
+                None => usage,
+            };
+            app.accrue_model_usage(&pricing_model, &unbilled);
+        }
+        _ => {}
+    }
+}
+
 fn queued_ui_to_session(msg: &QueuedMessage) -> QueuedSessionMessage {
     QueuedSessionMessage {
         display: msg.display.clone(),
         skill_instruction: msg.skill_instruction.clone(),
+        mode: msg.mode.map(|mode| mode.as_setting().to_string()),
     }
 }
 
@@ -3567,6 +4694,7 @@ fn queued_session_to_ui(msg: QueuedSessionMessage) -> QueuedMessage {
     QueuedMessage {
         display: msg.display,
         skill_instruction: msg.skill_instruction,
+        mode: msg.mode.as_deref().map(AppMode::from_setting),
     }
 }
 
@@ -3836,6 +4964,12 @@ fn queued_message_content_for_app(
         &app.workspace,
         cwd,
     );
+    // The active mode's prompt preset wraps the request; the transcript
+    // keeps what was typed.
+    let user_request = match app.active_mode_prompt() {
+        Some(prompt) => prompt.apply(&user_request),
+        None => user_request,
+    };
     if let Some(skill_instruction) = message.skill_instruction.as_ref() {
         format!("{skill_instruction}\n\n---\n\nUser request: {user_request}")
     } else {
@@ -3843,6 +4977,89 @@ fn queued_message_content_for_app(
     }
 }
 
+/// Evaluate skill frontmatter rules for a turn about to start. Returns the
+/// block injecting newly matched skills (and their `requires`), recording
+/// them on `app` so later turns neither repeat nor re-bill them.
+fn auto_activate_skills(
+    app: &mut App,
+    config: &Config,
+    prompt: &str,
+    references: &[crate::tui::file_mention::ContextReference],
+) -> Option<String> {
+    use crate::skills::activation::{
+        ActivationContext, render_auto_skills_block, select_auto_skills,
+    };
+
+    let budget = config.skills_auto_activate_tokens()?;
+    let registry = crate::skills::discover_for_workspace_and_dir(&app.workspace, &app.skills_dir);
+    if !registry
+        .list()
+        .iter()
+        .any(|skill| skill.rules.is_automatic())
+    {
+        return None;
+    }
+    let relative = |path: &std::path::Path| {
+        path.strip_prefix(&app.workspace)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned()
+    };
+    let mut files: Vec<String> = references
+        .iter()
+        .filter(|reference| reference.included)
+        .map(|reference| relative(std::path::Path::new(&reference.target)))
+        .collect();
+    files.extend(
+        app.session_changes
+            .changes()
+            .iter()
+            .map(|change| relative(&change.path)),
+    );
+    let mode = app.mode.label().to_ascii_lowercase();
+    let active: std::collections::HashSet<String> = app.auto_skills.iter().cloned().collect();
+    let outcome = select_auto_skills(
+        &registry,
+        &ActivationContext {
+            prompt,
+            files: &files,
+            mode: &mode,
+        },
+        &active,
+        &app.skills_off,
+        budget,
+    );
+
+    let names: Vec<String> = outcome.skills.iter().map(|s| s.name.clone()).collect();
+    let mut notice = Vec::new();
+    if !names.is_empty() {
+        notice.push(format!(
+            "Auto-activated skills: {} (/skill off <name> to stop)",
+            names.join(", ")
+        ));
+    }
+    if !outcome.over_budget.is_empty() {
+        notice.push(format!(
+            "Skipped (over the {budget}-token skill budget): {}",
+            outcome.over_budget.join(", ")
+        ));
+    }
+    if !outcome.missing.is_empty() {
+        notice.push(format!(
+            "Missing required skills: {}",
+            outcome.missing.join(", ")
+        ));
+    }
+    let block = (!outcome.skills.is_empty()).then(|| render_auto_skills_block(&outcome.skills));
+    if !notice.is_empty() {
+        app.add_message(HistoryCell::System {
+            content: notice.join("\n"),
+        });
+    }
+    app.auto_skills.extend(names);
+    block
+}
+
 async fn dispatch_user_message(
     app: &mut App,
     config: &Config,
@@ -3862,6 +5079,15 @@ async fn dispatch_user_message(
         let _ = app.execute_hooks(crate::hooks::HookEvent::MessageSubmit, &context);
     }
 
+    // A mode picked for this message in the `/queue` modal switches the
+    // session before the request is built, so prompt and tools match it.
+    if let Some(mode) = message.mode
+        && mode != app.mode
+    {
+        let msg = commands::switch_mode(app, mode);
+        app.add_message(HistoryCell::System { content: msg });
+    }
+
     // Set immediately to prevent double-dispatch before TurnStarted event arrives.
     let dispatch_started_at = Instant::now();
     app.is_loading = true;
@@ -3871,12 +5097,39 @@ async fn dispatch_user_message(
     app.last_submitted_prompt = Some(message.display.clone());
 
     let cwd = std::env::current_dir().ok();
-    let references = crate::tui::file_mention::context_references_from_input(
+    let mut references = crate::tui::file_mention::context_references_from_input(
         &message.display,
         &app.workspace,
         cwd.clone(),
     );
-    let content = queued_message_content_for_app(app, &message, cwd);
+    let mut content = queued_message_content_for_app(app, &message, cwd);
+    let smart_files = if app.smart_context_enabled {
+        let mentioned = references
+            .iter()
+            .filter(|reference| reference.included)
+            .map(|reference| PathBuf::from(&reference.target))
+            .collect();
+        crate::tui::smart_context::select_context_files(
+            &message.display,
+            &app.workspace,
+            app.smart_context_max_files,
+            &mentioned,
+        )
+    } else {
+        Vec::new()
+    };
+    if !smart_files.is_empty() {
+        content.push_str(&crate::tui::smart_context::render_context_block(
+            &smart_files,
+        ));
+        references.extend(crate::tui::smart_context::context_references(
+            &smart_files,
+            &app.workspace,
+        ));
+    }
+    if let Some(block) = auto_activate_skills(app, config, &message.display, &references) {
+        content = format!("{block}\n\n---\n\n{content}");
+    }
     let message_index = app.api_messages.len();
     app.system_prompt = Some(
         prompts::system_prompt_for_mode_with_context_skills_and_session(
@@ -3887,18 +5140,30 @@ async fn dispatch_user_message(
             None,
             prompts::PromptSessionContext {
                 user_memory_block: None,
+                command_lessons_block: None,
                 goal_objective: app.goal.goal_objective.as_deref(),
                 project_context_pack_enabled: config.project_context_pack_enabled(),
+                repo_map_tokens: config.repo_map_tokens(),
                 locale_tag: app.ui_locale.tag(),
                 translation_enabled: app.translation_enabled,
+                self_report: config.self_report.unwrap_or(false),
+                layer_overrides: Some(&config.prompt_layer_overrides()),
             },
         ),
     );
     app.add_message(HistoryCell::User {
         content: message.display.clone(),
     });
+    if let Some(share) = app.share.as_ref() {
+        share.hub().publish_user_message(&message.display);
+    }
     let history_cell = app.history.len().saturating_sub(1);
     app.record_context_references(history_cell, message_index, references);
+    if !smart_files.is_empty() {
+        app.add_message(HistoryCell::System {
+            content: crate::tui::smart_context::transcript_note(&smart_files),
+        });
+    }
     app.scroll_to_bottom();
     app.api_messages.push(Message {
         role: "user".to_string(),
@@ -4086,7 +5351,8 @@ async fn drain_web_config_events(
 
 /// Apply the choice made in the `/model` picker (#39): mutate App state so
 /// the next turn uses the new model/effort, persist the selection to
-/// `~/.deepseek/settings.toml` so it survives a restart, push the change to
+/// `~/.deepseek/settings.toml` when it was set as the default, warn when the
+/// new model's window can't hold the current context, push the change to
 /// the running engine via `Op::SetModel`/`Op::SetCompaction`, and surface
 /// a one-line status describing what changed.
 async fn apply_model_picker_choice(
@@ -4096,6 +5362,7 @@ async fn apply_model_picker_choice(
     mut effort: crate::tui::app::ReasoningEffort,
     previous_model: String,
     previous_effort: crate::tui::app::ReasoningEffort,
+    set_default: bool,
 ) {
     let model_is_auto = model.trim().eq_ignore_ascii_case("auto");
     if model_is_auto {
@@ -4103,17 +5370,33 @@ async fn apply_model_picker_choice(
     }
     let model_changed = model != previous_model || app.auto_model != model_is_auto;
     let effort_changed = effort != previous_effort;
-    if !model_changed && !effort_changed {
+    if !model_changed && !effort_changed && !set_default {
         app.status_message = Some(format!(
             "Model unchanged: {model} · thinking {}",
             effort.short_label()
         ));
         return;
     }
+    // Measure before switching: the usage snapshot is capped at the active
+    // model's window.
+    let context_warning = if model_changed {
+        crate::tui::model_picker::context_downsize_warning(
+            &model,
+            crate::tui::model_picker::current_context_tokens(app),
+        )
+    } else {
+        None
+    };
 
+    let mut preset_summary = None;
     if model_changed {
         app.set_model_selection(model.clone());
         app.clear_model_scoped_telemetry();
+        // An effort picked explicitly in the picker wins over the preset's.
+        if !effort_changed {
+            preset_summary = app.apply_model_preset();
+            effort = app.reasoning_effort;
+        }
     }
     if effort_changed {
         app.reasoning_effort = effort;
@@ -4123,22 +5406,21 @@ async fn apply_model_picker_choice(
         app.update_model_compaction_budget();
     }
 
-    // Best-effort persist; surface a status warning if the settings file
-    // can't be written rather than aborting the in-memory change.
+    // "Set as default" persists best-effort; surface a status warning if the
+    // settings file can't be written rather than aborting the in-memory
+    // change.
     let mut persist_warning: Option<String> = None;
-    let persist_result = (|| -> anyhow::Result<()> {
-        let mut settings = crate::settings::Settings::load()?;
-        if model_changed {
+    if set_default {
+        let persist_result = (|| -> anyhow::Result<()> {
+            let mut settings = crate::settings::Settings::load()?;
             settings.set("default_model", &model)?;
             settings.set_model_for_provider(app.api_provider.as_str(), &model);
-        }
-        if effort_changed {
             settings.set("reasoning_effort", effort.as_setting())?;
+            settings.save()
+        })();
+        if let Err(err) = persist_result {
+            persist_warning = Some(format!("(not persisted: {err})"));
         }
-        settings.save()
-    })();
-    if let Err(err) = persist_result {
-        persist_warning = Some(format!("(not persisted: {err})"));
     }
 
     if model_changed {
@@ -4167,11 +5449,21 @@ async fn apply_model_picker_choice(
         (false, true) => format!(
             "Thinking: {previous_effort_summary} → {effort_summary} · model {model_summary}"
         ),
-        (false, false) => unreachable!(),
+        (false, false) => format!("Model {model_summary} · thinking {effort_summary}"),
     };
+    if let Some(preset) = preset_summary {
+        summary.push_str(&format!(" · preset: {preset}"));
+    }
     if let Some(warning) = persist_warning {
         summary.push(' ');
         summary.push_str(&warning);
+    } else if set_default {
+        summary.push_str(" · saved as default");
+    }
+    if let Some(warning) = context_warning {
+        app.add_message(HistoryCell::System {
+            content: format!("Warning: {warning}"),
+        });
     }
     app.status_message = Some(summary);
 }
@@ -4291,6 +5583,95 @@ fn provider_picker_model_override(app: &App, provider: ApiProvider) -> Option<St
     (app.api_provider == provider).then(|| app.model.clone())
 }
 
+/// Load the saved session `session_id` into the app and sync the engine.
+async fn resume_saved_session(
+    app: &mut App,
+    config: &Config,
+    task_manager: &SharedTaskManager,
+    engine_handle: &EngineHandle,
+    session_id: &str,
+) {
+    let manager = match SessionManager::default_location() {
+        Ok(manager) => manager,
+        Err(err) => {
+            app.status_message = Some(format!("Failed to open sessions directory: {err}"));
+            return;
+        }
+    };
+
+    match manager.load_session(session_id) {
+        Ok(session) => {
+            let recovered = apply_loaded_session(app, config, &session);
+            sync_runtime_workspace_state(task_manager, app.workspace.clone()).await;
+            let _ = engine_handle
+                .send(Op::SyncSession {
+                    session_id: app.current_session_id.clone(),
+                    messages: app.api_messages.clone(),
+                    system_prompt: app.system_prompt.clone(),
+                    system_prompt_override: false,
+                    model: app.model.clone(),
+                    workspace: app.workspace.clone(),
+                })
+                .await;
+            let _ = engine_handle
+                .send(Op::SetCompaction {
+                    config: app.compaction_config(),
+                })
+                .await;
+            if !recovered {
+                app.status_message = Some(format!(
+                    "Session loaded (ID: {})",
+                    &session_id[..8.min(session_id.len())]
+                ));
+            }
+        }
+        Err(err) => {
+            app.status_message = Some(format!("Failed to load session {session_id}: {err}"));
+        }
+    }
+}
+
+/// Insert `@<path>` at the composer's cursor with surrounding whitespace so
+/// the existing `@`-mention parser picks it up.
+fn insert_path_mention(app: &mut App, path: &str) {
+    let cursor = app.cursor_position;
+    let needs_leading_space = cursor > 0
+        && !app
+            .input
+            .chars()
+            .nth(cursor.saturating_sub(1))
+            .is_some_and(|c| c.is_whitespace());
+    let mut insertion = String::new();
+    if needs_leading_space {
+        insertion.push(' ');
+    }
+    insertion.push('@');
+    insertion.push_str(path);
+    insertion.push(' ');
+    app.insert_str(&insertion);
+    app.status_message = Some(format!("Attached @{path}"));
+}
+
+/// Show a workspace file (relative or absolute path) in the pager.
+fn open_file_pager(app: &mut App, path: &str) {
+    const MAX_PAGER_FILE_BYTES: u64 = 512 * 1024;
+    let resolved = app.workspace.join(path);
+    let content = match std::fs::metadata(&resolved) {
+        Ok(meta) if meta.len() > MAX_PAGER_FILE_BYTES => Err(format!(
+            "{path} is too large to preview ({} KiB)",
+            meta.len() / 1024
+        )),
+        Ok(_) => std::fs::read(&resolved)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .map_err(|err| format!("Failed to read {path}: {err}")),
+        Err(err) => Err(format!("Failed to read {path}: {err}")),
+    };
+    match content {
+        Ok(content) => open_text_pager(app, path.to_string(), content),
+        Err(message) => app.status_message = Some(message),
+    }
+}
+
 fn open_text_pager(app: &mut App, title: String, content: String) {
     let width = app
         .viewport
@@ -4381,8 +5762,11 @@ async fn apply_command_result(
                         let session = build_session_snapshot(app, &manager);
                         app.current_session_id = Some(session.metadata.id.clone());
                         persistence_actor::persist(PersistRequest::SessionSnapshot(session));
+                        app.session_index = None;
                     }
-                    persistence_actor::persist(PersistRequest::ClearCheckpoint);
+                    persistence_actor::persist(PersistRequest::ClearCheckpoint(
+                        app.current_session_id.clone(),
+                    ));
                 }
             }
             AppAction::SendMessage(content) => {
@@ -4452,6 +5836,26 @@ async fn apply_command_result(
                     }
                 }
             }
+            AppAction::EnhancePrompt(draft) => {
+                app.status_message = Some("Enhancing prompt...".to_string());
+                match run_prompt_enhance(app, config, &draft).await {
+                    Ok(rewritten) => {
+                        app.prompt_hint = None;
+                        app.input = rewritten;
+                        app.cursor_position = app.input.chars().count();
+                        app.status_message = Some(
+                            "Enhanced draft is in the composer. Review, then press Enter."
+                                .to_string(),
+                        );
+                    }
+                    Err(error) => {
+                        app.add_message(HistoryCell::System {
+                            content: format!("Prompt enhance failed: {error}"),
+                        });
+                        app.status_message = Some("Prompt enhance failed".to_string());
+                    }
+                }
+            }
             AppAction::SwitchProvider { provider, model } => {
                 switch_provider(app, engine_handle, config, provider, model).await;
             }
@@ -4530,8 +5934,19 @@ async fn apply_command_result(
             }
             AppAction::OpenModelPicker => {
                 if app.view_stack.top_kind() != Some(ModalKind::ModelPicker) {
-                    app.view_stack
-                        .push(crate::tui::model_picker::ModelPickerView::new(app));
+                    let mut picker = crate::tui::model_picker::ModelPickerView::new(app);
+                    if !crate::config::provider_passes_model_through(config.api_provider()) {
+                        let (tx, rx) = std::sync::mpsc::channel();
+                        let config = config.clone();
+                        tokio::spawn(async move {
+                            let models = fetch_available_models(&config)
+                                .await
+                                .map_err(|err| err.to_string());
+                            let _ = tx.send(models);
+                        });
+                        picker = picker.with_live_models(rx);
+                    }
+                    app.view_stack.push(picker);
                 }
             }
             AppAction::OpenProviderPicker => {
@@ -4543,6 +5958,25 @@ async fn apply_command_result(
                         ));
                 }
             }
+            AppAction::OpenPlanPreview => {
+                if let Some(preview) = app.plan_preview.clone()
+                    && app.view_stack.top_kind() != Some(ModalKind::PlanPreview)
+                {
+                    app.view_stack
+                        .push(crate::tui::plan_preview::PlanPreviewView::new(preview));
+                }
+            }
+            AppAction::ExecutePlanPreview => {
+                execute_plan_preview(app, config, engine_handle).await?;
+            }
+            AppAction::OpenQueueEditor => {
+                if app.view_stack.top_kind() != Some(ModalKind::QueueEditor) {
+                    app.view_stack
+                        .push(crate::tui::views::queue_editor::QueueEditorView::new(
+                            app.queued_messages.iter().cloned().collect(),
+                        ));
+                }
+            }
             AppAction::OpenModePicker => {
                 if app.view_stack.top_kind() != Some(ModalKind::ModePicker) {
                     app.view_stack
@@ -4565,6 +5999,12 @@ async fn apply_command_result(
                         .push(crate::tui::feedback_picker::FeedbackPickerView::new());
                 }
             }
+            AppAction::OpenBookmarks => {
+                if app.view_stack.top_kind() != Some(ModalKind::Bookmarks) {
+                    let view = crate::tui::bookmarks::BookmarksView::new(app);
+                    app.view_stack.push(view);
+                }
+            }
             AppAction::OpenThemePicker => {
                 if app.view_stack.top_kind() != Some(ModalKind::ThemePicker) {
                     // Capture the active theme name straight from `app` so
@@ -4595,6 +6035,38 @@ async fn apply_command_result(
                 app.status_message = Some("Compacting context...".to_string());
                 let _ = engine_handle.send(Op::CompactContext).await;
             }
+            AppAction::ClearToolResultCache => {
+                let _ = engine_handle.send(Op::ClearToolResultCache).await;
+            }
+            AppAction::SetThinkingBudget(budget) => {
+                let _ = engine_handle.send(Op::SetThinkingBudget { budget }).await;
+            }
+            AppAction::SetTrustLevel(level) => {
+                let _ = engine_handle.send(Op::SetTrustLevel { level }).await;
+            }
+            AppAction::SetTrail(enabled) => {
+                let _ = engine_handle.send(Op::SetTrail { enabled }).await;
+            }
+            AppAction::GenerateReport => {
+                let events = app.recent_engine_events.iter().cloned().collect();
+                match crate::report::write_report(
+                    config,
+                    &app.workspace,
+                    app.config_path.as_deref(),
+                    crate::report::EventSource::Tui(events),
+                    None,
+                ) {
+                    Ok(path) => app.add_message(HistoryCell::System {
+                        content: format!(
+                            "Report written to {}\nSecrets are redacted, but review it before attaching it to an issue.",
+                            path.display()
+                        ),
+                    }),
+                    Err(err) => {
+                        app.status_message = Some(format!("Report failed: {err:#}"));
+                    }
+                }
+            }
             AppAction::TaskAdd { prompt } => {
                 let request = NewTaskRequest {
                     prompt: prompt.clone(),
@@ -4624,6 +6096,11 @@ async fn apply_command_result(
                 }
                 refresh_active_task_panel(app, task_manager).await;
             }
+            AppAction::TaskPanel => {
+                let tasks = task_manager.list_tasks(None).await;
+                app.view_stack
+                    .push(TaskManagerView::new(tasks, app.pinned_task_ids.clone()));
+            }
             AppAction::TaskList => {
                 let tasks = task_manager.list_tasks(Some(30)).await;
                 refresh_active_task_panel(app, task_manager).await;
@@ -4657,8 +6134,11 @@ async fn apply_command_result(
             AppAction::ShellJob(action) => {
                 handle_shell_job_action(app, action);
             }
+            AppAction::ShellSession(action) => {
+                handle_shell_session_action(app, action);
+            }
             AppAction::Mcp(action) => {
-                handle_mcp_ui_action(app, config, action).await;
+                handle_mcp_ui_action(app, engine_handle, config, action).await;
             }
             AppAction::SwitchWorkspace { workspace } => {
                 switch_workspace(app, engine_handle, task_manager, config, workspace).await;
@@ -4669,6 +6149,17 @@ async fn apply_command_result(
                     Ok(new_config) => {
                         *config = new_config.clone();
                         app.api_provider = config.api_provider();
+                        app.features = config.features();
+                        app.exec_profiles = config.exec_profiles.clone().unwrap_or_default();
+                        // Keep the selected exec profile only if the new config
+                        // still defines it, re-read from the new table.
+                        let exec_profile = app.exec_profile.get().and_then(|active| {
+                            let table = app.exec_profiles.get(&active.name)?;
+                            ExecProfile::from_config(&active.name, table).ok()
+                        });
+                        app.exec_profile.set(exec_profile);
+                        app.session_env
+                            .replace_from_config(&config.env.clone().unwrap_or_default());
                         let new_model = config.default_model();
                         app.set_model_selection(new_model.clone());
                         app.update_model_compaction_budget();
@@ -4713,8 +6204,11 @@ async fn apply_command_result(
                 let status = if app.api_messages.is_empty() {
                     "No session content to share.".to_string()
                 } else {
-                    let history_json = serde_json::to_string_pretty(&app.api_messages)
-                        .unwrap_or_else(|_| "[]".to_string());
+                    let mut history = serde_json::to_value(&app.api_messages)
+                        .unwrap_or_else(|_| serde_json::json!([]));
+                    crate::report::redact_json(&mut history);
+                    let history_json =
+                        serde_json::to_string_pretty(&history).unwrap_or_else(|_| "[]".to_string());
                     match crate::commands::share::perform_share(&history_json, &model, &mode).await
                     {
                         Ok(url) => format!("Session shared! URL: {url}"),
@@ -4788,6 +6282,10 @@ fn apply_workspace_runtime_state(app: &mut App, config: &Config, workspace: Path
     }
     app.workspace_context_refreshed_at = None;
     app.file_tree = None;
+    app.sidebar_file_tree = None;
+    app.sidebar_tree_focused = false;
+    app.agent_touched_files.clear();
+    app.session_changes.clear();
 
     let shell_manager = crate::tools::shell::new_shared_shell_manager(workspace);
     app.runtime_services.shell_manager = Some(shell_manager);
@@ -4844,14 +6342,70 @@ async fn switch_workspace(
     app.status_message = Some(format!("Workspace: {}", workspace.display()));
 }
 
+/// Insert a fetched `/mcp prompt` into the composer for the user to edit.
+fn mcp_prompt_fetched(
+    app: &mut App,
+    server: &str,
+    name: &str,
+    result: Result<serde_json::Value, String>,
+) {
+    match result {
+        Ok(result) => {
+            let text = crate::mcp::prompt_result_text(&result);
+            if text.trim().is_empty() {
+                add_mcp_message(
+                    app,
+                    format!("MCP prompt '{server}/{name}' returned no text"),
+                );
+            } else {
+                app.insert_str(&text);
+                app.status_message = Some(format!(
+                    "Inserted MCP prompt '{server}/{name}' — edit and send"
+                ));
+            }
+        }
+        Err(err) => add_mcp_message(app, format!("MCP prompt failed: {err}")),
+    }
+}
+
 async fn handle_mcp_ui_action(
     app: &mut App,
+    engine_handle: &EngineHandle,
     config: &Config,
     action: crate::tui::app::McpUiAction,
 ) {
     use crate::mcp::{self, McpWriteStatus};
 
     let path = app.mcp_config_path.clone();
+    let network_policy = || {
+        config.network.clone().map(|toml_cfg| {
+            crate::network_policy::NetworkPolicyDecider::with_default_audit(toml_cfg.into_runtime())
+        })
+    };
+
+    if let crate::tui::app::McpUiAction::Prompt {
+        server,
+        name,
+        arguments,
+    } = action
+    {
+        let status = format!("Fetching MCP prompt '{server}/{name}'...");
+        if engine_handle
+            .send(Op::FetchMcpPrompt {
+                server,
+                name,
+                arguments,
+            })
+            .await
+            .is_ok()
+        {
+            app.status_message = Some(status);
+        } else {
+            add_mcp_message(app, "MCP prompt failed: engine is not running".to_string());
+        }
+        return;
+    }
+
     let mut changed = false;
     let mut message = None;
     let discover = mcp_ui_action_refreshes_discovery(&action);
@@ -4909,7 +6463,9 @@ async fn handle_mcp_ui_action(
             mcp::remove_server_config(&path, &name)
                 .map(|()| message = Some(format!("Removed MCP server '{name}'")))
         }
-        crate::tui::app::McpUiAction::Validate | crate::tui::app::McpUiAction::Reload => Ok(()),
+        crate::tui::app::McpUiAction::Validate
+        | crate::tui::app::McpUiAction::Reload
+        | crate::tui::app::McpUiAction::Prompt { .. } => Ok(()),
     };
 
     if let Err(err) = action_result {
@@ -4925,10 +6481,7 @@ async fn handle_mcp_ui_action(
     }
 
     let snapshot_result = if discover {
-        let network_policy = config.network.clone().map(|toml_cfg| {
-            crate::network_policy::NetworkPolicyDecider::with_default_audit(toml_cfg.into_runtime())
-        });
-        mcp::discover_manager_snapshot(&path, network_policy, app.mcp_restart_required).await
+        mcp::discover_manager_snapshot(&path, network_policy(), app.mcp_restart_required).await
     } else {
         mcp::manager_snapshot_from_config(&path, app.mcp_restart_required)
     };
@@ -4980,6 +6533,21 @@ fn handle_shell_job_action(app: &mut App, action: crate::tui::app::ShellJobActio
             let jobs = manager.list_jobs();
             add_shell_job_message(app, format_shell_job_list(&jobs));
         }
+        crate::tui::app::ShellJobAction::Run { label, command } => {
+            let workspace = app.workspace.to_string_lossy().to_string();
+            match manager.execute(&command, Some(&workspace), 600_000, true) {
+                Ok(result) => {
+                    let id = result.task_id.unwrap_or_default();
+                    add_shell_job_message(
+                        app,
+                        format!(
+                            "{label}: started `{command}` as job {id}. /jobs show {id} for output."
+                        ),
+                    );
+                }
+                Err(err) => add_shell_job_message(app, format!("{label} failed to start: {err}")),
+            }
+        }
         crate::tui::app::ShellJobAction::Show { id } => match manager.inspect_job(&id) {
             Ok(detail) => open_shell_job_pager(app, &detail),
             Err(err) => add_shell_job_message(app, format!("Shell job lookup failed: {err}")),
@@ -5026,6 +6594,100 @@ fn handle_shell_job_action(app: &mut App, action: crate::tui::app::ShellJobActio
     }
 }
 
+fn handle_shell_session_action(app: &mut App, action: crate::tui::app::ShellSessionAction) {
+    use crate::tui::app::ShellSessionAction;
+
+    if action == ShellSessionAction::Detach {
+        match app.attached_shell.take() {
+            Some(name) => {
+                app.status_message = Some(format!("Detached from shell session `{name}`"));
+            }
+            None => app.status_message = Some("No shell session attached".to_string()),
+        }
+        return;
+    }
+
+    let Some(shell_manager) = app.runtime_services.shell_manager.clone() else {
+        add_shell_job_message(app, "Shell job center is not attached.".to_string());
+        return;
+    };
+    let mut manager = match shell_manager.lock() {
+        Ok(manager) => manager,
+        Err(_) => {
+            add_shell_job_message(app, "Shell job center lock is poisoned.".to_string());
+            return;
+        }
+    };
+
+    match action {
+        ShellSessionAction::List => {
+            let sessions = manager.list_sessions();
+            add_shell_job_message(
+                app,
+                format_shell_session_list(&sessions, app.attached_shell.as_deref()),
+            );
+        }
+        ShellSessionAction::Open { name, command } => {
+            let workspace = app.workspace.to_string_lossy().to_string();
+            match manager.open_session(
+                &name,
+                command.as_deref(),
+                Some(&workspace),
+                None,
+                std::collections::HashMap::new(),
+            ) {
+                Ok(result) => {
+                    app.status_message = Some(format!(
+                        "Opened shell session `{name}` ({}); /shells attach {name} to type into it",
+                        result.task_id.unwrap_or_default()
+                    ));
+                }
+                Err(err) => add_shell_job_message(app, format!("Shell open failed: {err}")),
+            }
+        }
+        ShellSessionAction::Attach { name } => {
+            let running = manager.list_sessions().iter().any(|session| {
+                session.name.as_deref() == Some(name.as_str())
+                    && session.status == crate::tools::shell::ShellStatus::Running
+            });
+            if running {
+                app.status_message = Some(format!(
+                    "Attached to `{name}`: input goes to the shell; /shells detach to return"
+                ));
+                app.attached_shell = Some(name);
+            } else {
+                add_shell_job_message(app, format!("No running shell session named `{name}`."));
+            }
+        }
+        // Handled above without taking the manager lock.
+        ShellSessionAction::Detach => {}
+    }
+}
+
+/// Send composer input to the attached shell session instead of the model.
+/// Returns false when no session is attached.
+fn send_input_to_attached_shell(app: &mut App, input: &str) -> bool {
+    let Some(name) = app.attached_shell.clone() else {
+        return false;
+    };
+    let sent = app
+        .runtime_services
+        .shell_manager
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("shell job center is not attached"))
+        .and_then(|shell_manager| {
+            shell_manager
+                .lock()
+                .map_err(|_| anyhow::anyhow!("shell job center lock is poisoned"))?
+                .write_stdin(&name, &format!("{input}\n"), false)
+        });
+    if let Err(err) = sent {
+        app.attached_shell = None;
+        app.status_message = Some(format!("Shell `{name}` input failed ({err}); detached"));
+    }
+    true
+}
+
 async fn execute_command_input(
     terminal: &mut AppTerminal,
     app: &mut App,
@@ -5090,6 +6752,9 @@ async fn steer_user_message(
     app.add_message(HistoryCell::User {
         content: format!("+ {}", message.display),
     });
+    if let Some(share) = app.share.as_ref() {
+        share.hub().publish_user_message(&message.display);
+    }
     let history_cell = app.history.len().saturating_sub(1);
     app.record_context_references(history_cell, message_index, references);
     app.api_messages.push(Message {
@@ -5281,6 +6946,64 @@ async fn apply_plan_choice(
     Ok(())
 }
 
+/// Close out a `/preview` turn: parse the reply into a plan and open the
+/// preview modal. Returns `true` when the modal was shown, so the ordinary
+/// plan-confirmation prompt stays out of the way.
+fn finish_plan_preview(
+    app: &mut App,
+    status: crate::core::events::TurnOutcomeStatus,
+    reply: &str,
+) -> bool {
+    let Some(request) = app.plan_preview_request.take() else {
+        return false;
+    };
+    if status != crate::core::events::TurnOutcomeStatus::Completed {
+        return false;
+    }
+    let Some(preview) = crate::tui::plan_preview::PlanPreview::parse(&request, reply) else {
+        app.status_message =
+            Some("Preview reply had no numbered steps; nothing to execute.".to_string());
+        return false;
+    };
+    app.plan_preview = Some(preview.clone());
+    if app.view_stack.top_kind() != Some(ModalKind::PlanPreview) {
+        app.view_stack
+            .push(crate::tui::plan_preview::PlanPreviewView::new(preview));
+    }
+    true
+}
+
+/// Switch to Agent mode and send the latest `/preview` plan as pinned
+/// context for execution.
+async fn execute_plan_preview(
+    app: &mut App,
+    config: &Config,
+    engine_handle: &EngineHandle,
+) -> Result<()> {
+    let Some(preview) = app.plan_preview.take() else {
+        app.status_message = Some("No plan preview to run.".to_string());
+        return Ok(());
+    };
+    app.set_mode(AppMode::Agent);
+    app.add_message(HistoryCell::System {
+        content: format!(
+            "Running the previewed plan ({} step(s)) in Agent mode.",
+            preview.steps.len()
+        ),
+    });
+    let followup = QueuedMessage::new(
+        "Execute the previewed plan.".to_string(),
+        Some(preview.execution_instruction()),
+    );
+    if app.is_loading {
+        app.queue_message(followup);
+        app.status_message = Some("Queued previewed plan execution (agent mode).".to_string());
+    } else {
+        dispatch_user_message(app, config, engine_handle, followup).await?;
+    }
+    Ok(())
+}
+
 async fn handle_plan_choice(
     app: &mut App,
     config: &Config,
@@ -5350,6 +7073,10 @@ fn build_pending_input_preview(app: &App) -> PendingInputPreview {
         .iter()
         .map(|m| m.display.clone())
         .collect();
+    preview.lint_hints = app
+        .visible_prompt_hint()
+        .map(|hint| hint.hints.iter().map(|h| h.suggestion()).collect())
+        .unwrap_or_default();
     preview
 }
 
@@ -5443,6 +7170,9 @@ fn render(f: &mut Frame, app: &mut App) {
         } else {
             app.turn_started_at
         };
+        let cache_saved = app.cache_saved_for_currency(app.cost_currency);
+        let cache_savings_label = (cache_saved >= 0.0001)
+            .then(|| format!("cache saved {}", app.format_cost_amount(cache_saved)));
         let header_data = HeaderData::new(
             app.mode,
             &model_label,
@@ -5456,8 +7186,18 @@ fn render(f: &mut Frame, app: &mut App) {
             app.session.session_cost,
             sanitized_prompt_tokens,
         )
+        .with_compaction_threshold(
+            app.auto_compact
+                .then(|| u32::try_from(app.compact_threshold).unwrap_or(u32::MAX)),
+        )
         .with_reasoning_effort(Some(&effort_label))
+        .with_cache_savings(cache_savings_label)
         .with_provider(provider_label)
+        .with_trust_level(Some(app.trust_level))
+        .with_inbox_unread(app.inbox.unread_count())
+        .with_tabs(&app.session_tabs, app.active_session_tab)
+        .with_active_skills(&app.auto_skills)
+        .with_startup(app.startup.header_label())
         .with_status_indicator(crate::tui::widgets::header_status_indicator_frame(
             status_indicator_started_at,
             &app.status_indicator,
@@ -5509,11 +7249,37 @@ fn render(f: &mut Frame, app: &mut App) {
             sidebar_area = Some(split[1]);
         }
 
+        // Optional tool-output pane between the transcript and the sidebar.
+        if app.tool_output_pane.visible
+            && chat_area.width >= tool_output_pane::TOOL_OUTPUT_PANE_MIN_CHAT_WIDTH
+        {
+            let split = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .split(chat_area);
+            chat_area = split[0];
+            tool_output_pane::render_tool_output_pane(
+                f,
+                split[1],
+                &app.tool_output_pane,
+                app.ui_theme.mode,
+            );
+        }
+
         let chat_widget = ChatWidget::new(app, chat_area);
         let buf = f.buffer_mut();
         chat_widget.render(chat_area, buf);
 
         if let Some(sidebar_area) = sidebar_area {
+            if app.sidebar_focus == SidebarFocus::Files {
+                let tree = app.sidebar_file_tree.get_or_insert_with(|| {
+                    let mut tree = crate::tui::file_tree::FileTreeState::new(&app.workspace);
+                    tree.set_touched(&app.agent_touched_files);
+                    tree
+                });
+                tree.poll_loading();
+                tree.adjust_scroll(sidebar_area.height.saturating_sub(3) as usize);
+            }
             super::sidebar::render_sidebar(f, sidebar_area, app);
         }
     }
@@ -5638,7 +7404,7 @@ fn open_backtrack_overlay(app: &mut App) {
     app.needs_redraw = true;
 }
 
-/// Toggle the live transcript overlay on `Ctrl+T`. Closes the overlay if it's
+/// Toggle the live transcript overlay on `Alt+T`. Closes the overlay if it's
 /// already on top; otherwise pushes a fresh one in sticky-tail mode.
 fn toggle_live_transcript_overlay(app: &mut App) {
     if app.view_stack.top_kind() == Some(ModalKind::LiveTranscript) {
@@ -5690,10 +7456,23 @@ async fn handle_view_events(
                 crate::tui::views::CommandPaletteAction::OpenTextPager { title, content } => {
                     open_text_pager(app, title, content);
                 }
+                crate::tui::views::CommandPaletteAction::ResumeSession { session_id } => {
+                    resume_saved_session(app, config, task_manager, engine_handle, &session_id)
+                        .await;
+                }
+                crate::tui::views::CommandPaletteAction::InsertPath { path } => {
+                    insert_path_mention(app, &path);
+                }
+                crate::tui::views::CommandPaletteAction::OpenFile { path } => {
+                    open_file_pager(app, &path);
+                }
             },
             ViewEvent::OpenTextPager { title, content } => {
                 open_text_pager(app, title, content);
             }
+            ViewEvent::OpenDiffPager { title, diff } => {
+                open_diff_pager(app, title, &diff);
+            }
             ViewEvent::CopyToClipboard { text, label } => {
                 if text.is_empty() {
                     app.status_message = Some(format!("{label} is empty"));
@@ -5711,38 +7490,53 @@ async fn handle_view_events(
                 approval_key,
                 approval_grouping_key,
             } => {
-                if decision == ReviewDecision::ApprovedForSession {
-                    // Store the tool name (backward compat) and the lossy
-                    // grouping key so later flag variants of the same
-                    // command family are also auto-approved (v0.8.37).
-                    app.approval_session_approved.insert(tool_name.clone());
-                    app.approval_session_approved
-                        .insert(approval_grouping_key.clone());
-                }
-
-                match decision {
-                    ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {
-                        let _ = engine_handle.approve_tool_call(tool_id).await;
-                    }
-                    ReviewDecision::Denied | ReviewDecision::Abort => {
-                        // Cache the denial so the model retry-loop doesn't
-                        // re-prompt for the exact same approval_key (#360).
-                        // Only the key (per-call unique) is stored — NOT
-                        // the tool_name, which would block all future
-                        // invocations of the same tool type (#1377).
-                        if !timed_out {
-                            app.approval_session_denied.insert(approval_key);
+                let queued = QueuedDecision {
+                    tool_id,
+                    tool_name,
+                    decision,
+                    timed_out,
+                    approval_key,
+                    approval_grouping_key,
+                };
+                // A card opened from the queue answers through the queue so
+                // the engine still hears back in request order.
+                let ready = match app.view_stack.find_mut::<ApprovalQueueView>() {
+                    Some(queue) if queue.contains(&queued.tool_id) => {
+                        queue.record(&queued.tool_id, queued.decision, queued.timed_out);
+                        let ready = queue.take_ready();
+                        if queue.is_empty() {
+                            app.view_stack.remove_kind(ModalKind::ApprovalQueue);
                         }
-                        let _ = engine_handle.deny_tool_call(tool_id).await;
+                        ready
                     }
+                    _ => vec![queued],
+                };
+                for queued in ready {
+                    apply_approval_decision(app, engine_handle, queued).await;
                 }
-
-                if timed_out {
-                    app.add_message(HistoryCell::System {
-                        content: "Approval request timed out - denied".to_string(),
-                    });
+            }
+            ViewEvent::ApprovalQueueResolved { decisions } => {
+                for queued in decisions {
+                    apply_approval_decision(app, engine_handle, queued).await;
                 }
             }
+            ViewEvent::SessionEnvProvided { key, value } => {
+                app.status_message = Some(match app.session_env.set_sealed(&key, value.expose()) {
+                    Ok(()) => {
+                        app.add_message(HistoryCell::System {
+                            content: format!(
+                                "Set {key} for this session (encrypted, masked in tool output). Re-run the command to use it."
+                            ),
+                        });
+                        format!("{key} set for this session")
+                    }
+                    Err(err) => format!("Could not set {key}: {err}"),
+                });
+            }
+            ViewEvent::ApprovalQueueOpen { request } => {
+                app.view_stack
+                    .push(ApprovalView::new_for_locale(*request, app.ui_locale));
+            }
             ViewEvent::ElevationDecision {
                 tool_id,
                 tool_name,
@@ -5770,6 +7564,17 @@ async fn handle_view_events(
                         let policy = option.to_policy(&app.workspace);
                         let _ = engine_handle.retry_tool_with_policy(tool_id, policy).await;
                     }
+                    ElevationOption::RaiseLimits(kind) => {
+                        app.add_message(HistoryCell::System {
+                            content: format!(
+                                "Retrying {tool_name} with a higher {} limit",
+                                kind.label()
+                            ),
+                        });
+                        let _ = engine_handle
+                            .retry_tool_with_higher_limits(tool_id, kind)
+                            .await;
+                    }
                     ElevationOption::FullAccess => {
                         app.add_message(HistoryCell::System {
                             content: format!("Retrying {tool_name} with full access (no sandbox)"),
@@ -5780,10 +7585,30 @@ async fn handle_view_events(
                 }
             }
             ViewEvent::UserInputSubmitted { tool_id, response } => {
-                let _ = engine_handle.submit_user_input(tool_id, response).await;
+                if take_restored_user_input(app, &tool_id) {
+                    let _ = engine_handle
+                        .send(Op::ResumePausedInput {
+                            tool_id,
+                            outcome: Ok(response),
+                            mode: app.mode,
+                        })
+                        .await;
+                } else {
+                    let _ = engine_handle.submit_user_input(tool_id, response).await;
+                }
             }
             ViewEvent::UserInputCancelled { tool_id } => {
-                let _ = engine_handle.cancel_user_input(tool_id).await;
+                if take_restored_user_input(app, &tool_id) {
+                    let _ = engine_handle
+                        .send(Op::ResumePausedInput {
+                            tool_id,
+                            outcome: Err("User input cancelled".to_string()),
+                            mode: app.mode,
+                        })
+                        .await;
+                } else {
+                    let _ = engine_handle.cancel_user_input(tool_id).await;
+                }
                 app.add_message(HistoryCell::System {
                     content: "User input cancelled".to_string(),
                 });
@@ -5799,52 +7624,33 @@ async fn handle_view_events(
                     }
                 }
             }
+            ViewEvent::PlanPreviewExecute => {
+                if let Err(err) = execute_plan_preview(app, config, engine_handle).await {
+                    app.status_message = Some(format!("Failed to run plan preview: {err}"));
+                }
+            }
+            ViewEvent::PasteResolved {
+                text,
+                choice,
+                language,
+            } => app.resolve_paste(&text, choice, language),
+            ViewEvent::BookmarkJump { cell_index } => {
+                if !crate::tui::bookmarks::jump_to(app, cell_index) {
+                    app.status_message =
+                        Some("Bookmarked message is hidden or no longer shown".to_string());
+                }
+            }
+            ViewEvent::BookmarkRemoved { cell_index } => {
+                app.bookmarks
+                    .retain(|bookmark| bookmark.cell_index != cell_index);
+            }
             ViewEvent::PlanPromptDismissed => {
                 app.plan_prompt_pending = true;
                 app.status_message =
                     Some("Plan prompt closed. Type 1-4 and press Enter to choose.".to_string());
             }
             ViewEvent::SessionSelected { session_id } => {
-                let manager = match SessionManager::default_location() {
-                    Ok(manager) => manager,
-                    Err(err) => {
-                        app.status_message =
-                            Some(format!("Failed to open sessions directory: {err}"));
-                        continue;
-                    }
-                };
-
-                match manager.load_session(&session_id) {
-                    Ok(session) => {
-                        let recovered = apply_loaded_session(app, config, &session);
-                        sync_runtime_workspace_state(task_manager, app.workspace.clone()).await;
-                        let _ = engine_handle
-                            .send(Op::SyncSession {
-                                session_id: app.current_session_id.clone(),
-                                messages: app.api_messages.clone(),
-                                system_prompt: app.system_prompt.clone(),
-                                system_prompt_override: false,
-                                model: app.model.clone(),
-                                workspace: app.workspace.clone(),
-                            })
-                            .await;
-                        let _ = engine_handle
-                            .send(Op::SetCompaction {
-                                config: app.compaction_config(),
-                            })
-                            .await;
-                        if !recovered {
-                            app.status_message = Some(format!(
-                                "Session loaded (ID: {})",
-                                &session_id[..8.min(session_id.len())]
-                            ));
-                        }
-                    }
-                    Err(err) => {
-                        app.status_message =
-                            Some(format!("Failed to load session {session_id}: {err}"));
-                    }
-                }
+                resume_saved_session(app, config, task_manager, engine_handle, &session_id).await;
             }
             ViewEvent::SessionDeleted { session_id, title } => {
                 app.status_message = Some(format!(
@@ -5902,35 +7708,63 @@ async fn handle_view_events(
                     }
                 }
             }
+            ViewEvent::TaskManagerRequested { action } => {
+                handle_task_manager_action(app, task_manager, action).await;
+            }
+            ViewEvent::SessionChangesRequested { action } => {
+                handle_session_changes_action(app, action);
+            }
+            ViewEvent::InboxRequested { action } => handle_inbox_action(app, action),
+            ViewEvent::ReviewDiffRequested { action } => match action {
+                ReviewDiffAction::SetAddressed { key, addressed } => {
+                    if addressed {
+                        app.review_addressed.insert(key);
+                    } else {
+                        app.review_addressed.remove(&key);
+                    }
+                }
+                ReviewDiffAction::Fix { prompt } => {
+                    let queued = build_queued_message(app, prompt);
+                    submit_or_steer_message(app, config, engine_handle, queued).await?;
+                }
+            },
             ViewEvent::SubAgentsRefresh => {
                 app.status_message = Some("Refreshing sub-agents...".to_string());
                 let _ = engine_handle.send(Op::ListSubAgents).await;
             }
+            ViewEvent::SubAgentCancel { agent_id } => {
+                match engine_handle.cancel_subagent(&agent_id).await {
+                    Ok(snapshot) => {
+                        // The op-based list refresh waits for the running
+                        // turn, so reflect the cancel in the view right away.
+                        if let Some(cached) = app
+                            .subagent_cache
+                            .iter_mut()
+                            .find(|agent| agent.agent_id == snapshot.agent_id)
+                        {
+                            *cached = snapshot;
+                        }
+                        let cache = app.subagent_cache.clone();
+                        let view_agents = subagent_view_agents(app, &cache);
+                        app.view_stack.update_subagents(&view_agents);
+                        app.status_message =
+                            Some(format!("Cancelling {agent_id}; partial output will follow"));
+                    }
+                    Err(err) => {
+                        app.status_message = Some(format!("Could not cancel {agent_id}: {err}"));
+                    }
+                }
+                let _ = engine_handle.send(Op::ListSubAgents).await;
+            }
             ViewEvent::FilePickerSelected { path } => {
-                // Insert `@<path>` at the composer's cursor with surrounding
-                // whitespace so the existing `@`-mention parser picks it up.
-                let cursor = app.cursor_position;
-                let needs_leading_space = cursor > 0
-                    && !app
-                        .input
-                        .chars()
-                        .nth(cursor.saturating_sub(1))
-                        .is_some_and(|c| c.is_whitespace());
-                let mut insertion = String::new();
-                if needs_leading_space {
-                    insertion.push(' ');
-                }
-                insertion.push('@');
-                insertion.push_str(&path);
-                insertion.push(' ');
-                app.insert_str(&insertion);
-                app.status_message = Some(format!("Attached @{path}"));
+                insert_path_mention(app, &path);
             }
             ViewEvent::ModelPickerApplied {
                 model,
                 effort,
                 previous_model,
                 previous_effort,
+                set_default,
             } => {
                 apply_model_picker_choice(
                     app,
@@ -5939,6 +7773,7 @@ async fn handle_view_events(
                     effort,
                     previous_model,
                     previous_effort,
+                    set_default,
                 )
                 .await;
             }
@@ -5953,6 +7788,10 @@ async fn handle_view_events(
                 let msg = commands::switch_mode(app, mode);
                 app.add_message(HistoryCell::System { content: msg });
             }
+            ViewEvent::QueueUpdated { messages } => {
+                app.queued_messages = messages.into();
+                app.needs_redraw = true;
+            }
             ViewEvent::BacktrackStep { direction } => {
                 app.backtrack.step(direction);
                 if let Some(idx) = app.backtrack.selected_idx() {
@@ -5979,6 +7818,11 @@ async fn handle_view_events(
                 app.status_message = Some("Backtrack canceled".to_string());
                 app.needs_redraw = true;
             }
+            ViewEvent::ContextMenuSelected {
+                action: ContextMenuAction::RerunTool { cell_index },
+            } => {
+                rerun_tool_cell(app, engine_handle, cell_index).await;
+            }
             ViewEvent::ContextMenuSelected { action } => {
                 handle_context_menu_action(app, action);
             }
@@ -5991,6 +7835,13 @@ async fn handle_view_events(
                 mark_active_turn_cancelled_locally(app);
                 app.status_message = Some("Request cancelled".to_string());
             }
+            ViewEvent::LoopAbortTurn => {
+                if app.is_loading {
+                    engine_handle.cancel();
+                    mark_active_turn_cancelled_locally(app);
+                    app.status_message = Some("Turn aborted after a tool loop".to_string());
+                }
+            }
         }
     }
 
@@ -6130,6 +7981,7 @@ fn apply_backtrack(app: &mut App, depth: usize) {
     }
     if let Some(idx) = cut {
         app.api_messages.truncate(idx);
+        app.api_message_times.truncate(idx);
     }
 
     // Hand the dropped text back to the user so they can edit + resend.
@@ -6209,7 +8061,20 @@ async fn apply_provider_picker_api_key(
 }
 
 fn apply_loaded_session(app: &mut App, config: &Config, session: &SavedSession) -> bool {
-    let (messages, recovered_draft) = recover_interrupted_user_tail(&session.messages);
+    // A turn paused on a question resumes at the assistant's tool calls;
+    // anything written after them while the app shut down is dropped.
+    let paused = session.paused_input.as_ref().and_then(|paused| {
+        paused_call_index(&session.messages, &paused.tool_id).map(|index| (paused, index))
+    });
+    let (messages, recovered_draft) = match paused {
+        Some((_, index)) => (session.messages[..=index].to_vec(), None),
+        None => recover_interrupted_user_tail(&session.messages),
+    };
+    app.api_message_times = if session.message_timestamps.len() == session.messages.len() {
+        session.message_timestamps[..messages.len()].to_vec()
+    } else {
+        Vec::new()
+    };
     app.api_messages = messages;
     app.clear_history();
     app.tool_cells.clear();
@@ -6251,6 +8116,7 @@ fn apply_loaded_session(app: &mut App, config: &Config, session: &SavedSession)
         app.extend_history(cells);
     }
     app.sync_context_references_from_session(&session.context_references, &message_to_cell);
+    app.bookmarks = crate::tui::bookmarks::restore(&app.history, &session.bookmarks);
     app.mark_history_updated();
     app.viewport.transcript_selection.clear();
     app.set_model_selection(session.metadata.model.clone());
@@ -6263,6 +8129,10 @@ fn apply_loaded_session(app: &mut App, config: &Config, session: &SavedSession)
     app.session.subagent_cost = session.metadata.cost.subagent_cost_usd;
     app.session.subagent_cost_cny = session.metadata.cost.subagent_cost_cny;
     app.session.subagent_cost_event_seqs.clear();
+    app.session.cache_hit_tokens_total = session.metadata.cost.cache_hit_tokens;
+    app.session.cache_miss_tokens_total = session.metadata.cost.cache_miss_tokens;
+    app.session.cache_saved = session.metadata.cost.cache_saved_usd;
+    app.session.cache_saved_cny = session.metadata.cost.cache_saved_cny;
     // Restore the high-water marks from persisted metadata so the
     // monotonic cost guarantee (#244) survives session restarts.
     // Take the max with the current totals — old sessions without
@@ -6296,7 +8166,17 @@ fn apply_loaded_session(app: &mut App, config: &Config, session: &SavedSession)
     } else {
         app.system_prompt = None;
     }
-    let recovered = if let Some(draft) = recovered_draft {
+    app.paused_user_input = paused.map(|(paused, _)| paused.clone());
+    app.paused_user_input_restored = app.paused_user_input.is_some();
+    let recovered = if let Some(paused) = app.paused_user_input.clone() {
+        let view = UserInputView::new(paused.tool_id, paused.request)
+            .with_workspace(app.workspace.clone());
+        app.view_stack.push(view);
+        app.status_message = Some(
+            "Resumed a turn paused on a question; answer it to continue (Esc cancels).".to_string(),
+        );
+        true
+    } else if let Some(draft) = recovered_draft {
         restore_recovered_retry_draft(app, draft);
         true
     } else {
@@ -6306,6 +8186,19 @@ fn apply_loaded_session(app: &mut App, config: &Config, session: &SavedSession)
     recovered
 }
 
+/// Clear the open question; `true` when it was restored from a saved session
+/// (the answer must resume the turn rather than reach a waiting engine).
+fn take_restored_user_input(app: &mut App, tool_id: &str) -> bool {
+    let restored = app.paused_user_input_restored
+        && app
+            .paused_user_input
+            .as_ref()
+            .is_some_and(|paused| paused.tool_id == tool_id);
+    app.paused_user_input = None;
+    app.paused_user_input_restored = false;
+    restored
+}
+
 /// Derive a short display title from the API message list.
 /// Skips the `<turn_meta>` block prepended by the engine and takes the first
 /// real user-text block, truncated to 32 characters.
@@ -6778,6 +8671,13 @@ pub(crate) fn context_usage_snapshot(app: &App) -> Option<(i64, u32, f64)> {
         .map(i64::from)
         .map(|tokens| tokens.max(0));
     let estimated = estimated_context_tokens(app).map(|tokens| tokens.max(0));
+    // While a turn streams, the engine reports the request the model is
+    // answering (prompt + output so far). It moves with every round and
+    // every streamed chunk, so it wins over both figures below.
+    let live = app
+        .session
+        .live_context
+        .map(|live| i64::from(live.total_tokens()));
 
     // Always prefer the estimated current-context size (computed from
     // `app.api_messages`) when we have it. Reported `last_prompt_tokens`
@@ -6791,10 +8691,11 @@ pub(crate) fn context_usage_snapshot(app: &App) -> Option<(i64, u32, f64)> {
     // up" indicator should show. We still consult `reported` only as a
     // fallback when no estimate is available (e.g., immediately after a
     // session restore before the api_messages are populated).
-    let used = match (estimated, reported) {
-        (Some(estimated), _) => estimated.min(max_i64),
-        (None, Some(reported)) => reported.min(max_i64),
-        (None, None) => return None,
+    let used = match (live, estimated, reported) {
+        (Some(live), _, _) => live.min(max_i64),
+        (None, Some(estimated), _) => estimated.min(max_i64),
+        (None, None, Some(reported)) => reported.min(max_i64),
+        (None, None, None) => return None,
     };
 
     let max_f64 = f64::from(max);
@@ -6858,6 +8759,14 @@ fn should_auto_compact_before_send(app: &App) -> bool {
         .unwrap_or(false)
 }
 
+/// Context is past the warning level, so an idle stretch is a good time to
+/// compact before the pre-send check has to.
+fn should_compact_while_idle(app: &App) -> bool {
+    app.auto_compact
+        && context_usage_snapshot(app)
+            .is_some_and(|(_, _, pct)| pct >= CONTEXT_WARNING_THRESHOLD_PERCENT)
+}
+
 fn status_animation_interval_ms(app: &App) -> u64 {
     if app.low_motion {
         2_400
@@ -7327,6 +9236,27 @@ fn activity_cell_to_text(cell: &HistoryCell, width: u16) -> String {
         .join("\n")
 }
 
+/// Ask the engine to run a cell's recorded tool call again. Re-runs are
+/// tool-only turns, so they wait for any turn in flight.
+async fn rerun_tool_cell(app: &mut App, engine_handle: &EngineHandle, cell_index: usize) {
+    if app.is_loading {
+        app.status_message = Some("Wait for the current turn to finish before re-running".into());
+        return;
+    }
+    let Some(detail) = app.tool_detail_record_for_cell(cell_index).cloned() else {
+        app.status_message = Some("No recorded tool call for that cell".to_string());
+        return;
+    };
+    app.status_message = Some(format!("Re-running {}", detail.tool_name));
+    let _ = engine_handle
+        .send(Op::RerunToolCall {
+            tool_name: detail.tool_name,
+            input: detail.input,
+            mode: app.mode,
+        })
+        .await;
+}
+
 fn open_tool_details_pager(app: &mut App) -> bool {
     let target_cell = detail_target_cell_index(app);
 
@@ -7379,7 +9309,7 @@ pub(crate) fn open_details_pager_for_cell(app: &mut App, cell_index: usize) -> b
         // model received against the full payload.
         let spillover_section = spillover_pager_section(app, cell_index);
 
-        let content = if let Some(section) = spillover_section {
+        let mut content = if let Some(section) = spillover_section {
             format!(
                 "Tool ID: {}\nTool: {}\n\nInput:\n{}\n\nOutput:\n{}\n\n{}",
                 detail.tool_id, detail.tool_name, input, output, section
@@ -7390,6 +9320,12 @@ pub(crate) fn open_details_pager_for_cell(app: &mut App, cell_index: usize) -> b
                 detail.tool_id, detail.tool_name, input, output
             )
         };
+        // File-writing tools report the exact before/after diff of what
+        // they wrote, whatever their model-facing output looked like.
+        if let Some(audit) = detail.write_audit.as_ref() {
+            content.push_str("\n\n");
+            content.push_str(&audit.render());
+        }
 
         let width = app
             .viewport
@@ -7432,6 +9368,49 @@ pub(crate) fn open_details_pager_for_cell(app: &mut App, cell_index: usize) -> b
     true
 }
 
+/// One-line summary of what write tools changed during a turn.
+fn turn_write_summary(stats: &TurnWriteStats) -> String {
+    let count = stats.files.len();
+    let noun = if count == 1 { "file" } else { "files" };
+    let listed = stats
+        .files
+        .iter()
+        .take(3)
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let more = count.saturating_sub(3);
+    let more = if more > 0 {
+        format!(" and {more} more")
+    } else {
+        String::new()
+    };
+    format!(
+        "Changed {count} {noun} this turn (+{} -{}): {listed}{more}. \
+         Alt+V on a write tool shows its diff.",
+        stats.added, stats.removed
+    )
+}
+
+fn toggle_bookmark(app: &mut App, cell_index: usize) {
+    match crate::tui::bookmarks::toggle(app, cell_index) {
+        Some(true) => app.push_status_toast(
+            format!(
+                "Bookmarked ({} total) — `/bookmarks` to jump back",
+                app.bookmarks.len()
+            ),
+            StatusToastLevel::Info,
+            Some(3_000),
+        ),
+        Some(false) => {
+            app.push_status_toast("Bookmark removed", StatusToastLevel::Info, Some(2_000));
+        }
+        None => {
+            app.status_message = Some("Only finished messages can be bookmarked".to_string());
+        }
+    }
+}
+
 /// Copy the "focused" transcript cell to the system clipboard.
 /// The focused cell is determined by the detail-target heuristic
 /// (viewport centre or most recent cell). Returns true when text
@@ -1017,6 +1017,51 @@ fn right_click_menu_includes_selection_and_clicked_cell_actions() {
     assert!(labels.contains(&"Paste"));
 }
 
+#[test]
+fn tool_context_menu_offers_copy_save_and_rerun_for_recorded_tool() {
+    let mut app = create_test_app();
+    app.history = vec![HistoryCell::Tool(ToolCell::Generic(GenericToolCell {
+        name: "exec_shell".to_string(),
+        status: ToolStatus::Success,
+        input_summary: None,
+        output: Some("hi".to_string()),
+        prompts: None,
+        spillover_path: None,
+        output_summary: None,
+        is_diff: false,
+    }))];
+    app.tool_details_by_cell.insert(
+        0,
+        ToolDetailRecord {
+            tool_id: "exec-1".to_string(),
+            tool_name: "exec_shell".to_string(),
+            input: serde_json::json!({"command": "echo hi"}),
+            output: Some("hi".to_string()),
+            write_audit: None,
+        },
+    );
+
+    let actions = tool_context_menu_entries(&app, 0)
+        .into_iter()
+        .map(|entry| entry.action)
+        .collect::<Vec<_>>();
+    assert!(actions.contains(&ContextMenuAction::CopyToolOutput { cell_index: 0 }));
+    assert!(actions.contains(&ContextMenuAction::SaveToolOutput { cell_index: 0 }));
+    assert!(actions.contains(&ContextMenuAction::RerunTool { cell_index: 0 }));
+    assert!(!actions.contains(&ContextMenuAction::OpenSpilledOutput { cell_index: 0 }));
+
+    app.tool_details_by_cell
+        .get_mut(&0)
+        .expect("detail")
+        .tool_name = "agent_spawn".to_string();
+    assert!(
+        !tool_context_menu_entries(&app, 0)
+            .iter()
+            .any(|entry| matches!(entry.action, ContextMenuAction::RerunTool { .. }))
+    );
+    assert!(tool_context_menu_entries(&app, 1).is_empty());
+}
+
 #[test]
 fn mouse_events_do_not_mutate_transcript_behind_modal() {
     let mut app = create_test_app();
@@ -1282,11 +1327,18 @@ fn saved_session_with_messages(messages: Vec<Message>) -> SavedSession {
             cost: crate::session_manager::SessionCostSnapshot::default(),
             parent_session_id: None,
             forked_from_message_count: None,
+            summary: None,
+            awaiting_input: None,
+            pinned: false,
+            tags: Vec::new(),
         },
         messages,
         system_prompt: None,
         context_references: Vec::new(),
         artifacts: Vec::new(),
+        message_timestamps: Vec::new(),
+        paused_input: None,
+        bookmarks: Vec::new(),
     }
 }
 
@@ -1323,6 +1375,46 @@ fn apply_loaded_session_restores_dangling_user_tail_as_retry_draft() {
     );
 }
 
+#[test]
+fn apply_loaded_session_reopens_question_the_turn_paused_on() {
+    let mut app = create_test_app();
+    let mut session = saved_session_with_messages(vec![
+        text_message("user", "cut the release"),
+        Message {
+            role: "assistant".to_string(),
+            content: vec![ContentBlock::ToolUse {
+                id: "call_ask".to_string(),
+                name: "request_user_input".to_string(),
+                input: serde_json::json!({}),
+                caller: None,
+            }],
+        },
+        text_message("user", "typed after the prompt opened"),
+    ]);
+    session.set_paused_input(Some(PausedUserInput {
+        tool_id: "call_ask".to_string(),
+        request: crate::tools::user_input::UserInputRequest {
+            questions: Vec::new(),
+            title: Some("Release channel".to_string()),
+            fields: Vec::new(),
+        },
+    }));
+    assert_eq!(
+        session.metadata.awaiting_input.as_deref(),
+        Some("Release channel")
+    );
+
+    let recovered = apply_loaded_session(&mut app, &Config::default(), &session);
+
+    assert!(recovered);
+    assert_eq!(app.api_messages.len(), 2, "stale tail trimmed");
+    assert!(app.paused_user_input_restored);
+    assert_eq!(app.view_stack.top_kind(), Some(ModalKind::UserInput));
+    assert!(take_restored_user_input(&mut app, "call_ask"));
+    assert!(app.paused_user_input.is_none());
+    assert!(!take_restored_user_input(&mut app, "call_ask"));
+}
+
 #[test]
 fn apply_loaded_session_resets_unpersisted_telemetry() {
     let mut app = create_test_app();
@@ -1520,6 +1612,7 @@ fn active_tool_status_label_summarizes_live_tool_group() {
             duration_ms: None,
             source: ExecSource::Assistant,
             interaction: None,
+            session: None,
             output_summary: None,
         })),
     );
@@ -1562,6 +1655,7 @@ fn active_tool_status_label_strips_shell_wrappers_from_ci_polling() {
             duration_ms: None,
             source: ExecSource::Assistant,
             interaction: None,
+            session: None,
             output_summary: None,
         })),
     );
@@ -1663,6 +1757,31 @@ fn file_mentions_add_local_text_context_to_model_payload() {
     assert_eq!(message.display, "Summarize @guide.md");
 }
 
+#[test]
+fn mode_prompt_wraps_the_payload_but_not_the_display() {
+    let mut app = create_test_app();
+    app.mode_prompts.insert(
+        "plan".to_string(),
+        crate::settings::ModePrompt {
+            prefix: None,
+            suffix: Some("Don't write code yet.".to_string()),
+        },
+    );
+    let message = QueuedMessage::new("Add caching".to_string(), None);
+
+    app.mode = AppMode::Agent;
+    assert_eq!(
+        queued_message_content_for_app(&app, &message, None),
+        "Add caching"
+    );
+    app.mode = AppMode::Plan;
+    assert_eq!(
+        queued_message_content_for_app(&app, &message, None),
+        "Add caching\n\nDon't write code yet."
+    );
+    assert_eq!(message.display, "Add caching");
+}
+
 #[test]
 fn compact_user_context_display_hides_persisted_mention_block() {
     let content = "Summarize @guide.md\n\n---\n\nLocal context from @mentions:\n<file>large</file>";
@@ -2005,6 +2124,50 @@ fn ctrl_alt_4_focuses_agents_sidebar_without_switching_modes() {
     assert_eq!(app.status_message.as_deref(), Some("Sidebar focus: agents"));
 }
 
+#[tokio::test]
+async fn files_sidebar_takes_and_releases_keyboard_focus() {
+    let mut app = create_test_app();
+    focus_files_sidebar(&mut app);
+    assert_eq!(app.sidebar_focus, SidebarFocus::Files);
+    assert!(app.sidebar_tree_focused);
+
+    // Without a built tree nothing is navigable; focus goes back.
+    assert!(!handle_sidebar_tree_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)
+    ));
+    assert!(!app.sidebar_tree_focused);
+
+    app.sidebar_tree_focused = true;
+    app.sidebar_file_tree = Some(crate::tui::file_tree::FileTreeState::new(
+        PathBuf::from(".").as_path(),
+    ));
+    assert!(handle_sidebar_tree_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE)
+    ));
+    assert!(app.sidebar_tree_focused);
+    // Typing anything the tree does not use hands the key to the composer.
+    assert!(!handle_sidebar_tree_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE)
+    ));
+    assert!(!app.sidebar_tree_focused);
+
+    app.set_sidebar_focus(SidebarFocus::Work);
+    app.sidebar_tree_focused = true;
+    app.set_sidebar_focus(SidebarFocus::Tasks);
+    assert!(!app.sidebar_tree_focused);
+}
+
+#[test]
+fn agent_touched_files_are_recent_first_and_deduplicated() {
+    let mut app = create_test_app();
+    app.record_agent_touched_files(vec!["a.rs".to_string(), "b.rs".to_string()]);
+    app.record_agent_touched_files(vec!["a.rs".to_string()]);
+    assert_eq!(app.agent_touched_files, vec!["a.rs", "b.rs"]);
+}
+
 #[test]
 fn alt_0_restores_auto_sidebar_focus() {
     let mut app = create_test_app();
@@ -2516,6 +2679,28 @@ fn context_usage_snapshot_prefers_live_estimate_while_loading() {
     assert!(percent > 0.0);
 }
 
+#[test]
+fn context_usage_snapshot_follows_engine_reported_request_mid_turn() {
+    let mut app = create_test_app();
+    app.is_loading = true;
+    app.api_messages = vec![text_message("user", "short prompt")];
+    app.session.live_context = Some(LiveContextUsage {
+        prompt_tokens: 200_000,
+        output_tokens: 50_000,
+        estimated: false,
+    });
+
+    let (used, max, percent) =
+        context_usage_snapshot(&app).expect("context usage should be available");
+    assert_eq!(used, 250_000);
+    assert_eq!(max, 1_000_000);
+    assert!((percent - 25.0).abs() < f64::EPSILON);
+
+    app.session.live_context = None;
+    let (used, _, _) = context_usage_snapshot(&app).expect("context usage should be available");
+    assert!(used < 1_000, "falls back to the message estimate: {used}");
+}
+
 #[test]
 fn should_auto_compact_before_send_respects_threshold_and_setting() {
     let mut app = create_test_app();
@@ -2647,6 +2832,35 @@ fn history_arrow_does_not_steal_open_menus() {
     assert!(app.history_index.is_none());
 }
 
+#[test]
+fn ghost_suggestion_accepts_history_and_tool_completions() {
+    use crate::tui::autocomplete::{GhostSource, accept_ghost_suggestion, ghost_suggestion};
+
+    let mut app = create_test_app();
+    app.input_history = vec!["summarize the diff".to_string()];
+    app.input = "summ".to_string();
+    app.cursor_position = 4;
+
+    let ghost = ghost_suggestion(&app, &[]).expect("history ghost");
+    assert_eq!(ghost.source, GhostSource::History);
+    assert!(accept_ghost_suggestion(&mut app, &[]));
+    assert_eq!(app.input, "summarize the diff");
+    assert_eq!(app.cursor_position, app.input.chars().count());
+    assert!(ghost_suggestion(&app, &[]).is_none());
+
+    app.input = "please run !git_sta".to_string();
+    app.cursor_position = app.input.chars().count();
+    assert!(accept_ghost_suggestion(&mut app, &[]));
+    assert_eq!(app.input, "please run !git_status");
+
+    // No ghost while the cursor is mid-input or a slash command is typed.
+    app.cursor_position = 3;
+    assert!(!accept_ghost_suggestion(&mut app, &[]));
+    app.input = "/mod".to_string();
+    app.cursor_position = 4;
+    assert!(ghost_suggestion(&app, &[]).is_none());
+}
+
 #[test]
 fn test_ctrl_c_cancels_streaming_sets_status() {
     let mut app = create_test_app();
@@ -3127,6 +3341,8 @@ fn onboarding_after_api_key_save_does_not_repeat_language_step() {
     app.trust_mode = true;
     app.status_message = Some("saved".to_string());
 
+    app.onboarding_configure_defaults = false;
+
     crate::tui::onboarding::advance_onboarding_after_language(&mut app);
 
     assert_eq!(app.onboarding, OnboardingState::Tips);
@@ -3140,10 +3356,36 @@ fn onboarding_after_api_key_save_routes_to_trust_when_needed() {
     app.workspace = tmpdir.path().to_path_buf();
     app.onboarding = OnboardingState::ApiKey;
     app.onboarding_needs_api_key = false;
+    app.onboarding_configure_defaults = false;
+    app.trust_mode = false;
+
+    crate::tui::onboarding::advance_onboarding_after_language(&mut app);
+
+    assert_eq!(app.onboarding, OnboardingState::TrustDirectory);
+}
+
+#[test]
+fn first_run_onboarding_visits_defaults_and_test_prompt_before_trust() {
+    let tmpdir = TempDir::new().expect("tempdir");
+    let mut app = create_test_app();
+    app.workspace = tmpdir.path().to_path_buf();
+    app.onboarding = OnboardingState::Language;
+    app.onboarding_needs_api_key = false;
+    app.onboarding_configure_defaults = true;
     app.trust_mode = false;
 
     crate::tui::onboarding::advance_onboarding_after_language(&mut app);
+    assert_eq!(app.onboarding, OnboardingState::Preferences);
+    assert_eq!(
+        app.onboarding_preferences.selected_model(),
+        app.model_selection_for_persistence()
+    );
+
+    crate::tui::onboarding::advance_onboarding_after_preferences(&mut app);
+    assert_eq!(app.onboarding, OnboardingState::TestPrompt);
+    assert!(!app.onboarding_configure_defaults);
 
+    crate::tui::onboarding::advance_onboarding_to_trust_or_tips(&mut app);
     assert_eq!(app.onboarding, OnboardingState::TrustDirectory);
 }
 
@@ -3283,6 +3525,7 @@ fn detail_target_prefers_visible_tool_card() {
             tool_name: "file_search".to_string(),
             input: serde_json::json!({"query": "foo"}),
             output: Some("done".to_string()),
+            write_audit: None,
         },
     );
     app.tool_details_by_cell.insert(
@@ -3292,6 +3535,7 @@ fn detail_target_prefers_visible_tool_card() {
             tool_name: "exec_shell".to_string(),
             input: serde_json::json!({"command": "ls"}),
             output: Some("...".to_string()),
+            write_audit: None,
         },
     );
     app.resync_history_revisions();
@@ -3469,6 +3713,7 @@ fn terminal_pause_has_live_owner_only_for_running_exec_cells() {
             duration_ms: None,
             source: ExecSource::Assistant,
             interaction: Some("interactive".to_string()),
+            session: None,
             output_summary: None,
         })),
     );
@@ -4014,7 +4259,7 @@ fn app_new_restores_saved_model_and_reasoning_effort() {
 }
 
 #[tokio::test]
-async fn model_picker_persists_model_and_reasoning_effort() {
+async fn model_picker_set_default_persists_model_and_reasoning_effort() {
     let _guard = ConfigPathEnvGuard::new();
     let mut app = create_test_app();
     app.set_model_selection("auto".to_string());
@@ -4028,6 +4273,7 @@ async fn model_picker_persists_model_and_reasoning_effort() {
         ReasoningEffort::High,
         "auto".to_string(),
         ReasoningEffort::Auto,
+        true,
     )
     .await;
 
@@ -4046,6 +4292,36 @@ async fn model_picker_persists_model_and_reasoning_effort() {
     assert_eq!(app.reasoning_effort, ReasoningEffort::High);
 }
 
+#[tokio::test]
+async fn model_picker_apply_without_default_keeps_settings() {
+    let _guard = ConfigPathEnvGuard::new();
+    let mut app = create_test_app();
+    app.set_model_selection("deepseek-v4-pro".to_string());
+    app.reasoning_effort = ReasoningEffort::High;
+    let engine = mock_engine_handle();
+
+    apply_model_picker_choice(
+        &mut app,
+        &engine.handle,
+        "deepseek-v4-flash".to_string(),
+        ReasoningEffort::High,
+        "deepseek-v4-pro".to_string(),
+        ReasoningEffort::High,
+        false,
+    )
+    .await;
+
+    let settings = crate::settings::Settings::load().expect("load settings");
+    assert_eq!(settings.default_model, None);
+    assert_eq!(app.model, "deepseek-v4-flash");
+    assert!(
+        !app.status_message
+            .as_deref()
+            .unwrap_or_default()
+            .contains("saved as default")
+    );
+}
+
 #[test]
 fn apply_loaded_session_restores_artifact_registry() {
     let mut app = create_test_app();
@@ -4114,6 +4390,55 @@ fn parallel_exploring_tool_starts_share_one_active_entry() {
     }
 }
 
+#[test]
+fn changed_read_files_badge_their_read_entries() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    std::fs::write(tmp.path().join("lib.rs"), "fn a() {}").expect("write");
+    let mut app = create_test_app();
+    app.workspace = tmp.path().to_path_buf();
+
+    handle_tool_call_started(
+        &mut app,
+        "t-1",
+        "read_file",
+        &serde_json::json!({"path": "lib.rs"}),
+    );
+    handle_tool_call_started(
+        &mut app,
+        "t-2",
+        "read_file",
+        &serde_json::json!({"path": "other.rs"}),
+    );
+    handle_tool_call_complete(&mut app, "t-1", "read_file", &ok_result("fn a() {}"));
+    handle_tool_call_complete(&mut app, "t-2", "read_file", &ok_result("x"));
+    app.flush_active_cell();
+
+    let changed = tmp.path().join("lib.rs").canonicalize().expect("canonical");
+    mark_stale_reads(&mut app, &[changed]);
+
+    let explore = app
+        .history
+        .iter()
+        .find_map(|cell| match cell {
+            HistoryCell::Tool(ToolCell::Exploring(explore)) => Some(explore),
+            _ => None,
+        })
+        .expect("exploring cell");
+    let stale: Vec<bool> = explore.entries.iter().map(|entry| entry.stale).collect();
+    assert_eq!(stale, [true, false]);
+    let text: String = explore
+        .lines_with_motion(80, true)
+        .iter()
+        .map(line_to_plain)
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(text.contains("Reading lib.rs (changed on disk)"), "{text}");
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("lib.rs changed on disk since it was read")
+    );
+}
+
 #[test]
 fn out_of_order_completes_finalize_one_history_cell_per_turn() {
     // Three parallel tools complete in reverse order; we then signal turn
@@ -4178,6 +4503,27 @@ fn out_of_order_completes_finalize_one_history_cell_per_turn() {
     );
 }
 
+#[test]
+fn cached_exploring_result_is_labelled_in_transcript() {
+    let mut app = create_test_app();
+    handle_tool_call_started(
+        &mut app,
+        "t-1",
+        "read_file",
+        &serde_json::json!({"path": "a.rs"}),
+    );
+    let cached = Ok(crate::tools::spec::ToolResult::success("contents A")
+        .with_metadata(serde_json::json!({"cache_hit": true})));
+    handle_tool_call_complete(&mut app, "t-1", "read_file", &cached);
+
+    let active = app.active_cell.as_ref().expect("active cell still present");
+    let HistoryCell::Tool(ToolCell::Exploring(explore)) = &active.entries()[0] else {
+        panic!("expected exploring cell")
+    };
+    assert_eq!(explore.entries[0].label, "Reading a.rs (cached)");
+    assert_eq!(explore.entries[0].status, ToolStatus::Success);
+}
+
 #[test]
 fn mixed_parallel_tools_render_in_single_active_cell() {
     // Tools of different shapes — exploring + exec + generic — all in flight
@@ -4362,6 +4708,38 @@ fn tool_details_survive_active_cell_flush() {
     assert_eq!(detail.tool_name, "exec_shell");
 }
 
+#[test]
+fn write_tool_audit_reaches_the_pager_and_turn_summary() {
+    use crate::tools::spec::ToolResult;
+    use crate::tools::write_audit::{FileWriteAudit, WriteAudit};
+
+    let mut app = create_test_app();
+    let input = serde_json::json!({"path": "src/lib.rs", "content": "a\nB\n"});
+    handle_tool_call_started(&mut app, "w1", "write_file", &input);
+    let audit = WriteAudit::new(vec![FileWriteAudit::new(
+        "src/lib.rs",
+        Some("a\nb\n"),
+        Some("a\nB\n"),
+    )]);
+    let result = Ok(audit.attach(ToolResult::success("Wrote 4 bytes to src/lib.rs")));
+    handle_tool_call_complete(&mut app, "w1", "write_file", &result);
+    app.flush_active_cell();
+
+    let detail = app.tool_details_by_cell.get(&0).expect("detail record");
+    let recorded = detail.write_audit.as_ref().expect("write audit recorded");
+    assert_eq!((recorded.added(), recorded.removed()), (1, 1));
+    assert_eq!(app.turn_write_stats.files.len(), 1);
+
+    assert!(open_details_pager_for_cell(&mut app, 0));
+    assert_eq!(app.view_stack.top_kind(), Some(ModalKind::Pager));
+
+    let summary = turn_write_summary(&app.turn_write_stats);
+    assert!(
+        summary.starts_with("Changed 1 file this turn (+1 -1): src/lib.rs"),
+        "{summary}"
+    );
+}
+
 // ---- exploring labels: codex-style progressive verbs ----
 //
 // Bare names like "Read foo.rs" / "Search pattern" read as past tense, which
@@ -5842,6 +6220,7 @@ fn notification_settings_tui_always_keeps_configured_method_no_threshold() {
             method: crate::config::NotificationMethod::Bel,
             threshold_secs: 120,
             include_summary: true,
+            ..Default::default()
         }),
         ..Config::default()
     };
@@ -5873,6 +6252,7 @@ fn notification_settings_no_tui_override_uses_notifications_block() {
             method: crate::config::NotificationMethod::Osc9,
             threshold_secs: 45,
             include_summary: false,
+            ..Default::default()
         }),
         ..Config::default()
     };
@@ -6150,3 +6530,170 @@ fn toast_stack_overlay_respects_composer_boundary() {
         "max_above ({max_above}) must never exceed the composer→footer gap ({gap})"
     );
 }
+
+#[test]
+fn bookmarks_are_saved_with_the_session_and_restored_on_resume() {
+    let mut app = create_test_app();
+    let session = saved_session_with_messages(vec![
+        text_message("user", "fix the parser"),
+        text_message("assistant", "parser fixed"),
+        text_message("user", "now add tests"),
+        text_message("assistant", "tests added"),
+    ]);
+    apply_loaded_session(&mut app, &Config::default(), &session);
+    let target = app
+        .history
+        .iter()
+        .rposition(|cell| matches!(cell, HistoryCell::User { .. }))
+        .expect("user cell");
+    assert_eq!(crate::tui::bookmarks::toggle(&mut app, target), Some(true));
+    assert_eq!(crate::tui::bookmarks::toggle(&mut app, 999), None);
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let manager =
+        crate::session_manager::SessionManager::new(tmp.path().join("sessions")).expect("manager");
+    let mut snapshot = build_session_snapshot(&app, &manager);
+    assert_eq!(snapshot.bookmarks, app.bookmarks);
+    // A status note above the bookmark shifted it while the session was live.
+    snapshot.bookmarks[0].cell_index += 1;
+
+    let mut resumed = create_test_app();
+    apply_loaded_session(&mut resumed, &Config::default(), &snapshot);
+    assert_eq!(resumed.bookmarks.len(), 1);
+    assert_eq!(resumed.bookmarks[0].cell_index, target);
+
+    resumed.truncate_history_to(target);
+    assert!(resumed.bookmarks.is_empty());
+}
+
+#[test]
+fn failed_shell_command_naming_a_missing_variable_prompts_once() {
+    let mut app = create_test_app();
+    let failed = || {
+        Ok(ToolResult {
+            content: "./deploy.sh: line 3: DEPLOY_TOKEN: unbound variable".to_string(),
+            success: false,
+            metadata: Some(serde_json::json!({"exit_code": 1})),
+        })
+    };
+
+    maybe_prompt_missing_env(&mut app, "read_file", &failed());
+    assert!(app.view_stack.is_empty());
+
+    maybe_prompt_missing_env(&mut app, "exec_shell", &failed());
+    assert_eq!(app.view_stack.top_kind(), Some(ModalKind::EnvPrompt));
+    app.view_stack.pop();
+
+    // Dismissed once: the retry loop does not ask again.
+    maybe_prompt_missing_env(&mut app, "exec_shell", &failed());
+    assert!(app.view_stack.is_empty());
+}
+
+#[test]
+fn second_pending_approval_turns_the_card_into_a_queue() {
+    let mut app = create_test_app();
+    let request = |id: &str| {
+        ApprovalRequest::new(
+            id,
+            "exec_shell",
+            "Run a command",
+            &serde_json::json!({"command": format!("echo {id}")}),
+            id,
+        )
+    };
+
+    enqueue_approval(&mut app, request("a"));
+    assert_eq!(app.view_stack.top_kind(), Some(ModalKind::Approval));
+
+    enqueue_approval(&mut app, request("b"));
+    enqueue_approval(&mut app, request("c"));
+    assert_eq!(app.view_stack.top_kind(), Some(ModalKind::ApprovalQueue));
+    let queue = app
+        .view_stack
+        .find_mut::<ApprovalQueueView>()
+        .expect("queue view");
+    assert_eq!(queue.pending(), 3);
+
+    forget_queued_approval(&mut app, "a");
+    forget_queued_approval(&mut app, "b");
+    assert_eq!(app.view_stack.top_kind(), Some(ModalKind::ApprovalQueue));
+    forget_queued_approval(&mut app, "c");
+    assert!(app.view_stack.is_empty());
+}
+
+#[tokio::test]
+async fn startup_jobs_fill_skill_cache_after_first_frame() {
+    let workspace = TempDir::new().expect("temp dir");
+    let skill_dir = workspace
+        .path()
+        .join(".agents")
+        .join("skills")
+        .join("deploy");
+    std::fs::create_dir_all(&skill_dir).expect("skill dir");
+    std::fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: deploy\ndescription: Ship it\n---\nSteps\n",
+    )
+    .expect("write skill");
+    let mut app = create_test_app();
+    app.workspace = workspace.path().to_path_buf();
+    app.mcp_configured_count = 0;
+    let engine = mock_engine_handle();
+
+    startup::start(&mut app, &engine.handle, None).await;
+    assert!(app.startup.header_label().is_some());
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while app.startup.header_label().is_some() && Instant::now() < deadline {
+        startup::poll(&mut app);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(app.startup.header_label(), None);
+    assert!(app.cached_skills.iter().any(|(name, _)| name == "deploy"));
+    assert!(app.session_index.is_some());
+}
+
+#[tokio::test]
+async fn closing_a_tab_replays_its_background_events_and_stops_its_engine() {
+    let first = mock_engine_handle();
+    let mut second = mock_engine_handle();
+    let mut tabs = SessionTabs::new(SessionTab::new(create_test_app(), first.handle.clone()));
+    tabs.push(SessionTab::new(create_test_app(), second.handle.clone()));
+    assert!(tabs.activate(0).await);
+
+    // Arrives while the second tab is parked behind its relay.
+    second
+        .tx_event
+        .send(EngineEvent::SessionUpdated {
+            session_id: "background-session".to_string(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: vec![ContentBlock::Text {
+                    text: "finished in the background".to_string(),
+                    cache_control: None,
+                }],
+            }],
+            system_prompt: None,
+            model: "deepseek-v4-pro".to_string(),
+            workspace: PathBuf::from("."),
+        })
+        .await
+        .unwrap();
+
+    let mut closed = tabs.close(1).await.expect("second tab closes");
+    assert_eq!(closed.pending_events.len(), 1);
+    retire_session_tab(&mut closed).await;
+
+    assert!(closed.pending_events.is_empty());
+    assert_eq!(
+        closed.app.current_session_id.as_deref(),
+        Some("background-session")
+    );
+    assert_eq!(closed.app.api_messages.len(), 1);
+    assert!(matches!(second.rx_op.try_recv(), Ok(Op::Shutdown)));
+
+    // The remaining tab is focused and the last tab cannot be closed.
+    assert_eq!(tabs.labels().len(), 1);
+    assert!(tabs.close(0).await.is_none());
+}
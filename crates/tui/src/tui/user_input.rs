@@ -1,15 +1,22 @@
 //! Modal for request_user_input tool prompts.
+//!
+//! Requests carrying `questions` walk through them one at a time; requests
+//! carrying typed `fields` render as a single inline form.
 
-use crossterm::event::{KeyCode, KeyEvent};
+use std::path::PathBuf;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::{Alignment, Rect};
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, Widget, Wrap};
 
 use crate::palette;
 use crate::tools::user_input::{
-    UserInputAnswer, UserInputQuestion, UserInputRequest, UserInputResponse,
+    UserInputAnswer, UserInputField, UserInputFieldKind, UserInputQuestion, UserInputRequest,
+    UserInputResponse,
 };
 use crate::tui::views::{ModalKind, ModalView, ViewAction, ViewEvent};
+use serde_json::{Map, Value};
 
 fn modal_block(title: &str) -> Block<'static> {
     Block::default()
@@ -85,6 +92,78 @@ enum InputMode {
     OtherInput,
 }
 
+/// Edited value of one form field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FieldValue {
+    Text(String),
+    Choice(Option<usize>),
+    Multi(Vec<bool>),
+    Flag(bool),
+}
+
+impl FieldValue {
+    fn initial(field: &UserInputField) -> Self {
+        let default = field.default.as_ref();
+        let index_of = |choice: &str| field.options.iter().position(|o| o == choice);
+        match field.kind {
+            UserInputFieldKind::Text | UserInputFieldKind::Path => Self::Text(
+                default
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
+            UserInputFieldKind::Select => {
+                Self::Choice(default.and_then(Value::as_str).and_then(index_of))
+            }
+            UserInputFieldKind::MultiSelect => {
+                let mut picked = vec![false; field.options.len()];
+                for choice in default
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                {
+                    if let Some(idx) = index_of(choice) {
+                        picked[idx] = true;
+                    }
+                }
+                Self::Multi(picked)
+            }
+            UserInputFieldKind::Boolean => {
+                Self::Flag(default.and_then(Value::as_bool).unwrap_or(false))
+            }
+        }
+    }
+
+    fn to_json(&self, field: &UserInputField) -> Value {
+        match self {
+            Self::Text(text) => Value::String(text.trim().to_string()),
+            Self::Choice(idx) => idx
+                .and_then(|idx| field.options.get(idx))
+                .map_or(Value::Null, |choice| Value::String(choice.clone())),
+            Self::Multi(picked) => Value::Array(
+                field
+                    .options
+                    .iter()
+                    .zip(picked)
+                    .filter(|(_, on)| **on)
+                    .map(|(choice, _)| Value::String(choice.clone()))
+                    .collect(),
+            ),
+            Self::Flag(on) => Value::Bool(*on),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FormState {
+    values: Vec<FieldValue>,
+    errors: Vec<Option<String>>,
+    focus: usize,
+    /// Highlighted option within the focused select/multi-select field.
+    option_cursor: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct UserInputView {
     tool_id: String,
@@ -94,11 +173,19 @@ pub struct UserInputView {
     mode: InputMode,
     other_input: String,
     answers: Vec<UserInputAnswer>,
+    form: Option<FormState>,
+    workspace: Option<PathBuf>,
 }
 
 impl UserInputView {
     pub fn new(tool_id: impl Into<String>, request: UserInputRequest) -> Self {
-        Self {
+        let form = request.is_form().then(|| FormState {
+            values: request.fields.iter().map(FieldValue::initial).collect(),
+            errors: vec![None; request.fields.len()],
+            focus: 0,
+            option_cursor: 0,
+        });
+        let mut view = Self {
             tool_id: tool_id.into(),
             request,
             question_index: 0,
@@ -106,7 +193,256 @@ impl UserInputView {
             mode: InputMode::Selecting,
             other_input: String::new(),
             answers: Vec::new(),
+            form,
+            workspace: None,
+        };
+        view.sync_option_cursor();
+        view
+    }
+
+    /// Resolve relative `path` fields against `workspace`.
+    #[must_use]
+    pub fn with_workspace(mut self, workspace: PathBuf) -> Self {
+        self.workspace = Some(workspace);
+        self
+    }
+
+    /// Status-line hint matching the prompt style.
+    #[must_use]
+    pub fn status_hint(&self) -> &'static str {
+        if self.form.is_some() {
+            "Action required: fill in the form (Tab moves, Enter on the last field submits)"
+        } else {
+            "Action required: answer the popup with 1-4, arrows, or Enter"
+        }
+    }
+
+    fn check_field(&self, form: &FormState, index: usize) -> Option<String> {
+        let field = &self.request.fields[index];
+        field
+            .check_value(
+                &form.values[index].to_json(field),
+                self.workspace.as_deref(),
+            )
+            .err()
+    }
+
+    /// Point the option cursor at the focused field's current choice.
+    fn sync_option_cursor(&mut self) {
+        let Some(form) = self.form.as_mut() else {
+            return;
+        };
+        form.option_cursor = match form.values.get(form.focus) {
+            Some(FieldValue::Choice(Some(idx))) => *idx,
+            Some(FieldValue::Multi(picked)) => picked.iter().position(|on| *on).unwrap_or(0),
+            _ => 0,
+        };
+    }
+
+    fn move_focus(&mut self, forward: bool) {
+        let Some(form) = self.form.as_ref() else {
+            return;
+        };
+        let leaving = form.focus;
+        let error = self.check_field(form, leaving);
+        let last = self.request.fields.len().saturating_sub(1);
+        let Some(form) = self.form.as_mut() else {
+            return;
+        };
+        form.errors[leaving] = error;
+        form.focus = if forward {
+            (leaving + 1).min(last)
+        } else {
+            leaving.saturating_sub(1)
+        };
+        self.sync_option_cursor();
+    }
+
+    fn submit_form(&mut self) -> ViewAction {
+        let Some(form) = self.form.as_ref() else {
+            return ViewAction::None;
+        };
+        let errors: Vec<Option<String>> = (0..self.request.fields.len())
+            .map(|idx| self.check_field(form, idx))
+            .collect();
+        let first_invalid = errors.iter().position(Option::is_some);
+        let values: Map<String, Value> = self
+            .request
+            .fields
+            .iter()
+            .zip(&form.values)
+            .map(|(field, value)| (field.id.clone(), value.to_json(field)))
+            .collect();
+        if let Some(idx) = first_invalid {
+            if let Some(form) = self.form.as_mut() {
+                form.errors = errors;
+                form.focus = idx;
+            }
+            self.sync_option_cursor();
+            return ViewAction::None;
+        }
+        ViewAction::EmitAndClose(ViewEvent::UserInputSubmitted {
+            tool_id: self.tool_id.clone(),
+            response: UserInputResponse {
+                answers: Vec::new(),
+                values,
+            },
+        })
+    }
+
+    fn handle_form_key(&mut self, key: KeyEvent) -> ViewAction {
+        let Some(form) = self.form.as_mut() else {
+            return ViewAction::None;
+        };
+        let focus = form.focus;
+        let last = self.request.fields.len().saturating_sub(1);
+        let option_count = self.request.fields[focus].options.len();
+        match key.code {
+            KeyCode::Esc => {
+                return ViewAction::EmitAndClose(ViewEvent::UserInputCancelled {
+                    tool_id: self.tool_id.clone(),
+                });
+            }
+            KeyCode::Enter if focus == last => return self.submit_form(),
+            KeyCode::Enter | KeyCode::Tab | KeyCode::Down => self.move_focus(true),
+            KeyCode::BackTab | KeyCode::Up => self.move_focus(false),
+            code => {
+                match (&mut form.values[focus], code) {
+                    (FieldValue::Text(text), KeyCode::Backspace) => {
+                        text.pop();
+                    }
+                    (FieldValue::Text(text), KeyCode::Char('h'))
+                        if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                    {
+                        text.pop();
+                    }
+                    (FieldValue::Text(text), KeyCode::Char(ch)) if !ch.is_control() => {
+                        text.push(ch);
+                    }
+                    (FieldValue::Choice(choice), KeyCode::Left) => {
+                        form.option_cursor = form.option_cursor.saturating_sub(1);
+                        *choice = Some(form.option_cursor);
+                    }
+                    (FieldValue::Choice(choice), KeyCode::Right) => {
+                        form.option_cursor =
+                            (form.option_cursor + 1).min(option_count.saturating_sub(1));
+                        *choice = Some(form.option_cursor);
+                    }
+                    (FieldValue::Choice(choice), KeyCode::Char(' ')) => {
+                        *choice = Some(form.option_cursor);
+                    }
+                    (FieldValue::Multi(_), KeyCode::Left) => {
+                        form.option_cursor = form.option_cursor.saturating_sub(1);
+                    }
+                    (FieldValue::Multi(_), KeyCode::Right) => {
+                        form.option_cursor =
+                            (form.option_cursor + 1).min(option_count.saturating_sub(1));
+                    }
+                    (FieldValue::Multi(picked), KeyCode::Char(' ')) => {
+                        if let Some(on) = picked.get_mut(form.option_cursor) {
+                            *on = !*on;
+                        }
+                    }
+                    (FieldValue::Flag(on), KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right) => {
+                        *on = !*on
+                    }
+                    (FieldValue::Flag(on), KeyCode::Char('y' | 'Y')) => *on = true,
+                    (FieldValue::Flag(on), KeyCode::Char('n' | 'N')) => *on = false,
+                    _ => return ViewAction::None,
+                }
+                form.errors[focus] = None;
+            }
+        }
+        ViewAction::None
+    }
+
+    fn render_form(&self, form: &FormState, area: Rect, buf: &mut Buffer) {
+        let title = self.request.title.as_deref().unwrap_or("Input needed");
+        let header = format!(" {title} ");
+        let key_style = Style::default().fg(palette::DEEPSEEK_SKY).bold();
+        let muted = Style::default().fg(palette::TEXT_MUTED);
+
+        let mut lines: Vec<Line> = Vec::new();
+        lines.push(Line::from(vec![Span::styled("Action required", key_style)]));
+        lines.push(Line::from(vec![
+            Span::styled(
+                title.to_string(),
+                Style::default().fg(palette::TEXT_PRIMARY).bold(),
+            ),
+            Span::styled(
+                format!(
+                    "  {} field{}",
+                    self.request.fields.len(),
+                    if self.request.fields.len() == 1 {
+                        ""
+                    } else {
+                        "s"
+                    }
+                ),
+                muted,
+            ),
+        ]));
+        lines.push(Line::from(""));
+
+        for (idx, (field, value)) in self.request.fields.iter().zip(&form.values).enumerate() {
+            let focused = idx == form.focus;
+            let label_style = if focused {
+                key_style
+            } else {
+                Style::default().fg(palette::TEXT_PRIMARY).bold()
+            };
+            let mut label = vec![Span::styled(
+                format!("{} {}", if focused { ">" } else { " " }, field.label),
+                label_style,
+            )];
+            if field.required {
+                label.push(Span::styled(
+                    " *",
+                    Style::default().fg(palette::STATUS_WARNING),
+                ));
+            }
+            lines.push(Line::from(label));
+            if let Some(description) = field.description.as_deref() {
+                lines.push(Line::from(Span::styled(
+                    format!("    {description}"),
+                    muted,
+                )));
+            }
+            lines.push(form_value_line(field, value, focused, form.option_cursor));
+            if let Some(error) = &form.errors[idx] {
+                lines.push(Line::from(Span::styled(
+                    format!("    ! {error}"),
+                    Style::default().fg(palette::STATUS_ERROR),
+                )));
+            }
+            lines.push(Line::from(""));
         }
+
+        lines.push(Line::from(vec![
+            Span::styled("Tab/Up/Down", key_style),
+            Span::styled(" field", muted),
+            Span::raw("  "),
+            Span::styled("Left/Right", key_style),
+            Span::styled(" choose", muted),
+            Span::raw("  "),
+            Span::styled("Space", key_style),
+            Span::styled(" toggle", muted),
+            Span::raw("  "),
+            Span::styled("Enter", key_style),
+            Span::styled(" next/submit", muted),
+            Span::raw("  "),
+            Span::styled("Esc", key_style),
+            Span::styled(" cancel", muted),
+        ]));
+
+        let paragraph = Paragraph::new(lines)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true })
+            .block(modal_block(&header));
+
+        let popup_area = centered_rect(82, 80, area);
+        render_modal_chrome(area, popup_area, buf);
+        paragraph.render(popup_area, buf);
     }
 
     fn current_question(&self) -> &UserInputQuestion {
@@ -126,6 +462,7 @@ impl UserInputView {
         if self.question_index + 1 >= self.request.questions.len() {
             let response = UserInputResponse {
                 answers: self.answers.clone(),
+                values: Map::new(),
             };
             return ViewAction::EmitAndClose(ViewEvent::UserInputSubmitted {
                 tool_id: self.tool_id.clone(),
@@ -248,6 +585,9 @@ impl ModalView for UserInputView {
     }
 
     fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+        if self.form.is_some() {
+            return self.handle_form_key(key);
+        }
         match self.mode {
             InputMode::Selecting => self.handle_selecting_key(key),
             InputMode::OtherInput => self.handle_other_input_key(key),
@@ -255,6 +595,10 @@ impl ModalView for UserInputView {
     }
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
+        if let Some(form) = &self.form {
+            self.render_form(form, area, buf);
+            return;
+        }
         let question = self.current_question();
         let total = self.request.questions.len();
         let header = format!(
@@ -362,6 +706,66 @@ impl ModalView for UserInputView {
     }
 }
 
+/// Indented widget row showing a form field's current value.
+fn form_value_line(
+    field: &UserInputField,
+    value: &FieldValue,
+    focused: bool,
+    option_cursor: usize,
+) -> Line<'static> {
+    let text_style = Style::default().fg(palette::DEEPSEEK_BLUE);
+    let idle_style = Style::default().fg(palette::TEXT_PRIMARY);
+    let cursor_style = Style::default()
+        .fg(palette::SELECTION_TEXT)
+        .bg(palette::SELECTION_BG)
+        .bold();
+    let mut spans = vec![Span::raw("    ")];
+    match value {
+        FieldValue::Text(text) => {
+            if text.is_empty() {
+                let hint = field.placeholder.clone().unwrap_or_else(|| {
+                    if field.kind == UserInputFieldKind::Path {
+                        "(type a path)".to_string()
+                    } else {
+                        "(type a value)".to_string()
+                    }
+                });
+                spans.push(Span::styled(hint, Style::default().fg(palette::TEXT_HINT)));
+            } else {
+                spans.push(Span::styled(text.clone(), text_style));
+            }
+            if focused {
+                spans.push(Span::styled("_", text_style));
+            }
+        }
+        FieldValue::Choice(_) | FieldValue::Multi(_) => {
+            for (idx, option) in field.options.iter().enumerate() {
+                let marker = match value {
+                    FieldValue::Choice(choice) if *choice == Some(idx) => "(•)",
+                    FieldValue::Choice(_) => "( )",
+                    FieldValue::Multi(picked) if picked.get(idx).copied().unwrap_or(false) => "[x]",
+                    _ => "[ ]",
+                };
+                let style = if focused && idx == option_cursor {
+                    cursor_style
+                } else {
+                    idle_style
+                };
+                if idx > 0 {
+                    spans.push(Span::raw("  "));
+                }
+                spans.push(Span::styled(format!("{marker} {option}"), style));
+            }
+        }
+        FieldValue::Flag(on) => {
+            let style = if focused { cursor_style } else { idle_style };
+            let label = if *on { "[x] Yes" } else { "[ ] No" };
+            spans.push(Span::styled(label, style));
+        }
+    }
+    Line::from(spans)
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -417,10 +821,84 @@ mod tests {
                         },
                     ],
                 }],
+                ..Default::default()
             },
         )
     }
 
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn form_view() -> UserInputView {
+        let request = UserInputRequest::from_value(&serde_json::json!({
+            "title": "Release",
+            "fields": [
+                {"id": "version", "label": "Version", "type": "text", "required": true},
+                {"id": "channel", "label": "Channel", "type": "select",
+                 "options": ["stable", "beta"], "default": "beta"},
+                {"id": "targets", "label": "Targets", "type": "multi_select",
+                 "options": ["linux", "macos", "windows"]},
+                {"id": "publish", "label": "Publish", "type": "boolean"}
+            ]
+        }))
+        .expect("valid form");
+        UserInputView::new("tool-form", request)
+    }
+
+    #[test]
+    fn form_blocks_submit_until_required_fields_are_filled() {
+        let mut view = form_view();
+        view.handle_key(key(KeyCode::Tab));
+        view.handle_key(key(KeyCode::Tab));
+        view.handle_key(key(KeyCode::Tab));
+        assert!(matches!(
+            view.handle_key(key(KeyCode::Enter)),
+            ViewAction::None
+        ));
+        let form = view.form.as_ref().expect("form mode");
+        assert_eq!(form.focus, 0);
+        assert_eq!(form.errors[0].as_deref(), Some("Version is required"));
+
+        let rendered = render_view(&view, 110, 40);
+        assert!(rendered.contains("Version is required"));
+        assert!(rendered.contains("(•) beta"));
+    }
+
+    #[test]
+    fn form_submits_typed_values_keyed_by_field_id() {
+        let mut view = form_view();
+        for ch in "1.4.0".chars() {
+            view.handle_key(key(KeyCode::Char(ch)));
+        }
+        view.handle_key(key(KeyCode::Tab));
+        view.handle_key(key(KeyCode::Left));
+        view.handle_key(key(KeyCode::Tab));
+        view.handle_key(key(KeyCode::Char(' ')));
+        view.handle_key(key(KeyCode::Right));
+        view.handle_key(key(KeyCode::Right));
+        view.handle_key(key(KeyCode::Char(' ')));
+        view.handle_key(key(KeyCode::Tab));
+        view.handle_key(key(KeyCode::Char('y')));
+
+        let ViewAction::EmitAndClose(ViewEvent::UserInputSubmitted { tool_id, response }) =
+            view.handle_key(key(KeyCode::Enter))
+        else {
+            panic!("expected submission");
+        };
+        assert_eq!(tool_id, "tool-form");
+        assert!(response.answers.is_empty());
+        assert_eq!(
+            Value::Object(response.values),
+            serde_json::json!({
+                "version": "1.4.0",
+                "channel": "stable",
+                "targets": ["linux", "windows"],
+                "publish": true
+            })
+        );
+    }
+
     #[test]
     fn user_input_modal_calls_out_required_action_and_controls() {
         let rendered = render_view(&sample_view(), 110, 36);
@@ -101,6 +101,32 @@ impl HelpView {
         view
     }
 
+    /// Add the workspace's project command aliases to the command section.
+    #[must_use]
+    pub fn with_project_aliases(mut self, workspace: &std::path::Path) -> Self {
+        for alias in crate::command_aliases::load(workspace) {
+            if commands::get_command_info(&alias.name).is_some() {
+                continue;
+            }
+            let label = format!("/{}", alias.name);
+            let description = format!("Project alias: {}", alias.summary());
+            let haystack = format!(
+                "{} {}",
+                label.to_ascii_lowercase(),
+                description.to_ascii_lowercase()
+            );
+            self.entries.push(HelpEntry {
+                section: HelpSection::Command,
+                sub_rank: 0,
+                label,
+                description,
+                haystack,
+            });
+        }
+        self.refilter();
+        self
+    }
+
     fn tr(&self, id: MessageId) -> &'static str {
         tr(self.locale, id)
     }
@@ -14,6 +14,7 @@ use crate::tui::history::{HistoryCell, SubAgentCell, summarize_tool_output};
 use crate::tui::widgets::agent_card::AgentLifecycle;
 
 pub mod mode_picker;
+pub mod queue_editor;
 pub mod status_picker;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,6 +23,7 @@ pub enum ModalKind {
     Elevation,
     UserInput,
     PlanPrompt,
+    PlanPreview,
     CommandPalette,
     Help,
     SubAgents,
@@ -38,13 +40,99 @@ pub enum ModalKind {
     ThemePicker,
     ContextMenu,
     ShellControl,
+    TaskManager,
+    SessionChanges,
+    QueueEditor,
+    ReviewDiff,
+    Inbox,
+    PastePrompt,
+    Bookmarks,
+    LoopPrompt,
+    ApprovalQueue,
+    EnvPrompt,
 }
 
 #[derive(Debug, Clone)]
 pub enum CommandPaletteAction {
-    ExecuteCommand { command: String },
-    InsertText { text: String },
-    OpenTextPager { title: String, content: String },
+    ExecuteCommand {
+        command: String,
+    },
+    InsertText {
+        text: String,
+    },
+    OpenTextPager {
+        title: String,
+        content: String,
+    },
+    /// Load a saved session, as picking it in `/sessions` does.
+    ResumeSession {
+        session_id: String,
+    },
+    /// Insert `@<path>` at the composer cursor.
+    InsertPath {
+        path: String,
+    },
+    /// Show a workspace file in the pager.
+    OpenFile {
+        path: String,
+    },
+}
+
+/// Request from the `/task` manager modal, executed by the host against the
+/// shared `TaskManager`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskManagerAction {
+    Refresh,
+    ViewTranscript {
+        id: String,
+    },
+    Cancel {
+        id: String,
+    },
+    SetPriority {
+        id: String,
+        priority: i32,
+    },
+    /// Pin (or unpin) the task's final output into the conversation.
+    TogglePin {
+        id: String,
+    },
+}
+
+/// Request from the `/changes` modal, run by the host against the shared
+/// session change registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionChangesAction {
+    ShowDiff { path: std::path::PathBuf },
+    Revert { path: std::path::PathBuf },
+}
+
+/// Request from the `/review show` modal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewDiffAction {
+    /// Remember (or forget) that the finding with `key` was addressed.
+    SetAddressed { key: String, addressed: bool },
+    /// Send `prompt` asking the agent to fix the chosen findings.
+    Fix { prompt: String },
+}
+
+/// Request from the `/inbox` modal, applied by the host to `App::inbox`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InboxAction {
+    /// Put the entry into the composer and close the inbox.
+    Insert {
+        id: String,
+    },
+    /// Write the entry under `.deepseek/inbox/`.
+    Save {
+        id: String,
+    },
+    Dismiss {
+        id: String,
+    },
+    MarkRead {
+        id: String,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -76,6 +164,22 @@ pub enum ContextMenuAction {
     },
     /// Show all currently hidden cells.
     ShowAllHidden,
+    /// Copy a tool cell's full output (spilled output included).
+    CopyToolOutput {
+        cell_index: usize,
+    },
+    /// Write a tool cell's full output to a file in the workspace.
+    SaveToolOutput {
+        cell_index: usize,
+    },
+    /// Run the cell's recorded tool call again with the same input.
+    RerunTool {
+        cell_index: usize,
+    },
+    /// Open the full spilled output file of a truncated tool result.
+    OpenSpilledOutput {
+        cell_index: usize,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -87,6 +191,11 @@ pub enum ViewEvent {
         title: String,
         content: String,
     },
+    /// Show a unified diff in the pager with diff rendering.
+    OpenDiffPager {
+        title: String,
+        diff: String,
+    },
     ApprovalDecision {
         tool_id: String,
         tool_name: String,
@@ -118,7 +227,27 @@ pub enum ViewEvent {
         option: usize,
     },
     PlanPromptDismissed,
+    /// The user chose how to insert a paste from the large-paste prompt.
+    PasteResolved {
+        text: String,
+        choice: crate::tui::paste_prompt::PasteChoice,
+        language: Option<&'static str>,
+    },
+    /// Run the plan shown by `/preview` in Agent mode.
+    PlanPreviewExecute,
+    /// Scroll the transcript to a bookmarked cell.
+    BookmarkJump {
+        cell_index: usize,
+    },
+    /// Remove a bookmark from the `/bookmarks` list.
+    BookmarkRemoved {
+        cell_index: usize,
+    },
     SubAgentsRefresh,
+    /// Cancel one running sub-agent from the `/subagents` view.
+    SubAgentCancel {
+        agent_id: String,
+    },
     /// Emitted by the file picker (`Ctrl+P`) when the user presses Enter on a
     /// candidate. The handler should insert `@<path>` at the composer's cursor
     /// position.
@@ -132,16 +261,18 @@ pub enum ViewEvent {
         session_id: String,
         title: String,
     },
-    /// Emitted by the `/model` picker on Enter — carries both the chosen
-    /// model id and reasoning effort tier so the UI handler can update App
-    /// state, persist via `Settings`, and forward `Op::SetModel` to the
-    /// running engine. `previous_*` fields let the handler skip work when
-    /// nothing changed and craft a clear status message.
+    /// Emitted by the `/model` picker on Enter or Ctrl+S — carries both the
+    /// chosen model id and reasoning effort tier so the UI handler can update
+    /// App state and forward `Op::SetModel` to the running engine.
+    /// `previous_*` fields let the handler skip work when nothing changed
+    /// and craft a clear status message; `set_default` asks it to persist
+    /// the choice via `Settings` too.
     ModelPickerApplied {
         model: String,
         effort: crate::tui::app::ReasoningEffort,
         previous_model: String,
         previous_effort: crate::tui::app::ReasoningEffort,
+        set_default: bool,
     },
     /// Emitted by the `/provider` picker when the user selects a provider
     /// that already has credentials — the handler should perform the same
@@ -160,6 +291,11 @@ pub enum ViewEvent {
     ModeSelected {
         mode: crate::tui::app::AppMode,
     },
+    /// Emitted by the `/queue` modal after every edit, move, mode toggle, or
+    /// delete; carries the full queue in its new order.
+    QueueUpdated {
+        messages: Vec<crate::tui::app::QueuedMessage>,
+    },
     /// Emitted by the `/statusline` picker every time the user toggles an
     /// item (live preview) and once more on Enter (final). The handler
     /// updates `app.status_items` immediately and persists on `final_save`
@@ -190,6 +326,35 @@ pub enum ViewEvent {
     },
     ShellControlBackground,
     ShellControlCancel,
+    /// Cancel the running turn from the loop-detected prompt.
+    LoopAbortTurn,
+    /// Decisions made in the approvals queue, in the order the engine
+    /// asked for them.
+    ApprovalQueueResolved {
+        decisions: Vec<crate::tui::approval_queue::QueuedDecision>,
+    },
+    /// A value typed into the missing-variable prompt, to be sealed into
+    /// the session environment.
+    SessionEnvProvided {
+        key: String,
+        value: crate::tui::env_prompt::PromptedValue,
+    },
+    /// Open the full approval card for one queued request.
+    ApprovalQueueOpen {
+        request: Box<crate::tui::approval::ApprovalRequest>,
+    },
+    TaskManagerRequested {
+        action: TaskManagerAction,
+    },
+    SessionChangesRequested {
+        action: SessionChangesAction,
+    },
+    ReviewDiffRequested {
+        action: ReviewDiffAction,
+    },
+    InboxRequested {
+        action: InboxAction,
+    },
     /// Emitted by the pager (`c` / `y`) to copy its body to the system
     /// clipboard. The host handler writes via `app.clipboard` and surfaces a
     /// status message — modal views cannot reach `app` directly. `label` is
@@ -229,6 +394,11 @@ pub trait ModalView: std::any::Any {
     fn tick(&mut self) -> ViewAction {
         ViewAction::None
     }
+    /// Pull in data loaded in the background. Returns `true` when the view
+    /// changed and needs a redraw.
+    fn poll_updates(&mut self) -> bool {
+        false
+    }
     /// Erased downcast hook for views that need a typed reference back from
     /// the boxed trait object (e.g. the live transcript overlay needs `&mut`
     /// access from outside the trait so it can refresh its snapshot of the
@@ -277,6 +447,19 @@ impl ViewStack {
         popped
     }
 
+    /// Topmost view of type `V`, wherever it sits in the stack.
+    pub fn find_mut<V: ModalView>(&mut self) -> Option<&mut V> {
+        self.views
+            .iter_mut()
+            .rev()
+            .find_map(|view| view.as_any_mut().downcast_mut::<V>())
+    }
+
+    /// Remove every view of `kind`, leaving the rest of the stack in order.
+    pub fn remove_kind(&mut self, kind: ModalKind) {
+        self.views.retain(|view| view.kind() != kind);
+    }
+
     pub fn render(&self, area: Rect, buf: &mut Buffer) {
         for view in &self.views {
             view.render(area, buf);
@@ -290,6 +473,12 @@ impl ViewStack {
             .unwrap_or(false)
     }
 
+    pub fn poll_updates(&mut self) -> bool {
+        self.views
+            .last_mut()
+            .is_some_and(|view| view.poll_updates())
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> Vec<ViewEvent> {
         let action = self
             .views
@@ -496,6 +685,8 @@ impl ModalView for ShellControlView {
 enum ConfigScope {
     Session,
     Saved,
+    /// Read from config.toml; shown but not editable here.
+    ConfigFile,
 }
 
 impl ConfigScope {
@@ -503,6 +694,7 @@ impl ConfigScope {
         match self {
             ConfigScope::Session => "SESSION",
             ConfigScope::Saved => "SAVED",
+            ConfigScope::ConfigFile => "CONFIG",
         }
     }
 
@@ -529,6 +721,7 @@ enum ConfigSection {
     Sidebar,
     History,
     Mcp,
+    Features,
 }
 
 impl ConfigSection {
@@ -541,6 +734,7 @@ impl ConfigSection {
             ConfigSection::Sidebar => "Sidebar",
             ConfigSection::History => "History",
             ConfigSection::Mcp => "MCP",
+            ConfigSection::Features => "Features",
         }
     }
 }
@@ -579,7 +773,7 @@ const CONFIG_VALUE_COLUMN_WIDTH: usize = 44;
 impl ConfigView {
     pub fn new_for_app(app: &App) -> Self {
         let settings = Settings::load().unwrap_or_else(|_| Settings::default());
-        let rows = vec![
+        let mut rows = vec![
             ConfigRow {
                 section: ConfigSection::Model,
                 key: "model".to_string(),
@@ -724,6 +918,13 @@ impl ConfigView {
                 editable: true,
                 scope: ConfigScope::Saved,
             },
+            ConfigRow {
+                section: ConfigSection::Composer,
+                key: "prompt_lint".to_string(),
+                value: settings.prompt_lint.to_string(),
+                editable: true,
+                scope: ConfigScope::Saved,
+            },
             ConfigRow {
                 section: ConfigSection::Composer,
                 key: "composer_vim_mode".to_string(),
@@ -745,6 +946,13 @@ impl ConfigView {
                 editable: true,
                 scope: ConfigScope::Saved,
             },
+            ConfigRow {
+                section: ConfigSection::Composer,
+                key: "paste_prompt_lines".to_string(),
+                value: settings.paste_prompt_lines.to_string(),
+                editable: true,
+                scope: ConfigScope::Saved,
+            },
             ConfigRow {
                 section: ConfigSection::Sidebar,
                 key: "sidebar_width".to_string(),
@@ -773,6 +981,13 @@ impl ConfigView {
                 editable: true,
                 scope: ConfigScope::Saved,
             },
+            ConfigRow {
+                section: ConfigSection::History,
+                key: "idle_maintenance_secs".to_string(),
+                value: settings.idle_maintenance_secs.to_string(),
+                editable: true,
+                scope: ConfigScope::Saved,
+            },
             ConfigRow {
                 section: ConfigSection::History,
                 key: "max_history".to_string(),
@@ -795,6 +1010,44 @@ impl ConfigView {
                 scope: ConfigScope::Saved,
             },
         ];
+        let mode_prompt_rows = ["agent", "plan", "yolo"].into_iter().flat_map(|mode| {
+            let prompt = settings.mode_prompts.get(mode);
+            [
+                ("prefix", prompt.and_then(|prompt| prompt.prefix.clone())),
+                ("suffix", prompt.and_then(|prompt| prompt.suffix.clone())),
+            ]
+            .map(|(part, text)| ConfigRow {
+                section: ConfigSection::Composer,
+                key: format!("mode_prompts.{mode}.{part}"),
+                value: text.unwrap_or_else(|| "(none)".to_string()),
+                editable: true,
+                scope: ConfigScope::Saved,
+            })
+        });
+        let composer_end = rows
+            .iter()
+            .rposition(|row| row.section == ConfigSection::Composer)
+            .map_or(rows.len(), |idx| idx + 1);
+        rows.splice(composer_end..composer_end, mode_prompt_rows);
+        rows.extend(crate::features::FEATURES.iter().map(|spec| {
+            let state = if app.features.enabled(spec.id) {
+                "on"
+            } else {
+                "off"
+            };
+            let values = app.features.values_summary(spec.id);
+            ConfigRow {
+                section: ConfigSection::Features,
+                key: spec.key.to_string(),
+                value: if values.is_empty() {
+                    state.to_string()
+                } else {
+                    format!("{state} {values}")
+                },
+                editable: false,
+                scope: ConfigScope::ConfigFile,
+            }
+        }));
 
         Self {
             rows,
@@ -1117,6 +1370,7 @@ fn config_hint_for_key(key: &str) -> &'static str {
         | "show_thinking"
         | "show_tool_details"
         | "composer_border"
+        | "prompt_lint"
         | "paste_burst_detection" => "on/off, true/false, yes/no, 1/0",
         "composer_density" | "transcript_spacing" => "compact | comfortable | spacious",
         "theme" => "system | dark | light | grayscale",
@@ -1126,6 +1380,9 @@ fn config_hint_for_key(key: &str) -> &'static str {
         "sidebar_width" => "10..=50",
         "sidebar_focus" => "auto | work | tasks | agents | context | hidden",
         "max_history" => "integer (0 allowed)",
+        "paste_prompt_lines" => "lines (0 disables)",
+        key if key.starts_with("mode_prompts.") => "text | none",
+        "idle_maintenance_secs" => "seconds (0 disables)",
         "default_model" => "deepseek-v4-pro | deepseek-v4-flash | deepseek-* | none/default",
         "reasoning_effort" => "auto | off | low | medium | high | max | default",
         "mcp_config_path" => "path to mcp.json",
@@ -1249,12 +1506,15 @@ impl ModalView for ConfigView {
                 ViewAction::None
             }
             KeyCode::Enter => {
-                if self
-                    .selected_row_index()
-                    .and_then(|idx| self.rows.get(idx))
-                    .is_some_and(|row| row.editable)
-                {
-                    self.start_edit();
+                match self.selected_row_index().and_then(|idx| self.rows.get(idx)) {
+                    Some(row) if row.editable => self.start_edit(),
+                    Some(row) if row.section == ConfigSection::Features => {
+                        self.status = Some(format!(
+                            "Read-only: set [features] {} in config.toml",
+                            row.key
+                        ));
+                    }
+                    _ => {}
                 }
                 ViewAction::None
             }
@@ -1511,7 +1771,8 @@ pub use help::HelpView;
 
 pub struct SubAgentsView {
     agents: Vec<SubAgentResult>,
-    scroll: usize,
+    /// Index into [`SubAgentsView::display_order`].
+    selected: usize,
 }
 
 /// Build the agent rows shown by `/subagents`.
@@ -1618,7 +1879,50 @@ fn live_subagent_result(
 
 impl SubAgentsView {
     pub fn new(agents: Vec<SubAgentResult>) -> Self {
-        Self { agents, scroll: 0 }
+        Self {
+            agents,
+            selected: 0,
+        }
+    }
+
+    /// Agents bucketed by status in display order (running first), each
+    /// bucket sorted by type then id.
+    fn groups(&self) -> [(&'static str, ratatui::style::Color, Vec<&SubAgentResult>); 5] {
+        let mut groups: [(&'static str, ratatui::style::Color, Vec<&SubAgentResult>); 5] = [
+            ("Running", palette::STATUS_WARNING, Vec::new()),
+            ("Completed", palette::STATUS_SUCCESS, Vec::new()),
+            ("Interrupted", palette::STATUS_WARNING, Vec::new()),
+            ("Failed", palette::DEEPSEEK_RED, Vec::new()),
+            ("Cancelled", palette::TEXT_MUTED, Vec::new()),
+        ];
+        for agent in &self.agents {
+            let bucket = match agent.status {
+                SubAgentStatus::Running => 0,
+                SubAgentStatus::Completed => 1,
+                SubAgentStatus::Interrupted(_) => 2,
+                SubAgentStatus::Failed(_) => 3,
+                SubAgentStatus::Cancelled => 4,
+            };
+            groups[bucket].2.push(agent);
+        }
+        for (_, _, agents) in &mut groups {
+            agents.sort_by(|a, b| {
+                let order = agent_type_order(&a.agent_type).cmp(&agent_type_order(&b.agent_type));
+                order.then_with(|| a.agent_id.cmp(&b.agent_id))
+            });
+        }
+        groups
+    }
+
+    fn display_order(&self) -> Vec<&SubAgentResult> {
+        self.groups()
+            .into_iter()
+            .flat_map(|(_, _, agents)| agents)
+            .collect()
+    }
+
+    fn selected_agent(&self) -> Option<&SubAgentResult> {
+        self.display_order().get(self.selected).copied()
     }
 }
 
@@ -1640,20 +1944,38 @@ impl ModalView for SubAgentsView {
                 ViewAction::Emit(ViewEvent::SubAgentsRefresh)
             }
             KeyCode::Up | KeyCode::Char('k') => {
-                self.scroll = self.scroll.saturating_sub(1);
+                self.selected = self.selected.saturating_sub(1);
                 ViewAction::None
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                self.scroll = self.scroll.saturating_add(1);
+                self.selected = (self.selected + 1).min(self.agents.len().saturating_sub(1));
                 ViewAction::None
             }
+            KeyCode::Char('x') | KeyCode::Delete => match self.selected_agent() {
+                Some(agent) if agent.status == SubAgentStatus::Running => {
+                    ViewAction::Emit(ViewEvent::SubAgentCancel {
+                        agent_id: agent.agent_id.clone(),
+                    })
+                }
+                _ => ViewAction::None,
+            },
             _ => ViewAction::None,
         }
     }
 
     fn update_subagents(&mut self, agents: &[SubAgentResult]) -> bool {
+        // Keep the cursor on the same agent when it moves between buckets
+        // (e.g. running → cancelled).
+        let selected_id = self.selected_agent().map(|agent| agent.agent_id.clone());
         self.agents = agents.to_vec();
-        self.scroll = self.scroll.min(self.agents.len().saturating_sub(1));
+        self.selected = selected_id
+            .and_then(|id| {
+                self.display_order()
+                    .iter()
+                    .position(|agent| agent.agent_id == id)
+            })
+            .unwrap_or(self.selected)
+            .min(self.agents.len().saturating_sub(1));
         true
     }
 
@@ -1677,6 +1999,7 @@ impl ModalView for SubAgentsView {
         Clear.render(popup_area, buf);
 
         let mut lines: Vec<Line> = Vec::new();
+        let mut selected_line = 0;
         let content_width = popup_width.saturating_sub(4) as usize;
 
         if self.agents.is_empty() {
@@ -1685,49 +2008,23 @@ impl ModalView for SubAgentsView {
                 Style::default().fg(palette::TEXT_MUTED),
             )));
         } else {
-            let mut running = Vec::new();
-            let mut completed = Vec::new();
-            let mut interrupted = Vec::new();
-            let mut failed = Vec::new();
-            let mut cancelled = Vec::new();
-
-            for agent in &self.agents {
-                match agent.status {
-                    SubAgentStatus::Running => running.push(agent),
-                    SubAgentStatus::Completed => completed.push(agent),
-                    SubAgentStatus::Interrupted(_) => interrupted.push(agent),
-                    SubAgentStatus::Failed(_) => failed.push(agent),
-                    SubAgentStatus::Cancelled => cancelled.push(agent),
-                }
-            }
-
-            let status_summary = [
-                ("Running", running.len(), palette::STATUS_WARNING),
-                ("Completed", completed.len(), palette::STATUS_SUCCESS),
-                ("Interrupted", interrupted.len(), palette::STATUS_WARNING),
-                ("Failed", failed.len(), palette::DEEPSEEK_RED),
-                ("Cancelled", cancelled.len(), palette::TEXT_MUTED),
-            ];
+            let groups = self.groups();
+            let selected_id = self.selected_agent().map(|agent| agent.agent_id.as_str());
 
             lines.push(Line::from(Span::styled(
                 "Sub-agents",
                 Style::default().fg(palette::DEEPSEEK_SKY).bold(),
             )));
 
-            let mut summary_parts = Vec::new();
-            for (label, count, color) in status_summary {
-                summary_parts.push(Line::from(Span::styled(
-                    format!("{}: {}", label, count),
-                    Style::default().fg(color),
-                )));
-            }
-
             let mut summary = vec![Span::styled("  ", Style::default().fg(palette::TEXT_DIM))];
-            for (idx, part) in summary_parts.into_iter().enumerate() {
+            for (idx, (label, color, agents)) in groups.iter().enumerate() {
                 if idx > 0 {
                     summary.push(Span::raw("  ·  "));
                 }
-                summary.extend(part);
+                summary.push(Span::styled(
+                    format!("{}: {}", label, agents.len()),
+                    Style::default().fg(*color),
+                ));
             }
             lines.push(Line::from(summary));
             lines.push(Line::from(Span::styled(
@@ -1735,68 +2032,26 @@ impl ModalView for SubAgentsView {
                 Style::default().fg(palette::TEXT_DIM),
             )));
 
-            running.sort_by(|a, b| {
-                let order = agent_type_order(&a.agent_type).cmp(&agent_type_order(&b.agent_type));
-                order.then_with(|| a.agent_id.cmp(&b.agent_id))
-            });
-            completed.sort_by(|a, b| {
-                let order = agent_type_order(&a.agent_type).cmp(&agent_type_order(&b.agent_type));
-                order.then_with(|| a.agent_id.cmp(&b.agent_id))
-            });
-            interrupted.sort_by(|a, b| {
-                let order = agent_type_order(&a.agent_type).cmp(&agent_type_order(&b.agent_type));
-                order.then_with(|| a.agent_id.cmp(&b.agent_id))
-            });
-            failed.sort_by(|a, b| {
-                let order = agent_type_order(&a.agent_type).cmp(&agent_type_order(&b.agent_type));
-                order.then_with(|| a.agent_id.cmp(&b.agent_id))
-            });
-            cancelled.sort_by(|a, b| {
-                let order = agent_type_order(&a.agent_type).cmp(&agent_type_order(&b.agent_type));
-                order.then_with(|| a.agent_id.cmp(&b.agent_id))
-            });
-
-            append_subagent_group(
-                &mut lines,
-                "Running",
-                palette::STATUS_WARNING.into(),
-                &running,
-                content_width,
-            );
-            append_subagent_group(
-                &mut lines,
-                "Completed",
-                palette::STATUS_SUCCESS.into(),
-                &completed,
-                content_width,
-            );
-            append_subagent_group(
-                &mut lines,
-                "Interrupted",
-                palette::STATUS_WARNING.into(),
-                &interrupted,
-                content_width,
-            );
-            append_subagent_group(
-                &mut lines,
-                "Failed",
-                palette::DEEPSEEK_RED.into(),
-                &failed,
-                content_width,
-            );
-            append_subagent_group(
-                &mut lines,
-                "Cancelled",
-                palette::TEXT_MUTED.into(),
-                &cancelled,
-                content_width,
-            );
+            for (label, color, agents) in &groups {
+                append_subagent_group(
+                    &mut lines,
+                    &mut selected_line,
+                    label,
+                    (*color).into(),
+                    agents,
+                    selected_id,
+                    content_width,
+                );
+            }
         }
 
         let total_lines = lines.len();
         let visible_lines = (popup_height as usize).saturating_sub(3);
         let max_scroll = total_lines.saturating_sub(visible_lines);
-        let scroll = self.scroll.min(max_scroll);
+        // Keep the selected row (plus a few detail lines) in view.
+        let scroll = selected_line
+            .saturating_sub(visible_lines.saturating_sub(4))
+            .min(max_scroll);
 
         let scroll_indicator = if total_lines > visible_lines {
             format!(" [{}/{} ↑↓] ", scroll + 1, max_scroll + 1)
@@ -1814,6 +2069,10 @@ impl ModalView for SubAgentsView {
                     .title_bottom(Line::from(vec![
                         Span::styled(" Esc to close ", Style::default().fg(palette::TEXT_MUTED)),
                         Span::styled(" R to refresh ", Style::default().fg(palette::TEXT_MUTED)),
+                        Span::styled(
+                            " x to cancel selected ",
+                            Style::default().fg(palette::TEXT_MUTED),
+                        ),
                         Span::styled(scroll_indicator, Style::default().fg(palette::DEEPSEEK_SKY)),
                     ]))
                     .borders(Borders::ALL)
@@ -1829,9 +2088,11 @@ impl ModalView for SubAgentsView {
 
 fn append_subagent_group(
     lines: &mut Vec<ratatui::text::Line<'static>>,
+    selected_line: &mut usize,
     title: &str,
     section_style: ratatui::style::Style,
     agents: &[&SubAgentResult],
+    selected_id: Option<&str>,
     content_width: usize,
 ) {
     use ratatui::{
@@ -1851,9 +2112,16 @@ fn append_subagent_group(
         let id = truncate_view_text(&agent.agent_id, 11);
         let kind = format_agent_type(&agent.agent_type);
         let (status, status_style, status_detail) = format_agent_status(&agent.status);
+        let selected = selected_id == Some(agent.agent_id.as_str());
+        if selected {
+            *selected_line = lines.len();
+        }
 
         lines.push(Line::from(vec![
-            Span::raw("  "),
+            Span::styled(
+                if selected { "› " } else { "  " },
+                Style::default().fg(palette::DEEPSEEK_SKY).bold(),
+            ),
             Span::styled(
                 format!("{id:<12}"),
                 Style::default().fg(palette::TEXT_PRIMARY),
@@ -1973,7 +2241,7 @@ fn truncate_view_text(text: &str, max_chars: usize) -> String {
 mod tests {
     use super::{
         ConfigListItem, ConfigSection, ConfigView, ModalKind, ModalView, ShellControlView,
-        ViewAction, ViewEvent, ViewStack, subagent_view_agents, truncate_view_text,
+        SubAgentsView, ViewAction, ViewEvent, ViewStack, subagent_view_agents, truncate_view_text,
     };
     use crate::config::Config;
     use crate::localization::Locale;
@@ -2135,6 +2403,7 @@ mod tests {
                 ConfigSection::Sidebar.label(),
                 ConfigSection::History.label(),
                 ConfigSection::Mcp.label(),
+                ConfigSection::Features.label(),
             ]
         );
     }
@@ -2165,7 +2434,13 @@ mod tests {
         assert!(keys.contains(&"cost_currency"));
         assert!(keys.contains(&"prefer_external_pdftotext"));
         assert!(keys.contains(&"mcp_config_path"));
-        assert!(view.rows.iter().all(|row| row.editable));
+        // Feature flags come from config.toml and are display-only.
+        assert!(
+            view.rows
+                .iter()
+                .all(|row| { row.editable == (row.section != ConfigSection::Features) })
+        );
+        assert!(keys.contains(&"capacity_controller"));
     }
 
     #[test]
@@ -2446,6 +2721,43 @@ mod tests {
         assert_eq!(stack.top_kind(), Some(ModalKind::ShellControl));
     }
 
+    #[test]
+    fn subagents_view_cancels_only_the_selected_running_agent() {
+        let mut view = SubAgentsView::new(vec![
+            manager_agent("agent_done", SubAgentStatus::Completed),
+            manager_agent("agent_b", SubAgentStatus::Running),
+            manager_agent("agent_a", SubAgentStatus::Running),
+        ]);
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        // Running agents list first, sorted by id.
+        match view.handle_key(key(KeyCode::Char('x'))) {
+            ViewAction::Emit(ViewEvent::SubAgentCancel { agent_id }) => {
+                assert_eq!(agent_id, "agent_a");
+            }
+            _ => panic!("expected a cancel event for the selected agent"),
+        }
+
+        view.handle_key(key(KeyCode::Down));
+        view.handle_key(key(KeyCode::Down));
+        assert!(matches!(
+            view.handle_key(key(KeyCode::Char('x'))),
+            ViewAction::None
+        ));
+
+        // After a refresh the cursor follows the agent it was on.
+        view.handle_key(key(KeyCode::Up));
+        view.update_subagents(&[
+            manager_agent("agent_done", SubAgentStatus::Completed),
+            manager_agent("agent_b", SubAgentStatus::Cancelled),
+            manager_agent("agent_a", SubAgentStatus::Running),
+        ]);
+        assert!(matches!(
+            view.handle_key(key(KeyCode::Delete)),
+            ViewAction::None
+        ));
+    }
+
     fn buffer_text(buf: &Buffer, area: Rect) -> String {
         let mut out = String::new();
         for y in area.top()..area.bottom() {
@@ -0,0 +1,498 @@
+//! `/queue` modal: edit, reorder, re-mode, and delete queued messages.
+//!
+//! The view works on a copy of the queue and emits the whole list after
+//! every change, so the host only has to swap `app.queued_messages`; the
+//! offline-queue persistence in the main loop picks the change up from
+//! there. When the engine pops a message while the modal is open the host
+//! calls [`QueueEditorView::sync`] so the copy never resurrects it.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Widget, Wrap},
+};
+
+use crate::palette;
+use crate::tui::app::{AppMode, QueuedMessage};
+use crate::tui::views::{ModalKind, ModalView, ViewAction, ViewEvent};
+
+const PREVIEW_CHARS: usize = 72;
+
+/// Inline edit of one entry. `cursor` is a char index into `buffer`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EditState {
+    index: usize,
+    buffer: String,
+    cursor: usize,
+}
+
+pub struct QueueEditorView {
+    entries: Vec<QueuedMessage>,
+    cursor: usize,
+    editing: Option<EditState>,
+    notice: Option<String>,
+}
+
+impl QueueEditorView {
+    #[must_use]
+    pub fn new(entries: Vec<QueuedMessage>) -> Self {
+        Self {
+            entries,
+            cursor: 0,
+            editing: None,
+            notice: None,
+        }
+    }
+
+    /// Adopt the host's queue after it changed outside the modal (usually
+    /// the engine dispatching the head entry after a turn).
+    pub fn sync(&mut self, entries: Vec<QueuedMessage>) {
+        if entries == self.entries {
+            return;
+        }
+        let front_popped = !self.entries.is_empty() && self.entries[1..] == entries[..];
+        if front_popped {
+            self.cursor = self.cursor.saturating_sub(1);
+            if let Some(edit) = self.editing.as_mut() {
+                if edit.index == 0 {
+                    self.editing = None;
+                    self.notice = Some("The message you were editing was sent.".to_string());
+                } else {
+                    edit.index -= 1;
+                }
+            }
+        } else if self
+            .editing
+            .as_ref()
+            .is_some_and(|edit| edit.index >= entries.len())
+        {
+            self.editing = None;
+            self.notice = Some("The message you were editing left the queue.".to_string());
+        }
+        self.entries = entries;
+        self.cursor = self.cursor.min(self.entries.len().saturating_sub(1));
+    }
+
+    fn updated(&self) -> ViewAction {
+        ViewAction::Emit(ViewEvent::QueueUpdated {
+            messages: self.entries.clone(),
+        })
+    }
+
+    fn move_entry(&mut self, down: bool) -> ViewAction {
+        let target = if down {
+            self.cursor + 1
+        } else {
+            match self.cursor.checked_sub(1) {
+                Some(target) => target,
+                None => return ViewAction::None,
+            }
+        };
+        if target >= self.entries.len() {
+            return ViewAction::None;
+        }
+        self.entries.swap(self.cursor, target);
+        self.cursor = target;
+        self.updated()
+    }
+
+    fn cycle_mode(&mut self) -> ViewAction {
+        let Some(entry) = self.entries.get_mut(self.cursor) else {
+            return ViewAction::None;
+        };
+        entry.mode = match entry.mode {
+            None => Some(AppMode::Agent),
+            Some(AppMode::Agent) => Some(AppMode::Plan),
+            Some(AppMode::Plan | AppMode::Yolo) => None,
+        };
+        self.updated()
+    }
+
+    fn delete(&mut self) -> ViewAction {
+        if self.cursor >= self.entries.len() {
+            return ViewAction::None;
+        }
+        self.entries.remove(self.cursor);
+        self.cursor = self.cursor.min(self.entries.len().saturating_sub(1));
+        self.updated()
+    }
+
+    fn start_edit(&mut self) {
+        if let Some(entry) = self.entries.get(self.cursor) {
+            self.editing = Some(EditState {
+                index: self.cursor,
+                cursor: entry.display.chars().count(),
+                buffer: entry.display.clone(),
+            });
+            self.notice = None;
+        }
+    }
+
+    fn handle_edit_key(&mut self, key: KeyEvent) -> ViewAction {
+        let Some(edit) = self.editing.as_mut() else {
+            return ViewAction::None;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.editing = None;
+                ViewAction::None
+            }
+            KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => {
+                insert_at(edit, "\n");
+                ViewAction::None
+            }
+            KeyCode::Enter => {
+                let edit = self.editing.take().expect("editing checked above");
+                if edit.buffer.trim().is_empty() {
+                    self.notice =
+                        Some("Empty message not saved; use d to delete it instead.".to_string());
+                    return ViewAction::None;
+                }
+                match self.entries.get_mut(edit.index) {
+                    Some(entry) if entry.display != edit.buffer => {
+                        entry.display = edit.buffer;
+                        self.updated()
+                    }
+                    _ => ViewAction::None,
+                }
+            }
+            KeyCode::Backspace => {
+                if edit.cursor > 0 {
+                    let start = byte_index(&edit.buffer, edit.cursor - 1);
+                    let end = byte_index(&edit.buffer, edit.cursor);
+                    edit.buffer.replace_range(start..end, "");
+                    edit.cursor -= 1;
+                }
+                ViewAction::None
+            }
+            KeyCode::Delete => {
+                if edit.cursor < edit.buffer.chars().count() {
+                    let start = byte_index(&edit.buffer, edit.cursor);
+                    let end = byte_index(&edit.buffer, edit.cursor + 1);
+                    edit.buffer.replace_range(start..end, "");
+                }
+                ViewAction::None
+            }
+            KeyCode::Left => {
+                edit.cursor = edit.cursor.saturating_sub(1);
+                ViewAction::None
+            }
+            KeyCode::Right => {
+                edit.cursor = (edit.cursor + 1).min(edit.buffer.chars().count());
+                ViewAction::None
+            }
+            KeyCode::Home => {
+                edit.cursor = 0;
+                ViewAction::None
+            }
+            KeyCode::End => {
+                edit.cursor = edit.buffer.chars().count();
+                ViewAction::None
+            }
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                let mut tmp = [0u8; 4];
+                insert_at(edit, c.encode_utf8(&mut tmp));
+                ViewAction::None
+            }
+            _ => ViewAction::None,
+        }
+    }
+
+    fn entry_line(&self, idx: usize, entry: &QueuedMessage) -> Line<'static> {
+        let is_cursor = idx == self.cursor;
+        let base = if is_cursor {
+            Style::default()
+                .fg(palette::SELECTION_TEXT)
+                .bg(palette::SELECTION_BG)
+        } else {
+            Style::default().fg(palette::TEXT_PRIMARY)
+        };
+        let pointer = if is_cursor { ">" } else { " " };
+        let mode = entry.mode.map_or("session", AppMode::as_setting);
+        let mut spans = vec![
+            Span::styled(format!("{pointer} {}. ", idx + 1), base),
+            Span::styled(
+                format!("[{mode}] "),
+                base.fg(if entry.mode.is_some() {
+                    palette::DEEPSEEK_SKY
+                } else {
+                    palette::TEXT_MUTED
+                }),
+            ),
+        ];
+        match self.editing.as_ref().filter(|edit| edit.index == idx) {
+            Some(edit) => {
+                let split = byte_index(&edit.buffer, edit.cursor);
+                spans.push(Span::styled(
+                    edit.buffer[..split].to_string(),
+                    base.add_modifier(Modifier::BOLD),
+                ));
+                spans.push(Span::styled(
+                    "▏",
+                    Style::default().fg(palette::DEEPSEEK_BLUE),
+                ));
+                spans.push(Span::styled(
+                    edit.buffer[split..].to_string(),
+                    base.add_modifier(Modifier::BOLD),
+                ));
+            }
+            None => spans.push(Span::styled(preview(&entry.display), base)),
+        }
+        Line::from(spans)
+    }
+}
+
+fn insert_at(edit: &mut EditState, text: &str) {
+    let at = byte_index(&edit.buffer, edit.cursor);
+    edit.buffer.insert_str(at, text);
+    edit.cursor += text.chars().count();
+}
+
+fn byte_index(text: &str, char_index: usize) -> usize {
+    text.char_indices()
+        .nth(char_index)
+        .map_or(text.len(), |(idx, _)| idx)
+}
+
+fn preview(text: &str) -> String {
+    let single_line = text.replace('\n', " ⏎ ");
+    if single_line.chars().count() <= PREVIEW_CHARS {
+        return single_line;
+    }
+    let mut out: String = single_line
+        .chars()
+        .take(PREVIEW_CHARS.saturating_sub(1))
+        .collect();
+    out.push('…');
+    out
+}
+
+impl ModalView for QueueEditorView {
+    fn kind(&self) -> ModalKind {
+        ModalKind::QueueEditor
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+        if self.editing.is_some() {
+            return self.handle_edit_key(key);
+        }
+        self.notice = None;
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => ViewAction::Close,
+            KeyCode::Up if shift => self.move_entry(false),
+            KeyCode::Down if shift => self.move_entry(true),
+            KeyCode::Char('K') => self.move_entry(false),
+            KeyCode::Char('J') => self.move_entry(true),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.cursor = self.cursor.saturating_sub(1);
+                ViewAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.cursor = (self.cursor + 1).min(self.entries.len().saturating_sub(1));
+                ViewAction::None
+            }
+            KeyCode::Enter | KeyCode::Char('e') => {
+                self.start_edit();
+                ViewAction::None
+            }
+            KeyCode::Char('m') => self.cycle_mode(),
+            KeyCode::Char('d') | KeyCode::Delete => self.delete(),
+            _ => ViewAction::None,
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) -> bool {
+        match self.editing.as_mut() {
+            Some(edit) => {
+                insert_at(edit, text);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let popup_width = 84.min(area.width.saturating_sub(4)).max(44);
+        let wanted_height = u16::try_from(self.entries.len())
+            .unwrap_or(u16::MAX)
+            .saturating_add(8);
+        let popup_height = wanted_height.min(area.height.saturating_sub(4)).max(8);
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(popup_width)) / 2,
+            y: area.y + (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        Clear.render(popup_area, buf);
+
+        let hints: &[(&str, &str)] = if self.editing.is_some() {
+            &[
+                (" Enter ", "save "),
+                (" Alt+Enter ", "newline "),
+                (" Esc ", "cancel "),
+            ]
+        } else {
+            &[
+                (" e ", "edit "),
+                (" Shift+↑/↓ ", "move "),
+                (" m ", "mode "),
+                (" d ", "delete "),
+                (" Esc ", "close "),
+            ]
+        };
+        let footer: Vec<Span<'static>> = hints
+            .iter()
+            .flat_map(|(key, label)| {
+                [
+                    Span::styled(*key, Style::default().fg(palette::TEXT_MUTED)),
+                    Span::raw(*label),
+                ]
+            })
+            .collect();
+
+        let block = Block::default()
+            .title(Line::from(Span::styled(
+                format!(" Queue ({}) ", self.entries.len()),
+                Style::default()
+                    .fg(palette::DEEPSEEK_SKY)
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .title_bottom(Line::from(footer))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(palette::BORDER_COLOR))
+            .style(Style::default().bg(palette::DEEPSEEK_INK))
+            .padding(Padding::uniform(1));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let mut lines = Vec::with_capacity(self.entries.len() + 3);
+        lines.push(Line::from(Span::styled(
+            "Sent in order after the current turn. [session] uses the mode active at send time.",
+            Style::default().fg(palette::TEXT_MUTED),
+        )));
+        lines.push(Line::from(""));
+        if self.entries.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "Queue is empty.",
+                Style::default().fg(palette::TEXT_MUTED),
+            )));
+        }
+        for (idx, entry) in self.entries.iter().enumerate() {
+            lines.push(self.entry_line(idx, entry));
+        }
+        if let Some(notice) = self.notice.as_deref() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                notice.to_string(),
+                Style::default().fg(palette::STATUS_WARNING),
+            )));
+        }
+
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .render(inner, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn emitted(action: ViewAction) -> Vec<QueuedMessage> {
+        match action {
+            ViewAction::Emit(ViewEvent::QueueUpdated { messages }) => messages,
+            other => panic!("expected QueueUpdated, got {other:?}"),
+        }
+    }
+
+    fn view(texts: &[&str]) -> QueueEditorView {
+        QueueEditorView::new(
+            texts
+                .iter()
+                .map(|text| QueuedMessage::new((*text).to_string(), None))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn shift_down_reorders_and_follows_the_entry() {
+        let mut view = view(&["a", "b", "c"]);
+        let messages = emitted(view.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT)));
+        let order: Vec<_> = messages.iter().map(|m| m.display.as_str()).collect();
+        assert_eq!(order, ["b", "a", "c"]);
+        assert_eq!(view.cursor, 1);
+        assert!(matches!(
+            view.handle_key(KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT)),
+            ViewAction::Emit(_)
+        ));
+        assert!(matches!(
+            view.handle_key(KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT)),
+            ViewAction::None
+        ));
+    }
+
+    #[test]
+    fn inline_edit_replaces_text_on_enter() {
+        let mut view = view(&["fix tests"]);
+        view.handle_key(key(KeyCode::Char('e')));
+        view.handle_key(key(KeyCode::Home));
+        for c in "please ".chars() {
+            view.handle_key(key(KeyCode::Char(c)));
+        }
+        let messages = emitted(view.handle_key(key(KeyCode::Enter)));
+        assert_eq!(messages[0].display, "please fix tests");
+        assert!(view.editing.is_none());
+    }
+
+    #[test]
+    fn mode_toggle_cycles_and_delete_removes() {
+        let mut view = view(&["a", "b"]);
+        assert_eq!(
+            emitted(view.handle_key(key(KeyCode::Char('m'))))[0].mode,
+            Some(AppMode::Agent)
+        );
+        assert_eq!(
+            emitted(view.handle_key(key(KeyCode::Char('m'))))[0].mode,
+            Some(AppMode::Plan)
+        );
+        assert_eq!(
+            emitted(view.handle_key(key(KeyCode::Char('m'))))[0].mode,
+            None
+        );
+        let messages = emitted(view.handle_key(key(KeyCode::Char('d'))));
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].display, "b");
+    }
+
+    #[test]
+    fn sync_after_front_pop_keeps_edit_on_the_same_message() {
+        let mut view = view(&["a", "b", "c"]);
+        view.handle_key(key(KeyCode::Down));
+        view.handle_key(key(KeyCode::Down));
+        view.handle_key(key(KeyCode::Char('e')));
+        view.sync(vec![
+            QueuedMessage::new("b".to_string(), None),
+            QueuedMessage::new("c".to_string(), None),
+        ]);
+        assert_eq!(view.editing.as_ref().map(|edit| edit.index), Some(1));
+        assert_eq!(view.cursor, 1);
+    }
+}
@@ -12,13 +12,18 @@ use ratatui::{
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::palette;
+use crate::trust_level::TrustLevel;
 use crate::tui::app::AppMode;
+use crate::tui::footer_ui::format_token_count_compact;
+use crate::tui::session_tabs::TabLabel;
 
 use super::Renderable;
 
 const CONTEXT_WARNING_THRESHOLD_PERCENT: f64 = 85.0;
 const CONTEXT_CRITICAL_THRESHOLD_PERCENT: f64 = 95.0;
-const CONTEXT_SIGNAL_WIDTH: usize = 4;
+const CONTEXT_SIGNAL_WIDTH: usize = 8;
+/// Gauge glyph marking where auto-compaction kicks in.
+const COMPACTION_MARKER: &str = "┊";
 
 /// Milliseconds between status-indicator frame advances. The original
 /// `deepseek_squiggle` (v0.3.5 → v0.8.x) used 420 ms; the dot replacement
@@ -82,6 +87,9 @@ pub struct HeaderData<'a> {
     /// Active context input tokens used for context utilization. Callers should
     /// pass a sanitized live-context estimate, not cumulative API usage.
     pub last_prompt_tokens: Option<u32>,
+    /// Token count at which auto-compaction runs, drawn as a marker on the
+    /// context gauge. `None` (auto-compaction off) hides the marker.
+    pub compaction_threshold: Option<u32>,
     /// Short label for the current reasoning-effort tier (e.g. "max", "high",
     /// "off"). Rendered as a chip when space allows.
     pub reasoning_effort_label: Option<&'a str>,
@@ -96,6 +104,23 @@ pub struct HeaderData<'a> {
     /// so the widget itself stays a pure pre-built render. `None` hides the
     /// chip entirely (e.g., `status_indicator = "off"`).
     pub status_indicator_frame: Option<&'static str>,
+    /// Pre-formatted context-cache savings chip (e.g. `cache saved $0.12`).
+    /// Shown only when the header has room to spare.
+    pub cache_savings_label: Option<String>,
+    /// Workspace trust level, rendered right after the mode label. `None`
+    /// hides the chip.
+    pub trust_level: Option<TrustLevel>,
+    /// Unread `/inbox` entries. Zero hides the badge.
+    pub inbox_unread: usize,
+    /// Open session tabs. Empty (the single-session case) hides the strip.
+    pub tabs: &'a [TabLabel],
+    /// Zero-based index of the focused tab within `tabs`.
+    pub active_tab: usize,
+    /// Skills auto-activated this conversation. Empty hides the chip.
+    pub active_skills: &'a [String],
+    /// Startup jobs still loading (e.g. `loading skills, MCP…`). `None`
+    /// hides the chip.
+    pub startup_label: Option<String>,
 }
 
 impl<'a> HeaderData<'a> {
@@ -118,12 +143,27 @@ impl<'a> HeaderData<'a> {
             context_window: None,
             session_cost: 0.0,
             last_prompt_tokens: None,
+            compaction_threshold: None,
             reasoning_effort_label: None,
             provider_label: None,
             status_indicator_frame: None,
+            cache_savings_label: None,
+            trust_level: None,
+            inbox_unread: 0,
+            tabs: &[],
+            active_tab: 0,
+            active_skills: &[],
+            startup_label: None,
         }
     }
 
+    /// Attach the auto-compaction threshold marked on the context gauge.
+    #[must_use]
+    pub fn with_compaction_threshold(mut self, tokens: Option<u32>) -> Self {
+        self.compaction_threshold = tokens;
+        self
+    }
+
     /// Attach a short reasoning-effort label for the header chip.
     #[must_use]
     pub fn with_reasoning_effort(mut self, label: Option<&'a str>) -> Self {
@@ -140,6 +180,50 @@ impl<'a> HeaderData<'a> {
         self
     }
 
+    /// Attach the cache-savings chip. Pass `None` until the session has
+    /// saved something measurable.
+    #[must_use]
+    pub fn with_cache_savings(mut self, label: Option<String>) -> Self {
+        self.cache_savings_label = label;
+        self
+    }
+
+    /// Attach the workspace trust-level chip.
+    #[must_use]
+    pub fn with_trust_level(mut self, level: Option<TrustLevel>) -> Self {
+        self.trust_level = level;
+        self
+    }
+
+    /// Attach the unread-inbox badge.
+    #[must_use]
+    pub fn with_inbox_unread(mut self, unread: usize) -> Self {
+        self.inbox_unread = unread;
+        self
+    }
+
+    /// Attach the session tab strip rendered after the mode label.
+    #[must_use]
+    pub fn with_tabs(mut self, tabs: &'a [TabLabel], active_tab: usize) -> Self {
+        self.tabs = tabs;
+        self.active_tab = active_tab;
+        self
+    }
+
+    /// Attach the auto-activated skills chip.
+    #[must_use]
+    pub fn with_active_skills(mut self, skills: &'a [String]) -> Self {
+        self.active_skills = skills;
+        self
+    }
+
+    /// Attach the startup-progress chip.
+    #[must_use]
+    pub fn with_startup(mut self, label: Option<String>) -> Self {
+        self.startup_label = label;
+        self
+    }
+
     /// Attach a short provider label for the header chip. Pass `None` when on
     /// the default DeepSeek provider so the chip is hidden.
     #[must_use]
@@ -192,6 +276,15 @@ impl<'a> HeaderWidget<'a> {
         }
     }
 
+    fn trust_color(level: TrustLevel) -> Color {
+        match level {
+            TrustLevel::Untrusted => palette::STATUS_ERROR,
+            TrustLevel::ReadOnly => palette::STATUS_WARNING,
+            TrustLevel::Standard => palette::TEXT_HINT,
+            TrustLevel::Full => palette::MODE_YOLO,
+        }
+    }
+
     fn span_width(spans: &[Span<'_>]) -> usize {
         spans.iter().map(|span| span.content.width()).sum()
     }
@@ -233,26 +326,48 @@ impl<'a> HeaderWidget<'a> {
         Some((used / max * 100.0).clamp(0.0, 100.0))
     }
 
-    fn context_color(percent: f64) -> Color {
+    /// Auto-compaction threshold as a percentage of the context window.
+    fn compaction_percent(&self) -> Option<f64> {
+        let threshold = f64::from(self.data.compaction_threshold?);
+        let max = f64::from(self.data.context_window?);
+        (max > 0.0 && threshold < max).then(|| threshold / max * 100.0)
+    }
+
+    /// Gauge color: warning from 85% or the compaction threshold, whichever
+    /// comes first, and critical from 95%.
+    fn context_color(&self, percent: f64) -> Color {
+        let warning = self
+            .compaction_percent()
+            .map_or(CONTEXT_WARNING_THRESHOLD_PERCENT, |compaction| {
+                compaction.min(CONTEXT_WARNING_THRESHOLD_PERCENT)
+            });
         if percent >= CONTEXT_CRITICAL_THRESHOLD_PERCENT {
             palette::STATUS_ERROR
-        } else if percent >= CONTEXT_WARNING_THRESHOLD_PERCENT {
+        } else if percent >= warning {
             palette::STATUS_WARNING
         } else {
             palette::DEEPSEEK_SKY
         }
     }
 
-    fn context_signal_spans(&self, show_percent: bool) -> Vec<Span<'static>> {
+    fn context_signal_spans(&self, show_percent: bool, show_remaining: bool) -> Vec<Span<'static>> {
         let Some(percent) = self.context_percent() else {
             return Vec::new();
         };
 
-        let color = Self::context_color(percent);
-        let filled = ((percent / 100.0) * CONTEXT_SIGNAL_WIDTH as f64)
-            .ceil()
-            .clamp(0.0, CONTEXT_SIGNAL_WIDTH as f64) as usize;
-        let empty = CONTEXT_SIGNAL_WIDTH.saturating_sub(filled);
+        let color = self.context_color(percent);
+        let cells = |percent: f64| {
+            ((percent / 100.0) * CONTEXT_SIGNAL_WIDTH as f64)
+                .ceil()
+                .clamp(0.0, CONTEXT_SIGNAL_WIDTH as f64) as usize
+        };
+        let filled = cells(percent);
+        // The marker sits between cells, so round its position instead of
+        // rounding up like the fill.
+        let marker = self
+            .compaction_percent()
+            .map(|compaction| ((compaction / 100.0) * CONTEXT_SIGNAL_WIDTH as f64).round() as usize)
+            .filter(|cell| (1..CONTEXT_SIGNAL_WIDTH).contains(cell));
 
         let mut spans = Vec::new();
         if show_percent {
@@ -262,14 +377,44 @@ impl<'a> HeaderWidget<'a> {
             ));
             spans.push(Span::raw(" "));
         }
-        spans.push(Span::styled("▰".repeat(filled), Style::default().fg(color)));
-        spans.push(Span::styled(
-            "▱".repeat(empty),
-            Style::default().fg(palette::BORDER_COLOR),
-        ));
+        for cell in 0..CONTEXT_SIGNAL_WIDTH {
+            if marker == Some(cell) {
+                let marker_color = if filled > cell {
+                    palette::STATUS_WARNING
+                } else {
+                    palette::TEXT_HINT
+                };
+                spans.push(Span::styled(
+                    COMPACTION_MARKER,
+                    Style::default().fg(marker_color),
+                ));
+            }
+            if cell < filled {
+                spans.push(Span::styled("▰", Style::default().fg(color)));
+            } else {
+                spans.push(Span::styled(
+                    "▱",
+                    Style::default().fg(palette::BORDER_COLOR),
+                ));
+            }
+        }
+        if show_remaining && let Some(remaining) = self.context_remaining() {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                format!("{} left", format_token_count_compact(u64::from(remaining))),
+                Style::default().fg(palette::TEXT_HINT),
+            ));
+        }
         spans
     }
 
+    /// Context window minus the tokens in use.
+    fn context_remaining(&self) -> Option<u32> {
+        let used = self.data.last_prompt_tokens?;
+        let max = self.data.context_window?;
+        (max > 0).then(|| max.saturating_sub(used))
+    }
+
     fn context_percent_spans(&self) -> Vec<Span<'static>> {
         let Some(percent) = self.context_percent() else {
             return Vec::new();
@@ -277,7 +422,7 @@ impl<'a> HeaderWidget<'a> {
 
         vec![Span::styled(
             format!("{percent:.0}%"),
-            Style::default().fg(Self::context_color(percent)),
+            Style::default().fg(self.context_color(percent)),
         )]
     }
 
@@ -336,11 +481,28 @@ impl<'a> HeaderWidget<'a> {
         vec![Span::styled(body, Style::default().fg(color))]
     }
 
+    /// Prepend the cache-savings chip to a status variant.
+    fn with_savings_chip(&self, status: Vec<Span<'static>>) -> Vec<Span<'static>> {
+        let Some(label) = self.data.cache_savings_label.as_deref() else {
+            return status;
+        };
+        let mut spans = vec![Span::styled(
+            label.to_string(),
+            Style::default().fg(palette::TEXT_HINT),
+        )];
+        if !status.is_empty() {
+            spans.push(Span::raw("  "));
+        }
+        spans.extend(status);
+        spans
+    }
+
     fn status_variant(
         &self,
         show_stream_label: bool,
         show_percent: bool,
         show_signal: bool,
+        show_remaining: bool,
     ) -> Vec<Span<'static>> {
         let mut spans = Vec::new();
 
@@ -392,7 +554,7 @@ impl<'a> HeaderWidget<'a> {
         }
 
         let context_spans = if show_signal {
-            self.context_signal_spans(show_percent)
+            self.context_signal_spans(show_percent, show_remaining)
         } else if show_percent {
             self.context_percent_spans()
         } else {
@@ -429,7 +591,7 @@ impl<'a> HeaderWidget<'a> {
     fn right_spans(&self, max_width: usize) -> Vec<Span<'static>> {
         // Width-priority cascade. Each row is a candidate; we pick the
         // first that fits. The version chip is the last thing to drop —
-        // once `status_variant(false, false, true)` no longer leaves room
+        // once `status_variant(false, false, true, false)` no longer leaves room
         // for `  v0.8.29`, we fall through to the same status variant
         // without the version chip.
         let pinned = |status: Vec<Span<'static>>| {
@@ -440,14 +602,16 @@ impl<'a> HeaderWidget<'a> {
         };
 
         let candidates = [
-            pinned(self.status_variant(true, true, true)),
-            pinned(self.status_variant(false, true, true)),
-            pinned(self.status_variant(false, true, false)),
-            pinned(self.status_variant(false, false, true)),
-            self.status_variant(true, true, true),
-            self.status_variant(false, true, true),
-            self.status_variant(false, true, false),
-            self.status_variant(false, false, true),
+            pinned(self.status_variant(true, true, true, true)),
+            pinned(self.status_variant(true, true, true, false)),
+            pinned(self.status_variant(false, true, true, false)),
+            pinned(self.status_variant(false, true, false, false)),
+            pinned(self.status_variant(false, false, true, false)),
+            self.status_variant(true, true, true, true),
+            self.status_variant(true, true, true, false),
+            self.status_variant(false, true, true, false),
+            self.status_variant(false, true, false, false),
+            self.status_variant(false, false, true, false),
             Self::version_spans(false),
         ];
 
@@ -524,6 +688,50 @@ impl<'a> HeaderWidget<'a> {
         ]
     }
 
+    /// Tab strip such as `1 fix-tests  [2 docs]  3*`. Falls back to bare
+    /// numbers when titles do not fit; the focused tab is always bracketed
+    /// and a trailing `*` flags background tabs waiting on the user.
+    fn tab_strip_spans(&self, max_width: usize) -> Vec<Span<'static>> {
+        if self.data.tabs.len() < 2 {
+            return Vec::new();
+        }
+        let build = |with_titles: bool| {
+            let mut spans = Vec::new();
+            for (index, tab) in self.data.tabs.iter().enumerate() {
+                if index > 0 {
+                    spans.push(Span::raw(" "));
+                }
+                let active = index == self.data.active_tab;
+                let mut text = (index + 1).to_string();
+                if with_titles {
+                    text.push(' ');
+                    text.push_str(&tab.title);
+                }
+                if tab.needs_attention && !active {
+                    text.push('*');
+                }
+                let (text, style) = if active {
+                    (
+                        format!("[{text}]"),
+                        Style::default()
+                            .fg(palette::DEEPSEEK_SKY)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else if tab.needs_attention {
+                    (text, Style::default().fg(palette::STATUS_WARNING))
+                } else {
+                    (text, Style::default().fg(palette::TEXT_HINT))
+                };
+                spans.push(Span::styled(text, style));
+            }
+            spans
+        };
+        [build(true), build(false)]
+            .into_iter()
+            .find(|spans| Self::span_width(spans) <= max_width)
+            .unwrap_or_default()
+    }
+
     fn left_spans(&self, max_width: usize) -> Vec<Span<'static>> {
         if max_width == 0 {
             return Vec::new();
@@ -547,8 +755,45 @@ impl<'a> HeaderWidget<'a> {
         }
 
         let mut spans = vec![Span::styled(mode_label.to_string(), mode_style)];
+        if let Some(level) = self.data.trust_level {
+            let label = level.as_str();
+            if Self::span_width(&spans) + 2 + label.width() <= max_width {
+                spans.push(Span::raw("  "));
+                spans.push(Span::styled(
+                    label,
+                    Style::default().fg(Self::trust_color(level)),
+                ));
+            }
+        }
+        if self.data.inbox_unread > 0 {
+            let label = format!("inbox {}", self.data.inbox_unread);
+            if Self::span_width(&spans) + 2 + label.width() <= max_width {
+                spans.push(Span::raw("  "));
+                spans.push(Span::styled(
+                    label,
+                    Style::default()
+                        .fg(palette::DEEPSEEK_SKY)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+        }
+        if let Some(label) = self.data.startup_label.as_deref()
+            && Self::span_width(&spans) + 2 + label.width() <= max_width
+        {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(
+                label.to_string(),
+                Style::default().fg(palette::TEXT_HINT),
+            ));
+        }
+        let tab_budget = max_width.saturating_sub(Self::span_width(&spans) + 2);
+        let tab_spans = self.tab_strip_spans(tab_budget);
+        if !tab_spans.is_empty() {
+            spans.push(Span::raw("  "));
+            spans.extend(tab_spans);
+        }
         let metadata_width = max_width
-            .saturating_sub(mode_label.width())
+            .saturating_sub(Self::span_width(&spans))
             .saturating_sub(2);
         let metadata = if metadata_width >= 4 {
             self.metadata_spans(metadata_width)
@@ -561,6 +806,21 @@ impl<'a> HeaderWidget<'a> {
             spans.extend(metadata);
         }
 
+        // Active skills are the last left-side chip: full names when they
+        // fit, a count when they don't, nothing when even that doesn't.
+        if !self.data.active_skills.is_empty() {
+            let used = Self::span_width(&spans) + 2;
+            let full = format!("skills: {}", self.data.active_skills.join(", "));
+            let count = format!("{} skills", self.data.active_skills.len());
+            let label = [full, count]
+                .into_iter()
+                .find(|label| used + label.width() <= max_width);
+            if let Some(label) = label {
+                spans.push(Span::raw("  "));
+                spans.push(Span::styled(label, Style::default().fg(palette::TEXT_HINT)));
+            }
+        }
+
         spans
     }
 }
@@ -573,7 +833,16 @@ impl Renderable for HeaderWidget<'_> {
 
         let available = area.width as usize;
         let right_budget = available.saturating_sub(6);
-        let right_spans = self.right_spans(right_budget);
+        let mut right_spans = self.right_spans(right_budget);
+        // The savings chip is the lowest-priority element: it only appears
+        // when the left cluster still fits untruncated beside it.
+        if self.data.cache_savings_label.is_some() {
+            let with_savings = self.with_savings_chip(right_spans.clone());
+            let natural_left = Self::span_width(&self.left_spans(available));
+            if natural_left + 1 + Self::span_width(&with_savings) <= available {
+                right_spans = with_savings;
+            }
+        }
         let right_width = Self::span_width(&right_spans);
         let spacer_min = usize::from(right_width > 0);
         let left_budget = available.saturating_sub(right_width + spacer_min);
@@ -602,6 +871,7 @@ mod tests {
     use super::{HeaderData, HeaderWidget, Renderable};
     use crate::palette;
     use crate::tui::app::AppMode;
+    use crate::tui::session_tabs::TabLabel;
     use ratatui::{buffer::Buffer, layout::Rect};
 
     fn render_header(data: HeaderData<'_>, width: u16) -> String {
@@ -769,6 +1039,75 @@ mod tests {
         assert!(!rendered.contains("250%"));
     }
 
+    #[test]
+    fn context_gauge_marks_compaction_threshold_and_remaining_tokens() {
+        let data = || {
+            HeaderData::new(
+                AppMode::Agent,
+                "deepseek-v4-pro",
+                "repo",
+                true,
+                palette::DEEPSEEK_INK,
+            )
+            .with_usage(0, Some(100_000), 0.0, Some(30_000))
+            .with_compaction_threshold(Some(75_000))
+        };
+        let rendered = render_header(data(), 100);
+        assert!(
+            rendered.contains("30% ▰▰▰▱▱▱┊▱▱ 70.0k left"),
+            "{rendered:?}"
+        );
+
+        let widget = HeaderWidget::new(data().with_usage(0, Some(100_000), 0.0, Some(80_000)));
+        assert_eq!(widget.context_color(80.0), palette::STATUS_WARNING);
+        assert_eq!(
+            HeaderWidget::new(data().with_compaction_threshold(None)).context_color(80.0),
+            palette::DEEPSEEK_SKY
+        );
+
+        let narrow = render_header(data(), 40);
+        assert!(!narrow.contains("left"), "{narrow:?}");
+    }
+
+    #[test]
+    fn header_shows_trust_level_after_mode() {
+        let rendered = render_header(
+            HeaderData::new(
+                AppMode::Agent,
+                "deepseek-v4-flash",
+                "repo",
+                false,
+                palette::DEEPSEEK_INK,
+            )
+            .with_trust_level(Some(crate::trust_level::TrustLevel::ReadOnly)),
+            72,
+        );
+        assert!(rendered.starts_with("Agent  read-only  repo"), "{rendered}");
+    }
+
+    #[test]
+    fn header_shows_unread_inbox_badge() {
+        let header = |unread| {
+            render_header(
+                HeaderData::new(
+                    AppMode::Agent,
+                    "deepseek-v4-flash",
+                    "repo",
+                    false,
+                    palette::DEEPSEEK_INK,
+                )
+                .with_inbox_unread(unread),
+                72,
+            )
+        };
+        assert!(
+            header(3).starts_with("Agent  inbox 3  repo"),
+            "{}",
+            header(3)
+        );
+        assert!(!header(0).contains("inbox"));
+    }
+
     #[test]
     fn header_shows_provider_chip_when_set() {
         let rendered = render_header(
@@ -902,4 +1241,129 @@ mod tests {
         assert!(!rendered.contains("🐋"));
         assert!(rendered.contains("max"));
     }
+
+    #[test]
+    fn header_renders_tab_strip_with_active_marker() {
+        let tabs = vec![
+            TabLabel {
+                title: "fix-tests".to_string(),
+                needs_attention: false,
+            },
+            TabLabel {
+                title: "docs".to_string(),
+                needs_attention: true,
+            },
+        ];
+        let rendered = render_header(
+            HeaderData::new(
+                AppMode::Agent,
+                "deepseek-v4-pro",
+                "deepseek-tui",
+                false,
+                palette::DEEPSEEK_INK,
+            )
+            .with_tabs(&tabs, 0),
+            100,
+        );
+        assert!(rendered.contains("[1 fix-tests]"), "got: {rendered}");
+        assert!(rendered.contains("2 docs*"), "got: {rendered}");
+
+        let narrow = render_header(
+            HeaderData::new(
+                AppMode::Agent,
+                "deepseek-v4-pro",
+                "deepseek-tui",
+                false,
+                palette::DEEPSEEK_INK,
+            )
+            .with_tabs(&tabs, 1),
+            24,
+        );
+        assert!(narrow.contains("[2]"), "got: {narrow}");
+    }
+
+    #[test]
+    fn header_hides_tab_strip_for_single_session() {
+        let tabs = vec![TabLabel {
+            title: "only".to_string(),
+            needs_attention: false,
+        }];
+        let rendered = render_header(
+            HeaderData::new(
+                AppMode::Agent,
+                "deepseek-v4-pro",
+                "deepseek-tui",
+                false,
+                palette::DEEPSEEK_INK,
+            )
+            .with_tabs(&tabs, 0),
+            100,
+        );
+        assert!(!rendered.contains("[1"));
+    }
+
+    #[test]
+    fn active_skills_chip_collapses_to_a_count_when_narrow() {
+        let skills = vec!["rust-style".to_string(), "rust-tests".to_string()];
+        let data = || {
+            HeaderData::new(
+                AppMode::Agent,
+                "deepseek-v4-pro",
+                "deepseek-tui",
+                false,
+                palette::DEEPSEEK_INK,
+            )
+            .with_active_skills(&skills)
+        };
+        let wide = render_header(data(), 140);
+        assert!(
+            wide.contains("skills: rust-style, rust-tests"),
+            "got: {wide}"
+        );
+        let medium = render_header(data(), 70);
+        assert!(medium.contains("2 skills"), "got: {medium}");
+        let narrow = render_header(data(), 30);
+        assert!(!narrow.contains("skills"), "got: {narrow}");
+    }
+
+    #[test]
+    fn startup_chip_shows_pending_jobs() {
+        let data = |label: Option<&str>| {
+            HeaderData::new(
+                AppMode::Agent,
+                "deepseek-v4-pro",
+                "deepseek-tui",
+                false,
+                palette::DEEPSEEK_INK,
+            )
+            .with_startup(label.map(str::to_string))
+        };
+        let loading = render_header(data(Some("loading skills, MCP…")), 120);
+        assert!(loading.contains("loading skills, MCP…"), "got: {loading}");
+        let ready = render_header(data(None), 120);
+        assert!(!ready.contains("loading"), "got: {ready}");
+    }
+
+    #[test]
+    fn cache_savings_chip_shows_only_when_metadata_fits() {
+        let data = || {
+            HeaderData::new(
+                AppMode::Agent,
+                "deepseek-v4-pro",
+                "deepseek-tui",
+                false,
+                palette::DEEPSEEK_INK,
+            )
+            .with_cache_savings(Some("cache saved $0.12".to_string()))
+        };
+        let wide = render_header(data(), 120);
+        assert!(wide.contains("cache saved $0.12"), "got: {wide}");
+        assert!(
+            wide.contains("deepseek-tui · deepseek-v4-pro"),
+            "got: {wide}"
+        );
+
+        let narrow = render_header(data(), 50);
+        assert!(!narrow.contains("cache saved"), "got: {narrow}");
+    }
 }
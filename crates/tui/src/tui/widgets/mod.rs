@@ -22,6 +22,7 @@ pub use renderable::Renderable;
 
 use std::time::Duration;
 
+use crate::command_guardrail::{GuardrailRisk, HIGH_RISK_CONFIRM_WORD};
 use crate::localization::Locale;
 use crate::palette;
 use crate::tui::app::{App, AppMode, ComposerDensity, VimMode};
@@ -129,6 +130,18 @@ impl ChatWidget {
         let history_len = app.history.len();
         let has_collapsed = !app.collapsed_cells.is_empty();
 
+        // Scrolled up and holding still: whatever streams in below the
+        // previous frame's viewport can wait to be re-wrapped until it is
+        // scrolled into view. Any pending scroll or live selection could
+        // bring it on screen this frame, so those render everything.
+        let offscreen_floor = (!app.viewport.transcript_scroll.is_at_tail()
+            && app.viewport.pending_scroll_delta == 0
+            && !app.viewport.transcript_selection.is_active())
+        .then(|| app.viewport.last_transcript_top + visible_lines);
+        app.viewport
+            .transcript_cache
+            .set_offscreen_floor(offscreen_floor);
+
         // Fast path: no collapsed cells — use original slices directly.
         if !has_collapsed {
             let mut cell_revisions: Vec<u64> =
@@ -639,6 +652,16 @@ impl Renderable for ComposerWidget<'_> {
                 .style(background);
             // Top-right corner: keep only editor state here. Session titles
             // belong in session/history surfaces, not in the input chrome.
+            if self.app.active_mode_prompt().is_some() {
+                // The active mode wraps what is sent (`mode_prompts`).
+                block = block.title_top(
+                    Line::from(Span::styled(
+                        format!(" {} preset ", self.app.mode.as_setting()),
+                        Style::default().fg(self.mode_color()),
+                    ))
+                    .right_aligned(),
+                );
+            }
             if self.app.composer.vim_enabled {
                 let color = match self.app.composer.vim_mode {
                     VimMode::Normal => palette::TEXT_MUTED,
@@ -681,6 +704,24 @@ impl Renderable for ComposerWidget<'_> {
                     Style::default().fg(palette::TEXT_PRIMARY),
                 )));
             }
+            // Ghost-text completion: a dimmed suffix after the cursor that
+            // Right/Tab accepts. Only drawn when it fits on the last row so
+            // it never changes the composer's wrapped height.
+            if let Some(ghost) =
+                crate::tui::autocomplete::ghost_suggestion(self.app, self.mention_menu_entries)
+                && let (Some(last_text), Some(last_line)) =
+                    (visible_lines.last(), input_lines.last_mut())
+            {
+                let remaining = content_width.saturating_sub(last_text.width());
+                let ghost_text =
+                    crate::tui::ui_text::truncate_line_to_width(&ghost.display_text(), remaining);
+                if !ghost_text.is_empty() {
+                    last_line.push_span(Span::styled(
+                        ghost_text,
+                        Style::default().fg(palette::TEXT_MUTED).italic(),
+                    ));
+                }
+            }
         }
 
         // For non-empty input, input_lines.len() already reflects wrapping via
@@ -1058,6 +1099,10 @@ const APPROVAL_CARD_MIN_WIDTH: u16 = 40;
 const APPROVAL_CARD_MAX_HEIGHT: u16 = 28;
 /// Maximum card width — readability craters past this on wide terminals.
 const APPROVAL_CARD_MAX_WIDTH: u16 = 96;
+/// Limits for a card that carries a diff preview: taller so the diff is
+/// readable, and wide enough for the side-by-side layout.
+const APPROVAL_CARD_DIFF_MAX_HEIGHT: u16 = 44;
+const APPROVAL_CARD_DIFF_MAX_WIDTH: u16 = 160;
 
 impl Renderable for ApprovalWidget<'_> {
     fn render(&self, area: Rect, buf: &mut Buffer) {
@@ -1090,12 +1135,18 @@ impl Renderable for ApprovalWidget<'_> {
             return;
         }
 
-        let card_area = compute_takeover_area(area);
+        let diff_preview = self.request.diff_preview.as_deref();
+        let card_area = compute_takeover_area(area, diff_preview.is_some());
         Clear.render(card_area, buf);
 
         let risk = self.request.risk;
         let locale = self.view.locale();
         let palette_colors = approval_palette(risk);
+        let controls = if diff_preview.is_some() {
+            footer_controls_diff(locale)
+        } else {
+            footer_controls(locale)
+        };
         let mut lines: Vec<Line<'static>> = Vec::with_capacity(20);
 
         // Header: stakes badge + tool identifier. The badge is the
@@ -1131,6 +1182,28 @@ impl Renderable for ApprovalWidget<'_> {
             ),
         ]));
 
+        // Guardrail verdict for shell commands: risk level plus the rule
+        // that produced it.
+        if let Some(guardrail) = &self.request.guardrail {
+            let risk_color = match guardrail.risk {
+                GuardrailRisk::High => palette::STATUS_ERROR,
+                GuardrailRisk::Medium => palette::STATUS_WARNING,
+                GuardrailRisk::Low => palette::STATUS_SUCCESS,
+            };
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(label_risk(locale), Style::default().fg(palette::TEXT_HINT)),
+                Span::styled(
+                    guardrail.risk.label(),
+                    Style::default().fg(risk_color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("  {} \u{2014} {}", guardrail.rule, guardrail.reason),
+                    Style::default().fg(palette::TEXT_BODY),
+                ),
+            ]));
+        }
+
         lines.push(Line::from(""));
         // About + impacts. Impact lines are the load-bearing content;
         // they tell the user what will happen.
@@ -1175,6 +1248,38 @@ impl Renderable for ApprovalWidget<'_> {
         let options = approval_options_for(risk, locale);
         let pending = self.view.pending_confirm();
 
+        // The diff takes whatever height is left once the options and the
+        // footer are accounted for; the rest is one `v` away.
+        if let Some(diff) = diff_preview {
+            let inner_height = card_area.height.saturating_sub(4) as usize;
+            let budget = inner_height.saturating_sub(lines.len() + options.len() + 3);
+            let diff_lines =
+                crate::tui::diff_render::render_diff(diff, card_area.width.saturating_sub(8));
+            if budget >= 2 {
+                let shown = if diff_lines.len() <= budget {
+                    diff_lines.len()
+                } else {
+                    budget - 1
+                };
+                let hidden = diff_lines.len() - shown;
+                for line in diff_lines.into_iter().take(shown) {
+                    let mut spans = vec![Span::raw("  ")];
+                    spans.extend(line.spans);
+                    lines.push(Line::from(spans));
+                }
+                if hidden > 0 {
+                    lines.push(Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled(
+                            diff_more_lines(locale, hidden),
+                            Style::default().fg(palette::TEXT_HINT),
+                        ),
+                    ]));
+                }
+                lines.push(Line::from(""));
+            }
+        }
+
         for (i, opt) in options.iter().enumerate() {
             let is_selected = i == self.view.selected();
             let staged = pending.is_some_and(|p| p == opt.option);
@@ -1218,73 +1323,104 @@ impl Renderable for ApprovalWidget<'_> {
         // destructive shows either the standing prompt or the
         // confirmation banner when an approve key has been staged.
         lines.push(Line::from(""));
-        match (risk, pending) {
-            (RiskLevel::Benign, _) => {
-                lines.push(Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled(
-                        single_key_prefix(locale),
-                        Style::default().fg(palette::TEXT_HINT),
-                    ),
-                    Span::styled(
-                        single_key_value(locale),
-                        Style::default()
-                            .fg(palette_colors.accent)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                    Span::styled(
-                        footer_controls(locale),
-                        Style::default().fg(palette::TEXT_HINT),
-                    ),
-                ]));
-            }
-            (RiskLevel::Destructive, Some(opt)) => {
-                let again_key = match opt {
-                    crate::tui::approval::ApprovalOption::ApproveOnce => confirm_key_once(locale),
-                    crate::tui::approval::ApprovalOption::ApproveAlways => {
-                        confirm_key_always(locale)
-                    }
-                    _ => "Enter",
-                };
-                lines.push(Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled(
-                        destructive_confirm_prefix(locale),
-                        Style::default()
-                            .fg(palette_colors.accent)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                    Span::styled(
-                        again_key.to_string(),
-                        Style::default()
-                            .fg(palette::DEEPSEEK_INK)
-                            .bg(palette_colors.accent)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                    Span::styled(
-                        destructive_confirm_suffix(locale),
-                        Style::default().fg(palette::TEXT_HINT),
-                    ),
-                ]));
-            }
-            (RiskLevel::Destructive, None) => {
-                lines.push(Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled(
-                        two_key_prefix(locale),
-                        Style::default().fg(palette::TEXT_HINT),
-                    ),
-                    Span::styled(
-                        two_key_value(locale),
-                        Style::default()
-                            .fg(palette_colors.accent)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                    Span::styled(
-                        footer_controls(locale),
-                        Style::default().fg(palette::TEXT_HINT),
-                    ),
-                ]));
+        if self.request.requires_typed_confirm() {
+            let typed = self.view.typed_confirm();
+            let (prefix, value) = match typed {
+                Some(typed) => (typed_confirm_prompt(locale), format!("{typed}\u{2588}")),
+                None => (
+                    typed_confirm_hint(locale),
+                    HIGH_RISK_CONFIRM_WORD.to_string(),
+                ),
+            };
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(
+                    prefix,
+                    Style::default()
+                        .fg(palette_colors.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    value,
+                    Style::default()
+                        .fg(palette::DEEPSEEK_INK)
+                        .bg(palette_colors.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    if typed.is_some() {
+                        typed_confirm_controls(locale)
+                    } else {
+                        controls
+                    },
+                    Style::default().fg(palette::TEXT_HINT),
+                ),
+            ]));
+        } else {
+            match (risk, pending) {
+                (RiskLevel::Benign, _) => {
+                    lines.push(Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled(
+                            single_key_prefix(locale),
+                            Style::default().fg(palette::TEXT_HINT),
+                        ),
+                        Span::styled(
+                            single_key_value(locale),
+                            Style::default()
+                                .fg(palette_colors.accent)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(controls, Style::default().fg(palette::TEXT_HINT)),
+                    ]));
+                }
+                (RiskLevel::Destructive, Some(opt)) => {
+                    let again_key = match opt {
+                        crate::tui::approval::ApprovalOption::ApproveOnce => {
+                            confirm_key_once(locale)
+                        }
+                        crate::tui::approval::ApprovalOption::ApproveAlways => {
+                            confirm_key_always(locale)
+                        }
+                        _ => "Enter",
+                    };
+                    lines.push(Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled(
+                            destructive_confirm_prefix(locale),
+                            Style::default()
+                                .fg(palette_colors.accent)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            again_key.to_string(),
+                            Style::default()
+                                .fg(palette::DEEPSEEK_INK)
+                                .bg(palette_colors.accent)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            destructive_confirm_suffix(locale),
+                            Style::default().fg(palette::TEXT_HINT),
+                        ),
+                    ]));
+                }
+                (RiskLevel::Destructive, None) => {
+                    lines.push(Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled(
+                            two_key_prefix(locale),
+                            Style::default().fg(palette::TEXT_HINT),
+                        ),
+                        Span::styled(
+                            two_key_value(locale),
+                            Style::default()
+                                .fg(palette_colors.accent)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(controls, Style::default().fg(palette::TEXT_HINT)),
+                    ]));
+                }
             }
         }
 
@@ -1323,15 +1459,20 @@ impl Renderable for ApprovalWidget<'_> {
 /// side so the takeover reads as a takeover but a small terminal still
 /// stays inside the buffer. Very small terminals may truncate the card
 /// content, but rendering must never address cells outside `area`.
-fn compute_takeover_area(area: Rect) -> Rect {
+fn compute_takeover_area(area: Rect, has_diff: bool) -> Rect {
+    let (max_width, max_height) = if has_diff {
+        (APPROVAL_CARD_DIFF_MAX_WIDTH, APPROVAL_CARD_DIFF_MAX_HEIGHT)
+    } else {
+        (APPROVAL_CARD_MAX_WIDTH, APPROVAL_CARD_MAX_HEIGHT)
+    };
     let avail_width = area.width.saturating_sub(APPROVAL_CARD_HORIZONTAL_PAD * 2);
     let avail_height = area.height.saturating_sub(APPROVAL_CARD_VERTICAL_PAD * 2);
-    let card_width = APPROVAL_CARD_MAX_WIDTH
+    let card_width = max_width
         .min(avail_width)
         .max(APPROVAL_CARD_MIN_WIDTH)
         .min(area.width);
     let card_height = APPROVAL_CARD_MIN_HEIGHT
-        .max(avail_height.min(APPROVAL_CARD_MAX_HEIGHT))
+        .max(avail_height.min(max_height))
         .min(area.height);
     let x = area.x + (area.width.saturating_sub(card_width)) / 2;
     let y = area.y + (area.height.saturating_sub(card_height)) / 2;
@@ -1447,6 +1588,34 @@ fn label_params(locale: Locale) -> &'static str {
     }
 }
 
+fn label_risk(locale: Locale) -> &'static str {
+    match locale {
+        Locale::ZhHans => "风险：",
+        _ => "Risk: ",
+    }
+}
+
+fn typed_confirm_hint(locale: Locale) -> &'static str {
+    match locale {
+        Locale::ZhHans => "高风险命令：按 y 后输入 ",
+        _ => "High-risk command — press y, then type ",
+    }
+}
+
+fn typed_confirm_prompt(locale: Locale) -> &'static str {
+    match locale {
+        Locale::ZhHans => "输入 run 并按 Enter 执行：",
+        _ => "Type run and press Enter to execute: ",
+    }
+}
+
+fn typed_confirm_controls(locale: Locale) -> &'static str {
+    match locale {
+        Locale::ZhHans => "  Esc 返回",
+        _ => "  Esc goes back",
+    }
+}
+
 fn staged_marker(locale: Locale) -> &'static str {
     match locale {
         Locale::ZhHans => "(待确认)",
@@ -1472,6 +1641,23 @@ fn footer_controls(locale: Locale) -> &'static str {
     }
 }
 
+fn footer_controls_diff(locale: Locale) -> &'static str {
+    match locale {
+        Locale::ZhHans => "  ·  v：完整差异  ·  Esc：终止",
+        _ => "  ·  v: full diff  ·  Esc: abort",
+    }
+}
+
+fn diff_more_lines(locale: Locale, hidden: usize) -> String {
+    match locale {
+        Locale::ZhHans => format!("… 还有 {hidden} 行差异，按 v 查看"),
+        _ => format!(
+            "… {hidden} more diff line{} (v to view)",
+            if hidden == 1 { "" } else { "s" }
+        ),
+    }
+}
+
 fn destructive_confirm_prefix(locale: Locale) -> &'static str {
     match locale {
         Locale::ZhHans => "确认破坏性操作：再次按 ",
@@ -1669,6 +1855,17 @@ impl Renderable for ElevationWidget<'_> {
                 Style::default().fg(palette::TEXT_PRIMARY),
             )));
         }
+        if self
+            .request
+            .options
+            .iter()
+            .any(|option| matches!(option, ElevationOption::RaiseLimits(_)))
+        {
+            lines.push(Line::from(Span::styled(
+                "    - higher limits let this command use more CPU, memory, or disk",
+                Style::default().fg(palette::TEXT_PRIMARY),
+            )));
+        }
         lines.push(Line::from(Span::styled(
             "    - full access removes sandbox restrictions entirely for this retry",
             Style::default().fg(palette::TEXT_PRIMARY),
@@ -1694,6 +1891,7 @@ impl Renderable for ElevationWidget<'_> {
             let key = match option {
                 ElevationOption::WithNetwork => "n",
                 ElevationOption::WithWriteAccess(_) => "w",
+                ElevationOption::RaiseLimits(_) => "l",
                 ElevationOption::FullAccess => "f",
                 ElevationOption::Abort => "a",
             };
@@ -2084,7 +2282,11 @@ pub(crate) fn slash_completion_hints(
                     };
                     (desc, hint)
                 } else {
-                    (String::from("User-defined command"), None)
+                    let alias = workspace.and_then(|ws| commands::alias_summary(command_key, ws));
+                    (
+                        alias.unwrap_or_else(|| String::from("User-defined command")),
+                        None,
+                    )
                 };
             entries.push(SlashMenuEntry {
                 name,
@@ -2829,6 +3031,67 @@ mod tests {
         assert!(!rendered.contains("hello could you"));
     }
 
+    #[test]
+    fn composer_marks_an_active_mode_prompt() {
+        let mut app = create_test_app();
+        app.composer_density = ComposerDensity::Comfortable;
+        app.mode = crate::tui::app::AppMode::Plan;
+        app.mode_prompts.insert(
+            "plan".to_string(),
+            crate::settings::ModePrompt {
+                prefix: None,
+                suffix: Some("Don't write code yet.".to_string()),
+            },
+        );
+        let slash_menu_entries = Vec::<SlashMenuEntry>::new();
+        let mention_menu_entries = Vec::<String>::new();
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: 60,
+            height: 5,
+        };
+        let mut buf = Buffer::empty(area);
+        ComposerWidget::new(&app, 5, &slash_menu_entries, &mention_menu_entries)
+            .render(area, &mut buf);
+        assert!(buffer_text(&buf, area).contains("plan preset"));
+
+        app.mode = crate::tui::app::AppMode::Agent;
+        let mut buf = Buffer::empty(area);
+        ComposerWidget::new(&app, 5, &slash_menu_entries, &mention_menu_entries)
+            .render(area, &mut buf);
+        assert!(!buffer_text(&buf, area).contains("preset"));
+    }
+
+    #[test]
+    fn composer_renders_history_ghost_suffix_after_cursor() {
+        let mut app = create_test_app();
+        app.composer_density = ComposerDensity::Comfortable;
+        app.input_history = vec!["fix the failing tests".to_string()];
+        app.input = "fix the".to_string();
+        app.cursor_position = app.input.chars().count();
+        let slash_menu_entries = Vec::<SlashMenuEntry>::new();
+        let mention_menu_entries = Vec::<String>::new();
+        let widget = ComposerWidget::new(&app, 5, &slash_menu_entries, &mention_menu_entries);
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: 60,
+            height: 5,
+        };
+        let mut buf = Buffer::empty(area);
+
+        widget.render(area, &mut buf);
+        assert!(buffer_text(&buf, area).contains("fix the failing tests"));
+
+        // Moving the cursor off the end hides the ghost.
+        app.cursor_position = 3;
+        let widget = ComposerWidget::new(&app, 5, &slash_menu_entries, &mention_menu_entries);
+        let mut buf = Buffer::empty(area);
+        widget.render(area, &mut buf);
+        assert!(!buffer_text(&buf, area).contains("failing"));
+    }
+
     #[test]
     fn slash_menu_open_locks_composer_height_against_match_count_changes() {
         // Repro for the Windows 10 PowerShell + WSL feedback: typing
@@ -3338,7 +3601,7 @@ mod tests {
         let widget = ApprovalWidget::new(&request, &view);
 
         for area in [Rect::new(0, 0, 162, 17), Rect::new(0, 0, 39, 17)] {
-            let card_area = compute_takeover_area(area);
+            let card_area = compute_takeover_area(area, false);
             assert!(card_area.x >= area.x);
             assert!(card_area.y >= area.y);
             assert!(card_area.right() <= area.right());
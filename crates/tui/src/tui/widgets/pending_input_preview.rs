@@ -43,6 +43,8 @@ pub struct PendingInputPreview {
     pub pending_steers: Vec<String>,
     pub rejected_steers: Vec<String>,
     pub queued_messages: Vec<String>,
+    /// Pre-send lint suggestions for the vague draft Enter just held back.
+    pub lint_hints: Vec<&'static str>,
     pub edit_binding: EditBinding,
 }
 
@@ -66,6 +68,7 @@ impl PendingInputPreview {
             pending_steers: Vec::new(),
             rejected_steers: Vec::new(),
             queued_messages: Vec::new(),
+            lint_hints: Vec::new(),
             edit_binding: EditBinding::UP,
         }
     }
@@ -80,7 +83,11 @@ impl PendingInputPreview {
     /// at `width`. Pulled out so `desired_height` can ask the same renderer
     /// without duplicating wrapping logic.
     fn lines(&self, width: u16) -> Vec<Line<'static>> {
-        if (self.context_items.is_empty() && !self.has_pending_inputs()) || width < 4 {
+        if (self.context_items.is_empty()
+            && !self.has_pending_inputs()
+            && self.lint_hints.is_empty())
+            || width < 4
+        {
             return Vec::new();
         }
 
@@ -126,6 +133,26 @@ impl PendingInputPreview {
             }
         }
 
+        if !self.lint_hints.is_empty() {
+            if !lines.is_empty() {
+                lines.push(Line::from(""));
+            }
+            push_section_header(
+                &mut lines,
+                Line::from(vec![
+                    Span::styled("• ", Style::default().fg(palette::STATUS_WARNING)),
+                    Span::raw("This prompt is vague"),
+                ]),
+            );
+            for hint in &self.lint_hints {
+                push_truncated_item(&mut lines, hint, width, dim, "  ↳ ", "    ");
+            }
+            lines.push(Line::from(vec![Span::styled(
+                "    Enter sends as-is · Esc dismisses · /enhance rewrites",
+                dim,
+            )]));
+        }
+
         lines
     }
 }
@@ -454,6 +481,17 @@ mod tests {
         assert!(!rows.iter().any(|r| r.contains("…")));
     }
 
+    #[test]
+    fn lint_hints_render_with_actions() {
+        let mut preview = PendingInputPreview::new();
+        preview.lint_hints = vec!["Name the file", "Say what done looks like"];
+        let rows = render_to_string(&preview, 60);
+        assert_eq!(rows.len(), 4, "got rows: {rows:?}");
+        assert!(rows[0].contains("This prompt is vague"));
+        assert!(rows[1].contains("Name the file"));
+        assert!(rows[3].contains("/enhance rewrites"));
+    }
+
     #[test]
     fn narrow_width_renders_nothing() {
         let mut preview = PendingInputPreview::new();
@@ -346,6 +346,23 @@ pub fn truncate_with_ellipsis(s: &str, max_len: usize, ellipsis: &str) -> String
     format!("{}{}", &s[..safe_end], ellipsis)
 }
 
+/// Like [`truncate_with_ellipsis`], but keeps the end of the string, for
+/// paths whose file name matters more than their leading directories.
+#[must_use]
+pub fn truncate_start_with_ellipsis(s: &str, max_len: usize, ellipsis: &str) -> String {
+    if s.len() <= max_len {
+        return s.to_string();
+    }
+    let budget = max_len.saturating_sub(ellipsis.len());
+    // Find the first char boundary whose suffix fits within the byte budget.
+    let safe_start = s
+        .char_indices()
+        .map(|(i, _)| i)
+        .find(|&i| s.len() - i <= budget)
+        .unwrap_or(s.len());
+    format!("{}{}", ellipsis, &s[safe_start..])
+}
+
 /// Percent-encode a string for use in URL query parameters.
 ///
 /// Encodes all characters except unreserved characters (A-Z, a-z, 0-9, `-`, `_`, `.`, `~`).
@@ -491,6 +508,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn truncate_start_keeps_the_tail_on_char_boundaries() {
+        use super::truncate_start_with_ellipsis;
+        assert_eq!(
+            truncate_start_with_ellipsis("src/lib.rs", 20, "…"),
+            "src/lib.rs"
+        );
+        assert_eq!(
+            truncate_start_with_ellipsis("crates/tui/src/lib.rs", 13, "…"),
+            "…src/lib.rs"
+        );
+        assert_eq!(truncate_start_with_ellipsis("日本語.rs", 9, "…"), "…語.rs");
+        assert_eq!(truncate_start_with_ellipsis("abc", 2, "…"), "…");
+    }
+
     #[test]
     fn display_path_with_no_home_returns_full_path() {
         assert_eq!(
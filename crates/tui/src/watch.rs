@@ -0,0 +1,377 @@
+//! `deepseek watch`: run a non-interactive agent turn whenever files
+//! matching a glob change.
+//!
+//! A recursive filesystem watcher feeds change events through a glob filter.
+//! The first matching change opens a debounce window; every further matching
+//! change inside the window extends it. When the workspace has been quiet
+//! for the full window, the collected paths are appended to the user's
+//! prompt and one agent turn runs.
+//!
+//! Only one turn runs at a time. Changes that arrive while a turn is running
+//! are reported as `watch_ignored` and dropped: they are almost always the
+//! agent's own edits, and re-triggering on them would loop forever. The
+//! watcher delivers events asynchronously, so after a turn the channel is
+//! drained until it has been quiet for a full debounce window; events for
+//! the turn's last writes that trail in are dropped too.
+//!
+//! All output is JSONL on stdout. The watch lifecycle events below are
+//! interleaved with the `exec --output-format stream-json` events of each
+//! turn.
+
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+/// Default quiet period before a batch of changes triggers a turn.
+pub const DEFAULT_DEBOUNCE_MS: u64 = 750;
+
+/// Matches workspace-relative paths against the `--on-change` globs.
+#[derive(Debug, Clone)]
+pub struct WatchFilter {
+    root: PathBuf,
+    globs: GlobSet,
+}
+
+impl WatchFilter {
+    pub fn new(root: &Path, patterns: &[String]) -> Result<Self> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob = Glob::new(pattern)
+                .with_context(|| format!("invalid --on-change glob '{pattern}'"))?;
+            builder.add(glob);
+        }
+        Ok(Self {
+            root: root.to_path_buf(),
+            globs: builder
+                .build()
+                .context("could not compile --on-change globs")?,
+        })
+    }
+
+    /// The workspace-relative form of `path` when it matches, else `None`.
+    /// Anything under `.git/` never matches.
+    #[must_use]
+    pub fn matching_path(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        if relative.starts_with(".git") || !self.globs.is_match(relative) {
+            return None;
+        }
+        Some(relative.to_string_lossy().replace('\\', "/"))
+    }
+}
+
+/// Deduplicated, sorted set of changed paths collected during one debounce
+/// window.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChangeBatch {
+    paths: BTreeSet<String>,
+}
+
+impl ChangeBatch {
+    pub fn extend(&mut self, paths: impl IntoIterator<Item = String>) {
+        self.paths.extend(paths);
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    #[must_use]
+    pub fn take(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.paths).into_iter().collect()
+    }
+}
+
+/// Watch lifecycle events written to stdout as JSONL.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+pub enum WatchEvent<'a> {
+    #[serde(rename = "watch_started")]
+    Started {
+        workspace: &'a Path,
+        patterns: &'a [String],
+        debounce_ms: u64,
+    },
+    #[serde(rename = "watch_triggered")]
+    Triggered { run: u64, paths: &'a [String] },
+    #[serde(rename = "watch_run_finished")]
+    RunFinished {
+        run: u64,
+        success: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// Matching changes that landed while a turn was running.
+    #[serde(rename = "watch_ignored")]
+    Ignored { run: u64, paths: &'a [String] },
+    #[serde(rename = "watch_error")]
+    Error { error: String },
+}
+
+fn emit(event: &WatchEvent<'_>) -> Result<()> {
+    println!("{}", serde_json::to_string(event)?);
+    Ok(())
+}
+
+/// The prompt sent for one triggered turn.
+#[must_use]
+pub fn compose_prompt(prompt: &str, paths: &[String]) -> String {
+    let mut out = prompt.trim_end().to_string();
+    out.push_str("\n\nFiles changed since the last run:");
+    for path in paths {
+        out.push_str("\n- ");
+        out.push_str(path);
+    }
+    out
+}
+
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    pub workspace: PathBuf,
+    pub patterns: Vec<String>,
+    pub prompt: String,
+    pub debounce: Duration,
+    /// Stop after this many turns. `None` watches until interrupted.
+    pub max_runs: Option<u64>,
+}
+
+/// Watch `options.workspace` and call `run_turn` with the composed prompt
+/// for each debounced batch of matching changes.
+pub async fn run<F, Fut>(options: WatchOptions, mut run_turn: F) -> Result<()>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let workspace = options
+        .workspace
+        .canonicalize()
+        .with_context(|| format!("cannot watch {}", options.workspace.display()))?;
+    let filter = WatchFilter::new(&workspace, &options.patterns)?;
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(move |event| {
+        let _ = tx.send(event);
+    })
+    .context("could not start filesystem watcher")?;
+    watcher
+        .watch(&workspace, RecursiveMode::Recursive)
+        .with_context(|| format!("could not watch {}", workspace.display()))?;
+
+    emit(&WatchEvent::Started {
+        workspace: &workspace,
+        patterns: &options.patterns,
+        debounce_ms: u64::try_from(options.debounce.as_millis()).unwrap_or(u64::MAX),
+    })?;
+
+    let mut run = 0u64;
+    let mut batch = ChangeBatch::default();
+    loop {
+        // Wait for the first matching change, then keep collecting until
+        // the workspace has been quiet for a full debounce window.
+        while batch.is_empty() {
+            let Some(event) = rx.recv().await else {
+                return Ok(());
+            };
+            batch.extend(matching_paths(&filter, event)?);
+        }
+        collect_until_quiet(&mut rx, &filter, options.debounce, &mut batch).await?;
+
+        run += 1;
+        let paths = batch.take();
+        emit(&WatchEvent::Triggered { run, paths: &paths })?;
+        let outcome = run_turn(compose_prompt(&options.prompt, &paths)).await;
+        emit(&WatchEvent::RunFinished {
+            run,
+            success: outcome.is_ok(),
+            error: outcome.err().map(|err| format!("{err:#}")),
+        })?;
+
+        // Concurrency guard: whatever changed during the turn, or trails in
+        // right after it, is dropped.
+        let mut ignored = ChangeBatch::default();
+        collect_until_quiet(&mut rx, &filter, options.debounce, &mut ignored).await?;
+        if !ignored.is_empty() {
+            emit(&WatchEvent::Ignored {
+                run,
+                paths: &ignored.take(),
+            })?;
+        }
+
+        if options.max_runs.is_some_and(|max| run >= max) {
+            return Ok(());
+        }
+    }
+}
+
+/// Add matching changes to `batch` until no event has arrived for a full
+/// `quiet` window (or the watcher shut down).
+async fn collect_until_quiet(
+    rx: &mut mpsc::UnboundedReceiver<notify::Result<notify::Event>>,
+    filter: &WatchFilter,
+    quiet: Duration,
+    batch: &mut ChangeBatch,
+) -> Result<()> {
+    while let Ok(Some(event)) = tokio::time::timeout(quiet, rx.recv()).await {
+        batch.extend(matching_paths(filter, event)?);
+    }
+    Ok(())
+}
+
+fn matching_paths(
+    filter: &WatchFilter,
+    event: notify::Result<notify::Event>,
+) -> Result<Vec<String>> {
+    let event = match event {
+        Ok(event) => event,
+        Err(err) => {
+            emit(&WatchEvent::Error {
+                error: err.to_string(),
+            })?;
+            return Ok(Vec::new());
+        }
+    };
+    if matches!(event.kind, EventKind::Access(_)) {
+        return Ok(Vec::new());
+    }
+    Ok(event
+        .paths
+        .iter()
+        .filter_map(|path| filter.matching_path(path))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_matches_relative_globs_and_skips_git_dir() {
+        let root = Path::new("/repo");
+        let filter = WatchFilter::new(root, &["src/**/*.rs".to_string()]).unwrap();
+        assert_eq!(
+            filter.matching_path(Path::new("/repo/src/core/engine.rs")),
+            Some("src/core/engine.rs".to_string())
+        );
+        assert_eq!(filter.matching_path(Path::new("/repo/README.md")), None);
+        assert_eq!(filter.matching_path(Path::new("/repo/.git/index")), None);
+
+        assert!(WatchFilter::new(root, &["src/[".to_string()]).is_err());
+    }
+
+    #[test]
+    fn change_batch_dedupes_and_sorts() {
+        let mut batch = ChangeBatch::default();
+        batch.extend(["b.rs".to_string(), "a.rs".to_string()]);
+        batch.extend(["b.rs".to_string()]);
+        assert_eq!(batch.take(), ["a.rs", "b.rs"]);
+        assert!(batch.is_empty());
+    }
+
+    #[test]
+    fn compose_prompt_lists_changed_files() {
+        let prompt = compose_prompt("run tests and fix failures\n", &["src/lib.rs".to_string()]);
+        assert_eq!(
+            prompt,
+            "run tests and fix failures\n\nFiles changed since the last run:\n- src/lib.rs"
+        );
+    }
+
+    #[test]
+    fn watch_events_serialize_as_tagged_jsonl() {
+        let paths = vec!["src/lib.rs".to_string()];
+        let line = serde_json::to_string(&WatchEvent::Triggered {
+            run: 2,
+            paths: &paths,
+        })
+        .unwrap();
+        assert_eq!(
+            line,
+            r#"{"type":"watch_triggered","run":2,"paths":["src/lib.rs"]}"#
+        );
+        let line = serde_json::to_string(&WatchEvent::RunFinished {
+            run: 2,
+            success: true,
+            error: None,
+        })
+        .unwrap();
+        assert_eq!(
+            line,
+            r#"{"type":"watch_run_finished","run":2,"success":true}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn late_events_after_a_turn_are_drained_until_quiet() {
+        let root = Path::new("/repo");
+        let filter = WatchFilter::new(root, &["src/*.rs".to_string()]).unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let modify = |name: &str| {
+            Ok(
+                notify::Event::new(EventKind::Modify(notify::event::ModifyKind::Any))
+                    .add_path(root.join("src").join(name)),
+            )
+        };
+        tx.send(modify("a.rs")).unwrap();
+        let late_tx = tx.clone();
+        tokio::spawn(async move {
+            // Trails the turn by less than the quiet window.
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            late_tx.send(modify("b.rs")).unwrap();
+        });
+
+        let mut ignored = ChangeBatch::default();
+        collect_until_quiet(&mut rx, &filter, Duration::from_millis(300), &mut ignored)
+            .await
+            .unwrap();
+        assert_eq!(ignored.take(), ["src/a.rs", "src/b.rs"]);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn debounced_change_triggers_a_single_turn() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join("src")).unwrap();
+        let options = WatchOptions {
+            workspace: tmp.path().to_path_buf(),
+            patterns: vec!["src/*.rs".to_string()],
+            prompt: "check".to_string(),
+            debounce: Duration::from_millis(200),
+            max_runs: Some(1),
+        };
+        let src = tmp.path().join("src");
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            std::fs::write(src.join("a.rs"), "fn a() {}").unwrap();
+            std::fs::write(src.join("b.rs"), "fn b() {}").unwrap();
+            std::fs::write(src.join("notes.txt"), "ignored").unwrap();
+        });
+
+        let mut prompts = Vec::new();
+        tokio::time::timeout(
+            Duration::from_secs(10),
+            run(options, |prompt| {
+                prompts.push(prompt);
+                async { Ok(()) }
+            }),
+        )
+        .await
+        .expect("watch should trigger before the timeout")
+        .unwrap();
+
+        assert_eq!(prompts.len(), 1);
+        assert!(
+            prompts[0].contains("- src/a.rs\n- src/b.rs"),
+            "{}",
+            prompts[0]
+        );
+        assert!(!prompts[0].contains("notes.txt"));
+    }
+}
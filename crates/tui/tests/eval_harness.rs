@@ -140,3 +140,77 @@ fn record_flag_writes_one_jsonl_line_per_step() {
         assert!(!events.is_empty(), "every fixture must have ≥1 event");
     }
 }
+
+#[test]
+fn yaml_scenario_replays_recorded_responses() {
+    let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/eval");
+    let scenarios = eval::load_scenarios(&fixtures).expect("load fixture scenarios");
+    assert_eq!(scenarios.len(), 1);
+
+    let report = eval::run_scenarios(&scenarios, 240);
+    let scenario = &report.scenarios[0];
+    assert!(scenario.passed, "fixture scenario failed: {scenario:#?}");
+    assert_eq!(scenario.steps.len(), 2);
+    assert_eq!((report.passed, report.failed), (1, 0));
+}
+
+#[test]
+fn scenario_failures_are_reported_and_compared() {
+    let scenario = eval::Scenario::from_yaml(
+        r#"
+name: broken
+files:
+  a.txt: "one\n"
+steps:
+  - tool: read_file
+    input: { path: a.txt }
+    expect: { contains: [two] }
+  - tool: read_file
+    input: { path: missing.txt }
+    expect: { success: false }
+expected_tool_calls: [read_file]
+assertions:
+  - file: b.txt
+"#,
+    )
+    .expect("parse scenario");
+    let report = scenario.run(240).expect("run scenario");
+    assert!(!report.passed);
+    assert_eq!(
+        report.failures,
+        vec![
+            "step 1 (read_file): output does not contain \"two\"".to_string(),
+            "expected tool calls [read_file], got [read_file, read_file]".to_string(),
+            "b.txt: missing".to_string(),
+        ]
+    );
+
+    let current = eval::SuiteReport {
+        passed: 0,
+        failed: 1,
+        scenarios: vec![report.clone()],
+    };
+    let baseline = eval::SuiteReport {
+        passed: 1,
+        failed: 0,
+        scenarios: vec![eval::ScenarioReport {
+            name: "broken".to_string(),
+            passed: true,
+            ..report.clone()
+        }],
+    };
+    let comparison = current.compare(&baseline);
+    assert_eq!(comparison.regressions, vec!["broken".to_string()]);
+    assert!(comparison.fixed.is_empty() && comparison.added.is_empty());
+    assert!(baseline.compare(&current).fixed == vec!["broken".to_string()]);
+}
+
+#[test]
+fn scenario_yaml_is_validated() {
+    let both = "name: x\nsteps: [{tool: list_dir}]\nresponses: [{text: hi}]\n";
+    assert!(eval::Scenario::from_yaml(both).is_err());
+    let escape = "name: x\nfiles:\n  ../outside.txt: nope\n";
+    assert!(eval::Scenario::from_yaml(escape).is_err());
+    let typo = "name: x\nstep: []\n";
+    assert!(eval::Scenario::from_yaml(typo).is_err());
+}